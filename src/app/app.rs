@@ -1,6 +1,8 @@
 use crate::db::Database;
+use crate::db::schema::NewerSchemaError;
 use crate::ui::MainWindow;
 use libadwaita as adw;
+use relm4::gtk::{self, gio, glib};
 use std::sync::Arc;
 use tokio::runtime::Runtime;
 
@@ -14,6 +16,26 @@ impl ReelApp {
     }
 
     pub fn run(self) -> anyhow::Result<()> {
+        // `--db-rollback` undoes the last database upgrade by restoring the
+        // backup taken before it, the way out after downgrading Reel
+        if std::env::args().any(|arg| arg == "--db-rollback") {
+            let backup = self.runtime.block_on(async {
+                let database = Database::new().await?;
+                let backup = crate::db::backup::stage_rollback(&database.get_connection()).await?;
+                database.close().await?;
+                anyhow::Ok(backup)
+            })?;
+            tracing::info!("Rolling back the database to {}", backup.created_at);
+        }
+
+        // A restore chosen in preferences replaces the database and config
+        // before either is loaded
+        match crate::db::backup::apply_pending_restore() {
+            Ok(Some(backup)) => tracing::info!("Restored backup from {}", backup.created_at),
+            Ok(None) => {}
+            Err(e) => tracing::error!("Failed to restore backup: {:#}", e),
+        }
+
         // Initialize ConfigService early before UI components
         tracing::info!("Initializing ConfigService at application startup");
         let config_service = crate::services::config_service::config_service();
@@ -47,13 +69,19 @@ impl ReelApp {
                 .expect("Failed to initialize database");
 
             // Run database migrations
-            database
-                .migrate()
-                .await
-                .expect("Failed to run database migrations");
+            database.migrate().await?;
 
-            database.get_connection()
+            anyhow::Ok(database.get_connection())
         });
+        let db = match db {
+            Ok(db) => db,
+            Err(e) if e.downcast_ref::<NewerSchemaError>().is_some() => {
+                tracing::error!("{:#}", e);
+                show_newer_schema_dialog();
+                return Ok(());
+            }
+            Err(e) => return Err(e.context("Failed to run database migrations")),
+        };
 
         // Initialize cache service after database is ready
         tracing::info!("Initializing file cache service");
@@ -76,3 +104,25 @@ impl ReelApp {
         Ok(())
     }
 }
+
+/// Explain that the database belongs to a newer release, which is checked
+/// before any window exists, and wait for the user to close the message
+fn show_newer_schema_dialog() {
+    let dialog = gtk::AlertDialog::builder()
+        .message("Library From a Newer Version")
+        .detail(
+            "Your library was upgraded by a newer version of Reel and can't be opened by this one. Nothing was changed.\n\n\
+             Update Reel to keep using it, or start Reel with --db-rollback to restore the backup taken before the upgrade, \
+             losing changes made since.",
+        )
+        .buttons(["Quit"])
+        .modal(true)
+        .build();
+
+    let main_loop = glib::MainLoop::new(None, false);
+    dialog.choose(None::<&gtk::Window>, None::<&gio::Cancellable>, {
+        let main_loop = main_loop.clone();
+        move |_| main_loop.quit()
+    });
+    main_loop.run();
+}
@@ -0,0 +1,296 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::time::Duration;
+
+use super::jellyfin::api::ChapterInfo;
+use super::jellyfin::{JellyfinApi, JellyfinBackend};
+use super::traits::MediaBackend;
+use crate::db::entities::QualityPreset;
+use crate::models::{
+    AuthProvider, AuthenticationResult, ChapterMarker, ChapterType, Credentials, DownloadInfo,
+    Episode, HomeSection, Library, LibraryId, MediaItemId, Movie, Season, SessionReport, Show,
+    ShowId, Source, SourceType, StreamInfo, TranscodeSession, User,
+};
+
+/// Backend for Emby servers. Jellyfin forked from Emby and both still speak
+/// the same API for authentication, libraries, streams and progress, so the
+/// Jellyfin backend does the talking. Only the intro and credits markers
+/// differ: Emby sets them on the chapters of an item instead of serving
+/// media segments.
+#[derive(Debug)]
+pub struct EmbyBackend {
+    inner: JellyfinBackend,
+}
+
+impl EmbyBackend {
+    pub fn new() -> Self {
+        Self {
+            inner: JellyfinBackend::with_id("emby".to_string()),
+        }
+    }
+
+    /// Create a new EmbyBackend from an AuthProvider and Source
+    pub fn from_auth(auth_provider: AuthProvider, source: Source) -> Result<Self> {
+        if !matches!(auth_provider, AuthProvider::EmbyAuth { .. }) {
+            return Err(anyhow!("Invalid auth provider type for Emby backend"));
+        }
+
+        if !matches!(source.source_type, SourceType::EmbyServer) {
+            return Err(anyhow!("Invalid source type for Emby backend"));
+        }
+
+        Ok(Self {
+            inner: JellyfinBackend::for_server(auth_provider, source),
+        })
+    }
+
+    /// Sign in with a username and password, returning the credentials to
+    /// store for the source: the access token the server issued and the id
+    /// of the user, so the password itself isn't kept
+    pub async fn authenticate_with_credentials(
+        &self,
+        base_url: &str,
+        username: &str,
+        password: &str,
+    ) -> Result<Credentials> {
+        let auth_response = JellyfinApi::authenticate(base_url, username, password).await?;
+
+        let credentials = Credentials::Token {
+            token: format!("{}|{}", auth_response.access_token, auth_response.user.id),
+        };
+        self.inner.set_base_url(base_url.to_string()).await;
+        self.inner.authenticate(credentials.clone()).await?;
+
+        Ok(credentials)
+    }
+}
+
+#[async_trait]
+impl MediaBackend for EmbyBackend {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn initialize(&self) -> Result<AuthenticationResult> {
+        self.inner.initialize().await
+    }
+
+    async fn authenticate(&self, credentials: Credentials) -> Result<User> {
+        self.inner.authenticate(credentials).await
+    }
+
+    async fn get_libraries(&self) -> Result<Vec<Library>> {
+        self.inner.get_libraries().await
+    }
+
+    async fn get_movies(&self, library_id: &LibraryId) -> Result<Vec<Movie>> {
+        self.inner.get_movies(library_id).await
+    }
+
+    async fn get_shows(&self, library_id: &LibraryId) -> Result<Vec<Show>> {
+        self.inner.get_shows(library_id).await
+    }
+
+    async fn get_movie_metadata(&self, movie_id: &MediaItemId) -> Result<Movie> {
+        self.inner.get_movie_metadata(movie_id).await
+    }
+
+    async fn get_show_metadata(&self, show_id: &ShowId) -> Result<Show> {
+        self.inner.get_show_metadata(show_id).await
+    }
+
+    async fn get_seasons(&self, show_id: &ShowId) -> Result<Vec<Season>> {
+        self.inner.get_seasons(show_id).await
+    }
+
+    async fn get_episodes(&self, show_id: &ShowId, season: u32) -> Result<Vec<Episode>> {
+        self.inner.get_episodes(show_id, season).await
+    }
+
+    async fn get_stream_url(&self, media_id: &MediaItemId) -> Result<StreamInfo> {
+        self.inner.get_stream_url(media_id).await
+    }
+
+    async fn get_download_url(
+        &self,
+        media_id: &MediaItemId,
+        quality: &QualityPreset,
+    ) -> Result<DownloadInfo> {
+        self.inner.get_download_url(media_id, quality).await
+    }
+
+    async fn keep_transcode_alive(&self, session: &TranscodeSession) -> Result<()> {
+        self.inner.keep_transcode_alive(session).await
+    }
+
+    async fn stop_transcode(&self, session: &TranscodeSession) -> Result<()> {
+        self.inner.stop_transcode(session).await
+    }
+
+    async fn update_progress(
+        &self,
+        media_id: &MediaItemId,
+        position: Duration,
+        duration: Duration,
+    ) -> Result<()> {
+        self.inner
+            .update_progress(media_id, position, duration)
+            .await
+    }
+
+    async fn report_session(&self, report: &SessionReport) -> Result<()> {
+        self.inner.report_session(report).await
+    }
+
+    async fn fetch_markers(
+        &self,
+        media_id: &MediaItemId,
+    ) -> Result<(Option<ChapterMarker>, Option<ChapterMarker>)> {
+        let api = self.inner.ensure_api_initialized().await?;
+        let item_id = self.inner.extract_jellyfin_item_id(media_id);
+
+        let item = api.get_chapters(&item_id).await?;
+        Ok(markers_from_chapters(&item.chapters, item.run_time_ticks))
+    }
+
+    async fn get_home_sections(&self) -> Result<Vec<HomeSection>> {
+        self.inner.get_home_sections().await
+    }
+
+    async fn test_connection(
+        &self,
+        url: &str,
+        auth_token: Option<&str>,
+    ) -> Result<(bool, Option<u64>)> {
+        self.inner.test_connection(url, auth_token).await
+    }
+
+    async fn mark_watched(&self, item_id: &str) -> Result<()> {
+        MediaBackend::mark_watched(&self.inner, item_id).await
+    }
+
+    async fn mark_unwatched(&self, item_id: &str) -> Result<()> {
+        MediaBackend::mark_unwatched(&self.inner, item_id).await
+    }
+
+    async fn get_favorites(&self) -> Result<Vec<String>> {
+        self.inner.get_favorites().await
+    }
+
+    async fn set_favorite(&self, item_id: &str, favorite: bool) -> Result<()> {
+        self.inner.set_favorite(item_id, favorite).await
+    }
+
+    async fn get_theme_music_url(&self, item_id: &str) -> Result<Option<String>> {
+        self.inner.get_theme_music_url(item_id).await
+    }
+
+    async fn can_delete(&self, item_id: &str) -> Result<bool> {
+        self.inner.can_delete(item_id).await
+    }
+
+    async fn delete_item(&self, item_id: &str, version_id: Option<&str>) -> Result<()> {
+        self.inner.delete_item(item_id, version_id).await
+    }
+}
+
+/// Convert Emby ticks (100ns units) to Duration
+fn ticks_to_duration(ticks: u64) -> Duration {
+    Duration::from_micros(ticks / 10)
+}
+
+/// Start of the first chapter Emby marked with `marker_type`
+fn marker_start(chapters: &[ChapterInfo], marker_type: &str) -> Option<Duration> {
+    chapters
+        .iter()
+        .find(|chapter| chapter.marker_type.as_deref() == Some(marker_type))
+        .map(|chapter| ticks_to_duration(chapter.start_position_ticks))
+}
+
+/// Intro and credits markers from the chapter markers Emby sets. The intro
+/// runs from its start marker to its end marker, the credits from their
+/// start to the end of the item.
+fn markers_from_chapters(
+    chapters: &[ChapterInfo],
+    run_time_ticks: Option<u64>,
+) -> (Option<ChapterMarker>, Option<ChapterMarker>) {
+    let intro = match (
+        marker_start(chapters, "IntroStart"),
+        marker_start(chapters, "IntroEnd"),
+    ) {
+        (Some(start_time), Some(end_time)) if start_time < end_time => Some(ChapterMarker {
+            start_time,
+            end_time,
+            marker_type: ChapterType::Intro,
+        }),
+        _ => None,
+    };
+
+    let credits = match (
+        marker_start(chapters, "CreditsStart"),
+        run_time_ticks.map(ticks_to_duration),
+    ) {
+        (Some(start_time), Some(end_time)) if start_time < end_time => Some(ChapterMarker {
+            start_time,
+            end_time,
+            marker_type: ChapterType::Credits,
+        }),
+        _ => None,
+    };
+
+    (intro, credits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(seconds: u64, marker_type: &str) -> ChapterInfo {
+        ChapterInfo {
+            start_position_ticks: seconds * 10_000_000,
+            name: None,
+            marker_type: Some(marker_type.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_markers_from_chapter_markers() {
+        let chapters = [
+            chapter(0, "Chapter"),
+            chapter(30, "IntroStart"),
+            chapter(95, "IntroEnd"),
+            chapter(600, "Chapter"),
+            chapter(2500, "CreditsStart"),
+        ];
+
+        let (intro, credits) = markers_from_chapters(&chapters, Some(2600 * 10_000_000));
+
+        let intro = intro.expect("intro marker");
+        assert_eq!(intro.start_time, Duration::from_secs(30));
+        assert_eq!(intro.end_time, Duration::from_secs(95));
+        assert!(matches!(intro.marker_type, ChapterType::Intro));
+
+        let credits = credits.expect("credits marker");
+        assert_eq!(credits.start_time, Duration::from_secs(2500));
+        assert_eq!(credits.end_time, Duration::from_secs(2600));
+        assert!(matches!(credits.marker_type, ChapterType::Credits));
+    }
+
+    #[test]
+    fn test_incomplete_markers_are_ignored() {
+        let chapters = [chapter(30, "IntroStart"), chapter(2500, "CreditsStart")];
+
+        let (intro, credits) = markers_from_chapters(&chapters, None);
+        assert!(intro.is_none());
+        assert!(credits.is_none());
+    }
+
+    #[test]
+    fn test_plain_chapters_have_no_markers() {
+        let chapters = [chapter(0, "Chapter"), chapter(600, "Chapter")];
+
+        let (intro, credits) = markers_from_chapters(&chapters, Some(1200 * 10_000_000));
+        assert!(intro.is_none());
+        assert!(credits.is_none());
+    }
+}
@@ -1,17 +1,30 @@
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::OnceCell;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use super::device_profile::DeviceProfile;
+use super::streaming_profile::StreamingProfile;
+use crate::backends::request_log::RecordedSend;
+use crate::backends::traits::HOME_SECTION_ITEM_LIMIT;
+use crate::db::entities::QualityPreset;
 use crate::models::{
-    Episode, HomeSection, HomeSectionType, Library, LibraryType, MediaItem, Movie, QualityOption,
-    Resolution, Season, Show, StreamInfo, User,
+    DownloadInfo, Episode, HomeSection, HomeSectionType, Library, LibraryType, MediaItem,
+    MediaVersion, Movie, PlaybackError, QualityOption, Resolution, Season, SessionEvent,
+    SessionReport, SessionStream, Show, StreamInfo, StreamMode, User,
 };
+use crate::services::core::client_identity::identity_for_source;
+use crate::services::core::network_policy::network_policy;
+use crate::services::core::playback_capabilities::PlaybackCapabilities;
 
 const JELLYFIN_CLIENT_NAME: &str = "Reel";
 const JELLYFIN_VERSION: &str = "0.1.0";
+const JELLYFIN_DEVICE_NAME: &str = "Linux";
 
 #[allow(dead_code)] // Used internally by JellyfinBackend
 #[derive(Clone)]
@@ -22,6 +35,9 @@ pub struct JellyfinApi {
     user_id: String,
     device_id: String,
     backend_id: String,
+    /// Collections each item is in, fetched once for all the libraries a
+    /// sync goes through
+    collections: Arc<OnceCell<HashMap<String, Vec<String>>>>,
 }
 
 impl JellyfinApi {
@@ -31,8 +47,8 @@ impl JellyfinApi {
         user_id: String,
         backend_id: String,
     ) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
+        let client = network_policy()
+            .client_builder()
             .build()
             .expect("Failed to create HTTP client");
 
@@ -45,6 +61,7 @@ impl JellyfinApi {
             user_id,
             device_id,
             backend_id,
+            collections: Arc::new(OnceCell::new()),
         }
     }
 
@@ -52,17 +69,38 @@ impl JellyfinApi {
         Uuid::new_v4().to_string()
     }
 
+    /// Device the server knows this source's sessions and transcodes by
+    fn device_id(&self) -> String {
+        identity_for_source(&self.backend_id)
+            .client_identifier
+            .unwrap_or_else(|| self.device_id.clone())
+    }
+
+    /// Authorization header identifying Reel as configured for this source,
+    /// so the server can tell this device's sessions apart
     fn get_auth_header(&self) -> String {
+        let identity = identity_for_source(&self.backend_id);
         format!(
-            r#"MediaBrowser Client="{}", Device="Linux", DeviceId="{}", Version="{}", Token="{}""#,
-            JELLYFIN_CLIENT_NAME, self.device_id, JELLYFIN_VERSION, self.api_key
+            r#"MediaBrowser Client="{}", Device="{}", DeviceId="{}", Version="{}", Token="{}""#,
+            JELLYFIN_CLIENT_NAME,
+            identity
+                .device_name
+                .as_deref()
+                .unwrap_or(JELLYFIN_DEVICE_NAME),
+            self.device_id(),
+            JELLYFIN_VERSION,
+            self.api_key
         )
     }
 
     pub async fn get_server_info(&self) -> Result<ServerInfo> {
         let url = format!("{}/System/Info/Public", self.base_url);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self
+            .client
+            .get(&url)
+            .send_recorded(&self.backend_id)
+            .await?;
 
         if !response.status().is_success() {
             return Err(anyhow!("Failed to get server info: {}", response.status()));
@@ -73,9 +111,7 @@ impl JellyfinApi {
     }
 
     pub async fn check_quick_connect_enabled(base_url: &str) -> Result<bool> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()?;
+        let client = network_policy().client_builder().build()?;
 
         let url = format!("{}/QuickConnect/Enabled", base_url.trim_end_matches('/'));
         info!("Checking Quick Connect status at: {}", url);
@@ -120,14 +156,12 @@ impl JellyfinApi {
     }
 
     pub async fn initiate_quick_connect(base_url: &str) -> Result<QuickConnectState> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()?;
+        let client = network_policy().client_builder().build()?;
 
         let device_id = Self::get_or_create_device_id();
         let auth_header = format!(
-            r#"MediaBrowser Client="{}", Device="Linux", DeviceId="{}", Version="{}""#,
-            JELLYFIN_CLIENT_NAME, device_id, JELLYFIN_VERSION
+            r#"MediaBrowser Client="{}", Device="{}", DeviceId="{}", Version="{}""#,
+            JELLYFIN_CLIENT_NAME, JELLYFIN_DEVICE_NAME, device_id, JELLYFIN_VERSION
         );
 
         let url = format!("{}/QuickConnect/Initiate", base_url.trim_end_matches('/'));
@@ -185,9 +219,7 @@ impl JellyfinApi {
         base_url: &str,
         secret: &str,
     ) -> Result<QuickConnectResult> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()?;
+        let client = network_policy().client_builder().build()?;
 
         let url = format!(
             "{}/QuickConnect/Connect?Secret={}",
@@ -219,14 +251,12 @@ impl JellyfinApi {
         base_url: &str,
         secret: &str,
     ) -> Result<AuthResponse> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()?;
+        let client = network_policy().client_builder().build()?;
 
         let device_id = Self::get_or_create_device_id();
         let auth_header = format!(
-            r#"MediaBrowser Client="{}", Device="Linux", DeviceId="{}", Version="{}""#,
-            JELLYFIN_CLIENT_NAME, device_id, JELLYFIN_VERSION
+            r#"MediaBrowser Client="{}", Device="{}", DeviceId="{}", Version="{}""#,
+            JELLYFIN_CLIENT_NAME, JELLYFIN_DEVICE_NAME, device_id, JELLYFIN_VERSION
         );
 
         let url = format!(
@@ -273,14 +303,12 @@ impl JellyfinApi {
         username: &str,
         password: &str,
     ) -> Result<AuthResponse> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()?;
+        let client = network_policy().client_builder().build()?;
 
         let device_id = Self::get_or_create_device_id();
         let auth_header = format!(
-            r#"MediaBrowser Client="{}", Device="Linux", DeviceId="{}", Version="{}""#,
-            JELLYFIN_CLIENT_NAME, device_id, JELLYFIN_VERSION
+            r#"MediaBrowser Client="{}", Device="{}", DeviceId="{}", Version="{}""#,
+            JELLYFIN_CLIENT_NAME, JELLYFIN_DEVICE_NAME, device_id, JELLYFIN_VERSION
         );
 
         let url = format!(
@@ -346,7 +374,7 @@ impl JellyfinApi {
             .client
             .get(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -378,7 +406,7 @@ impl JellyfinApi {
             .client
             .get(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -422,7 +450,7 @@ impl JellyfinApi {
 
     pub async fn get_movies(&self, library_id: &str) -> Result<Vec<Movie>> {
         let url = format!(
-            "{}/Users/{}/Items?ParentId={}&IncludeItemTypes=Movie&Fields=Overview,Genres,DateCreated,MediaStreams,People,ProviderIds,RunTimeTicks&SortBy=SortName",
+            "{}/Users/{}/Items?ParentId={}&IncludeItemTypes=Movie&Fields=Overview,Genres,DateCreated,MediaStreams,MediaSources,People,ProviderIds,RunTimeTicks,Studios,Tags&SortBy=SortName",
             self.base_url, self.user_id, library_id
         );
 
@@ -431,7 +459,7 @@ impl JellyfinApi {
             .client
             .get(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await
             .map_err(|e| {
                 error!("Failed to send request to Jellyfin: {}", e);
@@ -468,11 +496,13 @@ impl JellyfinApi {
             anyhow!("Failed to parse movies response: {}", e)
         })?;
 
+        let collections = self.collection_membership().await;
         let movies: Vec<Movie> = items_response
             .items
             .into_iter()
             .map(|item| {
                 let duration = Duration::from_secs(item.run_time_ticks.unwrap_or(0) / 10_000_000);
+                let video_height = item.video_height();
                 // Skip cast/crew during sync - will be loaded lazily when viewing details
                 // This avoids storing incomplete data from bulk API responses
 
@@ -517,6 +547,15 @@ impl JellyfinApi {
                         .map(|ticks| Duration::from_secs(ticks / 10_000_000)),
                     intro_marker: None,
                     credits_marker: None,
+                    video_height,
+                    hdr_format: item.hdr_format(),
+                    atmos: item.has_atmos(),
+                    versions: item.versions(),
+                    external_ids: item.external_ids(),
+                    studios: item.studios(),
+                    tags: item.tags(),
+                    collections: collections.get(&item.id).cloned().unwrap_or_default(),
+                    content_rating: item.official_rating.clone(),
                 }
             })
             .collect();
@@ -527,7 +566,7 @@ impl JellyfinApi {
 
     pub async fn get_shows(&self, library_id: &str) -> Result<Vec<Show>> {
         let url = format!(
-            "{}/Users/{}/Items?ParentId={}&IncludeItemTypes=Series&Fields=Overview,Genres,DateCreated,ChildCount,People&SortBy=SortName",
+            "{}/Users/{}/Items?ParentId={}&IncludeItemTypes=Series&Fields=Overview,Genres,DateCreated,ChildCount,People,ProviderIds,Studios,Tags&SortBy=SortName",
             self.base_url, self.user_id, library_id
         );
 
@@ -536,7 +575,7 @@ impl JellyfinApi {
             .client
             .get(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await
             .map_err(|e| {
                 error!("Failed to send request to Jellyfin: {}", e);
@@ -573,6 +612,7 @@ impl JellyfinApi {
             anyhow!("Failed to parse shows response: {}", e)
         })?;
 
+        let collections = self.collection_membership().await;
         let mut shows = Vec::new();
         for item in items_response.items {
             let seasons = self.get_seasons(&item.id).await?;
@@ -623,6 +663,11 @@ impl JellyfinApi {
                     .and_then(|ud| ud.last_played_date.as_ref())
                     .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
                     .map(|dt| dt.with_timezone(&Utc)),
+                external_ids: item.external_ids(),
+                studios: item.studios(),
+                tags: item.tags(),
+                collections: collections.get(&item.id).cloned().unwrap_or_default(),
+                content_rating: item.official_rating.clone(),
             });
         }
 
@@ -630,6 +675,69 @@ impl JellyfinApi {
         Ok(shows)
     }
 
+    /// Names of the collections each item is in, by item id, fetched on
+    /// first use
+    async fn collection_membership(&self) -> &HashMap<String, Vec<String>> {
+        self.collections
+            .get_or_init(|| self.get_collection_membership())
+            .await
+    }
+
+    /// Collections only list their items, so each one is fetched; failures
+    /// leave items out of their collections rather than failing the sync.
+    async fn get_collection_membership(&self) -> HashMap<String, Vec<String>> {
+        let mut membership: HashMap<String, Vec<String>> = HashMap::new();
+        let url = format!(
+            "{}/Users/{}/Items?IncludeItemTypes=BoxSet&Recursive=true&SortBy=SortName",
+            self.base_url, self.user_id
+        );
+        let collections = match self.get_items(&url).await {
+            Ok(collections) => collections,
+            Err(e) => {
+                warn!("Failed to get collections: {}", e);
+                return membership;
+            }
+        };
+
+        for collection in collections {
+            let url = format!(
+                "{}/Users/{}/Items?ParentId={}",
+                self.base_url, self.user_id, collection.id
+            );
+            match self.get_items(&url).await {
+                Ok(items) => {
+                    for item in items {
+                        membership
+                            .entry(item.id)
+                            .or_default()
+                            .push(collection.name.clone());
+                    }
+                }
+                Err(e) => warn!(
+                    "Failed to get items of collection {}: {}",
+                    collection.name, e
+                ),
+            }
+        }
+        membership
+    }
+
+    async fn get_items(&self, url: &str) -> Result<Vec<JellyfinItem>> {
+        let response = self
+            .client
+            .get(url)
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("{}", response.status()));
+        }
+
+        let items_response: ItemsResponse = response.json().await?;
+        Ok(items_response.items)
+    }
+
     pub async fn get_seasons(&self, show_id: &str) -> Result<Vec<Season>> {
         let url = format!(
             "{}/Shows/{}/Seasons?userId={}&Fields=ItemCounts",
@@ -640,7 +748,7 @@ impl JellyfinApi {
             .client
             .get(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -685,7 +793,7 @@ impl JellyfinApi {
             .client
             .get(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -737,6 +845,7 @@ impl JellyfinApi {
                 (None, None)
             };
 
+            let video_height = item.video_height();
             episodes.push(Episode {
                 id: item.id.clone(),
                 backend_id: self.backend_id.clone(),
@@ -774,13 +883,45 @@ impl JellyfinApi {
                 }),
                 intro_marker,
                 credits_marker,
+                video_height,
             });
         }
 
         Ok(episodes)
     }
 
+    /// Stream of an item, negotiated down to a transcode at lower bitrates
+    /// while the server refuses what was asked for
     pub async fn get_stream_url(&self, media_id: &str) -> Result<StreamInfo> {
+        let device_profile = DeviceProfile::new(&PlaybackCapabilities::current().await);
+
+        for profile in StreamingProfile::negotiation() {
+            match self
+                .request_stream(media_id, &device_profile, &profile)
+                .await?
+            {
+                Some(stream_info) => return Ok(stream_info),
+                None => warn!(
+                    "Server refused {} (direct: {}, max bitrate: {}), trying the next streaming profile",
+                    media_id, profile.allow_direct, profile.max_bitrate
+                ),
+            }
+        }
+
+        Err(anyhow!(
+            "Server refused every stream of {}, including transcodes",
+            media_id
+        ))
+    }
+
+    /// Stream of an item under one streaming profile, None when the server
+    /// refuses it with 400 Bad Request
+    async fn request_stream(
+        &self,
+        media_id: &str,
+        device_profile: &DeviceProfile,
+        profile: &StreamingProfile,
+    ) -> Result<Option<StreamInfo>> {
         let playback_info_url = format!(
             "{}/Items/{}/PlaybackInfo?UserId={}&StartTimeTicks=0&IsPlayback=true&AutoOpenLiveStream=true&MediaSourceId={}",
             self.base_url, media_id, self.user_id, media_id
@@ -791,34 +932,17 @@ impl JellyfinApi {
             .post(&playback_info_url)
             .header("X-Emby-Authorization", self.get_auth_header())
             .header("Content-Type", "application/json")
-            .json(&serde_json::json!({
-                "DeviceProfile": {
-                    "MaxStreamingBitrate": 120000000,
-                    "DirectPlayProfiles": [
-                        {
-                            "Container": "mp4,m4v,mkv,webm",
-                            "Type": "Video",
-                            "VideoCodec": "h264,hevc,vp8,vp9,av1",
-                            "AudioCodec": "aac,mp3,opus,flac,vorbis"
-                        }
-                    ],
-                    "TranscodingProfiles": [
-                        {
-                            "Container": "mp4",
-                            "Type": "Video",
-                            "AudioCodec": "aac",
-                            "VideoCodec": "h264",
-                            "Context": "Streaming",
-                            "Protocol": "hls",
-                            "MaxAudioChannels": "6"
-                        }
-                    ]
-                }
-            }))
-            .send()
+            .json(&profile.playback_info_body(&self.user_id, media_id, device_profile))
+            .send_recorded(&self.backend_id)
             .await?;
 
+        if response.status() == reqwest::StatusCode::BAD_REQUEST {
+            return Ok(None);
+        }
         if !response.status().is_success() {
+            if let Some(error) = PlaybackError::from_status(response.status()) {
+                return Err(error.into());
+            }
             return Err(anyhow!(
                 "Failed to get playback info: {}",
                 response.status()
@@ -826,6 +950,10 @@ impl JellyfinApi {
         }
 
         let playback_info: PlaybackInfoResponse = response.json().await?;
+        if let Some(error_code) = &playback_info.error_code {
+            debug!("Server can't play {}: {}", media_id, error_code);
+            return Ok(None);
+        }
 
         if playback_info.media_sources.is_empty() {
             return Err(anyhow!("No media sources available"));
@@ -833,30 +961,67 @@ impl JellyfinApi {
 
         let media_source = &playback_info.media_sources[0];
 
-        let stream_url = if let Some(direct_url) = &media_source.direct_stream_url {
+        let (stream_url, mode) = match &media_source.direct_stream_url {
             // Use the provided DirectStreamUrl if available
-            if direct_url.starts_with("http") {
-                direct_url.clone()
-            } else {
-                format!("{}{}", self.base_url, direct_url)
+            Some(direct_url) if profile.allow_direct => {
+                let url = if direct_url.starts_with("http") {
+                    direct_url.clone()
+                } else {
+                    format!("{}{}", self.base_url, direct_url)
+                };
+                let mode = if media_source.supports_direct_play {
+                    StreamMode::DirectPlay
+                } else {
+                    StreamMode::DirectStream
+                };
+                (url, mode)
+            }
+            _ if profile.allow_direct && media_source.supports_direct_play => (
+                format!(
+                    "{}/Videos/{}/stream?Static=true&mediaSourceId={}&api_key={}",
+                    self.base_url, media_id, media_source.id, self.api_key
+                ),
+                StreamMode::DirectPlay,
+            ),
+            _ if profile.allow_direct && media_source.supports_direct_stream => (
+                format!(
+                    "{}/Videos/{}/stream?mediaSourceId={}&api_key={}",
+                    self.base_url, media_id, media_source.id, self.api_key
+                ),
+                StreamMode::DirectStream,
+            ),
+            _ => {
+                // The session lets the transcode be kept alive, restarted and stopped
+                let play_session_id = playback_info
+                    .play_session_id
+                    .clone()
+                    .unwrap_or_else(|| Uuid::new_v4().to_string());
+                let transcoding = &device_profile.transcoding_profiles[0];
+                (
+                    format!(
+                        "{}/Videos/{}/main.m3u8?mediaSourceId={}&api_key={}&DeviceId={}&PlaySessionId={}&VideoCodec={}&AudioCodec={}&MaxStreamingBitrate={}",
+                        self.base_url,
+                        media_id,
+                        media_source.id,
+                        self.api_key,
+                        self.device_id(),
+                        play_session_id,
+                        transcoding.video_codec,
+                        transcoding.audio_codec,
+                        profile.max_bitrate
+                    ),
+                    StreamMode::Transcode,
+                )
             }
-        } else if media_source.supports_direct_play {
-            format!(
-                "{}/Videos/{}/stream?Static=true&mediaSourceId={}&api_key={}",
-                self.base_url, media_id, media_source.id, self.api_key
-            )
-        } else if media_source.supports_direct_stream {
-            format!(
-                "{}/Videos/{}/stream?mediaSourceId={}&api_key={}",
-                self.base_url, media_id, media_source.id, self.api_key
-            )
-        } else {
-            format!(
-                "{}/Videos/{}/main.m3u8?mediaSourceId={}&api_key={}",
-                self.base_url, media_id, media_source.id, self.api_key
-            )
         };
 
+        // The server may still refuse a direct stream when it is fetched.
+        // Transcodes are taken on the word of the playback info, fetching
+        // their playlist would start one.
+        if mode != StreamMode::Transcode && !self.accepts_stream(&stream_url).await? {
+            return Ok(None);
+        }
+
         let video_stream = media_source
             .media_streams
             .iter()
@@ -912,9 +1077,10 @@ impl JellyfinApi {
             },
         ];
 
-        Ok(StreamInfo {
+        Ok(Some(StreamInfo {
             url: stream_url,
-            direct_play: media_source.supports_direct_play,
+            direct_play: mode == StreamMode::DirectPlay,
+            mode,
             video_codec: video_stream.codec.clone().unwrap_or_default(),
             audio_codec: audio_stream
                 .and_then(|s| s.codec.clone())
@@ -926,32 +1092,176 @@ impl JellyfinApi {
                 height: video_stream.height.unwrap_or(0) as u32,
             },
             quality_options,
+        }))
+    }
+
+    /// Whether the server serves a direct stream rather than refusing it
+    /// with 400 Bad Request, asking with a HEAD so nothing is sent
+    async fn accepts_stream(&self, url: &str) -> Result<bool> {
+        let response = self
+            .client
+            .head(url)
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        Ok(response.status() != reqwest::StatusCode::BAD_REQUEST)
+    }
+
+    /// Build a download for offline use.
+    ///
+    /// The original file comes from the item download endpoint; lower presets use a
+    /// progressive (non-HLS) server-side transcode so the result is a single file.
+    pub async fn get_download_url(
+        &self,
+        media_id: &str,
+        quality: &QualityPreset,
+    ) -> Result<DownloadInfo> {
+        let playback_info_url = format!(
+            "{}/Items/{}/PlaybackInfo?UserId={}",
+            self.base_url, media_id, self.user_id
+        );
+
+        let response = self
+            .client
+            .get(&playback_info_url)
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            if let Some(error) = PlaybackError::from_status(response.status()) {
+                return Err(error.into());
+            }
+            return Err(anyhow!(
+                "Failed to get playback info: {}",
+                response.status()
+            ));
+        }
+
+        let playback_info: PlaybackInfoResponse = response.json().await?;
+        let media_source = playback_info
+            .media_sources
+            .first()
+            .ok_or_else(|| anyhow!("No media sources available"))?;
+
+        let source_height = media_source
+            .media_streams
+            .iter()
+            .find(|s| s.stream_type == "Video")
+            .and_then(|s| s.height)
+            .unwrap_or(0) as u32;
+
+        if !quality.requires_transcode(source_height) {
+            return Ok(DownloadInfo {
+                url: format!(
+                    "{}/Items/{}/Download?api_key={}",
+                    self.base_url, media_id, self.api_key
+                ),
+                container: media_source
+                    .container
+                    .as_deref()
+                    .and_then(|c| c.split(',').next())
+                    .unwrap_or("mkv")
+                    .to_string(),
+                requires_transcode: false,
+            });
+        }
+
+        let (max_width, max_height) = quality.get_max_resolution();
+        let url = format!(
+            "{}/Videos/{}/stream.mkv?MediaSourceId={}&VideoCodec=h264&AudioCodec=aac&MaxWidth={}&MaxHeight={}&VideoBitrate={}&api_key={}",
+            self.base_url,
+            media_id,
+            media_source.id,
+            max_width,
+            max_height,
+            quality.max_video_bitrate_kbps() * 1000,
+            self.api_key
+        );
+
+        Ok(DownloadInfo {
+            url,
+            container: "mkv".to_string(),
+            requires_transcode: true,
         })
     }
 
-    pub async fn report_playback_start(&self, media_id: &str) -> Result<()> {
-        let url = format!("{}/Sessions/Playing", self.base_url);
+    /// Report a playback session to the server, which lists it on the
+    /// dashboard and ties it to the transcode with the same play session
+    pub async fn report_session(&self, media_id: &str, report: &SessionReport) -> Result<()> {
+        let path = match report.event {
+            SessionEvent::Started => "/Sessions/Playing",
+            SessionEvent::Progress => "/Sessions/Playing/Progress",
+            SessionEvent::Stopped => "/Sessions/Playing/Stopped",
+        };
+        let url = format!("{}{}", self.base_url, path);
 
         let response = self
             .client
             .post(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .json(&serde_json::json!({
-                "ItemId": media_id,
-                "MediaSourceId": media_id,
-                "PositionTicks": 0,
-                "IsPaused": false,
-                "IsMuted": false,
-                "PlayMethod": "DirectPlay",
-                "PlaySessionId": Uuid::new_v4().to_string(),
-            }))
-            .send()
+            .json(&session_report_body(media_id, report))
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Failed to report {:?} of play session {}: {}",
+                report.event,
+                report.play_session_id,
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Keep the transcode of a play session from being stopped by the
+    /// server, which it does to transcodes nobody fetched segments of for a
+    /// while, like when paused
+    pub async fn ping_play_session(&self, play_session_id: &str) -> Result<()> {
+        let url = format!("{}/Sessions/Playing/Ping", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .query(&[("playSessionId", play_session_id)])
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
-            warn!("Failed to report playback start: {}", response.status());
+            return Err(anyhow!(
+                "Failed to ping play session {}: {}",
+                play_session_id,
+                response.status()
+            ));
         }
+        Ok(())
+    }
+
+    /// Stop the transcode of a play session playback moved away from
+    pub async fn stop_encoding(&self, play_session_id: &str) -> Result<()> {
+        let url = format!("{}/Videos/ActiveEncodings", self.base_url);
+
+        let response = self
+            .client
+            .delete(&url)
+            .query(&[
+                ("deviceId", self.device_id().as_str()),
+                ("playSessionId", play_session_id),
+            ])
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
+            .await?;
 
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Failed to stop the transcode of play session {}: {}",
+                play_session_id,
+                response.status()
+            ));
+        }
         Ok(())
     }
 
@@ -972,7 +1282,7 @@ impl JellyfinApi {
                 "IsMuted": false,
                 "PlayMethod": "DirectPlay",
             }))
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -996,7 +1306,7 @@ impl JellyfinApi {
                 "MediaSourceId": media_id,
                 "PositionTicks": position_ticks,
             }))
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -1017,7 +1327,7 @@ impl JellyfinApi {
             .client
             .post(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -1038,7 +1348,7 @@ impl JellyfinApi {
             .client
             .delete(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -1051,6 +1361,128 @@ impl JellyfinApi {
         Ok(())
     }
 
+    /// Get the IDs of the user's favorite movies, shows and episodes
+    pub async fn get_favorites(&self) -> Result<Vec<String>> {
+        let url = format!(
+            "{}/Users/{}/Items?Filters=IsFavorite&Recursive=true&IncludeItemTypes=Movie,Series,Episode",
+            self.base_url, self.user_id
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get favorites: {}", response.status()));
+        }
+
+        let items_response: ItemsResponse = response.json().await?;
+        Ok(items_response
+            .items
+            .into_iter()
+            .map(|item| item.id)
+            .collect())
+    }
+
+    /// Mark or unmark media as a favorite
+    pub async fn set_favorite(&self, media_id: &str, favorite: bool) -> Result<()> {
+        let url = format!(
+            "{}/Users/{}/FavoriteItems/{}",
+            self.base_url, self.user_id, media_id
+        );
+
+        let request = if favorite {
+            self.client.post(&url)
+        } else {
+            self.client.delete(&url)
+        };
+
+        let response = request
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Failed to update favorite state: {}",
+                response.status()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Get a stream URL of the first theme song of a movie or series
+    pub async fn get_theme_song_url(&self, item_id: &str) -> Result<Option<String>> {
+        let url = format!(
+            "{}/Items/{}/ThemeSongs?UserId={}",
+            self.base_url, item_id, self.user_id
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get theme songs: {}", response.status()));
+        }
+
+        let items_response: ItemsResponse = response.json().await?;
+        Ok(items_response.items.into_iter().next().map(|song| {
+            format!(
+                "{}/Audio/{}/stream?static=true&api_key={}",
+                self.base_url, song.id, self.api_key
+            )
+        }))
+    }
+
+    /// Whether the user may delete the item from the server
+    pub async fn can_delete(&self, item_id: &str) -> Result<bool> {
+        let url = format!("{}/Users/{}/Items/{}", self.base_url, self.user_id, item_id);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get item: {}", response.status()));
+        }
+
+        let permissions: ItemPermissions = response.json().await?;
+        Ok(permissions.can_delete)
+    }
+
+    /// Delete an item and its files from the server. Each version of a movie
+    /// is an item of its own, deleted by its media source id.
+    pub async fn delete_item(&self, item_id: &str) -> Result<()> {
+        let url = format!("{}/Items/{}", self.base_url, item_id);
+
+        let response = self
+            .client
+            .delete(&url)
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Failed to delete from the server: {}",
+                response.status()
+            ));
+        }
+
+        Ok(())
+    }
+
     pub async fn get_media_segments(&self, item_id: &str) -> Result<Vec<MediaSegment>> {
         let url = format!("{}/Items/{}/MediaSegments", self.base_url, item_id);
 
@@ -1058,7 +1490,7 @@ impl JellyfinApi {
             .client
             .get(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -1074,6 +1506,28 @@ impl JellyfinApi {
         Ok(segments.items)
     }
 
+    /// Chapters of an item along with its length. Emby servers mark the
+    /// intro and credits on chapters rather than as media segments.
+    pub async fn get_chapters(&self, item_id: &str) -> Result<ItemChapters> {
+        let url = format!(
+            "{}/Users/{}/Items/{}?Fields=Chapters",
+            self.base_url, self.user_id, item_id
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("X-Emby-Authorization", self.get_auth_header())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to get chapters: {}", response.status()));
+        }
+
+        Ok(response.json().await?)
+    }
+
     pub async fn get_home_sections(&self) -> Result<Vec<HomeSection>> {
         let mut sections = Vec::new();
 
@@ -1112,15 +1566,15 @@ impl JellyfinApi {
 
     async fn get_continue_watching(&self) -> Result<Vec<MediaItem>> {
         let url = format!(
-            "{}/Users/{}/Items/Resume?Fields=Overview,Genres,People&Limit=20",
-            self.base_url, self.user_id
+            "{}/Users/{}/Items/Resume?Fields=Overview,Genres,People&Limit={}",
+            self.base_url, self.user_id, HOME_SECTION_ITEM_LIMIT
         );
 
         let response = self
             .client
             .get(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -1134,15 +1588,15 @@ impl JellyfinApi {
 
     async fn get_latest_movies(&self) -> Result<Vec<MediaItem>> {
         let url = format!(
-            "{}/Users/{}/Items/Latest?IncludeItemTypes=Movie&Fields=Overview,Genres,People&Limit=20",
-            self.base_url, self.user_id
+            "{}/Users/{}/Items/Latest?IncludeItemTypes=Movie&Fields=Overview,Genres,People&Limit={}",
+            self.base_url, self.user_id, HOME_SECTION_ITEM_LIMIT
         );
 
         let response = self
             .client
             .get(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -1156,15 +1610,15 @@ impl JellyfinApi {
 
     async fn get_next_up(&self) -> Result<Vec<MediaItem>> {
         let url = format!(
-            "{}/Shows/NextUp?UserId={}&Fields=Overview,Genres,People&Limit=20",
-            self.base_url, self.user_id
+            "{}/Shows/NextUp?UserId={}&Fields=Overview,Genres,People&Limit={}",
+            self.base_url, self.user_id, HOME_SECTION_ITEM_LIMIT
         );
 
         let response = self
             .client
             .get(&url)
             .header("X-Emby-Authorization", self.get_auth_header())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -1184,6 +1638,7 @@ impl JellyfinApi {
                     let duration =
                         Duration::from_secs(item.run_time_ticks.unwrap_or(0) / 10_000_000);
                     let (cast, crew) = self.convert_people_to_cast_crew(item.people.clone());
+                    let video_height = item.video_height();
                     Some(MediaItem::Movie(Movie {
                         id: item.id.clone(),
                         backend_id: self.backend_id.clone(),
@@ -1217,11 +1672,21 @@ impl JellyfinApi {
                             .map(|ticks| Duration::from_secs(ticks / 10_000_000)),
                         intro_marker: None,
                         credits_marker: None,
+                        video_height,
+                        hdr_format: item.hdr_format(),
+                        atmos: item.has_atmos(),
+                        versions: item.versions(),
+                        external_ids: item.external_ids(),
+                        studios: item.studios(),
+                        tags: item.tags(),
+                        collections: Vec::new(),
+                        content_rating: item.official_rating.clone(),
                     }))
                 }
                 Some("Episode") => {
                     let duration =
                         Duration::from_secs(item.run_time_ticks.unwrap_or(0) / 10_000_000);
+                    let video_height = item.video_height();
                     Some(MediaItem::Episode(Episode {
                         id: item.id.clone(),
                         backend_id: self.backend_id.clone(),
@@ -1251,6 +1716,7 @@ impl JellyfinApi {
                         }),
                         intro_marker: None,
                         credits_marker: None,
+                        video_height,
                     }))
                 }
                 _ => None,
@@ -1309,6 +1775,25 @@ impl JellyfinApi {
     }
 }
 
+/// Body of a playback session report. Local files play like the original
+/// file would, so only transcodes differ in how they play.
+fn session_report_body(media_id: &str, report: &SessionReport) -> serde_json::Value {
+    let play_method = match report.stream {
+        SessionStream::Direct | SessionStream::Local => "DirectPlay",
+        SessionStream::Transcode => "Transcode",
+    };
+    serde_json::json!({
+        "ItemId": media_id,
+        "MediaSourceId": media_id,
+        "PositionTicks": report.position.as_micros() as u64 * 10,
+        "IsPaused": report.paused,
+        "IsMuted": false,
+        "PlayMethod": play_method,
+        "PlaySessionId": report.play_session_id,
+        "CanSeek": true,
+    })
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -1381,6 +1866,13 @@ struct ItemsResponse {
     items: Vec<JellyfinItem>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ItemPermissions {
+    #[serde(default)]
+    can_delete: bool,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -1396,6 +1888,7 @@ pub struct JellyfinItem {
     date_created: Option<String>,
     run_time_ticks: Option<u64>,
     community_rating: Option<f32>,
+    official_rating: Option<String>,
     overview: Option<String>,
     genres: Option<Vec<String>>,
     #[serde(default)]
@@ -1407,6 +1900,114 @@ pub struct JellyfinItem {
     series_id: Option<String>,
     child_count: Option<i32>,
     people: Option<Vec<BaseItemPerson>>,
+    #[serde(default)]
+    media_streams: Vec<MediaStream>,
+    #[serde(default)]
+    media_sources: Vec<MediaSource>,
+    provider_ids: Option<HashMap<String, String>>,
+    studios: Option<Vec<NameIdPair>>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl JellyfinItem {
+    /// Vertical resolution of the item's video stream, if reported
+    fn video_height(&self) -> Option<u32> {
+        self.media_streams
+            .iter()
+            .filter(|stream| stream.stream_type == "Video")
+            .filter_map(|stream| stream.height)
+            .max()
+            .map(|height| height as u32)
+    }
+
+    /// Files of the item, each a media source of its own
+    fn versions(&self) -> Vec<MediaVersion> {
+        self.media_sources
+            .iter()
+            .map(|source| MediaVersion {
+                id: source.id.clone(),
+                video_height: source
+                    .media_streams
+                    .iter()
+                    .filter(|stream| stream.stream_type == "Video")
+                    .filter_map(|stream| stream.height)
+                    .max()
+                    .map(|height| height as u32),
+                container: source.container.clone(),
+                size: source.size,
+            })
+            .collect()
+    }
+
+    /// HDR format of the item's video stream, if it isn't SDR
+    fn hdr_format(&self) -> Option<String> {
+        self.media_streams
+            .iter()
+            .filter(|stream| stream.stream_type == "Video")
+            .find_map(|stream| {
+                let format = match stream.video_range_type.as_deref() {
+                    Some(range) if range.starts_with("DOVI") => "Dolby Vision",
+                    Some("HDR10Plus") => "HDR10+",
+                    Some("HDR10") => "HDR10",
+                    Some("HLG") => "HLG",
+                    _ if stream.video_range.as_deref() == Some("HDR") => "HDR",
+                    _ => return None,
+                };
+                Some(format.to_string())
+            })
+    }
+
+    /// Whether one of the item's audio streams is Dolby Atmos
+    fn has_atmos(&self) -> bool {
+        self.media_streams
+            .iter()
+            .filter(|stream| stream.stream_type == "Audio")
+            .any(|stream| {
+                [&stream.profile, &stream.display_title]
+                    .into_iter()
+                    .flatten()
+                    .any(|text| text.contains("Atmos"))
+            })
+    }
+
+    /// Ids in outside databases, in the "imdb://tt0111161" form Plex uses
+    fn external_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .provider_ids
+            .iter()
+            .flatten()
+            .filter(|(_, id)| !id.is_empty())
+            .map(|(provider, id)| format!("{}://{}", provider.to_lowercase(), id))
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Names of the studios or networks that made the item
+    fn studios(&self) -> Vec<String> {
+        self.studios
+            .iter()
+            .flatten()
+            .map(|studio| studio.name.clone())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    /// Tags set on the item on the server
+    fn tags(&self) -> Vec<String> {
+        self.tags
+            .iter()
+            .filter(|tag| !tag.is_empty())
+            .cloned()
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct NameIdPair {
+    name: String,
 }
 
 #[allow(dead_code)]
@@ -1465,6 +2066,23 @@ pub enum MediaSegmentType {
     Other,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ItemChapters {
+    pub run_time_ticks: Option<u64>,
+    #[serde(default)]
+    pub chapters: Vec<ChapterInfo>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ChapterInfo {
+    pub start_position_ticks: u64,
+    pub name: Option<String>,
+    /// Set by Emby: "Chapter", "IntroStart", "IntroEnd" or "CreditsStart"
+    pub marker_type: Option<String>,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -1477,6 +2095,10 @@ struct MediaSegmentsResponse {
 #[serde(rename_all = "PascalCase")]
 struct PlaybackInfoResponse {
     media_sources: Vec<MediaSource>,
+    play_session_id: Option<String>,
+    /// Why the server can't play the item under the profile, like
+    /// "NoCompatibleStream"
+    error_code: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -1491,6 +2113,8 @@ struct MediaSource {
     #[serde(default)]
     supports_direct_stream: bool,
     direct_stream_url: Option<String>,
+    size: Option<u64>,
+    #[serde(default)]
     media_streams: Vec<MediaStream>,
 }
 
@@ -1503,4 +2127,8 @@ struct MediaStream {
     codec: Option<String>,
     width: Option<i32>,
     height: Option<i32>,
+    video_range: Option<String>,
+    video_range_type: Option<String>,
+    profile: Option<String>,
+    display_title: Option<String>,
 }
@@ -0,0 +1,242 @@
+//! DeviceProfile sent with PlaybackInfo requests
+//!
+//! Jellyfin decides between direct play, direct stream and transcoding from
+//! the profile of the requesting device. The profile lists what the player in
+//! use demuxes and decodes, and caps the height of video the machine can only
+//! decode in software, so the server transcodes exactly what wouldn't play.
+
+use serde::Serialize;
+
+use crate::services::core::hw_decode::VideoCodec;
+use crate::services::core::playback_capabilities::PlaybackCapabilities;
+
+/// Highest bitrate requested from the server, in bits per second
+pub const MAX_STREAMING_BITRATE: u64 = 120_000_000;
+
+/// Audio channels kept when the server transcodes
+const MAX_TRANSCODED_AUDIO_CHANNELS: &str = "6";
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeviceProfile {
+    pub name: String,
+    pub max_streaming_bitrate: u64,
+    pub max_static_bitrate: u64,
+    pub direct_play_profiles: Vec<DirectPlayProfile>,
+    pub transcoding_profiles: Vec<TranscodingProfile>,
+    pub codec_profiles: Vec<CodecProfile>,
+    pub subtitle_profiles: Vec<SubtitleProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct DirectPlayProfile {
+    pub container: String,
+    #[serde(rename = "Type")]
+    pub media_type: String,
+    pub video_codec: String,
+    pub audio_codec: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct TranscodingProfile {
+    pub container: String,
+    #[serde(rename = "Type")]
+    pub media_type: String,
+    pub video_codec: String,
+    pub audio_codec: String,
+    pub context: String,
+    pub protocol: String,
+    pub max_audio_channels: String,
+    pub break_on_non_key_frames: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct CodecProfile {
+    #[serde(rename = "Type")]
+    pub media_type: String,
+    pub codec: String,
+    pub conditions: Vec<ProfileCondition>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProfileCondition {
+    pub condition: String,
+    pub property: String,
+    pub value: String,
+    pub is_required: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct SubtitleProfile {
+    pub format: String,
+    pub method: String,
+}
+
+impl DeviceProfile {
+    /// Profile of the player described by `capabilities`
+    pub fn new(capabilities: &PlaybackCapabilities) -> Self {
+        let engine = capabilities.engine;
+
+        // Transcodes go to H.264 unless it would have to be decoded in
+        // software where HEVC wouldn't
+        let transcode_video = match (
+            capabilities.max_height(VideoCodec::H264),
+            capabilities.max_height(VideoCodec::Hevc),
+        ) {
+            (Some(_), None) => "hevc,h264",
+            _ => "h264",
+        };
+
+        let codec_profiles = VideoCodec::ALL
+            .iter()
+            .filter_map(|codec| {
+                let max_height = capabilities.max_height(*codec)?;
+                Some(CodecProfile {
+                    media_type: "Video".to_string(),
+                    codec: codec_name(*codec).to_string(),
+                    conditions: vec![ProfileCondition {
+                        condition: "LessThanEqual".to_string(),
+                        property: "Height".to_string(),
+                        value: max_height.to_string(),
+                        is_required: false,
+                    }],
+                })
+            })
+            .collect();
+
+        Self {
+            name: format!("Reel ({:?})", engine),
+            max_streaming_bitrate: MAX_STREAMING_BITRATE,
+            max_static_bitrate: MAX_STREAMING_BITRATE,
+            direct_play_profiles: vec![DirectPlayProfile {
+                container: engine.containers().join(","),
+                media_type: "Video".to_string(),
+                video_codec: engine.video_codecs().join(","),
+                audio_codec: engine.audio_codecs().join(","),
+            }],
+            transcoding_profiles: vec![TranscodingProfile {
+                container: "ts".to_string(),
+                media_type: "Video".to_string(),
+                video_codec: transcode_video.to_string(),
+                audio_codec: "aac,mp3,ac3".to_string(),
+                context: "Streaming".to_string(),
+                protocol: "hls".to_string(),
+                max_audio_channels: MAX_TRANSCODED_AUDIO_CHANNELS.to_string(),
+                break_on_non_key_frames: true,
+            }],
+            codec_profiles,
+            subtitle_profiles: engine
+                .subtitle_formats()
+                .iter()
+                .map(|format| SubtitleProfile {
+                    format: format.to_string(),
+                    method: "Embed".to_string(),
+                })
+                .chain(["srt", "ass", "vtt"].iter().map(|format| SubtitleProfile {
+                    format: format.to_string(),
+                    method: "External".to_string(),
+                }))
+                .collect(),
+        }
+    }
+}
+
+/// Name Jellyfin uses for a codec in profiles
+fn codec_name(codec: VideoCodec) -> &'static str {
+    match codec {
+        VideoCodec::H264 => "h264",
+        VideoCodec::Hevc => "hevc",
+        VideoCodec::Vp9 => "vp9",
+        VideoCodec::Av1 => "av1",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::core::hw_decode::{DecoderApi, HardwareDecodeSupport, HardwareDecoder};
+    use crate::services::core::playback_capabilities::PlayerEngine;
+
+    fn capabilities(engine: PlayerEngine, hardware: &[VideoCodec]) -> PlaybackCapabilities {
+        PlaybackCapabilities {
+            engine,
+            hardware: Some(HardwareDecodeSupport {
+                decoders: hardware
+                    .iter()
+                    .map(|codec| HardwareDecoder {
+                        codec: *codec,
+                        api: DecoderApi::VaApi,
+                        element: "vadec".to_string(),
+                    })
+                    .collect(),
+            }),
+            software_max_height: 1080,
+        }
+    }
+
+    #[test]
+    fn test_direct_play_lists_the_formats_of_the_player() {
+        let profile = DeviceProfile::new(&capabilities(PlayerEngine::GStreamer, &[]));
+        let direct_play = &profile.direct_play_profiles[0];
+        assert!(direct_play.container.split(',').any(|c| c == "mkv"));
+        assert!(!direct_play.container.split(',').any(|c| c == "avi"));
+        assert!(!direct_play.audio_codec.split(',').any(|c| c == "dts"));
+
+        let profile = DeviceProfile::new(&capabilities(PlayerEngine::Mpv, &[]));
+        assert!(
+            profile.direct_play_profiles[0]
+                .audio_codec
+                .split(',')
+                .any(|c| c == "dts")
+        );
+    }
+
+    #[test]
+    fn test_software_decoded_codecs_are_capped_in_height() {
+        let profile = DeviceProfile::new(&capabilities(
+            PlayerEngine::GStreamer,
+            &[VideoCodec::H264, VideoCodec::Hevc],
+        ));
+        let capped: Vec<&str> = profile
+            .codec_profiles
+            .iter()
+            .map(|profile| profile.codec.as_str())
+            .collect();
+        assert_eq!(capped, ["vp9", "av1"]);
+        assert_eq!(profile.codec_profiles[0].conditions[0].value, "1080");
+    }
+
+    #[test]
+    fn test_transcodes_prefer_hevc_only_when_it_is_hardware_decoded() {
+        let hevc_only =
+            DeviceProfile::new(&capabilities(PlayerEngine::GStreamer, &[VideoCodec::Hevc]));
+        assert_eq!(hevc_only.transcoding_profiles[0].video_codec, "hevc,h264");
+
+        let both = DeviceProfile::new(&capabilities(
+            PlayerEngine::GStreamer,
+            &[VideoCodec::H264, VideoCodec::Hevc],
+        ));
+        assert_eq!(both.transcoding_profiles[0].video_codec, "h264");
+    }
+
+    #[test]
+    fn test_serializes_with_jellyfin_field_names() {
+        let json = serde_json::to_value(DeviceProfile::new(&capabilities(
+            PlayerEngine::GStreamer,
+            &[],
+        )))
+        .unwrap();
+        assert_eq!(json["MaxStreamingBitrate"], MAX_STREAMING_BITRATE);
+        assert_eq!(json["DirectPlayProfiles"][0]["Type"], "Video");
+        assert_eq!(json["TranscodingProfiles"][0]["Protocol"], "hls");
+        assert_eq!(
+            json["CodecProfiles"][0]["Conditions"][0]["Property"],
+            "Height"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use tracing::{debug, info, warn};
+
+/// UDP port Jellyfin servers listen on for client auto-discovery
+const DISCOVERY_PORT: u16 = 7359;
+
+/// Message Jellyfin servers answer to on the discovery port
+const DISCOVERY_MESSAGE: &[u8] = b"who is JellyfinServer?";
+
+/// Addresses probed via `/System/Info/Public` in addition to the broadcast
+const COMMON_ADDRESSES: &[&str] = &[
+    "http://localhost:8096",
+    "http://jellyfin.local:8096",
+    "http://jellyfin:8096",
+];
+
+/// A Jellyfin server found on the local network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredServer {
+    pub id: String,
+    pub name: String,
+    pub address: String,
+}
+
+/// Reply sent by a Jellyfin server to a UDP discovery broadcast
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DiscoveryResponse {
+    address: String,
+    id: String,
+    name: String,
+}
+
+/// Subset of `/System/Info/Public` needed to identify a server
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PublicSystemInfo {
+    id: String,
+    server_name: String,
+    local_address: Option<String>,
+}
+
+/// Parse a UDP discovery reply into a discovered server
+fn parse_discovery_response(payload: &[u8]) -> Option<DiscoveredServer> {
+    let response: DiscoveryResponse = serde_json::from_slice(payload).ok()?;
+    Some(DiscoveredServer {
+        id: response.id,
+        name: response.name,
+        address: response.address.trim_end_matches('/').to_string(),
+    })
+}
+
+/// Discover Jellyfin servers via UDP broadcast and probing of common addresses.
+///
+/// Servers are deduplicated by their server id; broadcast replies win over probes
+/// since they report the address the server advertises for the LAN.
+pub async fn discover_servers(timeout: Duration) -> Vec<DiscoveredServer> {
+    let (broadcast, probed) =
+        tokio::join!(discover_by_broadcast(timeout), discover_by_probing(timeout));
+
+    let broadcast = broadcast.unwrap_or_else(|e| {
+        warn!("Jellyfin broadcast discovery failed: {}", e);
+        Vec::new()
+    });
+
+    let mut seen = HashSet::new();
+    let servers: Vec<DiscoveredServer> = broadcast
+        .into_iter()
+        .chain(probed)
+        .filter(|server| seen.insert(server.id.clone()))
+        .collect();
+
+    info!("Discovered {} Jellyfin server(s)", servers.len());
+    servers
+}
+
+async fn discover_by_broadcast(timeout: Duration) -> Result<Vec<DiscoveredServer>> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.set_broadcast(true)?;
+
+    let target = SocketAddr::from(([255, 255, 255, 255], DISCOVERY_PORT));
+    socket.send_to(DISCOVERY_MESSAGE, target).await?;
+    debug!("Sent Jellyfin discovery broadcast to {}", target);
+
+    let mut servers = Vec::new();
+    let mut buf = [0u8; 2048];
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    loop {
+        match tokio::time::timeout_at(deadline, socket.recv_from(&mut buf)).await {
+            Ok(Ok((len, from))) => match parse_discovery_response(&buf[..len]) {
+                Some(server) => {
+                    debug!("Jellyfin server {} replied from {}", server.name, from);
+                    servers.push(server);
+                }
+                None => debug!("Ignoring unrecognized discovery reply from {}", from),
+            },
+            Ok(Err(e)) => {
+                warn!("Error receiving Jellyfin discovery reply: {}", e);
+                break;
+            }
+            Err(_) => break,
+        }
+    }
+
+    Ok(servers)
+}
+
+async fn discover_by_probing(timeout: Duration) -> Vec<DiscoveredServer> {
+    let client = match reqwest::Client::builder().timeout(timeout).build() {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("Failed to create HTTP client for Jellyfin probing: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let probes = COMMON_ADDRESSES
+        .iter()
+        .map(|address| probe_address(&client, address));
+
+    futures::future::join_all(probes)
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+async fn probe_address(client: &reqwest::Client, address: &str) -> Option<DiscoveredServer> {
+    let url = format!("{}/System/Info/Public", address);
+    let response = client.get(&url).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+
+    let info: PublicSystemInfo = response.json().await.ok()?;
+    debug!(
+        "Jellyfin server {} answered probe at {}",
+        info.server_name, address
+    );
+
+    Some(DiscoveredServer {
+        id: info.id,
+        name: info.server_name,
+        address: info
+            .local_address
+            .filter(|addr| !addr.is_empty())
+            .unwrap_or_else(|| address.to_string())
+            .trim_end_matches('/')
+            .to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_discovery_response() {
+        let payload = br#"{"Address":"http://192.168.1.10:8096/","Id":"abc123","Name":"Living Room","EndpointAddress":null}"#;
+        let server = parse_discovery_response(payload).unwrap();
+
+        assert_eq!(server.id, "abc123");
+        assert_eq!(server.name, "Living Room");
+        assert_eq!(server.address, "http://192.168.1.10:8096");
+    }
+
+    #[test]
+    fn test_parse_discovery_response_rejects_garbage() {
+        assert!(parse_discovery_response(b"who is JellyfinServer?").is_none());
+        assert!(parse_discovery_response(br#"{"Name":"missing fields"}"#).is_none());
+    }
+}
@@ -1,4 +1,7 @@
 pub mod api;
+pub mod device_profile;
+pub mod discovery;
+pub mod streaming_profile;
 #[cfg(test)]
 mod tests;
 
@@ -7,7 +10,6 @@ pub use api::JellyfinApi;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use dirs;
 use reqwest::Client;
 use std::fmt;
 use std::sync::Arc;
@@ -16,10 +18,14 @@ use tokio::sync::RwLock;
 use tracing::{error, info};
 
 use super::traits::MediaBackend;
+use crate::db::entities::QualityPreset;
 use crate::models::{
-    AuthProvider, AuthenticationResult, Credentials, Episode, HomeSection, Library, LibraryId,
-    MediaItemId, Movie, Season, Show, ShowId, Source, SourceType, StreamInfo, User,
+    AuthProvider, AuthenticationResult, Credentials, DownloadInfo, Episode, HomeSection, Library,
+    LibraryId, MediaItemId, Movie, Season, SessionReport, Show, ShowId, Source, SourceType,
+    StreamInfo, TranscodeSession, User,
 };
+use crate::services::core::auth::SecureStorage;
+use crate::services::core::network_policy::network_policy;
 
 #[allow(dead_code)] // Used via dynamic dispatch in BackendService
 pub struct JellyfinBackend {
@@ -99,19 +105,25 @@ impl JellyfinBackend {
             return Err(anyhow!("Invalid source type for Jellyfin backend"));
         }
 
+        Ok(Self::for_server(auth_provider, source))
+    }
+
+    /// Backend talking to the server of an already validated source, shared
+    /// by Emby, whose servers speak the same API
+    pub(crate) fn for_server(auth_provider: AuthProvider, source: Source) -> Self {
         let _client = Client::builder()
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client");
 
         // Extract server URL from auth provider
-        let base_url = if let AuthProvider::JellyfinAuth { server_url, .. } = &auth_provider {
-            Some(server_url.clone())
-        } else {
-            source.connection_info.primary_url.clone()
+        let base_url = match &auth_provider {
+            AuthProvider::JellyfinAuth { server_url, .. }
+            | AuthProvider::EmbyAuth { server_url, .. } => Some(server_url.clone()),
+            _ => source.connection_info.primary_url.clone(),
         };
 
-        Ok(Self {
+        Self {
             base_url: Arc::new(RwLock::new(base_url)),
             api_key: Arc::new(RwLock::new(None)), // Will be loaded from AuthProvider
             user_id: Arc::new(RwLock::new(None)), // Will be loaded from AuthProvider
@@ -120,10 +132,10 @@ impl JellyfinBackend {
             api: Arc::new(RwLock::new(None)),
             server_name: Arc::new(RwLock::new(Some(source.name.clone()))),
             auth_provider: Some(auth_provider),
-        })
+        }
     }
 
-    async fn ensure_api_initialized(&self) -> Result<JellyfinApi> {
+    pub(crate) async fn ensure_api_initialized(&self) -> Result<JellyfinApi> {
         // Check if already initialized
         if let Some(api) = self.api.read().await.clone() {
             return Ok(api);
@@ -146,55 +158,15 @@ impl JellyfinBackend {
         api_key: &str,
         user_id: &str,
     ) -> Result<()> {
-        let service = "dev.arsfeld.Reel";
-        let account = &format!("{}_jellyfin", self.backend_id);
-
+        let account = format!("{}_jellyfin", self.backend_id);
         info!("Saving Jellyfin credentials for account: {}", account);
 
         let credentials = format!("{}|{}|{}", base_url, api_key, user_id);
+        SecureStorage::from_config()
+            .await?
+            .set(&account, &credentials)?;
 
-        match keyring::Entry::new(service, account) {
-            Ok(entry) => match entry.set_password(&credentials) {
-                Ok(_) => {
-                    info!("Credentials saved to keyring for {}", self.backend_id);
-                    return Ok(());
-                }
-                Err(e) => {
-                    error!("Failed to save to keyring: {}, using file fallback", e);
-                }
-            },
-            Err(e) => {
-                error!("Failed to create keyring entry: {}, using file fallback", e);
-            }
-        }
-
-        let config_dir =
-            dirs::config_dir().ok_or_else(|| anyhow!("Could not determine config directory"))?;
-        let cred_file = config_dir
-            .join("reel")
-            .join(format!(".{}_jellyfin.cred", self.backend_id));
-
-        if let Some(parent) = cred_file.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        let obfuscated = credentials
-            .bytes()
-            .enumerate()
-            .map(|(i, b)| b ^ ((i as u8) + 42))
-            .collect::<Vec<u8>>();
-
-        std::fs::write(&cred_file, obfuscated)?;
-
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = std::fs::metadata(&cred_file)?.permissions();
-            perms.set_mode(0o600);
-            std::fs::set_permissions(&cred_file, perms)?;
-        }
-
-        info!("Credentials saved to file for {}", self.backend_id);
+        info!("Credentials saved for {}", self.backend_id);
         Ok(())
     }
 
@@ -240,7 +212,7 @@ impl JellyfinBackend {
 
     /// Extract the actual Jellyfin item ID from a composite media ID
     /// Format: "backend_id:library_id:type:item_id" or variations
-    fn extract_jellyfin_item_id(&self, media_id: &MediaItemId) -> String {
+    pub(crate) fn extract_jellyfin_item_id(&self, media_id: &MediaItemId) -> String {
         let media_id_str = media_id.as_str();
         if media_id_str.contains(':') {
             // Split and get the last part which should be the item ID
@@ -289,6 +261,12 @@ impl MediaBackend for JellyfinBackend {
                     access_token,
                     user_id,
                     ..
+                }
+                | AuthProvider::EmbyAuth {
+                    server_url,
+                    access_token,
+                    user_id,
+                    ..
                 } => {
                     tracing::info!(
                         "JellyfinBackend::initialize - provider_id: {}, has_token: {}, user_id: '{}'",
@@ -540,12 +518,27 @@ impl MediaBackend for JellyfinBackend {
             media_id
         );
 
-        let stream_info = api.get_stream_url(&jellyfin_item_id).await?;
+        api.get_stream_url(&jellyfin_item_id).await
+    }
 
-        // Report playback start after successfully getting stream info
-        api.report_playback_start(&jellyfin_item_id).await.ok();
+    async fn get_download_url(
+        &self,
+        media_id: &MediaItemId,
+        quality: &QualityPreset,
+    ) -> Result<DownloadInfo> {
+        let api = self.ensure_api_initialized().await?;
+        let jellyfin_item_id = self.extract_jellyfin_item_id(media_id);
+        api.get_download_url(&jellyfin_item_id, quality).await
+    }
 
-        Ok(stream_info)
+    async fn keep_transcode_alive(&self, session: &TranscodeSession) -> Result<()> {
+        let api = self.ensure_api_initialized().await?;
+        api.ping_play_session(&session.id).await
+    }
+
+    async fn stop_transcode(&self, session: &TranscodeSession) -> Result<()> {
+        let api = self.ensure_api_initialized().await?;
+        api.stop_encoding(&session.id).await
     }
 
     async fn update_progress(
@@ -568,6 +561,12 @@ impl MediaBackend for JellyfinBackend {
         Ok(())
     }
 
+    async fn report_session(&self, report: &SessionReport) -> Result<()> {
+        let api = self.ensure_api_initialized().await?;
+        let jellyfin_item_id = self.extract_jellyfin_item_id(&report.media_id);
+        api.report_session(&jellyfin_item_id, report).await
+    }
+
     async fn fetch_markers(
         &self,
         media_id: &MediaItemId,
@@ -630,7 +629,7 @@ impl MediaBackend for JellyfinBackend {
         use std::time::Instant;
 
         let start = Instant::now();
-        let client = Client::builder().timeout(Duration::from_secs(5)).build()?;
+        let client = network_policy().probe_client_builder().build()?;
 
         // For Jellyfin, test the public system info endpoint
         let base = url.trim_end_matches('/');
@@ -671,4 +670,28 @@ impl MediaBackend for JellyfinBackend {
             .ok_or_else(|| anyhow!("Jellyfin API not initialized"))?;
         api.mark_unwatched(item_id).await
     }
+    async fn get_favorites(&self) -> Result<Vec<String>> {
+        let api = self.ensure_api_initialized().await?;
+        api.get_favorites().await
+    }
+
+    async fn set_favorite(&self, item_id: &str, favorite: bool) -> Result<()> {
+        let api = self.ensure_api_initialized().await?;
+        api.set_favorite(item_id, favorite).await
+    }
+
+    async fn get_theme_music_url(&self, item_id: &str) -> Result<Option<String>> {
+        let api = self.ensure_api_initialized().await?;
+        api.get_theme_song_url(item_id).await
+    }
+
+    async fn can_delete(&self, item_id: &str) -> Result<bool> {
+        let api = self.ensure_api_initialized().await?;
+        api.can_delete(item_id).await
+    }
+
+    async fn delete_item(&self, item_id: &str, version_id: Option<&str>) -> Result<()> {
+        let api = self.ensure_api_initialized().await?;
+        api.delete_item(version_id.unwrap_or(item_id)).await
+    }
 }
@@ -0,0 +1,102 @@
+//! Negotiating how a stream is requested from the server
+//!
+//! The original is asked for first. Servers refuse some items they list as
+//! direct playable with 400 Bad Request, either when playback info is
+//! requested or when the stream itself is fetched, which leaves the player
+//! with nothing to load. The item is then requested as an HLS transcode, at
+//! lower bitrates in turn until the server accepts one.
+
+use serde_json::{Value, json};
+
+use super::device_profile::{DeviceProfile, MAX_STREAMING_BITRATE};
+
+/// Bitrates a transcode is requested at once the original was refused,
+/// highest first, in bits per second
+pub const TRANSCODE_BITRATES: [u64; 4] = [20_000_000, 8_000_000, 4_000_000, 2_000_000];
+
+/// One way of asking the server for a stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamingProfile {
+    /// Whether the server may send the original, or must transcode
+    pub allow_direct: bool,
+    /// Highest bitrate asked for, in bits per second
+    pub max_bitrate: u64,
+}
+
+impl StreamingProfile {
+    /// Profiles to ask for in turn, the original first
+    pub fn negotiation() -> Vec<Self> {
+        std::iter::once(Self {
+            allow_direct: true,
+            max_bitrate: MAX_STREAMING_BITRATE,
+        })
+        .chain(TRANSCODE_BITRATES.iter().map(|&max_bitrate| Self {
+            allow_direct: false,
+            max_bitrate,
+        }))
+        .collect()
+    }
+
+    /// Body of the PlaybackInfo request for `media_id`
+    pub fn playback_info_body(
+        &self,
+        user_id: &str,
+        media_id: &str,
+        device_profile: &DeviceProfile,
+    ) -> Value {
+        let device_profile = DeviceProfile {
+            max_streaming_bitrate: self.max_bitrate,
+            ..device_profile.clone()
+        };
+        json!({
+            "UserId": user_id,
+            "MediaSourceId": media_id,
+            "MaxStreamingBitrate": self.max_bitrate,
+            "EnableDirectPlay": self.allow_direct,
+            "EnableDirectStream": self.allow_direct,
+            "EnableTranscoding": true,
+            "DeviceProfile": device_profile,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::core::playback_capabilities::{PlaybackCapabilities, PlayerEngine};
+
+    #[test]
+    fn test_original_is_asked_for_first_then_lower_bitrates() {
+        let profiles = StreamingProfile::negotiation();
+        assert!(profiles[0].allow_direct);
+        assert_eq!(profiles[0].max_bitrate, MAX_STREAMING_BITRATE);
+
+        let transcodes = &profiles[1..];
+        assert!(transcodes.iter().all(|profile| !profile.allow_direct));
+        assert!(
+            transcodes
+                .windows(2)
+                .all(|pair| pair[0].max_bitrate > pair[1].max_bitrate)
+        );
+    }
+
+    #[test]
+    fn test_transcode_body_rules_out_the_original() {
+        let capabilities = PlaybackCapabilities {
+            engine: PlayerEngine::Mpv,
+            hardware: None,
+            software_max_height: 1080,
+        };
+        let profile = StreamingProfile {
+            allow_direct: false,
+            max_bitrate: 4_000_000,
+        };
+        let body =
+            profile.playback_info_body("user-1", "movie-1", &DeviceProfile::new(&capabilities));
+
+        assert_eq!(body["EnableDirectPlay"], false);
+        assert_eq!(body["EnableDirectStream"], false);
+        assert_eq!(body["MaxStreamingBitrate"], 4_000_000);
+        assert_eq!(body["DeviceProfile"]["MaxStreamingBitrate"], 4_000_000);
+    }
+}
@@ -1,7 +1,9 @@
 #[cfg(test)]
 mod tests {
     use super::super::*;
-    use crate::models::{LibraryId, LibraryType, MediaItemId};
+    use crate::models::{
+        LibraryId, LibraryType, MediaItemId, SessionEvent, SessionReport, SessionStream, StreamMode,
+    };
     use mockito::Server;
     use serde_json::json;
     use std::time::Duration;
@@ -90,7 +92,21 @@ mod tests {
                     },
                     "BackdropImageTags": ["backdrop-1"],
                     "Genres": ["Action", "Adventure"],
+                    "ProviderIds": {"Imdb": "tt0111161", "Tmdb": "278", "Tvdb": ""},
                     "Studios": [{"Name": "Test Studios"}],
+                    "Tags": ["4K Remaster", ""],
+                    "MediaStreams": [
+                        {"Type": "Video", "Height": 2160, "VideoRange": "HDR", "VideoRangeType": "DOVIWithHDR10"},
+                        {"Type": "Audio", "Profile": "Dolby TrueHD + Dolby Atmos"}
+                    ],
+                    "MediaSources": [
+                        {
+                            "Id": "source-1",
+                            "Container": "mkv",
+                            "Size": 60000000000i64,
+                            "MediaStreams": [{"Type": "Video", "Height": 2160}]
+                        }
+                    ],
                     "People": [
                         {
                             "Name": "John Director",
@@ -270,7 +286,7 @@ mod tests {
                 mockito::Matcher::UrlEncoded("IncludeItemTypes".into(), "Movie".into()),
                 mockito::Matcher::UrlEncoded(
                     "Fields".into(),
-                    "Overview,Genres,DateCreated,MediaStreams,People,ProviderIds,RunTimeTicks"
+                    "Overview,Genres,DateCreated,MediaStreams,MediaSources,People,ProviderIds,RunTimeTicks,Studios,Tags"
                         .into(),
                 ),
                 mockito::Matcher::UrlEncoded("SortBy".into(), "SortName".into()),
@@ -297,6 +313,75 @@ mod tests {
         assert!(movie.watched);
         assert_eq!(movie.view_count, 2);
         assert_eq!(movie.genres.len(), 2);
+        assert_eq!(movie.external_ids, vec!["imdb://tt0111161", "tmdb://278"]);
+        assert_eq!(movie.studios, vec!["Test Studios"]);
+        assert_eq!(movie.tags, vec!["4K Remaster"]);
+        assert_eq!(movie.content_rating.as_deref(), Some("PG-13"));
+        assert_eq!(movie.video_height, Some(2160));
+        assert_eq!(movie.hdr_format.as_deref(), Some("Dolby Vision"));
+        assert!(movie.atmos);
+        assert_eq!(movie.versions.len(), 1);
+        assert_eq!(movie.versions[0].id, "source-1");
+        assert_eq!(movie.versions[0].video_height, Some(2160));
+        assert_eq!(movie.versions[0].size, Some(60_000_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_collections_are_fetched_once_for_all_libraries() {
+        let mut server = Server::new_async().await;
+        let backend = create_test_backend(&server).await;
+
+        let collections = server
+            .mock("GET", "/Users/test_user_id/Items")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "IncludeItemTypes".into(),
+                "BoxSet".into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "Items": [{"Id": "boxset-1", "Name": "Classics", "Type": "BoxSet"}],
+                    "TotalRecordCount": 1
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let members = server
+            .mock("GET", "/Users/test_user_id/Items")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "ParentId".into(),
+                "boxset-1".into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(create_movies_response().to_string())
+            .expect(1)
+            .create_async()
+            .await;
+        let _movies = server
+            .mock("GET", "/Users/test_user_id/Items")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "IncludeItemTypes".into(),
+                "Movie".into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(create_movies_response().to_string())
+            .create_async()
+            .await;
+
+        for library_id in ["library-1", "library-2"] {
+            let movies = backend
+                .get_movies(&LibraryId::new(library_id))
+                .await
+                .unwrap();
+            assert_eq!(movies[0].collections, vec!["Classics"]);
+        }
+        collections.assert_async().await;
+        members.assert_async().await;
     }
 
     #[tokio::test]
@@ -312,7 +397,7 @@ mod tests {
                 mockito::Matcher::UrlEncoded("IncludeItemTypes".into(), "Series".into()),
                 mockito::Matcher::UrlEncoded(
                     "Fields".into(),
-                    "Overview,Genres,DateCreated,ChildCount,People".into(),
+                    "Overview,Genres,DateCreated,ChildCount,People,ProviderIds,Studios,Tags".into(),
                 ),
                 mockito::Matcher::UrlEncoded("SortBy".into(), "SortName".into()),
             ]))
@@ -388,14 +473,10 @@ mod tests {
             .create_async()
             .await;
 
-        // Mock the playback start report that happens after getting stream URL
         let _m3 = server
-            .mock("POST", "/Sessions/Playing")
-            .match_header(
-                "X-Emby-Authorization",
-                mockito::Matcher::Regex(r#".*Token="test_token".*"#.to_string()),
-            )
-            .with_status(204) // No content response
+            .mock("HEAD", "/Videos/movie-1/stream")
+            .match_query(mockito::Matcher::Any)
+            .with_status(206)
             .create_async()
             .await;
 
@@ -404,10 +485,77 @@ mod tests {
 
         assert!(stream_info.url.contains("/Videos/movie-1/stream"));
         assert!(stream_info.url.contains("api_key=test_token"));
+        assert_eq!(stream_info.mode, StreamMode::DirectStream);
         // Jellyfin provides transcoding options
         assert!(!stream_info.quality_options.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_refused_direct_play_falls_back_to_lower_bitrate_transcodes() {
+        let mut server = Server::new_async().await;
+        let backend = create_test_backend(&server).await;
+
+        let _direct = server
+            .mock("POST", "/Items/movie-1/PlaybackInfo")
+            .match_query(mockito::Matcher::Any)
+            .match_body(mockito::Matcher::PartialJson(
+                json!({ "EnableDirectPlay": true }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(create_playback_info_response().to_string())
+            .create_async()
+            .await;
+        let _refused_stream = server
+            .mock("HEAD", "/Videos/movie-1/stream")
+            .match_query(mockito::Matcher::Any)
+            .with_status(400)
+            .create_async()
+            .await;
+        let refused_transcode = server
+            .mock("POST", "/Items/movie-1/PlaybackInfo")
+            .match_query(mockito::Matcher::Any)
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "EnableDirectPlay": false,
+                "MaxStreamingBitrate": 20_000_000,
+            })))
+            .with_status(400)
+            .create_async()
+            .await;
+        let mut transcode_info = create_playback_info_response();
+        transcode_info["PlaySessionId"] = json!("play-1");
+        let _transcode = server
+            .mock("POST", "/Items/movie-1/PlaybackInfo")
+            .match_query(mockito::Matcher::Any)
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "EnableDirectPlay": false,
+                "MaxStreamingBitrate": 8_000_000,
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(transcode_info.to_string())
+            .create_async()
+            .await;
+        // Fetching the playlist would start a transcode just to check it
+        let playlist = server
+            .mock("GET", "/Videos/movie-1/main.m3u8")
+            .match_query(mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let media_id = MediaItemId::new("movie-1");
+        let stream_info = backend.get_stream_url(&media_id).await.unwrap();
+
+        refused_transcode.assert_async().await;
+        playlist.assert_async().await;
+        assert_eq!(stream_info.mode, StreamMode::Transcode);
+        assert!(!stream_info.direct_play);
+        assert!(stream_info.url.contains("/Videos/movie-1/main.m3u8"));
+        assert!(stream_info.url.contains("PlaySessionId=play-1"));
+        assert!(stream_info.url.contains("MaxStreamingBitrate=8000000"));
+    }
+
     #[tokio::test]
     async fn test_playback_progress_reporting() {
         let mut server = Server::new_async().await;
@@ -433,6 +581,101 @@ mod tests {
             .unwrap();
     }
 
+    fn session_report(event: SessionEvent, stream: SessionStream) -> SessionReport {
+        SessionReport {
+            media_id: MediaItemId::new("source-1:movie-1"),
+            event,
+            stream,
+            play_session_id: "play-1".to_string(),
+            position: Duration::from_millis(300_500),
+            duration: Duration::from_secs(7200),
+            paused: false,
+            play_queue: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transcode_sessions_report_their_play_session() {
+        let mut server = Server::new_async().await;
+        let backend = create_test_backend(&server).await;
+
+        let started = server
+            .mock("POST", "/Sessions/Playing")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "ItemId": "movie-1",
+                "PlayMethod": "Transcode",
+                "PlaySessionId": "play-1",
+                "PositionTicks": 3_005_000_000u64,
+            })))
+            .with_status(204)
+            .create_async()
+            .await;
+        let paused = server
+            .mock("POST", "/Sessions/Playing/Progress")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "PlaySessionId": "play-1",
+                "IsPaused": true,
+            })))
+            .with_status(204)
+            .create_async()
+            .await;
+        let stopped = server
+            .mock("POST", "/Sessions/Playing/Stopped")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "PlaySessionId": "play-1",
+            })))
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let mut report = session_report(SessionEvent::Started, SessionStream::Transcode);
+        backend.report_session(&report).await.unwrap();
+        report.event = SessionEvent::Progress;
+        report.paused = true;
+        backend.report_session(&report).await.unwrap();
+        report.event = SessionEvent::Stopped;
+        backend.report_session(&report).await.unwrap();
+
+        started.assert_async().await;
+        paused.assert_async().await;
+        stopped.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_local_files_report_as_direct_play() {
+        let mut server = Server::new_async().await;
+        let backend = create_test_backend(&server).await;
+
+        let started = server
+            .mock("POST", "/Sessions/Playing")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "ItemId": "movie-1",
+                "PlayMethod": "DirectPlay",
+            })))
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let report = session_report(SessionEvent::Started, SessionStream::Local);
+        backend.report_session(&report).await.unwrap();
+        started.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_rejected_session_reports_fail() {
+        let mut server = Server::new_async().await;
+        let backend = create_test_backend(&server).await;
+
+        let _m = server
+            .mock("POST", "/Sessions/Playing/Progress")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let report = session_report(SessionEvent::Progress, SessionStream::Direct);
+        assert!(backend.report_session(&report).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_connection_retry_logic() {
         let mut server = Server::new_async().await;
@@ -511,7 +754,7 @@ mod tests {
                 mockito::Matcher::UrlEncoded("IncludeItemTypes".into(), "Movie".into()),
                 mockito::Matcher::UrlEncoded(
                     "Fields".into(),
-                    "Overview,Genres,DateCreated,MediaStreams,People,ProviderIds,RunTimeTicks"
+                    "Overview,Genres,DateCreated,MediaStreams,MediaSources,People,ProviderIds,RunTimeTicks,Studios,Tags"
                         .into(),
                 ),
                 mockito::Matcher::UrlEncoded("SortBy".into(), "SortName".into()),
@@ -584,4 +827,42 @@ mod tests {
         let result = backend.get_libraries().await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_transcodes_are_stopped_for_the_configured_device() {
+        use crate::models::ClientIdentity;
+        use crate::services::core::client_identity::set_identity_for_source;
+
+        let mut server = Server::new_async().await;
+        set_identity_for_source(
+            "test_jellyfin_identity",
+            ClientIdentity {
+                client_identifier: Some("living-room".to_string()),
+                ..Default::default()
+            },
+        );
+        let api = JellyfinApi::with_backend_id(
+            server.url(),
+            "test_token".to_string(),
+            "test_user_id".to_string(),
+            "test_jellyfin_identity".to_string(),
+        );
+
+        let stop = server
+            .mock("DELETE", "/Videos/ActiveEncodings")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("deviceId".into(), "living-room".into()),
+                mockito::Matcher::UrlEncoded("playSessionId".into(), "play-1".into()),
+            ]))
+            .match_header(
+                "X-Emby-Authorization",
+                mockito::Matcher::Regex(r#".*DeviceId="living-room".*"#.to_string()),
+            )
+            .with_status(204)
+            .create_async()
+            .await;
+
+        api.stop_encoding("play-1").await.unwrap();
+        stop.assert_async().await;
+    }
 }
@@ -1,115 +1,409 @@
-use anyhow::Result;
+mod probe;
+mod scanner;
+
+use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use std::path::PathBuf;
+use futures::stream::{self, StreamExt};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
+use tracing::{info, warn};
 
 use super::traits::MediaBackend;
 use crate::models::{
-    AuthenticationResult, Credentials, Episode, Library, LibraryId, MediaItemId, Movie, Season,
-    Show, ShowId, StreamInfo, User,
+    AuthenticationResult, Credentials, Episode, Library, LibraryId, LibraryType, MediaItemId,
+    Movie, Resolution, Season, Show, ShowId, Source, SourceType, StreamInfo, StreamMode, User,
 };
-// Stateful services removed during Relm4 migration
-// use crate::services::{AuthManager, DataService};
+use crate::workers::thumbnail_worker::poster_frame_url;
+use probe::MediaProbe;
+use scanner::{FileKind, ScannedFile};
+
+/// Files probed at once while scanning
+const PROBE_CONCURRENCY: usize = 4;
 
-#[allow(dead_code)] // Placeholder for future local files support
+/// Backend for a folder of video files on this machine, for people without
+/// a media server. Files are sorted into movies and shows by their names and
+/// folders, and probed for their runtime and streams. There is no server to
+/// keep watch state, so it stays in the database.
 #[derive(Debug)]
-pub struct LocalBackend {
-    media_directories: Arc<RwLock<Vec<PathBuf>>>,
-    backend_id: String,
-    last_scan_time: Arc<RwLock<Option<DateTime<Utc>>>>,
+pub struct LocalFilesBackend {
+    source_id: String,
+    root: PathBuf,
+    /// Everything found under the folder, scanned once per backend
+    catalog: Arc<RwLock<Option<Arc<Catalog>>>>,
 }
 
-impl LocalBackend {
-    // from_auth method removed - never used
-    // Local backend is mostly unimplemented placeholder
+/// Movies and shows found under the folder
+#[derive(Debug, Default)]
+struct Catalog {
+    movies: Vec<Movie>,
+    shows: Vec<Show>,
+    /// Episodes by the id of their show
+    episodes: HashMap<String, Vec<Episode>>,
 }
 
-#[async_trait]
-impl MediaBackend for LocalBackend {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+impl LocalFilesBackend {
+    /// Create a new LocalFilesBackend for a local folder source
+    pub fn from_source(source: Source) -> Result<Self> {
+        let SourceType::LocalFolder { path } = source.source_type else {
+            return Err(anyhow!("Invalid source type for local files backend"));
+        };
+
+        Ok(Self {
+            source_id: source.id,
+            root: path,
+            catalog: Arc::new(RwLock::new(None)),
+        })
     }
 
-    async fn initialize(&self) -> Result<AuthenticationResult> {
-        // Local backend doesn't need authentication
-        // Check if we have configured directories
-        let dirs = self.media_directories.read().await;
-        if dirs.is_empty() {
-            // No directories configured yet - needs configuration
-            return Ok(AuthenticationResult::AuthRequired);
+    fn library_id(&self, library_type: &LibraryType) -> String {
+        let kind = match library_type {
+            LibraryType::Shows => "shows",
+            _ => "movies",
+        };
+        format!("{}:{}", self.source_id, kind)
+    }
+
+    /// Item ids are the path of the file or show folder under the root,
+    /// prefixed with the source so two folders never share ids
+    fn item_id(&self, relative: &str) -> String {
+        format!("{}:{}", self.source_id, relative)
+    }
+
+    /// File a media item id points at
+    fn file_path(&self, media_id: &MediaItemId) -> Result<PathBuf> {
+        let relative = media_id
+            .as_str()
+            .strip_prefix(&format!("{}:", self.source_id))
+            .ok_or_else(|| anyhow!("Media item {} is not in this folder", media_id))?;
+        if Path::new(relative)
+            .components()
+            .any(|component| !matches!(component, std::path::Component::Normal(_)))
+        {
+            return Err(anyhow!("Invalid media item id: {}", media_id));
+        }
+        Ok(self.root.join(relative))
+    }
+
+    async fn catalog(&self) -> Result<Arc<Catalog>> {
+        let mut catalog = self.catalog.write().await;
+        if let Some(catalog) = catalog.as_ref() {
+            return Ok(catalog.clone());
+        }
+
+        let scanned = self.scan().await?;
+        *catalog = Some(scanned.clone());
+        Ok(scanned)
+    }
+
+    /// Walk the folder and probe every video file in it
+    async fn scan(&self) -> Result<Arc<Catalog>> {
+        let root = self.root.clone();
+        let files = tokio::task::spawn_blocking(move || {
+            let files = scanner::video_files(&root)?;
+            Ok::<_, anyhow::Error>(
+                files
+                    .iter()
+                    .filter_map(|path| scanner::classify(&root, path))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .await
+        .context("Folder scan task failed")??;
+        info!(
+            "Found {} video files in {}",
+            files.len(),
+            self.root.display()
+        );
+
+        let probed: Vec<(ScannedFile, MediaProbe, Option<DateTime<Utc>>)> = stream::iter(files)
+            .map(|file| async move {
+                let probe = probe::probe(&file.path).await.unwrap_or_else(|e| {
+                    warn!("Failed to probe {}: {}", file.path.display(), e);
+                    MediaProbe::default()
+                });
+                let added_at = tokio::fs::metadata(&file.path)
+                    .await
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .map(DateTime::<Utc>::from);
+                (file, probe, added_at)
+            })
+            .buffered(PROBE_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut catalog = Catalog::default();
+        // Title, episodes and latest file of each show, by show key
+        let mut shows: BTreeMap<String, (String, Vec<Episode>, Option<DateTime<Utc>>)> =
+            BTreeMap::new();
+        for (file, probe, added_at) in probed {
+            let id = self.item_id(&file.relative);
+            let poster_url = poster_frame_url(&file.path, probe.duration);
+            let duration = probe.duration.unwrap_or_default();
+            match file.kind {
+                FileKind::Movie { title, year } => catalog.movies.push(Movie {
+                    id,
+                    backend_id: self.source_id.clone(),
+                    title,
+                    year,
+                    duration,
+                    rating: None,
+                    poster_url,
+                    backdrop_url: None,
+                    overview: None,
+                    genres: Vec::new(),
+                    cast: Vec::new(),
+                    crew: Vec::new(),
+                    added_at,
+                    updated_at: added_at,
+                    watched: false,
+                    view_count: 0,
+                    last_watched_at: None,
+                    playback_position: None,
+                    intro_marker: None,
+                    credits_marker: None,
+                    video_height: (probe.height > 0).then_some(probe.height),
+                    hdr_format: None,
+                    atmos: false,
+                    versions: Vec::new(),
+                    external_ids: Vec::new(),
+                    studios: Vec::new(),
+                    tags: Vec::new(),
+                    collections: Vec::new(),
+                    content_rating: None,
+                }),
+                FileKind::Episode {
+                    show_key,
+                    show_title,
+                    season,
+                    episode,
+                    title,
+                } => {
+                    let show_id = self.item_id(&show_key);
+                    let (_, episodes, show_added_at) = shows
+                        .entry(show_key)
+                        .or_insert_with(|| (show_title.clone(), Vec::new(), added_at));
+                    *show_added_at = (*show_added_at).max(added_at);
+                    episodes.push(Episode {
+                        id,
+                        backend_id: self.source_id.clone(),
+                        show_id: Some(show_id),
+                        title: title.unwrap_or_else(|| format!("Episode {}", episode)),
+                        season_number: season,
+                        episode_number: episode,
+                        duration,
+                        thumbnail_url: poster_url,
+                        overview: None,
+                        air_date: None,
+                        watched: false,
+                        view_count: 0,
+                        last_watched_at: None,
+                        playback_position: None,
+                        show_title: Some(show_title),
+                        show_poster_url: None,
+                        intro_marker: None,
+                        credits_marker: None,
+                        video_height: (probe.height > 0).then_some(probe.height),
+                    });
+                }
+            }
+        }
+
+        for (show_key, (title, mut episodes, added_at)) in shows {
+            let show_id = self.item_id(&show_key);
+            episodes.sort_by_key(|episode| (episode.season_number, episode.episode_number));
+            // Shows are represented by the frame of their first episode
+            let poster_url = episodes
+                .first()
+                .and_then(|episode| episode.thumbnail_url.clone());
+            for episode in &mut episodes {
+                episode.show_poster_url = poster_url.clone();
+            }
+
+            let mut seasons: Vec<Season> = Vec::new();
+            for episode in &episodes {
+                match seasons.last_mut() {
+                    Some(season) if season.season_number == episode.season_number => {
+                        season.episode_count += 1;
+                    }
+                    _ => seasons.push(Season {
+                        id: format!("{}:season:{}", show_id, episode.season_number),
+                        season_number: episode.season_number,
+                        episode_count: 1,
+                        poster_url: None,
+                    }),
+                }
+            }
+
+            catalog.shows.push(Show {
+                id: show_id.clone(),
+                backend_id: self.source_id.clone(),
+                title,
+                year: None,
+                seasons,
+                rating: None,
+                poster_url,
+                backdrop_url: None,
+                overview: None,
+                genres: Vec::new(),
+                cast: Vec::new(),
+                added_at,
+                updated_at: added_at,
+                watched_episode_count: 0,
+                total_episode_count: episodes.len() as u32,
+                last_watched_at: None,
+                external_ids: Vec::new(),
+                studios: Vec::new(),
+                tags: Vec::new(),
+                collections: Vec::new(),
+                content_rating: None,
+            });
+            catalog.episodes.insert(show_id, episodes);
         }
 
-        // Return a local user - local backend is always "authenticated"
-        Ok(AuthenticationResult::Authenticated(User {
+        Ok(Arc::new(catalog))
+    }
+
+    fn user() -> User {
+        User {
             id: "local".to_string(),
-            username: "Local Media".to_string(),
+            username: "Local Files".to_string(),
             email: None,
             avatar_url: None,
-        }))
+        }
+    }
+}
+
+#[async_trait]
+impl MediaBackend for LocalFilesBackend {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn initialize(&self) -> Result<AuthenticationResult> {
+        // The folder may live on a drive that isn't mounted
+        if !self.root.is_dir() {
+            return Ok(AuthenticationResult::NetworkError(format!(
+                "Folder {} is not available",
+                self.root.display()
+            )));
+        }
+        Ok(AuthenticationResult::Authenticated(Self::user()))
     }
 
     async fn authenticate(&self, _credentials: Credentials) -> Result<User> {
-        // Local backend doesn't need authentication
-        Ok(User {
-            id: "local".to_string(),
-            username: "Local User".to_string(),
-            email: None,
-            avatar_url: None,
-        })
+        // Local files don't need authentication
+        Ok(Self::user())
     }
 
     async fn get_libraries(&self) -> Result<Vec<Library>> {
-        // TODO: Scan local directories for media
-        todo!("Local library scanning not yet implemented")
+        let catalog = self.catalog().await?;
+        let libraries = [
+            (LibraryType::Movies, "Movies", catalog.movies.len()),
+            (LibraryType::Shows, "TV Shows", catalog.shows.len()),
+        ];
+
+        Ok(libraries
+            .into_iter()
+            .filter(|(_, _, count)| *count > 0)
+            .map(|(library_type, title, count)| Library {
+                id: self.library_id(&library_type),
+                title: title.to_string(),
+                library_type,
+                icon: None,
+                item_count: count as i32,
+            })
+            .collect())
     }
 
-    async fn get_movies(&self, _library_id: &LibraryId) -> Result<Vec<Movie>> {
-        // TODO: Scan local directory for movies
-        todo!("Local movie scanning not yet implemented")
+    async fn get_movies(&self, library_id: &LibraryId) -> Result<Vec<Movie>> {
+        if library_id.as_str() != self.library_id(&LibraryType::Movies) {
+            return Ok(Vec::new());
+        }
+        Ok(self.catalog().await?.movies.clone())
     }
 
-    async fn get_shows(&self, _library_id: &LibraryId) -> Result<Vec<Show>> {
-        // TODO: Scan local directory for TV shows
-        todo!("Local show scanning not yet implemented")
+    async fn get_shows(&self, library_id: &LibraryId) -> Result<Vec<Show>> {
+        if library_id.as_str() != self.library_id(&LibraryType::Shows) {
+            return Ok(Vec::new());
+        }
+        Ok(self.catalog().await?.shows.clone())
     }
 
-    async fn get_movie_metadata(&self, _movie_id: &MediaItemId) -> Result<Movie> {
-        // TODO: Read metadata from local files
-        todo!("Local movie metadata not yet implemented")
+    async fn get_movie_metadata(&self, movie_id: &MediaItemId) -> Result<Movie> {
+        self.catalog()
+            .await?
+            .movies
+            .iter()
+            .find(|movie| movie.id == movie_id.as_str())
+            .cloned()
+            .ok_or_else(|| anyhow!("Movie {} not found in the folder", movie_id))
     }
 
-    async fn get_show_metadata(&self, _show_id: &ShowId) -> Result<Show> {
-        // TODO: Read metadata from local files
-        todo!("Local show metadata not yet implemented")
+    async fn get_show_metadata(&self, show_id: &ShowId) -> Result<Show> {
+        self.catalog()
+            .await?
+            .shows
+            .iter()
+            .find(|show| show.id == show_id.as_str())
+            .cloned()
+            .ok_or_else(|| anyhow!("Show {} not found in the folder", show_id))
     }
 
-    async fn get_seasons(&self, _show_id: &ShowId) -> Result<Vec<Season>> {
-        // TODO: Scan local directory for seasons
-        todo!("Local season scanning not yet implemented")
+    async fn get_seasons(&self, show_id: &ShowId) -> Result<Vec<Season>> {
+        Ok(self.get_show_metadata(show_id).await?.seasons)
     }
 
-    async fn get_episodes(&self, _show_id: &ShowId, _season: u32) -> Result<Vec<Episode>> {
-        // TODO: Scan local directory for episodes
-        todo!("Local episode scanning not yet implemented")
+    async fn get_episodes(&self, show_id: &ShowId, season: u32) -> Result<Vec<Episode>> {
+        Ok(self
+            .catalog()
+            .await?
+            .episodes
+            .get(show_id.as_str())
+            .map(|episodes| {
+                episodes
+                    .iter()
+                    .filter(|episode| episode.season_number == season)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
     }
 
     async fn get_stream_url(&self, media_id: &MediaItemId) -> Result<StreamInfo> {
-        // For local files, the stream URL is just the file path
+        let path = self.file_path(media_id)?;
+        if !path.is_file() {
+            return Err(anyhow!("File {} is not available", path.display()));
+        }
+        let url = url::Url::from_file_path(&path)
+            .map_err(|_| anyhow!("Not an absolute path: {}", path.display()))?;
+
+        // Unknown streams are left to the player to detect
+        let probe = probe::probe(&path).await.unwrap_or_else(|e| {
+            warn!("Failed to probe {}: {}", path.display(), e);
+            MediaProbe::default()
+        });
+
         Ok(StreamInfo {
-            url: format!("file://{}", media_id),
+            url: url.into(),
             direct_play: true,
-            video_codec: String::new(), // Will be detected by GStreamer
-            audio_codec: String::new(),
-            container: String::new(),
-            bitrate: 0,
-            resolution: crate::models::Resolution {
-                width: 0,
-                height: 0,
+            mode: StreamMode::DirectPlay,
+            video_codec: probe.video_codec,
+            audio_codec: probe.audio_codec,
+            container: path
+                .extension()
+                .map(|extension| extension.to_string_lossy().to_lowercase())
+                .unwrap_or_default(),
+            bitrate: probe.bitrate,
+            resolution: Resolution {
+                width: probe.width,
+                height: probe.height,
             },
-            quality_options: vec![], // Local files don't need quality options
+            quality_options: Vec::new(), // Files play as they are
         })
     }
 
@@ -119,27 +413,120 @@ impl MediaBackend for LocalBackend {
         _position: Duration,
         _duration: Duration,
     ) -> Result<()> {
-        // TODO: Store progress locally
-        todo!("Local progress tracking not yet implemented")
+        // Progress of local files is kept in the database only
+        Ok(())
     }
 
-    // Removed unused methods: mark_watched, mark_unwatched, get_watch_status, search
-    // Removed unused methods: get_last_sync_time, supports_offline, get_backend_id
-
     async fn test_connection(
         &self,
         url: &str,
         _auth_token: Option<&str>,
     ) -> Result<(bool, Option<u64>)> {
-        // For local backend, check if the path exists
-        if url.starts_with("file://") {
-            let path = url.strip_prefix("file://").unwrap_or(url);
-            let exists = std::path::Path::new(path).exists();
-            Ok((exists, Some(0))) // Instant access for local files
-        } else {
-            // Not a file:// URL, assume it's a local path
-            let exists = std::path::Path::new(url).exists();
-            Ok((exists, Some(0)))
+        // Folder URLs are percent-encoded, like "file:///home/me/My%20Videos"
+        let path = match url::Url::parse(url) {
+            Ok(url) if url.scheme() == "file" => url
+                .to_file_path()
+                .map_err(|_| anyhow!("Not a local folder: {}", url))?,
+            _ => PathBuf::from(url),
+        };
+        Ok((path.is_dir(), Some(0))) // Instant access for local files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend(root: &Path) -> LocalFilesBackend {
+        LocalFilesBackend::from_source(Source::new(
+            "local_1".to_string(),
+            "Videos".to_string(),
+            SourceType::LocalFolder {
+                path: root.to_path_buf(),
+            },
+            None,
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_folder_sorted_into_movies_and_shows() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("Shows/Severance/Season 1")).unwrap();
+        for file in [
+            "Heat (1995).mkv",
+            "Shows/Severance/Season 1/Severance S01E02.mkv",
+            "Shows/Severance/Season 1/Severance S01E01.mkv",
+        ] {
+            std::fs::write(root.path().join(file), b"").unwrap();
         }
+        let backend = backend(root.path());
+
+        let libraries = backend.get_libraries().await.unwrap();
+        assert_eq!(libraries.len(), 2);
+
+        let movies = backend
+            .get_movies(&LibraryId::new("local_1:movies".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(movies.len(), 1);
+        assert_eq!(movies[0].id, "local_1:Heat (1995).mkv");
+        assert_eq!(movies[0].year, Some(1995));
+
+        let shows = backend
+            .get_shows(&LibraryId::new("local_1:shows".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(shows.len(), 1);
+        assert_eq!(shows[0].title, "Severance");
+        assert_eq!(shows[0].total_episode_count, 2);
+
+        let episodes = backend
+            .get_episodes(&ShowId::new(shows[0].id.clone()), 1)
+            .await
+            .unwrap();
+        let numbers: Vec<u32> = episodes.iter().map(|e| e.episode_number).collect();
+        assert_eq!(numbers, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_ids_stay_inside_the_folder() {
+        let backend = backend(Path::new("/media"));
+        assert_eq!(
+            backend
+                .file_path(&MediaItemId::new("local_1:Movies/Heat.mkv".to_string()))
+                .unwrap(),
+            PathBuf::from("/media/Movies/Heat.mkv")
+        );
+        assert!(
+            backend
+                .file_path(&MediaItemId::new("local_1:../etc/passwd".to_string()))
+                .is_err()
+        );
+        assert!(
+            backend
+                .file_path(&MediaItemId::new("local_2:Heat.mkv".to_string()))
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connection_to_folder_url_with_spaces() {
+        let root = tempfile::tempdir().unwrap();
+        let folder = root.path().join("My Videos");
+        std::fs::create_dir(&folder).unwrap();
+        let backend = backend(&folder);
+
+        let url = url::Url::from_directory_path(&folder).unwrap();
+        assert!(url.as_str().contains("My%20Videos"));
+        let (reachable, _) = backend.test_connection(url.as_str(), None).await.unwrap();
+        assert!(reachable);
+
+        let missing = url::Url::from_directory_path(root.path().join("Gone")).unwrap();
+        let (reachable, _) = backend
+            .test_connection(missing.as_str(), None)
+            .await
+            .unwrap();
+        assert!(!reachable);
     }
 }
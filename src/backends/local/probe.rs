@@ -0,0 +1,238 @@
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, SystemTime};
+
+/// What probing a video file tells about its streams
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MediaProbe {
+    pub duration: Option<Duration>,
+    pub video_codec: String,
+    pub audio_codec: String,
+    pub width: u32,
+    pub height: u32,
+    /// Overall bitrate in bits per second
+    pub bitrate: u64,
+}
+
+/// Probes so far, by file and the time it was last modified
+static PROBES: LazyLock<RwLock<HashMap<PathBuf, (SystemTime, MediaProbe)>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Probe a video file, reusing the probe of the scan or an earlier playback
+/// while the file is unchanged
+pub async fn probe(path: &Path) -> Result<MediaProbe> {
+    let modified = tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok();
+    if let Some(modified) = modified
+        && let Some(probe) = PROBES.read().ok().and_then(|probes| {
+            probes
+                .get(path)
+                .filter(|(probed_at, _)| *probed_at == modified)
+                .map(|(_, probe)| probe.clone())
+        })
+    {
+        return Ok(probe);
+    }
+
+    let probe = run_probe(path).await?;
+    if let Some(modified) = modified
+        && let Ok(mut probes) = PROBES.write()
+    {
+        probes.insert(path.to_path_buf(), (modified, probe.clone()));
+    }
+    Ok(probe)
+}
+
+/// Probe a video file with ffprobe, or with the GStreamer discoverer where
+/// ffprobe isn't installed
+async fn run_probe(path: &Path) -> Result<MediaProbe> {
+    let output = tokio::process::Command::new("ffprobe")
+        .args([
+            "-v",
+            "quiet",
+            "-print_format",
+            "json",
+            "-show_format",
+            "-show_streams",
+        ])
+        .arg(path)
+        .output()
+        .await;
+
+    match output {
+        Ok(output) if output.status.success() => parse_ffprobe(&output.stdout),
+        Ok(output) => Err(anyhow!(
+            "ffprobe failed on {} ({})",
+            path.display(),
+            output.status
+        )),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => discover(path).await,
+        Err(e) => Err(e).context("Failed to run ffprobe"),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeOutput {
+    #[serde(default)]
+    streams: Vec<FfprobeStream>,
+    format: Option<FfprobeFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeStream {
+    codec_type: Option<String>,
+    codec_name: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+/// ffprobe prints numbers in the format section as strings
+#[derive(Debug, Deserialize)]
+struct FfprobeFormat {
+    duration: Option<String>,
+    bit_rate: Option<String>,
+}
+
+fn parse_ffprobe(json: &[u8]) -> Result<MediaProbe> {
+    let output: FfprobeOutput =
+        serde_json::from_slice(json).context("Failed to parse ffprobe output")?;
+    let stream = |codec_type: &str| {
+        output
+            .streams
+            .iter()
+            .find(|stream| stream.codec_type.as_deref() == Some(codec_type))
+    };
+    let video = stream("video");
+    let audio = stream("audio");
+
+    let duration = output
+        .format
+        .as_ref()
+        .and_then(|format| format.duration.as_deref()?.parse::<f64>().ok())
+        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+        .map(Duration::from_secs_f64);
+    let bitrate = output
+        .format
+        .as_ref()
+        .and_then(|format| format.bit_rate.as_deref()?.parse().ok())
+        .unwrap_or_default();
+
+    Ok(MediaProbe {
+        duration,
+        video_codec: video
+            .and_then(|stream| stream.codec_name.clone())
+            .unwrap_or_default(),
+        audio_codec: audio
+            .and_then(|stream| stream.codec_name.clone())
+            .unwrap_or_default(),
+        width: video.and_then(|stream| stream.width).unwrap_or_default(),
+        height: video.and_then(|stream| stream.height).unwrap_or_default(),
+        bitrate,
+    })
+}
+
+#[cfg(feature = "gstreamer")]
+async fn discover(path: &Path) -> Result<MediaProbe> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || discover_blocking(&path))
+        .await
+        .context("Discoverer task failed")?
+}
+
+#[cfg(feature = "gstreamer")]
+fn discover_blocking(path: &Path) -> Result<MediaProbe> {
+    use gstreamer as gst;
+    use gstreamer_pbutils as pbutils;
+    use gstreamer_pbutils::prelude::*;
+
+    /// How long the discoverer may take over one file
+    const DISCOVER_TIMEOUT: gst::ClockTime = gst::ClockTime::from_seconds(15);
+
+    gst::init()?;
+    let uri = url::Url::from_file_path(path)
+        .map_err(|_| anyhow!("Not an absolute path: {}", path.display()))?;
+    let discoverer = pbutils::Discoverer::new(DISCOVER_TIMEOUT)?;
+    let info = discoverer.discover_uri(uri.as_str())?;
+
+    // Codec from the caps of a stream, like "h264" for "video/x-h264"
+    let codec = |caps: Option<gst::Caps>| {
+        caps.and_then(|caps| {
+            let name = caps.structure(0)?.name().to_string();
+            let name = name.split('/').nth(1)?.trim_start_matches("x-");
+            Some(match name {
+                "h265" => "hevc".to_string(),
+                name => name.to_string(),
+            })
+        })
+        .unwrap_or_default()
+    };
+    let video = info.video_streams().into_iter().next();
+    let audio = info.audio_streams().into_iter().next();
+
+    Ok(MediaProbe {
+        duration: info
+            .duration()
+            .map(|duration| Duration::from_nanos(duration.nseconds())),
+        video_codec: codec(video.as_ref().and_then(|video| video.caps())),
+        audio_codec: codec(audio.as_ref().and_then(|audio| audio.caps())),
+        width: video
+            .as_ref()
+            .map(|video| video.width())
+            .unwrap_or_default(),
+        height: video
+            .as_ref()
+            .map(|video| video.height())
+            .unwrap_or_default(),
+        bitrate: video
+            .as_ref()
+            .map(|video| video.bitrate() as u64)
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(not(feature = "gstreamer"))]
+async fn discover(_path: &Path) -> Result<MediaProbe> {
+    Err(anyhow!(
+        "ffprobe is not installed, install FFmpeg to read local files"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ffprobe_output() {
+        let json = br#"{
+            "streams": [
+                {"index": 0, "codec_type": "video", "codec_name": "hevc", "width": 3840, "height": 2160},
+                {"index": 1, "codec_type": "audio", "codec_name": "eac3"},
+                {"index": 2, "codec_type": "subtitle", "codec_name": "subrip"}
+            ],
+            "format": {"format_name": "matroska,webm", "duration": "5423.104000", "bit_rate": "21500000"}
+        }"#;
+
+        let probe = parse_ffprobe(json).unwrap();
+        assert_eq!(probe.duration, Some(Duration::from_secs_f64(5423.104)));
+        assert_eq!(probe.video_codec, "hevc");
+        assert_eq!(probe.audio_codec, "eac3");
+        assert_eq!((probe.width, probe.height), (3840, 2160));
+        assert_eq!(probe.bitrate, 21_500_000);
+    }
+
+    #[test]
+    fn test_parse_ffprobe_output_without_video() {
+        let json = br#"{"streams": [{"codec_type": "audio", "codec_name": "aac"}], "format": {}}"#;
+
+        let probe = parse_ffprobe(json).unwrap();
+        assert_eq!(probe.duration, None);
+        assert_eq!(probe.video_codec, "");
+        assert_eq!(probe.audio_codec, "aac");
+        assert_eq!(probe.bitrate, 0);
+    }
+}
@@ -0,0 +1,386 @@
+use anyhow::{Result, anyhow};
+use std::path::{Path, PathBuf};
+
+/// Extensions of the files counted as videos
+const VIDEO_EXTENSIONS: &[&str] = &[
+    "mkv", "mp4", "m4v", "avi", "mov", "webm", "wmv", "ts", "m2ts", "mpg", "mpeg", "ogv", "flv",
+];
+
+/// What the name and place of a video file tell about it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileKind {
+    Movie {
+        title: String,
+        year: Option<u32>,
+    },
+    Episode {
+        /// Key grouping the episodes of a show: the show folder relative to
+        /// the root, or the show name within the folder holding its episodes
+        show_key: String,
+        show_title: String,
+        season: u32,
+        episode: u32,
+        title: Option<String>,
+    },
+}
+
+/// A video file found under the scanned folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannedFile {
+    pub path: PathBuf,
+    /// Path relative to the scanned folder, with `/` separators
+    pub relative: String,
+    pub kind: FileKind,
+}
+
+/// Video files under `root`, skipping hidden files and folders. Fails when
+/// `root` isn't a readable folder, like an unmounted drive, so a missing
+/// folder doesn't pass for an empty one.
+pub fn video_files(root: &Path) -> Result<Vec<PathBuf>> {
+    if !root.is_dir() {
+        return Err(anyhow!("Folder {} is not available", root.display()));
+    }
+
+    let mut files = Vec::new();
+    let mut folders = vec![root.to_path_buf()];
+    while let Some(folder) = folders.pop() {
+        let entries = match std::fs::read_dir(&folder) {
+            Ok(entries) => entries,
+            Err(e) if folder == root => return Err(e.into()),
+            Err(e) => {
+                tracing::warn!("Failed to read folder {}: {}", folder.display(), e);
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            if path.is_dir() {
+                folders.push(path);
+            } else if is_video(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn is_video(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| VIDEO_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+}
+
+/// What a video file at `path` under `root` holds, judged by its name and
+/// the folders it sits in
+pub fn classify(root: &Path, path: &Path) -> Option<ScannedFile> {
+    let relative_path = path.strip_prefix(root).ok()?;
+    let stem = path.file_stem()?.to_string_lossy();
+
+    // Folders between the root and the file, nearest first
+    let folders: Vec<&Path> = relative_path
+        .ancestors()
+        .skip(1)
+        .filter(|folder| !folder.as_os_str().is_empty())
+        .collect();
+    let is_season_folder = |folder: &&Path| {
+        folder
+            .file_name()
+            .is_some_and(|name| season_folder(&name.to_string_lossy()).is_some())
+    };
+
+    let kind = match episode_marker(&stem) {
+        Some(marker) => {
+            // Episodes in season folders belong to the show folder above,
+            // others to the show their name starts with
+            let prefix = clean_name(&stem[..marker.start]);
+            let in_season_folder = folders.first().is_some_and(is_season_folder);
+            let show_folder = folders.iter().find(|folder| !is_season_folder(folder));
+            let show = match show_folder {
+                Some(folder) if in_season_folder || prefix.is_empty() => Some((
+                    slash_path(folder),
+                    clean_name(&folder.file_name()?.to_string_lossy()),
+                )),
+                _ if prefix.is_empty() => None,
+                _ => Some((
+                    match folders.first() {
+                        Some(folder) => format!("{}/{}", slash_path(folder), prefix),
+                        None => prefix.clone(),
+                    },
+                    prefix,
+                )),
+            };
+            match show {
+                Some((show_key, show_title)) => {
+                    let title = clean_name(&stem[marker.end..]);
+                    FileKind::Episode {
+                        show_key,
+                        show_title,
+                        season: marker.season,
+                        episode: marker.episode,
+                        title: (!title.is_empty()).then_some(title),
+                    }
+                }
+                None => movie(&stem),
+            }
+        }
+        None => movie(&stem),
+    };
+
+    Some(ScannedFile {
+        path: path.to_path_buf(),
+        relative: slash_path(relative_path),
+        kind,
+    })
+}
+
+/// Relative path with `/` separators
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn movie(stem: &str) -> FileKind {
+    let (title, year) = movie_name(stem);
+    FileKind::Movie { title, year }
+}
+
+/// Season and episode numbers in a file name, like "S01E02" or "1x02"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EpisodeMarker {
+    start: usize,
+    end: usize,
+    season: u32,
+    episode: u32,
+}
+
+/// Length of the run of ASCII digits at the start of `bytes`
+fn digits(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+}
+
+fn number(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0, |n, b| {
+        n.saturating_mul(10).saturating_add((b - b'0') as u32)
+    })
+}
+
+fn episode_marker(stem: &str) -> Option<EpisodeMarker> {
+    let bytes = stem.as_bytes();
+    let boundary = |at: usize| at == 0 || !bytes[at - 1].is_ascii_alphanumeric();
+    let ends = |at: usize| at >= bytes.len() || !bytes[at].is_ascii_alphanumeric();
+
+    for start in 0..bytes.len() {
+        if !boundary(start) {
+            continue;
+        }
+
+        // S01E02
+        if bytes[start].eq_ignore_ascii_case(&b's') {
+            let season_len = digits(&bytes[start + 1..]);
+            let e = start + 1 + season_len;
+            if (1..=3).contains(&season_len)
+                && e < bytes.len()
+                && bytes[e].eq_ignore_ascii_case(&b'e')
+            {
+                let episode_len = digits(&bytes[e + 1..]);
+                let end = e + 1 + episode_len;
+                if (1..=3).contains(&episode_len) && ends(end) {
+                    return Some(EpisodeMarker {
+                        start,
+                        end,
+                        season: number(&bytes[start + 1..e]),
+                        episode: number(&bytes[e + 1..end]),
+                    });
+                }
+            }
+        }
+
+        // 1x02
+        let season_len = digits(&bytes[start..]);
+        let x = start + season_len;
+        if (1..=2).contains(&season_len) && x < bytes.len() && bytes[x].eq_ignore_ascii_case(&b'x')
+        {
+            let episode_len = digits(&bytes[x + 1..]);
+            let end = x + 1 + episode_len;
+            if (1..=3).contains(&episode_len) && ends(end) {
+                return Some(EpisodeMarker {
+                    start,
+                    end,
+                    season: number(&bytes[start..x]),
+                    episode: number(&bytes[x + 1..end]),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Season number of a folder named like "Season 2", "S02" or "Specials"
+fn season_folder(name: &str) -> Option<u32> {
+    let name = name.trim().to_lowercase();
+    if name == "specials" {
+        return Some(0);
+    }
+    let number = name
+        .strip_prefix("season")
+        .or_else(|| name.strip_prefix('s'))?
+        .trim_start_matches([' ', '.', '_']);
+    (!number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| self::number(number.as_bytes()))
+}
+
+/// Title and year of a movie file name, like "Heat (1995)" or
+/// "Heat.1995.1080p.BluRay". The year is the last one in the name, so a
+/// title that is itself a year keeps it.
+fn movie_name(stem: &str) -> (String, Option<u32>) {
+    let bytes = stem.as_bytes();
+    let year_at = (1..bytes.len().saturating_sub(3)).rev().find(|&at| {
+        let end = at + 4;
+        digits(&bytes[at..]) == 4
+            && !bytes[at - 1].is_ascii_alphanumeric()
+            && (end == bytes.len() || !bytes[end].is_ascii_alphanumeric())
+            && (1900..=2099).contains(&number(&bytes[at..end]))
+    });
+
+    match year_at {
+        Some(at) => {
+            let title = clean_name(&stem[..at]);
+            if title.is_empty() {
+                (clean_name(stem), None)
+            } else {
+                (title, Some(number(&bytes[at..at + 4])))
+            }
+        }
+        None => (clean_name(stem), None),
+    }
+}
+
+/// Name with dots and underscores as spaces and the separators around it
+/// trimmed
+fn clean_name(name: &str) -> String {
+    name.replace(['.', '_'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c == '-' || c == '(' || c == '[' || c.is_whitespace())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(path: &str) -> FileKind {
+        classify(Path::new("/media"), &Path::new("/media").join(path))
+            .unwrap()
+            .kind
+    }
+
+    #[test]
+    fn test_movie_names() {
+        assert_eq!(movie_name("Heat (1995)"), ("Heat".to_string(), Some(1995)));
+        assert_eq!(
+            movie_name("The.Matrix.1999.1080p.BluRay.x264"),
+            ("The Matrix".to_string(), Some(1999))
+        );
+        assert_eq!(movie_name("2012 (2009)"), ("2012".to_string(), Some(2009)));
+        assert_eq!(movie_name("1917"), ("1917".to_string(), None));
+        assert_eq!(movie_name("Home Video"), ("Home Video".to_string(), None));
+    }
+
+    #[test]
+    fn test_episode_markers() {
+        let marker = episode_marker("Show.Name.S01E02.Pilot").unwrap();
+        assert_eq!((marker.season, marker.episode), (1, 2));
+        assert_eq!(&"Show.Name.S01E02.Pilot"[..marker.start], "Show.Name.");
+
+        let marker = episode_marker("show - 3x10 - Title").unwrap();
+        assert_eq!((marker.season, marker.episode), (3, 10));
+
+        assert!(episode_marker("Heat (1995)").is_none());
+        assert!(episode_marker("Movie 1080p x264").is_none());
+        assert!(episode_marker("Thes01e02").is_none());
+    }
+
+    #[test]
+    fn test_season_folders() {
+        assert_eq!(season_folder("Season 02"), Some(2));
+        assert_eq!(season_folder("S3"), Some(3));
+        assert_eq!(season_folder("Specials"), Some(0));
+        assert_eq!(season_folder("Show Name"), None);
+        assert_eq!(season_folder("Season"), None);
+    }
+
+    #[test]
+    fn test_classify_by_name_and_folders() {
+        assert_eq!(
+            kind("Movies/Heat (1995)/Heat (1995).mkv"),
+            FileKind::Movie {
+                title: "Heat".to_string(),
+                year: Some(1995),
+            }
+        );
+        assert_eq!(
+            kind("TV/The Wire/Season 01/The Wire - S01E03 - The Buys.mkv"),
+            FileKind::Episode {
+                show_key: "TV/The Wire".to_string(),
+                show_title: "The Wire".to_string(),
+                season: 1,
+                episode: 3,
+                title: Some("The Buys".to_string()),
+            }
+        );
+        assert_eq!(
+            kind("TV/Some.Show.S02E01.mkv"),
+            FileKind::Episode {
+                show_key: "TV/Some Show".to_string(),
+                show_title: "Some Show".to_string(),
+                season: 2,
+                episode: 1,
+                title: None,
+            }
+        );
+        assert_eq!(
+            kind("Some Show/S02E01.mkv"),
+            FileKind::Episode {
+                show_key: "Some Show".to_string(),
+                show_title: "Some Show".to_string(),
+                season: 2,
+                episode: 1,
+                title: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_video_files_skip_hidden_and_other_files() {
+        let root = tempfile::tempdir().unwrap();
+        let show = root.path().join("Show/Season 1");
+        std::fs::create_dir_all(&show).unwrap();
+        std::fs::create_dir_all(root.path().join(".trash")).unwrap();
+        for file in [
+            "Show/Season 1/Show S01E01.mkv",
+            "Show/Season 1/Show S01E01.srt",
+            "Movie (2001).MP4",
+            ".trash/Old (1990).mkv",
+        ] {
+            std::fs::write(root.path().join(file), b"").unwrap();
+        }
+
+        let files = video_files(root.path()).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                root.path().join("Movie (2001).MP4"),
+                root.path().join("Show/Season 1/Show S01E01.mkv"),
+            ]
+        );
+        assert!(video_files(&root.path().join("missing")).is_err());
+    }
+}
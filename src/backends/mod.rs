@@ -1,6 +1,9 @@
+pub mod emby;
 pub mod jellyfin;
 pub mod local;
 pub mod plex;
+pub mod request_log;
+pub mod request_scheduler;
 pub mod traits;
 
 // Re-export commonly used types
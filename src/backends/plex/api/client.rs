@@ -1,12 +1,18 @@
 use anyhow::{Result, anyhow};
 use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
 use reqwest::header::{HeaderMap, HeaderValue};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
 use tracing::{debug, warn};
 
 use super::errors::PlexApiError;
 use super::retry::RetryPolicy;
 use super::types::PlexIdentityResponse;
+use crate::backends::request_log::RecordedSend;
+use crate::models::ClientIdentity;
+use crate::services::core::client_identity::identity_for_source;
+use crate::services::core::network_policy::network_policy;
 
 /// Standard Plex headers used across all API requests.
 /// These constants ensure consistent client identification across the entire Plex API integration.
@@ -81,6 +87,33 @@ pub fn create_standard_headers(auth_token: Option<&str>) -> HeaderMap {
     headers
 }
 
+/// Create standard Plex headers identifying Reel as configured for a source,
+/// so the server can tell this device's sessions apart
+pub fn create_source_headers(auth_token: Option<&str>, source_id: &str) -> HeaderMap {
+    let mut headers = create_standard_headers(auth_token);
+    apply_client_identity(&mut headers, &identity_for_source(source_id));
+    headers
+}
+
+fn apply_client_identity(headers: &mut HeaderMap, identity: &ClientIdentity) {
+    let overrides = [
+        ("X-Plex-Device-Name", &identity.device_name),
+        ("X-Plex-Client-Identifier", &identity.client_identifier),
+        ("X-Plex-Platform", &identity.platform),
+    ];
+    for (header, value) in overrides {
+        let Some(value) = value else {
+            continue;
+        };
+        match HeaderValue::from_str(value) {
+            Ok(value) => {
+                headers.insert(header, value);
+            }
+            Err(_) => warn!("Ignoring {} that isn't a valid header: {:?}", header, value),
+        }
+    }
+}
+
 #[derive(Clone)]
 #[allow(dead_code)] // Used internally by PlexBackend
 pub struct PlexApi {
@@ -89,22 +122,19 @@ pub struct PlexApi {
     pub(super) auth_token: String,
     pub(super) backend_id: String,
     pub(super) retry_policy: RetryPolicy,
+    /// Smart collections each item is in, fetched once for all the
+    /// libraries a sync goes through
+    pub(super) smart_collections: Arc<OnceCell<HashMap<String, Vec<String>>>>,
 }
 
 impl PlexApi {
     pub fn with_backend_id(base_url: String, auth_token: String, backend_id: String) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-
-        Self {
-            client,
+        Self::with_retry_policy(
             base_url,
             auth_token,
             backend_id,
-            retry_policy: RetryPolicy::default(),
-        }
+            RetryPolicy::from(&network_policy()),
+        )
     }
 
     /// Create a PlexApi with a custom retry policy
@@ -114,8 +144,8 @@ impl PlexApi {
         backend_id: String,
         retry_policy: RetryPolicy,
     ) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
+        let client = network_policy()
+            .client_builder()
             .build()
             .expect("Failed to create HTTP client");
 
@@ -125,6 +155,7 @@ impl PlexApi {
             auth_token,
             backend_id,
             retry_policy,
+            smart_collections: Arc::new(OnceCell::new()),
         }
     }
 
@@ -134,7 +165,7 @@ impl PlexApi {
 
     /// Build standard headers that should be included in all Plex API requests
     pub(super) fn standard_headers(&self) -> HeaderMap {
-        create_standard_headers(Some(&self.auth_token))
+        create_source_headers(Some(&self.auth_token), &self.backend_id)
     }
 
     /// Execute an HTTP GET request with retry logic and error handling
@@ -171,7 +202,7 @@ impl PlexApi {
                     .client
                     .get(url)
                     .headers(headers.clone())
-                    .send()
+                    .send_recorded(&self.backend_id)
                     .await
                     .map_err(PlexApiError::from_reqwest)?;
 
@@ -255,3 +286,28 @@ impl PlexApi {
         Ok(identity.media_container.machine_identifier)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_identity_overrides_headers() {
+        let mut headers = create_standard_headers(None);
+        apply_client_identity(
+            &mut headers,
+            &ClientIdentity::from_input("Living Room", "reel-living-room", ""),
+        );
+        assert_eq!(headers["X-Plex-Device-Name"], "Living Room");
+        assert_eq!(headers["X-Plex-Client-Identifier"], "reel-living-room");
+        assert_eq!(headers["X-Plex-Platform"], PLEX_PLATFORM);
+    }
+
+    #[test]
+    fn test_default_identity_keeps_standard_headers() {
+        let mut headers = create_standard_headers(None);
+        apply_client_identity(&mut headers, &ClientIdentity::default());
+        assert_eq!(headers["X-Plex-Client-Identifier"], PLEX_CLIENT_IDENTIFIER);
+        assert!(headers.get("X-Plex-Device-Name").is_none());
+    }
+}
@@ -0,0 +1,53 @@
+use anyhow::{Result, anyhow};
+use tracing::debug;
+
+use super::client::PlexApi;
+use super::types::PlexServerResponse;
+use crate::backends::request_log::RecordedSend;
+
+impl PlexApi {
+    /// Whether the server owner allowed clients to delete media
+    pub async fn allows_media_deletion(&self) -> Result<bool> {
+        let url = self.build_url("/");
+
+        let response = self
+            .execute_get(&url, "allows_media_deletion")
+            .await
+            .map_err(|e| anyhow!("Failed to get server settings: {}", e))?;
+
+        let server: PlexServerResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse server settings: {}", e))?;
+
+        Ok(server.media_container.allow_media_deletion)
+    }
+
+    /// Delete an item and its files from the server, or only the file of
+    /// one of its versions
+    pub async fn delete_item(&self, rating_key: &str, media_id: Option<&str>) -> Result<()> {
+        let path = match media_id {
+            Some(media_id) => format!("/library/metadata/{}/media/{}", rating_key, media_id),
+            None => format!("/library/metadata/{}", rating_key),
+        };
+        let url = self.build_url(&path);
+
+        debug!("Deleting {} from the server", path);
+
+        let response = self
+            .client
+            .delete(&url)
+            .headers(self.standard_headers())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            debug!("Deletion failed: {} - {}", status, text);
+            return Err(anyhow!("Failed to delete from the server: {}", status));
+        }
+
+        Ok(())
+    }
+}
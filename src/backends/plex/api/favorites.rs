@@ -0,0 +1,72 @@
+use anyhow::{Result, anyhow};
+use tracing::debug;
+
+use super::client::PlexApi;
+use super::types::PlexRatedResponse;
+use crate::backends::request_log::RecordedSend;
+
+/// Plex has no favorites, a "liked" item is one rated 10 (five stars)
+const LIKED_RATING: &str = "10";
+/// Rating value that clears the user rating
+const CLEAR_RATING: &str = "-1";
+
+impl PlexApi {
+    /// Get the rating keys of liked items of the given metadata type in a library
+    /// (1 = movie, 2 = show, 4 = episode)
+    pub async fn get_liked(&self, library_id: &str, plex_type: u8) -> Result<Vec<String>> {
+        // Plex writes `>` as `>>=`, so this matches ratings of 9 and 10
+        let url = self.build_url(&format!(
+            "/library/sections/{}/all?type={}&userRating>>=8",
+            library_id, plex_type
+        ));
+
+        let response = self.execute_get(&url, "get_liked").await.map_err(|e| {
+            anyhow!(
+                "Failed to get liked items from library {}: {}",
+                library_id,
+                e
+            )
+        })?;
+
+        let plex_response: PlexRatedResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse liked items response: {}", e))?;
+
+        Ok(plex_response
+            .media_container
+            .metadata
+            .into_iter()
+            .map(|item| item.rating_key)
+            .collect())
+    }
+
+    /// Like or unlike an item
+    pub async fn set_liked(&self, media_id: &str, liked: bool) -> Result<()> {
+        let url = self.build_url("/:/rate");
+        let rating = if liked { LIKED_RATING } else { CLEAR_RATING };
+
+        debug!("Setting liked={} for media_id: {}", liked, media_id);
+
+        let response = self
+            .client
+            .put(&url)
+            .headers(self.standard_headers())
+            .query(&[
+                ("identifier", "com.plexapp.plugins.library"),
+                ("key", media_id),
+                ("rating", rating),
+            ])
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            debug!("Rating failed: {} - {}", status, text);
+            return Err(anyhow!("Failed to update liked state: {}", status));
+        }
+
+        Ok(())
+    }
+}
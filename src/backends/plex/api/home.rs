@@ -5,6 +5,8 @@ use tracing::{debug, info};
 
 use super::client::PlexApi;
 use super::types::*;
+use crate::backends::request_log::RecordedSend;
+use crate::backends::traits::HOME_SECTION_ITEM_LIMIT;
 use crate::models::{Episode, HomeSection, HomeSectionType, MediaItem, Movie, Show};
 
 impl PlexApi {
@@ -28,12 +30,12 @@ impl PlexApi {
         let mut sections = Vec::new();
 
         // Fetch the home hubs from the standard endpoint
-        let url = self.build_url("/hubs");
+        let url = self.build_url(&format!("/hubs?count={}", HOME_SECTION_ITEM_LIMIT));
         let response = self
             .client
             .get(&url)
             .headers(self.standard_headers())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -168,6 +170,15 @@ impl PlexApi {
                     intro_marker: None,   // Will be fetched when playing
                     credits_marker: None, // Will be fetched when playing
                     backend_id: self.backend_id.clone(),
+                    video_height: None,
+                    hdr_format: None,
+                    atmos: false,
+                    versions: Vec::new(),
+                    external_ids: Vec::new(),
+                    studios: Vec::new(),
+                    tags: Vec::new(),
+                    collections: Vec::new(),
+                    content_rating: meta.content_rating,
                 };
                 Ok(MediaItem::Movie(movie))
             }
@@ -203,6 +214,11 @@ impl PlexApi {
                     last_watched_at: meta
                         .last_viewed_at
                         .map(|ts| DateTime::from_timestamp(ts, 0).unwrap()),
+                    external_ids: Vec::new(),
+                    studios: Vec::new(),
+                    tags: Vec::new(),
+                    collections: Vec::new(),
+                    content_rating: meta.content_rating,
                 };
                 Ok(MediaItem::Show(show))
             }
@@ -216,6 +232,7 @@ impl PlexApi {
                 let episode = Episode {
                     intro_marker: None,
                     credits_marker: None,
+                    video_height: None,
                     id: meta.rating_key,
                     backend_id: self.backend_id.clone(),
                     show_id: Some(meta.grandparent_rating_key),
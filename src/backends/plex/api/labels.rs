@@ -0,0 +1,76 @@
+use anyhow::{Result, anyhow};
+use tracing::debug;
+
+use super::client::PlexApi;
+use super::types::PlexLabelsResponse;
+use crate::backends::request_log::RecordedSend;
+
+impl PlexApi {
+    /// Labels on a movie or show
+    pub async fn get_labels(&self, rating_key: &str) -> Result<Vec<String>> {
+        let url = self.build_url(&format!("/library/metadata/{}", rating_key));
+
+        let response = self
+            .execute_get(&url, "get_labels")
+            .await
+            .map_err(|e| anyhow!("Failed to get labels of {}: {}", rating_key, e))?;
+
+        let plex_response: PlexLabelsResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse labels response: {}", e))?;
+
+        Ok(plex_response
+            .media_container
+            .metadata
+            .into_iter()
+            .next()
+            .map(|item| item.labels.into_iter().map(|label| label.tag).collect())
+            .unwrap_or_default())
+    }
+
+    /// Put a label on a movie or show, or take it off
+    pub async fn set_label(&self, rating_key: &str, label: &str, present: bool) -> Result<()> {
+        let url = self.build_url(&format!("/library/metadata/{}", rating_key));
+
+        // Locking keeps the agent from replacing the labels on the next refresh
+        let mut query = vec![("label.locked".to_string(), "1".to_string())];
+        if present {
+            // Labels are sent as the whole list, an index replaces what is there
+            let mut labels = self.get_labels(rating_key).await?;
+            if !labels.iter().any(|existing| existing == label) {
+                labels.push(label.to_string());
+            }
+            query.extend(
+                labels
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, label)| (format!("label[{}].tag.tag", index), label)),
+            );
+        } else {
+            query.push(("label[].tag.tag-".to_string(), label.to_string()));
+        }
+
+        debug!(
+            "Setting label '{}' present={} on media_id: {}",
+            label, present, rating_key
+        );
+
+        let response = self
+            .client
+            .put(&url)
+            .headers(self.standard_headers())
+            .query(&query)
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            debug!("Label edit failed: {} - {}", status, text);
+            return Err(anyhow!("Failed to update labels: {}", status));
+        }
+
+        Ok(())
+    }
+}
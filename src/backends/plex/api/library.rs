@@ -1,10 +1,12 @@
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use std::time::Duration;
-use tracing::info;
+use tracing::{info, warn};
 
 use super::client::PlexApi;
 use super::types::*;
+use crate::backends::request_log::RecordedSend;
 use crate::models::{Episode, Library, LibraryType, Movie, Person, Season, Show};
 
 impl PlexApi {
@@ -83,6 +85,8 @@ impl PlexApi {
             );
         }
 
+        let smart_collections = self.smart_collection_membership().await;
+
         let movies: Vec<Movie> = plex_response
             .media_container
             .metadata
@@ -108,6 +112,18 @@ impl PlexApi {
                 // Skip cast/crew during sync - will be loaded lazily when viewing details
                 // This avoids storing incomplete data from bulk API responses
 
+                let collections = meta
+                    .collections
+                    .into_iter()
+                    .map(|c| c.tag)
+                    .chain(
+                        smart_collections
+                            .get(&meta.rating_key)
+                            .cloned()
+                            .unwrap_or_default(),
+                    )
+                    .collect();
+
                 Movie {
                     id: meta.rating_key,
                     backend_id: self.backend_id.clone(),
@@ -133,6 +149,15 @@ impl PlexApi {
                     playback_position: meta.view_offset.map(|v| Duration::from_millis(v as u64)),
                     intro_marker: None,   // Will be fetched when playing
                     credits_marker: None, // Will be fetched when playing
+                    video_height: meta.media.iter().filter_map(|m| m.height).max(),
+                    hdr_format: meta.media.iter().find_map(|m| m.hdr_format()),
+                    atmos: meta.media.iter().any(|m| m.has_atmos()),
+                    versions: meta.media.iter().filter_map(|m| m.version()).collect(),
+                    external_ids: meta.guids.into_iter().map(|guid| guid.id).collect(),
+                    studios: meta.studio.into_iter().collect(),
+                    tags: meta.labels.into_iter().map(|l| l.tag).collect(),
+                    collections,
+                    content_rating: meta.content_rating,
                 }
             })
             .collect();
@@ -149,7 +174,7 @@ impl PlexApi {
             .client
             .get(&url)
             .headers(self.standard_headers())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -247,9 +272,46 @@ impl PlexApi {
             playback_position: meta.view_offset.map(|v| Duration::from_millis(v as u64)),
             intro_marker: None,
             credits_marker: None,
+            video_height: meta.media.iter().filter_map(|m| m.height).max(),
+            hdr_format: meta.media.iter().find_map(|m| m.hdr_format()),
+            atmos: meta.media.iter().any(|m| m.has_atmos()),
+            versions: meta.media.iter().filter_map(|m| m.version()).collect(),
+            external_ids: meta.guids.into_iter().map(|guid| guid.id).collect(),
+            studios: meta.studio.into_iter().collect(),
+            tags: meta.labels.into_iter().map(|l| l.tag).collect(),
+            collections: meta.collections.into_iter().map(|c| c.tag).collect(),
+            content_rating: meta.content_rating,
         })
     }
 
+    /// Get a stream URL of the theme music of a movie or show
+    pub async fn get_theme_url(&self, rating_key: &str) -> Result<Option<String>> {
+        let url = self.build_url(&format!("/library/metadata/{}", rating_key));
+
+        let response = self
+            .execute_get(&url, "get_theme_url")
+            .await
+            .map_err(|e| anyhow!("Failed to get metadata of {}: {}", rating_key, e))?;
+
+        let plex_response: PlexThemeResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse theme response: {}", e))?;
+
+        Ok(plex_response
+            .media_container
+            .metadata
+            .into_iter()
+            .next()
+            .and_then(|meta| meta.theme)
+            .map(|theme| {
+                format!(
+                    "{}{}?X-Plex-Token={}",
+                    self.base_url, theme, self.auth_token
+                )
+            }))
+    }
+
     /// Get all TV shows from a library
     pub async fn get_shows(&self, library_id: &str) -> Result<Vec<Show>> {
         let url = self.build_url(&format!(
@@ -261,7 +323,7 @@ impl PlexApi {
             .client
             .get(&url)
             .headers(self.standard_headers())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -276,6 +338,8 @@ impl PlexApi {
             library_id
         );
 
+        let smart_collections = self.smart_collection_membership().await;
+
         let mut shows = Vec::new();
         for (idx, meta) in plex_response
             .media_container
@@ -304,6 +368,18 @@ impl PlexApi {
             // Skip cast during sync - will be loaded lazily when viewing details
             // This avoids storing incomplete data from bulk API responses
 
+            let collections = meta
+                .collections
+                .into_iter()
+                .map(|c| c.tag)
+                .chain(
+                    smart_collections
+                        .get(&meta.rating_key)
+                        .cloned()
+                        .unwrap_or_default(),
+                )
+                .collect();
+
             shows.push(Show {
                 id: meta.rating_key,
                 backend_id: self.backend_id.clone(),
@@ -323,6 +399,11 @@ impl PlexApi {
                 watched_episode_count: meta.viewed_leaf_count.unwrap_or(0) as u32,
                 total_episode_count: meta.leaf_count.unwrap_or(0) as u32,
                 last_watched_at: None, // TODO: Fetch from episodes
+                external_ids: meta.guids.into_iter().map(|guid| guid.id).collect(),
+                studios: meta.studio.into_iter().collect(),
+                tags: meta.labels.into_iter().map(|l| l.tag).collect(),
+                collections,
+                content_rating: meta.content_rating,
             });
         }
 
@@ -338,7 +419,7 @@ impl PlexApi {
             .client
             .get(&url)
             .headers(self.standard_headers())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -401,9 +482,113 @@ impl PlexApi {
             watched_episode_count: meta.viewed_leaf_count.unwrap_or(0) as u32,
             total_episode_count: meta.leaf_count.unwrap_or(0) as u32,
             last_watched_at: None,
+            external_ids: meta.guids.into_iter().map(|guid| guid.id).collect(),
+            studios: meta.studio.into_iter().collect(),
+            tags: meta.labels.into_iter().map(|l| l.tag).collect(),
+            collections: meta.collections.into_iter().map(|c| c.tag).collect(),
+            content_rating: meta.content_rating,
         })
     }
 
+    /// Titles of the smart collections each item of the server is in, keyed
+    /// by rating key. Items list the collections they were added to, but not
+    /// the smart ones whose filter they match. Fetched on first use for all
+    /// libraries.
+    async fn smart_collection_membership(&self) -> &HashMap<String, Vec<String>> {
+        self.smart_collections
+            .get_or_init(|| self.get_server_smart_collection_membership())
+            .await
+    }
+
+    /// Smart collection membership of every movie and show library, failures
+    /// leave items out of their smart collections rather than failing the sync
+    async fn get_server_smart_collection_membership(&self) -> HashMap<String, Vec<String>> {
+        let mut membership: HashMap<String, Vec<String>> = HashMap::new();
+        let libraries = match self.get_libraries().await {
+            Ok(libraries) => libraries,
+            Err(e) => {
+                warn!("Failed to get smart collections: {}", e);
+                return membership;
+            }
+        };
+
+        for library in libraries {
+            if !matches!(
+                library.library_type,
+                LibraryType::Movies | LibraryType::Shows
+            ) {
+                continue;
+            }
+            match self.get_smart_collection_membership(&library.id).await {
+                Ok(items) => {
+                    for (rating_key, titles) in items {
+                        membership.entry(rating_key).or_default().extend(titles);
+                    }
+                }
+                Err(e) => warn!(
+                    "Failed to get smart collections of library {}: {}",
+                    library.id, e
+                ),
+            }
+        }
+        membership
+    }
+
+    /// Titles of the smart collections each item of a library is in, keyed by
+    /// rating key
+    async fn get_smart_collection_membership(
+        &self,
+        library_id: &str,
+    ) -> Result<HashMap<String, Vec<String>>> {
+        let url = self.build_url(&format!("/library/sections/{}/collections", library_id));
+
+        let response = self
+            .execute_get(&url, "get_collections")
+            .await
+            .map_err(|e| anyhow!("Failed to get collections: {}", e))?;
+
+        let plex_response: PlexCollectionsResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse collections response: {}", e))?;
+
+        let mut membership: HashMap<String, Vec<String>> = HashMap::new();
+        for collection in plex_response
+            .media_container
+            .metadata
+            .into_iter()
+            .filter(|collection| collection.smart)
+        {
+            let url = self.build_url(&format!(
+                "/library/collections/{}/children",
+                collection.rating_key
+            ));
+            let response = match self.execute_get(&url, "get_collection_items").await {
+                Ok(response) => response,
+                Err(e) => {
+                    warn!(
+                        "Failed to get items of collection {}: {}",
+                        collection.title, e
+                    );
+                    continue;
+                }
+            };
+            let items: PlexRatedResponse = response
+                .json()
+                .await
+                .map_err(|e| anyhow!("Failed to parse collection items response: {}", e))?;
+
+            for item in items.media_container.metadata {
+                membership
+                    .entry(item.rating_key)
+                    .or_default()
+                    .push(collection.title.clone());
+            }
+        }
+
+        Ok(membership)
+    }
+
     /// Get seasons for a TV show
     pub async fn get_seasons(&self, show_id: &str) -> Result<Vec<Season>> {
         let url = self.build_url(&format!("/library/metadata/{}/children", show_id));
@@ -412,7 +597,7 @@ impl PlexApi {
             .client
             .get(&url)
             .headers(self.standard_headers())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -444,7 +629,7 @@ impl PlexApi {
             .client
             .get(&url)
             .headers(self.standard_headers())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -491,6 +676,7 @@ impl PlexApi {
                     show_poster_url: None,
                     intro_marker: None,   // Will be fetched when playing
                     credits_marker: None, // Will be fetched when playing
+                    video_height: meta.media.iter().filter_map(|m| m.height).max(),
                 }
             })
             .collect();
@@ -1,11 +1,16 @@
 use anyhow::Result;
+use std::collections::HashMap;
 use std::time::Duration;
 use tracing::{error, info, warn};
 
 use super::client::PlexApi;
 use super::types::*;
+use crate::backends::request_log::RecordedSend;
 use crate::models::{ChapterMarker, ChapterType};
 
+/// Items per batched marker request, keeping the URL a reasonable length
+const MARKER_BATCH_SIZE: usize = 50;
+
 impl PlexApi {
     /// Fetch intro and credit markers for any media (episode or movie)
     pub async fn fetch_episode_markers(
@@ -29,7 +34,7 @@ impl PlexApi {
                 ("includeExtras", "1"),
                 ("includeGeolocation", "1"),
             ])
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -52,46 +57,78 @@ impl PlexApi {
             }
         };
 
-        let mut intro_marker = None;
-        let mut credits_marker = None;
-
-        if let Some(metadata) = data.media_container.metadata.first() {
-            if let Some(markers) = &metadata.marker {
-                info!(
-                    "Found {} markers for media ID: {}",
-                    markers.len(),
-                    rating_key
-                );
-                for marker in markers.iter() {
-                    info!(
-                        "Marker type: '{}', start: {}ms, end: {}ms",
-                        marker.type_, marker.start_time_offset, marker.end_time_offset
-                    );
-                    match marker.type_.as_str() {
-                        "intro" => {
-                            intro_marker = Some(ChapterMarker {
-                                start_time: Duration::from_millis(marker.start_time_offset as u64),
-                                end_time: Duration::from_millis(marker.end_time_offset as u64),
-                                marker_type: ChapterType::Intro,
-                            });
-                        }
-                        "credits" => {
-                            credits_marker = Some(ChapterMarker {
-                                start_time: Duration::from_millis(marker.start_time_offset as u64),
-                                end_time: Duration::from_millis(marker.end_time_offset as u64),
-                                marker_type: ChapterType::Credits,
-                            });
-                        }
-                        _ => {}
-                    }
+        match data.media_container.metadata.first() {
+            Some(metadata) => Ok(markers_of(metadata)),
+            None => {
+                warn!("No metadata found in response for media ID: {}", rating_key);
+                Ok((None, None))
+            }
+        }
+    }
+
+    /// Fetch intro and credit markers for several items in one request,
+    /// keyed by rating key. Items without markers are left out.
+    pub async fn fetch_markers_batch(
+        &self,
+        rating_keys: &[&str],
+    ) -> Result<HashMap<String, (Option<ChapterMarker>, Option<ChapterMarker>)>> {
+        let mut markers = HashMap::new();
+
+        for chunk in rating_keys.chunks(MARKER_BATCH_SIZE) {
+            let url = self.build_url(&format!("/library/metadata/{}", chunk.join(",")));
+            let response = self
+                .client
+                .get(&url)
+                .headers(self.standard_headers())
+                .query(&[("includeMarkers", "1")])
+                .send_recorded(&self.backend_id)
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(anyhow::anyhow!(
+                    "Failed to fetch markers for {} items: {}",
+                    chunk.len(),
+                    response.status()
+                ));
+            }
+
+            let data: PlexMetadataResponse = response.json().await?;
+            for metadata in &data.media_container.metadata {
+                let (intro, credits) = markers_of(metadata);
+                if intro.is_some() || credits.is_some() {
+                    markers.insert(metadata.rating_key.clone(), (intro, credits));
                 }
-            } else {
-                info!("No markers found for media ID: {}", rating_key);
             }
-        } else {
-            warn!("No metadata found in response for media ID: {}", rating_key);
         }
 
-        Ok((intro_marker, credits_marker))
+        info!(
+            "Found markers for {} of {} items",
+            markers.len(),
+            rating_keys.len()
+        );
+        Ok(markers)
+    }
+}
+
+/// Intro and credits markers of one metadata entry
+fn markers_of(
+    metadata: &PlexMetadataWithMarkers,
+) -> (Option<ChapterMarker>, Option<ChapterMarker>) {
+    let mut intro_marker = None;
+    let mut credits_marker = None;
+
+    for marker in metadata.marker.iter().flatten() {
+        let chapter = |marker_type| ChapterMarker {
+            start_time: Duration::from_millis(marker.start_time_offset as u64),
+            end_time: Duration::from_millis(marker.end_time_offset as u64),
+            marker_type,
+        };
+        match marker.type_.as_str() {
+            "intro" => intro_marker = Some(chapter(ChapterType::Intro)),
+            "credits" => credits_marker = Some(chapter(ChapterType::Credits)),
+            _ => {}
+        }
     }
+
+    (intro_marker, credits_marker)
 }
@@ -1,8 +1,11 @@
 // Module organization for Plex API
 
 mod client;
+mod deletion;
 pub mod errors;
+mod favorites;
 mod home;
+mod labels;
 mod library;
 mod markers;
 pub mod playqueue;
@@ -12,7 +15,9 @@ mod streaming;
 mod types;
 
 // Re-export the main PlexApi struct, constants, and helper functions
-pub use client::{PlexApi, create_standard_headers};
+pub use client::{
+    PLEX_CLIENT_IDENTIFIER, PLEX_PRODUCT, PlexApi, create_source_headers, create_standard_headers,
+};
 pub use errors::PlexApiError;
 pub use retry::RetryPolicy;
 // Re-export PlayQueue types for external use
@@ -1,9 +1,9 @@
 use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
-use tracing::{debug, warn};
+use tracing::debug;
 
 use super::client::PlexApi;
+use crate::backends::request_log::RecordedSend;
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -88,7 +88,7 @@ impl PlexApi {
                 ("includeChapters", "1"),
                 ("includeRelated", "1"),
             ])
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -133,7 +133,7 @@ impl PlexApi {
                 ("playlistID", playlist_id),
                 ("uri", &uri),
             ])
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -162,7 +162,7 @@ impl PlexApi {
             .query(&[
                 ("own", "1"), // Take ownership of the queue
             ])
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -202,7 +202,7 @@ impl PlexApi {
             .put(&url)
             .headers(self.standard_headers())
             .query(&[("uri", uri.as_str())])
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -234,7 +234,7 @@ impl PlexApi {
             .client
             .delete(&url)
             .headers(self.standard_headers())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -277,7 +277,7 @@ impl PlexApi {
             .put(&url)
             .headers(self.standard_headers())
             .query(&query_params)
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -292,65 +292,4 @@ impl PlexApi {
 
         response.json().await.map_err(Into::into)
     }
-
-    /// Update progress using PlayQueue
-    pub async fn update_play_queue_progress(
-        &self,
-        play_queue_id: i64,
-        play_queue_version: i32,
-        play_queue_item_id: i64,
-        media_id: &str,
-        position: Duration,
-        duration: Duration,
-        state: &str,
-    ) -> Result<()> {
-        // Use the timeline endpoint but with PlayQueue context
-        let timeline_url = self.build_url("/:/timeline");
-
-        let position_ms = position.as_millis() as u64;
-        let duration_ms = duration.as_millis() as u64;
-
-        debug!(
-            "Updating PlayQueue progress - queue_id: {}, item_id: {}, media_id: {}, position: {}ms",
-            play_queue_id, play_queue_item_id, media_id, position_ms
-        );
-
-        // If we're more than 90% through, mark as watched
-        if duration_ms > 0 && position_ms > (duration_ms * 9 / 10) {
-            debug!("Position is >90% of duration, marking as watched");
-            return self.mark_watched(media_id).await;
-        }
-
-        let response = self
-            .client
-            .post(&timeline_url)
-            .headers(self.standard_headers())
-            .query(&[
-                ("ratingKey", media_id),
-                ("key", &format!("/library/metadata/{}", media_id)),
-                ("playQueueID", &play_queue_id.to_string()),
-                ("playQueueVersion", &play_queue_version.to_string()),
-                ("playQueueItemID", &play_queue_item_id.to_string()),
-                ("state", state),
-                ("time", &position_ms.to_string()),
-                ("duration", &duration_ms.to_string()),
-                ("identifier", "com.plexapp.plugins.library"),
-            ])
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            warn!("PlayQueue timeline update failed: {} - {}", status, text);
-
-            // Fall back to regular timeline update
-            return self
-                .update_progress_with_state(media_id, position, duration, state)
-                .await;
-        }
-
-        debug!("PlayQueue timeline update successful");
-        Ok(())
-    }
 }
@@ -3,30 +3,19 @@ use std::time::Duration;
 use tracing::debug;
 
 use super::client::PlexApi;
+use crate::backends::request_log::RecordedSend;
+use crate::models::{SessionEvent, SessionReport};
 
 impl PlexApi {
-    /// Update playback progress
-    /// Note: state should be "playing" for active playback or "paused" when paused
+    /// Save the resume position of an item, marking it watched past 90%.
+    /// This goes to `/:/progress`, which keeps no session, so the now playing
+    /// state is left to [`report_timeline`](Self::report_timeline).
     pub async fn update_progress(
         &self,
         media_id: &str,
         position: Duration,
         duration: Duration,
     ) -> Result<()> {
-        self.update_progress_with_state(media_id, position, duration, "playing")
-            .await
-    }
-
-    /// Update playback progress with explicit state
-    pub async fn update_progress_with_state(
-        &self,
-        media_id: &str,
-        position: Duration,
-        duration: Duration,
-        state: &str,
-    ) -> Result<()> {
-        // For simple progress tracking without a playQueue, we can update the viewOffset directly
-        // by "scrobbling" with the current position
         let position_ms = position.as_millis() as u64;
 
         // If we're more than 90% through, mark as watched
@@ -36,42 +25,73 @@ impl PlexApi {
             return self.mark_watched(media_id).await;
         }
 
-        // Otherwise update the viewOffset using timeline endpoint with proper headers
-        // The timeline endpoint is more reliable for position updates
-        let timeline_url = self.build_url("/:/timeline");
-
         debug!(
-            "Updating progress via timeline - media_id: {}, position: {}ms",
+            "Updating progress - media_id: {}, position: {}ms",
             media_id, position_ms
         );
 
         let response = self
             .client
-            .post(&timeline_url)
+            .get(self.build_url("/:/progress"))
             .headers(self.standard_headers())
             .query(&[
-                ("ratingKey", media_id),
-                ("key", &format!("/library/metadata/{}", media_id)),
+                ("key", media_id),
                 ("identifier", "com.plexapp.plugins.library"),
-                ("state", state),
                 ("time", &position_ms.to_string()),
-                ("duration", &duration_ms.to_string()),
+                ("state", "stopped"),
             ])
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            debug!("Timeline update response: {} - {}", status, text);
-            // Timeline endpoint often returns 200 with empty response, which is OK
-            if status != 200 {
-                return Err(anyhow!("Failed to update progress: {}", status));
-            }
-        } else {
-            debug!("Timeline update successful for media_id: {}", media_id);
+            debug!("Progress update failed: {} - {}", status, text);
+            return Err(anyhow!("Failed to update progress: {}", status));
         }
 
+        debug!("Progress update successful for media_id: {}", media_id);
+        Ok(())
+    }
+
+    /// Report a playback session to the timeline, which lists it as now
+    /// playing and, for items playing in a PlayQueue, moves the queue along
+    pub async fn report_timeline(&self, rating_key: &str, report: &SessionReport) -> Result<()> {
+        let state = match (report.event, report.paused) {
+            (SessionEvent::Stopped, _) => "stopped",
+            (_, true) => "paused",
+            (_, false) => "playing",
+        };
+        let mut query = vec![
+            ("ratingKey", rating_key.to_string()),
+            ("key", format!("/library/metadata/{}", rating_key)),
+            ("identifier", "com.plexapp.plugins.library".to_string()),
+            ("state", state.to_string()),
+            ("time", report.position.as_millis().to_string()),
+            ("duration", report.duration.as_millis().to_string()),
+        ];
+        if let Some(queue) = &report.play_queue {
+            query.push(("playQueueID", queue.play_queue_id.to_string()));
+            query.push(("playQueueVersion", queue.play_queue_version.to_string()));
+            query.push(("playQueueItemID", queue.play_queue_item_id.to_string()));
+        }
+
+        let response = self
+            .client
+            .post(self.build_url("/:/timeline"))
+            .headers(self.standard_headers())
+            .header("X-Plex-Session-Identifier", &report.play_session_id)
+            .query(&query)
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Failed to report {} to the timeline: {}",
+                state,
+                response.status()
+            ));
+        }
         Ok(())
     }
 
@@ -89,7 +109,7 @@ impl PlexApi {
                 ("identifier", "com.plexapp.plugins.library"),
                 ("key", media_id),
             ])
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -117,7 +137,7 @@ impl PlexApi {
                 ("identifier", "com.plexapp.plugins.library"),
                 ("key", media_id),
             ])
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
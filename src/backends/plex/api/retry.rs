@@ -3,6 +3,7 @@ use tokio::time::sleep;
 use tracing::{debug, warn};
 
 use super::errors::PlexApiError;
+use crate::services::core::network_policy::NetworkPolicy;
 
 /// Configuration for retry behavior with exponential backoff
 #[derive(Debug, Clone)]
@@ -19,11 +20,17 @@ pub struct RetryPolicy {
 
 impl Default for RetryPolicy {
     fn default() -> Self {
+        Self::from(&NetworkPolicy::default())
+    }
+}
+
+impl From<&NetworkPolicy> for RetryPolicy {
+    fn from(policy: &NetworkPolicy) -> Self {
         Self {
-            max_attempts: 3,                        // Total of 4 attempts (1 initial + 3 retries)
-            base_delay_ms: 100,                     // Start with 100ms delay
-            max_delay_ms: 10_000,                   // Cap at 10 seconds
-            total_timeout: Duration::from_secs(30), // Total timeout of 30 seconds
+            max_attempts: policy.max_retries,
+            base_delay_ms: policy.retry_base_delay.as_millis() as u64,
+            max_delay_ms: policy.retry_max_delay.as_millis() as u64,
+            total_timeout: policy.request_timeout,
         }
     }
 }
@@ -35,7 +42,7 @@ impl RetryPolicy {
             max_attempts,
             base_delay_ms,
             max_delay_ms,
-            total_timeout: Duration::from_secs(30),
+            total_timeout: NetworkPolicy::default().request_timeout,
         }
     }
 
@@ -5,8 +5,9 @@ use std::collections::HashMap;
 use tracing::debug;
 use url::Url;
 
-use super::client::create_standard_headers;
+use super::client::create_source_headers;
 use super::types::PlexGenericMetadata;
+use crate::backends::request_log::RecordedSend;
 use crate::models::{Episode, MediaItem, Movie, Show};
 
 // Custom search response container that uses generic metadata
@@ -80,14 +81,16 @@ pub struct SearchResultItem {
 pub struct PlexSearch {
     base_url: String,
     auth_token: String,
+    backend_id: String,
     client: Client,
 }
 
 impl PlexSearch {
-    pub fn new(base_url: String, auth_token: String, client: Client) -> Self {
+    pub fn new(base_url: String, auth_token: String, backend_id: String, client: Client) -> Self {
         Self {
             base_url,
             auth_token,
+            backend_id,
             client,
         }
     }
@@ -107,8 +110,11 @@ impl PlexSearch {
         let response = self
             .client
             .get(url.as_str())
-            .headers(create_standard_headers(Some(&self.auth_token)))
-            .send()
+            .headers(create_source_headers(
+                Some(&self.auth_token),
+                &self.backend_id,
+            ))
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -200,8 +206,11 @@ impl PlexSearch {
         let response = self
             .client
             .get(url.as_str())
-            .headers(create_standard_headers(Some(&self.auth_token)))
-            .send()
+            .headers(create_source_headers(
+                Some(&self.auth_token),
+                &self.backend_id,
+            ))
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -270,8 +279,11 @@ impl PlexSearch {
         let response = self
             .client
             .get(url.as_str())
-            .headers(create_standard_headers(Some(&self.auth_token)))
-            .send()
+            .headers(create_source_headers(
+                Some(&self.auth_token),
+                &self.backend_id,
+            ))
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
@@ -345,6 +357,15 @@ impl PlexSearch {
             playback_position: None,
             intro_marker: None,
             credits_marker: None,
+            video_height: None,
+            hdr_format: None,
+            atmos: false,
+            versions: Vec::new(),
+            external_ids: Vec::new(),
+            studios: Vec::new(),
+            tags: Vec::new(),
+            collections: Vec::new(),
+            content_rating: item.content_rating,
         })
     }
 
@@ -370,6 +391,11 @@ impl PlexSearch {
             watched_episode_count: 0,
             total_episode_count: 0,
             last_watched_at: None,
+            external_ids: Vec::new(),
+            studios: Vec::new(),
+            tags: Vec::new(),
+            collections: Vec::new(),
+            content_rating: item.content_rating,
         })
     }
 
@@ -397,6 +423,7 @@ impl PlexSearch {
             show_poster_url: None,
             intro_marker: None,
             credits_marker: None,
+            video_height: None,
         })
     }
 
@@ -436,6 +463,15 @@ impl PlexSearch {
             playback_position: item.view_offset.map(|o| Duration::from_millis(o as u64)),
             intro_marker: None,
             credits_marker: None,
+            video_height: None,
+            hdr_format: None,
+            atmos: false,
+            versions: Vec::new(),
+            external_ids: Vec::new(),
+            studios: Vec::new(),
+            tags: Vec::new(),
+            collections: Vec::new(),
+            content_rating: item.content_rating,
         })
     }
 
@@ -467,6 +503,11 @@ impl PlexSearch {
             last_watched_at: item
                 .last_viewed_at
                 .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)),
+            external_ids: Vec::new(),
+            studios: Vec::new(),
+            tags: Vec::new(),
+            collections: Vec::new(),
+            content_rating: item.content_rating,
         })
     }
 
@@ -498,6 +539,7 @@ impl PlexSearch {
             show_poster_url: None,
             intro_marker: None,
             credits_marker: None,
+            video_height: None,
         })
     }
 }
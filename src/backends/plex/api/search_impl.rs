@@ -11,6 +11,7 @@ impl PlexApi {
         let search_client = PlexSearch::new(
             self.base_url.clone(),
             self.auth_token.clone(),
+            self.backend_id.clone(),
             self.client.clone(),
         );
 
@@ -29,6 +30,7 @@ impl PlexApi {
         let search_client = PlexSearch::new(
             self.base_url.clone(),
             self.auth_token.clone(),
+            self.backend_id.clone(),
             self.client.clone(),
         );
 
@@ -46,6 +48,7 @@ impl PlexApi {
         let search_client = PlexSearch::new(
             self.base_url.clone(),
             self.auth_token.clone(),
+            self.backend_id.clone(),
             self.client.clone(),
         );
 
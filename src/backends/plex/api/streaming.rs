@@ -2,22 +2,28 @@ use anyhow::{Result, anyhow};
 
 use super::client::PlexApi;
 use super::types::*;
-use crate::models::{QualityOption, Resolution, StreamInfo};
+use crate::backends::request_log::RecordedSend;
+use crate::db::entities::QualityPreset;
+use crate::models::{
+    DownloadInfo, PlaybackError, QualityOption, Resolution, StreamInfo, StreamMode,
+};
 
 impl PlexApi {
-    pub async fn get_stream_url(&self, media_id: &str) -> Result<StreamInfo> {
-        // For Plex, we can usually direct play
-        // This is a simplified version - real implementation would check transcoding needs
+    /// Fetch the media/part information for an item
+    async fn get_media_info(&self, media_id: &str) -> Result<PlexMediaResponse> {
         let url = self.build_url(&format!("/library/metadata/{}", media_id));
 
         let response = self
             .client
             .get(&url)
             .headers(self.standard_headers())
-            .send()
+            .send_recorded(&self.backend_id)
             .await?;
 
         if !response.status().is_success() {
+            if let Some(error) = PlaybackError::from_status(response.status()) {
+                return Err(error.into());
+            }
             return Err(anyhow!("Failed to get media info: {}", response.status()));
         }
 
@@ -25,12 +31,17 @@ impl PlexApi {
         let response_text = response.text().await?;
 
         // Try to parse the response and provide better error context
-        let plex_response: PlexMediaResponse =
-            serde_json::from_str(&response_text).map_err(|e| {
-                tracing::error!("Failed to decode Plex response: {}", e);
-                tracing::error!("Response was: {}", response_text);
-                anyhow!("Failed to decode Plex stream response: {}", e)
-            })?;
+        serde_json::from_str(&response_text).map_err(|e| {
+            tracing::error!("Failed to decode Plex response: {}", e);
+            tracing::error!("Response was: {}", response_text);
+            anyhow!("Failed to decode Plex stream response: {}", e)
+        })
+    }
+
+    pub async fn get_stream_url(&self, media_id: &str) -> Result<StreamInfo> {
+        // For Plex, we can usually direct play
+        // This is a simplified version - real implementation would check transcoding needs
+        let plex_response = self.get_media_info(media_id).await?;
 
         if let Some(metadata) = plex_response.media_container.metadata.first()
             && let Some(media) = metadata.media.first()
@@ -85,7 +96,7 @@ impl PlexApi {
                 if height < original_height {
                     let path = format!("/library/metadata/{}", media_id);
                     let transcode_url = format!(
-                        "{}/video/:/transcode/universal/start.m3u8?path={}&mediaIndex=0&partIndex=0&protocol=hls&directPlay=0&directStream=0&fastSeek=1&maxVideoBitrate={}&videoResolution={}x{}&X-Plex-Token={}",
+                        "{}/video/:/transcode/universal/start.m3u8?path={}&mediaIndex=0&partIndex=0&protocol=hls&directPlay=0&directStream=0&fastSeek=1&maxVideoBitrate={}&videoResolution={}x{}&session={}&X-Plex-Token={}",
                         self.base_url,
                         percent_encoding::utf8_percent_encode(
                             &path,
@@ -94,6 +105,7 @@ impl PlexApi {
                         bitrate / 1000, // Convert to kbps
                         width,
                         height,
+                        uuid::Uuid::new_v4(),
                         self.auth_token
                     );
 
@@ -110,6 +122,7 @@ impl PlexApi {
             return Ok(StreamInfo {
                 url: stream_url,
                 direct_play: true,
+                mode: StreamMode::DirectPlay,
                 video_codec: media.video_codec.clone().unwrap_or_default(),
                 audio_codec: media.audio_codec.clone().unwrap_or_default(),
                 container: part.container.clone().unwrap_or_default(),
@@ -140,4 +153,95 @@ impl PlexApi {
             plex_response.media_container.metadata.len()
         ))
     }
+
+    /// Keep a transcode from being stopped by the server, which it does to
+    /// transcodes nobody fetched segments of for a while, like when paused
+    pub async fn ping_transcode(&self, session_id: &str) -> Result<()> {
+        self.transcode_request("ping", session_id).await
+    }
+
+    /// Stop a transcode playback moved away from
+    pub async fn stop_transcode(&self, session_id: &str) -> Result<()> {
+        self.transcode_request("stop", session_id).await
+    }
+
+    async fn transcode_request(&self, action: &str, session_id: &str) -> Result<()> {
+        let url = self.build_url(&format!("/video/:/transcode/universal/{}", action));
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("session", session_id)])
+            .headers(self.standard_headers())
+            .send_recorded(&self.backend_id)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Failed to {} transcode {}: {}",
+                action,
+                session_id,
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Build a download for offline use.
+    ///
+    /// The original file is fetched from the part's download endpoint. Lower presets
+    /// use the universal transcoder in progressive (HTTP) mode so the result is a
+    /// single file rather than an HLS playlist.
+    pub async fn get_download_url(
+        &self,
+        media_id: &str,
+        quality: &QualityPreset,
+    ) -> Result<DownloadInfo> {
+        let plex_response = self.get_media_info(media_id).await?;
+
+        let (media, part) = plex_response
+            .media_container
+            .metadata
+            .first()
+            .and_then(|metadata| metadata.media.first())
+            .and_then(|media| {
+                media
+                    .parts
+                    .as_ref()
+                    .and_then(|parts| parts.first())
+                    .map(|part| (media, part))
+            })
+            .ok_or_else(|| anyhow!("No downloadable file found for media {}", media_id))?;
+
+        if !quality.requires_transcode(media.height.unwrap_or(0)) {
+            let separator = if part.key.contains('?') { "&" } else { "?" };
+            return Ok(DownloadInfo {
+                url: format!(
+                    "{}{}{}download=1&X-Plex-Token={}",
+                    self.base_url, part.key, separator, self.auth_token
+                ),
+                container: part.container.clone().unwrap_or_else(|| "mkv".to_string()),
+                requires_transcode: false,
+            });
+        }
+
+        let (width, height) = quality.get_max_resolution();
+        let path = format!("/library/metadata/{}", media_id);
+        let url = format!(
+            "{}/video/:/transcode/universal/start.mkv?path={}&mediaIndex=0&partIndex=0&protocol=http&directPlay=0&directStream=0&videoQuality=100&maxVideoBitrate={}&videoResolution={}x{}&session={}&X-Plex-Token={}",
+            self.base_url,
+            percent_encoding::utf8_percent_encode(&path, percent_encoding::NON_ALPHANUMERIC),
+            quality.max_video_bitrate_kbps(),
+            width,
+            height,
+            uuid::Uuid::new_v4(),
+            self.auth_token
+        );
+
+        Ok(DownloadInfo {
+            url,
+            container: "mkv".to_string(),
+            requires_transcode: true,
+        })
+    }
 }
@@ -1,4 +1,29 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+
+use crate::models::MediaVersion;
+
+/// Plex sends flags as "1", 1 or true depending on the endpoint
+fn flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::Bool(flag)) => flag,
+            Some(serde_json::Value::Number(flag)) => flag.as_i64() == Some(1),
+            Some(serde_json::Value::String(flag)) => flag == "1" || flag == "true",
+            _ => false,
+        },
+    )
+}
+
+/// Plex sends ids as numbers, keep them as strings
+fn id_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::String(id)) => Some(id),
+            Some(serde_json::Value::Number(id)) => Some(id.to_string()),
+            _ => None,
+        },
+    )
+}
 
 // Plex Identity response for getting server machine ID
 #[allow(dead_code)]
@@ -34,6 +59,8 @@ pub struct PlexMetadataContainer {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlexMetadataWithMarkers {
+    #[serde(default)]
+    pub rating_key: String,
     #[serde(rename = "Marker", default)]
     pub marker: Option<Vec<PlexMarker>>,
 }
@@ -95,6 +122,8 @@ pub struct PlexMovieMetadata {
     #[serde(default)]
     pub rating: Option<f64>,
     #[serde(default)]
+    pub content_rating: Option<String>,
+    #[serde(default)]
     pub thumb: Option<String>,
     #[serde(default)]
     pub art: Option<String>,
@@ -118,6 +147,16 @@ pub struct PlexMovieMetadata {
     pub directors: Vec<PlexDirector>,
     #[serde(rename = "Writer", default)]
     pub writers: Vec<PlexWriter>,
+    #[serde(rename = "Media", default)]
+    pub media: Vec<PlexMedia>,
+    #[serde(rename = "Guid", default)]
+    pub guids: Vec<PlexGuid>,
+    #[serde(default)]
+    pub studio: Option<String>,
+    #[serde(rename = "Label", default)]
+    pub labels: Vec<PlexTag>,
+    #[serde(rename = "Collection", default)]
+    pub collections: Vec<PlexTag>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -126,6 +165,12 @@ pub struct PlexTag {
     pub tag: String,
 }
 
+/// Id of an item in an outside database, like "imdb://tt0111161"
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlexGuid {
+    pub id: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlexRole {
@@ -183,6 +228,8 @@ pub struct PlexShowMetadata {
     #[serde(default)]
     pub rating: Option<f64>,
     #[serde(default)]
+    pub content_rating: Option<String>,
+    #[serde(default)]
     pub thumb: Option<String>,
     #[serde(default)]
     pub art: Option<String>,
@@ -202,6 +249,15 @@ pub struct PlexShowMetadata {
     pub leaf_count: Option<i32>,
     #[serde(default)]
     pub viewed_leaf_count: Option<i32>,
+    #[serde(rename = "Guid", default)]
+    pub guids: Vec<PlexGuid>,
+    /// The network for shows
+    #[serde(default)]
+    pub studio: Option<String>,
+    #[serde(rename = "Label", default)]
+    pub labels: Vec<PlexTag>,
+    #[serde(rename = "Collection", default)]
+    pub collections: Vec<PlexTag>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -289,6 +345,8 @@ pub struct PlexMediaMetadata {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlexMedia {
+    #[serde(default, deserialize_with = "id_string")]
+    pub id: Option<String>,
     #[serde(default)]
     pub bitrate: Option<u64>,
     #[serde(default)]
@@ -309,6 +367,73 @@ pub struct PlexPart {
     pub key: String,
     #[serde(default)]
     pub container: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// Only included in the full metadata of an item
+    #[serde(rename = "Stream", default)]
+    pub streams: Vec<PlexStream>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlexStream {
+    /// 1 for video, 2 for audio and 3 for subtitles
+    pub stream_type: i32,
+    #[serde(rename = "DOVIPresent", default)]
+    pub dovi_present: bool,
+    #[serde(default)]
+    pub color_trc: Option<String>,
+    #[serde(default)]
+    pub display_title: Option<String>,
+}
+
+impl PlexMedia {
+    fn streams(&self) -> impl Iterator<Item = &PlexStream> {
+        self.parts.iter().flatten().flat_map(|part| &part.streams)
+    }
+
+    /// The file this media is, for media with an id
+    pub fn version(&self) -> Option<MediaVersion> {
+        let parts = self.parts.as_deref().unwrap_or_default();
+        Some(MediaVersion {
+            id: self.id.clone()?,
+            video_height: self.height,
+            container: parts.iter().find_map(|part| part.container.clone()),
+            size: parts
+                .iter()
+                .filter_map(|part| part.size)
+                .reduce(|a, b| a + b),
+        })
+    }
+
+    /// HDR format of the video stream, if it isn't SDR
+    pub fn hdr_format(&self) -> Option<String> {
+        self.streams()
+            .filter(|stream| stream.stream_type == 1)
+            .find_map(|stream| {
+                let format = if stream.dovi_present {
+                    "Dolby Vision"
+                } else {
+                    match stream.color_trc.as_deref() {
+                        Some("smpte2084") => "HDR10",
+                        Some("arib-std-b67") => "HLG",
+                        _ => return None,
+                    }
+                };
+                Some(format.to_string())
+            })
+    }
+
+    /// Whether one of the audio streams is Dolby Atmos
+    pub fn has_atmos(&self) -> bool {
+        self.streams().any(|stream| {
+            stream.stream_type == 2
+                && stream
+                    .display_title
+                    .as_deref()
+                    .is_some_and(|title| title.contains("Atmos"))
+        })
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -327,6 +452,8 @@ pub struct PlexGenericMetadata {
     #[serde(default)]
     pub rating: Option<f64>,
     #[serde(default)]
+    pub content_rating: Option<String>,
+    #[serde(default)]
     pub summary: Option<String>,
     #[serde(default)]
     pub duration: Option<i64>,
@@ -407,3 +534,102 @@ pub struct PlexHub {
     #[serde(rename = "Metadata", default)]
     pub metadata: Vec<PlexGenericMetadata>,
 }
+
+// Items the user rated, used to find liked items
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlexRatedResponse {
+    pub media_container: PlexRatedContainer,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlexRatedContainer {
+    #[serde(rename = "Metadata", default)]
+    pub metadata: Vec<PlexRatedMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlexRatedMetadata {
+    pub rating_key: String,
+}
+
+// Labels of a movie or show
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlexLabelsResponse {
+    pub media_container: PlexLabelsContainer,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlexLabelsContainer {
+    #[serde(rename = "Metadata", default)]
+    pub metadata: Vec<PlexLabelsMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlexLabelsMetadata {
+    #[serde(rename = "Label", default)]
+    pub labels: Vec<PlexTag>,
+}
+
+// Collections of a library
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlexCollectionsResponse {
+    pub media_container: PlexCollectionsContainer,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlexCollectionsContainer {
+    #[serde(rename = "Metadata", default)]
+    pub metadata: Vec<PlexCollectionMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlexCollectionMetadata {
+    pub rating_key: String,
+    pub title: String,
+    /// Filled by a saved filter rather than by hand
+    #[serde(default, deserialize_with = "flag")]
+    pub smart: bool,
+}
+
+// Theme music of a movie or show
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlexThemeResponse {
+    pub media_container: PlexThemeContainer,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlexThemeContainer {
+    #[serde(rename = "Metadata", default)]
+    pub metadata: Vec<PlexThemeMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlexThemeMetadata {
+    #[serde(default)]
+    pub theme: Option<String>,
+}
+
+// Server settings, of which only the deletion setting is read
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlexServerResponse {
+    pub media_container: PlexServerContainer,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlexServerContainer {
+    #[serde(default)]
+    pub allow_media_deletion: bool,
+}
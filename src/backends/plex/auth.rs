@@ -5,7 +5,7 @@ use std::time::Duration;
 use tokio::sync::oneshot;
 use tracing::{debug, info};
 
-use super::api::create_standard_headers;
+use super::api::{PLEX_CLIENT_IDENTIFIER, PLEX_PRODUCT, create_standard_headers};
 
 const PLEX_TV_URL: &str = "https://plex.tv";
 
@@ -29,11 +29,22 @@ pub struct PlexAuth;
 impl PlexAuth {
     /// Request a new PIN from Plex for authentication
     pub async fn get_pin() -> Result<PlexPin> {
+        Self::request_pin(false).await
+    }
+
+    /// Request a PIN for signing in through a link, which Plex only accepts
+    /// for strong PINs
+    pub async fn get_link_pin() -> Result<PlexPin> {
+        Self::request_pin(true).await
+    }
+
+    async fn request_pin(strong: bool) -> Result<PlexPin> {
         let client = reqwest::Client::new();
 
         let response = client
             .post(format!("{}/api/v2/pins", PLEX_TV_URL))
             .headers(create_standard_headers(None))
+            .query(&[("strong", strong)])
             .send()
             .await?;
 
@@ -54,6 +65,40 @@ impl PlexAuth {
         })
     }
 
+    /// Link that signs a PIN in from any browser, including one on another
+    /// device or network
+    pub fn sign_in_link(pin: &PlexPin) -> String {
+        let mut link = url::Url::parse("https://app.plex.tv/auth").expect("valid URL");
+        let fragment = url::form_urlencoded::Serializer::new(String::from("?"))
+            .append_pair("clientID", PLEX_CLIENT_IDENTIFIER)
+            .append_pair("code", &pin.code)
+            .append_pair("context[device][product]", PLEX_PRODUCT)
+            .finish();
+        link.set_fragment(Some(&fragment));
+        link.to_string()
+    }
+
+    /// Check once whether a PIN has been signed in, returning its token if so
+    pub async fn check_pin(pin_id: &str) -> Result<Option<String>> {
+        let client = reqwest::Client::new();
+
+        let response = client
+            .get(format!("{}/api/v2/pins/{}", PLEX_TV_URL, pin_id))
+            .headers(create_standard_headers(None))
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(anyhow!("The sign-in link has expired"));
+        }
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to check PIN: {}", response.status()));
+        }
+
+        let pin_response: PlexPinResponse = response.json().await?;
+        Ok(pin_response.auth_token.filter(|token| !token.is_empty()))
+    }
+
     /// Poll for the auth token after user has entered the PIN with cancellation support
     pub async fn poll_for_token(
         pin_id: &str,
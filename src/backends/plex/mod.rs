@@ -2,13 +2,13 @@ pub mod api;
 mod auth;
 mod tests;
 
-pub use api::{PlexApi, create_standard_headers};
+pub use api::{PlexApi, create_source_headers, create_standard_headers};
 pub use auth::{PlexAuth, PlexConnection, PlexHomeUser, PlexPin, PlexServer};
 
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use dirs;
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -16,10 +16,14 @@ use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, warn};
 
 use super::traits::MediaBackend;
+use crate::db::entities::QualityPreset;
 use crate::models::{
-    AuthProvider, AuthenticationResult, Credentials, Episode, Library, LibraryId, MediaItemId,
-    Movie, Season, Show, ShowId, Source, SourceId, SourceType, StreamInfo, User,
+    AuthProvider, AuthenticationResult, ChapterMarker, ConnectionPreferences, Credentials,
+    DownloadInfo, Episode, Library, LibraryId, LibraryType, MediaItemId, Movie, Season,
+    SessionReport, Show, ShowId, Source, SourceId, SourceType, StreamInfo, TranscodeSession, User,
 };
+use crate::services::core::auth::SecureStorage;
+use crate::services::core::network_policy::network_policy;
 
 #[allow(dead_code)] // Used via dynamic dispatch in BackendService
 pub struct PlexBackend {
@@ -146,8 +150,8 @@ impl PlexBackend {
         if let Some(base_url) = self.base_url.read().await.as_ref()
             && let Some(token) = self.auth_token.read().await.as_ref()
         {
-            let client = reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(2))
+            let client = network_policy()
+                .probe_client_builder()
                 .danger_accept_invalid_certs(true)
                 .build();
 
@@ -156,7 +160,7 @@ impl PlexBackend {
                 let url_with_token = format!("{}/identity?X-Plex-Token={}", base_url, token);
                 let response = client
                     .get(&url_with_token)
-                    .headers(create_standard_headers(Some(token)))
+                    .headers(create_source_headers(Some(token), &self.backend_id))
                     .send()
                     .await;
 
@@ -166,219 +170,271 @@ impl PlexBackend {
         false
     }
 
-    /// Save the authentication token to keyring with file fallback
-
-    /// Test all connections in parallel and return the fastest responding one
-    async fn find_best_connection(
-        &self,
-        server: &PlexServer,
-        token: &str,
-    ) -> Result<PlexConnection> {
-        use futures::future::select_ok;
-        use std::time::Instant;
-
-        if server.connections.is_empty() {
-            return Err(anyhow!("No connections available for server"));
-        }
+    /// Test a connection's identity endpoint, returning how long it took to answer
+    async fn probe_connection(
+        conn: PlexConnection,
+        token: String,
+        backend_id: String,
+    ) -> Result<(PlexConnection, Duration)> {
+        let start = Instant::now();
+        let uri = conn.uri.clone();
+        let client = network_policy()
+            .probe_client_builder()
+            .danger_accept_invalid_certs(true) // Plex uses self-signed certs
+            .build()?;
 
-        // Create futures for testing each connection
-        let mut connection_futures = Vec::new();
+        let headers = create_source_headers(Some(&token), &backend_id);
+        let conn_type = if conn.local {
+            "local"
+        } else if conn.relay {
+            "relay"
+        } else {
+            "remote"
+        };
 
-        for conn in &server.connections {
-            let uri = conn.uri.clone();
-            let token = token.to_string();
-            let conn_clone = conn.clone();
+        // Log connection attempt with details
+        tracing::info!(
+            "Testing {} connection: {} (protocol: {}, local: {}, relay: {})",
+            conn_type,
+            uri,
+            conn.protocol,
+            conn.local,
+            conn.relay
+        );
 
-            let future = async move {
-                let start = Instant::now();
-                let client = reqwest::Client::builder()
-                    .timeout(Duration::from_secs(2))
-                    .danger_accept_invalid_certs(true) // Plex uses self-signed certs
-                    .build()?;
+        // Log token presence and first few chars for verification
+        let has_token = headers.get("X-Plex-Token").is_some();
+        let token_preview = if !token.is_empty() {
+            format!("{}...", &token[..8.min(token.len())])
+        } else {
+            "EMPTY".to_string()
+        };
+        tracing::debug!(
+            "Auth token for {} connection {}: {} (token starts with: {})",
+            conn_type,
+            uri,
+            if has_token { "YES" } else { "NO" },
+            token_preview
+        );
 
-                let headers = create_standard_headers(Some(&token));
-                let conn_type = if conn_clone.local {
-                    "local"
-                } else if conn_clone.relay {
-                    "relay"
-                } else {
-                    "remote"
-                };
+        // Try to access the server identity endpoint
+        // Send token both in headers AND as URL parameter for maximum compatibility
+        let url_with_token = format!("{}/identity?X-Plex-Token={}", uri, token);
+        let response = client.get(&url_with_token).headers(headers).send().await;
 
-                // Log connection attempt with details
+        match response {
+            Ok(resp) if resp.status().is_success() => {
+                let latency = start.elapsed();
                 tracing::info!(
-                    "Testing {} connection: {} (protocol: {}, local: {}, relay: {})",
+                    "✓ {} connection {} succeeded in {:?}",
                     conn_type,
                     uri,
-                    conn_clone.protocol,
-                    conn_clone.local,
-                    conn_clone.relay
+                    latency
                 );
-
-                // Log token presence and first few chars for verification
-                let has_token = headers.get("X-Plex-Token").is_some();
-                let token_preview = if !token.is_empty() {
-                    format!("{}...", &token[..8.min(token.len())])
-                } else {
-                    "EMPTY".to_string()
-                };
-                tracing::debug!(
-                    "Auth token for {} connection {}: {} (token starts with: {})",
+                Ok((conn, latency))
+            }
+            Ok(resp) => {
+                let status = resp.status();
+                tracing::warn!(
+                    "✗ {} connection {} returned status: {} ({})",
                     conn_type,
                     uri,
-                    if has_token { "YES" } else { "NO" },
-                    token_preview
+                    status.as_u16(),
+                    status.canonical_reason().unwrap_or("Unknown")
                 );
-
-                // Try to access the server identity endpoint
-                // Send token both in headers AND as URL parameter for maximum compatibility
-                let url_with_token = format!("{}/identity?X-Plex-Token={}", uri, token);
-                let response = client.get(&url_with_token).headers(headers).send().await;
-
-                match response {
-                    Ok(resp) if resp.status().is_success() => {
-                        let latency = start.elapsed();
-                        tracing::info!(
-                            "✓ {} connection {} succeeded in {:?}",
-                            conn_type,
-                            uri,
-                            latency
-                        );
-                        Ok((conn_clone, latency))
-                    }
-                    Ok(resp) => {
-                        let status = resp.status();
-                        tracing::warn!(
-                            "✗ {} connection {} returned status: {} ({})",
-                            conn_type,
-                            uri,
-                            status.as_u16(),
-                            status.canonical_reason().unwrap_or("Unknown")
-                        );
-                        if status.as_u16() == 401 {
-                            tracing::error!(
-                                "401 Unauthorized on {} connection {}. This suggests auth token issue.",
-                                conn_type,
-                                uri
-                            );
-                        }
-                        Err(anyhow!("Connection failed with status: {}", status))
-                    }
-                    Err(e) => {
-                        tracing::warn!("✗ {} connection {} failed: {}", conn_type, uri, e);
-                        Err(anyhow!("Connection failed: {}", e))
-                    }
+                if status.as_u16() == 401 {
+                    tracing::error!(
+                        "401 Unauthorized on {} connection {}. This suggests auth token issue.",
+                        conn_type,
+                        uri
+                    );
                 }
-            };
-
-            connection_futures.push(Box::pin(future));
+                Err(anyhow!("Connection failed with status: {}", status))
+            }
+            Err(e) => {
+                tracing::warn!("✗ {} connection {} failed: {}", conn_type, uri, e);
+                Err(anyhow!("Connection failed: {}", e))
+            }
         }
+    }
+
+    /// Race connections the source's preferences allow, one preference tier
+    /// at a time, so a preferred connection wins even when another answers
+    /// faster
+    async fn race_connections(
+        &self,
+        connections: &[PlexConnection],
+        token: &str,
+    ) -> Option<PlexConnection> {
+        use futures::future::select_ok;
+
+        let preferences = self.connection_preferences();
+        let mut tiers: Vec<u8> = connections
+            .iter()
+            .filter(|conn| preferences.allows(conn.relay))
+            .map(|conn| preferences.tier(&conn.uri, conn.local))
+            .collect();
+        tiers.sort_unstable();
+        tiers.dedup();
+
+        for tier in tiers {
+            let connection_futures: Vec<_> = connections
+                .iter()
+                .filter(|conn| {
+                    preferences.allows(conn.relay)
+                        && preferences.tier(&conn.uri, conn.local) == tier
+                })
+                .map(|conn| {
+                    Box::pin(Self::probe_connection(
+                        conn.clone(),
+                        token.to_string(),
+                        self.backend_id.clone(),
+                    ))
+                })
+                .collect();
 
-        // Race all connections and pick the first successful one
-        match select_ok(connection_futures).await {
-            Ok((result, _remaining)) => {
+            if let Ok(((conn, latency), _remaining)) = select_ok(connection_futures).await {
                 tracing::info!(
                     "Best connection found: {} (latency: {:?})",
-                    result.0.uri,
-                    result.1
+                    conn.uri,
+                    latency
                 );
-                Ok(result.0)
+                return Some(conn);
             }
-            Err(_) => {
-                // If all parallel attempts fail, try them sequentially with more time
-                tracing::warn!("All parallel connection attempts failed, trying sequentially...");
-
-                // Sort connections by priority: local non-relay first, then remote non-relay, then relay
-                let mut sorted_connections = server.connections.clone();
-                sorted_connections.sort_by_key(|c| {
-                    if c.local && !c.relay {
-                        0
-                    } else if !c.relay {
-                        1
-                    } else {
-                        2
-                    }
-                });
-
-                for conn in sorted_connections {
-                    let client = reqwest::Client::builder()
-                        .timeout(Duration::from_secs(5))
-                        .danger_accept_invalid_certs(true)
-                        .build()?;
-
-                    let headers = create_standard_headers(Some(token));
-                    let conn_type = if conn.local {
-                        "local"
-                    } else if conn.relay {
-                        "relay"
-                    } else {
-                        "remote"
-                    };
+        }
+
+        None
+    }
+
+    /// Which connections the source may use, and in what order
+    fn connection_preferences(&self) -> ConnectionPreferences {
+        self.source
+            .as_ref()
+            .map(|source| source.connection_preferences.clone())
+            .unwrap_or_default()
+    }
+
+    /// Test all connections in parallel and return the fastest responding one,
+    /// within the limits of the source's connection preferences
+    async fn find_best_connection(
+        &self,
+        server: &PlexServer,
+        token: &str,
+    ) -> Result<PlexConnection> {
+        if server.connections.is_empty() {
+            return Err(anyhow!("No connections available for server"));
+        }
+
+        let preferences = self.connection_preferences();
+        let candidates: Vec<PlexConnection> = server
+            .connections
+            .iter()
+            .filter(|conn| preferences.allows(conn.relay))
+            .cloned()
+            .collect();
+        if candidates.is_empty() {
+            return Err(anyhow!(
+                "Server is only reachable through a relay, which this source doesn't allow"
+            ));
+        }
 
+        if let Some(conn) = self.race_connections(&candidates, token).await {
+            return Ok(conn);
+        }
+
+        // If all parallel attempts fail, try them sequentially with more time
+        tracing::warn!("All parallel connection attempts failed, trying sequentially...");
+
+        // Sort connections by preference, then local non-relay first, then remote non-relay, then relay
+        let mut sorted_connections = candidates;
+        sorted_connections.sort_by_key(|c| {
+            let kind = if c.local && !c.relay {
+                0
+            } else if !c.relay {
+                1
+            } else {
+                2
+            };
+            (preferences.tier(&c.uri, c.local), kind)
+        });
+
+        for conn in sorted_connections {
+            let client = network_policy()
+                .probe_client_builder()
+                .danger_accept_invalid_certs(true)
+                .build()?;
+
+            let headers = create_source_headers(Some(token), &self.backend_id);
+            let conn_type = if conn.local {
+                "local"
+            } else if conn.relay {
+                "relay"
+            } else {
+                "remote"
+            };
+
+            tracing::info!(
+                "Sequential fallback: testing {} connection {}",
+                conn_type,
+                conn.uri
+            );
+
+            let has_token = headers.get("X-Plex-Token").is_some();
+            let token_preview = if !token.is_empty() {
+                format!("{}...", &token[..8.min(token.len())])
+            } else {
+                "EMPTY".to_string()
+            };
+            tracing::debug!(
+                "Auth token for {} connection {}: {} (token starts with: {})",
+                conn_type,
+                conn.uri,
+                if has_token { "YES" } else { "NO" },
+                token_preview
+            );
+
+            // Send token both in headers AND as URL parameter for maximum compatibility
+            let url_with_token = format!("{}/identity?X-Plex-Token={}", conn.uri, token);
+            let response = client.get(&url_with_token).headers(headers).send().await;
+
+            match response {
+                Ok(resp) if resp.status().is_success() => {
                     tracing::info!(
-                        "Sequential fallback: testing {} connection {}",
+                        "✓ Sequential {} connection {} succeeded",
                         conn_type,
                         conn.uri
                     );
-
-                    let has_token = headers.get("X-Plex-Token").is_some();
-                    let token_preview = if !token.is_empty() {
-                        format!("{}...", &token[..8.min(token.len())])
-                    } else {
-                        "EMPTY".to_string()
-                    };
-                    tracing::debug!(
-                        "Auth token for {} connection {}: {} (token starts with: {})",
+                    return Ok(conn);
+                }
+                Ok(resp) => {
+                    let status = resp.status();
+                    tracing::warn!(
+                        "✗ Sequential {} connection {} returned status: {} ({})",
                         conn_type,
                         conn.uri,
-                        if has_token { "YES" } else { "NO" },
-                        token_preview
+                        status.as_u16(),
+                        status.canonical_reason().unwrap_or("Unknown")
                     );
-
-                    // Send token both in headers AND as URL parameter for maximum compatibility
-                    let url_with_token = format!("{}/identity?X-Plex-Token={}", conn.uri, token);
-                    let response = client.get(&url_with_token).headers(headers).send().await;
-
-                    match response {
-                        Ok(resp) if resp.status().is_success() => {
-                            tracing::info!(
-                                "✓ Sequential {} connection {} succeeded",
-                                conn_type,
-                                conn.uri
-                            );
-                            return Ok(conn);
-                        }
-                        Ok(resp) => {
-                            let status = resp.status();
-                            tracing::warn!(
-                                "✗ Sequential {} connection {} returned status: {} ({})",
-                                conn_type,
-                                conn.uri,
-                                status.as_u16(),
-                                status.canonical_reason().unwrap_or("Unknown")
-                            );
-                            if status.as_u16() == 401 {
-                                tracing::error!(
-                                    "401 Unauthorized on {} connection {}. This suggests auth token issue.",
-                                    conn_type,
-                                    conn.uri
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            tracing::warn!(
-                                "✗ Sequential {} connection {} failed: {}",
-                                conn_type,
-                                conn.uri,
-                                e
-                            );
-                        }
+                    if status.as_u16() == 401 {
+                        tracing::error!(
+                            "401 Unauthorized on {} connection {}. This suggests auth token issue.",
+                            conn_type,
+                            conn.uri
+                        );
                     }
                 }
-
-                Err(anyhow!("Failed to connect to any server endpoint"))
+                Err(e) => {
+                    tracing::warn!(
+                        "✗ Sequential {} connection {} failed: {}",
+                        conn_type,
+                        conn.uri,
+                        e
+                    );
+                }
             }
         }
+
+        Err(anyhow!("Failed to connect to any server endpoint"))
     }
 
     /// Try to get a working connection from cache or rediscover
@@ -399,7 +455,7 @@ impl PlexBackend {
             }
 
             // Quick test current URL
-            if self.test_connection(current_url, 1).await {
+            if self.test_connection(current_url).await {
                 if let Some(ref sid) = source_id {
                     let cache = ConnectionService::cache();
                     cache.update_success(sid, 100).await;
@@ -463,7 +519,7 @@ impl PlexBackend {
     }
 
     /// Test a specific connection quickly
-    async fn test_connection(&self, url: &str, timeout_secs: u64) -> bool {
+    async fn test_connection(&self, url: &str) -> bool {
         let token = match self.auth_token.read().await.as_ref() {
             Some(t) => t.clone(),
             None => {
@@ -472,8 +528,9 @@ impl PlexBackend {
             }
         };
 
-        let client = match reqwest::Client::builder()
-            .timeout(Duration::from_secs(timeout_secs))
+        let policy = network_policy();
+        let client = match policy
+            .probe_client_builder()
             .danger_accept_invalid_certs(true)
             .build()
         {
@@ -484,7 +541,7 @@ impl PlexBackend {
             }
         };
 
-        let headers = create_standard_headers(Some(&token));
+        let headers = create_source_headers(Some(&token), &self.backend_id);
         let has_token = headers.get("X-Plex-Token").is_some();
         let token_preview = if !token.is_empty() {
             format!("{}...", &token[..8.min(token.len())])
@@ -493,9 +550,9 @@ impl PlexBackend {
         };
 
         tracing::debug!(
-            "test_connection: Testing {} (timeout: {}s, auth token: {}, starts with: {})",
+            "test_connection: Testing {} (timeout: {:?}, auth token: {}, starts with: {})",
             url,
-            timeout_secs,
+            policy.connection_timeout,
             if has_token { "YES" } else { "NO" },
             token_preview
         );
@@ -529,7 +586,7 @@ impl PlexBackend {
                         let url_with_token = format!("{}/identity?X-Plex-Token={}", url, token);
                         match client
                             .get(&url_with_token)
-                            .headers(create_standard_headers(Some(&token)))
+                            .headers(create_source_headers(Some(&token), &self.backend_id))
                             .send()
                             .await
                         {
@@ -566,86 +623,11 @@ impl PlexBackend {
         connections: &[PlexConnection],
         token: &str,
     ) -> Result<PlexConnection> {
-        use futures::future::select_ok;
-
         tracing::info!("Testing {} cached connections", connections.len());
 
-        let mut futures = Vec::new();
-        for conn in connections {
-            let uri = conn.uri.clone();
-            let token = token.to_string();
-            let conn_clone = conn.clone();
-
-            let future = async move {
-                let client = reqwest::Client::builder()
-                    .timeout(Duration::from_secs(1))
-                    .danger_accept_invalid_certs(true)
-                    .build()?;
-
-                let headers = create_standard_headers(Some(&token));
-                let conn_type = if conn_clone.local {
-                    "local"
-                } else if conn_clone.relay {
-                    "relay"
-                } else {
-                    "remote"
-                };
-
-                let has_token = headers.get("X-Plex-Token").is_some();
-                let token_preview = if !token.is_empty() {
-                    format!("{}...", &token[..8.min(token.len())])
-                } else {
-                    "EMPTY".to_string()
-                };
-                tracing::debug!(
-                    "Cached connection test: {} {} (auth token: {}, starts with: {})",
-                    conn_type,
-                    uri,
-                    if has_token { "YES" } else { "NO" },
-                    token_preview
-                );
-
-                // Send token both in headers AND as URL parameter for maximum compatibility
-                let url_with_token = format!("{}/identity?X-Plex-Token={}", uri, token);
-                let response = client.get(&url_with_token).headers(headers).send().await;
-
-                match response {
-                    Ok(resp) if resp.status().is_success() => {
-                        tracing::info!("✓ Cached {} connection {} succeeded", conn_type, uri);
-                        Ok(conn_clone)
-                    }
-                    Ok(resp) => {
-                        let status = resp.status();
-                        tracing::warn!(
-                            "✗ Cached {} connection {} returned status: {} ({})",
-                            conn_type,
-                            uri,
-                            status.as_u16(),
-                            status.canonical_reason().unwrap_or("Unknown")
-                        );
-                        if status.as_u16() == 401 {
-                            tracing::error!(
-                                "401 Unauthorized on cached {} connection {}",
-                                conn_type,
-                                uri
-                            );
-                        }
-                        Err(anyhow!("Connection failed with status: {}", status))
-                    }
-                    Err(e) => {
-                        tracing::warn!("✗ Cached {} connection {} failed: {}", conn_type, uri, e);
-                        Err(anyhow!("Connection failed: {}", e))
-                    }
-                }
-            };
-
-            futures.push(Box::pin(future));
-        }
-
-        match select_ok(futures).await {
-            Ok((result, _)) => Ok(result),
-            Err(_) => Err(anyhow!("All cached connections failed")),
-        }
+        self.race_connections(connections, token)
+            .await
+            .ok_or_else(|| anyhow!("All cached connections failed"))
     }
 
     /// Rediscover servers and update cache
@@ -837,92 +819,18 @@ impl MediaBackend for PlexBackend {
                 }
             }
         } else {
-            // Legacy path: Try to get token from keyring first, then fallback to file
-            let token = {
-                // Try keyring first
-                let service = "dev.arsfeld.Reel";
-                let account = &self.backend_id;
-
-                tracing::info!(
-                    "Looking for saved token - service: '{}', account: '{}'",
-                    service,
-                    account
-                );
-
-                match keyring::Entry::new(service, account) {
-                    Ok(entry) => match entry.get_password() {
-                        Ok(token) => {
-                            tracing::info!(
-                                "Successfully retrieved token from keyring for backend {}",
-                                self.backend_id
-                            );
-                            Some(token)
-                        }
-                        Err(e) => {
-                            let error_str = e.to_string();
-                            tracing::debug!(
-                                "Keyring error for backend {}: {}",
-                                self.backend_id,
-                                error_str
-                            );
-                            None
-                        }
-                    },
-                    Err(e) => {
-                        tracing::debug!("Failed to create keyring entry: {}", e);
-                        None
-                    }
+            // Legacy path: the token saved under the backend's id
+            match SecureStorage::from_config().await?.get(&self.backend_id) {
+                Ok(Some(token)) => token,
+                Ok(None) => {
+                    tracing::debug!("No saved token found for backend {}", self.backend_id);
+                    return Ok(AuthenticationResult::AuthRequired);
                 }
-            };
-
-            // If keyring failed, try file fallback
-            if let Some(token) = token {
-                token
-            } else {
-                // Try to read from fallback file
-                let config_dir = dirs::config_dir()
-                    .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
-                let token_file = config_dir
-                    .join("reel")
-                    .join(format!(".{}.token", self.backend_id));
-
-                if token_file.exists() {
-                    tracing::info!(
-                        "Checking fallback token file for backend {}",
-                        self.backend_id
-                    );
-                    match std::fs::read(&token_file) {
-                        Ok(obfuscated) => {
-                            // De-obfuscate the token
-                            let token_bytes: Vec<u8> = obfuscated
-                                .iter()
-                                .enumerate()
-                                .map(|(i, &b)| b ^ ((i as u8) + 42))
-                                .collect();
-
-                            match String::from_utf8(token_bytes) {
-                                Ok(token) => {
-                                    tracing::info!(
-                                        "Successfully retrieved token from file storage for backend {}",
-                                        self.backend_id
-                                    );
-                                    token
-                                }
-                                Err(e) => {
-                                    tracing::error!("Failed to decode token from file: {}", e);
-                                    return Ok(AuthenticationResult::AuthRequired);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            tracing::debug!("Failed to read token file: {}", e);
-                            return Ok(AuthenticationResult::AuthRequired);
-                        }
-                    }
-                } else {
-                    tracing::debug!(
-                        "No saved token found for backend {} in keyring or file",
-                        self.backend_id
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to read saved token for backend {}: {:#}",
+                        self.backend_id,
+                        e
                     );
                     return Ok(AuthenticationResult::AuthRequired);
                 }
@@ -965,17 +873,11 @@ impl MediaBackend for PlexBackend {
                         "Token appears to be invalid even after refresh attempt, removing from storage"
                     );
 
-                    // Try to delete from keyring
-                    if let Ok(entry) = keyring::Entry::new("dev.arsfeld.Reel", &self.backend_id) {
-                        entry.delete_credential().ok();
-                    }
-
-                    // Also delete from file fallback
-                    if let Some(config_dir) = dirs::config_dir() {
-                        let token_file = config_dir
-                            .join("reel")
-                            .join(format!(".{}.token", self.backend_id));
-                        std::fs::remove_file(token_file).ok();
+                    if let Err(e) = SecureStorage::from_config()
+                        .await
+                        .and_then(|storage| storage.delete(&self.backend_id))
+                    {
+                        tracing::warn!("Failed to remove saved token: {:#}", e);
                     }
 
                     return Ok(AuthenticationResult::AuthRequired);
@@ -1026,8 +928,8 @@ impl MediaBackend for PlexBackend {
 
             if should_test {
                 // Test if the URL is actually reachable
-                let test_client = reqwest::Client::builder()
-                    .timeout(Duration::from_secs(2))
+                let test_client = network_policy()
+                    .probe_client_builder()
                     .danger_accept_invalid_certs(true)
                     .build()?;
 
@@ -1035,7 +937,7 @@ impl MediaBackend for PlexBackend {
                 let url_with_token = format!("{}/identity?X-Plex-Token={}", url, token);
                 let test_result = test_client
                     .get(&url_with_token)
-                    .headers(create_standard_headers(Some(&token)))
+                    .headers(create_source_headers(Some(&token), &self.backend_id))
                     .send()
                     .await;
 
@@ -1419,6 +1321,31 @@ impl MediaBackend for PlexBackend {
         result
     }
 
+    async fn get_download_url(
+        &self,
+        media_id: &MediaItemId,
+        quality: &QualityPreset,
+    ) -> Result<DownloadInfo> {
+        // Extract the actual Plex rating key from the composite ID
+        let media_id_str = media_id.as_str();
+        let rating_key = if media_id_str.contains(':') {
+            media_id_str.split(':').next_back().unwrap_or(media_id_str)
+        } else {
+            media_id_str
+        };
+
+        let api = self.get_api().await?;
+        api.get_download_url(rating_key, quality).await
+    }
+
+    async fn keep_transcode_alive(&self, session: &TranscodeSession) -> Result<()> {
+        self.get_api().await?.ping_transcode(&session.id).await
+    }
+
+    async fn stop_transcode(&self, session: &TranscodeSession) -> Result<()> {
+        self.get_api().await?.stop_transcode(&session.id).await
+    }
+
     async fn update_progress(
         &self,
         media_id: &MediaItemId,
@@ -1433,33 +1360,19 @@ impl MediaBackend for PlexBackend {
             media_id_str
         };
 
+        // Only the resume position, the session itself is reported to the
+        // timeline by report_session with its real state
         let api = self.get_api().await?;
+        api.update_progress(rating_key, position, duration).await
+    }
 
-        // Check if we have an active PlayQueue
-        let play_queue_state = self.play_queue_state.lock().await;
-        if let (Some(queue_id), Some(version), Some(item_id)) = (
-            play_queue_state.play_queue_id,
-            play_queue_state.play_queue_version,
-            play_queue_state.play_queue_item_id,
-        ) {
-            drop(play_queue_state); // Release the lock early
-
-            // Use PlayQueue-based progress tracking
-            debug!(
-                "Using PlayQueue progress tracking - queue: {}, version: {}, item: {}",
-                queue_id, version, item_id
-            );
-            api.update_play_queue_progress(
-                queue_id, version, item_id, rating_key, position, duration, "playing",
-            )
+    async fn report_session(&self, report: &SessionReport) -> Result<()> {
+        let media_id = report.media_id.as_str();
+        let rating_key = media_id.rsplit(':').next().unwrap_or(media_id);
+        self.get_api()
+            .await?
+            .report_timeline(rating_key, report)
             .await
-        } else {
-            drop(play_queue_state); // Release the lock early
-
-            // Fall back to direct timeline updates
-            debug!("Using direct timeline progress tracking");
-            api.update_progress(rating_key, position, duration).await
-        }
     }
 
     async fn fetch_markers(
@@ -1481,6 +1394,26 @@ impl MediaBackend for PlexBackend {
         api.fetch_episode_markers(rating_key).await
     }
 
+    async fn fetch_markers_batch(
+        &self,
+        media_ids: &[MediaItemId],
+    ) -> Result<HashMap<MediaItemId, (Option<ChapterMarker>, Option<ChapterMarker>)>> {
+        // Plex looks items up by rating key, the last part of the composite ID
+        let rating_keys: Vec<&str> = media_ids
+            .iter()
+            .map(|id| id.as_str().rsplit(':').next().unwrap_or(id.as_str()))
+            .collect();
+
+        let api = self.get_api().await?;
+        let mut markers = api.fetch_markers_batch(&rating_keys).await?;
+
+        Ok(media_ids
+            .iter()
+            .zip(rating_keys)
+            .filter_map(|(id, key)| markers.remove(key).map(|found| (id.clone(), found)))
+            .collect())
+    }
+
     // Removed unused trait methods: mark_watched, mark_unwatched, get_watch_status, search,
     // get_backend_info, get_last_sync_time, supports_offline, find_next_episode
 
@@ -1497,8 +1430,8 @@ impl MediaBackend for PlexBackend {
         use std::time::Instant;
 
         let start = Instant::now();
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(5))
+        let client = network_policy()
+            .probe_client_builder()
             .danger_accept_invalid_certs(true) // Plex uses self-signed certs
             .build()?;
 
@@ -1514,7 +1447,7 @@ impl MediaBackend for PlexBackend {
 
         // Add auth token as header as well for maximum compatibility
         if let Some(token) = auth_token {
-            request = request.headers(create_standard_headers(Some(token)));
+            request = request.headers(create_source_headers(Some(token), &self.backend_id));
         }
 
         match request.send().await {
@@ -1546,6 +1479,53 @@ impl MediaBackend for PlexBackend {
         let api = self.get_api().await?;
         api.mark_unwatched(item_id).await
     }
+
+    async fn get_favorites(&self) -> Result<Vec<String>> {
+        let api = self.get_api().await?;
+        let mut liked = Vec::new();
+
+        for library in api.get_libraries().await? {
+            let types: &[u8] = match library.library_type {
+                LibraryType::Movies => &[1],
+                LibraryType::Shows => &[2, 4],
+                _ => continue,
+            };
+            // One library failing shouldn't drop the favorites of the others
+            for plex_type in types {
+                match api.get_liked(&library.id, *plex_type).await {
+                    Ok(items) => liked.extend(items),
+                    Err(e) => warn!("Skipping liked items of library {}: {}", library.id, e),
+                }
+            }
+        }
+
+        Ok(liked)
+    }
+
+    async fn set_favorite(&self, item_id: &str, favorite: bool) -> Result<()> {
+        let api = self.get_api().await?;
+        api.set_liked(item_id, favorite).await
+    }
+
+    async fn set_label(&self, item_id: &str, label: &str, present: bool) -> Result<()> {
+        let api = self.get_api().await?;
+        api.set_label(item_id, label, present).await
+    }
+
+    async fn get_theme_music_url(&self, item_id: &str) -> Result<Option<String>> {
+        let api = self.get_api().await?;
+        api.get_theme_url(item_id).await
+    }
+
+    async fn can_delete(&self, _item_id: &str) -> Result<bool> {
+        let api = self.get_api().await?;
+        api.allows_media_deletion().await
+    }
+
+    async fn delete_item(&self, item_id: &str, version_id: Option<&str>) -> Result<()> {
+        let api = self.get_api().await?;
+        api.delete_item(item_id, version_id).await
+    }
 }
 
 impl PlexBackend {
@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests {
     use super::super::*;
-    use crate::models::{LibraryId, LibraryType, MediaItemId};
+    use crate::models::{
+        LibraryId, LibraryType, MediaItemId, PlayQueueInfo, SessionEvent, SessionReport,
+        SessionStream,
+    };
     use mockito::Server;
     use serde::{Deserialize, Serialize};
     use serde_json::json;
@@ -113,7 +116,26 @@ mod tests {
                         "Role": [
                             {"tag": "Actor One"},
                             {"tag": "Actor Two"}
-                        ]
+                        ],
+                        "Guid": [
+                            {"id": "imdb://tt0111161"},
+                            {"id": "tmdb://278"}
+                        ],
+                        "Label": [
+                            {"tag": "Family Night"}
+                        ],
+                        "Collection": [
+                            {"tag": "Test Saga"}
+                        ],
+                        "Media": [{
+                            "id": 4321,
+                            "height": 1080,
+                            "Part": [{
+                                "key": "/library/parts/1/file.mkv",
+                                "container": "mkv",
+                                "size": 1073741824
+                            }]
+                        }]
                     }
                 ]
             }
@@ -217,6 +239,19 @@ mod tests {
         assert_eq!(pin.code, "TEST-CODE");
     }
 
+    #[test]
+    fn test_sign_in_link_carries_pin_code() {
+        let pin = PlexPin {
+            id: "1234".to_string(),
+            code: "abcd1234efgh".to_string(),
+        };
+
+        let link = PlexAuth::sign_in_link(&pin);
+        assert!(link.starts_with("https://app.plex.tv/auth#?"));
+        assert!(link.contains("code=abcd1234efgh"));
+        assert!(link.contains("clientID=reel-media-player"));
+    }
+
     // Internal struct for testing
     #[derive(Debug, Serialize, Deserialize)]
     struct PlexPinResponse {
@@ -264,6 +299,53 @@ mod tests {
             .create_async()
             .await;
 
+        // Smart collections are looked up across all libraries
+        let _sections = server
+            .mock("GET", "/library/sections")
+            .with_status(200)
+            .with_body(create_libraries_response().to_string())
+            .create_async()
+            .await;
+        let _show_collections = server
+            .mock("GET", "/library/sections/2/collections")
+            .with_status(200)
+            .with_body(json!({"MediaContainer": {}}).to_string())
+            .create_async()
+            .await;
+
+        // One hand-made and one smart collection, only the smart one is
+        // looked into since items list the others themselves
+        let collections = server
+            .mock("GET", "/library/sections/1/collections")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "MediaContainer": {
+                        "Metadata": [
+                            {"ratingKey": "10", "title": "Test Saga"},
+                            {"ratingKey": "11", "title": "Unwatched 4K", "smart": "1"}
+                        ]
+                    }
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let _smart_items = server
+            .mock("GET", "/library/collections/11/children")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "MediaContainer": {
+                        "Metadata": [{"ratingKey": "movie-1"}]
+                    }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
         let library_id = LibraryId::new("1");
         let movies = backend.get_movies(&library_id).await.unwrap();
 
@@ -276,6 +358,20 @@ mod tests {
         assert!(movie.watched);
         assert_eq!(movie.view_count, 2);
         assert_eq!(movie.genres.len(), 2);
+        assert_eq!(movie.external_ids, vec!["imdb://tt0111161", "tmdb://278"]);
+        assert_eq!(movie.studios, vec!["Test Studios"]);
+        assert_eq!(movie.tags, vec!["Family Night"]);
+        assert_eq!(movie.content_rating.as_deref(), Some("PG-13"));
+        assert_eq!(movie.collections, vec!["Test Saga", "Unwatched 4K"]);
+        assert_eq!(movie.versions.len(), 1);
+        assert_eq!(movie.versions[0].id, "4321");
+        assert_eq!(movie.versions[0].video_height, Some(1080));
+        assert_eq!(movie.versions[0].size, Some(1073741824));
+
+        // Further libraries of the same sync don't ask again
+        let movies = backend.get_movies(&library_id).await.unwrap();
+        assert_eq!(movies[0].collections, vec!["Test Saga", "Unwatched 4K"]);
+        collections.assert_async().await;
     }
 
     #[tokio::test]
@@ -292,6 +388,24 @@ mod tests {
             .with_body(create_shows_response().to_string())
             .create_async()
             .await;
+        let _sections = server
+            .mock("GET", "/library/sections")
+            .with_status(200)
+            .with_body(create_libraries_response().to_string())
+            .create_async()
+            .await;
+        let _m3 = server
+            .mock("GET", "/library/sections/2/collections")
+            .with_status(200)
+            .with_body(json!({"MediaContainer": {}}).to_string())
+            .create_async()
+            .await;
+        let _movie_collections = server
+            .mock("GET", "/library/sections/1/collections")
+            .with_status(200)
+            .with_body(json!({"MediaContainer": {}}).to_string())
+            .create_async()
+            .await;
 
         // Mock the seasons response for the show
         let seasons_response = json!({
@@ -375,22 +489,25 @@ mod tests {
         let backend = create_test_backend(&server).await;
 
         let _m = server
-            .mock("POST", "/:/timeline")
+            .mock("GET", "/:/progress")
             .match_header("X-Plex-Token", "test_token")
             .match_query(mockito::Matcher::AllOf(vec![
-                mockito::Matcher::UrlEncoded("ratingKey".into(), "movie-1".into()),
-                mockito::Matcher::UrlEncoded("key".into(), "/library/metadata/movie-1".into()),
+                mockito::Matcher::UrlEncoded("key".into(), "movie-1".into()),
                 mockito::Matcher::UrlEncoded(
                     "identifier".into(),
                     "com.plexapp.plugins.library".into(),
                 ),
-                mockito::Matcher::UrlEncoded("state".into(), "playing".into()),
                 mockito::Matcher::UrlEncoded("time".into(), "5000".into()),
-                mockito::Matcher::UrlEncoded("duration".into(), "7200000".into()),
             ]))
             .with_status(200)
             .create_async()
             .await;
+        // Saving the position must not touch the session on the timeline
+        let timeline = server
+            .mock("POST", "/:/timeline")
+            .expect(0)
+            .create_async()
+            .await;
 
         let media_id = MediaItemId::new("movie-1");
         let position = Duration::from_secs(5);
@@ -399,6 +516,153 @@ mod tests {
             .update_progress(&media_id, position, duration)
             .await
             .unwrap();
+        timeline.assert_async().await;
+    }
+
+    fn session_report(event: SessionEvent, paused: bool) -> SessionReport {
+        SessionReport {
+            media_id: MediaItemId::new("source-1:movie-1"),
+            event,
+            stream: SessionStream::Transcode,
+            play_session_id: "play-1".to_string(),
+            position: Duration::from_secs(5),
+            duration: Duration::from_secs(7200),
+            paused,
+            play_queue: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_reports_follow_playback_state() {
+        let mut server = Server::new_async().await;
+        let backend = create_test_backend(&server).await;
+
+        let mut mocks = Vec::new();
+        for state in ["playing", "paused", "stopped"] {
+            mocks.push(
+                server
+                    .mock("POST", "/:/timeline")
+                    .match_header("X-Plex-Token", "test_token")
+                    .match_header("X-Plex-Session-Identifier", "play-1")
+                    .match_query(mockito::Matcher::AllOf(vec![
+                        mockito::Matcher::UrlEncoded("ratingKey".into(), "movie-1".into()),
+                        mockito::Matcher::UrlEncoded("state".into(), state.into()),
+                        mockito::Matcher::UrlEncoded("time".into(), "5000".into()),
+                        mockito::Matcher::UrlEncoded("duration".into(), "7200000".into()),
+                    ]))
+                    .with_status(200)
+                    .create_async()
+                    .await,
+            );
+        }
+
+        for (event, paused) in [
+            (SessionEvent::Started, false),
+            (SessionEvent::Progress, true),
+            (SessionEvent::Stopped, true),
+        ] {
+            backend
+                .report_session(&session_report(event, paused))
+                .await
+                .unwrap();
+        }
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_reports_move_the_play_queue_along() {
+        let mut server = Server::new_async().await;
+        let backend = create_test_backend(&server).await;
+
+        let timeline = server
+            .mock("POST", "/:/timeline")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("ratingKey".into(), "movie-1".into()),
+                mockito::Matcher::UrlEncoded("playQueueID".into(), "7".into()),
+                mockito::Matcher::UrlEncoded("playQueueVersion".into(), "2".into()),
+                mockito::Matcher::UrlEncoded("playQueueItemID".into(), "70".into()),
+            ]))
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let mut report = session_report(SessionEvent::Progress, false);
+        report.play_queue = Some(PlayQueueInfo {
+            play_queue_id: 7,
+            play_queue_version: 2,
+            play_queue_item_id: 70,
+            source_uri: None,
+            shuffled: false,
+        });
+        backend.report_session(&report).await.unwrap();
+        timeline.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_delete_version() {
+        let mut server = Server::new_async().await;
+        let backend = create_test_backend(&server).await;
+
+        let _settings = server
+            .mock("GET", "/")
+            .match_header("X-Plex-Token", "test_token")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "MediaContainer": {
+                        "allowMediaDeletion": true,
+                    }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let delete = server
+            .mock("DELETE", "/library/metadata/movie-1/media/4321")
+            .match_header("X-Plex-Token", "test_token")
+            .with_status(200)
+            .create_async()
+            .await;
+
+        assert!(backend.can_delete("movie-1").await.unwrap());
+        backend.delete_item("movie-1", Some("4321")).await.unwrap();
+        delete.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_adding_a_label_keeps_the_others() {
+        let mut server = Server::new_async().await;
+        let backend = create_test_backend(&server).await;
+
+        let _labels = server
+            .mock("GET", "/library/metadata/movie-1")
+            .match_header("X-Plex-Token", "test_token")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "MediaContainer": {
+                        "Metadata": [{"Label": [{"tag": "Family Night"}]}]
+                    }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let edit = server
+            .mock("PUT", "/library/metadata/movie-1")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("label[0].tag.tag".into(), "Family Night".into()),
+                mockito::Matcher::UrlEncoded("label[1].tag.tag".into(), "Rewatch".into()),
+                mockito::Matcher::UrlEncoded("label.locked".into(), "1".into()),
+            ]))
+            .with_status(200)
+            .create_async()
+            .await;
+
+        backend.set_label("movie-1", "Rewatch", true).await.unwrap();
+        edit.assert_async().await;
     }
 
     #[tokio::test]
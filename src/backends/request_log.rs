@@ -0,0 +1,234 @@
+//! Recent backend HTTP requests, shown in the diagnostics window
+//!
+//! Recording is off until it is switched on from the diagnostics window, since
+//! it reads each response body into memory before handing it to the caller.
+//! It can be narrowed down to a single source, leaving the others untouched.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use once_cell::sync::Lazy;
+
+use super::request_scheduler::REQUEST_SCHEDULER;
+use crate::services::core::diagnostics::redact;
+
+/// Number of recent requests kept
+const REQUEST_LOG_CAPACITY: usize = 200;
+
+/// Bytes of each response body kept for display
+const BODY_PREVIEW_BYTES: usize = 4096;
+
+/// Recent requests, oldest first
+pub static REQUEST_LOG: Lazy<RequestLog> = Lazy::new(|| RequestLog::new(REQUEST_LOG_CAPACITY));
+
+/// A finished or failed backend request, with credentials redacted
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub source_id: String,
+    pub started_at: DateTime<Local>,
+    pub method: String,
+    pub url: String,
+    /// HTTP status, None when no response arrived
+    pub status: Option<u16>,
+    pub error: Option<String>,
+    pub duration: Duration,
+    /// Start of the response body, lossily decoded
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn failed(&self) -> bool {
+        self.error.is_some() || self.status.is_none_or(|status| status >= 400)
+    }
+}
+
+pub struct RequestLog {
+    capacity: usize,
+    enabled: AtomicBool,
+    /// Source whose requests are recorded, every source's if None
+    source_id: Mutex<Option<String>>,
+    state: Mutex<RequestLogState>,
+}
+
+struct RequestLogState {
+    requests: VecDeque<RecordedRequest>,
+    /// Sequence number the next recorded request gets
+    next_seq: u64,
+}
+
+impl RequestLog {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            enabled: AtomicBool::new(false),
+            source_id: Mutex::new(None),
+            state: Mutex::new(RequestLogState {
+                requests: VecDeque::with_capacity(capacity),
+                next_seq: 0,
+            }),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn source_id(&self) -> Option<String> {
+        self.source_id.lock().unwrap().clone()
+    }
+
+    /// Record only the requests of `source_id`, or of every source if None
+    pub fn set_source_id(&self, source_id: Option<String>) {
+        *self.source_id.lock().unwrap() = source_id;
+    }
+
+    /// Whether requests to `source_id`'s server are recorded
+    pub fn records(&self, source_id: &str) -> bool {
+        self.is_enabled()
+            && self
+                .source_id
+                .lock()
+                .unwrap()
+                .as_deref()
+                .is_none_or(|recorded| recorded == source_id)
+    }
+
+    fn push(&self, request: RecordedRequest) {
+        let mut state = self.state.lock().unwrap();
+        if state.requests.len() == self.capacity {
+            state.requests.pop_front();
+        }
+        state.requests.push_back(request);
+        state.next_seq += 1;
+    }
+
+    /// Requests recorded from sequence number `seq` on, oldest first, along
+    /// with the sequence number to resume from
+    pub fn since(&self, seq: u64) -> (Vec<RecordedRequest>, u64) {
+        let state = self.state.lock().unwrap();
+        let first_seq = state.next_seq - state.requests.len() as u64;
+        let requests = state
+            .requests
+            .iter()
+            .skip(seq.saturating_sub(first_seq) as usize)
+            .cloned()
+            .collect();
+        (requests, state.next_seq)
+    }
+
+    pub fn clear(&self) {
+        self.state.lock().unwrap().requests.clear();
+    }
+}
+
+fn body_preview(body: &[u8]) -> String {
+    let preview = String::from_utf8_lossy(&body[..body.len().min(BODY_PREVIEW_BYTES)]);
+    if body.len() > BODY_PREVIEW_BYTES {
+        format!("{}…", preview)
+    } else {
+        preview.into_owned()
+    }
+}
+
+/// Sending a backend request through the request log
+pub trait RecordedSend {
+    /// Send the request once the request scheduler gives `source_id`'s server
+    /// a slot, recording it against `source_id` while recording is on
+    fn send_recorded(
+        self,
+        source_id: &str,
+    ) -> impl Future<Output = reqwest::Result<reqwest::Response>> + Send;
+}
+
+impl RecordedSend for reqwest::RequestBuilder {
+    async fn send_recorded(self, source_id: &str) -> reqwest::Result<reqwest::Response> {
+        let _slot = REQUEST_SCHEDULER.acquire(source_id).await;
+
+        if !REQUEST_LOG.records(source_id) {
+            return self.send().await;
+        }
+
+        let (client, request) = self.build_split();
+        let request = request?;
+        let mut record = RecordedRequest {
+            source_id: source_id.to_string(),
+            started_at: Local::now(),
+            method: request.method().to_string(),
+            url: redact(request.url().as_str()),
+            status: None,
+            error: None,
+            duration: Duration::ZERO,
+            body: String::new(),
+        };
+        let start = Instant::now();
+
+        let response = match client.execute(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                record.duration = start.elapsed();
+                record.error = Some(e.to_string());
+                REQUEST_LOG.push(record);
+                return Err(e);
+            }
+        };
+
+        let status = response.status();
+        let version = response.version();
+        let headers = response.headers().clone();
+        record.status = Some(status.as_u16());
+
+        let body = match response.bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                record.duration = start.elapsed();
+                record.error = Some(e.to_string());
+                REQUEST_LOG.push(record);
+                return Err(e);
+            }
+        };
+        record.duration = start.elapsed();
+        record.body = redact(&body_preview(&body));
+        REQUEST_LOG.push(record);
+
+        // Hand the caller an equivalent response backed by the body read above
+        let mut rebuilt = http::Response::new(body);
+        *rebuilt.status_mut() = status;
+        *rebuilt.version_mut() = version;
+        *rebuilt.headers_mut() = headers;
+        Ok(reqwest::Response::from(rebuilt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_only_the_chosen_source() {
+        let log = RequestLog::new(10);
+        assert!(!log.records("plex"));
+
+        log.set_enabled(true);
+        assert!(log.records("plex"));
+        assert!(log.records("jellyfin"));
+
+        log.set_source_id(Some("plex".to_string()));
+        assert!(log.records("plex"));
+        assert!(!log.records("jellyfin"));
+
+        log.set_enabled(false);
+        assert!(!log.records("plex"));
+    }
+}
@@ -0,0 +1,149 @@
+//! Per-server pacing of backend HTTP requests
+//!
+//! Every backend request waits here for a slot on its server before it is
+//! sent: at most a configured number of requests run against one server at a
+//! time, and new requests start no faster than the configured rate. Large syncs
+//! then queue up instead of overwhelming small servers, and the limits follow
+//! the network preferences from the next request on.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use crate::services::core::network_policy::network_policy;
+
+/// Scheduler shared by all backends
+pub static REQUEST_SCHEDULER: Lazy<RequestScheduler> = Lazy::new(RequestScheduler::default);
+
+/// Held while a request runs; dropping it frees the slot for the next request
+pub struct RequestSlot {
+    _permit: OwnedSemaphorePermit,
+}
+
+#[derive(Default)]
+pub struct RequestScheduler {
+    servers: Mutex<HashMap<String, Arc<ServerQueue>>>,
+}
+
+/// Limits of one server, identified by source ID
+struct ServerQueue {
+    max_concurrent: usize,
+    permits: Arc<Semaphore>,
+    /// Earliest time the next request may start
+    next_start: Mutex<Instant>,
+}
+
+impl ServerQueue {
+    fn new(max_concurrent: usize) -> Self {
+        Self {
+            max_concurrent,
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+            next_start: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Reserve the next start time, spaced `interval` after the previous one
+    fn reserve_start(&self, interval: Duration) -> Instant {
+        let mut next_start = self.next_start.lock().unwrap();
+        let start = (*next_start).max(Instant::now());
+        *next_start = start + interval;
+        start
+    }
+}
+
+impl RequestScheduler {
+    /// Wait until a request to `source_id`'s server may start
+    pub async fn acquire(&self, source_id: &str) -> RequestSlot {
+        let policy = network_policy();
+        self.acquire_with(
+            source_id,
+            policy.max_concurrent_requests,
+            policy.min_request_interval,
+        )
+        .await
+    }
+
+    async fn acquire_with(
+        &self,
+        source_id: &str,
+        max_concurrent: usize,
+        interval: Duration,
+    ) -> RequestSlot {
+        let queue = self.queue(source_id, max_concurrent.max(1));
+        let permit = queue
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("request scheduler semaphores are never closed");
+
+        let start = queue.reserve_start(interval);
+        tokio::time::sleep_until(start).await;
+
+        RequestSlot { _permit: permit }
+    }
+
+    fn queue(&self, source_id: &str, max_concurrent: usize) -> Arc<ServerQueue> {
+        let mut servers = self.servers.lock().unwrap();
+        match servers.get(source_id) {
+            Some(queue) if queue.max_concurrent == max_concurrent => queue.clone(),
+            // A changed limit starts a fresh queue; requests already running
+            // finish on the old one
+            _ => {
+                let queue = Arc::new(ServerQueue::new(max_concurrent));
+                servers.insert(source_id.to_string(), queue.clone());
+                queue
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_caps_concurrent_requests_per_server() {
+        let scheduler = RequestScheduler::default();
+
+        let first = scheduler.acquire_with("server-a", 2, Duration::ZERO).await;
+        let _second = scheduler.acquire_with("server-a", 2, Duration::ZERO).await;
+
+        // A third request waits for a slot on the same server
+        let third = tokio::time::timeout(
+            Duration::from_millis(50),
+            scheduler.acquire_with("server-a", 2, Duration::ZERO),
+        )
+        .await;
+        assert!(third.is_err());
+
+        // Other servers are not held up
+        let _other = scheduler.acquire_with("server-b", 2, Duration::ZERO).await;
+
+        drop(first);
+        let third = tokio::time::timeout(
+            Duration::from_millis(50),
+            scheduler.acquire_with("server-a", 2, Duration::ZERO),
+        )
+        .await;
+        assert!(third.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_spaces_request_starts() {
+        let scheduler = RequestScheduler::default();
+        let interval = Duration::from_millis(20);
+        let begin = Instant::now();
+
+        for _ in 0..3 {
+            scheduler.acquire_with("server-a", 4, interval).await;
+        }
+
+        // The first request starts right away, the others one interval apart
+        assert!(begin.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -1,12 +1,19 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::time::Duration;
 
+use crate::db::entities::QualityPreset;
 use crate::models::{
-    AuthenticationResult, ChapterMarker, Credentials, Episode, HomeSection, Library, LibraryId,
-    MediaItemId, Movie, PlaybackProgress, Season, Show, ShowId, StreamInfo, User,
+    AuthenticationResult, ChapterMarker, Credentials, DownloadInfo, Episode, HomeSection, Library,
+    LibraryId, MediaItemId, Movie, PlaybackProgress, Season, SessionReport, Show, ShowId,
+    StreamInfo, TranscodeSession, User,
 };
 
+/// Most items fetched for each home section, the longest home row
+/// preferences allow
+pub const HOME_SECTION_ITEM_LIMIT: u32 = 50;
+
 #[async_trait]
 pub trait MediaBackend: Send + Sync + std::fmt::Debug {
     /// Initialize the backend with stored credentials
@@ -40,6 +47,28 @@ pub trait MediaBackend: Send + Sync + std::fmt::Debug {
 
     async fn get_stream_url(&self, media_id: &MediaItemId) -> Result<StreamInfo>;
 
+    /// Get a single-file download for offline use, transcoded on the server
+    /// when the original exceeds the requested quality
+    async fn get_download_url(
+        &self,
+        _media_id: &MediaItemId,
+        _quality: &QualityPreset,
+    ) -> Result<DownloadInfo> {
+        Err(anyhow::anyhow!(
+            "Downloads are not supported by this backend"
+        ))
+    }
+
+    /// Keep a server-side transcode running while playback streams from it
+    async fn keep_transcode_alive(&self, _session: &TranscodeSession) -> Result<()> {
+        Ok(())
+    }
+
+    /// Stop a server-side transcode playback no longer streams from
+    async fn stop_transcode(&self, _session: &TranscodeSession) -> Result<()> {
+        Ok(())
+    }
+
     async fn update_progress(
         &self,
         media_id: &MediaItemId,
@@ -47,6 +76,13 @@ pub trait MediaBackend: Send + Sync + std::fmt::Debug {
         duration: Duration,
     ) -> Result<()>;
 
+    /// Tell the server about a playback session, so it lists what plays
+    /// where. Direct streams, transcodes and local files all report through
+    /// here; servers without sessions ignore it.
+    async fn report_session(&self, _report: &SessionReport) -> Result<()> {
+        Ok(())
+    }
+
     /// Fetch intro and credits markers for a media item
     /// Returns (intro_marker, credits_marker) tuple with None if markers don't exist
     /// Used during playback initialization to enable skip intro/credits buttons
@@ -59,6 +95,31 @@ pub trait MediaBackend: Send + Sync + std::fmt::Debug {
         Ok((None, None))
     }
 
+    /// Fetch intro and credits markers for several media items, such as the
+    /// episodes of a season. Items without markers, or whose markers could
+    /// not be fetched, are left out. By default each item is fetched on its
+    /// own, with the request scheduler pacing the requests; backends that can
+    /// fetch several items in one request should override this.
+    async fn fetch_markers_batch(
+        &self,
+        media_ids: &[MediaItemId],
+    ) -> Result<HashMap<MediaItemId, (Option<ChapterMarker>, Option<ChapterMarker>)>> {
+        let results =
+            futures::future::join_all(media_ids.iter().map(|id| self.fetch_markers(id))).await;
+
+        let mut markers = HashMap::new();
+        for (media_id, result) in media_ids.iter().zip(results) {
+            match result {
+                Ok((None, None)) => {}
+                Ok(found) => {
+                    markers.insert(media_id.clone(), found);
+                }
+                Err(e) => tracing::warn!("Failed to fetch markers for {}: {}", media_id, e),
+            }
+        }
+        Ok(markers)
+    }
+
     // Watch status methods removed - never used in production
     // Search method removed - never used in production
 
@@ -83,6 +144,42 @@ pub trait MediaBackend: Send + Sync + std::fmt::Debug {
         Ok(())
     }
 
+    /// Get the IDs of items the user marked as favorite (Jellyfin) or liked (Plex)
+    async fn get_favorites(&self) -> Result<Vec<String>> {
+        // Default implementation returns no favorites
+        Ok(Vec::new())
+    }
+
+    /// Mark or unmark a media item as favorite on the backend server
+    async fn set_favorite(&self, _item_id: &str, _favorite: bool) -> Result<()> {
+        // Default implementation does nothing
+        // Backends should override this to sync favorite state
+        Ok(())
+    }
+
+    /// Put a label on a movie or show on the backend server, or take it off
+    async fn set_label(&self, _item_id: &str, _label: &str, _present: bool) -> Result<()> {
+        Err(anyhow::anyhow!("Labels are not supported"))
+    }
+
+    /// Get a stream URL of the theme music of a movie or show, if it has one
+    async fn get_theme_music_url(&self, _item_id: &str) -> Result<Option<String>> {
+        // Default implementation reports no theme music
+        Ok(None)
+    }
+
+    /// Whether the user may delete the item from the server
+    async fn can_delete(&self, _item_id: &str) -> Result<bool> {
+        // Default implementation allows no deletion
+        Ok(false)
+    }
+
+    /// Delete an item and its files from the server, or only the file of
+    /// the version `version_id`
+    async fn delete_item(&self, _item_id: &str, _version_id: Option<&str>) -> Result<()> {
+        Err(anyhow::anyhow!("Deleting from the server is not supported"))
+    }
+
     /// Get current playback progress for a media item from the backend
     /// Used for conflict resolution when syncing local changes
     /// Returns PlaybackProgress with position and watch status
@@ -536,7 +536,7 @@ impl FileCache {
         let repository = self.chunk_manager.repository();
 
         // Check if database entry already exists with valid expected_total_size
-        if let Some(entry) = repository
+        if let Some(mut entry) = repository
             .find_cache_entry(
                 &cache_key.source_id.to_string(),
                 &cache_key.media_id.to_string(),
@@ -544,6 +544,13 @@ impl FileCache {
             )
             .await?
         {
+            // Server URLs carry a token that can expire, chunks are fetched
+            // from the one the server handed out last
+            if entry.original_url != original_url {
+                debug!("Updating original URL of {:?}", cache_key);
+                entry.original_url = original_url.to_string();
+                entry = repository.update_cache_entry(entry).await?;
+            }
             if entry.expected_total_size.is_some() && entry.expected_total_size.unwrap() > 0 {
                 debug!(
                     "Database entry already exists with expected_total_size={:?}",
@@ -984,12 +991,13 @@ impl FileCacheHandle {
 #[allow(unused_imports)]
 mod tests {
     use super::*;
-    use crate::models::{QualityOption, Resolution};
+    use crate::models::{QualityOption, Resolution, StreamMode};
 
     fn create_test_stream_info(height: u32) -> StreamInfo {
         StreamInfo {
             url: "http://test.com/video.mp4".to_string(),
             direct_play: true,
+            mode: StreamMode::DirectPlay,
             video_codec: "h264".to_string(),
             audio_codec: "aac".to_string(),
             container: "mp4".to_string(),
@@ -18,6 +18,27 @@ pub struct Config {
 
     #[serde(default)]
     pub updates: UpdateConfig,
+
+    #[serde(default)]
+    pub network: NetworkConfig,
+
+    #[serde(default)]
+    pub downloads: DownloadsConfig,
+
+    #[serde(default)]
+    pub storage: StorageConfig,
+
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
+    #[serde(default)]
+    pub backups: BackupConfig,
+
+    #[serde(default)]
+    pub security: SecurityConfig,
+
+    #[serde(default)]
+    pub kids: KidsConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -66,6 +87,227 @@ pub struct PlaybackConfig {
 
     #[serde(default = "default_minimum_marker_duration")]
     pub minimum_marker_duration_seconds: u32,
+
+    /// Subtitle size relative to the player's default, 1.0 is unscaled
+    #[serde(default = "default_subtitle_scale")]
+    pub subtitle_scale: f64,
+
+    /// Use lighter playback settings in power saver mode or on low battery
+    #[serde(default = "default_true")]
+    pub power_saving_playback: bool,
+
+    /// Continue playback paused by suspend or screen lock once unlocked
+    #[serde(default)]
+    pub resume_after_unlock: bool,
+
+    /// Monitor for fullscreen playback: "window", "cursor", "last" or a
+    /// monitor's connector name
+    #[serde(default = "default_fullscreen_monitor")]
+    pub fullscreen_monitor: String,
+
+    /// Connector of the monitor fullscreen playback used last
+    #[serde(default)]
+    pub last_fullscreen_monitor: String,
+
+    /// Pointer and touch gestures in the video area
+    #[serde(default)]
+    pub gestures: GestureConfig,
+
+    /// Pitch correction and the speed each kind of content starts at
+    #[serde(default)]
+    pub speed: SpeedConfig,
+
+    /// Progress and resuming of audiobooks and podcasts
+    #[serde(default)]
+    pub long_audio: LongAudioConfig,
+
+    /// Resolve opened web page URLs to their stream with yt-dlp
+    #[serde(default = "default_true")]
+    pub resolve_urls_with_ytdlp: bool,
+
+    /// Subtitle text encoding and forced subtitle selection
+    #[serde(default)]
+    pub subtitles: SubtitleConfig,
+
+    /// What the time label after the seek bar shows, changed by clicking it
+    #[serde(default)]
+    pub duration_display: DurationDisplay,
+}
+
+/// What the player's time label after the seek bar shows
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DurationDisplay {
+    /// Length of the item
+    #[default]
+    Total,
+    /// Time left at the playback speed
+    Remaining,
+    /// Wall-clock time playback ends at the playback speed
+    EndsAt,
+}
+
+impl DurationDisplay {
+    /// The display a click on the label switches to
+    pub fn next(self) -> Self {
+        match self {
+            Self::Total => Self::Remaining,
+            Self::Remaining => Self::EndsAt,
+            Self::EndsAt => Self::Total,
+        }
+    }
+}
+
+/// Subtitle preferences
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubtitleConfig {
+    /// Character encoding of text subtitles, "auto" to detect it. Older .srt
+    /// files are often in a legacy code page such as "cp1252".
+    #[serde(default = "default_subtitle_charset")]
+    pub charset: String,
+
+    /// Show a forced subtitle track, which only translates foreign dialogue
+    /// and signs, when the audio is in the desktop's language
+    #[serde(default = "default_true")]
+    pub auto_forced: bool,
+}
+
+impl Default for SubtitleConfig {
+    fn default() -> Self {
+        Self {
+            charset: default_subtitle_charset(),
+            auto_forced: true,
+        }
+    }
+}
+
+fn default_subtitle_charset() -> String {
+    "auto".to_string()
+}
+
+/// Playback speed preferences
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SpeedConfig {
+    /// Keep voices at their normal pitch when playing faster or slower
+    #[serde(default = "default_true")]
+    pub pitch_correction: bool,
+
+    /// Speed movies start at
+    #[serde(default = "default_speed")]
+    pub movie: f64,
+
+    /// Speed episodes start at
+    #[serde(default = "default_speed")]
+    pub episode: f64,
+
+    /// Speed music and audiobook tracks start at
+    #[serde(default = "default_speed")]
+    pub music: f64,
+}
+
+impl SpeedConfig {
+    /// Speed an item of the given media type starts at
+    pub fn default_for(&self, media_type: &str) -> f64 {
+        match media_type {
+            "movie" => self.movie,
+            "episode" => self.episode,
+            "track" => self.music,
+            _ => default_speed(),
+        }
+    }
+}
+
+impl Default for SpeedConfig {
+    fn default() -> Self {
+        Self {
+            pitch_correction: true,
+            movie: default_speed(),
+            episode: default_speed(),
+            music: default_speed(),
+        }
+    }
+}
+
+fn default_speed() -> f64 {
+    1.0
+}
+
+/// Audiobooks and podcasts, audio tracks long enough to be listened to over
+/// several sittings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LongAudioConfig {
+    /// How often the position is saved while listening, more often than for
+    /// video since a lost minute of speech is hard to find again
+    #[serde(default = "default_long_audio_progress_interval")]
+    pub progress_interval_seconds: u32,
+
+    /// How far playback jumps back when resumed after a long pause, 0 to
+    /// resume where it was paused
+    #[serde(default = "default_snap_back")]
+    pub snap_back_seconds: u32,
+
+    /// Pauses at least this long jump back when resumed
+    #[serde(default = "default_snap_back_after")]
+    pub snap_back_after_minutes: u32,
+}
+
+impl Default for LongAudioConfig {
+    fn default() -> Self {
+        Self {
+            progress_interval_seconds: default_long_audio_progress_interval(),
+            snap_back_seconds: default_snap_back(),
+            snap_back_after_minutes: default_snap_back_after(),
+        }
+    }
+}
+
+fn default_long_audio_progress_interval() -> u32 {
+    2
+}
+
+fn default_snap_back() -> u32 {
+    15
+}
+
+fn default_snap_back_after() -> u32 {
+    5
+}
+
+/// Pointer and touch gestures in the player's video area, each of which can be
+/// turned off
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct GestureConfig {
+    /// The mouse wheel changes the volume
+    #[serde(default = "default_true")]
+    pub scroll_volume: bool,
+
+    /// Scrolling sideways seeks
+    #[serde(default = "default_true")]
+    pub scroll_seek: bool,
+
+    /// Dragging sideways across the video seeks instead of moving the window
+    #[serde(default)]
+    pub drag_seek: bool,
+
+    /// Double-clicking the video toggles fullscreen
+    #[serde(default = "default_true")]
+    pub double_click_fullscreen: bool,
+
+    /// Swiping on a touchscreen seeks and changes the volume
+    #[serde(default = "default_true")]
+    pub swipe: bool,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            scroll_volume: true,
+            scroll_seek: true,
+            drag_seek: false,
+            double_click_fullscreen: true,
+            swipe: true,
+        }
+    }
 }
 
 fn default_true() -> bool {
@@ -76,6 +318,14 @@ fn default_minimum_marker_duration() -> u32 {
     5
 }
 
+fn default_subtitle_scale() -> f64 {
+    1.0
+}
+
+fn default_fullscreen_monitor() -> String {
+    "window".to_string()
+}
+
 impl Default for PlaybackConfig {
     fn default() -> Self {
         Self {
@@ -94,6 +344,17 @@ impl Default for PlaybackConfig {
             auto_skip_intro: false,
             auto_skip_credits: false,
             minimum_marker_duration_seconds: 5,
+            subtitle_scale: 1.0,
+            power_saving_playback: true,
+            resume_after_unlock: false,
+            fullscreen_monitor: default_fullscreen_monitor(),
+            last_fullscreen_monitor: String::new(),
+            gestures: GestureConfig::default(),
+            speed: SpeedConfig::default(),
+            long_audio: LongAudioConfig::default(),
+            resolve_urls_with_ytdlp: true,
+            subtitles: SubtitleConfig::default(),
+            duration_display: DurationDisplay::default(),
         }
     }
 }
@@ -111,6 +372,90 @@ pub struct UiPreferences {
     /// Map of preset_name -> serialized FilterState JSON
     #[serde(default)]
     pub filter_presets: HashMap<String, String>,
+    /// Map of source_id -> library IDs in the order they appear in the sidebar
+    #[serde(default)]
+    pub sidebar_library_order: HashMap<String, Vec<String>>,
+
+    /// Library IDs hidden from the sidebar
+    #[serde(default)]
+    pub hidden_sidebar_libraries: Vec<String>,
+
+    /// Source IDs whose sidebar group is collapsed
+    #[serde(default)]
+    pub collapsed_sidebar_sources: Vec<String>,
+
+    /// Play a muted preview when hovering a movie or episode card
+    #[serde(default)]
+    pub hover_previews: bool,
+
+    /// Window size when the app was last closed, 0 for the default size
+    #[serde(default)]
+    pub window_width: i32,
+    #[serde(default)]
+    pub window_height: i32,
+
+    /// Whether the window was maximized when the app was last closed
+    #[serde(default)]
+    pub window_maximized: bool,
+
+    /// Page open when the app was last closed, e.g. "library:<id>"
+    #[serde(default)]
+    pub last_page: Option<String>,
+
+    /// Item that was playing when the app was closed
+    #[serde(default)]
+    pub interrupted_playback: Option<String>,
+
+    /// Ask at startup whether to resume playback interrupted by quitting
+    #[serde(default)]
+    pub resume_prompt: bool,
+
+    /// Network URLs opened for playback, most recent first
+    #[serde(default)]
+    pub recent_urls: Vec<String>,
+
+    /// Size and compression of posters and backdrops fetched from servers
+    #[serde(default)]
+    pub image_quality: ImageQuality,
+
+    /// Play the theme music of movies and shows on their detail pages
+    #[serde(default)]
+    pub theme_music: bool,
+
+    /// Most items shown in each home row, 0 for the default
+    #[serde(default)]
+    pub home_row_limit: u32,
+
+    /// Minutes idle on the home page or paused before the screensaver
+    /// starts, 0 to never start it
+    #[serde(default)]
+    pub screensaver_minutes: u32,
+}
+
+/// Items shown in each home row unless chosen in preferences
+pub const DEFAULT_HOME_ROW_LIMIT: u32 = 20;
+
+impl UiPreferences {
+    /// Most items shown in each home row
+    pub fn home_row_items(&self) -> usize {
+        match self.home_row_limit {
+            0 => DEFAULT_HOME_ROW_LIMIT as usize,
+            limit => limit as usize,
+        }
+    }
+}
+
+/// How sharp artwork is fetched, against bandwidth and image cache size
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageQuality {
+    /// Shown size ignoring the display scale, strongly compressed
+    Low,
+    /// Shown size on the display
+    #[default]
+    Balanced,
+    /// Twice the shown size, lightly compressed
+    High,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -152,6 +497,267 @@ impl Default for UpdateConfig {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NetworkConfig {
+    /// Send a Wake-on-LAN packet when a source with a MAC address can't be reached
+    #[serde(default)]
+    pub auto_wake_on_lan: bool,
+
+    /// Timeout for a single API request
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+
+    /// Timeout when probing whether a server address is reachable
+    #[serde(default = "default_connection_timeout_secs")]
+    pub connection_timeout_secs: u64,
+
+    /// Retries after the initial attempt for transient request failures
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Delay before the first retry, doubled on every further retry
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+
+    /// Upper bound for the delay between retries
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub retry_max_delay_ms: u64,
+
+    /// Requests sent to one server at the same time
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: u32,
+
+    /// Requests started per second against one server (0 disables the limit)
+    #[serde(default = "default_max_requests_per_second")]
+    pub max_requests_per_second: u32,
+
+    /// Hold back background downloads while on a metered connection
+    #[serde(default = "default_true")]
+    pub metered_pause_downloads: bool,
+
+    /// Only load images that are on screen while on a metered connection
+    #[serde(default = "default_true")]
+    pub metered_skip_image_prefetch: bool,
+
+    /// Start playback at a reduced quality while on a metered connection
+    #[serde(default = "default_true")]
+    pub metered_lower_stream_quality: bool,
+}
+
+fn default_request_timeout_secs() -> u64 {
+    30
+}
+
+fn default_connection_timeout_secs() -> u64 {
+    2
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_base_delay_ms() -> u64 {
+    100
+}
+
+fn default_retry_max_delay_ms() -> u64 {
+    10_000
+}
+
+fn default_max_concurrent_requests() -> u32 {
+    4
+}
+
+fn default_max_requests_per_second() -> u32 {
+    20
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            auto_wake_on_lan: false,
+            request_timeout_secs: default_request_timeout_secs(),
+            connection_timeout_secs: default_connection_timeout_secs(),
+            max_retries: default_max_retries(),
+            retry_base_delay_ms: default_retry_base_delay_ms(),
+            retry_max_delay_ms: default_retry_max_delay_ms(),
+            max_concurrent_requests: default_max_concurrent_requests(),
+            max_requests_per_second: default_max_requests_per_second(),
+            metered_pause_downloads: true,
+            metered_skip_image_prefetch: true,
+            metered_lower_stream_quality: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DownloadsConfig {
+    /// Days after watching before a download is deleted, 0 keeps watched downloads
+    #[serde(default = "default_delete_watched_after_days")]
+    pub delete_watched_after_days: u32,
+
+    /// Free disk space to keep, deleting watched downloads early when below it (0 disables)
+    #[serde(default = "default_min_free_space_gb")]
+    pub min_free_space_gb: u32,
+}
+
+fn default_delete_watched_after_days() -> u32 {
+    7
+}
+
+fn default_min_free_space_gb() -> u32 {
+    5
+}
+
+impl Default for DownloadsConfig {
+    fn default() -> Self {
+        Self {
+            delete_watched_after_days: default_delete_watched_after_days(),
+            min_free_space_gb: default_min_free_space_gb(),
+        }
+    }
+}
+
+/// Custom locations for the image cache and offline downloads.
+///
+/// The streaming cache location is `cache.cache_directory`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct StorageConfig {
+    #[serde(default)]
+    pub image_cache_directory: Option<PathBuf>,
+
+    #[serde(default)]
+    pub downloads_directory: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LoggingConfig {
+    /// Most verbose level logged: "error", "warn", "info", "debug" or "trace"
+    #[serde(default = "default_log_level")]
+    pub level: String,
+
+    /// Also write the log to rotating files in the data directory
+    #[serde(default)]
+    pub file_logging: bool,
+}
+
+fn default_log_level() -> String {
+    "debug".to_string()
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: default_log_level(),
+            file_logging: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackupConfig {
+    /// Back up the database and config on a schedule
+    #[serde(default = "default_backups_enabled")]
+    pub enabled: bool,
+
+    /// Hours between scheduled backups
+    #[serde(default = "default_backup_interval_hours")]
+    pub interval_hours: u32,
+
+    /// Backups kept of each kind, older ones are deleted
+    #[serde(default = "default_backups_kept")]
+    pub keep: u32,
+}
+
+fn default_backups_enabled() -> bool {
+    true
+}
+
+fn default_backup_interval_hours() -> u32 {
+    24
+}
+
+fn default_backups_kept() -> u32 {
+    5
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_backups_enabled(),
+            interval_hours: default_backup_interval_hours(),
+            keep: default_backups_kept(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KidsConfig {
+    /// Show only movies and shows rated for children, on a simpler home page
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Oldest age the movies and shows shown may be rated for
+    #[serde(default = "default_kids_max_age")]
+    pub max_age: u32,
+
+    /// Episodes auto-play plays in a row before stopping, 0 for no limit
+    #[serde(default = "default_kids_autoplay_limit")]
+    pub autoplay_limit: u32,
+
+    /// Hash of the PIN asked for to leave the kids profile
+    #[serde(default)]
+    pub pin_hash: Option<String>,
+}
+
+fn default_kids_max_age() -> u32 {
+    8
+}
+
+fn default_kids_autoplay_limit() -> u32 {
+    3
+}
+
+impl Default for KidsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_age: default_kids_max_age(),
+            autoplay_limit: default_kids_autoplay_limit(),
+            pin_hash: None,
+        }
+    }
+}
+
+impl KidsConfig {
+    /// Episodes auto-play plays in a row, if the kids profile limits them
+    pub fn episode_limit(&self) -> Option<u32> {
+        (self.enabled && self.autoplay_limit > 0).then_some(self.autoplay_limit)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SecurityConfig {
+    /// Where server tokens are kept
+    #[serde(default)]
+    pub token_storage: TokenStorage,
+}
+
+/// Where server tokens are kept
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenStorage {
+    /// The system keyring, or the encrypted file where no Secret Service
+    /// can be activated
+    #[default]
+    Keyring,
+    /// A file in the config directory encrypted with a key derived from the
+    /// machine
+    EncryptedFile,
+    /// A file in the config directory anyone who can read it can use
+    Plaintext,
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         let config_path = Self::config_path()?;
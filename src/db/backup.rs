@@ -0,0 +1,451 @@
+//! Snapshots of the database and config
+//!
+//! Each backup is a folder in the backups directory named after the time it
+//! was taken and why, holding a consistent copy of the database made with
+//! `VACUUM INTO` and a copy of the config file. Restoring can't replace the
+//! database while it is open, so it is staged and applied at the next start
+//! before anything connects.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, NaiveDateTime, SubsecRound, Utc};
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use tracing::{info, warn};
+
+use super::connection::{Database, DatabaseConnection};
+use super::schema::{SchemaStatus, file_schema_status};
+use crate::config::Config;
+
+/// Name of the database file inside a backup
+const DATABASE_FILE: &str = "data.db";
+
+/// Name of the config file inside a backup
+const CONFIG_FILE: &str = "config.toml";
+
+/// File in the backups directory naming the backup to restore at next start
+const PENDING_RESTORE_FILE: &str = "restore-pending";
+
+/// Timestamp format of backup folder names
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Why a backup was taken. Each reason keeps its own rotation, so frequent
+/// scheduled backups never push out the one taken before a migration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackupReason {
+    Scheduled,
+    Manual,
+    PreMigration,
+    PreRestore,
+}
+
+impl BackupReason {
+    const ALL: [Self; 4] = [
+        Self::Scheduled,
+        Self::Manual,
+        Self::PreMigration,
+        Self::PreRestore,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Scheduled => "scheduled",
+            Self::Manual => "manual",
+            Self::PreMigration => "pre-migration",
+            Self::PreRestore => "pre-restore",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Scheduled => "Scheduled",
+            Self::Manual => "Manual",
+            Self::PreMigration => "Before update",
+            Self::PreRestore => "Before restore",
+        }
+    }
+}
+
+/// A backup on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    pub reason: BackupReason,
+}
+
+impl Backup {
+    /// The backup a folder holds, None for anything else in the directory
+    fn from_dir(path: PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        let (timestamp, reason) = name.split_once('-')?;
+        let created_at = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+            .ok()?
+            .and_utc();
+        let reason = BackupReason::ALL
+            .into_iter()
+            .find(|candidate| candidate.as_str() == reason)?;
+        path.join(DATABASE_FILE).is_file().then_some(Self {
+            path,
+            created_at,
+            reason,
+        })
+    }
+
+    fn dir_name(created_at: DateTime<Utc>, reason: BackupReason) -> String {
+        format!(
+            "{}-{}",
+            created_at.format(TIMESTAMP_FORMAT),
+            reason.as_str()
+        )
+    }
+
+    /// The database copy
+    pub fn database_path(&self) -> PathBuf {
+        self.path.join(DATABASE_FILE)
+    }
+
+    /// Size of the database copy
+    pub fn size(&self) -> u64 {
+        fs::metadata(self.database_path())
+            .map(|metadata| metadata.len())
+            .unwrap_or_default()
+    }
+}
+
+/// Directory the backups are kept in
+pub fn backups_dir() -> Result<PathBuf> {
+    let db_path = Database::db_path()?;
+    let data_dir = db_path
+        .parent()
+        .context("Database path has no parent directory")?;
+    Ok(data_dir.join("backups"))
+}
+
+/// Snapshot the database and config into a new backup
+pub async fn create(db: &DatabaseConnection, reason: BackupReason) -> Result<Backup> {
+    create_in(db, &backups_dir()?, reason).await
+}
+
+async fn create_in(db: &DatabaseConnection, dir: &Path, reason: BackupReason) -> Result<Backup> {
+    // Folder names only hold whole seconds
+    let created_at = Utc::now().trunc_subsecs(0);
+    let path = dir.join(Backup::dir_name(created_at, reason));
+    if path.exists() {
+        bail!("Backup {} already exists", path.display());
+    }
+    fs::create_dir_all(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+
+    if let Err(e) = write_snapshot(db, &path).await {
+        let _ = fs::remove_dir_all(&path);
+        return Err(e);
+    }
+
+    info!("Backed up the database to {}", path.display());
+    Ok(Backup {
+        path,
+        created_at,
+        reason,
+    })
+}
+
+async fn write_snapshot(db: &DatabaseConnection, path: &Path) -> Result<()> {
+    let target = path.join(DATABASE_FILE);
+    let escaped = target.to_string_lossy().replace('\'', "''");
+    db.execute(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        format!("VACUUM INTO '{}'", escaped),
+    ))
+    .await
+    .context("Failed to copy the database")?;
+
+    let config_path = Config::config_path()?;
+    if config_path.is_file() {
+        fs::copy(&config_path, path.join(CONFIG_FILE)).context("Failed to copy the config")?;
+    }
+    Ok(())
+}
+
+/// Backups on disk, newest first
+pub fn list() -> Result<Vec<Backup>> {
+    list_in(&backups_dir()?)
+}
+
+fn list_in(dir: &Path) -> Result<Vec<Backup>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups: Vec<Backup> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| Backup::from_dir(entry.path()))
+        .collect();
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(backups)
+}
+
+/// Delete all but the newest `keep` backups of each reason, returning how
+/// many were deleted
+pub fn rotate(keep: usize) -> Result<usize> {
+    rotate_in(&backups_dir()?, keep)
+}
+
+fn rotate_in(dir: &Path, keep: usize) -> Result<usize> {
+    let backups = list_in(dir)?;
+    let mut removed = 0;
+    for reason in BackupReason::ALL {
+        for backup in backups
+            .iter()
+            .filter(|backup| backup.reason == reason)
+            .skip(keep.max(1))
+        {
+            fs::remove_dir_all(&backup.path)
+                .with_context(|| format!("Failed to delete {}", backup.path.display()))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Restore `backup` at the next start. The current database is backed up
+/// first so the restore itself can be undone.
+pub async fn stage_restore(db: &DatabaseConnection, backup: &Backup) -> Result<()> {
+    let dir = backups_dir()?;
+    create_in(db, &dir, BackupReason::PreRestore).await?;
+    stage_restore_in(&dir, backup)
+}
+
+/// Restore the newest backup taken before a migration at the next start,
+/// undoing the last database upgrade
+pub async fn stage_rollback(db: &DatabaseConnection) -> Result<Backup> {
+    let backup = rollback_target(&list()?)
+        .await
+        .context("No backup from before a database upgrade to roll back to")?;
+    stage_restore(db, &backup).await?;
+    Ok(backup)
+}
+
+/// Newest of `backups` taken before a migration that this build can open.
+/// A backup taken before a newer release upgraded the database again still
+/// holds a schema too new for it.
+async fn rollback_target(backups: &[Backup]) -> Option<Backup> {
+    for backup in backups
+        .iter()
+        .filter(|backup| backup.reason == BackupReason::PreMigration)
+    {
+        match file_schema_status(&backup.database_path()).await {
+            Ok(SchemaStatus::Newer(migrations)) => info!(
+                "Not rolling back to {}, its schema is too new ({})",
+                backup.path.display(),
+                migrations.join(", ")
+            ),
+            Ok(_) => return Some(backup.clone()),
+            Err(e) => warn!("Not rolling back to {}: {}", backup.path.display(), e),
+        }
+    }
+    None
+}
+
+fn stage_restore_in(dir: &Path, backup: &Backup) -> Result<()> {
+    let name = backup
+        .path
+        .file_name()
+        .context("Backup path has no folder name")?;
+    fs::write(dir.join(PENDING_RESTORE_FILE), name.as_encoded_bytes())
+        .context("Failed to stage the restore")?;
+    info!("Staged restore of {}", backup.path.display());
+    Ok(())
+}
+
+/// Apply a staged restore, replacing the database and config with the
+/// backup. Runs at startup before the config is loaded and the database is
+/// opened. Returns the restored backup.
+pub fn apply_pending_restore() -> Result<Option<Backup>> {
+    apply_pending_restore_in(
+        &backups_dir()?,
+        &Database::db_path()?,
+        &Config::config_path()?,
+    )
+}
+
+fn apply_pending_restore_in(
+    dir: &Path,
+    db_path: &Path,
+    config_path: &Path,
+) -> Result<Option<Backup>> {
+    let marker = dir.join(PENDING_RESTORE_FILE);
+    let Ok(name) = fs::read_to_string(&marker) else {
+        return Ok(None);
+    };
+    // Clear the marker first so a backup that can't be restored is only
+    // tried once
+    fs::remove_file(&marker).context("Failed to clear the staged restore")?;
+
+    let Some(backup) = Backup::from_dir(dir.join(name.trim())) else {
+        bail!("Staged backup {} no longer exists", name.trim());
+    };
+
+    // Copy next to the database and rename over it, so an interrupted
+    // restore leaves the old database in place
+    let staged = db_path.with_extension("db.restoring");
+    fs::copy(backup.path.join(DATABASE_FILE), &staged)
+        .context("Failed to copy the backup database")?;
+    for suffix in ["-wal", "-shm"] {
+        let mut journal = db_path.as_os_str().to_owned();
+        journal.push(suffix);
+        let journal = PathBuf::from(journal);
+        if journal.exists() {
+            fs::remove_file(&journal)
+                .with_context(|| format!("Failed to remove {}", journal.display()))?;
+        }
+    }
+    fs::rename(&staged, db_path).context("Failed to replace the database")?;
+
+    let backup_config = backup.path.join(CONFIG_FILE);
+    if backup_config.is_file() {
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent).context("Failed to create config directory")?;
+        }
+        if let Err(e) = fs::copy(&backup_config, config_path) {
+            warn!("Restored the database but not the config: {}", e);
+        }
+    }
+
+    info!("Restored backup {}", backup.path.display());
+    Ok(Some(backup))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fake_backup(dir: &Path, timestamp: &str, reason: BackupReason) -> PathBuf {
+        let path = dir.join(format!("{}-{}", timestamp, reason.as_str()));
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join(DATABASE_FILE), timestamp).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_backups_are_listed_newest_first() {
+        let dir = TempDir::new().unwrap();
+        fake_backup(dir.path(), "20250101T000000Z", BackupReason::Scheduled);
+        fake_backup(dir.path(), "20250301T000000Z", BackupReason::PreMigration);
+        fake_backup(dir.path(), "20250201T000000Z", BackupReason::Manual);
+        fs::create_dir_all(dir.path().join("unrelated")).unwrap();
+        // A folder without a database copy is an interrupted backup
+        fs::create_dir_all(dir.path().join("20250401T000000Z-scheduled")).unwrap();
+
+        let reasons: Vec<BackupReason> = list_in(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|backup| backup.reason)
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                BackupReason::PreMigration,
+                BackupReason::Manual,
+                BackupReason::Scheduled
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rotation_keeps_the_newest_of_each_reason() {
+        let dir = TempDir::new().unwrap();
+        let migration = fake_backup(dir.path(), "20250101T000000Z", BackupReason::PreMigration);
+        for day in 1..=4 {
+            fake_backup(
+                dir.path(),
+                &format!("2025020{}T000000Z", day),
+                BackupReason::Scheduled,
+            );
+        }
+
+        assert_eq!(rotate_in(dir.path(), 2).unwrap(), 2);
+        let remaining: Vec<String> = list_in(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|backup| backup.created_at.format("%d.%m").to_string())
+            .collect();
+        assert_eq!(remaining, ["04.02", "03.02", "01.01"]);
+        assert!(migration.exists());
+    }
+
+    /// A pre-migration backup of a database with these migrations applied
+    async fn migrated_backup(dir: &Path, timestamp: &str, migrations: &[&str]) -> Backup {
+        let path = fake_backup(dir, timestamp, BackupReason::PreMigration);
+        let database = path.join(DATABASE_FILE);
+        fs::remove_file(&database).unwrap();
+        let db = sea_orm::Database::connect(format!("sqlite://{}?mode=rwc", database.display()))
+            .await
+            .unwrap();
+        db.execute_unprepared(
+            "CREATE TABLE seaql_migrations (version TEXT PRIMARY KEY, applied_at BIGINT NOT NULL)",
+        )
+        .await
+        .unwrap();
+        for migration in migrations {
+            db.execute_unprepared(&format!(
+                "INSERT INTO seaql_migrations VALUES ('{}', 0)",
+                migration
+            ))
+            .await
+            .unwrap();
+        }
+        db.close().await.unwrap();
+        Backup::from_dir(path).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rollback_skips_backups_of_a_newer_schema() {
+        let dir = TempDir::new().unwrap();
+        let supported = migrated_backup(
+            dir.path(),
+            "20250101T000000Z",
+            &["m20250101_000001_create_initial_schema"],
+        )
+        .await;
+        migrated_backup(
+            dir.path(),
+            "20250201T000000Z",
+            &[
+                "m20250101_000001_create_initial_schema",
+                "m29991231_000001_future",
+            ],
+        )
+        .await;
+        fake_backup(dir.path(), "20250301T000000Z", BackupReason::Scheduled);
+
+        let backups = list_in(dir.path()).unwrap();
+        assert_eq!(rollback_target(&backups).await, Some(supported));
+    }
+
+    #[test]
+    fn test_staged_restore_replaces_the_database_once() {
+        let dir = TempDir::new().unwrap();
+        let data = TempDir::new().unwrap();
+        let db_path = data.path().join("data.db");
+        let config_path = data.path().join("config.toml");
+        fs::write(&db_path, "current").unwrap();
+        fs::write(data.path().join("data.db-wal"), "journal").unwrap();
+
+        let path = fake_backup(dir.path(), "20250101T000000Z", BackupReason::Scheduled);
+        fs::write(path.join(CONFIG_FILE), "restored config").unwrap();
+        stage_restore_in(dir.path(), &Backup::from_dir(path).unwrap()).unwrap();
+
+        let restored = apply_pending_restore_in(dir.path(), &db_path, &config_path).unwrap();
+        assert_eq!(restored.unwrap().reason, BackupReason::Scheduled);
+        assert_eq!(fs::read_to_string(&db_path).unwrap(), "20250101T000000Z");
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), "restored config");
+        assert!(!data.path().join("data.db-wal").exists());
+
+        assert_eq!(
+            apply_pending_restore_in(dir.path(), &db_path, &config_path).unwrap(),
+            None
+        );
+    }
+}
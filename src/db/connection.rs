@@ -1,10 +1,13 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
 use sea_orm::{ConnectOptions, Database as SeaOrmDatabase, DatabaseConnection as SeaOrmConnection};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::info;
 
+use super::backup::{self, BackupReason};
+use super::schema::{NewerSchemaError, SchemaStatus, schema_status};
+
 pub type DatabaseConnection = Arc<SeaOrmConnection>;
 
 pub struct Database {
@@ -76,34 +79,84 @@ impl Database {
     }
 
     /// Get the default database path
-    fn db_path() -> Result<PathBuf> {
+    pub fn db_path() -> Result<PathBuf> {
         let data_dir = dirs::data_dir().context("Failed to get data directory")?;
         Ok(data_dir.join("reel").join("data.db"))
     }
 
-    /// Run migrations
+    /// Close the connection, waiting for its pool to shut down. Fails while
+    /// other clones of the connection are still in use.
+    pub async fn close(self) -> Result<()> {
+        let connection = Arc::try_unwrap(self.connection)
+            .map_err(|_| anyhow!("Database connection is still in use"))?;
+        connection.close().await.context("Failed to close database")
+    }
+
+    /// Run migrations.
+    ///
+    /// Databases upgraded by a newer release are refused with a
+    /// [`NewerSchemaError`]. Before upgrading an existing database it is
+    /// backed up and the migrations are tried on a copy, so a failing
+    /// migration leaves the database as it was.
     pub async fn migrate(&self) -> Result<()> {
         use crate::db::migrations::Migrator;
         use sea_orm_migration::MigratorTrait;
 
         info!("Running database migrations");
 
-        // Get pending migrations count for the event
-        let pending_count = Migrator::get_pending_migrations(&*self.connection)
+        match schema_status(&self.connection)
+            .await
+            .context("Failed to check the database schema")?
+        {
+            SchemaStatus::Newer(migrations) => {
+                return Err(NewerSchemaError { migrations }.into());
+            }
+            SchemaStatus::UpToDate => {
+                info!("No pending migrations to apply");
+                return Ok(());
+            }
+            SchemaStatus::Pending(count) => {
+                info!("Upgrading the database with {} migrations", count);
+                let backup = backup::create(&self.connection, BackupReason::PreMigration)
+                    .await
+                    .context("Failed to back up the database before migrating")?;
+                Self::dry_run_migrations(&backup.database_path())
+                    .await
+                    .context(
+                        "Migrations failed on a copy of the database, it was left unchanged",
+                    )?;
+            }
+            SchemaStatus::Fresh => {}
+        }
+
+        Migrator::up(&*self.connection, None)
             .await
-            .context("Failed to get pending migrations")?
-            .len();
+            .context("Failed to run migrations")?;
 
-        if pending_count > 0 {
-            Migrator::up(&*self.connection, None)
-                .await
-                .context("Failed to run migrations")?;
+        info!("Database migrations completed successfully");
+        Ok(())
+    }
+
+    /// Run the migrations on a scratch copy of the database at `source`
+    async fn dry_run_migrations(source: &Path) -> Result<()> {
+        use crate::db::migrations::Migrator;
+        use sea_orm_migration::MigratorTrait;
 
-            info!("Database migrations completed successfully");
-        } else {
-            info!("No pending migrations to apply");
+        let scratch =
+            std::env::temp_dir().join(format!("reel-migration-check-{}.db", uuid::Uuid::new_v4()));
+        std::fs::copy(source, &scratch).context("Failed to copy the database")?;
+
+        let copy = Self::connect(&scratch).await?;
+        let result = Migrator::up(&*copy.connection, None).await;
+        copy.close().await?;
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = scratch.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
         }
 
+        result?;
+        info!("Migrations succeeded on a copy of the database");
         Ok(())
     }
 }
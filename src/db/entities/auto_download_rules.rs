@@ -0,0 +1,39 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::offline_content::QualityPreset;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "auto_download_rules")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub show_id: String,
+    pub episode_count: i32,
+    pub quality: String,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::media_items::Entity",
+        from = "Column::ShowId",
+        to = "super::media_items::Column::Id"
+    )]
+    MediaItem,
+}
+
+impl Related<super::media_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MediaItem.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// Quality episodes are downloaded at
+    pub fn get_quality_preset(&self) -> Option<QualityPreset> {
+        QualityPreset::from_str(&self.quality)
+    }
+}
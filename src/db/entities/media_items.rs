@@ -1,10 +1,13 @@
 use crate::models::{
-    Episode, MediaItem, Movie, MusicAlbum, MusicTrack, Person, Photo, Season, Show,
+    Episode, MediaItem, MediaVersion, Movie, MusicAlbum, MusicTrack, Person, Photo, Season, Show,
 };
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+/// Smallest video height counted as 4K, allowing for cropped widescreen encodes
+pub const UHD_MIN_HEIGHT: u32 = 1500;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "media_items")]
 pub struct Model {
@@ -150,6 +153,110 @@ impl Model {
             .unwrap_or_default()
     }
 
+    /// Ids of a movie or show in outside databases, like "imdb://tt0111161"
+    pub fn get_external_ids(&self) -> Vec<String> {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("external_ids"))
+            .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Studios or networks that made a movie or show
+    pub fn get_studios(&self) -> Vec<String> {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("studios"))
+            .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Tags set on a movie or show on its server
+    pub fn get_tags(&self) -> Vec<String> {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("tags"))
+            .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Collections a movie or show is in on its server
+    pub fn get_collections(&self) -> Vec<String> {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("collections"))
+            .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Parental rating of a movie or show, like "PG-13"
+    pub fn get_content_rating(&self) -> Option<String> {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("content_rating"))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    }
+
+    /// Vertical resolution of the main video stream, recorded for movies
+    pub fn get_video_height(&self) -> Option<u32> {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("video_height"))
+            .and_then(|v| v.as_u64())
+            .map(|h| h as u32)
+    }
+
+    /// Whether the video is 4K
+    pub fn is_uhd(&self) -> bool {
+        self.get_video_height()
+            .is_some_and(|height| height >= UHD_MIN_HEIGHT)
+    }
+
+    /// HDR format of the main video stream, like "HDR10" or "Dolby Vision"
+    pub fn get_hdr_format(&self) -> Option<String> {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("hdr_format"))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    }
+
+    /// Whether an audio stream is Dolby Atmos
+    pub fn has_atmos(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("atmos"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Files the server holds for a movie
+    pub fn get_versions(&self) -> Vec<MediaVersion> {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("versions"))
+            .and_then(|v| serde_json::from_value::<Vec<MediaVersion>>(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Short labels of the video and audio quality, like "4K", "DV" and "Atmos"
+    pub fn get_quality_badges(&self) -> Vec<&'static str> {
+        let mut badges = Vec::new();
+        if self.is_uhd() {
+            badges.push("4K");
+        }
+        match self.get_hdr_format().as_deref() {
+            Some("Dolby Vision") => badges.push("DV"),
+            Some(_) => badges.push("HDR"),
+            None => {}
+        }
+        if self.has_atmos() {
+            badges.push("Atmos");
+        }
+        badges
+    }
+
     pub fn get_metadata<T: for<'de> Deserialize<'de>>(&self) -> Option<T> {
         self.metadata
             .as_ref()
@@ -226,6 +333,39 @@ impl TryFrom<Model> for MediaItem {
                     .get("playback_position_ms")
                     .and_then(|v| v.as_u64())
                     .map(Duration::from_millis);
+                let video_height = metadata
+                    .get("video_height")
+                    .and_then(|v| v.as_u64())
+                    .map(|h| h as u32);
+                let hdr_format = metadata
+                    .get("hdr_format")
+                    .and_then(|v| v.as_str())
+                    .map(String::from);
+                let atmos = metadata
+                    .get("atmos")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let versions = metadata
+                    .get("versions")
+                    .and_then(|v| serde_json::from_value::<Vec<MediaVersion>>(v.clone()).ok())
+                    .unwrap_or_default();
+                let external_ids = metadata
+                    .get("external_ids")
+                    .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                    .unwrap_or_default();
+                let studios = metadata
+                    .get("studios")
+                    .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                    .unwrap_or_default();
+                let tags = metadata
+                    .get("tags")
+                    .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                    .unwrap_or_default();
+                let collections = metadata
+                    .get("collections")
+                    .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                    .unwrap_or_default();
+                let content_rating = model.get_content_rating();
 
                 // Deserialize intro marker if both start and end are present
                 let intro_marker = match (model.intro_marker_start_ms, model.intro_marker_end_ms) {
@@ -269,6 +409,15 @@ impl TryFrom<Model> for MediaItem {
                     playback_position,
                     intro_marker,
                     credits_marker,
+                    video_height,
+                    hdr_format,
+                    atmos,
+                    versions,
+                    external_ids,
+                    studios,
+                    tags,
+                    collections,
+                    content_rating,
                 }))
             }
             "show" => {
@@ -295,6 +444,23 @@ impl TryFrom<Model> for MediaItem {
                     .and_then(|v| v.as_str())
                     .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
                     .map(|dt| dt.with_timezone(&chrono::Utc));
+                let external_ids = metadata
+                    .get("external_ids")
+                    .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                    .unwrap_or_default();
+                let studios = metadata
+                    .get("studios")
+                    .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                    .unwrap_or_default();
+                let tags = metadata
+                    .get("tags")
+                    .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                    .unwrap_or_default();
+                let collections = metadata
+                    .get("collections")
+                    .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                    .unwrap_or_default();
+                let content_rating = model.get_content_rating();
 
                 Ok(MediaItem::Show(Show {
                     id: model.id.clone(),
@@ -313,6 +479,11 @@ impl TryFrom<Model> for MediaItem {
                     watched_episode_count,
                     total_episode_count,
                     last_watched_at,
+                    external_ids,
+                    studios,
+                    tags,
+                    collections,
+                    content_rating,
                 }))
             }
             "episode" => {
@@ -389,6 +560,7 @@ impl TryFrom<Model> for MediaItem {
                     show_poster_url,
                     intro_marker,
                     credits_marker,
+                    video_height: model.get_video_height(),
                 }))
             }
             "album" => {
@@ -1,26 +1,37 @@
 pub mod auth_tokens;
+pub mod auto_download_rules;
 pub mod cache_chunks;
 pub mod cache_download_queue;
 pub mod cache_entries;
 pub mod cache_headers;
 pub mod cache_quality_variants;
 pub mod cache_statistics;
+pub mod favorites;
+pub mod hidden_collections;
+pub mod hidden_items;
 pub mod home_section_items;
 pub mod home_sections;
 pub mod libraries;
+pub mod library_sync_exclusions;
 pub mod media_items;
 pub mod media_people;
 pub mod offline_content;
 pub mod people;
 pub mod playback_progress;
 pub mod playback_sync_queue;
+pub mod skip_preferences;
 pub mod sources;
 pub mod sync_status;
+pub mod track_preferences;
 
 // Re-export entities for convenience
 pub use auth_tokens::{
     ActiveModel as AuthTokenActiveModel, Entity as AuthToken, Model as AuthTokenModel,
 };
+pub use auto_download_rules::{
+    ActiveModel as AutoDownloadRuleActiveModel, Entity as AutoDownloadRule,
+    Model as AutoDownloadRuleModel,
+};
 pub use cache_chunks::{
     ActiveModel as CacheChunkActiveModel, Entity as CacheChunk, Model as CacheChunkModel,
 };
@@ -42,6 +53,16 @@ pub use cache_statistics::{
     ActiveModel as CacheStatisticsActiveModel, Entity as CacheStatistics,
     Model as CacheStatisticsModel,
 };
+pub use favorites::{
+    ActiveModel as FavoriteActiveModel, Entity as Favorite, Model as FavoriteModel,
+};
+pub use hidden_collections::{
+    ActiveModel as HiddenCollectionActiveModel, Entity as HiddenCollection,
+    Model as HiddenCollectionModel,
+};
+pub use hidden_items::{
+    ActiveModel as HiddenItemActiveModel, Entity as HiddenItem, Model as HiddenItemModel,
+};
 pub use home_section_items::{
     ActiveModel as HomeSectionItemActiveModel, Entity as HomeSectionItem,
     Model as HomeSectionItemModel,
@@ -50,6 +71,10 @@ pub use home_sections::{
     ActiveModel as HomeSectionActiveModel, Entity as HomeSection, Model as HomeSectionModel,
 };
 pub use libraries::{ActiveModel as LibraryActiveModel, Entity as Library, Model as LibraryModel};
+pub use library_sync_exclusions::{
+    ActiveModel as LibrarySyncExclusionActiveModel, Entity as LibrarySyncExclusion,
+    Model as LibrarySyncExclusionModel,
+};
 pub use media_items::{
     ActiveModel as MediaItemActiveModel, Entity as MediaItem, Model as MediaItemModel,
 };
@@ -57,6 +82,10 @@ pub use media_people::{
     ActiveModel as MediaPersonActiveModel, Entity as MediaPerson, Model as MediaPersonModel,
     PersonType,
 };
+pub use offline_content::{
+    ActiveModel as OfflineContentActiveModel, DownloadStatus, Entity as OfflineContent,
+    Model as OfflineContentModel, QualityPreset,
+};
 pub use people::{ActiveModel as PersonActiveModel, Entity as Person, Model as PersonModel};
 pub use playback_progress::{
     ActiveModel as PlaybackProgressActiveModel, Entity as PlaybackProgress,
@@ -66,7 +95,15 @@ pub use playback_sync_queue::{
     ActiveModel as PlaybackSyncQueueActiveModel, Entity as PlaybackSyncQueue,
     Model as PlaybackSyncQueueModel, PlaybackSyncStatus, SyncChangeType,
 };
+pub use skip_preferences::{
+    ActiveModel as SkipPreferenceActiveModel, Entity as SkipPreference,
+    Model as SkipPreferenceModel,
+};
 pub use sources::{ActiveModel as SourceActiveModel, Entity as Source, Model as SourceModel};
 pub use sync_status::{
     ActiveModel as SyncStatusActiveModel, Entity as SyncStatus, Model as SyncStatusModel,
 };
+pub use track_preferences::{
+    ActiveModel as TrackPreferenceActiveModel, Entity as TrackPreference,
+    Model as TrackPreferenceModel,
+};
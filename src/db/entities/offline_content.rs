@@ -12,6 +12,16 @@ pub struct Model {
     pub quality: Option<String>,
     pub downloaded_at: DateTime,
     pub last_accessed: Option<DateTime>,
+    pub status: String, // 'queued' | 'downloading' | 'completed' | 'failed'
+    pub downloaded_bytes: i64,
+    pub error_message: Option<String>,
+    pub auto_downloaded: bool,
+    pub pinned: bool,
+    /// ETag or Last-Modified of the file on the server, for resuming
+    pub validator: Option<String>,
+    /// SHA-256 of the first `downloaded_bytes` bytes, the whole file once
+    /// completed
+    pub checksum: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -32,6 +42,40 @@ impl Related<super::media_items::Entity> for Entity {
 
 impl ActiveModelBehavior for ActiveModel {}
 
+/// State of an offline download
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DownloadStatus {
+    Queued,
+    Downloading,
+    Completed,
+    Failed,
+}
+
+impl std::fmt::Display for DownloadStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DownloadStatus::Queued => write!(f, "queued"),
+            DownloadStatus::Downloading => write!(f, "downloading"),
+            DownloadStatus::Completed => write!(f, "completed"),
+            DownloadStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl std::str::FromStr for DownloadStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "queued" => Ok(DownloadStatus::Queued),
+            "downloading" => Ok(DownloadStatus::Downloading),
+            "completed" => Ok(DownloadStatus::Completed),
+            "failed" => Ok(DownloadStatus::Failed),
+            _ => Err(format!("Invalid download status: {}", s)),
+        }
+    }
+}
+
 // Quality preset enum
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum QualityPreset {
@@ -64,6 +108,47 @@ impl QualityPreset {
         }
     }
 
+    /// All presets, from best to smallest
+    pub fn all() -> [QualityPreset; 5] {
+        [
+            QualityPreset::Original,
+            QualityPreset::Ultra,
+            QualityPreset::High,
+            QualityPreset::Medium,
+            QualityPreset::Low,
+        ]
+    }
+
+    /// Human readable name for quality pickers
+    pub fn label(&self) -> &'static str {
+        match self {
+            QualityPreset::Low => "480p",
+            QualityPreset::Medium => "720p",
+            QualityPreset::High => "1080p",
+            QualityPreset::Ultra => "4K",
+            QualityPreset::Original => "Original",
+        }
+    }
+
+    /// Video bitrate cap in kbps when transcoding to this preset
+    pub fn max_video_bitrate_kbps(&self) -> u32 {
+        match self {
+            QualityPreset::Low => 2000,
+            QualityPreset::Medium => 4000,
+            QualityPreset::High => 8000,
+            QualityPreset::Ultra => 20000,
+            QualityPreset::Original => 0, // No limit
+        }
+    }
+
+    /// Whether a source of the given height has to be transcoded to fit this preset
+    pub fn requires_transcode(&self, source_height: u32) -> bool {
+        match self {
+            QualityPreset::Original => false,
+            _ => source_height > self.get_max_resolution().1,
+        }
+    }
+
     pub fn get_max_resolution(&self) -> (u32, u32) {
         match self {
             QualityPreset::Low => (854, 480),
@@ -76,6 +161,23 @@ impl QualityPreset {
 }
 
 impl Model {
+    /// Get the status as an enum
+    pub fn get_status(&self) -> Result<DownloadStatus, String> {
+        self.status.parse()
+    }
+
+    /// Whether the download finished and the file is ready for playback
+    pub fn is_playable(&self) -> bool {
+        self.status == DownloadStatus::Completed.to_string() && self.file_exists()
+    }
+
+    /// Download progress from 0.0 to 1.0, if the total size is known
+    pub fn progress(&self) -> Option<f64> {
+        self.file_size_bytes
+            .filter(|total| *total > 0)
+            .map(|total| (self.downloaded_bytes as f64 / total as f64).min(1.0))
+    }
+
     pub fn get_quality_preset(&self) -> Option<QualityPreset> {
         self.quality
             .as_ref()
@@ -89,6 +191,11 @@ impl Model {
             .unwrap_or(0.0)
     }
 
+    /// Where the file is written while downloading
+    pub fn partial_path(&self) -> std::path::PathBuf {
+        std::path::PathBuf::from(format!("{}.part", self.file_path))
+    }
+
     /// Check if file exists on disk
     pub fn file_exists(&self) -> bool {
         std::path::Path::new(&self.file_path).exists()
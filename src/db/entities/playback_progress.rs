@@ -18,6 +18,9 @@ pub struct Model {
     pub play_queue_version: Option<i32>,
     pub play_queue_item_id: Option<i64>,
     pub source_id: Option<i32>,
+    /// Starts in milliseconds of the chapters listened to the end
+    #[sea_orm(column_type = "Json", nullable)]
+    pub completed_chapters: Option<Json>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -79,4 +82,15 @@ impl Model {
     pub fn get_duration(&self) -> std::time::Duration {
         std::time::Duration::from_millis(self.duration_ms as u64)
     }
+
+    /// Starts of the chapters listened to the end
+    pub fn get_completed_chapters(&self) -> Vec<std::time::Duration> {
+        self.completed_chapters
+            .as_ref()
+            .and_then(|json| serde_json::from_value::<Vec<u64>>(json.clone()).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(std::time::Duration::from_millis)
+            .collect()
+    }
 }
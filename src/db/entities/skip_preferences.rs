@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How a show overrides the auto-skip settings for its intros and credits:
+/// "always", "ask" or "never", or NULL to follow the settings
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "skip_preferences")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub show_id: String,
+    pub intro: Option<String>,
+    pub credits: Option<String>,
+    pub updated_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::media_items::Entity",
+        from = "Column::ShowId",
+        to = "super::media_items::Column::Id"
+    )]
+    MediaItem,
+}
+
+impl Related<super::media_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MediaItem.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
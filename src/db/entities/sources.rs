@@ -20,6 +20,10 @@ pub struct Model {
     pub connection_quality: Option<String>,     // "local", "remote", or "relay"
     pub auth_status: String,                    // "authenticated", "auth_required", or "unknown"
     pub last_auth_check: Option<DateTime>,      // When authentication was last checked
+    pub mac_address: Option<String>,            // MAC address used for Wake-on-LAN
+    pub client_identity: Option<Json>,          // Device name/identifier reported to the server
+    pub active_user_id: Option<String>,         // Local user whose watch state is shown
+    pub connection_preferences: Option<Json>,   // Which connections may be used, and in what order
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
@@ -64,6 +68,10 @@ impl Model {
         self.source_type == "jellyfin"
     }
 
+    pub fn is_emby(&self) -> bool {
+        self.source_type == "emby"
+    }
+
     pub fn is_local(&self) -> bool {
         self.source_type == "local"
     }
@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Audio and subtitle tracks chosen for a movie or episode, or for a show as
+/// the default for its episodes. Tracks are stored by name, since track IDs
+/// differ between files. A subtitle file loaded from disk is kept per item.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "track_preferences")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub media_id: String,
+    pub audio_track: Option<String>,
+    pub subtitle_track: Option<String>,
+    pub subtitle_file: Option<String>,
+    pub updated_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::media_items::Entity",
+        from = "Column::MediaId",
+        to = "super::media_items::Column::Id"
+    )]
+    MediaItem,
+}
+
+impl Related<super::media_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MediaItem.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Add mac_address to sources table for Wake-on-LAN support
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sources::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Sources::MacAddress).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sources::Table)
+                    .drop_column(Sources::MacAddress)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Sources {
+    Table,
+    MacAddress,
+}
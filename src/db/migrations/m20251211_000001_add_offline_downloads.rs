@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Track the download queue state on offline_content
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OfflineContent::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(OfflineContent::Status)
+                            .string()
+                            .not_null()
+                            .default("queued"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OfflineContent::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(OfflineContent::DownloadedBytes)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OfflineContent::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(OfflineContent::ErrorMessage).string().null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_offline_content_status")
+                    .table(OfflineContent::Table)
+                    .col(OfflineContent::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_offline_content_status")
+                    .table(OfflineContent::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [
+            OfflineContent::Status,
+            OfflineContent::DownloadedBytes,
+            OfflineContent::ErrorMessage,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(OfflineContent::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum OfflineContent {
+    Table,
+    Status,
+    DownloadedBytes,
+    ErrorMessage,
+}
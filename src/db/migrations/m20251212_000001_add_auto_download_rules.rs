@@ -0,0 +1,103 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Per-show rules for keeping the next unwatched episodes downloaded
+        manager
+            .create_table(
+                Table::create()
+                    .table(AutoDownloadRules::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AutoDownloadRules::ShowId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AutoDownloadRules::EpisodeCount)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AutoDownloadRules::Quality)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AutoDownloadRules::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_auto_download_rules_show")
+                            .from(AutoDownloadRules::Table, AutoDownloadRules::ShowId)
+                            .to(MediaItems::Table, MediaItems::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Downloads queued by a rule are managed by the scheduler; manual ones are left alone
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OfflineContent::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(OfflineContent::AutoDownloaded)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OfflineContent::Table)
+                    .drop_column(OfflineContent::AutoDownloaded)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(AutoDownloadRules::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum AutoDownloadRules {
+    Table,
+    ShowId,
+    EpisodeCount,
+    Quality,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum MediaItems {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum OfflineContent {
+    Table,
+    AutoDownloaded,
+}
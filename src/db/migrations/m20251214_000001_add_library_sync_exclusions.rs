@@ -0,0 +1,87 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Libraries the user chose not to sync. The title is kept so they can be
+        // listed and re-included without their data being in the libraries table
+        manager
+            .create_table(
+                Table::create()
+                    .table(LibrarySyncExclusions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LibrarySyncExclusions::LibraryId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LibrarySyncExclusions::SourceId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LibrarySyncExclusions::LibraryTitle)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LibrarySyncExclusions::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_library_sync_exclusions_source")
+                            .from(
+                                LibrarySyncExclusions::Table,
+                                LibrarySyncExclusions::SourceId,
+                            )
+                            .to(Sources::Table, Sources::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_library_sync_exclusions_source")
+                    .table(LibrarySyncExclusions::Table)
+                    .col(LibrarySyncExclusions::SourceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LibrarySyncExclusions::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum LibrarySyncExclusions {
+    Table,
+    LibraryId,
+    SourceId,
+    LibraryTitle,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Sources {
+    Table,
+    Id,
+}
@@ -0,0 +1,87 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Items the user marked as favorite (Jellyfin) or liked (Plex). Kept apart
+        // from media_items so syncing item metadata never resets the flag
+        manager
+            .create_table(
+                Table::create()
+                    .table(Favorites::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Favorites::MediaId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Favorites::SourceId).string().not_null())
+                    .col(
+                        ColumnDef::new(Favorites::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_favorites_media")
+                            .from(Favorites::Table, Favorites::MediaId)
+                            .to(MediaItems::Table, MediaItems::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_favorites_source")
+                            .from(Favorites::Table, Favorites::SourceId)
+                            .to(Sources::Table, Sources::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_favorites_source")
+                    .table(Favorites::Table)
+                    .col(Favorites::SourceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Favorites::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Favorites {
+    Table,
+    MediaId,
+    SourceId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum MediaItems {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Sources {
+    Table,
+    Id,
+}
@@ -0,0 +1,87 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Items the user isn't interested in, left out of the home page rows.
+        // Local only, backends have no equivalent
+        manager
+            .create_table(
+                Table::create()
+                    .table(HiddenItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HiddenItems::MediaId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(HiddenItems::SourceId).string().not_null())
+                    .col(
+                        ColumnDef::new(HiddenItems::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_hidden_items_media")
+                            .from(HiddenItems::Table, HiddenItems::MediaId)
+                            .to(MediaItems::Table, MediaItems::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_hidden_items_source")
+                            .from(HiddenItems::Table, HiddenItems::SourceId)
+                            .to(Sources::Table, Sources::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hidden_items_source")
+                    .table(HiddenItems::Table)
+                    .col(HiddenItems::SourceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HiddenItems::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum HiddenItems {
+    Table,
+    MediaId,
+    SourceId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum MediaItems {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Sources {
+    Table,
+    Id,
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Audio and subtitle tracks the user picked, per item and per show
+        manager
+            .create_table(
+                Table::create()
+                    .table(TrackPreferences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TrackPreferences::MediaId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TrackPreferences::AudioTrack).string().null())
+                    .col(
+                        ColumnDef::new(TrackPreferences::SubtitleTrack)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(TrackPreferences::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_track_preferences_media")
+                            .from(TrackPreferences::Table, TrackPreferences::MediaId)
+                            .to(MediaItems::Table, MediaItems::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TrackPreferences::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum TrackPreferences {
+    Table,
+    MediaId,
+    AudioTrack,
+    SubtitleTrack,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum MediaItems {
+    Table,
+    Id,
+}
@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Add subtitle_file to track_preferences for subtitles loaded from disk
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TrackPreferences::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(TrackPreferences::SubtitleFile)
+                            .string()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TrackPreferences::Table)
+                    .drop_column(TrackPreferences::SubtitleFile)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum TrackPreferences {
+    Table,
+    SubtitleFile,
+}
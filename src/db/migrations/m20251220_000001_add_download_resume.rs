@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Validator of the file on the server, so a resumed download only
+        // continues the same file
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OfflineContent::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(OfflineContent::Validator).string().null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // SHA-256 of the bytes written so far, checked before resuming
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OfflineContent::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(OfflineContent::Checksum).string().null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OfflineContent::Table)
+                    .drop_column(OfflineContent::Validator)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OfflineContent::Table)
+                    .drop_column(OfflineContent::Checksum)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum OfflineContent {
+    Table,
+    Validator,
+    Checksum,
+}
@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Per-show overrides of the auto-skip settings, NULL follows them
+        manager
+            .create_table(
+                Table::create()
+                    .table(SkipPreferences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SkipPreferences::ShowId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SkipPreferences::Intro).string().null())
+                    .col(ColumnDef::new(SkipPreferences::Credits).string().null())
+                    .col(
+                        ColumnDef::new(SkipPreferences::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_skip_preferences_show")
+                            .from(SkipPreferences::Table, SkipPreferences::ShowId)
+                            .to(MediaItems::Table, MediaItems::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SkipPreferences::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SkipPreferences {
+    Table,
+    ShowId,
+    Intro,
+    Credits,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum MediaItems {
+    Table,
+    Id,
+}
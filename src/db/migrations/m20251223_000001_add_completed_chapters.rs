@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Add completed_chapters to playback_progress so audiobooks and
+        // podcasts remember which of their chapters were listened to
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PlaybackProgress::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(PlaybackProgress::CompletedChapters)
+                            .json()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PlaybackProgress::Table)
+                    .drop_column(PlaybackProgress::CompletedChapters)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum PlaybackProgress {
+    Table,
+    CompletedChapters,
+}
@@ -0,0 +1,83 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Collections whose movies and shows are left out of the home page
+        // rows. Collections are only known by name, per source
+        manager
+            .create_table(
+                Table::create()
+                    .table(HiddenCollections::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HiddenCollections::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HiddenCollections::SourceId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(HiddenCollections::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(HiddenCollections::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_hidden_collections_source")
+                            .from(HiddenCollections::Table, HiddenCollections::SourceId)
+                            .to(Sources::Table, Sources::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hidden_collections_source_name")
+                    .table(HiddenCollections::Table)
+                    .col(HiddenCollections::SourceId)
+                    .col(HiddenCollections::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HiddenCollections::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum HiddenCollections {
+    Table,
+    Id,
+    SourceId,
+    Name,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Sources {
+    Table,
+    Id,
+}
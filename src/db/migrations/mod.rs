@@ -15,6 +15,22 @@ mod m20251004_000001_add_filter_indexes;
 mod m20251120_000001_add_auth_status;
 mod m20251122_000001_add_playback_sync_queue;
 mod m20251209_000001_add_fetched_at;
+mod m20251210_000001_add_wake_on_lan;
+mod m20251211_000001_add_offline_downloads;
+mod m20251212_000001_add_auto_download_rules;
+mod m20251213_000001_add_download_pins;
+mod m20251214_000001_add_library_sync_exclusions;
+mod m20251215_000001_add_favorites;
+mod m20251216_000001_add_hidden_items;
+mod m20251217_000001_add_client_identity;
+mod m20251218_000001_add_track_preferences;
+mod m20251219_000001_add_subtitle_files;
+mod m20251220_000001_add_download_resume;
+mod m20251221_000001_add_skip_preferences;
+mod m20251222_000001_add_active_user;
+mod m20251223_000001_add_completed_chapters;
+mod m20251224_000001_add_connection_preferences;
+mod m20251225_000001_add_hidden_collections;
 
 pub struct Migrator;
 
@@ -37,6 +53,22 @@ impl MigratorTrait for Migrator {
             Box::new(m20251120_000001_add_auth_status::Migration),
             Box::new(m20251122_000001_add_playback_sync_queue::Migration),
             Box::new(m20251209_000001_add_fetched_at::Migration),
+            Box::new(m20251210_000001_add_wake_on_lan::Migration),
+            Box::new(m20251211_000001_add_offline_downloads::Migration),
+            Box::new(m20251212_000001_add_auto_download_rules::Migration),
+            Box::new(m20251213_000001_add_download_pins::Migration),
+            Box::new(m20251214_000001_add_library_sync_exclusions::Migration),
+            Box::new(m20251215_000001_add_favorites::Migration),
+            Box::new(m20251216_000001_add_hidden_items::Migration),
+            Box::new(m20251217_000001_add_client_identity::Migration),
+            Box::new(m20251218_000001_add_track_preferences::Migration),
+            Box::new(m20251219_000001_add_subtitle_files::Migration),
+            Box::new(m20251220_000001_add_download_resume::Migration),
+            Box::new(m20251221_000001_add_skip_preferences::Migration),
+            Box::new(m20251222_000001_add_active_user::Migration),
+            Box::new(m20251223_000001_add_completed_chapters::Migration),
+            Box::new(m20251224_000001_add_connection_preferences::Migration),
+            Box::new(m20251225_000001_add_hidden_collections::Migration),
         ]
     }
 }
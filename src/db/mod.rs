@@ -1,6 +1,8 @@
+pub mod backup;
 pub mod connection;
 pub mod entities;
 pub mod migrations;
 pub mod repository;
+pub mod schema;
 
 pub use connection::{Database, DatabaseConnection};
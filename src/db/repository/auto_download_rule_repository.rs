@@ -0,0 +1,101 @@
+use super::{BaseRepository, Repository};
+use crate::db::entities::{
+    AutoDownloadRule, AutoDownloadRuleActiveModel, AutoDownloadRuleModel, QualityPreset,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, PaginatorTrait, Set};
+use std::sync::Arc;
+
+/// Repository trait for per-show auto-download rules
+#[async_trait]
+pub trait AutoDownloadRuleRepository: Repository<AutoDownloadRuleModel> {
+    /// Keep the next `episode_count` unwatched episodes of a show downloaded
+    async fn set_rule(
+        &self,
+        show_id: &str,
+        episode_count: i32,
+        quality: &QualityPreset,
+    ) -> Result<AutoDownloadRuleModel>;
+}
+
+#[derive(Debug)]
+pub struct AutoDownloadRuleRepositoryImpl {
+    base: BaseRepository,
+}
+
+impl AutoDownloadRuleRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            base: BaseRepository::new(db),
+        }
+    }
+}
+
+#[async_trait]
+impl Repository<AutoDownloadRuleModel> for AutoDownloadRuleRepositoryImpl {
+    type Entity = AutoDownloadRule;
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<AutoDownloadRuleModel>> {
+        Ok(AutoDownloadRule::find_by_id(id.to_string())
+            .one(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn find_all(&self) -> Result<Vec<AutoDownloadRuleModel>> {
+        Ok(AutoDownloadRule::find().all(self.base.db.as_ref()).await?)
+    }
+
+    async fn insert(&self, entity: AutoDownloadRuleModel) -> Result<AutoDownloadRuleModel> {
+        let active_model: AutoDownloadRuleActiveModel = entity.into();
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn update(&self, entity: AutoDownloadRuleModel) -> Result<AutoDownloadRuleModel> {
+        let mut active_model: AutoDownloadRuleActiveModel = entity.clone().into();
+        active_model.episode_count = Set(entity.episode_count);
+        active_model.quality = Set(entity.quality);
+        Ok(active_model.update(self.base.db.as_ref()).await?)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        AutoDownloadRule::delete_by_id(id.to_string())
+            .exec(self.base.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<u64> {
+        Ok(AutoDownloadRule::find()
+            .count(self.base.db.as_ref())
+            .await?)
+    }
+}
+
+#[async_trait]
+impl AutoDownloadRuleRepository for AutoDownloadRuleRepositoryImpl {
+    async fn set_rule(
+        &self,
+        show_id: &str,
+        episode_count: i32,
+        quality: &QualityPreset,
+    ) -> Result<AutoDownloadRuleModel> {
+        match self.find_by_id(show_id).await? {
+            Some(existing) => {
+                let mut active_model: AutoDownloadRuleActiveModel = existing.into();
+                active_model.episode_count = Set(episode_count);
+                active_model.quality = Set(quality.as_str().to_string());
+                Ok(active_model.update(self.base.db.as_ref()).await?)
+            }
+            None => {
+                let active_model = AutoDownloadRuleActiveModel {
+                    show_id: Set(show_id.to_string()),
+                    episode_count: Set(episode_count),
+                    quality: Set(quality.as_str().to_string()),
+                    created_at: Set(chrono::Utc::now().naive_utc()),
+                };
+                Ok(active_model.insert(self.base.db.as_ref()).await?)
+            }
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::db::entities::{
     CacheStatisticsActiveModel, CacheStatisticsModel, cache_chunks, cache_download_queue,
     cache_entries, cache_headers, cache_quality_variants,
 };
+use crate::services::core::storage::rebase_path;
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Utc;
@@ -14,6 +15,7 @@ use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
     QueryOrder, Set, TransactionTrait,
 };
+use std::path::Path;
 use std::sync::Arc;
 
 /// Repository for cache-related database operations
@@ -112,6 +114,20 @@ impl CacheRepositoryImpl {
             base: BaseRepository::new(db),
         }
     }
+
+    /// Point cache entries stored under `from` at the same files under `to`
+    pub async fn relocate_files(&self, from: &Path, to: &Path) -> Result<u64> {
+        let mut relocated = 0;
+        for entry in self.list_cache_entries().await? {
+            if let Some(file_path) = rebase_path(&entry.file_path, from, to) {
+                let mut active_model: CacheEntryActiveModel = entry.into();
+                active_model.file_path = Set(file_path);
+                active_model.update(self.base.db.as_ref()).await?;
+                relocated += 1;
+            }
+        }
+        Ok(relocated)
+    }
 }
 
 #[async_trait]
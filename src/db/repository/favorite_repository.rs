@@ -0,0 +1,270 @@
+use super::{BaseRepository, Repository};
+use crate::db::entities::{
+    Favorite, FavoriteActiveModel, FavoriteModel, MediaItem, favorites, media_items,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use std::sync::Arc;
+
+/// Repository trait for favorite (Jellyfin) and liked (Plex) items
+#[async_trait]
+pub trait FavoriteRepository: Repository<FavoriteModel> {
+    /// Get the favorites of a source, most recently added first
+    async fn find_by_source(&self, source_id: &str) -> Result<Vec<FavoriteModel>>;
+
+    /// Mark a media item as favorite
+    async fn add(&self, source_id: &str, media_id: &str) -> Result<FavoriteModel>;
+
+    /// Replace the favorites of a source with the set reported by its server,
+    /// ignoring items that aren't synced locally
+    async fn replace_for_source(&self, source_id: &str, media_ids: &[String]) -> Result<()>;
+}
+
+#[derive(Debug)]
+pub struct FavoriteRepositoryImpl {
+    base: BaseRepository,
+}
+
+impl FavoriteRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            base: BaseRepository::new(db),
+        }
+    }
+}
+
+#[async_trait]
+impl Repository<FavoriteModel> for FavoriteRepositoryImpl {
+    type Entity = Favorite;
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<FavoriteModel>> {
+        Ok(Favorite::find_by_id(id.to_string())
+            .one(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn find_all(&self) -> Result<Vec<FavoriteModel>> {
+        Ok(Favorite::find().all(self.base.db.as_ref()).await?)
+    }
+
+    async fn insert(&self, entity: FavoriteModel) -> Result<FavoriteModel> {
+        let active_model: FavoriteActiveModel = entity.into();
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn update(&self, entity: FavoriteModel) -> Result<FavoriteModel> {
+        let mut active_model: FavoriteActiveModel = entity.clone().into();
+        active_model.source_id = Set(entity.source_id);
+        Ok(active_model.update(self.base.db.as_ref()).await?)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        Favorite::delete_by_id(id.to_string())
+            .exec(self.base.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<u64> {
+        Ok(Favorite::find().count(self.base.db.as_ref()).await?)
+    }
+}
+
+#[async_trait]
+impl FavoriteRepository for FavoriteRepositoryImpl {
+    async fn find_by_source(&self, source_id: &str) -> Result<Vec<FavoriteModel>> {
+        Ok(Favorite::find()
+            .filter(favorites::Column::SourceId.eq(source_id))
+            .order_by_desc(favorites::Column::CreatedAt)
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn add(&self, source_id: &str, media_id: &str) -> Result<FavoriteModel> {
+        if let Some(existing) = self.find_by_id(media_id).await? {
+            return Ok(existing);
+        }
+
+        let active_model = FavoriteActiveModel {
+            media_id: Set(media_id.to_string()),
+            source_id: Set(source_id.to_string()),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+        };
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn replace_for_source(&self, source_id: &str, media_ids: &[String]) -> Result<()> {
+        let txn = self.base.db.begin().await?;
+
+        let known: Vec<String> = if media_ids.is_empty() {
+            Vec::new()
+        } else {
+            MediaItem::find()
+                .select_only()
+                .column(media_items::Column::Id)
+                .filter(media_items::Column::SourceId.eq(source_id))
+                .filter(media_items::Column::Id.is_in(media_ids.iter().cloned()))
+                .into_tuple()
+                .all(&txn)
+                .await?
+        };
+
+        Favorite::delete_many()
+            .filter(favorites::Column::SourceId.eq(source_id))
+            .exec(&txn)
+            .await?;
+
+        let now = chrono::Utc::now().naive_utc();
+        for media_id in known {
+            FavoriteActiveModel {
+                media_id: Set(media_id),
+                source_id: Set(source_id.to_string()),
+                created_at: Set(now),
+            }
+            .insert(&txn)
+            .await?;
+        }
+
+        txn.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::get_test_db_connection;
+    use crate::db::entities::{libraries, sources};
+    use crate::db::repository::MediaRepositoryImpl;
+    use chrono::Utc;
+
+    async fn setup_test_db() -> Arc<DatabaseConnection> {
+        let db = Arc::new(get_test_db_connection().await.unwrap());
+
+        for source_id in ["source_a", "source_b"] {
+            sources::ActiveModel {
+                id: Set(source_id.to_string()),
+                name: Set(source_id.to_string()),
+                source_type: Set("jellyfin".to_string()),
+                auth_provider_id: Set(None),
+                connection_url: Set(None),
+                connections: Set(None),
+                machine_id: Set(None),
+                is_owned: Set(true),
+                is_online: Set(true),
+                last_sync: Set(None),
+                last_connection_test: Set(None),
+                connection_failure_count: Set(0),
+                connection_quality: Set(None),
+                auth_status: Set("authenticated".to_string()),
+                last_auth_check: Set(None),
+                mac_address: Set(None),
+                client_identity: Set(None),
+                active_user_id: Set(None),
+                connection_preferences: Set(None),
+                created_at: Set(Utc::now().naive_utc()),
+                updated_at: Set(Utc::now().naive_utc()),
+            }
+            .insert(db.as_ref())
+            .await
+            .unwrap();
+
+            libraries::ActiveModel {
+                id: Set(format!("{}_movies", source_id)),
+                source_id: Set(source_id.to_string()),
+                title: Set("Movies".to_string()),
+                library_type: Set("movie".to_string()),
+                icon: Set(None),
+                item_count: Set(0),
+                created_at: Set(Utc::now().naive_utc()),
+                updated_at: Set(Utc::now().naive_utc()),
+            }
+            .insert(db.as_ref())
+            .await
+            .unwrap();
+        }
+
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        for (id, source_id) in [("a1", "source_a"), ("a2", "source_a"), ("b1", "source_b")] {
+            media_repo
+                .insert(media_items::Model {
+                    id: id.to_string(),
+                    library_id: format!("{}_movies", source_id),
+                    source_id: source_id.to_string(),
+                    media_type: "movie".to_string(),
+                    title: id.to_string(),
+                    sort_title: None,
+                    year: None,
+                    duration_ms: None,
+                    rating: None,
+                    poster_url: None,
+                    backdrop_url: None,
+                    overview: None,
+                    genres: None,
+                    added_at: None,
+                    updated_at: Utc::now().naive_utc(),
+                    metadata: None,
+                    parent_id: None,
+                    season_number: None,
+                    episode_number: None,
+                    intro_marker_start_ms: None,
+                    intro_marker_end_ms: None,
+                    credits_marker_start_ms: None,
+                    credits_marker_end_ms: None,
+                    fetched_at: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        db
+    }
+
+    fn ids(favorites: Vec<FavoriteModel>) -> Vec<String> {
+        let mut ids: Vec<String> = favorites.into_iter().map(|f| f.media_id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_replace_for_source_replaces_previous_favorites() {
+        let repo = FavoriteRepositoryImpl::new(setup_test_db().await);
+        repo.add("source_a", "a1").await.unwrap();
+
+        repo.replace_for_source("source_a", &["a2".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(ids(repo.find_by_source("source_a").await.unwrap()), ["a2"]);
+    }
+
+    #[tokio::test]
+    async fn test_replace_for_source_ignores_unknown_and_foreign_items() {
+        let repo = FavoriteRepositoryImpl::new(setup_test_db().await);
+
+        repo.replace_for_source(
+            "source_a",
+            &["a1".to_string(), "missing".to_string(), "b1".to_string()],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(ids(repo.find_by_source("source_a").await.unwrap()), ["a1"]);
+    }
+
+    #[tokio::test]
+    async fn test_replace_for_source_leaves_other_sources_alone() {
+        let repo = FavoriteRepositoryImpl::new(setup_test_db().await);
+        repo.add("source_b", "b1").await.unwrap();
+        repo.add("source_a", "a1").await.unwrap();
+
+        repo.replace_for_source("source_a", &[]).await.unwrap();
+
+        assert!(repo.find_by_source("source_a").await.unwrap().is_empty());
+        assert_eq!(ids(repo.find_by_source("source_b").await.unwrap()), ["b1"]);
+    }
+}
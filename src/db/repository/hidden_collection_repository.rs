@@ -0,0 +1,121 @@
+use super::{BaseRepository, Repository};
+use crate::db::entities::{
+    HiddenCollection, HiddenCollectionActiveModel, HiddenCollectionModel, hidden_collections,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use std::sync::Arc;
+
+/// Repository trait for collections hidden from the home page
+#[async_trait]
+pub trait HiddenCollectionRepository: Repository<HiddenCollectionModel> {
+    /// Get all hidden collections, most recently hidden first
+    async fn find_recent(&self) -> Result<Vec<HiddenCollectionModel>>;
+
+    /// Hide a collection of a source
+    async fn hide(&self, source_id: &str, name: &str) -> Result<HiddenCollectionModel>;
+
+    /// Show a collection of a source again
+    async fn unhide(&self, source_id: &str, name: &str) -> Result<()>;
+}
+
+#[derive(Debug)]
+pub struct HiddenCollectionRepositoryImpl {
+    base: BaseRepository,
+}
+
+impl HiddenCollectionRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            base: BaseRepository::new(db),
+        }
+    }
+}
+
+#[async_trait]
+impl Repository<HiddenCollectionModel> for HiddenCollectionRepositoryImpl {
+    type Entity = HiddenCollection;
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<HiddenCollectionModel>> {
+        let id_parsed = id.parse::<i32>()?;
+        Ok(HiddenCollection::find_by_id(id_parsed)
+            .one(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn find_all(&self) -> Result<Vec<HiddenCollectionModel>> {
+        Ok(HiddenCollection::find().all(self.base.db.as_ref()).await?)
+    }
+
+    async fn insert(&self, entity: HiddenCollectionModel) -> Result<HiddenCollectionModel> {
+        let active_model = HiddenCollectionActiveModel {
+            source_id: Set(entity.source_id),
+            name: Set(entity.name),
+            created_at: Set(entity.created_at),
+            ..Default::default()
+        };
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn update(&self, entity: HiddenCollectionModel) -> Result<HiddenCollectionModel> {
+        let mut active_model: HiddenCollectionActiveModel = entity.clone().into();
+        active_model.name = Set(entity.name);
+        Ok(active_model.update(self.base.db.as_ref()).await?)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let id_parsed = id.parse::<i32>()?;
+        HiddenCollection::delete_by_id(id_parsed)
+            .exec(self.base.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<u64> {
+        Ok(HiddenCollection::find()
+            .count(self.base.db.as_ref())
+            .await?)
+    }
+}
+
+#[async_trait]
+impl HiddenCollectionRepository for HiddenCollectionRepositoryImpl {
+    async fn find_recent(&self) -> Result<Vec<HiddenCollectionModel>> {
+        Ok(HiddenCollection::find()
+            .order_by_desc(hidden_collections::Column::CreatedAt)
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn hide(&self, source_id: &str, name: &str) -> Result<HiddenCollectionModel> {
+        if let Some(existing) = HiddenCollection::find()
+            .filter(hidden_collections::Column::SourceId.eq(source_id))
+            .filter(hidden_collections::Column::Name.eq(name))
+            .one(self.base.db.as_ref())
+            .await?
+        {
+            return Ok(existing);
+        }
+
+        let active_model = HiddenCollectionActiveModel {
+            source_id: Set(source_id.to_string()),
+            name: Set(name.to_string()),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        };
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn unhide(&self, source_id: &str, name: &str) -> Result<()> {
+        HiddenCollection::delete_many()
+            .filter(hidden_collections::Column::SourceId.eq(source_id))
+            .filter(hidden_collections::Column::Name.eq(name))
+            .exec(self.base.db.as_ref())
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use super::{BaseRepository, Repository};
+use crate::db::entities::{HiddenItem, HiddenItemActiveModel, HiddenItemModel, hidden_items};
+use anyhow::Result;
+use async_trait::async_trait;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryOrder, Set};
+use std::sync::Arc;
+
+/// Repository trait for items hidden from the home page
+#[async_trait]
+pub trait HiddenItemRepository: Repository<HiddenItemModel> {
+    /// Get all hidden items, most recently hidden first
+    async fn find_recent(&self) -> Result<Vec<HiddenItemModel>>;
+
+    /// Hide a media item
+    async fn hide(&self, source_id: &str, media_id: &str) -> Result<HiddenItemModel>;
+}
+
+#[derive(Debug)]
+pub struct HiddenItemRepositoryImpl {
+    base: BaseRepository,
+}
+
+impl HiddenItemRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            base: BaseRepository::new(db),
+        }
+    }
+}
+
+#[async_trait]
+impl Repository<HiddenItemModel> for HiddenItemRepositoryImpl {
+    type Entity = HiddenItem;
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<HiddenItemModel>> {
+        Ok(HiddenItem::find_by_id(id.to_string())
+            .one(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn find_all(&self) -> Result<Vec<HiddenItemModel>> {
+        Ok(HiddenItem::find().all(self.base.db.as_ref()).await?)
+    }
+
+    async fn insert(&self, entity: HiddenItemModel) -> Result<HiddenItemModel> {
+        let active_model: HiddenItemActiveModel = entity.into();
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn update(&self, entity: HiddenItemModel) -> Result<HiddenItemModel> {
+        let mut active_model: HiddenItemActiveModel = entity.clone().into();
+        active_model.source_id = Set(entity.source_id);
+        Ok(active_model.update(self.base.db.as_ref()).await?)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        HiddenItem::delete_by_id(id.to_string())
+            .exec(self.base.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<u64> {
+        Ok(HiddenItem::find().count(self.base.db.as_ref()).await?)
+    }
+}
+
+#[async_trait]
+impl HiddenItemRepository for HiddenItemRepositoryImpl {
+    async fn find_recent(&self) -> Result<Vec<HiddenItemModel>> {
+        Ok(HiddenItem::find()
+            .order_by_desc(hidden_items::Column::CreatedAt)
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn hide(&self, source_id: &str, media_id: &str) -> Result<HiddenItemModel> {
+        if let Some(existing) = self.find_by_id(media_id).await? {
+            return Ok(existing);
+        }
+
+        let active_model = HiddenItemActiveModel {
+            media_id: Set(media_id.to_string()),
+            source_id: Set(source_id.to_string()),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+        };
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+}
@@ -0,0 +1,110 @@
+use super::{BaseRepository, Repository};
+use crate::db::entities::{
+    LibrarySyncExclusion, LibrarySyncExclusionActiveModel, LibrarySyncExclusionModel,
+    library_sync_exclusions,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    Set,
+};
+use std::sync::Arc;
+
+/// Repository trait for libraries excluded from sync
+#[async_trait]
+pub trait LibrarySyncExclusionRepository: Repository<LibrarySyncExclusionModel> {
+    /// Get the excluded libraries of a source
+    async fn find_by_source(&self, source_id: &str) -> Result<Vec<LibrarySyncExclusionModel>>;
+
+    /// Stop syncing a library
+    async fn exclude(
+        &self,
+        source_id: &str,
+        library_id: &str,
+        library_title: &str,
+    ) -> Result<LibrarySyncExclusionModel>;
+}
+
+#[derive(Debug)]
+pub struct LibrarySyncExclusionRepositoryImpl {
+    base: BaseRepository,
+}
+
+impl LibrarySyncExclusionRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            base: BaseRepository::new(db),
+        }
+    }
+}
+
+#[async_trait]
+impl Repository<LibrarySyncExclusionModel> for LibrarySyncExclusionRepositoryImpl {
+    type Entity = LibrarySyncExclusion;
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<LibrarySyncExclusionModel>> {
+        Ok(LibrarySyncExclusion::find_by_id(id.to_string())
+            .one(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn find_all(&self) -> Result<Vec<LibrarySyncExclusionModel>> {
+        Ok(LibrarySyncExclusion::find()
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn insert(&self, entity: LibrarySyncExclusionModel) -> Result<LibrarySyncExclusionModel> {
+        let active_model: LibrarySyncExclusionActiveModel = entity.into();
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn update(&self, entity: LibrarySyncExclusionModel) -> Result<LibrarySyncExclusionModel> {
+        let mut active_model: LibrarySyncExclusionActiveModel = entity.clone().into();
+        active_model.library_title = Set(entity.library_title);
+        Ok(active_model.update(self.base.db.as_ref()).await?)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        LibrarySyncExclusion::delete_by_id(id.to_string())
+            .exec(self.base.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<u64> {
+        Ok(LibrarySyncExclusion::find()
+            .count(self.base.db.as_ref())
+            .await?)
+    }
+}
+
+#[async_trait]
+impl LibrarySyncExclusionRepository for LibrarySyncExclusionRepositoryImpl {
+    async fn find_by_source(&self, source_id: &str) -> Result<Vec<LibrarySyncExclusionModel>> {
+        Ok(LibrarySyncExclusion::find()
+            .filter(library_sync_exclusions::Column::SourceId.eq(source_id))
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn exclude(
+        &self,
+        source_id: &str,
+        library_id: &str,
+        library_title: &str,
+    ) -> Result<LibrarySyncExclusionModel> {
+        if let Some(existing) = self.find_by_id(library_id).await? {
+            return Ok(existing);
+        }
+
+        let active_model = LibrarySyncExclusionActiveModel {
+            library_id: Set(library_id.to_string()),
+            source_id: Set(source_id.to_string()),
+            library_title: Set(library_title.to_string()),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+        };
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+}
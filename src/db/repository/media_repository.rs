@@ -1,3 +1,4 @@
+use super::playback_repository::{active_user_progress, active_user_progress_join};
 use super::{BaseRepository, Repository};
 use crate::db::entities::{MediaItem, MediaItemActiveModel, MediaItemModel, media_items};
 use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
@@ -6,8 +7,8 @@ use async_trait::async_trait;
 #[cfg(debug_assertions)]
 use sea_orm::QueryTrait;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, Order, PaginatorTrait,
-    QueryFilter, QueryOrder, QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, JoinType, Order,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
 };
 use std::sync::Arc;
 
@@ -109,6 +110,13 @@ pub trait MediaRepository: Repository<MediaItemModel> {
         after_episode: i32,
     ) -> Result<Option<MediaItemModel>>;
 
+    /// Find the first `limit` unwatched episodes of a show, in episode order
+    async fn find_unwatched_episodes(
+        &self,
+        show_id: &str,
+        limit: u64,
+    ) -> Result<Vec<MediaItemModel>>;
+
     /// Update show's watched_episode_count based on actual episode watch status
     async fn update_show_watched_count(&self, show_id: &str) -> Result<()>;
 
@@ -685,7 +693,7 @@ impl MediaRepository for MediaRepositoryImpl {
                     )
                     .add(media_items::Column::SeasonNumber.gt(after_season)),
             )
-            .left_join(playback_progress::Entity)
+            .join(JoinType::LeftJoin, active_user_progress_join())
             .filter(
                 Condition::any()
                     .add(playback_progress::Column::Watched.eq(false))
@@ -699,6 +707,30 @@ impl MediaRepository for MediaRepositoryImpl {
         Ok(result)
     }
 
+    async fn find_unwatched_episodes(
+        &self,
+        show_id: &str,
+        limit: u64,
+    ) -> Result<Vec<MediaItemModel>> {
+        use crate::db::entities::playback_progress;
+        use sea_orm::Condition;
+
+        Ok(MediaItem::find()
+            .filter(media_items::Column::ParentId.eq(show_id))
+            .filter(media_items::Column::MediaType.eq("episode"))
+            .join(JoinType::LeftJoin, active_user_progress_join())
+            .filter(
+                Condition::any()
+                    .add(playback_progress::Column::Watched.eq(false))
+                    .add(playback_progress::Column::Watched.is_null()),
+            )
+            .order_by_asc(media_items::Column::SeasonNumber)
+            .order_by_asc(media_items::Column::EpisodeNumber)
+            .limit(limit)
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
     async fn update_show_watched_count(&self, show_id: &str) -> Result<()> {
         use crate::db::entities::playback_progress;
 
@@ -734,6 +766,7 @@ impl MediaRepository for MediaRepositoryImpl {
             let progress = playback_progress::Entity::find()
                 .filter(playback_progress::Column::MediaId.eq(&episode.id))
                 .filter(playback_progress::Column::Watched.eq(true))
+                .filter(active_user_progress())
                 .one(self.base.db.as_ref())
                 .await?;
 
@@ -848,6 +881,44 @@ impl MediaRepository for MediaRepositoryImpl {
 }
 
 impl MediaRepositoryImpl {
+    /// Random movies and shows that have backdrop artwork
+    pub async fn find_random_with_backdrop(&self, limit: u64) -> Result<Vec<MediaItemModel>> {
+        use sea_orm::sea_query::Expr;
+
+        Ok(MediaItem::find()
+            .filter(media_items::Column::MediaType.is_in(["movie", "show"]))
+            .filter(media_items::Column::BackdropUrl.is_not_null())
+            .order_by(Expr::cust("RANDOM()"), Order::Asc)
+            .limit(limit)
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    /// First item of a type from a source, without loading the rest
+    pub async fn find_first_by_source_and_type(
+        &self,
+        source_id: &str,
+        media_type: &str,
+    ) -> Result<Option<MediaItemModel>> {
+        Ok(MediaItem::find()
+            .filter(media_items::Column::SourceId.eq(source_id))
+            .filter(media_items::Column::MediaType.eq(media_type))
+            .order_by(media_items::Column::SortTitle, Order::Asc)
+            .one(self.base.db.as_ref())
+            .await?)
+    }
+
+    /// Items with any of `ids`, in no particular order, in one query
+    pub async fn find_by_ids(&self, ids: &[String]) -> Result<Vec<MediaItemModel>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(MediaItem::find()
+            .filter(media_items::Column::Id.is_in(ids.iter().cloned()))
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
     pub async fn count_by_library(&self, library_id: &str) -> Result<i64> {
         use sea_orm::PaginatorTrait;
 
@@ -881,6 +952,202 @@ impl MediaRepositoryImpl {
         Ok(count as i64)
     }
 
+    /// Count the movies and episodes of a library that haven't been watched
+    pub async fn count_unwatched_by_library(&self, library_id: &str) -> Result<u64> {
+        use crate::db::entities::playback_progress;
+        use sea_orm::Condition;
+
+        Ok(MediaItem::find()
+            .filter(media_items::Column::LibraryId.eq(library_id))
+            .filter(media_items::Column::MediaType.is_in(["movie", "episode"]))
+            .join(JoinType::LeftJoin, active_user_progress_join())
+            .filter(
+                Condition::any()
+                    .add(playback_progress::Column::Watched.eq(false))
+                    .add(playback_progress::Column::Watched.is_null()),
+            )
+            .count(self.base.db.as_ref())
+            .await?)
+    }
+
+    /// Count the unwatched episodes of each of the given shows.
+    ///
+    /// Shows without unwatched episodes are left out.
+    pub async fn count_unwatched_episodes_by_show(
+        &self,
+        show_ids: &[String],
+    ) -> Result<std::collections::HashMap<String, u64>> {
+        use crate::db::entities::playback_progress;
+        use sea_orm::Condition;
+        use sea_orm::sea_query::Expr;
+
+        let counts: Vec<(Option<String>, i64)> = MediaItem::find()
+            .select_only()
+            .column(media_items::Column::ParentId)
+            .column_as(Expr::col(media_items::Column::Id).count(), "count")
+            .filter(media_items::Column::ParentId.is_in(show_ids.to_vec()))
+            .filter(media_items::Column::MediaType.eq("episode"))
+            .join(JoinType::LeftJoin, active_user_progress_join())
+            .filter(
+                Condition::any()
+                    .add(playback_progress::Column::Watched.eq(false))
+                    .add(playback_progress::Column::Watched.is_null()),
+            )
+            .group_by(media_items::Column::ParentId)
+            .into_tuple()
+            .all(self.base.db.as_ref())
+            .await?;
+
+        Ok(counts
+            .into_iter()
+            .filter_map(|(show_id, count)| show_id.map(|id| (id, count.max(0) as u64)))
+            .collect())
+    }
+
+    /// Highest video height among the episodes of each of the given shows
+    pub async fn max_episode_video_heights(
+        &self,
+        show_ids: &[String],
+    ) -> Result<std::collections::HashMap<String, u32>> {
+        use sea_orm::sea_query::Expr;
+
+        let heights: Vec<(Option<String>, Option<i64>)> = MediaItem::find()
+            .select_only()
+            .column(media_items::Column::ParentId)
+            .column_as(
+                Expr::cust(r#"MAX(json_extract("media_items"."metadata", '$.video_height'))"#),
+                "height",
+            )
+            .filter(media_items::Column::ParentId.is_in(show_ids.to_vec()))
+            .filter(media_items::Column::MediaType.eq("episode"))
+            .group_by(media_items::Column::ParentId)
+            .into_tuple()
+            .all(self.base.db.as_ref())
+            .await?;
+
+        Ok(heights
+            .into_iter()
+            .filter_map(|(show_id, height)| Some((show_id?, u32::try_from(height?).ok()?)))
+            .collect())
+    }
+
+    /// IDs of the movies and shows of a source in the named collection
+    pub async fn find_ids_in_collection(
+        &self,
+        source_id: &str,
+        collection: &str,
+    ) -> Result<Vec<String>> {
+        use sea_orm::sea_query::Expr;
+
+        Ok(MediaItem::find()
+            .select_only()
+            .column(media_items::Column::Id)
+            .filter(media_items::Column::SourceId.eq(source_id))
+            .filter(Expr::cust_with_values(
+                r#"EXISTS (SELECT 1 FROM json_each("media_items"."metadata", '$.collections') WHERE json_each.value = ?)"#,
+                [collection],
+            ))
+            .into_tuple()
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    /// Content ratings given to movies and shows, each once
+    pub async fn find_content_ratings(&self) -> Result<Vec<String>> {
+        use sea_orm::sea_query::Expr;
+
+        let ratings: Vec<Option<String>> = MediaItem::find()
+            .select_only()
+            .column_as(
+                Expr::cust(r#"json_extract("media_items"."metadata", '$.content_rating')"#),
+                "content_rating",
+            )
+            .filter(media_items::Column::MediaType.is_in(["movie", "show"]))
+            .distinct()
+            .into_tuple()
+            .all(self.base.db.as_ref())
+            .await?;
+
+        Ok(ratings.into_iter().flatten().collect())
+    }
+
+    /// IDs of the movies and shows not rated any of `ratings`, unrated ones
+    /// included
+    pub async fn find_ids_not_rated(&self, ratings: &[String]) -> Result<Vec<String>> {
+        use sea_orm::Condition;
+        use sea_orm::sea_query::Expr;
+
+        let rating = || Expr::cust(r#"json_extract("media_items"."metadata", '$.content_rating')"#);
+        Ok(MediaItem::find()
+            .select_only()
+            .column(media_items::Column::Id)
+            .filter(media_items::Column::MediaType.is_in(["movie", "show"]))
+            .filter(
+                Condition::any()
+                    .add(Expr::expr(rating()).is_null())
+                    .add(Expr::expr(rating()).is_not_in(ratings.to_vec())),
+            )
+            .into_tuple()
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    /// Find the most recently played regular episode of a show with its progress.
+    ///
+    /// Specials (season 0) are left out.
+    pub async fn find_last_played_episode(
+        &self,
+        show_id: &str,
+    ) -> Result<Option<(MediaItemModel, crate::db::entities::PlaybackProgressModel)>> {
+        use crate::db::entities::playback_progress;
+
+        let result = MediaItem::find()
+            .filter(media_items::Column::ParentId.eq(show_id))
+            .filter(media_items::Column::MediaType.eq("episode"))
+            .filter(media_items::Column::SeasonNumber.gt(0))
+            .select_also(playback_progress::Entity)
+            .join(JoinType::LeftJoin, active_user_progress_join())
+            .filter(playback_progress::Column::LastWatchedAt.is_not_null())
+            .order_by_desc(playback_progress::Column::LastWatchedAt)
+            .one(self.base.db.as_ref())
+            .await?;
+
+        Ok(result.and_then(|(episode, progress)| progress.map(|p| (episode, p))))
+    }
+
+    /// Count the watched and total episodes of each season of a show.
+    ///
+    /// Returns a map from season number to (watched, total).
+    pub async fn count_season_progress_by_show(
+        &self,
+        show_id: &str,
+    ) -> Result<std::collections::HashMap<u32, (u64, u64)>> {
+        use crate::db::entities::playback_progress;
+
+        let rows: Vec<(Option<i32>, Option<bool>)> = MediaItem::find()
+            .select_only()
+            .column(media_items::Column::SeasonNumber)
+            .column(playback_progress::Column::Watched)
+            .filter(media_items::Column::ParentId.eq(show_id))
+            .filter(media_items::Column::MediaType.eq("episode"))
+            .join(JoinType::LeftJoin, active_user_progress_join())
+            .into_tuple()
+            .all(self.base.db.as_ref())
+            .await?;
+
+        let mut progress = std::collections::HashMap::new();
+        for (season, watched) in rows {
+            let entry = progress
+                .entry(season.unwrap_or(0).max(0) as u32)
+                .or_insert((0, 0));
+            if watched.unwrap_or(false) {
+                entry.0 += 1;
+            }
+            entry.1 += 1;
+        }
+        Ok(progress)
+    }
+
     pub async fn find_by_library_paginated(
         &self,
         library_id: &str,
@@ -1326,6 +1593,10 @@ mod tests {
             connection_quality: Set(None),
             auth_status: Set("authenticated".to_string()),
             last_auth_check: Set(None),
+            mac_address: Set(None),
+            client_identity: Set(None),
+            active_user_id: Set(None),
+            connection_preferences: Set(None),
             created_at: Set(Utc::now().naive_utc()),
             updated_at: Set(Utc::now().naive_utc()),
         };
@@ -1849,4 +2120,83 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_find_ids_in_collection() -> Result<()> {
+        let (_db, repo) = setup_test_repository().await?;
+
+        let mut in_collection = create_test_movie("movie-1", "Alien", "test-movie-lib");
+        in_collection.metadata = Some(serde_json::json!({
+            "collections": ["Alien Collection", "Sci-Fi Classics"]
+        }));
+        repo.insert(in_collection).await?;
+        repo.insert(create_test_movie("movie-2", "Heat", "test-movie-lib"))
+            .await?;
+
+        let ids = repo
+            .find_ids_in_collection("test-source", "Alien Collection")
+            .await?;
+        assert_eq!(ids, vec!["movie-1".to_string()]);
+
+        let ids = repo.find_ids_in_collection("test-source", "Alien").await?;
+        assert!(ids.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_ids_not_rated() -> Result<()> {
+        let (_db, repo) = setup_test_repository().await?;
+
+        for (id, rating) in [
+            ("movie-1", Some("PG")),
+            ("movie-2", Some("R")),
+            ("movie-3", None),
+        ] {
+            let mut movie = create_test_movie(id, id, "test-movie-lib");
+            movie.metadata = Some(serde_json::json!({ "content_rating": rating }));
+            repo.insert(movie).await?;
+        }
+
+        let mut ratings = repo.find_content_ratings().await?;
+        ratings.sort();
+        assert_eq!(ratings, vec!["PG".to_string(), "R".to_string()]);
+
+        let mut ids = repo.find_ids_not_rated(&["PG".to_string()]).await?;
+        ids.sort();
+        assert_eq!(ids, vec!["movie-2".to_string(), "movie-3".to_string()]);
+        assert_eq!(repo.find_ids_not_rated(&[]).await?.len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_ids() -> Result<()> {
+        let (_db, repo) = setup_test_repository().await?;
+
+        for (id, title) in [
+            ("movie-1", "Alien"),
+            ("movie-2", "Heat"),
+            ("movie-3", "Ran"),
+        ] {
+            repo.insert(create_test_movie(id, title, "test-movie-lib"))
+                .await?;
+        }
+
+        let mut found: Vec<String> = repo
+            .find_by_ids(&[
+                "movie-3".to_string(),
+                "movie-1".to_string(),
+                "gone".to_string(),
+            ])
+            .await?
+            .into_iter()
+            .map(|item| item.id)
+            .collect();
+        found.sort();
+        assert_eq!(found, vec!["movie-1".to_string(), "movie-3".to_string()]);
+        assert!(repo.find_by_ids(&[]).await?.is_empty());
+
+        Ok(())
+    }
 }
@@ -1,13 +1,21 @@
 pub mod auth_token_repository;
+pub mod auto_download_rule_repository;
 pub mod cache_repository;
+pub mod favorite_repository;
+pub mod hidden_collection_repository;
+pub mod hidden_item_repository;
 pub mod home_section_repository;
 pub mod library_repository;
+pub mod library_sync_exclusion_repository;
 pub mod media_repository;
+pub mod offline_content_repository;
 pub mod people_repository;
 pub mod playback_repository;
 pub mod playback_sync_repository;
+pub mod skip_preference_repository;
 pub mod source_repository;
 pub mod sync_repository;
+pub mod track_preference_repository;
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -53,13 +61,31 @@ impl BaseRepository {
 
 // Re-export specific repositories
 pub use auth_token_repository::{AuthTokenRepository, AuthTokenRepositoryImpl};
+pub use auto_download_rule_repository::{
+    AutoDownloadRuleRepository, AutoDownloadRuleRepositoryImpl,
+};
 pub use cache_repository::{CacheRepository, CacheRepositoryImpl};
+pub use favorite_repository::{FavoriteRepository, FavoriteRepositoryImpl};
+pub use hidden_collection_repository::{
+    HiddenCollectionRepository, HiddenCollectionRepositoryImpl,
+};
+pub use hidden_item_repository::{HiddenItemRepository, HiddenItemRepositoryImpl};
 pub use home_section_repository::{HomeSectionRepository, HomeSectionRepositoryImpl};
 pub use library_repository::{LibraryRepository, LibraryRepositoryImpl};
+pub use library_sync_exclusion_repository::{
+    LibrarySyncExclusionRepository, LibrarySyncExclusionRepositoryImpl,
+};
 pub use media_repository::{
     MediaFilterBuilder, MediaRepository, MediaRepositoryImpl, MediaSortBy, SortDirection,
 };
+pub use offline_content_repository::{OfflineContentRepository, OfflineContentRepositoryImpl};
 pub use people_repository::{PeopleRepository, PeopleRepositoryImpl};
 pub use playback_repository::{PlaybackRepository, PlaybackRepositoryImpl};
 pub use playback_sync_repository::{PlaybackSyncRepository, PlaybackSyncRepositoryImpl};
+pub use skip_preference_repository::{
+    SkipKind, SkipPreferenceRepository, SkipPreferenceRepositoryImpl,
+};
 pub use source_repository::SourceRepositoryImpl;
+pub use track_preference_repository::{
+    TrackKind, TrackPreferenceRepository, TrackPreferenceRepositoryImpl,
+};
@@ -0,0 +1,343 @@
+use super::{BaseRepository, Repository};
+use crate::db::entities::{
+    DownloadStatus, OfflineContent, OfflineContentActiveModel, OfflineContentModel, offline_content,
+};
+use crate::services::core::storage::rebase_path;
+use anyhow::Result;
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, NotSet, Order, PaginatorTrait,
+    QueryFilter, QueryOrder, Set,
+};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Repository trait for offline downloads
+#[async_trait]
+pub trait OfflineContentRepository: Repository<OfflineContentModel> {
+    /// Queue a media item for download
+    async fn enqueue(
+        &self,
+        media_id: &str,
+        quality: &str,
+        file_path: &str,
+        auto_downloaded: bool,
+    ) -> Result<OfflineContentModel>;
+
+    /// Find the download for a media item, if any
+    async fn find_by_media_id(&self, media_id: &str) -> Result<Option<OfflineContentModel>>;
+
+    /// Find the downloads for several media items at once
+    async fn find_by_media_ids(&self, media_ids: &[String]) -> Result<Vec<OfflineContentModel>>;
+
+    /// Get queued downloads in the order they were requested
+    async fn get_queued(&self) -> Result<Vec<OfflineContentModel>>;
+
+    /// Get downloads that were queued by an auto-download rule
+    async fn get_auto_downloaded(&self) -> Result<Vec<OfflineContentModel>>;
+
+    /// Change whether a download is managed by an auto-download rule
+    async fn set_auto_downloaded(&self, id: i32, auto_downloaded: bool) -> Result<()>;
+
+    /// Get finished downloads
+    async fn get_completed(&self) -> Result<Vec<OfflineContentModel>>;
+
+    /// Pin a download so the cleanup policy keeps it
+    async fn set_pinned(&self, id: i32, pinned: bool) -> Result<()>;
+
+    /// Point downloads stored under `from` at the same files under `to`
+    async fn relocate_files(&self, from: &Path, to: &Path) -> Result<u64>;
+
+    /// Put downloads interrupted by an app exit back in the queue, keeping
+    /// how far they got
+    async fn requeue_interrupted(&self) -> Result<u64>;
+
+    /// Put a download back in the queue, keeping how far it got
+    async fn requeue(&self, id: i32) -> Result<()>;
+
+    /// Mark a download as in progress from `downloaded_bytes` on, with the
+    /// validator of the file on the server
+    async fn mark_downloading(
+        &self,
+        id: i32,
+        total_bytes: Option<i64>,
+        downloaded_bytes: i64,
+        validator: Option<&str>,
+    ) -> Result<()>;
+
+    /// Record how many bytes are safely on disk, and their checksum
+    async fn update_progress(&self, id: i32, downloaded_bytes: i64, checksum: &str) -> Result<()>;
+
+    /// Mark a download as finished, recording where the file ended up
+    async fn mark_completed(
+        &self,
+        id: i32,
+        file_path: &str,
+        file_size_bytes: i64,
+        checksum: &str,
+    ) -> Result<()>;
+
+    /// Mark a download as failed with error message
+    async fn mark_failed(&self, id: i32, error_message: &str) -> Result<()>;
+
+    /// Delete a download record by ID
+    async fn delete_by_id(&self, id: i32) -> Result<()>;
+}
+
+#[derive(Debug)]
+pub struct OfflineContentRepositoryImpl {
+    base: BaseRepository,
+}
+
+impl OfflineContentRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            base: BaseRepository::new(db),
+        }
+    }
+
+    async fn find_model(&self, id: i32) -> Result<Option<OfflineContentModel>> {
+        Ok(OfflineContent::find_by_id(id)
+            .one(self.base.db.as_ref())
+            .await?)
+    }
+}
+
+#[async_trait]
+impl Repository<OfflineContentModel> for OfflineContentRepositoryImpl {
+    type Entity = OfflineContent;
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<OfflineContentModel>> {
+        let id_parsed = id.parse::<i32>().unwrap_or(0);
+        self.find_model(id_parsed).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<OfflineContentModel>> {
+        Ok(OfflineContent::find().all(self.base.db.as_ref()).await?)
+    }
+
+    async fn insert(&self, entity: OfflineContentModel) -> Result<OfflineContentModel> {
+        let active_model = OfflineContentActiveModel {
+            id: NotSet,
+            media_id: Set(entity.media_id.clone()),
+            file_path: Set(entity.file_path.clone()),
+            file_size_bytes: Set(entity.file_size_bytes),
+            quality: Set(entity.quality.clone()),
+            downloaded_at: Set(entity.downloaded_at),
+            last_accessed: Set(entity.last_accessed),
+            status: Set(entity.status.clone()),
+            downloaded_bytes: Set(entity.downloaded_bytes),
+            error_message: Set(entity.error_message.clone()),
+            auto_downloaded: Set(entity.auto_downloaded),
+            pinned: Set(entity.pinned),
+            validator: Set(entity.validator.clone()),
+            checksum: Set(entity.checksum.clone()),
+        };
+
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn update(&self, entity: OfflineContentModel) -> Result<OfflineContentModel> {
+        let active_model: OfflineContentActiveModel = entity.into();
+        Ok(active_model.update(self.base.db.as_ref()).await?)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let id_parsed = id.parse::<i32>().unwrap_or(0);
+        self.delete_by_id(id_parsed).await
+    }
+
+    async fn count(&self) -> Result<u64> {
+        Ok(OfflineContent::find().count(self.base.db.as_ref()).await?)
+    }
+}
+
+#[async_trait]
+impl OfflineContentRepository for OfflineContentRepositoryImpl {
+    async fn enqueue(
+        &self,
+        media_id: &str,
+        quality: &str,
+        file_path: &str,
+        auto_downloaded: bool,
+    ) -> Result<OfflineContentModel> {
+        let active_model = OfflineContentActiveModel {
+            id: NotSet,
+            media_id: Set(media_id.to_string()),
+            file_path: Set(file_path.to_string()),
+            file_size_bytes: Set(None),
+            quality: Set(Some(quality.to_string())),
+            downloaded_at: Set(chrono::Utc::now().naive_utc()),
+            last_accessed: Set(None),
+            status: Set(DownloadStatus::Queued.to_string()),
+            downloaded_bytes: Set(0),
+            error_message: Set(None),
+            auto_downloaded: Set(auto_downloaded),
+            pinned: Set(false),
+            validator: Set(None),
+            checksum: Set(None),
+        };
+
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn find_by_media_id(&self, media_id: &str) -> Result<Option<OfflineContentModel>> {
+        Ok(OfflineContent::find()
+            .filter(offline_content::Column::MediaId.eq(media_id))
+            .one(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn find_by_media_ids(&self, media_ids: &[String]) -> Result<Vec<OfflineContentModel>> {
+        Ok(OfflineContent::find()
+            .filter(offline_content::Column::MediaId.is_in(media_ids.iter().cloned()))
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn get_queued(&self) -> Result<Vec<OfflineContentModel>> {
+        Ok(OfflineContent::find()
+            .filter(offline_content::Column::Status.eq(DownloadStatus::Queued.to_string()))
+            .order_by(offline_content::Column::Id, Order::Asc)
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn get_auto_downloaded(&self) -> Result<Vec<OfflineContentModel>> {
+        Ok(OfflineContent::find()
+            .filter(offline_content::Column::AutoDownloaded.eq(true))
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn set_auto_downloaded(&self, id: i32, auto_downloaded: bool) -> Result<()> {
+        if let Some(item) = self.find_model(id).await? {
+            let mut active_model: OfflineContentActiveModel = item.into();
+            active_model.auto_downloaded = Set(auto_downloaded);
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn get_completed(&self) -> Result<Vec<OfflineContentModel>> {
+        Ok(OfflineContent::find()
+            .filter(offline_content::Column::Status.eq(DownloadStatus::Completed.to_string()))
+            .all(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn set_pinned(&self, id: i32, pinned: bool) -> Result<()> {
+        if let Some(item) = self.find_model(id).await? {
+            let mut active_model: OfflineContentActiveModel = item.into();
+            active_model.pinned = Set(pinned);
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn relocate_files(&self, from: &Path, to: &Path) -> Result<u64> {
+        let mut relocated = 0;
+        for item in self.find_all().await? {
+            if let Some(file_path) = rebase_path(&item.file_path, from, to) {
+                let mut active_model: OfflineContentActiveModel = item.into();
+                active_model.file_path = Set(file_path);
+                active_model.update(self.base.db.as_ref()).await?;
+                relocated += 1;
+            }
+        }
+        Ok(relocated)
+    }
+
+    async fn requeue_interrupted(&self) -> Result<u64> {
+        let result = OfflineContent::update_many()
+            .col_expr(
+                offline_content::Column::Status,
+                sea_orm::sea_query::Expr::value(DownloadStatus::Queued.to_string()),
+            )
+            .filter(offline_content::Column::Status.eq(DownloadStatus::Downloading.to_string()))
+            .exec(self.base.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn requeue(&self, id: i32) -> Result<()> {
+        if let Some(item) = self.find_model(id).await? {
+            let mut active_model: OfflineContentActiveModel = item.into();
+            active_model.status = Set(DownloadStatus::Queued.to_string());
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn mark_downloading(
+        &self,
+        id: i32,
+        total_bytes: Option<i64>,
+        downloaded_bytes: i64,
+        validator: Option<&str>,
+    ) -> Result<()> {
+        if let Some(item) = self.find_model(id).await? {
+            let restarted = downloaded_bytes == 0;
+            let mut active_model: OfflineContentActiveModel = item.into();
+            active_model.status = Set(DownloadStatus::Downloading.to_string());
+            active_model.file_size_bytes = Set(total_bytes);
+            active_model.downloaded_bytes = Set(downloaded_bytes);
+            active_model.validator = Set(validator.map(str::to_string));
+            if restarted {
+                active_model.checksum = Set(None);
+            }
+            active_model.error_message = Set(None);
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn update_progress(&self, id: i32, downloaded_bytes: i64, checksum: &str) -> Result<()> {
+        if let Some(item) = self.find_model(id).await? {
+            let mut active_model: OfflineContentActiveModel = item.into();
+            active_model.downloaded_bytes = Set(downloaded_bytes);
+            active_model.checksum = Set(Some(checksum.to_string()));
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn mark_completed(
+        &self,
+        id: i32,
+        file_path: &str,
+        file_size_bytes: i64,
+        checksum: &str,
+    ) -> Result<()> {
+        if let Some(item) = self.find_model(id).await? {
+            let mut active_model: OfflineContentActiveModel = item.into();
+            active_model.status = Set(DownloadStatus::Completed.to_string());
+            active_model.file_path = Set(file_path.to_string());
+            active_model.file_size_bytes = Set(Some(file_size_bytes));
+            active_model.downloaded_bytes = Set(file_size_bytes);
+            active_model.checksum = Set(Some(checksum.to_string()));
+            active_model.downloaded_at = Set(chrono::Utc::now().naive_utc());
+            active_model.error_message = Set(None);
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i32, error_message: &str) -> Result<()> {
+        if let Some(item) = self.find_model(id).await? {
+            let mut active_model: OfflineContentActiveModel = item.into();
+            active_model.status = Set(DownloadStatus::Failed.to_string());
+            active_model.error_message = Set(Some(error_message.to_string()));
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_by_id(&self, id: i32) -> Result<()> {
+        OfflineContent::delete_by_id(id)
+            .exec(self.base.db.as_ref())
+            .await?;
+        Ok(())
+    }
+}
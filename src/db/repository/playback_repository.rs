@@ -1,19 +1,37 @@
 use super::{BaseRepository, Repository};
 use crate::db::entities::{
-    PlaybackProgress, PlaybackProgressActiveModel, PlaybackProgressModel, playback_progress,
+    PlaybackProgress, PlaybackProgressActiveModel, PlaybackProgressModel, media_items,
+    playback_progress,
 };
 use anyhow::Result;
 use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, SimpleExpr};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, NotSet, Order, PaginatorTrait,
-    QueryFilter, QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, NotSet, Order,
+    PaginatorTrait, QueryFilter, QueryOrder, RelationDef, RelationTrait, Set,
 };
 use std::sync::Arc;
 
+/// Matches the progress of the user signed in to the source of each item, or
+/// the progress without a user on sources nobody signs in to
+pub(crate) fn active_user_progress() -> SimpleExpr {
+    Expr::cust(
+        r#""playback_progress"."user_id" IS (SELECT "sources"."active_user_id" FROM "media_items" INNER JOIN "sources" ON "sources"."id" = "media_items"."source_id" WHERE "media_items"."id" = "playback_progress"."media_id")"#,
+    )
+}
+
+/// Joins media items to the progress of their active user only, so items
+/// other users have progress on are not repeated
+pub(crate) fn active_user_progress_join() -> RelationDef {
+    media_items::Relation::PlaybackProgresses
+        .def()
+        .on_condition(|_, _| Condition::all().add(active_user_progress()))
+}
+
 /// Repository trait for PlaybackProgress entities
 #[async_trait]
 pub trait PlaybackRepository: Repository<PlaybackProgressModel> {
-    /// Find playback progress for a specific media item
+    /// Find playback progress of the active user for a specific media item
     async fn find_by_media_id(&self, media_id: &str) -> Result<Option<PlaybackProgressModel>>;
 
     /// Find playback progress for a specific user and media
@@ -26,8 +44,8 @@ pub trait PlaybackRepository: Repository<PlaybackProgressModel> {
     /// Find all watched items
     async fn find_watched(&self, user_id: Option<&str>) -> Result<Vec<PlaybackProgressModel>>;
 
-    /// Find items in progress (started but not finished)
-    async fn find_in_progress(&self, user_id: Option<&str>) -> Result<Vec<PlaybackProgressModel>>;
+    /// Find items the active users are in progress on (started but not finished)
+    async fn find_in_progress(&self) -> Result<Vec<PlaybackProgressModel>>;
 
     /// Update or create playback progress
     async fn upsert_progress(
@@ -41,6 +59,15 @@ pub trait PlaybackRepository: Repository<PlaybackProgressModel> {
     /// Mark an item as watched
     async fn mark_watched(&self, media_id: &str, user_id: Option<&str>) -> Result<()>;
 
+    /// Replace the chapters listened to the end, given by their start in
+    /// milliseconds, of an item with progress
+    async fn update_completed_chapters(
+        &self,
+        media_id: &str,
+        user_id: Option<&str>,
+        chapter_starts_ms: &[u64],
+    ) -> Result<()>;
+
     /// Mark an item as unwatched
     async fn mark_unwatched(&self, media_id: &str, user_id: Option<&str>) -> Result<()>;
 
@@ -54,6 +81,11 @@ pub trait PlaybackRepository: Repository<PlaybackProgressModel> {
     /// Clean up old progress entries
     async fn cleanup_old_entries(&self, days: i64) -> Result<u64>;
 
+    /// Give the progress without a user on the items of a source, kept from
+    /// before anyone signed in to it, to a user who has none of their own
+    /// on those items. Returns the number of records given.
+    async fn adopt_progress_without_user(&self, source_id: &str, user_id: &str) -> Result<u64>;
+
     /// Save PlayQueue state for a media item
     async fn save_playqueue_state(
         &self,
@@ -141,6 +173,7 @@ impl Repository<PlaybackProgressModel> for PlaybackRepositoryImpl {
             play_queue_version: Set(entity.play_queue_version),
             play_queue_item_id: Set(entity.play_queue_item_id),
             source_id: Set(entity.source_id),
+            completed_chapters: Set(entity.completed_chapters.clone()),
         };
 
         Ok(active_model.insert(self.base.db.as_ref()).await?)
@@ -173,6 +206,7 @@ impl PlaybackRepository for PlaybackRepositoryImpl {
     async fn find_by_media_id(&self, media_id: &str) -> Result<Option<PlaybackProgressModel>> {
         Ok(PlaybackProgress::find()
             .filter(playback_progress::Column::MediaId.eq(media_id))
+            .filter(active_user_progress())
             .one(self.base.db.as_ref())
             .await?)
     }
@@ -200,16 +234,13 @@ impl PlaybackRepository for PlaybackRepositoryImpl {
         Ok(query.all(self.base.db.as_ref()).await?)
     }
 
-    async fn find_in_progress(&self, user_id: Option<&str>) -> Result<Vec<PlaybackProgressModel>> {
-        let mut query = PlaybackProgress::find()
+    async fn find_in_progress(&self) -> Result<Vec<PlaybackProgressModel>> {
+        Ok(PlaybackProgress::find()
             .filter(playback_progress::Column::Watched.eq(false))
-            .filter(playback_progress::Column::PositionMs.gt(0));
-
-        if let Some(uid) = user_id {
-            query = query.filter(playback_progress::Column::UserId.eq(uid));
-        }
-
-        Ok(query.all(self.base.db.as_ref()).await?)
+            .filter(playback_progress::Column::PositionMs.gt(0))
+            .filter(active_user_progress())
+            .all(self.base.db.as_ref())
+            .await?)
     }
 
     async fn upsert_progress(
@@ -281,6 +312,7 @@ impl PlaybackRepository for PlaybackRepositoryImpl {
                 play_queue_version: Set(None),
                 play_queue_item_id: Set(None),
                 source_id: Set(None),
+                completed_chapters: Set(None),
             };
 
             Ok(active_model.insert(self.base.db.as_ref()).await?)
@@ -319,6 +351,7 @@ impl PlaybackRepository for PlaybackRepositoryImpl {
                 play_queue_version: Set(None),
                 play_queue_item_id: Set(None),
                 source_id: Set(None),
+                completed_chapters: Set(None),
             };
             active_model.insert(self.base.db.as_ref()).await?;
         }
@@ -326,6 +359,26 @@ impl PlaybackRepository for PlaybackRepositoryImpl {
         Ok(())
     }
 
+    async fn update_completed_chapters(
+        &self,
+        media_id: &str,
+        user_id: Option<&str>,
+        chapter_starts_ms: &[u64],
+    ) -> Result<()> {
+        let progress = match user_id {
+            Some(uid) => self.find_by_media_and_user(media_id, uid).await?,
+            None => self.find_by_media_id(media_id).await?,
+        };
+        let Some(progress) = progress else {
+            return Ok(());
+        };
+
+        let mut active_model: PlaybackProgressActiveModel = progress.into();
+        active_model.completed_chapters = Set(Some(serde_json::json!(chapter_starts_ms)));
+        active_model.update(self.base.db.as_ref()).await?;
+        Ok(())
+    }
+
     async fn mark_unwatched(&self, media_id: &str, user_id: Option<&str>) -> Result<()> {
         let progress = if let Some(uid) = user_id {
             self.find_by_media_and_user(media_id, uid).await?
@@ -374,6 +427,25 @@ impl PlaybackRepository for PlaybackRepositoryImpl {
         Ok(result.rows_affected)
     }
 
+    async fn adopt_progress_without_user(&self, source_id: &str, user_id: &str) -> Result<u64> {
+        let result = PlaybackProgress::update_many()
+            .col_expr(playback_progress::Column::UserId, Expr::value(user_id))
+            .filter(playback_progress::Column::UserId.is_null())
+            .filter(Expr::cust_with_values(
+                r#""playback_progress"."media_id" IN (SELECT "id" FROM "media_items" WHERE "source_id" = ?)"#,
+                [source_id],
+            ))
+            // The user's own progress on an item wins over the one without a user
+            .filter(Expr::cust_with_values(
+                r#"NOT EXISTS (SELECT 1 FROM "playback_progress" AS "own" WHERE "own"."media_id" = "playback_progress"."media_id" AND "own"."user_id" = ?)"#,
+                [user_id],
+            ))
+            .exec(self.base.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
     async fn save_playqueue_state(
         &self,
         media_id: &str,
@@ -416,6 +488,7 @@ impl PlaybackRepository for PlaybackRepositoryImpl {
                 play_queue_version: Set(Some(play_queue_version)),
                 play_queue_item_id: Set(Some(play_queue_item_id)),
                 source_id: Set(Some(source_id)),
+                completed_chapters: Set(None),
             };
             active_model.insert(self.base.db.as_ref()).await?;
         }
@@ -550,6 +623,7 @@ impl PlaybackRepository for PlaybackRepositoryImpl {
                     play_queue_version: Set(None),
                     play_queue_item_id: Set(None),
                     source_id: Set(None),
+                    completed_chapters: Set(None),
                 };
                 active_model.insert(&txn).await?;
                 affected_rows += 1;
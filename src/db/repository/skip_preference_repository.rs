@@ -0,0 +1,103 @@
+use super::{BaseRepository, Repository};
+use crate::db::entities::{SkipPreference, SkipPreferenceActiveModel, SkipPreferenceModel};
+use anyhow::Result;
+use async_trait::async_trait;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, PaginatorTrait, Set};
+use std::sync::Arc;
+
+/// Which of the two skippable parts of an episode to change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipKind {
+    Intro,
+    Credits,
+}
+
+/// Repository trait for per-show auto-skip overrides
+#[async_trait]
+pub trait SkipPreferenceRepository: Repository<SkipPreferenceModel> {
+    /// Override how a show skips one kind of marker, None to follow the
+    /// settings again. A show left without overrides is forgotten.
+    async fn set_mode(&self, show_id: &str, kind: SkipKind, mode: Option<&str>) -> Result<()>;
+}
+
+#[derive(Debug)]
+pub struct SkipPreferenceRepositoryImpl {
+    base: BaseRepository,
+}
+
+impl SkipPreferenceRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            base: BaseRepository::new(db),
+        }
+    }
+}
+
+#[async_trait]
+impl Repository<SkipPreferenceModel> for SkipPreferenceRepositoryImpl {
+    type Entity = SkipPreference;
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<SkipPreferenceModel>> {
+        Ok(SkipPreference::find_by_id(id.to_string())
+            .one(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn find_all(&self) -> Result<Vec<SkipPreferenceModel>> {
+        Ok(SkipPreference::find().all(self.base.db.as_ref()).await?)
+    }
+
+    async fn insert(&self, entity: SkipPreferenceModel) -> Result<SkipPreferenceModel> {
+        let active_model: SkipPreferenceActiveModel = entity.into();
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn update(&self, entity: SkipPreferenceModel) -> Result<SkipPreferenceModel> {
+        let mut active_model: SkipPreferenceActiveModel = entity.clone().into();
+        active_model.intro = Set(entity.intro);
+        active_model.credits = Set(entity.credits);
+        active_model.updated_at = Set(entity.updated_at);
+        Ok(active_model.update(self.base.db.as_ref()).await?)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        SkipPreference::delete_by_id(id.to_string())
+            .exec(self.base.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<u64> {
+        Ok(SkipPreference::find().count(self.base.db.as_ref()).await?)
+    }
+}
+
+#[async_trait]
+impl SkipPreferenceRepository for SkipPreferenceRepositoryImpl {
+    async fn set_mode(&self, show_id: &str, kind: SkipKind, mode: Option<&str>) -> Result<()> {
+        let existing = self.find_by_id(show_id).await?;
+        let exists = existing.is_some();
+        let mut preference = existing.unwrap_or_else(|| SkipPreferenceModel {
+            show_id: show_id.to_string(),
+            intro: None,
+            credits: None,
+            updated_at: chrono::Utc::now().naive_utc(),
+        });
+        let mode = mode.map(str::to_string);
+        match kind {
+            SkipKind::Intro => preference.intro = mode,
+            SkipKind::Credits => preference.credits = mode,
+        }
+        preference.updated_at = chrono::Utc::now().naive_utc();
+
+        match (
+            exists,
+            preference.intro.is_none() && preference.credits.is_none(),
+        ) {
+            (true, true) => self.delete(show_id).await,
+            (true, false) => self.update(preference).await.map(|_| ()),
+            (false, true) => Ok(()),
+            (false, false) => self.insert(preference).await.map(|_| ()),
+        }
+    }
+}
@@ -61,6 +61,26 @@ pub trait SourceRepository: Repository<SourceModel> {
 
     /// Update the connections JSON for a source
     async fn update_connections(&self, id: &str, connections: serde_json::Value) -> Result<()>;
+
+    /// Update the Wake-on-LAN MAC address for a source
+    async fn update_mac_address(&self, id: &str, mac_address: Option<String>) -> Result<()>;
+
+    /// Update the client identity reported to a source's server
+    async fn update_client_identity(
+        &self,
+        id: &str,
+        client_identity: Option<serde_json::Value>,
+    ) -> Result<()>;
+
+    /// Update which connections a source may use, and in what order
+    async fn update_connection_preferences(
+        &self,
+        id: &str,
+        connection_preferences: Option<serde_json::Value>,
+    ) -> Result<()>;
+
+    /// Update the local user signed in to a source
+    async fn update_active_user(&self, id: &str, active_user_id: Option<String>) -> Result<()>;
 }
 
 #[derive(Debug)]
@@ -105,6 +125,10 @@ impl Repository<SourceModel> for SourceRepositoryImpl {
             connection_quality: Set(entity.connection_quality),
             auth_status: Set(entity.auth_status.clone()),
             last_auth_check: Set(entity.last_auth_check),
+            mac_address: Set(entity.mac_address.clone()),
+            client_identity: Set(entity.client_identity.clone()),
+            active_user_id: Set(entity.active_user_id.clone()),
+            connection_preferences: Set(entity.connection_preferences.clone()),
             created_at: Set(chrono::Utc::now().naive_utc()),
             updated_at: Set(chrono::Utc::now().naive_utc()),
         };
@@ -241,7 +265,10 @@ impl SourceRepository for SourceRepositoryImpl {
                 // Check if this source has a valid auth_provider_id and known source_type
                 let should_archive = match (&source.auth_provider_id, &source.source_type) {
                     (Some(_provider_id), source_type)
-                        if matches!(source_type.as_str(), "plex" | "jellyfin" | "local") =>
+                        if matches!(
+                            source_type.as_str(),
+                            "plex" | "jellyfin" | "emby" | "local"
+                        ) =>
                     {
                         // This is a valid source type with an auth provider - don't archive it
                         // It might just be using an old ID format
@@ -332,4 +359,52 @@ impl SourceRepository for SourceRepositoryImpl {
         }
         Ok(())
     }
+
+    async fn update_mac_address(&self, id: &str, mac_address: Option<String>) -> Result<()> {
+        if let Some(source) = self.find_by_id(id).await? {
+            let mut active_model: SourceActiveModel = source.into();
+            active_model.mac_address = Set(mac_address);
+            active_model.updated_at = Set(chrono::Utc::now().naive_utc());
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn update_client_identity(
+        &self,
+        id: &str,
+        client_identity: Option<serde_json::Value>,
+    ) -> Result<()> {
+        if let Some(source) = self.find_by_id(id).await? {
+            let mut active_model: SourceActiveModel = source.into();
+            active_model.client_identity = Set(client_identity);
+            active_model.updated_at = Set(chrono::Utc::now().naive_utc());
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn update_connection_preferences(
+        &self,
+        id: &str,
+        connection_preferences: Option<serde_json::Value>,
+    ) -> Result<()> {
+        if let Some(source) = self.find_by_id(id).await? {
+            let mut active_model: SourceActiveModel = source.into();
+            active_model.connection_preferences = Set(connection_preferences);
+            active_model.updated_at = Set(chrono::Utc::now().naive_utc());
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn update_active_user(&self, id: &str, active_user_id: Option<String>) -> Result<()> {
+        if let Some(source) = self.find_by_id(id).await? {
+            let mut active_model: SourceActiveModel = source.into();
+            active_model.active_user_id = Set(active_user_id);
+            active_model.updated_at = Set(chrono::Utc::now().naive_utc());
+            active_model.update(self.base.db.as_ref()).await?;
+        }
+        Ok(())
+    }
 }
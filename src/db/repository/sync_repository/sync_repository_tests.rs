@@ -78,6 +78,10 @@ mod tests {
                 connection_quality: Set(None),
                 auth_status: Set("authenticated".to_string()),
                 last_auth_check: Set(None),
+                mac_address: Set(None),
+                client_identity: Set(None),
+                active_user_id: Set(None),
+                connection_preferences: Set(None),
                 created_at: Set(Utc::now().naive_utc()),
                 updated_at: Set(Utc::now().naive_utc()),
             };
@@ -0,0 +1,137 @@
+use super::{BaseRepository, Repository};
+use crate::db::entities::{TrackPreference, TrackPreferenceActiveModel, TrackPreferenceModel};
+use anyhow::Result;
+use async_trait::async_trait;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, PaginatorTrait, Set};
+use std::sync::Arc;
+
+/// Which of the two remembered tracks to change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackKind {
+    Audio,
+    Subtitle,
+}
+
+/// Repository trait for remembered audio and subtitle track choices
+#[async_trait]
+pub trait TrackPreferenceRepository: Repository<TrackPreferenceModel> {
+    /// Remember a track choice for a media item or show, keeping the other kind
+    async fn set_track(
+        &self,
+        media_id: &str,
+        kind: TrackKind,
+        track: &str,
+    ) -> Result<TrackPreferenceModel>;
+
+    /// Remember a subtitle file loaded from disk for a media item
+    async fn set_subtitle_file(&self, media_id: &str, path: &str) -> Result<TrackPreferenceModel>;
+}
+
+#[derive(Debug)]
+pub struct TrackPreferenceRepositoryImpl {
+    base: BaseRepository,
+}
+
+impl TrackPreferenceRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            base: BaseRepository::new(db),
+        }
+    }
+}
+
+#[async_trait]
+impl Repository<TrackPreferenceModel> for TrackPreferenceRepositoryImpl {
+    type Entity = TrackPreference;
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<TrackPreferenceModel>> {
+        Ok(TrackPreference::find_by_id(id.to_string())
+            .one(self.base.db.as_ref())
+            .await?)
+    }
+
+    async fn find_all(&self) -> Result<Vec<TrackPreferenceModel>> {
+        Ok(TrackPreference::find().all(self.base.db.as_ref()).await?)
+    }
+
+    async fn insert(&self, entity: TrackPreferenceModel) -> Result<TrackPreferenceModel> {
+        let active_model: TrackPreferenceActiveModel = entity.into();
+        Ok(active_model.insert(self.base.db.as_ref()).await?)
+    }
+
+    async fn update(&self, entity: TrackPreferenceModel) -> Result<TrackPreferenceModel> {
+        let mut active_model: TrackPreferenceActiveModel = entity.clone().into();
+        active_model.audio_track = Set(entity.audio_track);
+        active_model.subtitle_track = Set(entity.subtitle_track);
+        active_model.subtitle_file = Set(entity.subtitle_file);
+        active_model.updated_at = Set(entity.updated_at);
+        Ok(active_model.update(self.base.db.as_ref()).await?)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        TrackPreference::delete_by_id(id.to_string())
+            .exec(self.base.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<u64> {
+        Ok(TrackPreference::find().count(self.base.db.as_ref()).await?)
+    }
+}
+
+#[async_trait]
+impl TrackPreferenceRepository for TrackPreferenceRepositoryImpl {
+    async fn set_track(
+        &self,
+        media_id: &str,
+        kind: TrackKind,
+        track: &str,
+    ) -> Result<TrackPreferenceModel> {
+        let (mut preference, exists) = self.find_or_new(media_id).await?;
+        match kind {
+            TrackKind::Audio => preference.audio_track = Some(track.to_string()),
+            TrackKind::Subtitle => preference.subtitle_track = Some(track.to_string()),
+        }
+        self.save(preference, exists).await
+    }
+
+    async fn set_subtitle_file(&self, media_id: &str, path: &str) -> Result<TrackPreferenceModel> {
+        let (mut preference, exists) = self.find_or_new(media_id).await?;
+        preference.subtitle_file = Some(path.to_string());
+        self.save(preference, exists).await
+    }
+}
+
+impl TrackPreferenceRepositoryImpl {
+    /// The stored preferences of a media item, or empty ones, and whether
+    /// they are stored
+    async fn find_or_new(&self, media_id: &str) -> Result<(TrackPreferenceModel, bool)> {
+        Ok(match self.find_by_id(media_id).await? {
+            Some(existing) => (existing, true),
+            None => (
+                TrackPreferenceModel {
+                    media_id: media_id.to_string(),
+                    audio_track: None,
+                    subtitle_track: None,
+                    subtitle_file: None,
+                    updated_at: chrono::Utc::now().naive_utc(),
+                },
+                false,
+            ),
+        })
+    }
+
+    async fn save(
+        &self,
+        mut preference: TrackPreferenceModel,
+        exists: bool,
+    ) -> Result<TrackPreferenceModel> {
+        preference.updated_at = chrono::Utc::now().naive_utc();
+        if exists {
+            self.update(preference).await
+        } else {
+            self.insert(preference).await
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Schema version of the database compared to this build
+//!
+//! The migrations applied to a database are its schema version. A database
+//! last opened by a newer release has migrations this build doesn't know,
+//! and migrating or even reading it could lose what the newer release wrote,
+//! so it is refused instead of opened.
+
+use std::path::Path;
+
+use anyhow::Result;
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use sea_orm_migration::{MigrationName, MigratorTrait};
+use thiserror::Error;
+
+use super::connection::DatabaseConnection;
+use super::migrations::Migrator;
+
+/// How the schema of a database compares to this build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaStatus {
+    /// No migrations applied yet, nothing to lose
+    Fresh,
+    UpToDate,
+    /// This many migrations of this build are not applied yet
+    Pending(usize),
+    /// Migrations applied by a newer release, by name
+    Newer(Vec<String>),
+}
+
+/// The database was upgraded by a newer release and can't be opened
+#[derive(Debug, Error)]
+#[error("The database was upgraded by a newer version of Reel ({})", .migrations.join(", "))]
+pub struct NewerSchemaError {
+    pub migrations: Vec<String>,
+}
+
+/// Schema status of a database
+pub async fn schema_status(db: &DatabaseConnection) -> Result<SchemaStatus> {
+    Migrator::install(&**db).await?;
+    let applied: Vec<String> = Migrator::get_migration_models(&**db)
+        .await?
+        .into_iter()
+        .map(|model| model.version)
+        .collect();
+    Ok(compare(&applied, &known_migrations()))
+}
+
+/// Schema status of the database file at `path`, read without changing it
+pub async fn file_schema_status(path: &Path) -> Result<SchemaStatus> {
+    let db = sea_orm::Database::connect(format!("sqlite://{}?mode=ro", path.display())).await?;
+    let migrated = db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'seaql_migrations'",
+        ))
+        .await?
+        .is_some();
+    let applied: Vec<String> = if migrated {
+        Migrator::get_migration_models(&db)
+            .await?
+            .into_iter()
+            .map(|model| model.version)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    db.close().await?;
+    Ok(compare(&applied, &known_migrations()))
+}
+
+fn known_migrations() -> Vec<String> {
+    Migrator::migrations()
+        .iter()
+        .map(|migration| migration.name().to_string())
+        .collect()
+}
+
+fn compare(applied: &[String], known: &[String]) -> SchemaStatus {
+    let unknown: Vec<String> = applied
+        .iter()
+        .filter(|version| !known.contains(version))
+        .cloned()
+        .collect();
+    if !unknown.is_empty() {
+        return SchemaStatus::Newer(unknown);
+    }
+    if applied.is_empty() {
+        return SchemaStatus::Fresh;
+    }
+
+    match known.iter().filter(|name| !applied.contains(name)).count() {
+        0 => SchemaStatus::UpToDate,
+        pending => SchemaStatus::Pending(pending),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_schema_status_from_applied_migrations() {
+        let known = names(&["m1", "m2", "m3"]);
+        assert_eq!(compare(&[], &known), SchemaStatus::Fresh);
+        assert_eq!(compare(&names(&["m1"]), &known), SchemaStatus::Pending(2));
+        assert_eq!(compare(&known, &known), SchemaStatus::UpToDate);
+    }
+
+    #[test]
+    fn test_unknown_migrations_mean_a_newer_schema() {
+        let known = names(&["m1", "m2"]);
+        assert_eq!(
+            compare(&names(&["m1", "m2", "m4"]), &known),
+            SchemaStatus::Newer(names(&["m4"]))
+        );
+    }
+}
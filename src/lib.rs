@@ -10,6 +10,7 @@ pub mod config;
 pub mod constants;
 pub mod core;
 pub mod db;
+pub mod logging;
 pub mod mapper;
 pub mod models;
 pub mod player;
@@ -0,0 +1,336 @@
+//! Tracing setup and the in-memory log shown in the diagnostics window
+//!
+//! Every event that passes the filter is also kept in a bounded ring buffer,
+//! so recent logs can be viewed and exported without a log file. The level and
+//! optional rotating log files follow the `logging` configuration at runtime.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{DateTime, Local};
+use once_cell::sync::{Lazy, OnceCell};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::{LevelFilter, filter_fn};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{EnvFilter, Registry, reload};
+
+use crate::config::LoggingConfig;
+
+/// Log file size at which it is rotated
+const LOG_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated log files kept next to the current one
+const LOG_FILES_KEPT: usize = 3;
+
+/// Number of recent log entries kept in memory
+const LOG_BUFFER_CAPACITY: usize = 5000;
+
+/// Levels offered for filtering and configuration, most severe first
+pub const LOG_LEVELS: [(&str, Level); 5] = [
+    ("Errors", Level::ERROR),
+    ("Warnings", Level::WARN),
+    ("Info", Level::INFO),
+    ("Debug", Level::DEBUG),
+    ("Trace", Level::TRACE),
+];
+
+/// Recent log entries, oldest first
+pub static LOG_BUFFER: Lazy<LogBuffer> = Lazy::new(|| LogBuffer::new(LOG_BUFFER_CAPACITY));
+
+/// A single captured tracing event
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+impl LogEntry {
+    /// One-line rendering matching the terminal output
+    pub fn format_line(&self) -> String {
+        format!(
+            "{} {:>5} {}: {}",
+            self.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+/// Bounded buffer of recent log entries shared with the capture layer
+pub struct LogBuffer {
+    capacity: usize,
+    state: Mutex<LogBufferState>,
+}
+
+struct LogBufferState {
+    entries: VecDeque<LogEntry>,
+    /// Sequence number the next captured entry gets
+    next_seq: u64,
+}
+
+impl LogBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(LogBufferState {
+                entries: VecDeque::with_capacity(capacity),
+                next_seq: 0,
+            }),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn push(&self, entry: LogEntry) {
+        let mut state = self.state.lock().unwrap();
+        if state.entries.len() == self.capacity {
+            state.entries.pop_front();
+        }
+        state.entries.push_back(entry);
+        state.next_seq += 1;
+    }
+
+    /// Entries captured from sequence number `seq` on that are at or above the
+    /// given severity, oldest first, along with the sequence number to resume from
+    pub fn since(&self, seq: u64, min_level: Level) -> (Vec<LogEntry>, u64) {
+        let state = self.state.lock().unwrap();
+        let first_seq = state.next_seq - state.entries.len() as u64;
+        let entries = state
+            .entries
+            .iter()
+            .skip(seq.saturating_sub(first_seq) as usize)
+            .filter(|entry| entry.level <= min_level)
+            .cloned()
+            .collect();
+        (entries, state.next_seq)
+    }
+
+    /// All buffered entries at or above the given severity, oldest first
+    pub fn snapshot(&self, min_level: Level) -> Vec<LogEntry> {
+        self.since(0, min_level).0
+    }
+}
+
+/// Layer that copies every event into [`LOG_BUFFER`]
+struct CaptureLayer;
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        LOG_BUFFER.push(LogEntry {
+            timestamp: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+/// Collects an event's message followed by its other fields as `key=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else if self.message.is_empty() {
+            self.fields
+        } else {
+            format!("{} {}", self.message, self.fields)
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            if !self.fields.is_empty() {
+                self.fields.push(' ');
+            }
+            let _ = write!(self.fields, "{}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Handle to swap the level filter once the configuration is loaded
+static FILTER_HANDLE: OnceCell<reload::Handle<EnvFilter, Registry>> = OnceCell::new();
+
+/// Whether events are also written to [`LOG_FILE`]
+static FILE_LOGGING: AtomicBool = AtomicBool::new(false);
+
+static LOG_FILE: Lazy<RotatingLogFile> = Lazy::new(|| RotatingLogFile::new(log_directory()));
+
+/// Directory holding the log files
+pub fn log_directory() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("reel")
+        .join("logs")
+}
+
+fn level_filter(level: LevelFilter) -> EnvFilter {
+    EnvFilter::new(format!("reel={}", level))
+}
+
+/// Install the global subscriber: terminal output, the in-memory buffer and the
+/// log file, logging at debug level until [`apply_config`] is called
+pub fn init() {
+    let (filter, handle) = reload::Layer::new(level_filter(LevelFilter::DEBUG));
+    let _ = FILTER_HANDLE.set(handle);
+
+    let file_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(LogFileWriter)
+        .with_filter(filter_fn(|_| FILE_LOGGING.load(Ordering::Relaxed)));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(file_layer)
+        .with(CaptureLayer)
+        .init();
+}
+
+/// Apply the configured level and file logging to the running subscriber
+pub fn apply_config(config: &LoggingConfig) {
+    if let Some(handle) = FILTER_HANDLE.get() {
+        match config.level.parse::<LevelFilter>() {
+            Ok(level) => {
+                let filter = level_filter(level);
+                let changed = handle
+                    .with_current(|current| current.to_string() != filter.to_string())
+                    .unwrap_or(true);
+                if changed && let Err(e) = handle.reload(filter) {
+                    tracing::error!("Failed to change the log level: {}", e);
+                }
+            }
+            Err(_) => tracing::warn!("Ignoring unknown log level {:?}", config.level),
+        }
+    }
+
+    let was_enabled = FILE_LOGGING.swap(config.file_logging, Ordering::Relaxed);
+    if was_enabled && !config.file_logging {
+        LOG_FILE.close();
+    } else if !was_enabled && config.file_logging {
+        tracing::info!("Writing log files to {:?}", log_directory());
+    }
+}
+
+/// Log file that is moved aside once it grows past [`LOG_FILE_MAX_BYTES`],
+/// keeping up to [`LOG_FILES_KEPT`] older files as `reel.log.1`, `reel.log.2`…
+struct RotatingLogFile {
+    directory: PathBuf,
+    /// The open file and its current size, opened on the first write
+    file: Mutex<Option<(File, u64)>>,
+}
+
+impl RotatingLogFile {
+    fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            file: Mutex::new(None),
+        }
+    }
+
+    fn path(&self, index: usize) -> PathBuf {
+        if index == 0 {
+            self.directory.join("reel.log")
+        } else {
+            self.directory.join(format!("reel.log.{}", index))
+        }
+    }
+
+    fn open(&self) -> io::Result<(File, u64)> {
+        fs::create_dir_all(&self.directory)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(0))?;
+        let size = file.metadata()?.len();
+        Ok((file, size))
+    }
+
+    fn rotate(&self) -> io::Result<()> {
+        for index in (1..LOG_FILES_KEPT).rev() {
+            let from = self.path(index);
+            if from.exists() {
+                fs::rename(from, self.path(index + 1))?;
+            }
+        }
+        fs::rename(self.path(0), self.path(1))
+    }
+
+    fn write(&self, buf: &[u8]) -> io::Result<()> {
+        let mut current = self.file.lock().unwrap();
+        let (mut file, mut size) = match current.take() {
+            Some(open) => open,
+            None => self.open()?,
+        };
+
+        if size > 0 && size + buf.len() as u64 > LOG_FILE_MAX_BYTES {
+            drop(file);
+            self.rotate()?;
+            (file, size) = self.open()?;
+        }
+
+        file.write_all(buf)?;
+        *current = Some((file, size + buf.len() as u64));
+        Ok(())
+    }
+
+    fn close(&self) {
+        self.file.lock().unwrap().take();
+    }
+}
+
+/// Writer handed to the file layer, forwarding to [`LOG_FILE`]
+struct LogFileWriter;
+
+impl Write for LogFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        LOG_FILE.write(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogFileWriter {
+    type Writer = LogFileWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogFileWriter
+    }
+}
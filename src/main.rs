@@ -6,9 +6,7 @@ fn main() -> Result<()> {
     use tracing::info;
 
     // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter("reel=debug")
-        .init();
+    reel::logging::init();
 
     info!("Starting Reel application");
 
@@ -47,6 +47,7 @@ impl TryFrom<MediaItemModel> for MediaItem {
                     .and_then(|s| DateTimeTransformer::from_rfc3339(Some(s)));
                 let playback_position = JsonTransformer::extract::<u64>(&metadata, "playback_position_ms")
                     .map(Duration::from_millis);
+                let video_height = JsonTransformer::extract::<u32>(&metadata, "video_height");
 
                 Ok(MediaItem::Movie(Movie {
                     id: model.id.clone(),
@@ -69,6 +70,7 @@ impl TryFrom<MediaItemModel> for MediaItem {
                     playback_position,
                     intro_marker: None,
                     credits_marker: None,
+                    video_height,
                 }))
             }
             "show" => {
@@ -141,6 +143,7 @@ impl TryFrom<MediaItemModel> for MediaItem {
                     show_poster_url,
                     intro_marker: None,
                     credits_marker: None,
+                    video_height: JsonTransformer::extract::<u32>(&metadata, "video_height"),
                 }))
             }
             "album" => Ok(MediaItem::MusicAlbum(MusicAlbum {
@@ -305,6 +308,15 @@ impl MediaItem {
                     "view_count": movie.view_count,
                     "last_watched_at": movie.last_watched_at.map(|dt| dt.to_rfc3339()),
                     "playback_position_ms": movie.playback_position.map(|d| d.as_millis() as u64),
+                    "video_height": movie.video_height,
+                    "hdr_format": movie.hdr_format,
+                    "atmos": movie.atmos,
+                    "versions": movie.versions,
+                    "external_ids": movie.external_ids,
+                    "studios": movie.studios,
+                    "tags": movie.tags,
+                    "collections": movie.collections,
+                    "content_rating": movie.content_rating,
                 })
             }
             MediaItem::Show(show) => {
@@ -313,6 +325,11 @@ impl MediaItem {
                     "watched_episode_count": show.watched_episode_count,
                     "total_episode_count": show.total_episode_count,
                     "last_watched_at": show.last_watched_at.map(|dt| dt.to_rfc3339()),
+                    "external_ids": show.external_ids,
+                    "studios": show.studios,
+                    "tags": show.tags,
+                    "collections": show.collections,
+                    "content_rating": show.content_rating,
                 })
             }
             MediaItem::Episode(episode) => {
@@ -324,6 +341,7 @@ impl MediaItem {
                     "air_date": episode.air_date.map(|dt| dt.to_rfc3339()),
                     "show_title": episode.show_title,
                     "show_poster_url": episode.show_poster_url,
+                    "video_height": episode.video_height,
                 })
             }
             MediaItem::MusicAlbum(album) => {
@@ -3,7 +3,7 @@
 #[cfg(test)]
 mod tests {
     use crate::db::entities::media_items::Model as MediaItemModel;
-    use crate::models::{Episode, MediaItem, Movie, Person, Show};
+    use crate::models::{Episode, MediaItem, MediaVersion, Movie, Person, Show};
     use chrono::Utc;
     use std::time::Duration;
 
@@ -39,6 +39,28 @@ mod tests {
             playback_position: None,
             intro_marker: None,
             credits_marker: None,
+            video_height: Some(2160),
+            hdr_format: Some("Dolby Vision".to_string()),
+            atmos: true,
+            versions: vec![
+                MediaVersion {
+                    id: "media-1".to_string(),
+                    video_height: Some(2160),
+                    container: Some("mkv".to_string()),
+                    size: Some(60_000_000_000),
+                },
+                MediaVersion {
+                    id: "media-2".to_string(),
+                    video_height: Some(1080),
+                    container: Some("mp4".to_string()),
+                    size: Some(8_000_000_000),
+                },
+            ],
+            external_ids: vec![],
+            studios: vec![],
+            tags: vec![],
+            collections: vec![],
+            content_rating: None,
         }
     }
 
@@ -67,6 +89,11 @@ mod tests {
             watched_episode_count: 5,
             total_episode_count: 10,
             last_watched_at: None,
+            external_ids: vec!["tvdb://81189".to_string()],
+            studios: vec!["HBO".to_string()],
+            tags: vec!["Rewatch".to_string()],
+            collections: vec!["Emmy Winners".to_string()],
+            content_rating: Some("TV-MA".to_string()),
         }
     }
 
@@ -90,6 +117,7 @@ mod tests {
             show_poster_url: None,
             intro_marker: None,
             credits_marker: None,
+            video_height: Some(1080),
         }
     }
 
@@ -118,6 +146,9 @@ mod tests {
         );
         assert_eq!(model.overview, Some("A test movie description".to_string()));
 
+        // Video and audio quality become card badges
+        assert_eq!(model.get_quality_badges(), vec!["4K", "DV", "Atmos"]);
+
         // Check genres
         assert!(model.genres.is_some());
         let genres_value = model.genres.unwrap();
@@ -146,6 +177,19 @@ mod tests {
         let genres_value = model.genres.unwrap();
         let genres: Vec<String> = serde_json::from_value(genres_value).unwrap();
         assert_eq!(genres, vec!["Drama", "Mystery"]);
+
+        // External ids, studios, tags, collections and the content rating
+        // survive the round trip through the metadata JSON
+        match MediaItem::try_from(media_item.to_model("source-2", None)).unwrap() {
+            MediaItem::Show(restored) => {
+                assert_eq!(restored.external_ids, show.external_ids);
+                assert_eq!(restored.studios, show.studios);
+                assert_eq!(restored.tags, show.tags);
+                assert_eq!(restored.collections, show.collections);
+                assert_eq!(restored.content_rating, show.content_rating);
+            }
+            _ => panic!("Expected Show variant"),
+        }
     }
 
     #[test]
@@ -408,8 +452,25 @@ mod tests {
                 assert_eq!(converted_movie.year, movie.year);
                 assert_eq!(converted_movie.rating, movie.rating);
                 assert_eq!(converted_movie.genres, movie.genres);
+                assert_eq!(converted_movie.video_height, movie.video_height);
+                assert_eq!(converted_movie.hdr_format, movie.hdr_format);
+                assert_eq!(converted_movie.atmos, movie.atmos);
+                assert_eq!(converted_movie.versions, movie.versions);
             }
             _ => panic!("Expected Movie variant"),
         }
     }
+
+    #[test]
+    fn test_episode_round_trip_keeps_video_height() {
+        let episode = create_test_episode();
+        let model = MediaItem::Episode(episode.clone()).to_model("source-1", None);
+
+        match MediaItem::try_from(model).unwrap() {
+            MediaItem::Episode(converted) => {
+                assert_eq!(converted.video_height, episode.video_height);
+            }
+            _ => panic!("Expected Episode variant"),
+        }
+    }
 }
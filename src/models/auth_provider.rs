@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::models::ConnectionPreferences;
+
 /// Represents different authentication providers for media sources
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type")]
@@ -25,6 +27,15 @@ pub enum AuthProvider {
         #[serde(skip)]
         access_token: String, // Store in keyring
     },
+    /// Direct Emby server connection
+    EmbyAuth {
+        id: String,
+        server_url: String,
+        username: String,
+        user_id: String,
+        #[serde(skip)]
+        access_token: String, // Store in keyring
+    },
     /// Network share credentials (SMB, NFS, WebDAV, etc.)
     NetworkCredentials {
         id: String,
@@ -42,6 +53,7 @@ impl AuthProvider {
         match self {
             Self::PlexAccount { id, .. } => id,
             Self::JellyfinAuth { id, .. } => id,
+            Self::EmbyAuth { id, .. } => id,
             Self::NetworkCredentials { id, .. } => id,
             Self::LocalFiles { id } => id,
         }
@@ -54,6 +66,11 @@ impl AuthProvider {
                 username,
                 server_url,
                 ..
+            }
+            | Self::EmbyAuth {
+                username,
+                server_url,
+                ..
             } => {
                 format!("{} @ {}", username, server_url)
             }
@@ -66,6 +83,7 @@ impl AuthProvider {
         match self {
             Self::PlexAccount { .. } => "plex",
             Self::JellyfinAuth { .. } => "jellyfin",
+            Self::EmbyAuth { .. } => "emby",
             Self::NetworkCredentials { .. } => "network",
             Self::LocalFiles { .. } => "local",
         }
@@ -89,7 +107,9 @@ impl AuthProvider {
     pub fn auth_token(&self) -> Option<&str> {
         match self {
             Self::PlexAccount { token, .. } => Some(token),
-            Self::JellyfinAuth { access_token, .. } => Some(access_token),
+            Self::JellyfinAuth { access_token, .. } | Self::EmbyAuth { access_token, .. } => {
+                Some(access_token)
+            }
             Self::NetworkCredentials { .. } | Self::LocalFiles { .. } => None,
         }
     }
@@ -142,6 +162,15 @@ pub struct Source {
     #[serde(default)]
     pub auth_status: crate::models::AuthStatus,
     pub last_auth_check: Option<DateTime<Utc>>,
+    /// MAC address used to wake the server via Wake-on-LAN
+    #[serde(default)]
+    pub mac_address: Option<String>,
+    /// Device name and identifier reported to the server
+    #[serde(default)]
+    pub client_identity: ClientIdentity,
+    /// Which connections to the server may be used, and in what order
+    #[serde(default)]
+    pub connection_preferences: ConnectionPreferences,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -151,6 +180,7 @@ pub enum SourceType {
         owned: bool,
     },
     JellyfinServer,
+    EmbyServer,
     NetworkShare {
         path: String,
         share_type: NetworkAuthType,
@@ -169,6 +199,58 @@ pub struct ConnectionInfo {
     pub connection_quality: Option<String>, // "local", "remote", or "relay"
 }
 
+/// What Reel reports to a source's server. Unset fields keep the backend's
+/// default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientIdentity {
+    /// Name the server shows for this device
+    #[serde(default)]
+    pub device_name: Option<String>,
+    /// Identifier the server tells devices apart by
+    #[serde(default)]
+    pub client_identifier: Option<String>,
+    /// Platform reported for the device, e.g. "Linux" (Plex only)
+    #[serde(default)]
+    pub platform: Option<String>,
+}
+
+impl ClientIdentity {
+    /// Identity from what the user typed, blank fields left unset
+    pub fn from_input(device_name: &str, client_identifier: &str, platform: &str) -> Self {
+        let field = |value: &str| {
+            let value = value.trim();
+            (!value.is_empty()).then(|| value.to_string())
+        };
+        Self {
+            device_name: field(device_name),
+            client_identifier: field(client_identifier),
+            platform: field(platform),
+        }
+    }
+
+    /// Whether nothing is overridden
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Identity stored in a source's `client_identity` column
+    pub fn from_json(value: Option<&serde_json::Value>) -> Self {
+        value
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Value for a source's `client_identity` column, None when nothing is
+    /// overridden
+    pub fn to_json(&self) -> Option<serde_json::Value> {
+        if self.is_default() {
+            None
+        } else {
+            serde_json::to_value(self).ok()
+        }
+    }
+}
+
 impl Source {
     pub fn new(
         id: String,
@@ -192,6 +274,9 @@ impl Source {
             library_count: 0,
             auth_status: crate::models::AuthStatus::Unknown,
             last_auth_check: None,
+            mac_address: None,
+            client_identity: ClientIdentity::default(),
+            connection_preferences: ConnectionPreferences::default(),
         }
     }
 
@@ -206,7 +291,7 @@ impl Source {
     pub fn source_icon(&self) -> &'static str {
         match &self.source_type {
             SourceType::PlexServer { .. } => "network-server-symbolic",
-            SourceType::JellyfinServer => "network-workgroup-symbolic",
+            SourceType::JellyfinServer | SourceType::EmbyServer => "network-workgroup-symbolic",
             SourceType::NetworkShare { .. } => "folder-remote-symbolic",
             SourceType::LocalFolder { .. } => "folder-symbolic",
         }
@@ -222,6 +307,7 @@ impl From<crate::db::entities::SourceModel> for Source {
                 owned: model.is_owned,
             },
             "jellyfin" => SourceType::JellyfinServer,
+            "emby" => SourceType::EmbyServer,
             "local" => SourceType::LocalFolder {
                 path: PathBuf::from(model.connection_url.as_deref().unwrap_or("/")),
             },
@@ -250,6 +336,11 @@ impl From<crate::db::entities::SourceModel> for Source {
             last_auth_check: model
                 .last_auth_check
                 .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc)),
+            mac_address: model.mac_address,
+            client_identity: ClientIdentity::from_json(model.client_identity.as_ref()),
+            connection_preferences: ConnectionPreferences::from_json(
+                model.connection_preferences.as_ref(),
+            ),
         }
     }
 }
@@ -54,6 +54,66 @@ impl ServerConnection {
     }
 }
 
+/// Which of a source's connections may be used, and which go first. Relay
+/// connections are bandwidth-capped, so they can quietly lower quality.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionPreferences {
+    /// Use a local connection whenever one answers, however fast others are
+    #[serde(default)]
+    pub prefer_local: bool,
+    /// Never connect through a relay
+    #[serde(default)]
+    pub never_relay: bool,
+    /// Connection URL tried before all others
+    #[serde(default)]
+    pub preferred_url: Option<String>,
+}
+
+impl ConnectionPreferences {
+    /// Whether a connection may be used at all
+    pub fn allows(&self, relay: bool) -> bool {
+        !(relay && self.never_relay)
+    }
+
+    /// Order connections are tried in, lower first. Connections in the same
+    /// tier race each other.
+    pub fn tier(&self, uri: &str, local: bool) -> u8 {
+        if self
+            .preferred_url
+            .as_deref()
+            .is_some_and(|url| url.trim_end_matches('/') == uri.trim_end_matches('/'))
+        {
+            0
+        } else if self.prefer_local && local {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Whether nothing is constrained
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Preferences stored in a source's `connection_preferences` column
+    pub fn from_json(value: Option<&serde_json::Value>) -> Self {
+        value
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Value for a source's `connection_preferences` column, None when
+    /// nothing is constrained
+    pub fn to_json(&self) -> Option<serde_json::Value> {
+        if self.is_default() {
+            None
+        } else {
+            serde_json::to_value(self).ok()
+        }
+    }
+}
+
 /// Collection of server connections with selection logic
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConnections {
@@ -69,12 +129,15 @@ impl ServerConnections {
         }
     }
 
-    /// Get the best available connection
-    pub fn best_connection(&self) -> Option<&ServerConnection> {
+    /// Get the best available connection the preferences allow
+    pub fn best_connection(
+        &self,
+        preferences: &ConnectionPreferences,
+    ) -> Option<&ServerConnection> {
         self.connections
             .iter()
-            .filter(|c| c.is_available)
-            .min_by_key(|c| c.priority_score())
+            .filter(|c| c.is_available && preferences.allows(c.relay))
+            .min_by_key(|c| (preferences.tier(&c.uri, c.local), c.priority_score()))
     }
 
     /// Get all local connections
@@ -98,3 +161,86 @@ impl ServerConnections {
         self.connections.iter().filter(|c| c.relay).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(uri: &str, local: bool, relay: bool, response_time_ms: u64) -> ServerConnection {
+        ServerConnection {
+            uri: uri.to_string(),
+            protocol: "https".to_string(),
+            address: String::new(),
+            port: 32400,
+            local,
+            relay,
+            priority: 0,
+            is_available: true,
+            response_time_ms: Some(response_time_ms),
+        }
+    }
+
+    #[test]
+    fn test_never_relay_rules_out_relays() {
+        let connections = ServerConnections::new(vec![
+            connection("https://relay.plex.direct:8443", false, true, 50),
+            connection("https://remote.plex.direct:32400", false, false, 900),
+        ]);
+        let preferences = ConnectionPreferences {
+            never_relay: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            connections.best_connection(&preferences).unwrap().uri,
+            "https://remote.plex.direct:32400"
+        );
+
+        let relay_only = ServerConnections::new(vec![connection(
+            "https://relay.plex.direct:8443",
+            false,
+            true,
+            50,
+        )]);
+        assert!(relay_only.best_connection(&preferences).is_none());
+        assert!(
+            relay_only
+                .best_connection(&ConnectionPreferences::default())
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_preferred_url_goes_first() {
+        let connections = ServerConnections::new(vec![
+            connection("http://192.168.1.10:32400", true, false, 5),
+            connection("https://media.example.com", false, false, 300),
+        ]);
+        let preferences = ConnectionPreferences {
+            prefer_local: true,
+            preferred_url: Some("https://media.example.com/".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            connections.best_connection(&preferences).unwrap().uri,
+            "https://media.example.com"
+        );
+        assert_eq!(preferences.tier("http://192.168.1.10:32400", true), 1);
+        assert_eq!(preferences.tier("https://other.example.com", false), 2);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        assert_eq!(ConnectionPreferences::default().to_json(), None);
+
+        let preferences = ConnectionPreferences {
+            prefer_local: true,
+            never_relay: true,
+            preferred_url: None,
+        };
+        let stored = preferences.to_json();
+        assert_eq!(
+            ConnectionPreferences::from_json(stored.as_ref()),
+            preferences
+        );
+    }
+}
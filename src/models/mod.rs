@@ -1,12 +1,18 @@
 pub mod auth_provider;
 pub mod connection;
 mod identifiers;
+mod playback_error;
 pub mod playlist_context;
+mod session_report;
+mod transcode_session;
 
-pub use auth_provider::{AuthProvider, ConnectionInfo, Source, SourceType};
-pub use connection::{ServerConnection, ServerConnections};
-pub use identifiers::{LibraryId, MediaItemId, ShowId, SourceId};
+pub use auth_provider::{AuthProvider, ClientIdentity, ConnectionInfo, Source, SourceType};
+pub use connection::{ConnectionPreferences, ServerConnection, ServerConnections};
+pub use identifiers::{LibraryId, MediaItemId, ShowId, SourceId, UserId};
+pub use playback_error::PlaybackError;
 pub use playlist_context::{EpisodeInfo, PlayQueueInfo, PlaylistContext, QueueItem};
+pub use session_report::{SessionEvent, SessionReport, SessionStream};
+pub use transcode_session::{TranscodeServer, TranscodeSession};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -125,6 +131,24 @@ pub struct Movie {
     pub playback_position: Option<Duration>,
     pub intro_marker: Option<ChapterMarker>, // Intro/opening credits marker
     pub credits_marker: Option<ChapterMarker>, // End credits marker
+    pub video_height: Option<u32>,           // Vertical resolution of the main video stream
+    pub hdr_format: Option<String>, // HDR format of the main video stream, like "Dolby Vision"
+    pub atmos: bool,                // Whether an audio stream is Dolby Atmos
+    pub versions: Vec<MediaVersion>, // Files the server holds for the movie
+    pub external_ids: Vec<String>,  // Ids in outside databases, like "imdb://tt0111161"
+    pub studios: Vec<String>,       // Studios that made the movie
+    pub tags: Vec<String>,          // Tags set on the server
+    pub collections: Vec<String>,   // Collections the movie is in
+    pub content_rating: Option<String>, // Parental rating, like "PG-13"
+}
+
+/// One of the files a server holds for a movie, such as a 4K and a 1080p copy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaVersion {
+    pub id: String, // Plex media id or Jellyfin media source id
+    pub video_height: Option<u32>,
+    pub container: Option<String>,
+    pub size: Option<u64>, // File size in bytes
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -145,6 +169,11 @@ pub struct Show {
     pub watched_episode_count: u32,
     pub total_episode_count: u32,
     pub last_watched_at: Option<DateTime<Utc>>,
+    pub external_ids: Vec<String>, // Ids in outside databases, like "tvdb://81189"
+    pub studios: Vec<String>,      // Networks or studios that made the show, like "HBO"
+    pub tags: Vec<String>,         // Tags set on the server
+    pub collections: Vec<String>,  // Collections the show is in
+    pub content_rating: Option<String>, // Parental rating, like "TV-Y7"
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -155,6 +184,24 @@ pub struct Season {
     pub poster_url: Option<String>,
 }
 
+/// How many episodes of a season have been watched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeasonProgress {
+    pub watched: u64,
+    pub total: u64,
+}
+
+impl SeasonProgress {
+    /// Watched share of the season, from 0.0 to 1.0
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.watched as f64 / self.total as f64
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Episode {
     pub id: String,
@@ -175,6 +222,7 @@ pub struct Episode {
     pub show_poster_url: Option<String>,       // Parent show poster URL
     pub intro_marker: Option<ChapterMarker>,   // Intro/opening credits marker
     pub credits_marker: Option<ChapterMarker>, // End credits marker
+    pub video_height: Option<u32>,             // Vertical resolution of the main video stream
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -204,6 +252,8 @@ pub struct Person {
 pub struct StreamInfo {
     pub url: String,
     pub direct_play: bool,
+    /// How the server delivers the stream at `url`
+    pub mode: StreamMode,
     pub video_codec: String,
     pub audio_codec: String,
     pub container: String,
@@ -212,7 +262,28 @@ pub struct StreamInfo {
     pub quality_options: Vec<QualityOption>,
 }
 
+/// How a server delivers a stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StreamMode {
+    /// The original file, as stored
+    DirectPlay,
+    /// The original streams, repackaged by the server
+    DirectStream,
+    /// Converted by the server
+    Transcode,
+}
+
+/// A single-file download of a media item for offline use
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadInfo {
+    pub url: String,
+    /// File extension of the downloaded file (e.g. "mkv")
+    pub container: String,
+    /// Whether the server transcodes the file to reach the requested quality
+    pub requires_transcode: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct QualityOption {
     pub name: String,
     pub resolution: Resolution,
@@ -221,7 +292,7 @@ pub struct QualityOption {
     pub requires_transcode: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct Resolution {
     pub width: u32,
     pub height: u32,
@@ -307,6 +378,9 @@ pub enum HomeSectionType {
     RecentlyPlayed,
     RecentPlaylists,
 
+    // Items the user marked as favorite, kept locally rather than served by the backend
+    Favorites,
+
     // Fallback for unknown types
     Custom(String),
 }
@@ -419,8 +493,11 @@ impl MediaItem {
     }
 
     pub fn content_rating(&self) -> Option<&str> {
-        // TODO: content_rating field doesn't exist, stub for now
-        None
+        match self {
+            MediaItem::Movie(m) => m.content_rating.as_deref(),
+            MediaItem::Show(s) => s.content_rating.as_deref(),
+            _ => None,
+        }
     }
 
     pub fn duration_millis(&self) -> Option<u64> {
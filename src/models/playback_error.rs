@@ -0,0 +1,100 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+/// Why an item failed to play, by what the user can do about it
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PlaybackError {
+    /// The server no longer accepts the sign-in of the source
+    #[error("Your sign-in to the server has expired")]
+    AuthExpired,
+
+    /// The server wouldn't convert the item into a stream for this player
+    #[error("The server refused to convert this video")]
+    TranscodeRefused,
+
+    /// The server didn't answer in time
+    #[error("The server took too long to respond")]
+    NetworkTimeout,
+
+    /// The player has no decoder for the item, with its codecs when known
+    #[error("This video can't be played on this device")]
+    UnsupportedCodec { codecs: Option<String> },
+
+    /// Any other failure, with its message
+    #[error("{0}")]
+    Other(String),
+}
+
+impl PlaybackError {
+    /// The playback error behind an error, looking through its causes
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if let Some(error) = cause.downcast_ref::<PlaybackError>() {
+                return error.clone();
+            }
+            if let Some(error) = cause.downcast_ref::<reqwest::Error>() {
+                if error.is_timeout() {
+                    return PlaybackError::NetworkTimeout;
+                }
+                if let Some(error) = error.status().and_then(Self::from_status) {
+                    return error;
+                }
+            }
+        }
+        PlaybackError::Other(error.to_string())
+    }
+
+    /// The playback error for a server answering a playback request with
+    /// `status`, if the user can do something about it
+    pub fn from_status(status: StatusCode) -> Option<Self> {
+        match status {
+            StatusCode::UNAUTHORIZED => Some(PlaybackError::AuthExpired),
+            StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => {
+                Some(PlaybackError::NetworkTimeout)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_playback_errors_are_found_behind_context() {
+        let error = Err::<(), _>(PlaybackError::AuthExpired)
+            .context("Failed to get stream URL")
+            .unwrap_err();
+        assert_eq!(
+            PlaybackError::from_error(&error),
+            PlaybackError::AuthExpired
+        );
+    }
+
+    #[test]
+    fn test_other_errors_keep_their_message() {
+        let error = anyhow::anyhow!("No media sources available");
+        assert_eq!(
+            PlaybackError::from_error(&error),
+            PlaybackError::Other("No media sources available".to_string())
+        );
+    }
+
+    #[test]
+    fn test_statuses_the_user_can_act_on() {
+        assert_eq!(
+            PlaybackError::from_status(StatusCode::UNAUTHORIZED),
+            Some(PlaybackError::AuthExpired)
+        );
+        assert_eq!(
+            PlaybackError::from_status(StatusCode::GATEWAY_TIMEOUT),
+            Some(PlaybackError::NetworkTimeout)
+        );
+        assert_eq!(
+            PlaybackError::from_status(StatusCode::INTERNAL_SERVER_ERROR),
+            None
+        );
+    }
+}
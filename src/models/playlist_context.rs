@@ -25,8 +25,8 @@ pub enum PlaylistContext {
 
     /// Generic PlayQueue context (for movies, mixed content, playlists)
     PlayQueue {
-        /// Plex PlayQueue metadata
-        play_queue_info: PlayQueueInfo,
+        /// Plex PlayQueue metadata, None for a queue built locally
+        play_queue_info: Option<PlayQueueInfo>,
         /// Current item index in the queue
         current_index: usize,
         /// All items in the queue
@@ -88,6 +88,23 @@ pub struct EpisodeInfo {
 }
 
 impl PlaylistContext {
+    /// Get the item at the current position in the playlist
+    pub fn get_current_item(&self) -> Option<MediaItemId> {
+        match self {
+            PlaylistContext::SingleItem => None,
+            PlaylistContext::TvShow {
+                episodes,
+                current_index,
+                ..
+            } => episodes.get(*current_index).map(|e| e.id.clone()),
+            PlaylistContext::PlayQueue {
+                items,
+                current_index,
+                ..
+            } => items.get(*current_index).map(|i| i.id.clone()),
+        }
+    }
+
     /// Get the next item in the playlist after the current one
     pub fn get_next_item(&self) -> Option<MediaItemId> {
         match self {
@@ -173,6 +190,7 @@ impl PlaylistContext {
                     // Update the selected item ID in PlayQueue info
                     if let Some(item) = items.get(new_index)
                         && let Some(item_id) = item.play_queue_item_id
+                        && let Some(play_queue_info) = play_queue_info
                     {
                         play_queue_info.play_queue_item_id = item_id;
                     }
@@ -241,7 +259,7 @@ impl PlaylistContext {
             } => play_queue_info.as_ref(),
             PlaylistContext::PlayQueue {
                 play_queue_info, ..
-            } => Some(play_queue_info),
+            } => play_queue_info.as_ref(),
         }
     }
 
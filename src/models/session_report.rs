@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use super::{MediaItemId, PlayQueueInfo};
+
+/// Where the playing item streams from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStream {
+    /// The original file, from the server
+    Direct,
+    /// A transcode the server runs
+    Transcode,
+    /// A file on this machine, like an offline download
+    Local,
+}
+
+impl SessionStream {
+    /// Where a stream URL streams from
+    pub fn of_url(url: &str) -> Self {
+        if url.starts_with("file://") {
+            Self::Local
+        } else if super::TranscodeSession::from_url(url).is_some() {
+            Self::Transcode
+        } else {
+            Self::Direct
+        }
+    }
+}
+
+/// What happened to a playback session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    Started,
+    Progress,
+    Stopped,
+}
+
+/// A report about playback of an item, telling its server which item plays
+/// where, so it lists the session as now playing. Playback reports the same
+/// way whatever it streams from.
+#[derive(Debug, Clone)]
+pub struct SessionReport {
+    pub media_id: MediaItemId,
+    pub event: SessionEvent,
+    pub stream: SessionStream,
+    /// Id the server ties the reports of one playback together by, the
+    /// session of the transcode when transcoding
+    pub play_session_id: String,
+    /// Position in the item
+    pub position: Duration,
+    pub duration: Duration,
+    pub paused: bool,
+    /// Plex PlayQueue the item plays in, if any
+    pub play_queue: Option<PlayQueueInfo>,
+}
@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use url::Url;
+
+/// Server running a transcode, which decides how its playlist URL is built
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscodeServer {
+    Plex,
+    Jellyfin,
+}
+
+/// A server-side HLS transcode that playback streams from.
+///
+/// The playlist of a transcode only covers what the server has converted so
+/// far and starts at the point the transcode was started from, so seeking
+/// far ahead restarts the transcode at the new position instead of seeking
+/// in the playlist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscodeSession {
+    pub server: TranscodeServer,
+    /// Session the server knows the transcode by
+    pub id: String,
+    /// Playlist of the transcode
+    pub url: String,
+    /// Position in the item the transcode starts at
+    pub offset: Duration,
+}
+
+impl TranscodeSession {
+    /// The transcode session behind a stream URL, None for anything that
+    /// isn't an HLS transcode
+    pub fn from_url(url: &str) -> Option<Self> {
+        let parsed = Url::parse(url).ok()?;
+        let path = parsed.path();
+        let param = |name: &str| {
+            parsed
+                .query_pairs()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.into_owned())
+        };
+
+        let (server, id, offset) = if path.ends_with("/transcode/universal/start.m3u8") {
+            let offset = param("offset").and_then(|secs| secs.parse::<f64>().ok());
+            (
+                TranscodeServer::Plex,
+                param("session")?,
+                offset.map(Duration::from_secs_f64),
+            )
+        } else if path.starts_with("/Videos/")
+            && (path.ends_with("/main.m3u8") || path.ends_with("/master.m3u8"))
+        {
+            let ticks = param("StartTimeTicks").and_then(|ticks| ticks.parse::<u64>().ok());
+            (
+                TranscodeServer::Jellyfin,
+                param("PlaySessionId")?,
+                ticks.map(|ticks| Duration::from_micros(ticks / 10)),
+            )
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            server,
+            id,
+            url: url.to_string(),
+            offset: offset.unwrap_or_default(),
+        })
+    }
+
+    /// The same transcode restarted at `offset` into the item. Plex needs a
+    /// new session for it, Jellyfin restarts the encoding of the session.
+    pub fn restarted_at(&self, offset: Duration) -> Self {
+        let Ok(mut url) = Url::parse(&self.url) else {
+            return self.clone();
+        };
+        let id = match self.server {
+            TranscodeServer::Plex => uuid::Uuid::new_v4().to_string(),
+            TranscodeServer::Jellyfin => self.id.clone(),
+        };
+        let (offset_param, offset_value, session_param) = match self.server {
+            TranscodeServer::Plex => ("offset", format!("{:.3}", offset.as_secs_f64()), "session"),
+            TranscodeServer::Jellyfin => (
+                "StartTimeTicks",
+                (offset.as_micros() * 10).to_string(),
+                "PlaySessionId",
+            ),
+        };
+
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| {
+                !key.eq_ignore_ascii_case(offset_param) && !key.eq_ignore_ascii_case(session_param)
+            })
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        url.query_pairs_mut()
+            .clear()
+            .extend_pairs(pairs)
+            .append_pair(session_param, &id)
+            .append_pair(offset_param, &offset_value);
+
+        Self {
+            server: self.server,
+            id,
+            url: url.to_string(),
+            offset,
+        }
+    }
+
+    /// Position in the item of a position in the playlist
+    pub fn item_position(&self, stream_position: Duration) -> Duration {
+        self.offset + stream_position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLEX: &str = "http://plex:32400/video/:/transcode/universal/start.m3u8?path=%2Flibrary%2Fmetadata%2F1&protocol=hls&session=abc&X-Plex-Token=t";
+    const JELLYFIN: &str =
+        "http://jf:8096/Videos/item/main.m3u8?mediaSourceId=item&api_key=k&PlaySessionId=play-1";
+
+    #[test]
+    fn test_sessions_are_found_in_transcode_urls() {
+        let plex = TranscodeSession::from_url(PLEX).unwrap();
+        assert_eq!(plex.server, TranscodeServer::Plex);
+        assert_eq!(plex.id, "abc");
+        assert_eq!(plex.offset, Duration::ZERO);
+
+        let jellyfin = TranscodeSession::from_url(JELLYFIN).unwrap();
+        assert_eq!(jellyfin.server, TranscodeServer::Jellyfin);
+        assert_eq!(jellyfin.id, "play-1");
+    }
+
+    #[test]
+    fn test_direct_streams_are_not_transcodes() {
+        assert_eq!(
+            TranscodeSession::from_url("http://plex:32400/library/parts/1/file.mkv?X-Plex-Token=t"),
+            None
+        );
+        assert_eq!(
+            TranscodeSession::from_url("http://jf:8096/Videos/item/stream?Static=true"),
+            None
+        );
+        assert_eq!(TranscodeSession::from_url("file:///movies/a.mkv"), None);
+    }
+
+    #[test]
+    fn test_plex_restarts_with_a_new_session_at_the_offset() {
+        let plex = TranscodeSession::from_url(PLEX).unwrap();
+        let restarted = plex.restarted_at(Duration::from_secs(600));
+        assert_ne!(restarted.id, plex.id);
+        assert_eq!(restarted.offset, Duration::from_secs(600));
+        assert_eq!(
+            TranscodeSession::from_url(&restarted.url),
+            Some(restarted.clone())
+        );
+        assert_eq!(
+            restarted.item_position(Duration::from_secs(5)),
+            Duration::from_secs(605)
+        );
+    }
+
+    #[test]
+    fn test_jellyfin_restarts_the_same_session_at_the_offset() {
+        let jellyfin = TranscodeSession::from_url(JELLYFIN).unwrap();
+        let restarted = jellyfin
+            .restarted_at(Duration::from_secs(90))
+            .restarted_at(Duration::from_secs(120));
+        assert_eq!(restarted.id, "play-1");
+        assert!(restarted.url.contains("StartTimeTicks=1200000000"));
+        assert_eq!(restarted.url.matches("StartTimeTicks").count(), 1);
+        assert_eq!(TranscodeSession::from_url(&restarted.url), Some(restarted));
+    }
+}
@@ -8,7 +8,7 @@ use tracing::{debug, trace};
 use super::{Player, PlayerState};
 use crate::config::Config;
 
-use crate::player::{UpscalingMode, ZoomMode};
+use crate::player::{Chapter, TrackLanguages, UpscalingMode, ZoomMode};
 
 #[cfg(feature = "gstreamer")]
 use crate::player::BufferingState;
@@ -71,6 +71,14 @@ pub enum PlayerCommand {
     GetSubtitleTracks {
         respond_to: oneshot::Sender<Vec<(i32, String)>>,
     },
+    /// Get the audio language and the subtitle languages
+    GetTrackLanguages {
+        respond_to: oneshot::Sender<TrackLanguages>,
+    },
+    /// Get the chapters of the loaded media
+    GetChapters {
+        respond_to: oneshot::Sender<Vec<Chapter>>,
+    },
     /// Set audio track
     SetAudioTrack {
         track_index: i32,
@@ -90,11 +98,36 @@ pub enum PlayerCommand {
         mode: UpscalingMode,
         respond_to: oneshot::Sender<Result<()>>,
     },
+    /// Use lighter playback settings to save power
+    SetPowerSaving {
+        enabled: bool,
+        respond_to: oneshot::Sender<Result<()>>,
+    },
+    /// Set subtitle size relative to the default
+    SetSubtitleScale {
+        scale: f64,
+        respond_to: oneshot::Sender<Result<()>>,
+    },
+    /// Set the character encoding of text subtitles
+    SetSubtitleCharset {
+        charset: String,
+        respond_to: oneshot::Sender<Result<()>>,
+    },
     /// Set playback speed
     SetPlaybackSpeed {
         speed: f64,
         respond_to: oneshot::Sender<Result<()>>,
     },
+    /// Keep the audio pitch when playing faster or slower
+    SetPitchCorrection {
+        enabled: bool,
+        respond_to: oneshot::Sender<Result<()>>,
+    },
+    /// Load a subtitle file as an extra track
+    AddSubtitleFile {
+        path: String,
+        respond_to: oneshot::Sender<Result<()>>,
+    },
     /// Frame step forward
     FrameStepForward {
         respond_to: oneshot::Sender<Result<()>>,
@@ -272,6 +305,14 @@ impl PlayerController {
                     let tracks = self.player.get_subtitle_tracks().await;
                     let _ = respond_to.send(tracks);
                 }
+                PlayerCommand::GetTrackLanguages { respond_to } => {
+                    let languages = self.player.get_track_languages().await;
+                    let _ = respond_to.send(languages);
+                }
+                PlayerCommand::GetChapters { respond_to } => {
+                    let chapters = self.player.get_chapters().await;
+                    let _ = respond_to.send(chapters);
+                }
                 PlayerCommand::SetAudioTrack {
                     track_index,
                     respond_to,
@@ -313,11 +354,45 @@ impl PlayerController {
                     };
                     let _ = respond_to.send(result);
                 }
+                PlayerCommand::SetPowerSaving {
+                    enabled,
+                    respond_to,
+                } => {
+                    trace!("Setting power saving to {}", enabled);
+                    let result = self.player.set_power_saving(enabled).await;
+                    let _ = respond_to.send(result);
+                }
+                PlayerCommand::SetSubtitleScale { scale, respond_to } => {
+                    trace!("Setting subtitle scale to {}", scale);
+                    let result = self.player.set_subtitle_scale(scale).await;
+                    let _ = respond_to.send(result);
+                }
+                PlayerCommand::SetSubtitleCharset {
+                    charset,
+                    respond_to,
+                } => {
+                    trace!("Setting subtitle encoding to {}", charset);
+                    let result = self.player.set_subtitle_charset(&charset).await;
+                    let _ = respond_to.send(result);
+                }
                 PlayerCommand::SetPlaybackSpeed { speed, respond_to } => {
                     trace!("Setting playback speed to {}", speed);
                     let result = self.player.set_playback_speed(speed).await;
                     let _ = respond_to.send(result);
                 }
+                PlayerCommand::SetPitchCorrection {
+                    enabled,
+                    respond_to,
+                } => {
+                    trace!("Setting pitch correction to {}", enabled);
+                    let result = self.player.set_pitch_correction(enabled).await;
+                    let _ = respond_to.send(result);
+                }
+                PlayerCommand::AddSubtitleFile { path, respond_to } => {
+                    trace!("Loading subtitle file {}", path);
+                    let result = self.player.add_subtitle_file(&path).await;
+                    let _ = respond_to.send(result);
+                }
                 PlayerCommand::FrameStepForward { respond_to } => {
                     trace!("Frame stepping forward");
                     let result = self.player.frame_step_forward().await;
@@ -550,6 +625,28 @@ impl PlayerHandle {
             .map_err(|_| anyhow::anyhow!("Failed to receive response from player controller"))
     }
 
+    /// Get the audio language and the subtitle languages
+    pub async fn get_track_languages(&self) -> Result<TrackLanguages> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(PlayerCommand::GetTrackLanguages { respond_to })
+            .map_err(|_| anyhow::anyhow!("Player controller disconnected"))?;
+        response
+            .await
+            .map_err(|_| anyhow::anyhow!("Failed to receive response from player controller"))
+    }
+
+    /// Get the chapters of the loaded media
+    pub async fn get_chapters(&self) -> Result<Vec<Chapter>> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(PlayerCommand::GetChapters { respond_to })
+            .map_err(|_| anyhow::anyhow!("Player controller disconnected"))?;
+        response
+            .await
+            .map_err(|_| anyhow::anyhow!("Failed to receive response from player controller"))
+    }
+
     /// Set audio track
     pub async fn set_audio_track(&self, track_index: i32) -> Result<()> {
         let (respond_to, response) = oneshot::channel();
@@ -611,6 +708,45 @@ impl PlayerHandle {
             .map_err(|_| anyhow::anyhow!("Failed to receive response from player controller"))?
     }
 
+    /// Use lighter playback settings to save power
+    pub async fn set_power_saving(&self, enabled: bool) -> Result<()> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(PlayerCommand::SetPowerSaving {
+                enabled,
+                respond_to,
+            })
+            .map_err(|_| anyhow::anyhow!("Player controller disconnected"))?;
+        response
+            .await
+            .map_err(|_| anyhow::anyhow!("Failed to receive response from player controller"))?
+    }
+
+    /// Set subtitle size relative to the default
+    pub async fn set_subtitle_scale(&self, scale: f64) -> Result<()> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(PlayerCommand::SetSubtitleScale { scale, respond_to })
+            .map_err(|_| anyhow::anyhow!("Player controller disconnected"))?;
+        response
+            .await
+            .map_err(|_| anyhow::anyhow!("Failed to receive response from player controller"))?
+    }
+
+    /// Set the character encoding of text subtitles, "auto" to detect it
+    pub async fn set_subtitle_charset(&self, charset: &str) -> Result<()> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(PlayerCommand::SetSubtitleCharset {
+                charset: charset.to_string(),
+                respond_to,
+            })
+            .map_err(|_| anyhow::anyhow!("Player controller disconnected"))?;
+        response
+            .await
+            .map_err(|_| anyhow::anyhow!("Failed to receive response from player controller"))?
+    }
+
     /// Set playback speed
     pub async fn set_playback_speed(&self, speed: f64) -> Result<()> {
         let (respond_to, response) = oneshot::channel();
@@ -622,6 +758,34 @@ impl PlayerHandle {
             .map_err(|_| anyhow::anyhow!("Failed to receive response from player controller"))?
     }
 
+    /// Keep the audio pitch when playing faster or slower
+    pub async fn set_pitch_correction(&self, enabled: bool) -> Result<()> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(PlayerCommand::SetPitchCorrection {
+                enabled,
+                respond_to,
+            })
+            .map_err(|_| anyhow::anyhow!("Player controller disconnected"))?;
+        response
+            .await
+            .map_err(|_| anyhow::anyhow!("Failed to receive response from player controller"))?
+    }
+
+    /// Load a subtitle file as an extra track and show it
+    pub async fn add_subtitle_file(&self, path: &str) -> Result<()> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(PlayerCommand::AddSubtitleFile {
+                path: path.to_string(),
+                respond_to,
+            })
+            .map_err(|_| anyhow::anyhow!("Player controller disconnected"))?;
+        response
+            .await
+            .map_err(|_| anyhow::anyhow!("Failed to receive response from player controller"))?
+    }
+
     /// Frame step forward
     pub async fn frame_step_forward(&self) -> Result<()> {
         let (respond_to, response) = oneshot::channel();
@@ -353,6 +353,24 @@ impl Player {
         }
     }
 
+    pub async fn get_track_languages(&self) -> crate::player::TrackLanguages {
+        match self {
+            #[cfg(feature = "gstreamer")]
+            Player::GStreamer(p) => p.get_track_languages().await,
+            #[cfg(all(feature = "mpv", not(target_os = "macos")))]
+            Player::Mpv(p) => p.get_track_languages().await,
+        }
+    }
+
+    pub async fn get_chapters(&self) -> Vec<crate::player::Chapter> {
+        match self {
+            #[cfg(feature = "gstreamer")]
+            Player::GStreamer(p) => p.get_chapters().await,
+            #[cfg(all(feature = "mpv", not(target_os = "macos")))]
+            Player::Mpv(p) => p.get_chapters().await,
+        }
+    }
+
     pub async fn set_audio_track(&self, track_index: i32) -> Result<()> {
         match self {
             #[cfg(feature = "gstreamer")]
@@ -389,6 +407,34 @@ impl Player {
         }
     }
 
+    pub async fn set_subtitle_scale(&self, scale: f64) -> Result<()> {
+        match self {
+            #[cfg(feature = "gstreamer")]
+            Player::GStreamer(p) => p.set_subtitle_scale(scale).await,
+            #[cfg(all(feature = "mpv", not(target_os = "macos")))]
+            Player::Mpv(p) => p.set_subtitle_scale(scale).await,
+        }
+    }
+
+    pub async fn set_subtitle_charset(&self, charset: &str) -> Result<()> {
+        match self {
+            #[cfg(feature = "gstreamer")]
+            Player::GStreamer(p) => p.set_subtitle_charset(charset).await,
+            #[cfg(all(feature = "mpv", not(target_os = "macos")))]
+            Player::Mpv(p) => p.set_subtitle_charset(charset).await,
+        }
+    }
+
+    pub async fn set_power_saving(&self, enabled: bool) -> Result<()> {
+        match self {
+            // GStreamer has no upscaling or cache settings to lighten
+            #[cfg(feature = "gstreamer")]
+            Player::GStreamer(_) => Ok(()),
+            #[cfg(all(feature = "mpv", not(target_os = "macos")))]
+            Player::Mpv(p) => p.set_power_saving(enabled).await,
+        }
+    }
+
     pub async fn set_playback_speed(&self, speed: f64) -> Result<()> {
         match self {
             #[cfg(feature = "gstreamer")]
@@ -398,6 +444,15 @@ impl Player {
         }
     }
 
+    pub async fn set_pitch_correction(&self, enabled: bool) -> Result<()> {
+        match self {
+            #[cfg(feature = "gstreamer")]
+            Player::GStreamer(p) => p.set_pitch_correction(enabled).await,
+            #[cfg(all(feature = "mpv", not(target_os = "macos")))]
+            Player::Mpv(p) => p.set_pitch_correction(enabled).await,
+        }
+    }
+
     pub async fn get_playback_speed(&self) -> f64 {
         match self {
             #[cfg(feature = "gstreamer")]
@@ -407,6 +462,18 @@ impl Player {
         }
     }
 
+    pub async fn add_subtitle_file(&self, path: &str) -> Result<()> {
+        match self {
+            // playbin only takes a subtitle file before playback starts
+            #[cfg(feature = "gstreamer")]
+            Player::GStreamer(_) => Err(anyhow::anyhow!(
+                "Loading subtitle files needs the MPV player"
+            )),
+            #[cfg(all(feature = "mpv", not(target_os = "macos")))]
+            Player::Mpv(p) => p.add_subtitle_file(path).await,
+        }
+    }
+
     pub async fn frame_step_forward(&self) -> Result<()> {
         match self {
             #[cfg(feature = "gstreamer")]
@@ -1,3 +1,4 @@
+use crate::player::Chapter;
 use crate::player::gstreamer::stream_manager::{StreamInfo, StreamManager};
 use crate::player::gstreamer_player::{BufferingState, PlayerState};
 use gstreamer as gst;
@@ -18,6 +19,7 @@ pub fn handle_bus_message_sync(
     playbin: &Arc<Mutex<Option<gst::Element>>>,
     buffering_state: &Arc<RwLock<BufferingState>>,
     paused_for_buffering: &Arc<Mutex<bool>>,
+    chapters: &Arc<Mutex<Vec<Chapter>>>,
 ) {
     use gst::MessageView;
 
@@ -239,6 +241,13 @@ pub fn handle_bus_message_sync(
                 trace!("Found language tag: {}", lang.get());
             }
         }
+        MessageView::Toc(toc_msg) => {
+            let (toc, _updated) = toc_msg.toc();
+            let mut found = Vec::new();
+            collect_chapters(&toc.entries(), &mut found);
+            debug!("Table of contents with {} chapters", found.len());
+            *chapters.lock().unwrap() = found;
+        }
         MessageView::StreamStart(_stream_start_msg) => {
             debug!("Stream started - collection should follow soon");
 
@@ -276,7 +285,6 @@ pub fn handle_bus_message_sync(
                     | gst::MessageType::SegmentDone
                     | gst::MessageType::DurationChanged
                     | gst::MessageType::Latency
-                    | gst::MessageType::Toc
                     | gst::MessageType::StreamStatus
             ) {
                 trace!("Unhandled message: {:?}", msg_type);
@@ -284,3 +292,23 @@ pub fn handle_bus_message_sync(
         }
     }
 }
+
+/// Chapters of `entries` in order, looking into editions for the chapters
+/// they hold
+fn collect_chapters(entries: &[gst::TocEntry], chapters: &mut Vec<Chapter>) {
+    for entry in entries {
+        if entry.entry_type() == gst::TocEntryType::Chapter
+            && let Some((start, _stop)) = entry.start_stop_times()
+        {
+            let title = entry
+                .tags()
+                .and_then(|tags| tags.get::<gst::tags::Title>().map(|t| t.get().to_string()))
+                .unwrap_or_default();
+            chapters.push(Chapter {
+                title,
+                start: std::time::Duration::from_nanos(start.max(0) as u64),
+            });
+        }
+        collect_chapters(&entry.sub_entries(), chapters);
+    }
+}
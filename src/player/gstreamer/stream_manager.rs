@@ -4,6 +4,8 @@ use gstreamer::prelude::*;
 use std::sync::{Arc, Mutex};
 use tracing::{debug, error, info, trace, warn};
 
+use crate::player::types::{SubtitleTrackInfo, TrackLanguages};
+
 /// Information about a stream (audio, subtitle, etc.)
 #[derive(Debug, Clone)]
 pub struct StreamInfo {
@@ -553,6 +555,36 @@ impl StreamManager {
         -1
     }
 
+    /// Language of the selected audio stream and of each subtitle stream.
+    /// Stream collections carry no forced flag.
+    pub fn get_track_languages(&self) -> TrackLanguages {
+        let audio = self
+            .current_audio_stream
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|current_id| {
+                self.audio_streams
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|stream| stream.stream_id == *current_id)
+                    .and_then(|stream| stream.language.clone())
+            });
+        let subtitles = self
+            .subtitle_streams
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|stream| SubtitleTrackInfo {
+                id: stream.index,
+                language: stream.language.clone(),
+                forced: false,
+            })
+            .collect();
+        TrackLanguages { audio, subtitles }
+    }
+
     /// Get current subtitle track index
     pub fn get_current_subtitle_track(&self) -> i32 {
         if let Some(ref current_id) = *self.current_subtitle_stream.lock().unwrap() {
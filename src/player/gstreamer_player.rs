@@ -1,7 +1,8 @@
-use crate::player::ZoomMode;
+use crate::models::PlaybackError;
 use crate::player::gstreamer::bus_handler;
 use crate::player::gstreamer::sink_factory;
 use crate::player::gstreamer::stream_manager::StreamManager;
+use crate::player::{Chapter, TrackLanguages, ZoomMode};
 use anyhow::{Context, Result};
 use gdk4 as gdk;
 use gstreamer as gst;
@@ -14,6 +15,21 @@ use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, trace, warn};
 
+/// Subtitle font size in points at a scale of 1.0
+const SUBTITLE_FONT_SIZE: f64 = 18.0;
+
+/// Pango font description for subtitles drawn at the given scale
+fn subtitle_font_desc(scale: f64) -> String {
+    format!("Sans, {}", (SUBTITLE_FONT_SIZE * scale).round() as u32)
+}
+
+/// Decode text subtitles with `charset`. Without an encoding playbin
+/// detects it, trying UTF-8 and then the locale's encoding.
+fn set_subtitle_encoding(playbin: &gst::Element, charset: &str) {
+    let encoding = (charset != "auto").then_some(charset);
+    playbin.set_property("subtitle-encoding", encoding);
+}
+
 #[derive(Debug, Clone)]
 pub enum PlayerState {
     Idle,
@@ -41,7 +57,12 @@ pub struct GStreamerPlayer {
     buffering_state: Arc<RwLock<BufferingState>>,
     bus_watch_guard: Arc<Mutex<Option<BusWatchGuard>>>,
     current_playback_speed: Arc<Mutex<f64>>,
+    pitch_correction: Arc<Mutex<bool>>,
+    subtitle_scale: Arc<Mutex<f64>>,
+    subtitle_charset: Arc<Mutex<String>>,
     paused_for_buffering: Arc<Mutex<bool>>,
+    /// Chapters from the table of contents the demuxer posts
+    chapters: Arc<Mutex<Vec<Chapter>>>,
 }
 
 impl GStreamerPlayer {
@@ -80,7 +101,11 @@ impl GStreamerPlayer {
             })),
             bus_watch_guard: Arc::new(Mutex::new(None)),
             current_playback_speed: Arc::new(Mutex::new(1.0)),
+            pitch_correction: Arc::new(Mutex::new(true)),
+            subtitle_scale: Arc::new(Mutex::new(1.0)),
+            subtitle_charset: Arc::new(Mutex::new("auto".to_string())),
             paused_for_buffering: Arc::new(Mutex::new(false)),
+            chapters: Arc::new(Mutex::new(Vec::new())),
         })
     }
 
@@ -275,8 +300,9 @@ impl GStreamerPlayer {
                 .context("Failed to set old playbin to null state")?;
         }
 
-        // Clear stream collections from previous media
+        // Clear stream collections and chapters from previous media
         self.stream_manager.clear();
+        self.chapters.lock().unwrap().clear();
 
         // Use playbin3 for modern stream handling and better performance
         //
@@ -333,13 +359,23 @@ impl GStreamerPlayer {
 
         // Configure subtitle properties if available
         if playbin.has_property("subtitle-encoding") {
-            playbin.set_property_from_str("subtitle-encoding", "UTF-8");
-            info!("Set subtitle encoding to UTF-8");
+            let charset = self.subtitle_charset.lock().unwrap().clone();
+            set_subtitle_encoding(&playbin, &charset);
+            info!("Set subtitle encoding to {}", charset);
         }
 
         if playbin.has_property("subtitle-font-desc") {
-            playbin.set_property_from_str("subtitle-font-desc", "Sans, 18");
-            info!("Set subtitle font to Sans, 18");
+            let font_desc = subtitle_font_desc(*self.subtitle_scale.lock().unwrap());
+            playbin.set_property_from_str("subtitle-font-desc", &font_desc);
+            info!("Set subtitle font to {}", font_desc);
+        }
+
+        // Keep the pitch of sped-up or slowed-down audio
+        if *self.pitch_correction.lock().unwrap() {
+            match gst::ElementFactory::make("scaletempo").build() {
+                Ok(scaletempo) => playbin.set_property("audio-filter", &scaletempo),
+                Err(e) => warn!("scaletempo unavailable, speed changes shift pitch: {}", e),
+            }
         }
 
         // Use our stored video sink if available
@@ -416,6 +452,7 @@ impl GStreamerPlayer {
         let playbin_clone = self.playbin.clone();
         let buffering_state_clone = self.buffering_state.clone();
         let paused_for_buffering_clone = self.paused_for_buffering.clone();
+        let chapters_clone = self.chapters.clone();
 
         let watch_guard = bus
             .add_watch(move |_, msg| {
@@ -454,6 +491,7 @@ impl GStreamerPlayer {
                     &playbin_clone,
                     &buffering_state_clone,
                     &paused_for_buffering_clone,
+                    &chapters_clone,
                 );
 
                 glib::ControlFlow::Continue
@@ -515,6 +553,7 @@ impl GStreamerPlayer {
 
                 // Check bus for specific error details
                 let mut error_details = Vec::new();
+                let mut missing_decoder = false;
                 if let Some(bus) = playbin.bus() {
                     while let Some(msg) = bus.pop() {
                         use gst::MessageView;
@@ -526,10 +565,18 @@ impl GStreamerPlayer {
                             );
                             error!("Bus error: {} ({:?})", err.error(), err.debug());
                             error_details.push(error_msg);
+                            missing_decoder |= err.error().matches(gst::StreamError::CodecNotFound)
+                                || err.error().matches(gst::StreamError::TypeNotFound)
+                                || err.error().matches(gst::CoreError::MissingPlugin);
                         }
                     }
                 }
 
+                if missing_decoder {
+                    return Err(PlaybackError::UnsupportedCodec { codecs: None })
+                        .context(error_details.join("; "));
+                }
+
                 let error_msg = if !error_details.is_empty() {
                     format!("Failed to play media: {}", error_details.join("; "))
                 } else {
@@ -794,6 +841,38 @@ impl GStreamerPlayer {
         self.stream_manager.get_current_subtitle_track()
     }
 
+    pub async fn get_track_languages(&self) -> TrackLanguages {
+        self.stream_manager.get_track_languages()
+    }
+
+    pub async fn get_chapters(&self) -> Vec<Chapter> {
+        self.chapters.lock().unwrap().clone()
+    }
+
+    /// Subtitle streams already decoding keep their encoding, so a change
+    /// shows once a stream is selected again or on the next load
+    pub async fn set_subtitle_charset(&self, charset: &str) -> Result<()> {
+        *self.subtitle_charset.lock().unwrap() = charset.to_string();
+
+        if let Some(playbin) = self.playbin.lock().unwrap().as_ref()
+            && playbin.has_property("subtitle-encoding")
+        {
+            set_subtitle_encoding(playbin, charset);
+        }
+        Ok(())
+    }
+
+    pub async fn set_subtitle_scale(&self, scale: f64) -> Result<()> {
+        *self.subtitle_scale.lock().unwrap() = scale;
+
+        if let Some(playbin) = self.playbin.lock().unwrap().as_ref()
+            && playbin.has_property("subtitle-font-desc")
+        {
+            playbin.set_property_from_str("subtitle-font-desc", &subtitle_font_desc(scale));
+        }
+        Ok(())
+    }
+
     pub async fn set_playback_speed(&self, speed: f64) -> Result<()> {
         if let Some(playbin) = self.playbin.lock().unwrap().as_ref() {
             // GStreamer uses a seek with rate to change playback speed
@@ -818,6 +897,13 @@ impl GStreamerPlayer {
         Ok(())
     }
 
+    /// The audio filter is linked when a pipeline is built, so a change
+    /// applies from the next load
+    pub async fn set_pitch_correction(&self, enabled: bool) -> Result<()> {
+        *self.pitch_correction.lock().unwrap() = enabled;
+        Ok(())
+    }
+
     pub async fn get_playback_speed(&self) -> f64 {
         // Return the currently stored playback speed
         *self.current_playback_speed.lock().unwrap()
@@ -12,7 +12,7 @@ pub use controller::{PlayerController, PlayerHandle};
 pub use factory::Player;
 #[allow(unused_imports)]
 pub use factory::PlayerState;
-pub use types::{UpscalingMode, ZoomMode};
+pub use types::{Chapter, SubtitleTrackInfo, TrackLanguages, UpscalingMode, ZoomMode};
 
 #[cfg(feature = "gstreamer")]
 pub use gstreamer_player::{BufferingState, GStreamerPlayer};
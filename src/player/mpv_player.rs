@@ -1,5 +1,6 @@
 use crate::config::Config;
-use anyhow::Result;
+use crate::models::PlaybackError;
+use anyhow::{Context, Result};
 use gtk4::GLArea;
 use gtk4::{self, glib, prelude::*};
 use libmpv2::Mpv;
@@ -46,7 +47,10 @@ pub enum PlayerState {
     Error,
 }
 
-use super::types::{UpscalingMode, ZoomMode};
+use super::types::{
+    Chapter, SubtitleTrackInfo, TrackLanguages, UpscalingMode, ZoomMode, subtitle_track_label,
+};
+use crate::services::core::power_saver;
 
 #[cfg(test)]
 mod tests {
@@ -141,17 +145,19 @@ struct MpvPlayerInner {
     cached_fbo: Arc<Mutex<i32>>,
     timer_handle: Arc<Mutex<Option<glib::SourceId>>>,
     verbose_logging: bool,
-    // Cache configuration fields - stored for potential future use
-    #[allow(dead_code)]
     cache_size_mb: u32,
-    #[allow(dead_code)]
     cache_backbuffer_mb: u32,
+    // Stored for potential future use
     #[allow(dead_code)]
     cache_secs: u32,
     seek_pending: Arc<Mutex<Option<(f64, Instant)>>>,
     seek_timer: Arc<Mutex<Option<glib::SourceId>>>,
     last_seek_target: Arc<Mutex<Option<f64>>>,
     upscaling_mode: Arc<Mutex<UpscalingMode>>,
+    // Lighter settings in place of the upscaling mode and cache size
+    power_saving: Arc<AtomicBool>,
+    subtitle_scale: Arc<Mutex<f64>>,
+    subtitle_charset: Arc<Mutex<String>>,
     zoom_mode: Arc<Mutex<ZoomMode>>,
     error_callback: Arc<Mutex<Option<Box<dyn Fn(String) + Send + 'static>>>>,
     event_monitor_handle: Arc<Mutex<Option<glib::SourceId>>>,
@@ -277,6 +283,9 @@ impl MpvPlayer {
                 seek_timer: Arc::new(Mutex::new(None)),
                 last_seek_target: Arc::new(Mutex::new(None)),
                 upscaling_mode: Arc::new(Mutex::new(UpscalingMode::None)),
+                power_saving: Arc::new(AtomicBool::new(false)),
+                subtitle_scale: Arc::new(Mutex::new(1.0)),
+                subtitle_charset: Arc::new(Mutex::new("auto".to_string())),
                 zoom_mode: Arc::new(Mutex::new(ZoomMode::default())),
                 error_callback: Arc::new(Mutex::new(None)),
                 event_monitor_handle: Arc::new(Mutex::new(None)),
@@ -607,11 +616,13 @@ impl MpvPlayer {
             if inner_realize.mpv.lock().unwrap().is_none() {
                 match MpvPlayerInner::init_mpv(&inner_realize) {
                     Ok(mpv) => {
-                        // Apply initial upscaling mode
-                        let initial_mode = *inner_realize.upscaling_mode.lock().unwrap();
-                        player_self
-                            .apply_upscaling_settings(&mpv, initial_mode)
-                            .unwrap_or(());
+                        // Apply initial upscaling mode and cache size
+                        player_self.apply_quality_settings(&mpv).unwrap_or(());
+                        let subtitle_scale = *inner_realize.subtitle_scale.lock().unwrap();
+                        let _ = mpv.set_property("sub-scale", subtitle_scale);
+                        let subtitle_charset =
+                            inner_realize.subtitle_charset.lock().unwrap().clone();
+                        let _ = mpv.set_property("sub-codepage", subtitle_charset.as_str());
 
                         *inner_realize.mpv.lock().unwrap() = Some(mpv);
                     }
@@ -904,11 +915,37 @@ impl MpvPlayer {
             warn!("MpvPlayer::play() - MPV not initialized yet, will auto-play when ready");
             let mut state = self.inner.state.write().await;
             *state = PlayerState::Playing;
+            return Ok(());
+        }
+
+        // mpv plays on without the tracks it has no decoder for
+        if let Some(codecs) = self.undecodable_codecs().await {
+            return Err(PlaybackError::UnsupportedCodec { codecs: None })
+                .context(format!("No decoder for {}", codecs));
         }
 
         Ok(())
     }
 
+    /// Codecs of the kinds of tracks mpv found no decoder for, waiting
+    /// for it to finish setting up the decoders of a file just loaded
+    async fn undecodable_codecs(&self) -> Option<String> {
+        let start = Instant::now();
+        loop {
+            let missing = match *self.inner.mpv.lock().unwrap() {
+                Some(ref mpv) => missing_decoders(mpv),
+                None => return None,
+            };
+            if missing.is_empty() {
+                return None;
+            }
+            if start.elapsed() > DECODER_SETUP_TIMEOUT {
+                return Some(missing.join(", "));
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
     pub async fn pause(&self) -> Result<()> {
         debug!("MpvPlayer::pause() - Pausing playback");
 
@@ -1179,6 +1216,8 @@ impl MpvPlayer {
                     } else if let Ok(lang) = mpv.get_property::<String>(&lang_key) {
                         title = format!("Subtitle {} ({})", id, lang);
                     }
+                    let (forced, hearing_impaired) = Self::subtitle_flags(mpv, i);
+                    let title = subtitle_track_label(&title, forced, hearing_impaired);
 
                     debug!("mpv: subtitle id={} title={}", id, title);
                     tracks.push((id as i32, title));
@@ -1189,6 +1228,81 @@ impl MpvPlayer {
         tracks
     }
 
+    /// Forced and hearing impaired flags of the track at `index` in the track list
+    fn subtitle_flags(mpv: &Mpv, index: i64) -> (bool, bool) {
+        let flag = |name: &str| {
+            mpv.get_property::<bool>(&format!("track-list/{}/{}", index, name))
+                .unwrap_or(false)
+        };
+        (flag("forced"), flag("hearing-impaired"))
+    }
+
+    pub async fn get_track_languages(&self) -> TrackLanguages {
+        let mut languages = TrackLanguages::default();
+
+        if let Some(ref mpv) = *self.inner.mpv.lock().unwrap()
+            && let Ok(count) = mpv.get_property::<i64>("track-list/count")
+        {
+            for i in 0..count {
+                let Ok(track_type) = mpv.get_property::<String>(&format!("track-list/{}/type", i))
+                else {
+                    continue;
+                };
+                let language = mpv
+                    .get_property::<String>(&format!("track-list/{}/lang", i))
+                    .ok();
+                match track_type.as_str() {
+                    "audio" => {
+                        if mpv
+                            .get_property::<bool>(&format!("track-list/{}/selected", i))
+                            .unwrap_or(false)
+                        {
+                            languages.audio = language;
+                        }
+                    }
+                    "sub" => {
+                        let Ok(id) = mpv.get_property::<i64>(&format!("track-list/{}/id", i))
+                        else {
+                            continue;
+                        };
+                        let (forced, _) = Self::subtitle_flags(mpv, i);
+                        languages.subtitles.push(SubtitleTrackInfo {
+                            id: id as i32,
+                            language,
+                            forced,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        languages
+    }
+
+    pub async fn get_chapters(&self) -> Vec<Chapter> {
+        let mut chapters = Vec::new();
+
+        if let Some(ref mpv) = *self.inner.mpv.lock().unwrap()
+            && let Ok(count) = mpv.get_property::<i64>("chapter-list/count")
+        {
+            for i in 0..count {
+                let Ok(time) = mpv.get_property::<f64>(&format!("chapter-list/{}/time", i)) else {
+                    continue;
+                };
+                let title = mpv
+                    .get_property::<String>(&format!("chapter-list/{}/title", i))
+                    .unwrap_or_default();
+                chapters.push(Chapter {
+                    title,
+                    start: Duration::from_secs_f64(time.max(0.0)),
+                });
+            }
+        }
+
+        chapters
+    }
+
     pub async fn set_audio_track(&self, track_index: i32) -> Result<()> {
         if let Some(ref mpv) = *self.inner.mpv.lock().unwrap() {
             mpv.set_property("aid", track_index as i64)
@@ -1239,11 +1353,48 @@ impl MpvPlayer {
         drop(current_mode);
 
         if let Some(ref mpv) = *self.inner.mpv.lock().unwrap() {
-            self.apply_upscaling_settings(mpv, mode)?;
+            self.apply_quality_settings(mpv)?;
         }
         Ok(())
     }
 
+    /// Trade the upscaling mode and a large cache for lower power use
+    pub async fn set_power_saving(&self, enabled: bool) -> Result<()> {
+        self.inner.power_saving.store(enabled, Ordering::Relaxed);
+
+        if let Some(ref mpv) = *self.inner.mpv.lock().unwrap() {
+            self.apply_quality_settings(mpv)?;
+        }
+        Ok(())
+    }
+
+    /// Apply the upscaling mode and cache size, or the power saving settings
+    /// in their place
+    fn apply_quality_settings(&self, mpv: &Mpv) -> Result<()> {
+        let (mode, cache_mb, backbuffer_mb) = if self.inner.power_saving.load(Ordering::Relaxed) {
+            (
+                UpscalingMode::None,
+                power_saver::POWER_SAVING_CACHE_MB,
+                power_saver::POWER_SAVING_BACKBUFFER_MB,
+            )
+        } else {
+            (
+                *self.inner.upscaling_mode.lock().unwrap(),
+                self.inner.cache_size_mb,
+                self.inner.cache_backbuffer_mb,
+            )
+        };
+
+        self.apply_upscaling_settings(mpv, mode)?;
+        let _ = mpv.set_property("demuxer-max-bytes", format!("{}MiB", cache_mb));
+        let _ = mpv.set_property("demuxer-max-back-bytes", format!("{}MiB", backbuffer_mb));
+        debug!(
+            "Demuxer cache set to {}MB ahead, {}MB behind",
+            cache_mb, backbuffer_mb
+        );
+        Ok(())
+    }
+
     fn apply_upscaling_settings(&self, mpv: &Mpv, mode: UpscalingMode) -> Result<()> {
         // Clear any existing shaders first
         let _ = mpv.set_property("glsl-shaders", "");
@@ -1303,6 +1454,32 @@ impl MpvPlayer {
         Ok(())
     }
 
+    pub async fn set_subtitle_scale(&self, scale: f64) -> Result<()> {
+        *self.inner.subtitle_scale.lock().unwrap() = scale;
+
+        if let Some(ref mpv) = *self.inner.mpv.lock().unwrap() {
+            mpv.set_property("sub-scale", scale)
+                .map_err(|e| anyhow::anyhow!("Failed to set subtitle scale: {:?}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Decode text subtitles with `charset`, "auto" to detect it, and reload
+    /// the shown subtitle track so the change is visible at once
+    pub async fn set_subtitle_charset(&self, charset: &str) -> Result<()> {
+        *self.inner.subtitle_charset.lock().unwrap() = charset.to_string();
+
+        if let Some(ref mpv) = *self.inner.mpv.lock().unwrap() {
+            mpv.set_property("sub-codepage", charset)
+                .map_err(|e| anyhow::anyhow!("Failed to set subtitle encoding: {:?}", e))?;
+            if mpv.get_property::<i64>("sid").is_ok() {
+                mpv.command("sub-reload", &[])
+                    .map_err(|e| anyhow::anyhow!("Failed to reload subtitles: {:?}", e))?;
+            }
+        }
+        Ok(())
+    }
+
     pub async fn set_playback_speed(&self, speed: f64) -> Result<()> {
         let inner = self.inner.clone();
         if let Some(ref mpv) = *inner.mpv.lock().unwrap() {
@@ -1312,6 +1489,15 @@ impl MpvPlayer {
         Ok(())
     }
 
+    pub async fn set_pitch_correction(&self, enabled: bool) -> Result<()> {
+        let inner = self.inner.clone();
+        if let Some(ref mpv) = *inner.mpv.lock().unwrap() {
+            mpv.set_property("audio-pitch-correction", enabled)
+                .map_err(|e| anyhow::anyhow!("Failed to set pitch correction: {:?}", e))?;
+        }
+        Ok(())
+    }
+
     pub async fn get_playback_speed(&self) -> f64 {
         let inner = self.inner.clone();
         if let Some(ref mpv) = *inner.mpv.lock().unwrap() {
@@ -1321,6 +1507,16 @@ impl MpvPlayer {
         }
     }
 
+    /// Load a subtitle file as an extra track and show it
+    pub async fn add_subtitle_file(&self, path: &str) -> Result<()> {
+        let inner = self.inner.clone();
+        if let Some(ref mpv) = *inner.mpv.lock().unwrap() {
+            mpv.command("sub-add", &[path, "select"])
+                .map_err(|e| anyhow::anyhow!("Failed to load subtitle file: {:?}", e))?;
+        }
+        Ok(())
+    }
+
     pub async fn frame_step_forward(&self) -> Result<()> {
         let inner = self.inner.clone();
         if let Some(ref mpv) = *inner.mpv.lock().unwrap() {
@@ -1480,6 +1676,42 @@ impl MpvPlayer {
     }
 }
 
+/// How long mpv may take to set up the decoders once a file is loaded
+const DECODER_SETUP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Codecs of the video and audio tracks when none of the tracks of their
+/// kind got a decoder. Cover art doesn't count as video.
+fn missing_decoders(mpv: &Mpv) -> Vec<String> {
+    let count = mpv.get_property::<i64>("track-list/count").unwrap_or(0);
+    let mut missing = Vec::new();
+    for kind in ["video", "audio"] {
+        let mut found = false;
+        let mut decoded = false;
+        let mut codecs = Vec::new();
+        for index in 0..count {
+            let property = |name: &str| format!("track-list/{}/{}", index, name);
+            let track_kind = mpv
+                .get_property::<String>(&property("type"))
+                .unwrap_or_default();
+            let cover_art = mpv
+                .get_property::<bool>(&property("albumart"))
+                .unwrap_or(false);
+            if track_kind != kind || cover_art {
+                continue;
+            }
+            found = true;
+            decoded |= mpv
+                .get_property::<String>(&property("decoder-desc"))
+                .is_ok();
+            codecs.extend(mpv.get_property::<String>(&property("codec")).ok());
+        }
+        if found && !decoded {
+            missing.extend(codecs);
+        }
+    }
+    missing
+}
+
 impl MpvPlayerInner {
     fn init_mpv(&self) -> Result<Mpv> {
         info!("Creating MPV instance");
@@ -1572,7 +1804,7 @@ impl MpvPlayerInner {
         mpv.set_property("audio-file-auto", "fuzzy")
             .map_err(|e| anyhow::anyhow!("Failed to set audio-file-auto: {:?}", e))?;
 
-        // Use MPV's default cache settings for optimal streaming performance
+        // Cache size is applied with the upscaling mode once the render context exists
 
         // Disable OSD
         mpv.set_property("osd-level", 0i64)
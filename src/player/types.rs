@@ -9,6 +9,43 @@ pub enum UpscalingMode {
     Custom,
 }
 
+/// Language of the playing audio and of the subtitle tracks, used to pick
+/// forced subtitles
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrackLanguages {
+    pub audio: Option<String>,
+    pub subtitles: Vec<SubtitleTrackInfo>,
+}
+
+/// A chapter of the loaded media, as named in the file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    pub start: std::time::Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleTrackInfo {
+    pub id: i32,
+    pub language: Option<String>,
+    /// Only translates foreign dialogue and signs
+    pub forced: bool,
+}
+
+/// Subtitle track name with its forced and SDH flags, unless the name
+/// already mentions them
+pub fn subtitle_track_label(name: &str, forced: bool, hearing_impaired: bool) -> String {
+    let lowercase = name.to_lowercase();
+    let mut label = name.to_string();
+    if forced && !lowercase.contains("forced") {
+        label.push_str(" [Forced]");
+    }
+    if hearing_impaired && !lowercase.contains("sdh") {
+        label.push_str(" [SDH]");
+    }
+    label
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ZoomMode {
     Fit,         // Fit entire video in window (default, may show black bars)
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use std::path::PathBuf;
 
 use crate::backends::traits::MediaBackend;
 use crate::db::connection::DatabaseConnection;
@@ -38,6 +39,19 @@ impl<'a> Command<Source> for CreateSourceCommand<'a> {
     }
 }
 
+/// Create a source for a folder of video files on this machine
+pub struct CreateLocalSourceCommand {
+    pub db: DatabaseConnection,
+    pub path: PathBuf,
+}
+
+#[async_trait]
+impl Command<Source> for CreateLocalSourceCommand {
+    async fn execute(&self) -> Result<Source> {
+        AuthService::create_local_source(&self.db, self.path.clone()).await
+    }
+}
+
 /// Remove a source and its credentials
 pub struct RemoveSourceCommand {
     pub db: DatabaseConnection,
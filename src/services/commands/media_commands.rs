@@ -1,9 +1,15 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 
 use crate::db::connection::DatabaseConnection;
-use crate::models::{Episode, MediaItem, MediaItemId, ShowId};
-use crate::services::commands::Command;
+use crate::db::entities::OfflineContentModel;
+use crate::models::{Episode, MediaItem, MediaItemId, ShowId, SourceId};
+use crate::services::commands::{Command, Undo, UndoableCommand};
+use crate::services::core::deletion::DeletionService;
+use crate::services::core::downloads::DownloadService;
+use crate::services::core::favorites::FavoritesService;
+use crate::services::core::hidden::HiddenItemsService;
+use crate::services::core::labels::LabelsService;
 use crate::services::core::media::MediaService;
 
 /// Get detailed information about a media item
@@ -19,11 +25,10 @@ impl Command<MediaItem> for GetItemDetailsCommand {
     }
 }
 
-/// Get playback progress for a media item
+/// Get playback progress of the user signed in to the item's source
 pub struct GetPlaybackProgressCommand {
     pub db: DatabaseConnection,
     pub media_id: MediaItemId,
-    pub user_id: String,
 }
 
 #[async_trait]
@@ -31,7 +36,7 @@ impl Command<Option<crate::db::entities::PlaybackProgressModel>> for GetPlayback
     async fn execute(&self) -> Result<Option<crate::db::entities::PlaybackProgressModel>> {
         use crate::services::core::playback::PlaybackService;
 
-        PlaybackService::get_progress(&self.db, &self.user_id, &self.media_id).await
+        PlaybackService::get_progress(&self.db, &self.media_id).await
     }
 }
 
@@ -149,6 +154,64 @@ impl Command<()> for MarkWatchedCommand {
     }
 }
 
+#[async_trait]
+impl UndoableCommand for MarkWatchedCommand {
+    async fn execute_undoable(&self) -> Result<Undo> {
+        use crate::services::core::playback::PlaybackService;
+
+        // Marking an item watched drops its resume position, undoing puts it back
+        let previous = PlaybackService::get_progress(&self.db, &self.media_id).await?;
+        self.execute().await?;
+
+        let db = self.db.clone();
+        let media_id = self.media_id.clone();
+        Ok(match previous {
+            // Already watched before, there is nothing to take back
+            Some(progress) if progress.watched => Box::new(MarkWatchedCommand { db, media_id }),
+            previous => Box::new(UndoMarkWatchedCommand {
+                db,
+                media_id,
+                position_ms: previous.as_ref().map_or(0, |progress| progress.position_ms),
+                duration_ms: previous.as_ref().map_or(0, |progress| progress.duration_ms),
+            }),
+        })
+    }
+}
+
+/// Take back marking an item watched: mark it unwatched, on its server too
+/// if the change was sent already, then put back its resume position
+pub struct UndoMarkWatchedCommand {
+    pub db: DatabaseConnection,
+    pub media_id: MediaItemId,
+    pub position_ms: i64,
+    pub duration_ms: i64,
+}
+
+#[async_trait]
+impl Command<()> for UndoMarkWatchedCommand {
+    async fn execute(&self) -> Result<()> {
+        MarkUnwatchedCommand {
+            db: self.db.clone(),
+            media_id: self.media_id.clone(),
+        }
+        .execute()
+        .await?;
+
+        if self.position_ms > 0 {
+            UpdatePlaybackProgressCommand {
+                db: self.db.clone(),
+                media_id: self.media_id.clone(),
+                position_ms: self.position_ms,
+                duration_ms: self.duration_ms,
+                watched: false,
+            }
+            .execute()
+            .await?;
+        }
+        Ok(())
+    }
+}
+
 /// Mark a media item as unwatched
 pub struct MarkUnwatchedCommand {
     pub db: DatabaseConnection,
@@ -267,6 +330,324 @@ impl Command<()> for MarkSeasonUnwatchedCommand {
     }
 }
 
+/// Mark the episodes of a show before the given one as watched
+pub struct MarkPreviousEpisodesWatchedCommand {
+    pub db: DatabaseConnection,
+    pub show_id: ShowId,
+    pub season_number: u32,
+    pub episode_number: u32,
+}
+
+impl MarkPreviousEpisodesWatchedCommand {
+    async fn mark(&self) -> Result<Vec<MediaItemId>> {
+        let marked = MediaService::mark_previous_episodes_watched(
+            &self.db,
+            &self.show_id,
+            self.season_number,
+            self.episode_number,
+        )
+        .await?;
+
+        // Broadcast general update since multiple items affected
+        use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+        BROKER
+            .broadcast(BrokerMessage::Data(DataMessage::MediaUpdated {
+                media_id: self.show_id.to_string(),
+            }))
+            .await;
+
+        Ok(marked)
+    }
+}
+
+#[async_trait]
+impl Command<()> for MarkPreviousEpisodesWatchedCommand {
+    async fn execute(&self) -> Result<()> {
+        self.mark().await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl UndoableCommand for MarkPreviousEpisodesWatchedCommand {
+    async fn execute_undoable(&self) -> Result<Undo> {
+        // Only the episodes this marked go back to unwatched
+        let media_ids = self.mark().await?;
+        Ok(Box::new(MarkEpisodesUnwatchedCommand {
+            db: self.db.clone(),
+            show_id: self.show_id.clone(),
+            media_ids,
+        }))
+    }
+}
+
+/// Mark some episodes of a show as unwatched
+pub struct MarkEpisodesUnwatchedCommand {
+    pub db: DatabaseConnection,
+    pub show_id: ShowId,
+    pub media_ids: Vec<MediaItemId>,
+}
+
+#[async_trait]
+impl Command<()> for MarkEpisodesUnwatchedCommand {
+    async fn execute(&self) -> Result<()> {
+        for media_id in &self.media_ids {
+            MediaService::mark_unwatched(&self.db, media_id).await?;
+        }
+
+        // Broadcast general update since multiple items affected
+        use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+        BROKER
+            .broadcast(BrokerMessage::Data(DataMessage::MediaUpdated {
+                media_id: self.show_id.to_string(),
+            }))
+            .await;
+
+        Ok(())
+    }
+}
+
+/// Mark or unmark a media item as favorite
+pub struct SetFavoriteCommand {
+    pub db: DatabaseConnection,
+    pub media_id: MediaItemId,
+    pub favorite: bool,
+}
+
+#[async_trait]
+impl Command<()> for SetFavoriteCommand {
+    async fn execute(&self) -> Result<()> {
+        FavoritesService::set_favorite(&self.db, &self.media_id, self.favorite).await?;
+
+        // Broadcast favorite state to all interested components
+        use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+        BROKER
+            .broadcast(BrokerMessage::Data(DataMessage::FavoriteChanged {
+                media_id: self.media_id.to_string(),
+                favorite: self.favorite,
+            }))
+            .await;
+
+        Ok(())
+    }
+}
+
+/// Delete a media item, or one version of a movie, from its server
+pub struct DeleteFromServerCommand {
+    pub db: DatabaseConnection,
+    pub media_id: MediaItemId,
+    pub version_id: Option<String>,
+}
+
+#[async_trait]
+impl Command<()> for DeleteFromServerCommand {
+    async fn execute(&self) -> Result<()> {
+        let item_deleted = DeletionService::delete_from_server(
+            &self.db,
+            &self.media_id,
+            self.version_id.as_deref(),
+        )
+        .await?;
+
+        use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+        let media_id = self.media_id.to_string();
+        let message = if item_deleted {
+            DataMessage::MediaDeleted { media_id }
+        } else {
+            DataMessage::MediaUpdated { media_id }
+        };
+        BROKER.broadcast(BrokerMessage::Data(message)).await;
+
+        Ok(())
+    }
+}
+
+/// Hide a media item from the home page or show it again
+pub struct SetHiddenCommand {
+    pub db: DatabaseConnection,
+    pub media_id: MediaItemId,
+    pub hidden: bool,
+}
+
+#[async_trait]
+impl Command<()> for SetHiddenCommand {
+    async fn execute(&self) -> Result<()> {
+        HiddenItemsService::set_hidden(&self.db, &self.media_id, self.hidden).await?;
+
+        use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+        BROKER
+            .broadcast(BrokerMessage::Data(DataMessage::HiddenItemsChanged {
+                media_id: self.media_id.to_string(),
+                hidden: self.hidden,
+            }))
+            .await;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UndoableCommand for SetHiddenCommand {
+    async fn execute_undoable(&self) -> Result<Undo> {
+        self.execute().await?;
+        Ok(Box::new(SetHiddenCommand {
+            db: self.db.clone(),
+            media_id: self.media_id.clone(),
+            hidden: !self.hidden,
+        }))
+    }
+}
+
+/// Hide a collection from the home page or show it again
+pub struct SetCollectionHiddenCommand {
+    pub db: DatabaseConnection,
+    pub source_id: SourceId,
+    pub collection: String,
+    pub hidden: bool,
+}
+
+#[async_trait]
+impl Command<()> for SetCollectionHiddenCommand {
+    async fn execute(&self) -> Result<()> {
+        HiddenItemsService::set_collection_hidden(
+            &self.db,
+            &self.source_id,
+            &self.collection,
+            self.hidden,
+        )
+        .await?;
+
+        use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+        BROKER
+            .broadcast(BrokerMessage::Data(DataMessage::HiddenCollectionChanged {
+                source_id: self.source_id.to_string(),
+                collection: self.collection.clone(),
+                hidden: self.hidden,
+            }))
+            .await;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UndoableCommand for SetCollectionHiddenCommand {
+    async fn execute_undoable(&self) -> Result<Undo> {
+        self.execute().await?;
+        Ok(Box::new(SetCollectionHiddenCommand {
+            db: self.db.clone(),
+            source_id: self.source_id.clone(),
+            collection: self.collection.clone(),
+            hidden: !self.hidden,
+        }))
+    }
+}
+
+/// Put a label on a movie or show, or take it off
+pub struct SetLabelCommand {
+    pub db: DatabaseConnection,
+    pub media_id: MediaItemId,
+    pub label: String,
+    pub present: bool,
+}
+
+#[async_trait]
+impl Command<()> for SetLabelCommand {
+    async fn execute(&self) -> Result<()> {
+        LabelsService::set_label(&self.db, &self.media_id, &self.label, self.present).await?;
+
+        use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+        BROKER
+            .broadcast(BrokerMessage::Data(DataMessage::MediaUpdated {
+                media_id: self.media_id.to_string(),
+            }))
+            .await;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UndoableCommand for SetLabelCommand {
+    async fn execute_undoable(&self) -> Result<Undo> {
+        self.execute().await?;
+        Ok(Box::new(SetLabelCommand {
+            db: self.db.clone(),
+            media_id: self.media_id.clone(),
+            label: self.label.clone(),
+            present: !self.present,
+        }))
+    }
+}
+
+/// Delete the download of a media item together with its file
+pub struct RemoveDownloadCommand {
+    pub db: DatabaseConnection,
+    pub media_id: MediaItemId,
+}
+
+#[async_trait]
+impl Command<()> for RemoveDownloadCommand {
+    async fn execute(&self) -> Result<()> {
+        if let Some(download) = DownloadService::find_download(&self.db, &self.media_id).await? {
+            DownloadService::remove_download(&self.db, &download).await?;
+        }
+
+        use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+        BROKER
+            .broadcast(BrokerMessage::Data(DataMessage::MediaUpdated {
+                media_id: self.media_id.to_string(),
+            }))
+            .await;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UndoableCommand for RemoveDownloadCommand {
+    async fn execute_undoable(&self) -> Result<Undo> {
+        // The files are kept aside for a while, undoing puts them back
+        let download = DownloadService::find_download(&self.db, &self.media_id)
+            .await?
+            .ok_or_else(|| anyhow!("{} has no download to delete", self.media_id))?;
+        DownloadService::remove_download_undoable(&self.db, &download).await?;
+
+        use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+        BROKER
+            .broadcast(BrokerMessage::Data(DataMessage::MediaUpdated {
+                media_id: self.media_id.to_string(),
+            }))
+            .await;
+
+        Ok(Box::new(RestoreDownloadCommand {
+            db: self.db.clone(),
+            download,
+        }))
+    }
+}
+
+/// Bring back a download deleted moments ago, with its files
+pub struct RestoreDownloadCommand {
+    pub db: DatabaseConnection,
+    pub download: OfflineContentModel,
+}
+
+#[async_trait]
+impl Command<()> for RestoreDownloadCommand {
+    async fn execute(&self) -> Result<()> {
+        DownloadService::restore_download(&self.db, &self.download).await?;
+
+        use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+        BROKER
+            .broadcast(BrokerMessage::Data(DataMessage::MediaUpdated {
+                media_id: self.download.media_id.clone(),
+            }))
+            .await;
+
+        Ok(())
+    }
+}
+
 // Tests disabled temporarily - need proper database mocking support
 #[cfg(test)]
 #[allow(dead_code, unused_imports)]
@@ -442,16 +823,10 @@ mod tests {
     async fn test_get_playback_progress_command() {
         let db = create_mock_db();
         let media_id = MediaItemId(1);
-        let user_id = "user1".to_string();
 
-        let command = GetPlaybackProgressCommand {
-            db,
-            media_id,
-            user_id: user_id.clone(),
-        };
+        let command = GetPlaybackProgressCommand { db, media_id };
 
         assert_eq!(command.media_id, MediaItemId(1));
-        assert_eq!(command.user_id, "user1");
     }
 
     #[tokio::test]
@@ -518,6 +893,15 @@ mod tests {
             playback_position: None,
             intro_marker: None,
             credits_marker: None,
+            video_height: None,
+            hdr_format: None,
+            atmos: false,
+            versions: vec![],
+            external_ids: vec![],
+            studios: vec![],
+            tags: vec![],
+            collections: vec![],
+            content_rating: None,
         });
 
         let command = SaveMediaItemCommand {
@@ -580,3 +964,155 @@ mod tests {
     }
     */
 }
+
+#[cfg(test)]
+mod undo_tests {
+    use super::*;
+    use crate::db::entities::playback_sync_queue::SyncChangeType;
+    use crate::db::entities::{DownloadStatus, libraries, media_items, sources};
+    use crate::db::repository::{
+        OfflineContentRepository, OfflineContentRepositoryImpl, PlaybackSyncRepository,
+        PlaybackSyncRepositoryImpl, Repository,
+    };
+    use crate::services::core::playback::PlaybackService;
+    use crate::test_utils::TestDatabase;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    /// A database holding one movie, of a source whose changes are synced
+    async fn database_with_movie() -> Result<TestDatabase> {
+        let db = TestDatabase::new().await?;
+        sources::ActiveModel {
+            id: Set("1".to_string()),
+            name: Set("Server".to_string()),
+            source_type: Set("plex".to_string()),
+            is_online: Set(true),
+            ..Default::default()
+        }
+        .insert(db.connection.as_ref())
+        .await?;
+        libraries::ActiveModel {
+            id: Set("lib".to_string()),
+            source_id: Set("1".to_string()),
+            title: Set("Movies".to_string()),
+            library_type: Set("movie".to_string()),
+            ..Default::default()
+        }
+        .insert(db.connection.as_ref())
+        .await?;
+        media_items::ActiveModel {
+            id: Set("movie".to_string()),
+            source_id: Set("1".to_string()),
+            library_id: Set("lib".to_string()),
+            title: Set("Movie".to_string()),
+            media_type: Set("movie".to_string()),
+            ..Default::default()
+        }
+        .insert(db.connection.as_ref())
+        .await?;
+        Ok(db)
+    }
+
+    #[tokio::test]
+    async fn test_undo_hiding_shows_the_item_again() -> Result<()> {
+        let db = database_with_movie().await?;
+        let cmd = SetHiddenCommand {
+            db: db.connection(),
+            media_id: MediaItemId::from("movie"),
+            hidden: true,
+        };
+
+        let undo = cmd.execute_undoable().await?;
+        assert!(
+            HiddenItemsService::hidden_ids(&db.connection)
+                .await?
+                .contains("movie")
+        );
+
+        undo.execute().await?;
+        assert!(
+            HiddenItemsService::hidden_ids(&db.connection)
+                .await?
+                .is_empty()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_undo_marking_watched_unscrobbles_and_restores_the_position() -> Result<()> {
+        let db = database_with_movie().await?;
+        let media_id = MediaItemId::from("movie");
+        MediaService::update_playback_progress(&db.connection, &media_id, 60_000, 7_200_000, false)
+            .await?;
+
+        let cmd = MarkWatchedCommand {
+            db: db.connection(),
+            media_id: media_id.clone(),
+        };
+        let undo = cmd.execute_undoable().await?;
+        undo.execute().await?;
+
+        let progress = PlaybackService::get_progress(&db.connection, &media_id)
+            .await?
+            .expect("progress of the movie");
+        assert!(!progress.watched);
+        assert_eq!(progress.position_ms, 60_000);
+
+        // The server hears of the change even if marking it watched went out
+        let changes = PlaybackSyncRepositoryImpl::new(db.connection())
+            .get_by_media_item("movie", 1)
+            .await?;
+        assert!(
+            changes
+                .iter()
+                .any(|change| change.change_type == SyncChangeType::MarkUnwatched.to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_undo_deleting_a_download_puts_its_file_back() -> Result<()> {
+        let db = database_with_movie().await?;
+        let dir = tempfile::TempDir::new()?;
+        let file_path = dir.path().join("movie_high");
+        tokio::fs::write(&file_path, b"movie").await?;
+        OfflineContentRepositoryImpl::new(db.connection())
+            .insert(OfflineContentModel {
+                id: 0,
+                media_id: "movie".to_string(),
+                file_path: file_path.to_string_lossy().into_owned(),
+                file_size_bytes: Some(5),
+                quality: Some("high".to_string()),
+                downloaded_at: chrono::Utc::now().naive_utc(),
+                last_accessed: None,
+                status: DownloadStatus::Completed.to_string(),
+                downloaded_bytes: 5,
+                error_message: None,
+                auto_downloaded: false,
+                pinned: true,
+                validator: None,
+                checksum: None,
+            })
+            .await?;
+
+        let media_id = MediaItemId::from("movie");
+        let cmd = RemoveDownloadCommand {
+            db: db.connection(),
+            media_id: media_id.clone(),
+        };
+        let undo = cmd.execute_undoable().await?;
+        assert!(!file_path.exists());
+        assert!(
+            DownloadService::find_download(&db.connection, &media_id)
+                .await?
+                .is_none()
+        );
+
+        undo.execute().await?;
+        assert_eq!(tokio::fs::read(&file_path).await?, b"movie");
+        let download = DownloadService::find_download(&db.connection, &media_id)
+            .await?
+            .expect("restored download");
+        assert!(download.pinned);
+        Ok(())
+    }
+}
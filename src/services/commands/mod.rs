@@ -13,3 +13,13 @@ pub trait Command<T>: Send + Sync {
     /// Execute the command and return the result
     async fn execute(&self) -> Result<T>;
 }
+
+/// Command reversing one the user took back
+pub type Undo = Box<dyn Command<()>>;
+
+/// Command the user can take back after it ran
+#[async_trait]
+pub trait UndoableCommand: Send + Sync {
+    /// Execute the command, returning the command that reverses it
+    async fn execute_undoable(&self) -> Result<Undo>;
+}
@@ -6,6 +6,7 @@ use crate::db::connection::DatabaseConnection;
 use crate::models::{Library, SourceId};
 use crate::services::commands::Command;
 use crate::services::core::sync::{SyncResult, SyncService};
+use crate::services::core::sync_control::SyncControl;
 
 /// Sync all libraries for a source
 pub struct SyncSourceCommand<'a> {
@@ -17,7 +18,13 @@ pub struct SyncSourceCommand<'a> {
 #[async_trait]
 impl<'a> Command<SyncResult> for SyncSourceCommand<'a> {
     async fn execute(&self) -> Result<SyncResult> {
-        SyncService::sync_source(&self.db, self.backend, &self.source_id).await
+        SyncService::sync_source(
+            &self.db,
+            self.backend,
+            &self.source_id,
+            &SyncControl::default(),
+        )
+        .await
     }
 }
 
@@ -42,11 +49,11 @@ impl<'a> Command<usize> for SyncLibraryCommand<'a> {
 mod tests {
     use super::*;
     use crate::backends::traits::BackendType;
-    use crate::models::StreamInfo;
     use crate::models::{
         BackendId, Credentials, Episode, HomeSection, Library, LibraryId, LibraryType, MediaItemId,
         Movie, Season, Show, ShowId, User,
     };
+    use crate::models::{StreamInfo, StreamMode};
     use async_trait::async_trait;
     use chrono::Utc;
     use std::time::Duration;
@@ -160,6 +167,15 @@ mod tests {
                     playback_position: None,
                     intro_marker: None,
                     credits_marker: None,
+                    video_height: None,
+                    hdr_format: None,
+                    atmos: false,
+                    versions: vec![],
+                    external_ids: vec![],
+                    studios: vec![],
+                    tags: vec![],
+                    collections: vec![],
+                    content_rating: None,
                 }])
             } else {
                 Ok(vec![])
@@ -185,6 +201,11 @@ mod tests {
                     watched_episode_count: 0,
                     total_episode_count: 10,
                     last_watched_at: None,
+                    external_ids: vec![],
+                    studios: vec![],
+                    tags: vec![],
+                    collections: vec![],
+                    content_rating: None,
                 }])
             } else {
                 Ok(vec![])
@@ -204,6 +225,7 @@ mod tests {
             Ok(StreamInfo {
                 url: format!("http://localhost:32400/stream/{}", media_id.as_str()),
                 direct_play: true,
+                mode: StreamMode::DirectPlay,
                 video_codec: "h264".to_string(),
                 audio_codec: "aac".to_string(),
                 container: "mp4".to_string(),
@@ -1,10 +1,19 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use once_cell::sync::Lazy;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
-use crate::config::{Config, PlaybackConfig};
+use crate::config::{
+    BackupConfig, Config, DownloadsConfig, DurationDisplay, GestureConfig, ImageQuality,
+    KidsConfig, LoggingConfig, LongAudioConfig, NetworkConfig, PlaybackConfig, SpeedConfig,
+};
+use crate::logging;
+use crate::services::core::kids::{KidsProfileService, MIN_PIN_LENGTH};
+use crate::services::core::network_policy::{NetworkPolicy, set_network_policy};
+use crate::services::core::storage::{StorageLocations, set_storage_locations};
+use crate::services::core::url_playback;
+use crate::services::core::{image_quality, power_saver};
 use crate::ui::shared::broker::{BROKER, BrokerMessage, ConfigMessage};
 
 /// Global configuration service instance
@@ -21,6 +30,11 @@ pub struct ConfigService {
 impl ConfigService {
     pub fn new() -> Self {
         let config = Config::load().unwrap_or_default();
+        set_network_policy(NetworkPolicy::from(&config.network));
+        set_storage_locations(StorageLocations::from(&config));
+        power_saver::set_enabled(config.playback.power_saving_playback);
+        image_quality::set_quality(config.ui.image_quality);
+        logging::apply_config(&config.logging);
 
         Self {
             config: Arc::new(RwLock::new(config)),
@@ -51,6 +65,11 @@ impl ConfigService {
             let mut current = self.config.write().await;
             *current = config.clone();
         }
+        set_network_policy(NetworkPolicy::from(&config.network));
+        set_storage_locations(StorageLocations::from(&config));
+        power_saver::set_enabled(config.playback.power_saving_playback);
+        image_quality::set_quality(config.ui.image_quality);
+        logging::apply_config(&config.logging);
 
         // Save and broadcast the change
         config.save()?;
@@ -99,6 +118,96 @@ impl ConfigService {
         Ok(())
     }
 
+    /// Update whether playback saves power in power saver mode or on low battery
+    pub async fn set_power_saving_playback(&self, enabled: bool) -> Result<()> {
+        debug!("Setting power saving playback to: {}", enabled);
+
+        let mut config = self.get_config().await;
+        if config.playback.power_saving_playback != enabled {
+            config.playback.power_saving_playback = enabled;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update whether playback paused by suspend or screen lock resumes on unlock
+    pub async fn set_resume_after_unlock(&self, enabled: bool) -> Result<()> {
+        debug!("Setting resume after unlock to: {}", enabled);
+
+        let mut config = self.get_config().await;
+        if config.playback.resume_after_unlock != enabled {
+            config.playback.resume_after_unlock = enabled;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update whether opened web page URLs are resolved with yt-dlp
+    pub async fn set_resolve_urls_with_ytdlp(&self, enabled: bool) -> Result<()> {
+        debug!("Setting yt-dlp URL resolution to: {}", enabled);
+
+        let mut config = self.get_config().await;
+        if config.playback.resolve_urls_with_ytdlp != enabled {
+            config.playback.resolve_urls_with_ytdlp = enabled;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update the character encoding of text subtitles
+    pub async fn set_subtitle_charset(&self, charset: String) -> Result<()> {
+        debug!("Setting subtitle encoding to: {}", charset);
+
+        let mut config = self.get_config().await;
+        if config.playback.subtitles.charset != charset {
+            config.playback.subtitles.charset = charset;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update whether forced subtitles are shown for audio in the desktop's
+    /// language
+    pub async fn set_auto_forced_subtitles(&self, enabled: bool) -> Result<()> {
+        debug!("Setting automatic forced subtitles to: {}", enabled);
+
+        let mut config = self.get_config().await;
+        if config.playback.subtitles.auto_forced != enabled {
+            config.playback.subtitles.auto_forced = enabled;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update the monitor fullscreen playback uses
+    pub async fn set_fullscreen_monitor(&self, monitor: String) -> Result<()> {
+        debug!("Setting fullscreen monitor to: {}", monitor);
+
+        let mut config = self.get_config().await;
+        if config.playback.fullscreen_monitor != monitor {
+            config.playback.fullscreen_monitor = monitor;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Remember the monitor fullscreen playback used last
+    pub async fn set_last_fullscreen_monitor(&self, connector: String) -> Result<()> {
+        let mut config = self.get_config().await;
+        if config.playback.last_fullscreen_monitor != connector {
+            config.playback.last_fullscreen_monitor = connector;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
     /// Update MPV cache settings
     pub async fn set_mpv_cache_settings(
         &self,
@@ -155,6 +264,176 @@ impl ConfigService {
         Ok(())
     }
 
+    /// Update the subtitle size used during playback
+    pub async fn set_subtitle_scale(&self, scale: f64) -> Result<()> {
+        debug!("Setting subtitle scale to: {}", scale);
+
+        let mut config = self.get_config().await;
+        if config.playback.subtitle_scale != scale {
+            config.playback.subtitle_scale = scale;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Remember what the player's time label after the seek bar shows
+    pub async fn set_duration_display(&self, display: DurationDisplay) -> Result<()> {
+        debug!("Setting duration display to: {:?}", display);
+
+        let mut config = self.get_config().await;
+        if config.playback.duration_display != display {
+            config.playback.duration_display = display;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update which gestures the player's video area responds to
+    pub async fn set_gesture_settings(&self, gestures: GestureConfig) -> Result<()> {
+        debug!("Setting player gestures to: {:?}", gestures);
+
+        let mut config = self.get_config().await;
+        if config.playback.gestures != gestures {
+            config.playback.gestures = gestures;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update pitch correction and the speed each kind of content starts at
+    pub async fn set_speed_settings(&self, speed: SpeedConfig) -> Result<()> {
+        debug!("Setting playback speed preferences to: {:?}", speed);
+
+        let mut config = self.get_config().await;
+        if config.playback.speed != speed {
+            config.playback.speed = speed;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update how audiobooks and podcasts save progress and resume
+    pub async fn set_long_audio_settings(&self, long_audio: LongAudioConfig) -> Result<()> {
+        debug!("Setting long-form audio preferences to: {:?}", long_audio);
+
+        let mut config = self.get_config().await;
+        if config.playback.long_audio != long_audio {
+            config.playback.long_audio = long_audio;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update HTTP timeout and retry settings used by backends
+    pub async fn set_network_settings(&self, network: NetworkConfig) -> Result<()> {
+        debug!("Setting network policy to: {:?}", network);
+
+        let mut config = self.get_config().await;
+        if config.network != network {
+            config.network = network;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update the log level and file logging
+    pub async fn set_logging_settings(&self, logging: LoggingConfig) -> Result<()> {
+        debug!("Setting logging config to: {:?}", logging);
+
+        let mut config = self.get_config().await;
+        if config.logging != logging {
+            config.logging = logging;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update the backup schedule and rotation
+    pub async fn set_backup_settings(&self, backups: BackupConfig) -> Result<()> {
+        debug!("Setting backup config to: {:?}", backups);
+
+        let mut config = self.get_config().await;
+        if config.backups != backups {
+            config.backups = backups;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Update the rating age and auto-play limit of the kids profile, which
+    /// stay as they are while it is on
+    pub async fn set_kids_settings(&self, kids: KidsConfig) -> Result<()> {
+        debug!(
+            "Setting kids profile to ages up to {}, {} episodes in a row",
+            kids.max_age, kids.autoplay_limit
+        );
+
+        let mut config = self.get_config().await;
+        if config.kids.enabled {
+            bail!("The kids profile is on");
+        }
+        if config.kids.max_age != kids.max_age || config.kids.autoplay_limit != kids.autoplay_limit
+        {
+            config.kids.max_age = kids.max_age;
+            config.kids.autoplay_limit = kids.autoplay_limit;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Turn the kids profile on, with the PIN asked for to leave it
+    pub async fn enable_kids_profile(&self, pin: &str) -> Result<()> {
+        if !KidsProfileService::is_valid_pin(pin) {
+            bail!("The PIN needs at least {} digits", MIN_PIN_LENGTH);
+        }
+        info!("Turning the kids profile on");
+
+        let mut config = self.get_config().await;
+        config.kids.enabled = true;
+        config.kids.pin_hash = Some(KidsProfileService::hash_pin(pin));
+        self.update_config(config).await
+    }
+
+    /// Leave the kids profile if `pin` is its PIN, returning whether it was
+    pub async fn disable_kids_profile(&self, pin: &str) -> Result<bool> {
+        let mut config = self.get_config().await;
+        if !config.kids.enabled {
+            return Ok(true);
+        }
+        if !KidsProfileService::pin_matches(&config.kids, pin) {
+            info!("Wrong PIN given to leave the kids profile");
+            return Ok(false);
+        }
+        info!("Leaving the kids profile");
+
+        config.kids.enabled = false;
+        config.kids.pin_hash = None;
+        self.update_config(config).await?;
+        Ok(true)
+    }
+
+    /// Update the offline download cleanup policy
+    pub async fn set_downloads_settings(&self, downloads: DownloadsConfig) -> Result<()> {
+        debug!("Setting downloads config to: {:?}", downloads);
+
+        let mut config = self.get_config().await;
+        if config.downloads != downloads {
+            config.downloads = downloads;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
     /// Get playback configuration
     pub async fn get_playback_config(&self) -> PlaybackConfig {
         self.config.read().await.playback.clone()
@@ -239,6 +518,159 @@ impl ConfigService {
         Ok(())
     }
 
+    /// Set the order of a source's libraries in the sidebar
+    pub async fn set_sidebar_library_order(
+        &self,
+        source_id: String,
+        library_ids: Vec<String>,
+    ) -> Result<()> {
+        debug!("Setting sidebar library order for source {}", source_id);
+
+        let mut config = self.get_config().await;
+        config
+            .ui
+            .sidebar_library_order
+            .insert(source_id, library_ids);
+        self.update_config(config).await?;
+
+        Ok(())
+    }
+
+    /// Hide a library from the sidebar or show it again
+    pub async fn set_sidebar_library_hidden(&self, library_id: String, hidden: bool) -> Result<()> {
+        debug!(
+            "Setting library {} hidden in sidebar: {}",
+            library_id, hidden
+        );
+
+        let mut config = self.get_config().await;
+        let hidden_libraries = &mut config.ui.hidden_sidebar_libraries;
+        hidden_libraries.retain(|id| *id != library_id);
+        if hidden {
+            hidden_libraries.push(library_id);
+        }
+        self.update_config(config).await?;
+
+        Ok(())
+    }
+
+    /// Remember whether a source's sidebar group is expanded
+    pub async fn set_sidebar_source_expanded(
+        &self,
+        source_id: String,
+        expanded: bool,
+    ) -> Result<()> {
+        let mut config = self.get_config().await;
+        let collapsed_sources = &mut config.ui.collapsed_sidebar_sources;
+        collapsed_sources.retain(|id| *id != source_id);
+        if !expanded {
+            collapsed_sources.push(source_id);
+        }
+        self.update_config(config).await?;
+
+        Ok(())
+    }
+
+    /// Update the size and compression of artwork fetched from servers
+    pub async fn set_image_quality(&self, quality: ImageQuality) -> Result<()> {
+        debug!("Setting image quality to: {:?}", quality);
+
+        let mut config = self.get_config().await;
+        if config.ui.image_quality != quality {
+            config.ui.image_quality = quality;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Enable or disable theme music on detail pages
+    pub async fn set_theme_music(&self, enabled: bool) -> Result<()> {
+        debug!("Setting theme music to: {}", enabled);
+
+        let mut config = self.get_config().await;
+        if config.ui.theme_music != enabled {
+            config.ui.theme_music = enabled;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the most items shown in each home row
+    pub async fn set_home_row_limit(&self, limit: u32) -> Result<()> {
+        debug!("Setting home row limit to: {}", limit);
+
+        let mut config = self.get_config().await;
+        if config.ui.home_row_limit != limit {
+            config.ui.home_row_limit = limit;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the minutes idle before the screensaver starts, 0 to turn it off
+    pub async fn set_screensaver_minutes(&self, minutes: u32) -> Result<()> {
+        debug!("Setting screensaver delay to: {} minutes", minutes);
+
+        let mut config = self.get_config().await;
+        if config.ui.screensaver_minutes != minutes {
+            config.ui.screensaver_minutes = minutes;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Enable or disable hover previews on media cards
+    pub async fn set_hover_previews(&self, enabled: bool) -> Result<()> {
+        debug!("Setting hover previews to: {}", enabled);
+
+        let mut config = self.get_config().await;
+        if config.ui.hover_previews != enabled {
+            config.ui.hover_previews = enabled;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Enable or disable asking at startup to resume interrupted playback
+    pub async fn set_resume_prompt(&self, enabled: bool) -> Result<()> {
+        debug!("Setting resume prompt to: {}", enabled);
+
+        let mut config = self.get_config().await;
+        if config.ui.resume_prompt != enabled {
+            config.ui.resume_prompt = enabled;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Forget the playback interrupted by quitting once it has been offered
+    pub async fn clear_interrupted_playback(&self) -> Result<()> {
+        let mut config = self.get_config().await;
+        if config.ui.interrupted_playback.take().is_some() {
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Put a URL opened for playback at the top of the recent URLs
+    pub async fn remember_opened_url(&self, url: &str) -> Result<()> {
+        let mut config = self.get_config().await;
+        let recent = url_playback::with_recent_url(&config.ui.recent_urls, url);
+        if config.ui.recent_urls != recent {
+            config.ui.recent_urls = recent;
+            self.update_config(config).await?;
+        }
+
+        Ok(())
+    }
+
     /// Get a filter preset by name
     pub async fn get_filter_preset(&self, preset_name: &str) -> Option<String> {
         let config = self.config.read().await;
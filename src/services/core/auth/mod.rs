@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use std::path::PathBuf;
 use tracing::{debug, info, warn};
 
 use crate::backends::traits::MediaBackend;
@@ -7,14 +8,62 @@ use crate::db::entities::{AuthTokenModel, SourceModel};
 use crate::db::repository::{
     AuthTokenRepository, AuthTokenRepositoryImpl, Repository, SourceRepositoryImpl,
 };
-use crate::models::Credentials;
-use crate::models::auth_provider::{ConnectionInfo, Source, SourceType};
+use crate::models::auth_provider::{ClientIdentity, ConnectionInfo, Source, SourceType};
+use crate::models::{ConnectionPreferences, Credentials};
 use crate::models::{SourceId, User};
 
+pub mod secure_storage;
+
+pub use secure_storage::SecureStorage;
+
+/// Token column of credentials whose secret is in the secure storage rather
+/// than the database
+const IN_SECURE_STORAGE: &str = "secure-storage";
+
 /// Pure functions for authentication operations
 pub struct AuthService;
 
 impl AuthService {
+    /// Name a credential of a source is kept under in the secure storage
+    fn secret_name(source_id: &str, token_type: &str) -> String {
+        format!("source-{}-{}", source_id, token_type)
+    }
+
+    /// Secret of a credential of a source, if it has one. Secrets older
+    /// versions saved in the database are moved to the secure storage.
+    pub async fn load_secret(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        token_type: &str,
+    ) -> Result<Option<String>> {
+        let repo = AuthTokenRepositoryImpl::new(db.clone());
+        let Some(auth_token) = repo
+            .find_by_source_and_type(source_id.as_ref(), token_type)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let storage = SecureStorage::from_config().await?;
+        let name = Self::secret_name(source_id.as_ref(), token_type);
+        if auth_token.token == IN_SECURE_STORAGE {
+            return storage.get(&name);
+        }
+
+        storage.set(&name, &auth_token.token)?;
+        let secret = auth_token.token.clone();
+        repo.upsert(AuthTokenModel {
+            token: IN_SECURE_STORAGE.to_string(),
+            ..auth_token
+        })
+        .await?;
+        info!(
+            "Moved {} of source {} from the database to the secure storage",
+            token_type, source_id
+        );
+        Ok(Some(secret))
+    }
+
     /// Migrate credentials from keyring to database if they exist
     pub async fn migrate_credentials_from_keyring(
         db: &DatabaseConnection,
@@ -103,7 +152,7 @@ impl AuthService {
             .context("Failed to authenticate with backend")
     }
 
-    /// Save authentication credentials to database
+    /// Save authentication credentials, their secret in the secure storage
     pub async fn save_credentials(
         db: &DatabaseConnection,
         source_id: &SourceId,
@@ -123,11 +172,20 @@ impl AuthService {
             Credentials::ApiKey { key, .. } => ("api_key".to_string(), key.clone()),
         };
 
+        // The database only records that there is a secret
+        SecureStorage::from_config()
+            .await?
+            .set(
+                &Self::secret_name(source_id.as_ref(), &token_type),
+                &token_value,
+            )
+            .with_context(|| format!("Failed to save credentials for source: {}", source_id))?;
+
         let auth_token = AuthTokenModel {
             id: 0, // Will be set by database
             source_id: source_id.to_string(),
             token_type,
-            token: token_value,
+            token: IN_SECURE_STORAGE.to_string(),
             created_at: chrono::Utc::now().naive_utc(),
             updated_at: chrono::Utc::now().naive_utc(),
             expires_at: None, // TODO: Handle token expiration if needed
@@ -141,39 +199,13 @@ impl AuthService {
         Ok(())
     }
 
-    /// Load authentication credentials from database
+    /// Load authentication credentials, their secret from the secure storage
     pub async fn load_credentials(
         db: &DatabaseConnection,
         source_id: &SourceId,
     ) -> Result<Option<Credentials>> {
-        let repo = AuthTokenRepositoryImpl::new(db.clone());
-
-        // Try to find token credentials
-        if let Some(auth_token) = repo
-            .find_by_source_and_type(source_id.as_ref(), "token")
-            .await?
-        {
-            debug!(
-                "Found token credentials for source: {} in database",
-                source_id
-            );
-            return Ok(Some(Credentials::Token {
-                token: auth_token.token,
-            }));
-        }
-
-        // Try to find API key credentials
-        if let Some(auth_token) = repo
-            .find_by_source_and_type(source_id.as_ref(), "api_key")
-            .await?
-        {
-            debug!(
-                "Found API key credentials for source: {} in database",
-                source_id
-            );
-            return Ok(Some(Credentials::ApiKey {
-                key: auth_token.token,
-            }));
+        if let Some(credentials) = Self::load_saved_credentials(db, source_id).await? {
+            return Ok(Some(credentials));
         }
 
         // For username/password, we'd need to know the username
@@ -186,27 +218,7 @@ impl AuthService {
         );
         if Self::migrate_credentials_from_keyring(db, source_id).await? {
             // Migration successful, try loading again from database
-            let repo = AuthTokenRepositoryImpl::new(db.clone());
-
-            // Try to find token credentials again
-            if let Some(auth_token) = repo
-                .find_by_source_and_type(source_id.as_ref(), "token")
-                .await?
-            {
-                return Ok(Some(Credentials::Token {
-                    token: auth_token.token,
-                }));
-            }
-
-            // Try to find API key credentials again
-            if let Some(auth_token) = repo
-                .find_by_source_and_type(source_id.as_ref(), "api_key")
-                .await?
-            {
-                return Ok(Some(Credentials::ApiKey {
-                    key: auth_token.token,
-                }));
-            }
+            return Self::load_saved_credentials(db, source_id).await;
         }
 
         debug!(
@@ -216,9 +228,44 @@ impl AuthService {
         Ok(None)
     }
 
+    /// Token or API key credentials saved for a source
+    async fn load_saved_credentials(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+    ) -> Result<Option<Credentials>> {
+        // Try to find token credentials
+        if let Some(token) = Self::load_secret(db, source_id, "token").await? {
+            debug!(
+                "Found token credentials for source: {} in database",
+                source_id
+            );
+            return Ok(Some(Credentials::Token { token }));
+        }
+
+        // Try to find API key credentials
+        if let Some(key) = Self::load_secret(db, source_id, "api_key").await? {
+            debug!(
+                "Found API key credentials for source: {} in database",
+                source_id
+            );
+            return Ok(Some(Credentials::ApiKey { key }));
+        }
+
+        Ok(None)
+    }
+
     /// Remove authentication credentials from database
     pub async fn remove_credentials(db: &DatabaseConnection, source_id: &SourceId) -> Result<()> {
         let repo = AuthTokenRepositoryImpl::new(db.clone());
+        let storage = SecureStorage::from_config().await?;
+        for auth_token in repo.find_all().await? {
+            if auth_token.source_id == source_id.as_ref() {
+                storage.delete(&Self::secret_name(
+                    source_id.as_ref(),
+                    &auth_token.token_type,
+                ))?;
+            }
+        }
         let deleted_count = repo.delete_by_source(source_id.as_ref()).await?;
         debug!(
             "Removed {} credential(s) for source: {} from database",
@@ -253,6 +300,7 @@ impl AuthService {
                 owned: is_owned.unwrap_or(true),
             },
             "jellyfin" => SourceType::JellyfinServer,
+            "emby" => SourceType::EmbyServer,
             "local" => SourceType::LocalFolder {
                 path: std::path::PathBuf::from("/"),
             },
@@ -280,6 +328,10 @@ impl AuthService {
             connection_quality: None,
             auth_status: "authenticated".to_string(),
             last_auth_check: Some(chrono::Utc::now().naive_utc()),
+            mac_address: None,
+            client_identity: None,
+            active_user_id: None,
+            connection_preferences: None,
             created_at: chrono::Utc::now().naive_utc(),
             updated_at: chrono::Utc::now().naive_utc(),
         };
@@ -300,6 +352,9 @@ impl AuthService {
             library_count: 0,
             auth_status: crate::models::AuthStatus::Authenticated,
             last_auth_check: Some(chrono::Utc::now()),
+            mac_address: None,
+            client_identity: ClientIdentity::default(),
+            connection_preferences: ConnectionPreferences::default(),
         };
 
         repo.insert(entity).await?;
@@ -311,6 +366,55 @@ impl AuthService {
         Ok(source)
     }
 
+    /// Create a source for a folder of video files on this machine. Local
+    /// folders need no credentials, the folder is kept as the connection URL.
+    pub async fn create_local_source(db: &DatabaseConnection, path: PathBuf) -> Result<Source> {
+        if !path.is_dir() {
+            return Err(anyhow::anyhow!("{} is not a folder", path.display()));
+        }
+
+        let source_id = SourceId::new(format!("local_{}", uuid::Uuid::new_v4()));
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+
+        let now = chrono::Utc::now().naive_utc();
+        let entity = SourceModel {
+            id: source_id.to_string(),
+            name,
+            source_type: "local".to_string(),
+            auth_provider_id: None,
+            connection_url: Some(path.to_string_lossy().to_string()),
+            connections: None,
+            machine_id: None,
+            is_owned: true,
+            is_online: true,
+            last_sync: None,
+            last_connection_test: None,
+            connection_failure_count: 0,
+            connection_quality: Some("local".to_string()),
+            auth_status: "authenticated".to_string(),
+            last_auth_check: Some(now),
+            mac_address: None,
+            client_identity: None,
+            active_user_id: None,
+            connection_preferences: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let repo = SourceRepositoryImpl::new(db.clone());
+        repo.insert(entity.clone()).await?;
+
+        info!(
+            "Created local folder source {} for {}",
+            source_id,
+            path.display()
+        );
+        Ok(Source::from(entity))
+    }
+
     /// Remove a source and its credentials
     pub async fn remove_source(db: &DatabaseConnection, source_id: &SourceId) -> Result<()> {
         // Remove credentials
@@ -405,6 +509,7 @@ impl AuthService {
                 owned: updated_source.is_owned,
             },
             "jellyfin" => SourceType::JellyfinServer,
+            "emby" => SourceType::EmbyServer,
             "local" => SourceType::LocalFolder {
                 path: std::path::PathBuf::from("/"),
             },
@@ -430,6 +535,11 @@ impl AuthService {
             library_count: 0,
             auth_status: crate::models::AuthStatus::from(updated_source.auth_status.clone()),
             last_auth_check: updated_source.last_auth_check.map(|dt| dt.and_utc()),
+            mac_address: updated_source.mac_address.clone(),
+            client_identity: ClientIdentity::from_json(updated_source.client_identity.as_ref()),
+            connection_preferences: ConnectionPreferences::from_json(
+                updated_source.connection_preferences.as_ref(),
+            ),
         };
 
         info!("Updated credentials for source: {}", source_id);
@@ -0,0 +1,402 @@
+//! Where server tokens are kept between runs
+//!
+//! The system keyring is used unless the config asks otherwise. Where no
+//! Secret Service can be activated, as on many minimal desktops and in some
+//! sandboxes, the keyring refuses every request; secrets then go to a file
+//! encrypted with XChaCha20-Poly1305 under a key derived from the machine, so
+//! copying the file elsewhere doesn't reveal them. Secrets older versions
+//! left in obfuscated files next to the config are moved into the store the
+//! first time they are read.
+
+use anyhow::{Context, Result, anyhow};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::config::TokenStorage;
+use crate::services::config_service::CONFIG_SERVICE;
+
+/// Keyring service secrets are saved under
+const KEYRING_SERVICE: &str = "dev.arsfeld.Reel";
+
+/// Length of the XChaCha20 nonce each encrypted file starts with
+const NONCE_LEN: usize = 24;
+
+/// Store for secrets such as server tokens, keyed by name
+#[derive(Debug, Clone)]
+pub struct SecureStorage {
+    kind: TokenStorage,
+    /// Directory the file stores keep one file per secret in
+    dir: PathBuf,
+    key: [u8; 32],
+}
+
+impl SecureStorage {
+    /// The storage the config selects, in the config directory
+    pub async fn from_config() -> Result<Self> {
+        let kind = CONFIG_SERVICE.get_config().await.security.token_storage;
+        let dir = dirs::config_dir()
+            .context("Could not determine config directory")?
+            .join("reel")
+            .join("secrets");
+        Ok(Self::new(kind, dir))
+    }
+
+    pub fn new(kind: TokenStorage, dir: PathBuf) -> Self {
+        Self {
+            kind,
+            dir,
+            key: machine_key(),
+        }
+    }
+
+    /// The secret saved as `name`, if any
+    pub fn get(&self, name: &str) -> Result<Option<String>> {
+        match self.get_stored(name)? {
+            Some(secret) => Ok(Some(secret)),
+            None => self.migrate_legacy_file(name),
+        }
+    }
+
+    fn get_stored(&self, name: &str) -> Result<Option<String>> {
+        match self.kind {
+            TokenStorage::Keyring => match keyring_entry(name)?.get_password() {
+                Ok(secret) => Ok(Some(secret)),
+                Err(keyring::Error::NoEntry) => Ok(None),
+                Err(e) if keyring_unavailable(&e) => {
+                    debug!("Keyring unavailable ({}), reading {} from file", e, name);
+                    self.read_encrypted(name)
+                }
+                Err(e) => Err(e).context("Failed to read from keyring"),
+            },
+            TokenStorage::EncryptedFile => self.read_encrypted(name),
+            TokenStorage::Plaintext => self.read_plaintext(name),
+        }
+    }
+
+    /// Save `secret` as `name`, replacing what was saved before
+    pub fn set(&self, name: &str, secret: &str) -> Result<()> {
+        match self.kind {
+            TokenStorage::Keyring => match keyring_entry(name)?.set_password(secret) {
+                Ok(()) => Ok(()),
+                Err(e) if keyring_unavailable(&e) => {
+                    warn!(
+                        "Keyring unavailable ({}), saving {} to an encrypted file",
+                        e, name
+                    );
+                    self.write_encrypted(name, secret)
+                }
+                Err(e) => Err(e).context("Failed to save to keyring"),
+            },
+            TokenStorage::EncryptedFile => self.write_encrypted(name, secret),
+            TokenStorage::Plaintext => {
+                warn!(
+                    "Saving {} unencrypted, anyone who can read {} can use it",
+                    name,
+                    self.dir.display()
+                );
+                write_private(&self.path(name, "txt"), secret.as_bytes())
+            }
+        }
+    }
+
+    /// Forget the secret saved as `name`, wherever it was saved
+    pub fn delete(&self, name: &str) -> Result<()> {
+        if self.kind == TokenStorage::Keyring {
+            match keyring_entry(name)?.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => {}
+                Err(e) if keyring_unavailable(&e) => {}
+                Err(e) => return Err(e).context("Failed to delete from keyring"),
+            }
+        }
+        let paths = ["enc", "txt"]
+            .into_iter()
+            .map(|extension| self.path(name, extension))
+            .chain(self.legacy_paths(name));
+        for path in paths {
+            match fs::remove_file(path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e).context("Failed to delete secret file"),
+            }
+        }
+        Ok(())
+    }
+
+    fn path(&self, name: &str, extension: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", name, extension))
+    }
+
+    /// Files older versions saved `name` to where the keyring failed, like
+    /// `~/.config/reel/.plex_1.token`
+    fn legacy_paths(&self, name: &str) -> Vec<PathBuf> {
+        let Some(config_dir) = self.dir.parent() else {
+            return Vec::new();
+        };
+        ["token", "cred"]
+            .into_iter()
+            .map(|extension| config_dir.join(format!(".{}.{}", name, extension)))
+            .collect()
+    }
+
+    /// Move a secret out of the file older versions saved it to, so it isn't
+    /// lost, and out of sight, when that file is no longer read
+    fn migrate_legacy_file(&self, name: &str) -> Result<Option<String>> {
+        for path in self.legacy_paths(name) {
+            let Some(data) = read_if_exists(&path)? else {
+                continue;
+            };
+            // Older versions only XORed the secret with its byte positions
+            let secret: Vec<u8> = data
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| byte ^ (i as u8).wrapping_add(42))
+                .collect();
+            let secret = String::from_utf8(secret).context("Saved secret is not text")?;
+
+            self.set(name, &secret)?;
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to delete {}", path.display()))?;
+            info!("Moved {} from {} to the token store", name, path.display());
+            return Ok(Some(secret));
+        }
+        Ok(None)
+    }
+
+    fn read_encrypted(&self, name: &str) -> Result<Option<String>> {
+        let Some(data) = read_if_exists(&self.path(name, "enc"))? else {
+            return Ok(None);
+        };
+        if data.len() < NONCE_LEN {
+            return Err(anyhow!("Encrypted secret {} is truncated", name));
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let secret = self
+            .cipher()
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                anyhow!(
+                    "Failed to decrypt secret {}, it was saved on another machine or altered",
+                    name
+                )
+            })?;
+        String::from_utf8(secret)
+            .map(Some)
+            .context("Decrypted secret is not text")
+    }
+
+    fn write_encrypted(&self, name: &str, secret: &str) -> Result<()> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: secret.as_bytes(),
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("Failed to encrypt secret {}", name))?;
+
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&ciphertext);
+        write_private(&self.path(name, "enc"), &data)
+    }
+
+    fn read_plaintext(&self, name: &str) -> Result<Option<String>> {
+        read_if_exists(&self.path(name, "txt"))?
+            .map(|data| String::from_utf8(data).context("Saved secret is not text"))
+            .transpose()
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.key.into())
+    }
+}
+
+fn keyring_entry(name: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, name).context("Failed to create keyring entry")
+}
+
+/// Whether the keyring failed because there is no keyring to use, like when
+/// the Secret Service is not activatable
+fn keyring_unavailable(error: &keyring::Error) -> bool {
+    matches!(
+        error,
+        keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_)
+    )
+}
+
+/// Key of the encrypted file store, the same on every run on this machine
+/// for this user
+fn machine_key() -> [u8; 32] {
+    let machine_id = ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .or_else(sysinfo::System::host_name)
+        .unwrap_or_default();
+    let home = dirs::home_dir().unwrap_or_default();
+
+    Sha256::new()
+        .chain_update(KEYRING_SERVICE.as_bytes())
+        .chain_update(machine_id.trim().as_bytes())
+        .chain_update(home.to_string_lossy().as_bytes())
+        .finalize()
+        .into()
+}
+
+fn read_if_exists(path: &Path) -> Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Write a file only the user can read
+fn write_private(path: &Path, data: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    // The mode only applies to new files, tighten one saved before
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(data)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn storage(kind: TokenStorage, dir: &TempDir) -> SecureStorage {
+        SecureStorage::new(kind, dir.path().to_path_buf())
+    }
+
+    #[test]
+    fn test_encrypted_file_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let storage = storage(TokenStorage::EncryptedFile, &dir);
+
+        assert_eq!(storage.get("plex-1").unwrap(), None);
+        storage.set("plex-1", "secret-token").unwrap();
+        assert_eq!(
+            storage.get("plex-1").unwrap().as_deref(),
+            Some("secret-token")
+        );
+
+        let data = fs::read(dir.path().join("plex-1.enc")).unwrap();
+        assert!(!data.windows(12).any(|window| window == b"secret-token"));
+
+        storage.delete("plex-1").unwrap();
+        assert_eq!(storage.get("plex-1").unwrap(), None);
+    }
+
+    #[test]
+    fn test_encrypted_file_is_tied_to_its_key_and_name() {
+        let dir = TempDir::new().unwrap();
+        let storage = storage(TokenStorage::EncryptedFile, &dir);
+        storage.set("plex-1", "secret-token").unwrap();
+
+        let other_machine = SecureStorage {
+            key: [7; 32],
+            ..storage.clone()
+        };
+        assert!(other_machine.get("plex-1").is_err());
+
+        fs::copy(dir.path().join("plex-1.enc"), dir.path().join("plex-2.enc")).unwrap();
+        assert!(storage.get("plex-2").is_err());
+    }
+
+    #[test]
+    fn test_plaintext_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let storage = storage(TokenStorage::Plaintext, &dir);
+
+        storage.set("jellyfin-1", "url|key|user").unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("jellyfin-1.txt")).unwrap(),
+            "url|key|user"
+        );
+        assert_eq!(
+            storage.get("jellyfin-1").unwrap().as_deref(),
+            Some("url|key|user")
+        );
+    }
+
+    #[test]
+    fn test_legacy_token_file_is_moved_into_the_store() {
+        let config_dir = TempDir::new().unwrap();
+        let storage = SecureStorage::new(
+            TokenStorage::EncryptedFile,
+            config_dir.path().join("secrets"),
+        );
+        let legacy_file = config_dir.path().join(".plex_1.token");
+        let obfuscated: Vec<u8> = b"legacy-token"
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| byte ^ (i as u8 + 42))
+            .collect();
+        fs::write(&legacy_file, obfuscated).unwrap();
+
+        assert_eq!(
+            storage.get("plex_1").unwrap().as_deref(),
+            Some("legacy-token")
+        );
+        assert!(!legacy_file.exists());
+        assert_eq!(
+            storage.get_stored("plex_1").unwrap().as_deref(),
+            Some("legacy-token")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_secret_files_are_only_readable_by_the_user() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("plex-1.txt");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        storage(TokenStorage::Plaintext, &dir)
+            .set("plex-1", "secret-token")
+            .unwrap();
+        storage(TokenStorage::EncryptedFile, &dir)
+            .set("plex-2", "secret-token")
+            .unwrap();
+        for name in ["plex-1.txt", "plex-2.enc"] {
+            let mode = fs::metadata(dir.path().join(name))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
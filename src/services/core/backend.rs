@@ -1,16 +1,22 @@
-use crate::backends::{jellyfin::JellyfinBackend, plex::PlexBackend, traits::MediaBackend};
+use crate::backends::{
+    emby::EmbyBackend, jellyfin::JellyfinBackend, local::LocalFilesBackend, plex::PlexBackend,
+    traits::MediaBackend,
+};
 use crate::db::connection::DatabaseConnection;
 use crate::db::repository::{
     Repository,
-    media_repository::MediaRepositoryImpl,
+    media_repository::{MediaRepository, MediaRepositoryImpl},
     people_repository::PeopleRepository,
     source_repository::{SourceRepository, SourceRepositoryImpl},
 };
 use crate::models::{
-    AuthProvider, AuthStatus, AuthenticationResult, ConnectionInfo, Credentials, HomeSection,
-    MediaItemId, Source, SourceId, SourceType, StreamInfo,
+    AuthProvider, AuthStatus, AuthenticationResult, ChapterMarker, ClientIdentity, ConnectionInfo,
+    ConnectionPreferences, Credentials, HomeSection, MediaItemId, Source, SourceId, SourceType,
+    StreamInfo, User,
 };
 use crate::services::core::auth::AuthService;
+use crate::services::core::client_identity;
+use crate::services::core::local_user::LocalUserService;
 use anyhow::{Context, Result};
 
 /// Stateless backend service following Relm4's pure function pattern
@@ -42,11 +48,44 @@ impl BackendService {
         backend.get_stream_url(media_item_id).await
     }
 
+    /// Show the watch state of the user a backend signed in as
+    async fn record_active_user(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        user: &User,
+    ) -> Result<()> {
+        if LocalUserService::set_active_user(db, source_id, &user.id)
+            .await
+            .context("Failed to update source active user")?
+        {
+            tracing::info!(
+                "Source {} switched to user {}, showing their watch state",
+                source_id,
+                user.username
+            );
+        }
+        Ok(())
+    }
+
     /// Create a backend instance for a source - stateless factory
     pub async fn create_backend_for_source(
         db: &DatabaseConnection,
         source_entity: &crate::db::entities::sources::Model,
     ) -> Result<Box<dyn MediaBackend>> {
+        // Local folders have no server to sign in to
+        if source_entity.is_local() {
+            let backend = LocalFilesBackend::from_source(Self::entity_to_source(source_entity))
+                .context("Failed to create local files backend")?;
+            if let AuthenticationResult::NetworkError(msg) = backend.initialize().await? {
+                return Err(anyhow::anyhow!(
+                    "Failed to open source {}: {}",
+                    source_entity.id,
+                    msg
+                ));
+            }
+            return Ok(Box::new(backend));
+        }
+
         // Load credentials from secure storage
         let source_id = SourceId::new(source_entity.id.clone());
         let credentials = AuthService::load_credentials(db, &source_id)
@@ -58,6 +97,7 @@ impl BackendService {
 
         // Create Source struct from entity
         let source = Self::entity_to_source(source_entity);
+        client_identity::set_identity_for_source(&source.id, source.client_identity.clone());
 
         // Create and initialize the appropriate backend
         let backend: Box<dyn MediaBackend> = match source_entity.source_type.as_str() {
@@ -81,7 +121,9 @@ impl BackendService {
 
                 // Handle authentication result
                 match auth_result {
-                    AuthenticationResult::Authenticated(_user) => {
+                    AuthenticationResult::Authenticated(user) => {
+                        Self::record_active_user(db, &source_id, &user).await?;
+
                         // Update the source with the best connection URL if it changed
                         if backend.has_url_changed().await
                             && let Some(new_url) = backend.get_current_url().await
@@ -134,8 +176,48 @@ impl BackendService {
 
                 // Handle authentication result
                 match auth_result {
-                    AuthenticationResult::Authenticated(_user) => {
-                        // Successfully authenticated
+                    AuthenticationResult::Authenticated(user) => {
+                        Self::record_active_user(db, &source_id, &user).await?;
+                    }
+                    AuthenticationResult::AuthRequired => {
+                        return Err(anyhow::anyhow!(
+                            "Authentication required for source {}",
+                            source_entity.id
+                        ));
+                    }
+                    AuthenticationResult::NetworkError(msg) => {
+                        return Err(anyhow::anyhow!(
+                            "Network error initializing source {}: {}",
+                            source_entity.id,
+                            msg
+                        ));
+                    }
+                }
+
+                Box::new(backend)
+            }
+            "emby" | "EmbyServer" => {
+                let backend = EmbyBackend::from_auth(auth_provider, source)
+                    .context("Failed to create Emby backend")?;
+                let auth_result = backend.initialize().await?;
+
+                // Update source auth status based on initialization result
+                let auth_status = match &auth_result {
+                    AuthenticationResult::Authenticated(_) => AuthStatus::Authenticated,
+                    AuthenticationResult::AuthRequired => AuthStatus::AuthRequired,
+                    AuthenticationResult::NetworkError(_) => AuthStatus::Unknown,
+                };
+
+                let source_repo = SourceRepositoryImpl::new(db.clone());
+                source_repo
+                    .update_auth_status(&source_entity.id, auth_status)
+                    .await
+                    .context("Failed to update source auth status")?;
+
+                // Handle authentication result
+                match auth_result {
+                    AuthenticationResult::Authenticated(user) => {
+                        Self::record_active_user(db, &source_id, &user).await?;
                     }
                     AuthenticationResult::AuthRequired => {
                         return Err(anyhow::anyhow!(
@@ -208,6 +290,20 @@ impl BackendService {
                     access_token: String::new(), // Will be populated during initialization
                 }
             }
+            // Emby with the token its sign-in returned (format: token|user_id)
+            (Credentials::Token { token }, "emby" | "EmbyServer") => {
+                let (access_token, user_id) = token
+                    .split_once('|')
+                    .context("Emby token is missing its user id")?;
+
+                AuthProvider::EmbyAuth {
+                    id: source.auth_provider_id.clone().unwrap_or_default(),
+                    server_url: source.connection_url.clone().unwrap_or_default(),
+                    username: String::new(),
+                    user_id: user_id.to_string(),
+                    access_token: access_token.to_string(),
+                }
+            }
             _ => return Err(anyhow::anyhow!("Unsupported credential type for source")),
         };
 
@@ -226,8 +322,11 @@ impl BackendService {
                     owned: entity.is_owned,
                 },
                 "jellyfin" | "JellyfinServer" => SourceType::JellyfinServer,
+                "emby" | "EmbyServer" => SourceType::EmbyServer,
                 _ => SourceType::LocalFolder {
-                    path: std::path::PathBuf::new(),
+                    path: std::path::PathBuf::from(
+                        entity.connection_url.clone().unwrap_or_default(),
+                    ),
                 },
             },
             auth_provider_id: entity.auth_provider_id.clone(),
@@ -242,6 +341,11 @@ impl BackendService {
             library_count: 0,
             auth_status: crate::models::AuthStatus::from(entity.auth_status.clone()),
             last_auth_check: entity.last_auth_check.map(|dt| dt.and_utc()),
+            mac_address: entity.mac_address.clone(),
+            client_identity: ClientIdentity::from_json(entity.client_identity.as_ref()),
+            connection_preferences: ConnectionPreferences::from_json(
+                entity.connection_preferences.as_ref(),
+            ),
         }
     }
 
@@ -341,8 +445,125 @@ impl BackendService {
         Ok(())
     }
 
+    /// Mark or unmark a media item as favorite on the backend
+    pub async fn set_favorite(
+        db: &DatabaseConnection,
+        source_id: &str,
+        media_id: &MediaItemId,
+        favorite: bool,
+    ) -> Result<()> {
+        // Load source configuration
+        let source_repo = SourceRepositoryImpl::new(db.clone());
+        let source_entity = source_repo
+            .find_by_id(source_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Source not found"))?;
+
+        let backend = Self::create_backend_for_source(db, &source_entity).await?;
+
+        // Extract the actual backend item ID (remove source prefix)
+        let media_id_str = media_id.to_string();
+        let item_id = if let Some(colon_pos) = media_id_str.find(':') {
+            &media_id_str[colon_pos + 1..]
+        } else {
+            media_id_str.as_str()
+        };
+
+        backend.set_favorite(item_id, favorite).await
+    }
+
+    /// Put a label on a media item on the backend, or take it off
+    pub async fn set_label(
+        db: &DatabaseConnection,
+        source_id: &str,
+        media_id: &MediaItemId,
+        label: &str,
+        present: bool,
+    ) -> Result<()> {
+        // Load source configuration
+        let source_repo = SourceRepositoryImpl::new(db.clone());
+        let source_entity = source_repo
+            .find_by_id(source_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Source not found"))?;
+
+        let backend = Self::create_backend_for_source(db, &source_entity).await?;
+
+        // Extract the actual backend item ID (remove source prefix)
+        let media_id_str = media_id.to_string();
+        let item_id = if let Some(colon_pos) = media_id_str.find(':') {
+            &media_id_str[colon_pos + 1..]
+        } else {
+            media_id_str.as_str()
+        };
+
+        backend.set_label(item_id, label, present).await
+    }
+
+    /// Whether the user may delete a media item from the backend
+    pub async fn can_delete(
+        db: &DatabaseConnection,
+        source_id: &str,
+        media_id: &MediaItemId,
+    ) -> Result<bool> {
+        // Load source configuration
+        let source_repo = SourceRepositoryImpl::new(db.clone());
+        let source_entity = source_repo
+            .find_by_id(source_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Source not found"))?;
+
+        // Plex reports the server-wide deletion setting even to shared users,
+        // who can't delete anything
+        if source_entity.is_plex() && !source_entity.is_owned {
+            return Ok(false);
+        }
+
+        let backend = Self::create_backend_for_source(db, &source_entity).await?;
+
+        // Extract the actual backend item ID (remove source prefix)
+        let media_id_str = media_id.to_string();
+        let item_id = if let Some(colon_pos) = media_id_str.find(':') {
+            &media_id_str[colon_pos + 1..]
+        } else {
+            media_id_str.as_str()
+        };
+
+        backend.can_delete(item_id).await
+    }
+
+    /// Delete a media item, or one of its versions, from the backend
+    pub async fn delete_item(
+        db: &DatabaseConnection,
+        source_id: &str,
+        media_id: &MediaItemId,
+        version_id: Option<&str>,
+    ) -> Result<()> {
+        // Load source configuration
+        let source_repo = SourceRepositoryImpl::new(db.clone());
+        let source_entity = source_repo
+            .find_by_id(source_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Source not found"))?;
+
+        let backend = Self::create_backend_for_source(db, &source_entity).await?;
+
+        // Extract the actual backend item ID (remove source prefix)
+        let media_id_str = media_id.to_string();
+        let item_id = if let Some(colon_pos) = media_id_str.find(':') {
+            &media_id_str[colon_pos + 1..]
+        } else {
+            media_id_str.as_str()
+        };
+
+        backend.delete_item(item_id, version_id).await
+    }
+
     /// Fetch intro and credits markers from the backend
     /// Returns tuple of (intro_marker, credits_marker) where each is Option<(start_ms, end_ms)>
+    ///
+    /// For an episode, the markers of the rest of its season are fetched in the
+    /// same batch and stored, so the following episodes start without a request.
     pub async fn fetch_markers(
         db: &DatabaseConnection,
         media_id: &MediaItemId,
@@ -361,25 +582,47 @@ impl BackendService {
             .await?
             .ok_or_else(|| anyhow::anyhow!("Source not found"))?;
 
+        // Episodes of the same season that have no markers yet
+        let mut batch = vec![media_id.clone()];
+        if let (Some(show_id), Some(season)) = (&media_item.parent_id, media_item.season_number) {
+            let siblings = media_repo.find_episodes_by_season(show_id, season).await?;
+            batch.extend(
+                siblings
+                    .into_iter()
+                    .filter(|episode| {
+                        episode.id != media_item.id
+                            && episode.intro_marker_start_ms.is_none()
+                            && episode.credits_marker_start_ms.is_none()
+                    })
+                    .map(|episode| MediaItemId::new(episode.id)),
+            );
+        }
+
         // Create backend and fetch markers
         let backend = Self::create_backend_for_source(db, &source_entity).await?;
-        let (intro_marker, credits_marker) = backend.fetch_markers(media_id).await?;
+        let mut markers = backend.fetch_markers_batch(&batch).await?;
 
         // Convert ChapterMarker to (start_ms, end_ms) tuples
-        let intro_tuple = intro_marker.map(|m| {
-            (
-                m.start_time.as_millis() as i64,
-                m.end_time.as_millis() as i64,
-            )
-        });
-        let credits_tuple = credits_marker.map(|m| {
-            (
-                m.start_time.as_millis() as i64,
-                m.end_time.as_millis() as i64,
-            )
-        });
-
-        Ok((intro_tuple, credits_tuple))
+        let to_tuple = |marker: Option<ChapterMarker>| {
+            marker.map(|m| {
+                (
+                    m.start_time.as_millis() as i64,
+                    m.end_time.as_millis() as i64,
+                )
+            })
+        };
+
+        let requested = markers.remove(media_id).unwrap_or((None, None));
+        for (episode_id, (intro, credits)) in markers {
+            if let Err(e) = media_repo
+                .update_markers(episode_id.as_str(), to_tuple(intro), to_tuple(credits))
+                .await
+            {
+                tracing::warn!("Failed to store markers for {}: {}", episode_id, e);
+            }
+        }
+
+        Ok((to_tuple(requested.0), to_tuple(requested.1)))
     }
 
     /// Get home sections from all active sources
@@ -467,6 +710,30 @@ impl BackendService {
         Ok(all_sections)
     }
 
+    /// Get a stream URL of the theme music of a movie or show, if its server
+    /// has one
+    pub async fn get_theme_music_url(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> Result<Option<String>> {
+        // Load media item to find its source
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        let media_item = media_repo
+            .find_by_id(media_id.as_str())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Media item not found"))?;
+
+        // Load source configuration
+        let source_repo = SourceRepositoryImpl::new(db.clone());
+        let source_entity = source_repo
+            .find_by_id(&media_item.source_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Source not found"))?;
+
+        let backend = Self::create_backend_for_source(db, &source_entity).await?;
+        backend.get_theme_music_url(media_id.as_str()).await
+    }
+
     /// Load full metadata (including full cast/crew) for a movie and update database
     pub async fn load_full_movie_metadata(
         db: &DatabaseConnection,
@@ -0,0 +1,105 @@
+//! Scheduled and manual backups of the database and config
+//!
+//! Backups are taken every `backups.interval_hours` while the app runs and
+//! rotated down to `backups.keep` of each kind. The snapshots themselves are
+//! made by [`crate::db::backup`], which also takes one before migrations.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tracing::{info, warn};
+
+use crate::db::backup::{self, Backup, BackupReason};
+use crate::db::connection::DatabaseConnection;
+use crate::services::config_service::config_service;
+
+/// How often the schedule checks whether a backup is due
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Stateless service for taking and restoring backups
+pub struct BackupService;
+
+impl BackupService {
+    /// Check for a due backup now and then every hour, for as long as the
+    /// app runs
+    pub async fn run_schedule(db: DatabaseConnection) {
+        loop {
+            if let Err(e) = Self::back_up_if_due(&db).await {
+                warn!("Scheduled backup failed: {:#}", e);
+            }
+            tokio::time::sleep(SCHEDULE_CHECK_INTERVAL).await;
+        }
+    }
+
+    /// Take a scheduled backup when backups are enabled and the last one is
+    /// older than the interval
+    pub async fn back_up_if_due(db: &DatabaseConnection) -> Result<Option<Backup>> {
+        let config = config_service().get_config().await.backups;
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let latest = backup::list()?
+            .into_iter()
+            .find(|backup| {
+                matches!(
+                    backup.reason,
+                    BackupReason::Scheduled | BackupReason::Manual
+                )
+            })
+            .map(|backup| backup.created_at);
+        if !is_due(latest, Utc::now(), config.interval_hours) {
+            return Ok(None);
+        }
+
+        let created = backup::create(db, BackupReason::Scheduled).await?;
+        Self::rotate(config.keep);
+        Ok(Some(created))
+    }
+
+    /// Take a backup right away
+    pub async fn back_up_now(db: &DatabaseConnection) -> Result<Backup> {
+        let created = backup::create(db, BackupReason::Manual).await?;
+        Self::rotate(config_service().get_config().await.backups.keep);
+        Ok(created)
+    }
+
+    /// Backups on disk, newest first
+    pub fn list() -> Result<Vec<Backup>> {
+        backup::list()
+    }
+
+    /// Replace the database and config with `backup` at the next start
+    pub async fn restore(db: &DatabaseConnection, backup: &Backup) -> Result<()> {
+        backup::stage_restore(db, backup).await
+    }
+
+    fn rotate(keep: u32) {
+        match backup::rotate(keep as usize) {
+            Ok(0) => {}
+            Ok(removed) => info!("Deleted {} old backups", removed),
+            Err(e) => warn!("Failed to delete old backups: {:#}", e),
+        }
+    }
+}
+
+/// Whether a backup is due given when the last one was taken
+fn is_due(latest: Option<DateTime<Utc>>, now: DateTime<Utc>, interval_hours: u32) -> bool {
+    latest.is_none_or(|latest| {
+        now.signed_duration_since(latest) >= chrono::Duration::hours(interval_hours.into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_is_due_once_the_interval_passed() {
+        let now = Utc::now();
+        assert!(is_due(None, now, 24));
+        assert!(!is_due(Some(now - chrono::Duration::hours(23)), now, 24));
+        assert!(is_due(Some(now - chrono::Duration::hours(24)), now, 24));
+    }
+}
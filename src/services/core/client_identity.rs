@@ -0,0 +1,85 @@
+//! Client identity reported to media servers
+//!
+//! Plex and Jellyfin list sessions by the device name and identifier a client
+//! reports, and Plex applies per-device server settings by identifier. Each
+//! source can override what Reel reports; backends look the identity up by
+//! source ID whenever they build request headers, so a change applies to the
+//! next request.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+use crate::db::connection::DatabaseConnection;
+use crate::models::{ClientIdentity, SourceId};
+
+/// Identities of the sources that have a backend, by source ID
+static IDENTITIES: LazyLock<RwLock<HashMap<String, ClientIdentity>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// The identity to report to a source's server
+pub fn identity_for_source(source_id: &str) -> ClientIdentity {
+    IDENTITIES
+        .read()
+        .ok()
+        .and_then(|identities| identities.get(source_id).cloned())
+        .unwrap_or_default()
+}
+
+/// Record the identity to report to a source's server
+pub fn set_identity_for_source(source_id: &str, identity: ClientIdentity) {
+    if let Ok(mut identities) = IDENTITIES.write() {
+        identities.insert(source_id.to_string(), identity);
+    }
+}
+
+pub struct ClientIdentityService;
+
+impl ClientIdentityService {
+    /// Store a source's identity and report it from the next request on
+    pub async fn set_for_source(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        identity: ClientIdentity,
+    ) -> Result<ClientIdentity> {
+        use crate::db::repository::source_repository::{SourceRepository, SourceRepositoryImpl};
+
+        let repo = SourceRepositoryImpl::new(db.clone());
+        repo.update_client_identity(source_id.as_ref(), identity.to_json())
+            .await?;
+        set_identity_for_source(source_id.as_ref(), identity.clone());
+
+        Ok(identity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blank_input_leaves_fields_unset() {
+        let identity = ClientIdentity::from_input("  Living Room ", "", " ");
+        assert_eq!(identity.device_name.as_deref(), Some("Living Room"));
+        assert_eq!(identity.client_identifier, None);
+        assert_eq!(identity.platform, None);
+        assert!(ClientIdentity::from_input("", "", "").is_default());
+    }
+
+    #[test]
+    fn test_identity_follows_source() {
+        let identity = ClientIdentity::from_input("Bedroom", "", "");
+        set_identity_for_source("test-identity-source", identity.clone());
+        assert_eq!(identity_for_source("test-identity-source"), identity);
+        assert!(identity_for_source("test-unknown-source").is_default());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let identity = ClientIdentity::from_input("Desk", "reel-desk", "Linux");
+        let stored = identity.to_json();
+        assert_eq!(ClientIdentity::from_json(stored.as_ref()), identity);
+        assert_eq!(ClientIdentity::default().to_json(), None);
+        assert_eq!(ClientIdentity::from_json(None), ClientIdentity::default());
+    }
+}
@@ -1,5 +1,7 @@
 use crate::db::DatabaseConnection;
-use crate::models::{Credentials, ServerConnection, ServerConnections, SourceId};
+use crate::models::{
+    ConnectionPreferences, Credentials, ServerConnection, ServerConnections, SourceId,
+};
 use crate::services::core::auth::AuthService;
 use crate::services::core::connection_cache::{ConnectionCache, ConnectionState, ConnectionType};
 use anyhow::Result;
@@ -20,6 +22,26 @@ impl ConnectionService {
         CONNECTION_CACHE.clone()
     }
 
+    /// Store which connections a source may use, and pick its connection
+    /// again under the new preferences
+    pub async fn set_preferences(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        preferences: ConnectionPreferences,
+    ) -> Result<ConnectionPreferences> {
+        use crate::db::repository::source_repository::{SourceRepository, SourceRepositoryImpl};
+
+        let repo = SourceRepositoryImpl::new(db.clone());
+        repo.update_connection_preferences(source_id.as_ref(), preferences.to_json())
+            .await?;
+
+        // The saved connection may be one the preferences now rule out
+        Self::cache().invalidate(source_id).await;
+        Self::retest_connections(db, source_id).await?;
+
+        Ok(preferences)
+    }
+
     /// Test all connections for a source and update the best one
     pub async fn select_best_connection(
         db: &DatabaseConnection,
@@ -30,6 +52,13 @@ impl ConnectionService {
         if cache.should_skip_test(source_id).await
             && let Some(state) = cache.get(source_id).await
         {
+            if state.failure_count > 0 {
+                debug!(
+                    "Source {} unreachable, backing off until {:?}",
+                    source_id, state.next_check
+                );
+                return Ok(None);
+            }
             debug!(
                 "Using cached connection for {}: {} (age: {:?})",
                 source_id,
@@ -38,6 +67,17 @@ impl ConnectionService {
             );
             return Ok(Some(state.url));
         }
+
+        Self::retest_connections(db, source_id).await
+    }
+
+    /// Test all connections for a source again, whatever the cache holds,
+    /// and update the best one
+    pub async fn retest_connections(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+    ) -> Result<Option<String>> {
+        let cache = Self::cache();
         use crate::db::repository::Repository;
         use crate::db::repository::source_repository::{SourceRepository, SourceRepositoryImpl};
 
@@ -48,28 +88,13 @@ impl ConnectionService {
 
         // Get auth token for the source (needed for authenticated connection testing)
         let auth_token = {
-            use crate::db::repository::auth_token_repository::{
-                AuthTokenRepository, AuthTokenRepositoryImpl,
-            };
-
-            let repo = AuthTokenRepositoryImpl::new(db.clone());
-
             // Legacy support: some tokens may still be stored under "auth"
-            if let Some(token) = repo
-                .find_by_source_and_type(source_id.as_ref(), "auth")
-                .await?
-            {
-                Some(token.token)
-            } else if let Some(token) = repo
-                .find_by_source_and_type(source_id.as_ref(), "token")
-                .await?
-            {
-                Some(token.token)
-            } else if let Some(token) = repo
-                .find_by_source_and_type(source_id.as_ref(), "access")
-                .await?
-            {
-                Some(token.token)
+            if let Some(token) = AuthService::load_secret(db, source_id, "auth").await? {
+                Some(token)
+            } else if let Some(token) = AuthService::load_secret(db, source_id, "token").await? {
+                Some(token)
+            } else if let Some(token) = AuthService::load_secret(db, source_id, "access").await? {
+                Some(token)
             } else {
                 match AuthService::load_credentials(db, source_id).await? {
                     Some(Credentials::Token { token }) => Some(token),
@@ -102,17 +127,23 @@ impl ConnectionService {
         }
 
         // Get stored connections from JSON column
-        if let Some(ref connections_json) = source.connections {
-            let connections: ServerConnections = serde_json::from_value(connections_json.clone())?;
+        let connections = match source.connections {
+            Some(ref connections_json) => {
+                serde_json::from_value::<ServerConnections>(connections_json.clone())?.connections
+            }
+            None => Vec::new(),
+        };
 
+        if !connections.is_empty() {
             // Test all connections in parallel
             let tested_connections =
-                Self::test_connections(db, &source, connections.connections, auth_token.as_deref())
-                    .await;
+                Self::test_connections(db, &source, connections, auth_token.as_deref()).await;
 
-            // Find the best available connection
+            // Find the best available connection the source's preferences allow
+            let preferences =
+                ConnectionPreferences::from_json(source.connection_preferences.as_ref());
             let server_connections = ServerConnections::new(tested_connections);
-            let best = server_connections.best_connection();
+            let best = server_connections.best_connection(&preferences);
 
             if let Some(best_conn) = best {
                 info!(
@@ -159,12 +190,57 @@ impl ConnectionService {
 
                 return Ok(Some(best_conn.uri.clone()));
             }
+
+            // No connection answered - record the failure so callers can detect
+            // the transition to unreachable, and back off before retesting
+            use crate::db::entities::sources::ActiveModel;
+            use sea_orm::{ActiveModelTrait, Set};
+
+            let active_model = ActiveModel {
+                id: Set(source_id.to_string()),
+                last_connection_test: Set(Some(chrono::Utc::now().naive_utc())),
+                connection_failure_count: Set(source.connection_failure_count + 1),
+                ..Default::default()
+            };
+            active_model.update(db.as_ref()).await?;
+            cache.update_failure(source_id).await;
+
+            // The server may be asleep
+            Self::auto_wake(&source).await;
+
+            return Ok(None);
         }
 
-        // Fall back to the existing connection_url if no connections stored
+        // Fall back to the existing connection_url if no connections were
+        // discovered for the source
         Ok(source.connection_url)
     }
 
+    /// Send a Wake-on-LAN packet to an unreachable source if the user enabled it
+    async fn auto_wake(source: &crate::db::entities::sources::Model) {
+        use crate::services::config_service::config_service;
+        use crate::services::core::wake_on_lan::WakeOnLanService;
+
+        let Some(ref mac) = source.mac_address else {
+            return;
+        };
+
+        if !config_service().get_config().await.network.auto_wake_on_lan {
+            return;
+        }
+
+        info!(
+            "No connections available for {}, sending Wake-on-LAN packet",
+            source.name
+        );
+        if let Err(e) = WakeOnLanService::send_magic_packet(mac).await {
+            warn!(
+                "Failed to send Wake-on-LAN packet to {}: {}",
+                source.name, e
+            );
+        }
+    }
+
     /// Test multiple connections in parallel using backend-specific logic
     async fn test_connections(
         db: &DatabaseConnection,
@@ -147,6 +147,18 @@ impl ConnectionCache {
         }
     }
 
+    /// Drop the cached state so the next check retests all connections
+    pub async fn invalidate(&self, source_id: &SourceId) {
+        let mut cache = self.states.write().await;
+        cache.pop(source_id);
+    }
+
+    /// Drop all cached states, e.g. after the network changed
+    pub async fn clear(&self) {
+        let mut cache = self.states.write().await;
+        cache.clear();
+    }
+
     pub async fn should_skip_test(&self, source_id: &SourceId) -> bool {
         if let Some(state) = self.get(source_id).await {
             // Skip test if:
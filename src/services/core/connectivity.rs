@@ -0,0 +1,149 @@
+use crate::models::SourceId;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::info;
+
+/// Global offline flag - readable from any thread without locking
+static OFFLINE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Transition of the application-wide connectivity mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectivityChange {
+    /// Every known source became unreachable
+    WentOffline,
+    /// At least one source is reachable again after being offline
+    BackOnline,
+}
+
+/// Tracks per-source reachability and derives the application-wide offline mode.
+///
+/// The app is considered offline once every source that reported a status is
+/// unreachable. While offline, the UI serves everything from the database and
+/// caches; coming back online triggers reconciliation (sync + queued action replay).
+#[derive(Debug, Default)]
+pub struct ConnectivityTracker {
+    online: HashSet<SourceId>,
+    offline: HashSet<SourceId>,
+}
+
+impl ConnectivityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the reachability of a source, returning a mode change if one happened
+    pub fn set_source_online(
+        &mut self,
+        source_id: &SourceId,
+        is_online: bool,
+    ) -> Option<ConnectivityChange> {
+        let was_offline = self.is_offline();
+
+        if is_online {
+            self.offline.remove(source_id);
+            self.online.insert(source_id.clone());
+        } else {
+            self.online.remove(source_id);
+            self.offline.insert(source_id.clone());
+        }
+
+        let change = match (was_offline, self.is_offline()) {
+            (false, true) => Some(ConnectivityChange::WentOffline),
+            (true, false) => Some(ConnectivityChange::BackOnline),
+            _ => None,
+        };
+
+        if let Some(change) = change {
+            OFFLINE_MODE.store(change == ConnectivityChange::WentOffline, Ordering::Relaxed);
+            info!("Connectivity mode changed: {:?}", change);
+        }
+
+        change
+    }
+
+    /// Start tracking a source, assuming it is reachable until told otherwise
+    pub fn register_source(&mut self, source_id: &SourceId) -> Option<ConnectivityChange> {
+        if self.online.contains(source_id) || self.offline.contains(source_id) {
+            return None;
+        }
+        self.set_source_online(source_id, true)
+    }
+
+    /// Whether a source has been reported unreachable
+    pub fn is_source_offline(&self, source_id: &SourceId) -> bool {
+        self.offline.contains(source_id)
+    }
+
+    /// Whether every known source is currently unreachable
+    pub fn is_offline(&self) -> bool {
+        self.online.is_empty() && !self.offline.is_empty()
+    }
+
+    /// Sources currently known to be reachable
+    pub fn online_sources(&self) -> impl Iterator<Item = &SourceId> {
+        self.online.iter()
+    }
+
+    /// Sources currently known to be unreachable
+    pub fn offline_sources(&self) -> impl Iterator<Item = &SourceId> {
+        self.offline.iter()
+    }
+}
+
+/// Whether the application is currently in offline mode
+pub fn is_offline() -> bool {
+    OFFLINE_MODE.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_goes_offline_when_all_sources_unreachable() {
+        let mut tracker = ConnectivityTracker::new();
+        let a = SourceId::new("a".to_string());
+        let b = SourceId::new("b".to_string());
+
+        assert_eq!(tracker.set_source_online(&a, true), None);
+        assert_eq!(tracker.set_source_online(&b, true), None);
+        assert_eq!(tracker.set_source_online(&a, false), None);
+        assert_eq!(
+            tracker.set_source_online(&b, false),
+            Some(ConnectivityChange::WentOffline)
+        );
+        assert!(tracker.is_offline());
+    }
+
+    #[test]
+    fn test_back_online_when_any_source_returns() {
+        let mut tracker = ConnectivityTracker::new();
+        let a = SourceId::new("a".to_string());
+        let b = SourceId::new("b".to_string());
+
+        tracker.set_source_online(&a, false);
+        tracker.set_source_online(&b, false);
+        assert!(tracker.is_offline());
+
+        assert_eq!(
+            tracker.set_source_online(&b, true),
+            Some(ConnectivityChange::BackOnline)
+        );
+        assert!(!tracker.is_offline());
+        assert_eq!(tracker.online_sources().count(), 1);
+    }
+
+    #[test]
+    fn test_register_source_does_not_override_known_state() {
+        let mut tracker = ConnectivityTracker::new();
+        let a = SourceId::new("a".to_string());
+
+        assert_eq!(
+            tracker.set_source_online(&a, false),
+            Some(ConnectivityChange::WentOffline)
+        );
+        assert_eq!(tracker.register_source(&a), None);
+        assert!(tracker.is_source_offline(&a));
+        assert!(tracker.is_offline());
+    }
+}
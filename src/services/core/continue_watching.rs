@@ -0,0 +1,381 @@
+//! Continue Watching across sources
+//!
+//! A movie or show on two servers would get a Continue Watching entry from
+//! each. Entries are matched by the outside database ids their servers
+//! report, an episode by those of its show, and only the one furthest along
+//! is kept: the later episode first, then the further playback position.
+//!
+//! The Watch Next row on the home page brings the Continue Watching and On
+//! Deck rows of every source together with what was played here, so an item
+//! started in the player shows up before the next sync picks it up.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use tracing::debug;
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::entities::{MediaItemModel, PlaybackProgressModel};
+use crate::db::repository::{
+    HomeSectionRepository, HomeSectionRepositoryImpl, MediaRepositoryImpl, PlaybackRepository,
+    PlaybackRepositoryImpl, Repository, SourceRepositoryImpl,
+};
+use crate::models::{HomeSectionType, HomeSectionWithModels, SourceId};
+use crate::services::core::{HiddenItemsService, MediaService};
+
+/// Pure functions for merging Continue Watching rows
+pub struct ContinueWatchingService;
+
+impl ContinueWatchingService {
+    /// Drop the Continue Watching entries of each source that another
+    /// source is further along on, and the rows left empty
+    pub async fn merge_sources(
+        db: &DatabaseConnection,
+        sources: &mut [(SourceId, Vec<HomeSectionWithModels>)],
+    ) -> Result<()> {
+        let rows: Vec<(usize, &MediaItemModel)> = sources
+            .iter()
+            .enumerate()
+            .flat_map(|(source, (_, sections))| {
+                sections
+                    .iter()
+                    .filter(|section| is_continue_watching(section))
+                    .flat_map(move |section| section.items.iter().map(move |item| (source, item)))
+            })
+            .collect();
+        let sources_with_rows: HashSet<usize> = rows.iter().map(|(source, _)| *source).collect();
+        if sources_with_rows.len() < 2 {
+            return Ok(());
+        }
+
+        // Episodes are matched by the ids of their show
+        let parent_ids: Vec<String> = rows
+            .iter()
+            .filter_map(|(_, item)| item.parent_id.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let show_ids: HashMap<String, Vec<String>> = MediaRepositoryImpl::new(db.clone())
+            .find_by_ids(&parent_ids)
+            .await?
+            .into_iter()
+            .map(|show| {
+                let ids = show.get_external_ids();
+                (show.id, ids)
+            })
+            .collect();
+
+        let media_ids: Vec<String> = rows.iter().map(|(_, item)| item.id.clone()).collect();
+        let progress = MediaService::get_playback_progress_batch(db, &media_ids).await?;
+
+        let entries: Vec<Entry> = rows
+            .iter()
+            .map(|(source, item)| Entry {
+                source: *source,
+                item_id: item.id.clone(),
+                external_ids: match &item.parent_id {
+                    Some(parent_id) => show_ids.get(parent_id).cloned().unwrap_or_default(),
+                    None => item.get_external_ids(),
+                },
+                progress: (
+                    item.season_number.unwrap_or(0),
+                    item.episode_number.unwrap_or(0),
+                    progress
+                        .get(&item.id)
+                        .map(|progress| progress.get_progress_percentage())
+                        .unwrap_or(0.0),
+                ),
+            })
+            .collect();
+
+        let superseded = superseded(&entries);
+        if superseded.is_empty() {
+            return Ok(());
+        }
+        debug!(
+            "Merged {} Continue Watching entries shown by another source",
+            superseded.len()
+        );
+
+        for (_, sections) in sources.iter_mut() {
+            for section in sections.iter_mut().filter(|s| is_continue_watching(s)) {
+                section.items.retain(|item| !superseded.contains(&item.id));
+            }
+            sections.retain(|section| !section.items.is_empty());
+        }
+        Ok(())
+    }
+
+    /// The items of the Watch Next row, at most `limit` of them
+    pub async fn watch_next(db: &DatabaseConnection, limit: usize) -> Result<Vec<MediaItemModel>> {
+        let section_repo = HomeSectionRepositoryImpl::new(db.clone());
+        let mut sources = Vec::new();
+        for source in SourceRepositoryImpl::new(db.clone()).find_all().await? {
+            let sections: Vec<HomeSectionWithModels> = section_repo
+                .find_by_source_with_items(&source.id)
+                .await?
+                .into_iter()
+                .filter_map(|(section, items)| {
+                    let section_type = match section.section_type.as_str() {
+                        "continue_watching" => HomeSectionType::ContinueWatching,
+                        "on_deck" => HomeSectionType::OnDeck,
+                        _ => return None,
+                    };
+                    Some(HomeSectionWithModels {
+                        id: section.hub_identifier,
+                        title: section.title,
+                        section_type,
+                        items,
+                    })
+                })
+                .collect();
+            sources.push((SourceId::new(source.id), sections));
+        }
+
+        // Entries merged away stay out, even if they were played here
+        let shown_by_servers: HashSet<String> = sources
+            .iter()
+            .flat_map(|(_, sections)| sections.iter().flat_map(|s| &s.items))
+            .map(|item| item.id.clone())
+            .collect();
+        Self::merge_sources(db, &mut sources).await?;
+
+        let mut items: HashMap<String, MediaItemModel> = HashMap::new();
+        let mut server_ids = Vec::new();
+        for item in sources
+            .into_iter()
+            .flat_map(|(_, sections)| sections)
+            .flat_map(|section| section.items)
+        {
+            server_ids.push(item.id.clone());
+            items.entry(item.id.clone()).or_insert(item);
+        }
+
+        let mut played: HashMap<String, Played> = PlaybackRepositoryImpl::new(db.clone())
+            .find_in_progress()
+            .await?
+            .iter()
+            .filter(|progress| !shown_by_servers.contains(&progress.media_id))
+            .map(|progress| (progress.media_id.clone(), Played::from(progress)))
+            .collect();
+        for (media_id, progress) in
+            MediaService::get_playback_progress_batch(db, &server_ids).await?
+        {
+            played.insert(media_id, Played::from(&progress));
+        }
+
+        // Items only played here, which includes music and audiobooks
+        let ordered = order(&server_ids, &played);
+        let played_only: Vec<String> = ordered
+            .iter()
+            .filter(|id| !items.contains_key(*id))
+            .cloned()
+            .collect();
+        for item in MediaRepositoryImpl::new(db.clone())
+            .find_by_ids(&played_only)
+            .await?
+        {
+            if matches!(item.media_type.as_str(), "movie" | "episode") {
+                items.insert(item.id.clone(), item);
+            }
+        }
+
+        let hidden_ids = HiddenItemsService::hidden_ids(db).await?;
+        Ok(ordered
+            .iter()
+            .filter_map(|id| items.remove(id))
+            .filter(|item| !HiddenItemsService::is_hidden(item, &hidden_ids))
+            .take(limit)
+            .collect())
+    }
+}
+
+fn is_continue_watching(section: &HomeSectionWithModels) -> bool {
+    matches!(
+        section.section_type,
+        HomeSectionType::ContinueWatching | HomeSectionType::OnDeck
+    )
+}
+
+/// Progress saved here for an item
+#[derive(Debug, Clone, Copy)]
+struct Played {
+    watched: bool,
+    at: NaiveDateTime,
+}
+
+impl From<&PlaybackProgressModel> for Played {
+    fn from(progress: &PlaybackProgressModel) -> Self {
+        Self {
+            watched: progress.watched,
+            at: progress.last_watched_at.unwrap_or(progress.updated_at),
+        }
+    }
+}
+
+/// Ids of the Watch Next row, each once: the items played here, the most
+/// recent first, then the rest in the order of the server rows. Items
+/// watched to the end here are left out before the servers catch up.
+fn order(server_ids: &[String], played: &HashMap<String, Played>) -> Vec<String> {
+    let mut played_here: Vec<&String> = played
+        .keys()
+        .filter(|id| !server_ids.contains(id))
+        .collect();
+    played_here.sort();
+
+    let mut seen = HashSet::new();
+    let mut ids: Vec<String> = server_ids
+        .iter()
+        .chain(played_here)
+        .filter(|id| seen.insert(*id))
+        .filter(|id| !played.get(*id).is_some_and(|played| played.watched))
+        .cloned()
+        .collect();
+    ids.sort_by_key(|id| Reverse(played.get(id).map(|played| played.at)));
+    ids
+}
+
+struct Entry {
+    source: usize,
+    item_id: String,
+    external_ids: Vec<String>,
+    /// Season, episode and fraction watched
+    progress: (i32, i32, f32),
+}
+
+impl Entry {
+    fn same_title_as(&self, other: &Entry) -> bool {
+        self.source != other.source
+            && self
+                .external_ids
+                .iter()
+                .any(|id| other.external_ids.contains(id))
+    }
+}
+
+/// Items of entries another source is further along on. Of entries just as
+/// far along, the one from the earlier source is kept.
+fn superseded(entries: &[Entry]) -> HashSet<String> {
+    entries
+        .iter()
+        .filter(|entry| {
+            entries.iter().any(|other| {
+                other.same_title_as(entry)
+                    && (other.progress > entry.progress
+                        || (other.progress == entry.progress && other.source < entry.source))
+            })
+        })
+        .map(|entry| entry.item_id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn played(watched: bool, minute: u32) -> Played {
+        Played {
+            watched,
+            at: NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(20, minute, 0)
+                .unwrap(),
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    fn entry(source: usize, item_id: &str, ids: &[&str], progress: (i32, i32, f32)) -> Entry {
+        Entry {
+            source,
+            item_id: item_id.to_string(),
+            external_ids: ids.iter().map(|id| id.to_string()).collect(),
+            progress,
+        }
+    }
+
+    #[test]
+    fn test_later_episode_wins_over_further_position() {
+        let entries = vec![
+            entry(0, "plex:s2e3", &["tvdb://81189"], (2, 3, 0.1)),
+            entry(
+                1,
+                "jellyfin:s2e1",
+                &["tvdb://81189", "imdb://tt0903747"],
+                (2, 1, 0.9),
+            ),
+        ];
+        assert_eq!(
+            superseded(&entries),
+            HashSet::from(["jellyfin:s2e1".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_further_position_wins_for_movies() {
+        let entries = vec![
+            entry(0, "plex:movie", &["imdb://tt0111161"], (0, 0, 0.2)),
+            entry(1, "jellyfin:movie", &["imdb://tt0111161"], (0, 0, 0.6)),
+        ];
+        assert_eq!(
+            superseded(&entries),
+            HashSet::from(["plex:movie".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_unmatched_and_same_source_entries_are_kept() {
+        let entries = vec![
+            entry(0, "plex:a", &["imdb://tt1"], (0, 0, 0.2)),
+            entry(0, "plex:b", &["imdb://tt1"], (0, 0, 0.5)),
+            entry(1, "jellyfin:c", &["imdb://tt2"], (0, 0, 0.9)),
+            entry(1, "jellyfin:d", &[], (0, 0, 0.9)),
+            entry(2, "emby:e", &[], (0, 0, 0.1)),
+        ];
+        assert!(superseded(&entries).is_empty());
+    }
+
+    #[test]
+    fn test_ties_keep_the_first_source() {
+        let entries = vec![
+            entry(0, "plex:movie", &["tmdb://278"], (0, 0, 0.5)),
+            entry(1, "jellyfin:movie", &["tmdb://278"], (0, 0, 0.5)),
+        ];
+        assert_eq!(
+            superseded(&entries),
+            HashSet::from(["jellyfin:movie".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_watch_next_lists_each_item_once() {
+        let server_ids = ids(&["a", "b", "a"]);
+        let played = HashMap::from([("b".to_string(), played(false, 5))]);
+        assert_eq!(order(&server_ids, &played), ids(&["b", "a"]));
+    }
+
+    #[test]
+    fn test_watch_next_puts_the_latest_played_first() {
+        let server_ids = ids(&["a", "b", "c", "d"]);
+        let played = HashMap::from([
+            ("c".to_string(), played(false, 10)),
+            ("local".to_string(), played(false, 30)),
+        ]);
+        assert_eq!(
+            order(&server_ids, &played),
+            ids(&["local", "c", "a", "b", "d"])
+        );
+    }
+
+    #[test]
+    fn test_watch_next_leaves_out_items_finished_here() {
+        let server_ids = ids(&["a", "b"]);
+        let played = HashMap::from([("a".to_string(), played(true, 50))]);
+        assert_eq!(order(&server_ids, &played), ids(&["b"]));
+    }
+}
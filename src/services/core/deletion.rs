@@ -0,0 +1,142 @@
+//! Deleting items from their server
+//!
+//! Deletion is only offered where the server allows it: on Plex servers the
+//! user owns when media deletion is enabled, on Jellyfin when the user may
+//! delete the item. The local copy, downloads included, is removed once the server confirmed the deletion.
+//! Nothing can be deleted while the kids profile is on.
+
+use anyhow::{Context, Result, anyhow};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{
+    MediaRepository, MediaRepositoryImpl, OfflineContentRepository, OfflineContentRepositoryImpl,
+    Repository,
+};
+use crate::models::MediaItemId;
+use crate::services::config_service::config_service;
+
+use super::backend::BackendService;
+use super::downloads::DownloadService;
+
+/// Pure functions for deleting from servers
+pub struct DeletionService;
+
+impl DeletionService {
+    /// Whether the user may delete the item from its server
+    pub async fn can_delete(db: &DatabaseConnection, media_id: &MediaItemId) -> Result<bool> {
+        if config_service().get_config().await.kids.enabled {
+            return Ok(false);
+        }
+
+        let item = MediaRepositoryImpl::new(db.clone())
+            .find_by_id(media_id.as_ref())
+            .await?
+            .ok_or_else(|| anyhow!("Media item not found: {}", media_id))?;
+
+        BackendService::can_delete(db, &item.source_id, media_id).await
+    }
+
+    /// Delete an item, or one version of a movie, from its server and then
+    /// locally. Returns whether the item itself is gone, which it also is
+    /// after deleting the only version of a movie.
+    pub async fn delete_from_server(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+        version_id: Option<&str>,
+    ) -> Result<bool> {
+        if config_service().get_config().await.kids.enabled {
+            return Err(anyhow!(
+                "Deleting isn't allowed while the kids profile is on"
+            ));
+        }
+
+        let repo = MediaRepositoryImpl::new(db.clone());
+        let mut item = repo
+            .find_by_id(media_id.as_ref())
+            .await?
+            .ok_or_else(|| anyhow!("Media item not found: {}", media_id))?;
+
+        let version_id = version_id.filter(|_| item.get_versions().len() > 1);
+        BackendService::delete_item(db, &item.source_id, media_id, version_id)
+            .await
+            .context("Failed to delete from the server")?;
+
+        if let Some(version_id) = version_id {
+            info!(
+                "Deleted version {} of {} from the server",
+                version_id, media_id
+            );
+            if let Some(metadata) = item.metadata.as_mut() {
+                remove_version(metadata, version_id);
+            }
+            repo.update(item).await?;
+            return Ok(false);
+        }
+
+        info!("Deleted {} from the server", media_id);
+        let mut ids: Vec<String> = repo
+            .find_episodes_by_show(&item.id)
+            .await?
+            .into_iter()
+            .map(|episode| episode.id)
+            .collect();
+        ids.push(item.id);
+
+        // The download records would go with the items, leaving their files behind
+        let downloads = OfflineContentRepositoryImpl::new(db.clone())
+            .find_by_media_ids(&ids)
+            .await?;
+        for download in &downloads {
+            if let Err(e) = DownloadService::remove_download(db, download).await {
+                warn!(
+                    "Failed to remove the download of {}: {}",
+                    download.media_id, e
+                );
+            }
+        }
+
+        repo.delete_by_ids(ids).await?;
+        Ok(true)
+    }
+}
+
+/// Drop a version from the stored versions of a movie
+fn remove_version(metadata: &mut Value, version_id: &str) {
+    if let Some(versions) = metadata.get_mut("versions").and_then(Value::as_array_mut) {
+        versions.retain(|version| version.get("id").and_then(Value::as_str) != Some(version_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_remove_version_keeps_the_others() {
+        let mut metadata = json!({
+            "video_height": 2160,
+            "versions": [
+                {"id": "1", "video_height": 2160},
+                {"id": "2", "video_height": 1080},
+            ],
+        });
+        remove_version(&mut metadata, "1");
+        assert_eq!(
+            metadata,
+            json!({
+                "video_height": 2160,
+                "versions": [{"id": "2", "video_height": 1080}],
+            })
+        );
+    }
+
+    #[test]
+    fn test_remove_unknown_version_changes_nothing() {
+        let mut metadata = json!({"versions": [{"id": "1"}]});
+        remove_version(&mut metadata, "2");
+        assert_eq!(metadata, json!({"versions": [{"id": "1"}]}));
+    }
+}
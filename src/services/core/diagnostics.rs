@@ -0,0 +1,311 @@
+//! Debug bundles attached to bug reports
+//!
+//! A bundle is a zip with the recent log, the configuration and a summary of
+//! the system, player and connected servers. Access tokens and the user's home
+//! directory are redacted before anything is written.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use sysinfo::{MemoryRefreshKind, RefreshKind, System};
+use tracing::{Level, info};
+use zip::write::SimpleFileOptions;
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{Repository, SourceRepositoryImpl};
+use crate::logging::LOG_BUFFER;
+use crate::services::config_service::config_service;
+
+use super::{connectivity, hw_decode, metered, power_saver};
+
+/// Query parameters and headers whose values are credentials
+const SECRET_KEYS: &[&str] = &[
+    "x-plex-token",
+    "x-emby-token",
+    "x-mediabrowser-token",
+    "api_key",
+    "apikey",
+    "access_token",
+    "accesstoken",
+    "authtoken",
+    "authenticationtoken",
+    "token",
+    "password",
+];
+
+/// Suggested file name for a bundle created now
+pub fn bundle_file_name() -> String {
+    format!(
+        "reel-debug-{}.zip",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// Write a debug bundle to `path`
+pub async fn export_debug_bundle(db: &DatabaseConnection, path: PathBuf) -> Result<()> {
+    let config = config_service().get_config().await;
+    let config_toml =
+        toml::to_string_pretty(&config).context("Failed to serialize configuration")?;
+
+    let mut system = system_info();
+    let _ = writeln!(system, "\n[Player]");
+    let _ = writeln!(system, "Backend: {}", config.playback.player_backend);
+    let _ = writeln!(
+        system,
+        "Hardware acceleration: {}",
+        config.playback.hardware_acceleration
+    );
+    let _ = writeln!(system, "MPV support: {}", cfg!(feature = "mpv"));
+    let _ = writeln!(system, "GStreamer support: {}", cfg!(feature = "gstreamer"));
+    #[cfg(feature = "gstreamer")]
+    let _ = writeln!(system, "GStreamer: {}", gstreamer::version_string());
+    match hw_decode::hardware_decode_support() {
+        Some(support) => {
+            for line in support.summary() {
+                let _ = writeln!(system, "Hardware decoding {}", line);
+            }
+        }
+        None => {
+            let _ = writeln!(system, "Hardware decoding: not probed");
+        }
+    }
+    let _ = writeln!(
+        system,
+        "Power saving: {} (enabled: {})",
+        power_saver::is_active(),
+        config.playback.power_saving_playback
+    );
+    system.push_str(&sources_summary(db).await?);
+
+    let log = LOG_BUFFER
+        .snapshot(Level::TRACE)
+        .iter()
+        .map(|entry| entry.format_line())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let files = [
+        ("reel.log", redact(&log)),
+        ("config.toml", redact(&config_toml)),
+        ("system.txt", redact(&system)),
+    ];
+
+    let target = path.clone();
+    tokio::task::spawn_blocking(move || write_zip(&target, &files))
+        .await
+        .context("Debug bundle task failed")??;
+
+    info!("Debug bundle written to {:?}", path);
+    Ok(())
+}
+
+fn write_zip(path: &Path, files: &[(&str, String)]) -> Result<()> {
+    let file = File::create(path).context("Failed to create debug bundle")?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, contents) in files {
+        zip.start_file(*name, options)
+            .with_context(|| format!("Failed to add {} to debug bundle", name))?;
+        zip.write_all(contents.as_bytes())
+            .with_context(|| format!("Failed to write {} to debug bundle", name))?;
+    }
+
+    zip.finish().context("Failed to finish debug bundle")?;
+    Ok(())
+}
+
+fn system_info() -> String {
+    let system = System::new_with_specifics(
+        RefreshKind::nothing().with_memory(MemoryRefreshKind::nothing().with_ram()),
+    );
+
+    let mut info = String::new();
+    let _ = writeln!(info, "[Reel]");
+    let _ = writeln!(info, "Version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(
+        info,
+        "Generated: {}",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S %z")
+    );
+
+    let _ = writeln!(info, "\n[System]");
+    let _ = writeln!(
+        info,
+        "OS: {}",
+        System::long_os_version().unwrap_or_else(|| std::env::consts::OS.to_string())
+    );
+    let _ = writeln!(
+        info,
+        "Kernel: {}",
+        System::kernel_version().unwrap_or_else(|| "unknown".to_string())
+    );
+    let _ = writeln!(info, "Architecture: {}", std::env::consts::ARCH);
+    let _ = writeln!(
+        info,
+        "CPUs: {}",
+        std::thread::available_parallelism().map_or(0, |n| n.get())
+    );
+    let _ = writeln!(
+        info,
+        "Memory: {}",
+        gtk4::glib::format_size(system.total_memory())
+    );
+    let _ = writeln!(
+        info,
+        "GTK: {}.{}.{}",
+        gtk4::major_version(),
+        gtk4::minor_version(),
+        gtk4::micro_version()
+    );
+    let _ = writeln!(
+        info,
+        "libadwaita: {}.{}.{}",
+        libadwaita::major_version(),
+        libadwaita::minor_version(),
+        libadwaita::micro_version()
+    );
+
+    let _ = writeln!(info, "\n[Network]");
+    let _ = writeln!(info, "Offline: {}", connectivity::is_offline());
+    let _ = writeln!(info, "Metered: {}", metered::is_metered());
+
+    info
+}
+
+/// Connection state of each server, without names or addresses
+async fn sources_summary(db: &DatabaseConnection) -> Result<String> {
+    let sources = SourceRepositoryImpl::new(db.clone())
+        .find_all()
+        .await
+        .context("Failed to load sources")?;
+
+    let mut by_type: BTreeMap<&str, usize> = BTreeMap::new();
+    for source in &sources {
+        *by_type.entry(source.source_type.as_str()).or_default() += 1;
+    }
+
+    let mut summary = String::new();
+    let _ = writeln!(summary, "\n[Sources]");
+    for (source_type, count) in &by_type {
+        let _ = writeln!(summary, "{}: {}", source_type, count);
+    }
+    for (index, source) in sources.iter().enumerate() {
+        let _ = writeln!(
+            summary,
+            "#{} {}: online={} auth={} connection={} failures={}",
+            index + 1,
+            source.source_type,
+            source.is_online,
+            source.auth_status,
+            source.connection_quality.as_deref().unwrap_or("unknown"),
+            source.connection_failure_count
+        );
+    }
+
+    Ok(summary)
+}
+
+/// Mask credentials and the home directory in text headed for a bundle
+pub fn redact(text: &str) -> String {
+    let mut redacted = redact_secrets(text);
+    if let Some(home) = dirs::home_dir()
+        && let Some(home) = home.to_str()
+        && !home.is_empty()
+        && home != "/"
+    {
+        redacted = redacted.replace(home, "~");
+    }
+    redacted
+}
+
+/// Replace the value following any `key=` or `key: ` in [`SECRET_KEYS`]
+fn redact_secrets(text: &str) -> String {
+    let lower = text.to_ascii_lowercase();
+    let mut result = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut search = 0;
+
+    while search < text.len() {
+        let Some((start, key)) = SECRET_KEYS
+            .iter()
+            .filter_map(|key| lower[search..].find(key).map(|pos| (search + pos, *key)))
+            .min_by_key(|(pos, key)| (*pos, std::cmp::Reverse(key.len())))
+        else {
+            break;
+        };
+
+        let key_end = start + key.len();
+        let preceded_by_word = text[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        let separator_len = ["=", ": ", ":", "\":\"", "\": \""]
+            .iter()
+            .find(|sep| text[key_end..].starts_with(**sep))
+            .map(|sep| sep.len());
+
+        match separator_len {
+            Some(len) if !preceded_by_word => {
+                let value_start = key_end + len;
+                let value_end = text[value_start..]
+                    .find(|c: char| {
+                        c.is_whitespace() || matches!(c, '&' | '"' | '\'' | ',' | ';' | ')' | '}')
+                    })
+                    .map_or(text.len(), |pos| value_start + pos);
+
+                result.push_str(&text[copied..value_start]);
+                if value_end > value_start {
+                    result.push_str("[REDACTED]");
+                }
+                copied = value_end;
+                search = value_end.max(key_end);
+            }
+            _ => search = key_end,
+        }
+    }
+
+    result.push_str(&text[copied..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_tokens_in_urls() {
+        let line = "GET http://server:32400/library?X-Plex-Token=abc123&type=1";
+        assert_eq!(
+            redact_secrets(line),
+            "GET http://server:32400/library?X-Plex-Token=[REDACTED]&type=1"
+        );
+    }
+
+    #[test]
+    fn test_redacts_header_and_json_values() {
+        assert_eq!(
+            redact_secrets("headers: X-Emby-Token: secret other"),
+            "headers: X-Emby-Token: [REDACTED] other"
+        );
+        assert_eq!(
+            redact_secrets(r#"{"password":"hunter2","user":"me"}"#),
+            r#"{"password":"[REDACTED]","user":"me"}"#
+        );
+    }
+
+    #[test]
+    fn test_ignores_keys_inside_other_words() {
+        let line = "tokenizer=fast mytoken=keep";
+        assert_eq!(redact_secrets(line), line);
+    }
+
+    #[test]
+    fn test_leaves_text_without_secrets_untouched() {
+        let line = "Loaded 42 items from library";
+        assert_eq!(redact_secrets(line), line);
+    }
+}
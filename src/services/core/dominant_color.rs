@@ -0,0 +1,140 @@
+//! Dominant colors of artwork, used to tint detail page headers and the
+//! player's loading screen
+//!
+//! A color is found once per item, from its backdrop or else its poster,
+//! while the image loads, and kept for the rest of the session.
+
+use image::DynamicImage;
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+/// Side of the thumbnail the colors are counted on
+const SAMPLE_SIZE: u32 = 48;
+
+/// Color of artwork whose pixels are all close to black or white
+const NEUTRAL: DominantColor = DominantColor {
+    red: 48,
+    green: 48,
+    blue: 48,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DominantColor {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+/// Colors found so far, by media item ID
+static COLORS: LazyLock<RwLock<HashMap<String, DominantColor>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// The color found earlier for an item's artwork
+pub fn cached(item_id: &str) -> Option<DominantColor> {
+    COLORS
+        .read()
+        .ok()
+        .and_then(|colors| colors.get(item_id).copied())
+}
+
+/// Keep the color of an item's artwork for the rest of the session
+pub fn remember(item_id: &str, color: DominantColor) {
+    if let Ok(mut colors) = COLORS.write() {
+        colors.insert(item_id.to_string(), color);
+    }
+}
+
+/// The dominant color of an encoded image, or None if it can't be decoded
+pub fn from_image_bytes(bytes: &[u8]) -> Option<DominantColor> {
+    let image = image::load_from_memory(bytes).ok()?;
+    Some(dominant_color(&image))
+}
+
+#[derive(Default)]
+struct Bucket {
+    weight: u64,
+    count: u64,
+    sums: [u64; 3],
+}
+
+fn dominant_color(image: &DynamicImage) -> DominantColor {
+    let sample = image.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE).to_rgb8();
+
+    // Pixels are grouped by the top four bits of each channel, and weighted
+    // by saturation so a vivid color wins over the dark and grey areas most
+    // artwork has plenty of
+    let mut buckets: HashMap<u16, Bucket> = HashMap::new();
+    for pixel in sample.pixels() {
+        let [red, green, blue] = pixel.0;
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        if max < 24 || min > 232 {
+            continue;
+        }
+
+        let key = (u16::from(red >> 4) << 8) | (u16::from(green >> 4) << 4) | u16::from(blue >> 4);
+        let bucket = buckets.entry(key).or_default();
+        bucket.weight += 1 + u64::from(max - min);
+        bucket.count += 1;
+        for (sum, channel) in bucket.sums.iter_mut().zip([red, green, blue]) {
+            *sum += u64::from(channel);
+        }
+    }
+
+    buckets
+        .values()
+        .max_by_key(|bucket| bucket.weight)
+        .map(|bucket| {
+            let average = |sum: u64| (sum / bucket.count) as u8;
+            DominantColor {
+                red: average(bucket.sums[0]),
+                green: average(bucket.sums[1]),
+                blue: average(bucket.sums[2]),
+            }
+        })
+        .unwrap_or(NEUTRAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn test_vivid_color_wins_over_grey() {
+        // Two thirds grey, one third red
+        let image = RgbImage::from_fn(90, 90, |x, _| {
+            if x < 60 {
+                Rgb([100, 100, 100])
+            } else {
+                Rgb([200, 30, 40])
+            }
+        });
+        let color = dominant_color(&DynamicImage::ImageRgb8(image));
+        assert!(color.red > 180 && color.green < 50 && color.blue < 60);
+    }
+
+    #[test]
+    fn test_black_and_white_artwork_is_neutral() {
+        let image = RgbImage::from_fn(40, 40, |x, _| {
+            if x % 2 == 0 {
+                Rgb([0, 0, 0])
+            } else {
+                Rgb([255, 255, 255])
+            }
+        });
+        assert_eq!(dominant_color(&DynamicImage::ImageRgb8(image)), NEUTRAL);
+    }
+
+    #[test]
+    fn test_colors_are_cached_per_item() {
+        let color = DominantColor {
+            red: 10,
+            green: 20,
+            blue: 30,
+        };
+        assert_eq!(cached("dominant-color-test"), None);
+        remember("dominant-color-test", color);
+        assert_eq!(cached("dominant-color-test"), Some(color));
+    }
+}
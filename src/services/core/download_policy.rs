@@ -0,0 +1,140 @@
+//! Cleanup policy for offline downloads
+//!
+//! Decides which downloads can go: watched ones once they have been watched for
+//! longer than the configured period, and watched ones ahead of time when the
+//! disk is running low. Unwatched and pinned downloads are always kept.
+
+use chrono::{Duration, NaiveDateTime};
+
+use crate::config::DownloadsConfig;
+
+/// Cleanup settings, derived from the downloads configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CleanupPolicy {
+    /// How long a watched download is kept, None keeps it until space is needed
+    pub delete_watched_after: Option<Duration>,
+    /// Free space to keep on the downloads disk, 0 disables space-based cleanup
+    pub min_free_bytes: u64,
+}
+
+impl From<&DownloadsConfig> for CleanupPolicy {
+    fn from(config: &DownloadsConfig) -> Self {
+        Self {
+            delete_watched_after: (config.delete_watched_after_days > 0)
+                .then(|| Duration::days(config.delete_watched_after_days as i64)),
+            min_free_bytes: config.min_free_space_gb as u64 * 1024 * 1024 * 1024,
+        }
+    }
+}
+
+/// A finished download as seen by the policy
+#[derive(Debug, Clone)]
+pub struct CleanupCandidate {
+    pub id: i32,
+    pub size_bytes: u64,
+    /// When the item was watched, None while it is unwatched
+    pub watched_at: Option<NaiveDateTime>,
+    pub pinned: bool,
+}
+
+impl CleanupPolicy {
+    /// Pick the downloads to delete.
+    ///
+    /// `available_bytes` is the free space on the downloads disk, if known.
+    pub fn select(
+        &self,
+        candidates: &[CleanupCandidate],
+        now: NaiveDateTime,
+        available_bytes: Option<u64>,
+    ) -> Vec<i32> {
+        let mut deletable: Vec<&CleanupCandidate> = candidates
+            .iter()
+            .filter(|c| !c.pinned && c.watched_at.is_some())
+            .collect();
+        // Longest watched first
+        deletable.sort_by_key(|c| c.watched_at);
+
+        let mut selected = Vec::new();
+        let mut freed: u64 = 0;
+
+        if let Some(after) = self.delete_watched_after {
+            for candidate in &deletable {
+                if candidate.watched_at.is_some_and(|at| at + after <= now) {
+                    selected.push(candidate.id);
+                    freed += candidate.size_bytes;
+                }
+            }
+        }
+
+        if self.min_free_bytes > 0
+            && let Some(available) = available_bytes
+        {
+            for candidate in &deletable {
+                if available + freed >= self.min_free_bytes {
+                    break;
+                }
+                if !selected.contains(&candidate.id) {
+                    selected.push(candidate.id);
+                    freed += candidate.size_bytes;
+                }
+            }
+        }
+
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn now() -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2025, 12, 13)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    fn candidate(id: i32, watched_days_ago: Option<i64>, pinned: bool) -> CleanupCandidate {
+        CleanupCandidate {
+            id,
+            size_bytes: GB,
+            watched_at: watched_days_ago.map(|days| now() - Duration::days(days)),
+            pinned,
+        }
+    }
+
+    #[test]
+    fn test_deletes_downloads_watched_longer_than_the_period() {
+        let policy = CleanupPolicy {
+            delete_watched_after: Some(Duration::days(7)),
+            min_free_bytes: 0,
+        };
+        let candidates = vec![
+            candidate(1, Some(10), false),
+            candidate(2, Some(3), false),
+            candidate(3, None, false),
+            candidate(4, Some(30), true),
+        ];
+
+        assert_eq!(policy.select(&candidates, now(), None), vec![1]);
+    }
+
+    #[test]
+    fn test_frees_space_with_the_longest_watched_first() {
+        let policy = CleanupPolicy {
+            delete_watched_after: None,
+            min_free_bytes: 5 * GB,
+        };
+        let candidates = vec![
+            candidate(1, Some(1), false),
+            candidate(2, Some(5), false),
+            candidate(3, Some(3), false),
+            candidate(4, None, false),
+        ];
+
+        assert_eq!(policy.select(&candidates, now(), Some(3 * GB)), vec![2, 3]);
+    }
+}
@@ -0,0 +1,957 @@
+use crate::db::connection::DatabaseConnection;
+use crate::db::entities::{
+    AutoDownloadRuleModel, DownloadStatus, OfflineContentModel, QualityPreset,
+};
+use crate::db::repository::{
+    AutoDownloadRuleRepository, AutoDownloadRuleRepositoryImpl, MediaRepository,
+    MediaRepositoryImpl, OfflineContentRepository, OfflineContentRepositoryImpl,
+    PlaybackRepository, PlaybackRepositoryImpl, Repository,
+    source_repository::SourceRepositoryImpl,
+};
+use crate::models::MediaItemId;
+use crate::services::config_service::config_service;
+use crate::services::core::backend::BackendService;
+use crate::services::core::download_policy::{CleanupCandidate, CleanupPolicy};
+use crate::services::core::network_policy::network_policy;
+use crate::services::core::storage::{available_space_async, storage_locations};
+use anyhow::{Context, Result, anyhow};
+use futures::StreamExt;
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tracing::{debug, info, warn};
+
+/// How often download progress is written to the database
+const PROGRESS_INTERVAL_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Quality used for episodes queued by auto-download rules
+const AUTO_DOWNLOAD_QUALITY: QualityPreset = QualityPreset::High;
+
+/// Audio bitrate assumed when estimating the size of a download
+const ESTIMATED_AUDIO_KBPS: u64 = 256;
+
+/// How long the files of a deleted download are kept aside, so deleting it
+/// can be undone without downloading it again
+const DELETED_KEEP: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Extension of download files kept aside after deleting them
+const DELETED_EXTENSION: &str = "deleted";
+
+/// What downloading a batch of items, such as a season, would take
+#[derive(Debug, Clone)]
+pub struct DownloadEstimate {
+    /// Items not downloaded or queued at the chosen quality yet
+    pub media_ids: Vec<MediaItemId>,
+    /// Largest total size, None at original quality where it isn't known ahead
+    pub max_bytes: Option<u64>,
+    /// Free space where downloads are stored
+    pub available_bytes: Option<u64>,
+}
+
+/// Stateless service for offline downloads
+pub struct DownloadService;
+
+impl DownloadService {
+    /// Directory where offline downloads are stored
+    pub fn downloads_directory() -> PathBuf {
+        storage_locations().downloads
+    }
+
+    /// Queue a media item for download at the given quality.
+    ///
+    /// An existing download of the same quality is kept; any other previous
+    /// download of the item is replaced.
+    pub async fn queue_download(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+        quality: QualityPreset,
+    ) -> Result<OfflineContentModel> {
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+
+        if let Some(existing) = repo.find_by_media_id(media_id.as_str()).await? {
+            let failed = existing.status == DownloadStatus::Failed.to_string();
+            if existing.get_quality_preset() == Some(quality.clone()) && !failed {
+                debug!("{} is already downloaded or queued", media_id);
+                // Asking for it explicitly takes it out of the auto-download scheduler's hands
+                if existing.auto_downloaded {
+                    repo.set_auto_downloaded(existing.id, false).await?;
+                }
+                return Ok(existing);
+            }
+            Self::remove_download(db, &existing).await?;
+        }
+
+        Self::enqueue(&repo, media_id, quality, false).await
+    }
+
+    async fn enqueue(
+        repo: &OfflineContentRepositoryImpl,
+        media_id: &MediaItemId,
+        quality: QualityPreset,
+        auto_downloaded: bool,
+    ) -> Result<OfflineContentModel> {
+        let file_path = Self::downloads_directory().join(Self::file_stem(media_id, &quality));
+        let item = repo
+            .enqueue(
+                media_id.as_str(),
+                quality.as_str(),
+                &file_path.to_string_lossy(),
+                auto_downloaded,
+            )
+            .await?;
+
+        info!(
+            "Queued download of {} at {} quality",
+            media_id,
+            quality.label()
+        );
+        Ok(item)
+    }
+
+    /// The episodes of a show, or of one of its seasons, in episode order
+    pub async fn show_episode_ids(
+        db: &DatabaseConnection,
+        show_id: &MediaItemId,
+        season: Option<u32>,
+    ) -> Result<Vec<MediaItemId>> {
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        let episodes = match season {
+            Some(season) => {
+                media_repo
+                    .find_episodes_by_season(show_id.as_str(), season as i32)
+                    .await?
+            }
+            None => media_repo.find_episodes_by_show(show_id.as_str()).await?,
+        };
+        Ok(episodes
+            .into_iter()
+            .map(|episode| MediaItemId::new(episode.id))
+            .collect())
+    }
+
+    /// Work out which of the items still need downloading at `quality` and
+    /// how much space they would take
+    pub async fn estimate_downloads(
+        db: &DatabaseConnection,
+        media_ids: &[MediaItemId],
+        quality: &QualityPreset,
+    ) -> Result<DownloadEstimate> {
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+
+        let mut needed = Vec::new();
+        let mut max_bytes = Some(0u64);
+        for media_id in media_ids {
+            let existing = repo.find_by_media_id(media_id.as_str()).await?;
+            let present = existing.is_some_and(|item| {
+                item.get_quality_preset().as_ref() == Some(quality)
+                    && item.status != DownloadStatus::Failed.to_string()
+            });
+            if present {
+                continue;
+            }
+
+            let duration_ms = media_repo
+                .find_by_id(media_id.as_str())
+                .await?
+                .and_then(|item| item.duration_ms)
+                .unwrap_or(0);
+            max_bytes = max_bytes
+                .zip(max_download_bytes(duration_ms, quality))
+                .map(|(total, bytes)| total + bytes);
+            needed.push(media_id.clone());
+        }
+
+        Ok(DownloadEstimate {
+            media_ids: needed,
+            max_bytes,
+            available_bytes: available_space_async(Self::downloads_directory()).await,
+        })
+    }
+
+    /// Queue several items for download at the same quality
+    pub async fn queue_downloads(
+        db: &DatabaseConnection,
+        media_ids: &[MediaItemId],
+        quality: QualityPreset,
+    ) -> Result<()> {
+        for media_id in media_ids {
+            Self::queue_download(db, media_id, quality.clone()).await?;
+        }
+        info!(
+            "Queued {} downloads at {} quality",
+            media_ids.len(),
+            quality.label()
+        );
+        Ok(())
+    }
+
+    /// Get the auto-download rule for a show, if any
+    pub async fn auto_download_rule(
+        db: &DatabaseConnection,
+        show_id: &MediaItemId,
+    ) -> Result<Option<AutoDownloadRuleModel>> {
+        let repo = AutoDownloadRuleRepositoryImpl::new(db.clone());
+        repo.find_by_id(show_id.as_str()).await
+    }
+
+    /// Keep the next `episode_count` unwatched episodes of a show downloaded.
+    ///
+    /// A count of zero removes the rule. The downloads are brought in line right away.
+    pub async fn set_auto_download(
+        db: &DatabaseConnection,
+        show_id: &MediaItemId,
+        episode_count: u32,
+    ) -> Result<()> {
+        let repo = AutoDownloadRuleRepositoryImpl::new(db.clone());
+        if episode_count == 0 {
+            repo.delete(show_id.as_str()).await?;
+            info!("Disabled auto-download for {}", show_id);
+        } else {
+            repo.set_rule(
+                show_id.as_str(),
+                episode_count as i32,
+                &AUTO_DOWNLOAD_QUALITY,
+            )
+            .await?;
+            info!(
+                "Auto-downloading the next {} episodes of {}",
+                episode_count, show_id
+            );
+        }
+
+        Self::schedule_auto_downloads(db).await
+    }
+
+    /// Apply the auto-download rules: queue the next unwatched episodes of each
+    /// show and remove auto downloads that are no longer among them. Watched
+    /// ones are left to the cleanup policy.
+    ///
+    /// Downloads requested by hand are never touched.
+    pub async fn schedule_auto_downloads(db: &DatabaseConnection) -> Result<()> {
+        let rule_repo = AutoDownloadRuleRepositoryImpl::new(db.clone());
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+
+        let mut wanted = HashSet::new();
+        let mut queued = 0;
+        for rule in rule_repo.find_all().await? {
+            let Some(quality) = rule.get_quality_preset() else {
+                warn!(
+                    "Auto-download rule for {} has invalid quality '{}'",
+                    rule.show_id, rule.quality
+                );
+                continue;
+            };
+
+            let episodes = media_repo
+                .find_unwatched_episodes(&rule.show_id, rule.episode_count.max(0) as u64)
+                .await?;
+            for episode in episodes {
+                // Episodes that already have a download, including failed ones, are left as they are
+                if repo.find_by_media_id(&episode.id).await?.is_none() {
+                    let media_id = MediaItemId::new(episode.id.clone());
+                    Self::enqueue(&repo, &media_id, quality.clone(), true).await?;
+                    queued += 1;
+                }
+                wanted.insert(episode.id);
+            }
+        }
+
+        let playback_repo = PlaybackRepositoryImpl::new(db.clone());
+        let mut removed = 0;
+        for item in repo.get_auto_downloaded().await? {
+            if wanted.contains(&item.media_id)
+                || item.pinned
+                || item.status == DownloadStatus::Downloading.to_string()
+            {
+                continue;
+            }
+            // Episodes drop out of the next unwatched ones by being watched,
+            // and watched downloads are kept as long as the cleanup policy says
+            if item.status == DownloadStatus::Completed.to_string()
+                && playback_repo
+                    .find_by_media_id(&item.media_id)
+                    .await?
+                    .is_some_and(|progress| progress.watched)
+            {
+                continue;
+            }
+            Self::remove_download(db, &item).await?;
+            removed += 1;
+        }
+
+        if queued > 0 || removed > 0 {
+            info!(
+                "Auto-download: queued {} episodes, removed {} stale downloads",
+                queued, removed
+            );
+        }
+        Ok(())
+    }
+
+    /// Get the download record for a media item, whatever its state
+    pub async fn find_download(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> Result<Option<OfflineContentModel>> {
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+        repo.find_by_media_id(media_id.as_str()).await
+    }
+
+    /// Get the finished download for a media item, if its file is on disk
+    pub async fn playable_download(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> Result<Option<OfflineContentModel>> {
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+        Ok(repo
+            .find_by_media_id(media_id.as_str())
+            .await?
+            .filter(|item| item.is_playable()))
+    }
+
+    /// Delete a download record together with its file
+    pub async fn remove_download(
+        db: &DatabaseConnection,
+        item: &OfflineContentModel,
+    ) -> Result<()> {
+        if item.file_exists() {
+            tokio::fs::remove_file(&item.file_path)
+                .await
+                .with_context(|| format!("Failed to delete {}", item.file_path))?;
+        }
+        Self::discard_partial(item).await?;
+
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+        repo.delete_by_id(item.id).await
+    }
+
+    /// Delete a download record, keeping its files aside for a minute so
+    /// [`restore_download`](Self::restore_download) can bring it back
+    pub async fn remove_download_undoable(
+        db: &DatabaseConnection,
+        item: &OfflineContentModel,
+    ) -> Result<()> {
+        let mut kept = Vec::new();
+        for path in [PathBuf::from(&item.file_path), item.partial_path()] {
+            if path.exists() {
+                let deleted = deleted_path(&path);
+                tokio::fs::rename(&path, &deleted)
+                    .await
+                    .with_context(|| format!("Failed to delete {}", path.display()))?;
+                // Renaming keeps the time the file was written, its time
+                // aside starts now
+                tokio::fs::OpenOptions::new()
+                    .write(true)
+                    .open(&deleted)
+                    .await?
+                    .into_std()
+                    .await
+                    .set_modified(std::time::SystemTime::now())?;
+                kept.push(deleted);
+            }
+        }
+
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+        repo.delete_by_id(item.id).await?;
+
+        if !kept.is_empty() {
+            tokio::spawn(async move {
+                tokio::time::sleep(DELETED_KEEP).await;
+                for path in kept {
+                    match tokio::fs::remove_file(&path).await {
+                        Ok(()) => {}
+                        // Restored meanwhile
+                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(e) => warn!("Failed to delete {}: {}", path.display(), e),
+                    }
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Bring back a download deleted by
+    /// [`remove_download_undoable`](Self::remove_download_undoable),
+    /// together with its files
+    pub async fn restore_download(
+        db: &DatabaseConnection,
+        item: &OfflineContentModel,
+    ) -> Result<()> {
+        // The files are gone for good once kept aside for `DELETED_KEEP`
+        let expected = if item.status == DownloadStatus::Completed.to_string() {
+            Some(PathBuf::from(&item.file_path))
+        } else if item.downloaded_bytes > 0 {
+            Some(item.partial_path())
+        } else {
+            None
+        };
+        if let Some(path) = expected
+            && !deleted_path(&path).exists()
+        {
+            return Err(anyhow!(
+                "The file of the download is already deleted: {}",
+                path.display()
+            ));
+        }
+
+        for path in [PathBuf::from(&item.file_path), item.partial_path()] {
+            let deleted = deleted_path(&path);
+            if deleted.exists() {
+                tokio::fs::rename(&deleted, &path)
+                    .await
+                    .with_context(|| format!("Failed to restore {}", path.display()))?;
+            }
+        }
+
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+        repo.insert(item.clone()).await?;
+        Ok(())
+    }
+
+    /// Delete the files of deleted downloads left aside by an earlier run
+    async fn purge_deleted_files() -> Result<()> {
+        let mut entries = match tokio::fs::read_dir(Self::downloads_directory()).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e).context("Failed to list downloads"),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let expired = entry
+                .metadata()
+                .await
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > DELETED_KEEP);
+            if expired && path.extension().is_some_and(|ext| ext == DELETED_EXTENSION) {
+                tokio::fs::remove_file(&path)
+                    .await
+                    .with_context(|| format!("Failed to delete {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete the partial file of a download that won't be continued
+    pub async fn discard_partial(item: &OfflineContentModel) -> Result<()> {
+        let partial_path = item.partial_path();
+        if partial_path.exists() {
+            tokio::fs::remove_file(&partial_path)
+                .await
+                .with_context(|| format!("Failed to delete {}", partial_path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Pin a download so the cleanup policy never deletes it
+    pub async fn set_pinned(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+        pinned: bool,
+    ) -> Result<()> {
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+        let item = repo
+            .find_by_media_id(media_id.as_str())
+            .await?
+            .ok_or_else(|| anyhow!("{} has no download to pin", media_id))?;
+        repo.set_pinned(item.id, pinned).await
+    }
+
+    /// Delete finished downloads according to the configured cleanup policy.
+    ///
+    /// Returns how many downloads were removed.
+    pub async fn apply_cleanup_policy(db: &DatabaseConnection) -> Result<usize> {
+        if let Err(e) = Self::purge_deleted_files().await {
+            warn!("Failed to purge deleted downloads: {:#}", e);
+        }
+
+        let policy = CleanupPolicy::from(&config_service().get_config().await.downloads);
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+        let playback_repo = PlaybackRepositoryImpl::new(db.clone());
+
+        let completed = repo.get_completed().await?;
+        let mut candidates = Vec::with_capacity(completed.len());
+        for item in &completed {
+            let watched_at = playback_repo
+                .find_by_media_id(&item.media_id)
+                .await?
+                .filter(|progress| progress.watched)
+                .map(|progress| progress.last_watched_at.unwrap_or(progress.updated_at));
+            candidates.push(CleanupCandidate {
+                id: item.id,
+                size_bytes: item.file_size_bytes.unwrap_or(0).max(0) as u64,
+                watched_at,
+                pinned: item.pinned,
+            });
+        }
+
+        let available = available_space_async(Self::downloads_directory()).await;
+        let selected = policy.select(&candidates, chrono::Utc::now().naive_utc(), available);
+
+        for item in completed.iter().filter(|item| selected.contains(&item.id)) {
+            info!("Deleting watched download of {}", item.media_id);
+            Self::remove_download(db, item).await?;
+        }
+
+        Ok(selected.len())
+    }
+
+    /// Download a queued item to disk, reporting `(downloaded, total)` bytes as it goes.
+    ///
+    /// Returns the final path of the downloaded file.
+    pub async fn download_item(
+        db: &DatabaseConnection,
+        item: &OfflineContentModel,
+        mut on_progress: impl FnMut(u64, Option<u64>),
+    ) -> Result<PathBuf> {
+        let repo = OfflineContentRepositoryImpl::new(db.clone());
+        let quality = item
+            .get_quality_preset()
+            .ok_or_else(|| anyhow!("Download {} has no quality preset", item.id))?;
+
+        // Resolve the backend for the item's source
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        let media_item = media_repo
+            .find_by_id(&item.media_id)
+            .await?
+            .ok_or_else(|| anyhow!("Media item not found: {}", item.media_id))?;
+        let source_repo = SourceRepositoryImpl::new(db.clone());
+        let source = source_repo
+            .find_by_id(&media_item.source_id)
+            .await?
+            .ok_or_else(|| anyhow!("Source not found: {}", media_item.source_id))?;
+        let backend = BackendService::create_backend_for_source(db, &source).await?;
+
+        let media_id = MediaItemId::new(item.media_id.clone());
+        let download = backend.get_download_url(&media_id, &quality).await?;
+        debug!(
+            "Downloading {} (transcode: {})",
+            item.media_id, download.requires_transcode
+        );
+
+        let final_path = PathBuf::from(&item.file_path).with_extension(&download.container);
+        if let Some(parent) = final_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let partial_path = item.partial_path();
+
+        // Transcodes are produced on the fly and differ between requests, so
+        // only the original file is continued where it stopped
+        let mut hasher = Sha256::new();
+        let resume_from = if download.requires_transcode {
+            0
+        } else {
+            Self::verified_prefix(item, &partial_path, &mut hasher).await?
+        };
+
+        let client = network_policy()
+            .download_client_builder()
+            .danger_accept_invalid_certs(true) // Plex uses self-signed certs
+            .build()?;
+        let mut request = client.get(&download.url);
+        if resume_from > 0 {
+            request = request.header(RANGE, format!("bytes={}-", resume_from));
+            if let Some(validator) = &item.validator {
+                request = request.header(IF_RANGE, validator);
+            }
+        }
+        let response = request.send().await?;
+        let mut response =
+            if response.status() == StatusCode::RANGE_NOT_SATISFIABLE && resume_from > 0 {
+                let server_total = response
+                    .headers()
+                    .get(CONTENT_RANGE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(content_range_total);
+                if complete_at(resume_from, server_total, item.file_size_bytes) {
+                    info!(
+                        "Download of {} was complete already at {} bytes",
+                        item.media_id, resume_from
+                    );
+                    tokio::fs::OpenOptions::new()
+                        .write(true)
+                        .open(&partial_path)
+                        .await?
+                        .set_len(resume_from)
+                        .await?;
+                    return Self::finish_download(
+                        &repo,
+                        item,
+                        &partial_path,
+                        &final_path,
+                        resume_from,
+                        hasher,
+                        &mut on_progress,
+                    )
+                    .await;
+                }
+                checked_response(client.get(&download.url)).await?
+            } else {
+                successful(response)?
+            };
+
+        // Servers answer a range they can't serve, or of a file that changed,
+        // with the whole file. A range of a file of another length is
+        // dropped for the whole file as well.
+        let content_range_total = response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(content_range_total);
+        let partial = response.status() == StatusCode::PARTIAL_CONTENT;
+        let resumed = resume_from > 0
+            && partial
+            && content_range_total.is_some()
+            && content_range_total.map(|total| total as i64) == item.file_size_bytes;
+        if partial && !resumed {
+            response = checked_response(client.get(&download.url)).await?;
+        }
+        let (mut downloaded, total) = if resumed {
+            info!(
+                "Resuming download of {} at {} bytes",
+                item.media_id, resume_from
+            );
+            (resume_from, content_range_total)
+        } else {
+            hasher = Sha256::new();
+            // Transcoded downloads have no known length
+            (0, response.content_length())
+        };
+        let validator = response
+            .headers()
+            .get(ETAG)
+            .or_else(|| response.headers().get(LAST_MODIFIED))
+            .and_then(|value| value.to_str().ok())
+            .filter(|_| !download.requires_transcode)
+            .map(str::to_string);
+        repo.mark_downloading(
+            item.id,
+            total.map(|t| t as i64),
+            downloaded as i64,
+            validator.as_deref(),
+        )
+        .await?;
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(&partial_path)
+            .await
+            .with_context(|| format!("Failed to open {}", partial_path.display()))?;
+        file.set_len(downloaded).await?;
+        file.seek(SeekFrom::Start(downloaded)).await?;
+
+        let mut stream = response.bytes_stream();
+        let mut last_reported = downloaded;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk).await?;
+            hasher.update(&chunk);
+            downloaded += chunk.len() as u64;
+
+            if downloaded - last_reported >= PROGRESS_INTERVAL_BYTES {
+                last_reported = downloaded;
+                // The offset is only recorded once the bytes before it are
+                // on disk, so a crash never resumes past them
+                file.sync_data().await?;
+                let checksum = format!("{:x}", hasher.clone().finalize());
+                repo.update_progress(item.id, downloaded as i64, &checksum)
+                    .await?;
+                on_progress(downloaded, total);
+            }
+        }
+        file.sync_all().await?;
+        drop(file);
+
+        if let Some(total) = total
+            && downloaded != total
+        {
+            return Err(anyhow!(
+                "Download incomplete: received {} of {} bytes",
+                downloaded,
+                total
+            ));
+        }
+        Self::finish_download(
+            &repo,
+            item,
+            &partial_path,
+            &final_path,
+            downloaded,
+            hasher,
+            &mut on_progress,
+        )
+        .await
+    }
+
+    /// Move a download with all its bytes on disk in place and record it as
+    /// completed
+    async fn finish_download(
+        repo: &OfflineContentRepositoryImpl,
+        item: &OfflineContentModel,
+        partial_path: &Path,
+        final_path: &Path,
+        downloaded: u64,
+        hasher: Sha256,
+        on_progress: &mut impl FnMut(u64, Option<u64>),
+    ) -> Result<PathBuf> {
+        let on_disk = tokio::fs::metadata(partial_path).await?.len();
+        if on_disk != downloaded {
+            return Err(anyhow!(
+                "Download damaged: {} bytes on disk, {} received",
+                on_disk,
+                downloaded
+            ));
+        }
+
+        tokio::fs::rename(partial_path, final_path).await?;
+
+        // The extension is only known once the server answered, so the stored path
+        // is updated along with the status
+        let checksum = format!("{:x}", hasher.finalize());
+        repo.mark_completed(
+            item.id,
+            &final_path.to_string_lossy(),
+            downloaded as i64,
+            &checksum,
+        )
+        .await?;
+        on_progress(downloaded, Some(downloaded));
+
+        info!(
+            "Downloaded {} ({} bytes, sha256 {}) to {}",
+            item.media_id,
+            downloaded,
+            checksum,
+            final_path.display()
+        );
+        Ok(final_path.to_path_buf())
+    }
+
+    /// How many bytes of an earlier attempt can be kept, feeding them to
+    /// `hasher`. Only the bytes recorded as written are kept, and only when
+    /// they still hash to the recorded checksum.
+    async fn verified_prefix(
+        item: &OfflineContentModel,
+        partial_path: &Path,
+        hasher: &mut Sha256,
+    ) -> Result<u64> {
+        let recorded = item.downloaded_bytes.max(0) as u64;
+        let (Some(checksum), true) = (&item.checksum, recorded > 0) else {
+            return Ok(0);
+        };
+        let Ok(mut file) = tokio::fs::File::open(partial_path).await else {
+            return Ok(0);
+        };
+        if file.metadata().await?.len() < recorded {
+            warn!(
+                "Partial download of {} is shorter than recorded, starting over",
+                item.media_id
+            );
+            return Ok(0);
+        }
+
+        let mut prefix = Sha256::new();
+        let mut remaining = recorded;
+        let mut buffer = vec![0; 1024 * 1024];
+        while remaining > 0 {
+            let want = remaining.min(buffer.len() as u64) as usize;
+            let read = file.read(&mut buffer[..want]).await?;
+            if read == 0 {
+                return Ok(0);
+            }
+            prefix.update(&buffer[..read]);
+            remaining -= read as u64;
+        }
+
+        if format!("{:x}", prefix.clone().finalize()) != *checksum {
+            warn!(
+                "Partial download of {} doesn't match its checksum, starting over",
+                item.media_id
+            );
+            return Ok(0);
+        }
+        *hasher = prefix;
+        Ok(recorded)
+    }
+
+    /// File name (without extension) for a download
+    fn file_stem(media_id: &MediaItemId, quality: &QualityPreset) -> String {
+        let safe_id: String = media_id
+            .as_str()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("{}_{}", safe_id, quality.as_str())
+    }
+}
+
+/// Where a download file is kept aside after deleting it
+fn deleted_path(path: &Path) -> PathBuf {
+    let mut deleted = path.as_os_str().to_owned();
+    deleted.push(".");
+    deleted.push(DELETED_EXTENSION);
+    PathBuf::from(deleted)
+}
+
+/// Send a download request, failing on error statuses
+async fn checked_response(request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+    successful(request.send().await?)
+}
+
+/// A response to a download request, failing on error statuses
+fn successful(response: reqwest::Response) -> Result<reqwest::Response> {
+    if !response.status().is_success() {
+        return Err(anyhow!("Server returned {}", response.status()));
+    }
+    Ok(response)
+}
+
+/// Full length from a Content-Range header like "bytes 100-199/200", None
+/// when the server doesn't know it
+fn content_range_total(value: &str) -> Option<u64> {
+    value
+        .strip_prefix("bytes ")?
+        .rsplit_once('/')?
+        .1
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Whether a download refused to resume at `resume_from` has all its bytes.
+/// Resuming at the end of the file asks for nothing, which only finishes it
+/// when the length recorded and the one the server tells, if any, agree.
+fn complete_at(resume_from: u64, server_total: Option<u64>, recorded_total: Option<i64>) -> bool {
+    recorded_total == Some(resume_from as i64) && server_total.unwrap_or(resume_from) == resume_from
+}
+
+/// Largest size of a download of `duration_ms` at `quality`, from its
+/// bitrate cap. Unknown at original quality.
+fn max_download_bytes(duration_ms: i64, quality: &QualityPreset) -> Option<u64> {
+    if *quality == QualityPreset::Original {
+        return None;
+    }
+    let kbps = u64::from(quality.max_video_bitrate_kbps()) + ESTIMATED_AUDIO_KBPS;
+    // Milliseconds times kilobits per second is bits
+    Some(duration_ms.max(0) as u64 * kbps / 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_download_bytes_follows_bitrate_cap() {
+        // An hour at 2000 + 256 kbps
+        assert_eq!(
+            max_download_bytes(3_600_000, &QualityPreset::Low),
+            Some(3_600 * 2_256 * 1000 / 8)
+        );
+        assert_eq!(
+            max_download_bytes(3_600_000, &QualityPreset::Original),
+            None
+        );
+        assert_eq!(max_download_bytes(-5, &QualityPreset::High), Some(0));
+    }
+
+    #[test]
+    fn test_content_range_total() {
+        assert_eq!(content_range_total("bytes 100-199/200"), Some(200));
+        assert_eq!(content_range_total("bytes 0-0/*"), None);
+        assert_eq!(content_range_total("items 0-1/2"), None);
+    }
+
+    #[test]
+    fn test_refused_resume_at_the_full_length_is_complete() {
+        assert!(complete_at(100, Some(100), Some(100)));
+        assert!(complete_at(100, None, Some(100)));
+        assert!(!complete_at(100, Some(200), Some(100)));
+        assert!(!complete_at(100, Some(100), Some(200)));
+        assert!(!complete_at(100, None, None));
+    }
+
+    fn partial_download(dir: &Path, downloaded_bytes: i64, checksum: &str) -> OfflineContentModel {
+        OfflineContentModel {
+            id: 1,
+            media_id: "plex:1".to_string(),
+            file_path: dir.join("movie_high").to_string_lossy().into_owned(),
+            file_size_bytes: Some(100),
+            quality: Some("high".to_string()),
+            downloaded_at: chrono::Utc::now().naive_utc(),
+            last_accessed: None,
+            status: DownloadStatus::Queued.to_string(),
+            downloaded_bytes,
+            error_message: None,
+            auto_downloaded: false,
+            pinned: false,
+            validator: Some("\"etag\"".to_string()),
+            checksum: Some(checksum.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_keeps_only_recorded_bytes_matching_their_checksum() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let checksum = format!("{:x}", Sha256::digest(b"0123"));
+        let item = partial_download(dir.path(), 4, &checksum);
+        // Bytes written after the last recorded offset are dropped
+        tokio::fs::write(item.partial_path(), b"012345")
+            .await
+            .unwrap();
+
+        let mut hasher = Sha256::new();
+        let kept = DownloadService::verified_prefix(&item, &item.partial_path(), &mut hasher)
+            .await
+            .unwrap();
+        assert_eq!(kept, 4);
+        assert_eq!(format!("{:x}", hasher.finalize()), checksum);
+
+        tokio::fs::write(item.partial_path(), b"X12345")
+            .await
+            .unwrap();
+        let kept =
+            DownloadService::verified_prefix(&item, &item.partial_path(), &mut Sha256::new())
+                .await
+                .unwrap();
+        assert_eq!(kept, 0);
+    }
+
+    #[tokio::test]
+    async fn test_resume_starts_over_without_a_long_enough_partial_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let item = partial_download(dir.path(), 4, &format!("{:x}", Sha256::digest(b"0123")));
+        let mut hasher = Sha256::new();
+        assert_eq!(
+            DownloadService::verified_prefix(&item, &item.partial_path(), &mut hasher)
+                .await
+                .unwrap(),
+            0
+        );
+
+        tokio::fs::write(item.partial_path(), b"01").await.unwrap();
+        assert_eq!(
+            DownloadService::verified_prefix(&item, &item.partial_path(), &mut hasher)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_file_stem_is_filesystem_safe() {
+        let media_id = MediaItemId::new("plex:abc/123:movie:42".to_string());
+        assert_eq!(
+            DownloadService::file_stem(&media_id, &QualityPreset::Medium),
+            "plex_abc_123_movie_42_medium"
+        );
+    }
+}
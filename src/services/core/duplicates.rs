@@ -0,0 +1,138 @@
+//! Finding movies stored more than once
+//!
+//! A movie is duplicated when its server holds several files of it, or when
+//! items in different libraries or sources share an id in an outside
+//! database, like "imdb://tt0111161".
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use tracing::debug;
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::entities::MediaItemModel;
+use crate::db::repository::{MediaRepository, MediaRepositoryImpl};
+
+/// The items holding copies of one movie
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub title: String,
+    pub items: Vec<MediaItemModel>,
+}
+
+impl DuplicateGroup {
+    /// Files across all items of the group
+    pub fn copy_count(&self) -> usize {
+        self.items
+            .iter()
+            .map(|item| item.get_versions().len().max(1))
+            .sum()
+    }
+}
+
+/// Stateless service for finding duplicates
+pub struct DuplicatesService;
+
+impl DuplicatesService {
+    /// Movies with more than one copy, by title
+    pub async fn find_duplicates(db: &DatabaseConnection) -> Result<Vec<DuplicateGroup>> {
+        let movies = MediaRepositoryImpl::new(db.clone())
+            .find_by_type("movie")
+            .await?;
+        let ids: Vec<Vec<String>> = movies
+            .iter()
+            .map(MediaItemModel::get_external_ids)
+            .collect();
+
+        let mut movies: Vec<Option<MediaItemModel>> = movies.into_iter().map(Some).collect();
+        let mut groups: Vec<DuplicateGroup> = group_by_external_ids(&ids)
+            .into_iter()
+            .map(|indexes| {
+                let items: Vec<MediaItemModel> = indexes
+                    .into_iter()
+                    .filter_map(|index| movies[index].take())
+                    .collect();
+                DuplicateGroup {
+                    title: items[0].title.clone(),
+                    items,
+                }
+            })
+            .filter(|group| group.copy_count() > 1)
+            .collect();
+        groups.sort_by_key(|group| group.title.to_lowercase());
+
+        debug!("Found {} duplicated movies", groups.len());
+        Ok(groups)
+    }
+}
+
+/// Indexes of the items sharing outside ids with each other, directly or
+/// through another item. Items without ids are alone in their group.
+fn group_by_external_ids(ids: &[Vec<String>]) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..ids.len()).collect();
+    fn root(parent: &mut [usize], mut index: usize) -> usize {
+        while parent[index] != index {
+            parent[index] = parent[parent[index]];
+            index = parent[index];
+        }
+        index
+    }
+
+    let mut first_with_id: HashMap<&str, usize> = HashMap::new();
+    for (index, item_ids) in ids.iter().enumerate() {
+        for id in item_ids {
+            let other = *first_with_id.entry(id.as_str()).or_insert(index);
+            let (a, b) = (root(&mut parent, index), root(&mut parent, other));
+            parent[a] = b;
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for index in 0..ids.len() {
+        groups
+            .entry(root(&mut parent, index))
+            .or_default()
+            .push(index);
+    }
+    let mut groups: Vec<Vec<usize>> = groups.into_values().collect();
+    groups.sort();
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(items: &[&[&str]]) -> Vec<Vec<String>> {
+        items
+            .iter()
+            .map(|ids| ids.iter().map(|id| id.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_items_sharing_an_id_are_grouped() {
+        let groups = group_by_external_ids(&ids(&[
+            &["imdb://tt1", "tmdb://1"],
+            &["imdb://tt2"],
+            &["tmdb://1"],
+        ]));
+        assert_eq!(groups, vec![vec![0, 2], vec![1]]);
+    }
+
+    #[test]
+    fn test_groups_join_through_a_shared_item() {
+        let groups = group_by_external_ids(&ids(&[
+            &["imdb://tt1"],
+            &["tmdb://1"],
+            &["imdb://tt1", "tmdb://1"],
+        ]));
+        assert_eq!(groups, vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn test_items_without_ids_stay_apart() {
+        let groups = group_by_external_ids(&ids(&[&[], &[]]));
+        assert_eq!(groups, vec![vec![0], vec![1]]);
+    }
+}
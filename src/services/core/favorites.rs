@@ -0,0 +1,108 @@
+//! Favorite (Jellyfin) and liked (Plex) items
+//!
+//! The server is the source of truth: changes are sent to it first and the
+//! local table is replaced with the server's list on every sync.
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result, anyhow};
+use tracing::info;
+
+use crate::backends::traits::MediaBackend;
+use crate::db::connection::DatabaseConnection;
+use crate::db::entities::MediaItemModel;
+use crate::db::repository::{
+    FavoriteRepository, FavoriteRepositoryImpl, MediaRepositoryImpl, Repository,
+};
+use crate::models::{MediaItemId, SourceId};
+
+use super::backend::BackendService;
+
+/// Pure functions for favorite operations
+pub struct FavoritesService;
+
+impl FavoritesService {
+    /// IDs of all favorite items across sources
+    pub async fn favorite_ids(db: &DatabaseConnection) -> Result<HashSet<String>> {
+        let repo = FavoriteRepositoryImpl::new(db.clone());
+        Ok(repo
+            .find_all()
+            .await
+            .context("Failed to get favorites")?
+            .into_iter()
+            .map(|favorite| favorite.media_id)
+            .collect())
+    }
+
+    /// Whether a media item is a favorite
+    pub async fn is_favorite(db: &DatabaseConnection, media_id: &MediaItemId) -> Result<bool> {
+        let repo = FavoriteRepositoryImpl::new(db.clone());
+        Ok(repo.find_by_id(media_id.as_ref()).await?.is_some())
+    }
+
+    /// Favorite items of a source, most recently added first
+    pub async fn get_favorites_for_source(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+    ) -> Result<Vec<MediaItemModel>> {
+        let favorite_repo = FavoriteRepositoryImpl::new(db.clone());
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+
+        let mut items = Vec::new();
+        for favorite in favorite_repo.find_by_source(source_id.as_str()).await? {
+            if let Some(item) = media_repo.find_by_id(&favorite.media_id).await? {
+                items.push(item);
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Mark or unmark a media item as favorite, on its server and locally
+    pub async fn set_favorite(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+        favorite: bool,
+    ) -> Result<()> {
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        let item = media_repo
+            .find_by_id(media_id.as_ref())
+            .await?
+            .ok_or_else(|| anyhow!("Media item not found: {}", media_id))?;
+
+        BackendService::set_favorite(db, &item.source_id, media_id, favorite)
+            .await
+            .context("Failed to update favorite on server")?;
+
+        let repo = FavoriteRepositoryImpl::new(db.clone());
+        if favorite {
+            repo.add(&item.source_id, media_id.as_ref()).await?;
+        } else {
+            repo.delete(media_id.as_ref()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace the local favorites of a source with the ones on its server
+    pub async fn sync_favorites(
+        db: &DatabaseConnection,
+        backend: &dyn MediaBackend,
+        source_id: &SourceId,
+    ) -> Result<()> {
+        let favorites = backend
+            .get_favorites()
+            .await
+            .context("Failed to get favorites from server")?;
+
+        info!(
+            "Syncing {} favorites for source {}",
+            favorites.len(),
+            source_id
+        );
+
+        let repo = FavoriteRepositoryImpl::new(db.clone());
+        repo.replace_for_source(source_id.as_str(), &favorites)
+            .await
+    }
+}
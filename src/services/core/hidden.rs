@@ -0,0 +1,124 @@
+//! Items hidden from the home page ("Not interested")
+//!
+//! Hiding is local to this device. Hiding a show also hides its episodes, so a
+//! whole series can be dropped from Continue Watching at once. Whole
+//! collections can be hidden too, which hides their movies and shows. While
+//! the kids profile is on, what it leaves out is hidden as well.
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::entities::{HiddenCollectionModel, MediaItemModel};
+use crate::db::repository::{
+    HiddenCollectionRepository, HiddenCollectionRepositoryImpl, HiddenItemRepository,
+    HiddenItemRepositoryImpl, MediaRepositoryImpl, Repository,
+};
+use crate::models::{MediaItemId, SourceId};
+
+use super::kids::KidsProfileService;
+
+/// Pure functions for hidden item operations
+pub struct HiddenItemsService;
+
+impl HiddenItemsService {
+    /// IDs of all hidden items, including the movies and shows of hidden
+    /// collections and those the kids profile leaves out
+    pub async fn hidden_ids(db: &DatabaseConnection) -> Result<HashSet<String>> {
+        let repo = HiddenItemRepositoryImpl::new(db.clone());
+        let mut ids: HashSet<String> = repo
+            .find_all()
+            .await
+            .context("Failed to get hidden items")?
+            .into_iter()
+            .map(|hidden| hidden.media_id)
+            .collect();
+
+        let collection_repo = HiddenCollectionRepositoryImpl::new(db.clone());
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        for collection in collection_repo
+            .find_all()
+            .await
+            .context("Failed to get hidden collections")?
+        {
+            ids.extend(
+                media_repo
+                    .find_ids_in_collection(&collection.source_id, &collection.name)
+                    .await?,
+            );
+        }
+
+        ids.extend(KidsProfileService::blocked_ids(db).await?);
+        Ok(ids)
+    }
+
+    /// Whether an item is left out of home rows, directly or through its show
+    pub fn is_hidden(item: &MediaItemModel, hidden_ids: &HashSet<String>) -> bool {
+        hidden_ids.contains(&item.id)
+            || item
+                .parent_id
+                .as_ref()
+                .is_some_and(|parent_id| hidden_ids.contains(parent_id))
+    }
+
+    /// Hidden items, most recently hidden first
+    pub async fn get_hidden_items(db: &DatabaseConnection) -> Result<Vec<MediaItemModel>> {
+        let hidden_repo = HiddenItemRepositoryImpl::new(db.clone());
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+
+        let mut items = Vec::new();
+        for hidden in hidden_repo.find_recent().await? {
+            if let Some(item) = media_repo.find_by_id(&hidden.media_id).await? {
+                items.push(item);
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Hidden collections, most recently hidden first
+    pub async fn get_hidden_collections(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<HiddenCollectionModel>> {
+        let repo = HiddenCollectionRepositoryImpl::new(db.clone());
+        repo.find_recent().await
+    }
+
+    /// Hide a collection of a source from the home page or show it again
+    pub async fn set_collection_hidden(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        collection: &str,
+        hidden: bool,
+    ) -> Result<()> {
+        let repo = HiddenCollectionRepositoryImpl::new(db.clone());
+        if hidden {
+            repo.hide(source_id.as_str(), collection).await?;
+        } else {
+            repo.unhide(source_id.as_str(), collection).await?;
+        }
+        Ok(())
+    }
+
+    /// Hide a media item from the home page or show it again
+    pub async fn set_hidden(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+        hidden: bool,
+    ) -> Result<()> {
+        let repo = HiddenItemRepositoryImpl::new(db.clone());
+        if !hidden {
+            return repo.delete(media_id.as_ref()).await;
+        }
+
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        let item = media_repo
+            .find_by_id(media_id.as_ref())
+            .await?
+            .ok_or_else(|| anyhow!("Media item not found: {}", media_id))?;
+
+        repo.hide(&item.source_id, media_id.as_ref()).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+//! Hardware video decoders available for playback
+//!
+//! Probed from the GStreamer registry, which only lists a hardware decoder
+//! when a device on this machine can decode that codec. Playback with the
+//! GStreamer player uses the result to transcode high resolution video the
+//! machine would otherwise have to decode in software.
+
+use std::fmt;
+use std::sync::RwLock;
+use tracing::info;
+
+use crate::models::{QualityOption, StreamInfo};
+
+use super::metered;
+
+/// Tallest video direct-played when it has to be decoded in software
+pub const SOFTWARE_DECODE_MAX_HEIGHT: u32 = 1080;
+
+/// Codecs probed for hardware decoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VideoCodec {
+    H264,
+    Hevc,
+    Vp9,
+    Av1,
+}
+
+impl VideoCodec {
+    pub const ALL: [VideoCodec; 4] = [Self::H264, Self::Hevc, Self::Vp9, Self::Av1];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::H264 => "H.264",
+            Self::Hevc => "HEVC",
+            Self::Vp9 => "VP9",
+            Self::Av1 => "AV1",
+        }
+    }
+
+    /// Media type of the codec in GStreamer caps
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    fn caps_name(&self) -> &'static str {
+        match self {
+            Self::H264 => "video/x-h264",
+            Self::Hevc => "video/x-h265",
+            Self::Vp9 => "video/x-vp9",
+            Self::Av1 => "video/x-av1",
+        }
+    }
+
+    /// Parse the codec name a server reports for a stream
+    pub fn from_stream_codec(codec: &str) -> Option<Self> {
+        match codec.to_ascii_lowercase().as_str() {
+            "h264" | "avc" | "avc1" => Some(Self::H264),
+            "hevc" | "h265" | "hvc1" | "hev1" => Some(Self::Hevc),
+            "vp9" => Some(Self::Vp9),
+            "av1" => Some(Self::Av1),
+            _ => None,
+        }
+    }
+}
+
+/// Driver interface a hardware decoder goes through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecoderApi {
+    VaApi,
+    Nvdec,
+    VideoToolbox,
+    V4l2,
+    Direct3D,
+    Other,
+}
+
+impl DecoderApi {
+    /// Interface of a GStreamer decoder, from its plugin's naming scheme
+    pub fn from_element(name: &str) -> Self {
+        if name.starts_with("va") {
+            Self::VaApi
+        } else if name.starts_with("nv") {
+            Self::Nvdec
+        } else if name.starts_with("vtdec") {
+            Self::VideoToolbox
+        } else if name.starts_with("v4l2") {
+            Self::V4l2
+        } else if name.starts_with("d3d11") || name.starts_with("d3d12") {
+            Self::Direct3D
+        } else {
+            Self::Other
+        }
+    }
+}
+
+impl fmt::Display for DecoderApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::VaApi => "VA-API",
+            Self::Nvdec => "NVDEC",
+            Self::VideoToolbox => "VideoToolbox",
+            Self::V4l2 => "V4L2",
+            Self::Direct3D => "Direct3D",
+            Self::Other => "Other",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardwareDecoder {
+    pub codec: VideoCodec,
+    pub api: DecoderApi,
+    /// GStreamer element implementing the decoder
+    pub element: String,
+}
+
+/// Result of a probe
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HardwareDecodeSupport {
+    pub decoders: Vec<HardwareDecoder>,
+}
+
+impl HardwareDecodeSupport {
+    pub fn decoders_for(&self, codec: VideoCodec) -> impl Iterator<Item = &HardwareDecoder> {
+        self.decoders
+            .iter()
+            .filter(move |decoder| decoder.codec == codec)
+    }
+
+    pub fn supports(&self, codec: VideoCodec) -> bool {
+        self.decoders_for(codec).next().is_some()
+    }
+
+    /// One line per codec, e.g. "HEVC: VA-API (vah265dec)"
+    pub fn summary(&self) -> Vec<String> {
+        VideoCodec::ALL
+            .iter()
+            .map(|codec| {
+                let decoders: Vec<String> = self
+                    .decoders_for(*codec)
+                    .map(|decoder| format!("{} ({})", decoder.api, decoder.element))
+                    .collect();
+                if decoders.is_empty() {
+                    format!("{}: software", codec.label())
+                } else {
+                    format!("{}: {}", codec.label(), decoders.join(", "))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Most recent probe result, None until the first probe finishes
+static SUPPORT: RwLock<Option<HardwareDecodeSupport>> = RwLock::new(None);
+
+/// Result of the last probe
+pub fn hardware_decode_support() -> Option<HardwareDecodeSupport> {
+    SUPPORT.read().ok().and_then(|support| support.clone())
+}
+
+/// Probe the available hardware decoders and remember the result
+pub async fn probe() -> HardwareDecodeSupport {
+    let support = tokio::task::spawn_blocking(probe_registry)
+        .await
+        .unwrap_or_default();
+
+    for line in support.summary() {
+        info!("Hardware decoding {}", line);
+    }
+    if let Ok(mut current) = SUPPORT.write() {
+        *current = Some(support.clone());
+    }
+    support
+}
+
+#[cfg(feature = "gstreamer")]
+fn probe_registry() -> HardwareDecodeSupport {
+    use gstreamer as gst;
+    use gstreamer::prelude::*;
+
+    if let Err(e) = gst::init() {
+        tracing::warn!("Cannot probe hardware decoders: {}", e);
+        return HardwareDecodeSupport::default();
+    }
+
+    let factories = gst::ElementFactory::factories_with_type(
+        gst::ElementFactoryType::DECODER | gst::ElementFactoryType::MEDIA_VIDEO,
+        gst::Rank::NONE,
+    );
+
+    let mut decoders = Vec::new();
+    for factory in factories {
+        if !factory.klass().contains("Hardware") {
+            continue;
+        }
+        let element = factory.name().to_string();
+        for template in factory.static_pad_templates() {
+            if template.direction() != gst::PadDirection::Sink {
+                continue;
+            }
+            let caps = template.caps();
+            for codec in VideoCodec::ALL {
+                let decodes_codec = caps
+                    .iter()
+                    .any(|structure| structure.has_name(codec.caps_name()));
+                if decodes_codec
+                    && !decoders
+                        .iter()
+                        .any(|d: &HardwareDecoder| d.codec == codec && d.element == element)
+                {
+                    decoders.push(HardwareDecoder {
+                        codec,
+                        api: DecoderApi::from_element(&element),
+                        element: element.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    HardwareDecodeSupport { decoders }
+}
+
+#[cfg(not(feature = "gstreamer"))]
+fn probe_registry() -> HardwareDecodeSupport {
+    HardwareDecodeSupport::default()
+}
+
+/// Transcode to play instead of the original when the machine would have to
+/// decode a stream taller than `max_height` in software
+pub fn software_decode_transcode<'a>(
+    stream_info: &'a StreamInfo,
+    support: &HardwareDecodeSupport,
+    max_height: u32,
+) -> Option<&'a QualityOption> {
+    let codec = VideoCodec::from_stream_codec(&stream_info.video_codec)?;
+    if support.supports(codec) {
+        return None;
+    }
+    metered::reduced_quality_option(stream_info, max_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Resolution, StreamMode};
+
+    fn stream(codec: &str, height: u32) -> StreamInfo {
+        StreamInfo {
+            url: "http://server/original".to_string(),
+            direct_play: true,
+            mode: StreamMode::DirectPlay,
+            video_codec: codec.to_string(),
+            audio_codec: "aac".to_string(),
+            container: "mkv".to_string(),
+            bitrate: 40_000_000,
+            resolution: Resolution {
+                width: height * 16 / 9,
+                height,
+            },
+            quality_options: vec![QualityOption {
+                name: "1080p".to_string(),
+                resolution: Resolution {
+                    width: 1920,
+                    height: 1080,
+                },
+                bitrate: 8_000_000,
+                url: "http://server/transcode".to_string(),
+                requires_transcode: true,
+            }],
+        }
+    }
+
+    fn vaapi(codec: VideoCodec) -> HardwareDecodeSupport {
+        HardwareDecodeSupport {
+            decoders: vec![HardwareDecoder {
+                codec,
+                api: DecoderApi::VaApi,
+                element: "vah265dec".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parses_server_codec_names() {
+        assert_eq!(
+            VideoCodec::from_stream_codec("HEVC"),
+            Some(VideoCodec::Hevc)
+        );
+        assert_eq!(
+            VideoCodec::from_stream_codec("h264"),
+            Some(VideoCodec::H264)
+        );
+        assert_eq!(VideoCodec::from_stream_codec("mpeg2video"), None);
+    }
+
+    #[test]
+    fn test_decoder_api_from_element_name() {
+        assert_eq!(DecoderApi::from_element("vah265dec"), DecoderApi::VaApi);
+        assert_eq!(DecoderApi::from_element("vaapih264dec"), DecoderApi::VaApi);
+        assert_eq!(DecoderApi::from_element("nvav1dec"), DecoderApi::Nvdec);
+        assert_eq!(
+            DecoderApi::from_element("vtdec_hw"),
+            DecoderApi::VideoToolbox
+        );
+    }
+
+    #[test]
+    fn test_transcodes_4k_without_hardware_decoder() {
+        let info = stream("hevc", 2160);
+        let option = software_decode_transcode(
+            &info,
+            &HardwareDecodeSupport::default(),
+            SOFTWARE_DECODE_MAX_HEIGHT,
+        );
+        assert_eq!(
+            option.map(|o| o.url.as_str()),
+            Some("http://server/transcode")
+        );
+    }
+
+    #[test]
+    fn test_direct_plays_with_hardware_decoder_or_low_resolution() {
+        let info = stream("hevc", 2160);
+        assert!(
+            software_decode_transcode(&info, &vaapi(VideoCodec::Hevc), SOFTWARE_DECODE_MAX_HEIGHT)
+                .is_none()
+        );
+
+        let info = stream("hevc", 1080);
+        assert!(
+            software_decode_transcode(
+                &info,
+                &HardwareDecodeSupport::default(),
+                SOFTWARE_DECODE_MAX_HEIGHT
+            )
+            .is_none()
+        );
+    }
+}
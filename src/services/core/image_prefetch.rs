@@ -0,0 +1,114 @@
+//! Artwork to have in the image cache before it is shown
+//!
+//! After a sync, the posters of the home rows and of the first screen of
+//! each library are fetched into the disk cache while the app sits idle, so
+//! those pages show their artwork at once even on a slow connection.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{
+    HomeSectionRepository, HomeSectionRepositoryImpl, LibraryRepository, LibraryRepositoryImpl,
+    MediaRepositoryImpl, Repository,
+};
+use crate::models::SourceId;
+
+/// Items on the first screen of a library, with a row or two to spare
+const LIBRARY_FIRST_SCREEN: u64 = 48;
+
+/// Pure functions for picking the artwork to prefetch
+pub struct ImagePrefetchService;
+
+impl ImagePrefetchService {
+    /// Poster URLs shown first for a source, the home rows before the
+    /// libraries, each URL once. Home rows count up to `home_row_items`.
+    pub async fn poster_urls(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        home_row_items: usize,
+    ) -> Result<Vec<String>> {
+        let home_repo = HomeSectionRepositoryImpl::new(db.clone());
+        let library_repo = LibraryRepositoryImpl::new(db.clone());
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        let mut urls = Vec::new();
+
+        for (_, items) in home_repo
+            .find_by_source_with_items(source_id.as_str())
+            .await?
+        {
+            for item in items.into_iter().take(home_row_items) {
+                // Episodes show the poster of their show on the home page
+                let poster_url = match (item.media_type.as_str(), &item.parent_id) {
+                    ("episode", Some(show_id)) => media_repo
+                        .find_by_id(show_id)
+                        .await?
+                        .and_then(|show| show.poster_url),
+                    _ => item.poster_url,
+                };
+                urls.extend(poster_url);
+            }
+        }
+
+        for library in library_repo.find_by_source(source_id.as_str()).await? {
+            let items = match library_media_type(&library.library_type) {
+                Some(media_type) => {
+                    media_repo
+                        .find_by_library_and_type_paginated(
+                            &library.id,
+                            media_type,
+                            0,
+                            LIBRARY_FIRST_SCREEN,
+                        )
+                        .await?
+                }
+                None => {
+                    media_repo
+                        .find_by_library_paginated(&library.id, 0, LIBRARY_FIRST_SCREEN)
+                        .await?
+                }
+            };
+            urls.extend(items.into_iter().filter_map(|item| item.poster_url));
+        }
+
+        Ok(unique_urls(urls))
+    }
+}
+
+/// Items the library page lists for a type of library, None for all of them
+fn library_media_type(library_type: &str) -> Option<&'static str> {
+    match library_type.to_lowercase().as_str() {
+        "movies" => Some("movie"),
+        "shows" => Some("show"),
+        "music" => Some("album"),
+        _ => None,
+    }
+}
+
+/// URLs in their first order, without blanks and repeats
+fn unique_urls(urls: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    urls.into_iter()
+        .filter(|url| !url.is_empty() && seen.insert(url.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_media_type_follows_library_page() {
+        assert_eq!(library_media_type("movies"), Some("movie"));
+        assert_eq!(library_media_type("Shows"), Some("show"));
+        assert_eq!(library_media_type("music"), Some("album"));
+        assert_eq!(library_media_type("mixed"), None);
+    }
+
+    #[test]
+    fn test_unique_urls_keep_first_order() {
+        let urls = ["b", "a", "", "b", "c", "a"].map(str::to_string).to_vec();
+        assert_eq!(unique_urls(urls), vec!["b", "a", "c"]);
+    }
+}
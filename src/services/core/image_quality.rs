@@ -0,0 +1,184 @@
+//! Size and quality of artwork downloaded from servers
+//!
+//! Posters and backdrops are requested from the server's own image
+//! transcoder, Plex's photo transcode endpoint or Jellyfin's image resizing
+//! parameters, at the size they are shown at times the display's scale
+//! factor, instead of at full size. The quality preference trades sharpness
+//! for bandwidth and image cache size.
+
+use std::sync::atomic::{AtomicU8, AtomicU32, Ordering};
+use url::Url;
+
+use crate::config::ImageQuality;
+
+/// Image qualities offered in preferences, by label
+pub const IMAGE_QUALITIES: [(&str, ImageQuality); 3] = [
+    ("Low", ImageQuality::Low),
+    ("Balanced", ImageQuality::Balanced),
+    ("High", ImageQuality::High),
+];
+
+/// Image quality chosen in preferences, kept in sync with the configuration
+/// by the ConfigService
+static QUALITY: AtomicU8 = AtomicU8::new(ImageQuality::Balanced as u8);
+
+/// Scale factor of the main window's display
+static SCALE_FACTOR: AtomicU32 = AtomicU32::new(1);
+
+pub fn set_quality(quality: ImageQuality) {
+    QUALITY.store(quality as u8, Ordering::Relaxed);
+}
+
+pub fn quality() -> ImageQuality {
+    match QUALITY.load(Ordering::Relaxed) {
+        q if q == ImageQuality::Low as u8 => ImageQuality::Low,
+        q if q == ImageQuality::High as u8 => ImageQuality::High,
+        _ => ImageQuality::Balanced,
+    }
+}
+
+/// Record the scale factor of the display the main window is on
+pub fn set_scale_factor(scale: i32) {
+    SCALE_FACTOR.store(scale.max(1) as u32, Ordering::Relaxed);
+}
+
+impl ImageQuality {
+    /// Pixels per logical pixel of an image on a display with `scale_factor`
+    fn pixel_scale(self, scale_factor: u32) -> u32 {
+        match self {
+            ImageQuality::Low => 1,
+            ImageQuality::Balanced => scale_factor,
+            ImageQuality::High => scale_factor * 2,
+        }
+    }
+
+    /// JPEG quality of transcoded and cached images
+    pub fn jpeg_quality(self) -> u8 {
+        match self {
+            ImageQuality::Low => 70,
+            ImageQuality::Balanced => 85,
+            ImageQuality::High => 95,
+        }
+    }
+}
+
+/// Pixel size to fetch an image shown at `width` x `height` logical pixels
+/// in. A zero dimension is left unconstrained.
+pub fn pixel_size(width: u32, height: u32) -> (u32, u32) {
+    let scale = quality().pixel_scale(SCALE_FACTOR.load(Ordering::Relaxed));
+    (width * scale, height * scale)
+}
+
+/// Part of image cache file names, so a change of quality or display scale
+/// fetches images again instead of showing ones of the old size
+pub fn cache_tag() -> String {
+    let quality = quality();
+    format!(
+        "q{}@{}x",
+        quality.jpeg_quality(),
+        quality.pixel_scale(SCALE_FACTOR.load(Ordering::Relaxed))
+    )
+}
+
+/// The image URL asking the server for an image of `width` x `height` pixels
+/// at the chosen quality. URLs of servers without a known image transcoder
+/// are returned unchanged.
+pub fn sized_url(url: &str, width: u32, height: u32) -> String {
+    sized_url_with_quality(url, width, height, quality().jpeg_quality())
+}
+
+fn sized_url_with_quality(url: &str, width: u32, height: u32, jpeg_quality: u8) -> String {
+    if width == 0 && height == 0 {
+        return url.to_string();
+    }
+    let Ok(mut parsed) = Url::parse(url) else {
+        return url.to_string();
+    };
+
+    let params: Vec<(String, String)> = if parsed.path().ends_with("/photo/:/transcode") {
+        // Plex scales the image to cover the box, so a missing side takes
+        // the other's length
+        let (width, height) = match (width, height) {
+            (0, height) => (height, height),
+            (width, 0) => (width, width),
+            size => size,
+        };
+        vec![
+            ("width".to_string(), width.to_string()),
+            ("height".to_string(), height.to_string()),
+        ]
+    } else if parsed.path().contains("/Images/") {
+        // Jellyfin fits the image within the given maximum sides
+        let mut params = vec![("quality".to_string(), jpeg_quality.to_string())];
+        if width > 0 {
+            params.push(("maxWidth".to_string(), width.to_string()));
+        }
+        if height > 0 {
+            params.push(("maxHeight".to_string(), height.to_string()));
+        }
+        params
+    } else {
+        return url.to_string();
+    };
+
+    let replaced = ["width", "height", "quality", "maxWidth", "maxHeight"];
+    let kept: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !replaced.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    parsed
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(kept)
+        .extend_pairs(params);
+    parsed.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plex_transcode_size_is_replaced() {
+        let url = "http://plex:32400/photo/:/transcode?width=320&height=480&minSize=1&upscale=1&url=%2Flibrary%2Fmetadata%2F1%2Fthumb&X-Plex-Token=abc";
+        let sized = sized_url_with_quality(url, 360, 540, 85);
+        assert!(sized.contains("width=360"));
+        assert!(sized.contains("height=540"));
+        assert!(sized.contains("minSize=1"));
+        assert!(sized.contains("X-Plex-Token=abc"));
+        assert!(!sized.contains("width=320"));
+
+        let backdrop = sized_url_with_quality(url, 0, 550, 85);
+        assert!(backdrop.contains("width=550"));
+        assert!(backdrop.contains("height=550"));
+    }
+
+    #[test]
+    fn test_jellyfin_image_gets_max_size_and_quality() {
+        let url = "http://jellyfin:8096/Items/abc/Images/Primary?tag=123";
+        let sized = sized_url_with_quality(url, 360, 540, 70);
+        assert_eq!(
+            sized,
+            "http://jellyfin:8096/Items/abc/Images/Primary?tag=123&quality=70&maxWidth=360&maxHeight=540"
+        );
+
+        let backdrop = sized_url_with_quality(url, 0, 1100, 70);
+        assert!(backdrop.ends_with("quality=70&maxHeight=1100"));
+    }
+
+    #[test]
+    fn test_other_urls_are_unchanged() {
+        let url = "https://image.tmdb.org/t/p/original/poster.jpg";
+        assert_eq!(sized_url_with_quality(url, 360, 540, 85), url);
+        let jellyfin = "http://jellyfin:8096/Items/abc/Images/Primary?tag=123";
+        assert_eq!(sized_url_with_quality(jellyfin, 0, 0, 85), jellyfin);
+    }
+
+    #[test]
+    fn test_pixel_scale_follows_quality() {
+        assert_eq!(ImageQuality::Low.pixel_scale(2), 1);
+        assert_eq!(ImageQuality::Balanced.pixel_scale(2), 2);
+        assert_eq!(ImageQuality::High.pixel_scale(1), 2);
+    }
+}
@@ -0,0 +1,172 @@
+//! Kids profile: only movies and shows rated for children
+//!
+//! While the profile is on, movies and shows rated above its age, or not
+//! rated at all, are left out of the home page, libraries and search, and
+//! the episodes of left out shows with them. Leaving the profile asks for
+//! the PIN set when it was turned on.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use tracing::error;
+
+use crate::config::KidsConfig;
+use crate::db::connection::DatabaseConnection;
+use crate::db::entities::MediaItemModel;
+use crate::db::repository::MediaRepositoryImpl;
+use crate::services::config_service::config_service;
+
+use super::hidden::HiddenItemsService;
+
+/// Shortest PIN accepted for the kids profile
+pub const MIN_PIN_LENGTH: usize = 4;
+
+/// Pure functions for the kids profile
+pub struct KidsProfileService;
+
+impl KidsProfileService {
+    /// Youngest age a content rating is meant for, `None` for unrated items
+    /// and ratings this doesn't know
+    pub fn rating_age(rating: &str) -> Option<u32> {
+        // Ratings of other countries carry the country, like "gb/15" or "de/12"
+        let rating = rating.rsplit('/').next().unwrap_or(rating);
+        let rating = rating.trim().to_ascii_uppercase();
+        let age = match rating.as_str() {
+            "G" | "TV-Y" | "TV-G" | "U" | "ALL" | "FSK 0" | "FSK-0" => 0,
+            "TV-Y7" | "TV-Y7-FV" => 7,
+            "PG" | "TV-PG" => 8,
+            "PG-13" => 13,
+            "TV-14" => 14,
+            "R" | "TV-MA" => 17,
+            "NC-17" | "X" | "XXX" => 18,
+            "NR" | "UR" | "UNRATED" | "NOT RATED" | "APPROVED" => return None,
+            // Numeric ratings, like "12", "12A", "FSK-16" or "MA15+"
+            other => {
+                let digits: String = other
+                    .chars()
+                    .skip_while(|c| !c.is_ascii_digit())
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect();
+                digits.parse().ok()?
+            }
+        };
+        Some(age)
+    }
+
+    /// Whether an item of a content rating is shown in the kids profile
+    pub fn allows(config: &KidsConfig, rating: Option<&str>) -> bool {
+        rating
+            .and_then(Self::rating_age)
+            .is_some_and(|age| age <= config.max_age)
+    }
+
+    /// IDs of the movies and shows the kids profile leaves out, none while
+    /// it is off. Episodes go by their show.
+    pub async fn blocked_ids(db: &DatabaseConnection) -> Result<HashSet<String>> {
+        let config = config_service().get_config().await.kids;
+        if !config.enabled {
+            return Ok(HashSet::new());
+        }
+
+        // Only the few ratings in use are judged here, the items are matched
+        // to them by the database
+        let repo = MediaRepositoryImpl::new(db.clone());
+        let allowed: Vec<String> = repo
+            .find_content_ratings()
+            .await?
+            .into_iter()
+            .filter(|rating| Self::allows(&config, Some(rating.as_str())))
+            .collect();
+        Ok(repo
+            .find_ids_not_rated(&allowed)
+            .await?
+            .into_iter()
+            .collect())
+    }
+
+    /// Leave out of `items` what the kids profile leaves out. Should that
+    /// fail to be looked up, which only happens while the profile is on,
+    /// every item is left out rather than none.
+    pub async fn retain_allowed(db: &DatabaseConnection, items: &mut Vec<MediaItemModel>) {
+        match Self::blocked_ids(db).await {
+            Ok(blocked) => items.retain(|item| !HiddenItemsService::is_hidden(item, &blocked)),
+            Err(e) => {
+                error!("Failed to apply the kids profile, showing nothing: {}", e);
+                items.clear();
+            }
+        }
+    }
+
+    /// Hash a PIN is kept as in the config
+    pub fn hash_pin(pin: &str) -> String {
+        format!("{:x}", Sha256::digest(format!("reel-kids:{}", pin.trim())))
+    }
+
+    /// Whether `pin` is the PIN of the kids profile
+    pub fn pin_matches(config: &KidsConfig, pin: &str) -> bool {
+        config
+            .pin_hash
+            .as_ref()
+            .is_some_and(|hash| *hash == Self::hash_pin(pin))
+    }
+
+    /// Whether `pin` can be set as the PIN of the kids profile
+    pub fn is_valid_pin(pin: &str) -> bool {
+        let pin = pin.trim();
+        pin.len() >= MIN_PIN_LENGTH && pin.chars().all(|c| c.is_ascii_digit())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rating_ages() {
+        assert_eq!(KidsProfileService::rating_age("G"), Some(0));
+        assert_eq!(KidsProfileService::rating_age("TV-Y7"), Some(7));
+        assert_eq!(KidsProfileService::rating_age("PG"), Some(8));
+        assert_eq!(KidsProfileService::rating_age("PG-13"), Some(13));
+        assert_eq!(KidsProfileService::rating_age("TV-MA"), Some(17));
+        assert_eq!(KidsProfileService::rating_age("gb/15"), Some(15));
+        assert_eq!(KidsProfileService::rating_age("de/12"), Some(12));
+        assert_eq!(KidsProfileService::rating_age("FSK-16"), Some(16));
+        assert_eq!(KidsProfileService::rating_age("MA15+"), Some(15));
+        assert_eq!(KidsProfileService::rating_age("gb/U"), Some(0));
+        assert_eq!(KidsProfileService::rating_age("NR"), None);
+        assert_eq!(KidsProfileService::rating_age("Unknown"), None);
+    }
+
+    #[test]
+    fn test_unrated_and_older_items_are_left_out() {
+        let config = KidsConfig {
+            enabled: true,
+            max_age: 8,
+            ..KidsConfig::default()
+        };
+        assert!(KidsProfileService::allows(&config, Some("TV-Y7")));
+        assert!(KidsProfileService::allows(&config, Some("PG")));
+        assert!(!KidsProfileService::allows(&config, Some("PG-13")));
+        assert!(!KidsProfileService::allows(&config, Some("NR")));
+        assert!(!KidsProfileService::allows(&config, None));
+    }
+
+    #[test]
+    fn test_pin_matches_only_the_pin_set() {
+        let config = KidsConfig {
+            enabled: true,
+            pin_hash: Some(KidsProfileService::hash_pin("1234")),
+            ..KidsConfig::default()
+        };
+        assert!(KidsProfileService::pin_matches(&config, "1234"));
+        assert!(!KidsProfileService::pin_matches(&config, "4321"));
+        assert!(!KidsProfileService::pin_matches(
+            &KidsConfig::default(),
+            "1234"
+        ));
+        assert!(KidsProfileService::is_valid_pin("0000"));
+        assert!(!KidsProfileService::is_valid_pin("123"));
+        assert!(!KidsProfileService::is_valid_pin("12a4"));
+    }
+}
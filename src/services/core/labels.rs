@@ -0,0 +1,103 @@
+//! Labels on movies and shows (Plex)
+//!
+//! Labels are synced into the item's tags, so the library tag filter and the
+//! tag chips of detail pages cover them. Editing sends the change to the
+//! server first and then updates the stored tags, the next sync agrees.
+
+use anyhow::{Context, Result, anyhow};
+use serde_json::Value;
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{
+    MediaRepository, MediaRepositoryImpl, Repository, SourceRepositoryImpl,
+};
+use crate::models::MediaItemId;
+
+use super::backend::BackendService;
+
+/// Pure functions for label operations
+pub struct LabelsService;
+
+impl LabelsService {
+    /// Whether labels of the item can be edited on its server
+    pub async fn can_edit_labels(db: &DatabaseConnection, media_id: &MediaItemId) -> Result<bool> {
+        let Some(item) = MediaRepositoryImpl::new(db.clone())
+            .find_by_id(media_id.as_ref())
+            .await?
+        else {
+            return Ok(false);
+        };
+        if item.media_type != "movie" && item.media_type != "show" {
+            return Ok(false);
+        }
+
+        Ok(SourceRepositoryImpl::new(db.clone())
+            .find_by_id(&item.source_id)
+            .await?
+            .is_some_and(|source| source.is_plex()))
+    }
+
+    /// Put a label on a movie or show, or take it off, on its server and
+    /// locally
+    pub async fn set_label(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+        label: &str,
+        present: bool,
+    ) -> Result<()> {
+        let repo = MediaRepositoryImpl::new(db.clone());
+        let item = repo
+            .find_by_id(media_id.as_ref())
+            .await?
+            .ok_or_else(|| anyhow!("Media item not found: {}", media_id))?;
+
+        BackendService::set_label(db, &item.source_id, media_id, label, present)
+            .await
+            .context("Failed to update labels on server")?;
+
+        let mut metadata = item.metadata.unwrap_or_else(|| serde_json::json!({}));
+        set_tag(&mut metadata, label, present);
+        repo.update_metadata(&item.id, metadata).await
+    }
+}
+
+/// Add `tag` to the stored tags of an item, or drop it
+fn set_tag(metadata: &mut Value, tag: &str, present: bool) {
+    let Some(metadata) = metadata.as_object_mut() else {
+        return;
+    };
+    let tags = metadata
+        .entry("tags")
+        .or_insert_with(|| Value::Array(Vec::new()));
+    let Some(tags) = tags.as_array_mut() else {
+        return;
+    };
+
+    tags.retain(|existing| existing.as_str() != Some(tag));
+    if present {
+        tags.push(Value::String(tag.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_set_tag_adds_once() {
+        let mut metadata = json!({ "studios": ["Test Studios"], "tags": ["Family Night"] });
+        set_tag(&mut metadata, "Rewatch", true);
+        set_tag(&mut metadata, "Rewatch", true);
+        assert_eq!(metadata["tags"], json!(["Family Night", "Rewatch"]));
+        assert_eq!(metadata["studios"], json!(["Test Studios"]));
+    }
+
+    #[test]
+    fn test_set_tag_removes() {
+        let mut metadata = json!({});
+        set_tag(&mut metadata, "Rewatch", true);
+        set_tag(&mut metadata, "Rewatch", false);
+        assert_eq!(metadata["tags"], json!([]));
+    }
+}
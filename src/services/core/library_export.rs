@@ -0,0 +1,373 @@
+//! Exporting a library as a catalog
+//!
+//! A catalog lists the movies and shows of a library with their year,
+//! genres and watched state, for sharing or archiving outside the app. CSV
+//! catalogs are a single file. HTML catalogs are a folder holding a page
+//! that opens in any browser and the artwork thumbnails it shows, fetched
+//! from the server at export time.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use tracing::{debug, info};
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::entities::{MediaItemModel, PlaybackProgressModel};
+use crate::db::repository::{
+    LibraryRepositoryImpl, MediaRepository, MediaRepositoryImpl, Repository,
+};
+use crate::services::core::image_quality;
+use crate::services::core::media::MediaService;
+use crate::services::core::network_policy::network_policy;
+
+/// Size artwork thumbnails are fetched at
+const ARTWORK_SIZE: (u32, u32) = (180, 270);
+
+/// Folder next to the HTML page holding the artwork
+const ARTWORK_DIR: &str = "artwork";
+
+/// File format of a catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A folder with a browsable page and artwork thumbnails
+    Html,
+    /// A spreadsheet-friendly table, without artwork
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Html => "HTML",
+            Self::Csv => "CSV",
+        }
+    }
+}
+
+/// One title of the catalog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogEntry {
+    pub title: String,
+    pub year: Option<i32>,
+    pub kind: String,
+    pub genres: Vec<String>,
+    pub watched: bool,
+    /// Artwork relative to the catalog page, once fetched
+    pub artwork: Option<String>,
+}
+
+/// Stateless service for exporting library catalogs
+pub struct LibraryExportService;
+
+impl LibraryExportService {
+    /// Suggested name of the catalog of a library, a file for CSV and a
+    /// folder for HTML
+    pub fn file_name(library_title: &str, format: ExportFormat) -> String {
+        let safe: String = library_title
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        match format {
+            ExportFormat::Html => format!("{} Catalog", safe),
+            ExportFormat::Csv => format!("{} Catalog.csv", safe),
+        }
+    }
+
+    /// Write the catalog of a library to `path`, reporting each title as it
+    /// is done with the count so far and the total. Returns the file to open,
+    /// the page itself for HTML catalogs.
+    pub async fn export(
+        db: &DatabaseConnection,
+        library_id: &str,
+        format: ExportFormat,
+        path: PathBuf,
+        mut on_progress: impl FnMut(usize, usize),
+    ) -> Result<PathBuf> {
+        let library = LibraryRepositoryImpl::new(db.clone())
+            .find_by_id(library_id)
+            .await?
+            .ok_or_else(|| anyhow!("Library not found: {}", library_id))?;
+        let items: Vec<MediaItemModel> = MediaRepositoryImpl::new(db.clone())
+            .find_by_library(library_id)
+            .await?
+            .into_iter()
+            .filter(|item| !item.is_episode())
+            .collect();
+        let ids: Vec<String> = items.iter().map(|item| item.id.clone()).collect();
+        let progress = MediaService::get_playback_progress_batch(db, &ids).await?;
+
+        let mut entries: Vec<CatalogEntry> = items
+            .iter()
+            .map(|item| catalog_entry(item, &progress))
+            .collect();
+        let total = entries.len();
+        on_progress(0, total);
+
+        let output = match format {
+            ExportFormat::Csv => {
+                write_file(&path, render_csv(&entries)).await?;
+                on_progress(total, total);
+                path
+            }
+            ExportFormat::Html => {
+                let artwork_dir = path.join(ARTWORK_DIR);
+                tokio::fs::create_dir_all(&artwork_dir)
+                    .await
+                    .with_context(|| format!("Failed to create {}", artwork_dir.display()))?;
+
+                let client = network_policy()
+                    .download_client_builder()
+                    .danger_accept_invalid_certs(true) // Plex uses self-signed certs
+                    .build()?;
+                for (index, (item, entry)) in items.iter().zip(entries.iter_mut()).enumerate() {
+                    if let Some(url) = &item.poster_url {
+                        entry.artwork = fetch_artwork(&client, url, &artwork_dir, index).await;
+                    }
+                    on_progress(index + 1, total);
+                }
+
+                let page = path.join("index.html");
+                write_file(&page, render_html(&library.title, &entries)).await?;
+                page
+            }
+        };
+
+        info!(
+            "Exported {} titles of {} as {} to {}",
+            total,
+            library.title,
+            format.label(),
+            output.display()
+        );
+        Ok(output)
+    }
+}
+
+fn catalog_entry(
+    item: &MediaItemModel,
+    progress: &HashMap<String, PlaybackProgressModel>,
+) -> CatalogEntry {
+    // Shows count as watched once every episode is
+    let watched = if item.is_show() {
+        let count = |key: &str| {
+            item.metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get(key))
+                .and_then(|value| value.as_u64())
+                .unwrap_or(0)
+        };
+        let total = count("total_episode_count");
+        total > 0 && count("watched_episode_count") == total
+    } else {
+        progress
+            .get(&item.id)
+            .is_some_and(|progress| progress.watched)
+    };
+
+    CatalogEntry {
+        title: item.title.clone(),
+        year: item.year,
+        kind: item.media_type.clone(),
+        genres: item.get_genres(),
+        watched,
+        artwork: None,
+    }
+}
+
+/// Fetch the artwork of the title at `index`, None when it can't be had.
+/// A catalog without some artwork is still worth having, so failures are
+/// only logged.
+async fn fetch_artwork(
+    client: &reqwest::Client,
+    url: &str,
+    artwork_dir: &Path,
+    index: usize,
+) -> Option<String> {
+    // Frames of local files only exist in the image cache
+    if !url.starts_with("http") {
+        return None;
+    }
+    let (width, height) = ARTWORK_SIZE;
+    let url = image_quality::sized_url(url, width, height);
+
+    let bytes = match client.get(&url).send().await {
+        Ok(response) if response.status().is_success() => response.bytes().await.ok()?,
+        Ok(response) => {
+            debug!("Artwork request returned {}", response.status());
+            return None;
+        }
+        Err(e) => {
+            debug!("Failed to fetch artwork: {}", e);
+            return None;
+        }
+    };
+
+    let name = format!("{}.jpg", index);
+    if let Err(e) = tokio::fs::write(artwork_dir.join(&name), &bytes).await {
+        debug!("Failed to write artwork: {}", e);
+        return None;
+    }
+    Some(format!("{}/{}", ARTWORK_DIR, name))
+}
+
+async fn write_file(path: &Path, contents: String) -> Result<()> {
+    tokio::fs::write(path, contents)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Catalog as CSV with a header row
+pub fn render_csv(entries: &[CatalogEntry]) -> String {
+    let mut csv = String::from("Title,Year,Type,Genres,Watched\n");
+    for entry in entries {
+        let year = entry.year.map(|year| year.to_string()).unwrap_or_default();
+        let row = [
+            csv_field(&entry.title),
+            year,
+            csv_field(&entry.kind),
+            csv_field(&entry.genres.join(", ")),
+            if entry.watched { "yes" } else { "no" }.to_string(),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// A CSV field, quoted when it holds a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Catalog as a standalone HTML page
+pub fn render_html(library_title: &str, entries: &[CatalogEntry]) -> String {
+    let title = html_escape(library_title);
+    let watched = entries.iter().filter(|entry| entry.watched).count();
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2rem; background: #fafafa; color: #222; }}
+.grid {{ display: grid; grid-template-columns: repeat(auto-fill, minmax(180px, 1fr)); gap: 1.5rem; }}
+.title img, .title .placeholder {{ width: 100%; aspect-ratio: 2 / 3; object-fit: cover; border-radius: 6px; background: #ddd; }}
+.title h2 {{ font-size: 1rem; margin: 0.5rem 0 0.25rem; }}
+.title p {{ font-size: 0.85rem; margin: 0; color: #666; }}
+.watched {{ color: #2a7a2a; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p>{count} titles, {watched} watched</p>
+<div class="grid">
+"#,
+        count = entries.len(),
+    );
+
+    for entry in entries {
+        let artwork = match &entry.artwork {
+            Some(path) => format!(r#"<img src="{}" alt="" loading="lazy">"#, html_escape(path)),
+            None => r#"<div class="placeholder"></div>"#.to_string(),
+        };
+        let mut details: Vec<String> = entry
+            .year
+            .map(|year| year.to_string())
+            .into_iter()
+            .collect();
+        if !entry.genres.is_empty() {
+            details.push(entry.genres.join(", "));
+        }
+        let watched = if entry.watched {
+            r#" · <span class="watched">Watched</span>"#
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            html,
+            r#"<div class="title">{}<h2>{}</h2><p>{}{}</p></div>"#,
+            artwork,
+            html_escape(&entry.title),
+            html_escape(&details.join(" · ")),
+            watched
+        );
+    }
+
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(title: &str, watched: bool) -> CatalogEntry {
+        CatalogEntry {
+            title: title.to_string(),
+            year: Some(1999),
+            kind: "movie".to_string(),
+            genres: vec!["Action".to_string(), "Sci-Fi".to_string()],
+            watched,
+            artwork: None,
+        }
+    }
+
+    #[test]
+    fn test_csv_quotes_fields_with_separators() {
+        let csv = render_csv(&[
+            entry("The Matrix", true),
+            entry("Crouching \"Tiger\", Hidden", false),
+        ]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "Title,Year,Type,Genres,Watched");
+        assert_eq!(lines[1], "The Matrix,1999,movie,\"Action, Sci-Fi\",yes");
+        assert_eq!(
+            lines[2],
+            "\"Crouching \"\"Tiger\"\", Hidden\",1999,movie,\"Action, Sci-Fi\",no"
+        );
+    }
+
+    #[test]
+    fn test_html_escapes_titles_and_links_artwork() {
+        let mut with_artwork = entry("<Alien> & Co", true);
+        with_artwork.artwork = Some("artwork/0.jpg".to_string());
+        let html = render_html("Movies & More", &[with_artwork, entry("Heat", false)]);
+
+        assert!(html.contains("<title>Movies &amp; More</title>"));
+        assert!(html.contains("<h2>&lt;Alien&gt; &amp; Co</h2>"));
+        assert!(html.contains(r#"<img src="artwork/0.jpg""#));
+        assert!(html.contains("2 titles, 1 watched"));
+        assert_eq!(html.matches("class=\"watched\"").count(), 1);
+    }
+
+    #[test]
+    fn test_file_names_are_filesystem_safe() {
+        assert_eq!(
+            LibraryExportService::file_name("TV/Kids", ExportFormat::Csv),
+            "TV_Kids Catalog.csv"
+        );
+        assert_eq!(
+            LibraryExportService::file_name("Movies", ExportFormat::Html),
+            "Movies Catalog"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Local users, so each backend user keeps their own watch state
+//!
+//! Several people can share one profile of the app, taking turns signing in
+//! to a source as different Plex Home or Jellyfin users. Resume positions and
+//! watched flags are stored per local user, the pairing of a source with the
+//! backend user signed in to it, and only those of the user currently signed
+//! in are shown. Sources nobody signs in to, like local folders, have no
+//! local user and keep their progress without one. Progress kept from before
+//! anyone signed in to a source goes to the first local user of it.
+
+use anyhow::Result;
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::source_repository::{SourceRepository, SourceRepositoryImpl};
+use crate::db::repository::{
+    MediaRepositoryImpl, PlaybackRepository, PlaybackRepositoryImpl, Repository,
+};
+use crate::models::{MediaItemId, SourceId, UserId};
+
+pub struct LocalUserService;
+
+impl LocalUserService {
+    /// Local user of a backend user signed in to a source
+    pub fn local_user_id(source_id: &SourceId, backend_user_id: &str) -> UserId {
+        UserId::new(format!("{}:{}", source_id, backend_user_id))
+    }
+
+    /// Remember the backend user signed in to a source, returning whether
+    /// that switched to another user
+    pub async fn set_active_user(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        backend_user_id: &str,
+    ) -> Result<bool> {
+        let repo = SourceRepositoryImpl::new(db.clone());
+        let Some(source) = repo.find_by_id(source_id.as_str()).await? else {
+            return Ok(false);
+        };
+
+        let user_id = Self::local_user_id(source_id, backend_user_id);
+        if source.active_user_id.as_deref() == Some(user_id.as_str()) {
+            return Ok(false);
+        }
+        // Adopted first, so a failure leaves the source without a user and the
+        // progress is adopted on the next attempt instead of being orphaned
+        if source.active_user_id.is_none() {
+            let adopted = PlaybackRepositoryImpl::new(db.clone())
+                .adopt_progress_without_user(source_id.as_str(), user_id.as_str())
+                .await?;
+            if adopted > 0 {
+                tracing::info!(
+                    "Gave {} progress records from before local users to {}",
+                    adopted,
+                    user_id
+                );
+            }
+        }
+
+        repo.update_active_user(source_id.as_str(), Some(user_id.to_string()))
+            .await?;
+        Ok(source.active_user_id.is_some())
+    }
+
+    /// Local user whose watch state a source shows
+    pub async fn active_user(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+    ) -> Result<Option<UserId>> {
+        let repo = SourceRepositoryImpl::new(db.clone());
+        Ok(repo
+            .find_by_id(source_id.as_str())
+            .await?
+            .and_then(|source| source.active_user_id)
+            .map(UserId::new))
+    }
+
+    /// Local user whose watch state an item shows, that of its source
+    pub async fn active_user_for_item(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> Result<Option<UserId>> {
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        match media_repo.find_by_id(media_id.as_ref()).await? {
+            Some(item) => Self::active_user(db, &SourceId::new(item.source_id)).await,
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_user_depends_on_source_and_backend_user() {
+        let plex = SourceId::new("plex-1");
+        assert_eq!(
+            LocalUserService::local_user_id(&plex, "42"),
+            LocalUserService::local_user_id(&plex, "42")
+        );
+        assert_ne!(
+            LocalUserService::local_user_id(&plex, "42"),
+            LocalUserService::local_user_id(&plex, "43")
+        );
+        assert_ne!(
+            LocalUserService::local_user_id(&plex, "42"),
+            LocalUserService::local_user_id(&SourceId::new("plex-2"), "42")
+        );
+    }
+}
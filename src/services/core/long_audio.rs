@@ -0,0 +1,232 @@
+//! Audiobooks and podcasts
+//!
+//! Audio tracks long enough to be listened to over several sittings save
+//! their position more often than video, remember which of their chapters
+//! were listened to the end, and jump back a little when resumed after a
+//! long pause, so the listener picks the thread up again.
+
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::config::LongAudioConfig;
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{PlaybackRepository, PlaybackRepositoryImpl};
+use crate::models::{MediaItemId, UserId};
+use crate::services::core::LocalUserService;
+
+/// Audio tracks at least this long count as audiobooks or podcasts
+pub const LONG_AUDIO_MIN_DURATION: Duration = Duration::from_secs(20 * 60);
+
+/// A chapter counts as listened to once played to this close to its end
+const CHAPTER_END_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// Positions further apart than this, even at the fastest speed, were
+/// reached by seeking rather than by listening
+const MAX_PLAYBACK_STEP: Duration = Duration::from_secs(10);
+
+pub struct LongAudioService;
+
+impl LongAudioService {
+    /// Whether an item of `media_type` lasting `duration` is long-form audio
+    pub fn is_long_audio(media_type: &str, duration: Duration) -> bool {
+        media_type == "track" && duration >= LONG_AUDIO_MIN_DURATION
+    }
+
+    /// Where to resume from `position` after a pause of `paused_for`
+    pub fn snap_back(
+        config: &LongAudioConfig,
+        position: Duration,
+        paused_for: Duration,
+    ) -> Duration {
+        let after = Duration::from_secs(config.snap_back_after_minutes as u64 * 60);
+        if paused_for < after {
+            return position;
+        }
+        position.saturating_sub(Duration::from_secs(config.snap_back_seconds as u64))
+    }
+
+    /// Starts of the chapters whose end playback went through from `from`
+    /// to `to`. Skipping past the end of a chapter doesn't count.
+    pub fn chapters_played_through(
+        chapter_starts: &[Duration],
+        duration: Duration,
+        from: Duration,
+        to: Duration,
+    ) -> Vec<Duration> {
+        if to < from || to - from > MAX_PLAYBACK_STEP {
+            return Vec::new();
+        }
+        let before = finished_chapters(chapter_starts, duration, from);
+        finished_chapters(chapter_starts, duration, to)
+            .into_iter()
+            .filter(|start| !before.contains(start))
+            .collect()
+    }
+
+    /// Where playback is among the chapters, like "Chapter 4 of 12 · 3
+    /// listened", None for items without chapters
+    pub fn chapter_summary(
+        chapter_starts: &[Duration],
+        finished: &[Duration],
+        position: Duration,
+    ) -> Option<String> {
+        if chapter_starts.len() < 2 {
+            return None;
+        }
+        let current = chapter_starts
+            .iter()
+            .rposition(|start| *start <= position)
+            .unwrap_or(0);
+        let listened = chapter_starts
+            .iter()
+            .filter(|start| finished.contains(start))
+            .count();
+        Some(format!(
+            "Chapter {} of {} · {} listened",
+            current + 1,
+            chapter_starts.len(),
+            listened
+        ))
+    }
+
+    /// Chapters of an item the active user listened to the end
+    pub async fn completed_chapters(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> Result<Vec<Duration>> {
+        let repo = PlaybackRepositoryImpl::new(db.clone());
+        // Read the row of the same user the chapters are saved for
+        let progress = match LocalUserService::active_user_for_item(db, media_id).await? {
+            Some(user_id) => {
+                repo.find_by_media_and_user(media_id.as_ref(), user_id.as_str())
+                    .await?
+            }
+            None => repo.find_by_media_id(media_id.as_ref()).await?,
+        };
+        Ok(progress
+            .map(|progress| progress.get_completed_chapters())
+            .unwrap_or_default())
+    }
+
+    /// Remember the chapters of an item the active user listened to the end
+    pub async fn save_completed_chapters(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+        chapter_starts: &[Duration],
+    ) -> Result<()> {
+        let repo = PlaybackRepositoryImpl::new(db.clone());
+        let user_id = LocalUserService::active_user_for_item(db, media_id).await?;
+        let starts_ms: Vec<u64> = chapter_starts
+            .iter()
+            .map(|start| start.as_millis() as u64)
+            .collect();
+        repo.update_completed_chapters(
+            media_id.as_ref(),
+            user_id.as_ref().map(UserId::as_str),
+            &starts_ms,
+        )
+        .await
+    }
+}
+
+/// Starts of the chapters played to their end at `position`. A chapter ends
+/// where the next one starts, the last one with the item.
+fn finished_chapters(
+    chapter_starts: &[Duration],
+    duration: Duration,
+    position: Duration,
+) -> Vec<Duration> {
+    let ends = chapter_starts
+        .iter()
+        .skip(1)
+        .copied()
+        .chain(std::iter::once(duration));
+    chapter_starts
+        .iter()
+        .zip(ends)
+        .filter(|(_, end)| position + CHAPTER_END_TOLERANCE >= *end)
+        .map(|(start, _)| *start)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_only_long_tracks_are_long_audio() {
+        assert!(LongAudioService::is_long_audio("track", secs(3600)));
+        assert!(!LongAudioService::is_long_audio("track", secs(240)));
+        assert!(!LongAudioService::is_long_audio("movie", secs(7200)));
+    }
+
+    #[test]
+    fn test_snap_back_only_after_long_pauses() {
+        let config = LongAudioConfig::default();
+        assert_eq!(
+            LongAudioService::snap_back(&config, secs(600), secs(30)),
+            secs(600)
+        );
+        assert_eq!(
+            LongAudioService::snap_back(&config, secs(600), secs(3600)),
+            secs(585)
+        );
+        assert_eq!(
+            LongAudioService::snap_back(&config, secs(5), secs(3600)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_chapters_finish_where_the_next_starts() {
+        let chapters = [secs(0), secs(600), secs(1200)];
+        assert_eq!(
+            finished_chapters(&chapters, secs(1800), secs(599)),
+            vec![secs(0)]
+        );
+        assert_eq!(
+            finished_chapters(&chapters, secs(1800), secs(900)),
+            vec![secs(0)]
+        );
+        assert_eq!(
+            finished_chapters(&chapters, secs(1800), secs(1800)),
+            chapters.to_vec()
+        );
+    }
+
+    #[test]
+    fn test_only_listening_through_a_chapter_end_counts() {
+        let chapters = [secs(0), secs(600), secs(1200)];
+        assert_eq!(
+            LongAudioService::chapters_played_through(&chapters, secs(1800), secs(597), secs(598)),
+            vec![secs(0)]
+        );
+        assert!(
+            LongAudioService::chapters_played_through(&chapters, secs(1800), secs(598), secs(599))
+                .is_empty()
+        );
+        // Seeking past the end of chapters
+        assert!(
+            LongAudioService::chapters_played_through(&chapters, secs(1800), secs(10), secs(1300))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_chapter_summary_counts_listened_chapters() {
+        let chapters = [secs(0), secs(600), secs(1200)];
+        assert_eq!(
+            LongAudioService::chapter_summary(&chapters, &[secs(0)], secs(700)).as_deref(),
+            Some("Chapter 2 of 3 · 1 listened")
+        );
+        assert_eq!(
+            LongAudioService::chapter_summary(&[secs(0)], &[], secs(10)),
+            None
+        );
+    }
+}
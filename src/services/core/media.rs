@@ -3,14 +3,18 @@ use tracing::{debug, warn};
 
 use crate::db::{
     connection::DatabaseConnection,
-    entities::{LibraryModel, SyncChangeType},
+    entities::{LibraryModel, MediaItemModel, SyncChangeType},
     repository::{
         LibraryRepository, LibraryRepositoryImpl, MediaRepository, MediaRepositoryImpl,
         PeopleRepository, PlaybackRepository, PlaybackRepositoryImpl, PlaybackSyncRepository,
-        PlaybackSyncRepositoryImpl, Repository,
+        PlaybackSyncRepositoryImpl, Repository, playback_repository::active_user_progress,
     },
 };
-use crate::models::{Library, LibraryId, MediaItem, MediaItemId, MediaType, ShowId, SourceId};
+use crate::models::{
+    Episode, Library, LibraryId, MediaItem, MediaItemId, MediaType, SeasonProgress, ShowId,
+    SourceId, UserId,
+};
+use crate::services::core::{HiddenItemsService, LocalUserService};
 
 /// Pure functions for media operations
 /// No state, no Arc<Self>, just functions that operate on data
@@ -228,6 +232,8 @@ impl MediaService {
     ) -> Result<()> {
         let repo = MediaRepositoryImpl::new(db.clone());
         let playback_repo = PlaybackRepositoryImpl::new(db.clone());
+        // Watch state from the server is that of the user signed in to it
+        let user_id = LocalUserService::active_user(db, source_id).await?;
 
         // Convert to entity using the new mapper
         let mut entity = item.to_model(source_id.as_str(), Some(library_id.to_string()));
@@ -357,7 +363,7 @@ impl MediaService {
                         let progress = crate::db::entities::PlaybackProgressModel {
                             id: 0, // Will be auto-generated
                             media_id: movie.id.clone(),
-                            user_id: None,
+                            user_id: user_id.as_ref().map(UserId::to_string),
                             position_ms,
                             duration_ms,
                             watched: movie.watched,
@@ -368,6 +374,7 @@ impl MediaService {
                             play_queue_version: None,
                             play_queue_item_id: None,
                             source_id: None,
+                            completed_chapters: None,
                         };
                         playback_repo.insert(progress).await?;
                     }
@@ -398,7 +405,7 @@ impl MediaService {
                         let progress = crate::db::entities::PlaybackProgressModel {
                             id: 0, // Will be auto-generated
                             media_id: episode.id.clone(),
-                            user_id: None,
+                            user_id: user_id.as_ref().map(UserId::to_string),
                             position_ms,
                             duration_ms,
                             watched: episode.watched,
@@ -409,6 +416,7 @@ impl MediaService {
                             play_queue_version: None,
                             play_queue_item_id: None,
                             source_id: None,
+                            completed_chapters: None,
                         };
                         playback_repo.insert(progress).await?;
                     }
@@ -439,6 +447,8 @@ impl MediaService {
 
         let repo = MediaRepositoryImpl::new(db.clone());
         let playback_repo = PlaybackRepositoryImpl::new(db.clone());
+        // Watch state from the server is that of the user signed in to it
+        let user_id = LocalUserService::active_user(db, source_id).await?;
 
         // Collect playback progress updates for batch operation
         let mut progress_updates = Vec::new();
@@ -495,7 +505,7 @@ impl MediaService {
 
                         progress_updates.push((
                             movie.id.clone(),
-                            None, // user_id
+                            user_id.as_ref().map(UserId::to_string),
                             position_ms,
                             duration_ms,
                             movie.watched,
@@ -517,7 +527,7 @@ impl MediaService {
 
                         progress_updates.push((
                             episode.id.clone(),
-                            None, // user_id
+                            user_id.as_ref().map(UserId::to_string),
                             position_ms,
                             duration_ms,
                             episode.watched,
@@ -627,6 +637,22 @@ impl MediaService {
         Self::get_recently_added(db, limit).await
     }
 
+    /// Random movies and shows with backdrop artwork, leaving out hidden ones
+    pub async fn get_backdrop_items(
+        db: &DatabaseConnection,
+        limit: u64,
+    ) -> Result<Vec<MediaItemModel>> {
+        let items = MediaRepositoryImpl::new(db.clone())
+            .find_random_with_backdrop(limit)
+            .await
+            .context("Failed to get items with backdrops")?;
+        let hidden_ids = HiddenItemsService::hidden_ids(db).await?;
+        Ok(items
+            .into_iter()
+            .filter(|item| !HiddenItemsService::is_hidden(item, &hidden_ids))
+            .collect())
+    }
+
     /// Get playback progress for multiple media items in batch
     pub async fn get_playback_progress_batch(
         db: &DatabaseConnection,
@@ -637,6 +663,7 @@ impl MediaService {
 
         let progress_records = PlaybackProgress::find()
             .filter(playback_progress::Column::MediaId.is_in(media_ids.to_vec()))
+            .filter(active_user_progress())
             .all(db.as_ref())
             .await
             .context("Failed to fetch playback progress batch")?;
@@ -649,6 +676,88 @@ impl MediaService {
         Ok(progress_map)
     }
 
+    /// Count the unwatched movies and episodes of a library
+    pub async fn get_unwatched_count(
+        db: &DatabaseConnection,
+        library_id: &LibraryId,
+    ) -> Result<u64> {
+        let repo = MediaRepositoryImpl::new(db.clone());
+        repo.count_unwatched_by_library(library_id.as_ref())
+            .await
+            .context("Failed to count unwatched items")
+    }
+
+    /// Count the unwatched episodes of each of the given shows
+    pub async fn get_unwatched_episode_counts(
+        db: &DatabaseConnection,
+        show_ids: &[String],
+    ) -> Result<std::collections::HashMap<String, u64>> {
+        let repo = MediaRepositoryImpl::new(db.clone());
+        repo.count_unwatched_episodes_by_show(show_ids)
+            .await
+            .context("Failed to count unwatched episodes")
+    }
+
+    /// Watched episode counts for each season of a show, keyed by season number
+    pub async fn get_season_progress(
+        db: &DatabaseConnection,
+        show_id: &ShowId,
+    ) -> Result<std::collections::HashMap<u32, SeasonProgress>> {
+        let repo = MediaRepositoryImpl::new(db.clone());
+        let counts = repo
+            .count_season_progress_by_show(show_id.as_ref())
+            .await
+            .context("Failed to count season progress")?;
+
+        Ok(counts
+            .into_iter()
+            .map(|(season, (watched, total))| (season, SeasonProgress { watched, total }))
+            .collect())
+    }
+
+    /// Recount the unwatched items of the library holding a media item.
+    ///
+    /// Returns the library ID with its new count, or None if the item is unknown.
+    pub async fn get_unwatched_count_for_item(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> Result<Option<(LibraryId, u64)>> {
+        let repo = MediaRepositoryImpl::new(db.clone());
+        let Some(item) = repo.find_by_id(media_id.as_ref()).await? else {
+            return Ok(None);
+        };
+
+        let library_id = LibraryId::new(item.library_id);
+        let count = Self::get_unwatched_count(db, &library_id).await?;
+        Ok(Some((library_id, count)))
+    }
+
+    /// Recount the unwatched episodes of the show a media item belongs to.
+    ///
+    /// Returns the show ID with its new count, or None if the item is not part of a show.
+    pub async fn get_unwatched_count_for_show_of(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> Result<Option<(MediaItemId, u64)>> {
+        let repo = MediaRepositoryImpl::new(db.clone());
+        let Some(item) = repo.find_by_id(media_id.as_ref()).await? else {
+            return Ok(None);
+        };
+
+        let show_id = match item.media_type.as_str() {
+            "show" => item.id,
+            "episode" => match item.parent_id {
+                Some(parent_id) => parent_id,
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        let counts = Self::get_unwatched_episode_counts(db, std::slice::from_ref(&show_id)).await?;
+        let count = counts.get(&show_id).copied().unwrap_or(0);
+        Ok(Some((MediaItemId::new(show_id), count)))
+    }
+
     /// Get continue watching items
     pub async fn get_continue_watching(
         db: &DatabaseConnection,
@@ -659,7 +768,7 @@ impl MediaService {
 
         // Get items with progress
         let progress_items = playback_repo
-            .find_in_progress(None) // Use None for single-user system
+            .find_in_progress()
             .await
             .context("Failed to get in-progress items")?;
 
@@ -785,6 +894,58 @@ impl MediaService {
         Ok(episodes)
     }
 
+    /// Next episode to watch in a show, following the servers' Next Up logic.
+    ///
+    /// Resumes the most recently played episode if it's unfinished, otherwise
+    /// picks the first unwatched episode after it. Shows that haven't been
+    /// started begin at the first regular episode. Specials are skipped and
+    /// None means the show is caught up.
+    pub async fn get_next_up_episode(
+        db: &DatabaseConnection,
+        show_id: &ShowId,
+    ) -> Result<Option<Episode>> {
+        let repo = MediaRepositoryImpl::new(db.clone());
+
+        let (season, episode) = match repo
+            .find_last_played_episode(show_id.as_str())
+            .await
+            .context("Failed to find last played episode")?
+        {
+            Some((model, progress)) if !progress.watched && progress.position_ms > 0 => {
+                return Self::episode_from_model(db, model).await;
+            }
+            Some((model, _)) => (
+                model.season_number.unwrap_or(1),
+                model.episode_number.unwrap_or(0),
+            ),
+            None => (1, 0),
+        };
+
+        match repo
+            .find_next_unwatched_episode(show_id.as_str(), season, episode)
+            .await
+            .context("Failed to find next unwatched episode")?
+        {
+            Some(model) => Self::episode_from_model(db, model).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn episode_from_model(
+        db: &DatabaseConnection,
+        model: crate::db::entities::media_items::Model,
+    ) -> Result<Option<Episode>> {
+        let enriched = Self::enrich_with_playback_progress(db, vec![model]).await?;
+        Ok(enriched
+            .into_iter()
+            .next()
+            .and_then(|model| MediaItem::try_from(model).ok())
+            .and_then(|item| match item {
+                MediaItem::Episode(episode) => Some(episode),
+                _ => None,
+            }))
+    }
+
     /// Clear all media for a library
     pub async fn clear_library(db: &DatabaseConnection, library_id: &LibraryId) -> Result<()> {
         let repo = MediaRepositoryImpl::new(db.clone());
@@ -814,12 +975,13 @@ impl MediaService {
         watched: bool,
     ) -> Result<()> {
         let repo = PlaybackRepositoryImpl::new(db.clone());
+        let user_id = LocalUserService::active_user_for_item(db, media_id).await?;
+        let user_id = user_id.as_ref().map(UserId::as_str);
 
-        // Use None for user_id for now (single-user system)
         if watched {
-            repo.mark_watched(media_id.as_ref(), None).await?;
+            repo.mark_watched(media_id.as_ref(), user_id).await?;
         } else {
-            repo.upsert_progress(media_id.as_ref(), None, position_ms, duration_ms)
+            repo.upsert_progress(media_id.as_ref(), user_id, position_ms, duration_ms)
                 .await?;
         }
 
@@ -845,7 +1007,7 @@ impl MediaService {
                     .enqueue_change(
                         media_id.as_ref(),
                         source_id,
-                        None, // user_id (single-user system)
+                        user_id,
                         change_type.clone(),
                         pos_ms,
                         Some(watched),
@@ -880,9 +1042,11 @@ impl MediaService {
     /// Mark a media item as watched
     pub async fn mark_watched(db: &DatabaseConnection, media_id: &MediaItemId) -> Result<()> {
         let repo = PlaybackRepositoryImpl::new(db.clone());
+        let user_id = LocalUserService::active_user_for_item(db, media_id).await?;
+        let user_id = user_id.as_ref().map(UserId::as_str);
 
-        // Mark as watched in database (None for user_id for single-user system)
-        repo.mark_watched(media_id.as_ref(), None).await?;
+        // Mark as watched in database for the user signed in to its source
+        repo.mark_watched(media_id.as_ref(), user_id).await?;
 
         // Enqueue sync change to be processed by PlaybackSyncWorker
         let media_repo = MediaRepositoryImpl::new(db.clone());
@@ -902,7 +1066,7 @@ impl MediaService {
                     .enqueue_change(
                         media_id.as_ref(),
                         source_id,
-                        None, // user_id (single-user system)
+                        user_id,
                         SyncChangeType::MarkWatched,
                         None,       // position_ms not needed for mark_watched
                         Some(true), // completed = true
@@ -943,9 +1107,11 @@ impl MediaService {
     /// Mark a media item as unwatched
     pub async fn mark_unwatched(db: &DatabaseConnection, media_id: &MediaItemId) -> Result<()> {
         let repo = PlaybackRepositoryImpl::new(db.clone());
+        let user_id = LocalUserService::active_user_for_item(db, media_id).await?;
+        let user_id = user_id.as_ref().map(UserId::as_str);
 
-        // Mark as unwatched in database (None for user_id for single-user system)
-        repo.mark_unwatched(media_id.as_ref(), None).await?;
+        // Mark as unwatched in database for the user signed in to its source
+        repo.mark_unwatched(media_id.as_ref(), user_id).await?;
 
         // Enqueue sync change to be processed by PlaybackSyncWorker
         let media_repo = MediaRepositoryImpl::new(db.clone());
@@ -965,7 +1131,7 @@ impl MediaService {
                     .enqueue_change(
                         media_id.as_ref(),
                         source_id,
-                        None, // user_id (single-user system)
+                        user_id,
                         SyncChangeType::MarkUnwatched,
                         None,        // position_ms not needed for mark_unwatched
                         Some(false), // completed = false
@@ -1097,6 +1263,56 @@ impl MediaService {
 
         Ok(())
     }
+
+    /// Mark the episodes of a show before the given one as watched, earlier
+    /// seasons included, returning those that were not watched yet
+    pub async fn mark_previous_episodes_watched(
+        db: &DatabaseConnection,
+        show_id: &ShowId,
+        season_number: u32,
+        episode_number: u32,
+    ) -> Result<Vec<MediaItemId>> {
+        let episodes: Vec<Episode> = Self::get_episodes_for_show(db, show_id, None)
+            .await?
+            .into_iter()
+            .filter_map(|item| match item {
+                MediaItem::Episode(episode) => Some(episode),
+                _ => None,
+            })
+            .collect();
+
+        let previous = unwatched_before(&episodes, season_number, episode_number);
+        tracing::info!(
+            "mark_previous_episodes_watched: show_id={}, before S{}E{}, marking {} episodes",
+            show_id.as_ref(),
+            season_number,
+            episode_number,
+            previous.len()
+        );
+        for media_id in &previous {
+            Self::mark_watched(db, media_id).await?;
+        }
+
+        Ok(previous)
+    }
+}
+
+/// Unwatched episodes airing before episode `episode_number` of season
+/// `season_number`. Specials are left alone unless catching up within them.
+fn unwatched_before(
+    episodes: &[Episode],
+    season_number: u32,
+    episode_number: u32,
+) -> Vec<MediaItemId> {
+    episodes
+        .iter()
+        .filter(|episode| !episode.watched)
+        .filter(|episode| episode.season_number > 0 || season_number == 0)
+        .filter(|episode| {
+            (episode.season_number, episode.episode_number) < (season_number, episode_number)
+        })
+        .map(|episode| MediaItemId::new(&episode.id))
+        .collect()
 }
 
 #[cfg(test)]
@@ -1147,6 +1363,10 @@ mod tests {
             connection_quality: Set(None),
             auth_status: Set("authenticated".to_string()),
             last_auth_check: Set(None),
+            mac_address: Set(None),
+            client_identity: Set(None),
+            active_user_id: Set(None),
+            connection_preferences: Set(None),
             created_at: Set(Utc::now().naive_utc()),
             updated_at: Set(Utc::now().naive_utc()),
         };
@@ -1276,6 +1496,10 @@ mod tests {
             connection_quality: Set(None),
             auth_status: Set("authenticated".to_string()),
             last_auth_check: Set(None),
+            mac_address: Set(None),
+            client_identity: Set(None),
+            active_user_id: Set(None),
+            connection_preferences: Set(None),
             created_at: Set(Utc::now().naive_utc()),
             updated_at: Set(Utc::now().naive_utc()),
         };
@@ -1615,6 +1839,7 @@ mod tests {
             show_poster_url: Some("https://example.com/show.jpg".to_string()),
             intro_marker: None,
             credits_marker: None,
+            video_height: None,
         }
     }
 
@@ -1977,4 +2202,101 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_progress_follows_active_user() -> Result<()> {
+        use crate::services::core::LocalUserService;
+        use crate::services::core::playback::PlaybackService;
+
+        let db = setup_test_database().await?;
+        let db_conn = db.get_connection();
+
+        let lib_repo = LibraryRepositoryImpl::new(db_conn.clone());
+        lib_repo
+            .insert(LibraryModel {
+                id: "test-lib".to_string(),
+                source_id: "test-source".to_string(),
+                title: "Test Library".to_string(),
+                library_type: "movie".to_string(),
+                icon: None,
+                item_count: 0,
+                created_at: Utc::now().naive_utc(),
+                updated_at: Utc::now().naive_utc(),
+            })
+            .await?;
+        let media_repo = MediaRepositoryImpl::new(db_conn.clone());
+        media_repo
+            .insert(create_test_movie_model("movie-1", "Movie One", "test-lib"))
+            .await?;
+
+        let source_id = SourceId::from("test-source");
+        let library_id = LibraryId::from("test-lib");
+        let movie_id = MediaItemId::from("movie-1");
+
+        assert!(!LocalUserService::set_active_user(&db_conn, &source_id, "alice").await?);
+        MediaService::update_playback_progress(&db_conn, &movie_id, 60_000, 7_200_000, false)
+            .await?;
+
+        // Another user of the same source starts from scratch
+        assert!(LocalUserService::set_active_user(&db_conn, &source_id, "bob").await?);
+        assert!(
+            PlaybackService::get_progress(&db_conn, &movie_id)
+                .await?
+                .is_none()
+        );
+        MediaService::mark_watched(&db_conn, &movie_id).await?;
+        assert_eq!(
+            MediaService::get_unwatched_count(&db_conn, &library_id).await?,
+            0
+        );
+
+        // Switching back shows the first user's progress again
+        LocalUserService::set_active_user(&db_conn, &source_id, "alice").await?;
+        let progress = PlaybackService::get_progress(&db_conn, &movie_id)
+            .await?
+            .expect("progress of the first user");
+        assert_eq!(progress.position_ms, 60_000);
+        assert!(!progress.watched);
+        assert_eq!(
+            MediaService::get_unwatched_count(&db_conn, &library_id).await?,
+            1
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unwatched_before_skips_watched_episodes_and_specials() {
+        let mut watched = create_test_episode_domain("s1e1", "show", 1, 1, "Pilot");
+        watched.watched = true;
+        let episodes = vec![
+            create_test_episode_domain("s0e1", "show", 0, 1, "Special"),
+            watched,
+            create_test_episode_domain("s1e2", "show", 1, 2, "Second"),
+            create_test_episode_domain("s2e1", "show", 2, 1, "Premiere"),
+            create_test_episode_domain("s2e2", "show", 2, 2, "Follow-up"),
+            create_test_episode_domain("s2e3", "show", 2, 3, "Later"),
+        ];
+
+        let ids: Vec<String> = unwatched_before(&episodes, 2, 2)
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        assert_eq!(ids, vec!["s1e2", "s2e1"]);
+    }
+
+    #[test]
+    fn test_unwatched_before_within_specials() {
+        let episodes = vec![
+            create_test_episode_domain("s0e1", "show", 0, 1, "Special"),
+            create_test_episode_domain("s0e2", "show", 0, 2, "Another Special"),
+            create_test_episode_domain("s1e1", "show", 1, 1, "Pilot"),
+        ];
+
+        let ids: Vec<String> = unwatched_before(&episodes, 0, 2)
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        assert_eq!(ids, vec!["s0e1"]);
+    }
 }
@@ -9,6 +9,8 @@
 //! 2. If stale, queue a refresh using `queue_library_refresh()` or `queue_items_refresh()`
 //! 3. Workers listen for `MetadataRefreshMessage` and process requests
 //! 4. After refresh, the cache is updated with new `fetched_at` timestamps
+//!
+//! Refreshes are not queued while the app is offline; cached data is served as-is.
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -19,6 +21,7 @@ use crate::db::connection::DatabaseConnection;
 use crate::db::repository::{MediaRepository, MediaRepositoryImpl};
 use crate::models::{LibraryId, MediaItem, MediaItemId, SourceId};
 use crate::services::core::cache_config::{CacheConfig, ContentType, cache_config};
+use crate::services::core::connectivity;
 use crate::services::core::media::MediaService;
 use crate::ui::shared::broker::{BROKER, BrokerMessage, MetadataRefreshMessage, RefreshPriority};
 
@@ -60,6 +63,11 @@ impl MetadataRefreshService {
         library_id: &LibraryId,
         priority: RefreshPriority,
     ) -> Result<()> {
+        if connectivity::is_offline() {
+            debug!("Offline, serving library {} from cache", library_id);
+            return Ok(());
+        }
+
         info!(
             "Queueing library refresh: source={}, library={}, priority={:?}",
             source_id, library_id, priority
@@ -86,7 +94,7 @@ impl MetadataRefreshService {
         item_ids: &[MediaItemId],
         priority: RefreshPriority,
     ) -> Result<()> {
-        if item_ids.is_empty() {
+        if item_ids.is_empty() || connectivity::is_offline() {
             return Ok(());
         }
 
@@ -118,6 +126,11 @@ impl MetadataRefreshService {
         source_id: &SourceId,
         item_id: &MediaItemId,
     ) -> Result<()> {
+        if connectivity::is_offline() {
+            debug!("Offline, serving item {} from cache", item_id);
+            return Ok(());
+        }
+
         debug!(
             "Queueing item metadata refresh: source={}, item={}",
             source_id, item_id
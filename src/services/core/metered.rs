@@ -0,0 +1,158 @@
+//! Metered connection awareness
+//!
+//! The network monitor (NetworkManager, or the network portal inside Flatpak)
+//! reports whether the active connection is metered. While it is, the user
+//! configured restrictions in the network preferences apply, unless they chose
+//! to ignore them for the current session.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::info;
+
+use crate::config::NetworkConfig;
+use crate::models::{QualityOption, StreamInfo};
+
+use super::network_policy::network_policy;
+
+/// Highest video resolution requested while streaming on a metered connection
+pub const METERED_MAX_STREAM_HEIGHT: u32 = 720;
+
+/// Whether the active network connection is metered
+static METERED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the user chose to ignore metered restrictions for this session
+static OVERRIDDEN: AtomicBool = AtomicBool::new(false);
+
+/// What to hold back while on a metered connection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MeteredRestrictions {
+    pub pause_downloads: bool,
+    pub skip_image_prefetch: bool,
+    pub lower_stream_quality: bool,
+}
+
+impl From<&NetworkConfig> for MeteredRestrictions {
+    fn from(config: &NetworkConfig) -> Self {
+        Self {
+            pause_downloads: config.metered_pause_downloads,
+            skip_image_prefetch: config.metered_skip_image_prefetch,
+            lower_stream_quality: config.metered_lower_stream_quality,
+        }
+    }
+}
+
+impl MeteredRestrictions {
+    /// Whether any restriction is enabled
+    pub fn any(&self) -> bool {
+        self.pause_downloads || self.skip_image_prefetch || self.lower_stream_quality
+    }
+}
+
+/// Record the metered state reported by the network monitor.
+///
+/// Returns true if the state changed. Leaving a metered network clears the
+/// session override so the restrictions apply again on the next one.
+pub fn set_metered(metered: bool) -> bool {
+    let changed = METERED.swap(metered, Ordering::Relaxed) != metered;
+    if changed {
+        info!(
+            "Network connection is now {}",
+            if metered { "metered" } else { "unmetered" }
+        );
+        if !metered {
+            OVERRIDDEN.store(false, Ordering::Relaxed);
+        }
+    }
+    changed
+}
+
+/// Whether the active network connection is metered
+pub fn is_metered() -> bool {
+    METERED.load(Ordering::Relaxed)
+}
+
+/// Ignore metered restrictions until the next unmetered connection
+pub fn set_overridden(overridden: bool) {
+    OVERRIDDEN.store(overridden, Ordering::Relaxed);
+}
+
+/// Restrictions in effect right now - none unless metered and not overridden
+pub fn active_restrictions() -> MeteredRestrictions {
+    if is_metered() && !OVERRIDDEN.load(Ordering::Relaxed) {
+        network_policy().metered
+    } else {
+        MeteredRestrictions::default()
+    }
+}
+
+/// Pick the best transcode option that stays within `max_height`.
+///
+/// Returns None when the stream already fits or no lower option is offered.
+pub fn reduced_quality_option(stream_info: &StreamInfo, max_height: u32) -> Option<&QualityOption> {
+    if stream_info.resolution.height <= max_height {
+        return None;
+    }
+
+    stream_info
+        .quality_options
+        .iter()
+        .filter(|option| option.requires_transcode && option.resolution.height <= max_height)
+        .max_by_key(|option| option.resolution.height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Resolution, StreamMode};
+
+    fn option(height: u32, requires_transcode: bool) -> QualityOption {
+        QualityOption {
+            name: format!("{}p", height),
+            resolution: Resolution {
+                width: height * 16 / 9,
+                height,
+            },
+            bitrate: 0,
+            url: format!("http://server/{}", height),
+            requires_transcode,
+        }
+    }
+
+    fn stream(height: u32, options: Vec<QualityOption>) -> StreamInfo {
+        StreamInfo {
+            url: "http://server/original".to_string(),
+            direct_play: true,
+            mode: StreamMode::DirectPlay,
+            video_codec: String::new(),
+            audio_codec: String::new(),
+            container: String::new(),
+            bitrate: 0,
+            resolution: Resolution {
+                width: height * 16 / 9,
+                height,
+            },
+            quality_options: options,
+        }
+    }
+
+    #[test]
+    fn test_reduced_quality_picks_highest_allowed_transcode() {
+        let info = stream(
+            1080,
+            vec![
+                option(1080, false),
+                option(720, true),
+                option(480, true),
+                option(360, true),
+            ],
+        );
+
+        let selected = reduced_quality_option(&info, 720).unwrap();
+        assert_eq!(selected.resolution.height, 720);
+    }
+
+    #[test]
+    fn test_reduced_quality_keeps_streams_that_already_fit() {
+        let info = stream(720, vec![option(720, false), option(480, true)]);
+        assert!(reduced_quality_option(&info, 720).is_none());
+    }
+}
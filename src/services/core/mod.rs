@@ -2,22 +2,82 @@
 /// These are pure functions that operate on data without maintaining state
 pub mod auth;
 pub mod backend;
+pub mod backup;
 pub mod cache_config;
+pub mod client_identity;
 pub mod connection;
 pub mod connection_cache;
+pub mod connectivity;
+pub mod continue_watching;
+pub mod deletion;
+pub mod diagnostics;
+pub mod dominant_color;
+pub mod download_policy;
+pub mod downloads;
+pub mod duplicates;
+pub mod favorites;
+pub mod hidden;
+pub mod hw_decode;
+pub mod image_prefetch;
+pub mod image_quality;
+pub mod kids;
+pub mod labels;
+pub mod library_export;
+pub mod local_user;
+pub mod long_audio;
 pub mod media;
 pub mod metadata_refresh;
+pub mod metered;
+pub mod network_policy;
 pub mod playback;
+pub mod playback_capabilities;
+pub mod playback_decision;
 pub mod playlist;
 pub mod playqueue;
+pub mod power_saver;
+pub mod previews;
+pub mod progress_reporter;
+pub mod skip_preferences;
+pub mod storage;
+pub mod stream_resolution;
 pub mod sync;
+pub mod sync_control;
+pub mod theme_music;
+pub mod track_preferences;
+pub mod troubleshoot;
 pub mod update;
+pub mod url_playback;
+pub mod wake_on_lan;
 
 pub use backend::BackendService;
+pub use backup::BackupService;
 pub use cache_config::{CacheConfig, ContentType, cache_config};
+pub use client_identity::ClientIdentityService;
 pub use connection::ConnectionService;
 pub use connection_cache::ConnectionType;
+pub use connectivity::{ConnectivityChange, ConnectivityTracker};
+pub use continue_watching::ContinueWatchingService;
+pub use deletion::DeletionService;
+pub use downloads::DownloadService;
+pub use duplicates::{DuplicateGroup, DuplicatesService};
+pub use favorites::FavoritesService;
+pub use hidden::HiddenItemsService;
+pub use image_prefetch::ImagePrefetchService;
+pub use kids::KidsProfileService;
+pub use labels::LabelsService;
+pub use library_export::{ExportFormat, LibraryExportService};
+pub use local_user::LocalUserService;
+pub use long_audio::LongAudioService;
 pub use media::MediaService;
 pub use metadata_refresh::MetadataRefreshService;
+pub use metered::MeteredRestrictions;
+pub use network_policy::{NetworkPolicy, network_policy};
 pub use playlist::PlaylistService;
+pub use progress_reporter::ProgressReporter;
+pub use skip_preferences::{ShowSkipModes, SkipMode, SkipPreferencesService};
+pub use storage::{StorageKind, StorageService, storage_locations};
+pub use stream_resolution::{ResolveStatus, StreamResolutionService};
+pub use sync_control::{SyncCancelled, SyncControl};
+pub use track_preferences::TrackPreferencesService;
 pub use update::UpdateService;
+pub use wake_on_lan::WakeOnLanService;
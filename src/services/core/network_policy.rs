@@ -0,0 +1,133 @@
+//! Shared HTTP timeout, retry and request pacing policy for backends
+//!
+//! Backends read the current policy when they build HTTP clients or retry requests,
+//! so changes made in preferences apply to the next request without a restart.
+
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::config::NetworkConfig;
+
+use super::metered::MeteredRestrictions;
+
+/// Timeouts and retry/backoff settings applied to all backend HTTP traffic
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkPolicy {
+    /// Timeout for a single API request
+    pub request_timeout: Duration,
+    /// Timeout when probing whether a server address is reachable
+    pub connection_timeout: Duration,
+    /// Retries after the initial attempt for transient failures
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles on every further retry
+    pub retry_base_delay: Duration,
+    /// Upper bound for the delay between retries
+    pub retry_max_delay: Duration,
+    /// Requests sent to one server at the same time
+    pub max_concurrent_requests: usize,
+    /// Least time between the starts of two requests to one server
+    pub min_request_interval: Duration,
+    /// What to hold back while on a metered connection
+    pub metered: MeteredRestrictions,
+}
+
+impl From<&NetworkConfig> for NetworkPolicy {
+    fn from(config: &NetworkConfig) -> Self {
+        Self {
+            request_timeout: Duration::from_secs(config.request_timeout_secs),
+            connection_timeout: Duration::from_secs(config.connection_timeout_secs),
+            max_retries: config.max_retries,
+            retry_base_delay: Duration::from_millis(config.retry_base_delay_ms),
+            retry_max_delay: Duration::from_millis(config.retry_max_delay_ms),
+            max_concurrent_requests: config.max_concurrent_requests as usize,
+            min_request_interval: match config.max_requests_per_second {
+                0 => Duration::ZERO,
+                rate => Duration::from_secs(1) / rate,
+            },
+            metered: MeteredRestrictions::from(config),
+        }
+    }
+}
+
+impl Default for NetworkPolicy {
+    fn default() -> Self {
+        Self::from(&NetworkConfig::default())
+    }
+}
+
+impl NetworkPolicy {
+    /// Client builder for regular API requests
+    pub fn client_builder(&self) -> reqwest::ClientBuilder {
+        reqwest::Client::builder().timeout(self.request_timeout)
+    }
+
+    /// Client builder for file downloads, which may legitimately take hours,
+    /// so only establishing the connection is bounded
+    pub fn download_client_builder(&self) -> reqwest::ClientBuilder {
+        reqwest::Client::builder().connect_timeout(self.request_timeout)
+    }
+
+    /// Client builder for reachability probes
+    pub fn probe_client_builder(&self) -> reqwest::ClientBuilder {
+        reqwest::Client::builder().timeout(self.connection_timeout)
+    }
+}
+
+/// Global policy, kept in sync with the configuration by the ConfigService
+static NETWORK_POLICY: RwLock<Option<NetworkPolicy>> = RwLock::new(None);
+
+/// Get the current network policy
+pub fn network_policy() -> NetworkPolicy {
+    NETWORK_POLICY
+        .read()
+        .ok()
+        .and_then(|policy| policy.clone())
+        .unwrap_or_default()
+}
+
+/// Replace the global network policy
+pub fn set_network_policy(policy: NetworkPolicy) {
+    if let Ok(mut current) = NETWORK_POLICY.write() {
+        *current = Some(policy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_from_config() {
+        let config = NetworkConfig {
+            request_timeout_secs: 10,
+            connection_timeout_secs: 3,
+            max_retries: 5,
+            retry_base_delay_ms: 250,
+            retry_max_delay_ms: 4000,
+            max_concurrent_requests: 2,
+            max_requests_per_second: 5,
+            ..Default::default()
+        };
+
+        let policy = NetworkPolicy::from(&config);
+        assert_eq!(policy.request_timeout, Duration::from_secs(10));
+        assert_eq!(policy.connection_timeout, Duration::from_secs(3));
+        assert_eq!(policy.max_retries, 5);
+        assert_eq!(policy.retry_base_delay, Duration::from_millis(250));
+        assert_eq!(policy.retry_max_delay, Duration::from_millis(4000));
+        assert_eq!(policy.max_concurrent_requests, 2);
+        assert_eq!(policy.min_request_interval, Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_zero_rate_disables_rate_limit() {
+        let config = NetworkConfig {
+            max_requests_per_second: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            NetworkPolicy::from(&config).min_request_interval,
+            Duration::ZERO
+        );
+    }
+}
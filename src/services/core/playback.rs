@@ -3,35 +3,32 @@ use anyhow::{Context, Result};
 use crate::db::connection::DatabaseConnection;
 use crate::db::entities::PlaybackProgressModel;
 use crate::db::repository::{PlaybackRepository, PlaybackRepositoryImpl};
-use crate::models::MediaItemId;
+use crate::models::{MediaItemId, UserId};
+use crate::services::core::LocalUserService;
 
 /// Pure functions for playback operations
 pub struct PlaybackService;
 
 impl PlaybackService {
-    /// Get playback progress for a media item
+    /// Get playback progress of the user signed in to the item's source
     pub async fn get_progress(
         db: &DatabaseConnection,
-        _user_id: &str,
         item_id: &MediaItemId,
     ) -> Result<Option<PlaybackProgressModel>> {
         let repo = PlaybackRepositoryImpl::new(db.clone());
-        // Use find_by_media_id for single-user system (matching update_playback_progress behavior)
-        // which uses None for user_id
         repo.find_by_media_id(item_id.as_ref())
             .await
             .context("Failed to get playback progress")
     }
 
-    /// Get PlayQueue state for a media item
+    /// Get PlayQueue state of the user signed in to the item's source
     pub async fn get_playqueue_state(
         db: &DatabaseConnection,
-        _user_id: &str,
         item_id: &MediaItemId,
     ) -> Result<Option<(i64, i32, i64, i32)>> {
         let repo = PlaybackRepositoryImpl::new(db.clone());
-        // Use None for user_id in single-user system
-        repo.get_playqueue_state(item_id.as_ref(), None)
+        let user_id = LocalUserService::active_user_for_item(db, item_id).await?;
+        repo.get_playqueue_state(item_id.as_ref(), user_id.as_ref().map(UserId::as_str))
             .await
             .context("Failed to get PlayQueue state")
     }
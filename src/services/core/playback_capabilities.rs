@@ -0,0 +1,209 @@
+//! What this machine can play without a server-side transcode
+//!
+//! Combines the formats of the player backend in use with the probed
+//! hardware decoders and the power saving state. Playback uses it to decide
+//! when to ask for a transcode, and backends describe it to servers that pick
+//! the stream themselves.
+
+use super::hw_decode::{self, HardwareDecodeSupport, VideoCodec};
+use super::power_saver;
+use crate::player::factory::PlayerBackend;
+use crate::services::config_service::config_service;
+
+/// Player backend formats are described for, independent of the enabled features
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerEngine {
+    Mpv,
+    GStreamer,
+}
+
+impl From<&PlayerBackend> for PlayerEngine {
+    fn from(backend: &PlayerBackend) -> Self {
+        match backend {
+            #[cfg(feature = "gstreamer")]
+            PlayerBackend::GStreamer => Self::GStreamer,
+            #[cfg(all(feature = "mpv", not(target_os = "macos")))]
+            PlayerBackend::Mpv => Self::Mpv,
+        }
+    }
+}
+
+impl PlayerEngine {
+    /// Whether the engine also plays formats its lists leave out, which
+    /// then only describe it to servers. mpv decodes through FFmpeg.
+    pub fn plays_unlisted_formats(&self) -> bool {
+        matches!(self, Self::Mpv)
+    }
+
+    /// Containers the engine demuxes, in the names servers use
+    pub fn containers(&self) -> &'static [&'static str] {
+        match self {
+            Self::Mpv => &[
+                "mkv", "mp4", "m4v", "mov", "webm", "avi", "ts", "m2ts", "mpegts", "mpeg", "mpg",
+                "vob", "flv", "wmv", "asf", "ogg", "3gp",
+            ],
+            Self::GStreamer => &["mkv", "mp4", "m4v", "mov", "webm", "ts", "mpegts", "ogg"],
+        }
+    }
+
+    /// Video codecs the engine decodes, in software or hardware
+    pub fn video_codecs(&self) -> &'static [&'static str] {
+        match self {
+            Self::Mpv => &[
+                "h264",
+                "hevc",
+                "vp8",
+                "vp9",
+                "av1",
+                "mpeg2video",
+                "mpeg4",
+                "vc1",
+                "msmpeg4v3",
+            ],
+            Self::GStreamer => &["h264", "hevc", "vp8", "vp9", "av1", "mpeg2video"],
+        }
+    }
+
+    /// Audio codecs the engine decodes
+    pub fn audio_codecs(&self) -> &'static [&'static str] {
+        match self {
+            Self::Mpv => &[
+                "aac",
+                "mp3",
+                "ac3",
+                "eac3",
+                "dts",
+                "truehd",
+                "flac",
+                "alac",
+                "opus",
+                "vorbis",
+                "pcm",
+                "pcm_s16le",
+                "pcm_s24le",
+                "pcm_s16be",
+                "pcm_s24be",
+                "mp2",
+                "wmav2",
+                "wmapro",
+            ],
+            Self::GStreamer => &["aac", "mp3", "ac3", "eac3", "flac", "opus", "vorbis"],
+        }
+    }
+
+    /// Subtitle formats the engine renders from inside the stream
+    pub fn subtitle_formats(&self) -> &'static [&'static str] {
+        match self {
+            Self::Mpv => &[
+                "srt", "subrip", "ass", "ssa", "vtt", "pgssub", "dvdsub", "dvbsub",
+            ],
+            Self::GStreamer => &["srt", "subrip", "ass", "ssa", "vtt"],
+        }
+    }
+}
+
+/// Formats and decoding limits of the player on this machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaybackCapabilities {
+    pub engine: PlayerEngine,
+    /// Hardware decoders in use, None until the first probe finishes and
+    /// for engines whose decoders aren't probed
+    pub hardware: Option<HardwareDecodeSupport>,
+    /// Tallest video direct-played when it has to be decoded in software
+    pub software_max_height: u32,
+}
+
+impl PlaybackCapabilities {
+    /// Capabilities under the current configuration and power state.
+    ///
+    /// Everything is decoded in software while hardware acceleration is
+    /// turned off, and saving power lowers the bar for software decoding.
+    /// Only the GStreamer engine is limited, the probe reads its registry
+    /// while MPV picks decoders of its own.
+    pub async fn current() -> Self {
+        let playback = config_service().get_config().await.playback;
+        let engine = PlayerEngine::from(&PlayerBackend::from(playback.player_backend.as_str()));
+        let hardware = match engine {
+            PlayerEngine::GStreamer => hw_decode::hardware_decode_support().map(|support| {
+                if playback.hardware_acceleration {
+                    support
+                } else {
+                    HardwareDecodeSupport::default()
+                }
+            }),
+            PlayerEngine::Mpv => None,
+        };
+        let software_max_height = if power_saver::is_active() {
+            power_saver::POWER_SAVING_SOFTWARE_DECODE_MAX_HEIGHT
+        } else {
+            hw_decode::SOFTWARE_DECODE_MAX_HEIGHT
+        };
+
+        Self {
+            engine,
+            hardware,
+            software_max_height,
+        }
+    }
+
+    /// Tallest video of `codec` that plays without a transcode, None when
+    /// any height does
+    pub fn max_height(&self, codec: VideoCodec) -> Option<u32> {
+        match &self.hardware {
+            Some(support) if !support.supports(codec) => Some(self.software_max_height),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::core::hw_decode::{DecoderApi, HardwareDecoder};
+
+    fn capabilities(hardware: Option<HardwareDecodeSupport>) -> PlaybackCapabilities {
+        PlaybackCapabilities {
+            engine: PlayerEngine::GStreamer,
+            hardware,
+            software_max_height: 1080,
+        }
+    }
+
+    #[test]
+    fn test_software_decoded_codecs_are_limited_in_height() {
+        let capabilities = capabilities(Some(HardwareDecodeSupport {
+            decoders: vec![HardwareDecoder {
+                codec: VideoCodec::Hevc,
+                api: DecoderApi::VaApi,
+                element: "vah265dec".to_string(),
+            }],
+        }));
+        assert_eq!(capabilities.max_height(VideoCodec::Hevc), None);
+        assert_eq!(capabilities.max_height(VideoCodec::Av1), Some(1080));
+    }
+
+    #[test]
+    fn test_no_limits_before_the_probe_finishes() {
+        assert_eq!(capabilities(None).max_height(VideoCodec::Av1), None);
+    }
+
+    #[test]
+    fn test_gstreamer_formats_are_a_subset_of_mpv() {
+        for (gstreamer, mpv) in [
+            (
+                PlayerEngine::GStreamer.containers(),
+                PlayerEngine::Mpv.containers(),
+            ),
+            (
+                PlayerEngine::GStreamer.video_codecs(),
+                PlayerEngine::Mpv.video_codecs(),
+            ),
+            (
+                PlayerEngine::GStreamer.audio_codecs(),
+                PlayerEngine::Mpv.audio_codecs(),
+            ),
+        ] {
+            assert!(gstreamer.iter().all(|format| mpv.contains(format)));
+        }
+    }
+}
@@ -0,0 +1,482 @@
+//! Choosing how an item is streamed
+//!
+//! Before playback starts, the media info of the item is compared against
+//! what the player on this machine plays and the user's settings. The
+//! original is played when nothing stands in the way, the server's remuxed
+//! stream when it already repackages the file, and a server-side transcode
+//! when a codec, the container, the decoders or a metered connection call
+//! for one. The decision and its reason are shown in the player stats.
+
+use std::fmt;
+
+use super::hw_decode::VideoCodec;
+use super::playback_capabilities::{PlaybackCapabilities, PlayerEngine};
+use super::{hw_decode, metered};
+use crate::models::{PlaybackError, QualityOption, StreamInfo, StreamMode};
+
+/// Which stream of an item to play
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StreamChoice {
+    /// The original, unless the connection or the decoders call for a transcode
+    #[default]
+    Automatic,
+    /// The original, even where a transcode would be picked
+    Original,
+    /// The best transcode the server offers, for files this device can't decode
+    Transcoded,
+}
+
+/// How the server delivers the item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackMethod {
+    /// The original file, as stored
+    DirectPlay,
+    /// The original streams, repackaged by the server into another container
+    Remux,
+    /// Converted by the server
+    Transcode,
+}
+
+impl PlaybackMethod {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::DirectPlay => "Direct Play",
+            Self::Remux => "Direct Stream",
+            Self::Transcode => "Transcode",
+        }
+    }
+}
+
+/// Why a method was picked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecisionReason {
+    /// The player handles the file as it is
+    Supported,
+    /// Played from a finished offline download
+    Downloaded,
+    /// The user asked for this stream
+    Requested,
+    /// The server repackages the file on its own
+    ServerRemux,
+    /// The server refused the original and only serves a transcode
+    ServerTranscode,
+    UnsupportedVideoCodec(String),
+    UnsupportedAudioCodec(String),
+    UnsupportedContainer(String),
+    /// Lower quality on a metered connection
+    Metered {
+        max_height: u32,
+    },
+    /// Too tall to decode in software, with no hardware decoder for the codec
+    SoftwareDecode {
+        codec: VideoCodec,
+        height: u32,
+    },
+}
+
+impl fmt::Display for DecisionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Supported => write!(f, "Supported by the player"),
+            Self::Downloaded => write!(f, "Offline download"),
+            Self::Requested => write!(f, "Chosen after a playback error"),
+            Self::ServerRemux => write!(f, "Repackaged by the server"),
+            Self::ServerTranscode => write!(f, "Original refused by the server"),
+            Self::UnsupportedVideoCodec(codec) => write!(f, "Video codec {} not supported", codec),
+            Self::UnsupportedAudioCodec(codec) => write!(f, "Audio codec {} not supported", codec),
+            Self::UnsupportedContainer(container) => {
+                write!(f, "Container {} not supported", container)
+            }
+            Self::Metered { max_height } => {
+                write!(f, "Metered connection, limited to {}p", max_height)
+            }
+            Self::SoftwareDecode { codec, height } => write!(
+                f,
+                "No hardware decoder for {} at {}p",
+                codec.label(),
+                height
+            ),
+        }
+    }
+}
+
+/// How an item is streamed, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaybackDecision {
+    pub method: PlaybackMethod,
+    pub reason: DecisionReason,
+    /// Stream to load, None for the original stream of the item
+    pub transcode: Option<QualityOption>,
+}
+
+impl PlaybackDecision {
+    fn original(method: PlaybackMethod, reason: DecisionReason) -> Self {
+        Self {
+            method,
+            reason,
+            transcode: None,
+        }
+    }
+
+    fn transcode(option: &QualityOption, reason: DecisionReason) -> Self {
+        Self {
+            method: PlaybackMethod::Transcode,
+            reason,
+            transcode: Some(option.clone()),
+        }
+    }
+
+    /// Playing a finished offline download
+    pub fn downloaded() -> Self {
+        Self::original(PlaybackMethod::DirectPlay, DecisionReason::Downloaded)
+    }
+}
+
+/// Decide how to stream an item.
+///
+/// `lower_quality` is whether metered restrictions ask for a lower quality
+/// right now. Fails only when a transcode was asked for and the server
+/// offers none.
+pub fn decide(
+    stream_info: &StreamInfo,
+    capabilities: &PlaybackCapabilities,
+    choice: StreamChoice,
+    lower_quality: bool,
+) -> Result<PlaybackDecision, PlaybackError> {
+    // Nothing else to choose from once the server settled on a transcode
+    if stream_info.mode == StreamMode::Transcode {
+        return Ok(PlaybackDecision::transcode(
+            &server_transcode(stream_info),
+            DecisionReason::ServerTranscode,
+        ));
+    }
+
+    match choice {
+        StreamChoice::Transcoded => {
+            let option = best_transcode(stream_info).ok_or(PlaybackError::TranscodeRefused)?;
+            return Ok(PlaybackDecision::transcode(
+                option,
+                DecisionReason::Requested,
+            ));
+        }
+        StreamChoice::Original => {
+            return Ok(PlaybackDecision::original(
+                PlaybackMethod::DirectPlay,
+                DecisionReason::Requested,
+            ));
+        }
+        StreamChoice::Automatic => {}
+    }
+
+    // Formats the player can't handle at all
+    if let Some(reason) = unsupported_format(stream_info, capabilities.engine) {
+        if !stream_info.direct_play && matches!(reason, DecisionReason::UnsupportedContainer(_)) {
+            return Ok(PlaybackDecision::original(
+                PlaybackMethod::Remux,
+                DecisionReason::ServerRemux,
+            ));
+        }
+        if let Some(option) = best_transcode(stream_info) {
+            return Ok(PlaybackDecision::transcode(option, reason));
+        }
+    }
+
+    if lower_quality
+        && let Some(option) =
+            metered::reduced_quality_option(stream_info, metered::METERED_MAX_STREAM_HEIGHT)
+        && option.url != stream_info.url
+    {
+        return Ok(PlaybackDecision::transcode(
+            option,
+            DecisionReason::Metered {
+                max_height: metered::METERED_MAX_STREAM_HEIGHT,
+            },
+        ));
+    }
+
+    if let Some(support) = &capabilities.hardware
+        && let Some(option) = hw_decode::software_decode_transcode(
+            stream_info,
+            support,
+            capabilities.software_max_height,
+        )
+        && option.url != stream_info.url
+        && let Some(codec) = VideoCodec::from_stream_codec(&stream_info.video_codec)
+    {
+        return Ok(PlaybackDecision::transcode(
+            option,
+            DecisionReason::SoftwareDecode {
+                codec,
+                height: stream_info.resolution.height,
+            },
+        ));
+    }
+
+    if !stream_info.direct_play {
+        return Ok(PlaybackDecision::original(
+            PlaybackMethod::Remux,
+            DecisionReason::ServerRemux,
+        ));
+    }
+
+    Ok(PlaybackDecision::original(
+        PlaybackMethod::DirectPlay,
+        DecisionReason::Supported,
+    ))
+}
+
+/// The transcode a server streams in place of the original
+fn server_transcode(stream_info: &StreamInfo) -> QualityOption {
+    QualityOption {
+        name: "Server transcode".to_string(),
+        resolution: stream_info.resolution.clone(),
+        bitrate: stream_info.bitrate,
+        url: stream_info.url.clone(),
+        requires_transcode: true,
+    }
+}
+
+/// Best transcode the server offers
+fn best_transcode(stream_info: &StreamInfo) -> Option<&QualityOption> {
+    stream_info
+        .quality_options
+        .iter()
+        .filter(|option| option.requires_transcode && option.url != stream_info.url)
+        .max_by_key(|option| option.resolution.height)
+}
+
+/// First part of the item the player can't handle. Unknown formats are
+/// assumed to play, and so is anything on an engine that plays formats it
+/// doesn't list.
+fn unsupported_format(stream_info: &StreamInfo, engine: PlayerEngine) -> Option<DecisionReason> {
+    if engine.plays_unlisted_formats() {
+        return None;
+    }
+
+    let video = normalize_codec(&stream_info.video_codec);
+    if !video.is_empty() && !engine.video_codecs().contains(&video.as_str()) {
+        return Some(DecisionReason::UnsupportedVideoCodec(video));
+    }
+
+    let audio = normalize_codec(&stream_info.audio_codec);
+    if !audio.is_empty() && !engine.audio_codecs().contains(&audio.as_str()) {
+        return Some(DecisionReason::UnsupportedAudioCodec(audio));
+    }
+
+    // Servers may list every name a container goes by, like "mov,mp4,m4a"
+    let container = stream_info.container.to_ascii_lowercase();
+    if !container.is_empty()
+        && !container
+            .split(',')
+            .any(|name| engine.containers().contains(&name.trim()))
+    {
+        return Some(DecisionReason::UnsupportedContainer(container));
+    }
+
+    None
+}
+
+/// Codec name as the engines list it, from the names servers use
+fn normalize_codec(codec: &str) -> String {
+    let codec = codec.to_ascii_lowercase();
+    match codec.as_str() {
+        "avc" | "avc1" => "h264".to_string(),
+        "h265" | "hvc1" | "hev1" => "hevc".to_string(),
+        "dca" => "dts".to_string(),
+        "mpeg2" => "mpeg2video".to_string(),
+        _ => codec,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Resolution;
+    use crate::services::core::hw_decode::HardwareDecodeSupport;
+
+    fn stream(video: &str, audio: &str, container: &str, height: u32) -> StreamInfo {
+        StreamInfo {
+            url: "http://server/original".to_string(),
+            direct_play: true,
+            mode: StreamMode::DirectPlay,
+            video_codec: video.to_string(),
+            audio_codec: audio.to_string(),
+            container: container.to_string(),
+            bitrate: 40_000_000,
+            resolution: Resolution {
+                width: height * 16 / 9,
+                height,
+            },
+            quality_options: vec![
+                QualityOption {
+                    name: "1080p".to_string(),
+                    resolution: Resolution {
+                        width: 1920,
+                        height: 1080,
+                    },
+                    bitrate: 8_000_000,
+                    url: "http://server/1080".to_string(),
+                    requires_transcode: true,
+                },
+                QualityOption {
+                    name: "720p".to_string(),
+                    resolution: Resolution {
+                        width: 1280,
+                        height: 720,
+                    },
+                    bitrate: 4_000_000,
+                    url: "http://server/720".to_string(),
+                    requires_transcode: true,
+                },
+            ],
+        }
+    }
+
+    fn capabilities(engine: PlayerEngine) -> PlaybackCapabilities {
+        PlaybackCapabilities {
+            engine,
+            hardware: None,
+            software_max_height: 1080,
+        }
+    }
+
+    fn automatic(stream_info: &StreamInfo, engine: PlayerEngine) -> PlaybackDecision {
+        decide(
+            stream_info,
+            &capabilities(engine),
+            StreamChoice::Automatic,
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_supported_files_are_played_directly() {
+        let decision = automatic(&stream("h264", "aac", "mkv", 1080), PlayerEngine::Mpv);
+        assert_eq!(decision.method, PlaybackMethod::DirectPlay);
+        assert_eq!(decision.reason, DecisionReason::Supported);
+        assert_eq!(decision.transcode, None);
+    }
+
+    #[test]
+    fn test_unsupported_codecs_are_transcoded() {
+        let decision = automatic(&stream("h264", "dca", "mkv", 1080), PlayerEngine::GStreamer);
+        assert_eq!(decision.method, PlaybackMethod::Transcode);
+        assert_eq!(
+            decision.reason,
+            DecisionReason::UnsupportedAudioCodec("dts".to_string())
+        );
+        assert_eq!(decision.transcode.unwrap().name, "1080p");
+
+        let decision = automatic(&stream("h264", "dca", "mkv", 1080), PlayerEngine::Mpv);
+        assert_eq!(decision.method, PlaybackMethod::DirectPlay);
+    }
+
+    #[test]
+    fn test_mpv_plays_formats_it_does_not_list() {
+        for audio in ["pcm_s32le", "mp2", "wmav2"] {
+            let decision = automatic(&stream("h264", audio, "mpeg", 1080), PlayerEngine::Mpv);
+            assert_eq!(decision.method, PlaybackMethod::DirectPlay);
+        }
+        let decision = automatic(&stream("theora", "aac", "nut", 1080), PlayerEngine::Mpv);
+        assert_eq!(decision.method, PlaybackMethod::DirectPlay);
+    }
+
+    #[test]
+    fn test_unsupported_container_uses_the_server_remux() {
+        let mut remuxed = stream("h264", "aac", "avi", 1080);
+        remuxed.direct_play = false;
+        let decision = automatic(&remuxed, PlayerEngine::GStreamer);
+        assert_eq!(decision.method, PlaybackMethod::Remux);
+
+        let decision = automatic(&stream("h264", "aac", "avi", 1080), PlayerEngine::GStreamer);
+        assert_eq!(decision.method, PlaybackMethod::Transcode);
+        assert_eq!(
+            decision.reason,
+            DecisionReason::UnsupportedContainer("avi".to_string())
+        );
+    }
+
+    #[test]
+    fn test_server_transcodes_are_played_as_transcodes() {
+        let mut transcoded = stream("hevc", "truehd", "mkv", 2160);
+        transcoded.url = "http://server/main.m3u8".to_string();
+        transcoded.direct_play = false;
+        transcoded.mode = StreamMode::Transcode;
+
+        for choice in [StreamChoice::Automatic, StreamChoice::Original] {
+            let decision =
+                decide(&transcoded, &capabilities(PlayerEngine::Mpv), choice, false).unwrap();
+            assert_eq!(decision.method, PlaybackMethod::Transcode);
+            assert_eq!(decision.reason, DecisionReason::ServerTranscode);
+            assert_eq!(decision.transcode.unwrap().url, "http://server/main.m3u8");
+        }
+    }
+
+    #[test]
+    fn test_container_lists_match_any_name() {
+        let decision = automatic(
+            &stream("h264", "aac", "mov,mp4,m4a,3gp", 1080),
+            PlayerEngine::GStreamer,
+        );
+        assert_eq!(decision.method, PlaybackMethod::DirectPlay);
+    }
+
+    #[test]
+    fn test_metered_connections_lower_the_quality() {
+        let decision = decide(
+            &stream("h264", "aac", "mkv", 2160),
+            &capabilities(PlayerEngine::Mpv),
+            StreamChoice::Automatic,
+            true,
+        )
+        .unwrap();
+        assert_eq!(decision.reason, DecisionReason::Metered { max_height: 720 });
+        assert_eq!(decision.transcode.unwrap().name, "720p");
+    }
+
+    #[test]
+    fn test_software_decoding_of_tall_video_is_transcoded() {
+        let mut capabilities = capabilities(PlayerEngine::GStreamer);
+        capabilities.hardware = Some(HardwareDecodeSupport::default());
+        let decision = decide(
+            &stream("hevc", "aac", "mkv", 2160),
+            &capabilities,
+            StreamChoice::Automatic,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            decision.reason,
+            DecisionReason::SoftwareDecode {
+                codec: VideoCodec::Hevc,
+                height: 2160
+            }
+        );
+    }
+
+    #[test]
+    fn test_requested_streams_override_the_checks() {
+        let unsupported = stream("h264", "dca", "avi", 2160);
+        let original = decide(
+            &unsupported,
+            &capabilities(PlayerEngine::GStreamer),
+            StreamChoice::Original,
+            true,
+        )
+        .unwrap();
+        assert_eq!(original.method, PlaybackMethod::DirectPlay);
+
+        let mut no_transcodes = stream("h264", "aac", "mkv", 1080);
+        no_transcodes.quality_options.clear();
+        assert_eq!(
+            decide(
+                &no_transcodes,
+                &capabilities(PlayerEngine::Mpv),
+                StreamChoice::Transcoded,
+                false,
+            ),
+            Err(PlaybackError::TranscodeRefused)
+        );
+    }
+}
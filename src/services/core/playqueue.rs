@@ -2,7 +2,10 @@ use crate::backends::plex::PlexBackend;
 use crate::backends::plex::api::playqueue::{PlayQueueContainer, PlayQueueResponse};
 use crate::db::connection::DatabaseConnection;
 use crate::db::repository::{MediaRepository, MediaRepositoryImpl, PlaybackRepository, Repository};
-use crate::models::{EpisodeInfo, MediaItemId, PlayQueueInfo, PlaylistContext, QueueItem, ShowId};
+use crate::models::{
+    EpisodeInfo, MediaItemId, PlayQueueInfo, PlaylistContext, QueueItem, ShowId, SourceId, UserId,
+};
+use crate::services::core::LocalUserService;
 use anyhow::{Result, anyhow};
 use std::any::Any;
 use tracing::{debug, info, warn};
@@ -69,6 +72,41 @@ impl PlayQueueService {
         ))
     }
 
+    /// Create a local PlayQueue context from items queued in the app
+    pub async fn create_local(
+        db: &DatabaseConnection,
+        media_ids: &[MediaItemId],
+    ) -> Result<PlaylistContext> {
+        let repo = MediaRepositoryImpl::new(db.clone());
+        let mut items = Vec::new();
+
+        for media_id in media_ids {
+            match repo.find_by_id(media_id.as_ref()).await? {
+                Some(media) => items.push(QueueItem {
+                    id: media_id.clone(),
+                    title: media.title,
+                    media_type: media.media_type,
+                    duration_ms: media.duration_ms,
+                    play_queue_item_id: None,
+                }),
+                None => warn!("Queued media {} no longer exists", media_id),
+            }
+        }
+
+        if items.is_empty() {
+            return Err(anyhow!("Nothing left to play in the queue"));
+        }
+
+        info!("Created local PlayQueue context with {} items", items.len());
+
+        Ok(PlaylistContext::PlayQueue {
+            play_queue_info: None,
+            current_index: 0,
+            items,
+            auto_play_next: true,
+        })
+    }
+
     /// Retrieve an existing PlayQueue by ID
     pub async fn get_play_queue(
         backend: &dyn Any,
@@ -190,7 +228,7 @@ impl PlayQueueService {
         );
 
         Ok(PlaylistContext::PlayQueue {
-            play_queue_info,
+            play_queue_info: Some(play_queue_info),
             current_index,
             items,
             auto_play_next: true, // PlayQueues always support auto-play
@@ -272,44 +310,6 @@ impl PlayQueueService {
             "PlayQueue modification not available for this backend"
         ))
     }
-
-    /// Update playback progress with PlayQueue context
-    pub async fn update_progress_with_queue(
-        backend: &dyn Any,
-        context: &PlaylistContext,
-        media_id: &MediaItemId,
-        position: std::time::Duration,
-        duration: std::time::Duration,
-        state: &str,
-    ) -> Result<()> {
-        if let Some(queue_info) = context.get_play_queue_info()
-            && let Some(plex_backend) = backend.downcast_ref::<PlexBackend>()
-            && let Some(api) = plex_backend.get_api_for_playqueue().await
-        {
-            debug!(
-                "Updating PlayQueue progress - queue: {}, item: {}, media: {}",
-                queue_info.play_queue_id, queue_info.play_queue_item_id, media_id
-            );
-
-            // Use the PlayQueue-aware timeline update
-            api.update_play_queue_progress(
-                queue_info.play_queue_id,
-                queue_info.play_queue_version,
-                queue_info.play_queue_item_id,
-                media_id.as_ref(),
-                position,
-                duration,
-                state,
-            )
-            .await?;
-
-            return Ok(());
-        }
-
-        // Fall back to regular progress update
-        debug!("No PlayQueue context, using regular progress update");
-        Ok(())
-    }
 }
 
 /// Service for managing playlist contexts and episode navigation
@@ -355,10 +355,15 @@ impl PlaylistService {
                             if let Some(queue_info) = context.get_play_queue_info() {
                                 let playback_repo =
                                     crate::db::repository::PlaybackRepositoryImpl::new(db.clone());
+                                let user_id = LocalUserService::active_user(
+                                    db,
+                                    &SourceId::new(media.source_id.clone()),
+                                )
+                                .await?;
                                 if let Err(e) = playback_repo
                                     .save_playqueue_state(
                                         episode_id.as_ref(),
-                                        None, // TODO: Get actual user ID
+                                        user_id.as_ref().map(UserId::as_str),
                                         queue_info.play_queue_id,
                                         queue_info.play_queue_version,
                                         queue_info.play_queue_item_id,
@@ -0,0 +1,95 @@
+//! Power saving awareness
+//!
+//! The power profile monitor (power-profiles-daemon, or the power profile
+//! portal inside Flatpak) reports whether power saver mode is on, and UPower
+//! reports the battery level. While either asks for it, playback uses lighter
+//! settings: no upscaling shaders, a smaller demuxer cache, transcoding what
+//! GStreamer would have to decode in software and no image prefetching. Users who
+//! would rather keep full quality turn this off in the player preferences.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::info;
+
+/// Battery level at or below which a discharging battery counts as low
+pub const LOW_BATTERY_PERCENT: f64 = 20.0;
+
+/// Tallest video direct-played in software while saving power
+pub const POWER_SAVING_SOFTWARE_DECODE_MAX_HEIGHT: u32 = 720;
+
+/// Demuxer cache ahead of and behind the playback position while saving power
+pub const POWER_SAVING_CACHE_MB: u32 = 32;
+pub const POWER_SAVING_BACKBUFFER_MB: u32 = 8;
+
+/// Whether the system power profile is power saver
+static POWER_SAVER: AtomicBool = AtomicBool::new(false);
+
+/// Whether the battery is discharging and low
+static LOW_BATTERY: AtomicBool = AtomicBool::new(false);
+
+/// Whether the user lets playback save power, kept in sync with the
+/// configuration by the ConfigService
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Whether playback should use lighter settings right now
+pub fn is_active() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+        && (POWER_SAVER.load(Ordering::Relaxed) || LOW_BATTERY.load(Ordering::Relaxed))
+}
+
+fn update(flag: &AtomicBool, value: bool) -> bool {
+    let was_active = is_active();
+    flag.store(value, Ordering::Relaxed);
+    let active = is_active();
+    if active != was_active {
+        info!(
+            "Power saving playback {}",
+            if active { "enabled" } else { "disabled" }
+        );
+    }
+    active != was_active
+}
+
+/// Record whether power saver mode is on. Returns true if that turned
+/// power saving playback on or off.
+pub fn set_power_saver(enabled: bool) -> bool {
+    update(&POWER_SAVER, enabled)
+}
+
+/// Record whether the battery is low. Returns true if that turned power
+/// saving playback on or off.
+pub fn set_low_battery(low: bool) -> bool {
+    update(&LOW_BATTERY, low)
+}
+
+/// Allow or forbid power saving playback. Returns true if that turned it on
+/// or off.
+pub fn set_enabled(enabled: bool) -> bool {
+    update(&ENABLED, enabled)
+}
+
+/// Whether UPower's display device describes a low battery.
+///
+/// `state` is UPower's device state, of which discharging (2), empty (3) and
+/// pending discharge (6) mean the machine is running on the battery.
+pub fn is_battery_low(is_present: bool, state: u32, percentage: f64) -> bool {
+    is_present && matches!(state, 2 | 3 | 6) && percentage <= LOW_BATTERY_PERCENT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_battery_low_only_while_discharging() {
+        assert!(is_battery_low(true, 2, 15.0));
+        assert!(is_battery_low(true, 6, LOW_BATTERY_PERCENT));
+        assert!(!is_battery_low(true, 1, 15.0));
+        assert!(!is_battery_low(true, 4, 15.0));
+    }
+
+    #[test]
+    fn test_battery_low_needs_a_battery_below_threshold() {
+        assert!(!is_battery_low(true, 2, 60.0));
+        assert!(!is_battery_low(false, 2, 0.0));
+    }
+}
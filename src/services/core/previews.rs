@@ -0,0 +1,36 @@
+//! Muted video previews shown while hovering media cards
+//!
+//! Previews are opt-in and never start on a metered or offline connection,
+//! since each one streams from the server.
+
+use anyhow::Result;
+
+use crate::db::connection::DatabaseConnection;
+use crate::models::MediaItemId;
+use crate::services::config_service::config_service;
+
+use super::backend::BackendService;
+use super::{connectivity, metered};
+
+/// Highest video resolution requested for a preview, cards are much smaller
+const PREVIEW_MAX_HEIGHT: u32 = 480;
+
+/// Stream URL to preview a media item with, or None if previews shouldn't play now
+pub async fn preview_url(
+    db: &DatabaseConnection,
+    media_id: &MediaItemId,
+) -> Result<Option<String>> {
+    if !config_service().get_config().await.ui.hover_previews
+        || metered::is_metered()
+        || connectivity::is_offline()
+    {
+        return Ok(None);
+    }
+
+    let stream_info = BackendService::get_stream_url(db, media_id).await?;
+    let url = metered::reduced_quality_option(&stream_info, PREVIEW_MAX_HEIGHT)
+        .map(|option| option.url.clone())
+        .unwrap_or(stream_info.url);
+
+    Ok(Some(url))
+}
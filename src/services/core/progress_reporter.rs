@@ -0,0 +1,237 @@
+//! Writing back playback progress
+//!
+//! The player reports its position several times a second. Reports are
+//! coalesced in memory and only the latest is written, to the database and
+//! to the queue the playback sync worker sends to the server: once per
+//! interval, as soon as the item counts as watched, and when playback
+//! pauses, stops or moves on to another item. Writes happen in order on a
+//! task of their own, so the player never waits for them.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+use crate::db::connection::DatabaseConnection;
+use crate::models::MediaItemId;
+use crate::services::core::MediaService;
+use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+
+/// Fraction of an item played after which it counts as watched
+pub const WATCHED_THRESHOLD: f64 = 0.9;
+
+/// Position of an item at one moment of playback
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressReport {
+    pub media_id: MediaItemId,
+    pub position_ms: i64,
+    pub duration_ms: i64,
+    pub watched: bool,
+}
+
+impl ProgressReport {
+    pub fn new(media_id: &MediaItemId, position: Duration, duration: Duration) -> Self {
+        Self {
+            media_id: media_id.clone(),
+            position_ms: position.as_millis() as i64,
+            duration_ms: duration.as_millis() as i64,
+            watched: position.as_secs_f64() / duration.as_secs_f64() > WATCHED_THRESHOLD,
+        }
+    }
+}
+
+/// Coalesces the progress reports of a player
+#[derive(Debug)]
+pub struct ProgressReporter {
+    /// Reports due to be written, in the order they were due
+    writes: mpsc::UnboundedSender<ProgressReport>,
+    interval: Duration,
+    /// Latest report not written yet
+    pending: Option<ProgressReport>,
+    last_write: Instant,
+    /// Last report written, so the same state isn't written twice
+    written: Option<ProgressReport>,
+}
+
+impl ProgressReporter {
+    pub fn new(db: DatabaseConnection, interval: Duration) -> Self {
+        let (writes, mut due) = mpsc::unbounded_channel::<ProgressReport>();
+        relm4::spawn(async move {
+            while let Some(report) = due.recv().await {
+                if let Err(e) = write_report(&db, &report).await {
+                    debug!("Failed to save playback progress: {}", e);
+                }
+            }
+        });
+
+        Self {
+            writes,
+            interval,
+            pending: None,
+            last_write: Instant::now(),
+            written: None,
+        }
+    }
+
+    /// Change how often progress is written during playback
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Note the position of an item, writing it if it is due
+    pub fn report(&mut self, media_id: &MediaItemId, position: Duration, duration: Duration) {
+        let due = self.record(
+            ProgressReport::new(media_id, position, duration),
+            Instant::now(),
+        );
+        for report in due {
+            self.write(report);
+        }
+    }
+
+    /// Write the latest position right away, like when playback pauses
+    pub fn flush(&mut self) {
+        if let Some(report) = self.take_pending(Instant::now()) {
+            self.write(report);
+        }
+    }
+
+    /// Keep a report, returning the ones due to be written
+    fn record(&mut self, report: ProgressReport, now: Instant) -> Vec<ProgressReport> {
+        let mut due = Vec::new();
+
+        // The last position of the previous item is never dropped
+        if self
+            .pending
+            .as_ref()
+            .is_some_and(|pending| pending.media_id != report.media_id)
+        {
+            due.extend(self.take_pending(now));
+        }
+
+        let newly_watched = report.watched
+            && !self
+                .written
+                .as_ref()
+                .is_some_and(|written| written.media_id == report.media_id && written.watched);
+        self.pending = Some(report);
+
+        if newly_watched || now.duration_since(self.last_write) >= self.interval {
+            due.extend(self.take_pending(now));
+        }
+        due
+    }
+
+    fn take_pending(&mut self, now: Instant) -> Option<ProgressReport> {
+        let report = self.pending.take()?;
+        self.last_write = now;
+        if self.written.as_ref() == Some(&report) {
+            return None;
+        }
+        self.written = Some(report.clone());
+        Some(report)
+    }
+
+    fn write(&self, report: ProgressReport) {
+        // The writer only stops with the reporter
+        let _ = self.writes.send(report);
+    }
+}
+
+async fn write_report(db: &DatabaseConnection, report: &ProgressReport) -> Result<()> {
+    MediaService::update_playback_progress(
+        db,
+        &report.media_id,
+        report.position_ms,
+        report.duration_ms,
+        report.watched,
+    )
+    .await?;
+
+    BROKER
+        .broadcast(BrokerMessage::Data(DataMessage::PlaybackProgressUpdated {
+            media_id: report.media_id.to_string(),
+            watched: report.watched,
+        }))
+        .await;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    const INTERVAL: Duration = Duration::from_secs(10);
+
+    async fn reporter() -> ProgressReporter {
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        ProgressReporter::new(Arc::new(db), INTERVAL)
+    }
+
+    fn report(media_id: &str, position_secs: u64) -> ProgressReport {
+        ProgressReport::new(
+            &MediaItemId::new(media_id),
+            Duration::from_secs(position_secs),
+            Duration::from_secs(100),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reports_within_the_interval_are_coalesced() {
+        let mut reporter = reporter().await;
+        let start = reporter.last_write;
+
+        assert!(reporter.record(report("a", 1), start).is_empty());
+        assert!(
+            reporter
+                .record(report("a", 2), start + Duration::from_secs(5))
+                .is_empty()
+        );
+        assert_eq!(
+            reporter.record(report("a", 3), start + INTERVAL),
+            vec![report("a", 3)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watched_is_written_once_right_away() {
+        let mut reporter = reporter().await;
+        let start = reporter.last_write;
+
+        assert_eq!(
+            reporter.record(report("a", 91), start),
+            vec![report("a", 91)]
+        );
+        assert!(
+            reporter
+                .record(report("a", 92), start + Duration::from_secs(1))
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_switching_items_writes_the_previous_one() {
+        let mut reporter = reporter().await;
+        let start = reporter.last_write;
+
+        reporter.record(report("a", 40), start);
+        assert_eq!(
+            reporter.record(report("b", 1), start + Duration::from_secs(1)),
+            vec![report("a", 40)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_position_is_not_written_again() {
+        let mut reporter = reporter().await;
+        let start = reporter.last_write;
+
+        reporter.record(report("a", 40), start);
+        assert_eq!(reporter.take_pending(start), Some(report("a", 40)));
+        reporter.record(report("a", 40), start);
+        assert_eq!(reporter.take_pending(start), None);
+    }
+}
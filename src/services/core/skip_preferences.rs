@@ -0,0 +1,150 @@
+//! Per-show overrides of the auto-skip settings
+//!
+//! A show can always skip its intros or credits, always ask with the skip
+//! button, or never offer to skip, whatever the playback settings say. Shows
+//! without an override follow the settings.
+
+use anyhow::Result;
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{
+    MediaRepositoryImpl, Repository, SkipKind, SkipPreferenceRepository,
+    SkipPreferenceRepositoryImpl,
+};
+use crate::models::MediaItemId;
+
+/// How a show skips intros or credits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipMode {
+    /// Skip without asking
+    Always,
+    /// Show the skip button
+    Ask,
+    /// Neither skip nor show the button
+    Never,
+}
+
+impl SkipMode {
+    pub const ALL: [SkipMode; 3] = [SkipMode::Always, SkipMode::Ask, SkipMode::Never];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SkipMode::Always => "always",
+            SkipMode::Ask => "ask",
+            SkipMode::Never => "never",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.as_str() == value)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SkipMode::Always => "Always skip",
+            SkipMode::Ask => "Ask",
+            SkipMode::Never => "Never",
+        }
+    }
+}
+
+/// Overrides of a show, None where it follows the settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShowSkipModes {
+    pub intro: Option<SkipMode>,
+    pub credits: Option<SkipMode>,
+}
+
+/// Pure functions for per-show skip overrides
+pub struct SkipPreferencesService;
+
+impl SkipPreferencesService {
+    /// Overrides of a show
+    pub async fn show_modes(
+        db: &DatabaseConnection,
+        show_id: &MediaItemId,
+    ) -> Result<ShowSkipModes> {
+        let repo = SkipPreferenceRepositoryImpl::new(db.clone());
+        Ok(repo
+            .find_by_id(show_id.as_ref())
+            .await?
+            .map(|preference| ShowSkipModes {
+                intro: preference.intro.as_deref().and_then(SkipMode::parse),
+                credits: preference.credits.as_deref().and_then(SkipMode::parse),
+            })
+            .unwrap_or_default())
+    }
+
+    /// Overrides that apply to an item, those of its show for an episode
+    pub async fn item_modes(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> Result<ShowSkipModes> {
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        match media_repo
+            .find_by_id(media_id.as_ref())
+            .await?
+            .and_then(|item| item.parent_id)
+        {
+            Some(show_id) => Self::show_modes(db, &MediaItemId::new(show_id)).await,
+            None => Ok(ShowSkipModes::default()),
+        }
+    }
+
+    /// Override how a show skips one kind of marker, None to follow the
+    /// settings
+    pub async fn set_mode(
+        db: &DatabaseConnection,
+        show_id: &MediaItemId,
+        kind: SkipKind,
+        mode: Option<SkipMode>,
+    ) -> Result<()> {
+        let repo = SkipPreferenceRepositoryImpl::new(db.clone());
+        repo.set_mode(show_id.as_ref(), kind, mode.map(SkipMode::as_str))
+            .await
+    }
+
+    /// Whether to skip a marker automatically and whether to show its
+    /// button, from the show's override or else the settings
+    pub fn behavior(mode: Option<SkipMode>, auto_skip: bool, show_button: bool) -> (bool, bool) {
+        match mode {
+            Some(SkipMode::Always) => (true, false),
+            Some(SkipMode::Ask) => (false, true),
+            Some(SkipMode::Never) => (false, false),
+            None => (auto_skip, show_button),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_modes_round_trip() {
+        for mode in SkipMode::ALL {
+            assert_eq!(SkipMode::parse(mode.as_str()), Some(mode));
+        }
+        assert_eq!(SkipMode::parse("sometimes"), None);
+    }
+
+    #[test]
+    fn test_override_replaces_settings() {
+        assert_eq!(
+            SkipPreferencesService::behavior(None, false, true),
+            (false, true)
+        );
+        assert_eq!(
+            SkipPreferencesService::behavior(Some(SkipMode::Always), false, true),
+            (true, false)
+        );
+        assert_eq!(
+            SkipPreferencesService::behavior(Some(SkipMode::Ask), true, false),
+            (false, true)
+        );
+        assert_eq!(
+            SkipPreferencesService::behavior(Some(SkipMode::Never), true, true),
+            (false, false)
+        );
+    }
+}
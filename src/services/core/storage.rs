@@ -0,0 +1,407 @@
+//! Storage locations for the streaming cache, image cache and offline downloads
+//!
+//! Each location can be moved to another directory, including one on an external
+//! drive. Moving a location migrates the existing content before the new
+//! directory is used.
+
+use anyhow::{Context, Result, anyhow};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use sysinfo::Disks;
+use tracing::info;
+
+use crate::cache::FileCacheConfig;
+use crate::config::Config;
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{
+    CacheRepositoryImpl, OfflineContentRepository, OfflineContentRepositoryImpl,
+};
+use crate::services::cache_service::cache_service;
+use crate::services::config_service::config_service;
+
+/// The kinds of content Reel stores on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageKind {
+    StreamingCache,
+    ImageCache,
+    Downloads,
+}
+
+impl StorageKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StorageKind::StreamingCache => "Streaming Cache",
+            StorageKind::ImageCache => "Image Cache",
+            StorageKind::Downloads => "Offline Downloads",
+        }
+    }
+}
+
+/// Resolved directories for each kind of stored content
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageLocations {
+    pub streaming_cache: PathBuf,
+    pub image_cache: PathBuf,
+    pub downloads: PathBuf,
+}
+
+impl StorageLocations {
+    /// Directory for the given kind of content
+    pub fn get(&self, kind: StorageKind) -> &Path {
+        match kind {
+            StorageKind::StreamingCache => &self.streaming_cache,
+            StorageKind::ImageCache => &self.image_cache,
+            StorageKind::Downloads => &self.downloads,
+        }
+    }
+}
+
+impl From<&Config> for StorageLocations {
+    fn from(config: &Config) -> Self {
+        let streaming_cache = config.cache.cache_directory().unwrap_or_else(|_| {
+            FileCacheConfig::default_cache_directory()
+                .unwrap_or_else(|_| PathBuf::from("/tmp/reel/media"))
+        });
+        let image_cache = config
+            .storage
+            .image_cache_directory
+            .clone()
+            .unwrap_or_else(default_image_cache_directory);
+        let downloads = config
+            .storage
+            .downloads_directory
+            .clone()
+            .unwrap_or_else(default_downloads_directory);
+
+        Self {
+            streaming_cache,
+            image_cache,
+            downloads,
+        }
+    }
+}
+
+impl Default for StorageLocations {
+    fn default() -> Self {
+        Self::from(&Config::default())
+    }
+}
+
+fn default_image_cache_directory() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("reel")
+        .join("images")
+}
+
+fn default_downloads_directory() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("reel")
+        .join("downloads")
+}
+
+/// Global locations, kept in sync with the configuration by the ConfigService
+static STORAGE_LOCATIONS: RwLock<Option<StorageLocations>> = RwLock::new(None);
+
+/// Get the current storage locations
+pub fn storage_locations() -> StorageLocations {
+    STORAGE_LOCATIONS
+        .read()
+        .ok()
+        .and_then(|locations| locations.clone())
+        .unwrap_or_default()
+}
+
+/// Replace the global storage locations
+pub fn set_storage_locations(locations: StorageLocations) {
+    if let Ok(mut current) = STORAGE_LOCATIONS.write() {
+        *current = Some(locations);
+    }
+}
+
+/// Free space on the disk holding `path`, if it can be determined
+pub fn available_space(path: &Path) -> Option<u64> {
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// Free space on the disk holding `path`, looked up off the calling thread
+/// since listing the disks can stall on slow or sleeping drives
+pub async fn available_space_async(path: PathBuf) -> Option<u64> {
+    tokio::task::spawn_blocking(move || available_space(&path))
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Stateless service for moving storage locations
+pub struct StorageService;
+
+impl StorageService {
+    /// Move a kind of content to `new_dir`, migrating what is already stored.
+    ///
+    /// The streaming cache is stopped while its files move and restarted
+    /// afterwards, whether or not the move succeeded.
+    pub async fn relocate(
+        db: &DatabaseConnection,
+        kind: StorageKind,
+        new_dir: PathBuf,
+    ) -> Result<()> {
+        let old_dir = storage_locations().get(kind).to_path_buf();
+        if old_dir == new_dir {
+            return Ok(());
+        }
+        if new_dir.starts_with(&old_dir) {
+            return Err(anyhow!(
+                "{} can't be moved into its own directory",
+                kind.label()
+            ));
+        }
+
+        tokio::fs::create_dir_all(&new_dir)
+            .await
+            .with_context(|| format!("Failed to create {}", new_dir.display()))?;
+
+        info!(
+            "Moving {} from {} to {}",
+            kind.label(),
+            old_dir.display(),
+            new_dir.display()
+        );
+
+        let mut config = config_service().get_config().await;
+        match kind {
+            StorageKind::StreamingCache => {
+                cache_service().shutdown().await?;
+                let moved = async {
+                    Self::move_contents(&old_dir, &new_dir).await?;
+                    CacheRepositoryImpl::new(db.clone())
+                        .relocate_files(&old_dir, &new_dir)
+                        .await?;
+                    config.cache.cache_directory = Some(new_dir);
+                    config_service().update_config(config).await
+                }
+                .await;
+                // The cache comes back in whichever directory is configured
+                // now, the old one if the move failed
+                cache_service().initialize(db.clone()).await?;
+                moved?;
+            }
+            StorageKind::ImageCache => {
+                Self::move_contents(&old_dir, &new_dir).await?;
+                config.storage.image_cache_directory = Some(new_dir);
+                config_service().update_config(config).await?;
+            }
+            StorageKind::Downloads => {
+                Self::move_contents(&old_dir, &new_dir).await?;
+                OfflineContentRepositoryImpl::new(db.clone())
+                    .relocate_files(&old_dir, &new_dir)
+                    .await?;
+                config.storage.downloads_directory = Some(new_dir);
+                config_service().update_config(config).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move everything inside `from` to `to`, copying across file systems
+    async fn move_contents(from: &Path, to: &Path) -> Result<()> {
+        if !from.exists() {
+            return Ok(());
+        }
+
+        let from = from.to_path_buf();
+        let to = to.to_path_buf();
+        tokio::task::spawn_blocking(move || move_dir_contents(&from, &to)).await??;
+        Ok(())
+    }
+}
+
+/// Move everything inside `from` to `to`, all or nothing: entries are only
+/// taken out of `from` once all of them are in `to`, and a failure puts back
+/// what was moved so far
+fn move_dir_contents(from: &Path, to: &Path) -> Result<()> {
+    std::fs::create_dir_all(to).with_context(|| format!("Failed to create {}", to.display()))?;
+
+    let mut moved = Vec::new();
+    if let Err(e) = transfer_entries(from, to, &mut moved) {
+        for entry in moved.iter().rev() {
+            entry.undo();
+        }
+        return Err(e);
+    }
+
+    for entry in &moved {
+        entry.finish()?;
+    }
+    Ok(())
+}
+
+/// An entry moved by `move_dir_contents`
+struct MovedEntry {
+    source: PathBuf,
+    target: PathBuf,
+    /// Renamed in one step rather than copied, so there is nothing to clean up
+    renamed: bool,
+}
+
+impl MovedEntry {
+    /// Put the entry back where it was
+    fn undo(&self) {
+        let result = if self.renamed {
+            std::fs::rename(&self.target, &self.source)
+        } else {
+            remove_path(&self.target)
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to roll back {}: {}", self.target.display(), e);
+        }
+    }
+
+    /// Delete the original of a copied entry
+    fn finish(&self) -> Result<()> {
+        if self.renamed {
+            return Ok(());
+        }
+        remove_path(&self.source)
+            .with_context(|| format!("Failed to delete {}", self.source.display()))
+    }
+}
+
+fn transfer_entries(from: &Path, to: &Path, moved: &mut Vec<MovedEntry>) -> Result<()> {
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+
+        // A rename fails across file systems, e.g. when moving to an external drive
+        if std::fs::rename(&source, &target).is_ok() {
+            moved.push(MovedEntry {
+                source,
+                target,
+                renamed: true,
+            });
+            continue;
+        }
+
+        // Recorded before copying so a copy that fails halfway is removed too
+        moved.push(MovedEntry {
+            source: source.clone(),
+            target: target.clone(),
+            renamed: false,
+        });
+        if entry.file_type()?.is_dir() {
+            copy_dir(&source, &target)?;
+        } else {
+            std::fs::copy(&source, &target)
+                .with_context(|| format!("Failed to copy {}", source.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    std::fs::create_dir_all(to).with_context(|| format!("Failed to create {}", to.display()))?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&source, &target)?;
+        } else {
+            std::fs::copy(&source, &target)
+                .with_context(|| format!("Failed to copy {}", source.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else if path.exists() {
+        std::fs::remove_file(path)
+    } else {
+        Ok(())
+    }
+}
+
+/// Rewrite `path` from under `from` to under `to`, if it lives there
+pub fn rebase_path(path: &str, from: &Path, to: &Path) -> Option<String> {
+    Path::new(path)
+        .strip_prefix(from)
+        .ok()
+        .map(|relative| to.join(relative).to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebase_path() {
+        let from = Path::new("/home/user/.local/share/reel/downloads");
+        let to = Path::new("/media/external/reel");
+
+        assert_eq!(
+            rebase_path(
+                "/home/user/.local/share/reel/downloads/movie_1_high.mkv",
+                from,
+                to
+            ),
+            Some("/media/external/reel/movie_1_high.mkv".to_string())
+        );
+        assert_eq!(rebase_path("/elsewhere/movie.mkv", from, to), None);
+    }
+
+    #[test]
+    fn test_move_dir_contents() {
+        let from = tempfile::tempdir().unwrap();
+        let to = tempfile::tempdir().unwrap();
+        std::fs::write(from.path().join("a.cache"), b"a").unwrap();
+        std::fs::create_dir(from.path().join("nested")).unwrap();
+        std::fs::write(from.path().join("nested").join("b.jpg"), b"b").unwrap();
+
+        move_dir_contents(from.path(), to.path()).unwrap();
+
+        assert_eq!(std::fs::read(to.path().join("a.cache")).unwrap(), b"a");
+        assert_eq!(
+            std::fs::read(to.path().join("nested").join("b.jpg")).unwrap(),
+            b"b"
+        );
+        assert_eq!(std::fs::read_dir(from.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_failed_move_puts_everything_back() {
+        let from = tempfile::tempdir().unwrap();
+        let to = tempfile::tempdir().unwrap();
+        std::fs::write(from.path().join("a.cache"), b"a").unwrap();
+        let moved = vec![MovedEntry {
+            source: from.path().join("a.cache"),
+            target: to.path().join("a.cache"),
+            renamed: true,
+        }];
+        std::fs::rename(&moved[0].source, &moved[0].target).unwrap();
+        std::fs::write(to.path().join("b.cache"), b"partial").unwrap();
+        let copied = MovedEntry {
+            source: from.path().join("b.cache"),
+            target: to.path().join("b.cache"),
+            renamed: false,
+        };
+
+        copied.undo();
+        moved[0].undo();
+
+        assert_eq!(std::fs::read(from.path().join("a.cache")).unwrap(), b"a");
+        assert_eq!(std::fs::read_dir(to.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,238 @@
+//! Resolving the stream of an item within a time budget
+//!
+//! Playback first asks the server for the stream over the connection its
+//! source last used. When that doesn't answer quickly, the connections of the
+//! source are tested again alongside it and whichever gives a stream first
+//! wins. The whole search is bounded, so a server that can't be reached fails
+//! playback within seconds instead of leaving it loading.
+
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use tracing::{debug, info};
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::source_repository::SourceRepositoryImpl;
+use crate::db::repository::{MediaRepositoryImpl, Repository};
+use crate::models::{MediaItemId, PlaybackError, SourceId, StreamInfo};
+
+use super::backend::BackendService;
+use super::connection::ConnectionService;
+use super::connection_cache::ConnectionType;
+
+/// Longest playback waits for the stream of an item
+pub const RESOLVE_BUDGET: Duration = Duration::from_secs(15);
+
+/// How long the connection the source last used has to answer before its
+/// other connections are tested
+const REDISCOVERY_HEAD_START: Duration = Duration::from_secs(3);
+
+/// Where resolving the stream is at, shown while playback loads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveStatus {
+    Connecting,
+    Rediscovering,
+    Relay,
+}
+
+impl ResolveStatus {
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::Connecting => "Connecting to server…",
+            Self::Rediscovering => "Looking for another connection…",
+            Self::Relay => "Falling back to relay…",
+        }
+    }
+}
+
+pub struct StreamResolutionService;
+
+impl StreamResolutionService {
+    /// Stream of an item, resolved within `RESOLVE_BUDGET`. `on_status` hears
+    /// where resolving is at.
+    pub async fn resolve(
+        db: &DatabaseConnection,
+        media_item_id: &MediaItemId,
+        on_status: &(dyn Fn(ResolveStatus) + Send + Sync),
+    ) -> Result<StreamInfo> {
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        let media_item = media_repo
+            .find_by_id(media_item_id.as_str())
+            .await?
+            .ok_or_else(|| anyhow!("Media item not found"))?;
+
+        let source_repo = SourceRepositoryImpl::new(db.clone());
+        let source_entity = source_repo
+            .find_by_id(&media_item.source_id)
+            .await?
+            .ok_or_else(|| anyhow!("Source not found"))?;
+        let source_id = SourceId::new(source_entity.id.clone());
+
+        on_status(ResolveStatus::Connecting);
+        let cached = async {
+            let backend = BackendService::create_backend_for_source(db, &source_entity).await?;
+            backend.get_stream_url(media_item_id).await
+        };
+
+        let (source, source_id) = (&source_entity, &source_id);
+        let rediscovered = move || async move {
+            // Sources with a single address have nothing else to try
+            if source.connections.is_none() {
+                return Err(anyhow!("No other connections to try"));
+            }
+
+            on_status(ResolveStatus::Rediscovering);
+            let url = ConnectionService::retest_connections(db, source_id)
+                .await?
+                .ok_or_else(|| anyhow!("No connection of the server answered"))?;
+            if ConnectionService::cache()
+                .get(source_id)
+                .await
+                .is_some_and(|state| state.connection_type == ConnectionType::Relay)
+            {
+                on_status(ResolveStatus::Relay);
+            }
+            info!("Resolving the stream of {} over {}", media_item_id, url);
+
+            let mut source = source.clone();
+            source.connection_url = Some(url);
+            let backend = BackendService::create_backend_for_source(db, &source).await?;
+            backend.get_stream_url(media_item_id).await
+        };
+
+        race(cached, rediscovered, REDISCOVERY_HEAD_START, RESOLVE_BUDGET).await
+    }
+}
+
+/// Result of `cached`, or of `rediscovered` once `cached` failed or took
+/// longer than `head_start`, whichever succeeds first, within `budget`
+async fn race<T, C, R, F>(
+    cached: C,
+    rediscovered: R,
+    head_start: Duration,
+    budget: Duration,
+) -> Result<T>
+where
+    C: Future<Output = Result<T>>,
+    R: FnOnce() -> F,
+    F: Future<Output = Result<T>>,
+{
+    let resolve = async {
+        tokio::pin!(cached);
+        let cached_error = tokio::select! {
+            result = &mut cached => match result {
+                Ok(value) => return Ok(value),
+                Err(e) => Some(e),
+            },
+            _ = tokio::time::sleep(head_start) => None,
+        };
+
+        let rediscovered = rediscovered();
+        if let Some(e) = cached_error {
+            debug!("Connection the source last used failed: {}", e);
+            return rediscovered.await;
+        }
+
+        tokio::pin!(rediscovered);
+        tokio::select! {
+            result = &mut cached => match result {
+                Ok(value) => Ok(value),
+                Err(e) => {
+                    debug!("Connection the source last used failed: {}", e);
+                    rediscovered.await
+                }
+            },
+            result = &mut rediscovered => match result {
+                Ok(value) => Ok(value),
+                Err(e) => {
+                    debug!("No other connection gave a stream: {}", e);
+                    cached.await
+                }
+            },
+        }
+    };
+
+    tokio::time::timeout(budget, resolve)
+        .await
+        .map_err(|_| PlaybackError::NetworkTimeout)?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    const HEAD_START: Duration = Duration::from_millis(50);
+    const BUDGET: Duration = Duration::from_millis(500);
+
+    async fn after(delay: Duration, result: Result<&'static str>) -> Result<&'static str> {
+        tokio::time::sleep(delay).await;
+        result
+    }
+
+    #[tokio::test]
+    async fn test_quick_cached_connection_skips_rediscovery() {
+        let rediscovered = AtomicBool::new(false);
+        let result = race(
+            after(Duration::ZERO, Ok("cached")),
+            || {
+                rediscovered.store(true, Ordering::SeqCst);
+                after(Duration::ZERO, Ok("rediscovered"))
+            },
+            HEAD_START,
+            BUDGET,
+        )
+        .await;
+        assert_eq!(result.unwrap(), "cached");
+        assert!(!rediscovered.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_failed_cached_connection_rediscovers() {
+        let result = race(
+            after(Duration::ZERO, Err(anyhow!("unreachable"))),
+            || after(Duration::ZERO, Ok("rediscovered")),
+            HEAD_START,
+            BUDGET,
+        )
+        .await;
+        assert_eq!(result.unwrap(), "rediscovered");
+    }
+
+    #[tokio::test]
+    async fn test_slow_cached_connection_races_rediscovery() {
+        let result = race(
+            after(Duration::from_millis(300), Ok("cached")),
+            || after(Duration::from_millis(10), Ok("rediscovered")),
+            HEAD_START,
+            BUDGET,
+        )
+        .await;
+        assert_eq!(result.unwrap(), "rediscovered");
+
+        let result = race(
+            after(Duration::from_millis(100), Ok("cached")),
+            || after(Duration::from_millis(10), Err(anyhow!("no connection"))),
+            HEAD_START,
+            BUDGET,
+        )
+        .await;
+        assert_eq!(result.unwrap(), "cached");
+    }
+
+    #[tokio::test]
+    async fn test_resolving_gives_up_after_the_budget() {
+        let result = race(
+            after(Duration::from_secs(10), Ok("cached")),
+            || after(Duration::from_secs(10), Ok("rediscovered")),
+            HEAD_START,
+            BUDGET,
+        )
+        .await;
+        assert_eq!(
+            PlaybackError::from_error(&result.unwrap_err()),
+            PlaybackError::NetworkTimeout
+        );
+    }
+}
@@ -1,28 +1,34 @@
 use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage, SourceMessage};
 use anyhow::{Context, Result};
 use chrono::NaiveDateTime;
+use std::collections::HashSet;
 use tracing::{info, warn};
 
 use crate::backends::traits::MediaBackend;
 use crate::db::connection::DatabaseConnection;
 use crate::db::entities::SyncStatusModel;
 use crate::db::repository::{
-    Repository,
+    LibraryRepository, LibraryRepositoryImpl, LibrarySyncExclusionRepository,
+    LibrarySyncExclusionRepositoryImpl, Repository,
     source_repository::{SourceRepository, SourceRepositoryImpl},
     sync_repository::{SyncRepository, SyncRepositoryImpl},
 };
 use crate::models::{Library, MediaItem, Season, SourceId};
+use crate::services::core::favorites::FavoritesService;
 use crate::services::core::media::MediaService;
+use crate::services::core::sync_control::{SyncCancelled, SyncControl};
 
 /// Pure functions for synchronization operations
 pub struct SyncService;
 
 impl SyncService {
-    /// Sync all libraries for a source
+    /// Sync all libraries for a source. Pausing and cancelling through
+    /// `control` take effect between libraries, batches and seasons.
     pub async fn sync_source(
         db: &DatabaseConnection,
         backend: &dyn MediaBackend,
         source_id: &SourceId,
+        control: &SyncControl,
     ) -> Result<SyncResult> {
         info!("Starting sync for source: {}", source_id);
 
@@ -36,12 +42,6 @@ impl SyncService {
                     .errors
                     .push(format!("Failed to get libraries: {}", e));
                 Self::update_sync_status(db, source_id, SyncStatus::Failed, None).await?;
-                BROKER
-                    .broadcast(BrokerMessage::Source(SourceMessage::SyncError {
-                        source_id: source_id.to_string(),
-                        error: e.to_string(),
-                    }))
-                    .await;
                 BROKER
                     .broadcast(BrokerMessage::Data(DataMessage::LoadError {
                         source: source_id.to_string(),
@@ -52,6 +52,13 @@ impl SyncService {
             }
         };
 
+        // Libraries the user excluded never reach the database
+        let excluded = Self::excluded_library_ids(db, source_id).await?;
+        let libraries: Vec<Library> = libraries
+            .into_iter()
+            .filter(|library| !excluded.contains(&library.id))
+            .collect();
+
         result.libraries_synced = libraries.len();
 
         // Estimate total items to sync (rough estimate based on library types)
@@ -81,6 +88,8 @@ impl SyncService {
 
         // Sync libraries
         for library in libraries {
+            control.checkpoint().await?;
+
             // Save library
             MediaService::save_library(db, library.clone(), source_id).await?;
 
@@ -101,6 +110,7 @@ impl SyncService {
                 &library,
                 &mut cumulative_items_synced,
                 estimated_total_items,
+                control,
             )
             .await
             {
@@ -117,6 +127,7 @@ impl SyncService {
 
                     result.items_synced += items_count;
                 }
+                Err(e) if e.is::<SyncCancelled>() => return Err(e),
                 Err(e) => {
                     warn!("Failed to sync library {}: {}", library.id, e);
                     // Also log the full error chain for debugging
@@ -137,6 +148,14 @@ impl SyncService {
             }
         }
 
+        control.checkpoint().await?;
+
+        // Favorites point at synced items, so refresh them once the libraries are in
+        if let Err(e) = FavoritesService::sync_favorites(db, backend, source_id).await {
+            warn!("Failed to sync favorites for source {}: {}", source_id, e);
+            result.errors.push(format!("Favorites: {}", e));
+        }
+
         // Mark sync as complete
         Self::update_sync_status(
             db,
@@ -179,8 +198,16 @@ impl SyncService {
         library: &Library,
     ) -> Result<usize> {
         let mut dummy_progress = 0;
-        Self::sync_library_with_progress(db, backend, source_id, library, &mut dummy_progress, 0)
-            .await
+        Self::sync_library_with_progress(
+            db,
+            backend,
+            source_id,
+            library,
+            &mut dummy_progress,
+            0,
+            &SyncControl::default(),
+        )
+        .await
     }
 
     /// Sync a single library with cumulative progress tracking
@@ -191,6 +218,7 @@ impl SyncService {
         library: &Library,
         cumulative_items_synced: &mut usize,
         estimated_total: usize,
+        control: &SyncControl,
     ) -> Result<usize> {
         info!(
             "Syncing library: {} ({}) of type {:?}",
@@ -246,6 +274,7 @@ impl SyncService {
         // Save items in batches
         let batch_size = 100;
         for chunk in items.chunks(batch_size) {
+            control.checkpoint().await?;
             MediaService::save_media_items_batch(
                 db,
                 chunk.to_vec(),
@@ -315,6 +344,11 @@ impl SyncService {
                     let sync_counter = Arc::clone(&sync_counter);
 
                     async move {
+                        // Shows left once cancelled are skipped, the
+                        // cancellation is reported after the loop
+                        if control.checkpoint().await.is_err() {
+                            return;
+                        }
                         if let MediaItem::Show(mut show_data) = show {
                             // Check if show has seasons data, fetch if missing
                             let should_update_seasons =
@@ -370,6 +404,7 @@ impl SyncService {
                                 show_data.seasons.clone(),
                                 &mut *local_progress,
                                 estimated_total,
+                                control,
                             )
                             .await
                             {
@@ -377,6 +412,7 @@ impl SyncService {
                                     let mut sync = sync_counter.lock().await;
                                     *sync += episodes_count;
                                 }
+                                Err(e) if e.is::<SyncCancelled>() => {}
                                 Err(e) => {
                                     warn!(
                                         "Failed to sync episodes for show {}: {}",
@@ -388,6 +424,7 @@ impl SyncService {
                     }
                 })
                 .await;
+            control.checkpoint().await?;
 
             // Update local counters from Arc<Mutex> after parallel processing
             *cumulative_items_synced = *progress_counter.lock().await;
@@ -395,7 +432,6 @@ impl SyncService {
         }
 
         // Update library item count in database
-        use crate::db::repository::LibraryRepositoryImpl;
         let library_repo = LibraryRepositoryImpl::new(db.clone());
         if let Ok(Some(mut lib_entity)) = library_repo.find_by_id(&library.id).await {
             // Get actual count from media repository
@@ -448,6 +484,7 @@ impl SyncService {
             seasons,
             &mut dummy_progress,
             0,
+            &SyncControl::default(),
         )
         .await
     }
@@ -467,6 +504,7 @@ impl SyncService {
         seasons: Vec<Season>,
         cumulative_items_synced: &mut usize,
         estimated_total: usize,
+        control: &SyncControl,
     ) -> Result<usize> {
         use futures::stream::{FuturesUnordered, StreamExt};
 
@@ -505,6 +543,7 @@ impl SyncService {
 
         // Process each season's episodes as they come in
         while let Some((season_number, result)) = fetch_futures.next().await {
+            control.checkpoint().await?;
             match result {
                 Ok(episodes) => {
                     // Check for season_number = 0 issues
@@ -814,6 +853,102 @@ impl SyncService {
             None => SyncProgress::default(),
         })
     }
+
+    /// IDs of the libraries of a source that are excluded from sync
+    async fn excluded_library_ids(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+    ) -> Result<HashSet<String>> {
+        let repo = LibrarySyncExclusionRepositoryImpl::new(db.clone());
+        Ok(repo
+            .find_by_source(source_id.as_str())
+            .await?
+            .into_iter()
+            .map(|exclusion| exclusion.library_id)
+            .collect())
+    }
+
+    /// Libraries of a source with whether each one is synced, by title
+    pub async fn library_sync_choices(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+    ) -> Result<Vec<LibrarySyncChoice>> {
+        let library_repo = LibraryRepositoryImpl::new(db.clone());
+        let exclusion_repo = LibrarySyncExclusionRepositoryImpl::new(db.clone());
+
+        let mut choices: Vec<LibrarySyncChoice> = library_repo
+            .find_by_source(source_id.as_str())
+            .await?
+            .into_iter()
+            .map(|library| LibrarySyncChoice {
+                library_id: library.id,
+                title: library.title,
+                synced: true,
+            })
+            .chain(
+                exclusion_repo
+                    .find_by_source(source_id.as_str())
+                    .await?
+                    .into_iter()
+                    .map(|exclusion| LibrarySyncChoice {
+                        library_id: exclusion.library_id,
+                        title: exclusion.library_title,
+                        synced: false,
+                    }),
+            )
+            .collect();
+        choices.sort_by_key(|choice| choice.title.to_lowercase());
+
+        Ok(choices)
+    }
+
+    /// Include a library in sync or exclude it.
+    ///
+    /// Excluding removes everything already synced from the library. An included
+    /// library is backfilled by the next sync of its source.
+    pub async fn set_library_synced(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        library_id: &str,
+        synced: bool,
+    ) -> Result<()> {
+        let exclusion_repo = LibrarySyncExclusionRepositoryImpl::new(db.clone());
+
+        if synced {
+            exclusion_repo.delete(library_id).await?;
+            info!("Library {} will be synced again", library_id);
+            return Ok(());
+        }
+
+        let library_repo = LibraryRepositoryImpl::new(db.clone());
+        let library = library_repo
+            .find_by_id(library_id)
+            .await?
+            .with_context(|| format!("Library not found: {}", library_id))?;
+
+        exclusion_repo
+            .exclude(source_id.as_str(), library_id, &library.title)
+            .await?;
+        MediaService::clear_library(db, &library_id.to_string().into()).await?;
+        library_repo.delete(library_id).await?;
+        info!("Excluded library {} from sync", library.title);
+
+        BROKER
+            .broadcast(BrokerMessage::Source(SourceMessage::LibrariesChanged {
+                source_id: source_id.to_string(),
+            }))
+            .await;
+
+        Ok(())
+    }
+}
+
+/// A library as offered in the sync inclusion settings
+#[derive(Debug, Clone)]
+pub struct LibrarySyncChoice {
+    pub library_id: String,
+    pub title: String,
+    pub synced: bool,
 }
 
 #[derive(Debug, Default)]
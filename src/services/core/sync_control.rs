@@ -0,0 +1,112 @@
+//! Cancelling and pausing a running sync
+//!
+//! A sync checks in at [`SyncControl::checkpoint`] between libraries, batches
+//! of items and seasons. A paused sync waits there until it is resumed, and
+//! a cancelled one stops there with [`SyncCancelled`], so a sync is never cut
+//! off halfway through writing a batch to the database.
+
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+/// The sync was cancelled at a checkpoint
+#[derive(Debug, Error)]
+#[error("Sync cancelled")]
+pub struct SyncCancelled;
+
+/// Shared between a running sync and whoever may cancel or pause it
+#[derive(Debug, Clone)]
+pub struct SyncControl {
+    cancel: CancellationToken,
+    paused: Arc<watch::Sender<bool>>,
+}
+
+impl Default for SyncControl {
+    fn default() -> Self {
+        Self {
+            cancel: CancellationToken::new(),
+            paused: Arc::new(watch::Sender::new(false)),
+        }
+    }
+}
+
+impl SyncControl {
+    /// Stop the sync at its next checkpoint, even while paused
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Pause or resume the sync, returning whether that changed anything
+    pub fn set_paused(&self, paused: bool) -> bool {
+        self.paused.send_if_modified(|current| {
+            let changed = *current != paused;
+            *current = paused;
+            changed
+        })
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Wait while the sync is paused, and fail once it is cancelled
+    pub async fn checkpoint(&self) -> Result<(), SyncCancelled> {
+        let mut paused = self.paused.subscribe();
+        loop {
+            if self.cancel.is_cancelled() {
+                return Err(SyncCancelled);
+            }
+            if !*paused.borrow_and_update() {
+                return Ok(());
+            }
+            tokio::select! {
+                _ = self.cancel.cancelled() => {}
+                _ = paused.changed() => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_checkpoint_waits_while_paused() {
+        let control = SyncControl::default();
+        assert!(control.checkpoint().await.is_ok());
+
+        assert!(control.set_paused(true));
+        assert!(!control.set_paused(true));
+        let waiting = tokio::spawn({
+            let control = control.clone();
+            async move { control.checkpoint().await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        control.set_paused(false);
+        assert!(waiting.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_a_paused_sync() {
+        let control = SyncControl::default();
+        control.set_paused(true);
+        let waiting = tokio::spawn({
+            let control = control.clone();
+            async move { control.checkpoint().await }
+        });
+
+        control.cancel();
+        assert!(waiting.await.unwrap().is_err());
+        assert!(control.checkpoint().await.is_err());
+    }
+}
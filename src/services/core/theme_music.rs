@@ -0,0 +1,29 @@
+//! Theme music of movies and shows, played quietly on their detail pages
+//!
+//! Theme music is opt-in and never starts on a metered or offline
+//! connection, since it streams from the server.
+
+use anyhow::Result;
+
+use crate::db::connection::DatabaseConnection;
+use crate::models::MediaItemId;
+use crate::services::config_service::config_service;
+
+use super::backend::BackendService;
+use super::{connectivity, metered};
+
+/// Stream URL of the theme music of a movie or show, or None if it has none
+/// or theme music shouldn't play now
+pub async fn theme_music_url(
+    db: &DatabaseConnection,
+    media_id: &MediaItemId,
+) -> Result<Option<String>> {
+    if !config_service().get_config().await.ui.theme_music
+        || metered::is_metered()
+        || connectivity::is_offline()
+    {
+        return Ok(None);
+    }
+
+    BackendService::get_theme_music_url(db, media_id).await
+}
@@ -0,0 +1,255 @@
+//! Remembered audio and subtitle track choices
+//!
+//! A track picked during playback is remembered for the item and, for an
+//! episode, for its show, so re-watching the item or starting another episode
+//! selects it again. Tracks are matched by name because track IDs differ
+//! between files.
+
+use anyhow::Result;
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::entities::TrackPreferenceModel;
+use crate::db::repository::{
+    MediaRepositoryImpl, Repository, TrackKind, TrackPreferenceRepository,
+    TrackPreferenceRepositoryImpl,
+};
+use crate::models::MediaItemId;
+use crate::player::TrackLanguages;
+
+/// Three-letter ISO 639-2 codes, bibliographic and terminological, of common
+/// languages with their two-letter ISO 639-1 code
+const THREE_LETTER_CODES: &[(&str, &str)] = &[
+    ("ara", "ar"),
+    ("ces", "cs"),
+    ("chi", "zh"),
+    ("cze", "cs"),
+    ("dan", "da"),
+    ("deu", "de"),
+    ("dut", "nl"),
+    ("ell", "el"),
+    ("eng", "en"),
+    ("fin", "fi"),
+    ("fra", "fr"),
+    ("fre", "fr"),
+    ("ger", "de"),
+    ("gre", "el"),
+    ("heb", "he"),
+    ("hin", "hi"),
+    ("hun", "hu"),
+    ("ind", "id"),
+    ("ita", "it"),
+    ("jpn", "ja"),
+    ("kor", "ko"),
+    ("nld", "nl"),
+    ("nob", "nb"),
+    ("nor", "no"),
+    ("pol", "pl"),
+    ("por", "pt"),
+    ("ron", "ro"),
+    ("rum", "ro"),
+    ("rus", "ru"),
+    ("spa", "es"),
+    ("swe", "sv"),
+    ("tha", "th"),
+    ("tur", "tr"),
+    ("ukr", "uk"),
+    ("vie", "vi"),
+    ("zho", "zh"),
+];
+
+/// Tracks to select when an item starts playing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreferredTracks {
+    pub audio: Option<String>,
+    pub subtitle: Option<String>,
+    /// Subtitle file loaded for this item, not inherited from its show
+    pub subtitle_file: Option<String>,
+}
+
+/// Pure functions for track preference operations
+pub struct TrackPreferencesService;
+
+impl TrackPreferencesService {
+    /// Remember a track picked for an item, and for its show if it is an episode
+    pub async fn remember(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+        kind: TrackKind,
+        track: &str,
+    ) -> Result<()> {
+        let repo = TrackPreferenceRepositoryImpl::new(db.clone());
+        repo.set_track(media_id.as_ref(), kind, track).await?;
+
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        if let Some(show_id) = media_repo
+            .find_by_id(media_id.as_ref())
+            .await?
+            .and_then(|item| item.parent_id)
+        {
+            repo.set_track(&show_id, kind, track).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Remember a subtitle file loaded for an item. Unlike tracks it is not
+    /// passed on to the show, as a file belongs to a single episode.
+    pub async fn remember_subtitle_file(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+        path: &str,
+    ) -> Result<()> {
+        let repo = TrackPreferenceRepositoryImpl::new(db.clone());
+        repo.set_subtitle_file(media_id.as_ref(), path).await?;
+        Ok(())
+    }
+
+    /// Tracks to restore for an item: its own choices, else its show's
+    pub async fn preferred_tracks(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> Result<PreferredTracks> {
+        let repo = TrackPreferenceRepositoryImpl::new(db.clone());
+        let item = repo.find_by_id(media_id.as_ref()).await?;
+
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        let show = match media_repo
+            .find_by_id(media_id.as_ref())
+            .await?
+            .and_then(|item| item.parent_id)
+        {
+            Some(show_id) => repo.find_by_id(&show_id).await?,
+            None => None,
+        };
+
+        let pick = |track: fn(&TrackPreferenceModel) -> &Option<String>| {
+            item.as_ref()
+                .and_then(|item| track(item).clone())
+                .or_else(|| show.as_ref().and_then(|show| track(show).clone()))
+        };
+        Ok(PreferredTracks {
+            audio: pick(|preference| &preference.audio_track),
+            subtitle: pick(|preference| &preference.subtitle_track),
+            subtitle_file: item.and_then(|item| item.subtitle_file),
+        })
+    }
+
+    /// Forced subtitle track to show when the audio is in `language`, so
+    /// foreign dialogue and signs are translated. A forced track in the
+    /// audio's language is preferred over one without a language.
+    pub fn forced_subtitle_track(languages: &TrackLanguages, language: &str) -> Option<i32> {
+        let audio = languages.audio.as_deref()?;
+        if !same_language(audio, language) {
+            return None;
+        }
+
+        let forced = || languages.subtitles.iter().filter(|track| track.forced);
+        forced()
+            .find(|track| {
+                track
+                    .language
+                    .as_deref()
+                    .is_some_and(|track_language| same_language(track_language, audio))
+            })
+            .or_else(|| forced().find(|track| track.language.is_none()))
+            .map(|track| track.id)
+    }
+
+    /// ID of the track with a remembered name, among the tracks of the
+    /// playing file
+    pub fn find_track(tracks: &[(i32, String)], name: &str) -> Option<i32> {
+        tracks
+            .iter()
+            .find(|(_, track_name)| track_name == name)
+            .map(|(id, _)| *id)
+    }
+}
+
+/// Whether two language codes or locale names name the same language, such as
+/// "eng", "en" and "en_US.UTF-8"
+fn same_language(a: &str, b: &str) -> bool {
+    let (a, b) = (language_code(a), language_code(b));
+    !a.is_empty() && a == b
+}
+
+/// Two-letter code of a language code or locale name where one is known
+fn language_code(code: &str) -> String {
+    let code = code
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    THREE_LETTER_CODES
+        .iter()
+        .find(|(three_letter, _)| *three_letter == code)
+        .map(|(_, two_letter)| two_letter.to_string())
+        .unwrap_or(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::SubtitleTrackInfo;
+
+    #[test]
+    fn test_find_track_by_name() {
+        let tracks = vec![
+            (-1, "None".to_string()),
+            (1, "English".to_string()),
+            (2, "Japanese".to_string()),
+        ];
+        assert_eq!(
+            TrackPreferencesService::find_track(&tracks, "Japanese"),
+            Some(2)
+        );
+        assert_eq!(
+            TrackPreferencesService::find_track(&tracks, "None"),
+            Some(-1)
+        );
+        assert_eq!(TrackPreferencesService::find_track(&tracks, "French"), None);
+    }
+
+    #[test]
+    fn test_language_codes_match_across_forms() {
+        assert!(same_language("eng", "en_US.UTF-8"));
+        assert!(same_language("ger", "deu"));
+        assert!(same_language("pt-BR", "por"));
+        assert!(!same_language("eng", "fr_FR"));
+        assert!(!same_language("", ""));
+    }
+
+    #[test]
+    fn test_forced_subtitles_only_for_own_language_audio() {
+        let track = |id, language: Option<&str>, forced| SubtitleTrackInfo {
+            id,
+            language: language.map(str::to_string),
+            forced,
+        };
+        let languages = TrackLanguages {
+            audio: Some("eng".to_string()),
+            subtitles: vec![
+                track(1, Some("eng"), false),
+                track(2, None, true),
+                track(3, Some("eng"), true),
+                track(4, Some("fre"), true),
+            ],
+        };
+        assert_eq!(
+            TrackPreferencesService::forced_subtitle_track(&languages, "en_GB.UTF-8"),
+            Some(3)
+        );
+        assert_eq!(
+            TrackPreferencesService::forced_subtitle_track(&languages, "fr_FR.UTF-8"),
+            None
+        );
+
+        let untagged = TrackLanguages {
+            audio: Some("en".to_string()),
+            subtitles: vec![track(1, Some("eng"), false), track(2, None, true)],
+        };
+        assert_eq!(
+            TrackPreferencesService::forced_subtitle_track(&untagged, "en"),
+            Some(2)
+        );
+    }
+}
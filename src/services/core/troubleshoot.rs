@@ -0,0 +1,464 @@
+//! Step-by-step connection checks for a single source
+//!
+//! Runs what Reel does when it talks to a server one step at a time (name
+//! resolution, reaching each known address, signing in and opening a stream)
+//! and explains each failure in terms of what the user can do about it.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::error::Error as _;
+use std::time::Instant;
+use tracing::info;
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{MediaRepositoryImpl, Repository, SourceRepositoryImpl};
+use crate::models::{MediaItemId, ServerConnection, ServerConnections, SourceId};
+
+use super::auth::AuthService;
+use super::backend::BackendService;
+use super::network_policy::network_policy;
+
+/// Result of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Passed,
+    /// Failed in a way Reel can work around, such as a local address while away
+    Warning,
+    Failed,
+    /// Not run because an earlier check failed
+    Skipped,
+}
+
+/// Outcome of one troubleshooting step
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub title: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What the user can try when the check did not pass
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    fn passed(title: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            status: CheckStatus::Passed,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn failed(
+        title: impl Into<String>,
+        detail: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            status: CheckStatus::Failed,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn skipped(title: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            status: CheckStatus::Skipped,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+}
+
+/// Run every check for a source, in order
+pub async fn troubleshoot_source(
+    db: &DatabaseConnection,
+    source_id: &SourceId,
+) -> Result<Vec<CheckResult>> {
+    let source = SourceRepositoryImpl::new(db.clone())
+        .find_by_id(source_id.as_str())
+        .await?
+        .context("Source not found")?;
+    info!("Troubleshooting connection to source {}", source.name);
+
+    let candidates = candidate_connections(
+        source.connections.as_ref(),
+        source.connection_url.as_deref(),
+    );
+    let mut results = Vec::new();
+
+    if candidates.is_empty() {
+        results.push(CheckResult::failed(
+            "Server addresses",
+            "No addresses are known for this server",
+            "Sign in again so Reel can discover the server's addresses",
+        ));
+        results.push(CheckResult::skipped(
+            "Sign in",
+            "The server could not be reached",
+        ));
+        results.push(CheckResult::skipped(
+            "Playback",
+            "The server could not be reached",
+        ));
+        return Ok(results);
+    }
+
+    for host in unique_hosts(&candidates) {
+        results.push(check_dns(&host).await);
+    }
+
+    let mut reachability = Vec::new();
+    for connection in &candidates {
+        reachability.push(check_reachability(connection, source.mac_address.is_some()).await);
+    }
+    let any_reachable = reachability
+        .iter()
+        .any(|result| result.status == CheckStatus::Passed);
+    if any_reachable {
+        // Other addresses failing is expected, e.g. the local address while away
+        for result in &mut reachability {
+            if result.status == CheckStatus::Failed {
+                result.status = CheckStatus::Warning;
+            }
+        }
+    }
+    results.extend(reachability);
+
+    if !any_reachable {
+        results.push(CheckResult::skipped(
+            "Sign in",
+            "The server could not be reached",
+        ));
+        results.push(CheckResult::skipped(
+            "Playback",
+            "The server could not be reached",
+        ));
+        return Ok(results);
+    }
+
+    if AuthService::load_credentials(db, source_id)
+        .await?
+        .is_none()
+    {
+        results.push(CheckResult::failed(
+            "Sign in",
+            "No credentials are stored for this server",
+            "Use Re-authenticate to sign in again",
+        ));
+        results.push(CheckResult::skipped("Playback", "Signing in failed"));
+        return Ok(results);
+    }
+
+    let backend = match BackendService::create_backend_for_source(db, &source).await {
+        Ok(backend) => {
+            results.push(CheckResult::passed(
+                "Sign in",
+                "The server accepted the stored credentials",
+            ));
+            backend
+        }
+        Err(e) => {
+            results.push(CheckResult::failed(
+                "Sign in",
+                e.to_string(),
+                "Use Re-authenticate to sign in again. If that fails, check that your account still has access to this server",
+            ));
+            results.push(CheckResult::skipped("Playback", "Signing in failed"));
+            return Ok(results);
+        }
+    };
+
+    let media_repo = MediaRepositoryImpl::new(db.clone());
+    let mut sample = None;
+    for media_type in ["movie", "episode"] {
+        if let Some(item) = media_repo
+            .find_first_by_source_and_type(source_id.as_str(), media_type)
+            .await?
+        {
+            sample = Some(item);
+            break;
+        }
+    }
+    let Some(sample) = sample else {
+        results.push(CheckResult::skipped(
+            "Playback",
+            "No movies or episodes have been synced from this server yet",
+        ));
+        return Ok(results);
+    };
+
+    let stream_url = match backend
+        .get_stream_url(&MediaItemId::new(sample.id.clone()))
+        .await
+    {
+        Ok(stream) => stream.url,
+        Err(e) => {
+            results.push(CheckResult::failed(
+                "Playback",
+                format!("Requesting a stream for \"{}\" failed: {}", sample.title, e),
+                "Sync the source to refresh its library, then try again",
+            ));
+            return Ok(results);
+        }
+    };
+    results.push(check_stream(&sample.title, &stream_url).await);
+
+    Ok(results)
+}
+
+/// Stored connections of a source, best first, falling back to its single URL
+fn candidate_connections(
+    connections: Option<&serde_json::Value>,
+    connection_url: Option<&str>,
+) -> Vec<ServerConnection> {
+    let mut candidates = connections
+        .and_then(|json| serde_json::from_value::<ServerConnections>(json.clone()).ok())
+        .map(|stored| stored.connections)
+        .unwrap_or_default();
+    candidates.sort_by_key(|connection| connection.priority_score());
+
+    if candidates.is_empty()
+        && let Some(url) = connection_url
+        && let Ok(parsed) = url::Url::parse(url)
+    {
+        candidates.push(ServerConnection {
+            uri: url.to_string(),
+            protocol: parsed.scheme().to_string(),
+            address: parsed.host_str().unwrap_or_default().to_string(),
+            port: u32::from(parsed.port_or_known_default().unwrap_or_default()),
+            local: false,
+            relay: false,
+            priority: 0,
+            is_available: false,
+            response_time_ms: None,
+        });
+    }
+
+    candidates
+}
+
+/// Host names that need resolving, skipping literal IP addresses
+fn unique_hosts(candidates: &[ServerConnection]) -> Vec<String> {
+    candidates
+        .iter()
+        .filter_map(|connection| url::Url::parse(&connection.uri).ok())
+        .filter_map(|url| match url.host() {
+            Some(url::Host::Domain(domain)) => Some(domain.to_string()),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+async fn check_dns(host: &str) -> CheckResult {
+    let title = format!("Resolve {}", host);
+    let lookup = tokio::time::timeout(
+        network_policy().connection_timeout,
+        tokio::net::lookup_host((host, 0)),
+    )
+    .await;
+
+    match lookup {
+        Ok(Ok(addresses)) => {
+            let addresses: BTreeSet<_> = addresses.map(|address| address.ip()).collect();
+            if addresses.is_empty() {
+                CheckResult::failed(
+                    title,
+                    "The name has no addresses",
+                    "Check the server address, or your DNS and VPN settings",
+                )
+            } else {
+                CheckResult::passed(
+                    title,
+                    addresses
+                        .iter()
+                        .map(|ip| ip.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+            }
+        }
+        Ok(Err(e)) => CheckResult::failed(
+            title,
+            e.to_string(),
+            "Check the server address, or your DNS and VPN settings",
+        ),
+        Err(_) => CheckResult::failed(
+            title,
+            "The lookup timed out",
+            "Your DNS server is not responding. Check your network connection",
+        ),
+    }
+}
+
+async fn check_reachability(connection: &ServerConnection, can_wake: bool) -> CheckResult {
+    let title = format!("Reach {} address", connection_kind(connection));
+
+    let client = match network_policy().probe_client_builder().build() {
+        Ok(client) => client,
+        Err(e) => return CheckResult::failed(title, e.to_string(), "Restart Reel and try again"),
+    };
+
+    let start = Instant::now();
+    // Any HTTP response, even an authorization error, means the server is there
+    match client.get(&connection.uri).send().await {
+        Ok(_) => CheckResult::passed(
+            title,
+            format!(
+                "{} answered in {} ms",
+                connection.uri,
+                start.elapsed().as_millis()
+            ),
+        ),
+        Err(e) => CheckResult::failed(
+            title,
+            format!("{}: {}", connection.uri, error_chain(&e)),
+            reachability_hint(connection, &e, can_wake),
+        ),
+    }
+}
+
+async fn check_stream(title: &str, stream_url: &str) -> CheckResult {
+    let client = match network_policy().probe_client_builder().build() {
+        Ok(client) => client,
+        Err(e) => {
+            return CheckResult::failed("Playback", e.to_string(), "Restart Reel and try again");
+        }
+    };
+
+    // A single byte is enough to show the server will serve the file
+    let response = client
+        .get(stream_url)
+        .header(reqwest::header::RANGE, "bytes=0-0")
+        .send()
+        .await;
+
+    match response {
+        Ok(response) if response.status().is_success() => {
+            CheckResult::passed("Playback", format!("The server is serving \"{}\"", title))
+        }
+        Ok(response) => CheckResult::failed(
+            "Playback",
+            format!("Streaming \"{}\" returned {}", title, response.status()),
+            stream_status_hint(response.status().as_u16()),
+        ),
+        Err(e) => CheckResult::failed(
+            "Playback",
+            format!("Streaming \"{}\" failed: {}", title, error_chain(&e)),
+            "The server answered API requests but not the stream. Check for a proxy or firewall limiting large transfers",
+        ),
+    }
+}
+
+fn connection_kind(connection: &ServerConnection) -> &'static str {
+    if connection.relay {
+        "relay"
+    } else if connection.local {
+        "local"
+    } else {
+        "remote"
+    }
+}
+
+fn reachability_hint(
+    connection: &ServerConnection,
+    error: &reqwest::Error,
+    can_wake: bool,
+) -> String {
+    if is_certificate_error(error) {
+        return "The server's certificate is not trusted. Check the system clock and the server's certificate settings".to_string();
+    }
+
+    if connection.relay {
+        "The relay is only available while the server is signed in to Plex. Check that the server is running".to_string()
+    } else if connection.local {
+        let mut hint = "This address only works on the server's network. Make sure you are connected to the same network".to_string();
+        if can_wake {
+            hint.push_str(", or wake the server if it is asleep");
+        }
+        hint
+    } else if error.is_timeout() {
+        "The server did not answer in time. Check that it is running and that remote access is enabled".to_string()
+    } else {
+        "Check that remote access is enabled on the server and that its port is forwarded on your router".to_string()
+    }
+}
+
+fn stream_status_hint(status: u16) -> &'static str {
+    match status {
+        401 | 403 => {
+            "The server refused to stream to this account. Check its sharing and playback permissions"
+        }
+        404 => "The file was not found on the server. Sync the source to refresh its library",
+        _ => "The server could not serve the file. Check the server's logs for errors",
+    }
+}
+
+fn is_certificate_error(error: &reqwest::Error) -> bool {
+    error_chain(error).to_lowercase().contains("certificate")
+}
+
+/// An error with all of its causes, since reqwest's own message is often generic
+fn error_chain(error: &reqwest::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(uri: &str, local: bool) -> ServerConnection {
+        let url = url::Url::parse(uri).unwrap();
+        ServerConnection {
+            uri: uri.to_string(),
+            protocol: url.scheme().to_string(),
+            address: url.host_str().unwrap().to_string(),
+            port: u32::from(url.port_or_known_default().unwrap()),
+            local,
+            relay: false,
+            priority: 0,
+            is_available: false,
+            response_time_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_unique_hosts_skips_ip_addresses_and_duplicates() {
+        let candidates = [
+            connection("http://192.168.1.10:32400", true),
+            connection("https://server.example.com:32400", false),
+            connection("https://server.example.com:443", false),
+        ];
+        assert_eq!(unique_hosts(&candidates), vec!["server.example.com"]);
+    }
+
+    #[test]
+    fn test_candidates_fall_back_to_connection_url() {
+        let candidates = candidate_connections(None, Some("https://jellyfin.example.com"));
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].address, "jellyfin.example.com");
+        assert_eq!(candidates[0].port, 443);
+    }
+
+    #[test]
+    fn test_candidates_prefer_local_connections() {
+        let stored = ServerConnections::new(vec![
+            connection("https://remote.example.com:32400", false),
+            connection("http://192.168.1.10:32400", true),
+        ]);
+        let json = serde_json::to_value(&stored).unwrap();
+        let candidates = candidate_connections(Some(&json), None);
+        assert!(candidates[0].local);
+    }
+}
@@ -0,0 +1,124 @@
+//! Playback of network URLs that are not library items
+//!
+//! Direct links to media files and streams play as they are. Other links,
+//! such as video pages, are resolved to their stream with yt-dlp when it is
+//! installed and enabled.
+
+use anyhow::{Context, Result, anyhow};
+use std::io::ErrorKind;
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::{debug, info};
+
+/// Number of recently opened URLs kept in the configuration
+pub const RECENT_URL_LIMIT: usize = 10;
+
+/// How long yt-dlp may take to find a stream before it is killed
+const YTDLP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Extensions of files and playlists the players open without help
+const MEDIA_EXTENSIONS: &[&str] = &[
+    "mp4", "mkv", "webm", "mov", "avi", "m4v", "ts", "mp3", "m4a", "flac", "ogg", "opus", "wav",
+    "m3u8", "mpd",
+];
+
+/// Whether a URL points at a media file or stream rather than a web page
+pub fn is_direct_media_url(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let Some(file_name) = path.rsplit('/').next() else {
+        return false;
+    };
+    file_name.rsplit_once('.').is_some_and(|(_, extension)| {
+        MEDIA_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+    })
+}
+
+/// Whether the text is a URL the players can open
+pub fn is_playable_url(url: &str) -> bool {
+    ["http://", "https://", "rtsp://", "rtmp://"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+}
+
+/// The recent URLs with `url` moved or added to the top
+pub fn with_recent_url(recent: &[String], url: &str) -> Vec<String> {
+    std::iter::once(url.to_string())
+        .chain(recent.iter().filter(|other| *other != url).cloned())
+        .take(RECENT_URL_LIMIT)
+        .collect()
+}
+
+/// The URL to hand to the player for a URL opened by the user
+pub async fn resolve_stream_url(url: &str, use_ytdlp: bool) -> Result<String> {
+    if !use_ytdlp || is_direct_media_url(url) {
+        return Ok(url.to_string());
+    }
+
+    let mut command = Command::new("yt-dlp");
+    command
+        .args(["--get-url", "--format", "best", "--no-playlist", "--", url])
+        .kill_on_drop(true);
+    let output = match tokio::time::timeout(YTDLP_TIMEOUT, command.output())
+        .await
+        .map_err(|_| anyhow!("yt-dlp took too long to find a stream"))?
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            debug!("yt-dlp is not installed, opening {} directly", url);
+            return Ok(url.to_string());
+        }
+        Err(e) => return Err(e).context("Failed to run yt-dlp"),
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!(
+            "yt-dlp could not find a stream: {}",
+            stderr.lines().last().unwrap_or("unknown error").trim()
+        ));
+    }
+
+    let stream_url = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("yt-dlp returned no stream for {}", url))?;
+    info!("Resolved {} with yt-dlp", url);
+    Ok(stream_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direct_media_urls() {
+        assert!(is_direct_media_url("https://example.com/video.mp4"));
+        assert!(is_direct_media_url(
+            "https://cdn.example.com/live/index.M3U8?token=abc"
+        ));
+        assert!(!is_direct_media_url("https://www.youtube.com/watch?v=abc"));
+        assert!(!is_direct_media_url("https://example.com/"));
+    }
+
+    #[test]
+    fn test_playable_url_schemes() {
+        assert!(is_playable_url("https://example.com/watch"));
+        assert!(is_playable_url("rtsp://camera.local/stream"));
+        assert!(!is_playable_url("file:///etc/passwd"));
+        assert!(!is_playable_url("example.com/video.mp4"));
+    }
+
+    #[test]
+    fn test_recent_urls_move_to_top_and_are_capped() {
+        let recent = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        assert_eq!(with_recent_url(&recent, "b"), vec!["b", "a", "c"]);
+
+        let full: Vec<String> = (0..RECENT_URL_LIMIT).map(|i| i.to_string()).collect();
+        let updated = with_recent_url(&full, "new");
+        assert_eq!(updated.len(), RECENT_URL_LIMIT);
+        assert_eq!(updated[0], "new");
+        assert!(!updated.contains(&(RECENT_URL_LIMIT - 1).to_string()));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::db::DatabaseConnection;
+use crate::models::SourceId;
+use anyhow::{Result, anyhow};
+use std::net::SocketAddr;
+use tokio::net::UdpSocket;
+use tracing::{debug, info};
+
+/// Port conventionally used for Wake-on-LAN magic packets
+const WOL_PORT: u16 = 9;
+
+/// Service for waking sleeping media servers via Wake-on-LAN
+pub struct WakeOnLanService;
+
+impl WakeOnLanService {
+    /// Parse a MAC address in `AA:BB:CC:DD:EE:FF`, `AA-BB-CC-DD-EE-FF` or
+    /// `AABBCCDDEEFF` notation
+    pub fn parse_mac_address(mac: &str) -> Result<[u8; 6]> {
+        let hex: String = mac
+            .trim()
+            .chars()
+            .filter(|c| !matches!(c, ':' | '-' | '.'))
+            .collect();
+
+        if hex.len() != 12 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow!("Invalid MAC address: {}", mac));
+        }
+
+        let mut bytes = [0u8; 6];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)?;
+        }
+        Ok(bytes)
+    }
+
+    /// Normalize a MAC address to upper-case colon-separated notation
+    pub fn normalize_mac_address(mac: &str) -> Result<String> {
+        let bytes = Self::parse_mac_address(mac)?;
+        Ok(bytes
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(":"))
+    }
+
+    /// Build a magic packet: 6 bytes of 0xFF followed by the MAC repeated 16 times
+    pub fn magic_packet(mac: &[u8; 6]) -> Vec<u8> {
+        let mut packet = vec![0xFF; 6];
+        for _ in 0..16 {
+            packet.extend_from_slice(mac);
+        }
+        packet
+    }
+
+    /// Broadcast a magic packet for the given MAC address
+    pub async fn send_magic_packet(mac: &str) -> Result<()> {
+        let bytes = Self::parse_mac_address(mac)?;
+        let packet = Self::magic_packet(&bytes);
+
+        let socket = UdpSocket::bind("0.0.0.0:0").await?;
+        socket.set_broadcast(true)?;
+
+        let target = SocketAddr::from(([255, 255, 255, 255], WOL_PORT));
+        socket.send_to(&packet, target).await?;
+
+        debug!("Sent Wake-on-LAN packet for {} to {}", mac, target);
+        Ok(())
+    }
+
+    /// Wake the server behind a source using its stored MAC address
+    pub async fn wake_source(db: &DatabaseConnection, source_id: &SourceId) -> Result<()> {
+        use crate::db::repository::{Repository, SourceRepositoryImpl};
+
+        let repo = SourceRepositoryImpl::new(db.clone());
+        let source = repo
+            .find_by_id(source_id.as_ref())
+            .await?
+            .ok_or_else(|| anyhow!("Source not found: {}", source_id))?;
+
+        let mac = source
+            .mac_address
+            .ok_or_else(|| anyhow!("No MAC address configured for {}", source.name))?;
+
+        Self::send_magic_packet(&mac).await?;
+        info!("Sent Wake-on-LAN packet to {} ({})", source.name, mac);
+        Ok(())
+    }
+
+    /// Validate and store the MAC address for a source; an empty string clears it
+    pub async fn set_mac_address(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        mac: &str,
+    ) -> Result<Option<String>> {
+        use crate::db::repository::source_repository::{SourceRepository, SourceRepositoryImpl};
+
+        let mac_address = if mac.trim().is_empty() {
+            None
+        } else {
+            Some(Self::normalize_mac_address(mac)?)
+        };
+
+        let repo = SourceRepositoryImpl::new(db.clone());
+        repo.update_mac_address(source_id.as_ref(), mac_address.clone())
+            .await?;
+
+        Ok(mac_address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mac_address_formats() {
+        let expected = [0xAA, 0xBB, 0xCC, 0x01, 0x02, 0x03];
+        assert_eq!(
+            WakeOnLanService::parse_mac_address("AA:BB:CC:01:02:03").unwrap(),
+            expected
+        );
+        assert_eq!(
+            WakeOnLanService::parse_mac_address("aa-bb-cc-01-02-03").unwrap(),
+            expected
+        );
+        assert_eq!(
+            WakeOnLanService::parse_mac_address(" aabbcc010203 ").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_mac_address_rejects_invalid() {
+        assert!(WakeOnLanService::parse_mac_address("").is_err());
+        assert!(WakeOnLanService::parse_mac_address("AA:BB:CC:DD:EE").is_err());
+        assert!(WakeOnLanService::parse_mac_address("GG:BB:CC:DD:EE:FF").is_err());
+    }
+
+    #[test]
+    fn test_normalize_mac_address() {
+        assert_eq!(
+            WakeOnLanService::normalize_mac_address("aa-bb-cc-01-02-03").unwrap(),
+            "AA:BB:CC:01:02:03"
+        );
+    }
+
+    #[test]
+    fn test_magic_packet_layout() {
+        let mac = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+        let packet = WakeOnLanService::magic_packet(&mac);
+
+        assert_eq!(packet.len(), 102);
+        assert!(packet[..6].iter().all(|&b| b == 0xFF));
+        for chunk in packet[6..].chunks(6) {
+            assert_eq!(chunk, &mac);
+        }
+    }
+}
@@ -2,6 +2,7 @@ pub mod cache_service;
 pub mod config_service;
 pub mod conflict_resolver;
 pub mod initialization;
+pub mod playback_session;
 
 // Relm4 architecture modules
 pub mod commands;
@@ -0,0 +1,1017 @@
+//! The item a player is playing and what happens around it
+//!
+//! The player page shows playback; the session decides everything that
+//! doesn't need a widget. It knows which item and playlist are playing,
+//! writes progress back through a [`ProgressReporter`], reports the session
+//! to the server as it starts, plays, pauses and stops, looks up the intro
+//! and credits markers of the item and where to resume it, and tells the
+//! page what to do once the item nears its end. Audiobooks and podcasts
+//! save their position more often, remember the chapters listened to the
+//! end and jump back a little when resumed after a long pause.
+//!
+//! When the item streams from a server-side HLS transcode, the session also
+//! keeps the transcode alive, restarts it at the new position for seeks
+//! beyond what it has converted, and maps the positions of its playlist,
+//! which starts where the transcode started and grows as it runs, back to
+//! positions in the item.
+//!
+//! Sessions are reported the same way whether the item streams directly,
+//! from a transcode or from a download on disk, so the server lists every
+//! playback as now playing and a Plex PlayQueue moves along with it.
+
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info, warn};
+
+use crate::backends::traits::MediaBackend;
+use crate::config::{LongAudioConfig, PlaybackConfig};
+use crate::db::connection::DatabaseConnection;
+use crate::db::entities::PlaybackProgressModel;
+use crate::db::repository::source_repository::SourceRepositoryImpl;
+use crate::db::repository::{MediaRepository, MediaRepositoryImpl, Repository};
+use crate::models::{
+    ChapterMarker, MediaItem, MediaItemId, PlaylistContext, SessionEvent, SessionReport,
+    SessionStream, TranscodeSession,
+};
+use crate::player::PlayerState;
+use crate::services::commands::{Command, GetPlaybackProgressCommand};
+use crate::services::core::backend::BackendService;
+use crate::services::core::{LongAudioService, ProgressReporter};
+
+/// Fraction of an item played after which its ending is handled
+pub const ENDING_THRESHOLD: f64 = 0.95;
+
+/// How often a transcode, and the session of a paused item, are kept
+/// alive, well within the idle timeout of Plex and Jellyfin
+pub const SESSION_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often a playing session is reported, about as often as the servers'
+/// own clients do
+pub const SESSION_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Seeks this far past the playing position restart a transcode, which
+/// likely hasn't converted that far yet
+const TRANSCODE_SEEK_AHEAD: Duration = Duration::from_secs(30);
+
+/// What the player does once the item nears its end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemEnding {
+    /// Move on to the next item of the playlist
+    PlayNext,
+    /// The playlist is over, leave the player
+    EndOfPlaylist,
+    /// Auto-play played as many items in a row as the kids profile allows,
+    /// leave the player
+    AutoPlayLimitReached,
+}
+
+/// When playback picks up where it was left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResumeSettings {
+    pub auto_resume: bool,
+    /// Positions before this start from the beginning
+    pub threshold: Duration,
+    /// How far audiobooks and podcasts jump back when resumed a while later
+    pub long_audio: LongAudioConfig,
+}
+
+impl ResumeSettings {
+    pub fn from_config(config: &PlaybackConfig) -> Self {
+        Self {
+            auto_resume: config.auto_resume,
+            threshold: Duration::from_secs(config.resume_threshold_seconds as u64),
+            long_audio: config.long_audio,
+        }
+    }
+
+    /// Where to start an item, from its saved progress
+    pub async fn position(
+        &self,
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> Option<Duration> {
+        if !self.auto_resume {
+            return None;
+        }
+
+        let progress = GetPlaybackProgressCommand {
+            db: db.clone(),
+            media_id: media_id.clone(),
+        }
+        .execute()
+        .await
+        .ok()??;
+
+        let mut position = self.resume_from(&progress)?;
+        if is_long_audio(db, media_id).await {
+            let paused_for = (chrono::Utc::now().naive_utc() - progress.updated_at)
+                .to_std()
+                .unwrap_or_default();
+            position = LongAudioService::snap_back(&self.long_audio, position, paused_for);
+        }
+        info!(
+            "Resuming playback from {:?} ({:.1}% complete)",
+            position,
+            progress.get_progress_percentage() * 100.0
+        );
+        Some(position)
+    }
+
+    /// Items barely started, nearly finished or watched start over
+    fn resume_from(&self, progress: &PlaybackProgressModel) -> Option<Duration> {
+        let resumable = progress.position_ms > self.threshold.as_millis() as i64
+            && (progress.get_progress_percentage() as f64) < ENDING_THRESHOLD
+            && !progress.watched;
+        resumable.then(|| Duration::from_millis(progress.position_ms as u64))
+    }
+}
+
+/// Whether an item is an audiobook or podcast
+async fn is_long_audio(db: &DatabaseConnection, media_id: &MediaItemId) -> bool {
+    let media_repo = MediaRepositoryImpl::new(db.clone());
+    match media_repo.find_by_id(media_id.as_ref()).await {
+        Ok(Some(media)) => LongAudioService::is_long_audio(
+            &media.media_type,
+            Duration::from_millis(media.duration_ms.unwrap_or(0).max(0) as u64),
+        ),
+        _ => false,
+    }
+}
+
+/// Backend of the server an item is on
+async fn backend_of(
+    db: &DatabaseConnection,
+    media_id: &MediaItemId,
+) -> anyhow::Result<Box<dyn MediaBackend>> {
+    let media_repo = MediaRepositoryImpl::new(db.clone());
+    let media = media_repo
+        .find_by_id(media_id.as_ref())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Media item not found: {}", media_id))?;
+    let source_repo = SourceRepositoryImpl::new(db.clone());
+    let source = source_repo
+        .find_by_id(&media.source_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Source not found: {}", media.source_id))?;
+    BackendService::create_backend_for_source(db, &source).await
+}
+
+/// What to tell the server about a transcode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscodeAction {
+    KeepAlive,
+    Stop,
+}
+
+/// A request about a transcode, sent to the server running it
+#[derive(Debug)]
+pub struct TranscodeRequest {
+    db: DatabaseConnection,
+    media_id: MediaItemId,
+    session: TranscodeSession,
+    action: TranscodeAction,
+}
+
+impl TranscodeRequest {
+    pub async fn send(self) {
+        let backend = match backend_of(&self.db, &self.media_id).await {
+            Ok(backend) => backend,
+            Err(e) => {
+                debug!("No backend for transcode {}: {}", self.session.id, e);
+                return;
+            }
+        };
+        let result = match self.action {
+            TranscodeAction::KeepAlive => backend.keep_transcode_alive(&self.session).await,
+            TranscodeAction::Stop => backend.stop_transcode(&self.session).await,
+        };
+        if let Err(e) = result {
+            debug!(
+                "{:?} of transcode {} failed: {}",
+                self.action, self.session.id, e
+            );
+        }
+    }
+}
+
+/// How to seek to a position in the item
+#[derive(Debug)]
+pub enum SeekPlan {
+    /// Seek the player to this position of the loaded stream
+    InStream(Duration),
+    /// Stop the transcode and load the playlist of one restarted at the
+    /// position
+    Restart { stop: TranscodeRequest, url: String },
+}
+
+/// A report about the playback session, sent to the server of the item
+#[derive(Debug)]
+pub struct SessionReportRequest {
+    db: DatabaseConnection,
+    report: SessionReport,
+}
+
+impl SessionReportRequest {
+    pub async fn send(self) {
+        let backend = match backend_of(&self.db, &self.report.media_id).await {
+            Ok(backend) => backend,
+            Err(e) => {
+                debug!("No backend to report the playback session to: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = backend.report_session(&self.report).await {
+            debug!(
+                "Failed to report {:?} of {}: {}",
+                self.report.event, self.report.media_id, e
+            );
+        }
+    }
+}
+
+/// Playback of one item after another in a player
+#[derive(Debug)]
+pub struct PlaybackSession {
+    db: DatabaseConnection,
+    media_id: Option<MediaItemId>,
+    context: Option<PlaylistContext>,
+    reporter: ProgressReporter,
+    /// How often progress is written for video
+    progress_interval: Duration,
+    resume: ResumeSettings,
+    /// Whether the item is an audiobook or podcast
+    long_audio: bool,
+    /// Starts of the chapters of the item, once the player knows them
+    chapter_starts: Vec<Duration>,
+    /// Starts of the chapters listened to the end, for long-form audio
+    completed_chapters: Vec<Duration>,
+    /// When playback paused, to jump back after a long pause
+    paused_at: Option<Instant>,
+    /// Whether the ending of the item was handled already
+    ending_handled: bool,
+    /// Items auto-play may play in a row, set by the kids profile
+    auto_play_limit: Option<u32>,
+    /// Items auto-play played in a row
+    auto_played: u32,
+    /// Whether the next item is started by auto-play
+    auto_play_pending: bool,
+    /// Transcode the item streams from, if any
+    transcode: Option<TranscodeSession>,
+    /// Length of the item, which a transcode's playlist only reaches once
+    /// it is fully converted
+    item_duration: Option<Duration>,
+    /// Last position in the item
+    position: Duration,
+    /// Last length of the item
+    duration: Duration,
+    /// Where the item streams from, once it is known
+    stream: Option<SessionStream>,
+    /// Id the reports of a playback not streaming from a transcode share
+    play_session_id: String,
+    /// Whether the server was told the session started and not yet that
+    /// it stopped
+    session_reported: bool,
+    paused: bool,
+    last_session_report: Option<Instant>,
+}
+
+impl PlaybackSession {
+    pub fn new(db: DatabaseConnection, config: &PlaybackConfig) -> Self {
+        let progress_interval = Duration::from_secs(config.progress_update_interval_seconds as u64);
+        Self {
+            reporter: ProgressReporter::new(db.clone(), progress_interval),
+            db,
+            media_id: None,
+            context: None,
+            progress_interval,
+            resume: ResumeSettings::from_config(config),
+            long_audio: false,
+            chapter_starts: Vec::new(),
+            completed_chapters: Vec::new(),
+            paused_at: None,
+            ending_handled: false,
+            auto_play_limit: None,
+            auto_played: 0,
+            auto_play_pending: false,
+            transcode: None,
+            item_duration: None,
+            position: Duration::ZERO,
+            duration: Duration::ZERO,
+            stream: None,
+            play_session_id: String::new(),
+            session_reported: false,
+            paused: false,
+            last_session_report: None,
+        }
+    }
+
+    pub fn update_config(&mut self, config: &PlaybackConfig) {
+        self.resume = ResumeSettings::from_config(config);
+        self.progress_interval =
+            Duration::from_secs(config.progress_update_interval_seconds as u64);
+        self.reporter.set_interval(self.current_progress_interval());
+    }
+
+    /// How often the progress of the item is written, more often for
+    /// long-form audio
+    fn current_progress_interval(&self) -> Duration {
+        if self.long_audio {
+            Duration::from_secs(self.resume.long_audio.progress_interval_seconds as u64)
+        } else {
+            self.progress_interval
+        }
+    }
+
+    /// Limit the items auto-play plays in a row, `None` for no limit
+    pub fn set_auto_play_limit(&mut self, limit: Option<u32>) {
+        self.auto_play_limit = limit;
+    }
+
+    pub fn media_id(&self) -> Option<&MediaItemId> {
+        self.media_id.as_ref()
+    }
+
+    pub fn context(&self) -> Option<&PlaylistContext> {
+        self.context.as_ref()
+    }
+
+    pub fn resume_settings(&self) -> ResumeSettings {
+        self.resume
+    }
+
+    /// Move on to another item, or to a stream that isn't one, writing the
+    /// position the previous item was left at. The session of the previous
+    /// item is ended with [`Self::end_session`] before.
+    pub async fn start(&mut self, media_id: Option<MediaItemId>, context: Option<PlaylistContext>) {
+        self.reporter.flush();
+        self.media_id = media_id;
+        self.context = context;
+        self.ending_handled = false;
+        // Items started another way begin a new run of auto-play
+        if !std::mem::take(&mut self.auto_play_pending) {
+            self.auto_played = 0;
+        }
+        self.item_duration = None;
+        self.position = Duration::ZERO;
+        self.duration = Duration::ZERO;
+        self.stream = None;
+        self.session_reported = false;
+        self.paused = false;
+        self.paused_at = None;
+        self.chapter_starts.clear();
+
+        self.long_audio = match &self.media_id {
+            Some(media_id) => is_long_audio(&self.db, media_id).await,
+            None => false,
+        };
+        self.reporter.set_interval(self.current_progress_interval());
+        self.completed_chapters = match (&self.media_id, self.long_audio) {
+            (Some(media_id), true) => LongAudioService::completed_chapters(&self.db, media_id)
+                .await
+                .unwrap_or_else(|e| {
+                    debug!("Failed to load the chapters listened to: {}", e);
+                    Vec::new()
+                }),
+            _ => Vec::new(),
+        };
+    }
+
+    /// Note the chapters of the item, once the player knows them
+    pub fn chapters_loaded(&mut self, chapter_starts: Vec<Duration>) {
+        self.chapter_starts = chapter_starts;
+    }
+
+    /// Where long-form audio is among its chapters, for the pause overlay
+    pub fn chapter_summary(&self) -> Option<String> {
+        if !self.long_audio {
+            return None;
+        }
+        LongAudioService::chapter_summary(
+            &self.chapter_starts,
+            &self.completed_chapters,
+            self.position,
+        )
+    }
+
+    /// Where to jump back to as playback resumes, for long-form audio
+    /// resumed after a long pause. Called whenever playback starts playing.
+    pub fn snap_back(&mut self) -> Option<Duration> {
+        let paused_at = self.paused_at.take()?;
+        if !self.long_audio {
+            return None;
+        }
+        let target = LongAudioService::snap_back(
+            &self.resume.long_audio,
+            self.position,
+            paused_at.elapsed(),
+        );
+        (target != self.position).then_some(target)
+    }
+
+    /// Note the stream the item plays from, once it is known
+    pub fn stream_started(&mut self, url: &str, item_duration: Option<Duration>) {
+        self.transcode = TranscodeSession::from_url(url);
+        self.item_duration = item_duration;
+        self.stream = Some(SessionStream::of_url(url));
+        self.play_session_id = uuid::Uuid::new_v4().to_string();
+        if let Some(transcode) = &self.transcode {
+            info!(
+                "Streaming from transcode {} starting at {:?}",
+                transcode.id, transcode.offset
+            );
+        }
+    }
+
+    pub fn transcode(&self) -> Option<&TranscodeSession> {
+        self.transcode.as_ref()
+    }
+
+    /// Stop the transcode playback leaves, like when another item starts
+    pub fn end_transcode(&mut self) -> Option<TranscodeRequest> {
+        let session = self.transcode.take()?;
+        self.transcode_request(session, TranscodeAction::Stop)
+    }
+
+    /// Keep the transcode running, called every
+    /// [`SESSION_KEEP_ALIVE_INTERVAL`]
+    pub fn keep_transcode_alive(&self) -> Option<TranscodeRequest> {
+        let session = self.transcode.clone()?;
+        self.transcode_request(session, TranscodeAction::KeepAlive)
+    }
+
+    fn transcode_request(
+        &self,
+        session: TranscodeSession,
+        action: TranscodeAction,
+    ) -> Option<TranscodeRequest> {
+        Some(TranscodeRequest {
+            db: self.db.clone(),
+            media_id: self.media_id.clone()?,
+            session,
+            action,
+        })
+    }
+
+    /// How to seek to `target` in the item. Seeks into what a transcode has
+    /// converted stay in its playlist, anything before its start or well
+    /// ahead of the playing position restarts it there.
+    pub fn seek(&mut self, target: Duration) -> SeekPlan {
+        let Some(transcode) = self.transcode.clone() else {
+            return SeekPlan::InStream(target);
+        };
+        if target >= transcode.offset && target <= self.position + TRANSCODE_SEEK_AHEAD {
+            return SeekPlan::InStream(target - transcode.offset);
+        }
+        let Some(stop) = self.transcode_request(transcode.clone(), TranscodeAction::Stop) else {
+            return SeekPlan::InStream(target.saturating_sub(transcode.offset));
+        };
+
+        let restarted = transcode.restarted_at(target);
+        info!(
+            "Restarting transcode {} at {:?} as {}",
+            transcode.id, target, restarted.id
+        );
+        let url = restarted.url.clone();
+        self.transcode = Some(restarted);
+        self.position = target;
+        SeekPlan::Restart { stop, url }
+    }
+
+    /// Position in the item of a position reported by the player
+    pub fn item_position(&self, stream_position: Duration) -> Duration {
+        match &self.transcode {
+            Some(transcode) => transcode.item_position(stream_position),
+            None => stream_position,
+        }
+    }
+
+    /// Length of the item, given the duration reported by the player. The
+    /// playlist of a transcode grows as it runs, so the item's own length
+    /// is used for it.
+    pub fn item_duration(&self, stream_duration: Duration) -> Duration {
+        match (&self.transcode, self.item_duration) {
+            (Some(_), Some(duration)) => duration,
+            (Some(transcode), None) => transcode.offset + stream_duration,
+            (None, _) => stream_duration,
+        }
+    }
+
+    /// Handle the ending of the item again once it is reached, after the
+    /// auto-play it started was called off
+    pub fn reset_ending(&mut self) {
+        self.ending_handled = false;
+        if std::mem::take(&mut self.auto_play_pending) {
+            self.auto_played -= 1;
+        }
+    }
+
+    /// Write the latest position right away, like before the system suspends
+    pub fn flush(&mut self) {
+        self.reporter.flush();
+    }
+
+    /// Note the position of the item, returning what to do if it is nearing
+    /// its end
+    pub async fn position_changed(
+        &mut self,
+        position: Duration,
+        duration: Duration,
+    ) -> Option<ItemEnding> {
+        let media_id = self.media_id.clone()?;
+        let previous = std::mem::replace(&mut self.position, position);
+        self.duration = duration;
+        let ending = self.ending(position, duration);
+        self.reporter.report(&media_id, position, duration);
+        if self.long_audio {
+            self.note_listened_chapters(&media_id, previous, position, duration)
+                .await;
+        }
+        ending
+    }
+
+    /// Remember the chapters playback went through the end of
+    async fn note_listened_chapters(
+        &mut self,
+        media_id: &MediaItemId,
+        from: Duration,
+        to: Duration,
+        duration: Duration,
+    ) {
+        let listened: Vec<Duration> =
+            LongAudioService::chapters_played_through(&self.chapter_starts, duration, from, to)
+                .into_iter()
+                .filter(|start| !self.completed_chapters.contains(start))
+                .collect();
+        if listened.is_empty() {
+            return;
+        }
+        self.completed_chapters.extend(listened);
+        self.completed_chapters.sort();
+        if let Err(e) =
+            LongAudioService::save_completed_chapters(&self.db, media_id, &self.completed_chapters)
+                .await
+        {
+            debug!("Failed to save the chapters listened to: {}", e);
+        }
+    }
+
+    /// Follow the state of the player. Progress is written when playback
+    /// pauses or stops, and the server is told about pause, play and stop.
+    pub async fn state_changed(&mut self, state: &PlayerState) -> Option<SessionReportRequest> {
+        if matches!(state, PlayerState::Paused | PlayerState::Stopped) {
+            self.reporter.flush();
+        }
+        if matches!(state, PlayerState::Paused) {
+            self.paused_at.get_or_insert_with(Instant::now);
+        }
+        match state {
+            PlayerState::Playing | PlayerState::Paused => {
+                self.paused = matches!(state, PlayerState::Paused);
+                self.session_reported
+                    .then(|| self.session_report(SessionEvent::Progress))
+                    .flatten()
+            }
+            PlayerState::Stopped => self.end_session(),
+            _ => None,
+        }
+    }
+
+    /// Report the session once the item has a position to report, then
+    /// every [`SESSION_REPORT_INTERVAL`] while it plays, called as the
+    /// position changes
+    pub fn session_update(&mut self) -> Option<SessionReportRequest> {
+        if !self.session_reported {
+            return self.session_report(SessionEvent::Started);
+        }
+        if self.paused {
+            return None;
+        }
+        let due = self
+            .last_session_report
+            .is_none_or(|last| last.elapsed() >= SESSION_REPORT_INTERVAL);
+        due.then(|| self.session_report(SessionEvent::Progress))
+            .flatten()
+    }
+
+    /// Tell the server a paused session is still there, so it doesn't drop
+    /// it as idle, called every [`SESSION_KEEP_ALIVE_INTERVAL`]
+    pub fn keep_session_alive(&mut self) -> Option<SessionReportRequest> {
+        if !self.session_reported || !self.paused {
+            return None;
+        }
+        self.session_report(SessionEvent::Progress)
+    }
+
+    /// Tell the server the session stopped, like when playback leaves the
+    /// item, unless it knows already
+    pub fn end_session(&mut self) -> Option<SessionReportRequest> {
+        if !self.session_reported {
+            return None;
+        }
+        self.session_report(SessionEvent::Stopped)
+    }
+
+    fn session_report(&mut self, event: SessionEvent) -> Option<SessionReportRequest> {
+        let stream = self.stream?;
+        let media_id = self.media_id.clone()?;
+        let play_session_id = match &self.transcode {
+            Some(transcode) => transcode.id.clone(),
+            None => self.play_session_id.clone(),
+        };
+        self.session_reported = event != SessionEvent::Stopped;
+        self.last_session_report = Some(Instant::now());
+
+        Some(SessionReportRequest {
+            db: self.db.clone(),
+            report: SessionReport {
+                media_id,
+                event,
+                stream,
+                play_session_id,
+                position: self.position,
+                duration: self.duration,
+                paused: self.paused,
+                play_queue: self
+                    .context
+                    .as_ref()
+                    .and_then(|context| context.get_play_queue_info())
+                    .cloned(),
+            },
+        })
+    }
+
+    /// The ending of the item, decided once when it is nearly played
+    fn ending(&mut self, position: Duration, duration: Duration) -> Option<ItemEnding> {
+        if self.ending_handled
+            || position.as_secs_f64() / duration.as_secs_f64() <= ENDING_THRESHOLD
+        {
+            return None;
+        }
+        self.ending_handled = true;
+
+        match &self.context {
+            Some(context) if context.is_auto_play_enabled() => {
+                if !context.has_next() {
+                    info!("Item ending without a next one, will navigate back");
+                    Some(ItemEnding::EndOfPlaylist)
+                } else if self
+                    .auto_play_limit
+                    .is_some_and(|limit| self.auto_played >= limit)
+                {
+                    info!(
+                        "Auto-play played {} items in a row, stopping",
+                        self.auto_played
+                    );
+                    Some(ItemEnding::AutoPlayLimitReached)
+                } else {
+                    info!("Auto-play triggered, loading next item");
+                    self.auto_played += 1;
+                    self.auto_play_pending = true;
+                    Some(ItemEnding::PlayNext)
+                }
+            }
+            Some(_) => {
+                debug!("Item ending with auto-play disabled, letting it finish naturally");
+                None
+            }
+            None => {
+                debug!("Item ending without playlist context, letting it finish naturally");
+                None
+            }
+        }
+    }
+
+    /// Intro and credits markers of an item, fetched from its server when
+    /// the database has none
+    pub async fn load_markers(
+        db: &DatabaseConnection,
+        media_id: &MediaItemId,
+    ) -> (Option<ChapterMarker>, Option<ChapterMarker>) {
+        let media_repo = MediaRepositoryImpl::new(db.clone());
+        let Ok(Some(mut db_media)) = media_repo.find_by_id(media_id.as_ref()).await else {
+            return (None, None);
+        };
+
+        if db_media.intro_marker_start_ms.is_none() && db_media.credits_marker_start_ms.is_none() {
+            match BackendService::fetch_markers(db, media_id).await {
+                Ok((intro, credits)) => {
+                    if let Err(e) = media_repo
+                        .update_markers(media_id.as_ref(), intro, credits)
+                        .await
+                    {
+                        warn!("Failed to store markers in database: {}", e);
+                    } else {
+                        if let Some((start, end)) = intro {
+                            db_media.intro_marker_start_ms = Some(start);
+                            db_media.intro_marker_end_ms = Some(end);
+                        }
+                        if let Some((start, end)) = credits {
+                            db_media.credits_marker_start_ms = Some(start);
+                            db_media.credits_marker_end_ms = Some(end);
+                        }
+                    }
+                }
+                Err(e) => debug!(
+                    "Could not fetch markers from backend: {} (this is normal if markers aren't available)",
+                    e
+                ),
+            }
+        }
+
+        match MediaItem::try_from(db_media) {
+            Ok(MediaItem::Movie(movie)) => (movie.intro_marker, movie.credits_marker),
+            Ok(MediaItem::Episode(episode)) => (episode.intro_marker, episode.credits_marker),
+            _ => (None, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{PlayQueueInfo, QueueItem};
+    use std::sync::Arc;
+
+    async fn session() -> PlaybackSession {
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        PlaybackSession::new(Arc::new(db), &PlaybackConfig::default())
+    }
+
+    fn queue(current_index: usize, auto_play_next: bool, from_server: bool) -> PlaylistContext {
+        PlaylistContext::PlayQueue {
+            play_queue_info: from_server.then(|| PlayQueueInfo {
+                play_queue_id: 1,
+                play_queue_version: 1,
+                play_queue_item_id: 1,
+                source_uri: None,
+                shuffled: false,
+            }),
+            current_index,
+            items: ["a", "b"]
+                .iter()
+                .map(|id| QueueItem {
+                    id: MediaItemId::new(*id),
+                    title: id.to_string(),
+                    media_type: "movie".to_string(),
+                    duration_ms: Some(100_000),
+                    play_queue_item_id: None,
+                })
+                .collect(),
+            auto_play_next,
+        }
+    }
+
+    fn at(position_secs: u64) -> (Duration, Duration) {
+        (Duration::from_secs(position_secs), Duration::from_secs(100))
+    }
+
+    fn progress(position_ms: i64, watched: bool) -> PlaybackProgressModel {
+        PlaybackProgressModel {
+            id: 1,
+            media_id: "a".to_string(),
+            user_id: None,
+            position_ms,
+            duration_ms: 100_000,
+            watched,
+            view_count: 0,
+            last_watched_at: None,
+            updated_at: chrono::Utc::now().naive_utc(),
+            play_queue_id: None,
+            play_queue_version: None,
+            play_queue_item_id: None,
+            source_id: None,
+            completed_chapters: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ending_is_handled_once_per_item() {
+        let mut session = session().await;
+        session
+            .start(Some(MediaItemId::new("a")), Some(queue(0, true, false)))
+            .await;
+
+        let (position, duration) = at(90);
+        assert_eq!(session.ending(position, duration), None);
+        let (position, duration) = at(96);
+        assert_eq!(
+            session.ending(position, duration),
+            Some(ItemEnding::PlayNext)
+        );
+        let (position, duration) = at(97);
+        assert_eq!(session.ending(position, duration), None);
+
+        session
+            .start(Some(MediaItemId::new("b")), Some(queue(1, true, false)))
+            .await;
+        assert_eq!(
+            session.ending(position, duration),
+            Some(ItemEnding::EndOfPlaylist)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ending_is_handled_again_once_auto_play_is_called_off() {
+        let mut session = session().await;
+        session
+            .start(Some(MediaItemId::new("a")), Some(queue(0, true, false)))
+            .await;
+
+        let (position, duration) = at(96);
+        assert_eq!(
+            session.ending(position, duration),
+            Some(ItemEnding::PlayNext)
+        );
+        session.reset_ending();
+        assert_eq!(
+            session.ending(position, duration),
+            Some(ItemEnding::PlayNext)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_auto_play_stops_at_the_kids_profile_limit() {
+        let (position, duration) = at(96);
+        let mut session = session().await;
+        session.set_auto_play_limit(Some(1));
+
+        session
+            .start(Some(MediaItemId::new("a")), Some(queue(0, true, false)))
+            .await;
+        assert_eq!(
+            session.ending(position, duration),
+            Some(ItemEnding::PlayNext)
+        );
+        // Started by auto-play, the run goes on
+        session
+            .start(Some(MediaItemId::new("a")), Some(queue(0, true, false)))
+            .await;
+        assert_eq!(
+            session.ending(position, duration),
+            Some(ItemEnding::AutoPlayLimitReached)
+        );
+
+        // Started another way, a new run begins
+        session
+            .start(Some(MediaItemId::new("a")), Some(queue(0, true, false)))
+            .await;
+        assert_eq!(
+            session.ending(position, duration),
+            Some(ItemEnding::PlayNext)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ending_plays_out_without_auto_play() {
+        let (position, duration) = at(96);
+
+        let mut session = session().await;
+        session
+            .start(Some(MediaItemId::new("a")), Some(queue(0, false, false)))
+            .await;
+        assert_eq!(session.ending(position, duration), None);
+
+        session.start(Some(MediaItemId::new("a")), None).await;
+        assert_eq!(session.ending(position, duration), None);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_report_start_pause_and_stop() {
+        let mut session = session().await;
+        session
+            .start(Some(MediaItemId::new("a")), Some(queue(0, true, true)))
+            .await;
+        // Nothing to report before the stream and its position are known
+        assert!(session.session_update().is_none());
+        session.stream_started("http://plex:32400/library/parts/1/file.mkv", None);
+        assert!(session.state_changed(&PlayerState::Paused).await.is_none());
+
+        let (position, duration) = at(40);
+        session.position_changed(position, duration).await;
+        let started = session.session_update().unwrap().report;
+        assert_eq!(started.event, SessionEvent::Started);
+        assert_eq!(started.stream, SessionStream::Direct);
+        assert_eq!(started.position, position);
+        assert!(started.play_queue.is_some());
+        // Progress follows at the interval, pauses right away
+        assert!(session.session_update().is_none());
+        let paused = session.state_changed(&PlayerState::Paused).await.unwrap();
+        assert_eq!(paused.report.event, SessionEvent::Progress);
+        assert!(paused.report.paused);
+        assert_eq!(paused.report.play_session_id, started.play_session_id);
+        // Paused sessions are kept alive rather than reported as they poll
+        session.last_session_report = None;
+        assert!(session.session_update().is_none());
+        let alive = session.keep_session_alive().unwrap().report;
+        assert_eq!(alive.event, SessionEvent::Progress);
+        assert!(alive.paused);
+
+        let stopped = session.end_session().unwrap().report;
+        assert_eq!(stopped.event, SessionEvent::Stopped);
+        assert!(session.end_session().is_none());
+        assert!(session.keep_session_alive().is_none());
+        assert!(session.state_changed(&PlayerState::Stopped).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_downloads_and_transcodes_report_like_direct_streams() {
+        let mut session = session().await;
+        for (url, stream) in [
+            ("file:///downloads/a.mkv", SessionStream::Local),
+            (TRANSCODE, SessionStream::Transcode),
+        ] {
+            session.start(Some(MediaItemId::new("a")), None).await;
+            session.stream_started(url, None);
+            let (position, duration) = at(10);
+            session.position_changed(position, duration).await;
+            let report = session.session_update().unwrap().report;
+            assert_eq!(report.event, SessionEvent::Started);
+            assert_eq!(report.stream, stream);
+            assert!(session.end_session().is_some());
+        }
+        // Transcodes are reported under the session the server runs them in
+        let report = session
+            .session_report(SessionEvent::Started)
+            .unwrap()
+            .report;
+        assert_eq!(report.play_session_id, "play-1");
+    }
+
+    const TRANSCODE: &str = "http://jf:8096/Videos/a/main.m3u8?api_key=k&PlaySessionId=play-1";
+
+    #[tokio::test]
+    async fn test_transcode_positions_map_to_the_item() {
+        let mut session = session().await;
+        session.start(Some(MediaItemId::new("a")), None).await;
+        session.stream_started(TRANSCODE, Some(Duration::from_secs(3600)));
+
+        let SeekPlan::Restart { url, .. } = session.seek(Duration::from_secs(600)) else {
+            panic!("seeking far ahead should restart the transcode");
+        };
+        assert_eq!(session.transcode().unwrap().url, url);
+        assert_eq!(
+            session.item_position(Duration::from_secs(10)),
+            Duration::from_secs(610)
+        );
+        // The playlist only covers what was converted so far
+        assert_eq!(
+            session.item_duration(Duration::from_secs(40)),
+            Duration::from_secs(3600)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_seeks_within_the_transcode_stay_in_the_playlist() {
+        let mut session = session().await;
+        session.start(Some(MediaItemId::new("a")), None).await;
+        session.stream_started(TRANSCODE, None);
+        session
+            .position_changed(Duration::from_secs(100), Duration::from_secs(3600))
+            .await;
+
+        assert!(matches!(
+            session.seek(Duration::from_secs(50)),
+            SeekPlan::InStream(position) if position == Duration::from_secs(50)
+        ));
+        assert!(matches!(
+            session.seek(Duration::from_secs(200)),
+            SeekPlan::Restart { .. }
+        ));
+        // Before the start of the restarted transcode
+        assert!(matches!(
+            session.seek(Duration::from_secs(100)),
+            SeekPlan::Restart { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_direct_streams_seek_in_place() {
+        let mut session = session().await;
+        session.start(Some(MediaItemId::new("a")), None).await;
+        session.stream_started("http://jf:8096/Videos/a/stream?Static=true", None);
+
+        assert!(matches!(
+            session.seek(Duration::from_secs(600)),
+            SeekPlan::InStream(position) if position == Duration::from_secs(600)
+        ));
+        assert!(session.keep_transcode_alive().is_none());
+        assert!(session.end_transcode().is_none());
+    }
+
+    #[test]
+    fn test_resume_skips_started_finished_and_watched_items() {
+        let settings = ResumeSettings {
+            auto_resume: true,
+            threshold: Duration::from_secs(5),
+            long_audio: LongAudioConfig::default(),
+        };
+
+        assert_eq!(
+            settings.resume_from(&progress(40_000, false)),
+            Some(Duration::from_secs(40))
+        );
+        assert_eq!(settings.resume_from(&progress(3_000, false)), None);
+        assert_eq!(settings.resume_from(&progress(96_000, false)), None);
+        assert_eq!(settings.resume_from(&progress(40_000, true)), None);
+    }
+}
@@ -237,6 +237,7 @@ pub mod mock_backend {
             Ok(StreamInfo {
                 url: "http://mock.example.com/stream".to_string(),
                 direct_play: true,
+                mode: StreamMode::DirectPlay,
                 video_codec: "h264".to_string(),
                 audio_codec: "aac".to_string(),
                 container: "mp4".to_string(),
@@ -311,6 +312,15 @@ pub mod fixtures {
             playback_position: None,
             intro_marker: None,
             credits_marker: None,
+            video_height: None,
+            hdr_format: None,
+            atmos: false,
+            versions: vec![],
+            external_ids: vec![],
+            studios: vec![],
+            tags: vec![],
+            collections: vec![],
+            content_rating: None,
         }
     }
 
@@ -2,16 +2,19 @@ use gtk4::{glib, prelude::*};
 use libadwaita as adw;
 use libadwaita::prelude::*;
 use relm4::component::{AsyncComponent, AsyncComponentParts, AsyncComponentSender};
+use std::path::PathBuf;
 use tracing::{error, info};
 
 use crate::backends::MediaBackend;
+use crate::backends::emby::EmbyBackend;
 use crate::backends::jellyfin::JellyfinBackend;
+use crate::backends::jellyfin::discovery::DiscoveredServer;
 use crate::backends::plex::{PlexAuth, PlexHomeUser, PlexPin};
 use crate::db::connection::DatabaseConnection;
 use crate::models::{Credentials, SourceId};
 use crate::services::commands::Command;
 use crate::services::commands::auth_commands::{
-    CreateSourceCommand, UpdateSourceCredentialsCommand,
+    CreateLocalSourceCommand, CreateSourceCommand, UpdateSourceCredentialsCommand,
 };
 
 /// Convert technical errors to user-friendly, actionable messages
@@ -84,6 +87,8 @@ fn error_to_user_message(error: &anyhow::Error) -> String {
 pub enum BackendType {
     Plex,
     Jellyfin,
+    Emby,
+    Local,
 }
 
 #[derive(Debug, Clone)]
@@ -110,6 +115,14 @@ pub enum AuthDialogInput {
     CancelPlexAuth,
     RetryPlexAuth,
     OpenPlexLink,
+    // Plex sign-in link inputs, for networks where PIN polling fails
+    StartPlexSignInLink,
+    PlexSignInLinkReceived(PlexPin),
+    OpenPlexSignInLink,
+    CopyPlexSignInLink,
+    CheckPlexSignInLink,
+    PlexSignInLinkPending,
+    ShowPlexManualToken,
     // Jellyfin inputs
     UpdateJellyfinUrl(String),
     UpdateJellyfinUsername(String),
@@ -124,6 +137,23 @@ pub enum AuthDialogInput {
     JellyfinQuickConnectFailed(String),
     CancelJellyfinQuickConnect,
     CheckJellyfinQuickConnectStatus,
+    // Jellyfin server discovery inputs
+    DiscoverJellyfinServers,
+    JellyfinServersDiscovered(Vec<DiscoveredServer>),
+    SelectDiscoveredJellyfinServer(String),
+    // Emby inputs
+    UpdateEmbyUrl(String),
+    UpdateEmbyUsername(String),
+    UpdateEmbyPassword(String),
+    ConnectEmby,
+    EmbyAuthError(String),
+    RetryEmby,
+    // Local folder inputs
+    ChooseLocalFolder,
+    LocalFolderChosen(PathBuf),
+    AddLocalFolder,
+    LocalFolderError(String),
+    RetryLocalFolder,
     // Manual Plex inputs
     ConnectManualPlex,
 }
@@ -161,6 +191,11 @@ pub struct AuthDialog {
     plex_primary_token: Option<String>, // Store primary token during profile selection
     plex_pin_input_active: bool,
 
+    // Plex sign-in link state
+    plex_link_pin: Option<PlexPin>,
+    plex_link_checking: bool,
+    plex_link_pending: bool,
+
     // Jellyfin state
     jellyfin_url: String,
     jellyfin_url_confirmed: bool,
@@ -175,6 +210,20 @@ pub struct AuthDialog {
     jellyfin_quick_connect_code: Option<String>,
     jellyfin_quick_connect_secret: Option<String>,
     jellyfin_quick_connect_check_handle: Option<glib::SourceId>,
+    // Jellyfin server discovery state
+    jellyfin_discovery_in_progress: bool,
+    jellyfin_discovered_servers: Vec<DiscoveredServer>,
+
+    // Emby state
+    emby_url: String,
+    emby_username: String,
+    emby_password: String,
+    emby_auth_in_progress: bool,
+    emby_auth_error: Option<String>,
+
+    // Local folder state
+    local_folder: Option<PathBuf>,
+    local_folder_error: Option<String>,
 
     // Manual Plex state
     plex_server_url: String,
@@ -199,8 +248,13 @@ pub struct AuthDialog {
     jellyfin_error: adw::StatusPage,
     jellyfin_quick_connect_code_label: gtk4::Label,
     jellyfin_quick_connect_progress: gtk4::ProgressBar,
+    jellyfin_discovered_list: gtk4::ListBox,
+
+    // Emby widgets
+    emby_progress: gtk4::ProgressBar,
 
     // Manual Plex widgets
+    manual_plex_expander: adw::ExpanderRow,
     server_url_entry: adw::EntryRow,
     token_entry: adw::PasswordEntryRow,
 
@@ -289,28 +343,33 @@ impl AuthDialog {
                 return;
             }
 
-            // Discover available servers
-            let servers = match PlexAuth::discover_servers(&token).await {
-                Ok(servers) => servers,
-                Err(e) => {
-                    error!("Failed to discover Plex servers: {}", e);
-                    sender_clone.input(AuthDialogInput::PlexAuthError(
-                        "Unable to find your Plex servers. Check your network connection and try again.".to_string()
-                    ));
-                    return;
+            // Check if we have a manual URL
+            let is_manual = server_url.is_some();
+
+            // Discover available servers, unless one was entered by hand: then
+            // plex.tv, which restrictive networks may block, isn't needed
+            let servers = if is_manual {
+                Vec::new()
+            } else {
+                match PlexAuth::discover_servers(&token).await {
+                    Ok(servers) => servers,
+                    Err(e) => {
+                        error!("Failed to discover Plex servers: {}", e);
+                        sender_clone.input(AuthDialogInput::PlexAuthError(
+                            "Unable to find your Plex servers. Check your network connection, or enter the server URL and a token by hand.".to_string()
+                        ));
+                        return;
+                    }
                 }
             };
 
-            if servers.is_empty() {
+            if !is_manual && servers.is_empty() {
                 sender_clone.input(AuthDialogInput::PlexAuthError(
                     "No Plex servers found. You can add a server manually or switch to a different profile.".to_string(),
                 ));
                 return;
             }
 
-            // Check if we have a manual URL
-            let is_manual = server_url.is_some();
-
             // Select the best server URL
             let selected_server_url = if let Some(manual_url) = server_url {
                 // User provided a manual URL
@@ -520,7 +579,7 @@ impl AsyncComponent for AuthDialog {
                             set_valign: gtk4::Align::Center,
                             set_vexpand: true,
                             #[watch]
-                            set_visible: !model.plex_auth_in_progress && !model.plex_auth_success && !model.plex_profile_selection_active && !model.plex_pin_input_active && model.plex_auth_error.is_none(),
+                            set_visible: !model.plex_auth_in_progress && !model.plex_auth_success && !model.plex_profile_selection_active && !model.plex_pin_input_active && model.plex_auth_error.is_none() && model.plex_link_pin.is_none(),
 
                             adw::StatusPage {
                                 set_icon_name: Some("network-server-symbolic"),
@@ -600,6 +659,81 @@ impl AsyncComponent for AuthDialog {
                             },
                         },
 
+                        // Sign-in link state
+                        gtk4::Box {
+                            set_orientation: gtk4::Orientation::Vertical,
+                            set_spacing: 24,
+                            set_valign: gtk4::Align::Center,
+                            set_vexpand: true,
+                            #[watch]
+                            set_visible: model.plex_link_pin.is_some(),
+
+                            adw::StatusPage {
+                                set_icon_name: Some("send-to-symbolic"),
+                                set_title: "Sign In with a Link",
+                                set_description: Some("Open this link in a browser on any device, sign in, then come back and continue"),
+                                #[wrap(Some)]
+                                set_child = &gtk4::Box {
+                                    set_orientation: gtk4::Orientation::Vertical,
+                                    set_spacing: 12,
+
+                                    gtk4::Label {
+                                        add_css_class: "monospace",
+                                        set_selectable: true,
+                                        set_wrap: true,
+                                        set_wrap_mode: gtk4::pango::WrapMode::Char,
+                                        set_justify: gtk4::Justification::Center,
+                                        #[watch]
+                                        set_label: &model.plex_link_pin.as_ref().map(PlexAuth::sign_in_link).unwrap_or_default(),
+                                    },
+
+                                    gtk4::Box {
+                                        set_orientation: gtk4::Orientation::Horizontal,
+                                        set_spacing: 12,
+                                        set_halign: gtk4::Align::Center,
+
+                                        gtk4::Button {
+                                            set_label: "Open Link",
+                                            add_css_class: "pill",
+                                            connect_clicked => AuthDialogInput::OpenPlexSignInLink,
+                                        },
+
+                                        gtk4::Button {
+                                            set_label: "Copy Link",
+                                            add_css_class: "pill",
+                                            connect_clicked => AuthDialogInput::CopyPlexSignInLink,
+                                        },
+                                    },
+
+                                    gtk4::Label {
+                                        set_label: "Not signed in yet. Finish signing in through the link, then try again.",
+                                        add_css_class: "dim-label",
+                                        set_wrap: true,
+                                        set_justify: gtk4::Justification::Center,
+                                        #[watch]
+                                        set_visible: model.plex_link_pending,
+                                    },
+
+                                    gtk4::Button {
+                                        set_label: "I've Signed In",
+                                        add_css_class: "suggested-action",
+                                        add_css_class: "pill",
+                                        set_halign: gtk4::Align::Center,
+                                        set_margin_top: 12,
+                                        #[watch]
+                                        set_sensitive: !model.plex_link_checking,
+                                        connect_clicked => AuthDialogInput::CheckPlexSignInLink,
+                                    },
+
+                                    gtk4::Button {
+                                        set_label: "Cancel",
+                                        set_halign: gtk4::Align::Center,
+                                        connect_clicked => AuthDialogInput::CancelPlexAuth,
+                                    },
+                                },
+                            },
+                        },
+
                         // Profile Selection state
                         gtk4::Box {
                             set_orientation: gtk4::Orientation::Vertical,
@@ -734,11 +868,31 @@ impl AsyncComponent for AuthDialog {
                             #[watch]
                             set_visible: model.plex_auth_error.is_some(),
                             #[wrap(Some)]
-            set_child = &gtk4::Button {
-                                set_label: "Try Again",
+                            set_child = &gtk4::Box {
+                                set_orientation: gtk4::Orientation::Vertical,
+                                set_spacing: 12,
                                 set_halign: gtk4::Align::Center,
-                                add_css_class: "pill",
-                                connect_clicked => AuthDialogInput::RetryPlexAuth,
+
+                                gtk4::Button {
+                                    set_label: "Try Again",
+                                    add_css_class: "suggested-action",
+                                    add_css_class: "pill",
+                                    connect_clicked => AuthDialogInput::RetryPlexAuth,
+                                },
+
+                                // Networks that block polling plex.tv can still
+                                // sign in through a link or with a token
+                                gtk4::Button {
+                                    set_label: "Sign In with a Link",
+                                    add_css_class: "pill",
+                                    connect_clicked => AuthDialogInput::StartPlexSignInLink,
+                                },
+
+                                gtk4::Button {
+                                    set_label: "Enter a Token",
+                                    add_css_class: "pill",
+                                    connect_clicked => AuthDialogInput::ShowPlexManualToken,
+                                },
                             },
                         },
 
@@ -747,9 +901,10 @@ impl AsyncComponent for AuthDialog {
                             set_title: "Manual Configuration",
                             set_description: Some("Connect using server URL and auth token"),
                             #[watch]
-                            set_visible: !model.plex_auth_in_progress && !model.plex_auth_success && model.plex_auth_error.is_none(),
+                            set_visible: !model.plex_auth_in_progress && !model.plex_auth_success && model.plex_auth_error.is_none() && model.plex_link_pin.is_none(),
                             set_margin_top: 24,
 
+                            #[name = "manual_plex_expander"]
                             add = &adw::ExpanderRow {
                                 set_title: "Advanced Options",
                                 set_expanded: false,
@@ -801,6 +956,40 @@ impl AsyncComponent for AuthDialog {
                             #[watch]
                             set_visible: !model.jellyfin_auth_in_progress && !model.jellyfin_auth_success && model.jellyfin_auth_error.is_none() && !model.jellyfin_quick_connect_in_progress,
 
+                            // Servers found via network discovery
+                            adw::PreferencesGroup {
+                                set_title: "Servers on Your Network",
+                                #[watch]
+                                set_description: Some(if model.jellyfin_discovery_in_progress {
+                                    "Searching for Jellyfin servers..."
+                                } else if model.jellyfin_discovered_servers.is_empty() {
+                                    "No servers found automatically"
+                                } else {
+                                    "Select a server to use its address"
+                                }),
+                                #[watch]
+                                set_visible: model.reauth_mode.is_none(),
+
+                                #[wrap(Some)]
+                                set_header_suffix = &gtk4::Button {
+                                    set_icon_name: "view-refresh-symbolic",
+                                    set_tooltip_text: Some("Search again"),
+                                    set_valign: gtk4::Align::Center,
+                                    add_css_class: "flat",
+                                    #[watch]
+                                    set_sensitive: !model.jellyfin_discovery_in_progress,
+                                    connect_clicked => AuthDialogInput::DiscoverJellyfinServers,
+                                },
+
+                                #[name = "jellyfin_discovered_list"]
+                                add = &gtk4::ListBox {
+                                    add_css_class: "boxed-list",
+                                    set_selection_mode: gtk4::SelectionMode::None,
+                                    #[watch]
+                                    set_visible: !model.jellyfin_discovered_servers.is_empty(),
+                                },
+                            },
+
                             // Server URL - always editable
                             adw::PreferencesGroup {
                                 set_title: "Server Configuration",
@@ -1014,6 +1203,180 @@ impl AsyncComponent for AuthDialog {
                             },
                         },
                     },
+
+                    // Emby page - Emby has no Quick Connect, so username and password only
+                    add_titled[Some("emby"), "Emby"] = &gtk4::Box {
+                        set_orientation: gtk4::Orientation::Vertical,
+                        set_spacing: 24,
+                        set_margin_top: 12,
+                        set_margin_bottom: 12,
+                        set_margin_start: 12,
+                        set_margin_end: 12,
+
+                        // Main form
+                        gtk4::Box {
+                            set_orientation: gtk4::Orientation::Vertical,
+                            set_spacing: 24,
+                            #[watch]
+                            set_visible: !model.emby_auth_in_progress && model.emby_auth_error.is_none(),
+
+                            adw::PreferencesGroup {
+                                set_title: "Server Configuration",
+                                set_description: Some("Enter your Emby server address"),
+
+                                add = &adw::EntryRow {
+                                    set_title: "Server URL",
+                                    set_text: &model.emby_url,
+                                    set_input_hints: gtk4::InputHints::NO_SPELLCHECK,
+                                    connect_changed[sender] => move |entry| {
+                                        sender.input(AuthDialogInput::UpdateEmbyUrl(entry.text().to_string()));
+                                    },
+                                },
+
+                                add = &adw::ActionRow {
+                                    set_title: "Example",
+                                    set_subtitle: "http://192.168.1.100:8096 or https://emby.example.com",
+                                    add_css_class: "property",
+                                },
+                            },
+
+                            adw::PreferencesGroup {
+                                set_title: "Username & Password",
+                                set_description: Some("Sign in with your Emby credentials"),
+                                #[watch]
+                                set_sensitive: !model.emby_url.is_empty(),
+
+                                add = &adw::EntryRow {
+                                    set_title: "Username",
+                                    set_text: &model.emby_username,
+                                    connect_changed[sender] => move |entry| {
+                                        sender.input(AuthDialogInput::UpdateEmbyUsername(entry.text().to_string()));
+                                    },
+                                },
+
+                                add = &adw::PasswordEntryRow {
+                                    set_title: "Password",
+                                    set_text: &model.emby_password,
+                                    connect_changed[sender] => move |entry| {
+                                        sender.input(AuthDialogInput::UpdateEmbyPassword(entry.text().to_string()));
+                                    },
+                                },
+
+                                add = &adw::ActionRow {
+                                    #[wrap(Some)]
+                                    set_child = &gtk4::Button {
+                                        set_label: "Sign In",
+                                        set_valign: gtk4::Align::Center,
+                                        add_css_class: "suggested-action",
+                                        #[watch]
+                                        set_sensitive: !model.emby_url.is_empty() && !model.emby_username.is_empty() && !model.emby_auth_in_progress,
+                                        connect_clicked => AuthDialogInput::ConnectEmby,
+                                    },
+                                },
+                            },
+                        },
+
+                        // Progress state
+                        gtk4::Box {
+                            set_orientation: gtk4::Orientation::Vertical,
+                            set_spacing: 24,
+                            set_valign: gtk4::Align::Center,
+                            set_vexpand: true,
+                            #[watch]
+                            set_visible: model.emby_auth_in_progress,
+
+                            adw::StatusPage {
+                                set_icon_name: Some("network-transmit-receive-symbolic"),
+                                set_title: "Connecting...",
+                                set_description: Some("Authenticating with Emby server"),
+                            },
+
+                            #[name = "emby_progress"]
+                            gtk4::ProgressBar {
+                                #[watch]
+                                set_pulse_step: if model.emby_auth_in_progress { 0.1 } else { 0.0 },
+                            },
+                        },
+
+                        // Error state
+                        adw::StatusPage {
+                            set_icon_name: Some("dialog-error-symbolic"),
+                            set_title: "Connection Failed",
+                            #[watch]
+                            set_description: model.emby_auth_error.as_deref(),
+                            #[watch]
+                            set_visible: model.emby_auth_error.is_some(),
+                            #[wrap(Some)]
+                            set_child = &gtk4::Button {
+                                set_label: "Try Again",
+                                set_halign: gtk4::Align::Center,
+                                add_css_class: "pill",
+                                connect_clicked => AuthDialogInput::RetryEmby,
+                            },
+                        },
+                    },
+
+                    // Local folder page - no server, the files are scanned where they are
+                    add_titled[Some("local"), "Folder"] = &gtk4::Box {
+                        set_orientation: gtk4::Orientation::Vertical,
+                        set_spacing: 24,
+                        set_margin_top: 12,
+                        set_margin_bottom: 12,
+                        set_margin_start: 12,
+                        set_margin_end: 12,
+
+                        adw::PreferencesGroup {
+                            set_title: "Local Folder",
+                            set_description: Some("Play movies and shows from a folder on this computer. Shows are found by names like \"Show S01E02\"."),
+                            #[watch]
+                            set_visible: model.local_folder_error.is_none(),
+
+                            add = &adw::ActionRow {
+                                set_title: "Folder",
+                                #[watch]
+                                set_subtitle: &model
+                                    .local_folder
+                                    .as_ref()
+                                    .map(|path| path.display().to_string())
+                                    .unwrap_or_else(|| "No folder chosen".to_string()),
+                                add_css_class: "property",
+                                add_suffix = &gtk4::Button {
+                                    set_label: "Choose…",
+                                    set_valign: gtk4::Align::Center,
+                                    connect_clicked => AuthDialogInput::ChooseLocalFolder,
+                                },
+                            },
+
+                            add = &adw::ActionRow {
+                                #[wrap(Some)]
+                                set_child = &gtk4::Button {
+                                    set_label: "Add Folder",
+                                    set_valign: gtk4::Align::Center,
+                                    add_css_class: "suggested-action",
+                                    #[watch]
+                                    set_sensitive: model.local_folder.is_some(),
+                                    connect_clicked => AuthDialogInput::AddLocalFolder,
+                                },
+                            },
+                        },
+
+                        // Error state
+                        adw::StatusPage {
+                            set_icon_name: Some("dialog-error-symbolic"),
+                            set_title: "Folder Not Added",
+                            #[watch]
+                            set_description: model.local_folder_error.as_deref(),
+                            #[watch]
+                            set_visible: model.local_folder_error.is_some(),
+                            #[wrap(Some)]
+                            set_child = &gtk4::Button {
+                                set_label: "Try Again",
+                                set_halign: gtk4::Align::Center,
+                                add_css_class: "pill",
+                                connect_clicked => AuthDialogInput::RetryLocalFolder,
+                            },
+                        },
+                    },
                     },
                 },
             },
@@ -1030,6 +1393,7 @@ impl AsyncComponent for AuthDialog {
             match reauth.source_type.as_str() {
                 "plex" => BackendType::Plex,
                 "jellyfin" => BackendType::Jellyfin,
+                "emby" => BackendType::Emby,
                 _ => BackendType::Plex,
             }
         } else {
@@ -1057,6 +1421,11 @@ impl AsyncComponent for AuthDialog {
             plex_primary_token: None,
             plex_pin_input_active: false,
 
+            // Plex sign-in link state
+            plex_link_pin: None,
+            plex_link_checking: false,
+            plex_link_pending: false,
+
             // Jellyfin state
             jellyfin_url: String::new(),
             jellyfin_url_confirmed: false,
@@ -1071,6 +1440,20 @@ impl AsyncComponent for AuthDialog {
             jellyfin_quick_connect_code: None,
             jellyfin_quick_connect_secret: None,
             jellyfin_quick_connect_check_handle: None,
+            // Jellyfin server discovery state
+            jellyfin_discovery_in_progress: false,
+            jellyfin_discovered_servers: Vec::new(),
+
+            // Emby state
+            emby_url: String::new(),
+            emby_username: String::new(),
+            emby_password: String::new(),
+            emby_auth_in_progress: false,
+            emby_auth_error: None,
+
+            // Local folder state
+            local_folder: None,
+            local_folder_error: None,
 
             // Manual Plex state
             plex_server_url: String::new(),
@@ -1091,6 +1474,9 @@ impl AsyncComponent for AuthDialog {
             jellyfin_error: adw::StatusPage::new(),
             jellyfin_quick_connect_code_label: gtk4::Label::new(None),
             jellyfin_quick_connect_progress: gtk4::ProgressBar::new(),
+            jellyfin_discovered_list: gtk4::ListBox::new(),
+            emby_progress: gtk4::ProgressBar::new(),
+            manual_plex_expander: adw::ExpanderRow::new(),
             server_url_entry: adw::EntryRow::new(),
             token_entry: adw::PasswordEntryRow::new(),
             profile_flowbox: gtk4::FlowBox::new(),
@@ -1104,6 +1490,23 @@ impl AsyncComponent for AuthDialog {
         // Store reference to profile_flowbox from the view
         model.profile_flowbox = widgets.profile_flowbox.clone();
 
+        // Store references needed to read the manual Plex configuration
+        model.manual_plex_expander = widgets.manual_plex_expander.clone();
+        model.server_url_entry = widgets.server_url_entry.clone();
+        model.token_entry = widgets.token_entry.clone();
+
+        // Store references needed to fill in discovered Jellyfin servers
+        model.jellyfin_url_entry = widgets.jellyfin_url_entry.clone();
+        model.jellyfin_discovered_list = widgets.jellyfin_discovered_list.clone();
+
+        // Store reference to the Emby progress bar so it pulses while signing in
+        model.emby_progress = widgets.emby_progress.clone();
+
+        // Look for Jellyfin servers on the local network when adding a new source
+        if model.reauth_mode.is_none() {
+            sender.input(AuthDialogInput::DiscoverJellyfinServers);
+        }
+
         // Explicitly ensure no icons are displayed on auth tabs
         if let Some(plex_child) = widgets.view_stack.child_by_name("plex") {
             let plex_page = widgets.view_stack.page(&plex_child);
@@ -1113,16 +1516,38 @@ impl AsyncComponent for AuthDialog {
             let jellyfin_page = widgets.view_stack.page(&jellyfin_child);
             jellyfin_page.set_icon_name(None);
         }
+        if let Some(emby_child) = widgets.view_stack.child_by_name("emby") {
+            let emby_page = widgets.view_stack.page(&emby_child);
+            emby_page.set_icon_name(None);
+        }
+        if let Some(local_child) = widgets.view_stack.child_by_name("local") {
+            let local_page = widgets.view_stack.page(&local_child);
+            local_page.set_icon_name(None);
+        }
+
+        // Show the page of the source being re-authenticated, as the switcher is hidden
+        if model.reauth_mode.is_some() {
+            widgets
+                .view_stack
+                .set_visible_child_name(match model.backend_type {
+                    BackendType::Plex => "plex",
+                    BackendType::Jellyfin => "jellyfin",
+                    BackendType::Emby => "emby",
+                    BackendType::Local => "local",
+                });
+        }
 
         // Start progress bar pulse animations
         glib::timeout_add_local(std::time::Duration::from_millis(100), {
             let auth_progress = model.auth_progress.clone();
             let jellyfin_progress = model.jellyfin_progress.clone();
             let jellyfin_quick_connect_progress = model.jellyfin_quick_connect_progress.clone();
+            let emby_progress = model.emby_progress.clone();
             move || {
                 auth_progress.pulse();
                 jellyfin_progress.pulse();
                 jellyfin_quick_connect_progress.pulse();
+                emby_progress.pulse();
                 glib::ControlFlow::Continue
             }
         });
@@ -1197,6 +1622,9 @@ impl AsyncComponent for AuthDialog {
                 self.plex_auth_in_progress = false;
                 self.plex_auth_error = Some(error);
                 self.plex_pin = None;
+                self.plex_link_pin = None;
+                self.plex_link_checking = false;
+                self.plex_link_pending = false;
             }
 
             AuthDialogInput::PlexTokenReceived(token) => {
@@ -1206,6 +1634,8 @@ impl AsyncComponent for AuthDialog {
                     &token[..10.min(token.len())]
                 );
                 self.plex_auth_in_progress = false;
+                self.plex_link_pin = None;
+                self.plex_link_checking = false;
 
                 // Store the primary token
                 self.plex_primary_token = Some(token.clone());
@@ -1476,6 +1906,8 @@ impl AsyncComponent for AuthDialog {
                 info!("Cancelling Plex auth");
                 self.plex_auth_in_progress = false;
                 self.plex_pin = None;
+                self.plex_link_pin = None;
+                self.plex_link_pending = false;
                 self.plex_profile_selection_active = false;
                 self.plex_pin_input_active = false;
                 self.plex_primary_token = None;
@@ -1503,6 +1935,83 @@ impl AsyncComponent for AuthDialog {
                 );
             }
 
+            AuthDialogInput::StartPlexSignInLink => {
+                info!("Starting Plex sign-in link flow");
+                self.plex_auth_error = None;
+                self.plex_pin = None;
+
+                let sender_clone = sender.clone();
+                sender.oneshot_command(async move {
+                    match PlexAuth::get_link_pin().await {
+                        Ok(pin) => sender_clone.input(AuthDialogInput::PlexSignInLinkReceived(pin)),
+                        Err(e) => sender_clone.input(AuthDialogInput::PlexAuthError(format!(
+                            "Failed to create a sign-in link: {}. You can enter a token instead.",
+                            e
+                        ))),
+                    }
+                });
+            }
+
+            AuthDialogInput::PlexSignInLinkReceived(pin) => {
+                self.plex_link_pin = Some(pin);
+                self.plex_link_pending = false;
+            }
+
+            AuthDialogInput::OpenPlexSignInLink => {
+                if let Some(pin) = &self.plex_link_pin {
+                    let _ = gtk4::gio::AppInfo::launch_default_for_uri(
+                        &PlexAuth::sign_in_link(pin),
+                        None::<&gtk4::gio::AppLaunchContext>,
+                    );
+                }
+            }
+
+            AuthDialogInput::CopyPlexSignInLink => {
+                if let Some(pin) = &self.plex_link_pin {
+                    self.dialog
+                        .clipboard()
+                        .set_text(&PlexAuth::sign_in_link(pin));
+                }
+            }
+
+            AuthDialogInput::CheckPlexSignInLink => {
+                let Some(pin_id) = self.plex_link_pin.as_ref().map(|pin| pin.id.clone()) else {
+                    return;
+                };
+                self.plex_link_checking = true;
+
+                // A single check when asked, rather than the polling that
+                // restrictive networks cut off
+                let sender_clone = sender.clone();
+                sender.oneshot_command(async move {
+                    match PlexAuth::check_pin(&pin_id).await {
+                        Ok(Some(token)) => {
+                            sender_clone.input(AuthDialogInput::PlexTokenReceived(token))
+                        }
+                        Ok(None) => sender_clone.input(AuthDialogInput::PlexSignInLinkPending),
+                        Err(e) => sender_clone.input(AuthDialogInput::PlexAuthError(format!(
+                            "Failed to check the sign-in link: {}",
+                            e
+                        ))),
+                    }
+                });
+            }
+
+            AuthDialogInput::PlexSignInLinkPending => {
+                self.plex_link_checking = false;
+                self.plex_link_pending = true;
+            }
+
+            AuthDialogInput::ShowPlexManualToken => {
+                self.plex_auth_error = None;
+                self.manual_plex_expander.set_expanded(true);
+                // The token entry only shows once the view has updated
+                let token_entry = self.token_entry.clone();
+                glib::idle_add_local_once(move || {
+                    token_entry.grab_focus();
+                });
+            }
+
             AuthDialogInput::UpdateJellyfinUrl(url) => {
                 self.jellyfin_url = url;
             }
@@ -1636,6 +2145,164 @@ impl AsyncComponent for AuthDialog {
                 // Keep all fields so user can retry or modify them
             }
 
+            AuthDialogInput::UpdateEmbyUrl(url) => {
+                self.emby_url = url;
+            }
+
+            AuthDialogInput::UpdateEmbyUsername(username) => {
+                self.emby_username = username;
+            }
+
+            AuthDialogInput::UpdateEmbyPassword(password) => {
+                self.emby_password = password;
+            }
+
+            AuthDialogInput::ConnectEmby => {
+                info!("Connecting to Emby");
+
+                // Emby accounts may have no password
+                if self.emby_url.is_empty() || self.emby_username.is_empty() {
+                    self.emby_auth_error =
+                        Some("Please fill in the server URL and username".to_string());
+                    return;
+                }
+
+                self.emby_auth_in_progress = true;
+                self.emby_auth_error = None;
+
+                let url = self.emby_url.clone();
+                let username = self.emby_username.clone();
+                let password = self.emby_password.clone();
+                let db = self.db.clone();
+                let reauth_mode = self.reauth_mode.clone();
+                let sender_clone = sender.clone();
+
+                sender.oneshot_command(async move {
+                    let emby_backend = EmbyBackend::new();
+
+                    let credentials = match emby_backend
+                        .authenticate_with_credentials(&url, &username, &password)
+                        .await
+                    {
+                        Ok(credentials) => credentials,
+                        Err(e) => {
+                            error!("Emby authentication failed: {}", e);
+                            sender_clone
+                                .input(AuthDialogInput::EmbyAuthError(error_to_user_message(&e)));
+                            return;
+                        }
+                    };
+                    info!("Emby authentication successful for user: {}", username);
+
+                    let result = if let Some(reauth) = reauth_mode {
+                        info!(
+                            "Re-authentication mode: updating credentials for source {}",
+                            reauth.source_id
+                        );
+                        UpdateSourceCredentialsCommand {
+                            db,
+                            backend: &emby_backend as &dyn MediaBackend,
+                            source_id: reauth.source_id.clone(),
+                            new_credentials: credentials,
+                        }
+                        .execute()
+                        .await
+                    } else {
+                        CreateSourceCommand {
+                            db,
+                            backend: &emby_backend as &dyn MediaBackend,
+                            source_type: "emby".to_string(),
+                            name: format!("Emby - {}", username),
+                            credentials,
+                            server_url: Some(url),
+                            machine_id: None, // Not used for Emby
+                            is_owned: None,   // Not used for Emby
+                        }
+                        .execute()
+                        .await
+                    };
+
+                    match result {
+                        Ok(source) => {
+                            info!("Saved Emby source: {}", source.id);
+                            sender_clone
+                                .input(AuthDialogInput::SourceCreated(SourceId::new(source.id)));
+                        }
+                        Err(e) => {
+                            error!("Failed to save Emby source: {}", e);
+                            sender_clone
+                                .input(AuthDialogInput::EmbyAuthError(error_to_user_message(&e)));
+                        }
+                    }
+                });
+            }
+
+            AuthDialogInput::EmbyAuthError(error) => {
+                info!("Emby auth error: {}", error);
+                self.emby_auth_error = Some(error);
+                self.emby_auth_in_progress = false;
+            }
+
+            AuthDialogInput::RetryEmby => {
+                info!("Retrying Emby auth");
+                self.emby_auth_error = None;
+                self.emby_auth_in_progress = false;
+            }
+
+            AuthDialogInput::ChooseLocalFolder => {
+                let dialog = gtk4::FileDialog::builder()
+                    .title("Choose a Folder of Videos")
+                    .modal(true)
+                    .build();
+                let sender = sender.clone();
+                dialog.select_folder(
+                    self.parent_window.as_ref(),
+                    None::<&gtk4::gio::Cancellable>,
+                    move |result| {
+                        if let Ok(folder) = result
+                            && let Some(path) = folder.path()
+                        {
+                            sender.input(AuthDialogInput::LocalFolderChosen(path));
+                        }
+                    },
+                );
+            }
+
+            AuthDialogInput::LocalFolderChosen(path) => {
+                self.local_folder = Some(path);
+            }
+
+            AuthDialogInput::AddLocalFolder => {
+                let Some(path) = self.local_folder.clone() else {
+                    return;
+                };
+                info!("Adding local folder {}", path.display());
+
+                let db = self.db.clone();
+                let sender_clone = sender.clone();
+                sender.oneshot_command(async move {
+                    match (CreateLocalSourceCommand { db, path }).execute().await {
+                        Ok(source) => {
+                            info!("Saved local folder source: {}", source.id);
+                            sender_clone
+                                .input(AuthDialogInput::SourceCreated(SourceId::new(source.id)));
+                        }
+                        Err(e) => {
+                            error!("Failed to add local folder: {}", e);
+                            sender_clone.input(AuthDialogInput::LocalFolderError(e.to_string()));
+                        }
+                    }
+                });
+            }
+
+            AuthDialogInput::LocalFolderError(error) => {
+                self.local_folder_error = Some(error);
+            }
+
+            AuthDialogInput::RetryLocalFolder => {
+                self.local_folder_error = None;
+            }
+
             AuthDialogInput::StartJellyfinQuickConnect => {
                 info!("Starting Jellyfin Quick Connect - button clicked");
                 info!("Current URL: '{}'", self.jellyfin_url);
@@ -1899,13 +2566,66 @@ impl AsyncComponent for AuthDialog {
                 self.jellyfin_quick_connect_secret = None;
             }
 
+            AuthDialogInput::DiscoverJellyfinServers => {
+                if self.jellyfin_discovery_in_progress {
+                    return;
+                }
+
+                info!("Discovering Jellyfin servers on the local network");
+                self.jellyfin_discovery_in_progress = true;
+
+                let sender_clone = sender.clone();
+                sender.oneshot_command(async move {
+                    use crate::backends::jellyfin::discovery::discover_servers;
+
+                    let servers = discover_servers(std::time::Duration::from_secs(3)).await;
+                    sender_clone.input(AuthDialogInput::JellyfinServersDiscovered(servers));
+                });
+            }
+
+            AuthDialogInput::JellyfinServersDiscovered(servers) => {
+                info!("Found {} Jellyfin server(s)", servers.len());
+                self.jellyfin_discovery_in_progress = false;
+
+                while let Some(child) = self.jellyfin_discovered_list.first_child() {
+                    self.jellyfin_discovered_list.remove(&child);
+                }
+
+                for server in &servers {
+                    let row = adw::ActionRow::builder()
+                        .title(&server.name)
+                        .subtitle(&server.address)
+                        .activatable(true)
+                        .build();
+                    row.add_suffix(&gtk4::Image::from_icon_name("go-next-symbolic"));
+
+                    let sender_clone = sender.clone();
+                    let address = server.address.clone();
+                    row.connect_activated(move |_| {
+                        sender_clone.input(AuthDialogInput::SelectDiscoveredJellyfinServer(
+                            address.clone(),
+                        ));
+                    });
+
+                    self.jellyfin_discovered_list.append(&row);
+                }
+
+                self.jellyfin_discovered_servers = servers;
+            }
+
+            AuthDialogInput::SelectDiscoveredJellyfinServer(address) => {
+                info!("Using discovered Jellyfin server at {}", address);
+                // Setting the entry text emits UpdateJellyfinUrl through connect_changed
+                self.jellyfin_url_entry.set_text(&address);
+            }
+
             AuthDialogInput::ConnectManualPlex => {
                 info!("Connecting with manual Plex credentials");
                 self.plex_server_url = self.server_url_entry.text().to_string();
                 self.plex_token = self.token_entry.text().to_string();
 
-                if self.plex_server_url.is_empty() || self.plex_token.is_empty() {
-                    self.plex_auth_error = Some("Please fill in all fields".to_string());
+                if self.plex_token.is_empty() {
+                    self.plex_auth_error = Some("Please enter an auth token".to_string());
                     return;
                 }
 
@@ -0,0 +1,551 @@
+use adw::prelude::*;
+use libadwaita as adw;
+use relm4::gtk;
+use relm4::gtk::glib;
+use relm4::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::Level;
+
+use crate::backends::request_log::{REQUEST_LOG, RecordedRequest};
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{Repository, SourceRepositoryImpl};
+use crate::logging::{LOG_BUFFER, LOG_LEVELS};
+use crate::services::core::diagnostics;
+
+/// How often the log and request views pick up newly captured entries
+const LOG_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Mark that stays at the end of the log, used to keep following new entries
+const LOG_END_MARK: &str = "log-end";
+
+#[derive(Debug)]
+pub struct DiagnosticsDialog {
+    db: DatabaseConnection,
+    level: Level,
+    /// Sequence number of the next log entry to show
+    next_seq: u64,
+    refresh_timer: Option<glib::SourceId>,
+    exporting: bool,
+    log_view: gtk::TextView,
+    toast_overlay: adw::ToastOverlay,
+    /// Name of the visible page, "log" or "requests"
+    page: String,
+    recording_requests: bool,
+    /// Sequence number of the next recorded request to show
+    next_request_seq: u64,
+    request_list: gtk::ListBox,
+    /// Source whose requests are recorded and listed, every source's if None
+    request_source: Option<String>,
+    source_dropdown: gtk::DropDown,
+    /// Sources in the order of the dropdown, after "All Sources"
+    source_choices: Vec<String>,
+    source_names: HashMap<String, String>,
+}
+
+impl DiagnosticsDialog {
+    /// Append entries captured since the last refresh, following the end of the
+    /// log only if it was already scrolled to the bottom
+    fn append_new_entries(&mut self) {
+        let (entries, next_seq) = LOG_BUFFER.since(self.next_seq, self.level);
+        self.next_seq = next_seq;
+        if entries.is_empty() {
+            return;
+        }
+
+        let following = self
+            .log_view
+            .vadjustment()
+            .is_none_or(|adj| adj.value() + adj.page_size() >= adj.upper() - 1.0);
+
+        let buffer = self.log_view.buffer();
+        let mut text = String::new();
+        for entry in &entries {
+            if buffer.char_count() > 0 || !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(&entry.format_line());
+        }
+        buffer.insert(&mut buffer.end_iter(), &text);
+
+        let excess = buffer.line_count() - LOG_BUFFER.capacity() as i32;
+        if excess > 0 {
+            let mut start = buffer.start_iter();
+            if let Some(mut cut) = buffer.iter_at_line(excess) {
+                buffer.delete(&mut start, &mut cut);
+            }
+        }
+
+        if following && let Some(end) = buffer.mark(LOG_END_MARK) {
+            self.log_view.scroll_to_mark(&end, 0.0, false, 0.0, 1.0);
+        }
+    }
+
+    /// Prepend requests recorded since the last refresh, newest first
+    fn append_new_requests(&mut self) {
+        let (requests, next_seq) = REQUEST_LOG.since(self.next_request_seq);
+        self.next_request_seq = next_seq;
+
+        for request in requests.iter().filter(|request| {
+            self.request_source
+                .as_ref()
+                .is_none_or(|source_id| &request.source_id == source_id)
+        }) {
+            self.request_list.prepend(&self.request_row(request));
+        }
+        while let Some(row) = self
+            .request_list
+            .row_at_index(REQUEST_LOG.capacity() as i32)
+        {
+            self.request_list.remove(&row);
+        }
+    }
+
+    /// Whether the request list replaces the explanation of recording
+    fn shows_requests(&self) -> bool {
+        self.recording_requests || self.next_request_seq > 0
+    }
+
+    fn request_row(&self, request: &RecordedRequest) -> adw::ExpanderRow {
+        let source = self
+            .source_names
+            .get(&request.source_id)
+            .map_or(request.source_id.as_str(), String::as_str);
+        let row = adw::ExpanderRow::builder()
+            .title(format!("{} {}", request.method, request.url))
+            .title_lines(2)
+            .subtitle(format!(
+                "{} · {} ms · {}",
+                source,
+                request.duration.as_millis(),
+                request.started_at.format("%H:%M:%S")
+            ))
+            .use_markup(false)
+            .build();
+
+        let status = gtk::Label::builder()
+            .label(
+                request
+                    .status
+                    .map_or_else(|| "Failed".to_string(), |status| status.to_string()),
+            )
+            .valign(gtk::Align::Center)
+            .css_classes([
+                "monospace",
+                if request.failed() { "error" } else { "success" },
+            ])
+            .build();
+        row.add_suffix(&status);
+
+        let details = match (&request.error, request.body.is_empty()) {
+            (Some(error), _) => error.clone(),
+            (None, true) => "Empty response body".to_string(),
+            (None, false) => request.body.clone(),
+        };
+        row.add_row(
+            &gtk::Label::builder()
+                .label(details)
+                .xalign(0.0)
+                .wrap(true)
+                .wrap_mode(gtk::pango::WrapMode::WordChar)
+                .selectable(true)
+                .css_classes(["monospace"])
+                .margin_top(12)
+                .margin_bottom(12)
+                .margin_start(12)
+                .margin_end(12)
+                .build(),
+        );
+
+        row
+    }
+
+    /// Offer every known source in the source dropdown, keeping the choice
+    fn show_source_choices(&mut self) {
+        let mut choices: Vec<(&String, &String)> = self.source_names.iter().collect();
+        choices.sort_by(|a, b| a.1.cmp(b.1));
+        self.source_choices = choices.iter().map(|(id, _)| (*id).clone()).collect();
+
+        let labels: Vec<&str> = std::iter::once("All Sources")
+            .chain(choices.iter().map(|(_, name)| name.as_str()))
+            .collect();
+        let selected = self
+            .request_source
+            .as_ref()
+            .and_then(|source_id| self.source_choices.iter().position(|id| id == source_id))
+            .map_or(0, |index| index as u32 + 1);
+        self.source_dropdown
+            .set_model(Some(&gtk::StringList::new(&labels)));
+        self.source_dropdown.set_selected(selected);
+    }
+
+    fn load_source_names(&self, sender: &AsyncComponentSender<Self>) {
+        let db = self.db.clone();
+        let sender = sender.clone();
+        relm4::spawn(async move {
+            match SourceRepositoryImpl::new(db).find_all().await {
+                Ok(sources) => sender.input(DiagnosticsDialogInput::SourceNamesLoaded(
+                    sources
+                        .into_iter()
+                        .map(|source| (source.id, source.name))
+                        .collect(),
+                )),
+                Err(e) => tracing::error!("Failed to load sources for diagnostics: {}", e),
+            }
+        });
+    }
+
+    fn reload(&mut self) {
+        self.log_view.buffer().set_text("");
+        self.next_seq = 0;
+        self.append_new_entries();
+    }
+
+    fn stop_refresh(&mut self) {
+        if let Some(timer) = self.refresh_timer.take() {
+            timer.remove();
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DiagnosticsDialogInput {
+    /// Start following the log, sent each time the dialog is presented
+    Start,
+    Refresh,
+    SetLevel(Level),
+    ClearView,
+    PageChanged(String),
+    SetRecordingRequests(bool),
+    /// Record and list only the source at this position of the dropdown
+    SetRequestSource(u32),
+    ClearRequests,
+    SourceNamesLoaded(HashMap<String, String>),
+    ChooseExportLocation,
+    Export(PathBuf),
+    Exported(Option<String>),
+    Close,
+}
+
+#[relm4::component(pub async)]
+impl AsyncComponent for DiagnosticsDialog {
+    type Init = DatabaseConnection;
+    type Input = DiagnosticsDialogInput;
+    type Output = ();
+    type CommandOutput = ();
+
+    view! {
+        #[root]
+        adw::Dialog {
+            set_title: "Diagnostics",
+            set_content_width: 900,
+            set_content_height: 600,
+            connect_closed => DiagnosticsDialogInput::Close,
+
+            #[wrap(Some)]
+            set_child = &adw::ToolbarView {
+                add_top_bar = &adw::HeaderBar {
+                    #[wrap(Some)]
+                    set_title_widget = &adw::ViewSwitcher {
+                        set_stack: Some(&view_stack),
+                        set_policy: adw::ViewSwitcherPolicy::Wide,
+                    },
+
+                    pack_start = &gtk::DropDown {
+                        #[watch]
+                        set_visible: model.page == "log",
+                        set_model: Some(&gtk::StringList::new(&LOG_LEVELS.map(|(label, _)| label))),
+                        set_selected: LOG_LEVELS
+                            .iter()
+                            .position(|(_, level)| *level == Level::DEBUG)
+                            .unwrap_or_default() as u32,
+                        set_tooltip_text: Some("Minimum Level"),
+                        update_property: &[gtk::accessible::Property::Label("Minimum Level")],
+                        connect_selected_notify[sender] => move |dropdown| {
+                            if let Some((_, level)) = LOG_LEVELS.get(dropdown.selected() as usize) {
+                                sender.input(DiagnosticsDialogInput::SetLevel(*level));
+                            }
+                        },
+                    },
+
+                    pack_start = &gtk::Button {
+                        set_icon_name: "edit-clear-all-symbolic",
+                        set_tooltip_text: Some("Clear View"),
+                        update_property: &[gtk::accessible::Property::Label("Clear View")],
+                        #[watch]
+                        set_visible: model.page == "log",
+                        connect_clicked => DiagnosticsDialogInput::ClearView,
+                    },
+
+                    pack_start = &gtk::Box {
+                        set_spacing: 6,
+                        #[watch]
+                        set_visible: model.page == "requests",
+
+                        gtk::Switch {
+                            set_valign: gtk::Align::Center,
+                            set_tooltip_text: Some("Record backend requests and responses"),
+                            update_property: &[gtk::accessible::Property::Label("Record Requests")],
+                            #[watch]
+                            set_active: model.recording_requests,
+                            connect_active_notify[sender] => move |switch| {
+                                sender.input(DiagnosticsDialogInput::SetRecordingRequests(switch.is_active()));
+                            },
+                        },
+
+                        gtk::Label {
+                            set_label: "Record",
+                        },
+                    },
+
+                    #[name(source_dropdown)]
+                    pack_start = &gtk::DropDown {
+                        #[watch]
+                        set_visible: model.page == "requests",
+                        set_model: Some(&gtk::StringList::new(&["All Sources"])),
+                        set_tooltip_text: Some("Source to Record"),
+                        update_property: &[gtk::accessible::Property::Label("Source to Record")],
+                        connect_selected_notify[sender] => move |dropdown| {
+                            sender.input(DiagnosticsDialogInput::SetRequestSource(dropdown.selected()));
+                        },
+                    },
+
+                    pack_start = &gtk::Button {
+                        set_icon_name: "edit-clear-all-symbolic",
+                        set_tooltip_text: Some("Clear Requests"),
+                        update_property: &[gtk::accessible::Property::Label("Clear Requests")],
+                        #[watch]
+                        set_visible: model.page == "requests",
+                        connect_clicked => DiagnosticsDialogInput::ClearRequests,
+                    },
+
+                    pack_end = &gtk::Button {
+                        set_label: "Export Debug Bundle",
+                        set_tooltip_text: Some("Save recent logs, configuration and system details to a zip for a bug report"),
+                        add_css_class: "suggested-action",
+                        #[watch]
+                        set_sensitive: !model.exporting,
+                        connect_clicked => DiagnosticsDialogInput::ChooseExportLocation,
+                    },
+                },
+
+                #[wrap(Some)]
+                #[name(toast_overlay)]
+                set_content = &adw::ToastOverlay {
+                    #[wrap(Some)]
+                    #[name = "view_stack"]
+                    set_child = &adw::ViewStack {
+                        connect_visible_child_name_notify[sender] => move |stack| {
+                            if let Some(name) = stack.visible_child_name() {
+                                sender.input(DiagnosticsDialogInput::PageChanged(name.to_string()));
+                            }
+                        },
+
+                        add_titled_with_icon[Some("log"), "Log", "utilities-terminal-symbolic"] = &gtk::ScrolledWindow {
+                            set_vexpand: true,
+
+                            #[name(log_view)]
+                            gtk::TextView {
+                                set_editable: false,
+                                set_cursor_visible: false,
+                                set_monospace: true,
+                                set_wrap_mode: gtk::WrapMode::WordChar,
+                                set_top_margin: 12,
+                                set_bottom_margin: 12,
+                                set_left_margin: 12,
+                                set_right_margin: 12,
+                                update_property: &[gtk::accessible::Property::Label("Application Log")],
+                            },
+                        },
+
+                        add_titled_with_icon[Some("requests"), "Requests", "network-transmit-receive-symbolic"] = &gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+
+                            adw::StatusPage {
+                                set_vexpand: true,
+                                #[watch]
+                                set_visible: !model.shows_requests(),
+                                set_icon_name: Some("network-transmit-receive-symbolic"),
+                                set_title: "Requests Aren't Recorded",
+                                set_description: Some("Turn on recording to capture the requests Reel sends to your servers, including the start of each response"),
+                            },
+
+                            gtk::ScrolledWindow {
+                                set_vexpand: true,
+                                #[watch]
+                                set_visible: model.shows_requests(),
+
+                                adw::Clamp {
+                                    set_maximum_size: 1000,
+                                    set_margin_top: 12,
+                                    set_margin_bottom: 12,
+                                    set_margin_start: 12,
+                                    set_margin_end: 12,
+
+                                    #[name(request_list)]
+                                    gtk::ListBox {
+                                        add_css_class: "boxed-list",
+                                        set_selection_mode: gtk::SelectionMode::None,
+                                        set_valign: gtk::Align::Start,
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    async fn init(
+        db: Self::Init,
+        root: Self::Root,
+        sender: AsyncComponentSender<Self>,
+    ) -> AsyncComponentParts<Self> {
+        let mut model = Self {
+            db,
+            level: Level::DEBUG,
+            next_seq: 0,
+            refresh_timer: None,
+            exporting: false,
+            log_view: gtk::TextView::new(),
+            toast_overlay: adw::ToastOverlay::new(),
+            page: "log".to_string(),
+            recording_requests: REQUEST_LOG.is_enabled(),
+            next_request_seq: 0,
+            request_list: gtk::ListBox::new(),
+            request_source: REQUEST_LOG.source_id(),
+            source_dropdown: gtk::DropDown::default(),
+            source_choices: Vec::new(),
+            source_names: HashMap::new(),
+        };
+
+        let widgets = view_output!();
+
+        model.log_view.clone_from(&widgets.log_view);
+        model.toast_overlay.clone_from(&widgets.toast_overlay);
+        model.request_list.clone_from(&widgets.request_list);
+        model.source_dropdown.clone_from(&widgets.source_dropdown);
+        let buffer = model.log_view.buffer();
+        buffer.create_mark(Some(LOG_END_MARK), &buffer.end_iter(), false);
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(
+        &mut self,
+        msg: Self::Input,
+        sender: AsyncComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match msg {
+            DiagnosticsDialogInput::Start => {
+                if self.refresh_timer.is_none() {
+                    let sender = sender.clone();
+                    self.refresh_timer =
+                        Some(glib::timeout_add_local(LOG_REFRESH_INTERVAL, move || {
+                            sender.input(DiagnosticsDialogInput::Refresh);
+                            glib::ControlFlow::Continue
+                        }));
+                }
+                self.reload();
+                self.load_source_names(&sender);
+            }
+            DiagnosticsDialogInput::Refresh => {
+                self.append_new_entries();
+                self.append_new_requests();
+            }
+            DiagnosticsDialogInput::SetLevel(level) => {
+                if self.level != level {
+                    self.level = level;
+                    self.reload();
+                }
+            }
+            DiagnosticsDialogInput::ClearView => {
+                self.log_view.buffer().set_text("");
+            }
+            DiagnosticsDialogInput::PageChanged(page) => {
+                self.page = page;
+            }
+            DiagnosticsDialogInput::SetRecordingRequests(enabled) => {
+                self.recording_requests = enabled;
+                REQUEST_LOG.set_enabled(enabled);
+            }
+            DiagnosticsDialogInput::SetRequestSource(index) => {
+                let source_id = index
+                    .checked_sub(1)
+                    .and_then(|index| self.source_choices.get(index as usize))
+                    .cloned();
+                if source_id == self.request_source {
+                    return;
+                }
+                REQUEST_LOG.set_source_id(source_id.clone());
+                self.request_source = source_id;
+
+                // List what was already recorded of the chosen source
+                self.request_list.remove_all();
+                self.next_request_seq = 0;
+                self.append_new_requests();
+            }
+            DiagnosticsDialogInput::ClearRequests => {
+                REQUEST_LOG.clear();
+                self.request_list.remove_all();
+            }
+            DiagnosticsDialogInput::SourceNamesLoaded(names) => {
+                self.source_names = names;
+                self.show_source_choices();
+            }
+            DiagnosticsDialogInput::ChooseExportLocation => {
+                let dialog = gtk::FileDialog::builder()
+                    .title("Export Debug Bundle")
+                    .initial_name(diagnostics::bundle_file_name())
+                    .modal(true)
+                    .build();
+                let parent = root.root().and_downcast::<gtk::Window>();
+                let sender = sender.clone();
+                dialog.save(
+                    parent.as_ref(),
+                    None::<&gtk::gio::Cancellable>,
+                    move |result| {
+                        if let Ok(file) = result
+                            && let Some(path) = file.path()
+                        {
+                            sender.input(DiagnosticsDialogInput::Export(path));
+                        }
+                    },
+                );
+            }
+            DiagnosticsDialogInput::Export(path) => {
+                if self.exporting {
+                    return;
+                }
+                self.exporting = true;
+
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let error = diagnostics::export_debug_bundle(&db, path)
+                        .await
+                        .err()
+                        .map(|e| format!("{:#}", e));
+                    sender.input(DiagnosticsDialogInput::Exported(error));
+                });
+            }
+            DiagnosticsDialogInput::Exported(error) => {
+                self.exporting = false;
+                let message = match error {
+                    Some(e) => {
+                        tracing::error!("Failed to export debug bundle: {}", e);
+                        format!("Failed to export debug bundle: {}", e)
+                    }
+                    None => "Debug bundle exported".to_string(),
+                };
+                self.toast_overlay.add_toast(adw::Toast::new(&message));
+            }
+            DiagnosticsDialogInput::Close => {
+                self.stop_refresh();
+            }
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use adw::prelude::*;
+use libadwaita as adw;
+use relm4::gtk;
+use relm4::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::entities::MediaItemModel;
+use crate::db::repository::{LibraryRepositoryImpl, Repository, SourceRepositoryImpl};
+use crate::models::{MediaItemId, MediaVersion};
+use crate::services::commands::Command;
+use crate::services::commands::media_commands::DeleteFromServerCommand;
+use crate::services::core::{DeletionService, DuplicateGroup, DuplicatesService};
+use crate::ui::shared::delete_confirmation;
+
+#[derive(Debug)]
+pub struct DuplicatesDialog {
+    db: DatabaseConnection,
+    scanning: bool,
+    groups: Vec<DuplicateGroup>,
+    group_list: gtk::Box,
+    /// Names of sources and libraries by id
+    names: HashMap<String, String>,
+    /// Items the user may delete from their server
+    deletable: HashSet<String>,
+    toast_overlay: adw::ToastOverlay,
+}
+
+impl DuplicatesDialog {
+    fn rebuild_groups(&self, sender: &AsyncComponentSender<Self>) {
+        while let Some(child) = self.group_list.first_child() {
+            self.group_list.remove(&child);
+        }
+
+        for group in &self.groups {
+            let preferences_group = adw::PreferencesGroup::builder()
+                .title(gtk::glib::markup_escape_text(&group.title))
+                .description(format!("{} copies", group.copy_count()))
+                .build();
+
+            for item in &group.items {
+                let versions = item.get_versions();
+                if versions.is_empty() {
+                    preferences_group.add(&self.copy_row(group, item, None, sender));
+                }
+                for version in &versions {
+                    preferences_group.add(&self.copy_row(group, item, Some(version), sender));
+                }
+            }
+
+            self.group_list.append(&preferences_group);
+        }
+    }
+
+    /// A row for one file of an item, with where it is stored and its quality
+    fn copy_row(
+        &self,
+        group: &DuplicateGroup,
+        item: &MediaItemModel,
+        version: Option<&MediaVersion>,
+        sender: &AsyncComponentSender<Self>,
+    ) -> adw::ActionRow {
+        let name = |id: &String| self.names.get(id).cloned().unwrap_or_else(|| id.clone());
+        let height = version
+            .and_then(|version| version.video_height)
+            .or_else(|| item.get_video_height());
+
+        let mut quality = Vec::new();
+        if let Some(height) = height {
+            quality.push(format!("{}p", height));
+        }
+        if let Some(container) = version.and_then(|version| version.container.as_ref()) {
+            quality.push(container.to_uppercase());
+        }
+        if let Some(size) = version.and_then(|version| version.size) {
+            quality.push(gtk::glib::format_size(size).to_string());
+        }
+        if quality.is_empty() {
+            quality.push("Unknown quality".to_string());
+        }
+
+        let title = quality.join(" · ");
+        let row = adw::ActionRow::builder()
+            .title(&title)
+            .subtitle(format!(
+                "{} · {}",
+                name(&item.source_id),
+                name(&item.library_id)
+            ))
+            .use_markup(false)
+            .build();
+
+        let play_button = gtk::Button::builder()
+            .icon_name("media-playback-start-symbolic")
+            .tooltip_text("Play")
+            .valign(gtk::Align::Center)
+            .css_classes(["flat"])
+            .build();
+        play_button.update_property(&[gtk::accessible::Property::Label("Play")]);
+        let item_id = MediaItemId::new(item.id.clone());
+        let play_sender = sender.clone();
+        let play_id = item_id.clone();
+        play_button.connect_clicked(move |_| {
+            play_sender.input(DuplicatesDialogInput::Play(play_id.clone()));
+        });
+        row.add_suffix(&play_button);
+
+        if self.deletable.contains(&item.id) {
+            let delete_button = gtk::Button::builder()
+                .icon_name("user-trash-symbolic")
+                .tooltip_text("Delete from Server")
+                .valign(gtk::Align::Center)
+                .css_classes(["flat"])
+                .build();
+            delete_button
+                .update_property(&[gtk::accessible::Property::Label("Delete from Server")]);
+            let what = format!("The {} copy of “{}”", title, group.title);
+            let version_id = version.map(|version| version.id.clone());
+            let sender = sender.clone();
+            delete_button.connect_clicked(move |button| {
+                let sender = sender.clone();
+                let item_id = item_id.clone();
+                let version_id = version_id.clone();
+                delete_confirmation::present(button, &what, move || {
+                    sender.input(DuplicatesDialogInput::Delete {
+                        item_id: item_id.clone(),
+                        version_id: version_id.clone(),
+                    });
+                });
+            });
+            row.add_suffix(&delete_button);
+        }
+
+        row
+    }
+}
+
+#[derive(Debug)]
+pub enum DuplicatesDialogInput {
+    /// Scan for duplicates, sent each time the dialog is presented
+    Scan,
+    Scanned {
+        groups: Vec<DuplicateGroup>,
+        names: HashMap<String, String>,
+        deletable: HashSet<String>,
+    },
+    Play(MediaItemId),
+    /// Delete an item, or one of its versions, from its server
+    Delete {
+        item_id: MediaItemId,
+        version_id: Option<String>,
+    },
+    DeleteFailed(String),
+}
+
+#[derive(Debug)]
+pub enum DuplicatesDialogOutput {
+    Play(MediaItemId),
+}
+
+#[relm4::component(pub async)]
+impl AsyncComponent for DuplicatesDialog {
+    type Init = DatabaseConnection;
+    type Input = DuplicatesDialogInput;
+    type Output = DuplicatesDialogOutput;
+    type CommandOutput = ();
+
+    view! {
+        #[root]
+        adw::Dialog {
+            set_title: "Duplicates",
+            set_content_width: 640,
+            set_content_height: 600,
+
+            #[wrap(Some)]
+            set_child = &adw::ToolbarView {
+                add_top_bar = &adw::HeaderBar {
+                    pack_start = &gtk::Button {
+                        set_icon_name: "view-refresh-symbolic",
+                        set_tooltip_text: Some("Scan Again"),
+                        update_property: &[gtk::accessible::Property::Label("Scan Again")],
+                        #[watch]
+                        set_sensitive: !model.scanning,
+                        connect_clicked => DuplicatesDialogInput::Scan,
+                    },
+                },
+
+                #[wrap(Some)]
+                #[name(toast_overlay)]
+                set_content = &adw::ToastOverlay {
+                    #[wrap(Some)]
+                    set_child = &gtk::Stack {
+                        #[watch]
+                        set_visible_child_name: if model.scanning {
+                            "scanning"
+                        } else if model.groups.is_empty() {
+                            "empty"
+                        } else {
+                            "groups"
+                        },
+
+                        add_named[Some("scanning")] = &adw::Spinner {
+                            set_halign: gtk::Align::Center,
+                            set_valign: gtk::Align::Center,
+                            set_width_request: 32,
+                            set_height_request: 32,
+                        },
+
+                        add_named[Some("empty")] = &adw::StatusPage {
+                            set_icon_name: Some("edit-copy-symbolic"),
+                            set_title: "No Duplicates",
+                            set_description: Some("No movie is stored more than once in your libraries"),
+                        },
+
+                        add_named[Some("groups")] = &gtk::ScrolledWindow {
+                            set_vexpand: true,
+
+                            adw::Clamp {
+                                set_maximum_size: 800,
+                                set_margin_top: 12,
+                                set_margin_bottom: 12,
+                                set_margin_start: 12,
+                                set_margin_end: 12,
+
+                                #[name(group_list)]
+                                gtk::Box {
+                                    set_orientation: gtk::Orientation::Vertical,
+                                    set_spacing: 24,
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    async fn init(
+        db: Self::Init,
+        root: Self::Root,
+        sender: AsyncComponentSender<Self>,
+    ) -> AsyncComponentParts<Self> {
+        let mut model = Self {
+            db,
+            scanning: false,
+            groups: Vec::new(),
+            group_list: gtk::Box::default(),
+            names: HashMap::new(),
+            deletable: HashSet::new(),
+            toast_overlay: adw::ToastOverlay::default(),
+        };
+
+        let widgets = view_output!();
+        model.group_list.clone_from(&widgets.group_list);
+        model.toast_overlay.clone_from(&widgets.toast_overlay);
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(
+        &mut self,
+        msg: Self::Input,
+        sender: AsyncComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match msg {
+            DuplicatesDialogInput::Scan => {
+                if self.scanning {
+                    return;
+                }
+                self.scanning = true;
+
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let groups = DuplicatesService::find_duplicates(&db)
+                        .await
+                        .unwrap_or_else(|e| {
+                            tracing::error!("Failed to find duplicates: {}", e);
+                            Vec::new()
+                        });
+
+                    let mut names = HashMap::new();
+                    match SourceRepositoryImpl::new(db.clone()).find_all().await {
+                        Ok(sources) => {
+                            names.extend(sources.into_iter().map(|source| (source.id, source.name)))
+                        }
+                        Err(e) => tracing::error!("Failed to load sources: {}", e),
+                    }
+                    match LibraryRepositoryImpl::new(db.clone()).find_all().await {
+                        Ok(libraries) => names.extend(
+                            libraries
+                                .into_iter()
+                                .map(|library| (library.id, library.title)),
+                        ),
+                        Err(e) => tracing::error!("Failed to load libraries: {}", e),
+                    }
+
+                    // Each check may ask a server, so they run side by side
+                    let checks = groups.iter().flat_map(|group| &group.items).map(|item| {
+                        let db = &db;
+                        async move {
+                            let item_id = MediaItemId::new(item.id.clone());
+                            match DeletionService::can_delete(db, &item_id).await {
+                                Ok(true) => Some(item.id.clone()),
+                                Ok(false) => None,
+                                Err(e) => {
+                                    tracing::debug!(
+                                        "Can't tell if {} may be deleted: {}",
+                                        item_id,
+                                        e
+                                    );
+                                    None
+                                }
+                            }
+                        }
+                    });
+                    let deletable: HashSet<String> = futures::future::join_all(checks)
+                        .await
+                        .into_iter()
+                        .flatten()
+                        .collect();
+
+                    sender.input(DuplicatesDialogInput::Scanned {
+                        groups,
+                        names,
+                        deletable,
+                    });
+                });
+            }
+            DuplicatesDialogInput::Scanned {
+                groups,
+                names,
+                deletable,
+            } => {
+                self.scanning = false;
+                self.groups = groups;
+                self.names = names;
+                self.deletable = deletable;
+                self.rebuild_groups(&sender);
+            }
+            DuplicatesDialogInput::Play(item_id) => {
+                root.close();
+                sender
+                    .output(DuplicatesDialogOutput::Play(item_id))
+                    .unwrap();
+            }
+            DuplicatesDialogInput::Delete {
+                item_id,
+                version_id,
+            } => {
+                let cmd = DeleteFromServerCommand {
+                    db: self.db.clone(),
+                    media_id: item_id,
+                    version_id,
+                };
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    if let Err(e) = Command::execute(&cmd).await {
+                        tracing::error!("Failed to delete from the server: {}", e);
+                        sender.input(DuplicatesDialogInput::DeleteFailed(format!(
+                            "Couldn't delete from the server: {}",
+                            e
+                        )));
+                    }
+                    sender.input(DuplicatesDialogInput::Scan);
+                });
+            }
+            DuplicatesDialogInput::DeleteFailed(message) => {
+                self.toast_overlay.add_toast(adw::Toast::new(&message));
+            }
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use adw::prelude::*;
+use libadwaita as adw;
+use relm4::gtk;
+use relm4::prelude::*;
+use std::path::PathBuf;
+
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{LibraryRepositoryImpl, Repository};
+use crate::services::core::{ExportFormat, LibraryExportService};
+
+/// Formats in the order of the format row
+const FORMATS: [ExportFormat; 2] = [ExportFormat::Html, ExportFormat::Csv];
+
+#[derive(Debug)]
+pub struct ExportDialog {
+    db: DatabaseConnection,
+    /// Ids and titles of the libraries in the library row
+    libraries: Vec<(String, String)>,
+    library_list: gtk::StringList,
+    selected_library: u32,
+    selected_format: u32,
+    /// Titles done and total while an export runs
+    progress: Option<(usize, usize)>,
+    toast_overlay: adw::ToastOverlay,
+}
+
+impl ExportDialog {
+    fn format(&self) -> ExportFormat {
+        FORMATS[self.selected_format as usize]
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportDialogInput {
+    /// Load the libraries, sent each time the dialog is presented
+    Load,
+    LibrariesLoaded(Vec<(String, String)>),
+    SelectLibrary(u32),
+    SelectFormat(u32),
+    ChooseLocation,
+    Export(PathBuf),
+    Progress {
+        done: usize,
+        total: usize,
+    },
+    Exported(Result<PathBuf, String>),
+}
+
+#[relm4::component(pub async)]
+impl AsyncComponent for ExportDialog {
+    type Init = DatabaseConnection;
+    type Input = ExportDialogInput;
+    type Output = ();
+    type CommandOutput = ();
+
+    view! {
+        #[root]
+        adw::Dialog {
+            set_title: "Export Library",
+            set_content_width: 480,
+
+            #[wrap(Some)]
+            set_child = &adw::ToolbarView {
+                add_top_bar = &adw::HeaderBar {},
+
+                #[wrap(Some)]
+                #[name(toast_overlay)]
+                set_content = &adw::ToastOverlay {
+                    #[wrap(Some)]
+                    set_child = &adw::PreferencesPage {
+                        add = &adw::PreferencesGroup {
+                            set_description: Some("Save a catalog of titles, years, genres and watched state to share or archive"),
+
+                            add = &adw::ComboRow {
+                                set_title: "Library",
+                                set_model: Some(&model.library_list),
+                                #[watch]
+                                set_sensitive: model.progress.is_none(),
+                                connect_selected_notify[sender] => move |row| {
+                                    sender.input(ExportDialogInput::SelectLibrary(row.selected()));
+                                },
+                            },
+
+                            add = &adw::ComboRow {
+                                set_title: "Format",
+                                set_subtitle: "HTML includes artwork and opens in any browser",
+                                set_model: Some(&gtk::StringList::new(&FORMATS.map(|format| format.label()))),
+                                #[watch]
+                                set_sensitive: model.progress.is_none(),
+                                connect_selected_notify[sender] => move |row| {
+                                    sender.input(ExportDialogInput::SelectFormat(row.selected()));
+                                },
+                            },
+                        },
+
+                        add = &adw::PreferencesGroup {
+                            gtk::ProgressBar {
+                                set_show_text: true,
+                                #[watch]
+                                set_visible: model.progress.is_some(),
+                                #[watch]
+                                set_fraction: match model.progress {
+                                    Some((done, total)) if total > 0 => done as f64 / total as f64,
+                                    _ => 0.0,
+                                },
+                                #[watch]
+                                set_text: model.progress
+                                    .map(|(done, total)| format!("{} of {} titles", done, total))
+                                    .as_deref(),
+                            },
+
+                            gtk::Button {
+                                set_label: "Export…",
+                                set_halign: gtk::Align::Center,
+                                set_margin_top: 12,
+                                add_css_class: "pill",
+                                add_css_class: "suggested-action",
+                                #[watch]
+                                set_sensitive: model.progress.is_none() && !model.libraries.is_empty(),
+                                connect_clicked => ExportDialogInput::ChooseLocation,
+                            },
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    async fn init(
+        db: Self::Init,
+        root: Self::Root,
+        sender: AsyncComponentSender<Self>,
+    ) -> AsyncComponentParts<Self> {
+        let mut model = Self {
+            db,
+            libraries: Vec::new(),
+            library_list: gtk::StringList::new(&[]),
+            selected_library: 0,
+            selected_format: 0,
+            progress: None,
+            toast_overlay: adw::ToastOverlay::new(),
+        };
+
+        let widgets = view_output!();
+        model.toast_overlay.clone_from(&widgets.toast_overlay);
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(
+        &mut self,
+        msg: Self::Input,
+        sender: AsyncComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match msg {
+            ExportDialogInput::Load => {
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let libraries = match LibraryRepositoryImpl::new(db).find_all().await {
+                        Ok(libraries) => libraries
+                            .into_iter()
+                            .map(|library| (library.id, library.title))
+                            .collect(),
+                        Err(e) => {
+                            tracing::error!("Failed to load libraries: {}", e);
+                            Vec::new()
+                        }
+                    };
+                    sender.input(ExportDialogInput::LibrariesLoaded(libraries));
+                });
+            }
+            ExportDialogInput::LibrariesLoaded(libraries) => {
+                let titles: Vec<&str> = libraries.iter().map(|(_, title)| title.as_str()).collect();
+                self.library_list
+                    .splice(0, self.library_list.n_items(), &titles);
+                self.libraries = libraries;
+            }
+            ExportDialogInput::SelectLibrary(index) => {
+                self.selected_library = index;
+            }
+            ExportDialogInput::SelectFormat(index) => {
+                self.selected_format = index;
+            }
+            ExportDialogInput::ChooseLocation => {
+                let Some((_, title)) = self.libraries.get(self.selected_library as usize) else {
+                    return;
+                };
+                let dialog = gtk::FileDialog::builder()
+                    .title("Export Library")
+                    .initial_name(LibraryExportService::file_name(title, self.format()))
+                    .modal(true)
+                    .build();
+                let parent = root.root().and_downcast::<gtk::Window>();
+                let sender = sender.clone();
+                dialog.save(
+                    parent.as_ref(),
+                    None::<&gtk::gio::Cancellable>,
+                    move |result| {
+                        if let Ok(file) = result
+                            && let Some(path) = file.path()
+                        {
+                            sender.input(ExportDialogInput::Export(path));
+                        }
+                    },
+                );
+            }
+            ExportDialogInput::Export(path) => {
+                let Some((library_id, _)) = self.libraries.get(self.selected_library as usize)
+                else {
+                    return;
+                };
+                if self.progress.is_some() {
+                    return;
+                }
+                self.progress = Some((0, 0));
+
+                let db = self.db.clone();
+                let library_id = library_id.clone();
+                let format = self.format();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let progress_sender = sender.clone();
+                    let result = LibraryExportService::export(
+                        &db,
+                        &library_id,
+                        format,
+                        path,
+                        |done, total| {
+                            progress_sender.input(ExportDialogInput::Progress { done, total });
+                        },
+                    )
+                    .await
+                    .map_err(|e| format!("{:#}", e));
+                    sender.input(ExportDialogInput::Exported(result));
+                });
+            }
+            ExportDialogInput::Progress { done, total } => {
+                if self.progress.is_some() {
+                    self.progress = Some((done, total));
+                }
+            }
+            ExportDialogInput::Exported(result) => {
+                self.progress = None;
+                let toast = match result {
+                    Ok(path) => {
+                        let toast = adw::Toast::builder()
+                            .title("Library exported")
+                            .button_label("Open")
+                            .build();
+                        toast.connect_button_clicked(move |_| {
+                            gtk::FileLauncher::new(Some(&gtk::gio::File::for_path(&path))).launch(
+                                None::<&gtk::Window>,
+                                None::<&gtk::gio::Cancellable>,
+                                |result| {
+                                    if let Err(e) = result {
+                                        tracing::error!("Failed to open the catalog: {}", e);
+                                    }
+                                },
+                            );
+                        });
+                        toast
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to export library: {}", e);
+                        adw::Toast::new(&format!("Failed to export library: {}", e))
+                    }
+                };
+                self.toast_overlay.add_toast(toast);
+            }
+        }
+    }
+}
@@ -1,5 +1,11 @@
 pub mod auth_dialog;
+pub mod diagnostics_dialog;
+pub mod duplicates_dialog;
+pub mod export_dialog;
 pub mod preferences_dialog;
 
 pub use auth_dialog::{AuthDialog, AuthDialogInput, AuthDialogOutput, ReauthMode};
+pub use diagnostics_dialog::{DiagnosticsDialog, DiagnosticsDialogInput};
+pub use duplicates_dialog::{DuplicatesDialog, DuplicatesDialogInput, DuplicatesDialogOutput};
+pub use export_dialog::{ExportDialog, ExportDialogInput};
 pub use preferences_dialog::{PreferencesDialog, PreferencesDialogInput, PreferencesDialogOutput};
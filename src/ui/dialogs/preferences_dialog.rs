@@ -3,8 +3,27 @@ use libadwaita as adw;
 use relm4::gtk;
 use relm4::prelude::*;
 
+use crate::backends::traits::HOME_SECTION_ITEM_LIMIT;
+use crate::config::{
+    BackupConfig, DownloadsConfig, GestureConfig, ImageQuality, KidsConfig, LoggingConfig,
+    LongAudioConfig, NetworkConfig, SpeedConfig,
+};
+use crate::db::backup::Backup;
 use crate::db::connection::DatabaseConnection;
+use crate::db::entities::{HiddenCollectionModel, MediaItemModel};
+use crate::logging::{LOG_LEVELS, log_directory};
+use crate::models::{MediaItemId, SourceId};
+use crate::services::commands::Command;
+use crate::services::commands::media_commands::{SetCollectionHiddenCommand, SetHiddenCommand};
 use crate::services::config_service::CONFIG_SERVICE;
+use crate::services::core::hw_decode::{self, HardwareDecodeSupport, VideoCodec};
+use crate::services::core::image_quality::IMAGE_QUALITIES;
+use crate::services::core::storage::{StorageLocations, available_space_async};
+use crate::services::core::{
+    BackupService, HiddenItemsService, StorageKind, StorageService, storage_locations,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 #[tracker::track]
 #[derive(Debug)]
@@ -15,14 +34,317 @@ pub struct PreferencesDialog {
     // Player preferences
     default_player: String,
     hardware_acceleration: bool,
+    // Hardware decoders found by the last probe, one row per codec
+    #[do_not_track]
+    hw_decode_expander: adw::ExpanderRow,
+    #[do_not_track]
+    hw_decode_rows: Vec<adw::ActionRow>,
+    power_saving_playback: bool,
+    resume_after_unlock: bool,
+    resume_prompt: bool,
+    resolve_urls_with_ytdlp: bool,
+    auto_forced_subtitles: bool,
+    gestures: GestureConfig,
+    speed: SpeedConfig,
+    long_audio: LongAudioConfig,
     // Display preferences
     items_per_page: i32,
+    hover_previews: bool,
+    image_quality: ImageQuality,
+    theme_music: bool,
+    home_row_limit: u32,
+    screensaver_minutes: u32,
+    // Items hidden from the home page, listed so they can be shown again
+    #[do_not_track]
+    hidden_expander: adw::ExpanderRow,
+    #[do_not_track]
+    hidden_rows: Vec<adw::ActionRow>,
+    // Kids profile, whose settings are locked while it is on
+    kids: KidsConfig,
     // Cache preferences
     cache_size_mb: i32,
     auto_clean_cache: bool,
+    // Network preferences
+    network: NetworkConfig,
+    // Offline download preferences
+    downloads: DownloadsConfig,
+    // Storage locations
+    storage: StorageLocations,
+    /// Free space on the disk of each location, looked up in the background
+    storage_free: HashMap<StorageKind, u64>,
+    relocating: Option<StorageKind>,
+    // Backup schedule and the backups that can be restored
+    backups: BackupConfig,
+    backing_up: bool,
+    #[do_not_track]
+    backup_expander: adw::ExpanderRow,
+    #[do_not_track]
+    backup_rows: Vec<adw::ActionRow>,
+    // Log level and log files
+    logging: LoggingConfig,
 }
 
 impl PreferencesDialog {
+    fn save_network_settings(&self) {
+        let network = self.network.clone();
+        relm4::spawn_local(async move {
+            if let Err(e) = CONFIG_SERVICE.set_network_settings(network).await {
+                tracing::error!("Failed to save network preferences: {}", e);
+            }
+        });
+    }
+
+    fn save_gesture_settings(&self) {
+        let gestures = self.gestures;
+        relm4::spawn_local(async move {
+            if let Err(e) = CONFIG_SERVICE.set_gesture_settings(gestures).await {
+                tracing::error!("Failed to save gesture preferences: {}", e);
+            }
+        });
+    }
+
+    fn save_speed_settings(&self) {
+        let speed = self.speed;
+        relm4::spawn_local(async move {
+            if let Err(e) = CONFIG_SERVICE.set_speed_settings(speed).await {
+                tracing::error!("Failed to save playback speed preferences: {}", e);
+            }
+        });
+    }
+
+    fn save_long_audio_settings(&self) {
+        let long_audio = self.long_audio;
+        relm4::spawn_local(async move {
+            if let Err(e) = CONFIG_SERVICE.set_long_audio_settings(long_audio).await {
+                tracing::error!("Failed to save audiobook and podcast preferences: {}", e);
+            }
+        });
+    }
+
+    fn save_downloads_settings(&self) {
+        let downloads = self.downloads.clone();
+        relm4::spawn_local(async move {
+            if let Err(e) = CONFIG_SERVICE.set_downloads_settings(downloads).await {
+                tracing::error!("Failed to save download preferences: {}", e);
+            }
+        });
+    }
+
+    fn save_backup_settings(&self) {
+        let backups = self.backups.clone();
+        relm4::spawn_local(async move {
+            if let Err(e) = CONFIG_SERVICE.set_backup_settings(backups).await {
+                tracing::error!("Failed to save backup preferences: {}", e);
+            }
+        });
+    }
+
+    fn save_kids_settings(&self) {
+        let kids = self.kids.clone();
+        relm4::spawn_local(async move {
+            if let Err(e) = CONFIG_SERVICE.set_kids_settings(kids).await {
+                tracing::error!("Failed to save kids profile preferences: {}", e);
+            }
+        });
+    }
+
+    fn save_logging_settings(&self) {
+        let logging = self.logging.clone();
+        relm4::spawn_local(async move {
+            if let Err(e) = CONFIG_SERVICE.set_logging_settings(logging).await {
+                tracing::error!("Failed to save logging preferences: {}", e);
+            }
+        });
+    }
+
+    fn image_quality_index(&self) -> u32 {
+        IMAGE_QUALITIES
+            .iter()
+            .position(|(_, quality)| *quality == self.image_quality)
+            .unwrap_or_default() as u32
+    }
+
+    fn log_level_index(&self) -> u32 {
+        LOG_LEVELS
+            .iter()
+            .position(|(_, level)| level.as_str().eq_ignore_ascii_case(&self.logging.level))
+            .unwrap_or_default() as u32
+    }
+
+    fn show_hidden_items(
+        &mut self,
+        items: Vec<MediaItemModel>,
+        collections: Vec<HiddenCollectionModel>,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        for row in self.hidden_rows.drain(..) {
+            self.hidden_expander.remove(&row);
+        }
+
+        let count = items.len() + collections.len();
+        self.hidden_expander.set_subtitle(&match count {
+            0 => "Nothing is hidden".to_string(),
+            1 => "1 item".to_string(),
+            count => format!("{} items", count),
+        });
+        self.hidden_expander.set_enable_expansion(count > 0);
+
+        for collection in collections {
+            let row = adw::ActionRow::builder()
+                .title(&collection.name)
+                .subtitle("Collection")
+                .use_markup(false)
+                .build();
+
+            let button = gtk::Button::builder()
+                .label("Show")
+                .valign(gtk::Align::Center)
+                .build();
+            let sender = sender.clone();
+            let source_id = SourceId::new(collection.source_id);
+            let name = collection.name;
+            button.connect_clicked(move |_| {
+                sender.input(PreferencesDialogInput::UnhideCollection {
+                    source_id: source_id.clone(),
+                    collection: name.clone(),
+                });
+            });
+            row.add_suffix(&button);
+
+            self.hidden_expander.add_row(&row);
+            self.hidden_rows.push(row);
+        }
+
+        for item in items {
+            let subtitle = match (
+                item.media_type.as_str(),
+                item.season_number,
+                item.episode_number,
+            ) {
+                ("episode", Some(season), Some(episode)) => {
+                    format!("Episode S{}E{}", season, episode)
+                }
+                ("show", _, _) => "Show and its episodes".to_string(),
+                ("movie", _, _) => "Movie".to_string(),
+                (other, _, _) => other.to_string(),
+            };
+            let row = adw::ActionRow::builder()
+                .title(&item.title)
+                .subtitle(&subtitle)
+                .use_markup(false)
+                .build();
+
+            let button = gtk::Button::builder()
+                .label("Show")
+                .valign(gtk::Align::Center)
+                .build();
+            let sender = sender.clone();
+            let media_id = MediaItemId::new(item.id.clone());
+            button.connect_clicked(move |_| {
+                sender.input(PreferencesDialogInput::UnhideItem(media_id.clone()));
+            });
+            row.add_suffix(&button);
+
+            self.hidden_expander.add_row(&row);
+            self.hidden_rows.push(row);
+        }
+    }
+
+    fn show_backups(&mut self, backups: Vec<Backup>, sender: &AsyncComponentSender<Self>) {
+        for row in self.backup_rows.drain(..) {
+            self.backup_expander.remove(&row);
+        }
+
+        self.backup_expander.set_subtitle(&match backups.first() {
+            None => "No backups yet".to_string(),
+            Some(latest) => format!("Last taken {}", backup_time(latest)),
+        });
+        self.backup_expander
+            .set_enable_expansion(!backups.is_empty());
+
+        for backup in backups {
+            let row = adw::ActionRow::builder()
+                .title(backup_time(&backup))
+                .subtitle(format!(
+                    "{} · {}",
+                    backup.reason.label(),
+                    gtk::glib::format_size(backup.size())
+                ))
+                .build();
+
+            let button = gtk::Button::builder()
+                .label("Restore…")
+                .valign(gtk::Align::Center)
+                .build();
+            let sender = sender.clone();
+            button.connect_clicked(move |_| {
+                sender.input(PreferencesDialogInput::ConfirmRestore(backup.clone()));
+            });
+            row.add_suffix(&button);
+
+            self.backup_expander.add_row(&row);
+            self.backup_rows.push(row);
+        }
+    }
+
+    fn show_hardware_decoders(&mut self, support: Option<&HardwareDecodeSupport>) {
+        for row in self.hw_decode_rows.drain(..) {
+            self.hw_decode_expander.remove(&row);
+        }
+
+        let Some(support) = support else {
+            self.hw_decode_expander
+                .set_subtitle("Checking available decoders…");
+            self.hw_decode_expander.set_enable_expansion(false);
+            return;
+        };
+
+        let accelerated: Vec<&str> = VideoCodec::ALL
+            .iter()
+            .filter(|codec| support.supports(**codec))
+            .map(|codec| codec.label())
+            .collect();
+        self.hw_decode_expander
+            .set_subtitle(&if accelerated.is_empty() {
+                format!(
+                    "No hardware decoders found, the GStreamer player has video above {}p transcoded by the server",
+                    hw_decode::SOFTWARE_DECODE_MAX_HEIGHT
+                )
+            } else {
+                format!("Accelerated: {}", accelerated.join(", "))
+            });
+        self.hw_decode_expander.set_enable_expansion(true);
+
+        for codec in VideoCodec::ALL {
+            let decoders: Vec<String> = support
+                .decoders_for(codec)
+                .map(|decoder| format!("{} ({})", decoder.api, decoder.element))
+                .collect();
+            let row = adw::ActionRow::builder()
+                .title(codec.label())
+                .subtitle(if decoders.is_empty() {
+                    "Software".to_string()
+                } else {
+                    decoders.join(", ")
+                })
+                .build();
+            self.hw_decode_expander.add_row(&row);
+            self.hw_decode_rows.push(row);
+        }
+    }
+
+    fn storage_subtitle(&self, kind: StorageKind) -> String {
+        if self.relocating == Some(kind) {
+            return "Moving existing content…".to_string();
+        }
+
+        let path = self.storage.get(kind);
+        match self.storage_free.get(&kind).copied() {
+            Some(free) => format!("{} · {} free", path.display(), gtk::glib::format_size(free)),
+            None => path.display().to_string(),
+        }
+    }
+
     fn backend_subtitle(&self) -> String {
         if cfg!(target_os = "macos") {
             "GStreamer is required on macOS; playback always uses the GStreamer engine.".to_string()
@@ -41,14 +363,142 @@ impl PreferencesDialog {
     }
 }
 
+/// When a backup was taken, in local time
+fn backup_time(backup: &Backup) -> String {
+    backup
+        .created_at
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
 #[derive(Debug)]
 pub enum PreferencesDialogInput {
     SetDefaultPlayer(String),
-    HydrateDefaultPlayer(String),
+    SetHoverPreviews(bool),
+    SetPowerSavingPlayback(bool),
+    SetResumeAfterUnlock(bool),
+    SetResumePrompt(bool),
+    SetResolveUrlsWithYtdlp(bool),
+    SetAutoForcedSubtitles(bool),
+    ProbeHardwareDecoders,
+    HardwareDecodersProbed(HardwareDecodeSupport),
+    LoadHiddenItems,
+    HiddenItemsLoaded {
+        items: Vec<MediaItemModel>,
+        collections: Vec<HiddenCollectionModel>,
+    },
+    UnhideItem(MediaItemId),
+    UnhideCollection {
+        source_id: SourceId,
+        collection: String,
+    },
+    SetAutoWakeOnLan(bool),
+    SetNetworkSetting(NetworkSetting),
+    SetGestureSetting(GestureSetting),
+    SetSpeedSetting(SpeedSetting),
+    SetLongAudioSetting(LongAudioSetting),
+    SetDownloadsSetting(DownloadsSetting),
+    SetImageQuality(ImageQuality),
+    SetThemeMusic(bool),
+    SetHomeRowLimit(u32),
+    SetScreensaverMinutes(u32),
+    SetLogLevel(String),
+    SetFileLogging(bool),
+    ChooseStorageLocation(StorageKind),
+    RelocateStorage(StorageKind, PathBuf),
+    LoadStorageSpace,
+    StorageSpaceLoaded(HashMap<StorageKind, u64>),
+    StorageRelocated {
+        kind: StorageKind,
+        error: Option<String>,
+    },
+    SetKidsSetting(KidsSetting),
+    /// Ask for the PIN to turn the kids profile on or off
+    PromptKidsPin,
+    SetKidsProfile {
+        enabled: bool,
+        pin: String,
+    },
+    KidsProfileSet {
+        kids: KidsConfig,
+        error: Option<String>,
+    },
+    SetBackupsSetting(BackupsSetting),
+    LoadBackups,
+    BackUpNow,
+    BackedUp(Option<String>),
+    ConfirmRestore(Backup),
+    Restore(Backup),
+    RestoreStaged(Option<String>),
+    HydrateFromConfig(Box<crate::config::Config>),
     ReloadConfig,
     Close,
 }
 
+/// A single editable value of the network preferences
+#[derive(Debug, Clone, Copy)]
+pub enum NetworkSetting {
+    RequestTimeoutSecs(u64),
+    ConnectionTimeoutSecs(u64),
+    MaxRetries(u32),
+    RetryBaseDelayMs(u64),
+    MaxConcurrentRequests(u32),
+    MaxRequestsPerSecond(u32),
+    MeteredPauseDownloads(bool),
+    MeteredSkipImagePrefetch(bool),
+    MeteredLowerStreamQuality(bool),
+}
+
+/// A single player gesture turned on or off
+#[derive(Debug, Clone, Copy)]
+pub enum GestureSetting {
+    ScrollVolume(bool),
+    ScrollSeek(bool),
+    DragSeek(bool),
+    DoubleClickFullscreen(bool),
+    Swipe(bool),
+}
+
+/// A single editable value of the playback speed preferences
+#[derive(Debug, Clone, Copy)]
+pub enum SpeedSetting {
+    PitchCorrection(bool),
+    Movie(f64),
+    Episode(f64),
+    Music(f64),
+}
+
+/// A single editable value of the audiobook and podcast preferences
+#[derive(Debug, Clone, Copy)]
+pub enum LongAudioSetting {
+    ProgressIntervalSeconds(u32),
+    SnapBackSeconds(u32),
+    SnapBackAfterMinutes(u32),
+}
+
+/// A single editable value of the download preferences
+#[derive(Debug, Clone, Copy)]
+pub enum DownloadsSetting {
+    DeleteWatchedAfterDays(u32),
+    MinFreeSpaceGb(u32),
+}
+
+/// A single editable value of the kids profile preferences
+#[derive(Debug, Clone, Copy)]
+pub enum KidsSetting {
+    MaxAge(u32),
+    AutoplayLimit(u32),
+}
+
+/// A single editable value of the backup preferences
+#[derive(Debug, Clone, Copy)]
+pub enum BackupsSetting {
+    Enabled(bool),
+    IntervalHours(u32),
+    Keep(u32),
+}
+
 #[derive(Debug)]
 pub enum PreferencesDialogOutput {
     Closed,
@@ -78,6 +528,9 @@ impl AsyncComponent for PreferencesDialog {
 
                 add = &adw::PreferencesGroup {
                     set_title: "Player",
+                    // Settings other than the kids profile's are locked while it is on
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
                     set_description: Some("Configure media playback settings"),
                     set_margin_top: 24,
                     set_margin_bottom: 24,
@@ -119,6 +572,670 @@ impl AsyncComponent for PreferencesDialog {
                             }
                         }
                     },
+
+                    add: &model.hw_decode_expander,
+
+                    add = &adw::SwitchRow {
+                        set_title: "Save Power",
+                        set_subtitle: "Turn off upscaling, use a smaller cache and prefer hardware decoding in power saver mode or on low battery",
+                        #[track(model.changed(PreferencesDialog::power_saving_playback()))]
+                        set_active: model.power_saving_playback,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetPowerSavingPlayback(row.is_active()));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Resume After Unlock",
+                        set_subtitle: "Continue playback that was paused by suspending or locking the screen",
+                        #[track(model.changed(PreferencesDialog::resume_after_unlock()))]
+                        set_active: model.resume_after_unlock,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetResumeAfterUnlock(row.is_active()));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Offer to Resume on Startup",
+                        set_subtitle: "Ask whether to continue playback that was interrupted by closing Reel",
+                        #[track(model.changed(PreferencesDialog::resume_prompt()))]
+                        set_active: model.resume_prompt,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetResumePrompt(row.is_active()));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Open Web Pages with yt-dlp",
+                        set_subtitle: "Find the video on pages opened with Open URL, when yt-dlp is installed",
+                        #[track(model.changed(PreferencesDialog::resolve_urls_with_ytdlp()))]
+                        set_active: model.resolve_urls_with_ytdlp,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetResolveUrlsWithYtdlp(row.is_active()));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Show Forced Subtitles",
+                        set_subtitle: "Translate foreign dialogue and signs when the audio is in your language",
+                        #[track(model.changed(PreferencesDialog::auto_forced_subtitles()))]
+                        set_active: model.auto_forced_subtitles,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetAutoForcedSubtitles(row.is_active()));
+                        }
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Playback Speed",
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
+                    set_description: Some("Speed each kind of content starts playing at"),
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::SwitchRow {
+                        set_title: "Pitch Correction",
+                        set_subtitle: "Keep voices at their normal pitch when playing faster or slower",
+                        #[track(model.changed(PreferencesDialog::speed()))]
+                        set_active: model.speed.pitch_correction,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetSpeedSetting(
+                                SpeedSetting::PitchCorrection(row.is_active()),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(0.25, 4.0, 0.05) {
+                        set_title: "Movies",
+                        set_digits: 2,
+                        #[track(model.changed(PreferencesDialog::speed()))]
+                        set_value: model.speed.movie,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetSpeedSetting(
+                                SpeedSetting::Movie(row.value()),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(0.25, 4.0, 0.05) {
+                        set_title: "Episodes",
+                        set_digits: 2,
+                        #[track(model.changed(PreferencesDialog::speed()))]
+                        set_value: model.speed.episode,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetSpeedSetting(
+                                SpeedSetting::Episode(row.value()),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(0.25, 4.0, 0.05) {
+                        set_title: "Music and Audiobooks",
+                        set_digits: 2,
+                        #[track(model.changed(PreferencesDialog::speed()))]
+                        set_value: model.speed.music,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetSpeedSetting(
+                                SpeedSetting::Music(row.value()),
+                            ));
+                        }
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Audiobooks and Podcasts",
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
+                    set_description: Some("Audio tracks of 20 minutes or more"),
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::SpinRow::with_range(1.0, 30.0, 1.0) {
+                        set_title: "Save Position Every",
+                        set_subtitle: "Seconds between saves of the listening position",
+                        #[track(model.changed(PreferencesDialog::long_audio()))]
+                        set_value: model.long_audio.progress_interval_seconds as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetLongAudioSetting(
+                                LongAudioSetting::ProgressIntervalSeconds(row.value() as u32),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(0.0, 120.0, 5.0) {
+                        set_title: "Jump Back on Resume",
+                        set_subtitle: "Seconds to replay when resuming after a long pause, 0 resumes where it stopped",
+                        #[track(model.changed(PreferencesDialog::long_audio()))]
+                        set_value: model.long_audio.snap_back_seconds as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetLongAudioSetting(
+                                LongAudioSetting::SnapBackSeconds(row.value() as u32),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(1.0, 120.0, 1.0) {
+                        set_title: "Long Pause",
+                        set_subtitle: "Minutes a pause lasts before resuming jumps back",
+                        #[track(model.changed(PreferencesDialog::long_audio()))]
+                        set_value: model.long_audio.snap_back_after_minutes as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetLongAudioSetting(
+                                LongAudioSetting::SnapBackAfterMinutes(row.value() as u32),
+                            ));
+                        }
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Player Gestures",
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::SwitchRow {
+                        set_title: "Scroll to Change Volume",
+                        set_subtitle: "Turn the mouse wheel over the video to raise or lower the volume",
+                        #[track(model.changed(PreferencesDialog::gestures()))]
+                        set_active: model.gestures.scroll_volume,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetGestureSetting(
+                                GestureSetting::ScrollVolume(row.is_active()),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Scroll Sideways to Seek",
+                        set_subtitle: "Scroll horizontally over the video to skip backward or forward",
+                        #[track(model.changed(PreferencesDialog::gestures()))]
+                        set_active: model.gestures.scroll_seek,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetGestureSetting(
+                                GestureSetting::ScrollSeek(row.is_active()),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Drag to Seek",
+                        set_subtitle: "Drag sideways across the video to seek instead of moving the window",
+                        #[track(model.changed(PreferencesDialog::gestures()))]
+                        set_active: model.gestures.drag_seek,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetGestureSetting(
+                                GestureSetting::DragSeek(row.is_active()),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Double-Click for Fullscreen",
+                        set_subtitle: "Double-click the video to enter or leave fullscreen",
+                        #[track(model.changed(PreferencesDialog::gestures()))]
+                        set_active: model.gestures.double_click_fullscreen,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetGestureSetting(
+                                GestureSetting::DoubleClickFullscreen(row.is_active()),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Swipe Gestures",
+                        set_subtitle: "Swipe sideways to seek and up or down to change the volume on touch devices",
+                        #[track(model.changed(PreferencesDialog::gestures()))]
+                        set_active: model.gestures.swipe,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetGestureSetting(
+                                GestureSetting::Swipe(row.is_active()),
+                            ));
+                        }
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Browsing",
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::SwitchRow {
+                        set_title: "Hover Previews",
+                        set_subtitle: "Play a short muted preview when hovering a movie or episode, except on metered connections",
+                        #[track(model.changed(PreferencesDialog::hover_previews()))]
+                        set_active: model.hover_previews,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetHoverPreviews(row.is_active()));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Theme Music",
+                        set_subtitle: "Quietly play the theme of a movie or show on its page, except on metered connections",
+                        #[track(model.changed(PreferencesDialog::theme_music()))]
+                        set_active: model.theme_music,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetThemeMusic(row.is_active()));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(10.0, HOME_SECTION_ITEM_LIMIT as f64, 5.0) {
+                        set_title: "Items per Home Row",
+                        set_subtitle: "How many items each row of the home page shows before See All",
+                        #[track(model.changed(PreferencesDialog::home_row_limit()))]
+                        set_value: model.home_row_limit as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetHomeRowLimit(row.value() as u32));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(0.0, 120.0, 1.0) {
+                        set_title: "Screensaver After",
+                        set_subtitle: "Minutes idle on the home page or paused before showing library artwork, 0 for never",
+                        #[track(model.changed(PreferencesDialog::screensaver_minutes()))]
+                        set_value: model.screensaver_minutes as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetScreensaverMinutes(row.value() as u32));
+                        }
+                    },
+
+                    add = &adw::ComboRow {
+                        set_title: "Artwork Quality",
+                        set_subtitle: "Sharper posters and backdrops use more bandwidth and disk space",
+                        set_model: Some(&gtk::StringList::new(&IMAGE_QUALITIES.map(|(label, _)| label))),
+                        #[track(model.changed(PreferencesDialog::image_quality()))]
+                        set_selected: model.image_quality_index(),
+                        connect_selected_notify[sender] => move |row| {
+                            if let Some((_, quality)) = IMAGE_QUALITIES.get(row.selected() as usize) {
+                                sender.input(PreferencesDialogInput::SetImageQuality(*quality));
+                            }
+                        }
+                    },
+
+                    add: &model.hidden_expander,
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Kids Profile",
+                    set_description: Some("Only movies and shows rated for children, on a simpler home page. Unrated ones are left out too."),
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::ActionRow {
+                        set_title: "Kids Profile",
+                        #[track(model.changed(PreferencesDialog::kids()))]
+                        set_subtitle: if model.kids.enabled {
+                            "On, leaving it asks for the PIN"
+                        } else {
+                            "Off, turning it on sets a PIN"
+                        },
+
+                        add_suffix = &gtk::Button {
+                            #[track(model.changed(PreferencesDialog::kids()))]
+                            set_label: if model.kids.enabled { "Turn Off…" } else { "Turn On…" },
+                            set_valign: gtk::Align::Center,
+                            connect_clicked => PreferencesDialogInput::PromptKidsPin,
+                        },
+                    },
+
+                    add = &adw::SpinRow::with_range(0.0, 18.0, 1.0) {
+                        set_title: "Rated up to Age",
+                        set_subtitle: "Oldest age movies and shows may be rated for",
+                        #[track(model.changed(PreferencesDialog::kids()))]
+                        set_sensitive: !model.kids.enabled,
+                        #[track(model.changed(PreferencesDialog::kids()))]
+                        set_value: model.kids.max_age as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetKidsSetting(
+                                KidsSetting::MaxAge(row.value() as u32),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(0.0, 20.0, 1.0) {
+                        set_title: "Episodes in a Row",
+                        set_subtitle: "Episodes auto-play plays before stopping, 0 for no limit",
+                        #[track(model.changed(PreferencesDialog::kids()))]
+                        set_sensitive: !model.kids.enabled,
+                        #[track(model.changed(PreferencesDialog::kids()))]
+                        set_value: model.kids.autoplay_limit as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetKidsSetting(
+                                KidsSetting::AutoplayLimit(row.value() as u32),
+                            ));
+                        }
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Network",
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
+                    set_description: Some("Configure how Reel reaches your servers"),
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::SwitchRow {
+                        set_title: "Wake Servers Automatically",
+                        set_subtitle: "Send a Wake-on-LAN packet when a server with a MAC address can't be reached",
+                        #[track(model.changed(PreferencesDialog::network()))]
+                        set_active: model.network.auto_wake_on_lan,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetAutoWakeOnLan(row.is_active()));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(5.0, 300.0, 5.0) {
+                        set_title: "Request Timeout",
+                        set_subtitle: "Seconds to wait for a server to answer a request",
+                        #[track(model.changed(PreferencesDialog::network()))]
+                        set_value: model.network.request_timeout_secs as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetNetworkSetting(
+                                NetworkSetting::RequestTimeoutSecs(row.value() as u64),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(1.0, 30.0, 1.0) {
+                        set_title: "Connection Test Timeout",
+                        set_subtitle: "Seconds to wait when checking whether a server address is reachable",
+                        #[track(model.changed(PreferencesDialog::network()))]
+                        set_value: model.network.connection_timeout_secs as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetNetworkSetting(
+                                NetworkSetting::ConnectionTimeoutSecs(row.value() as u64),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(0.0, 10.0, 1.0) {
+                        set_title: "Retries",
+                        set_subtitle: "How often a failed request is retried",
+                        #[track(model.changed(PreferencesDialog::network()))]
+                        set_value: model.network.max_retries as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetNetworkSetting(
+                                NetworkSetting::MaxRetries(row.value() as u32),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(50.0, 5000.0, 50.0) {
+                        set_title: "Retry Backoff",
+                        set_subtitle: "Milliseconds before the first retry, doubled for each further retry",
+                        #[track(model.changed(PreferencesDialog::network()))]
+                        set_value: model.network.retry_base_delay_ms as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetNetworkSetting(
+                                NetworkSetting::RetryBaseDelayMs(row.value() as u64),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(1.0, 16.0, 1.0) {
+                        set_title: "Simultaneous Requests",
+                        set_subtitle: "Requests sent to one server at the same time",
+                        #[track(model.changed(PreferencesDialog::network()))]
+                        set_value: model.network.max_concurrent_requests as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetNetworkSetting(
+                                NetworkSetting::MaxConcurrentRequests(row.value() as u32),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(0.0, 100.0, 1.0) {
+                        set_title: "Request Rate Limit",
+                        set_subtitle: "Requests per second to one server, 0 for no limit",
+                        #[track(model.changed(PreferencesDialog::network()))]
+                        set_value: model.network.max_requests_per_second as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetNetworkSetting(
+                                NetworkSetting::MaxRequestsPerSecond(row.value() as u32),
+                            ));
+                        }
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Metered Connections",
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
+                    set_description: Some("Limit data usage when the network is marked as metered"),
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::SwitchRow {
+                        set_title: "Pause Downloads",
+                        set_subtitle: "Hold back offline downloads until an unmetered network is available",
+                        #[track(model.changed(PreferencesDialog::network()))]
+                        set_active: model.network.metered_pause_downloads,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetNetworkSetting(
+                                NetworkSetting::MeteredPauseDownloads(row.is_active()),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Skip Image Prefetch",
+                        set_subtitle: "Only load artwork that is on screen",
+                        #[track(model.changed(PreferencesDialog::network()))]
+                        set_active: model.network.metered_skip_image_prefetch,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetNetworkSetting(
+                                NetworkSetting::MeteredSkipImagePrefetch(row.is_active()),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Lower Streaming Quality",
+                        set_subtitle: "Start playback at 720p or below when the server can transcode",
+                        #[track(model.changed(PreferencesDialog::network()))]
+                        set_active: model.network.metered_lower_stream_quality,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetNetworkSetting(
+                                NetworkSetting::MeteredLowerStreamQuality(row.is_active()),
+                            ));
+                        }
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Downloads",
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
+                    set_description: Some("Clean up offline downloads once they have been watched"),
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::SpinRow::with_range(0.0, 365.0, 1.0) {
+                        set_title: "Delete Watched Downloads After",
+                        set_subtitle: "Days after watching, 0 keeps watched downloads. Kept downloads are never deleted",
+                        #[track(model.changed(PreferencesDialog::downloads()))]
+                        set_value: model.downloads.delete_watched_after_days as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetDownloadsSetting(
+                                DownloadsSetting::DeleteWatchedAfterDays(row.value() as u32),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(0.0, 500.0, 1.0) {
+                        set_title: "Minimum Free Space",
+                        set_subtitle: "Gigabytes to keep free, deleting watched downloads early when needed. 0 disables",
+                        #[track(model.changed(PreferencesDialog::downloads()))]
+                        set_value: model.downloads.min_free_space_gb as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetDownloadsSetting(
+                                DownloadsSetting::MinFreeSpaceGb(row.value() as u32),
+                            ));
+                        }
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Storage",
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
+                    set_description: Some("Choose where Reel keeps its files. Existing content is moved to the new location"),
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::ActionRow {
+                        set_title: StorageKind::StreamingCache.label(),
+                        #[track(model.changed(PreferencesDialog::storage()) || model.changed(PreferencesDialog::storage_free()) || model.changed(PreferencesDialog::relocating()))]
+                        set_subtitle: &model.storage_subtitle(StorageKind::StreamingCache),
+
+                        add_suffix = &gtk::Button {
+                            set_label: "Change…",
+                            set_valign: gtk::Align::Center,
+                            #[track(model.changed(PreferencesDialog::relocating()))]
+                            set_sensitive: model.relocating.is_none(),
+                            connect_clicked => PreferencesDialogInput::ChooseStorageLocation(StorageKind::StreamingCache),
+                        },
+                    },
+
+                    add = &adw::ActionRow {
+                        set_title: StorageKind::ImageCache.label(),
+                        #[track(model.changed(PreferencesDialog::storage()) || model.changed(PreferencesDialog::storage_free()) || model.changed(PreferencesDialog::relocating()))]
+                        set_subtitle: &model.storage_subtitle(StorageKind::ImageCache),
+
+                        add_suffix = &gtk::Button {
+                            set_label: "Change…",
+                            set_valign: gtk::Align::Center,
+                            #[track(model.changed(PreferencesDialog::relocating()))]
+                            set_sensitive: model.relocating.is_none(),
+                            connect_clicked => PreferencesDialogInput::ChooseStorageLocation(StorageKind::ImageCache),
+                        },
+                    },
+
+                    add = &adw::ActionRow {
+                        set_title: StorageKind::Downloads.label(),
+                        #[track(model.changed(PreferencesDialog::storage()) || model.changed(PreferencesDialog::storage_free()) || model.changed(PreferencesDialog::relocating()))]
+                        set_subtitle: &model.storage_subtitle(StorageKind::Downloads),
+
+                        add_suffix = &gtk::Button {
+                            set_label: "Change…",
+                            set_valign: gtk::Align::Center,
+                            #[track(model.changed(PreferencesDialog::relocating()))]
+                            set_sensitive: model.relocating.is_none(),
+                            connect_clicked => PreferencesDialogInput::ChooseStorageLocation(StorageKind::Downloads),
+                        },
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Backups",
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
+                    set_description: Some("Snapshots of your library database and settings, including watch history, to recover from corruption"),
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::SwitchRow {
+                        set_title: "Automatic Backups",
+                        set_subtitle: "A backup is also taken before each database upgrade",
+                        #[track(model.changed(PreferencesDialog::backups()))]
+                        set_active: model.backups.enabled,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetBackupsSetting(
+                                BackupsSetting::Enabled(row.is_active()),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(1.0, 168.0, 1.0) {
+                        set_title: "Back Up Every",
+                        set_subtitle: "Hours between automatic backups",
+                        #[track(model.changed(PreferencesDialog::backups()))]
+                        set_sensitive: model.backups.enabled,
+                        #[track(model.changed(PreferencesDialog::backups()))]
+                        set_value: model.backups.interval_hours as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetBackupsSetting(
+                                BackupsSetting::IntervalHours(row.value() as u32),
+                            ));
+                        }
+                    },
+
+                    add = &adw::SpinRow::with_range(1.0, 50.0, 1.0) {
+                        set_title: "Backups to Keep",
+                        set_subtitle: "Of each kind, older backups are deleted",
+                        #[track(model.changed(PreferencesDialog::backups()))]
+                        set_value: model.backups.keep as f64,
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetBackupsSetting(
+                                BackupsSetting::Keep(row.value() as u32),
+                            ));
+                        }
+                    },
+
+                    add = &adw::ActionRow {
+                        set_title: "Back Up Now",
+                        set_subtitle: "Take a backup before making big changes",
+
+                        add_suffix = &gtk::Button {
+                            set_label: "Back Up",
+                            set_valign: gtk::Align::Center,
+                            #[track(model.changed(PreferencesDialog::backing_up()))]
+                            set_sensitive: !model.backing_up,
+                            connect_clicked => PreferencesDialogInput::BackUpNow,
+                        },
+                    },
+
+                    add: &model.backup_expander,
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Logging",
+                    #[track(model.changed(PreferencesDialog::kids()))]
+                    set_sensitive: !model.kids.enabled,
+                    set_description: Some("Control how much Reel records for troubleshooting"),
+                    set_margin_start: 24,
+                    set_margin_end: 24,
+                    set_margin_bottom: 24,
+
+                    add = &adw::ComboRow {
+                        set_title: "Log Level",
+                        set_subtitle: "Most detailed messages to record, more detail helps when reporting a problem",
+                        set_model: Some(&gtk::StringList::new(&LOG_LEVELS.map(|(label, _)| label))),
+                        #[track(model.changed(PreferencesDialog::logging()))]
+                        set_selected: model.log_level_index(),
+                        connect_selected_notify[sender] => move |row| {
+                            if let Some((_, level)) = LOG_LEVELS.get(row.selected() as usize) {
+                                sender.input(PreferencesDialogInput::SetLogLevel(
+                                    level.as_str().to_ascii_lowercase(),
+                                ));
+                            }
+                        }
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Write Log Files",
+                        set_subtitle: &format!("Keep rotating log files in {}", log_directory().display()),
+                        #[track(model.changed(PreferencesDialog::logging()))]
+                        set_active: model.logging.file_logging,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesDialogInput::SetFileLogging(row.is_active()));
+                        }
+                    },
                 },
             },
         }
@@ -132,18 +1249,78 @@ impl AsyncComponent for PreferencesDialog {
         // Load preferences from ConfigService
         let config = CONFIG_SERVICE.get_config().await;
 
-        let model = Self {
+        let hidden_expander = adw::ExpanderRow::builder()
+            .title("Hidden from Home")
+            .build();
+
+        let backup_expander = adw::ExpanderRow::builder()
+            .title("Restore a Backup")
+            .build();
+
+        let hw_decode_expander = adw::ExpanderRow::builder()
+            .title("Hardware Decoding")
+            .build();
+        let probe_button = gtk::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .tooltip_text("Check Again")
+            .valign(gtk::Align::Center)
+            .css_classes(["flat"])
+            .build();
+        probe_button.connect_clicked({
+            let sender = sender.clone();
+            move |_| sender.input(PreferencesDialogInput::ProbeHardwareDecoders)
+        });
+        hw_decode_expander.add_suffix(&probe_button);
+
+        let mut model = Self {
             db,
             default_player: config.playback.player_backend,
             hardware_acceleration: config.playback.hardware_acceleration,
+            hw_decode_expander,
+            hw_decode_rows: Vec::new(),
+            power_saving_playback: config.playback.power_saving_playback,
+            resume_after_unlock: config.playback.resume_after_unlock,
+            resume_prompt: config.ui.resume_prompt,
+            resolve_urls_with_ytdlp: config.playback.resolve_urls_with_ytdlp,
+            auto_forced_subtitles: config.playback.subtitles.auto_forced,
+            gestures: config.playback.gestures,
+            speed: config.playback.speed,
+            long_audio: config.playback.long_audio,
             items_per_page: 48,
+            hover_previews: config.ui.hover_previews,
+            image_quality: config.ui.image_quality,
+            theme_music: config.ui.theme_music,
+            home_row_limit: config.ui.home_row_items() as u32,
+            screensaver_minutes: config.ui.screensaver_minutes,
+            hidden_expander,
+            hidden_rows: Vec::new(),
+            kids: config.kids,
             cache_size_mb: config.playback.mpv_cache_size_mb as i32,
             auto_clean_cache: true,
+            network: config.network,
+            storage: StorageLocations::from(&config),
+            storage_free: HashMap::new(),
+            downloads: config.downloads,
+            relocating: None,
+            backups: config.backups,
+            backing_up: false,
+            backup_expander,
+            backup_rows: Vec::new(),
+            logging: config.logging,
             tracker: 0,
         };
 
         let widgets = view_output!();
 
+        let support = hw_decode::hardware_decode_support();
+        model.show_hardware_decoders(support.as_ref());
+        if support.is_none() {
+            sender.input(PreferencesDialogInput::ProbeHardwareDecoders);
+        }
+        sender.input(PreferencesDialogInput::LoadHiddenItems);
+        sender.input(PreferencesDialogInput::LoadBackups);
+        sender.input(PreferencesDialogInput::LoadStorageSpace);
+
         AsyncComponentParts { model, widgets }
     }
 
@@ -169,17 +1346,592 @@ impl AsyncComponent for PreferencesDialog {
                     }
                 });
             }
-            PreferencesDialogInput::HydrateDefaultPlayer(player) => {
-                self.set_default_player(player);
+            PreferencesDialogInput::SetHoverPreviews(enabled) => {
+                if self.hover_previews == enabled {
+                    return;
+                }
+                self.set_hover_previews(enabled);
+
+                relm4::spawn_local(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_hover_previews(enabled).await {
+                        tracing::error!("Failed to save hover preview preference: {}", e);
+                    }
+                });
+            }
+            PreferencesDialogInput::SetImageQuality(quality) => {
+                if self.image_quality == quality {
+                    return;
+                }
+                self.set_image_quality(quality);
+
+                relm4::spawn_local(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_image_quality(quality).await {
+                        tracing::error!("Failed to save image quality: {}", e);
+                    }
+                });
+            }
+            PreferencesDialogInput::SetThemeMusic(enabled) => {
+                if self.theme_music == enabled {
+                    return;
+                }
+                self.set_theme_music(enabled);
+
+                relm4::spawn_local(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_theme_music(enabled).await {
+                        tracing::error!("Failed to save theme music preference: {}", e);
+                    }
+                });
+            }
+            PreferencesDialogInput::SetHomeRowLimit(limit) => {
+                if self.home_row_limit == limit {
+                    return;
+                }
+                self.set_home_row_limit(limit);
+
+                relm4::spawn_local(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_home_row_limit(limit).await {
+                        tracing::error!("Failed to save home row limit: {}", e);
+                    }
+                });
+            }
+            PreferencesDialogInput::SetScreensaverMinutes(minutes) => {
+                if self.screensaver_minutes == minutes {
+                    return;
+                }
+                self.set_screensaver_minutes(minutes);
+
+                relm4::spawn_local(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_screensaver_minutes(minutes).await {
+                        tracing::error!("Failed to save screensaver delay: {}", e);
+                    }
+                });
+            }
+            PreferencesDialogInput::SetPowerSavingPlayback(enabled) => {
+                if self.power_saving_playback == enabled {
+                    return;
+                }
+                self.set_power_saving_playback(enabled);
+
+                relm4::spawn_local(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_power_saving_playback(enabled).await {
+                        tracing::error!("Failed to save power saving preference: {}", e);
+                    }
+                });
+            }
+            PreferencesDialogInput::SetResumeAfterUnlock(enabled) => {
+                if self.resume_after_unlock == enabled {
+                    return;
+                }
+                self.set_resume_after_unlock(enabled);
+
+                relm4::spawn_local(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_resume_after_unlock(enabled).await {
+                        tracing::error!("Failed to save resume after unlock preference: {}", e);
+                    }
+                });
+            }
+            PreferencesDialogInput::SetResolveUrlsWithYtdlp(enabled) => {
+                if self.resolve_urls_with_ytdlp == enabled {
+                    return;
+                }
+                self.set_resolve_urls_with_ytdlp(enabled);
+
+                relm4::spawn_local(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_resolve_urls_with_ytdlp(enabled).await {
+                        tracing::error!("Failed to save yt-dlp preference: {}", e);
+                    }
+                });
+            }
+            PreferencesDialogInput::SetAutoForcedSubtitles(enabled) => {
+                if self.auto_forced_subtitles == enabled {
+                    return;
+                }
+                self.set_auto_forced_subtitles(enabled);
+
+                relm4::spawn_local(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_auto_forced_subtitles(enabled).await {
+                        tracing::error!("Failed to save forced subtitle preference: {}", e);
+                    }
+                });
+            }
+            PreferencesDialogInput::SetResumePrompt(enabled) => {
+                if self.resume_prompt == enabled {
+                    return;
+                }
+                self.set_resume_prompt(enabled);
+
+                relm4::spawn_local(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_resume_prompt(enabled).await {
+                        tracing::error!("Failed to save resume prompt preference: {}", e);
+                    }
+                });
+            }
+            PreferencesDialogInput::ProbeHardwareDecoders => {
+                self.show_hardware_decoders(None);
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let support = hw_decode::probe().await;
+                    sender.input(PreferencesDialogInput::HardwareDecodersProbed(support));
+                });
+            }
+            PreferencesDialogInput::HardwareDecodersProbed(support) => {
+                self.show_hardware_decoders(Some(&support));
+            }
+            PreferencesDialogInput::LoadHiddenItems => {
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let items = HiddenItemsService::get_hidden_items(&db).await;
+                    let collections = HiddenItemsService::get_hidden_collections(&db).await;
+                    match (items, collections) {
+                        (Ok(items), Ok(collections)) => {
+                            sender.input(PreferencesDialogInput::HiddenItemsLoaded {
+                                items,
+                                collections,
+                            })
+                        }
+                        (Err(e), _) | (_, Err(e)) => {
+                            tracing::error!("Failed to load hidden items: {}", e)
+                        }
+                    }
+                });
+            }
+            PreferencesDialogInput::HiddenItemsLoaded { items, collections } => {
+                self.show_hidden_items(items, collections, &sender);
+            }
+            PreferencesDialogInput::UnhideItem(media_id) => {
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let cmd = SetHiddenCommand {
+                        db,
+                        media_id,
+                        hidden: false,
+                    };
+                    if let Err(e) = cmd.execute().await {
+                        tracing::error!("Failed to show hidden item: {}", e);
+                    }
+                    sender.input(PreferencesDialogInput::LoadHiddenItems);
+                });
+            }
+            PreferencesDialogInput::UnhideCollection {
+                source_id,
+                collection,
+            } => {
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let cmd = SetCollectionHiddenCommand {
+                        db,
+                        source_id,
+                        collection,
+                        hidden: false,
+                    };
+                    if let Err(e) = cmd.execute().await {
+                        tracing::error!("Failed to show hidden collection: {}", e);
+                    }
+                    sender.input(PreferencesDialogInput::LoadHiddenItems);
+                });
+            }
+            PreferencesDialogInput::SetAutoWakeOnLan(enabled) => {
+                if self.network.auto_wake_on_lan == enabled {
+                    return;
+                }
+                self.get_mut_network().auto_wake_on_lan = enabled;
+                self.save_network_settings();
+            }
+            PreferencesDialogInput::SetNetworkSetting(setting) => {
+                let mut network = self.network.clone();
+                match setting {
+                    NetworkSetting::RequestTimeoutSecs(secs) => network.request_timeout_secs = secs,
+                    NetworkSetting::ConnectionTimeoutSecs(secs) => {
+                        network.connection_timeout_secs = secs
+                    }
+                    NetworkSetting::MaxRetries(retries) => network.max_retries = retries,
+                    NetworkSetting::RetryBaseDelayMs(ms) => network.retry_base_delay_ms = ms,
+                    NetworkSetting::MaxConcurrentRequests(requests) => {
+                        network.max_concurrent_requests = requests
+                    }
+                    NetworkSetting::MaxRequestsPerSecond(rate) => {
+                        network.max_requests_per_second = rate
+                    }
+                    NetworkSetting::MeteredPauseDownloads(enabled) => {
+                        network.metered_pause_downloads = enabled
+                    }
+                    NetworkSetting::MeteredSkipImagePrefetch(enabled) => {
+                        network.metered_skip_image_prefetch = enabled
+                    }
+                    NetworkSetting::MeteredLowerStreamQuality(enabled) => {
+                        network.metered_lower_stream_quality = enabled
+                    }
+                }
+
+                if network == self.network {
+                    return;
+                }
+                self.set_network(network);
+                self.save_network_settings();
+            }
+            PreferencesDialogInput::SetGestureSetting(setting) => {
+                let mut gestures = self.gestures;
+                match setting {
+                    GestureSetting::ScrollVolume(enabled) => gestures.scroll_volume = enabled,
+                    GestureSetting::ScrollSeek(enabled) => gestures.scroll_seek = enabled,
+                    GestureSetting::DragSeek(enabled) => gestures.drag_seek = enabled,
+                    GestureSetting::DoubleClickFullscreen(enabled) => {
+                        gestures.double_click_fullscreen = enabled
+                    }
+                    GestureSetting::Swipe(enabled) => gestures.swipe = enabled,
+                }
+
+                if gestures == self.gestures {
+                    return;
+                }
+                self.set_gestures(gestures);
+                self.save_gesture_settings();
+            }
+            PreferencesDialogInput::SetSpeedSetting(setting) => {
+                let mut speed = self.speed;
+                match setting {
+                    SpeedSetting::PitchCorrection(enabled) => speed.pitch_correction = enabled,
+                    SpeedSetting::Movie(value) => speed.movie = value,
+                    SpeedSetting::Episode(value) => speed.episode = value,
+                    SpeedSetting::Music(value) => speed.music = value,
+                }
+
+                if speed == self.speed {
+                    return;
+                }
+                self.set_speed(speed);
+                self.save_speed_settings();
+            }
+            PreferencesDialogInput::SetLongAudioSetting(setting) => {
+                let mut long_audio = self.long_audio;
+                match setting {
+                    LongAudioSetting::ProgressIntervalSeconds(seconds) => {
+                        long_audio.progress_interval_seconds = seconds
+                    }
+                    LongAudioSetting::SnapBackSeconds(seconds) => {
+                        long_audio.snap_back_seconds = seconds
+                    }
+                    LongAudioSetting::SnapBackAfterMinutes(minutes) => {
+                        long_audio.snap_back_after_minutes = minutes
+                    }
+                }
+
+                if long_audio == self.long_audio {
+                    return;
+                }
+                self.set_long_audio(long_audio);
+                self.save_long_audio_settings();
+            }
+            PreferencesDialogInput::SetDownloadsSetting(setting) => {
+                let mut downloads = self.downloads.clone();
+                match setting {
+                    DownloadsSetting::DeleteWatchedAfterDays(days) => {
+                        downloads.delete_watched_after_days = days
+                    }
+                    DownloadsSetting::MinFreeSpaceGb(gb) => downloads.min_free_space_gb = gb,
+                }
+
+                if downloads == self.downloads {
+                    return;
+                }
+                self.set_downloads(downloads);
+                self.save_downloads_settings();
+            }
+            PreferencesDialogInput::SetLogLevel(level) => {
+                if self.logging.level == level {
+                    return;
+                }
+                self.get_mut_logging().level = level;
+                self.save_logging_settings();
+            }
+            PreferencesDialogInput::SetFileLogging(enabled) => {
+                if self.logging.file_logging == enabled {
+                    return;
+                }
+                self.get_mut_logging().file_logging = enabled;
+                self.save_logging_settings();
+            }
+            PreferencesDialogInput::ChooseStorageLocation(kind) => {
+                let dialog = gtk::FileDialog::builder()
+                    .title(format!("Choose a Location for the {}", kind.label()))
+                    .modal(true)
+                    .build();
+                let parent = root.root().and_downcast::<gtk::Window>();
+                let sender = sender.clone();
+                dialog.select_folder(
+                    parent.as_ref(),
+                    None::<&gtk::gio::Cancellable>,
+                    move |result| {
+                        if let Ok(folder) = result
+                            && let Some(path) = folder.path()
+                        {
+                            sender.input(PreferencesDialogInput::RelocateStorage(kind, path));
+                        }
+                    },
+                );
+            }
+            PreferencesDialogInput::RelocateStorage(kind, path) => {
+                if self.relocating.is_some() {
+                    return;
+                }
+                self.set_relocating(Some(kind));
+
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let error = StorageService::relocate(&db, kind, path)
+                        .await
+                        .err()
+                        .map(|e| e.to_string());
+                    sender.input(PreferencesDialogInput::StorageRelocated { kind, error });
+                });
+            }
+            PreferencesDialogInput::LoadStorageSpace => {
+                let storage = self.storage.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let mut free = HashMap::new();
+                    for kind in [
+                        StorageKind::StreamingCache,
+                        StorageKind::ImageCache,
+                        StorageKind::Downloads,
+                    ] {
+                        if let Some(bytes) =
+                            available_space_async(storage.get(kind).to_path_buf()).await
+                        {
+                            free.insert(kind, bytes);
+                        }
+                    }
+                    sender.input(PreferencesDialogInput::StorageSpaceLoaded(free));
+                });
+            }
+            PreferencesDialogInput::StorageSpaceLoaded(free) => {
+                self.set_storage_free(free);
+            }
+            PreferencesDialogInput::StorageRelocated { kind, error } => {
+                self.set_relocating(None);
+                self.set_storage(storage_locations());
+                sender.input(PreferencesDialogInput::LoadStorageSpace);
+                let message = match error {
+                    Some(e) => {
+                        tracing::error!("Failed to move {}: {}", kind.label(), e);
+                        format!("Failed to move {}: {}", kind.label(), e)
+                    }
+                    None => format!("{} moved", kind.label()),
+                };
+                root.add_toast(adw::Toast::new(&message));
+            }
+            PreferencesDialogInput::SetKidsSetting(setting) => {
+                let mut kids = self.kids.clone();
+                match setting {
+                    KidsSetting::MaxAge(age) => kids.max_age = age,
+                    KidsSetting::AutoplayLimit(limit) => kids.autoplay_limit = limit,
+                }
+
+                if kids == self.kids || self.kids.enabled {
+                    return;
+                }
+                self.set_kids(kids);
+                self.save_kids_settings();
+            }
+            PreferencesDialogInput::PromptKidsPin => {
+                let enabled = self.kids.enabled;
+                let (heading, body, confirm) = if enabled {
+                    (
+                        "Leave Kids Profile?",
+                        "Enter the PIN set when the kids profile was turned on.",
+                        "_Leave",
+                    )
+                } else {
+                    (
+                        "Turn On Kids Profile?",
+                        "Choose a PIN of at least 4 digits. It is asked for to leave the kids profile.",
+                        "_Turn On",
+                    )
+                };
+                let dialog = adw::AlertDialog::builder()
+                    .heading(heading)
+                    .body(body)
+                    .close_response("cancel")
+                    .default_response("confirm")
+                    .build();
+                dialog.add_responses(&[("cancel", "_Cancel"), ("confirm", confirm)]);
+                dialog.set_response_appearance("confirm", adw::ResponseAppearance::Suggested);
+
+                let entry = gtk::PasswordEntry::builder()
+                    .placeholder_text("PIN")
+                    .show_peek_icon(true)
+                    .activates_default(true)
+                    .build();
+                dialog.set_extra_child(Some(&entry));
+
+                let sender = sender.clone();
+                dialog.connect_response(None, move |_, response| {
+                    if response == "confirm" {
+                        sender.input(PreferencesDialogInput::SetKidsProfile {
+                            enabled: !enabled,
+                            pin: entry.text().to_string(),
+                        });
+                    }
+                });
+                dialog.present(Some(root));
+            }
+            PreferencesDialogInput::SetKidsProfile { enabled, pin } => {
+                let sender = sender.clone();
+                relm4::spawn_local(async move {
+                    let error = if enabled {
+                        CONFIG_SERVICE
+                            .enable_kids_profile(&pin)
+                            .await
+                            .err()
+                            .map(|e| format!("{:#}", e))
+                    } else {
+                        match CONFIG_SERVICE.disable_kids_profile(&pin).await {
+                            Ok(true) => None,
+                            Ok(false) => Some("Wrong PIN".to_string()),
+                            Err(e) => Some(format!("{:#}", e)),
+                        }
+                    };
+                    let kids = CONFIG_SERVICE.get_config().await.kids;
+                    sender.input(PreferencesDialogInput::KidsProfileSet { kids, error });
+                });
+            }
+            PreferencesDialogInput::KidsProfileSet { kids, error } => {
+                let message = match error {
+                    Some(e) => {
+                        tracing::warn!("Failed to change the kids profile: {}", e);
+                        e
+                    }
+                    None if kids.enabled => "Kids profile on".to_string(),
+                    None => "Kids profile off".to_string(),
+                };
+                self.set_kids(kids);
+                root.add_toast(adw::Toast::new(&message));
+            }
+            PreferencesDialogInput::SetBackupsSetting(setting) => {
+                let mut backups = self.backups.clone();
+                match setting {
+                    BackupsSetting::Enabled(enabled) => backups.enabled = enabled,
+                    BackupsSetting::IntervalHours(hours) => backups.interval_hours = hours,
+                    BackupsSetting::Keep(keep) => backups.keep = keep,
+                }
+
+                if backups == self.backups {
+                    return;
+                }
+                self.set_backups(backups);
+                self.save_backup_settings();
+            }
+            PreferencesDialogInput::LoadBackups => match BackupService::list() {
+                Ok(backups) => self.show_backups(backups, &sender),
+                Err(e) => tracing::error!("Failed to list backups: {}", e),
+            },
+            PreferencesDialogInput::BackUpNow => {
+                if self.backing_up {
+                    return;
+                }
+                self.set_backing_up(true);
+
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let error = BackupService::back_up_now(&db)
+                        .await
+                        .err()
+                        .map(|e| format!("{:#}", e));
+                    sender.input(PreferencesDialogInput::BackedUp(error));
+                });
+            }
+            PreferencesDialogInput::BackedUp(error) => {
+                self.set_backing_up(false);
+                let message = match error {
+                    Some(e) => {
+                        tracing::error!("Failed to back up: {}", e);
+                        format!("Failed to back up: {}", e)
+                    }
+                    None => "Backup taken".to_string(),
+                };
+                root.add_toast(adw::Toast::new(&message));
+                sender.input(PreferencesDialogInput::LoadBackups);
+            }
+            PreferencesDialogInput::ConfirmRestore(backup) => {
+                let dialog = adw::AlertDialog::builder()
+                    .heading("Restore Backup?")
+                    .body(format!(
+                        "Your libraries, watch history and settings go back to how they were on {}. The backup is restored the next time Reel starts, after backing up what you have now.",
+                        backup_time(&backup)
+                    ))
+                    .close_response("cancel")
+                    .default_response("cancel")
+                    .build();
+                dialog.add_responses(&[("cancel", "_Cancel"), ("restore", "_Restore")]);
+                dialog.set_response_appearance("restore", adw::ResponseAppearance::Destructive);
+
+                let sender = sender.clone();
+                dialog.connect_response(None, move |_, response| {
+                    if response == "restore" {
+                        sender.input(PreferencesDialogInput::Restore(backup.clone()));
+                    }
+                });
+                dialog.present(Some(root));
+            }
+            PreferencesDialogInput::Restore(backup) => {
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    let error = BackupService::restore(&db, &backup)
+                        .await
+                        .err()
+                        .map(|e| format!("{:#}", e));
+                    sender.input(PreferencesDialogInput::RestoreStaged(error));
+                });
+            }
+            PreferencesDialogInput::RestoreStaged(error) => {
+                let message = match error {
+                    Some(e) => {
+                        tracing::error!("Failed to restore backup: {}", e);
+                        format!("Failed to restore backup: {}", e)
+                    }
+                    None => "Restart Reel to finish restoring the backup".to_string(),
+                };
+                root.add_toast(adw::Toast::new(&message));
+                sender.input(PreferencesDialogInput::LoadBackups);
+            }
+            PreferencesDialogInput::HydrateFromConfig(config) => {
+                let storage = StorageLocations::from(config.as_ref());
+                if storage != self.storage {
+                    sender.input(PreferencesDialogInput::LoadStorageSpace);
+                }
+                self.set_storage(storage);
+                self.set_power_saving_playback(config.playback.power_saving_playback);
+                self.set_resume_after_unlock(config.playback.resume_after_unlock);
+                self.set_resume_prompt(config.ui.resume_prompt);
+                self.set_resolve_urls_with_ytdlp(config.playback.resolve_urls_with_ytdlp);
+                self.set_auto_forced_subtitles(config.playback.subtitles.auto_forced);
+                self.set_gestures(config.playback.gestures);
+                self.set_speed(config.playback.speed);
+                self.set_long_audio(config.playback.long_audio);
+                self.set_default_player(config.playback.player_backend);
+                self.set_hover_previews(config.ui.hover_previews);
+                self.set_image_quality(config.ui.image_quality);
+                self.set_theme_music(config.ui.theme_music);
+                self.set_home_row_limit(config.ui.home_row_items() as u32);
+                self.set_screensaver_minutes(config.ui.screensaver_minutes);
+                self.set_network(config.network);
+                self.set_downloads(config.downloads);
+                self.set_backups(config.backups);
+                self.set_kids(config.kids);
+                self.set_logging(config.logging);
                 tracing::info!("Preferences dialog hydrated from config");
             }
             PreferencesDialogInput::ReloadConfig => {
                 let sender_clone = sender.clone();
                 relm4::spawn_local(async move {
                     let config = CONFIG_SERVICE.get_config().await;
-                    sender_clone.input(PreferencesDialogInput::HydrateDefaultPlayer(
-                        config.playback.player_backend,
-                    ));
+                    sender_clone.input(PreferencesDialogInput::HydrateFromConfig(Box::new(config)));
                 });
             }
             PreferencesDialogInput::Close => {
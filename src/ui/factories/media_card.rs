@@ -1,8 +1,17 @@
 use crate::db::entities::MediaItemModel;
-use crate::models::MediaItemId;
+use crate::models::{MediaItemId, SourceId};
+use crate::ui::shared::accessibility;
+use crate::ui::shared::keyboard;
 use gtk::prelude::*;
 use relm4::factory::FactoryComponent;
 use relm4::prelude::*;
+use std::time::Duration;
+
+/// How long the pointer has to rest on a card before its preview starts
+const PREVIEW_HOVER_DELAY: Duration = Duration::from_millis(800);
+
+/// How long a preview plays before it stops on its own
+const PREVIEW_DURATION: Duration = Duration::from_secs(15);
 
 #[derive(Debug, Clone)]
 pub struct MediaCardInit {
@@ -11,6 +20,8 @@ pub struct MediaCardInit {
     pub watched: bool,
     pub progress_percent: f64,
     pub show_media_type_icon: bool, // For mixed libraries
+    pub unwatched_count: u32,       // Unwatched episodes, for shows
+    pub favorite: bool,
 }
 
 #[tracker::track]
@@ -18,6 +29,8 @@ pub struct MediaCardInit {
 pub struct MediaCard {
     item: MediaItemModel,
     #[do_not_track]
+    index: DynamicIndex,
+    #[do_not_track]
     item_id: MediaItemId,
     #[do_not_track]
     parent_show_id: Option<MediaItemId>, // For episodes, ID of parent show
@@ -29,10 +42,16 @@ pub struct MediaCard {
     image_loaded: bool,
     load_failed: bool,
     watched: bool,
+    unwatched_count: u32,
+    favorite: bool,
     #[do_not_track]
     texture: Option<gtk::gdk::Texture>,
     #[do_not_track]
     popover: Option<gtk::PopoverMenu>,
+    #[do_not_track]
+    preview_media: Option<gtk::MediaFile>,
+    #[do_not_track]
+    preview_generation: u32, // Bumped on every hover change to drop stale preview timers
 }
 
 #[derive(Debug, Clone)]
@@ -43,6 +62,12 @@ pub enum MediaCardInput {
     ImageLoaded(gtk::gdk::Texture),
     ImageLoadFailed,
     Play,
+    SetUnwatchedCount(u32),
+    PreviewDelayElapsed(u32),
+    PlayPreview(String),
+    StopPreview(u32),
+    ToggleFavorite,
+    SetFavorite(bool),
 }
 
 #[derive(Debug, Clone)]
@@ -52,6 +77,19 @@ pub enum MediaCardOutput {
     GoToShow(MediaItemId), // Navigate to parent show (for episodes)
     MarkWatched(MediaItemId),
     MarkUnwatched(MediaItemId),
+    Hide(MediaItemId), // Not interested, leave it out of home rows
+    HideCollection {
+        source_id: SourceId,
+        collection: String,
+    },
+    PreviewRequested {
+        id: MediaItemId,
+        index: DynamicIndex,
+    },
+    SetFavorite {
+        id: MediaItemId,
+        favorite: bool,
+    },
 }
 
 #[allow(unused_assignments)]
@@ -88,6 +126,16 @@ impl FactoryComponent for MediaCard {
                     add_css_class: if self.image_loaded { "poster-fade-in" } else { "poster-skeleton" },
                 },
 
+                // Muted hover preview, shown over the poster once it starts playing
+                #[name(preview)]
+                add_overlay = &gtk::Picture {
+                    set_content_fit: gtk::ContentFit::Cover,
+                    set_can_shrink: true,
+                    set_can_target: false,
+                    set_visible: false,
+                    add_css_class: "rounded-poster",
+                },
+
 
                 // Info gradient at bottom
                 add_overlay = &gtk::Box {
@@ -105,6 +153,14 @@ impl FactoryComponent for MediaCard {
                         set_margin_top: 4,
                         add_css_class: "media-card-info",
 
+                        // Quality badges, filled in once the widgets exist
+                        #[name(quality_badges)]
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Horizontal,
+                            set_spacing: 4,
+                            set_visible: false,
+                        },
+
                         gtk::Label {
                             set_label: &self.item.title,
                             set_xalign: 0.0,
@@ -150,15 +206,33 @@ impl FactoryComponent for MediaCard {
                     }
                 },
 
+                // Favorite toggle (top-left), shown on hover or once favorited
+                #[name(favorite_button)]
+                add_overlay = &gtk::Button {
+                    set_halign: gtk::Align::Start,
+                    set_valign: gtk::Align::Start,
+                    set_margin_top: 8,
+                    set_margin_start: if self.show_media_type_icon { 44 } else { 8 },
+                    set_icon_name: "emblem-favorite-symbolic",
+                    add_css_class: "osd",
+                    add_css_class: "circular",
+                    add_css_class: "favorite-toggle",
+
+                    connect_clicked[sender] => move |_| {
+                        sender.input(MediaCardInput::ToggleFavorite);
+                    }
+                },
+
                 // Unwatched indicator (top-right glowing dot)
+                #[name(unwatched_dot)]
                 add_overlay = &gtk::Box {
                     set_orientation: gtk::Orientation::Horizontal,
                     set_halign: gtk::Align::End,
                     set_valign: gtk::Align::Start,
                     set_margin_top: 4,
                     set_margin_end: 4,
-                    #[track(self.changed(MediaCard::item()))]
-                    set_visible: !self.is_watched(),
+                    #[track(self.changed(MediaCard::item()) || self.changed(MediaCard::unwatched_count()))]
+                    set_visible: !self.is_watched() && self.unwatched_count == 0,
                     add_css_class: "unwatched-indicator",
 
                     gtk::Box {
@@ -168,6 +242,20 @@ impl FactoryComponent for MediaCard {
                     }
                 },
 
+                // Unwatched episode count badge (top-right, shows only)
+                #[name(unwatched_badge)]
+                add_overlay = &gtk::Label {
+                    set_halign: gtk::Align::End,
+                    set_valign: gtk::Align::Start,
+                    set_margin_top: 6,
+                    set_margin_end: 6,
+                    add_css_class: "unwatched-count-badge",
+                    #[track(self.changed(MediaCard::unwatched_count()))]
+                    set_label: &self.unwatched_count.to_string(),
+                    #[track(self.changed(MediaCard::unwatched_count()))]
+                    set_visible: self.unwatched_count > 0,
+                },
+
                 // Progress bar overlay (bottom)
                 add_overlay = &gtk::ProgressBar {
                     set_valign: gtk::Align::End,
@@ -189,7 +277,7 @@ impl FactoryComponent for MediaCard {
         &mut self,
         _index: &DynamicIndex,
         root: Self::Root,
-        _returned_widget: &<Self::ParentWidget as relm4::factory::FactoryView>::ReturnedWidget,
+        returned_widget: &<Self::ParentWidget as relm4::factory::FactoryView>::ReturnedWidget,
         sender: FactorySender<Self>,
     ) -> Self::Widgets {
         // Set up context menu BEFORE calling view_output! (which consumes root)
@@ -212,6 +300,19 @@ impl FactoryComponent for MediaCard {
             menu.append(Some("Mark as Watched"), Some("card.mark_watched"));
         }
 
+        menu.append(Some("Hide from Home"), Some("card.hide"));
+        for collection in self.item.get_collections() {
+            let menu_item = gtk::gio::MenuItem::new(
+                Some(&format!("Hide {} Collection from Home", collection)),
+                None,
+            );
+            menu_item.set_action_and_target_value(
+                Some("card.hide_collection"),
+                Some(&collection.to_variant()),
+            );
+            menu.append_item(&menu_item);
+        }
+
         // Create popover menu
         let popover = gtk::PopoverMenu::from_model(Some(&menu));
         popover.set_parent(&root);
@@ -269,6 +370,34 @@ impl FactoryComponent for MediaCard {
         });
         action_group.add_action(&mark_unwatched_action);
 
+        // Hide action
+        let hide_action = gtk::gio::SimpleAction::new("hide", None);
+        let sender_clone = sender.clone();
+        let item_id_clone = self.item_id.clone();
+        hide_action.connect_activate(move |_, _| {
+            sender_clone
+                .output(MediaCardOutput::Hide(item_id_clone.clone()))
+                .unwrap();
+        });
+        action_group.add_action(&hide_action);
+
+        // Hide Collection action, the collection name is its target
+        let hide_collection_action =
+            gtk::gio::SimpleAction::new("hide_collection", Some(gtk::glib::VariantTy::STRING));
+        let sender_clone = sender.clone();
+        let source_id = SourceId::new(self.item.source_id.clone());
+        hide_collection_action.connect_activate(move |_, parameter| {
+            if let Some(collection) = parameter.and_then(|p| p.get::<String>()) {
+                sender_clone
+                    .output(MediaCardOutput::HideCollection {
+                        source_id: source_id.clone(),
+                        collection,
+                    })
+                    .unwrap();
+            }
+        });
+        action_group.add_action(&hide_collection_action);
+
         // Insert action group
         root.insert_action_group("card", Some(&action_group));
 
@@ -287,12 +416,54 @@ impl FactoryComponent for MediaCard {
         });
         root.add_controller(gesture);
 
+        // Arrow keys focus the flow box child rather than the button, Enter
+        // on it opens the card like a click
+        let button = root.clone();
+        returned_widget.connect_activate(move |_| button.emit_clicked());
+        keyboard::connect_context_menu_key(returned_widget, &popover);
+
+        // Let playable items be dragged onto the queue
+        if self.is_playable() {
+            let drag_source = gtk::DragSource::new();
+            drag_source.set_actions(gtk::gdk::DragAction::COPY);
+            drag_source.set_content(Some(&gtk::gdk::ContentProvider::for_value(
+                &self.item_id.to_string().to_value(),
+            )));
+            drag_source.connect_drag_begin(|source, _| {
+                if let Some(widget) = source.widget() {
+                    let icon = gtk::WidgetPaintable::new(Some(&widget));
+                    source.set_icon(Some(&icon), 0, 0);
+                }
+            });
+            root.add_controller(drag_source);
+        }
+
+        // Track hovering for previews
+        let motion = gtk::EventControllerMotion::new();
+        let sender_clone = sender.clone();
+        motion.connect_enter(move |_, _, _| {
+            sender_clone.input(MediaCardInput::SetHover(true));
+        });
+        let sender_clone = sender.clone();
+        motion.connect_leave(move |_| {
+            sender_clone.input(MediaCardInput::SetHover(false));
+        });
+        root.add_controller(motion);
+
         // Now call view_output! which will consume root
         let widgets = view_output!();
+        for badge in self.item.get_quality_badges() {
+            let label = gtk::Label::new(Some(badge));
+            label.add_css_class("quality-badge");
+            widgets.quality_badges.append(&label);
+            widgets.quality_badges.set_visible(true);
+        }
+        self.update_favorite_widgets(&widgets);
+        self.update_accessible_label(&widgets);
         widgets
     }
 
-    fn init_model(init: Self::Init, _index: &DynamicIndex, _sender: FactorySender<Self>) -> Self {
+    fn init_model(init: Self::Init, index: &DynamicIndex, _sender: FactorySender<Self>) -> Self {
         let item_id = MediaItemId::new(init.item.id.clone());
 
         // For episodes, get parent show ID for "Go to Show" context menu action
@@ -311,6 +482,7 @@ impl FactoryComponent for MediaCard {
 
         Self {
             item: init.item,
+            index: index.clone(),
             item_id,
             parent_show_id,
             show_progress: init.show_progress,
@@ -321,8 +493,12 @@ impl FactoryComponent for MediaCard {
             image_loaded,
             load_failed: false,
             watched: init.watched,
+            unwatched_count: init.unwatched_count,
+            favorite: init.favorite,
             texture: None,
             popover: None,
+            preview_media: None,
+            preview_generation: 0,
             tracker: 0,
         }
     }
@@ -337,13 +513,71 @@ impl FactoryComponent for MediaCard {
 
         match msg {
             MediaCardInput::SetHover(hover) => {
+                if hover == self.hover {
+                    return;
+                }
                 self.set_hover(hover);
+                self.update_favorite_widgets(widgets);
+                self.preview_generation = self.preview_generation.wrapping_add(1);
+                self.stop_preview(widgets);
+
+                // Autoplaying video is motion the user asked to avoid
+                if hover && self.is_playable() && !accessibility::reduced_motion() {
+                    let generation = self.preview_generation;
+                    let sender = sender.clone();
+                    gtk::glib::timeout_add_local_once(PREVIEW_HOVER_DELAY, move || {
+                        sender.input(MediaCardInput::PreviewDelayElapsed(generation));
+                    });
+                }
+            }
+            MediaCardInput::PreviewDelayElapsed(generation) => {
+                if self.hover && generation == self.preview_generation {
+                    sender
+                        .output(MediaCardOutput::PreviewRequested {
+                            id: self.item_id.clone(),
+                            index: self.index.clone(),
+                        })
+                        .unwrap();
+                }
+            }
+            MediaCardInput::PlayPreview(url) => {
+                if !self.hover || self.preview_media.is_some() {
+                    return;
+                }
+
+                let media = gtk::MediaFile::for_file(&gtk::gio::File::for_uri(&url));
+                media.set_muted(true);
+                media.set_loop(false);
+
+                // Keep showing the poster until the first frame is ready
+                let picture = widgets.preview.clone();
+                media.connect_prepared_notify(move |media| {
+                    if media.is_prepared() {
+                        picture.set_visible(true);
+                    }
+                });
+
+                widgets.preview.set_paintable(Some(&media));
+                media.play();
+                self.preview_media = Some(media);
+
+                let generation = self.preview_generation;
+                let sender = sender.clone();
+                gtk::glib::timeout_add_local_once(PREVIEW_DURATION, move || {
+                    sender.input(MediaCardInput::StopPreview(generation));
+                });
+            }
+            MediaCardInput::StopPreview(generation) => {
+                if generation == self.preview_generation {
+                    self.stop_preview(widgets);
+                }
             }
             MediaCardInput::SetSelected(selected) => {
                 self.set_selected(selected);
             }
             MediaCardInput::UpdateProgress(progress) => {
                 self.set_progress_percent(progress);
+                self.update_accessible_label(widgets);
             }
             MediaCardInput::ImageLoaded(texture) => {
                 // Set the texture on the picture widget
@@ -361,10 +595,34 @@ impl FactoryComponent for MediaCard {
                     .output(MediaCardOutput::Play(self.item_id.clone()))
                     .unwrap();
             }
+            MediaCardInput::SetUnwatchedCount(count) => {
+                self.set_unwatched_count(count);
+                widgets.unwatched_badge.set_label(&count.to_string());
+                widgets.unwatched_badge.set_visible(count > 0);
+                widgets
+                    .unwatched_dot
+                    .set_visible(!self.is_watched() && count == 0);
+                self.update_accessible_label(widgets);
+            }
+            MediaCardInput::ToggleFavorite => {
+                sender
+                    .output(MediaCardOutput::SetFavorite {
+                        id: self.item_id.clone(),
+                        favorite: !self.favorite,
+                    })
+                    .unwrap();
+            }
+            MediaCardInput::SetFavorite(favorite) => {
+                self.set_favorite(favorite);
+                self.update_favorite_widgets(widgets);
+                self.update_accessible_label(widgets);
+            }
         }
     }
 
-    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
+    fn shutdown(&mut self, widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
+        self.stop_preview(widgets);
+
         // Properly unparent the popover before the button is finalized
         // This prevents GTK warnings about finalizing buttons with children
         if let Some(popover) = self.popover.take() {
@@ -374,6 +632,62 @@ impl FactoryComponent for MediaCard {
 }
 
 impl MediaCard {
+    pub fn item(&self) -> &MediaItemModel {
+        &self.item
+    }
+
+    /// Movies and episodes can be previewed and queued, shows and others can't
+    fn is_playable(&self) -> bool {
+        matches!(self.item.media_type.as_str(), "movie" | "episode")
+    }
+
+    fn update_favorite_widgets(&self, widgets: &MediaCardWidgets) {
+        let button = &widgets.favorite_button;
+        button.set_visible(self.hover || self.favorite);
+        if self.favorite {
+            button.add_css_class("favorited");
+            button.set_tooltip_text(Some("Remove from Favorites"));
+        } else {
+            button.remove_css_class("favorited");
+            button.set_tooltip_text(Some("Add to Favorites"));
+        }
+        button.update_property(&[gtk::accessible::Property::Label("Favorite")]);
+        button.update_state(&[accessibility::pressed(self.favorite)]);
+    }
+
+    /// Name the card after its title and watch state, the poster itself says nothing
+    fn update_accessible_label(&self, widgets: &MediaCardWidgets) {
+        let mut parts = vec![self.item.title.clone()];
+        let subtitle = self.format_subtitle();
+        if !subtitle.is_empty() {
+            parts.push(subtitle);
+        }
+        parts.extend(self.item.get_quality_badges().into_iter().map(String::from));
+        if self.unwatched_count > 0 {
+            parts.push(format!("{} unwatched", self.unwatched_count));
+        } else if self.is_partially_watched() {
+            parts.push(format!("{:.0}% watched", self.progress_percent * 100.0));
+        } else if self.is_watched() {
+            parts.push("Watched".to_string());
+        }
+        if self.favorite {
+            parts.push("Favorite".to_string());
+        }
+
+        widgets
+            .root
+            .update_property(&[gtk::accessible::Property::Label(&parts.join(", "))]);
+    }
+
+    fn stop_preview(&mut self, widgets: &MediaCardWidgets) {
+        if let Some(media) = self.preview_media.take() {
+            media.pause();
+            media.clear();
+        }
+        widgets.preview.set_visible(false);
+        widgets.preview.set_paintable(None::<&gtk::gdk::Paintable>);
+    }
+
     fn format_subtitle(&self) -> String {
         match self.item.media_type.as_str() {
             "movie" => {
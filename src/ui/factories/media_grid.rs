@@ -0,0 +1,198 @@
+use gtk::prelude::*;
+use relm4::factory::FactoryVecDeque;
+use relm4::prelude::*;
+
+use super::media_card::{MediaCard, MediaCardInit, MediaCardInput, MediaCardOutput};
+use crate::ui::shared::keyboard;
+
+/// A grid of media cards that can be split into groups under headings, such
+/// as the days items were added on. Cards are addressed by their index
+/// across all groups.
+pub struct MediaGrid<Msg: Send + 'static> {
+    container: gtk::Box,
+    groups: Vec<GridGroup>,
+    sender: relm4::Sender<Msg>,
+    forward: fn(MediaCardOutput) -> Msg,
+}
+
+struct GridGroup {
+    header: Option<String>,
+    start: usize,
+    factory: FactoryVecDeque<MediaCard>,
+}
+
+impl<Msg: Send + 'static> MediaGrid<Msg> {
+    /// Create an empty grid whose card outputs are forwarded to `sender`
+    pub fn new(
+        container: gtk::Box,
+        sender: &relm4::Sender<Msg>,
+        forward: fn(MediaCardOutput) -> Msg,
+    ) -> Self {
+        Self {
+            container,
+            groups: Vec::new(),
+            sender: sender.clone(),
+            forward,
+        }
+    }
+
+    pub fn widget(&self) -> &gtk::Box {
+        &self.container
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups
+            .last()
+            .map_or(0, |group| group.start + group.factory.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove every card and heading
+    pub fn clear(&mut self) {
+        self.groups.clear();
+        while let Some(child) = self.container.first_child() {
+            self.container.remove(&child);
+        }
+    }
+
+    /// Add cards at the end, each under the given heading. A new group is
+    /// started whenever the heading changes.
+    pub fn extend(&mut self, cards: impl IntoIterator<Item = (Option<String>, MediaCardInit)>) {
+        let mut cards = cards.into_iter().peekable();
+        while let Some((header, card)) = cards.next() {
+            if self
+                .groups
+                .last()
+                .is_none_or(|group| group.header != header)
+            {
+                self.push_group(header.clone());
+            }
+
+            let group = self.groups.last_mut().expect("a group was just added");
+            let mut guard = group.factory.guard();
+            guard.push_back(card);
+            while let Some((_, card)) = cards.next_if(|(next, _)| *next == header) {
+                guard.push_back(card);
+            }
+        }
+    }
+
+    /// Send a message to the card at `index`
+    pub fn send(&self, index: usize, msg: MediaCardInput) {
+        if let Some((group, local)) = self.locate(index) {
+            group.factory.send(local, msg);
+        }
+    }
+
+    /// The flow box child holding the card at `index`
+    pub fn child_at_index(&self, index: usize) -> Option<gtk::FlowBoxChild> {
+        let (group, local) = self.locate(index)?;
+        group.factory.widget().child_at_index(local as i32)
+    }
+
+    /// Move keyboard focus to the card at `index`, unless focus is on a
+    /// card already
+    pub fn focus(&self, index: usize) {
+        if self
+            .container
+            .root()
+            .and_then(|root| root.focus())
+            .is_some_and(|focus| focus.is_ancestor(&self.container))
+        {
+            return;
+        }
+        if let Some(child) = self.child_at_index(index.min(self.len().saturating_sub(1))) {
+            child.grab_focus();
+        }
+    }
+
+    /// Cards on each row of the grid
+    pub fn columns(&self) -> usize {
+        self.groups.first().map_or(4, |group| {
+            group.factory.widget().min_children_per_line() as usize
+        })
+    }
+
+    /// Index of the first card on the row at `y` in the grid's parent, for
+    /// rows of `row_height`
+    pub fn index_at(&self, y: f64, row_height: f64) -> usize {
+        let columns = self.columns();
+        let parent = self.container.parent();
+        let mut index = 0;
+        for group in &self.groups {
+            let flow_box = group.factory.widget();
+            let top = parent
+                .as_ref()
+                .and_then(|parent| flow_box.compute_bounds(parent))
+                .filter(|bounds| bounds.height() > 0.0)
+                .map(|bounds| bounds.y() as f64);
+            let offset = match top {
+                Some(top) if y < top => break,
+                Some(top) => ((y - top) / row_height).floor() as usize * columns,
+                // Groups not laid out yet are taken to follow on directly
+                None => {
+                    let flat = (y / row_height).floor() as usize * columns;
+                    if flat < group.start {
+                        break;
+                    }
+                    flat - group.start
+                }
+            };
+            index = group.start + offset.min(group.factory.len());
+        }
+        index
+    }
+
+    fn locate(&self, index: usize) -> Option<(&GridGroup, usize)> {
+        let group = self
+            .groups
+            .iter()
+            .rev()
+            .find(|group| group.start <= index)?;
+        let local = index - group.start;
+        (local < group.factory.len()).then_some((group, local))
+    }
+
+    fn push_group(&mut self, header: Option<String>) {
+        if let Some(header) = &header {
+            let label = gtk::Label::builder()
+                .label(header)
+                .halign(gtk::Align::Start)
+                .margin_start(16)
+                .margin_end(16)
+                .margin_top(if self.groups.is_empty() { 0 } else { 24 })
+                .build();
+            label.add_css_class("title-3");
+            self.container.append(&label);
+        }
+
+        let flow_box = gtk::FlowBox::builder()
+            .column_spacing(12) // Tighter grid spacing
+            .row_spacing(16) // Reduced vertical spacing
+            .homogeneous(true)
+            .min_children_per_line(4) // More items per row with smaller sizes
+            .max_children_per_line(12) // Allow more on wide screens
+            .selection_mode(gtk::SelectionMode::None)
+            .margin_top(if header.is_some() { 12 } else { 0 })
+            .margin_start(16)
+            .margin_end(16)
+            .valign(gtk::Align::Start)
+            .build();
+        // Up and Down carry on across headings
+        keyboard::connect_grid_edges(&flow_box);
+        self.container.append(&flow_box);
+
+        let factory = FactoryVecDeque::<MediaCard>::builder()
+            .launch(flow_box)
+            .forward(&self.sender, self.forward);
+
+        self.groups.push(GridGroup {
+            header,
+            start: self.len(),
+            factory,
+        });
+    }
+}
@@ -1,3 +1,4 @@
 pub mod media_card;
+pub mod media_grid;
 pub mod section_row;
 pub mod source_item;
@@ -5,7 +5,7 @@ use relm4::prelude::*;
 
 use super::media_card::{MediaCard, MediaCardInit, MediaCardOutput};
 use crate::db::entities::MediaItemModel;
-use crate::models::MediaItemId;
+use crate::models::{MediaItemId, SourceId};
 
 #[derive(Debug)]
 pub struct SectionRow {
@@ -30,6 +30,19 @@ pub enum SectionRowOutput {
     MediaPlayRequested(MediaItemId),
     MarkWatched(MediaItemId),
     MarkUnwatched(MediaItemId),
+    Hide(MediaItemId),
+    HideCollection {
+        source_id: SourceId,
+        collection: String,
+    },
+    PreviewRequested {
+        id: MediaItemId,
+        index: DynamicIndex,
+    },
+    SetFavorite {
+        id: MediaItemId,
+        favorite: bool,
+    },
     LoadMore,
 }
 
@@ -121,6 +134,20 @@ impl FactoryComponent for SectionRow {
                 MediaCardOutput::GoToShow(id) => SectionRowOutput::MediaSelected(id),
                 MediaCardOutput::MarkWatched(id) => SectionRowOutput::MarkWatched(id),
                 MediaCardOutput::MarkUnwatched(id) => SectionRowOutput::MarkUnwatched(id),
+                MediaCardOutput::Hide(id) => SectionRowOutput::Hide(id),
+                MediaCardOutput::HideCollection {
+                    source_id,
+                    collection,
+                } => SectionRowOutput::HideCollection {
+                    source_id,
+                    collection,
+                },
+                MediaCardOutput::PreviewRequested { id, index } => {
+                    SectionRowOutput::PreviewRequested { id, index }
+                }
+                MediaCardOutput::SetFavorite { id, favorite } => {
+                    SectionRowOutput::SetFavorite { id, favorite }
+                }
             });
 
         Self {
@@ -178,6 +205,8 @@ impl FactoryComponent for SectionRow {
                         watched: false,
                         progress_percent: 0.0,
                         show_media_type_icon: false,
+                        unwatched_count: 0,
+                        favorite: false,
                     });
                 }
             }
@@ -190,6 +219,8 @@ impl FactoryComponent for SectionRow {
                         watched: false,
                         progress_percent: 0.0,
                         show_media_type_icon: false,
+                        unwatched_count: 0,
+                        favorite: false,
                     });
                 }
             }
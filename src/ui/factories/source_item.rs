@@ -84,6 +84,7 @@ impl FactoryComponent for SourceItem {
                         set_icon_name: Some(match &self.source.source_type {
                             crate::models::SourceType::PlexServer { .. } => "network-server-symbolic",
                             crate::models::SourceType::JellyfinServer => "network-server-symbolic",
+                            crate::models::SourceType::EmbyServer => "network-server-symbolic",
                             crate::models::SourceType::NetworkShare { .. } => "folder-remote-symbolic",
                             crate::models::SourceType::LocalFolder { .. } => "folder-symbolic",
                         }),
@@ -0,0 +1,166 @@
+//! Deciding where a back request returns to
+//!
+//! Back requests reach the main window from the header, from keyboard
+//! shortcuts and from the player, which can send several of them for one
+//! failure: the error overlay, the auto-play countdown and the user pressing
+//! Escape. By the time a later one is handled the player may already be off
+//! the stack, or still on it while its pop animation runs. Deciding from the
+//! titles of the pages on the stack, instead of blindly popping, keeps a
+//! stray request from popping past the page the player was opened from or
+//! emptying the stack.
+
+use adw::prelude::*;
+use libadwaita as adw;
+
+/// Title of the player page on the navigation stack
+pub const PLAYER_TITLE: &str = "Player";
+
+/// Who asked to go back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackRequest {
+    /// Back from whatever page is on top
+    Page,
+    /// Leaving the player, which only makes sense while it is on the stack
+    Player,
+}
+
+/// Where a back request returns to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackTarget {
+    /// Nothing to go back to, the request is dropped
+    Stay,
+    /// Pop everything above the page at this index of the stack
+    PopTo(usize),
+}
+
+/// Where `request` returns to given the titles of the stack, root first
+pub fn back_target<S: AsRef<str>>(titles: &[S], request: BackRequest) -> BackTarget {
+    match request {
+        BackRequest::Page if titles.len() > 1 => BackTarget::PopTo(titles.len() - 2),
+        BackRequest::Page => BackTarget::Stay,
+        BackRequest::Player => match titles
+            .iter()
+            .rposition(|title| title.as_ref() == PLAYER_TITLE)
+        {
+            Some(index) if index > 0 => BackTarget::PopTo(index - 1),
+            _ => BackTarget::Stay,
+        },
+    }
+}
+
+/// Pages on the stack of `view`, root first
+pub fn pages(view: &adw::NavigationView) -> Vec<adw::NavigationPage> {
+    let stack = view.navigation_stack();
+    (0..stack.n_items())
+        .filter_map(|i| stack.item(i).and_downcast::<adw::NavigationPage>())
+        .collect()
+}
+
+/// A step back on the stack of a navigation view
+#[derive(Debug)]
+pub struct BackStep {
+    /// Page the step returns to
+    pub page: adw::NavigationPage,
+    /// Whether the player is among the pages the step pops
+    pub leaves_player: bool,
+}
+
+/// The step `request` takes back on the stack of `view`, None when it is
+/// dropped
+pub fn back_step(view: &adw::NavigationView, request: BackRequest) -> Option<BackStep> {
+    let pages = pages(view);
+    let titles: Vec<String> = pages.iter().map(|page| page.title().to_string()).collect();
+    let BackTarget::PopTo(index) = back_target(&titles, request) else {
+        tracing::debug!("Ignoring back request {:?} on stack {:?}", request, titles);
+        return None;
+    };
+
+    Some(BackStep {
+        page: pages[index].clone(),
+        leaves_player: titles[index + 1..]
+            .iter()
+            .any(|title| title == PLAYER_TITLE),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use relm4::gtk;
+
+    /// A navigation view holding pages with these titles, root first
+    fn view(titles: &[&str]) -> adw::NavigationView {
+        adw::init().unwrap();
+        let view = adw::NavigationView::new();
+        view.set_animate_transitions(false);
+        for title in titles {
+            push(&view, title);
+        }
+        view
+    }
+
+    fn push(view: &adw::NavigationView, title: &str) {
+        view.push(&adw::NavigationPage::new(&gtk::Box::default(), title));
+    }
+
+    /// Go back like the main window does, returning whether it left the
+    /// player
+    fn back(view: &adw::NavigationView, request: BackRequest) -> bool {
+        let Some(step) = back_step(view, request) else {
+            return false;
+        };
+        view.pop_to_page(&step.page);
+        step.leaves_player
+    }
+
+    fn titles(view: &adw::NavigationView) -> Vec<String> {
+        pages(view)
+            .iter()
+            .map(|page| page.title().to_string())
+            .collect()
+    }
+
+    #[gtk::test]
+    fn test_page_back_pops_one_page() {
+        let view = view(&["Home", "Library", "Movie"]);
+        assert!(!back(&view, BackRequest::Page));
+        assert_eq!(titles(&view), ["Home", "Library"]);
+        assert_eq!(view.visible_page().unwrap().title(), "Library");
+    }
+
+    #[gtk::test]
+    fn test_root_page_is_never_popped() {
+        let view = view(&["Home"]);
+        back(&view, BackRequest::Page);
+        back(&view, BackRequest::Player);
+        assert_eq!(titles(&view), ["Home"]);
+    }
+
+    #[gtk::test]
+    fn test_leaving_the_player_returns_to_the_page_it_was_opened_from() {
+        let view = view(&["Home", "Show"]);
+        push(&view, PLAYER_TITLE);
+        assert!(back(&view, BackRequest::Player));
+        assert_eq!(titles(&view), ["Home", "Show"]);
+    }
+
+    #[gtk::test]
+    fn test_repeated_back_after_a_failed_load_stops_at_the_previous_page() {
+        // The error overlay, the auto-play countdown and Escape all ask to
+        // leave the player of one failed load
+        let view = view(&["Home", "Library", "Movie"]);
+        push(&view, PLAYER_TITLE);
+        assert!(back(&view, BackRequest::Player));
+        assert!(!back(&view, BackRequest::Player));
+        assert!(!back(&view, BackRequest::Player));
+        assert_eq!(titles(&view), ["Home", "Library", "Movie"]);
+    }
+
+    #[gtk::test]
+    fn test_player_back_with_pages_pushed_above_it() {
+        // A page was pushed while the player's pop was still animating
+        let view = view(&["Home", "Movie", PLAYER_TITLE, "Sources"]);
+        assert!(back(&view, BackRequest::Player));
+        assert_eq!(titles(&view), ["Home", "Movie"]);
+    }
+}
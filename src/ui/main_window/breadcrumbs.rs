@@ -0,0 +1,161 @@
+//! Breadcrumbs under the header: where the open page sits, from its source
+//! through its library down to the show and season, each a way back up
+
+use adw::prelude::*;
+use anyhow::Result;
+use libadwaita as adw;
+use relm4::gtk;
+use relm4::prelude::AsyncComponentSender;
+
+use super::session::SavedPage;
+use super::{MainWindow, MainWindowInput};
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{
+    LibraryRepositoryImpl, MediaRepositoryImpl, Repository, SourceRepositoryImpl,
+};
+use crate::models::LibraryId;
+
+/// Where clicking a crumb goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrumbTarget {
+    Sources,
+    Library(LibraryId),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crumb {
+    pub label: String,
+    pub target: Option<CrumbTarget>,
+}
+
+/// Crumbs leading to the page of `route`, the page itself last. Pages
+/// outside any library, such as home and the player, have none.
+pub async fn crumbs_for(db: &DatabaseConnection, route: &SavedPage) -> Result<Vec<Crumb>> {
+    let (library_id, item) = match route {
+        SavedPage::Library(library_id) => (library_id.to_string(), None),
+        SavedPage::Movie(id) | SavedPage::Show(id) => {
+            let Some(item) = MediaRepositoryImpl::new(db.clone())
+                .find_by_id(id.as_str())
+                .await?
+            else {
+                return Ok(Vec::new());
+            };
+            (item.library_id.clone(), Some(item))
+        }
+        SavedPage::Player => return Ok(Vec::new()),
+    };
+
+    let Some(library) = LibraryRepositoryImpl::new(db.clone())
+        .find_by_id(&library_id)
+        .await?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut crumbs = Vec::new();
+    if let Some(source) = SourceRepositoryImpl::new(db.clone())
+        .find_by_id(&library.source_id)
+        .await?
+    {
+        crumbs.push(Crumb {
+            label: source.name,
+            target: Some(CrumbTarget::Sources),
+        });
+    }
+    crumbs.push(Crumb {
+        label: library.title,
+        target: item
+            .is_some()
+            .then(|| CrumbTarget::Library(LibraryId::new(library.id.clone()))),
+    });
+    if let Some(item) = item {
+        crumbs.push(Crumb {
+            label: item.title,
+            target: None,
+        });
+    }
+    Ok(crumbs)
+}
+
+/// Look up the crumbs of the visible page if it changed, showing them once
+/// they are known
+pub fn update(window: &mut MainWindow, sender: &AsyncComponentSender<MainWindow>) {
+    let route = window
+        .navigation_view
+        .visible_page()
+        .and_then(|page| window.page_routes.route_of(&page));
+    if route == window.breadcrumb_route {
+        return;
+    }
+
+    window.breadcrumb_route = route.clone();
+    window.breadcrumbs.clear();
+    render(window, sender);
+
+    if let Some(route) = route {
+        let db = window.db.clone();
+        let sender = sender.clone();
+        relm4::spawn_local(async move {
+            match crumbs_for(&db, &route).await {
+                Ok(crumbs) => sender.input(MainWindowInput::BreadcrumbsLoaded { route, crumbs }),
+                Err(e) => tracing::warn!("Failed to look up breadcrumbs: {}", e),
+            }
+        });
+    }
+}
+
+/// Show the crumbs of the visible page, followed by the season picked on a
+/// show page
+pub fn render(window: &MainWindow, sender: &AsyncComponentSender<MainWindow>) {
+    let bar = &window.breadcrumb_bar;
+    while let Some(child) = bar.first_child() {
+        bar.remove(&child);
+    }
+
+    let mut crumbs = window.breadcrumbs.clone();
+    if let Some(SavedPage::Show(show_id)) = &window.breadcrumb_route
+        && let Some((season_show, season)) = &window.breadcrumb_season
+        && season_show == show_id
+        && !crumbs.is_empty()
+    {
+        crumbs.push(Crumb {
+            label: format!("Season {}", season),
+            target: None,
+        });
+    }
+
+    for (index, crumb) in crumbs.iter().enumerate() {
+        if index > 0 {
+            let separator = gtk::Label::new(Some("▸"));
+            separator.add_css_class("dim-label");
+            bar.append(&separator);
+        }
+
+        let last = index + 1 == crumbs.len();
+        match crumb.target.clone().filter(|_| !last) {
+            Some(target) => {
+                let button = gtk::Button::builder()
+                    .label(&crumb.label)
+                    .css_classes(["flat", "breadcrumb"])
+                    .build();
+                let sender = sender.input_sender().clone();
+                button.connect_clicked(move |_| {
+                    sender.emit(match &target {
+                        CrumbTarget::Sources => MainWindowInput::Navigate("sources".to_string()),
+                        CrumbTarget::Library(id) => MainWindowInput::NavigateToLibrary(id.clone()),
+                    });
+                });
+                bar.append(&button);
+            }
+            None => {
+                let label = gtk::Label::new(Some(&crumb.label));
+                label.add_css_class("breadcrumb");
+                if last {
+                    label.add_css_class("heading");
+                }
+                bar.append(&label);
+            }
+        }
+    }
+    bar.set_visible(!crumbs.is_empty());
+}
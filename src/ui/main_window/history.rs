@@ -0,0 +1,113 @@
+//! Going forward again to pages left by going back
+//!
+//! The navigation view only keeps the pages on its stack, so pages popped by
+//! the back button, Alt+Left, the mouse back button or the sidebar are gone.
+//! Their routes are kept here instead, by comparing the stack every time it
+//! changes with how it was before, and reopened by going forward. Opening any
+//! other page drops them, as in a browser. The player is never reopened this
+//! way, going forward into it would start playback again.
+
+use super::session::SavedPage;
+
+/// A page on the navigation stack, with its route if it can be reopened
+pub type StackEntry = Option<SavedPage>;
+
+#[derive(Debug, Default)]
+pub struct History {
+    stack: Vec<StackEntry>,
+    /// Routes to go forward to, the next one last
+    forward: Vec<SavedPage>,
+}
+
+impl History {
+    /// Follow the navigation stack to `stack`, root first
+    pub fn update(&mut self, stack: Vec<StackEntry>) {
+        let common = self
+            .stack
+            .iter()
+            .zip(&stack)
+            .take_while(|(before, now)| before == now)
+            .count();
+
+        // The deepest page left goes furthest from the next step forward
+        for route in self.stack[common..].iter().rev().flatten() {
+            if *route != SavedPage::Player {
+                self.forward.push(route.clone());
+            }
+        }
+
+        for entry in &stack[common..] {
+            match entry {
+                Some(SavedPage::Player) => {}
+                Some(route) if self.forward.last() == Some(route) => {
+                    self.forward.pop();
+                }
+                _ => self.forward.clear(),
+            }
+        }
+
+        self.stack = stack;
+    }
+
+    /// The page going forward reopens
+    pub fn next(&self) -> Option<&SavedPage> {
+        self.forward.last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{LibraryId, MediaItemId};
+
+    fn library() -> StackEntry {
+        Some(SavedPage::Library(LibraryId::new("movies")))
+    }
+
+    fn movie(id: &str) -> StackEntry {
+        Some(SavedPage::Movie(MediaItemId::new(id)))
+    }
+
+    #[test]
+    fn test_back_then_forward_reopens_the_page() {
+        let mut history = History::default();
+        history.update(vec![None, library(), movie("a")]);
+        history.update(vec![None, library()]);
+        assert_eq!(history.next(), movie("a").as_ref());
+
+        history.update(vec![None, library(), movie("a")]);
+        assert_eq!(history.next(), None);
+    }
+
+    #[test]
+    fn test_going_back_several_pages_goes_forward_one_at_a_time() {
+        let mut history = History::default();
+        history.update(vec![None, library(), movie("a")]);
+        history.update(vec![None]);
+        assert_eq!(history.next(), library().as_ref());
+
+        history.update(vec![None, library()]);
+        assert_eq!(history.next(), movie("a").as_ref());
+    }
+
+    #[test]
+    fn test_opening_another_page_drops_the_forward_pages() {
+        let mut history = History::default();
+        history.update(vec![None, library(), movie("a")]);
+        history.update(vec![None, library()]);
+        history.update(vec![None, library(), movie("b")]);
+        assert_eq!(history.next(), None);
+    }
+
+    #[test]
+    fn test_the_player_is_not_reopened() {
+        let mut history = History::default();
+        history.update(vec![None, library(), movie("a"), Some(SavedPage::Player)]);
+        history.update(vec![None, library()]);
+        assert_eq!(history.next(), movie("a").as_ref());
+
+        // Playing from the library keeps the way forward
+        history.update(vec![None, library(), Some(SavedPage::Player)]);
+        assert_eq!(history.next(), movie("a").as_ref());
+    }
+}
@@ -1,4 +1,10 @@
+mod back_stack;
+mod breadcrumbs;
+mod history;
 mod navigation;
+mod open_url;
+mod screensaver;
+mod session;
 mod workers;
 
 use adw::prelude::*;
@@ -7,20 +13,35 @@ use libadwaita as adw;
 use relm4::gtk;
 use relm4::prelude::*;
 
-use super::dialogs::{AuthDialog, AuthDialogOutput, PreferencesDialog};
+use super::dialogs::{
+    AuthDialog, AuthDialogOutput, DiagnosticsDialog, DuplicatesDialog, ExportDialog,
+    PreferencesDialog,
+};
 use super::pages::{
     HomePage, LibraryPage, MovieDetailsPage, PlayerPage, SearchPage, ShowDetailsPage, SourcesPage,
 };
 use super::sidebar::{Sidebar, SidebarInput, SidebarOutput};
 use crate::db::connection::DatabaseConnection;
 use crate::models::{LibraryId, MediaItemId, PlaylistContext, SourceId};
-use crate::services::core::ConnectionType;
+use crate::services::config_service::CONFIG_SERVICE;
+use crate::services::core::{
+    ConnectionType, ConnectivityChange, ConnectivityTracker, image_quality, metered, power_saver,
+};
+use crate::ui::pages::library::{FilterState, GridPosition, LibraryPageInput};
+use crate::ui::shared::broker::{
+    BROKER, BrokerMessage, ConfigMessage, ConnectivityMessage, PowerMessage, SourceMessage,
+    Subscription, Topic,
+};
 use crate::workers::{
-    ConnectionMonitor, ConnectionMonitorInput, SearchWorker, SearchWorkerInput, SyncWorker,
-    SyncWorkerInput,
+    ConnectionMonitor, ConnectionMonitorInput, DownloadWorkerOutput, ImagePrefetchInput,
+    PlaybackSyncWorkerInput, SearchWorker, SearchWorkerInput, SyncWorker, SyncWorkerInput,
 };
+use screensaver::{Screensaver, ScreensaverInput};
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
 
 #[derive(Debug)]
@@ -33,7 +54,6 @@ pub struct MainWindow {
     home_page: AsyncController<HomePage>,
     connection_monitor: relm4::WorkerController<ConnectionMonitor>,
     sync_worker: relm4::WorkerController<SyncWorker>,
-    #[allow(dead_code)]
     playback_sync_worker: relm4::WorkerController<crate::workers::PlaybackSyncWorker>,
     search_worker: relm4::WorkerController<SearchWorker>,
     #[allow(dead_code)]
@@ -41,6 +61,11 @@ pub struct MainWindow {
     #[allow(dead_code)]
     cache_cleanup_worker:
         relm4::WorkerController<crate::workers::cache_cleanup_worker::CacheCleanupWorker>,
+    #[allow(dead_code)]
+    download_worker: relm4::WorkerController<crate::workers::DownloadWorker>,
+    image_prefetch_worker: relm4::WorkerController<crate::workers::ImagePrefetchWorker>,
+    /// Something plays or loads in the player
+    playback_active: bool,
     library_page: Option<AsyncController<LibraryPage>>,
     movie_details_page: Option<AsyncController<MovieDetailsPage>>,
     show_details_page: Option<AsyncController<ShowDetailsPage>>,
@@ -50,6 +75,9 @@ pub struct MainWindow {
     search_page: Option<AsyncController<SearchPage>>,
     search_nav_page: Option<adw::NavigationPage>,
     preferences_dialog: Option<AsyncController<PreferencesDialog>>,
+    diagnostics_dialog: Option<AsyncController<DiagnosticsDialog>>,
+    duplicates_dialog: Option<AsyncController<DuplicatesDialog>>,
+    export_dialog: Option<AsyncController<ExportDialog>>,
     auth_dialog: AsyncController<AuthDialog>,
     navigation_view: adw::NavigationView,
     // Window chrome management
@@ -61,6 +89,14 @@ pub struct MainWindow {
     split_view: adw::NavigationSplitView,
     content_stack: gtk::Stack,
     back_button: gtk::Button,
+    forward_button: gtk::Button,
+    // Pages to go forward to after going back
+    history: history::History,
+    // Source, library and show of the visible page, and the season picked on a show
+    breadcrumb_bar: gtk::Box,
+    breadcrumb_route: Option<session::SavedPage>,
+    breadcrumbs: Vec<breadcrumbs::Crumb>,
+    breadcrumb_season: Option<(MediaItemId, u32)>,
     content_title: adw::WindowTitle,
     // Header bar dynamic content
     header_start_box: gtk::Box,
@@ -71,12 +107,26 @@ pub struct MainWindow {
     was_fullscreen: bool,
     // Current navigation state
     current_library_id: Option<LibraryId>,
-    // Track the page we were on before entering the player
-    previous_page_before_player: Option<String>,
+    // Where each library's grid was left, restored when it is opened again
+    library_positions: HashMap<LibraryId, GridPosition>,
+    // What each navigation page shows, saved on close and reopened at startup
+    page_routes: session::PageRoutes,
     // Toast overlay for notifications
     toast_overlay: adw::ToastOverlay,
     // Connection type tracking for remote connection warnings
     connection_types: HashMap<SourceId, ConnectionType>,
+    // Offline mode tracking
+    connectivity: ConnectivityTracker,
+    offline_banner: adw::Banner,
+    // Screensaver after a while without input
+    screensaver: Controller<Screensaver>,
+    screensaver_active: Rc<Cell<bool>>,
+    last_activity: Rc<Cell<Instant>>,
+    // Item playback is paused on and since when
+    paused: Option<(MediaItemId, Instant)>,
+    // Actions unavailable while the kids profile is on
+    kids_locked_actions: Vec<gio::SimpleAction>,
+    _broker_subscription: Subscription,
 }
 
 #[derive(Debug)]
@@ -84,6 +134,16 @@ pub enum MainWindowInput {
     Navigate(String),
     NavigateToSource(SourceId),
     NavigateToLibrary(LibraryId),
+    /// Open a library with the given filters, such as a home row's See All
+    NavigateToLibraryView {
+        library_id: LibraryId,
+        filter: FilterState,
+    },
+    /// A library grid was scrolled or an item opened from it
+    LibraryPositionChanged {
+        library_id: LibraryId,
+        position: GridPosition,
+    },
     NavigateToMediaItem(MediaItemId),
     NavigateToMovie(MediaItemId),
     NavigateToShow(MediaItemId),
@@ -92,7 +152,13 @@ pub enum MainWindowInput {
         media_id: MediaItemId,
         context: PlaylistContext,
     },
+    PlayQueue(Vec<MediaItemId>),
     NavigateToPreferences,
+    ShowOpenUrl,
+    NavigateToUrl(String),
+    ShowDiagnostics,
+    ShowDuplicates,
+    ShowExportLibrary,
     NavigateToSearch,
     SearchQuery(String),
     SearchResultsReceived {
@@ -101,7 +167,34 @@ pub enum MainWindowInput {
         total_hits: usize,
     },
     ToggleSidebar,
+    /// Crumbs of a page were looked up
+    BreadcrumbsLoaded {
+        route: session::SavedPage,
+        crumbs: Vec<breadcrumbs::Crumb>,
+    },
+    /// A season was picked on a show page
+    BreadcrumbSeason {
+        show_id: MediaItemId,
+        season: u32,
+    },
+    /// Move keyboard focus to the sidebar
+    FocusSidebar,
+    /// Open the filters of the library in view
+    FocusFilters,
+    /// Move keyboard focus to the page in view, to its grid on library pages
+    FocusContent,
     SyncSource(SourceId),
+    /// Check a source's connection again, then retry its sync if it is reachable
+    ReconnectSource(SourceId),
+    /// Cancel the running sync of a source
+    CancelSync(SourceId),
+    /// Pause or resume the running sync of a source
+    SetSyncPaused {
+        source_id: SourceId,
+        paused: bool,
+    },
+    /// Playback started or stopped, syncs hold off while something plays
+    PlaybackActive(bool),
     OpenReauthDialog {
         source_id: SourceId,
         source_name: String,
@@ -122,13 +215,36 @@ pub enum MainWindowInput {
         source_id: SourceId,
         status: ConnectionStatus,
     },
+    ConnectivityChanged(ConnectivityChange),
+    RetryConnection,
+    MeteredChanged(bool),
+    PowerSaverChanged(bool),
+    LowBatteryChanged(bool),
+    IgnoreMeteredRestrictions,
+    DownloadEvent(DownloadWorkerOutput),
     AuthStatusChanged {
         source_id: SourceId,
         needs_auth: bool,
     },
     ConfigUpdated,
+    BrokerMsg(BrokerMessage),
+    RestoreSession,
+    OfferResume {
+        media_id: MediaItemId,
+        title: String,
+    },
+    /// Start the screensaver if the window has been idle long enough
+    CheckIdle,
+    ExitScreensaver,
+    PlaybackPaused(Option<MediaItemId>),
 }
 
+/// How often the window checks whether to start the screensaver
+const IDLE_CHECK_SECONDS: u32 = 15;
+
+/// Time without input after which artwork is prefetched
+const PREFETCH_IDLE: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Clone)]
 pub enum ConnectionStatus {
     Connected {
@@ -161,6 +277,10 @@ impl AsyncComponent for MainWindow {
             #[wrap(Some)]
             #[name(toast_overlay)]
             set_content = &adw::ToastOverlay {
+                #[wrap(Some)]
+                set_child = &gtk::Overlay {
+                add_overlay: model.screensaver.widget(),
+
                 #[wrap(Some)]
                 #[name(split_view)]
                 set_child = &adw::NavigationSplitView {
@@ -222,6 +342,15 @@ impl AsyncComponent for MainWindow {
                                 connect_clicked => MainWindowInput::Navigate("back".to_string()),
                             },
 
+                            #[name(forward_button)]
+                            pack_start = &gtk::Button {
+                                set_icon_name: "go-next-symbolic",
+                                set_tooltip_text: Some("Go Forward"),
+                                add_css_class: "flat",
+                                set_visible: false,
+                                connect_clicked => MainWindowInput::Navigate("forward".to_string()),
+                            },
+
                             pack_start = &gtk::Button {
                                 set_icon_name: "sidebar-show-symbolic",
                                 set_tooltip_text: Some("Toggle Sidebar"),
@@ -260,6 +389,22 @@ impl AsyncComponent for MainWindow {
                             },
                         },
 
+                        #[name(breadcrumb_bar)]
+                        add_top_bar = &gtk::Box {
+                            set_orientation: gtk::Orientation::Horizontal,
+                            set_spacing: 6,
+                            add_css_class: "breadcrumbs",
+                            set_visible: false,
+                        },
+
+                        #[name(offline_banner)]
+                        add_top_bar = &adw::Banner {
+                            set_title: "You're offline — showing content saved on this device",
+                            set_button_label: Some("Retry"),
+                            set_revealed: false,
+                            connect_button_clicked => MainWindowInput::RetryConnection,
+                        },
+
                         #[wrap(Some)]
                         #[name(content_stack)]
                         set_content = &gtk::Stack {
@@ -282,6 +427,7 @@ impl AsyncComponent for MainWindow {
                     },
                 },
                 },
+                },
             },
         }
     }
@@ -306,6 +452,98 @@ impl AsyncComponent for MainWindow {
         root.add_action(&preferences_action);
         tracing::info!("Preferences action registered on window");
 
+        // Open URL action
+        let open_url_action = gio::SimpleAction::new("open-url", None);
+        let sender_clone = sender.clone();
+        open_url_action.connect_activate(move |_, _| {
+            sender_clone.input(MainWindowInput::ShowOpenUrl);
+        });
+        root.add_action(&open_url_action);
+
+        // Diagnostics action
+        let diagnostics_action = gio::SimpleAction::new("diagnostics", None);
+        let sender_clone = sender.clone();
+        diagnostics_action.connect_activate(move |_, _| {
+            sender_clone.input(MainWindowInput::ShowDiagnostics);
+        });
+        root.add_action(&diagnostics_action);
+
+        // Duplicates action
+        let duplicates_action = gio::SimpleAction::new("duplicates", None);
+        let sender_clone = sender.clone();
+        duplicates_action.connect_activate(move |_, _| {
+            sender_clone.input(MainWindowInput::ShowDuplicates);
+        });
+        root.add_action(&duplicates_action);
+
+        // Library export action
+        let export_action = gio::SimpleAction::new("export-library", None);
+        let sender_clone = sender.clone();
+        export_action.connect_activate(move |_, _| {
+            sender_clone.input(MainWindowInput::ShowExportLibrary);
+        });
+        root.add_action(&export_action);
+
+        // Opening arbitrary URLs and deleting duplicates aren't for kids
+        let kids_enabled = CONFIG_SERVICE.get_config().await.kids.enabled;
+        for action in [&open_url_action, &duplicates_action] {
+            action.set_enabled(!kids_enabled);
+        }
+
+        // Back and forward through the pages visited
+        let go_back_action = gio::SimpleAction::new("go-back", None);
+        let sender_clone = sender.clone();
+        go_back_action.connect_activate(move |_, _| {
+            sender_clone.input(MainWindowInput::Navigate("back".to_string()));
+        });
+        root.add_action(&go_back_action);
+
+        let go_forward_action = gio::SimpleAction::new("go-forward", None);
+        let sender_clone = sender.clone();
+        go_forward_action.connect_activate(move |_, _| {
+            sender_clone.input(MainWindowInput::Navigate("forward".to_string()));
+        });
+        root.add_action(&go_forward_action);
+
+        // The mouse back and forward buttons, taken before the navigation
+        // view pops on the back button by itself
+        let mouse_history = gtk::GestureClick::new();
+        mouse_history.set_button(0);
+        mouse_history.set_propagation_phase(gtk::PropagationPhase::Capture);
+        let sender_clone = sender.clone();
+        mouse_history.connect_pressed(move |gesture, _, _, _| {
+            let page = match gesture.current_button() {
+                8 => "back",
+                9 => "forward",
+                _ => return,
+            };
+            gesture.set_state(gtk::EventSequenceState::Claimed);
+            sender_clone.input(MainWindowInput::Navigate(page.to_string()));
+        });
+        root.add_controller(mouse_history);
+
+        // Focus actions, jumping between sidebar, filters and content
+        let focus_sidebar_action = gio::SimpleAction::new("focus-sidebar", None);
+        let sender_clone = sender.clone();
+        focus_sidebar_action.connect_activate(move |_, _| {
+            sender_clone.input(MainWindowInput::FocusSidebar);
+        });
+        root.add_action(&focus_sidebar_action);
+
+        let focus_filters_action = gio::SimpleAction::new("focus-filters", None);
+        let sender_clone = sender.clone();
+        focus_filters_action.connect_activate(move |_, _| {
+            sender_clone.input(MainWindowInput::FocusFilters);
+        });
+        root.add_action(&focus_filters_action);
+
+        let focus_content_action = gio::SimpleAction::new("focus-content", None);
+        let sender_clone = sender.clone();
+        focus_content_action.connect_activate(move |_, _| {
+            sender_clone.input(MainWindowInput::FocusContent);
+        });
+        root.add_action(&focus_content_action);
+
         // About action
         let about_action = gio::SimpleAction::new("about", None);
         about_action.set_enabled(true);
@@ -315,7 +553,7 @@ impl AsyncComponent for MainWindow {
                 .application_name("Reel")
                 .application_icon("media-reel")
                 .version(env!("CARGO_PKG_VERSION"))
-                .comments("A native media player for Plex and Jellyfin")
+                .comments("A native media player for Plex, Jellyfin and Emby")
                 .website("https://github.com/arsfeld/reel")
                 .issue_url("https://github.com/arsfeld/reel/issues")
                 .license_type(gtk::License::Gpl30)
@@ -340,6 +578,9 @@ impl AsyncComponent for MainWindow {
         // Apply platform-specific styling
         crate::utils::platform::Platform::apply_platform_classes(&root);
 
+        // Follow the system high-contrast and reduce-animations settings
+        crate::ui::shared::accessibility::follow_system_settings(&root);
+
         // Configure native window controls for macOS
         #[cfg(target_os = "macos")]
         {
@@ -365,8 +606,14 @@ impl AsyncComponent for MainWindow {
             && let Some(adw_app) = app.downcast_ref::<adw::Application>()
         {
             adw_app.set_accels_for_action("win.preferences", &["<primary>comma"]);
+            adw_app.set_accels_for_action("win.open-url", &["<primary>u"]);
             adw_app.set_accels_for_action("win.quit", &["<primary>q"]);
             adw_app.set_accels_for_action("window.close", &["<primary>w"]);
+            adw_app.set_accels_for_action("win.go-back", &["<alt>Left"]);
+            adw_app.set_accels_for_action("win.go-forward", &["<alt>Right"]);
+            adw_app.set_accels_for_action("win.focus-sidebar", &["<alt>1"]);
+            adw_app.set_accels_for_action("win.focus-filters", &["<alt>2"]);
+            adw_app.set_accels_for_action("win.focus-content", &["<alt>3"]);
         }
 
         // Initialize the sidebar
@@ -379,6 +626,17 @@ impl AsyncComponent for MainWindow {
                     SidebarOutput::NavigateToSources => {
                         MainWindowInput::Navigate("sources".to_string())
                     }
+                    SidebarOutput::PlayQueue(items) => MainWindowInput::PlayQueue(items),
+                    SidebarOutput::ReconnectSource(id) => MainWindowInput::ReconnectSource(id),
+                    SidebarOutput::ReauthenticateSource {
+                        source_id,
+                        source_name,
+                        source_type,
+                    } => MainWindowInput::OpenReauthDialog {
+                        source_id,
+                        source_name,
+                        source_type,
+                    },
                 });
 
         // Initialize the home page
@@ -389,6 +647,10 @@ impl AsyncComponent for MainWindow {
                     crate::ui::pages::home::HomePageOutput::NavigateToMediaItem(id) => {
                         MainWindowInput::NavigateToMediaItem(id)
                     }
+                    crate::ui::pages::home::HomePageOutput::NavigateToLibraryView {
+                        library_id,
+                        filter,
+                    } => MainWindowInput::NavigateToLibraryView { library_id, filter },
                 });
 
         // Initialize the auth dialog with parent window (no reauth mode)
@@ -415,6 +677,8 @@ impl AsyncComponent for MainWindow {
             playback_sync_worker,
             search_worker,
             cache_cleanup_worker,
+            download_worker,
+            image_prefetch_worker,
         } = workers_result;
 
         let mut model = Self {
@@ -429,6 +693,9 @@ impl AsyncComponent for MainWindow {
             search_worker,
             config_manager,
             cache_cleanup_worker,
+            download_worker,
+            image_prefetch_worker,
+            playback_active: false,
             library_page: None,
             movie_details_page: None,
             show_details_page: None,
@@ -438,6 +705,9 @@ impl AsyncComponent for MainWindow {
             search_page: None,
             search_nav_page: None,
             preferences_dialog: None,
+            diagnostics_dialog: None,
+            duplicates_dialog: None,
+            export_dialog: None,
             navigation_view: adw::NavigationView::new(),
             content_header: adw::HeaderBar::new(),
             sidebar_header: adw::HeaderBar::new(),
@@ -446,6 +716,12 @@ impl AsyncComponent for MainWindow {
             split_view: adw::NavigationSplitView::new(),
             content_stack: gtk::Stack::new(),
             back_button: gtk::Button::new(),
+            forward_button: gtk::Button::new(),
+            history: history::History::default(),
+            breadcrumb_bar: gtk::Box::new(gtk::Orientation::Horizontal, 6),
+            breadcrumb_route: None,
+            breadcrumbs: Vec::new(),
+            breadcrumb_season: None,
             content_title: adw::WindowTitle::new("", ""),
             header_start_box: gtk::Box::new(gtk::Orientation::Horizontal, 6),
             header_end_box: gtk::Box::new(gtk::Orientation::Horizontal, 6),
@@ -453,9 +729,22 @@ impl AsyncComponent for MainWindow {
             was_maximized: false,
             was_fullscreen: false,
             current_library_id: None,
-            previous_page_before_player: None,
+            library_positions: HashMap::new(),
+            page_routes: session::PageRoutes::default(),
             toast_overlay: adw::ToastOverlay::new(),
             connection_types: HashMap::new(),
+            connectivity: ConnectivityTracker::new(),
+            offline_banner: adw::Banner::new(""),
+            screensaver: Screensaver::builder().launch(db.clone()).detach(),
+            screensaver_active: Rc::new(Cell::new(false)),
+            last_activity: Rc::new(Cell::new(Instant::now())),
+            paused: None,
+            kids_locked_actions: vec![open_url_action, duplicates_action],
+            _broker_subscription: BROKER.subscribe(
+                &[Topic::Config],
+                sender.input_sender(),
+                MainWindowInput::BrokerMsg,
+            ),
         };
 
         let widgets = view_output!();
@@ -466,13 +755,17 @@ impl AsyncComponent for MainWindow {
         // Create primary menu
         let primary_menu = gio::Menu::new();
 
-        // First section with preferences
+        // First section with opening a URL and preferences
         let section1 = gio::Menu::new();
+        section1.append(Some("_Open URL…"), Some("win.open-url"));
         section1.append(Some("_Preferences"), Some("win.preferences"));
         primary_menu.append_section(None, &section1);
 
-        // Second section with about
+        // Second section with diagnostics and about
         let section2 = gio::Menu::new();
+        section2.append(Some("Find D_uplicates"), Some("win.duplicates"));
+        section2.append(Some("_Export Library…"), Some("win.export-library"));
+        section2.append(Some("_Diagnostics"), Some("win.diagnostics"));
         section2.append(Some("_About Reel"), Some("win.about"));
         primary_menu.append_section(None, &section2);
 
@@ -509,6 +802,7 @@ impl AsyncComponent for MainWindow {
 
         // Store references to widgets for later use
         model.toast_overlay.clone_from(&widgets.toast_overlay);
+        model.offline_banner.clone_from(&widgets.offline_banner);
         model.navigation_view.clone_from(&widgets.navigation_view);
         model.content_header.clone_from(&widgets.content_header);
         model.sidebar_header.clone_from(&widgets.sidebar_header);
@@ -517,6 +811,8 @@ impl AsyncComponent for MainWindow {
         model.split_view.clone_from(&widgets.split_view);
         model.content_stack.clone_from(&widgets.content_stack);
         model.back_button.clone_from(&widgets.back_button);
+        model.forward_button.clone_from(&widgets.forward_button);
+        model.breadcrumb_bar.clone_from(&widgets.breadcrumb_bar);
         model.content_title.clone_from(&widgets.content_title);
         model.header_start_box.clone_from(&widgets.header_start_box);
         model.header_end_box.clone_from(&widgets.header_end_box);
@@ -524,13 +820,28 @@ impl AsyncComponent for MainWindow {
         // Start with empty state shown
         widgets.content_stack.set_visible_child_name("empty");
 
+        // Reopen the window as it was left and save it again on close
+        session::restore_window_state(&root, &CONFIG_SERVICE.get_config().await);
+        {
+            let navigation_view = model.navigation_view.clone();
+            let page_routes = model.page_routes.clone();
+            root.connect_close_request(move |window| {
+                session::save_on_close(window, &navigation_view, &page_routes);
+                gtk::glib::Propagation::Proceed
+            });
+        }
+
+        // Artwork is fetched at the display's resolution
+        image_quality::set_scale_factor(root.scale_factor());
+        root.connect_scale_factor_notify(|window| {
+            image_quality::set_scale_factor(window.scale_factor());
+        });
+
         // Connect navigation view signals
         {
             let sender_clone = sender.input_sender().clone();
             model.navigation_view.connect_pushed(move |_nav_view| {
-                sender_clone
-                    .send(MainWindowInput::Navigate("update_header".to_string()))
-                    .unwrap();
+                sender_clone.emit(MainWindowInput::Navigate("update_header".to_string()));
             });
         }
         {
@@ -538,9 +849,7 @@ impl AsyncComponent for MainWindow {
             model
                 .navigation_view
                 .connect_popped(move |_nav_view, _page| {
-                    sender_clone
-                        .send(MainWindowInput::Navigate("update_header".to_string()))
-                        .unwrap();
+                    sender_clone.emit(MainWindowInput::Navigate("update_header".to_string()));
                 });
         }
 
@@ -585,12 +894,32 @@ impl AsyncComponent for MainWindow {
             );
         }
 
+        // Start the screensaver after a while without input
+        screensaver::watch_activity(
+            &root,
+            model.last_activity.clone(),
+            model.screensaver_active.clone(),
+            sender.input_sender().clone(),
+        );
+        {
+            let sender_clone = sender.input_sender().clone();
+            gtk::glib::timeout_add_seconds_local(IDLE_CHECK_SECONDS, move || {
+                sender_clone.emit(MainWindowInput::CheckIdle);
+                gtk::glib::ControlFlow::Continue
+            });
+        }
+
         // Trigger initial sync of all sources after a short delay to let UI initialize
         sender.input(MainWindowInput::Navigate("init_sync".to_string()));
 
         // Initialize search index with existing media items
         sender.input(MainWindowInput::Navigate("init_search_index".to_string()));
 
+        // Probe hardware decoders for playback decisions
+        relm4::spawn(async {
+            crate::services::core::hw_decode::probe().await;
+        });
+
         AsyncComponentParts { model, widgets }
     }
 
@@ -607,6 +936,21 @@ impl AsyncComponent for MainWindow {
             MainWindowInput::NavigateToPreferences => {
                 navigation::navigate_to_preferences(self, &sender, root);
             }
+            MainWindowInput::ShowOpenUrl => {
+                open_url::present_open_url_dialog(root.clone(), sender.clone());
+            }
+            MainWindowInput::NavigateToUrl(url) => {
+                navigation::navigate_to_url(self, url, &sender, root);
+            }
+            MainWindowInput::ShowDiagnostics => {
+                navigation::show_diagnostics(self, root);
+            }
+            MainWindowInput::ShowDuplicates => {
+                navigation::show_duplicates(self, &sender, root);
+            }
+            MainWindowInput::ShowExportLibrary => {
+                navigation::show_export_library(self, root);
+            }
             MainWindowInput::NavigateToSearch => {
                 navigation::navigate_to_search(self, &sender);
             }
@@ -653,7 +997,10 @@ impl AsyncComponent for MainWindow {
                 navigation::navigate_to_source(self, source_id, &sender);
             }
             MainWindowInput::NavigateToLibrary(library_id) => {
-                navigation::navigate_to_library(self, library_id, &sender);
+                navigation::navigate_to_library(self, library_id, None, &sender);
+            }
+            MainWindowInput::NavigateToLibraryView { library_id, filter } => {
+                navigation::navigate_to_library(self, library_id, Some(filter), &sender);
             }
             MainWindowInput::NavigateToMediaItem(item_id) => {
                 navigation::navigate_to_media_item(self, item_id, &sender);
@@ -670,6 +1017,31 @@ impl AsyncComponent for MainWindow {
             MainWindowInput::NavigateToPlayerWithContext { media_id, context } => {
                 navigation::navigate_to_player_with_context(self, media_id, context, &sender, root);
             }
+            MainWindowInput::PlayQueue(items) => {
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    use crate::services::core::playqueue::PlayQueueService;
+
+                    match PlayQueueService::create_local(&db, &items).await {
+                        Ok(context) => {
+                            if let Some(media_id) = context.get_current_item() {
+                                sender.input(MainWindowInput::NavigateToPlayerWithContext {
+                                    media_id,
+                                    context,
+                                });
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to play queue: {}", e);
+                            sender.input(MainWindowInput::ShowToast(format!(
+                                "Failed to play queue: {}",
+                                e
+                            )));
+                        }
+                    }
+                });
+            }
             MainWindowInput::ToggleSidebar => {
                 tracing::info!("Toggling sidebar");
 
@@ -682,6 +1054,50 @@ impl AsyncComponent for MainWindow {
                     self.split_view.set_show_content(true);
                 }
             }
+            MainWindowInput::BreadcrumbsLoaded { route, crumbs } => {
+                if self.breadcrumb_route.as_ref() == Some(&route) {
+                    self.breadcrumbs = crumbs;
+                    breadcrumbs::render(self, &sender);
+                }
+            }
+            MainWindowInput::LibraryPositionChanged {
+                library_id,
+                position,
+            } => {
+                self.library_positions.insert(library_id, position);
+            }
+            MainWindowInput::BreadcrumbSeason { show_id, season } => {
+                self.breadcrumb_season = Some((show_id, season));
+                breadcrumbs::render(self, &sender);
+            }
+            MainWindowInput::FocusSidebar => {
+                if self.split_view.is_collapsed() {
+                    self.split_view.set_show_content(false);
+                }
+                self.sidebar
+                    .widget()
+                    .child_focus(gtk::DirectionType::TabForward);
+            }
+            MainWindowInput::FocusFilters => {
+                let page = self.navigation_view.visible_page();
+                if page.is_some_and(|page| page.title() == "Library")
+                    && let Some(library_page) = &self.library_page
+                {
+                    library_page.emit(LibraryPageInput::ToggleFiltersPopover);
+                }
+            }
+            MainWindowInput::FocusContent => {
+                self.split_view.set_show_content(true);
+                let page = self.navigation_view.visible_page();
+                if let Some(page) = &page
+                    && page.title() == "Library"
+                    && let Some(library_page) = &self.library_page
+                {
+                    library_page.emit(LibraryPageInput::FocusGrid);
+                } else if let Some(page) = page {
+                    page.child_focus(gtk::DirectionType::TabForward);
+                }
+            }
             MainWindowInput::OpenReauthDialog {
                 source_id,
                 source_name,
@@ -790,7 +1206,44 @@ impl AsyncComponent for MainWindow {
                         });
                 }
             }
+            MainWindowInput::CancelSync(source_id) => {
+                self.sync_worker
+                    .emit(SyncWorkerInput::StopSync { source_id });
+            }
+            MainWindowInput::SetSyncPaused { source_id, paused } => {
+                self.sync_worker
+                    .emit(SyncWorkerInput::PauseSync { source_id, paused });
+            }
+            MainWindowInput::PlaybackActive(active) => {
+                self.playback_active = active;
+                self.sync_worker
+                    .emit(SyncWorkerInput::PlaybackActive(active));
+                if active {
+                    self.image_prefetch_worker
+                        .emit(ImagePrefetchInput::SetIdle(false));
+                }
+            }
+            MainWindowInput::ReconnectSource(source_id) => {
+                tracing::info!("Reconnecting source {}", source_id);
+                self.connection_monitor
+                    .emit(ConnectionMonitorInput::Reconnect(source_id.clone()));
+                if !self.connectivity.is_source_offline(&source_id) {
+                    sender.input(MainWindowInput::SyncSource(source_id));
+                }
+            }
             MainWindowInput::SyncSource(source_id) => {
+                if let Some(change) = self.connectivity.register_source(&source_id) {
+                    sender.input(MainWindowInput::ConnectivityChanged(change));
+                }
+
+                if self.connectivity.is_source_offline(&source_id) {
+                    tracing::info!("Skipping sync for unreachable source {}", source_id);
+                    sender.input(MainWindowInput::ShowToast(
+                        "Can't sync while the server is unreachable".to_string(),
+                    ));
+                    return;
+                }
+
                 tracing::info!("Syncing new source: {:?}", source_id);
 
                 // Trigger sync using the SyncWorker
@@ -828,6 +1281,8 @@ impl AsyncComponent for MainWindow {
                             tracing::error!("Failed to stop player");
                         });
                 }
+                // The player page may be gone before it reports the stop
+                sender.input(MainWindowInput::PlaybackActive(false));
 
                 // Show window chrome again
                 self.content_header.set_visible(true);
@@ -929,6 +1384,87 @@ impl AsyncComponent for MainWindow {
                 // Handle configuration updates from file watcher
                 tracing::info!("Configuration has been updated from disk");
             }
+            MainWindowInput::BrokerMsg(BrokerMessage::Config(ConfigMessage::Updated {
+                config,
+            })) => {
+                for action in &self.kids_locked_actions {
+                    action.set_enabled(!config.kids.enabled);
+                }
+            }
+            MainWindowInput::BrokerMsg(_) => {}
+            MainWindowInput::RestoreSession => {
+                session::restore_session(self.db.clone(), sender.clone());
+            }
+            MainWindowInput::OfferResume { media_id, title } => {
+                let dialog = adw::AlertDialog::builder()
+                    .heading(format!("Resume watching {}?", title))
+                    .body("Playback was interrupted when Reel was closed")
+                    .close_response("dismiss")
+                    .default_response("resume")
+                    .build();
+                dialog.add_responses(&[("dismiss", "_Not Now"), ("resume", "_Resume")]);
+                dialog.set_response_appearance("resume", adw::ResponseAppearance::Suggested);
+                let sender = sender.clone();
+                dialog.connect_response(None, move |_, response| {
+                    if response == "resume" {
+                        sender.input(MainWindowInput::NavigateToPlayer(media_id.clone()));
+                    }
+                });
+                dialog.present(Some(root));
+            }
+            MainWindowInput::CheckIdle => {
+                // Playback needs the bandwidth more than artwork does
+                let idle =
+                    !self.playback_active && self.last_activity.get().elapsed() >= PREFETCH_IDLE;
+                self.image_prefetch_worker
+                    .emit(ImagePrefetchInput::SetIdle(idle));
+
+                let minutes = CONFIG_SERVICE.get_config().await.ui.screensaver_minutes;
+                if minutes == 0 || self.screensaver_active.get() || root.visible_dialog().is_some()
+                {
+                    return;
+                }
+                let idle_for = Duration::from_secs(u64::from(minutes) * 60);
+                if self.last_activity.get().elapsed() < idle_for {
+                    return;
+                }
+
+                // Only over the home page, or the player paused that long
+                let page = self.navigation_view.visible_page().map(|page| page.title());
+                let now_playing = match page.as_deref() {
+                    Some("Home")
+                        if self.content_stack.visible_child_name().as_deref()
+                            == Some("content") =>
+                    {
+                        None
+                    }
+                    Some(back_stack::PLAYER_TITLE) => match &self.paused {
+                        Some((media_id, since)) if since.elapsed() >= idle_for => {
+                            Some(media_id.clone())
+                        }
+                        _ => return,
+                    },
+                    _ => return,
+                };
+                self.screensaver_active.set(true);
+                self.screensaver
+                    .emit(ScreensaverInput::Start { now_playing });
+            }
+            MainWindowInput::ExitScreensaver => {
+                if self.screensaver_active.replace(false) {
+                    self.screensaver.emit(ScreensaverInput::Stop);
+                }
+            }
+            MainWindowInput::PlaybackPaused(media_id) => match media_id {
+                // Repeated reports of the same pause keep when it started
+                Some(media_id) if self.paused.as_ref().is_some_and(|(id, _)| *id == media_id) => {}
+                Some(media_id) => self.paused = Some((media_id, Instant::now())),
+                None => {
+                    self.paused = None;
+                    // Playback resumed some other way, such as media keys
+                    sender.input(MainWindowInput::ExitScreensaver);
+                }
+            },
             MainWindowInput::AuthStatusChanged {
                 source_id,
                 needs_auth,
@@ -942,6 +1478,14 @@ impl AsyncComponent for MainWindow {
                     self.toast_overlay.add_toast(toast);
                 }
 
+                let message = BrokerMessage::Source(SourceMessage::AuthChanged {
+                    source_id: source_id.to_string(),
+                    needs_auth,
+                });
+                relm4::spawn(async move {
+                    BROKER.broadcast(message).await;
+                });
+
                 // Forward to sources page if it's open to update status indicators
                 if let Some(ref sources_page) = self.sources_page {
                     sources_page
@@ -957,6 +1501,7 @@ impl AsyncComponent for MainWindow {
             }
             MainWindowInput::ConnectionStatusChanged { source_id, status } => {
                 // Handle connection status changes from ConnectionMonitor
+                let was_offline = self.connectivity.is_source_offline(&source_id);
                 let (is_connected, status_text) = match &status {
                     ConnectionStatus::Connected {
                         url,
@@ -1027,16 +1572,25 @@ impl AsyncComponent for MainWindow {
                     }
                     ConnectionStatus::Disconnected => {
                         tracing::warn!("Source {} disconnected", source_id);
-                        sender.input(MainWindowInput::ShowToast(format!(
-                            "Connection lost for source {}",
-                            source_id
-                        )));
+                        if !was_offline {
+                            sender.input(MainWindowInput::ShowToast(format!(
+                                "Connection lost for source {}",
+                                source_id
+                            )));
+                        }
                         // Clear stored connection type on disconnect
                         self.connection_types.remove(&source_id);
                         (false, format!("Source {} disconnected", source_id))
                     }
                 };
 
+                if let Some(change) = self
+                    .connectivity
+                    .set_source_online(&source_id, is_connected)
+                {
+                    sender.input(MainWindowInput::ConnectivityChanged(change));
+                }
+
                 // Update sidebar with overall connection status text
                 self.sidebar
                     .sender()
@@ -1045,48 +1599,21 @@ impl AsyncComponent for MainWindow {
                         tracing::error!("Failed to update sidebar connection status: {:?}", e);
                     });
 
-                // Update specific source with connection type info
-                let conn_state = if is_connected {
-                    super::sidebar::ConnectionState::Connected
-                } else {
-                    super::sidebar::ConnectionState::Disconnected
-                };
-
+                // Tell views tracking source health, such as the sidebar
                 let conn_type = match &status {
                     ConnectionStatus::Connected {
                         connection_type, ..
                     } => Some(*connection_type),
                     ConnectionStatus::Disconnected => None,
                 };
-
-                tracing::info!(
-                    "Sending connection type {:?} to sidebar for source {}",
-                    conn_type,
-                    source_id
-                );
-
-                self.sidebar
-                    .sender()
-                    .send(SidebarInput::UpdateSourceConnectionStatus {
-                        source_id: source_id.clone(),
-                        state: conn_state,
-                        error: None,
-                        connection_type: conn_type,
-                    })
-                    .unwrap_or_else(|e| {
-                        tracing::error!("Failed to update source connection status: {:?}", e);
-                    });
-
-                // Also use ConnectionCheckResult which won't override SyncFailed state
-                self.sidebar
-                    .sender()
-                    .send(SidebarInput::ConnectionCheckResult {
-                        source_id: source_id.clone(),
-                        is_connected,
-                    })
-                    .unwrap_or_else(|e| {
-                        tracing::error!("Failed to update source connection indicator: {:?}", e);
-                    });
+                let message = BrokerMessage::Source(SourceMessage::ConnectionChanged {
+                    source_id: source_id.to_string(),
+                    connected: is_connected,
+                    connection_type: conn_type,
+                });
+                relm4::spawn(async move {
+                    BROKER.broadcast(message).await;
+                });
 
                 // If we have a sources page open, update it too
                 if let Some(ref sources_page) = self.sources_page {
@@ -1107,8 +1634,9 @@ impl AsyncComponent for MainWindow {
                         });
                 }
 
-                // Trigger connection monitor to check this specific source again in case of disconnect
-                if matches!(status, ConnectionStatus::Disconnected) {
+                // Trigger connection monitor to check this specific source again when it
+                // first disconnects; afterwards the regular monitoring interval applies
+                if matches!(status, ConnectionStatus::Disconnected) && !was_offline {
                     // Wait a bit before retrying
                     let monitor_sender = self.connection_monitor.sender().clone();
                     let source_id_clone = source_id.clone();
@@ -1122,6 +1650,131 @@ impl AsyncComponent for MainWindow {
                     });
                 }
             }
+            MainWindowInput::ConnectivityChanged(change) => {
+                let message = match change {
+                    ConnectivityChange::WentOffline => {
+                        tracing::warn!("All sources unreachable, entering offline mode");
+                        self.offline_banner.set_revealed(true);
+
+                        // Queued playback changes would only burn retry attempts while offline
+                        self.playback_sync_worker
+                            .sender()
+                            .send(PlaybackSyncWorkerInput::PauseSync)
+                            .unwrap_or_else(|e| {
+                                tracing::error!("Failed to pause playback sync: {:?}", e);
+                            });
+
+                        ConnectivityMessage::WentOffline
+                    }
+                    ConnectivityChange::BackOnline => {
+                        tracing::info!("Connectivity restored, reconciling with servers");
+                        self.offline_banner.set_revealed(false);
+                        sender.input(MainWindowInput::ShowToast(
+                            "Back online — syncing changes".to_string(),
+                        ));
+
+                        // Replay actions queued while offline, including ones that
+                        // failed just before connectivity was lost
+                        let playback_sync = self.playback_sync_worker.sender();
+                        for input in [
+                            PlaybackSyncWorkerInput::ResumeSync,
+                            PlaybackSyncWorkerInput::RetryFailed,
+                            PlaybackSyncWorkerInput::ProcessQueue,
+                        ] {
+                            playback_sync.send(input).unwrap_or_else(|e| {
+                                tracing::error!("Failed to resume playback sync: {:?}", e);
+                            });
+                        }
+
+                        // Pull in changes made on the servers while we were away
+                        for source_id in self.connectivity.online_sources() {
+                            self.sync_worker
+                                .sender()
+                                .send(SyncWorkerInput::StartSync {
+                                    source_id: source_id.clone(),
+                                    library_id: None,
+                                    force: false,
+                                })
+                                .unwrap_or_else(|e| {
+                                    tracing::error!(
+                                        "Failed to send sync command to worker: {:?}",
+                                        e
+                                    );
+                                });
+                        }
+
+                        ConnectivityMessage::BackOnline
+                    }
+                };
+
+                relm4::spawn(async move {
+                    BROKER.broadcast(BrokerMessage::Connectivity(message)).await;
+                });
+            }
+            MainWindowInput::MeteredChanged(is_metered) => {
+                if metered::set_metered(is_metered)
+                    && is_metered
+                    && metered::active_restrictions().any()
+                {
+                    let toast = adw::Toast::builder()
+                        .title("Metered connection — limiting data usage")
+                        .button_label("Use Anyway")
+                        .timeout(10)
+                        .build();
+                    let sender = sender.clone();
+                    toast.connect_button_clicked(move |_| {
+                        sender.input(MainWindowInput::IgnoreMeteredRestrictions);
+                    });
+                    self.toast_overlay.add_toast(toast);
+                }
+            }
+            MainWindowInput::PowerSaverChanged(enabled) => {
+                if power_saver::set_power_saver(enabled) {
+                    broadcast_power_saving();
+                }
+            }
+            MainWindowInput::LowBatteryChanged(low) => {
+                if power_saver::set_low_battery(low) {
+                    broadcast_power_saving();
+                }
+            }
+            MainWindowInput::IgnoreMeteredRestrictions => {
+                tracing::info!("Ignoring metered connection restrictions for this session");
+                metered::set_overridden(true);
+            }
+            MainWindowInput::DownloadEvent(event) => match event {
+                DownloadWorkerOutput::DownloadCompleted { media_id } => {
+                    tracing::info!("Download completed: {}", media_id);
+                    self.toast_overlay
+                        .add_toast(adw::Toast::new("Download complete — available offline"));
+                }
+                DownloadWorkerOutput::DownloadFailed { media_id, error } => {
+                    tracing::error!("Download of {} failed: {}", media_id, error);
+                    self.toast_overlay
+                        .add_toast(adw::Toast::new(&format!("Download failed: {}", error)));
+                }
+                DownloadWorkerOutput::DownloadStarted { .. }
+                | DownloadWorkerOutput::DownloadProgress { .. } => {}
+            },
+            MainWindowInput::RetryConnection => {
+                tracing::info!("Retrying connections to unreachable sources");
+                let monitor_sender = self.connection_monitor.sender().clone();
+                let source_ids: Vec<SourceId> =
+                    self.connectivity.offline_sources().cloned().collect();
+
+                relm4::spawn(async move {
+                    let cache = crate::services::core::ConnectionService::cache();
+                    for source_id in source_ids {
+                        // Skip the failure backoff so the check actually hits the network
+                        cache.invalidate(&source_id).await;
+                        monitor_sender
+                            .send(ConnectionMonitorInput::CheckSource(source_id))
+                            .unwrap_or_else(|e| {
+                                tracing::error!("Failed to trigger connection check: {:?}", e);
+                            });
+                    }
+                });
+            }
         }
     }
 
@@ -1134,6 +1787,7 @@ impl AsyncComponent for MainWindow {
         // Start sync for all loaded sources using SyncWorker
         for source in sources {
             let source_id = SourceId::new(source.id.clone());
+            self.connectivity.register_source(&source_id);
             tracing::info!("Starting startup sync for source: {}", source.name);
 
             // Trigger sync using the SyncWorker
@@ -1150,3 +1804,13 @@ impl AsyncComponent for MainWindow {
         }
     }
 }
+
+/// Tell the player that power saving playback was turned on or off
+fn broadcast_power_saving() {
+    let active = power_saver::is_active();
+    relm4::spawn(async move {
+        BROKER
+            .broadcast(BrokerMessage::Power(PowerMessage::SavingChanged { active }))
+            .await;
+    });
+}
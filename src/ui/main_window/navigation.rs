@@ -3,9 +3,14 @@ use libadwaita as adw;
 use relm4::gtk;
 use relm4::prelude::*;
 
+use super::back_stack::{self, BackRequest};
+use super::breadcrumbs;
+use super::session::SavedPage;
 use super::{MainWindow, MainWindowInput};
 use crate::models::{LibraryId, MediaItemId, PlaylistContext, SourceId};
 use crate::ui::dialogs::PreferencesDialogOutput;
+use crate::ui::pages::library::FilterState;
+use crate::ui::pages::player::PlayerInput;
 use crate::ui::pages::{LibraryPage, MovieDetailsPage, PlayerPage, ShowDetailsPage, SourcesPage};
 use crate::ui::sidebar::SidebarInput;
 use crate::workers::SyncWorkerInput;
@@ -18,11 +23,13 @@ pub fn handle_navigate(
 ) {
     tracing::info!("Navigating to: {}", page);
     match page.as_str() {
-        "back" => navigate_back(window, sender),
+        "back" => navigate_back(window, BackRequest::Page, sender),
+        "leave_player" => navigate_back(window, BackRequest::Player, sender),
+        "forward" => navigate_forward(window, sender),
         "init_sync" => init_sync(window, sender),
         "init_search_index" => init_search_index(),
         "refresh_search_index" => refresh_search_index(window),
-        "update_header" => update_header(window),
+        "update_header" => update_header(window, sender),
         "home" => navigate_home(window, sender),
         "sources" => navigate_sources(window, sender),
         "preferences" => navigate_preferences(window, sender),
@@ -34,67 +41,70 @@ pub fn handle_navigate(
 }
 
 /// Navigate back to the previous page
-fn navigate_back(window: &mut MainWindow, sender: &AsyncComponentSender<MainWindow>) {
-    // Check if we have pages to pop (more than 1 page in stack)
-    if window.navigation_view.navigation_stack().n_items() > 1 {
-        // Check if we're leaving the player page and need to stop it
-        if let Some(page) = window.navigation_view.visible_page()
-            && page.title() == "Player"
-        {
-            // Stop the player before leaving the page
-            if let Some(ref player_page) = window.player_page {
-                tracing::info!("Stopping player before navigation back");
-                player_page
-                    .sender()
-                    .send(crate::ui::pages::player::PlayerInput::Stop)
-                    .unwrap_or_else(|_| {
-                        tracing::error!("Failed to stop player");
-                    });
-            }
-
-            // Use the stored previous page to navigate back to the correct location
-            if let Some(previous_page) = window.previous_page_before_player.take() {
-                tracing::info!("Navigating back from player to: {}", previous_page);
-
-                // Pop the player page
-                window.navigation_view.pop();
+fn navigate_back(
+    window: &mut MainWindow,
+    request: BackRequest,
+    sender: &AsyncComponentSender<MainWindow>,
+) {
+    let Some(step) = back_stack::back_step(&window.navigation_view, request) else {
+        return;
+    };
 
-                // Clear the stored previous page
-                window.previous_page_before_player = None;
+    // Stop the player before leaving its page
+    if step.leaves_player
+        && let Some(ref player_page) = window.player_page
+    {
+        tracing::info!("Stopping player before navigation back");
+        player_page
+            .sender()
+            .send(crate::ui::pages::player::PlayerInput::Stop)
+            .unwrap_or_else(|_| {
+                tracing::error!("Failed to stop player");
+            });
+    }
 
-                // Clear any custom header content when going back
-                sender.input(MainWindowInput::ClearHeaderContent);
+    tracing::info!("Navigating back to: {}", step.page.title());
+    window.navigation_view.pop_to_page(&step.page);
 
-                return;
-            }
-        }
+    // Clear any custom header content when going back
+    sender.input(MainWindowInput::ClearHeaderContent);
 
-        window.navigation_view.pop();
+    // Check if we're back on Sources page and restore its header button
+    if let Some(page) = window.navigation_view.visible_page()
+        && page.title() == "Sources"
+    {
+        // Re-add the "Add Source" button to the header
+        let add_button = gtk::Button::builder()
+            .icon_name("list-add-symbolic")
+            .tooltip_text("Add Source")
+            .css_classes(vec!["suggested-action"])
+            .build();
 
-        // Clear any custom header content when going back
-        sender.input(MainWindowInput::ClearHeaderContent);
+        let sender_clone = sender.input_sender().clone();
+        add_button.connect_clicked(move |_| {
+            sender_clone.emit(MainWindowInput::Navigate("auth_dialog".to_string()));
+        });
 
-        // Check if we're back on Sources page and restore its header button
-        if let Some(page) = window.navigation_view.visible_page()
-            && page.title() == "Sources"
-        {
-            // Re-add the "Add Source" button to the header
-            let add_button = gtk::Button::builder()
-                .icon_name("list-add-symbolic")
-                .tooltip_text("Add Source")
-                .css_classes(vec!["suggested-action"])
-                .build();
+        sender.input(MainWindowInput::SetHeaderEndContent(Some(
+            add_button.upcast(),
+        )));
+    }
+}
 
-            let sender_clone = sender.input_sender().clone();
-            add_button.connect_clicked(move |_| {
-                sender_clone.emit(MainWindowInput::Navigate("auth_dialog".to_string()));
-            });
+/// Reopen the page left by the last step back
+fn navigate_forward(window: &mut MainWindow, sender: &AsyncComponentSender<MainWindow>) {
+    let Some(route) = window.history.next().cloned() else {
+        tracing::debug!("Nothing to go forward to");
+        return;
+    };
 
-            sender.input(MainWindowInput::SetHeaderEndContent(Some(
-                add_button.upcast(),
-            )));
-        }
-    }
+    tracing::info!("Navigating forward to: {:?}", route);
+    sender.input(match route {
+        SavedPage::Library(library_id) => MainWindowInput::NavigateToLibrary(library_id),
+        SavedPage::Movie(item_id) => MainWindowInput::NavigateToMovie(item_id),
+        SavedPage::Show(item_id) => MainWindowInput::NavigateToShow(item_id),
+        SavedPage::Player => return,
+    });
 }
 
 /// Initialize sync for all sources on startup
@@ -120,6 +130,7 @@ fn init_sync(window: &mut MainWindow, sender: &AsyncComponentSender<MainWindow>)
                         if !sources.is_empty() {
                             tracing::info!("Sources configured, navigating to home immediately");
                             sender.input(MainWindowInput::Navigate("home".to_string()));
+                            sender.input(MainWindowInput::RestoreSession);
 
                             // Trigger sync for all sources via SyncWorker
                             for source in sources {
@@ -180,11 +191,23 @@ fn refresh_search_index(window: &mut MainWindow) {
 }
 
 /// Update header based on current navigation state
-fn update_header(window: &mut MainWindow) {
+fn update_header(window: &mut MainWindow, sender: &AsyncComponentSender<MainWindow>) {
     // Update back button visibility based on navigation stack
     let can_pop = window.navigation_view.navigation_stack().n_items() > 1;
     window.back_button.set_visible(can_pop);
 
+    // Keep the pages left by going back, for going forward again
+    let stack = back_stack::pages(&window.navigation_view)
+        .iter()
+        .map(|page| window.page_routes.route_of(page))
+        .collect();
+    window.history.update(stack);
+    window
+        .forward_button
+        .set_visible(window.history.next().is_some());
+
+    breadcrumbs::update(window, sender);
+
     // Update title and subtitle based on current page
     if let Some(page) = window.navigation_view.visible_page() {
         let title = page.title();
@@ -347,6 +370,13 @@ fn navigate_sources(window: &mut MainWindow, sender: &AsyncComponentSender<MainW
                         tracing::info!("Source page requesting sync for: {:?}", source_id);
                         MainWindowInput::SyncSource(source_id)
                     }
+                    crate::ui::pages::sources::SourcesPageOutput::CancelSync(source_id) => {
+                        MainWindowInput::CancelSync(source_id)
+                    }
+                    crate::ui::pages::sources::SourcesPageOutput::SetSyncPaused {
+                        source_id,
+                        paused,
+                    } => MainWindowInput::SetSyncPaused { source_id, paused },
                 },
             );
 
@@ -442,6 +472,49 @@ pub fn navigate_to_preferences(
     create_and_show_preferences_dialog_with_root(window, sender, root);
 }
 
+/// Open the diagnostics dialog (from menu), reusing it if it was opened before
+pub fn show_diagnostics(window: &mut MainWindow, root: &adw::ApplicationWindow) {
+    use crate::ui::dialogs::{DiagnosticsDialog, DiagnosticsDialogInput};
+
+    let dialog = window.diagnostics_dialog.get_or_insert_with(|| {
+        DiagnosticsDialog::builder()
+            .launch(window.db.clone())
+            .detach()
+    });
+    dialog.emit(DiagnosticsDialogInput::Start);
+    dialog.widget().present(Some(root));
+}
+
+/// Open the duplicates dialog (from menu), scanning again each time
+pub fn show_duplicates(
+    window: &mut MainWindow,
+    sender: &AsyncComponentSender<MainWindow>,
+    root: &adw::ApplicationWindow,
+) {
+    use crate::ui::dialogs::{DuplicatesDialog, DuplicatesDialogInput, DuplicatesDialogOutput};
+
+    let dialog = window.duplicates_dialog.get_or_insert_with(|| {
+        DuplicatesDialog::builder()
+            .launch(window.db.clone())
+            .forward(sender.input_sender(), |output| match output {
+                DuplicatesDialogOutput::Play(id) => MainWindowInput::NavigateToPlayer(id),
+            })
+    });
+    dialog.emit(DuplicatesDialogInput::Scan);
+    dialog.widget().present(Some(root));
+}
+
+/// Open the library export dialog (from menu), loading the libraries each time
+pub fn show_export_library(window: &mut MainWindow, root: &adw::ApplicationWindow) {
+    use crate::ui::dialogs::{ExportDialog, ExportDialogInput};
+
+    let dialog = window
+        .export_dialog
+        .get_or_insert_with(|| ExportDialog::builder().launch(window.db.clone()).detach());
+    dialog.emit(ExportDialogInput::Load);
+    dialog.widget().present(Some(root));
+}
+
 /// Navigate to search page
 pub fn navigate_to_search(window: &mut MainWindow, sender: &AsyncComponentSender<MainWindow>) {
     tracing::info!("Navigating to search page");
@@ -541,6 +614,7 @@ pub fn navigate_to_source(
 pub fn navigate_to_library(
     window: &mut MainWindow,
     library_id: LibraryId,
+    filter: Option<FilterState>,
     sender: &AsyncComponentSender<MainWindow>,
 ) {
     tracing::info!("Navigating to library: {}", library_id);
@@ -548,6 +622,7 @@ pub fn navigate_to_library(
     // Check if we're already on this library page
     if let Some(ref current_id) = window.current_library_id
         && current_id == &library_id
+        && filter.is_none()
     {
         tracing::debug!("Already on library: {}, skipping navigation", library_id);
         return;
@@ -589,12 +664,31 @@ pub fn navigate_to_library(
                 crate::ui::pages::library::LibraryPageOutput::SetHeaderTitleWidget(widget) => {
                     MainWindowInput::SetTitleWidget(Some(widget))
                 }
+                crate::ui::pages::library::LibraryPageOutput::PositionChanged {
+                    library_id,
+                    position,
+                } => MainWindowInput::LibraryPositionChanged {
+                    library_id,
+                    position,
+                },
             });
 
+    // Pick up where the library was left, unless opened with other filters
+    if filter.is_none()
+        && let Some(position) = window.library_positions.get(&library_id)
+    {
+        library_controller
+            .emit(crate::ui::pages::library::LibraryPageInput::RestorePosition(position.clone()));
+    }
+
     // Set the library on the new controller
-    library_controller.emit(crate::ui::pages::library::LibraryPageInput::SetLibrary(
-        library_id.clone(),
-    ));
+    library_controller.emit(match filter {
+        Some(filter) => crate::ui::pages::library::LibraryPageInput::SetLibraryWithFilter {
+            library_id: library_id.clone(),
+            filter,
+        },
+        None => crate::ui::pages::library::LibraryPageInput::SetLibrary(library_id.clone()),
+    });
 
     // Create navigation page with the new controller's widget
     let page = adw::NavigationPage::builder()
@@ -602,6 +696,10 @@ pub fn navigate_to_library(
         .child(library_controller.widget())
         .build();
 
+    window
+        .page_routes
+        .register(&page, SavedPage::Library(library_id.clone()));
+
     // Store the controller for later use
     window.library_page = Some(library_controller);
 
@@ -681,6 +779,16 @@ pub fn navigate_to_movie(
                 tracing::info!("Playing media: {}", id);
                 MainWindowInput::NavigateToPlayer(id)
             }
+            crate::ui::pages::movie_details::MovieDetailsOutput::NavigateToLibraryView {
+                library_id,
+                filter,
+            } => MainWindowInput::NavigateToLibraryView { library_id, filter },
+            crate::ui::pages::movie_details::MovieDetailsOutput::NavigateBack => {
+                MainWindowInput::Navigate("back".to_string())
+            }
+            crate::ui::pages::movie_details::MovieDetailsOutput::ShowToast(message) => {
+                MainWindowInput::ShowToast(message)
+            }
         });
 
     // Create navigation page with the new controller's widget
@@ -689,6 +797,10 @@ pub fn navigate_to_movie(
         .child(movie_controller.widget())
         .build();
 
+    window
+        .page_routes
+        .register(&page, SavedPage::Movie(item_id.clone()));
+
     // Store the controller for later use
     window.movie_details_page = Some(movie_controller);
 
@@ -710,6 +822,7 @@ pub fn navigate_to_show(
     // Always recreate the show details page to avoid widget parent conflicts
     // This ensures the widget isn't already attached to another navigation page
     let db = std::sync::Arc::new(window.db.clone());
+    let show_id = item_id.clone();
     let show_controller = ShowDetailsPage::builder()
         .launch((item_id.clone(), db))
         .forward(sender.input_sender(), move |output| match output {
@@ -724,6 +837,22 @@ pub fn navigate_to_show(
                 tracing::info!("Playing episode with context: {}", media_id);
                 MainWindowInput::NavigateToPlayerWithContext { media_id, context }
             }
+            crate::ui::pages::show_details::ShowDetailsOutput::NavigateToLibraryView {
+                library_id,
+                filter,
+            } => MainWindowInput::NavigateToLibraryView { library_id, filter },
+            crate::ui::pages::show_details::ShowDetailsOutput::SeasonSelected(season) => {
+                MainWindowInput::BreadcrumbSeason {
+                    show_id: show_id.clone(),
+                    season,
+                }
+            }
+            crate::ui::pages::show_details::ShowDetailsOutput::NavigateBack => {
+                MainWindowInput::Navigate("back".to_string())
+            }
+            crate::ui::pages::show_details::ShowDetailsOutput::ShowToast(message) => {
+                MainWindowInput::ShowToast(message)
+            }
         });
 
     // Create navigation page with the new controller's widget
@@ -732,6 +861,10 @@ pub fn navigate_to_show(
         .child(show_controller.widget())
         .build();
 
+    window
+        .page_routes
+        .register(&page, SavedPage::Show(item_id.clone()));
+
     // Store the controller for later use
     window.show_details_page = Some(show_controller);
 
@@ -750,84 +883,7 @@ pub fn navigate_to_player(
     root: &adw::ApplicationWindow,
 ) {
     tracing::info!("Navigating to player for media: {}", media_id);
-
-    // Save the current page so we can return to it
-    if let Some(current_page) = window.navigation_view.visible_page() {
-        let page_title = current_page.title().to_string();
-        tracing::info!("Saving previous page before player: {}", page_title);
-        window.previous_page_before_player = Some(page_title);
-    }
-
-    // Save current window state before entering player
-    let (width, height) = root.default_size();
-    window.saved_window_size = Some((width, height));
-    window.was_maximized = root.is_maximized();
-    window.was_fullscreen = root.is_fullscreen();
-
-    // Hide window chrome for immersive viewing
-    window.content_header.set_visible(false);
-    window.sidebar_header.set_visible(false);
-    window.split_view.set_collapsed(true);
-    window
-        .content_toolbar
-        .set_top_bar_style(adw::ToolbarStyle::Flat);
-    window
-        .sidebar_toolbar
-        .set_top_bar_style(adw::ToolbarStyle::Flat);
-
-    // Create player page if not exists
-    if window.player_page.is_none() {
-        let db = std::sync::Arc::new(window.db.clone());
-        let sender_clone = sender.clone();
-        window.player_page = Some(
-            PlayerPage::builder()
-                .launch((Some(media_id.clone()), db, root.clone()))
-                .forward(sender.input_sender(), move |output| match output {
-                    crate::ui::pages::player::PlayerOutput::NavigateBack => {
-                        // Restore window chrome when leaving player
-                        sender_clone.input(MainWindowInput::RestoreWindowChrome);
-                        MainWindowInput::Navigate("back".to_string())
-                    }
-                    crate::ui::pages::player::PlayerOutput::MediaLoaded => {
-                        tracing::info!("Media loaded in player");
-                        MainWindowInput::Navigate("media_loaded".to_string())
-                    }
-                    crate::ui::pages::player::PlayerOutput::Error(msg) => {
-                        tracing::error!("Player error: {}", msg);
-                        // Show error toast - user can manually navigate back
-                        let toast_msg = format!("Playback error: {}", msg);
-                        MainWindowInput::ShowToast(toast_msg)
-                    }
-                    crate::ui::pages::player::PlayerOutput::ShowToast(msg) => {
-                        MainWindowInput::ShowToast(msg)
-                    }
-                    crate::ui::pages::player::PlayerOutput::WindowStateChanged {
-                        width,
-                        height,
-                    } => {
-                        // Player is requesting window size change for aspect ratio
-                        MainWindowInput::ResizeWindow(width, height)
-                    }
-                }),
-        );
-    } else if let Some(ref player_page) = window.player_page {
-        // Update existing page with new media
-        player_page
-            .sender()
-            .send(crate::ui::pages::player::PlayerInput::LoadMedia(
-                media_id.clone(),
-            ))
-            .unwrap();
-    }
-
-    // Push the player page to navigation
-    if let Some(ref player_page) = window.player_page {
-        let page = adw::NavigationPage::builder()
-            .title("Player")
-            .child(player_page.widget())
-            .build();
-        window.navigation_view.push(&page);
-    }
+    show_player(window, PlayerInput::LoadMedia(media_id), sender, root);
 }
 
 /// Navigate to player page with playback context
@@ -839,14 +895,32 @@ pub fn navigate_to_player_with_context(
     root: &adw::ApplicationWindow,
 ) {
     tracing::info!("Navigating to player with context for media: {}", media_id);
+    show_player(
+        window,
+        PlayerInput::LoadMediaWithContext { media_id, context },
+        sender,
+        root,
+    );
+}
 
-    // Save the current page so we can return to it
-    if let Some(current_page) = window.navigation_view.visible_page() {
-        let page_title = current_page.title().to_string();
-        tracing::info!("Saving previous page before player: {}", page_title);
-        window.previous_page_before_player = Some(page_title);
-    }
+/// Navigate to player page to play a network URL that is not a library item
+pub fn navigate_to_url(
+    window: &mut MainWindow,
+    url: String,
+    sender: &AsyncComponentSender<MainWindow>,
+    root: &adw::ApplicationWindow,
+) {
+    tracing::info!("Navigating to player for URL: {}", url);
+    show_player(window, PlayerInput::LoadUrl(url), sender, root);
+}
 
+/// Enter the immersive player, creating it on first use, and start loading
+fn show_player(
+    window: &mut MainWindow,
+    load: PlayerInput,
+    sender: &AsyncComponentSender<MainWindow>,
+    root: &adw::ApplicationWindow,
+) {
     // Save current window state before entering player
     let (width, height) = root.default_size();
     window.saved_window_size = Some((width, height));
@@ -865,73 +939,60 @@ pub fn navigate_to_player_with_context(
         .set_top_bar_style(adw::ToolbarStyle::Flat);
 
     // Create player page if not exists
-    if window.player_page.is_none() {
+    let player_page = window.player_page.get_or_insert_with(|| {
         let db = std::sync::Arc::new(window.db.clone());
         let sender_clone = sender.clone();
-        window.player_page = Some(
-            PlayerPage::builder()
-                .launch((Some(media_id.clone()), db, root.clone()))
-                .forward(sender.input_sender(), move |output| match output {
-                    crate::ui::pages::player::PlayerOutput::NavigateBack => {
-                        // Restore window chrome when leaving player
-                        sender_clone.input(MainWindowInput::RestoreWindowChrome);
-                        MainWindowInput::Navigate("back".to_string())
-                    }
-                    crate::ui::pages::player::PlayerOutput::MediaLoaded => {
-                        tracing::info!("Media loaded in player");
-                        MainWindowInput::Navigate("media_loaded".to_string())
-                    }
-                    crate::ui::pages::player::PlayerOutput::Error(msg) => {
-                        tracing::error!("Player error: {}", msg);
-                        // Show error toast - user can manually navigate back
-                        let toast_msg = format!("Playback error: {}", msg);
-                        MainWindowInput::ShowToast(toast_msg)
-                    }
-                    crate::ui::pages::player::PlayerOutput::ShowToast(msg) => {
-                        MainWindowInput::ShowToast(msg)
-                    }
-                    crate::ui::pages::player::PlayerOutput::WindowStateChanged {
-                        width,
-                        height,
-                    } => {
-                        // Player is requesting window size change for aspect ratio
-                        MainWindowInput::ResizeWindow(width, height)
-                    }
-                }),
-        );
-        // Send the context to the player
-        if let Some(ref player_page) = window.player_page {
-            player_page
-                .sender()
-                .send(
-                    crate::ui::pages::player::PlayerInput::LoadMediaWithContext {
-                        media_id: media_id.clone(),
-                        context,
-                    },
-                )
-                .unwrap();
-        }
-    } else if let Some(ref player_page) = window.player_page {
-        // Update existing page with new media and context
-        player_page
-            .sender()
-            .send(
-                crate::ui::pages::player::PlayerInput::LoadMediaWithContext {
-                    media_id: media_id.clone(),
-                    context,
+        PlayerPage::builder()
+            .launch((None, db, root.clone()))
+            .forward(sender.input_sender(), move |output| match output {
+                crate::ui::pages::player::PlayerOutput::NavigateBack => {
+                    // Restore window chrome when leaving player
+                    sender_clone.input(MainWindowInput::RestoreWindowChrome);
+                    MainWindowInput::Navigate("leave_player".to_string())
+                }
+                crate::ui::pages::player::PlayerOutput::MediaLoaded => {
+                    tracing::info!("Media loaded in player");
+                    MainWindowInput::Navigate("media_loaded".to_string())
+                }
+                crate::ui::pages::player::PlayerOutput::Error(msg) => {
+                    tracing::error!("Player error: {}", msg);
+                    // Show error toast - user can manually navigate back
+                    let toast_msg = format!("Playback error: {}", msg);
+                    MainWindowInput::ShowToast(toast_msg)
+                }
+                crate::ui::pages::player::PlayerOutput::ShowToast(msg) => {
+                    MainWindowInput::ShowToast(msg)
+                }
+                crate::ui::pages::player::PlayerOutput::WindowStateChanged { width, height } => {
+                    // Player is requesting window size change for aspect ratio
+                    MainWindowInput::ResizeWindow(width, height)
+                }
+                crate::ui::pages::player::PlayerOutput::Reauthenticate {
+                    source_id,
+                    source_name,
+                    source_type,
+                } => MainWindowInput::OpenReauthDialog {
+                    source_id,
+                    source_name,
+                    source_type,
                 },
-            )
-            .unwrap();
-    }
+                crate::ui::pages::player::PlayerOutput::Paused(media_id) => {
+                    MainWindowInput::PlaybackPaused(media_id)
+                }
+                crate::ui::pages::player::PlayerOutput::Playing(active) => {
+                    MainWindowInput::PlaybackActive(active)
+                }
+            })
+    });
+    player_page.emit(load);
 
     // Push the player page to navigation
-    if let Some(ref player_page) = window.player_page {
-        let page = adw::NavigationPage::builder()
-            .title("Player")
-            .child(player_page.widget())
-            .build();
-        window.navigation_view.push(&page);
-    }
+    let page = adw::NavigationPage::builder()
+        .title("Player")
+        .child(player_page.widget())
+        .build();
+    window.page_routes.register(&page, SavedPage::Player);
+    window.navigation_view.push(&page);
 }
 
 /// Helper function to create and show preferences dialog
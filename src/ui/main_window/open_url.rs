@@ -0,0 +1,86 @@
+use adw::prelude::*;
+use libadwaita as adw;
+use relm4::gtk;
+use relm4::prelude::AsyncComponentSender;
+
+use super::{MainWindow, MainWindowInput};
+use crate::services::config_service::CONFIG_SERVICE;
+use crate::services::core::url_playback::is_playable_url;
+
+/// Ask for a network URL to play, offering the recently opened ones
+pub fn present_open_url_dialog(
+    root: adw::ApplicationWindow,
+    sender: AsyncComponentSender<MainWindow>,
+) {
+    relm4::spawn_local(async move {
+        let recent_urls = CONFIG_SERVICE.get_config().await.ui.recent_urls;
+
+        let dialog = adw::AlertDialog::builder()
+            .heading("Open URL")
+            .body("Play a video file, stream or web page")
+            .close_response("cancel")
+            .default_response("open")
+            .build();
+        dialog.add_responses(&[("cancel", "_Cancel"), ("open", "_Play")]);
+        dialog.set_response_appearance("open", adw::ResponseAppearance::Suggested);
+        dialog.set_response_enabled("open", false);
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 12);
+        let entry = gtk::Entry::builder()
+            .placeholder_text("https://")
+            .activates_default(true)
+            .input_purpose(gtk::InputPurpose::Url)
+            .build();
+        {
+            let dialog = dialog.clone();
+            entry.connect_changed(move |entry| {
+                dialog.set_response_enabled("open", is_playable_url(entry.text().trim()));
+            });
+        }
+        content.append(&entry);
+
+        if !recent_urls.is_empty() {
+            let heading = gtk::Label::builder()
+                .label("Recent")
+                .halign(gtk::Align::Start)
+                .css_classes(["heading"])
+                .build();
+            content.append(&heading);
+
+            let list = gtk::ListBox::builder()
+                .selection_mode(gtk::SelectionMode::None)
+                .css_classes(["boxed-list"])
+                .build();
+            for url in &recent_urls {
+                let row = adw::ActionRow::builder()
+                    .title(url.as_str())
+                    .use_markup(false)
+                    .title_lines(1)
+                    .activatable(true)
+                    .build();
+                row.add_suffix(&gtk::Image::from_icon_name("media-playback-start-symbolic"));
+                list.append(&row);
+            }
+            {
+                let dialog = dialog.clone();
+                let sender = sender.clone();
+                list.connect_row_activated(move |_, row| {
+                    if let Some(url) = recent_urls.get(row.index() as usize) {
+                        sender.input(MainWindowInput::NavigateToUrl(url.clone()));
+                        dialog.close();
+                    }
+                });
+            }
+            content.append(&list);
+        }
+        dialog.set_extra_child(Some(&content));
+
+        dialog.connect_response(None, move |_, response| {
+            let url = entry.text().trim().to_string();
+            if response == "open" && is_playable_url(&url) {
+                sender.input(MainWindowInput::NavigateToUrl(url));
+            }
+        });
+        dialog.present(Some(&root));
+    });
+}
@@ -0,0 +1,386 @@
+//! Screensaver shown over the window after a while without input on the
+//! home page or with playback paused
+//!
+//! It cycles through backdrops from the library with a clock and, when
+//! playback is paused, what is playing. Any input closes it; the main window
+//! watches for input with [`watch_activity`] and decides when to start it.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Instant;
+
+use gtk::glib;
+use gtk::prelude::*;
+use relm4::gtk;
+use relm4::prelude::*;
+
+use super::MainWindowInput;
+use crate::db::connection::DatabaseConnection;
+use crate::models::{MediaItem, MediaItemId};
+use crate::services::core::MediaService;
+use crate::ui::shared::image_helpers::load_image_from_url;
+
+/// Backdrops picked for one run of the screensaver
+const BACKDROP_COUNT: u64 = 50;
+
+/// Seconds each backdrop is shown
+const BACKDROP_SECONDS: u32 = 20;
+
+/// Width backdrops are fetched at, the height follows the artwork
+const BACKDROP_WIDTH: i32 = 1920;
+
+/// Pointer movement in pixels that counts as input, smaller jitter doesn't
+const MOTION_THRESHOLD: f64 = 4.0;
+
+/// A backdrop and the title it belongs to
+#[derive(Debug, Clone)]
+pub struct Backdrop {
+    url: String,
+    title: String,
+}
+
+#[derive(Debug)]
+pub enum ScreensaverInput {
+    /// Show the screensaver, with the item paused on if there is one
+    Start {
+        now_playing: Option<MediaItemId>,
+    },
+    Stop,
+    Loaded {
+        run: u64,
+        backdrops: Vec<Backdrop>,
+        now_playing: Option<String>,
+    },
+    ShowNext,
+    BackdropLoaded {
+        run: u64,
+        texture: gtk::gdk::Texture,
+        title: String,
+    },
+    Tick,
+}
+
+#[derive(Debug)]
+pub struct Screensaver {
+    db: DatabaseConnection,
+    active: bool,
+    /// Counts the runs, so artwork loaded for an earlier one is dropped
+    run: u64,
+    backdrops: Vec<Backdrop>,
+    next_backdrop: usize,
+    title: String,
+    now_playing: Option<String>,
+    clock: String,
+    stack: gtk::Stack,
+    pictures: [gtk::Picture; 2],
+    front: usize,
+    timers: Vec<glib::SourceId>,
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for Screensaver {
+    type Init = DatabaseConnection;
+    type Input = ScreensaverInput;
+    type Output = ();
+
+    view! {
+        gtk::Overlay {
+            add_css_class: "screensaver",
+            set_cursor_from_name: Some("none"),
+            #[watch]
+            set_visible: model.active,
+
+            #[local_ref]
+            stack -> gtk::Stack {},
+
+            add_overlay = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_halign: gtk::Align::Start,
+                set_valign: gtk::Align::End,
+                set_margin_start: 48,
+                set_margin_bottom: 48,
+                set_spacing: 6,
+
+                gtk::Label {
+                    add_css_class: "screensaver-clock",
+                    set_halign: gtk::Align::Start,
+                    #[watch]
+                    set_label: &model.clock,
+                },
+
+                gtk::Label {
+                    add_css_class: "screensaver-now-playing",
+                    set_halign: gtk::Align::Start,
+                    set_ellipsize: gtk::pango::EllipsizeMode::End,
+                    #[watch]
+                    set_visible: model.now_playing.is_some(),
+                    #[watch]
+                    set_label: &format!("Paused · {}", model.now_playing.as_deref().unwrap_or_default()),
+                },
+
+                gtk::Label {
+                    add_css_class: "screensaver-title",
+                    set_halign: gtk::Align::Start,
+                    set_ellipsize: gtk::pango::EllipsizeMode::End,
+                    #[watch]
+                    set_label: &model.title,
+                },
+            },
+        }
+    }
+
+    fn init(
+        db: Self::Init,
+        root: Self::Root,
+        _sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let pictures = [(); 2].map(|_| {
+            gtk::Picture::builder()
+                .content_fit(gtk::ContentFit::Cover)
+                .can_shrink(true)
+                .build()
+        });
+        let stack = gtk::Stack::builder()
+            .transition_type(gtk::StackTransitionType::Crossfade)
+            .transition_duration(1500)
+            .build();
+        for picture in &pictures {
+            stack.add_child(picture);
+        }
+
+        let model = Self {
+            db,
+            active: false,
+            run: 0,
+            backdrops: Vec::new(),
+            next_backdrop: 0,
+            title: String::new(),
+            now_playing: None,
+            clock: String::new(),
+            stack,
+            pictures,
+            front: 0,
+            timers: Vec::new(),
+        };
+        let stack = &model.stack;
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            ScreensaverInput::Start { now_playing } => {
+                if self.active {
+                    return;
+                }
+                self.active = true;
+                self.run += 1;
+                self.update_clock();
+
+                let run = self.run;
+                let db = self.db.clone();
+                let sender_clone = sender.clone();
+                relm4::spawn(async move {
+                    let backdrops = MediaService::get_backdrop_items(&db, BACKDROP_COUNT)
+                        .await
+                        .unwrap_or_else(|e| {
+                            tracing::warn!("Failed to load screensaver backdrops: {}", e);
+                            Vec::new()
+                        })
+                        .into_iter()
+                        .filter_map(|item| {
+                            Some(Backdrop {
+                                url: item.backdrop_url?,
+                                title: item.title,
+                            })
+                        })
+                        .collect();
+                    let now_playing = match now_playing {
+                        Some(id) => MediaService::get_media_item(&db, &id)
+                            .await
+                            .ok()
+                            .flatten()
+                            .map(|item| now_playing_title(&item)),
+                        None => None,
+                    };
+                    sender_clone.input(ScreensaverInput::Loaded {
+                        run,
+                        backdrops,
+                        now_playing,
+                    });
+                });
+
+                let sender_clone = sender.clone();
+                self.timers.push(glib::timeout_add_seconds_local(
+                    BACKDROP_SECONDS,
+                    move || {
+                        sender_clone.input(ScreensaverInput::ShowNext);
+                        glib::ControlFlow::Continue
+                    },
+                ));
+                self.timers
+                    .push(glib::timeout_add_seconds_local(5, move || {
+                        sender.input(ScreensaverInput::Tick);
+                        glib::ControlFlow::Continue
+                    }));
+            }
+            ScreensaverInput::Stop => {
+                self.active = false;
+                for timer in self.timers.drain(..) {
+                    timer.remove();
+                }
+                self.backdrops.clear();
+                self.next_backdrop = 0;
+                self.title.clear();
+                self.now_playing = None;
+                for picture in &self.pictures {
+                    picture.set_paintable(None::<&gtk::gdk::Paintable>);
+                }
+            }
+            ScreensaverInput::Loaded {
+                run,
+                backdrops,
+                now_playing,
+            } => {
+                if run != self.run || !self.active {
+                    return;
+                }
+                self.backdrops = backdrops;
+                self.now_playing = now_playing;
+                sender.input(ScreensaverInput::ShowNext);
+            }
+            ScreensaverInput::ShowNext => {
+                let Some(backdrop) = self.backdrops.get(self.next_backdrop).cloned() else {
+                    return;
+                };
+                self.next_backdrop = (self.next_backdrop + 1) % self.backdrops.len();
+
+                let run = self.run;
+                relm4::spawn(async move {
+                    match load_image_from_url(&backdrop.url, BACKDROP_WIDTH, -1).await {
+                        Ok(texture) => sender.input(ScreensaverInput::BackdropLoaded {
+                            run,
+                            texture,
+                            title: backdrop.title,
+                        }),
+                        Err(e) => tracing::debug!("Failed to load screensaver backdrop: {}", e),
+                    }
+                });
+            }
+            ScreensaverInput::BackdropLoaded {
+                run,
+                texture,
+                title,
+            } => {
+                if run != self.run || !self.active {
+                    return;
+                }
+                // Fade from the shown picture to the other one
+                self.front = 1 - self.front;
+                let picture = &self.pictures[self.front];
+                picture.set_paintable(Some(&texture));
+                self.stack.set_visible_child(picture);
+                self.title = title;
+            }
+            ScreensaverInput::Tick => self.update_clock(),
+        }
+    }
+}
+
+impl Screensaver {
+    fn update_clock(&mut self) {
+        self.clock = chrono::Local::now().format("%H:%M").to_string();
+    }
+}
+
+/// What is playing, with the show for episodes
+fn now_playing_title(item: &MediaItem) -> String {
+    match item {
+        MediaItem::Episode(episode) => match &episode.show_title {
+            Some(show) => format!(
+                "{} · S{}E{} {}",
+                show, episode.season_number, episode.episode_number, episode.title
+            ),
+            None => episode.title.clone(),
+        },
+        _ => item.title().to_string(),
+    }
+}
+
+/// Track input on `window` in `last_activity`. While the screensaver is
+/// shown, the first input closes it and goes no further, so a key press
+/// meant to wake the screen doesn't also resume playback.
+pub fn watch_activity(
+    window: &impl IsA<gtk::Widget>,
+    last_activity: Rc<Cell<Instant>>,
+    active: Rc<Cell<bool>>,
+    sender: relm4::Sender<MainWindowInput>,
+) {
+    let on_input = Rc::new(move || {
+        last_activity.set(Instant::now());
+        if active.get() {
+            sender.emit(MainWindowInput::ExitScreensaver);
+            true
+        } else {
+            false
+        }
+    });
+
+    let keys = gtk::EventControllerKey::new();
+    keys.set_propagation_phase(gtk::PropagationPhase::Capture);
+    {
+        let on_input = on_input.clone();
+        keys.connect_key_pressed(move |_, _, _, _| {
+            if on_input() {
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        });
+    }
+    window.add_controller(keys);
+
+    let clicks = gtk::GestureClick::new();
+    clicks.set_button(0);
+    clicks.set_propagation_phase(gtk::PropagationPhase::Capture);
+    {
+        let on_input = on_input.clone();
+        clicks.connect_pressed(move |gesture, _, _, _| {
+            if on_input() {
+                gesture.set_state(gtk::EventSequenceState::Claimed);
+            }
+        });
+    }
+    window.add_controller(clicks);
+
+    let scroll = gtk::EventControllerScroll::new(gtk::EventControllerScrollFlags::BOTH_AXES);
+    scroll.set_propagation_phase(gtk::PropagationPhase::Capture);
+    {
+        let on_input = on_input.clone();
+        scroll.connect_scroll(move |_, _, _| {
+            if on_input() {
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        });
+    }
+    window.add_controller(scroll);
+
+    // Motion is also reported when widgets move under a still pointer, so
+    // only movement past a few pixels counts
+    let motion = gtk::EventControllerMotion::new();
+    motion.set_propagation_phase(gtk::PropagationPhase::Capture);
+    let last_position = Cell::new(None::<(f64, f64)>);
+    motion.connect_motion(move |_, x, y| {
+        let moved = last_position.get().is_none_or(|(last_x, last_y)| {
+            (x - last_x).abs() > MOTION_THRESHOLD || (y - last_y).abs() > MOTION_THRESHOLD
+        });
+        if moved {
+            last_position.set(Some((x, y)));
+            on_input();
+        }
+    });
+    window.add_controller(motion);
+}
@@ -0,0 +1,213 @@
+use adw::prelude::*;
+use libadwaita as adw;
+use relm4::gtk::glib;
+use relm4::prelude::AsyncComponentSender;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::{MainWindow, MainWindowInput};
+use crate::config::Config;
+use crate::db::connection::DatabaseConnection;
+use crate::db::repository::{
+    LibraryRepositoryImpl, MediaRepositoryImpl, PlaybackRepository, PlaybackRepositoryImpl,
+    Repository,
+};
+use crate::models::{LibraryId, MediaItemId};
+use crate::services::config_service::CONFIG_SERVICE;
+
+/// A page that is reopened at startup if it was open when the app closed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SavedPage {
+    Library(LibraryId),
+    Movie(MediaItemId),
+    Show(MediaItemId),
+    /// The player, restored as the page it was opened from
+    Player,
+}
+
+impl SavedPage {
+    pub fn from_config(value: &str) -> Option<Self> {
+        let (kind, id) = value.split_once(':')?;
+        match kind {
+            "library" => Some(Self::Library(LibraryId::new(id))),
+            "movie" => Some(Self::Movie(MediaItemId::new(id))),
+            "show" => Some(Self::Show(MediaItemId::new(id))),
+            _ => None,
+        }
+    }
+
+    pub fn to_config(&self) -> Option<String> {
+        match self {
+            Self::Library(id) => Some(format!("library:{}", id)),
+            Self::Movie(id) => Some(format!("movie:{}", id)),
+            Self::Show(id) => Some(format!("show:{}", id)),
+            Self::Player => None,
+        }
+    }
+}
+
+/// What each navigation page shows, for saving the open page on close
+#[derive(Debug, Clone, Default)]
+pub struct PageRoutes(Rc<RefCell<Vec<(glib::WeakRef<adw::NavigationPage>, SavedPage)>>>);
+
+impl PageRoutes {
+    pub fn register(&self, page: &adw::NavigationPage, route: SavedPage) {
+        let mut routes = self.0.borrow_mut();
+        routes.retain(|(page, _)| page.upgrade().is_some());
+        routes.push((page.downgrade(), route));
+    }
+
+    pub(super) fn route_of(&self, page: &adw::NavigationPage) -> Option<SavedPage> {
+        self.0
+            .borrow()
+            .iter()
+            .find(|(registered, _)| registered.upgrade().as_ref() == Some(page))
+            .map(|(_, route)| route.clone())
+    }
+
+    /// The page to reopen next time: the visible one, or the one the player
+    /// was opened from
+    fn page_to_restore(&self, navigation_view: &adw::NavigationView) -> Option<SavedPage> {
+        let visible = navigation_view.visible_page()?;
+        match self.route_of(&visible)? {
+            SavedPage::Player => navigation_view
+                .previous_page(&visible)
+                .and_then(|previous| self.route_of(&previous)),
+            route => Some(route),
+        }
+    }
+}
+
+/// Save the window state and open page as the window closes. This runs while
+/// the main loop is shutting down, so the configuration is written directly.
+pub fn save_on_close(
+    window: &adw::ApplicationWindow,
+    navigation_view: &adw::NavigationView,
+    routes: &PageRoutes,
+) {
+    let mut config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("Failed to load config to save window state: {}", e);
+            return;
+        }
+    };
+
+    // The player resizes the window to the video, so keep the size from
+    // before it was opened
+    let in_player = navigation_view
+        .visible_page()
+        .and_then(|page| routes.route_of(&page))
+        == Some(SavedPage::Player);
+    if !in_player {
+        let (width, height) = window.default_size();
+        config.ui.window_width = width;
+        config.ui.window_height = height;
+        config.ui.window_maximized = window.is_maximized();
+    }
+    config.ui.last_page = routes
+        .page_to_restore(navigation_view)
+        .and_then(|page| page.to_config());
+
+    if let Err(e) = config.save() {
+        tracing::error!("Failed to save window state: {}", e);
+    }
+}
+
+/// Apply the window size and maximized state saved on close
+pub fn restore_window_state(window: &adw::ApplicationWindow, config: &Config) {
+    if config.ui.window_width > 0 && config.ui.window_height > 0 {
+        window.set_default_size(config.ui.window_width, config.ui.window_height);
+    }
+    if config.ui.window_maximized {
+        window.maximize();
+    }
+}
+
+/// Reopen the page open when the app was last closed, if it still exists,
+/// and offer to resume playback that closing interrupted
+pub fn restore_session(db: DatabaseConnection, sender: AsyncComponentSender<MainWindow>) {
+    relm4::spawn_local(async move {
+        let config = CONFIG_SERVICE.get_config().await;
+
+        match config
+            .ui
+            .last_page
+            .as_deref()
+            .and_then(SavedPage::from_config)
+        {
+            Some(SavedPage::Library(library_id)) => {
+                let repo = LibraryRepositoryImpl::new(db.clone());
+                if let Ok(Some(_)) = repo.find_by_id(library_id.as_ref()).await {
+                    sender.input(MainWindowInput::NavigateToLibrary(library_id));
+                }
+            }
+            Some(SavedPage::Movie(item_id)) => {
+                let repo = MediaRepositoryImpl::new(db.clone());
+                if let Ok(Some(_)) = repo.find_by_id(item_id.as_ref()).await {
+                    sender.input(MainWindowInput::NavigateToMovie(item_id));
+                }
+            }
+            Some(SavedPage::Show(item_id)) => {
+                let repo = MediaRepositoryImpl::new(db.clone());
+                if let Ok(Some(_)) = repo.find_by_id(item_id.as_ref()).await {
+                    sender.input(MainWindowInput::NavigateToShow(item_id));
+                }
+            }
+            Some(SavedPage::Player) | None => {}
+        }
+
+        let Some(media_id) = config.ui.interrupted_playback.map(MediaItemId::new) else {
+            return;
+        };
+        if let Err(e) = CONFIG_SERVICE.clear_interrupted_playback().await {
+            tracing::error!("Failed to clear interrupted playback: {}", e);
+        }
+        if !config.ui.resume_prompt {
+            return;
+        }
+
+        // Nothing to resume if it was watched to the end
+        let progress = PlaybackRepositoryImpl::new(db.clone())
+            .find_by_media_id(media_id.as_ref())
+            .await;
+        if let Ok(Some(progress)) = progress
+            && progress.watched
+        {
+            return;
+        }
+        if let Ok(Some(media)) = MediaRepositoryImpl::new(db)
+            .find_by_id(media_id.as_ref())
+            .await
+        {
+            sender.input(MainWindowInput::OfferResume {
+                media_id,
+                title: media.title,
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_page_config_round_trip() {
+        for page in [
+            SavedPage::Library(LibraryId::new("lib-1")),
+            SavedPage::Movie(MediaItemId::new("plex:123")),
+            SavedPage::Show(MediaItemId::new("show-7")),
+        ] {
+            let value = page.to_config().unwrap();
+            assert_eq!(SavedPage::from_config(&value), Some(page));
+        }
+    }
+
+    #[test]
+    fn test_player_and_unknown_pages_are_not_saved() {
+        assert_eq!(SavedPage::Player.to_config(), None);
+        assert_eq!(SavedPage::from_config("sources"), None);
+        assert_eq!(SavedPage::from_config("episode:1"), None);
+    }
+}
@@ -3,8 +3,11 @@ use std::sync::Arc;
 use tokio::runtime::Runtime;
 
 use crate::db::connection::DatabaseConnection;
+use crate::services::core::BackupService;
+use crate::services::core::power_saver;
 use crate::workers::{
-    ConnectionMonitor, ConnectionMonitorInput, ConnectionMonitorOutput, PlaybackSyncWorker,
+    ConnectionMonitor, ConnectionMonitorInput, ConnectionMonitorOutput, DownloadWorker,
+    DownloadWorkerInput, ImagePrefetchInput, ImagePrefetchWorker, PlaybackSyncWorker,
     PlaybackSyncWorkerOutput, SearchWorker, SearchWorkerOutput, SyncWorker, SyncWorkerOutput,
     cache_cleanup_worker::{
         CacheCleanupInput, CacheCleanupOutput, CacheCleanupWorker, CleanupConfig,
@@ -22,6 +25,8 @@ pub struct Workers {
     pub playback_sync_worker: relm4::WorkerController<PlaybackSyncWorker>,
     pub search_worker: relm4::WorkerController<SearchWorker>,
     pub cache_cleanup_worker: relm4::WorkerController<CacheCleanupWorker>,
+    pub download_worker: relm4::WorkerController<DownloadWorker>,
+    pub image_prefetch_worker: relm4::WorkerController<ImagePrefetchWorker>,
 }
 
 /// Initialize all background workers
@@ -133,10 +138,35 @@ pub fn initialize_workers(
         runtime_handle.clone(),
     );
 
+    // Re-check connections right away when the network changes
+    ConnectionMonitor::watch_network_changes(connection_monitor.sender().clone());
+
+    // Apply metered-connection restrictions as the network monitor reports them
+    watch_metered_connection(sender.input_sender().clone());
+
+    // Switch playback to lighter settings in power saver mode or on low battery
+    watch_power_profile(sender.input_sender().clone());
+    watch_battery(sender.input_sender().clone());
+
+    // Back up the database and config on the configured schedule
+    relm4::spawn(BackupService::run_schedule(db.clone()));
+
     // Trigger an immediate initial check to populate connection types
     connection_monitor.emit(ConnectionMonitorInput::CheckAllSources);
 
+    // Initialize the DownloadWorker for offline downloads
+    let download_worker = DownloadWorker::builder()
+        .detach_worker(Arc::new(db.clone()))
+        .forward(sender.input_sender(), MainWindowInput::DownloadEvent);
+
+    // Initialize the ImagePrefetchWorker, which caches artwork after syncs
+    let image_prefetch_worker = ImagePrefetchWorker::builder()
+        .detach_worker(Arc::new(db.clone()))
+        .detach();
+
     // Initialize the SyncWorker
+    let download_sender = download_worker.sender().clone();
+    let prefetch_sender = image_prefetch_worker.sender().clone();
     let sync_worker = SyncWorker::builder()
         .detach_worker(Arc::new(db.clone()))
         .forward(sender.input_sender(), move |output| match output {
@@ -183,6 +213,12 @@ pub fn initialize_workers(
                 // The home page will be updated through data change events
                 // without forcing navigation away from the user's current page.
 
+                // Newly synced episodes may be due for auto-download
+                download_sender.emit(DownloadWorkerInput::ScheduleAutoDownloads);
+
+                // Cache the artwork shown first once the app is idle
+                prefetch_sender.emit(ImagePrefetchInput::SourceSynced(source_id));
+
                 // Trigger search index refresh after sync
                 MainWindowInput::Navigate("refresh_search_index".to_string())
             }
@@ -405,5 +441,83 @@ pub fn initialize_workers(
         playback_sync_worker,
         search_worker,
         cache_cleanup_worker,
+        download_worker,
+        image_prefetch_worker,
     }
 }
+
+/// Follow the metered state of the active connection, as reported by
+/// NetworkManager or the network portal
+fn watch_metered_connection(sender: relm4::Sender<MainWindowInput>) {
+    use relm4::gtk::gio;
+
+    let monitor = gio::NetworkMonitor::default();
+    sender.emit(MainWindowInput::MeteredChanged(
+        monitor.is_network_metered(),
+    ));
+    monitor.connect_network_metered_notify(move |monitor| {
+        sender.emit(MainWindowInput::MeteredChanged(
+            monitor.is_network_metered(),
+        ));
+    });
+}
+
+/// Follow the power saver mode of the system power profile
+fn watch_power_profile(sender: relm4::Sender<MainWindowInput>) {
+    use relm4::gtk::gio;
+
+    let monitor = gio::PowerProfileMonitor::dup_default();
+    sender.emit(MainWindowInput::PowerSaverChanged(
+        monitor.is_power_saver_enabled(),
+    ));
+    monitor.connect_power_saver_enabled_notify(move |monitor| {
+        sender.emit(MainWindowInput::PowerSaverChanged(
+            monitor.is_power_saver_enabled(),
+        ));
+    });
+}
+
+/// Follow the battery level of UPower's display device, which aggregates
+/// all batteries of the machine
+fn watch_battery(sender: relm4::Sender<MainWindowInput>) {
+    use relm4::gtk::gio;
+
+    fn battery_low(proxy: &gio::DBusProxy) -> bool {
+        let property = |name: &str| proxy.cached_property(name);
+        power_saver::is_battery_low(
+            property("IsPresent")
+                .and_then(|v| v.get::<bool>())
+                .unwrap_or(false),
+            property("State").and_then(|v| v.get::<u32>()).unwrap_or(0),
+            property("Percentage")
+                .and_then(|v| v.get::<f64>())
+                .unwrap_or(100.0),
+        )
+    }
+
+    gio::DBusProxy::for_bus(
+        gio::BusType::System,
+        gio::DBusProxyFlags::DO_NOT_AUTO_START,
+        None,
+        "org.freedesktop.UPower",
+        "/org/freedesktop/UPower/devices/DisplayDevice",
+        "org.freedesktop.UPower.Device",
+        None::<&gio::Cancellable>,
+        move |result| {
+            let proxy = match result {
+                Ok(proxy) => proxy,
+                Err(e) => {
+                    tracing::debug!("UPower unavailable, not following the battery: {}", e);
+                    return;
+                }
+            };
+            sender.emit(MainWindowInput::LowBatteryChanged(battery_low(&proxy)));
+            // The handler holds the proxy, keeping it alive for the session
+            let watched = proxy.clone();
+            proxy.connect_local("g-properties-changed", false, move |_| {
+                sender.emit(MainWindowInput::LowBatteryChanged(battery_low(&watched)));
+                None
+            });
+        },
+    );
+}
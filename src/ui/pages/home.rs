@@ -1,19 +1,26 @@
 use gtk::prelude::*;
-use relm4::factory::FactoryVecDeque;
+use relm4::factory::{DynamicIndex, FactoryVecDeque};
 use relm4::gtk;
 use relm4::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use tracing::{debug, error, info, trace};
 
+use crate::config::KidsConfig;
 use crate::db::connection::DatabaseConnection;
 use crate::db::entities::MediaItemModel;
 use crate::db::repository::{
     Repository,
     home_section_repository::{HomeSectionRepository, HomeSectionRepositoryImpl},
 };
-use crate::models::{HomeSectionType, HomeSectionWithModels, MediaItemId, SourceId};
+use crate::models::{HomeSectionType, HomeSectionWithModels, LibraryId, MediaItemId, SourceId};
+use crate::services::config_service::CONFIG_SERVICE;
+use crate::services::core::{
+    ContinueWatchingService, FavoritesService, HiddenItemsService, KidsProfileService,
+};
 use crate::ui::factories::media_card::{MediaCard, MediaCardInit, MediaCardInput, MediaCardOutput};
-use crate::ui::shared::broker::{BROKER, BrokerMessage};
+use crate::ui::pages::library::FilterState;
+use crate::ui::shared::broker::{BROKER, BrokerMessage, ConfigMessage, Subscription, Topic};
+use crate::ui::shared::undo_toast::UndoOffer;
 use crate::workers::{ImageLoader, ImageLoaderInput, ImageLoaderOutput, ImageRequest, ImageSize};
 
 #[derive(Debug, Clone)]
@@ -23,6 +30,30 @@ pub enum SectionLoadState {
     Failed(String), // Error message
 }
 
+/// Cards added to a row at a time, more following as it is scrolled
+const ROW_BATCH: usize = 12;
+
+/// Section id of the Watch Next row
+const WATCH_NEXT_ID: &str = "watch_next";
+
+/// Shown for a source whose rows can't be filtered by the kids profile
+const KIDS_PROFILE_ERROR: &str = "Couldn't apply the kids profile";
+
+/// Section id of a source's Favorites row, prefixed like the rest of its rows
+fn favorites_section_id(source_id: &SourceId) -> String {
+    format!("{}::favorites", source_id)
+}
+
+/// Wait after a progress update before rebuilding the Watch Next row, so a
+/// whole season marked watched rebuilds it once
+const WATCH_NEXT_REFRESH_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Cards of a row not added yet
+struct PendingCards {
+    cards_box: gtk::FlowBox,
+    cards: VecDeque<(MediaCardInit, Option<String>)>, // card and poster URL
+}
+
 pub struct HomePage {
     db: DatabaseConnection,
     sections: Vec<HomeSectionWithModels>,
@@ -35,6 +66,11 @@ pub struct HomePage {
     loading_containers: HashMap<SourceId, gtk::Box>,    // UI containers for loading/error states
     section_ui_containers: HashMap<String, gtk::Box>, // Track actual section UI containers by section_id
     load_in_progress: bool, // Track if a LoadData operation is currently in progress
+    row_limit: usize,       // Most items shown in a row
+    kids: KidsConfig,       // Kids profile, which leaves out rows and items
+    pending_cards: HashMap<String, PendingCards>, // section_id -> cards left to add
+    watch_next_refresh: Option<gtk::glib::SourceId>,
+    _broker_subscription: Subscription,
 }
 
 impl std::fmt::Debug for HomePage {
@@ -58,14 +94,56 @@ pub enum HomePageInput {
         source_id: SourceId,
         sections: Result<Vec<HomeSectionWithModels>, String>,
     },
+    /// Rebuild the Watch Next row from the database
+    RefreshWatchNext,
+    /// Rebuild the Favorites row of the source of a media item
+    RefreshFavoritesOf(MediaItemId),
+    /// Favorites row of a source loaded, `None` if it has no favorites
+    FavoritesLoaded {
+        source_id: SourceId,
+        section: Option<HomeSectionWithModels>,
+    },
+    /// Reload the rows of the source of a media item
+    ReloadSourceOf(MediaItemId),
+    /// Reload the rows of a source
+    ReloadSource(SourceId),
+    /// Watch Next items loaded
+    WatchNextLoaded(Vec<MediaItemModel>),
     /// Retry loading a specific source
     RetrySource(SourceId),
+    /// A row was scrolled near its last card
+    ShowMoreCards(String),
+    /// Open the library view of a row
+    SeeAll(String),
     /// Media item selected
     MediaItemSelected(MediaItemId),
     /// Mark media item as watched
     MarkWatched(MediaItemId),
     /// Mark media item as unwatched
     MarkUnwatched(MediaItemId),
+    /// Add or remove a media item from favorites
+    SetFavorite { id: MediaItemId, favorite: bool },
+    /// Leave a media item out of the home rows
+    Hide(MediaItemId),
+    /// Leave the movies and shows of a collection out of the home rows
+    HideCollection {
+        source_id: SourceId,
+        collection: String,
+    },
+    /// An action ran in the background, offer taking it back
+    OfferUndo(UndoOffer),
+    /// A card was hovered long enough to start its preview
+    PreviewRequested {
+        section_id: String,
+        id: MediaItemId,
+        index: DynamicIndex,
+    },
+    /// Preview stream resolved for a card of a section
+    PreviewReady {
+        section_id: String,
+        index: DynamicIndex,
+        url: String,
+    },
     /// Image loaded from worker
     ImageLoaded {
         id: String,
@@ -81,6 +159,11 @@ pub enum HomePageInput {
 pub enum HomePageOutput {
     /// Navigate to media item
     NavigateToMediaItem(MediaItemId),
+    /// Navigate to a library with the filters of a row
+    NavigateToLibraryView {
+        library_id: LibraryId,
+        filter: FilterState,
+    },
 }
 
 #[allow(unused_assignments)]
@@ -96,6 +179,8 @@ impl AsyncComponent for HomePage {
             set_orientation: gtk::Orientation::Vertical,
             set_spacing: 24,
             add_css_class: "background",
+            #[watch]
+            set_class_active: ("kids", model.kids.enabled),
 
             // Scrollable content
             gtk::ScrolledWindow {
@@ -181,6 +266,7 @@ impl AsyncComponent for HomePage {
                     ImageLoaderOutput::CacheCleared => HomePageInput::LoadData,
                 });
 
+        let config = CONFIG_SERVICE.get_config().await;
         let model = Self {
             db,
             sections: Vec::new(),
@@ -193,23 +279,20 @@ impl AsyncComponent for HomePage {
             loading_containers: HashMap::new(),
             section_ui_containers: HashMap::new(),
             load_in_progress: false,
+            row_limit: config.ui.home_row_items(),
+            kids: config.kids,
+            pending_cards: HashMap::new(),
+            watch_next_refresh: None,
+            // Subscribe to MessageBroker for config updates
+            _broker_subscription: BROKER.subscribe(
+                &[Topic::Config, Topic::Data, Topic::Source],
+                sender.input_sender(),
+                HomePageInput::BrokerMsg,
+            ),
         };
 
         let widgets = view_output!();
 
-        // Subscribe to MessageBroker for config updates
-        {
-            let broker_sender = sender.input_sender().clone();
-            relm4::spawn(async move {
-                let (tx, rx) = relm4::channel::<BrokerMessage>();
-                BROKER.subscribe("HomePage".to_string(), tx).await;
-
-                while let Some(msg) = rx.recv().await {
-                    broker_sender.send(HomePageInput::BrokerMsg(msg)).unwrap();
-                }
-            });
-        }
-
         // Load initial data
         sender.input(HomePageInput::LoadData);
 
@@ -220,7 +303,7 @@ impl AsyncComponent for HomePage {
         &mut self,
         msg: Self::Input,
         sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         match msg {
             HomePageInput::LoadData => {
@@ -240,80 +323,63 @@ impl AsyncComponent for HomePage {
                 // Clone database for async operations
                 let db = self.db.clone();
                 let sender_clone = sender.clone();
+                let row_limit = self.row_limit;
 
                 // First, load cached data immediately (offline-first)
                 relm4::spawn(async move {
                     info!("Loading cached home sections from database");
 
-                    // Load cached sections from HomeSectionRepository for instant display
-                    let section_repo = HomeSectionRepositoryImpl::new(db.clone());
-
                     // Get all sources to load sections for
                     use crate::db::repository::source_repository::SourceRepositoryImpl;
                     let source_repo = SourceRepositoryImpl::new(db.clone());
 
                     let mut _sources_processed = 0;
+                    let mut loaded = Vec::new();
+
+                    // Continue Watching and On Deck of every source share one row
+                    match ContinueWatchingService::watch_next(&db, row_limit).await {
+                        Ok(items) => sender_clone.input(HomePageInput::WatchNextLoaded(items)),
+                        Err(e) => error!("Failed to load Watch Next: {}", e),
+                    }
+
+                    // Items the user isn't interested in are left out of every row
+                    let hidden_ids = Self::load_hidden_ids(&db).await;
 
                     if let Ok(sources) = source_repo.find_all().await {
                         for source in sources {
                             let source_id = SourceId::new(source.id.clone());
                             _sources_processed += 1;
 
-                            // Load cached sections for this source
-                            if let Ok(persisted_sections) =
-                                section_repo.find_by_source_with_items(&source.id).await
-                                && !persisted_sections.is_empty()
-                            {
-                                // Convert to HomeSectionWithModels
-                                let mut sections = Vec::new();
-                                for (section_model, items) in persisted_sections {
-                                    if !items.is_empty() {
-                                        let section_type = match section_model.section_type.as_str()
-                                        {
-                                            "continue_watching" => {
-                                                HomeSectionType::ContinueWatching
-                                            }
-                                            "on_deck" => HomeSectionType::OnDeck,
-                                            "suggested" => HomeSectionType::Suggested,
-                                            "top_rated" => HomeSectionType::TopRated,
-                                            "trending" => HomeSectionType::Trending,
-                                            "recently_played" => HomeSectionType::RecentlyPlayed,
-                                            "recent_playlists" => HomeSectionType::RecentPlaylists,
-                                            s if s.starts_with("recently_added_") => {
-                                                let media_type = s
-                                                    .strip_prefix("recently_added_")
-                                                    .unwrap_or("unknown");
-                                                HomeSectionType::RecentlyAdded(
-                                                    media_type.to_string(),
-                                                )
-                                            }
-                                            custom => HomeSectionType::Custom(custom.to_string()),
-                                        };
-
-                                        sections.push(HomeSectionWithModels {
-                                            id: section_model.hub_identifier.clone(),
-                                            title: section_model.title.clone(),
-                                            section_type,
-                                            items,
-                                        });
-                                    }
-                                }
-
-                                if !sections.is_empty() {
-                                    info!(
-                                        "Displaying {} cached sections for source {}",
-                                        sections.len(),
-                                        source_id
-                                    );
-                                    sender_clone.input(HomePageInput::SourceSectionsLoaded {
-                                        source_id: source_id.clone(),
-                                        sections: Ok(sections),
-                                    });
-                                }
+                            let Some(hidden_ids) = &hidden_ids else {
+                                sender_clone.input(HomePageInput::SourceSectionsLoaded {
+                                    source_id,
+                                    sections: Err(KIDS_PROFILE_ERROR.to_string()),
+                                });
+                                continue;
+                            };
+                            let sections =
+                                Self::load_source_sections(&db, &source_id, hidden_ids).await;
+                            if !sections.is_empty() {
+                                loaded.push((source_id, sections));
                             }
                         }
                     }
 
+                    for (source_id, mut sections) in loaded {
+                        for section in &mut sections {
+                            section.items.truncate(row_limit);
+                        }
+                        info!(
+                            "Displaying {} cached sections for source {}",
+                            sections.len(),
+                            source_id
+                        );
+                        sender_clone.input(HomePageInput::SourceSectionsLoaded {
+                            source_id,
+                            sections: Ok(sections),
+                        });
+                    }
+
                     // Signal that we've finished processing all sources
                     sender_clone.input(HomePageInput::LoadDataComplete);
 
@@ -356,14 +422,21 @@ impl AsyncComponent for HomePage {
                             self.sections_container.remove(&container);
                         }
 
-                        // If we had cached sections, clear them before displaying fresh ones
+                        // If we had cached sections, clear them before displaying fresh ones,
+                        // keeping their place on the page
+                        let anchor = self.source_anchor(&source_id);
                         if had_cached_sections {
                             self.clear_source_sections(&source_id);
                         }
 
                         // Process and display sections for this source
+                        let section_ids: Vec<String> =
+                            sections.iter().map(|section| section.id.clone()).collect();
                         self.display_source_sections(&source_id, sections, &sender)
                             .await;
+                        if let Some(anchor) = anchor {
+                            self.place_sections_after(&section_ids, anchor);
+                        }
                     }
                     Err(error) => {
                         error!("Source {} failed with error: {}", source_id, error);
@@ -386,6 +459,74 @@ impl AsyncComponent for HomePage {
                 self.update_overall_loading_state();
             }
 
+            HomePageInput::RefreshWatchNext => {
+                self.watch_next_refresh = None;
+                let db = self.db.clone();
+                let row_limit = self.row_limit;
+                let sender_clone = sender.clone();
+                sender.oneshot_command(async move {
+                    match ContinueWatchingService::watch_next(&db, row_limit).await {
+                        Ok(items) => sender_clone.input(HomePageInput::WatchNextLoaded(items)),
+                        Err(e) => error!("Failed to refresh Watch Next: {}", e),
+                    }
+                });
+            }
+
+            HomePageInput::RefreshFavoritesOf(media_id) => {
+                let db = self.db.clone();
+                let sender_clone = sender.clone();
+                sender.oneshot_command(async move {
+                    let Some(source_id) = Self::source_of(&db, &media_id).await else {
+                        return;
+                    };
+                    let section = Self::load_favorites_section(&db, &source_id).await;
+                    sender_clone.input(HomePageInput::FavoritesLoaded { source_id, section });
+                });
+            }
+
+            HomePageInput::FavoritesLoaded { source_id, section } => {
+                self.display_favorites(&source_id, section, &sender).await;
+            }
+
+            HomePageInput::ReloadSourceOf(media_id) => {
+                let db = self.db.clone();
+                let sender_clone = sender.clone();
+                sender.oneshot_command(async move {
+                    if let Some(source_id) = Self::source_of(&db, &media_id).await {
+                        sender_clone.input(HomePageInput::ReloadSource(source_id));
+                    }
+                });
+            }
+
+            HomePageInput::ReloadSource(source_id) => {
+                let db = self.db.clone();
+                let sender_clone = sender.clone();
+                let row_limit = self.row_limit;
+                sender.oneshot_command(async move {
+                    let Some(hidden_ids) = Self::load_hidden_ids(&db).await else {
+                        sender_clone.input(HomePageInput::SourceSectionsLoaded {
+                            source_id,
+                            sections: Err(KIDS_PROFILE_ERROR.to_string()),
+                        });
+                        return;
+                    };
+                    let mut sections =
+                        Self::load_source_sections(&db, &source_id, &hidden_ids).await;
+                    for section in &mut sections {
+                        section.items.truncate(row_limit);
+                    }
+                    sender_clone.input(HomePageInput::SourceSectionsLoaded {
+                        source_id,
+                        sections: Ok(sections),
+                    });
+                });
+            }
+
+            HomePageInput::WatchNextLoaded(items) => {
+                debug!("Watch Next has {} items", items.len());
+                self.display_watch_next(items, &sender).await;
+            }
+
             HomePageInput::RetrySource(source_id) => {
                 info!("Retrying source {}", source_id);
 
@@ -430,6 +571,34 @@ impl AsyncComponent for HomePage {
                 });
             }
 
+            HomePageInput::ShowMoreCards(section_id) => {
+                self.show_more_cards(&section_id);
+            }
+
+            HomePageInput::SeeAll(section_id) => {
+                let Some(section) = self.sections.iter().find(|s| s.id == section_id) else {
+                    return;
+                };
+                let Some(filter) = FilterState::for_home_section(&section.section_type) else {
+                    return;
+                };
+                // Rows can mix libraries, so open the one most of it is from
+                let mut library_counts: HashMap<&str, usize> = HashMap::new();
+                for item in &section.items {
+                    *library_counts.entry(item.library_id.as_str()).or_default() += 1;
+                }
+                if let Some((library_id, _)) =
+                    library_counts.into_iter().max_by_key(|(_, count)| *count)
+                {
+                    sender
+                        .output(HomePageOutput::NavigateToLibraryView {
+                            library_id: LibraryId::new(library_id.to_string()),
+                            filter,
+                        })
+                        .unwrap();
+                }
+            }
+
             HomePageInput::MediaItemSelected(item_id) => {
                 debug!("Media item selected: {}", item_id);
                 sender
@@ -439,48 +608,153 @@ impl AsyncComponent for HomePage {
 
             HomePageInput::MarkWatched(media_id) => {
                 debug!("Marking item as watched: {}", media_id);
+                use crate::services::commands::media_commands::MarkWatchedCommand;
+
+                let cmd = MarkWatchedCommand {
+                    db: self.db.clone(),
+                    media_id,
+                };
+
+                // Command already broadcasts via BROKER
+                let input_sender = sender.input_sender().clone();
+                sender.oneshot_command(async move {
+                    if let Some(offer) =
+                        UndoOffer::run(cmd, "Marked as watched", "Failed to mark item as watched")
+                            .await
+                    {
+                        input_sender.emit(HomePageInput::OfferUndo(offer));
+                    }
+                });
+            }
+
+            HomePageInput::MarkUnwatched(media_id) => {
+                debug!("Marking item as unwatched: {}", media_id);
                 let db = self.db.clone();
                 let media_id_clone = media_id.clone();
 
                 sender.oneshot_command(async move {
                     use crate::services::commands::Command;
-                    use crate::services::commands::media_commands::MarkWatchedCommand;
+                    use crate::services::commands::media_commands::MarkUnwatchedCommand;
 
-                    let cmd = MarkWatchedCommand {
+                    let cmd = MarkUnwatchedCommand {
                         db,
                         media_id: media_id_clone,
                     };
 
                     if let Err(e) = cmd.execute().await {
-                        tracing::error!("Failed to mark item as watched: {}", e);
+                        tracing::error!("Failed to mark item as unwatched: {}", e);
                     }
 
                     // Command already broadcasts via BROKER
                 });
             }
 
-            HomePageInput::MarkUnwatched(media_id) => {
-                debug!("Marking item as unwatched: {}", media_id);
+            HomePageInput::Hide(media_id) => {
+                debug!("Hiding item from home: {}", media_id);
+                use crate::services::commands::media_commands::SetHiddenCommand;
+
+                let cmd = SetHiddenCommand {
+                    db: self.db.clone(),
+                    media_id,
+                    hidden: true,
+                };
+
+                let input_sender = sender.input_sender().clone();
+                sender.oneshot_command(async move {
+                    if let Some(offer) =
+                        UndoOffer::run(cmd, "Hidden from Home", "Failed to hide item").await
+                    {
+                        input_sender.emit(HomePageInput::OfferUndo(offer));
+                    }
+                });
+            }
+
+            HomePageInput::HideCollection {
+                source_id,
+                collection,
+            } => {
+                debug!("Hiding collection from home: {}", collection);
+                use crate::services::commands::media_commands::SetCollectionHiddenCommand;
+
+                let cmd = SetCollectionHiddenCommand {
+                    db: self.db.clone(),
+                    source_id,
+                    collection,
+                    hidden: true,
+                };
+
+                let input_sender = sender.input_sender().clone();
+                sender.oneshot_command(async move {
+                    if let Some(offer) = UndoOffer::run(
+                        cmd,
+                        "Collection hidden from Home",
+                        "Failed to hide collection",
+                    )
+                    .await
+                    {
+                        input_sender.emit(HomePageInput::OfferUndo(offer));
+                    }
+                });
+            }
+
+            HomePageInput::OfferUndo(offer) => offer.present(root),
+
+            HomePageInput::SetFavorite { id, favorite } => {
+                debug!("Setting favorite={} for item: {}", favorite, id);
                 let db = self.db.clone();
-                let media_id_clone = media_id.clone();
 
                 sender.oneshot_command(async move {
                     use crate::services::commands::Command;
-                    use crate::services::commands::media_commands::MarkUnwatchedCommand;
+                    use crate::services::commands::media_commands::SetFavoriteCommand;
 
-                    let cmd = MarkUnwatchedCommand {
+                    let cmd = SetFavoriteCommand {
                         db,
-                        media_id: media_id_clone,
+                        media_id: id,
+                        favorite,
                     };
 
                     if let Err(e) = cmd.execute().await {
-                        tracing::error!("Failed to mark item as unwatched: {}", e);
+                        tracing::error!("Failed to update favorite: {}", e);
                     }
 
                     // Command already broadcasts via BROKER
                 });
             }
 
+            HomePageInput::PreviewRequested {
+                section_id,
+                id,
+                index,
+            } => {
+                let db = self.db.clone();
+                let sender_clone = sender.clone();
+                sender.oneshot_command(async move {
+                    match crate::services::core::previews::preview_url(&db, &id).await {
+                        Ok(Some(url)) => sender_clone.input(HomePageInput::PreviewReady {
+                            section_id,
+                            index,
+                            url,
+                        }),
+                        Ok(None) => {}
+                        Err(e) => debug!("Failed to get preview for {}: {}", id, e),
+                    }
+                });
+            }
+
+            HomePageInput::PreviewReady {
+                section_id,
+                index,
+                url,
+            } => {
+                // Sections are rebuilt on reload, so the card may be gone
+                let index = index.current_index();
+                if let Some(factory) = self.section_factories.get(&section_id)
+                    && index < factory.len()
+                {
+                    factory.send(index, MediaCardInput::PlayPreview(url));
+                }
+            }
+
             HomePageInput::ImageLoaded { id, texture } => {
                 trace!("Image loaded for item: {}", id);
                 // Find the section and card index for this image
@@ -506,12 +780,14 @@ impl AsyncComponent for HomePage {
             }
             HomePageInput::BrokerMsg(msg) => {
                 match msg {
-                    BrokerMessage::Config(_) => {
-                        // Home page might reload sections if config changes affect display
-                        // For now, we'll just log the config update
+                    BrokerMessage::Config(ConfigMessage::Updated { config }) => {
                         debug!("Home page received config update");
-                        // Could potentially reload sections if display preferences changed
-                        // sender.input(HomePageInput::LoadData);
+                        let row_limit = config.ui.home_row_items();
+                        if row_limit != self.row_limit || config.kids != self.kids {
+                            self.row_limit = row_limit;
+                            self.kids = config.kids.clone();
+                            sender.input(HomePageInput::LoadData);
+                        }
                     }
                     BrokerMessage::Source(source_msg) => match source_msg {
                         crate::ui::shared::broker::SourceMessage::SyncCompleted {
@@ -538,9 +814,50 @@ impl AsyncComponent for HomePage {
                                 "Playback progress updated for media {}: watched={}",
                                 media_id, watched
                             );
-                            // Don't reload here - progress updates happen while watching video
-                            // and the home page isn't even visible. We'll reload when navigating
-                            // back to the home page instead.
+                            // Only Watch Next follows progress, the other rows stay put
+                            if let Some(handle) = self.watch_next_refresh.take() {
+                                handle.remove();
+                            }
+                            let sender_clone = sender.clone();
+                            self.watch_next_refresh = Some(gtk::glib::timeout_add_local_once(
+                                WATCH_NEXT_REFRESH_DELAY,
+                                move || sender_clone.input(HomePageInput::RefreshWatchNext),
+                            ));
+                        }
+                        crate::ui::shared::broker::DataMessage::FavoriteChanged {
+                            media_id,
+                            favorite,
+                        } => {
+                            debug!("Favorite changed for media {}: {}", media_id, favorite);
+                            // Update the hearts in place, only the Favorites row is rebuilt
+                            self.set_card_favorite(&media_id, favorite);
+                            sender.input(HomePageInput::RefreshFavoritesOf(MediaItemId::new(
+                                media_id,
+                            )));
+                        }
+                        crate::ui::shared::broker::DataMessage::HiddenItemsChanged {
+                            media_id,
+                            hidden,
+                        } => {
+                            if hidden {
+                                self.remove_cards(&media_id);
+                            } else {
+                                // There is no telling which rows it was taken out of
+                                sender.input(HomePageInput::ReloadSourceOf(MediaItemId::new(
+                                    media_id,
+                                )));
+                                sender.input(HomePageInput::RefreshWatchNext);
+                            }
+                        }
+                        crate::ui::shared::broker::DataMessage::HiddenCollectionChanged {
+                            source_id,
+                            ..
+                        } => {
+                            sender.input(HomePageInput::ReloadSource(SourceId::new(source_id)));
+                            sender.input(HomePageInput::RefreshWatchNext);
+                        }
+                        crate::ui::shared::broker::DataMessage::MediaDeleted { media_id } => {
+                            self.remove_cards(&media_id);
                         }
                         _ => {
                             // Ignore other data messages
@@ -553,13 +870,6 @@ impl AsyncComponent for HomePage {
             }
         }
     }
-
-    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
-        // Unsubscribe from MessageBroker
-        relm4::spawn(async move {
-            BROKER.unsubscribe("HomePage").await;
-        });
-    }
 }
 
 impl HomePage {
@@ -643,6 +953,289 @@ impl HomePage {
         sections: Vec<HomeSectionWithModels>,
         sender: &AsyncComponentSender<Self>,
     ) {
+        let displayed = self.display_sections(sections, sender).await;
+        info!(
+            "Displayed {} sections for source {} (filtered from {})",
+            displayed,
+            source_id,
+            self.sections.len()
+        );
+    }
+
+    /// Replace the Watch Next row, keeping it at the top
+    async fn display_watch_next(
+        &mut self,
+        items: Vec<MediaItemModel>,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        self.clear_section(WATCH_NEXT_ID);
+        let section = HomeSectionWithModels {
+            id: WATCH_NEXT_ID.to_string(),
+            title: "Watch Next".to_string(),
+            section_type: HomeSectionType::ContinueWatching,
+            items,
+        };
+        self.display_sections(vec![section], sender).await;
+
+        if let Some(container) = self.section_ui_containers.get(WATCH_NEXT_ID) {
+            self.sections_container
+                .reorder_child_after(container, None::<&gtk::Widget>);
+        }
+        // The section lives outside of the sources' lists
+        if let Some(index) = self.sections.iter().position(|s| s.id == WATCH_NEXT_ID) {
+            let section = self.sections.remove(index);
+            self.sections.insert(0, section);
+        }
+    }
+
+    /// Replace the Favorites row of a source, in front of its other rows
+    async fn display_favorites(
+        &mut self,
+        source_id: &SourceId,
+        section: Option<HomeSectionWithModels>,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        let section_id = favorites_section_id(source_id);
+        let anchor = self.source_anchor(source_id);
+        self.clear_section(&section_id);
+
+        let Some(mut section) = section else {
+            return;
+        };
+        section.items.truncate(self.row_limit);
+        self.display_sections(vec![section], sender).await;
+        if let Some(anchor) = anchor {
+            self.place_sections_after(&[section_id], anchor);
+        }
+    }
+
+    /// The widget just before the first row of a source, `None` if the source
+    /// has no rows shown
+    fn source_anchor(&self, source_id: &SourceId) -> Option<Option<gtk::Widget>> {
+        let prefix = format!("{}::", source_id);
+        let source_containers: Vec<&gtk::Box> = self
+            .section_ui_containers
+            .iter()
+            .filter(|(section_id, _)| section_id.starts_with(&prefix))
+            .map(|(_, container)| container)
+            .collect();
+
+        let mut child = self.sections_container.first_child();
+        while let Some(widget) = child {
+            if source_containers
+                .iter()
+                .any(|container| container.upcast_ref::<gtk::Widget>() == &widget)
+            {
+                return Some(widget.prev_sibling());
+            }
+            child = widget.next_sibling();
+        }
+        None
+    }
+
+    /// Move the rows of `section_ids`, in order, to just after `anchor`
+    fn place_sections_after(&self, section_ids: &[String], mut anchor: Option<gtk::Widget>) {
+        for section_id in section_ids {
+            if let Some(container) = self.section_ui_containers.get(section_id) {
+                self.sections_container
+                    .reorder_child_after(container, anchor.as_ref());
+                anchor = Some(container.clone().upcast());
+            }
+        }
+    }
+
+    /// Show the favorite state of an item on all of its cards
+    fn set_card_favorite(&mut self, media_id: &str, favorite: bool) {
+        for factory in self.section_factories.values() {
+            for (index, card) in factory.iter().enumerate() {
+                if card.item().id == media_id {
+                    factory.send(index, MediaCardInput::SetFavorite(favorite));
+                }
+            }
+        }
+        for pending in self.pending_cards.values_mut() {
+            for (card, _) in pending.cards.iter_mut() {
+                if card.item.id == media_id {
+                    card.favorite = favorite;
+                }
+            }
+        }
+    }
+
+    /// Take the cards of an item, or of a show's episodes, out of every row
+    fn remove_cards(&mut self, media_id: &str) {
+        let matches = |item: &MediaItemModel| {
+            item.id == media_id || item.parent_id.as_deref() == Some(media_id)
+        };
+
+        let mut emptied = Vec::new();
+        for (section_id, factory) in self.section_factories.iter_mut() {
+            let mut guard = factory.guard();
+            let removed: Vec<usize> = guard
+                .iter()
+                .enumerate()
+                .filter(|(_, card)| matches(card.item()))
+                .map(|(index, _)| index)
+                .collect();
+
+            for index in removed.into_iter().rev() {
+                if let Some(card) = guard.remove(index) {
+                    let tracking_key = format!("{}::{}", section_id, card.item().id);
+                    if self.image_requests.remove(&tracking_key).is_some() {
+                        let _ = self
+                            .image_loader
+                            .sender()
+                            .send(ImageLoaderInput::CancelLoad { id: tracking_key });
+                    }
+                }
+            }
+            // Cards after the removed ones moved up
+            for (index, card) in guard.iter().enumerate() {
+                let tracking_key = format!("{}::{}", section_id, card.item().id);
+                if let Some((_, card_idx)) = self.image_requests.get_mut(&tracking_key) {
+                    *card_idx = index;
+                }
+            }
+
+            let shown = guard.len();
+            if let Some(pending) = self.pending_cards.get_mut(section_id) {
+                pending.cards.retain(|(card, _)| !matches(&card.item));
+                pending.cards_box.set_min_children_per_line(shown as u32);
+                pending.cards_box.set_max_children_per_line(shown as u32);
+                if shown == 0 && pending.cards.is_empty() {
+                    emptied.push(section_id.clone());
+                }
+            }
+        }
+
+        for section in &mut self.sections {
+            section.items.retain(|item| !matches(item));
+        }
+        for section_id in emptied {
+            self.clear_section(&section_id);
+        }
+    }
+
+    /// Source of a media item, for rebuilding rows of that source
+    async fn source_of(db: &DatabaseConnection, media_id: &MediaItemId) -> Option<SourceId> {
+        use crate::db::repository::media_repository::MediaRepositoryImpl;
+        match MediaRepositoryImpl::new(db.clone())
+            .find_by_id(media_id.as_ref())
+            .await
+        {
+            Ok(Some(item)) => Some(SourceId::new(item.source_id)),
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to look up media item {}: {}", media_id, e);
+                None
+            }
+        }
+    }
+
+    /// Rows of a source as last synced, leaving out hidden items. The kids
+    /// profile only keeps the rows of recently added items.
+    async fn load_source_sections(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        hidden_ids: &HashSet<String>,
+    ) -> Vec<HomeSectionWithModels> {
+        let kids = CONFIG_SERVICE.get_config().await.kids.enabled;
+
+        // Load cached sections from HomeSectionRepository for instant display
+        let section_repo = HomeSectionRepositoryImpl::new(db.clone());
+        let mut sections = Vec::new();
+        if let Ok(persisted_sections) = section_repo
+            .find_by_source_with_items(source_id.as_str())
+            .await
+        {
+            // Convert to HomeSectionWithModels
+            for (section_model, items) in persisted_sections.into_iter().filter(|(section, _)| {
+                let section_type = section.section_type.as_str();
+                if kids {
+                    section_type.starts_with("recently_added_")
+                } else {
+                    !matches!(section_type, "continue_watching" | "on_deck")
+                }
+            }) {
+                let items: Vec<MediaItemModel> = items
+                    .into_iter()
+                    .filter(|item| !HiddenItemsService::is_hidden(item, hidden_ids))
+                    .collect();
+                if !items.is_empty() {
+                    let section_type = match section_model.section_type.as_str() {
+                        "suggested" => HomeSectionType::Suggested,
+                        "top_rated" => HomeSectionType::TopRated,
+                        "trending" => HomeSectionType::Trending,
+                        "recently_played" => HomeSectionType::RecentlyPlayed,
+                        "recent_playlists" => HomeSectionType::RecentPlaylists,
+                        "favorites" => HomeSectionType::Favorites,
+                        s if s.starts_with("recently_added_") => {
+                            let media_type = s.strip_prefix("recently_added_").unwrap_or("unknown");
+                            HomeSectionType::RecentlyAdded(media_type.to_string())
+                        }
+                        custom => HomeSectionType::Custom(custom.to_string()),
+                    };
+
+                    sections.push(HomeSectionWithModels {
+                        id: section_model.hub_identifier.clone(),
+                        title: section_model.title.clone(),
+                        section_type,
+                        items,
+                    });
+                }
+            }
+        }
+
+        // Favorites come from the local table, shown first
+        if let Some(favorites) = Self::load_favorites_section(db, source_id).await {
+            sections.insert(0, favorites);
+        }
+
+        sections
+    }
+
+    /// Hidden items to leave out of the rows. `None` when they can't be
+    /// looked up while the kids profile is on, as then no rows are shown
+    /// rather than unfiltered ones.
+    async fn load_hidden_ids(db: &DatabaseConnection) -> Option<HashSet<String>> {
+        match HiddenItemsService::hidden_ids(db).await {
+            Ok(ids) => Some(ids),
+            Err(e) => {
+                error!("Failed to load hidden items: {}", e);
+                (!CONFIG_SERVICE.get_config().await.kids.enabled).then(HashSet::new)
+            }
+        }
+    }
+
+    /// The Favorites row of a source, if it has any the kids profile doesn't
+    /// leave out
+    async fn load_favorites_section(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+    ) -> Option<HomeSectionWithModels> {
+        match FavoritesService::get_favorites_for_source(db, source_id).await {
+            Ok(mut items) => {
+                KidsProfileService::retain_allowed(db, &mut items).await;
+                (!items.is_empty()).then(|| HomeSectionWithModels {
+                    id: favorites_section_id(source_id),
+                    title: "Favorites".to_string(),
+                    section_type: HomeSectionType::Favorites,
+                    items,
+                })
+            }
+            Err(e) => {
+                error!("Failed to load favorites for {}: {}", source_id, e);
+                None
+            }
+        }
+    }
+
+    /// Add rows for the non-empty of `sections`, returning how many
+    async fn display_sections(
+        &mut self,
+        sections: Vec<HomeSectionWithModels>,
+        sender: &AsyncComponentSender<Self>,
+    ) -> usize {
         // Filter out empty sections before processing
         let non_empty_sections: Vec<HomeSectionWithModels> = sections
             .into_iter()
@@ -673,6 +1266,14 @@ impl HomePage {
             std::collections::HashMap::new()
         };
 
+        let favorite_ids = match FavoritesService::favorite_ids(&self.db).await {
+            Ok(ids) => ids,
+            Err(e) => {
+                debug!("Failed to fetch favorites: {}", e);
+                std::collections::HashSet::new()
+            }
+        };
+
         // Collect parent show IDs for episodes
         let episodes_with_parents: Vec<(MediaItemModel, String)> = non_empty_sections
             .iter()
@@ -747,6 +1348,22 @@ impl HomePage {
             title_label.add_css_class("title-2");
             header_box.append(&title_label);
 
+            // Rows that match a library view link to it
+            if FilterState::for_home_section(&section.section_type).is_some() {
+                let see_all_button = gtk::Button::builder()
+                    .label("See All")
+                    .tooltip_text("Show the whole row in the library")
+                    .valign(gtk::Align::Center)
+                    .build();
+                see_all_button.add_css_class("flat");
+                let sender = sender.clone();
+                let section_id = section.id.clone();
+                see_all_button.connect_clicked(move |_| {
+                    sender.input(HomePageInput::SeeAll(section_id.clone()));
+                });
+                header_box.append(&see_all_button);
+            }
+
             // Scroll navigation buttons
             let scroll_left_button = gtk::Button::builder()
                 .icon_name("go-previous-symbolic")
@@ -779,8 +1396,8 @@ impl HomePage {
             let cards_box = gtk::FlowBox::builder()
                 .orientation(gtk::Orientation::Horizontal)
                 .column_spacing(12)
-                .min_children_per_line(section.items.len() as u32) // Set to actual number of items
-                .max_children_per_line(section.items.len() as u32) // Force single row with all items
+                .min_children_per_line(1) // Kept at the number of cards added
+                .max_children_per_line(1) // to force a single row
                 .selection_mode(gtk::SelectionMode::None)
                 .valign(gtk::Align::Start)
                 .homogeneous(false)
@@ -788,140 +1405,141 @@ impl HomePage {
 
             // Create factory for this section
             let sender_input = sender.input_sender();
-            let mut factory = FactoryVecDeque::<MediaCard>::builder()
+            let section_id = section.id.clone();
+            let factory = FactoryVecDeque::<MediaCard>::builder()
                 .launch(cards_box.clone())
-                .forward(sender_input, |output| match output {
+                .forward(sender_input, move |output| match output {
                     MediaCardOutput::Clicked(id) => HomePageInput::MediaItemSelected(id),
                     MediaCardOutput::Play(id) => HomePageInput::MediaItemSelected(id),
                     MediaCardOutput::GoToShow(id) => HomePageInput::MediaItemSelected(id),
                     MediaCardOutput::MarkWatched(id) => HomePageInput::MarkWatched(id),
                     MediaCardOutput::MarkUnwatched(id) => HomePageInput::MarkUnwatched(id),
+                    MediaCardOutput::Hide(id) => HomePageInput::Hide(id),
+                    MediaCardOutput::HideCollection {
+                        source_id,
+                        collection,
+                    } => HomePageInput::HideCollection {
+                        source_id,
+                        collection,
+                    },
+                    MediaCardOutput::PreviewRequested { id, index } => {
+                        HomePageInput::PreviewRequested {
+                            section_id: section_id.clone(),
+                            id,
+                            index,
+                        }
+                    }
+                    MediaCardOutput::SetFavorite { id, favorite } => {
+                        HomePageInput::SetFavorite { id, favorite }
+                    }
                 });
 
-            // Add items to factory and queue image loads
-            {
-                let mut guard = factory.guard();
-                for (idx, model) in section.items.iter().enumerate() {
-                    // Items are already MediaItemModel
-                    let item_id = model.id.clone();
-
-                    // Determine if we should show progress
-                    let show_progress =
-                        matches!(section.section_type, HomeSectionType::ContinueWatching);
-
-                    // Use the pre-fetched playback progress data
-                    let (watched, progress_percent) =
-                        if let Some(progress) = playback_progress_map.get(&model.id) {
-                            (progress.watched, progress.get_progress_percentage() as f64)
-                        } else {
-                            (false, 0.0) // No progress record means unwatched
-                        };
-
-                    // For episodes, we want to show the show poster instead
-                    let mut display_item = model.clone();
-                    if model.media_type == "episode" {
-                        // Check if we have the parent show data
-                        if let Some(parent_id) = &model.parent_id {
-                            if let Some(parent_show) = parent_shows_map.get(parent_id) {
-                                // Use the show's poster instead of episode thumbnail
-                                display_item.poster_url = parent_show.poster_url.clone();
-
-                                // Store original episode title in metadata for subtitle display
-                                let episode_info = if let (Some(season), Some(episode)) =
-                                    (model.season_number, model.episode_number)
-                                {
-                                    format!("S{}E{} - {}", season, episode, model.title)
-                                } else {
-                                    model.title.clone()
-                                };
-
-                                // Update title to show title
-                                display_item.title = parent_show.title.clone();
-
-                                // Store episode info in metadata for subtitle display
-                                let mut metadata_obj = if let Some(metadata) = &model.metadata {
-                                    serde_json::from_value::<
-                                        serde_json::Map<String, serde_json::Value>,
-                                    >(metadata.clone())
-                                    .unwrap_or_else(|_| serde_json::Map::new())
-                                } else {
-                                    serde_json::Map::new()
-                                };
-
-                                metadata_obj.insert(
-                                    "episode_subtitle".to_string(),
-                                    serde_json::Value::String(episode_info),
-                                );
-                                display_item.metadata =
-                                    Some(serde_json::to_value(metadata_obj).unwrap());
+            // Build the row's cards, added to the factory as it is scrolled
+            let mut cards = VecDeque::new();
+            for model in &section.items {
+                // Determine if we should show progress
+                let show_progress =
+                    matches!(section.section_type, HomeSectionType::ContinueWatching);
+
+                // Use the pre-fetched playback progress data
+                let (watched, progress_percent) =
+                    if let Some(progress) = playback_progress_map.get(&model.id) {
+                        (progress.watched, progress.get_progress_percentage() as f64)
+                    } else {
+                        (false, 0.0) // No progress record means unwatched
+                    };
+
+                // For episodes, we want to show the show poster instead
+                let mut display_item = model.clone();
+                if model.media_type == "episode" {
+                    // Check if we have the parent show data
+                    if let Some(parent_id) = &model.parent_id {
+                        if let Some(parent_show) = parent_shows_map.get(parent_id) {
+                            // Use the show's poster instead of episode thumbnail
+                            display_item.poster_url = parent_show.poster_url.clone();
+
+                            // Store original episode title in metadata for subtitle display
+                            let episode_info = if let (Some(season), Some(episode)) =
+                                (model.season_number, model.episode_number)
+                            {
+                                format!("S{}E{} - {}", season, episode, model.title)
                             } else {
-                                error!(
-                                    "Parent show not found for episode {} with parent_id {:?}",
-                                    model.id, parent_id
-                                );
-                                // Skip this episode if we can't find its parent show
-                                continue;
-                            }
+                                model.title.clone()
+                            };
+
+                            // Update title to show title
+                            display_item.title = parent_show.title.clone();
+
+                            // Store episode info in metadata for subtitle display
+                            let mut metadata_obj = if let Some(metadata) = &model.metadata {
+                                serde_json::from_value::<
+                                    serde_json::Map<String, serde_json::Value>,
+                                >(metadata.clone())
+                                .unwrap_or_else(|_| serde_json::Map::new())
+                            } else {
+                                serde_json::Map::new()
+                            };
+
+                            metadata_obj.insert(
+                                "episode_subtitle".to_string(),
+                                serde_json::Value::String(episode_info),
+                            );
+                            display_item.metadata =
+                                Some(serde_json::to_value(metadata_obj).unwrap());
                         } else {
-                            error!("Episode {} has no parent_id set!", model.id);
-                            // Skip episodes without parent shows
+                            error!(
+                                "Parent show not found for episode {} with parent_id {:?}",
+                                model.id, parent_id
+                            );
+                            // Skip this episode if we can't find its parent show
                             continue;
                         }
+                    } else {
+                        error!("Episode {} has no parent_id set!", model.id);
+                        // Skip episodes without parent shows
+                        continue;
                     }
+                }
 
-                    // Clone the poster URL before moving display_item
-                    let poster_url_to_load = display_item.poster_url.clone();
+                // Clone the poster URL before moving display_item
+                let poster_url_to_load = display_item.poster_url.clone();
 
-                    guard.push_back(MediaCardInit {
+                cards.push_back((
+                    MediaCardInit {
                         item: display_item,
                         show_progress,
                         watched,
                         progress_percent,
                         show_media_type_icon: false,
-                    });
-
-                    // Queue image load if poster URL exists (use the correct poster from display_item)
-                    if let Some(poster_url) = poster_url_to_load
-                        && !poster_url.is_empty()
-                    {
-                        // Create a unique key for tracking that includes both section and item ID
-                        // This allows the same item to appear in multiple sections
-                        let tracking_key = format!("{}::{}", section.id, item_id);
-
-                        // Track this request with the unique key
-                        self.image_requests
-                            .insert(tracking_key.clone(), (section.id.clone(), idx));
-
-                        // Queue the image load with priority based on position
-                        let priority = (idx / 10).min(10) as u8;
-                        trace!(
-                            "Queueing image for item {} with priority {}",
-                            item_id, priority
-                        );
-
-                        let _ = self.image_loader.sender().send(ImageLoaderInput::LoadImage(
-                            ImageRequest {
-                                id: tracking_key, // Use the unique tracking key as the ID
-                                url: poster_url.clone(),
-                                size: ImageSize::Thumbnail,
-                                priority,
-                            },
-                        ));
-                    }
-                }
+                        unwatched_count: 0,
+                        favorite: favorite_ids.contains(&model.id),
+                    },
+                    poster_url_to_load,
+                ));
             }
 
-            // Store factory
+            // Store factory and add the first cards
             self.section_factories.insert(section.id.clone(), factory);
+            self.pending_cards.insert(
+                section.id.clone(),
+                PendingCards {
+                    cards_box: cards_box.clone(),
+                    cards,
+                },
+            );
+            self.show_more_cards(&section.id);
 
             scrolled_window.set_child(Some(&cards_box));
 
             // Connect scroll button handlers
             let h_adjustment = scrolled_window.hadjustment();
 
-            // Update button sensitivity based on scroll position
+            // Update button sensitivity based on scroll position, and add
+            // more cards once the last ones are close to coming into view
             let left_btn = scroll_left_button.clone();
             let right_btn = scroll_right_button.clone();
+            let sender_more = sender.clone();
+            let section_id = section.id.clone();
             h_adjustment.connect_value_changed(move |adj| {
                 let value = adj.value();
                 let lower = adj.lower();
@@ -932,6 +1550,16 @@ impl HomePage {
                 // Use 1.0 pixel threshold to avoid floating point comparison issues
                 left_btn.set_sensitive(value > lower + 1.0);
                 right_btn.set_sensitive(value + 1.0 < upper - page_size);
+
+                // Within two cards of the end
+                if value + page_size >= upper - 2.0 * 192.0 {
+                    sender_more.input(HomePageInput::ShowMoreCards(section_id.clone()));
+                }
+            });
+            // Wide windows show the whole first batch, so check again when
+            // the row's width changes
+            h_adjustment.connect_changed(|adj| {
+                adj.emit_by_name::<()>("value-changed", &[]);
             });
 
             // Scroll left button handler
@@ -993,12 +1621,65 @@ impl HomePage {
             self.sections_container.append(&section_box);
         }
 
-        info!(
-            "Displayed {} sections for source {} (filtered from {})",
-            non_empty_sections.len(),
-            source_id,
-            self.sections.len()
-        );
+        non_empty_sections.len()
+    }
+
+    /// Add the next batch of a row's cards and queue their image loads
+    fn show_more_cards(&mut self, section_id: &str) {
+        let (Some(pending), Some(factory)) = (
+            self.pending_cards.get_mut(section_id),
+            self.section_factories.get_mut(section_id),
+        ) else {
+            return;
+        };
+        if pending.cards.is_empty() {
+            return;
+        }
+
+        let mut guard = factory.guard();
+        for _ in 0..ROW_BATCH {
+            let Some((card, poster_url)) = pending.cards.pop_front() else {
+                break;
+            };
+            let idx = guard.len();
+            let item_id = card.item.id.clone();
+            guard.push_back(card);
+
+            // Queue image load if poster URL exists (use the correct poster from display_item)
+            if let Some(poster_url) = poster_url
+                && !poster_url.is_empty()
+            {
+                // Create a unique key for tracking that includes both section and item ID
+                // This allows the same item to appear in multiple sections
+                let tracking_key = format!("{}::{}", section_id, item_id);
+
+                // Track this request with the unique key
+                self.image_requests
+                    .insert(tracking_key.clone(), (section_id.to_string(), idx));
+
+                // Queue the image load with priority based on position
+                let priority = (idx / 10).min(10) as u8;
+                trace!(
+                    "Queueing image for item {} with priority {}",
+                    item_id, priority
+                );
+
+                let _ =
+                    self.image_loader
+                        .sender()
+                        .send(ImageLoaderInput::LoadImage(ImageRequest {
+                            id: tracking_key, // Use the unique tracking key as the ID
+                            url: poster_url,
+                            size: ImageSize::Thumbnail,
+                            priority,
+                        }));
+            }
+        }
+
+        // Keep every card on a single line
+        let shown = guard.len() as u32;
+        pending.cards_box.set_min_children_per_line(shown);
+        pending.cards_box.set_max_children_per_line(shown);
     }
 
     /// Update the overall loading state based on all source states
@@ -1038,6 +1719,7 @@ impl HomePage {
 
         // Clear section factories
         self.section_factories.clear();
+        self.pending_cards.clear();
 
         // Clear UI container references
         self.section_ui_containers.clear();
@@ -1084,6 +1766,7 @@ impl HomePage {
         for section_id in sections_to_remove {
             // Remove factory
             self.section_factories.remove(&section_id);
+            self.pending_cards.remove(&section_id);
 
             // Remove UI container
             if let Some(container) = self.section_ui_containers.remove(&section_id) {
@@ -1095,4 +1778,25 @@ impl HomePage {
         self.sections
             .retain(|section| !section.id.starts_with(&format!("{}::", source_id)));
     }
+
+    /// Clear a single section, such as Watch Next before it is rebuilt
+    fn clear_section(&mut self, section_id: &str) {
+        let image_loader = self.image_loader.sender();
+        self.image_requests.retain(|tracking_key, (id, _)| {
+            if id.as_str() != section_id {
+                return true;
+            }
+            let _ = image_loader.send(ImageLoaderInput::CancelLoad {
+                id: tracking_key.clone(),
+            });
+            false
+        });
+
+        self.section_factories.remove(section_id);
+        self.pending_cards.remove(section_id);
+        if let Some(container) = self.section_ui_containers.remove(section_id) {
+            self.sections_container.remove(&container);
+        }
+        self.sections.retain(|section| section.id != section_id);
+    }
 }
@@ -4,8 +4,10 @@ use std::collections::HashMap;
 use tracing::{debug, error, trace};
 
 use super::LibraryPage;
-use super::messages::LibraryPageInput;
+use super::messages::{LibraryPageInput, LibraryPageOutput};
 use super::types::{SortBy, SortOrder};
+use crate::models::{LibraryId, MediaItemId};
+use crate::services::core::{KidsProfileService, MediaService, metered, power_saver};
 use crate::workers::ImageLoaderInput;
 
 impl LibraryPage {
@@ -75,6 +77,9 @@ impl LibraryPage {
 
                 match media_result {
                     Ok(mut items) => {
+                        // The kids profile leaves out what isn't rated for children
+                        KidsProfileService::retain_allowed(&db, &mut items).await;
+
                         // For LastWatched sort, we need to fetch playback progress data
                         let playback_map = if matches!(sort_by, SortBy::LastWatched) {
                             let media_ids: Vec<String> =
@@ -171,17 +176,79 @@ impl LibraryPage {
         }
     }
 
+    /// Switch to another library, saving the filters of the current one
+    pub(super) async fn switch_library(
+        &mut self,
+        library_id: LibraryId,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        debug!("Setting library: {}", library_id);
+
+        // Save current filter state before switching libraries
+        self.save_filter_state().await;
+
+        self.library_id = Some(library_id);
+        self.loaded_count = 0;
+        self.total_items.clear();
+        self.has_loaded_all = false;
+        self.needs_factory_clear = true;
+        // Cancel pending images BEFORE clearing image_requests
+        // Otherwise cancel_pending_images() has no requests to cancel
+        self.cancel_pending_images();
+        self.image_requests.clear();
+        self.images_requested.clear();
+        self.images_loaded.clear();
+        self.visible_start_idx = 0;
+        self.visible_end_idx = 0;
+
+        // Send view switcher bar to main window header
+        // The view switcher bar provides navigation tabs in the header
+        sender
+            .output(LibraryPageOutput::SetHeaderTitleWidget(
+                self.view_switcher_bar.clone().upcast(),
+            ))
+            .expect("Failed to send header widget");
+    }
+
     /// Refresh the library view by clearing cache and reloading
+    /// Recount the unwatched episodes of the show a media item belongs to,
+    /// if the library shows any
+    pub(super) fn recount_show_of(&self, media_id: String, sender: &AsyncComponentSender<Self>) {
+        // Only shows carry an unwatched count on their card
+        if !self
+            .total_items
+            .iter()
+            .any(|item| item.media_type == "show")
+        {
+            return;
+        }
+
+        let db = self.db.clone();
+        let sender = sender.clone();
+        relm4::spawn(async move {
+            let media_id = MediaItemId::new(media_id);
+            match MediaService::get_unwatched_count_for_show_of(&db, &media_id).await {
+                Ok(Some((show_id, count))) => {
+                    sender.input(LibraryPageInput::ShowUnwatchedCountChanged { show_id, count })
+                }
+                Ok(None) => {}
+                Err(e) => debug!("Failed to recount unwatched episodes: {}", e),
+            }
+        });
+    }
+
     pub(super) fn refresh(&mut self, sender: AsyncComponentSender<Self>) {
+        self.restore_position = self.position.clone();
         self.loaded_count = 0;
         self.total_items.clear();
         self.has_loaded_all = false;
         self.needs_factory_clear = true;
-        // Cancel pending images BEFORE clearing image_requests
+        // Cancel pending images BEFORE clearing images_requested
         // Otherwise cancel_pending_images() has no requests to cancel
         self.cancel_pending_images();
         self.image_requests.clear();
         self.images_requested.clear();
+        self.images_loaded.clear();
         self.visible_start_idx = 0;
         self.visible_end_idx = 0;
         // Keep genre filters during refresh to maintain user selection
@@ -206,27 +273,21 @@ impl LibraryPage {
             let scroll_pos = adjustment.value();
             let page_size = adjustment.page_size();
 
-            // Get the flow box to determine actual item dimensions
-            let flow_box = scrolled
-                .child()
-                .and_then(|w| w.first_child())
-                .and_then(|w| w.downcast::<gtk::FlowBox>().ok());
-
-            let items_per_row = if let Some(flow_box) = flow_box {
-                // Use actual columns from flowbox
-                flow_box.min_children_per_line() as usize
-            } else {
-                4 // Default fallback
-            };
+            let items_per_row = self.media_grid.columns();
 
             // More accurate row height accounting for reduced spacing
             let row_height = 270.0; // Card height (180) + spacing (16)
 
-            let visible_start_row = (scroll_pos / row_height).floor() as usize;
-            let visible_end_row = ((scroll_pos + page_size) / row_height).ceil() as usize + 1; // Add 1 for partial visibility
+            // Rows are found within their group, below its heading
+            let visible_start_idx = self.media_grid.index_at(scroll_pos, row_height);
+            let visible_end_idx =
+                self.media_grid.index_at(scroll_pos + page_size, row_height) + 3 * items_per_row; // The partly visible row and one more
 
-            self.visible_start_idx = visible_start_row * items_per_row;
-            self.visible_end_idx = ((visible_end_row + 1) * items_per_row).min(self.loaded_count);
+            if visible_start_idx != self.visible_start_idx {
+                self.scrolling_up = visible_start_idx < self.visible_start_idx;
+            }
+            self.visible_start_idx = visible_start_idx;
+            self.visible_end_idx = visible_end_idx.min(self.loaded_count);
 
             trace!(
                 "Viewport updated: scroll_pos={:.0}, page_size={:.0}, items {} to {} visible",
@@ -237,98 +298,102 @@ impl LibraryPage {
 
     /// Load images for items currently in the visible viewport
     pub(super) fn load_images_for_visible_range(&mut self) {
-        // Calculate which items need images with lookahead
-        let lookahead_items = 30; // Load 30 items ahead and behind for smoother scrolling
-        let load_start = self.visible_start_idx.saturating_sub(lookahead_items);
-        let load_end = (self.visible_end_idx + lookahead_items).min(self.loaded_count);
+        // Prefetch further in the direction of scrolling than behind it, unless
+        // a metered connection or power saving restricts loading to what is
+        // on screen
+        let (lookahead_items, lookbehind_items) =
+            if metered::active_restrictions().skip_image_prefetch || power_saver::is_active() {
+                (0, 0)
+            } else {
+                (45, 15)
+            };
+        let (before, after) = if self.scrolling_up {
+            (lookahead_items, lookbehind_items)
+        } else {
+            (lookbehind_items, lookahead_items)
+        };
+        let load_start = self.visible_start_idx.saturating_sub(before);
+        let load_end = (self.visible_end_idx + after).min(self.loaded_count);
 
         debug!(
             "Loading images for items {} to {} (visible: {} to {})",
             load_start, load_end, self.visible_start_idx, self.visible_end_idx
         );
 
-        // Cancel images outside visible range
-        let mut to_cancel = Vec::new();
-        for idx in 0..self.loaded_count {
-            if (idx < load_start || idx >= load_end) && idx < self.total_items.len() {
-                let item_id = &self.total_items[idx].id;
-                if self.image_requests.contains_key(item_id) {
-                    to_cancel.push(item_id.clone());
-                }
-            }
-        }
-
-        // Cancel out-of-range images
+        // Cancel loads that scrolled out of range
+        let to_cancel: Vec<String> = self
+            .images_requested
+            .keys()
+            .filter(|id| {
+                self.image_requests
+                    .get(*id)
+                    .is_none_or(|&idx| idx < load_start || idx >= load_end)
+            })
+            .cloned()
+            .collect();
         for id in to_cancel {
             trace!("Cancelling image load for out-of-range item: {}", id);
             let _ = self
                 .image_loader
                 .sender()
                 .send(ImageLoaderInput::CancelLoad { id: id.clone() });
-            self.pending_image_cancels.push(id);
+            self.images_requested.remove(&id);
         }
 
-        // Load images for items in range
+        // Queue images in range, or re-prioritize them as the viewport moves
         let mut images_queued = 0;
-        for idx in load_start..load_end {
-            if idx < self.total_items.len() {
-                let item = &self.total_items[idx];
-                if let Some(poster_url) = &item.poster_url {
-                    let id = item.id.clone();
-
-                    // Skip if already requested or recently cancelled
-                    if self.images_requested.contains(&id)
-                        || self.pending_image_cancels.contains(&id)
-                    {
-                        continue;
-                    }
+        for idx in load_start..load_end.min(self.total_items.len()) {
+            let item = &self.total_items[idx];
+            let Some(poster_url) = &item.poster_url else {
+                continue;
+            };
+            if self.images_loaded.contains(&item.id) {
+                continue;
+            }
 
-                    // Calculate priority based on distance from current viewport
-                    let priority = if idx >= self.visible_start_idx && idx < self.visible_end_idx {
-                        0 // Highest priority for visible items
-                    } else {
-                        // Priority increases with distance from viewport
-                        let distance = if idx < self.visible_start_idx {
-                            self.visible_start_idx - idx
-                        } else {
-                            idx - self.visible_end_idx
-                        };
-                        (distance / 10).min(10) as u8
-                    };
-
-                    trace!(
-                        "Queueing image for item {} (id: {}) with priority {}",
-                        idx, id, priority
-                    );
-
-                    let _ = self.image_loader.sender().send(ImageLoaderInput::LoadImage(
-                        crate::workers::ImageRequest {
-                            id: id.clone(),
-                            url: poster_url.clone(),
-                            size: crate::workers::ImageSize::Thumbnail,
-                            priority,
-                        },
-                    ));
-
-                    // Mark this image as requested
-                    self.images_requested.insert(id);
-                    images_queued += 1;
-                }
+            // Visible items first, then those ahead of the scroll, then those behind it
+            let priority = if idx >= self.visible_start_idx && idx < self.visible_end_idx {
+                0
+            } else {
+                let (distance, ahead) = if idx < self.visible_start_idx {
+                    (self.visible_start_idx - idx, self.scrolling_up)
+                } else {
+                    (idx - self.visible_end_idx, !self.scrolling_up)
+                };
+                let base = if ahead { 1 } else { 6 };
+                base + (distance / 10).min(4) as u8
+            };
+
+            if self.images_requested.get(&item.id) == Some(&priority) {
+                continue;
             }
+
+            trace!(
+                "Queueing image for item {} (id: {}) with priority {}",
+                idx, item.id, priority
+            );
+
+            let _ = self.image_loader.sender().send(ImageLoaderInput::LoadImage(
+                crate::workers::ImageRequest {
+                    id: item.id.clone(),
+                    url: poster_url.clone(),
+                    size: crate::workers::ImageSize::Thumbnail,
+                    priority,
+                },
+            ));
+            self.images_requested.insert(item.id.clone(), priority);
+            images_queued += 1;
         }
 
         if images_queued > 0 {
-            debug!("Queued {} new image loads", images_queued);
+            debug!("Queued or re-prioritized {} image loads", images_queued);
         }
-
-        // Clear pending cancels after a delay
-        self.pending_image_cancels.clear();
     }
 
     /// Cancel all pending image load requests
     pub(super) fn cancel_pending_images(&mut self) {
         // Cancel all pending image loads
-        for (id, _) in self.image_requests.iter() {
+        for id in self.images_requested.keys() {
             let _ = self
                 .image_loader
                 .sender()
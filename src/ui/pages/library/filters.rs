@@ -28,12 +28,26 @@ impl LibraryPage {
 
         self.filter_text = state.filter_text.clone();
         self.selected_genres = state.selected_genres.clone();
+        self.selected_studios = state.selected_studios.clone();
+        self.selected_tags = state.selected_tags.clone();
+        self.selected_collections = state.selected_collections.clone();
         self.selected_min_year = state.selected_min_year;
         self.selected_max_year = state.selected_max_year;
         self.min_rating = state.min_rating;
+        self.uhd_only = state.uhd_only;
+        self.hdr_only = state.hdr_only;
         self.watch_status_filter = state.watch_status_filter;
         self.selected_media_type = state.selected_media_type.clone();
         self.selected_view_mode = state.selected_view_mode;
+        self.recent_grouping = state.recent_grouping;
+
+        // Show the tab of the restored view mode
+        self.view_stack
+            .set_visible_child_name(match state.selected_view_mode {
+                ViewMode::All => "all",
+                ViewMode::Unwatched => "unwatched",
+                ViewMode::RecentlyAdded => "recent",
+            });
     }
 
     /// Save the current filter state to config
@@ -95,9 +109,14 @@ impl LibraryPage {
     /// Check if any filters are active
     pub(super) fn has_active_filters(&self) -> bool {
         !self.selected_genres.is_empty()
+            || !self.selected_studios.is_empty()
+            || !self.selected_tags.is_empty()
+            || !self.selected_collections.is_empty()
             || self.selected_min_year.is_some()
             || self.selected_max_year.is_some()
             || self.min_rating.is_some()
+            || self.uhd_only
+            || self.hdr_only
             || self.watch_status_filter != WatchStatus::All
             || !self.filter_text.is_empty()
             || self.selected_view_mode != ViewMode::All
@@ -125,6 +144,28 @@ impl LibraryPage {
             }
         }
 
+        // Studio filters
+        for studio in &self.selected_studios {
+            filters.push(ActiveFilter {
+                label: format!("Studio: {}", studio),
+                filter_type: ActiveFilterType::Studio(studio.clone()),
+            });
+        }
+
+        // Collection and tag filters
+        for collection in &self.selected_collections {
+            filters.push(ActiveFilter {
+                label: format!("Collection: {}", collection),
+                filter_type: ActiveFilterType::Collection(collection.clone()),
+            });
+        }
+        for tag in &self.selected_tags {
+            filters.push(ActiveFilter {
+                label: format!("Tag: {}", tag),
+                filter_type: ActiveFilterType::Tag(tag.clone()),
+            });
+        }
+
         // Year range filter
         if self.selected_min_year.is_some() || self.selected_max_year.is_some() {
             let label = match (self.selected_min_year, self.selected_max_year) {
@@ -148,11 +189,26 @@ impl LibraryPage {
             });
         }
 
+        // Quality filters
+        if self.uhd_only {
+            filters.push(ActiveFilter {
+                label: "4K only".to_string(),
+                filter_type: ActiveFilterType::UhdOnly,
+            });
+        }
+        if self.hdr_only {
+            filters.push(ActiveFilter {
+                label: "HDR only".to_string(),
+                filter_type: ActiveFilterType::HdrOnly,
+            });
+        }
+
         // Watch status filter
         if self.watch_status_filter != WatchStatus::All {
             let label = match self.watch_status_filter {
                 WatchStatus::Watched => "Watched".to_string(),
                 WatchStatus::Unwatched => "Unwatched".to_string(),
+                WatchStatus::InProgress => "In Progress".to_string(),
                 WatchStatus::All => unreachable!(),
             };
             filters.push(ActiveFilter {
@@ -190,6 +246,10 @@ impl LibraryPage {
 
                 let close_button = gtk::Button::new();
                 close_button.set_icon_name("window-close-symbolic");
+                close_button.update_property(&[gtk::accessible::Property::Label(&format!(
+                    "Remove {} filter",
+                    filter.label
+                ))]);
                 close_button.add_css_class("flat");
                 close_button.add_css_class("circular");
                 close_button.set_margin_end(4);
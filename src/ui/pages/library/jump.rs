@@ -0,0 +1,160 @@
+use gtk::prelude::*;
+use relm4::prelude::*;
+use tracing::trace;
+
+use super::LibraryPage;
+use super::messages::{LibraryPageInput, LibraryPageOutput};
+use super::types::GridPosition;
+use crate::db::entities::MediaItemModel;
+
+/// Entries on the A–Z rail, '#' collects titles that don't start with a letter
+pub(super) const JUMP_LETTERS: &str = "#ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Title the library is sorted by
+fn sort_key(item: &MediaItemModel) -> &str {
+    item.sort_title.as_deref().unwrap_or(&item.title)
+}
+
+/// Rail entry an item is listed under
+pub(super) fn jump_letter(item: &MediaItemModel) -> char {
+    match sort_key(item).chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => c.to_ascii_uppercase(),
+        _ => '#',
+    }
+}
+
+impl LibraryPage {
+    /// Enable the rail entries that have at least one item
+    pub(super) fn update_jump_rail(&self) {
+        if let Some(ref rail) = self.jump_rail {
+            let letters: std::collections::HashSet<char> =
+                self.total_items.iter().map(jump_letter).collect();
+
+            let mut child = rail.first_child();
+            while let Some(button) = child {
+                let letter = button
+                    .downcast_ref::<gtk::Button>()
+                    .and_then(|b| b.label())
+                    .and_then(|label| label.chars().next());
+                button.set_sensitive(letter.is_some_and(|l| letters.contains(&l)));
+                child = button.next_sibling();
+            }
+        }
+    }
+
+    /// Jump to the first item listed under `letter`
+    pub(super) fn jump_to_letter(&mut self, letter: char, sender: AsyncComponentSender<Self>) {
+        if let Some(index) = self
+            .total_items
+            .iter()
+            .position(|item| jump_letter(item) == letter)
+        {
+            self.jump_to_index(index, sender);
+        }
+    }
+
+    /// Extend the type-ahead prefix and jump to the first title starting with it
+    pub(super) fn type_ahead(&mut self, c: char, sender: AsyncComponentSender<Self>) {
+        let prefix = self.type_ahead.push(c).to_owned();
+        let matches = |item: &MediaItemModel| {
+            sort_key(item).to_lowercase().starts_with(&prefix)
+                || item.title.to_lowercase().starts_with(&prefix)
+        };
+        if let Some(index) = self.total_items.iter().position(matches) {
+            trace!("Type-ahead '{}' matched item {}", prefix, index);
+            self.jump_to_index(index, sender);
+        }
+    }
+
+    /// Scroll to an item, rendering the batches up to it first if needed
+    pub(super) fn jump_to_index(&mut self, index: usize, sender: AsyncComponentSender<Self>) {
+        self.pending_focus = None;
+        if index < self.loaded_count {
+            self.pending_jump = None;
+            self.scroll_to_index(index);
+        } else {
+            self.pending_jump = Some(index);
+            sender.input(LibraryPageInput::RenderBatch);
+        }
+    }
+
+    /// Scroll the grid so the card at `index` is at the top and focus it
+    pub(super) fn scroll_to_index(&mut self, index: usize) {
+        let grid = self.media_grid.widget().clone();
+        let Some(child) = self.media_grid.child_at_index(index) else {
+            return;
+        };
+        // A restored position focuses the item opened last, if rendered
+        let focus = self
+            .pending_focus
+            .take()
+            .and_then(|index| self.media_grid.child_at_index(index))
+            .unwrap_or_else(|| child.clone());
+
+        // Wait for freshly rendered cards to be laid out
+        gtk::glib::idle_add_local_once(move || {
+            let Some(content) = grid.parent() else {
+                return;
+            };
+            let Some(scrolled) = grid
+                .ancestor(gtk::ScrolledWindow::static_type())
+                .and_downcast::<gtk::ScrolledWindow>()
+            else {
+                return;
+            };
+
+            if let Some(point) = child.compute_point(&content, &gtk::graphene::Point::new(0.0, 0.0))
+            {
+                scrolled.vadjustment().set_value(point.y() as f64);
+            }
+            focus.grab_focus();
+        });
+    }
+
+    /// Render up to the position to restore, if its top item is still
+    /// listed, so it is scrolled to like a jump
+    pub(super) fn resume_position(&mut self) {
+        let Some(position) = self.restore_position.take() else {
+            return;
+        };
+        let find = |id: &str| self.total_items.iter().position(|item| item.id == id);
+        let Some(top) = find(&position.top_item) else {
+            return;
+        };
+        self.pending_focus = position.selected_item.as_deref().and_then(find);
+        self.pending_jump = Some(top);
+    }
+
+    /// Note the item at the top of the view and the one last opened,
+    /// passing them on to be restored when the library is opened again
+    pub(super) fn update_position(
+        &mut self,
+        selected_item: Option<String>,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        // Until restored, the view is still on its way back there
+        if self.restore_position.is_some() || self.pending_jump.is_some() {
+            return;
+        }
+        let Some(top) = self.total_items.get(self.visible_start_idx) else {
+            return;
+        };
+        let position = GridPosition {
+            top_item: top.id.clone(),
+            selected_item,
+        };
+        if self.position.as_ref() == Some(&position) {
+            return;
+        }
+
+        self.position = Some(position.clone());
+        if let Some(library_id) = self.library_id.clone() {
+            sender
+                .output(LibraryPageOutput::PositionChanged {
+                    library_id,
+                    position,
+                })
+                .expect("Failed to send output");
+        }
+    }
+}
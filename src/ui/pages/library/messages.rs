@@ -2,17 +2,27 @@ use relm4::gtk::Widget;
 use relm4::gtk::gdk;
 
 use crate::db::entities::MediaItemModel;
-use crate::models::{LibraryId, MediaItemId};
+use crate::models::{LibraryId, MediaItemId, SourceId};
 use crate::ui::shared::broker::BrokerMessage;
+use crate::ui::shared::undo_toast::UndoOffer;
 
-use super::types::{ActiveFilterType, FilterState, SortBy, ViewMode, WatchStatus};
+use super::types::{
+    ActiveFilterType, FilterState, GridPosition, RecentGrouping, SortBy, ViewMode, WatchStatus,
+};
 
 #[derive(Debug)]
 pub enum LibraryPageInput {
     /// Set the library to display
     SetLibrary(LibraryId),
+    /// Set the library to display with the given filters instead of its saved ones
+    SetLibraryWithFilter {
+        library_id: LibraryId,
+        filter: FilterState,
+    },
     /// Restore filter state from saved state
     RestoreFilterState(FilterState),
+    /// Scroll back to where the library was left, once it is loaded
+    RestorePosition(GridPosition),
     /// Load more items into view
     LoadMoreBatch,
     /// All media items loaded from database
@@ -28,16 +38,35 @@ pub enum LibraryPageInput {
     MarkWatched(MediaItemId),
     /// Mark media item as unwatched
     MarkUnwatched(MediaItemId),
+    /// Add or remove a media item from favorites
+    SetFavorite { id: MediaItemId, favorite: bool },
+    /// Hide a media item from the home page
+    Hide(MediaItemId),
+    /// Hide the movies and shows of a collection from the home page
+    HideCollection {
+        source_id: SourceId,
+        collection: String,
+    },
+    /// An action ran in the background, offer taking it back
+    OfferUndo(UndoOffer),
     /// Change sort order
     SetSortBy(SortBy),
     /// Toggle sort order (ascending/descending)
     ToggleSortOrder,
+    /// Group the Recently Added view under day or week headings
+    SetRecentGrouping(RecentGrouping),
     /// Filter by text
     SetFilter(String),
     /// Toggle genre filter
     ToggleGenreFilter(String),
     /// Clear all genre filters
     ClearGenreFilters,
+    /// Toggle studio or network filter
+    ToggleStudioFilter(String),
+    /// Toggle collection filter
+    ToggleCollectionFilter(String),
+    /// Toggle tag filter
+    ToggleTagFilter(String),
     /// Set year range filter
     SetYearRange { min: Option<i32>, max: Option<i32> },
     /// Clear year range filter
@@ -46,6 +75,10 @@ pub enum LibraryPageInput {
     SetRatingFilter(Option<f32>),
     /// Clear rating filter
     ClearRatingFilter,
+    /// Show only 4K items
+    SetUhdOnly(bool),
+    /// Show only HDR items
+    SetHdrOnly(bool),
     /// Set watch status filter
     SetWatchStatusFilter(WatchStatus),
     /// Clear watch status filter
@@ -76,6 +109,18 @@ pub enum LibraryPageInput {
     ProcessDebouncedScroll,
     /// Load images for visible items
     LoadVisibleImages,
+    /// Jump to the first title under a letter of the A–Z rail
+    JumpToLetter(char),
+    /// Type-ahead find, jumping to the first title starting with what was typed
+    TypeAhead(char),
+    /// Move keyboard focus to the first card in view
+    FocusGrid,
+    /// A card was hovered long enough to start its preview
+    PreviewRequested(MediaItemId),
+    /// Preview stream resolved for the card of an item
+    PreviewReady { id: MediaItemId, url: String },
+    /// Unwatched episode count of a show changed
+    ShowUnwatchedCountChanged { show_id: MediaItemId, count: u64 },
     /// Message broker messages
     BrokerMsg(BrokerMessage),
 }
@@ -86,4 +131,9 @@ pub enum LibraryPageOutput {
     NavigateToMediaItem(MediaItemId),
     /// Set header title widget (for view switcher tabs)
     SetHeaderTitleWidget(Widget),
+    /// The grid was scrolled or an item opened from it
+    PositionChanged {
+        library_id: LibraryId,
+        position: GridPosition,
+    },
 }
@@ -1,6 +1,5 @@
 use gtk::prelude::*;
 use libadwaita as adw;
-use relm4::factory::FactoryVecDeque;
 use relm4::gtk;
 use relm4::prelude::*;
 use std::time::Duration;
@@ -9,6 +8,7 @@ use tracing::{debug, trace};
 // Module declarations first
 mod data;
 mod filters;
+mod jump;
 mod messages;
 mod types;
 mod ui_builders;
@@ -16,15 +16,18 @@ mod ui_builders;
 // Re-export public types (also makes them available in this module)
 pub use messages::{LibraryPageInput, LibraryPageOutput};
 pub use types::{
-    ActiveFilter, ActiveFilterType, FilterState, FilterStatistics, SortBy, SortOrder, ViewMode,
-    WatchStatus,
+    ActiveFilter, ActiveFilterType, FilterState, FilterStatistics, GridPosition, RecentGrouping,
+    SortBy, SortOrder, ViewMode, WatchState, WatchStatus,
 };
 
+use crate::config::KidsConfig;
 use crate::db::connection::DatabaseConnection;
 use crate::db::entities::MediaItemModel;
 use crate::models::LibraryId;
-use crate::ui::factories::media_card::{MediaCard, MediaCardInit, MediaCardInput, MediaCardOutput};
-use crate::ui::shared::broker::{BROKER, BrokerMessage};
+use crate::ui::factories::media_card::{MediaCardInit, MediaCardInput, MediaCardOutput};
+use crate::ui::factories::media_grid::MediaGrid;
+use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage, Subscription, Topic};
+use crate::ui::shared::type_ahead::TypeAhead;
 use crate::workers::{ImageLoader, ImageLoaderOutput};
 use std::collections::HashMap;
 
@@ -47,7 +50,7 @@ impl std::fmt::Debug for LibraryPage {
 pub struct LibraryPage {
     db: DatabaseConnection,
     library_id: Option<LibraryId>,
-    media_factory: FactoryVecDeque<MediaCard>,
+    media_grid: MediaGrid<LibraryPageInput>,
     image_loader: relm4::WorkerController<ImageLoader>,
     image_requests: std::collections::HashMap<String, usize>,
     is_loading: bool,
@@ -55,6 +58,8 @@ pub struct LibraryPage {
     batch_size: usize,
     total_items: Vec<MediaItemModel>,
     has_loaded_all: bool,
+    // Kids profile, which leaves out items not rated for children
+    kids: KidsConfig,
     // Current sort settings (updated when view mode changes)
     sort_by: SortBy,
     sort_order: SortOrder,
@@ -66,6 +71,14 @@ pub struct LibraryPage {
     selected_genres: Vec<String>,
     available_genres: Vec<String>,
     genre_popover: Option<gtk::Popover>,
+    // Studio and network filtering
+    selected_studios: Vec<String>,
+    available_studios: Vec<String>,
+    // Collection and tag filtering
+    selected_collections: Vec<String>,
+    available_collections: Vec<String>,
+    selected_tags: Vec<String>,
+    available_tags: Vec<String>,
     // Year range filtering
     min_year: Option<i32>,
     max_year: Option<i32>,
@@ -75,6 +88,9 @@ pub struct LibraryPage {
     // Rating filtering
     min_rating: Option<f32>,
     rating_popover: Option<gtk::Popover>,
+    // Video quality filtering
+    uhd_only: bool,
+    hdr_only: bool,
     // Watch status filtering
     watch_status_filter: WatchStatus,
     watch_status_popover: Option<gtk::Popover>,
@@ -84,15 +100,19 @@ pub struct LibraryPage {
     // Viewport tracking
     visible_start_idx: usize,
     visible_end_idx: usize,
+    // Direction of the last scroll, which gets the larger prefetch
+    scrolling_up: bool,
     // Scroll debouncing
     scroll_debounce_handle: Option<gtk::glib::SourceId>,
     // Image loading state
-    images_requested: std::collections::HashSet<String>, // Track which images have been requested
-    pending_image_cancels: Vec<String>,
+    images_requested: HashMap<String, u8>, // In-flight image loads and the priority they were queued with
+    images_loaded: std::collections::HashSet<String>,
     // Handler IDs for cleanup
     scroll_handler_id: Option<gtk::glib::SignalHandlerId>,
     // View mode selection
     selected_view_mode: ViewMode,
+    // Headings of the Recently Added view
+    recent_grouping: RecentGrouping,
     // View switcher widgets for header bar
     view_stack: adw::ViewStack,
     view_switcher_bar: adw::ViewSwitcherBar,
@@ -103,6 +123,17 @@ pub struct LibraryPage {
     filters_button: Option<gtk::Button>,
     // Flag to defer factory clearing until items are ready to render
     needs_factory_clear: bool,
+    // A–Z rail and type-ahead find
+    jump_rail: Option<gtk::Box>,
+    pending_jump: Option<usize>,
+    type_ahead: TypeAhead,
+    // Where the grid is scrolled to, and where to scroll back to once reloaded
+    position: Option<GridPosition>,
+    restore_position: Option<GridPosition>,
+    pending_focus: Option<usize>,
+    // Last watched flag seen for each item, to tell progress from watched changes
+    watched_states: HashMap<String, bool>,
+    _broker_subscription: Subscription,
 }
 
 #[allow(unused_assignments)]
@@ -120,9 +151,15 @@ impl AsyncComponent for LibraryPage {
 
             // Add keyboard event controller to capture typing
             add_controller = gtk::EventControllerKey {
-                connect_key_pressed[sender] => move |_, key, _, _| {
+                connect_key_pressed[sender] => move |_, key, _, state| {
+                    let modified = state.intersects(
+                        gtk::gdk::ModifierType::CONTROL_MASK | gtk::gdk::ModifierType::ALT_MASK,
+                    );
                     // Show search on slash or Control+F
-                    if key == gtk::gdk::Key::slash || key == gtk::gdk::Key::f {
+                    if key == gtk::gdk::Key::slash
+                        || (key == gtk::gdk::Key::f
+                            && state.contains(gtk::gdk::ModifierType::CONTROL_MASK))
+                    {
                         sender.input(LibraryPageInput::ShowSearch);
                         gtk::glib::Propagation::Stop
                     }
@@ -130,6 +167,13 @@ impl AsyncComponent for LibraryPage {
                     else if key == gtk::gdk::Key::Escape {
                         sender.input(LibraryPageInput::HideSearch);
                         gtk::glib::Propagation::Stop
+                    }
+                    // Type-ahead find on letters and digits
+                    else if let Some(c) = key.to_unicode().filter(|c| c.is_alphanumeric())
+                        && !modified
+                    {
+                        sender.input(LibraryPageInput::TypeAhead(c));
+                        gtk::glib::Propagation::Stop
                     } else {
                         gtk::glib::Propagation::Proceed
                     }
@@ -197,16 +241,51 @@ impl AsyncComponent for LibraryPage {
                                 "view-sort-descending-symbolic"
                             },
                             set_tooltip_text: Some("Toggle sort order"),
+                            #[watch]
+                            update_property: &[gtk::accessible::Property::Label(
+                                if model.sort_order == SortOrder::Ascending {
+                                    "Sort Ascending"
+                                } else {
+                                    "Sort Descending"
+                                },
+                            )],
                             add_css_class: "flat",
                             connect_clicked[sender] => move |_| {
                                 sender.input(LibraryPageInput::ToggleSortOrder);
                             }
                         },
 
+                        // Day or week headings for Recently Added
+                        gtk::DropDown {
+                            set_model: Some(&gtk::StringList::new(&[
+                                "No Grouping",
+                                "Group by Day",
+                                "Group by Week",
+                            ])),
+                            set_tooltip_text: Some("Group by date added"),
+                            #[watch]
+                            set_visible: model.selected_view_mode == ViewMode::RecentlyAdded,
+                            #[watch]
+                            set_selected: match model.recent_grouping {
+                                RecentGrouping::Ungrouped => 0,
+                                RecentGrouping::Day => 1,
+                                RecentGrouping::Week => 2,
+                            },
+                            connect_selected_notify[sender] => move |dropdown| {
+                                let grouping = match dropdown.selected() {
+                                    1 => RecentGrouping::Day,
+                                    2 => RecentGrouping::Week,
+                                    _ => RecentGrouping::Ungrouped,
+                                };
+                                sender.input(LibraryPageInput::SetRecentGrouping(grouping));
+                            }
+                        },
+
                         // Add search button
                         gtk::Button {
                             set_icon_name: "system-search-symbolic",
                             set_tooltip_text: Some("Search (/)"),
+                            update_property: &[gtk::accessible::Property::Label("Search")],
                             add_css_class: "flat",
                             connect_clicked[sender] => move |_| {
                                 sender.input(LibraryPageInput::ShowSearch);
@@ -218,6 +297,7 @@ impl AsyncComponent for LibraryPage {
                         gtk::Button {
                             set_icon_name: "funnel-symbolic",
                             set_tooltip_text: Some("Filters"),
+                            update_property: &[gtk::accessible::Property::Label("Filters")],
                             add_css_class: "flat",
                             connect_clicked[sender] => move |_| {
                                 sender.input(LibraryPageInput::ToggleFiltersPopover);
@@ -448,17 +528,10 @@ impl AsyncComponent for LibraryPage {
                         },
 
                         #[local_ref]
-                        media_box -> gtk::FlowBox {
-                            set_column_spacing: 12,  // Tighter grid spacing
-                            set_row_spacing: 16,      // Reduced vertical spacing
-                            set_homogeneous: true,
-                            set_min_children_per_line: 4,   // More items per row with smaller sizes
-                            set_max_children_per_line: 12,  // Allow more on wide screens
-                            set_selection_mode: gtk::SelectionMode::None,
+                        media_grid_box -> gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
                             set_margin_top: 24,
                             set_margin_bottom: 16,
-                            set_margin_start: 16,
-                            set_margin_end: 16,
                             set_valign: gtk::Align::Start,
                         },
 
@@ -484,7 +557,7 @@ impl AsyncComponent for LibraryPage {
                         // Empty state - modern design with large icon
                         adw::StatusPage {
                             #[watch]
-                            set_visible: !model.is_loading && model.media_factory.is_empty(),
+                            set_visible: !model.is_loading && model.media_grid.is_empty(),
                             set_icon_name: Some("folder-videos-symbolic"),
                             set_title: "No Media Found",
                             set_description: Some("This library is empty or still syncing"),
@@ -492,6 +565,17 @@ impl AsyncComponent for LibraryPage {
                         }
                     },
                 }
+
+                    // A–Z rail for jumping through titles
+                    #[name = "jump_rail"]
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_valign: gtk::Align::Center,
+                        set_margin_end: 4,
+                        add_css_class: "jump-rail",
+                        #[watch]
+                        set_visible: model.sort_by == SortBy::Title && !model.total_items.is_empty(),
+                    },
                 }
             },
 
@@ -529,17 +613,34 @@ impl AsyncComponent for LibraryPage {
         root: Self::Root,
         sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
-        let media_box = gtk::FlowBox::default();
-
-        let media_factory = FactoryVecDeque::<MediaCard>::builder()
-            .launch(media_box.clone())
-            .forward(sender.input_sender(), |output| match output {
-                MediaCardOutput::Clicked(id) => LibraryPageInput::MediaItemSelected(id),
-                MediaCardOutput::Play(id) => LibraryPageInput::MediaItemSelected(id),
-                MediaCardOutput::GoToShow(id) => LibraryPageInput::MediaItemSelected(id),
-                MediaCardOutput::MarkWatched(id) => LibraryPageInput::MarkWatched(id),
-                MediaCardOutput::MarkUnwatched(id) => LibraryPageInput::MarkUnwatched(id),
-            });
+        let media_grid_box = gtk::Box::default();
+
+        let media_grid =
+            MediaGrid::new(
+                media_grid_box.clone(),
+                sender.input_sender(),
+                |output| match output {
+                    MediaCardOutput::Clicked(id) => LibraryPageInput::MediaItemSelected(id),
+                    MediaCardOutput::Play(id) => LibraryPageInput::MediaItemSelected(id),
+                    MediaCardOutput::GoToShow(id) => LibraryPageInput::MediaItemSelected(id),
+                    MediaCardOutput::MarkWatched(id) => LibraryPageInput::MarkWatched(id),
+                    MediaCardOutput::MarkUnwatched(id) => LibraryPageInput::MarkUnwatched(id),
+                    MediaCardOutput::Hide(id) => LibraryPageInput::Hide(id),
+                    MediaCardOutput::HideCollection {
+                        source_id,
+                        collection,
+                    } => LibraryPageInput::HideCollection {
+                        source_id,
+                        collection,
+                    },
+                    MediaCardOutput::PreviewRequested { id, .. } => {
+                        LibraryPageInput::PreviewRequested(id)
+                    }
+                    MediaCardOutput::SetFavorite { id, favorite } => {
+                        LibraryPageInput::SetFavorite { id, favorite }
+                    }
+                },
+            );
 
         // Create the image loader worker
         let image_loader =
@@ -560,11 +661,15 @@ impl AsyncComponent for LibraryPage {
 
         // Initialize view mode sort preferences with defaults
         let view_mode_sort_prefs = FilterState::default().view_mode_sort_prefs;
+        let kids = crate::services::config_service::config_service()
+            .get_config()
+            .await
+            .kids;
 
         let model = Self {
             db,
             library_id: None,
-            media_factory,
+            media_grid,
             image_loader,
             image_requests: std::collections::HashMap::new(),
             is_loading: false,
@@ -572,6 +677,7 @@ impl AsyncComponent for LibraryPage {
             batch_size: 50, // Number of items to render at once
             total_items: Vec::new(),
             has_loaded_all: false,
+            kids,
             sort_by: SortBy::Title,
             sort_order: SortOrder::Ascending,
             view_mode_sort_prefs,
@@ -581,6 +687,14 @@ impl AsyncComponent for LibraryPage {
             selected_genres: Vec::new(),
             available_genres: Vec::new(),
             genre_popover: None,
+            // Studio and network filtering
+            selected_studios: Vec::new(),
+            available_studios: Vec::new(),
+            // Collection and tag filtering
+            selected_collections: Vec::new(),
+            available_collections: Vec::new(),
+            selected_tags: Vec::new(),
+            available_tags: Vec::new(),
             // Year range filtering
             min_year: None,
             max_year: None,
@@ -589,6 +703,8 @@ impl AsyncComponent for LibraryPage {
             year_popover: None,
             // Rating filtering
             min_rating: None,
+            uhd_only: false,
+            hdr_only: false,
             rating_popover: None,
             // Watch status filtering
             watch_status_filter: WatchStatus::All,
@@ -599,15 +715,17 @@ impl AsyncComponent for LibraryPage {
             // Viewport tracking
             visible_start_idx: 0,
             visible_end_idx: 0,
+            scrolling_up: false,
             // Scroll debouncing
             scroll_debounce_handle: None,
             // Image loading state
-            images_requested: std::collections::HashSet::new(),
-            pending_image_cancels: Vec::new(),
+            images_requested: HashMap::new(),
+            images_loaded: std::collections::HashSet::new(),
             // Handler IDs for cleanup
             scroll_handler_id: None,
             // View mode selection
             selected_view_mode: ViewMode::All,
+            recent_grouping: RecentGrouping::Ungrouped,
             // View switcher widgets for header bar
             view_stack: adw::ViewStack::new(),
             view_switcher_bar: adw::ViewSwitcherBar::new(),
@@ -618,6 +736,19 @@ impl AsyncComponent for LibraryPage {
             filters_button: None,
             // Flag to defer factory clearing until items are ready to render
             needs_factory_clear: false,
+            jump_rail: None,
+            pending_jump: None,
+            position: None,
+            restore_position: None,
+            pending_focus: None,
+            watched_states: HashMap::new(),
+            type_ahead: TypeAhead::default(),
+            // Subscribe to MessageBroker for config updates
+            _broker_subscription: BROKER.subscribe(
+                &[Topic::Config, Topic::Data],
+                sender.input_sender(),
+                LibraryPageInput::BrokerMsg,
+            ),
         };
 
         let mut model = model;
@@ -627,31 +758,25 @@ impl AsyncComponent for LibraryPage {
         // Store reference to active filters box
         model.active_filters_box = Some(widgets.active_filters_box.clone());
 
+        // Fill the A–Z rail
+        for letter in jump::JUMP_LETTERS.chars() {
+            let button = gtk::Button::with_label(&letter.to_string());
+            button.add_css_class("flat");
+            button.add_css_class("jump-rail-button");
+            let sender_clone = sender.clone();
+            button.connect_clicked(move |_| {
+                sender_clone.input(LibraryPageInput::JumpToLetter(letter));
+            });
+            widgets.jump_rail.append(&button);
+        }
+        model.jump_rail = Some(widgets.jump_rail.clone());
+
         // Store reference to filters button and create unified filters popover
         model.filters_button = Some(widgets.filters_button.clone());
         let filters_popover = gtk::Popover::new();
         filters_popover.set_parent(&widgets.filters_button);
         model.filters_popover = Some(filters_popover);
 
-        // Subscribe to MessageBroker for config updates
-        {
-            let broker_sender = sender.input_sender().clone();
-            relm4::spawn(async move {
-                let (tx, rx) = relm4::channel::<BrokerMessage>();
-                BROKER.subscribe("LibraryPage".to_string(), tx).await;
-
-                while let Some(msg) = rx.recv().await {
-                    if broker_sender
-                        .send(LibraryPageInput::BrokerMsg(msg))
-                        .is_err()
-                    {
-                        // Component is shutting down, break the loop
-                        break;
-                    }
-                }
-            });
-        }
-
         // Create and set the genre filter popover
         let genre_popover = gtk::Popover::new();
         genre_popover.set_child(Some(&gtk::Box::new(gtk::Orientation::Vertical, 0)));
@@ -744,31 +869,7 @@ impl AsyncComponent for LibraryPage {
     ) {
         match msg {
             LibraryPageInput::SetLibrary(library_id) => {
-                debug!("Setting library: {}", library_id);
-
-                // Save current filter state before switching libraries
-                self.save_filter_state().await;
-
-                self.library_id = Some(library_id.clone());
-                self.loaded_count = 0;
-                self.total_items.clear();
-                self.has_loaded_all = false;
-                self.needs_factory_clear = true;
-                // Cancel pending images BEFORE clearing image_requests
-                // Otherwise cancel_pending_images() has no requests to cancel
-                self.cancel_pending_images();
-                self.image_requests.clear();
-                self.images_requested.clear();
-                self.visible_start_idx = 0;
-                self.visible_end_idx = 0;
-
-                // Send view switcher bar to main window header
-                // The view switcher bar provides navigation tabs in the header
-                sender
-                    .output(LibraryPageOutput::SetHeaderTitleWidget(
-                        self.view_switcher_bar.clone().upcast(),
-                    ))
-                    .expect("Failed to send header widget");
+                self.switch_library(library_id.clone(), &sender).await;
 
                 // Load saved filter state for the new library
                 let library_id_for_config = library_id.clone();
@@ -811,17 +912,30 @@ impl AsyncComponent for LibraryPage {
                 self.load_all_items(sender.clone());
             }
 
+            LibraryPageInput::SetLibraryWithFilter { library_id, filter } => {
+                self.switch_library(library_id, &sender).await;
+                self.apply_filter_state(&filter);
+                self.load_all_items(sender.clone());
+            }
+
             LibraryPageInput::RestoreFilterState(state) => {
                 debug!("Restoring filter state: {:?}", state);
                 self.apply_filter_state(&state);
 
                 // Trigger a refresh with the restored state
+                self.restore_position = self.position.clone();
                 self.loaded_count = 0;
                 self.needs_factory_clear = true;
                 self.image_requests.clear();
                 self.load_all_items(sender.clone());
             }
 
+            LibraryPageInput::RestorePosition(position) => {
+                debug!("Restoring grid position: {:?}", position);
+                self.position = Some(position.clone());
+                self.restore_position = Some(position);
+            }
+
             LibraryPageInput::LoadMoreBatch => {
                 if !self.is_loading && !self.has_loaded_all && !self.total_items.is_empty() {
                     debug!("Loading more items into view");
@@ -850,6 +964,33 @@ impl AsyncComponent for LibraryPage {
                 self.available_genres = available_genres;
                 debug!("Found {} unique genres", self.available_genres.len());
 
+                let mut available_studios: Vec<String> = items
+                    .iter()
+                    .flat_map(|item| item.get_studios())
+                    .collect::<std::collections::HashSet<_>>()
+                    .into_iter()
+                    .collect();
+                available_studios.sort();
+                self.available_studios = available_studios;
+
+                let mut available_collections: Vec<String> = items
+                    .iter()
+                    .flat_map(|item| item.get_collections())
+                    .collect::<std::collections::HashSet<_>>()
+                    .into_iter()
+                    .collect();
+                available_collections.sort();
+                self.available_collections = available_collections;
+
+                let mut available_tags: Vec<String> = items
+                    .iter()
+                    .flat_map(|item| item.get_tags())
+                    .collect::<std::collections::HashSet<_>>()
+                    .into_iter()
+                    .collect();
+                available_tags.sort();
+                self.available_tags = available_tags;
+
                 // Update the genre popover with available genres
                 if !self.available_genres.is_empty()
                     && let Some(ref popover) = self.genre_popover
@@ -921,6 +1062,19 @@ impl AsyncComponent for LibraryPage {
                                 .any(|selected| item_genres.contains(selected))
                         };
 
+                        // Studio filter
+                        let studio_match = self.selected_studios.is_empty() || {
+                            let item_studios = item.get_studios();
+                            self.selected_studios
+                                .iter()
+                                .any(|selected| item_studios.contains(selected))
+                        };
+
+                        // Collection and tag filters
+                        let collection_match =
+                            types::in_selected_collections(item, &self.selected_collections);
+                        let tag_match = types::has_selected_tag(item, &self.selected_tags);
+
                         // Year range filter
                         let year_match = if let Some(year) = item.year {
                             let min_match = self.selected_min_year.map_or(true, |min| year >= min);
@@ -940,37 +1094,16 @@ impl AsyncComponent for LibraryPage {
                             true
                         };
 
-                        // Determine watched status based on media type
-                        // Used for both user-added watch status filter and Unwatched view mode
-                        let is_watched = if item.media_type == "show" {
-                            // For TV shows, check metadata for watched_episode_count
-                            let watched_count =
-                                item.metadata
-                                    .as_ref()
-                                    .and_then(|m| m.get("watched_episode_count"))
-                                    .and_then(|v| v.as_u64())
-                                    .unwrap_or(0) as u32;
-                            let total_count = item
-                                .metadata
-                                .as_ref()
-                                .and_then(|m| m.get("total_episode_count"))
-                                .and_then(|v| v.as_u64())
-                                .unwrap_or(0) as u32;
+                        // Video quality filters
+                        let quality_match = (!self.uhd_only || item.is_uhd())
+                            && (!self.hdr_only || item.get_hdr_format().is_some());
 
-                            total_count > 0 && watched_count == total_count
-                        } else {
-                            // For movies and episodes, use playback_progress table
-                            playback_progress_map
-                                .get(&item.id)
-                                .map_or(false, |progress| progress.watched)
-                        };
+                        // Used for both user-added watch status filter and Unwatched view mode
+                        let watch_state = WatchState::of(item, playback_progress_map.get(&item.id));
+                        let is_watched = watch_state == WatchState::Watched;
 
                         // Watch status filter (user-added filter)
-                        let watch_status_match = match self.watch_status_filter {
-                            WatchStatus::All => true,
-                            WatchStatus::Watched => is_watched,
-                            WatchStatus::Unwatched => !is_watched,
-                        };
+                        let watch_status_match = self.watch_status_filter.matches(watch_state);
 
                         // Filter by view mode (built-in immutable filters)
                         let tab_match = match self.selected_view_mode {
@@ -994,8 +1127,12 @@ impl AsyncComponent for LibraryPage {
 
                         text_match
                             && genre_match
+                            && studio_match
+                            && collection_match
+                            && tag_match
                             && year_match
                             && rating_match
+                            && quality_match
                             && watch_status_match
                             && tab_match
                     })
@@ -1006,10 +1143,16 @@ impl AsyncComponent for LibraryPage {
                 self.is_loading = false;
 
                 // Clear image requests when loading new items
+                self.cancel_pending_images();
                 self.images_requested.clear();
+                self.images_loaded.clear();
 
                 // Update active filters display
                 self.update_active_filters_display(sender.clone());
+                self.update_jump_rail();
+
+                // Render up to where the grid was before it was reloaded
+                self.resume_position();
 
                 // Start rendering the first batch immediately
                 if !self.total_items.is_empty() {
@@ -1032,7 +1175,7 @@ impl AsyncComponent for LibraryPage {
                     // Clear factory before rendering first batch if needed
                     // This defers the clear until filtered items are ready, minimizing flashing
                     if self.needs_factory_clear && start_idx == 0 {
-                        self.media_factory.guard().clear();
+                        self.media_grid.clear();
                         self.needs_factory_clear = false;
                     }
 
@@ -1060,49 +1203,93 @@ impl AsyncComponent for LibraryPage {
                         std::collections::HashMap::new()
                     };
 
-                    {
-                        let mut factory_guard = self.media_factory.guard();
-                        for idx in start_idx..end_idx {
-                            let item = &self.total_items[idx];
-
-                            // Calculate watched status differently for shows vs movies/episodes
-                            let (watched, progress_percent) = if item.media_type == "show" {
-                                // For TV shows, check metadata for watched_episode_count
-                                let watched_count =
-                                    item.metadata
-                                        .as_ref()
-                                        .and_then(|m| m.get("watched_episode_count"))
-                                        .and_then(|v| v.as_u64())
-                                        .unwrap_or(0) as u32;
-                                let total_count =
-                                    item.metadata
-                                        .as_ref()
-                                        .and_then(|m| m.get("total_episode_count"))
-                                        .and_then(|v| v.as_u64())
-                                        .unwrap_or(0) as u32;
-
-                                if total_count > 0 {
-                                    let watched = watched_count > 0 && watched_count == total_count;
-                                    let progress =
-                                        if watched_count < total_count && watched_count > 0 {
-                                            watched_count as f64 / total_count as f64
-                                        } else {
-                                            0.0
-                                        };
-                                    (watched, progress)
+                    // Unwatched episode counts for the shows in this batch
+                    let batch_show_ids: Vec<String> = self.total_items[start_idx..end_idx]
+                        .iter()
+                        .filter(|item| item.media_type == "show")
+                        .map(|item| item.id.clone())
+                        .collect();
+                    let unwatched_counts = if !batch_show_ids.is_empty() {
+                        match crate::services::core::MediaService::get_unwatched_episode_counts(
+                            &self.db,
+                            &batch_show_ids,
+                        )
+                        .await
+                        {
+                            Ok(map) => map,
+                            Err(e) => {
+                                debug!("Failed to fetch unwatched episode counts: {}", e);
+                                std::collections::HashMap::new()
+                            }
+                        }
+                    } else {
+                        std::collections::HashMap::new()
+                    };
+
+                    let favorite_ids =
+                        match crate::services::core::FavoritesService::favorite_ids(&self.db).await
+                        {
+                            Ok(ids) => ids,
+                            Err(e) => {
+                                debug!("Failed to fetch favorites: {}", e);
+                                std::collections::HashSet::new()
+                            }
+                        };
+
+                    // Recently Added items go under the day or week they were added
+                    let today = chrono::Local::now().date_naive();
+                    let grouping = if self.selected_view_mode == ViewMode::RecentlyAdded {
+                        self.recent_grouping
+                    } else {
+                        RecentGrouping::Ungrouped
+                    };
+
+                    let mut cards = Vec::with_capacity(end_idx - start_idx);
+                    for idx in start_idx..end_idx {
+                        let item = &self.total_items[idx];
+
+                        // Calculate watched status differently for shows vs movies/episodes
+                        let (watched, progress_percent) = if item.media_type == "show" {
+                            // For TV shows, check metadata for watched_episode_count
+                            let watched_count =
+                                item.metadata
+                                    .as_ref()
+                                    .and_then(|m| m.get("watched_episode_count"))
+                                    .and_then(|v| v.as_u64())
+                                    .unwrap_or(0) as u32;
+                            let total_count = item
+                                .metadata
+                                .as_ref()
+                                .and_then(|m| m.get("total_episode_count"))
+                                .and_then(|v| v.as_u64())
+                                .unwrap_or(0) as u32;
+
+                            if total_count > 0 {
+                                let watched = watched_count > 0 && watched_count == total_count;
+                                let progress = if watched_count < total_count && watched_count > 0 {
+                                    watched_count as f64 / total_count as f64
                                 } else {
-                                    (false, 0.0)
-                                }
+                                    0.0
+                                };
+                                (watched, progress)
                             } else {
-                                // For movies and episodes, use playback_progress table
-                                if let Some(progress) = playback_progress_map.get(&item.id) {
-                                    (progress.watched, progress.get_progress_percentage() as f64)
-                                } else {
-                                    (false, 0.0)
-                                }
-                            };
+                                (false, 0.0)
+                            }
+                        } else {
+                            // For movies and episodes, use playback_progress table
+                            if let Some(progress) = playback_progress_map.get(&item.id) {
+                                (progress.watched, progress.get_progress_percentage() as f64)
+                            } else {
+                                (false, 0.0)
+                            }
+                        };
 
-                            let index = factory_guard.push_back(MediaCardInit {
+                        let header = item
+                            .added_at
+                            .and_then(|added_at| grouping.heading(added_at, today));
+                        cards.push((
+                            header,
+                            MediaCardInit {
                                 item: item.clone(),
                                 show_progress: progress_percent > 0.0,
                                 watched,
@@ -1111,16 +1298,21 @@ impl AsyncComponent for LibraryPage {
                                     .library_type
                                     .as_ref()
                                     .is_some_and(|t| t == "mixed"),
-                            });
+                                unwatched_count: unwatched_counts
+                                    .get(&item.id)
+                                    .copied()
+                                    .unwrap_or(0)
+                                    as u32,
+                                favorite: favorite_ids.contains(&item.id),
+                            },
+                        ));
 
-                            // Store the mapping but don't request images yet
-                            if item.poster_url.is_some() {
-                                let id = item.id.clone();
-                                self.image_requests
-                                    .insert(id.clone(), index.current_index());
-                            }
+                        // Store the mapping but don't request images yet
+                        if item.poster_url.is_some() {
+                            self.image_requests.insert(item.id.clone(), idx);
                         }
-                    } // Guard dropped here
+                    }
+                    self.media_grid.extend(cards);
 
                     self.loaded_count = end_idx;
                     self.has_loaded_all = end_idx >= self.total_items.len();
@@ -1133,10 +1325,35 @@ impl AsyncComponent for LibraryPage {
                 }
 
                 self.is_loading = false;
+
+                // Keep rendering until a pending jump target exists
+                if let Some(index) = self.pending_jump {
+                    if index < self.loaded_count {
+                        self.pending_jump = None;
+                        self.scroll_to_index(index);
+                    } else if self.loaded_count < self.total_items.len() {
+                        sender.input(LibraryPageInput::RenderBatch);
+                    } else {
+                        self.pending_jump = None;
+                    }
+                }
+            }
+
+            LibraryPageInput::JumpToLetter(letter) => {
+                self.jump_to_letter(letter, sender.clone());
+            }
+
+            LibraryPageInput::TypeAhead(c) => {
+                self.type_ahead(c, sender.clone());
+            }
+
+            LibraryPageInput::FocusGrid => {
+                self.media_grid.focus(self.visible_start_idx);
             }
 
             LibraryPageInput::MediaItemSelected(item_id) => {
                 debug!("Media item selected: {}", item_id);
+                self.update_position(Some(item_id.to_string()), &sender);
                 sender
                     .output(LibraryPageOutput::NavigateToMediaItem(item_id))
                     .expect("Failed to send output");
@@ -1144,40 +1361,110 @@ impl AsyncComponent for LibraryPage {
 
             LibraryPageInput::MarkWatched(media_id) => {
                 debug!("Marking item as watched: {}", media_id);
+                use crate::services::commands::media_commands::MarkWatchedCommand;
+
+                let cmd = MarkWatchedCommand {
+                    db: self.db.clone(),
+                    media_id,
+                };
+
+                let input_sender = sender.input_sender().clone();
+                sender.oneshot_command(async move {
+                    if let Some(offer) =
+                        UndoOffer::run(cmd, "Marked as watched", "Failed to mark item as watched")
+                            .await
+                    {
+                        input_sender.emit(LibraryPageInput::OfferUndo(offer));
+                    }
+                });
+            }
+
+            LibraryPageInput::MarkUnwatched(media_id) => {
+                debug!("Marking item as unwatched: {}", media_id);
                 let db = self.db.clone();
                 let media_id_clone = media_id.clone();
 
                 sender.oneshot_command(async move {
                     use crate::services::commands::Command;
-                    use crate::services::commands::media_commands::MarkWatchedCommand;
+                    use crate::services::commands::media_commands::MarkUnwatchedCommand;
 
-                    let cmd = MarkWatchedCommand {
+                    let cmd = MarkUnwatchedCommand {
                         db,
                         media_id: media_id_clone,
                     };
 
                     if let Err(e) = cmd.execute().await {
-                        tracing::error!("Failed to mark item as watched: {}", e);
+                        tracing::error!("Failed to mark item as unwatched: {}", e);
                     }
                 });
             }
 
-            LibraryPageInput::MarkUnwatched(media_id) => {
-                debug!("Marking item as unwatched: {}", media_id);
+            LibraryPageInput::Hide(media_id) => {
+                debug!("Hiding item from home: {}", media_id);
+                use crate::services::commands::media_commands::SetHiddenCommand;
+
+                let cmd = SetHiddenCommand {
+                    db: self.db.clone(),
+                    media_id,
+                    hidden: true,
+                };
+
+                let input_sender = sender.input_sender().clone();
+                sender.oneshot_command(async move {
+                    if let Some(offer) =
+                        UndoOffer::run(cmd, "Hidden from Home", "Failed to hide item").await
+                    {
+                        input_sender.emit(LibraryPageInput::OfferUndo(offer));
+                    }
+                });
+            }
+
+            LibraryPageInput::HideCollection {
+                source_id,
+                collection,
+            } => {
+                debug!("Hiding collection from home: {}", collection);
+                use crate::services::commands::media_commands::SetCollectionHiddenCommand;
+
+                let cmd = SetCollectionHiddenCommand {
+                    db: self.db.clone(),
+                    source_id,
+                    collection,
+                    hidden: true,
+                };
+
+                let input_sender = sender.input_sender().clone();
+                sender.oneshot_command(async move {
+                    if let Some(offer) = UndoOffer::run(
+                        cmd,
+                        "Collection hidden from Home",
+                        "Failed to hide collection",
+                    )
+                    .await
+                    {
+                        input_sender.emit(LibraryPageInput::OfferUndo(offer));
+                    }
+                });
+            }
+
+            LibraryPageInput::OfferUndo(offer) => offer.present(root),
+
+            LibraryPageInput::SetFavorite { id, favorite } => {
+                debug!("Setting favorite={} for item: {}", favorite, id);
                 let db = self.db.clone();
-                let media_id_clone = media_id.clone();
 
                 sender.oneshot_command(async move {
                     use crate::services::commands::Command;
-                    use crate::services::commands::media_commands::MarkUnwatchedCommand;
+                    use crate::services::commands::media_commands::SetFavoriteCommand;
 
-                    let cmd = MarkUnwatchedCommand {
+                    let cmd = SetFavoriteCommand {
                         db,
-                        media_id: media_id_clone,
+                        media_id: id,
+                        favorite,
                     };
 
                     if let Err(e) = cmd.execute().await {
-                        tracing::error!("Failed to mark item as unwatched: {}", e);
+                        tracing::error!("Failed to update favorite: {}", e);
                     }
                 });
             }
@@ -1215,6 +1502,14 @@ impl AsyncComponent for LibraryPage {
                 self.refresh(sender.clone());
             }
 
+            LibraryPageInput::SetRecentGrouping(grouping) => {
+                if self.recent_grouping != grouping {
+                    self.recent_grouping = grouping;
+                    self.save_filter_state().await;
+                    self.refresh(sender.clone());
+                }
+            }
+
             LibraryPageInput::SetFilter(text) => {
                 self.filter_text = text;
                 self.save_filter_state().await;
@@ -1268,6 +1563,45 @@ impl AsyncComponent for LibraryPage {
                 self.load_all_items(sender.clone());
             }
 
+            LibraryPageInput::ToggleStudioFilter(studio) => {
+                if self.selected_studios.contains(&studio) {
+                    self.selected_studios.retain(|s| s != &studio);
+                } else {
+                    self.selected_studios.push(studio);
+                }
+                self.save_filter_state().await;
+                self.loaded_count = 0;
+                self.needs_factory_clear = true;
+                self.image_requests.clear();
+                self.load_all_items(sender.clone());
+            }
+
+            LibraryPageInput::ToggleCollectionFilter(collection) => {
+                if self.selected_collections.contains(&collection) {
+                    self.selected_collections.retain(|c| c != &collection);
+                } else {
+                    self.selected_collections.push(collection);
+                }
+                self.save_filter_state().await;
+                self.loaded_count = 0;
+                self.needs_factory_clear = true;
+                self.image_requests.clear();
+                self.load_all_items(sender.clone());
+            }
+
+            LibraryPageInput::ToggleTagFilter(tag) => {
+                if self.selected_tags.contains(&tag) {
+                    self.selected_tags.retain(|t| t != &tag);
+                } else {
+                    self.selected_tags.push(tag);
+                }
+                self.save_filter_state().await;
+                self.loaded_count = 0;
+                self.needs_factory_clear = true;
+                self.image_requests.clear();
+                self.load_all_items(sender.clone());
+            }
+
             LibraryPageInput::ClearGenreFilters => {
                 self.selected_genres.clear();
                 self.save_filter_state().await;
@@ -1315,6 +1649,24 @@ impl AsyncComponent for LibraryPage {
                 self.load_all_items(sender.clone());
             }
 
+            LibraryPageInput::SetUhdOnly(uhd_only) => {
+                self.uhd_only = uhd_only;
+                self.save_filter_state().await;
+                self.loaded_count = 0;
+                self.needs_factory_clear = true;
+                self.image_requests.clear();
+                self.load_all_items(sender.clone());
+            }
+
+            LibraryPageInput::SetHdrOnly(hdr_only) => {
+                self.hdr_only = hdr_only;
+                self.save_filter_state().await;
+                self.loaded_count = 0;
+                self.needs_factory_clear = true;
+                self.image_requests.clear();
+                self.load_all_items(sender.clone());
+            }
+
             LibraryPageInput::SetWatchStatusFilter(status) => {
                 self.watch_status_filter = status;
                 self.save_filter_state().await;
@@ -1362,9 +1714,14 @@ impl AsyncComponent for LibraryPage {
             LibraryPageInput::ClearAllFilters => {
                 self.filter_text.clear();
                 self.selected_genres.clear();
+                self.selected_studios.clear();
+                self.selected_collections.clear();
+                self.selected_tags.clear();
                 self.selected_min_year = None;
                 self.selected_max_year = None;
                 self.min_rating = None;
+                self.uhd_only = false;
+                self.hdr_only = false;
                 self.watch_status_filter = WatchStatus::All;
                 self.selected_view_mode = ViewMode::All;
                 self.save_filter_state().await;
@@ -1382,6 +1739,15 @@ impl AsyncComponent for LibraryPage {
                     ActiveFilterType::Genre(genre) => {
                         self.selected_genres.retain(|g| g != &genre);
                     }
+                    ActiveFilterType::Studio(studio) => {
+                        self.selected_studios.retain(|s| s != &studio);
+                    }
+                    ActiveFilterType::Collection(collection) => {
+                        self.selected_collections.retain(|c| c != &collection);
+                    }
+                    ActiveFilterType::Tag(tag) => {
+                        self.selected_tags.retain(|t| t != &tag);
+                    }
                     ActiveFilterType::YearRange => {
                         self.selected_min_year = None;
                         self.selected_max_year = None;
@@ -1389,6 +1755,12 @@ impl AsyncComponent for LibraryPage {
                     ActiveFilterType::Rating => {
                         self.min_rating = None;
                     }
+                    ActiveFilterType::UhdOnly => {
+                        self.uhd_only = false;
+                    }
+                    ActiveFilterType::HdrOnly => {
+                        self.hdr_only = false;
+                    }
                     ActiveFilterType::WatchStatus => {
                         self.watch_status_filter = WatchStatus::All;
                     }
@@ -1435,17 +1807,20 @@ impl AsyncComponent for LibraryPage {
 
             LibraryPageInput::ImageLoaded { id, texture } => {
                 trace!("Image loaded for item: {}", id);
+                self.images_requested.remove(&id);
+                self.images_loaded.insert(id.clone());
                 if let Some(&index) = self.image_requests.get(&id) {
-                    self.media_factory
+                    self.media_grid
                         .send(index, MediaCardInput::ImageLoaded(texture));
                 }
             }
 
             LibraryPageInput::ImageLoadFailed { id } => {
                 trace!("Image load failed for item: {}", id);
+                self.images_requested.remove(&id);
+                self.images_loaded.insert(id.clone());
                 if let Some(&index) = self.image_requests.get(&id) {
-                    self.media_factory
-                        .send(index, MediaCardInput::ImageLoadFailed);
+                    self.media_grid.send(index, MediaCardInput::ImageLoadFailed);
                 }
             }
 
@@ -1468,6 +1843,12 @@ impl AsyncComponent for LibraryPage {
                 self.scroll_debounce_handle = None;
                 self.update_visible_range(root);
                 sender.input(LibraryPageInput::LoadVisibleImages);
+
+                let selected_item = self
+                    .position
+                    .as_ref()
+                    .and_then(|position| position.selected_item.clone());
+                self.update_position(selected_item, &sender);
             }
 
             LibraryPageInput::LoadVisibleImages => {
@@ -1477,8 +1858,18 @@ impl AsyncComponent for LibraryPage {
             LibraryPageInput::BrokerMsg(msg) => {
                 match msg {
                     BrokerMessage::Config(crate::ui::shared::broker::ConfigMessage::Updated {
-                        ..
+                        config,
                     }) => {
+                        // The kids profile changes which items are shown
+                        if config.kids != self.kids {
+                            self.kids = config.kids.clone();
+                            self.restore_position = self.position.clone();
+                            self.loaded_count = 0;
+                            self.needs_factory_clear = true;
+                            self.image_requests.clear();
+                            self.load_all_items(sender.clone());
+                        }
+
                         // Reload filter state when config changes
                         if let Some(ref library_id) = self.library_id {
                             let library_id_clone = library_id.clone();
@@ -1500,9 +1891,81 @@ impl AsyncComponent for LibraryPage {
                             });
                         }
                     }
+                    BrokerMessage::Data(DataMessage::PlaybackProgressUpdated {
+                        media_id,
+                        watched,
+                    }) => {
+                        // Progress is saved every few seconds during playback,
+                        // but only a change of the watched flag changes a count
+                        if self.watched_states.insert(media_id.clone(), watched) != Some(watched) {
+                            self.recount_show_of(media_id, &sender);
+                        }
+                    }
+                    BrokerMessage::Data(DataMessage::MediaUpdated { media_id }) => {
+                        // Whole shows and seasons change without an event per episode
+                        self.watched_states.clear();
+                        self.recount_show_of(media_id, &sender);
+                    }
+                    BrokerMessage::Data(DataMessage::FavoriteChanged { media_id, favorite }) => {
+                        if let Some(index) =
+                            self.total_items.iter().position(|item| item.id == media_id)
+                            && index < self.media_grid.len()
+                        {
+                            self.media_grid
+                                .send(index, MediaCardInput::SetFavorite(favorite));
+                        }
+                    }
+                    BrokerMessage::Data(DataMessage::MediaDeleted { media_id }) => {
+                        if self.total_items.iter().any(|item| item.id == media_id) {
+                            self.restore_position = self.position.clone();
+                            self.loaded_count = 0;
+                            self.needs_factory_clear = true;
+                            self.image_requests.clear();
+                            self.load_all_items(sender.clone());
+                        }
+                    }
                     _ => {}
                 }
             }
+
+            LibraryPageInput::PreviewRequested(id) => {
+                let db = self.db.clone();
+                let sender_clone = sender.clone();
+                relm4::spawn(async move {
+                    match crate::services::core::previews::preview_url(&db, &id).await {
+                        Ok(Some(url)) => {
+                            sender_clone.input(LibraryPageInput::PreviewReady { id, url })
+                        }
+                        Ok(None) => {}
+                        Err(e) => debug!("Failed to get preview for {}: {}", id, e),
+                    }
+                });
+            }
+
+            LibraryPageInput::PreviewReady { id, url } => {
+                // The card may have been removed while the stream was resolved
+                if let Some(index) = self
+                    .total_items
+                    .iter()
+                    .position(|item| item.id == id.as_str())
+                    && index < self.media_grid.len()
+                {
+                    self.media_grid
+                        .send(index, MediaCardInput::PlayPreview(url));
+                }
+            }
+
+            LibraryPageInput::ShowUnwatchedCountChanged { show_id, count } => {
+                if let Some(index) = self
+                    .total_items
+                    .iter()
+                    .position(|item| item.id == show_id.as_str())
+                    && index < self.media_grid.len()
+                {
+                    self.media_grid
+                        .send(index, MediaCardInput::SetUnwatchedCount(count as u32));
+                }
+            }
         }
     }
 
@@ -1519,10 +1982,5 @@ impl AsyncComponent for LibraryPage {
             handle.remove();
             debug!("Removed scroll debounce timer on library page shutdown");
         }
-
-        // Unsubscribe from MessageBroker
-        relm4::spawn(async move {
-            BROKER.unsubscribe("LibraryPage").await;
-        });
     }
 }
@@ -1,6 +1,10 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::db::entities::{MediaItemModel, PlaybackProgressModel};
+use crate::models::HomeSectionType;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SortBy {
     Title,
@@ -34,6 +38,8 @@ pub enum WatchStatus {
     All,
     Watched,
     Unwatched,
+    /// Started but not finished
+    InProgress,
 }
 
 impl Default for WatchStatus {
@@ -42,6 +48,73 @@ impl Default for WatchStatus {
     }
 }
 
+impl WatchStatus {
+    pub fn matches(self, state: WatchState) -> bool {
+        match self {
+            WatchStatus::All => true,
+            WatchStatus::Watched => state == WatchState::Watched,
+            WatchStatus::Unwatched => state != WatchState::Watched,
+            WatchStatus::InProgress => state == WatchState::InProgress,
+        }
+    }
+}
+
+/// How far an item has been watched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchState {
+    Unwatched,
+    InProgress,
+    Watched,
+}
+
+impl WatchState {
+    /// State of an item, from its playback progress for movies and
+    /// episodes and from its episode counts for shows
+    pub fn of(item: &MediaItemModel, progress: Option<&PlaybackProgressModel>) -> Self {
+        if item.media_type == "show" {
+            let count = |key: &str| {
+                item.metadata
+                    .as_ref()
+                    .and_then(|m| m.get(key))
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0)
+            };
+            let (watched, total) = (count("watched_episode_count"), count("total_episode_count"));
+            if total > 0 && watched == total {
+                Self::Watched
+            } else if watched > 0 {
+                Self::InProgress
+            } else {
+                Self::Unwatched
+            }
+        } else {
+            match progress {
+                Some(progress) if progress.watched => Self::Watched,
+                Some(progress) if progress.is_in_progress() => Self::InProgress,
+                _ => Self::Unwatched,
+            }
+        }
+    }
+}
+
+/// Whether an item is in one of the selected collections, everything
+/// passing while none are selected
+pub fn in_selected_collections(item: &MediaItemModel, selected: &[String]) -> bool {
+    selected.is_empty() || {
+        let collections = item.get_collections();
+        selected.iter().any(|name| collections.contains(name))
+    }
+}
+
+/// Whether an item has one of the selected tags, everything passing while
+/// none are selected
+pub fn has_selected_tag(item: &MediaItemModel, selected: &[String]) -> bool {
+    selected.is_empty() || {
+        let tags = item.get_tags();
+        selected.iter().any(|tag| tags.contains(tag))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ViewMode {
     All,
@@ -55,6 +128,46 @@ impl Default for ViewMode {
     }
 }
 
+/// Headings the Recently Added view is grouped under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecentGrouping {
+    Ungrouped,
+    Day,
+    Week,
+}
+
+impl Default for RecentGrouping {
+    fn default() -> Self {
+        Self::Ungrouped
+    }
+}
+
+impl RecentGrouping {
+    /// Heading of the group of an item added at `added_at` (UTC), counting
+    /// days in local time from `today`
+    pub fn heading(self, added_at: NaiveDateTime, today: NaiveDate) -> Option<String> {
+        let added = Local.from_utc_datetime(&added_at).date_naive();
+        match self {
+            RecentGrouping::Ungrouped => None,
+            RecentGrouping::Day => Some(match (today - added).num_days() {
+                0 => "Today".to_string(),
+                1 => "Yesterday".to_string(),
+                _ => added.format("%A, %B %-d").to_string(),
+            }),
+            RecentGrouping::Week => {
+                let week_start = |date: NaiveDate| {
+                    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+                };
+                Some(match (week_start(today) - week_start(added)).num_weeks() {
+                    0 => "This Week".to_string(),
+                    1 => "Last Week".to_string(),
+                    _ => format!("Week of {}", week_start(added).format("%B %-d")),
+                })
+            }
+        }
+    }
+}
+
 /// Statistics about filtered items
 #[derive(Debug, Clone, Default)]
 pub struct FilterStatistics {
@@ -76,8 +189,13 @@ pub struct ActiveFilter {
 pub enum ActiveFilterType {
     Text,
     Genre(String),
+    Studio(String),
+    Collection(String),
+    Tag(String),
     YearRange,
     Rating,
+    UhdOnly,
+    HdrOnly,
     WatchStatus,
 }
 
@@ -97,6 +215,14 @@ impl Default for ViewModeSortPrefs {
     }
 }
 
+/// Where a library grid was left: the first item in view and the one opened
+/// from it, found again by id once the grid is rebuilt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridPosition {
+    pub top_item: String,
+    pub selected_item: Option<String>,
+}
+
 /// Filter state that can be persisted and shared via URL
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct FilterState {
@@ -107,6 +233,12 @@ pub struct FilterState {
     pub filter_text: String,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub selected_genres: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selected_studios: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selected_collections: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selected_tags: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub selected_min_year: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -114,11 +246,17 @@ pub struct FilterState {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_rating: Option<f32>,
     #[serde(default)]
+    pub uhd_only: bool,
+    #[serde(default)]
+    pub hdr_only: bool,
+    #[serde(default)]
     pub watch_status_filter: WatchStatus,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub selected_media_type: Option<String>,
     #[serde(default)]
     pub selected_view_mode: ViewMode,
+    #[serde(default)]
+    pub recent_grouping: RecentGrouping,
 }
 
 impl Default for FilterState {
@@ -152,12 +290,18 @@ impl Default for FilterState {
             view_mode_sort_prefs,
             filter_text: String::new(),
             selected_genres: Vec::new(),
+            selected_studios: Vec::new(),
+            selected_collections: Vec::new(),
+            selected_tags: Vec::new(),
             selected_min_year: None,
             selected_max_year: None,
             min_rating: None,
+            uhd_only: false,
+            hdr_only: false,
             watch_status_filter: WatchStatus::All,
             selected_media_type: None,
             selected_view_mode: ViewMode::All,
+            recent_grouping: RecentGrouping::Ungrouped,
         }
     }
 }
@@ -184,12 +328,76 @@ impl FilterState {
             view_mode_sort_prefs,
             filter_text: page.filter_text.clone(),
             selected_genres: page.selected_genres.clone(),
+            selected_studios: page.selected_studios.clone(),
+            selected_collections: page.selected_collections.clone(),
+            selected_tags: page.selected_tags.clone(),
             selected_min_year: page.selected_min_year,
             selected_max_year: page.selected_max_year,
             min_rating: page.min_rating,
+            uhd_only: page.uhd_only,
+            hdr_only: page.hdr_only,
             watch_status_filter: page.watch_status_filter,
             selected_media_type: page.selected_media_type.clone(),
             selected_view_mode: page.selected_view_mode,
+            recent_grouping: page.recent_grouping,
+        }
+    }
+
+    /// The library view listing what a home row of `section_type` shows,
+    /// for the rows that have one
+    pub fn for_home_section(section_type: &HomeSectionType) -> Option<Self> {
+        let mut state = Self::default();
+        let sort_by = match section_type {
+            HomeSectionType::RecentlyAdded(_) => {
+                state.selected_view_mode = ViewMode::RecentlyAdded;
+                None
+            }
+            HomeSectionType::ContinueWatching | HomeSectionType::OnDeck => {
+                state.watch_status_filter = WatchStatus::InProgress;
+                Some(SortBy::LastWatched)
+            }
+            HomeSectionType::RecentlyPlayed => Some(SortBy::LastWatched),
+            HomeSectionType::TopRated => Some(SortBy::Rating),
+            HomeSectionType::Suggested
+            | HomeSectionType::Trending
+            | HomeSectionType::RecentPlaylists
+            | HomeSectionType::Favorites
+            | HomeSectionType::Custom(_) => return None,
+        };
+
+        if let Some(sort_by) = sort_by {
+            state.view_mode_sort_prefs.insert(
+                ViewMode::All,
+                ViewModeSortPrefs {
+                    sort_by,
+                    sort_order: SortOrder::Descending,
+                },
+            );
+        }
+        Some(state)
+    }
+
+    /// The library view of everything from a studio or network
+    pub fn for_studio(studio: &str) -> Self {
+        Self {
+            selected_studios: vec![studio.to_string()],
+            ..Self::default()
+        }
+    }
+
+    /// The library view of everything in a collection
+    pub fn for_collection(collection: &str) -> Self {
+        Self {
+            selected_collections: vec![collection.to_string()],
+            ..Self::default()
+        }
+    }
+
+    /// The library view of everything with a tag
+    pub fn for_tag(tag: &str) -> Self {
+        Self {
+            selected_tags: vec![tag.to_string()],
+            ..Self::default()
         }
     }
 
@@ -203,3 +411,168 @@ impl FilterState {
         serde_urlencoded::from_str(params)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn item(media_type: &str, metadata: Option<serde_json::Value>) -> MediaItemModel {
+        MediaItemModel {
+            id: "item".to_string(),
+            library_id: "library".to_string(),
+            source_id: "source".to_string(),
+            media_type: media_type.to_string(),
+            title: "Item".to_string(),
+            sort_title: None,
+            year: None,
+            duration_ms: None,
+            rating: None,
+            poster_url: None,
+            backdrop_url: None,
+            overview: None,
+            genres: None,
+            added_at: None,
+            updated_at: Utc::now().naive_utc(),
+            metadata,
+            parent_id: None,
+            season_number: None,
+            episode_number: None,
+            intro_marker_start_ms: None,
+            intro_marker_end_ms: None,
+            credits_marker_start_ms: None,
+            credits_marker_end_ms: None,
+            fetched_at: None,
+        }
+    }
+
+    fn progress(position_ms: i64, watched: bool) -> PlaybackProgressModel {
+        PlaybackProgressModel {
+            id: 1,
+            media_id: "item".to_string(),
+            user_id: None,
+            position_ms,
+            duration_ms: 100_000,
+            watched,
+            view_count: 0,
+            last_watched_at: None,
+            updated_at: Utc::now().naive_utc(),
+            play_queue_id: None,
+            play_queue_version: None,
+            play_queue_item_id: None,
+            source_id: None,
+            completed_chapters: None,
+        }
+    }
+
+    fn sort_of(state: &FilterState) -> SortBy {
+        state.view_mode_sort_prefs[&ViewMode::All].sort_by
+    }
+
+    #[test]
+    fn test_continue_watching_and_on_deck_list_items_in_progress() {
+        for section_type in [HomeSectionType::ContinueWatching, HomeSectionType::OnDeck] {
+            let state = FilterState::for_home_section(&section_type).unwrap();
+            assert_eq!(state.watch_status_filter, WatchStatus::InProgress);
+            assert_eq!(sort_of(&state), SortBy::LastWatched);
+        }
+    }
+
+    #[test]
+    fn test_home_sections_map_to_library_views() {
+        let recent =
+            FilterState::for_home_section(&HomeSectionType::RecentlyAdded("movie".to_string()))
+                .unwrap();
+        assert_eq!(recent.selected_view_mode, ViewMode::RecentlyAdded);
+
+        let top_rated = FilterState::for_home_section(&HomeSectionType::TopRated).unwrap();
+        assert_eq!(top_rated.watch_status_filter, WatchStatus::All);
+        assert_eq!(sort_of(&top_rated), SortBy::Rating);
+
+        assert!(FilterState::for_home_section(&HomeSectionType::Favorites).is_none());
+        assert!(FilterState::for_home_section(&HomeSectionType::Suggested).is_none());
+    }
+
+    #[test]
+    fn test_watch_state_of_movies_follows_progress() {
+        let movie = item("movie", None);
+        assert_eq!(WatchState::of(&movie, None), WatchState::Unwatched);
+        assert_eq!(
+            WatchState::of(&movie, Some(&progress(0, false))),
+            WatchState::Unwatched
+        );
+        assert_eq!(
+            WatchState::of(&movie, Some(&progress(30_000, false))),
+            WatchState::InProgress
+        );
+        assert_eq!(
+            WatchState::of(&movie, Some(&progress(30_000, true))),
+            WatchState::Watched
+        );
+    }
+
+    #[test]
+    fn test_watch_state_of_shows_follows_episode_counts() {
+        let show = |watched: u64, total: u64| {
+            item(
+                "show",
+                Some(serde_json::json!({
+                    "watched_episode_count": watched,
+                    "total_episode_count": total,
+                })),
+            )
+        };
+        assert_eq!(WatchState::of(&show(0, 10), None), WatchState::Unwatched);
+        assert_eq!(WatchState::of(&show(4, 10), None), WatchState::InProgress);
+        assert_eq!(WatchState::of(&show(10, 10), None), WatchState::Watched);
+    }
+
+    #[test]
+    fn test_collection_and_tag_filters_keep_items_with_any_selected() {
+        let movie = item(
+            "movie",
+            Some(serde_json::json!({
+                "collections": ["Classics", "Noir"],
+                "tags": ["Rewatch"],
+            })),
+        );
+        let untagged = item("movie", None);
+
+        assert!(in_selected_collections(&movie, &[]));
+        assert!(in_selected_collections(&untagged, &[]));
+        assert!(in_selected_collections(
+            &movie,
+            &["Sequels".to_string(), "Noir".to_string()]
+        ));
+        assert!(!in_selected_collections(&movie, &["Sequels".to_string()]));
+        assert!(!in_selected_collections(&untagged, &["Noir".to_string()]));
+
+        assert!(has_selected_tag(&movie, &[]));
+        assert!(has_selected_tag(&movie, &["Rewatch".to_string()]));
+        assert!(!has_selected_tag(&movie, &["4K".to_string()]));
+        assert!(!has_selected_tag(&untagged, &["Rewatch".to_string()]));
+    }
+
+    #[test]
+    fn test_saved_collection_and_tag_views_restore() {
+        let collection = FilterState::for_collection("Classics");
+        let json = serde_json::to_string(&collection).unwrap();
+        let restored: FilterState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.selected_collections, vec!["Classics"]);
+        assert!(restored.selected_tags.is_empty());
+
+        let tag = FilterState::for_tag("Rewatch");
+        let json = serde_json::to_string(&tag).unwrap();
+        let restored: FilterState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.selected_tags, vec!["Rewatch"]);
+        assert!(restored.selected_collections.is_empty());
+    }
+
+    #[test]
+    fn test_in_progress_filter_leaves_out_unstarted_and_finished_items() {
+        assert!(WatchStatus::InProgress.matches(WatchState::InProgress));
+        assert!(!WatchStatus::InProgress.matches(WatchState::Unwatched));
+        assert!(!WatchStatus::InProgress.matches(WatchState::Watched));
+        assert!(WatchStatus::Unwatched.matches(WatchState::InProgress));
+    }
+}
@@ -4,6 +4,7 @@ use relm4::prelude::*;
 use super::LibraryPage;
 use super::messages::LibraryPageInput;
 use super::types::WatchStatus;
+use crate::ui::shared::filter_widgets;
 
 impl LibraryPage {
     /// Update genre popover with filter options
@@ -306,7 +307,7 @@ impl LibraryPage {
         let unwatched_radio = gtk::CheckButton::with_label("Unwatched");
         unwatched_radio.set_group(Some(&all_radio));
         unwatched_radio.set_active(self.watch_status_filter == WatchStatus::Unwatched);
-        let sender_clone = sender;
+        let sender_clone = sender.clone();
         unwatched_radio.connect_toggled(move |btn| {
             if btn.is_active() {
                 sender_clone.input(LibraryPageInput::SetWatchStatusFilter(
@@ -316,6 +317,20 @@ impl LibraryPage {
         });
         radio_box.append(&unwatched_radio);
 
+        // In progress option
+        let in_progress_radio = gtk::CheckButton::with_label("In Progress");
+        in_progress_radio.set_group(Some(&all_radio));
+        in_progress_radio.set_active(self.watch_status_filter == WatchStatus::InProgress);
+        let sender_clone = sender;
+        in_progress_radio.connect_toggled(move |btn| {
+            if btn.is_active() {
+                sender_clone.input(LibraryPageInput::SetWatchStatusFilter(
+                    WatchStatus::InProgress,
+                ));
+            }
+        });
+        radio_box.append(&in_progress_radio);
+
         content.append(&radio_box);
 
         popover.set_child(Some(&content));
@@ -335,6 +350,22 @@ impl LibraryPage {
             let genre_section = self.build_genre_filter_section(sender.clone());
             content.append(&genre_section);
 
+            // Studio Filter Section, for libraries whose server reports studios
+            if !self.available_studios.is_empty() {
+                content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+                content.append(&self.build_studio_filter_section(sender.clone()));
+            }
+
+            // Collection and tag chips, for servers that report them
+            if !self.available_collections.is_empty() {
+                content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+                content.append(&self.build_collection_filter_section(sender.clone()));
+            }
+            if !self.available_tags.is_empty() {
+                content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+                content.append(&self.build_tag_filter_section(sender.clone()));
+            }
+
             // Separator
             content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
 
@@ -352,6 +383,13 @@ impl LibraryPage {
             // Separator
             content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
 
+            // Video Quality Filter Section
+            let quality_section = self.build_quality_filter_section(sender.clone());
+            content.append(&quality_section);
+
+            // Separator
+            content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
             // Watch Status Filter Section
             let watch_status_section = self.build_watch_status_filter_section(sender.clone());
             content.append(&watch_status_section);
@@ -365,120 +403,65 @@ impl LibraryPage {
         &self,
         sender: AsyncComponentSender<Self>,
     ) -> gtk::Box {
-        let section = gtk::Box::new(gtk::Orientation::Vertical, 6);
-
-        // Header
-        let header = gtk::Label::new(Some("Genre"));
-        header.set_halign(gtk::Align::Start);
-        header.add_css_class("heading");
-        section.append(&header);
-
-        // Scrolled window for genres
-        let scrolled = gtk::ScrolledWindow::new();
-        scrolled.set_max_content_height(200);
-        scrolled.set_propagate_natural_height(true);
-        scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
-
-        let genre_box = gtk::Box::new(gtk::Orientation::Vertical, 2);
-
-        if self.available_genres.is_empty() {
-            let no_genres = gtk::Label::new(Some("No genres available"));
-            no_genres.add_css_class("dim-label");
-            genre_box.append(&no_genres);
-        } else {
-            for genre in &self.available_genres {
-                let check_button = gtk::CheckButton::with_label(genre);
-                check_button.set_active(self.selected_genres.contains(genre));
-                check_button.add_css_class("flat");
-
-                let genre_clone = genre.clone();
-                let sender_clone = sender.clone();
-                check_button.connect_toggled(move |_| {
-                    sender_clone.input(LibraryPageInput::ToggleGenreFilter(genre_clone.clone()));
-                });
+        filter_widgets::build_genre_section(
+            &self.available_genres,
+            &self.selected_genres,
+            move |genre| sender.input(LibraryPageInput::ToggleGenreFilter(genre)),
+        )
+    }
 
-                genre_box.append(&check_button);
-            }
-        }
+    /// Build studio filter section for unified popover
+    pub(super) fn build_studio_filter_section(
+        &self,
+        sender: AsyncComponentSender<Self>,
+    ) -> gtk::Box {
+        filter_widgets::build_studio_section(
+            &self.available_studios,
+            &self.selected_studios,
+            move |studio| sender.input(LibraryPageInput::ToggleStudioFilter(studio)),
+        )
+    }
 
-        scrolled.set_child(Some(&genre_box));
-        section.append(&scrolled);
+    /// Build collection filter section for unified popover
+    pub(super) fn build_collection_filter_section(
+        &self,
+        sender: AsyncComponentSender<Self>,
+    ) -> gtk::Box {
+        filter_widgets::build_collection_section(
+            &self.available_collections,
+            &self.selected_collections,
+            move |collection| sender.input(LibraryPageInput::ToggleCollectionFilter(collection)),
+        )
+    }
 
-        section
+    /// Build tag filter section for unified popover
+    pub(super) fn build_tag_filter_section(&self, sender: AsyncComponentSender<Self>) -> gtk::Box {
+        filter_widgets::build_tag_section(&self.available_tags, &self.selected_tags, move |tag| {
+            sender.input(LibraryPageInput::ToggleTagFilter(tag))
+        })
     }
 
     /// Build year range filter section for unified popover
     pub(super) fn build_year_filter_section(&self, sender: AsyncComponentSender<Self>) -> gtk::Box {
-        let section = gtk::Box::new(gtk::Orientation::Vertical, 6);
-
-        // Header
-        let header = gtk::Label::new(Some("Year Range"));
-        header.set_halign(gtk::Align::Start);
-        header.add_css_class("heading");
-        section.append(&header);
-
-        // Min/Max year controls
-        let year_controls = gtk::Box::new(gtk::Orientation::Horizontal, 12);
-
-        // Min year
-        let min_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
-        let min_label = gtk::Label::new(Some("From:"));
-        min_label.set_halign(gtk::Align::Start);
-        min_label.add_css_class("dim-label");
-        min_box.append(&min_label);
-
-        let min_entry = gtk::SpinButton::with_range(
-            self.min_year.unwrap_or(1900) as f64,
-            self.max_year.unwrap_or(2100) as f64,
-            1.0,
-        );
-        if let Some(year) = self.selected_min_year {
-            min_entry.set_value(year as f64);
-        } else if let Some(year) = self.min_year {
-            min_entry.set_value(year as f64);
-        }
-
-        let sender_clone = sender.clone();
-        let max_year = self.selected_max_year;
-        min_entry.connect_value_changed(move |spin| {
-            let min = Some(spin.value_as_int());
-            sender_clone.input(LibraryPageInput::SetYearRange { min, max: max_year });
-        });
-
-        min_box.append(&min_entry);
-        year_controls.append(&min_box);
-
-        // Max year
-        let max_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
-        let max_label = gtk::Label::new(Some("To:"));
-        max_label.set_halign(gtk::Align::Start);
-        max_label.add_css_class("dim-label");
-        max_box.append(&max_label);
-
-        let max_entry = gtk::SpinButton::with_range(
-            self.min_year.unwrap_or(1900) as f64,
-            self.max_year.unwrap_or(2100) as f64,
-            1.0,
-        );
-        if let Some(year) = self.selected_max_year {
-            max_entry.set_value(year as f64);
-        } else if let Some(year) = self.max_year {
-            max_entry.set_value(year as f64);
-        }
-
-        let sender_clone = sender.clone();
-        let min_year = self.selected_min_year;
-        max_entry.connect_value_changed(move |spin| {
-            let max = Some(spin.value_as_int());
-            sender_clone.input(LibraryPageInput::SetYearRange { min: min_year, max });
-        });
-
-        max_box.append(&max_entry);
-        year_controls.append(&max_box);
-
-        section.append(&year_controls);
+        filter_widgets::build_year_section(
+            (self.min_year, self.max_year),
+            (self.selected_min_year, self.selected_max_year),
+            move |min, max| sender.input(LibraryPageInput::SetYearRange { min, max }),
+        )
+    }
 
-        section
+    /// Build video quality filter section for unified popover
+    pub(super) fn build_quality_filter_section(
+        &self,
+        sender: AsyncComponentSender<Self>,
+    ) -> gtk::Box {
+        let hdr_sender = sender.clone();
+        filter_widgets::build_quality_section(
+            self.uhd_only,
+            self.hdr_only,
+            move |uhd_only| sender.input(LibraryPageInput::SetUhdOnly(uhd_only)),
+            move |hdr_only| hdr_sender.input(LibraryPageInput::SetHdrOnly(hdr_only)),
+        )
     }
 
     /// Build rating filter section for unified popover
@@ -486,35 +469,9 @@ impl LibraryPage {
         &self,
         sender: AsyncComponentSender<Self>,
     ) -> gtk::Box {
-        let section = gtk::Box::new(gtk::Orientation::Vertical, 6);
-
-        // Header
-        let header = gtk::Label::new(Some("Minimum Rating"));
-        header.set_halign(gtk::Align::Start);
-        header.add_css_class("heading");
-        section.append(&header);
-
-        // Rating scale
-        let scale = gtk::Scale::with_range(gtk::Orientation::Horizontal, 0.0, 10.0, 0.5);
-        scale.set_draw_value(true);
-        scale.set_value_pos(gtk::PositionType::Right);
-
-        if let Some(rating) = self.min_rating {
-            scale.set_value(rating as f64);
-        } else {
-            scale.set_value(0.0);
-        }
-
-        let sender_clone = sender.clone();
-        scale.connect_value_changed(move |scale| {
-            let value = scale.value() as f32;
-            let rating = if value > 0.0 { Some(value) } else { None };
-            sender_clone.input(LibraryPageInput::SetRatingFilter(rating));
-        });
-
-        section.append(&scale);
-
-        section
+        filter_widgets::build_rating_section(self.min_rating, move |rating| {
+            sender.input(LibraryPageInput::SetRatingFilter(rating))
+        })
     }
 
     /// Build watch status filter section for unified popover
@@ -522,53 +479,8 @@ impl LibraryPage {
         &self,
         sender: AsyncComponentSender<Self>,
     ) -> gtk::Box {
-        let section = gtk::Box::new(gtk::Orientation::Vertical, 6);
-
-        // Header
-        let header = gtk::Label::new(Some("Watch Status"));
-        header.set_halign(gtk::Align::Start);
-        header.add_css_class("heading");
-        section.append(&header);
-
-        // Radio buttons
-        let all_radio = gtk::CheckButton::with_label("All");
-        all_radio.set_active(self.watch_status_filter == WatchStatus::All);
-
-        let watched_radio = gtk::CheckButton::with_label("Watched");
-        watched_radio.set_group(Some(&all_radio));
-        watched_radio.set_active(self.watch_status_filter == WatchStatus::Watched);
-
-        let unwatched_radio = gtk::CheckButton::with_label("Unwatched");
-        unwatched_radio.set_group(Some(&all_radio));
-        unwatched_radio.set_active(self.watch_status_filter == WatchStatus::Unwatched);
-
-        let sender_clone = sender.clone();
-        all_radio.connect_toggled(move |btn| {
-            if btn.is_active() {
-                sender_clone.input(LibraryPageInput::SetWatchStatusFilter(WatchStatus::All));
-            }
-        });
-
-        let sender_clone = sender.clone();
-        watched_radio.connect_toggled(move |btn| {
-            if btn.is_active() {
-                sender_clone.input(LibraryPageInput::SetWatchStatusFilter(WatchStatus::Watched));
-            }
-        });
-
-        let sender_clone = sender.clone();
-        unwatched_radio.connect_toggled(move |btn| {
-            if btn.is_active() {
-                sender_clone.input(LibraryPageInput::SetWatchStatusFilter(
-                    WatchStatus::Unwatched,
-                ));
-            }
-        });
-
-        section.append(&all_radio);
-        section.append(&watched_radio);
-        section.append(&unwatched_radio);
-
-        section
+        filter_widgets::build_watch_status_section(self.watch_status_filter, move |status| {
+            sender.input(LibraryPageInput::SetWatchStatusFilter(status))
+        })
     }
 }
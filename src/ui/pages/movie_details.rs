@@ -1,11 +1,29 @@
-use crate::models::{MediaItem, MediaItemId, Movie};
-use crate::services::commands::Command;
+use crate::db::entities::QualityPreset;
+use crate::db::entities::media_items::UHD_MIN_HEIGHT;
+use crate::db::repository::{MediaRepositoryImpl, Repository};
+use crate::models::{LibraryId, MediaItem, MediaItemId, Movie};
 use crate::services::commands::media_commands::{
-    GetItemDetailsCommand, MarkUnwatchedCommand, MarkWatchedCommand,
+    DeleteFromServerCommand, GetItemDetailsCommand, MarkUnwatchedCommand, MarkWatchedCommand,
+    RemoveDownloadCommand, SetFavoriteCommand, SetLabelCommand,
 };
-use crate::ui::shared::broker::{BROKER, BrokerMessage};
-use crate::ui::shared::image_helpers::load_image_from_url;
+use crate::services::commands::{Command, UndoableCommand};
+use crate::services::core::dominant_color::{self, DominantColor};
+use crate::services::core::theme_music::theme_music_url;
+use crate::services::core::{
+    DeletionService, DownloadService, FavoritesService, LabelsService, connectivity,
+};
+use crate::ui::pages::library::FilterState;
+use crate::ui::shared::accessibility;
+use crate::ui::shared::broker::{
+    BROKER, BrokerMessage, ConnectivityMessage, DataMessage, Subscription, Topic,
+};
+use crate::ui::shared::delete_confirmation;
+use crate::ui::shared::image_helpers::{load_image_from_url, load_image_with_color};
+use crate::ui::shared::label_chips::{self, ChipAction, LabelChips};
 use crate::ui::shared::person_card::create_person_card;
+use crate::ui::shared::theme_music;
+use crate::ui::shared::tint;
+use crate::ui::shared::undo_toast::UndoOffer;
 use adw::prelude::*;
 use libadwaita as adw;
 use relm4::RelmWidgetExt;
@@ -22,24 +40,62 @@ pub struct MovieDetailsPage {
     db: Arc<crate::db::connection::DatabaseConnection>,
     loading: bool,
     genre_box: gtk::Box,
+    studio_box: gtk::Box,
+    label_chips: LabelChips,
     cast_box: gtk::Box,
     crew_box: gtk::Box,
     poster_texture: Option<gtk::gdk::Texture>,
     backdrop_texture: Option<gtk::gdk::Texture>,
+    hero_tint: gtk::Box,
     person_textures: HashMap<String, gtk::gdk::Texture>,
     full_metadata_loaded: bool,
+    keep_download_check: gtk::CheckButton,
+    delete_download_button: gtk::Button,
+    download_pinned: bool,
+    favorite: bool,
+    /// Whether the server lets the user delete the movie
+    can_delete: bool,
+    /// Whether labels of the movie can be edited on its server
+    can_edit_labels: bool,
+    /// Whether every server is unreachable, ruling out changes made on them
+    offline: bool,
+    theme_music: Option<gtk::MediaFile>,
+    _broker_subscription: Subscription,
 }
 
 #[derive(Debug)]
 pub enum MovieDetailsInput {
     PlayMovie,
     ToggleWatched,
+    ToggleFavorite,
+    Download(QualityPreset),
+    SetKeepDownload(bool),
+    DeleteDownload,
+    /// Open the item's library filtered to what it shares a studio,
+    /// collection or tag with
+    BrowseLibrary(FilterState),
+    AddLabel,
+    SetLabel {
+        label: String,
+        present: bool,
+    },
+    ConfirmDelete,
+    DeleteFromServer,
     BrokerMsg(BrokerMessage),
 }
 
 #[derive(Debug)]
 pub enum MovieDetailsOutput {
     PlayMedia(MediaItemId),
+    /// Open the movie's library filtered to a studio
+    NavigateToLibraryView {
+        library_id: LibraryId,
+        filter: FilterState,
+    },
+    /// The movie was deleted from its server
+    NavigateBack,
+    /// Tell the user something went wrong
+    ShowToast(String),
 }
 
 #[derive(Debug)]
@@ -47,6 +103,7 @@ pub enum MovieDetailsCommand {
     LoadDetails,
     LoadPosterImage {
         url: String,
+        find_color: bool,
     },
     LoadBackdropImage {
         url: String,
@@ -57,9 +114,11 @@ pub enum MovieDetailsCommand {
     },
     PosterImageLoaded {
         texture: gtk::gdk::Texture,
+        color: Option<DominantColor>,
     },
     BackdropImageLoaded {
         texture: gtk::gdk::Texture,
+        color: Option<DominantColor>,
     },
     PersonImageLoaded {
         person_id: String,
@@ -67,6 +126,13 @@ pub enum MovieDetailsCommand {
     },
     LoadFullMetadata,
     FullMetadataLoaded,
+    ThemeMusicLoaded {
+        url: Option<String>,
+    },
+    DeletePermissionLoaded(bool),
+    LabelsEditableLoaded(bool),
+    /// An action ran, offer taking it back unless it failed
+    OfferUndo(Option<UndoOffer>),
 }
 
 #[allow(unused_assignments)]
@@ -102,6 +168,17 @@ impl AsyncComponent for MovieDetailsPage {
                         set_visible: !model.loading,
                     },
 
+                    // Scrim keeping the poster and title readable over bright backdrops
+                    add_overlay = &gtk::Box {
+                        add_css_class: "hero-scrim",
+                        set_can_target: false,
+                        #[watch]
+                        set_visible: model.backdrop_texture.is_some(),
+                    },
+
+                    // Wash of the artwork's dominant color
+                    add_overlay: &model.hero_tint,
+
                     // Enhanced gradient overlay with glass morphism
                     add_overlay = &gtk::Box {
                         add_css_class: "hero-gradient-modern",
@@ -239,6 +316,25 @@ impl AsyncComponent for MovieDetailsPage {
                                                 .unwrap_or_default(),
                                         },
                                     },
+
+                                    // Video and audio quality pill
+                                    gtk::Box {
+                                        add_css_class: "metadata-pill-modern",
+                                        add_css_class: "interactive-element",
+                                        #[watch]
+                                        set_visible: model.movie.as_ref().is_some_and(|m| !quality_summary(m).is_empty()),
+
+                                        gtk::Label {
+                                            set_margin_start: 12,
+                                            set_margin_end: 12,
+                                            set_margin_top: 6,
+                                            set_margin_bottom: 6,
+                                            #[watch]
+                                            set_label: &model.movie.as_ref()
+                                                .map(quality_summary)
+                                                .unwrap_or_default(),
+                                        },
+                                    },
                                 },
 
                                 // Action buttons - separated from metadata
@@ -280,6 +376,11 @@ impl AsyncComponent for MovieDetailsPage {
                                         } else {
                                             "Mark as watched"
                                         }),
+                                        update_property: &[gtk::accessible::Property::Label("Watched")],
+                                        #[watch]
+                                        update_state: &[accessibility::pressed(
+                                            model.movie.as_ref().is_some_and(|m| m.watched),
+                                        )],
 
                                         gtk::Box {
                                             set_width_request: 20,
@@ -302,6 +403,51 @@ impl AsyncComponent for MovieDetailsPage {
 
                                         connect_clicked => MovieDetailsInput::ToggleWatched,
                                     },
+
+                                    gtk::Button {
+                                        add_css_class: "action-button-secondary",
+                                        add_css_class: "interactive-element",
+                                        add_css_class: "favorite-toggle",
+                                        set_icon_name: "emblem-favorite-symbolic",
+                                        #[watch]
+                                        set_class_active: ("favorited", model.favorite),
+                                        #[watch]
+                                        set_tooltip_text: Some(if model.favorite {
+                                            "Remove from Favorites"
+                                        } else {
+                                            "Add to Favorites"
+                                        }),
+                                        update_property: &[gtk::accessible::Property::Label("Favorite")],
+                                        #[watch]
+                                        update_state: &[accessibility::pressed(model.favorite)],
+                                        #[watch]
+                                        set_sensitive: !model.offline,
+
+                                        connect_clicked => MovieDetailsInput::ToggleFavorite,
+                                    },
+
+                                    #[name = "download_button"]
+                                    gtk::MenuButton {
+                                        add_css_class: "action-button-secondary",
+                                        add_css_class: "interactive-element",
+                                        set_icon_name: "folder-download-symbolic",
+                                        set_tooltip_text: Some("Download for offline playback"),
+                                        update_property: &[gtk::accessible::Property::Label("Download")],
+                                    },
+
+                                    gtk::Button {
+                                        add_css_class: "action-button-secondary",
+                                        add_css_class: "interactive-element",
+                                        set_icon_name: "user-trash-symbolic",
+                                        set_tooltip_text: Some("Delete from Server"),
+                                        update_property: &[gtk::accessible::Property::Label("Delete from Server")],
+                                        #[watch]
+                                        set_visible: model.can_delete,
+                                        #[watch]
+                                        set_sensitive: !model.offline,
+
+                                        connect_clicked => MovieDetailsInput::ConfirmDelete,
+                                    },
                                 },
                             },
                         },
@@ -328,6 +474,25 @@ impl AsyncComponent for MovieDetailsPage {
                         append: &model.genre_box,
                     },
 
+                    // More from the movie's studios and collections
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 6,
+                        #[watch]
+                        set_visible: model.movie.as_ref().map(|m| !m.studios.is_empty() || !m.collections.is_empty()).unwrap_or(false),
+
+                        append: &model.studio_box,
+                    },
+
+                    // Tags and labels, each browsing the library for the others with it
+                    gtk::Box {
+                        #[watch]
+                        set_visible: model.can_edit_labels
+                            || model.movie.as_ref().map(|m| !m.tags.is_empty()).unwrap_or(false),
+
+                        append: model.label_chips.widget(),
+                    },
+
                     // Removed redundant overview section since it's now in the hero
 
                     // Cast
@@ -386,6 +551,23 @@ impl AsyncComponent for MovieDetailsPage {
             .spacing(8)
             .css_classes(["stagger-animation"])
             .build();
+        let studio_box = gtk::Box::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .spacing(8)
+            .build();
+        let input_sender = sender.input_sender().clone();
+        let label_chips = LabelChips::new(move |action| {
+            input_sender.emit(match action {
+                ChipAction::Browse(tag) => {
+                    MovieDetailsInput::BrowseLibrary(FilterState::for_tag(&tag))
+                }
+                ChipAction::Remove(label) => MovieDetailsInput::SetLabel {
+                    label,
+                    present: false,
+                },
+                ChipAction::Add => MovieDetailsInput::AddLabel,
+            });
+        });
         let cast_box = gtk::Box::builder()
             .orientation(gtk::Orientation::Horizontal)
             .spacing(16)
@@ -397,39 +579,139 @@ impl AsyncComponent for MovieDetailsPage {
             .css_classes(["stagger-animation"])
             .build();
 
+        // Pinned downloads survive the watched-download cleanup
+        let keep_download_check = gtk::CheckButton::builder()
+            .label("Keep After Watching")
+            .sensitive(false)
+            .margin_start(6)
+            .margin_end(6)
+            .build();
+        {
+            let input_sender = sender.input_sender().clone();
+            keep_download_check.connect_toggled(move |check| {
+                input_sender.emit(MovieDetailsInput::SetKeepDownload(check.is_active()));
+            });
+        }
+        let delete_download_button = gtk::Button::builder()
+            .label("Delete Download")
+            .css_classes(["flat", "error"])
+            .sensitive(false)
+            .build();
+
+        // Tinted once the artwork loads, right away if its color is known
+        let hero_tint = gtk::Box::builder()
+            .css_classes(["hero-tint"])
+            .can_target(false)
+            .build();
+        tint::apply(&hero_tint, dominant_color::cached(init.0.as_str()));
+
         let model = Self {
             movie: None,
             item_id: init.0.clone(),
             db: init.1,
             loading: true,
             genre_box: genre_box.clone(),
+            studio_box: studio_box.clone(),
+            label_chips,
             cast_box: cast_box.clone(),
             crew_box: crew_box.clone(),
             poster_texture: None,
             backdrop_texture: None,
+            hero_tint,
             person_textures: HashMap::new(),
             full_metadata_loaded: false,
+            keep_download_check: keep_download_check.clone(),
+            delete_download_button: delete_download_button.clone(),
+            download_pinned: false,
+            favorite: false,
+            can_delete: false,
+            can_edit_labels: false,
+            offline: connectivity::is_offline(),
+            theme_music: None,
+            // Subscribe to MessageBroker for playback progress updates
+            _broker_subscription: BROKER.subscribe(
+                &[Topic::Data, Topic::Connectivity],
+                sender.input_sender(),
+                MovieDetailsInput::BrokerMsg,
+            ),
         };
 
         let widgets = view_output!();
 
-        // Subscribe to MessageBroker for playback progress updates
+        // Quality choices for offline downloads
+        let quality_box = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(2)
+            .build();
+        let quality_popover = gtk::Popover::builder().child(&quality_box).build();
+        for preset in QualityPreset::all() {
+            let button = gtk::Button::builder()
+                .label(preset.label())
+                .css_classes(["flat"])
+                .build();
+            let input_sender = sender.input_sender().clone();
+            let popover = quality_popover.clone();
+            button.connect_clicked(move |_| {
+                popover.popdown();
+                input_sender.emit(MovieDetailsInput::Download(preset.clone()));
+            });
+            quality_box.append(&button);
+        }
+        quality_box.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+        quality_box.append(&keep_download_check);
         {
-            let broker_sender = sender.input_sender().clone();
-            relm4::spawn(async move {
-                let (tx, rx) = relm4::channel::<BrokerMessage>();
-                BROKER.subscribe("MovieDetailsPage".to_string(), tx).await;
-
-                while let Some(msg) = rx.recv().await {
-                    broker_sender
-                        .send(MovieDetailsInput::BrokerMsg(msg))
-                        .unwrap();
-                }
+            let input_sender = sender.input_sender().clone();
+            let popover = quality_popover.clone();
+            delete_download_button.connect_clicked(move |_| {
+                popover.popdown();
+                input_sender.emit(MovieDetailsInput::DeleteDownload);
             });
         }
+        quality_box.append(&delete_download_button);
+        widgets.download_button.set_popover(Some(&quality_popover));
 
         sender.oneshot_command(async { MovieDetailsCommand::LoadDetails });
 
+        {
+            let db = (*model.db).clone();
+            let item_id = model.item_id.clone();
+            sender.oneshot_command(async move {
+                let url = theme_music_url(&db, &item_id).await.unwrap_or_else(|e| {
+                    tracing::debug!("No theme music for {}: {}", item_id, e);
+                    None
+                });
+                MovieDetailsCommand::ThemeMusicLoaded { url }
+            });
+        }
+
+        {
+            let db = (*model.db).clone();
+            let item_id = model.item_id.clone();
+            sender.oneshot_command(async move {
+                let can_delete = DeletionService::can_delete(&db, &item_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::debug!("Can't tell if {} may be deleted: {}", item_id, e);
+                        false
+                    });
+                MovieDetailsCommand::DeletePermissionLoaded(can_delete)
+            });
+        }
+
+        {
+            let db = (*model.db).clone();
+            let item_id = model.item_id.clone();
+            sender.oneshot_command(async move {
+                let editable = LabelsService::can_edit_labels(&db, &item_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::debug!("Can't tell if labels of {} may be edited: {}", item_id, e);
+                        false
+                    });
+                MovieDetailsCommand::LabelsEditableLoaded(editable)
+            });
+        }
+
         AsyncComponentParts { model, widgets }
     }
 
@@ -437,7 +719,7 @@ impl AsyncComponent for MovieDetailsPage {
         &mut self,
         msg: Self::Input,
         sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         match msg {
             MovieDetailsInput::PlayMovie => {
@@ -454,23 +736,161 @@ impl AsyncComponent for MovieDetailsPage {
                     let media_id = self.item_id.clone();
                     let watched = movie.watched;
 
-                    relm4::spawn(async move {
-                        let result = if watched {
-                            // Mark as unwatched
+                    if watched {
+                        relm4::spawn(async move {
                             let cmd = MarkUnwatchedCommand { db, media_id };
-                            Command::execute(&cmd).await
-                        } else {
-                            // Mark as watched
-                            let cmd = MarkWatchedCommand { db, media_id };
-                            Command::execute(&cmd).await
-                        };
-
-                        if let Err(e) = result {
-                            error!("Failed to toggle watch status: {}", e);
-                        }
-                    });
+                            if let Err(e) = Command::execute(&cmd).await {
+                                error!("Failed to toggle watch status: {}", e);
+                            }
+                        });
+                    } else {
+                        let cmd = MarkWatchedCommand { db, media_id };
+                        sender.oneshot_command(async move {
+                            MovieDetailsCommand::OfferUndo(
+                                UndoOffer::run(
+                                    cmd,
+                                    "Marked as watched",
+                                    "Failed to toggle watch status",
+                                )
+                                .await,
+                            )
+                        });
+                    }
+                }
+            }
+            MovieDetailsInput::ToggleFavorite => {
+                let db = (*self.db).clone();
+                let media_id = self.item_id.clone();
+                let favorite = !self.favorite;
+
+                relm4::spawn(async move {
+                    let cmd = SetFavoriteCommand {
+                        db,
+                        media_id,
+                        favorite,
+                    };
+                    if let Err(e) = Command::execute(&cmd).await {
+                        error!("Failed to update favorite: {}", e);
+                    }
+                });
+            }
+            MovieDetailsInput::Download(quality) => {
+                let db = (*self.db).clone();
+                let media_id = self.item_id.clone();
+                self.keep_download_check.set_sensitive(true);
+                self.delete_download_button.set_sensitive(true);
+
+                relm4::spawn(async move {
+                    if let Err(e) = DownloadService::queue_download(&db, &media_id, quality).await {
+                        error!("Failed to queue download: {}", e);
+                    }
+                });
+            }
+            MovieDetailsInput::SetKeepDownload(pinned) => {
+                // Reflecting the stored state in the check button must not write it back
+                if pinned == self.download_pinned {
+                    return;
+                }
+                self.download_pinned = pinned;
+
+                let db = (*self.db).clone();
+                let media_id = self.item_id.clone();
+                relm4::spawn(async move {
+                    if let Err(e) = DownloadService::set_pinned(&db, &media_id, pinned).await {
+                        error!("Failed to update download pin: {}", e);
+                    }
+                });
+            }
+            MovieDetailsInput::DeleteDownload => {
+                let cmd = RemoveDownloadCommand {
+                    db: (*self.db).clone(),
+                    media_id: self.item_id.clone(),
+                };
+                sender.oneshot_command(async move {
+                    MovieDetailsCommand::OfferUndo(
+                        UndoOffer::run(cmd, "Download deleted", "Failed to delete download").await,
+                    )
+                });
+            }
+            MovieDetailsInput::BrowseLibrary(filter) => {
+                let media_repo = MediaRepositoryImpl::new((*self.db).clone());
+                match media_repo.find_by_id(self.item_id.as_str()).await {
+                    Ok(Some(item)) => {
+                        sender
+                            .output(MovieDetailsOutput::NavigateToLibraryView {
+                                library_id: LibraryId::new(item.library_id),
+                                filter,
+                            })
+                            .unwrap();
+                    }
+                    Ok(None) => error!("Movie {} not found", self.item_id),
+                    Err(e) => error!("Failed to find the movie's library: {}", e),
                 }
             }
+            MovieDetailsInput::AddLabel => {
+                let input_sender = sender.input_sender().clone();
+                label_chips::ask_for_label(root, move |label| {
+                    input_sender.emit(MovieDetailsInput::SetLabel {
+                        label,
+                        present: true,
+                    });
+                });
+            }
+            MovieDetailsInput::SetLabel { label, present } => {
+                let cmd = SetLabelCommand {
+                    db: (*self.db).clone(),
+                    media_id: self.item_id.clone(),
+                    label,
+                    present,
+                };
+                let output = sender.output_sender().clone();
+                sender.oneshot_command(async move {
+                    match cmd.execute_undoable().await {
+                        // Only taking a label off is offered back
+                        Ok(undo) => MovieDetailsCommand::OfferUndo(
+                            (!present).then(|| UndoOffer::new("Label removed", undo)),
+                        ),
+                        Err(e) => {
+                            error!("Failed to update labels: {}", e);
+                            output.emit(MovieDetailsOutput::ShowToast(format!(
+                                "Couldn't update labels: {}",
+                                e
+                            )));
+                            MovieDetailsCommand::OfferUndo(None)
+                        }
+                    }
+                });
+            }
+            MovieDetailsInput::ConfirmDelete => {
+                let Some(movie) = &self.movie else {
+                    return;
+                };
+                let what = match movie.versions.len() {
+                    0 | 1 => format!("“{}” and its file", movie.title),
+                    count => format!("“{}” and its {} files", movie.title, count),
+                };
+                let input_sender = sender.input_sender().clone();
+                delete_confirmation::present(root, &what, move || {
+                    input_sender.emit(MovieDetailsInput::DeleteFromServer);
+                });
+            }
+            MovieDetailsInput::DeleteFromServer => {
+                let cmd = DeleteFromServerCommand {
+                    db: (*self.db).clone(),
+                    media_id: self.item_id.clone(),
+                    version_id: None,
+                };
+                let output = sender.output_sender().clone();
+                relm4::spawn(async move {
+                    if let Err(e) = Command::execute(&cmd).await {
+                        error!("Failed to delete from the server: {}", e);
+                        output.emit(MovieDetailsOutput::ShowToast(format!(
+                            "Couldn't delete from the server: {}",
+                            e
+                        )));
+                    }
+                });
+            }
             MovieDetailsInput::BrokerMsg(msg) => match msg {
                 BrokerMessage::Data(data_msg) => match data_msg {
                     crate::ui::shared::broker::DataMessage::PlaybackProgressUpdated {
@@ -488,8 +908,33 @@ impl AsyncComponent for MovieDetailsPage {
                             sender.oneshot_command(async { MovieDetailsCommand::LoadDetails });
                         }
                     }
+                    crate::ui::shared::broker::DataMessage::FavoriteChanged {
+                        media_id,
+                        favorite,
+                    } => {
+                        if self.item_id.to_string() == media_id {
+                            self.favorite = favorite;
+                        }
+                    }
+                    DataMessage::MediaDeleted { media_id } => {
+                        if self.item_id.to_string() == media_id {
+                            sender.output(MovieDetailsOutput::NavigateBack).unwrap();
+                        }
+                    }
+                    DataMessage::MediaUpdated { media_id } => {
+                        if self.item_id.to_string() == media_id {
+                            sender.oneshot_command(async { MovieDetailsCommand::LoadDetails });
+                        }
+                    }
                     _ => {}
                 },
+                BrokerMessage::Connectivity(change) => {
+                    self.offline = matches!(change, ConnectivityMessage::WentOffline);
+                    if let Some(movie) = &self.movie {
+                        self.label_chips
+                            .set_labels(&movie.tags, self.labels_editable());
+                    }
+                }
                 _ => {}
             },
         }
@@ -499,9 +944,14 @@ impl AsyncComponent for MovieDetailsPage {
         &mut self,
         msg: Self::CommandOutput,
         sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         match msg {
+            MovieDetailsCommand::OfferUndo(offer) => {
+                if let Some(offer) = offer {
+                    offer.present(root);
+                }
+            }
             MovieDetailsCommand::LoadDetails => {
                 let cmd = GetItemDetailsCommand {
                     db: (*self.db).clone(),
@@ -514,6 +964,23 @@ impl AsyncComponent for MovieDetailsPage {
                             self.movie = Some(movie.clone());
                             self.loading = false;
 
+                            match DownloadService::find_download(&self.db, &self.item_id).await {
+                                Ok(download) => {
+                                    self.download_pinned =
+                                        download.as_ref().is_some_and(|d| d.pinned);
+                                    self.keep_download_check.set_sensitive(download.is_some());
+                                    self.delete_download_button
+                                        .set_sensitive(download.is_some());
+                                    self.keep_download_check.set_active(self.download_pinned);
+                                }
+                                Err(e) => error!("Failed to load download state: {}", e),
+                            }
+
+                            match FavoritesService::is_favorite(&self.db, &self.item_id).await {
+                                Ok(favorite) => self.favorite = favorite,
+                                Err(e) => error!("Failed to load favorite state: {}", e),
+                            }
+
                             // Check if we need to load full cast/crew (if cast count <= 3, likely only preview)
                             // Only attempt once to avoid infinite loop if movie really has ≤3 cast members
                             if movie.cast.len() <= 3 && !self.full_metadata_loaded {
@@ -527,9 +994,14 @@ impl AsyncComponent for MovieDetailsPage {
                             }
 
                             // Load poster and backdrop images
+                            // The backdrop gives the page its color, or the poster without one
                             if let Some(poster_url) = movie.poster_url.clone() {
+                                let find_color = movie.backdrop_url.is_none();
                                 sender.oneshot_command(async move {
-                                    MovieDetailsCommand::LoadPosterImage { url: poster_url }
+                                    MovieDetailsCommand::LoadPosterImage {
+                                        url: poster_url,
+                                        find_color,
+                                    }
                                 });
                             }
 
@@ -559,6 +1031,45 @@ impl AsyncComponent for MovieDetailsPage {
                                 self.genre_box.append(&pill);
                             }
 
+                            // Update studio links
+                            while let Some(child) = self.studio_box.first_child() {
+                                self.studio_box.remove(&child);
+                            }
+
+                            for studio in &movie.studios {
+                                let button = gtk::Button::builder()
+                                    .label(format!("More from {}", studio))
+                                    .css_classes(["pill"])
+                                    .build();
+                                let input_sender = sender.input_sender().clone();
+                                let studio = studio.clone();
+                                button.connect_clicked(move |_| {
+                                    input_sender.emit(MovieDetailsInput::BrowseLibrary(
+                                        FilterState::for_studio(&studio),
+                                    ));
+                                });
+                                self.studio_box.append(&button);
+                            }
+
+                            for collection in &movie.collections {
+                                let button = gtk::Button::builder()
+                                    .label(format!("{} Collection", collection))
+                                    .css_classes(["pill"])
+                                    .build();
+                                let input_sender = sender.input_sender().clone();
+                                let collection = collection.clone();
+                                button.connect_clicked(move |_| {
+                                    input_sender.emit(MovieDetailsInput::BrowseLibrary(
+                                        FilterState::for_collection(&collection),
+                                    ));
+                                });
+                                self.studio_box.append(&button);
+                            }
+
+                            // Update tag chips
+                            self.label_chips
+                                .set_labels(&movie.tags, self.labels_editable());
+
                             // Load person images for cast and crew
                             for person in movie.cast.iter().take(10) {
                                 if let Some(image_url) = &person.image_url {
@@ -625,14 +1136,22 @@ impl AsyncComponent for MovieDetailsPage {
                     }
                 }
             }
-            MovieDetailsCommand::LoadPosterImage { url } => {
+            MovieDetailsCommand::LoadPosterImage { url, find_color } => {
                 // Spawn async task to download and create texture
                 let sender_clone = sender.clone();
+                let item_id = self.item_id.to_string();
                 relm4::spawn(async move {
-                    match load_image_from_url(&url, 300, 450).await {
-                        Ok(texture) => {
+                    let result = if find_color {
+                        load_image_with_color(&url, 300, 450, &item_id).await
+                    } else {
+                        load_image_from_url(&url, 300, 450)
+                            .await
+                            .map(|texture| (texture, None))
+                    };
+                    match result {
+                        Ok((texture, color)) => {
                             sender_clone.oneshot_command(async move {
-                                MovieDetailsCommand::PosterImageLoaded { texture }
+                                MovieDetailsCommand::PosterImageLoaded { texture, color }
                             });
                         }
                         Err(e) => {
@@ -644,11 +1163,12 @@ impl AsyncComponent for MovieDetailsPage {
             MovieDetailsCommand::LoadBackdropImage { url } => {
                 // Spawn async task to download and create texture
                 let sender_clone = sender.clone();
+                let item_id = self.item_id.to_string();
                 relm4::spawn(async move {
-                    match load_image_from_url(&url, -1, 550).await {
-                        Ok(texture) => {
+                    match load_image_with_color(&url, -1, 550, &item_id).await {
+                        Ok((texture, color)) => {
                             sender_clone.oneshot_command(async move {
-                                MovieDetailsCommand::BackdropImageLoaded { texture }
+                                MovieDetailsCommand::BackdropImageLoaded { texture, color }
                             });
                         }
                         Err(e) => {
@@ -673,11 +1193,32 @@ impl AsyncComponent for MovieDetailsPage {
                     }
                 });
             }
-            MovieDetailsCommand::PosterImageLoaded { texture } => {
+            MovieDetailsCommand::PosterImageLoaded { texture, color } => {
                 self.poster_texture = Some(texture);
+                if color.is_some() {
+                    tint::apply(&self.hero_tint, color);
+                }
             }
-            MovieDetailsCommand::BackdropImageLoaded { texture } => {
+            MovieDetailsCommand::BackdropImageLoaded { texture, color } => {
                 self.backdrop_texture = Some(texture);
+                if color.is_some() {
+                    tint::apply(&self.hero_tint, color);
+                }
+            }
+            MovieDetailsCommand::DeletePermissionLoaded(can_delete) => {
+                self.can_delete = can_delete;
+            }
+            MovieDetailsCommand::LabelsEditableLoaded(editable) => {
+                self.can_edit_labels = editable;
+                if let Some(movie) = &self.movie {
+                    self.label_chips
+                        .set_labels(&movie.tags, self.labels_editable());
+                }
+            }
+            MovieDetailsCommand::ThemeMusicLoaded { url } => {
+                if let Some(url) = url {
+                    self.theme_music = Some(theme_music::play_while_shown(&url, root));
+                }
             }
             MovieDetailsCommand::PersonImageLoaded { person_id, texture } => {
                 // Store the loaded texture
@@ -851,11 +1392,29 @@ impl AsyncComponent for MovieDetailsPage {
             }
         }
     }
+}
+
+impl MovieDetailsPage {
+    /// Labels are edited on the server, so not while it can't be reached
+    fn labels_editable(&self) -> bool {
+        self.can_edit_labels && !self.offline
+    }
+}
 
-    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
-        // Unsubscribe from MessageBroker
-        relm4::spawn(async move {
-            BROKER.unsubscribe("MovieDetailsPage").await;
-        });
+/// Video and audio quality of a movie, like "4K · Dolby Vision · Atmos"
+fn quality_summary(movie: &Movie) -> String {
+    let mut parts = Vec::new();
+    if movie
+        .video_height
+        .is_some_and(|height| height >= UHD_MIN_HEIGHT)
+    {
+        parts.push("4K");
+    }
+    if let Some(hdr_format) = &movie.hdr_format {
+        parts.push(hdr_format);
+    }
+    if movie.atmos {
+        parts.push("Atmos");
     }
+    parts.join(" · ")
 }
@@ -1,12 +1,15 @@
 use crate::config::Config;
+use crate::models::PlaybackError;
 use crate::player::{PlayerController, PlayerHandle, PlayerState};
 use crate::services::config_service::CONFIG_SERVICE;
+use crate::services::core::power_saver;
 use gtk::glib;
 use gtk::prelude::*;
 use relm4::gtk;
 use relm4::prelude::*;
 use tracing::{error, info, warn};
 
+use super::fullscreen::FullscreenMonitor;
 use super::{PlayerInput, PlayerOutput, PlayerPage};
 
 /// Backend management and lifecycle methods
@@ -44,10 +47,7 @@ impl PlayerPage {
             glib::spawn_future_local(async move {
                 while let Some(error_msg) = error_receiver.recv().await {
                     error!("Player error received: {}", error_msg);
-                    sender_clone
-                        .output(PlayerOutput::ShowToast(error_msg.clone()))
-                        .unwrap();
-                    sender_clone.input(PlayerInput::ShowError(error_msg));
+                    sender_clone.input(PlayerInput::ShowError(PlaybackError::Other(error_msg)));
                 }
             });
         }
@@ -81,6 +81,39 @@ impl PlayerPage {
             }
         });
 
+        let subtitle_scale = self.subtitle_scale;
+        let handle_for_subtitles = handle.clone();
+        glib::spawn_future_local(async move {
+            if let Err(err) = handle_for_subtitles
+                .set_subtitle_scale(subtitle_scale)
+                .await
+            {
+                warn!("Failed to apply subtitle scale: {}", err);
+            }
+        });
+
+        let subtitle_charset = self.subtitle_config.charset.clone();
+        let handle_for_charset = handle.clone();
+        glib::spawn_future_local(async move {
+            if let Err(err) = handle_for_charset
+                .set_subtitle_charset(&subtitle_charset)
+                .await
+            {
+                warn!("Failed to apply subtitle encoding: {}", err);
+            }
+        });
+
+        let pitch_correction = self.speed_config.pitch_correction;
+        let handle_for_pitch = handle.clone();
+        glib::spawn_future_local(async move {
+            if let Err(err) = handle_for_pitch
+                .set_pitch_correction(pitch_correction)
+                .await
+            {
+                warn!("Failed to apply pitch correction: {}", err);
+            }
+        });
+
         if self.is_mpv_backend {
             let saved_mode = self.current_upscaling_mode;
             let handle_for_upscaling = handle.clone();
@@ -92,6 +125,20 @@ impl PlayerPage {
         }
 
         self.player = Some(handle);
+        self.apply_power_saving();
+    }
+
+    /// Switch the player to lighter settings while power saving is active
+    pub(super) fn apply_power_saving(&self) {
+        if let Some(ref player) = self.player {
+            let player_handle = player.clone();
+            let enabled = power_saver::is_active();
+            glib::spawn_future_local(async move {
+                if let Err(err) = player_handle.set_power_saving(enabled).await {
+                    warn!("Failed to apply power saving settings: {}", err);
+                }
+            });
+        }
     }
 
     pub(super) async fn rebuild_player_backend(
@@ -122,8 +169,8 @@ impl PlayerPage {
             });
         }
 
-        let active_media = self.media_item_id.clone();
-        let active_context = self.playlist_context.clone();
+        let active_media = self.session.media_id().cloned();
+        let active_context = self.session.context().cloned();
 
         match PlayerController::new(config) {
             Ok((handle, controller)) => {
@@ -153,7 +200,10 @@ impl PlayerPage {
             Err(e) => {
                 error!("Failed to rebuild player backend: {}", e);
                 self.error_retry_manager
-                    .show_error(format!("Failed to initialize player: {}", e));
+                    .show_error(PlaybackError::Other(format!(
+                        "Failed to initialize player: {}",
+                        e
+                    )));
                 self.player_state = PlayerState::Error;
             }
         }
@@ -164,12 +214,62 @@ impl PlayerPage {
         config: &Config,
         sender: &AsyncComponentSender<Self>,
     ) {
-        // Update progress tracker config
-        self.progress_tracker.update_config(
-            config.playback.auto_resume,
-            config.playback.resume_threshold_seconds as u64,
-            config.playback.progress_update_interval_seconds as u64,
-        );
+        // Update resume and progress write-back config
+        self.session.update_config(&config.playback);
+        self.session
+            .set_auto_play_limit(config.kids.episode_limit());
+
+        // Subtitle size may have been changed from another player window
+        if config.playback.subtitle_scale != self.subtitle_scale {
+            self.subtitle_scale = config.playback.subtitle_scale;
+            if let Some(ref player) = self.player {
+                let player_handle = player.clone();
+                let scale = self.subtitle_scale;
+                glib::spawn_future_local(async move {
+                    if let Err(err) = player_handle.set_subtitle_scale(scale).await {
+                        warn!("Failed to update subtitle scale: {}", err);
+                    }
+                });
+            }
+        }
+
+        self.resume_after_unlock = config.playback.resume_after_unlock;
+        self.fullscreen_monitor =
+            FullscreenMonitor::from_config(&config.playback.fullscreen_monitor);
+        self.last_fullscreen_monitor = config.playback.last_fullscreen_monitor.clone();
+        self.gestures.set(config.playback.gestures);
+
+        // Pitch correction may have been changed from another player window
+        if config.playback.speed.pitch_correction != self.speed_config.pitch_correction
+            && let Some(ref player) = self.player
+        {
+            let player_handle = player.clone();
+            let enabled = config.playback.speed.pitch_correction;
+            glib::spawn_future_local(async move {
+                if let Err(err) = player_handle.set_pitch_correction(enabled).await {
+                    warn!("Failed to update pitch correction: {}", err);
+                }
+            });
+        }
+        self.speed_config = config.playback.speed;
+        self.speed_control
+            .set_pitch_correction(self.speed_config.pitch_correction);
+
+        if config.playback.subtitles.charset != self.subtitle_config.charset
+            && let Some(ref player) = self.player
+        {
+            let player_handle = player.clone();
+            let charset = config.playback.subtitles.charset.clone();
+            glib::spawn_future_local(async move {
+                if let Err(err) = player_handle.set_subtitle_charset(&charset).await {
+                    warn!("Failed to update subtitle encoding: {}", err);
+                }
+            });
+        }
+        self.subtitle_config = config.playback.subtitles.clone();
+
+        // Power saving playback may have been turned off in preferences
+        self.apply_power_saving();
 
         // Update skip marker manager config
         self.skip_marker_manager.update_config(
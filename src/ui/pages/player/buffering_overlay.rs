@@ -20,6 +20,9 @@ pub enum BufferingOverlayInput {
     UpdateCacheStats(CurrentCacheStats),
     /// Update estimated bitrate for warning detection
     UpdateEstimatedBitrate(u64),
+    /// Show what loading the stream waits on, like "Connecting to server…",
+    /// or None once the stream is known
+    SetStatus(Option<String>),
     /// Show the overlay
     Show,
     /// Hide the overlay
@@ -40,6 +43,8 @@ pub struct BufferingOverlay {
     estimated_bitrate_bps: Option<u64>,
     /// Active performance warnings
     warnings: Vec<PerformanceWarning>,
+    /// What loading the stream waits on, shown until the stream is known
+    status: Option<String>,
 }
 
 #[allow(unused_assignments)]
@@ -56,7 +61,7 @@ impl SimpleComponent for BufferingOverlay {
             set_valign: gtk::Align::Center,
             set_spacing: 16,
             #[watch]
-            set_visible: model.visible && (model.is_buffering || model.cache_stats.active_downloads > 0),
+            set_visible: model.status.is_some() || (model.visible && (model.is_buffering || model.cache_stats.active_downloads > 0)),
             add_css_class: "osd",
             add_css_class: "buffering-overlay",
 
@@ -77,7 +82,9 @@ impl SimpleComponent for BufferingOverlay {
                 // Buffering percentage
                 gtk::Label {
                     #[watch]
-                    set_label: &if model.is_buffering {
+                    set_label: &if let Some(status) = &model.status {
+                        status.clone()
+                    } else if model.is_buffering {
                         format!("{}%", model.buffering_percentage)
                     } else {
                         "Loading...".to_string()
@@ -197,6 +204,7 @@ impl SimpleComponent for BufferingOverlay {
             cache_stats: CurrentCacheStats::empty(),
             estimated_bitrate_bps: None,
             warnings: Vec::new(),
+            status: None,
         };
 
         let widgets = view_output!();
@@ -227,6 +235,9 @@ impl SimpleComponent for BufferingOverlay {
                 // Update warnings when bitrate changes
                 self.update_warnings();
             }
+            BufferingOverlayInput::SetStatus(status) => {
+                self.status = status;
+            }
             BufferingOverlayInput::Show => {
                 self.visible = true;
             }
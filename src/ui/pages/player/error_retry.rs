@@ -1,4 +1,6 @@
-use crate::models::{MediaItemId, PlaylistContext};
+use crate::models::{MediaItemId, PlaybackError, PlaylistContext};
+use crate::services::core::playback_decision::{PlaybackMethod, StreamChoice};
+use crate::ui::shared::commands::StartedStream;
 use relm4::AsyncComponentSender;
 use relm4::gtk::glib::{self, SourceId};
 use std::time::Duration;
@@ -6,10 +8,79 @@ use tracing::info;
 
 use super::PlayerInput;
 
+/// The error of a player failing to play a stream. Codec errors get the
+/// codecs of the stream, and a transcode failing for no known reason is put
+/// down to the server refusing it.
+pub fn stream_error(error: &anyhow::Error, stream: &StartedStream) -> PlaybackError {
+    match PlaybackError::from_error(error) {
+        PlaybackError::UnsupportedCodec { .. } => PlaybackError::UnsupportedCodec {
+            codecs: stream.codecs.clone(),
+        },
+        PlaybackError::Other(_) if stream.decision.method == PlaybackMethod::Transcode => {
+            PlaybackError::TranscodeRefused
+        }
+        error => error,
+    }
+}
+
+/// What the error overlay offers to get playback going again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    SignIn,
+    PlayTranscoded,
+    PlayOriginal,
+    Retry,
+}
+
+impl Recovery {
+    /// The recovery for an error of a stream played with `stream`
+    pub fn for_error(error: &PlaybackError, stream: StreamChoice) -> Option<Self> {
+        match error {
+            PlaybackError::AuthExpired => Some(Recovery::SignIn),
+            PlaybackError::UnsupportedCodec { .. } if stream == StreamChoice::Transcoded => None,
+            PlaybackError::UnsupportedCodec { .. } => Some(Recovery::PlayTranscoded),
+            PlaybackError::TranscodeRefused => Some(Recovery::PlayOriginal),
+            PlaybackError::NetworkTimeout | PlaybackError::Other(_) => Some(Recovery::Retry),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Recovery::SignIn => "Sign In Again",
+            Recovery::PlayTranscoded => "Play Converted",
+            Recovery::PlayOriginal => "Play Original",
+            Recovery::Retry => "Retry",
+        }
+    }
+}
+
+/// What the user can do about an error, shown under it
+pub fn error_hint(error: &PlaybackError) -> String {
+    match error {
+        PlaybackError::AuthExpired => {
+            "Sign in to the server again to keep watching.".to_string()
+        }
+        PlaybackError::TranscodeRefused => {
+            "The server may not allow converting videos, or be too busy to. The original file may still play.".to_string()
+        }
+        PlaybackError::NetworkTimeout => {
+            "Check your connection to the server and try again.".to_string()
+        }
+        PlaybackError::UnsupportedCodec { codecs: Some(codecs) } => format!(
+            "No decoder is installed for its format ({}). The server can convert it to one this device plays.",
+            codecs
+        ),
+        PlaybackError::UnsupportedCodec { codecs: None } => {
+            "No decoder is installed for its format. The server can convert it to one this device plays.".to_string()
+        }
+        PlaybackError::Other(_) => String::new(),
+    }
+}
+
 /// Manages error state and retry logic with exponential backoff for playback failures.
 pub struct ErrorRetryManager {
-    /// Current error message, if any
-    error_message: Option<String>,
+    /// Current error, if any
+    error: Option<PlaybackError>,
     /// Number of retry attempts for the current error
     retry_count: u32,
     /// Maximum number of retries before giving up
@@ -21,31 +92,31 @@ pub struct ErrorRetryManager {
 impl ErrorRetryManager {
     pub fn new(max_retries: u32) -> Self {
         Self {
-            error_message: None,
+            error: None,
             retry_count: 0,
             max_retries,
             retry_timer: None,
         }
     }
 
-    /// Get the current error message
-    pub fn get_error_message(&self) -> Option<&str> {
-        self.error_message.as_deref()
+    /// Get the current error
+    pub fn error(&self) -> Option<&PlaybackError> {
+        self.error.as_ref()
     }
 
     /// Check if there's an active error
     pub fn has_error(&self) -> bool {
-        self.error_message.is_some()
+        self.error.is_some()
     }
 
-    /// Show an error message
-    pub fn show_error(&mut self, message: String) {
-        self.error_message = Some(message);
+    /// Show an error
+    pub fn show_error(&mut self, error: PlaybackError) {
+        self.error = Some(error);
     }
 
-    /// Clear the error message and reset retry state
+    /// Clear the error and reset retry state
     pub fn clear_error(&mut self) {
-        self.error_message = None;
+        self.error = None;
         self.retry_count = 0;
         if let Some(timer) = self.retry_timer.take() {
             let _ = timer.remove();
@@ -61,13 +132,13 @@ impl ErrorRetryManager {
         sender: &AsyncComponentSender<super::PlayerPage>,
     ) -> bool {
         // Clear any previous error
-        self.error_message = None;
+        self.error = None;
 
         // Check if we've exceeded max retries
         if self.retry_count >= self.max_retries {
-            self.error_message = Some(
+            self.error = Some(PlaybackError::Other(
                 "Failed to load media after multiple attempts. Please try again later.".to_string(),
-            );
+            ));
             self.retry_count = 0;
             return false;
         }
@@ -116,3 +187,57 @@ impl Drop for ErrorRetryManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::core::playback_decision::{DecisionReason, PlaybackDecision};
+
+    fn transcode() -> StartedStream {
+        StartedStream {
+            url: "https://server/transcode.m3u8".to_string(),
+            decision: PlaybackDecision {
+                method: PlaybackMethod::Transcode,
+                reason: DecisionReason::Requested,
+                transcode: None,
+            },
+            codecs: Some("hevc, truehd".to_string()),
+            duration: None,
+            server_url: None,
+        }
+    }
+
+    #[test]
+    fn test_known_errors_of_a_transcode_are_kept() {
+        let error = anyhow::Error::new(PlaybackError::AuthExpired);
+        assert_eq!(
+            stream_error(&error, &transcode()),
+            PlaybackError::AuthExpired
+        );
+        let error = anyhow::Error::new(PlaybackError::NetworkTimeout);
+        assert_eq!(
+            stream_error(&error, &transcode()),
+            PlaybackError::NetworkTimeout
+        );
+    }
+
+    #[test]
+    fn test_unknown_errors_of_a_transcode_are_refusals() {
+        let error = anyhow::anyhow!("Failed to play media");
+        assert_eq!(
+            stream_error(&error, &transcode()),
+            PlaybackError::TranscodeRefused
+        );
+    }
+
+    #[test]
+    fn test_codec_errors_get_the_codecs_of_the_stream() {
+        let error = anyhow::Error::new(PlaybackError::UnsupportedCodec { codecs: None });
+        assert_eq!(
+            stream_error(&error, &transcode()),
+            PlaybackError::UnsupportedCodec {
+                codecs: Some("hevc, truehd".to_string())
+            }
+        );
+    }
+}
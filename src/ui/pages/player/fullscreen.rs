@@ -0,0 +1,128 @@
+use gtk4::gdk;
+use gtk4::prelude::*;
+use libadwaita as adw;
+
+/// Which monitor fullscreen playback uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FullscreenMonitor {
+    /// The monitor the window is on
+    Window,
+    /// The monitor under the pointer
+    Cursor,
+    /// The monitor fullscreen playback used last
+    Last,
+    /// A specific monitor, by connector name such as "HDMI-A-1"
+    Connector(String),
+}
+
+impl FullscreenMonitor {
+    pub fn from_config(value: &str) -> Self {
+        match value {
+            "" | "window" => Self::Window,
+            "cursor" => Self::Cursor,
+            "last" => Self::Last,
+            connector => Self::Connector(connector.to_string()),
+        }
+    }
+
+    pub fn to_config(&self) -> String {
+        match self {
+            Self::Window => "window".to_string(),
+            Self::Cursor => "cursor".to_string(),
+            Self::Last => "last".to_string(),
+            Self::Connector(connector) => connector.clone(),
+        }
+    }
+}
+
+/// Monitors currently connected to the window's display
+pub fn connected_monitors(window: &adw::ApplicationWindow) -> Vec<gdk::Monitor> {
+    let monitors = window.display().monitors();
+    (0..monitors.n_items())
+        .filter_map(|i| monitors.item(i).and_downcast::<gdk::Monitor>())
+        .collect()
+}
+
+/// Name of a monitor for menus, e.g. "Dell U2720Q (DP-1)"
+pub fn monitor_label(monitor: &gdk::Monitor) -> String {
+    match (monitor.description(), connector_name(monitor)) {
+        (Some(description), Some(connector)) => format!("{} ({})", description, connector),
+        (Some(description), None) => description.to_string(),
+        (None, Some(connector)) => connector,
+        (None, None) => "Unknown Monitor".to_string(),
+    }
+}
+
+fn monitor_by_connector(window: &adw::ApplicationWindow, connector: &str) -> Option<gdk::Monitor> {
+    connected_monitors(window)
+        .into_iter()
+        .find(|monitor| connector_name(monitor).as_deref() == Some(connector))
+}
+
+/// Monitor the window is on right now
+pub fn window_monitor(window: &adw::ApplicationWindow) -> Option<gdk::Monitor> {
+    window
+        .surface()
+        .and_then(|surface| window.display().monitor_at_surface(&surface))
+}
+
+/// Monitor under the pointer. GTK only knows where the pointer is while it is
+/// over one of the app's own windows, so elsewhere there is no answer.
+fn pointer_monitor(window: &adw::ApplicationWindow) -> Option<gdk::Monitor> {
+    let display = window.display();
+    let pointer = display.default_seat()?.pointer()?;
+    let (surface, _, _) = pointer.surface_at_position();
+    display.monitor_at_surface(&surface?)
+}
+
+/// Monitor to go fullscreen on, None to let the compositor decide
+pub fn target_monitor(
+    window: &adw::ApplicationWindow,
+    choice: &FullscreenMonitor,
+    last_connector: &str,
+) -> Option<gdk::Monitor> {
+    match choice {
+        FullscreenMonitor::Window => None,
+        FullscreenMonitor::Cursor => pointer_monitor(window),
+        FullscreenMonitor::Last => monitor_by_connector(window, last_connector),
+        FullscreenMonitor::Connector(connector) => monitor_by_connector(window, connector),
+    }
+}
+
+/// Call `on_change` whenever a monitor is connected or disconnected
+pub fn connect_monitors_changed(window: &adw::ApplicationWindow, on_change: impl Fn() + 'static) {
+    window
+        .display()
+        .monitors()
+        .connect_items_changed(move |_, _, _, _| on_change());
+}
+
+/// Connector of a monitor as stored in the configuration
+pub fn connector_name(monitor: &gdk::Monitor) -> Option<String> {
+    monitor.connector().map(|c| c.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fullscreen_monitor_config_round_trip() {
+        for choice in [
+            FullscreenMonitor::Window,
+            FullscreenMonitor::Cursor,
+            FullscreenMonitor::Last,
+            FullscreenMonitor::Connector("HDMI-A-1".to_string()),
+        ] {
+            assert_eq!(FullscreenMonitor::from_config(&choice.to_config()), choice);
+        }
+    }
+
+    #[test]
+    fn test_fullscreen_monitor_defaults_to_window() {
+        assert_eq!(
+            FullscreenMonitor::from_config(""),
+            FullscreenMonitor::Window
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use gtk::gdk;
+use gtk::glib;
+use gtk::prelude::*;
+use libadwaita as adw;
+use relm4::AsyncComponentSender;
+use relm4::gtk;
+use std::cell::Cell;
+use std::rc::Rc;
+
+use super::{PlayerInput, PlayerPage};
+use crate::config::GestureConfig;
+
+/// Pixels of smooth (touchpad) scrolling that count as one wheel step
+const PIXELS_PER_STEP: f64 = 20.0;
+/// Seconds skipped by one horizontal scroll step
+const SCROLL_SEEK_SECS: i64 = 5;
+/// Pixels a press has to move before it becomes a drag
+const DRAG_THRESHOLD: f64 = 8.0;
+/// Seconds a drag across the whole video width seeks
+const DRAG_SEEK_FULL_WIDTH_SECS: f64 = 120.0;
+/// Speed in pixels per second a swipe needs to count
+const SWIPE_MIN_VELOCITY: f64 = 500.0;
+/// Seconds skipped by a sideways swipe
+const SWIPE_SEEK_SECS: i64 = 10;
+
+/// What a press on the video turned into once it moved past the threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DragMode {
+    Pending,
+    Moving,
+    Seeking,
+}
+
+/// Add the scroll, click, drag and swipe gestures of the video area. Each
+/// gesture checks `config` when it fires, so preference changes apply at once.
+pub fn attach(
+    video_area: &gtk::Box,
+    window: &adw::ApplicationWindow,
+    sender: &AsyncComponentSender<PlayerPage>,
+    config: Rc<Cell<GestureConfig>>,
+) {
+    attach_scroll(video_area, sender, config.clone());
+    attach_double_click(video_area, sender, config.clone());
+    attach_drag(video_area, window, sender, config.clone());
+    attach_swipe(video_area, sender, config);
+}
+
+/// Wheel up and down changes the volume, sideways seeks. Touchpad scrolling
+/// reports pixels rather than steps, so it is gathered into whole steps.
+fn attach_scroll(
+    video_area: &gtk::Box,
+    sender: &AsyncComponentSender<PlayerPage>,
+    config: Rc<Cell<GestureConfig>>,
+) {
+    let scroll = gtk::EventControllerScroll::new(gtk::EventControllerScrollFlags::BOTH_AXES);
+    let pending = Rc::new(Cell::new((0.0, 0.0)));
+
+    {
+        let pending = pending.clone();
+        scroll.connect_scroll_begin(move |_| pending.set((0.0, 0.0)));
+    }
+
+    let sender = sender.clone();
+    scroll.connect_scroll(move |controller, dx, dy| {
+        let gestures = config.get();
+        if !gestures.scroll_volume && !gestures.scroll_seek {
+            return glib::Propagation::Proceed;
+        }
+
+        let step = match controller.unit() {
+            gdk::ScrollUnit::Surface => PIXELS_PER_STEP,
+            _ => 1.0,
+        };
+        let (pending_x, pending_y) = pending.get();
+        let (seek_steps, pending_x) = scroll_steps(pending_x, dx, step);
+        let (volume_steps, pending_y) = scroll_steps(pending_y, dy, step);
+        pending.set((pending_x, pending_y));
+
+        if gestures.scroll_seek && seek_steps != 0 {
+            sender.input(PlayerInput::SeekRelative(
+                seek_steps as i64 * SCROLL_SEEK_SECS,
+            ));
+        }
+        if gestures.scroll_volume {
+            // Scrolling up moves towards negative y
+            for _ in 0..volume_steps.unsigned_abs() {
+                sender.input(if volume_steps < 0 {
+                    PlayerInput::VolumeUp
+                } else {
+                    PlayerInput::VolumeDown
+                });
+            }
+        }
+        glib::Propagation::Stop
+    });
+
+    video_area.add_controller(scroll);
+}
+
+/// Whole steps in the scrolled distance, and the remainder to carry over
+fn scroll_steps(pending: f64, delta: f64, step: f64) -> (i32, f64) {
+    let total = pending + delta;
+    let steps = (total / step).trunc();
+    (steps as i32, total - steps * step)
+}
+
+fn attach_double_click(
+    video_area: &gtk::Box,
+    sender: &AsyncComponentSender<PlayerPage>,
+    config: Rc<Cell<GestureConfig>>,
+) {
+    let click = gtk::GestureClick::new();
+    click.set_button(gdk::BUTTON_PRIMARY);
+
+    let sender = sender.clone();
+    click.connect_pressed(move |_gesture, n_press, _x, _y| {
+        if n_press == 2 && config.get().double_click_fullscreen {
+            sender.input(PlayerInput::ToggleFullscreen);
+        }
+    });
+
+    video_area.add_controller(click);
+}
+
+/// Dragging the video moves the window, or seeks when dragged sideways with
+/// drag seeking on. Nothing happens until the pointer passes a small
+/// threshold, so clicks and double-clicks stay clicks.
+fn attach_drag(
+    video_area: &gtk::Box,
+    window: &adw::ApplicationWindow,
+    sender: &AsyncComponentSender<PlayerPage>,
+    config: Rc<Cell<GestureConfig>>,
+) {
+    let drag = gtk::GestureDrag::new();
+    drag.set_button(gdk::BUTTON_PRIMARY);
+    let mode = Rc::new(Cell::new(DragMode::Pending));
+
+    {
+        let mode = mode.clone();
+        drag.connect_drag_begin(move |_, _, _| mode.set(DragMode::Pending));
+    }
+
+    {
+        let mode = mode.clone();
+        let window = window.clone();
+        let config = config.clone();
+        drag.connect_drag_update(move |gesture, offset_x, offset_y| {
+            if mode.get() != DragMode::Pending || offset_x.hypot(offset_y) < DRAG_THRESHOLD {
+                return;
+            }
+            let Some(event) = gesture.current_event() else {
+                return;
+            };
+            // Touch drags are left to the swipe gesture
+            let touch = event
+                .device()
+                .is_some_and(|device| device.source() == gdk::InputSource::Touchscreen);
+            if touch && config.get().swipe {
+                return;
+            }
+
+            if config.get().drag_seek && offset_x.abs() > offset_y.abs() {
+                mode.set(DragMode::Seeking);
+                return;
+            }
+
+            mode.set(DragMode::Moving);
+            if window.is_fullscreen() {
+                return;
+            }
+            let Some((start_x, start_y)) = gesture.start_point() else {
+                return;
+            };
+            use gdk::prelude::ToplevelExt;
+            if let (Some(device), Some(toplevel)) = (
+                event.device(),
+                window
+                    .surface()
+                    .and_then(|surface| surface.downcast::<gdk::Toplevel>().ok()),
+            ) {
+                toplevel.begin_move(
+                    &device,
+                    gesture.current_button() as i32,
+                    start_x,
+                    start_y,
+                    event.time(),
+                );
+            }
+        });
+    }
+
+    let sender = sender.clone();
+    let video = video_area.clone();
+    drag.connect_drag_end(move |_gesture, offset_x, _offset_y| {
+        if mode.replace(DragMode::Pending) != DragMode::Seeking {
+            return;
+        }
+        let width = video.width().max(1) as f64;
+        let seconds = (offset_x / width * DRAG_SEEK_FULL_WIDTH_SECS).round() as i64;
+        if seconds != 0 {
+            sender.input(PlayerInput::SeekRelative(seconds));
+        }
+    });
+
+    video_area.add_controller(drag);
+}
+
+/// Quick swipes on a touchscreen: sideways seeks, up and down changes the
+/// volume. Two-finger touchpad swipes arrive as scrolling instead.
+fn attach_swipe(
+    video_area: &gtk::Box,
+    sender: &AsyncComponentSender<PlayerPage>,
+    config: Rc<Cell<GestureConfig>>,
+) {
+    let swipe = gtk::GestureSwipe::new();
+    swipe.set_touch_only(true);
+
+    let sender = sender.clone();
+    swipe.connect_swipe(move |_gesture, velocity_x, velocity_y| {
+        if !config.get().swipe {
+            return;
+        }
+        let input = if velocity_x.abs() > velocity_y.abs() {
+            if velocity_x.abs() < SWIPE_MIN_VELOCITY {
+                return;
+            }
+            PlayerInput::SeekRelative(SWIPE_SEEK_SECS * velocity_x.signum() as i64)
+        } else {
+            if velocity_y.abs() < SWIPE_MIN_VELOCITY {
+                return;
+            }
+            if velocity_y < 0.0 {
+                PlayerInput::VolumeUp
+            } else {
+                PlayerInput::VolumeDown
+            }
+        };
+        sender.input(input);
+    });
+
+    video_area.add_controller(swipe);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_steps_carry_remainder() {
+        // Wheel steps pass straight through
+        assert_eq!(scroll_steps(0.0, -1.0, 1.0), (-1, 0.0));
+
+        // Smooth scrolling gathers pixels until a whole step
+        let (steps, pending) = scroll_steps(0.0, 15.0, PIXELS_PER_STEP);
+        assert_eq!(steps, 0);
+        let (steps, pending) = scroll_steps(pending, 30.0, PIXELS_PER_STEP);
+        assert_eq!(steps, 2);
+        assert_eq!(pending, 5.0);
+        assert_eq!(scroll_steps(0.0, -45.0, PIXELS_PER_STEP), (-2, -5.0));
+    }
+}
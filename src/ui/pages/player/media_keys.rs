@@ -0,0 +1,265 @@
+use gtk4::prelude::*;
+use gtk4::{gio, glib};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+use tracing::debug;
+
+const PORTAL: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const GLOBAL_SHORTCUTS: &str = "org.freedesktop.portal.GlobalShortcuts";
+const REQUEST: &str = "org.freedesktop.portal.Request";
+
+/// A focused window can get the same press both as an accelerator and from
+/// the portal; a repeat within this window is the same press
+pub const REPEAT_WINDOW: Duration = Duration::from_millis(250);
+
+/// Multimedia keys that control playback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKey {
+    /// The play key, which toggles between playing and paused
+    PlayPause,
+    Pause,
+    Next,
+    Previous,
+}
+
+impl MediaKey {
+    const ALL: [MediaKey; 4] = [
+        MediaKey::PlayPause,
+        MediaKey::Pause,
+        MediaKey::Next,
+        MediaKey::Previous,
+    ];
+
+    /// Shortcut id for the portal, also the window action name
+    fn id(self) -> &'static str {
+        match self {
+            MediaKey::PlayPause => "media-play-pause",
+            MediaKey::Pause => "media-pause",
+            MediaKey::Next => "media-next",
+            MediaKey::Previous => "media-previous",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|key| key.id() == id)
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            MediaKey::PlayPause => "Play or pause",
+            MediaKey::Pause => "Pause",
+            MediaKey::Next => "Next item",
+            MediaKey::Previous => "Previous item",
+        }
+    }
+
+    /// The key's X keysym name, understood by both GTK and the portal
+    fn keysym(self) -> &'static str {
+        match self {
+            MediaKey::PlayPause => "XF86AudioPlay",
+            MediaKey::Pause => "XF86AudioPause",
+            MediaKey::Next => "XF86AudioNext",
+            MediaKey::Previous => "XF86AudioPrev",
+        }
+    }
+}
+
+/// Deliver media key presses to `on_key` while the window has focus
+pub fn add_window_actions(
+    window: &adw::ApplicationWindow,
+    on_key: impl Fn(MediaKey) + Clone + 'static,
+) {
+    for key in MediaKey::ALL {
+        let action = gio::SimpleAction::new(key.id(), None);
+        let on_key = on_key.clone();
+        action.connect_activate(move |_, _| on_key(key));
+        window.add_action(&action);
+        if let Some(app) = window.application() {
+            app.set_accels_for_action(&format!("win.{}", key.id()), &[key.keysym()]);
+        }
+    }
+}
+
+/// Delivers media key presses while the window is unfocused, through the
+/// global shortcuts portal, until dropped
+pub struct GlobalMediaKeys {
+    // Activations only arrive while the proxy is alive
+    #[allow(dead_code)]
+    proxy: Rc<RefCell<Option<gio::DBusProxy>>>,
+}
+
+impl GlobalMediaKeys {
+    /// Ask the portal to bind the media keys. The desktop may ask the user
+    /// to confirm, and desktops without the portal leave only the window's
+    /// accelerators.
+    pub fn bind(on_key: impl Fn(MediaKey) + 'static) -> Self {
+        let slot = Rc::new(RefCell::new(None));
+        let proxy_slot = slot.clone();
+        gio::DBusProxy::for_bus(
+            gio::BusType::Session,
+            gio::DBusProxyFlags::DO_NOT_LOAD_PROPERTIES,
+            None,
+            PORTAL,
+            PORTAL_PATH,
+            GLOBAL_SHORTCUTS,
+            None::<&gio::Cancellable>,
+            move |result| {
+                let proxy = match result {
+                    Ok(proxy) => proxy,
+                    Err(e) => {
+                        debug!("Portal unavailable, media keys need focus: {}", e);
+                        return;
+                    }
+                };
+                proxy.connect_local("g-signal", false, move |values| {
+                    let signal = values[2].get::<String>().ok()?;
+                    let parameters = values[3].get::<glib::Variant>().ok()?;
+                    if signal == "Activated"
+                        && let Some(key) =
+                            parameters.child_value(1).str().and_then(MediaKey::from_id)
+                    {
+                        on_key(key);
+                    }
+                    None
+                });
+                create_session(&proxy);
+                proxy_slot.replace(Some(proxy));
+            },
+        );
+        Self { proxy: slot }
+    }
+}
+
+fn create_session(proxy: &gio::DBusProxy) {
+    let bind_proxy = proxy.clone();
+    portal_request(
+        proxy,
+        "CreateSession",
+        "reel_media_keys_session",
+        |mut options| {
+            options.insert(
+                "session_handle_token".to_string(),
+                "reel_media_keys".to_variant(),
+            );
+            (options,).to_variant()
+        },
+        move |results| {
+            let session = results
+                .get("session_handle")
+                .and_then(|handle| handle.str().map(str::to_string));
+            match session.map(glib::variant::ObjectPath::try_from) {
+                Some(Ok(session)) => bind_shortcuts(&bind_proxy, session),
+                _ => debug!("Global shortcuts portal returned no session"),
+            }
+        },
+    );
+}
+
+fn bind_shortcuts(proxy: &gio::DBusProxy, session: glib::variant::ObjectPath) {
+    let shortcuts: Vec<(String, HashMap<String, glib::Variant>)> = MediaKey::ALL
+        .into_iter()
+        .map(|key| {
+            let properties = HashMap::from([
+                ("description".to_string(), key.description().to_variant()),
+                ("preferred_trigger".to_string(), key.keysym().to_variant()),
+            ]);
+            (key.id().to_string(), properties)
+        })
+        .collect();
+    portal_request(
+        proxy,
+        "BindShortcuts",
+        "reel_media_keys_bind",
+        move |options| (session, shortcuts, "", options).to_variant(),
+        |_| debug!("Media keys bound through the global shortcuts portal"),
+    );
+}
+
+/// Call a portal method that answers through the Response signal of a
+/// request object. The request is watched before calling so the answer
+/// can't be missed.
+fn portal_request(
+    proxy: &gio::DBusProxy,
+    method: &'static str,
+    token: &'static str,
+    parameters: impl FnOnce(HashMap<String, glib::Variant>) -> glib::Variant + 'static,
+    on_response: impl FnOnce(HashMap<String, glib::Variant>) + 'static,
+) {
+    let connection = proxy.connection();
+    let Some(sender) = connection.unique_name() else {
+        return;
+    };
+    let request_path = format!(
+        "{}/request/{}/{}",
+        PORTAL_PATH,
+        sender.trim_start_matches(':').replace('.', "_"),
+        token
+    );
+
+    let proxy = proxy.clone();
+    gio::DBusProxy::new(
+        &connection,
+        gio::DBusProxyFlags::DO_NOT_LOAD_PROPERTIES,
+        None,
+        Some(PORTAL),
+        &request_path,
+        REQUEST,
+        None::<&gio::Cancellable>,
+        move |request| {
+            let request = match request {
+                Ok(request) => request,
+                Err(e) => {
+                    debug!("Could not watch portal request: {}", e);
+                    return;
+                }
+            };
+            // Holding the request proxy until it answers keeps the signal
+            // connected
+            let pending = RefCell::new(Some((request.clone(), on_response)));
+            request.connect_local("g-signal", false, move |values| {
+                if values[2].get::<String>().ok()? != "Response" {
+                    return None;
+                }
+                let (_request, on_response) = pending.borrow_mut().take()?;
+                let parameters = values[3].get::<glib::Variant>().ok()?;
+                match parameters.get::<(u32, HashMap<String, glib::Variant>)>() {
+                    Some((0, results)) => on_response(results),
+                    Some((code, _)) => debug!("Portal {} was not granted ({})", method, code),
+                    None => debug!("Unexpected portal {} response", method),
+                }
+                None
+            });
+
+            let options = HashMap::from([("handle_token".to_string(), token.to_variant())]);
+            proxy.call(
+                method,
+                Some(&parameters(options)),
+                gio::DBusCallFlags::NONE,
+                -1,
+                None::<&gio::Cancellable>,
+                move |result| {
+                    if let Err(e) = result {
+                        debug!("Global shortcuts portal {} failed: {}", method, e);
+                    }
+                },
+            );
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_key_ids_round_trip() {
+        for key in MediaKey::ALL {
+            assert_eq!(MediaKey::from_id(key.id()), Some(key));
+        }
+        assert_eq!(MediaKey::from_id("media-stop"), None);
+    }
+}
@@ -5,9 +5,26 @@ use relm4::gtk;
 use relm4::prelude::*;
 use tracing::debug;
 
+use super::fullscreen::{self, FullscreenMonitor};
 use super::{PlayerInput, PlayerPage};
 
-/// Menu population methods for audio/subtitle/zoom/quality menus
+/// Character encodings offered for text subtitles, by name
+const SUBTITLE_CHARSETS: [(&str, &str); 12] = [
+    ("auto", "Automatic"),
+    ("UTF-8", "Unicode (UTF-8)"),
+    ("CP1252", "Western (Windows-1252)"),
+    ("CP1250", "Central European (Windows-1250)"),
+    ("CP1251", "Cyrillic (Windows-1251)"),
+    ("CP1253", "Greek (Windows-1253)"),
+    ("CP1254", "Turkish (Windows-1254)"),
+    ("CP1255", "Hebrew (Windows-1255)"),
+    ("CP1256", "Arabic (Windows-1256)"),
+    ("GB18030", "Chinese Simplified (GB18030)"),
+    ("BIG5", "Chinese Traditional (Big5)"),
+    ("SHIFT_JIS", "Japanese (Shift JIS)"),
+];
+
+/// Menu population methods for audio/subtitle/zoom/quality/monitor menus
 impl PlayerPage {
     pub(super) fn populate_audio_menu(&self, sender: AsyncComponentSender<Self>) {
         if let Some(player) = &self.player {
@@ -83,13 +100,17 @@ impl PlayerPage {
             let player_clone = player.clone();
             let subtitle_menu_button = self.subtitle_menu_button.clone();
             let _current_track = self.current_subtitle_track;
+            let subtitle_scale = self.subtitle_scale;
+            let subtitle_charset = self.subtitle_config.charset.clone();
+            // Only MPV can add a subtitle file during playback
+            let can_load_file = self.is_mpv_backend;
             let sender = sender.clone();
             let popover_count = self.active_popover_count.clone();
 
             glib::spawn_future_local(async move {
                 let tracks = player_clone.get_subtitle_tracks().await.unwrap_or_default();
 
-                if tracks.is_empty() || tracks.len() == 1 {
+                if tracks.len() <= 1 && !can_load_file {
                     // No subtitle tracks available (only "None" option), disable the button
                     subtitle_menu_button.set_sensitive(false);
                     subtitle_menu_button.set_popover(None::<&gtk::Popover>);
@@ -106,6 +127,37 @@ impl PlayerPage {
                         menu.append_item(&item);
                     }
 
+                    // Subtitle size, also on Shift+G / Shift+F
+                    let size_menu = gtk::gio::Menu::new();
+                    size_menu.append(Some("Larger"), Some("player.subtitle-larger"));
+                    size_menu.append(Some("Smaller"), Some("player.subtitle-smaller"));
+                    size_menu.append(Some("Default Size"), Some("player.subtitle-size-reset"));
+                    menu.append_section(
+                        Some(&format!("Size {:.0}%", subtitle_scale * 100.0)),
+                        &size_menu,
+                    );
+
+                    // Legacy .srt files are often not in UTF-8
+                    let charset_menu = gtk::gio::Menu::new();
+                    for (charset, label) in SUBTITLE_CHARSETS {
+                        let item = gtk::gio::MenuItem::new(Some(label), None);
+                        item.set_action_and_target_value(
+                            Some("player.subtitle-charset"),
+                            Some(&charset.to_variant()),
+                        );
+                        charset_menu.append_item(&item);
+                    }
+                    menu.append_submenu(Some("Encoding"), &charset_menu);
+
+                    if can_load_file {
+                        let file_menu = gtk::gio::Menu::new();
+                        file_menu.append(
+                            Some("Load Subtitle File…"),
+                            Some("player.subtitle-load-file"),
+                        );
+                        menu.append_section(None, &file_menu);
+                    }
+
                     // Create popover from menu model
                     let popover = gtk::PopoverMenu::from_model(Some(&menu));
 
@@ -139,6 +191,35 @@ impl PlayerPage {
                         action_group.add_action(&action);
                     }
 
+                    let menu_actions: [(&str, fn() -> PlayerInput); 4] = [
+                        ("subtitle-larger", || PlayerInput::SubtitleScaleUp),
+                        ("subtitle-smaller", || PlayerInput::SubtitleScaleDown),
+                        ("subtitle-size-reset", || PlayerInput::SubtitleScaleReset),
+                        ("subtitle-load-file", || PlayerInput::LoadSubtitleFile),
+                    ];
+                    for (action_name, input) in menu_actions {
+                        let action = gtk::gio::SimpleAction::new(action_name, None);
+                        let sender_clone = sender.clone();
+                        action.connect_activate(move |_, _| {
+                            sender_clone.input(input());
+                        });
+                        action_group.add_action(&action);
+                    }
+
+                    let charset_action = gtk::gio::SimpleAction::new_stateful(
+                        "subtitle-charset",
+                        Some(glib::VariantTy::STRING),
+                        &subtitle_charset.to_variant(),
+                    );
+                    let sender_clone = sender.clone();
+                    charset_action.connect_activate(move |action, parameter| {
+                        if let Some(charset) = parameter.and_then(|p| p.get::<String>()) {
+                            action.set_state(&charset.to_variant());
+                            sender_clone.input(PlayerInput::SetSubtitleCharset(charset));
+                        }
+                    });
+                    action_group.add_action(&charset_action);
+
                     // Insert the action group
                     subtitle_menu_button.insert_action_group("player", Some(&action_group));
                     subtitle_menu_button.set_popover(Some(&popover));
@@ -360,4 +441,94 @@ impl PlayerPage {
         quality_menu_button.insert_action_group("player", Some(&action_group));
         quality_menu_button.set_popover(Some(&popover));
     }
+
+    pub(super) fn populate_monitor_menu(&self, sender: AsyncComponentSender<Self>) {
+        let monitor_menu_button = self.monitor_menu_button.clone();
+        let popover_count = self.active_popover_count.clone();
+        let monitors = fullscreen::connected_monitors(&self.window);
+
+        // Only worth a choice with more than one monitor
+        monitor_menu_button.set_visible(monitors.len() > 1);
+        if monitors.len() <= 1 {
+            return;
+        }
+
+        let modes = [
+            (
+                FullscreenMonitor::Window,
+                "Monitor of the Window".to_string(),
+            ),
+            (
+                FullscreenMonitor::Cursor,
+                "Monitor Under the Cursor".to_string(),
+            ),
+            (FullscreenMonitor::Last, "Last Used Monitor".to_string()),
+        ];
+        let choices: Vec<(FullscreenMonitor, String)> = monitors
+            .iter()
+            .filter_map(|monitor| {
+                fullscreen::connector_name(monitor).map(|connector| {
+                    (
+                        FullscreenMonitor::Connector(connector),
+                        fullscreen::monitor_label(monitor),
+                    )
+                })
+            })
+            .collect();
+
+        let menu = gtk::gio::Menu::new();
+        for (section_choices, section) in [(&modes[..], None), (&choices[..], Some("Monitors"))] {
+            let section_menu = gtk::gio::Menu::new();
+            for (choice, label) in section_choices {
+                let item = gtk::gio::MenuItem::new(Some(label), None);
+                item.set_action_and_target_value(
+                    Some("player.fullscreen-monitor"),
+                    Some(&choice.to_config().to_variant()),
+                );
+
+                // Add checkmark for current choice
+                if *choice == self.fullscreen_monitor {
+                    item.set_attribute_value("icon", Some(&"object-select-symbolic".to_variant()));
+                }
+
+                section_menu.append_item(&item);
+            }
+            menu.append_section(section, &section_menu);
+        }
+
+        // Create popover
+        let popover = gtk::PopoverMenu::from_model(Some(&menu));
+
+        // Track popover state to prevent control hiding
+        let popover_count_clone = popover_count.clone();
+        popover.connect_show(move |_| {
+            *popover_count_clone.borrow_mut() += 1;
+            debug!(
+                "Monitor popover shown, count: {}",
+                *popover_count_clone.borrow()
+            );
+        });
+        popover.connect_hide(move |_| {
+            let mut count = popover_count.borrow_mut();
+            if *count > 0 {
+                *count -= 1;
+            }
+            debug!("Monitor popover hidden, count: {}", *count);
+        });
+
+        let action_group = gtk::gio::SimpleActionGroup::new();
+        let action =
+            gtk::gio::SimpleAction::new("fullscreen-monitor", Some(glib::VariantTy::STRING));
+        action.connect_activate(move |_, parameter| {
+            if let Some(value) = parameter.and_then(|p| p.get::<String>()) {
+                sender.input(PlayerInput::SetFullscreenMonitor(
+                    FullscreenMonitor::from_config(&value),
+                ));
+            }
+        });
+        action_group.add_action(&action);
+
+        monitor_menu_button.insert_action_group("player", Some(&action_group));
+        monitor_menu_button.set_popover(Some(&popover));
+    }
 }
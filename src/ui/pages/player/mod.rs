@@ -1,18 +1,41 @@
-use crate::config::Config;
-use crate::models::{ChapterMarker, MediaItemId, PlaylistContext};
-use crate::player::{PlayerController, PlayerHandle, PlayerState};
+use crate::config::{Config, GestureConfig, SpeedConfig, SubtitleConfig};
+use crate::db::repository::TrackKind;
+use crate::models::{
+    ChapterMarker, MediaItemId, PlaybackError, PlaylistContext, SourceId, TranscodeSession,
+};
+use crate::player::{Chapter, PlayerController, PlayerHandle, PlayerState};
 use crate::services::commands::Command;
 use crate::services::config_service::CONFIG_SERVICE;
-use crate::ui::shared::broker::{BROKER, BrokerMessage, ConfigMessage};
+use crate::services::core::playback_decision::{PlaybackDecision, StreamChoice};
+use crate::services::core::{
+    ResolveStatus, ShowSkipModes, SkipPreferencesService, TrackPreferencesService, dominant_color,
+};
+use crate::services::playback_session::{PlaybackSession, SESSION_KEEP_ALIVE_INTERVAL, SeekPlan};
+use crate::ui::shared::accessibility;
+use crate::ui::shared::broker::{
+    BROKER, BrokerMessage, ConfigMessage, PowerMessage, Subscription, Topic,
+};
+use crate::ui::shared::commands::StartedStream;
+use crate::ui::shared::tint;
+use crate::workers::{ThumbnailWorker, ThumbnailWorkerInput, ThumbnailWorkerOutput};
 use adw::prelude::*;
 use gtk::glib::{self, SourceId};
 use libadwaita as adw;
 use relm4::gtk;
 use relm4::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
+mod fullscreen;
+use fullscreen::FullscreenMonitor;
+mod gestures;
+mod media_keys;
+use media_keys::{GlobalMediaKeys, MediaKey};
+mod session_events;
+use session_events::{SessionEvent, SessionMonitor};
 mod sleep_inhibition;
 use sleep_inhibition::SleepInhibitor;
 mod controls_visibility;
@@ -23,18 +46,24 @@ mod playlist_navigation;
 mod skip_markers;
 use skip_markers::SkipMarkerManager;
 mod buffering_overlay;
-use buffering_overlay::BufferingOverlay;
+use buffering_overlay::{BufferingOverlay, BufferingOverlayInput};
 mod auto_play;
 mod buffering_warnings;
 use auto_play::AutoPlayManager;
 mod error_retry;
-use error_retry::ErrorRetryManager;
-mod progress_tracker;
-use progress_tracker::ProgressTracker;
+use error_retry::{ErrorRetryManager, Recovery};
 mod seek_bar;
 use seek_bar::SeekBarManager;
 mod volume;
 use volume::VolumeManager;
+mod osd;
+use osd::OsdFlash;
+mod speed;
+use speed::{SPEED_RANGE, SpeedControl};
+mod pause_info;
+use pause_info::{ItemInfo, PauseInfo};
+mod stream_refresh;
+use stream_refresh::{StreamFailure, StreamRefresh};
 
 fn format_duration(duration: Duration) -> String {
     let total_secs = duration.as_secs();
@@ -49,8 +78,31 @@ fn format_duration(duration: Duration) -> String {
     }
 }
 
+/// Real time left until the end at the playback speed
+fn time_left(position: Duration, duration: Duration, speed: f64) -> Duration {
+    duration
+        .saturating_sub(position)
+        .div_f64(if speed > 0.0 { speed } else { 1.0 })
+}
+
+/// Wall-clock time playback ends after `left`
+fn ends_at(left: Duration) -> String {
+    let left = chrono::Duration::from_std(left).unwrap_or_default();
+    (chrono::Local::now() + left).format("%H:%M").to_string()
+}
+
 pub struct PlayerPage {
-    media_item_id: Option<MediaItemId>,
+    // Item and playlist playing, progress write-back and auto-play decisions
+    session: PlaybackSession,
+    // Keeps the server's transcode and session of the stream alive
+    keep_alive: Option<SourceId>,
+    // Bumped whenever a stream ends, telling apart the stream of a load
+    // that was replaced or stopped while resolving it
+    stream_generation: u64,
+    // Read when the window closes to remember interrupted playback
+    loaded_media: Rc<RefCell<Option<MediaItemId>>>,
+    // Set once the server sessions ended, so the window may close
+    sessions_ended: Rc<Cell<bool>>,
     player: Option<PlayerHandle>,
     player_state: PlayerState,
     position: Duration,
@@ -58,8 +110,6 @@ pub struct PlayerPage {
     db: Arc<crate::db::connection::DatabaseConnection>,
     video_container: gtk::Box,
     video_placeholder: Option<gtk::Label>,
-    // Playlist context
-    playlist_context: Option<PlaylistContext>,
     // Navigation state
     can_go_previous: bool,
     can_go_next: bool,
@@ -80,10 +130,19 @@ pub struct PlayerPage {
     window: adw::ApplicationWindow,
     // Error handling and retry management
     error_retry_manager: ErrorRetryManager,
-    // Progress tracking and syncing
-    progress_tracker: ProgressTracker,
+    // Stream asked for by the last load, for the recovery its errors offer
+    stream_choice: StreamChoice,
+    // Resolves the stream again once the server stops accepting its URL
+    stream_refresh: StreamRefresh,
+    // How the playing stream is delivered, shown in the stats overlay
+    stream_decision: Option<PlaybackDecision>,
+    stats_visible: bool,
     // Playback state
     playback_speed: f64,
+    speed_config: SpeedConfig,
+    speed_control: SpeedControl,
+    subtitle_scale: f64,
+    subtitle_config: SubtitleConfig,
     // Track selection menus
     audio_menu_button: gtk::MenuButton,
     subtitle_menu_button: gtk::MenuButton,
@@ -99,6 +158,12 @@ pub struct PlayerPage {
     zoom_menu_button: gtk::MenuButton,
     current_zoom_mode: crate::player::ZoomMode,
     zoom_label: gtk::Label,
+    // Fullscreen monitor selection
+    monitor_menu_button: gtk::MenuButton,
+    fullscreen_monitor: FullscreenMonitor,
+    last_fullscreen_monitor: String,
+    // Gestures of the video area, shared with their event controllers
+    gestures: Rc<Cell<GestureConfig>>,
     // Control widgets for bounds detection
     controls_overlay: Option<gtk::Box>,
     // Popover state tracking to prevent control hiding when popover is open
@@ -110,20 +175,490 @@ pub struct PlayerPage {
     skip_marker_manager: SkipMarkerManager,
     // Sleep inhibition
     sleep_inhibitor: SleepInhibitor,
+    // Suspend and screen lock, which pause playback
+    _session_monitor: SessionMonitor,
+    paused_by_session: bool,
+    resume_after_unlock: bool,
+    // Media keys pressed while the window is unfocused
+    _global_media_keys: GlobalMediaKeys,
+    last_media_key: Option<(MediaKey, Instant)>,
     // Buffering overlay component
     buffering_overlay: Controller<BufferingOverlay>,
+    // Feedback for keyboard and gesture actions while controls are hidden
+    osd_flash: OsdFlash,
+    // What is playing and when it ends, shown while paused
+    pause_info: PauseInfo,
+    // Glow in the color of the item's artwork while it loads
+    loading_tint: gtk::Box,
+    // Decodes seek thumbnails of local files in the background
+    thumbnail_worker: relm4::WorkerController<ThumbnailWorker>,
+    // Item whose seek thumbnails are being made or shown
+    seek_thumbnails_for: Option<String>,
+    _broker_subscription: Subscription,
 }
 
 impl PlayerPage {
     // Configuration constants for control visibility behavior
     const DEFAULT_INACTIVITY_TIMEOUT_SECS: u64 = 3;
     const DEFAULT_MOUSE_MOVE_THRESHOLD: f64 = 5.0; // pixels
+    // Subtitle size adjustment
+    const SUBTITLE_SCALE_STEP: f64 = 0.1;
+    const SUBTITLE_SCALE_RANGE: (f64, f64) = (0.5, 3.0);
+    // Subtitle formats offered when loading a file
+    const SUBTITLE_FILE_EXTENSIONS: [&str; 5] = ["srt", "ass", "ssa", "vtt", "sub"];
+    // How long closing the window waits for the server to hear playback ended
+    const SESSION_END_TIMEOUT: Duration = Duration::from_secs(3);
+
+    /// Tint the loading screen with the artwork color of the first of `ids`
+    /// whose color is known, such as an episode and then its show
+    fn tint_loading_screen(&self, ids: &[&str]) {
+        let color = ids.iter().find_map(|id| dominant_color::cached(id));
+        tint::apply(&self.loading_tint, color);
+    }
+
+    /// Start playing an item, with the playlist it is played from
+    async fn load_item(
+        &mut self,
+        id: MediaItemId,
+        context: Option<PlaylistContext>,
+        stream: StreamChoice,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        self.loaded_media.replace(Some(id.clone()));
+        self.stream_choice = stream;
+        self.player_state = PlayerState::Loading;
+        match &context {
+            Some(PlaylistContext::TvShow { show_id, .. }) => {
+                self.tint_loading_screen(&[id.as_str(), show_id.as_str()])
+            }
+            _ => self.tint_loading_screen(&[id.as_str()]),
+        }
+
+        // Reset scrubber UI to prevent showing previous video's position
+        self.seek_bar_manager.reset();
+
+        // Update navigation state and position label based on context
+        self.can_go_previous = context.as_ref().is_some_and(|c| c.has_previous());
+        self.can_go_next = context.as_ref().is_some_and(|c| c.has_next());
+        match &context {
+            Some(context) => self.update_playlist_position_label(context),
+            None => self.playlist_position_label.set_text(""),
+        }
+
+        self.end_stream();
+        self.session.start(Some(id.clone()), context).await;
+        // Clear any existing error and reset retry state
+        self.error_retry_manager.clear_error();
+        self.stream_refresh.clear();
+        self.stream_decision = None;
+        self.cancel_auto_play();
+        self.skip_marker_manager.clear_markers();
+
+        let db = self.db.clone();
+        let media_id = id.clone();
+        let markers_sender = sender.clone();
+        glib::spawn_future_local(async move {
+            match SkipPreferencesService::item_modes(&db, &media_id).await {
+                Ok(modes) => markers_sender.input(PlayerInput::LoadedSkipModes(modes)),
+                Err(e) => warn!("Failed to load skip overrides: {}", e),
+            }
+            let (intro, credits) = PlaybackSession::load_markers(&db, &media_id).await;
+            markers_sender.input(PlayerInput::LoadedMarkers { intro, credits });
+        });
+
+        self.pause_info.set_info(None);
+        let db = self.db.clone();
+        let media_id = id.clone();
+        let info_sender = sender.clone();
+        glib::spawn_future_local(async move {
+            match pause_info::load_item_info(&db, &media_id).await {
+                Ok(info) => info_sender.input(PlayerInput::LoadedItemInfo(info)),
+                Err(e) => warn!("Failed to load pause information: {}", e),
+            }
+        });
+
+        let Some(player) = &self.player else {
+            return;
+        };
+        let player_handle = player.clone();
+        let db = self.db.clone();
+        let resume = self.session.resume_settings();
+        let generation = self.stream_generation;
+        let sender_clone = sender.clone();
+        sender.oneshot_command(async move {
+            use crate::ui::shared::commands::{AppCommand, CommandResult, execute_command};
+
+            // Use the proper StartPlayback command which includes cache integration
+            let command_result = execute_command(
+                AppCommand::StartPlayback {
+                    media_id: id.to_string(),
+                    stream,
+                },
+                &db,
+                &|status| sender_clone.input(PlayerInput::ResolveStatus(status)),
+            )
+            .await;
+
+            let stream = match command_result {
+                CommandResult::PlaybackStarted { stream, .. } => stream,
+                CommandResult::Error(e) => {
+                    error!("Failed to start playback: {}", e);
+                    return PlayerCommandOutput::LoadError(e);
+                }
+            };
+
+            info!("Got stream URL (potentially cached): {}", stream.url);
+
+            // A transcode starts where playback resumes, its playlist can't
+            // be seeked past what the server has converted
+            let mut stream = stream;
+            let mut resume_position = resume.position(&db, &id).await;
+            if let Some(position) = resume_position
+                && let Some(transcode) = TranscodeSession::from_url(&stream.url)
+            {
+                stream.url = transcode.restarted_at(position).url;
+                resume_position = None;
+            }
+            sender_clone.input(PlayerInput::StreamStarted {
+                stream: stream.clone(),
+                generation,
+            });
+
+            // Load the media into the player using channel-based API
+            if let Err(e) = player_handle.load_media(&stream.url).await {
+                error!("Failed to load media: {}", e);
+                return PlayerCommandOutput::LoadError(error_retry::stream_error(&e, &stream));
+            }
+            info!("Media loaded successfully");
+
+            // Populate track menus after media loads
+            sender_clone.input(PlayerInput::UpdateTrackMenus);
+            sender_clone.input(PlayerInput::ApplyDefaultSpeed);
+
+            // Wait for player backend to be ready for seeking operations
+            info!("Waiting for player to be ready for seeking...");
+            if let Err(e) = player_handle
+                .wait_until_ready(std::time::Duration::from_secs(5))
+                .await
+            {
+                warn!("Player not ready after timeout: {}", e);
+            }
+            sender_clone.input(PlayerInput::RestoreTrackChoices);
+
+            // Chapters are known once the player is ready, they stand in
+            // for intro and credits markers the server doesn't have
+            if let Ok(chapters) = player_handle.get_chapters().await
+                && !chapters.is_empty()
+                && let Ok(Some(duration)) = player_handle.get_duration().await
+            {
+                sender_clone.input(PlayerInput::LoadedChapters { chapters, duration });
+            }
+
+            if let Some(position) = resume_position
+                && let Err(e) = player_handle.seek(position).await
+            {
+                error!("Failed to seek to saved position: {}", e);
+            }
+
+            // Try to get video dimensions and calculate appropriate window size
+            if let Ok(Some((width, height))) = player_handle.get_video_dimensions().await
+                && width > 0
+                && height > 0
+            {
+                // Calculate window size based on video aspect ratio
+                // Keep width reasonable (max 1920) and scale height accordingly
+                let max_width = 1920.0_f32.min(width as f32);
+                let scale = max_width / width as f32;
+                let window_width = max_width as i32;
+                let window_height = (height as f32 * scale) as i32;
+
+                // Add some padding for controls
+                let final_height = window_height + 100; // Extra space for controls
+
+                info!(
+                    "Video dimensions: {}x{}, window size: {}x{}",
+                    width, height, window_width, final_height
+                );
+
+                // Request window resize through output
+                sender_clone
+                    .output(PlayerOutput::WindowStateChanged {
+                        width: window_width,
+                        height: final_height,
+                    })
+                    .ok();
+            }
+
+            // Start playback automatically after loading. A stream the
+            // player has no decoder for only fails here.
+            if let Err(e) = player_handle.play().await {
+                match error_retry::stream_error(&e, &stream) {
+                    PlaybackError::Other(_) => warn!("Failed to auto-start playback: {}", e),
+                    error => {
+                        error!("Failed to start playback: {}", e);
+                        return PlayerCommandOutput::LoadError(error);
+                    }
+                }
+            }
+
+            // Get the actual state from the player after loading and playing
+            let actual_state = player_handle.get_state().await.unwrap_or(PlayerState::Idle);
+            PlayerCommandOutput::StateChanged(actual_state)
+        });
+    }
+
+    /// Resolve the stream of the playing item again, after its server
+    /// stopped accepting the URL, and go on where it stopped
+    fn refresh_stream(&mut self, sender: &AsyncComponentSender<Self>) {
+        let (Some(player), Some(media_id)) = (&self.player, self.session.media_id().cloned())
+        else {
+            return;
+        };
+        let player_handle = player.clone();
+        let position = self.position;
+        let speed = self.playback_speed;
+        self.end_stream();
+        self.error_retry_manager.clear_error();
+        self.player_state = PlayerState::Loading;
+
+        let db = self.db.clone();
+        let choice = self.stream_choice;
+        let generation = self.stream_generation;
+        let sender_clone = sender.clone();
+        sender.oneshot_command(async move {
+            use crate::ui::shared::commands::{AppCommand, CommandResult, execute_command};
+
+            let command_result = execute_command(
+                AppCommand::StartPlayback {
+                    media_id: media_id.to_string(),
+                    stream: choice,
+                },
+                &db,
+                &|status| sender_clone.input(PlayerInput::ResolveStatus(status)),
+            )
+            .await;
+            let mut stream = match command_result {
+                CommandResult::PlaybackStarted { stream, .. } => stream,
+                CommandResult::Error(e) => {
+                    error!("Failed to resolve the stream again: {}", e);
+                    return PlayerCommandOutput::LoadError(e);
+                }
+            };
+
+            // A transcode restarts at the position, other streams seek to it
+            let mut seek_position = Some(position);
+            if let Some(transcode) = TranscodeSession::from_url(&stream.url) {
+                stream.url = transcode.restarted_at(position).url;
+                seek_position = None;
+            }
+            sender_clone.input(PlayerInput::StreamStarted {
+                stream: stream.clone(),
+                generation,
+            });
+
+            if let Err(e) = player_handle.load_media(&stream.url).await {
+                error!("Failed to load the refreshed stream: {}", e);
+                return PlayerCommandOutput::LoadError(error_retry::stream_error(&e, &stream));
+            }
+            sender_clone.input(PlayerInput::UpdateTrackMenus);
+            sender_clone.input(PlayerInput::DefaultSpeedLoaded(speed));
+
+            if let Err(e) = player_handle.wait_until_ready(Duration::from_secs(5)).await {
+                warn!("Player not ready after timeout: {}", e);
+            }
+            sender_clone.input(PlayerInput::RestoreTrackChoices);
+            if let Some(position) = seek_position
+                && let Err(e) = player_handle.seek(position).await
+            {
+                error!("Failed to seek back to {:?}: {}", position, e);
+            }
+            if let Err(e) = player_handle.play().await {
+                warn!("Failed to resume the refreshed stream: {}", e);
+            }
+
+            let actual_state = player_handle.get_state().await.unwrap_or(PlayerState::Idle);
+            PlayerCommandOutput::StateChanged(actual_state)
+        });
+    }
+
+    /// Tell the server playback leaves the item, and stop its transcode
+    /// Make seek thumbnails if the stream is a local file, dropping those
+    /// of the previous item
+    fn start_seek_thumbnails(&mut self, stream: &StartedStream) {
+        let media_id = self.loaded_media.borrow().as_ref().map(|id| id.to_string());
+        if media_id.is_some() && media_id == self.seek_thumbnails_for {
+            // The same item resolved again
+            return;
+        }
+        self.cancel_seek_thumbnails();
+
+        let path = url::Url::parse(&stream.url)
+            .ok()
+            .filter(|url| url.scheme() == "file")
+            .and_then(|url| url.to_file_path().ok());
+        if let (Some(media_id), Some(path), Some(duration)) = (media_id, path, stream.duration) {
+            self.thumbnail_worker
+                .emit(ThumbnailWorkerInput::GenerateSeekThumbnails {
+                    media_id: media_id.clone(),
+                    path,
+                    duration,
+                });
+            self.seek_thumbnails_for = Some(media_id);
+        }
+    }
+
+    fn cancel_seek_thumbnails(&mut self) {
+        if let Some(media_id) = self.seek_thumbnails_for.take() {
+            self.thumbnail_worker
+                .emit(ThumbnailWorkerInput::Cancel { media_id });
+        }
+    }
+
+    /// Call off a pending auto-play, letting the ending of the item start
+    /// it again
+    fn cancel_auto_play(&mut self) {
+        self.auto_play_manager.cancel();
+        self.session.reset_ending();
+    }
+
+    /// Servers drop transcodes and sessions nobody asks about for a while,
+    /// like during a long pause
+    fn start_keep_alive(&mut self, sender: &AsyncComponentSender<Self>) {
+        self.stop_keep_alive();
+        let sender = sender.clone();
+        self.keep_alive = Some(glib::timeout_add_local(
+            SESSION_KEEP_ALIVE_INTERVAL,
+            move || {
+                sender.input(PlayerInput::KeepSessionAlive);
+                glib::ControlFlow::Continue
+            },
+        ));
+    }
+
+    fn stop_keep_alive(&mut self) {
+        if let Some(timer) = self.keep_alive.take() {
+            let _ = timer.remove();
+        }
+    }
+
+    fn end_stream(&mut self) {
+        self.stream_generation += 1;
+        self.stop_keep_alive();
+        if let Some(report) = self.session.end_session() {
+            glib::spawn_future_local(report.send());
+        }
+        if let Some(request) = self.session.end_transcode() {
+            glib::spawn_future_local(request.send());
+        }
+    }
+
+    /// Recovery the error overlay offers, if any
+    fn error_recovery(&self) -> Option<Recovery> {
+        self.error_retry_manager
+            .error()
+            .and_then(|error| Recovery::for_error(error, self.stream_choice))
+    }
+
+    /// Resize subtitles now and remember the size for future playback
+    fn set_subtitle_scale(&mut self, scale: f64, sender: &AsyncComponentSender<Self>) {
+        let (min, max) = Self::SUBTITLE_SCALE_RANGE;
+        // Round to whole steps so repeated adjustments don't drift
+        let scale = ((scale / Self::SUBTITLE_SCALE_STEP).round() * Self::SUBTITLE_SCALE_STEP)
+            .clamp(min, max);
+        if (scale - self.subtitle_scale).abs() < f64::EPSILON {
+            return;
+        }
+        self.subtitle_scale = scale;
+
+        if let Some(player) = &self.player {
+            let player_handle = player.clone();
+            glib::spawn_future_local(async move {
+                if let Err(err) = player_handle.set_subtitle_scale(scale).await {
+                    warn!("Failed to set subtitle scale: {}", err);
+                }
+            });
+        }
+
+        relm4::spawn(async move {
+            if let Err(err) = CONFIG_SERVICE.set_subtitle_scale(scale).await {
+                error!("Failed to save subtitle scale: {}", err);
+            }
+        });
+
+        accessibility::announce(
+            &self.subtitle_menu_button,
+            &format!("Subtitle size {:.0}%", scale * 100.0),
+        );
+        self.populate_subtitle_menu(sender.clone());
+    }
+
+    /// Change playback speed, within the supported range
+    fn set_playback_speed(&mut self, speed: f64) {
+        let (min, max) = SPEED_RANGE;
+        self.playback_speed = speed.clamp(min, max);
+        self.speed_control.set_speed(self.playback_speed);
+        self.seek_bar_manager.set_speed(self.playback_speed);
+
+        if let Some(player) = &self.player {
+            let player_handle = player.clone();
+            let speed = self.playback_speed;
+            glib::spawn_future_local(async move {
+                if let Err(err) = player_handle.set_playback_speed(speed).await {
+                    warn!("Failed to set playback speed: {}", err);
+                }
+            });
+        }
+    }
+
+    /// Go fullscreen on the chosen monitor and remember which one that was
+    fn enter_fullscreen(&mut self) {
+        let target = fullscreen::target_monitor(
+            &self.window,
+            &self.fullscreen_monitor,
+            &self.last_fullscreen_monitor,
+        );
+        match &target {
+            Some(monitor) => self.window.fullscreen_on_monitor(monitor),
+            None => self.window.fullscreen(),
+        }
+
+        // Without a target the window stays on its current monitor
+        let used = target.or_else(|| fullscreen::window_monitor(&self.window));
+        if let Some(connector) = used.as_ref().and_then(fullscreen::connector_name)
+            && connector != self.last_fullscreen_monitor
+        {
+            self.last_fullscreen_monitor = connector.clone();
+            relm4::spawn(async move {
+                if let Err(e) = CONFIG_SERVICE.set_last_fullscreen_monitor(connector).await {
+                    error!("Failed to remember fullscreen monitor: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Name of the play/pause button for its current action
+    fn play_pause_label(&self) -> &'static str {
+        if matches!(self.player_state, PlayerState::Playing) {
+            "Pause"
+        } else {
+            "Play"
+        }
+    }
+
+    /// Name of the fullscreen buttons for their current action
+    fn fullscreen_label(&self) -> &'static str {
+        if self.is_fullscreen {
+            "Exit Fullscreen"
+        } else {
+            "Enter Fullscreen"
+        }
+    }
 }
 
 impl std::fmt::Debug for PlayerPage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PlayerPage")
-            .field("media_item_id", &self.media_item_id)
+            .field("media_item_id", &self.session.media_id())
             .field("player_state", &self.player_state)
             .field("position", &self.position)
             .field("duration", &self.duration)
@@ -139,7 +674,11 @@ pub enum PlayerInput {
         media_id: MediaItemId,
         context: PlaylistContext,
     },
+    /// Play a network URL that is not a library item
+    LoadUrl(String),
     UpdateTrackMenus,
+    /// Select the remembered tracks once the player knows the stream's tracks
+    RestoreTrackChoices,
     SetAudioTrack(i32),
     SetSubtitleTrack(i32),
     PlayPause,
@@ -164,8 +703,10 @@ pub enum PlayerInput {
     Rewind,
     Forward,
     RetryLoad,
+    /// Recover from the error shown the way it offers
+    Recover,
     ClearError,
-    ShowError(String),
+    ShowError(PlaybackError),
     EscapePressed,
     NavigateBack,
     ClearAutoPlayTimeout,
@@ -173,6 +714,20 @@ pub enum PlayerInput {
     SpeedUp,
     SpeedDown,
     SpeedReset,
+    SetPlaybackSpeed(f64),
+    SetPitchCorrection(bool),
+    /// Start the loaded item at the speed set for its kind of content
+    ApplyDefaultSpeed,
+    DefaultSpeedLoaded(f64),
+    SubtitleScaleUp,
+    SubtitleScaleDown,
+    SubtitleScaleReset,
+    /// Ask for a subtitle file to load next to the playing item
+    LoadSubtitleFile,
+    SubtitleFileChosen(std::path::PathBuf),
+    RefreshSubtitleMenu,
+    /// Decode text subtitles with a character encoding, "auto" to detect it
+    SetSubtitleCharset(String),
     // Frame stepping
     FrameStepForward,
     FrameStepBackward,
@@ -185,6 +740,27 @@ pub enum PlayerInput {
     CycleAudioTrack,
     // Control visibility (for keyboard toggle)
     ToggleControlsVisibility,
+    ToggleStats,
+    /// Where resolving the stream of the loading item is at
+    ResolveStatus(ResolveStatus),
+    /// The stream of the loaded item is known, for the stream generation
+    /// it was loaded in
+    StreamStarted {
+        stream: StartedStream,
+        generation: u64,
+    },
+    /// Seek thumbnails of a local file are ready, or could not be made
+    SeekThumbnails(ThumbnailWorkerOutput),
+    /// The server of a failed stream was asked whether it still accepts
+    /// the stream URL
+    StreamProbed {
+        expired: bool,
+        error: PlaybackError,
+    },
+    /// Keep the transcode and a paused session from being dropped as idle
+    KeepSessionAlive,
+    /// End the server sessions of the item before the window closes
+    CloseWindow,
     // Skip intro/credits
     SkipIntro,
     SkipCredits,
@@ -195,6 +771,17 @@ pub enum PlayerInput {
         intro: Option<ChapterMarker>,
         credits: Option<ChapterMarker>,
     },
+    /// Skip overrides of the loaded item's show
+    LoadedSkipModes(ShowSkipModes),
+    /// Chapters of the loaded media, for skip buttons without markers
+    LoadedChapters {
+        chapters: Vec<Chapter>,
+        duration: Duration,
+    },
+    LoadedItemInfo(ItemInfo),
+    /// Switch the time label after the seek bar between total, remaining
+    /// and end time
+    CycleDurationDisplay,
     // Message broker messages
     BrokerMsg(BrokerMessage),
     // Zoom controls
@@ -208,6 +795,13 @@ pub enum PlayerInput {
     // Upscaling mode
     SetUpscalingMode(crate::player::UpscalingMode),
     UpdateQualityMenu,
+    // Suspend and screen lock
+    SessionEvent(SessionEvent),
+    // Multimedia keys
+    MediaKey(MediaKey),
+    // Fullscreen monitor
+    SetFullscreenMonitor(FullscreenMonitor),
+    UpdateMonitorMenu,
 }
 
 #[derive(Debug, Clone)]
@@ -216,7 +810,20 @@ pub enum PlayerOutput {
     MediaLoaded,
     Error(String),
     ShowToast(String),
-    WindowStateChanged { width: i32, height: i32 },
+    WindowStateChanged {
+        width: i32,
+        height: i32,
+    },
+    /// Sign in to the source of the item again
+    Reauthenticate {
+        source_id: SourceId,
+        source_name: String,
+        source_type: String,
+    },
+    /// The item playback was paused on, None once it plays or stops
+    Paused(Option<MediaItemId>),
+    /// Whether something is playing or loading, syncs hold off meanwhile
+    Playing(bool),
 }
 
 pub enum PlayerCommandOutput {
@@ -226,7 +833,7 @@ pub enum PlayerCommandOutput {
         duration: Option<Duration>,
         state: PlayerState,
     },
-    LoadError(String),
+    LoadError(PlaybackError),
 }
 
 impl std::fmt::Debug for PlayerCommandOutput {
@@ -244,14 +851,30 @@ impl std::fmt::Debug for PlayerCommandOutput {
                     position, duration, state
                 )
             }
-            Self::LoadError(msg) => write!(f, "LoadError({})", msg),
+            Self::LoadError(error) => write!(f, "LoadError({:?})", error),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::PlayerPage;
+    use super::{PlayerPage, time_left};
+    use std::time::Duration;
+
+    #[test]
+    fn time_left_follows_playback_speed() {
+        let position = Duration::from_secs(600);
+        let duration = Duration::from_secs(3000);
+        assert_eq!(
+            time_left(position, duration, 1.0),
+            Duration::from_secs(2400)
+        );
+        assert_eq!(
+            time_left(position, duration, 2.0),
+            Duration::from_secs(1200)
+        );
+        assert_eq!(time_left(duration, position, 1.0), Duration::ZERO);
+    }
 
     #[test]
     fn gstreamer_selection_is_never_mpv() {
@@ -301,6 +924,16 @@ impl AsyncComponent for PlayerPage {
                 add_css_class: "video-area",
             },
 
+            // Loading screen glow in the color of the item's artwork
+            add_overlay = &model.loading_tint.clone() {
+                set_can_target: false,
+                #[watch]
+                set_visible: model.player_state == PlayerState::Loading,
+            },
+
+            // Title, synopsis and time left while paused
+            add_overlay = model.pause_info.widget(),
+
             // Top left OSD controls (back button)
             add_overlay = &gtk::Box {
                 set_halign: gtk::Align::Start,
@@ -315,6 +948,7 @@ impl AsyncComponent for PlayerPage {
                 gtk::Button {
                     set_icon_name: "go-previous-symbolic",
                     set_tooltip_text: Some("Back"),
+                    update_property: &[gtk::accessible::Property::Label("Back")],
                     add_css_class: "osd",
                     add_css_class: "circular",
                     connect_clicked[sender] => move |_| {
@@ -341,13 +975,53 @@ impl AsyncComponent for PlayerPage {
                     } else {
                         "view-fullscreen-symbolic"
                     },
-                    set_tooltip_text: Some("Toggle Fullscreen"),
+                    #[watch]
+                    set_tooltip_text: Some(model.fullscreen_label()),
+                    #[watch]
+                    update_property: &[gtk::accessible::Property::Label(model.fullscreen_label())],
+                    #[watch]
+                    update_state: &[accessibility::pressed(model.is_fullscreen)],
                     add_css_class: "osd",
                     add_css_class: "circular",
                     connect_clicked => PlayerInput::ToggleFullscreen,
                 },
             },
 
+            // Playback stats overlay
+            add_overlay = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_halign: gtk::Align::Start,
+                set_valign: gtk::Align::Start,
+                set_margin_all: 20,
+                set_spacing: 4,
+                add_css_class: "osd",
+                add_css_class: "player-stats",
+                #[watch]
+                set_visible: model.stats_visible && model.stream_decision.is_some(),
+
+                gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    add_css_class: "heading",
+                    #[watch]
+                    set_label: model
+                        .stream_decision
+                        .as_ref()
+                        .map_or("", |decision| decision.method.label()),
+                },
+
+                gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    add_css_class: "caption",
+                    #[watch]
+                    set_label: &model.stream_decision.as_ref().map_or_else(String::new, |decision| {
+                        match &decision.transcode {
+                            Some(option) => format!("{}, converted to {}", decision.reason, option.name),
+                            None => decision.reason.to_string(),
+                        }
+                    }),
+                },
+            },
+
             // Error message overlay
             add_overlay = &gtk::Box {
                 set_orientation: gtk::Orientation::Vertical,
@@ -367,22 +1041,47 @@ impl AsyncComponent for PlayerPage {
 
                 gtk::Label {
                     #[watch]
-                    set_label: model.error_retry_manager.get_error_message().unwrap_or("An error occurred"),
+                    set_label: &model
+                        .error_retry_manager
+                        .error()
+                        .map(|error| error.to_string())
+                        .unwrap_or_else(|| "An error occurred".to_string()),
                     set_wrap: true,
                     set_max_width_chars: 50,
+                    set_justify: gtk::Justification::Center,
                     add_css_class: "title-2",
                 },
 
+                gtk::Label {
+                    #[watch]
+                    set_label: &model
+                        .error_retry_manager
+                        .error()
+                        .map(error_retry::error_hint)
+                        .unwrap_or_default(),
+                    #[watch]
+                    set_visible: model
+                        .error_retry_manager
+                        .error()
+                        .is_some_and(|error| !error_retry::error_hint(error).is_empty()),
+                    set_wrap: true,
+                    set_max_width_chars: 50,
+                    set_justify: gtk::Justification::Center,
+                },
+
                 gtk::Box {
                     set_orientation: gtk::Orientation::Horizontal,
                     set_spacing: 12,
                     set_halign: gtk::Align::Center,
 
                     gtk::Button {
-                        set_label: "Retry",
+                        #[watch]
+                        set_label: model.error_recovery().map_or("Retry", |recovery| recovery.label()),
+                        #[watch]
+                        set_visible: model.error_recovery().is_some(),
                         add_css_class: "suggested-action",
                         add_css_class: "pill",
-                        connect_clicked => PlayerInput::RetryLoad,
+                        connect_clicked => PlayerInput::Recover,
                     },
 
                     gtk::Button {
@@ -398,6 +1097,9 @@ impl AsyncComponent for PlayerPage {
             // Buffering overlay
             add_overlay = model.buffering_overlay.widget(),
 
+            // Volume, speed and seek feedback
+            add_overlay = model.osd_flash.widget(),
+
             // Skip intro button overlay
             add_overlay = &gtk::Box {
                 set_halign: gtk::Align::End,
@@ -514,6 +1216,7 @@ impl AsyncComponent for PlayerPage {
                             set_icon_name: "media-skip-backward-symbolic",
                             add_css_class: "flat",
                             set_tooltip_text: Some("Previous"),
+                            update_property: &[gtk::accessible::Property::Label("Previous")],
                             #[watch]
                             set_sensitive: model.can_go_previous,
                             connect_clicked => PlayerInput::Previous,
@@ -524,6 +1227,7 @@ impl AsyncComponent for PlayerPage {
                             set_icon_name: "media-seek-backward-symbolic",
                             add_css_class: "flat",
                             set_tooltip_text: Some("Rewind 10 seconds"),
+                            update_property: &[gtk::accessible::Property::Label("Rewind 10 seconds")],
                             connect_clicked => PlayerInput::Rewind,
                         },
 
@@ -541,6 +1245,10 @@ impl AsyncComponent for PlayerPage {
                                 } else {
                                     "media-playback-start-symbolic"
                                 },
+                                #[watch]
+                                set_tooltip_text: Some(model.play_pause_label()),
+                                #[watch]
+                                update_property: &[gtk::accessible::Property::Label(model.play_pause_label())],
                                 add_css_class: "circular",
                                 add_css_class: "play-pause-button",
                                 set_can_shrink: false,
@@ -553,6 +1261,7 @@ impl AsyncComponent for PlayerPage {
                             set_icon_name: "media-seek-forward-symbolic",
                             add_css_class: "flat",
                             set_tooltip_text: Some("Forward 10 seconds"),
+                            update_property: &[gtk::accessible::Property::Label("Forward 10 seconds")],
                             connect_clicked => PlayerInput::Forward,
                         },
 
@@ -561,6 +1270,7 @@ impl AsyncComponent for PlayerPage {
                             set_icon_name: "media-skip-forward-symbolic",
                             add_css_class: "flat",
                             set_tooltip_text: Some("Next"),
+                            update_property: &[gtk::accessible::Property::Label("Next")],
                             #[watch]
                             set_sensitive: model.can_go_next,
                             connect_clicked => PlayerInput::Next,
@@ -579,6 +1289,7 @@ impl AsyncComponent for PlayerPage {
                             set_icon_name: "audio-x-generic-symbolic",
                             add_css_class: "flat",
                             set_tooltip_text: Some("Audio Track"),
+                            update_property: &[gtk::accessible::Property::Label("Audio Track")],
                         },
 
                         // Subtitle tracks button
@@ -586,6 +1297,7 @@ impl AsyncComponent for PlayerPage {
                             set_icon_name: "media-view-subtitles-symbolic",
                             add_css_class: "flat",
                             set_tooltip_text: Some("Subtitles"),
+                            update_property: &[gtk::accessible::Property::Label("Subtitles")],
                         },
 
                         // Quality/Resolution button (hidden - doesn't work with current MPV embed)
@@ -593,14 +1305,29 @@ impl AsyncComponent for PlayerPage {
                             set_icon_name: "preferences-system-symbolic",
                             add_css_class: "flat",
                             set_tooltip_text: Some("Video Quality"),
+                            update_property: &[gtk::accessible::Property::Label("Video Quality")],
                             set_visible: false,
                         },
 
+                        // Playback speed button
+                        model.speed_control.widget().clone() {
+                            set_valign: gtk::Align::Center,
+                        },
+
                         // Zoom button
                         model.zoom_menu_button.clone() {
                             set_icon_name: "zoom-in-symbolic",
                             add_css_class: "flat",
                             set_tooltip_text: Some("Video Zoom"),
+                            update_property: &[gtk::accessible::Property::Label("Video Zoom")],
+                        },
+
+                        // Fullscreen monitor button
+                        model.monitor_menu_button.clone() {
+                            set_icon_name: "video-display-symbolic",
+                            add_css_class: "flat",
+                            set_tooltip_text: Some("Fullscreen Monitor"),
+                            update_property: &[gtk::accessible::Property::Label("Fullscreen Monitor")],
                         },
 
                         // Fullscreen button
@@ -612,7 +1339,12 @@ impl AsyncComponent for PlayerPage {
                                 "view-fullscreen-symbolic"
                             },
                             add_css_class: "flat",
-                            set_tooltip_text: Some("Fullscreen"),
+                            #[watch]
+                            set_tooltip_text: Some(model.fullscreen_label()),
+                            #[watch]
+                            update_property: &[gtk::accessible::Property::Label(model.fullscreen_label())],
+                            #[watch]
+                            update_state: &[accessibility::pressed(model.is_fullscreen)],
                             connect_clicked => PlayerInput::ToggleFullscreen,
                         },
                     },
@@ -642,35 +1374,9 @@ impl AsyncComponent for PlayerPage {
             .orientation(gtk::Orientation::Vertical)
             .build();
 
-        // Add drag gesture to make the window draggable by clicking on the video area
-        let drag_gesture = gtk::GestureDrag::new();
-        let window_clone = window.clone();
-        drag_gesture.connect_drag_begin(move |gesture, start_x, start_y| {
-            // Only start drag if it's a primary button (left click) and not in fullscreen
-            if let Some(event) = gesture.current_event() {
-                if event.triggers_context_menu() {
-                    return; // Don't drag on right-click
-                }
-
-                // Get the surface and start the drag
-                if !window_clone.is_fullscreen()
-                    && let Some(surface) = window_clone.surface()
-                {
-                    // Check if surface implements Toplevel interface
-                    use gtk::gdk::prelude::ToplevelExt;
-                    if let Some(toplevel) = surface.downcast_ref::<gtk::gdk::Toplevel>() {
-                        toplevel.begin_move(
-                            &event.device().unwrap(),
-                            gesture.current_button() as i32,
-                            start_x,
-                            start_y,
-                            event.time(),
-                        );
-                    }
-                }
-            }
-        });
-        video_container.add_controller(drag_gesture);
+        // Scroll, click, drag and swipe gestures on the video area
+        let gestures = Rc::new(Cell::new(GestureConfig::default()));
+        gestures::attach(&video_container, &window, &sender, gestures.clone());
 
         // Add a placeholder initially
         let placeholder = gtk::Label::new(Some("Initializing player..."));
@@ -679,8 +1385,11 @@ impl AsyncComponent for PlayerPage {
         placeholder.set_halign(gtk::Align::Center);
         video_container.append(&placeholder);
 
+        // Load config via the shared ConfigService so runtime updates stay in sync
+        let config = CONFIG_SERVICE.get_config().await;
+
         // Create seek bar manager (handles widgets and gesture setup)
-        let seek_bar_manager = SeekBarManager::new(&sender);
+        let seek_bar_manager = SeekBarManager::new(&sender, config.playback.duration_display);
 
         // Create volume manager (handles widget and change handler setup)
         let volume_manager = VolumeManager::new(&sender);
@@ -693,12 +1402,19 @@ impl AsyncComponent for PlayerPage {
         let quality_menu_button = gtk::MenuButton::new();
         let zoom_menu_button = gtk::MenuButton::new();
         let zoom_label = gtk::Label::new(Some("Fit"));
+        let monitor_menu_button = gtk::MenuButton::new();
+        let active_popover_count = Rc::new(RefCell::new(0));
+        let speed_control = SpeedControl::new(&sender, active_popover_count.clone());
 
-        // Load config via the shared ConfigService so runtime updates stay in sync
-        let config = CONFIG_SERVICE.get_config().await;
+        gestures.set(config.playback.gestures);
+        speed_control.set_pitch_correction(config.playback.speed.pitch_correction);
 
         let mut model = Self {
-            media_item_id,
+            loaded_media: Rc::new(RefCell::new(media_item_id.clone())),
+            sessions_ended: Rc::new(Cell::new(false)),
+            session: PlaybackSession::new((*db).clone(), &config.playback),
+            keep_alive: None,
+            stream_generation: 0,
             player: None,
             player_state: PlayerState::Idle,
             position: Duration::from_secs(0),
@@ -706,7 +1422,6 @@ impl AsyncComponent for PlayerPage {
             db,
             video_container: video_container.clone(),
             video_placeholder: Some(placeholder.clone()),
-            playlist_context: None,
             can_go_previous: false,
             can_go_next: false,
             control_state: ControlState::Visible {
@@ -721,12 +1436,15 @@ impl AsyncComponent for PlayerPage {
             playlist_position_label: playlist_position_label.clone(),
             window: window.clone(),
             error_retry_manager: ErrorRetryManager::new(3),
-            progress_tracker: ProgressTracker::new(
-                config.playback.auto_resume,
-                config.playback.resume_threshold_seconds as u64,
-                config.playback.progress_update_interval_seconds as u64,
-            ),
+            stream_refresh: StreamRefresh::new(),
+            stream_choice: StreamChoice::default(),
+            stream_decision: None,
+            stats_visible: false,
             playback_speed: 1.0,
+            speed_config: config.playback.speed,
+            speed_control,
+            subtitle_scale: config.playback.subtitle_scale,
+            subtitle_config: config.playback.subtitles.clone(),
             audio_menu_button: audio_menu_button.clone(),
             subtitle_menu_button: subtitle_menu_button.clone(),
             current_audio_track: None,
@@ -738,8 +1456,12 @@ impl AsyncComponent for PlayerPage {
             zoom_menu_button: zoom_menu_button.clone(),
             current_zoom_mode: crate::player::ZoomMode::default(),
             zoom_label: zoom_label.clone(),
+            monitor_menu_button: monitor_menu_button.clone(),
+            fullscreen_monitor: FullscreenMonitor::from_config(&config.playback.fullscreen_monitor),
+            last_fullscreen_monitor: config.playback.last_fullscreen_monitor.clone(),
+            gestures,
             controls_overlay: None, // Will be set when controls are created
-            active_popover_count: std::rc::Rc::new(std::cell::RefCell::new(0)),
+            active_popover_count,
             inactivity_timeout_secs: Self::DEFAULT_INACTIVITY_TIMEOUT_SECS,
             mouse_move_threshold: Self::DEFAULT_MOUSE_MOVE_THRESHOLD,
             // Skip intro/credits management
@@ -752,9 +1474,38 @@ impl AsyncComponent for PlayerPage {
             ),
             // Sleep inhibition
             sleep_inhibitor: SleepInhibitor::new(),
+            _session_monitor: SessionMonitor::start({
+                let sender = sender.input_sender().clone();
+                move |event| sender.emit(PlayerInput::SessionEvent(event))
+            }),
+            paused_by_session: false,
+            resume_after_unlock: config.playback.resume_after_unlock,
+            _global_media_keys: GlobalMediaKeys::bind({
+                let sender = sender.input_sender().clone();
+                move |key| sender.emit(PlayerInput::MediaKey(key))
+            }),
+            last_media_key: None,
             // Buffering overlay
             buffering_overlay: BufferingOverlay::builder().launch(()).detach(),
+            thumbnail_worker: ThumbnailWorker::builder()
+                .detach_worker(())
+                .forward(sender.input_sender(), PlayerInput::SeekThumbnails),
+            seek_thumbnails_for: None,
+            osd_flash: OsdFlash::new(),
+            pause_info: PauseInfo::new(),
+            loading_tint: gtk::Box::builder()
+                .css_classes(["player-loading-tint"])
+                .build(),
+            // Subscribe to MessageBroker for config updates
+            _broker_subscription: BROKER.subscribe(
+                &[Topic::Config, Topic::Power],
+                sender.input_sender(),
+                PlayerInput::BrokerMsg,
+            ),
         };
+        model
+            .session
+            .set_auto_play_limit(config.kids.episode_limit());
 
         // Initialize the player controller
         match PlayerController::new(&config) {
@@ -766,7 +1517,10 @@ impl AsyncComponent for PlayerPage {
                 error!("Failed to initialize player controller: {}", e);
                 model
                     .error_retry_manager
-                    .show_error(format!("Failed to initialize player: {}", e));
+                    .show_error(PlaybackError::Other(format!(
+                        "Failed to initialize player: {}",
+                        e
+                    )));
                 model.player_state = PlayerState::Error;
             }
         }
@@ -876,6 +1630,17 @@ impl AsyncComponent for PlayerPage {
                         sender.input(PlayerInput::SpeedReset);
                         glib::Propagation::Stop
                     }
+                    // Subtitle size
+                    gtk::gdk::Key::G => {
+                        // Shift+G: larger subtitles
+                        sender.input(PlayerInput::SubtitleScaleUp);
+                        glib::Propagation::Stop
+                    }
+                    gtk::gdk::Key::F => {
+                        // Shift+F: smaller subtitles
+                        sender.input(PlayerInput::SubtitleScaleDown);
+                        glib::Propagation::Stop
+                    }
                     // Volume controls
                     gtk::gdk::Key::_9 => {
                         // 9: volume down by 10%
@@ -935,9 +1700,15 @@ impl AsyncComponent for PlayerPage {
                         sender.input(PlayerInput::CycleAudioTrack);
                         glib::Propagation::Stop
                     }
-                    // Controls visibility
-                    gtk::gdk::Key::Tab => {
-                        // Tab: toggle controls visibility
+                    // Controls visibility, Tab is left to move focus through the
+                    // controls, which any key press has already revealed
+                    gtk::gdk::Key::i => {
+                        // i: toggle playback stats
+                        sender.input(PlayerInput::ToggleStats);
+                        glib::Propagation::Stop
+                    }
+                    gtk::gdk::Key::c => {
+                        // c: toggle controls visibility
                         sender.input(PlayerInput::ToggleControlsVisibility);
                         glib::Propagation::Stop
                     }
@@ -971,6 +1742,42 @@ impl AsyncComponent for PlayerPage {
         // Populate quality menu
         model.populate_quality_menu(sender.clone());
 
+        // Offer a fullscreen monitor while more than one is connected
+        model.populate_monitor_menu(sender.clone());
+        {
+            let sender = sender.clone();
+            fullscreen::connect_monitors_changed(&model.window, move || {
+                sender.input(PlayerInput::UpdateMonitorMenu);
+            });
+        }
+
+        // Media keys while the window has focus
+        media_keys::add_window_actions(&model.window, {
+            let sender = sender.input_sender().clone();
+            move |key| sender.emit(PlayerInput::MediaKey(key))
+        });
+
+        // Remember what was playing if the app is closed mid-playback, so
+        // startup can offer to resume it
+        {
+            let loaded_media = model.loaded_media.clone();
+            let sessions_ended = model.sessions_ended.clone();
+            let page = root.clone();
+            let sender = sender.clone();
+            model.window.connect_close_request(move |_| {
+                if sessions_ended.get() || !page.is_mapped() {
+                    return glib::Propagation::Proceed;
+                }
+                let Some(media_id) = loaded_media.borrow().clone() else {
+                    return glib::Propagation::Proceed;
+                };
+                remember_interrupted_playback(&media_id);
+                // The window closes once the server knows playback ended
+                sender.input(PlayerInput::CloseWindow);
+                glib::Propagation::Stop
+            });
+        }
+
         // Start position update timer (1Hz)
         {
             let sender = sender.clone();
@@ -983,22 +1790,9 @@ impl AsyncComponent for PlayerPage {
         // Start with controls visible with timer
         model.transition_to_visible(sender.clone());
 
-        // Subscribe to MessageBroker for config updates
-        {
-            let broker_sender = sender.input_sender().clone();
-            relm4::spawn(async move {
-                let (tx, rx) = relm4::channel::<BrokerMessage>();
-                BROKER.subscribe("PlayerPage".to_string(), tx).await;
-
-                while let Some(msg) = rx.recv().await {
-                    broker_sender.send(PlayerInput::BrokerMsg(msg)).unwrap();
-                }
-            });
-        }
-
         // Load media if provided
-        if let Some(id) = &model.media_item_id {
-            sender.input(PlayerInput::LoadMedia(id.clone()));
+        if let Some(id) = media_item_id {
+            sender.input(PlayerInput::LoadMedia(id));
         }
 
         let widgets = view_output!();
@@ -1017,526 +1811,120 @@ impl AsyncComponent for PlayerPage {
     ) {
         match msg {
             PlayerInput::LoadMedia(id) => {
-                self.media_item_id = Some(id.clone());
+                self.load_item(id, None, StreamChoice::Automatic, &sender)
+                    .await;
+            }
+            PlayerInput::LoadUrl(url) => {
+                // Nothing to track progress, markers or tracks against
+                self.loaded_media.replace(None);
                 self.player_state = PlayerState::Loading;
-
-                // Reset scrubber UI to prevent showing previous video's position
+                self.tint_loading_screen(&[]);
                 self.seek_bar_manager.reset();
-
-                // Clear context when loading without context
-                self.playlist_context = None;
-                // No navigation available for single items
+                self.cancel_seek_thumbnails();
+                self.end_stream();
+                self.session.start(None, None).await;
                 self.can_go_previous = false;
                 self.can_go_next = false;
-                // Clear playlist position label
                 self.playlist_position_label.set_text("");
-                // Clear any existing error and reset retry state
                 self.error_retry_manager.clear_error();
-                // Reset auto-play state
-                self.auto_play_manager.cancel();
-                // Clear skip button state
+                self.stream_decision = None;
+                self.cancel_auto_play();
                 self.skip_marker_manager.clear_markers();
 
-                // Load marker data from database and fetch from backend if missing
-                let db_clone_for_markers = self.db.clone();
-                let media_id_for_markers = id.clone();
-                let sender_for_markers = sender.clone();
-                glib::spawn_future_local(async move {
-                    use crate::db::repository::{MediaRepository, MediaRepositoryImpl, Repository};
-                    use crate::models::{MediaItem, MediaItemId};
-                    use crate::services::core::backend::BackendService;
-
-                    let media_repo =
-                        MediaRepositoryImpl::new(db_clone_for_markers.as_ref().clone());
-                    if let Ok(Some(mut db_media)) =
-                        media_repo.find_by_id(media_id_for_markers.as_ref()).await
-                    {
-                        // Check if markers are missing in database
-                        let markers_missing = db_media.intro_marker_start_ms.is_none()
-                            && db_media.credits_marker_start_ms.is_none();
-
-                        if markers_missing {
-                            // Fetch markers from backend
-                            let media_id_typed = MediaItemId::new(media_id_for_markers.to_string());
-                            match BackendService::fetch_markers(
-                                &db_clone_for_markers,
-                                &media_id_typed,
-                            )
-                            .await
-                            {
-                                Ok((intro_marker, credits_marker)) => {
-                                    // Store markers in database
-                                    let intro_tuple =
-                                        intro_marker.as_ref().map(|(start, end)| (*start, *end));
-                                    let credits_tuple =
-                                        credits_marker.as_ref().map(|(start, end)| (*start, *end));
-
-                                    if let Err(e) = media_repo
-                                        .update_markers(
-                                            media_id_for_markers.as_ref(),
-                                            intro_tuple,
-                                            credits_tuple,
-                                        )
-                                        .await
-                                    {
-                                        tracing::warn!(
-                                            "Failed to store markers in database: {}",
-                                            e
-                                        );
-                                    } else {
-                                        // Update local db_media with the new markers
-                                        if let Some((start, end)) = intro_tuple {
-                                            db_media.intro_marker_start_ms = Some(start);
-                                            db_media.intro_marker_end_ms = Some(end);
-                                        }
-                                        if let Some((start, end)) = credits_tuple {
-                                            db_media.credits_marker_start_ms = Some(start);
-                                            db_media.credits_marker_end_ms = Some(end);
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    tracing::debug!(
-                                        "Could not fetch markers from backend: {} (this is normal if markers aren't available)",
-                                        e
-                                    );
-                                }
-                            }
-                        }
-
-                        // Convert to domain model to get markers
-                        if let Ok(media_item) = MediaItem::try_from(db_media) {
-                            match media_item {
-                                MediaItem::Movie(movie) => {
-                                    sender_for_markers.input(PlayerInput::LoadedMarkers {
-                                        intro: movie.intro_marker,
-                                        credits: movie.credits_marker,
-                                    });
-                                }
-                                MediaItem::Episode(episode) => {
-                                    sender_for_markers.input(PlayerInput::LoadedMarkers {
-                                        intro: episode.intro_marker,
-                                        credits: episode.credits_marker,
-                                    });
-                                }
-                                _ => {}
-                            }
-                        }
+                let remembered_url = url.clone();
+                relm4::spawn(async move {
+                    if let Err(err) = CONFIG_SERVICE.remember_opened_url(&remembered_url).await {
+                        error!("Failed to remember opened URL: {}", err);
                     }
                 });
 
-                // Get actual media URL from backend using GetStreamUrlCommand
-                let db_clone = self.db.clone();
-                let media_id = id.clone();
-                let media_id_for_resume = media_id.clone();
-                let sender_clone = sender.clone();
-                // Capture progress tracker config values for use in async closure
-                let auto_resume = self.progress_tracker.get_auto_resume();
-                let resume_threshold_seconds = self.progress_tracker.get_resume_threshold_seconds();
-
                 if let Some(player) = &self.player {
                     let player_handle = player.clone();
+                    let sender_clone = sender.clone();
                     sender.oneshot_command(async move {
-                        use crate::ui::shared::commands::{
-                            AppCommand, CommandResult, execute_command,
-                        };
+                        use crate::services::core::url_playback;
 
-                        // Use the proper StartPlayback command which includes cache integration
-                        let command_result = execute_command(
-                            AppCommand::StartPlayback {
-                                media_id: media_id.to_string(),
-                            },
-                            &db_clone,
-                        )
-                        .await;
-
-                        let stream_url = match command_result {
-                            CommandResult::PlaybackStarted { url, .. } => url,
-                            CommandResult::Error(e) => {
-                                error!("Failed to start playback: {}", e);
-                                return PlayerCommandOutput::LoadError(format!(
-                                    "Failed to load media: {}",
-                                    e
-                                ));
-                            }
-                        };
-
-                        info!("Got stream URL (potentially cached): {}", stream_url);
-
-                        // Load the media into the player using channel-based API
-                        match player_handle.load_media(&stream_url).await {
-                            Ok(_) => {
-                                info!("Media loaded successfully");
-
-                                // Populate track menus after media loads
-                                sender_clone.input(PlayerInput::UpdateTrackMenus);
-
-                                // Wait for player backend to be ready for seeking operations
-                                info!("Waiting for player to be ready for seeking...");
-                                if let Err(e) = player_handle
-                                    .wait_until_ready(std::time::Duration::from_secs(5))
-                                    .await
-                                {
-                                    warn!("Player not ready after timeout: {}", e);
-                                }
-
-                                // Check for saved playback progress and resume if configured
-                                use crate::services::commands::GetPlaybackProgressCommand;
-
-                                // Use cached config values
-                                if auto_resume {
-                                    // TODO: Restore PlayQueue state when loading without context
-                                    // This requires restructuring to avoid Send/Sync issues with backend.as_any()
-
-                                    // Get saved progress
-                                    if let Ok(Some(progress)) = (GetPlaybackProgressCommand {
-                                        db: db_clone.as_ref().clone(),
-                                        media_id: media_id_for_resume.clone(),
-                                        user_id: "default".to_string(), // TODO: Get actual user ID
-                                    })
-                                    .execute()
-                                    .await
-                                    {
-                                        // Only resume if:
-                                        // 1. Position is above threshold (e.g., 5 seconds)
-                                        // 2. Progress is less than 95% (not near completion)
-                                        // 3. Media is not marked as watched
-                                        let threshold_ms = (resume_threshold_seconds as i64) * 1000;
-                                        let progress_percentage =
-                                            progress.get_progress_percentage();
-
-                                        if progress.position_ms > threshold_ms
-                                            && progress_percentage < 0.95
-                                            && !progress.watched
-                                        {
-                                            let resume_position = std::time::Duration::from_millis(
-                                                progress.position_ms as u64,
-                                            );
-                                            info!(
-                                                "Resuming playback from {:?} ({:.1}% complete)",
-                                                resume_position,
-                                                progress_percentage * 100.0
-                                            );
-
-                                            // Seek to saved position (player is now ready)
-                                            info!("Player ready, seeking to saved position");
-                                            if let Err(e) =
-                                                player_handle.seek(resume_position).await
-                                            {
-                                                error!("Failed to seek to saved position: {}", e);
-                                            }
-                                        }
-                                    }
-                                }
-
-                                // Try to get video dimensions and calculate appropriate window size
-                                if let Ok(Some((width, height))) =
-                                    player_handle.get_video_dimensions().await
-                                    && width > 0
-                                    && height > 0
-                                {
-                                    // Calculate window size based on video aspect ratio
-                                    // Keep width reasonable (max 1920) and scale height accordingly
-                                    let max_width = 1920.0_f32.min(width as f32);
-                                    let scale = max_width / width as f32;
-                                    let window_width = max_width as i32;
-                                    let window_height = (height as f32 * scale) as i32;
-
-                                    // Add some padding for controls
-                                    let final_height = window_height + 100; // Extra space for controls
-
-                                    info!(
-                                        "Video dimensions: {}x{}, window size: {}x{}",
-                                        width, height, window_width, final_height
+                        let use_ytdlp = CONFIG_SERVICE
+                            .get_config()
+                            .await
+                            .playback
+                            .resolve_urls_with_ytdlp;
+                        let stream_url =
+                            match url_playback::resolve_stream_url(&url, use_ytdlp).await {
+                                Ok(stream_url) => stream_url,
+                                Err(e) => {
+                                    error!("Failed to resolve {}: {}", url, e);
+                                    return PlayerCommandOutput::LoadError(
+                                        PlaybackError::from_error(&e),
                                     );
-
-                                    // Request window resize through output
-                                    sender_clone
-                                        .output(PlayerOutput::WindowStateChanged {
-                                            width: window_width,
-                                            height: final_height,
-                                        })
-                                        .ok();
                                 }
+                            };
 
-                                // Start playback automatically after loading
-                                if let Err(e) = player_handle.play().await {
-                                    warn!("Failed to auto-start playback: {}", e);
-                                }
+                        if let Err(e) = player_handle.load_media(&stream_url).await {
+                            error!("Failed to load URL: {}", e);
+                            return PlayerCommandOutput::LoadError(PlaybackError::from_error(&e));
+                        }
+                        sender_clone.input(PlayerInput::UpdateTrackMenus);
 
-                                // Get the actual state from the player after loading and playing
-                                let actual_state =
-                                    player_handle.get_state().await.unwrap_or(PlayerState::Idle);
-                                PlayerCommandOutput::StateChanged(actual_state)
-                            }
-                            Err(e) => {
-                                error!("Failed to load media: {}", e);
-                                PlayerCommandOutput::LoadError(format!("Playback error: {}", e))
-                            }
+                        if let Err(e) = player_handle
+                            .wait_until_ready(std::time::Duration::from_secs(5))
+                            .await
+                        {
+                            warn!("Player not ready after timeout: {}", e);
                         }
+                        if let Err(e) = player_handle.play().await {
+                            warn!("Failed to auto-start playback: {}", e);
+                        }
+
+                        let actual_state =
+                            player_handle.get_state().await.unwrap_or(PlayerState::Idle);
+                        PlayerCommandOutput::StateChanged(actual_state)
                     });
                 }
             }
             PlayerInput::LoadMediaWithContext { media_id, context } => {
-                self.media_item_id = Some(media_id.clone());
-                self.player_state = PlayerState::Loading;
-
-                // Reset scrubber UI to prevent showing previous video's position
-                self.seek_bar_manager.reset();
-
-                // Update navigation state based on context
-                self.can_go_previous = context.has_previous();
-                self.can_go_next = context.has_next();
-
-                // Update playlist position label
-                self.update_playlist_position_label(&context);
-
-                self.playlist_context = Some(context);
-                // Clear any existing error and reset retry state
-                self.error_retry_manager.clear_error();
-                // Reset auto-play state
-                self.auto_play_manager.cancel();
-                // Clear skip button state
-                self.skip_marker_manager.clear_markers();
-
-                // Load marker data from database and fetch from backend if missing
-                let db_clone_for_markers = self.db.clone();
-                let media_id_for_markers = media_id.clone();
-                let sender_for_markers = sender.clone();
-                glib::spawn_future_local(async move {
-                    use crate::db::repository::{MediaRepository, MediaRepositoryImpl, Repository};
-                    use crate::models::{MediaItem, MediaItemId};
-                    use crate::services::core::backend::BackendService;
-
-                    let media_repo =
-                        MediaRepositoryImpl::new(db_clone_for_markers.as_ref().clone());
-                    if let Ok(Some(mut db_media)) =
-                        media_repo.find_by_id(media_id_for_markers.as_ref()).await
+                self.load_item(media_id, Some(context), StreamChoice::Automatic, &sender)
+                    .await;
+            }
+            PlayerInput::SessionEvent(event) => match event {
+                SessionEvent::Suspending | SessionEvent::Locked => {
+                    // Pausing releases the suspend delay taken while playing
+                    if matches!(self.player_state, PlayerState::Playing) {
+                        info!("Pausing playback: {:?}", event);
+                        self.session.flush();
+                        self.paused_by_session = true;
+                        sender.input(PlayerInput::PlayPause);
+                    }
+                }
+                SessionEvent::Unlocked => {
+                    if std::mem::take(&mut self.paused_by_session)
+                        && self.resume_after_unlock
+                        && matches!(self.player_state, PlayerState::Paused)
                     {
-                        // Check if markers are missing in database
-                        let markers_missing = db_media.intro_marker_start_ms.is_none()
-                            && db_media.credits_marker_start_ms.is_none();
-
-                        if markers_missing {
-                            // Fetch markers from backend
-                            let media_id_typed = MediaItemId::new(media_id_for_markers.to_string());
-                            match BackendService::fetch_markers(
-                                &db_clone_for_markers,
-                                &media_id_typed,
-                            )
-                            .await
-                            {
-                                Ok((intro_marker, credits_marker)) => {
-                                    // Store markers in database
-                                    let intro_tuple =
-                                        intro_marker.as_ref().map(|(start, end)| (*start, *end));
-                                    let credits_tuple =
-                                        credits_marker.as_ref().map(|(start, end)| (*start, *end));
-
-                                    if let Err(e) = media_repo
-                                        .update_markers(
-                                            media_id_for_markers.as_ref(),
-                                            intro_tuple,
-                                            credits_tuple,
-                                        )
-                                        .await
-                                    {
-                                        tracing::warn!(
-                                            "Failed to store markers in database: {}",
-                                            e
-                                        );
-                                    } else {
-                                        // Update local db_media with the new markers
-                                        if let Some((start, end)) = intro_tuple {
-                                            db_media.intro_marker_start_ms = Some(start);
-                                            db_media.intro_marker_end_ms = Some(end);
-                                        }
-                                        if let Some((start, end)) = credits_tuple {
-                                            db_media.credits_marker_start_ms = Some(start);
-                                            db_media.credits_marker_end_ms = Some(end);
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    tracing::debug!(
-                                        "Could not fetch markers from backend: {} (this is normal if markers aren't available)",
-                                        e
-                                    );
-                                }
-                            }
-                        }
-
-                        // Convert to domain model to get markers
-                        if let Ok(media_item) = MediaItem::try_from(db_media) {
-                            match media_item {
-                                MediaItem::Movie(movie) => {
-                                    sender_for_markers.input(PlayerInput::LoadedMarkers {
-                                        intro: movie.intro_marker,
-                                        credits: movie.credits_marker,
-                                    });
-                                }
-                                MediaItem::Episode(episode) => {
-                                    sender_for_markers.input(PlayerInput::LoadedMarkers {
-                                        intro: episode.intro_marker,
-                                        credits: episode.credits_marker,
-                                    });
-                                }
-                                _ => {}
-                            }
-                        }
+                        info!("Resuming playback after unlock");
+                        sender.input(PlayerInput::PlayPause);
                     }
+                }
+                SessionEvent::Resumed => {}
+            },
+            PlayerInput::MediaKey(key) => {
+                let now = Instant::now();
+                let repeated = self.last_media_key.is_some_and(|(last, at)| {
+                    last == key && now.duration_since(at) < media_keys::REPEAT_WINDOW
                 });
-
-                // Get actual media URL from backend using GetStreamUrlCommand
-                let db_clone = self.db.clone();
-                let media_id_clone = media_id.clone();
-                let media_id_for_resume = media_id_clone.clone();
-                let sender_clone = sender.clone();
-                // Capture progress tracker config values for use in async closure
-                let auto_resume = self.progress_tracker.get_auto_resume();
-                let resume_threshold_seconds = self.progress_tracker.get_resume_threshold_seconds();
-
-                if let Some(player) = &self.player {
-                    let player_handle = player.clone();
-                    sender.oneshot_command(async move {
-                        use crate::ui::shared::commands::{
-                            AppCommand, CommandResult, execute_command,
-                        };
-
-                        // Use the proper StartPlayback command which includes cache integration
-                        let command_result = execute_command(
-                            AppCommand::StartPlayback {
-                                media_id: media_id_clone.to_string(),
-                            },
-                            &db_clone,
-                        )
-                        .await;
-
-                        let stream_url = match command_result {
-                            CommandResult::PlaybackStarted { url, .. } => url,
-                            CommandResult::Error(e) => {
-                                error!("Failed to start playback: {}", e);
-                                return PlayerCommandOutput::LoadError(format!(
-                                    "Failed to load media: {}",
-                                    e
-                                ));
-                            }
-                        };
-
-                        info!("Got stream URL (potentially cached): {}", stream_url);
-
-                        // Load the media into the player using channel-based API
-                        match player_handle.load_media(&stream_url).await {
-                            Ok(_) => {
-                                info!("Media loaded successfully with playlist context");
-
-                                // Populate track menus after media loads
-                                sender_clone.input(PlayerInput::UpdateTrackMenus);
-
-                                // Wait for player backend to be ready for seeking operations
-                                info!("Waiting for player to be ready for seeking...");
-                                if let Err(e) = player_handle
-                                    .wait_until_ready(std::time::Duration::from_secs(5))
-                                    .await
-                                {
-                                    warn!("Player not ready after timeout: {}", e);
-                                }
-
-                                // Check for saved playback progress and resume if configured
-                                use crate::services::commands::GetPlaybackProgressCommand;
-
-                                // Use cached config values
-                                if auto_resume {
-                                    // TODO: Restore PlayQueue state when loading without context
-                                    // This requires restructuring to avoid Send/Sync issues with backend.as_any()
-
-                                    // Get saved progress
-                                    if let Ok(Some(progress)) = (GetPlaybackProgressCommand {
-                                        db: db_clone.as_ref().clone(),
-                                        media_id: media_id_for_resume.clone(),
-                                        user_id: "default".to_string(), // TODO: Get actual user ID
-                                    })
-                                    .execute()
-                                    .await
-                                    {
-                                        // Only resume if:
-                                        // 1. Position is above threshold (e.g., 5 seconds)
-                                        // 2. Progress is less than 95% (not near completion)
-                                        // 3. Media is not marked as watched
-                                        let threshold_ms = (resume_threshold_seconds as i64) * 1000;
-                                        let progress_percentage =
-                                            progress.get_progress_percentage();
-
-                                        if progress.position_ms > threshold_ms
-                                            && progress_percentage < 0.95
-                                            && !progress.watched
-                                        {
-                                            let resume_position = std::time::Duration::from_millis(
-                                                progress.position_ms as u64,
-                                            );
-                                            info!(
-                                                "Resuming playback from {:?} ({:.1}% complete)",
-                                                resume_position,
-                                                progress_percentage * 100.0
-                                            );
-
-                                            // Seek to saved position (player is now ready)
-                                            info!("Player ready, seeking to saved position");
-                                            if let Err(e) =
-                                                player_handle.seek(resume_position).await
-                                            {
-                                                error!("Failed to seek to saved position: {}", e);
-                                            }
-                                        }
-                                    }
-                                }
-
-                                // Try to get video dimensions and calculate appropriate window size
-                                if let Ok(Some((width, height))) =
-                                    player_handle.get_video_dimensions().await
-                                    && width > 0
-                                    && height > 0
-                                {
-                                    // Calculate window size based on video aspect ratio
-                                    // Keep width reasonable (max 1920) and scale height accordingly
-                                    let max_width = 1920.0_f32.min(width as f32);
-                                    let scale = max_width / width as f32;
-                                    let window_width = max_width as i32;
-                                    let window_height = (height as f32 * scale) as i32;
-
-                                    // Add some padding for controls
-                                    let final_height = window_height + 100; // Extra space for controls
-
-                                    info!(
-                                        "Video dimensions: {}x{}, window size: {}x{}",
-                                        width, height, window_width, final_height
-                                    );
-
-                                    // Request window resize through output
-                                    sender_clone
-                                        .output(PlayerOutput::WindowStateChanged {
-                                            width: window_width,
-                                            height: final_height,
-                                        })
-                                        .ok();
-                                }
-
-                                // Start playback automatically after loading
-                                if let Err(e) = player_handle.play().await {
-                                    warn!("Failed to auto-start playback: {}", e);
-                                }
-
-                                // Get the actual state from the player after loading and playing
-                                let actual_state =
-                                    player_handle.get_state().await.unwrap_or(PlayerState::Idle);
-                                PlayerCommandOutput::StateChanged(actual_state)
-                            }
-                            Err(e) => {
-                                error!("Failed to load media: {}", e);
-                                PlayerCommandOutput::LoadError(format!("Playback error: {}", e))
+                // Media keys only control playback the player is showing
+                if root.is_mapped() && !repeated {
+                    self.last_media_key = Some((key, now));
+                    match key {
+                        MediaKey::PlayPause => sender.input(PlayerInput::PlayPause),
+                        MediaKey::Pause => {
+                            if matches!(self.player_state, PlayerState::Playing) {
+                                sender.input(PlayerInput::PlayPause);
                             }
                         }
-                    });
+                        MediaKey::Next => sender.input(PlayerInput::Next),
+                        MediaKey::Previous => sender.input(PlayerInput::Previous),
+                    }
                 }
             }
             PlayerInput::PlayPause => {
@@ -1567,29 +1955,8 @@ impl AsyncComponent for PlayerPage {
             }
             PlayerInput::Stop => {
                 // Save current progress before stopping
-                if let Some(media_id) = &self.media_item_id {
-                    let db = (*self.db).clone();
-                    let media_id = media_id.clone();
-                    let position_ms = self.position.as_millis() as i64;
-                    let duration_ms = self.duration.as_millis() as i64;
-                    let watched = position_ms as f64 / duration_ms as f64 > 0.9;
-
-                    relm4::spawn(async move {
-                        use crate::services::commands::{Command, UpdatePlaybackProgressCommand};
-
-                        let command = UpdatePlaybackProgressCommand {
-                            db,
-                            media_id,
-                            position_ms,
-                            duration_ms,
-                            watched,
-                        };
-
-                        if let Err(e) = command.execute().await {
-                            debug!("Failed to save final playback progress: {}", e);
-                        }
-                    });
-                }
+                self.session.flush();
+                self.end_stream();
 
                 if let Some(player) = &self.player {
                     let player_handle = player.clone();
@@ -1607,8 +1974,24 @@ impl AsyncComponent for PlayerPage {
             PlayerInput::Seek(position) => {
                 if let Some(player) = &self.player {
                     let player_handle = player.clone();
+                    let plan = self.session.seek(position);
                     sender.oneshot_command(async move {
-                        player_handle.seek(position).await.ok();
+                        match plan {
+                            SeekPlan::InStream(position) => {
+                                player_handle.seek(position).await.ok();
+                            }
+                            SeekPlan::Restart { stop, url } => {
+                                stop.send().await;
+                                if let Err(e) = player_handle.load_media(&url).await {
+                                    error!("Failed to restart the transcode: {}", e);
+                                }
+                                player_handle
+                                    .wait_until_ready(Duration::from_secs(5))
+                                    .await
+                                    .ok();
+                                player_handle.play().await.ok();
+                            }
+                        }
                         // Get the actual state from the player after seeking
                         let actual_state = player_handle
                             .get_state()
@@ -1655,7 +2038,7 @@ impl AsyncComponent for PlayerPage {
                 self.is_fullscreen = !self.is_fullscreen;
                 debug!("Toggling fullscreen to: {}", self.is_fullscreen);
                 if self.is_fullscreen {
-                    self.window.fullscreen();
+                    self.enter_fullscreen();
                     // In fullscreen mode: transition to visible state
                     self.transition_to_visible(sender.clone());
                 } else {
@@ -1757,6 +2140,7 @@ impl AsyncComponent for PlayerPage {
             PlayerInput::Rewind => {
                 // Seek backward 10 seconds
                 let new_position = self.position.saturating_sub(Duration::from_secs(10));
+                self.seek_bar_manager.announce_position(new_position);
                 sender.input(PlayerInput::Seek(new_position));
             }
             PlayerInput::Forward => {
@@ -1768,6 +2152,7 @@ impl AsyncComponent for PlayerPage {
                 } else {
                     new_position
                 };
+                self.seek_bar_manager.announce_position(final_position);
                 sender.input(PlayerInput::Seek(final_position));
             }
             PlayerInput::SeekRelative(seconds) => {
@@ -1792,42 +2177,73 @@ impl AsyncComponent for PlayerPage {
                     self.position.saturating_sub(duration)
                 };
 
+                self.seek_bar_manager.announce_position(new_position);
+                self.osd_flash
+                    .show_seek(new_position, self.duration, seconds);
                 sender.input(PlayerInput::Seek(new_position));
             }
             PlayerInput::SpeedUp => {
                 // Increase playback speed by 10%
-                self.playback_speed = (self.playback_speed * 1.1).min(4.0); // Cap at 4x speed
-                if let Some(player) = &self.player {
-                    let player_handle = player.clone();
-                    let speed = self.playback_speed;
-                    sender.oneshot_command(async move {
-                        player_handle.set_playback_speed(speed).await.ok();
-                        PlayerCommandOutput::StateChanged(PlayerState::Playing)
-                    });
-                }
+                self.set_playback_speed(self.playback_speed * 1.1);
+                self.osd_flash.show_speed(self.playback_speed);
             }
             PlayerInput::SpeedDown => {
                 // Decrease playback speed by 10%
-                self.playback_speed = (self.playback_speed * 0.9).max(0.25); // Min 0.25x speed
-                if let Some(player) = &self.player {
-                    let player_handle = player.clone();
-                    let speed = self.playback_speed;
-                    sender.oneshot_command(async move {
-                        player_handle.set_playback_speed(speed).await.ok();
-                        PlayerCommandOutput::StateChanged(PlayerState::Playing)
+                self.set_playback_speed(self.playback_speed * 0.9);
+                self.osd_flash.show_speed(self.playback_speed);
+            }
+            PlayerInput::SpeedReset => {
+                self.set_playback_speed(1.0);
+                self.osd_flash.show_speed(self.playback_speed);
+            }
+            PlayerInput::SetPlaybackSpeed(speed) => {
+                self.set_playback_speed(speed);
+                self.osd_flash.show_speed(self.playback_speed);
+            }
+            PlayerInput::DefaultSpeedLoaded(speed) => {
+                self.set_playback_speed(speed);
+            }
+            PlayerInput::ApplyDefaultSpeed => {
+                if let Some(media_id) = self.session.media_id().cloned() {
+                    let db = self.db.clone();
+                    let speeds = self.speed_config;
+                    let sender = sender.clone();
+                    glib::spawn_future_local(async move {
+                        use crate::db::repository::{MediaRepositoryImpl, Repository};
+
+                        let media_type = MediaRepositoryImpl::new(db.as_ref().clone())
+                            .find_by_id(media_id.as_ref())
+                            .await
+                            .ok()
+                            .flatten()
+                            .map(|item| item.media_type)
+                            .unwrap_or_default();
+                        sender.input(PlayerInput::DefaultSpeedLoaded(
+                            speeds.default_for(&media_type),
+                        ));
                     });
                 }
             }
-            PlayerInput::SpeedReset => {
-                // Reset playback speed to normal
-                self.playback_speed = 1.0;
+            PlayerInput::SetPitchCorrection(enabled) => {
+                if self.speed_config.pitch_correction == enabled {
+                    return;
+                }
+                self.speed_config.pitch_correction = enabled;
                 if let Some(player) = &self.player {
                     let player_handle = player.clone();
-                    sender.oneshot_command(async move {
-                        player_handle.set_playback_speed(1.0).await.ok();
-                        PlayerCommandOutput::StateChanged(PlayerState::Playing)
+                    glib::spawn_future_local(async move {
+                        if let Err(err) = player_handle.set_pitch_correction(enabled).await {
+                            warn!("Failed to set pitch correction: {}", err);
+                        }
                     });
                 }
+
+                let speeds = self.speed_config;
+                relm4::spawn(async move {
+                    if let Err(err) = CONFIG_SERVICE.set_speed_settings(speeds).await {
+                        error!("Failed to save pitch correction: {}", err);
+                    }
+                });
             }
             PlayerInput::FrameStepForward => {
                 // Step one frame forward (while paused)
@@ -1853,8 +2269,100 @@ impl AsyncComponent for PlayerPage {
                     });
                 }
             }
+            PlayerInput::SubtitleScaleUp => {
+                self.set_subtitle_scale(self.subtitle_scale + Self::SUBTITLE_SCALE_STEP, &sender);
+            }
+            PlayerInput::SubtitleScaleDown => {
+                self.set_subtitle_scale(self.subtitle_scale - Self::SUBTITLE_SCALE_STEP, &sender);
+            }
+            PlayerInput::SubtitleScaleReset => {
+                self.set_subtitle_scale(1.0, &sender);
+            }
+            PlayerInput::LoadSubtitleFile => {
+                let filter = gtk::FileFilter::new();
+                filter.set_name(Some("Subtitle Files"));
+                for extension in Self::SUBTITLE_FILE_EXTENSIONS {
+                    filter.add_suffix(extension);
+                }
+                let filters = gtk::gio::ListStore::new::<gtk::FileFilter>();
+                filters.append(&filter);
+
+                let dialog = gtk::FileDialog::builder()
+                    .title("Load Subtitle File")
+                    .modal(true)
+                    .filters(&filters)
+                    .default_filter(&filter)
+                    .build();
+                let sender = sender.clone();
+                dialog.open(
+                    Some(&self.window),
+                    None::<&gtk::gio::Cancellable>,
+                    move |result| {
+                        if let Ok(file) = result
+                            && let Some(path) = file.path()
+                        {
+                            sender.input(PlayerInput::SubtitleFileChosen(path));
+                        }
+                    },
+                );
+            }
+            PlayerInput::SubtitleFileChosen(path) => {
+                if let Some(player) = &self.player {
+                    let player_handle = player.clone();
+                    let db = self.db.clone();
+                    let media_id = self.session.media_id().cloned();
+                    let subtitle_menu_button = self.subtitle_menu_button.clone();
+                    let sender = sender.clone();
+                    glib::spawn_future_local(async move {
+                        let path = path.to_string_lossy().into_owned();
+                        if let Err(e) = player_handle.add_subtitle_file(&path).await {
+                            warn!("Failed to load subtitle file {}: {}", path, e);
+                            accessibility::announce(
+                                &subtitle_menu_button,
+                                "Could not load the subtitle file",
+                            );
+                            return;
+                        }
+                        if let Some(media_id) = media_id
+                            && let Err(e) = TrackPreferencesService::remember_subtitle_file(
+                                &db, &media_id, &path,
+                            )
+                            .await
+                        {
+                            warn!("Failed to remember subtitle file: {}", e);
+                        }
+                        sender.input(PlayerInput::RefreshSubtitleMenu);
+                    });
+                }
+            }
+            PlayerInput::RefreshSubtitleMenu => {
+                self.populate_subtitle_menu(sender.clone());
+            }
+            PlayerInput::SetSubtitleCharset(charset) => {
+                if self.subtitle_config.charset == charset {
+                    return;
+                }
+                self.subtitle_config.charset = charset.clone();
+                if let Some(player) = &self.player {
+                    let player_handle = player.clone();
+                    let charset = charset.clone();
+                    glib::spawn_future_local(async move {
+                        if let Err(err) = player_handle.set_subtitle_charset(&charset).await {
+                            warn!("Failed to set subtitle encoding: {}", err);
+                        }
+                    });
+                }
+
+                relm4::spawn(async move {
+                    if let Err(err) = CONFIG_SERVICE.set_subtitle_charset(charset).await {
+                        error!("Failed to save subtitle encoding: {}", err);
+                    }
+                });
+            }
             PlayerInput::ToggleMute => {
                 // Toggle mute state
+                let muted = self.volume_manager.toggle_muted();
+                self.osd_flash.show_mute(muted);
                 if let Some(player) = &self.player {
                     let player_handle = player.clone();
                     sender.oneshot_command(async move {
@@ -1866,19 +2374,39 @@ impl AsyncComponent for PlayerPage {
             PlayerInput::VolumeUp => {
                 // Increase volume by 10%
                 let new_volume = self.volume_manager.volume_up();
+                self.osd_flash.show_volume(new_volume);
                 sender.input(PlayerInput::SetVolume(new_volume));
             }
             PlayerInput::VolumeDown => {
                 // Decrease volume by 10%
                 let new_volume = self.volume_manager.volume_down();
+                self.osd_flash.show_volume(new_volume);
                 sender.input(PlayerInput::SetVolume(new_volume));
             }
             PlayerInput::CycleSubtitleTrack => {
                 // Cycle through available subtitle tracks
                 if let Some(player) = &self.player {
                     let player_handle = player.clone();
+                    let db = self.db.clone();
+                    let media_id = self.session.media_id().cloned();
                     sender.oneshot_command(async move {
                         player_handle.cycle_subtitle_track().await.ok();
+                        if let (Some(media_id), Ok(track_id)) =
+                            (media_id, player_handle.get_current_subtitle_track().await)
+                        {
+                            let tracks = player_handle
+                                .get_subtitle_tracks()
+                                .await
+                                .unwrap_or_default();
+                            remember_track_choice(
+                                &db,
+                                &media_id,
+                                TrackKind::Subtitle,
+                                &tracks,
+                                track_id,
+                            )
+                            .await;
+                        }
                         PlayerCommandOutput::StateChanged(PlayerState::Playing)
                     });
                 }
@@ -1887,8 +2415,23 @@ impl AsyncComponent for PlayerPage {
                 // Cycle through available audio tracks
                 if let Some(player) = &self.player {
                     let player_handle = player.clone();
+                    let db = self.db.clone();
+                    let media_id = self.session.media_id().cloned();
                     sender.oneshot_command(async move {
                         player_handle.cycle_audio_track().await.ok();
+                        if let (Some(media_id), Ok(track_id)) =
+                            (media_id, player_handle.get_current_audio_track().await)
+                        {
+                            let tracks = player_handle.get_audio_tracks().await.unwrap_or_default();
+                            remember_track_choice(
+                                &db,
+                                &media_id,
+                                TrackKind::Audio,
+                                &tracks,
+                                track_id,
+                            )
+                            .await;
+                        }
                         PlayerCommandOutput::StateChanged(PlayerState::Playing)
                     });
                 }
@@ -1899,19 +2442,25 @@ impl AsyncComponent for PlayerPage {
                 self.populate_subtitle_menu(sender.clone());
                 self.populate_quality_menu(sender.clone());
                 self.populate_zoom_menu(sender.clone());
-
-                // Also get current track selections
-                if let Some(player) = &self.player {
+            }
+            PlayerInput::RestoreTrackChoices => {
+                // Select the tracks picked last time this item or show played
+                if let (Some(player), Some(media_id)) = (&self.player, self.session.media_id()) {
                     let player_handle = player.clone();
-                    let _sender_clone = sender.clone();
+                    let db = self.db.clone();
+                    let media_id = media_id.clone();
+                    let forced_language = self
+                        .subtitle_config
+                        .auto_forced
+                        .then(desktop_language)
+                        .flatten();
+                    let sender = sender.clone();
                     glib::spawn_future_local(async move {
-                        let _audio_track =
-                            player_handle.get_current_audio_track().await.unwrap_or(-1);
-                        let _subtitle_track = player_handle
-                            .get_current_subtitle_track()
+                        if restore_track_choices(&player_handle, &db, &media_id, forced_language)
                             .await
-                            .unwrap_or(-1);
-                        // Store the current tracks (we'll update the model in a moment)
+                        {
+                            sender.input(PlayerInput::RefreshSubtitleMenu);
+                        }
                     });
                 }
             }
@@ -1919,8 +2468,21 @@ impl AsyncComponent for PlayerPage {
                 if let Some(player) = &self.player {
                     self.current_audio_track = Some(track_id);
                     let player_handle = player.clone();
+                    let db = self.db.clone();
+                    let media_id = self.session.media_id().cloned();
                     sender.oneshot_command(async move {
                         let _ = player_handle.set_audio_track(track_id).await;
+                        if let Some(media_id) = media_id {
+                            let tracks = player_handle.get_audio_tracks().await.unwrap_or_default();
+                            remember_track_choice(
+                                &db,
+                                &media_id,
+                                TrackKind::Audio,
+                                &tracks,
+                                track_id,
+                            )
+                            .await;
+                        }
                         PlayerCommandOutput::StateChanged(PlayerState::Playing)
                     });
                 }
@@ -1929,12 +2491,98 @@ impl AsyncComponent for PlayerPage {
                 if let Some(player) = &self.player {
                     self.current_subtitle_track = Some(track_id);
                     let player_handle = player.clone();
+                    let db = self.db.clone();
+                    let media_id = self.session.media_id().cloned();
                     sender.oneshot_command(async move {
                         let _ = player_handle.set_subtitle_track(track_id).await;
+                        if let Some(media_id) = media_id {
+                            let tracks = player_handle
+                                .get_subtitle_tracks()
+                                .await
+                                .unwrap_or_default();
+                            remember_track_choice(
+                                &db,
+                                &media_id,
+                                TrackKind::Subtitle,
+                                &tracks,
+                                track_id,
+                            )
+                            .await;
+                        }
                         PlayerCommandOutput::StateChanged(PlayerState::Playing)
                     });
                 }
             }
+            PlayerInput::ToggleStats => {
+                self.stats_visible = !self.stats_visible;
+            }
+            PlayerInput::ResolveStatus(status) => {
+                self.buffering_overlay
+                    .emit(BufferingOverlayInput::SetStatus(Some(
+                        status.message().to_string(),
+                    )));
+            }
+            PlayerInput::StreamStarted { stream, generation } => {
+                if generation == self.stream_generation {
+                    self.buffering_overlay
+                        .emit(BufferingOverlayInput::SetStatus(None));
+                    self.session.stream_started(&stream.url, stream.duration);
+                    self.start_seek_thumbnails(&stream);
+                    self.stream_refresh.stream_started(stream.server_url);
+                    self.stream_decision = Some(stream.decision);
+                    self.start_keep_alive(&sender);
+                } else {
+                    debug!("Ignoring the stream of a load that was replaced");
+                }
+            }
+            PlayerInput::SeekThumbnails(output) => match output {
+                ThumbnailWorkerOutput::SeekThumbnailsReady {
+                    media_id,
+                    thumbnails,
+                } => {
+                    if self.seek_thumbnails_for.as_deref() == Some(media_id.as_str()) {
+                        self.seek_bar_manager.set_thumbnails(&thumbnails);
+                    }
+                }
+                ThumbnailWorkerOutput::Failed { media_id, error } => {
+                    debug!("No seek thumbnails for {}: {}", media_id, error);
+                }
+            },
+            PlayerInput::StreamProbed { expired, error } => {
+                if self.stream_refresh.probed(expired) {
+                    info!("The server no longer accepts the stream URL, resolving it again");
+                    self.refresh_stream(&sender);
+                } else {
+                    sender.input(PlayerInput::ShowError(error));
+                }
+            }
+            PlayerInput::KeepSessionAlive => {
+                if let Some(request) = self.session.keep_transcode_alive() {
+                    glib::spawn_future_local(request.send());
+                }
+                if let Some(report) = self.session.keep_session_alive() {
+                    glib::spawn_future_local(report.send());
+                }
+            }
+            PlayerInput::CloseWindow => {
+                self.session.flush();
+                // Sent in place, the main loop ends with the window
+                let report = self.session.end_session();
+                let transcode = self.session.end_transcode();
+                let ended = glib::future_with_timeout(Self::SESSION_END_TIMEOUT, async move {
+                    if let Some(report) = report {
+                        report.send().await;
+                    }
+                    if let Some(transcode) = transcode {
+                        transcode.send().await;
+                    }
+                });
+                if ended.await.is_err() {
+                    warn!("Server didn't confirm the end of playback before closing");
+                }
+                self.sessions_ended.set(true);
+                self.window.close();
+            }
             PlayerInput::ToggleControlsVisibility => {
                 // Toggle between Hidden and Visible states
                 match self.control_state {
@@ -1967,6 +2615,26 @@ impl AsyncComponent for PlayerPage {
                 // Trigger visibility check
                 sender.input(PlayerInput::UpdateSkipButtonsVisibility);
             }
+            PlayerInput::LoadedSkipModes(modes) => {
+                self.skip_marker_manager.load_show_modes(modes);
+            }
+            PlayerInput::LoadedChapters { chapters, duration } => {
+                self.skip_marker_manager.load_chapters(&chapters, duration);
+                self.session
+                    .chapters_loaded(chapters.iter().map(|chapter| chapter.start).collect());
+                sender.input(PlayerInput::UpdateSkipButtonsVisibility);
+            }
+            PlayerInput::LoadedItemInfo(info) => {
+                self.pause_info.set_info(Some(&info));
+            }
+            PlayerInput::CycleDurationDisplay => {
+                let display = self.seek_bar_manager.cycle_duration_display();
+                relm4::spawn(async move {
+                    if let Err(err) = CONFIG_SERVICE.set_duration_display(display).await {
+                        error!("Failed to save duration display: {}", err);
+                    }
+                });
+            }
             PlayerInput::BrokerMsg(msg) => {
                 match msg {
                     BrokerMessage::Config(ConfigMessage::Updated { config }) => {
@@ -1975,6 +2643,9 @@ impl AsyncComponent for PlayerPage {
                     BrokerMessage::Config(ConfigMessage::PlayerBackendChanged { backend }) => {
                         self.ensure_backend_alignment(&backend, &sender).await;
                     }
+                    BrokerMessage::Power(PowerMessage::SavingChanged { .. }) => {
+                        self.apply_power_saving();
+                    }
                     _ => {
                         // Ignore other broker messages
                     }
@@ -1982,20 +2653,74 @@ impl AsyncComponent for PlayerPage {
             }
             PlayerInput::RetryLoad => {
                 // Retry loading the media with exponential backoff
-                if let Some(media_id) = &self.media_item_id {
+                if let Some(media_id) = self.session.media_id() {
                     self.error_retry_manager.schedule_retry(
                         media_id.clone(),
-                        self.playlist_context.clone(),
+                        self.session.context().cloned(),
                         &sender,
                     );
                 }
             }
+            PlayerInput::Recover => {
+                let Some(media_id) = self.session.media_id().cloned() else {
+                    return;
+                };
+                let context = self.session.context().cloned();
+                match self.error_recovery() {
+                    Some(Recovery::SignIn) => {
+                        let db = self.db.clone();
+                        let output_sender = sender.clone();
+                        relm4::spawn(async move {
+                            match source_of(&db, &media_id).await {
+                                Ok(Some(source)) => {
+                                    output_sender
+                                        .output(PlayerOutput::Reauthenticate {
+                                            source_id: SourceId::new(source.id),
+                                            source_name: source.name,
+                                            source_type: source.source_type,
+                                        })
+                                        .ok();
+                                }
+                                Ok(None) => {}
+                                Err(e) => {
+                                    error!("Failed to find the source of {}: {}", media_id, e)
+                                }
+                            }
+                        });
+                        sender.input(PlayerInput::NavigateBack);
+                    }
+                    Some(Recovery::PlayTranscoded) => {
+                        self.load_item(media_id, context, StreamChoice::Transcoded, &sender)
+                            .await;
+                    }
+                    Some(Recovery::PlayOriginal) => {
+                        self.load_item(media_id, context, StreamChoice::Original, &sender)
+                            .await;
+                    }
+                    Some(Recovery::Retry) => sender.input(PlayerInput::RetryLoad),
+                    None => {}
+                }
+            }
             PlayerInput::ClearError => {
                 self.error_retry_manager.clear_error();
             }
-            PlayerInput::ShowError(msg) => {
-                error!("Player error: {}", msg);
-                self.error_retry_manager.show_error(msg);
+            PlayerInput::ShowError(error) => {
+                // Streams whose URL expired are resolved again rather than
+                // failing, the server is asked first
+                match self.stream_refresh.failed() {
+                    StreamFailure::Probe(url) => {
+                        let sender = sender.clone();
+                        glib::spawn_future_local(async move {
+                            let expired = stream_refresh::expired(&url).await;
+                            sender.input(PlayerInput::StreamProbed { expired, error });
+                        });
+                        return;
+                    }
+                    StreamFailure::Wait => return,
+                    StreamFailure::Show => {}
+                }
+                error!("Player error: {}", error);
+                self.error_retry_manager.show_error(error);
                 self.player_state = PlayerState::Error;
             }
             PlayerInput::EscapePressed => {
@@ -2033,8 +2758,8 @@ impl AsyncComponent for PlayerPage {
                     surface.set_cursor(Some(&cursor));
                 }
 
-                // Navigate back
-                sender.output(PlayerOutput::NavigateBack).unwrap();
+                // The main window may already be closing, nothing to go back to then
+                sender.output(PlayerOutput::NavigateBack).ok();
             }
             PlayerInput::ClearAutoPlayTimeout => {
                 // Clear the auto-play timeout reference without trying to remove it
@@ -2110,6 +2835,23 @@ impl AsyncComponent for PlayerPage {
             PlayerInput::UpdateQualityMenu => {
                 self.populate_quality_menu(sender.clone());
             }
+            PlayerInput::SetFullscreenMonitor(choice) => {
+                let value = choice.to_config();
+                self.fullscreen_monitor = choice;
+                relm4::spawn(async move {
+                    if let Err(e) = CONFIG_SERVICE.set_fullscreen_monitor(value).await {
+                        error!("Failed to save fullscreen monitor: {}", e);
+                    }
+                });
+                // Move over right away when already fullscreen
+                if self.is_fullscreen {
+                    self.enter_fullscreen();
+                }
+                self.populate_monitor_menu(sender.clone());
+            }
+            PlayerInput::UpdateMonitorMenu => {
+                self.populate_monitor_menu(sender.clone());
+            }
         }
     }
 
@@ -2122,6 +2864,11 @@ impl AsyncComponent for PlayerPage {
         match message {
             PlayerCommandOutput::StateChanged(state) => {
                 self.player_state = state.clone();
+                // Only a pause by suspend or lock that is still in effect
+                // resumes on unlock
+                if !matches!(&state, PlayerState::Paused) {
+                    self.paused_by_session = false;
+                }
                 // Clear error on successful state change
                 if !matches!(&state, PlayerState::Error) {
                     self.error_retry_manager.reset();
@@ -2141,94 +2888,54 @@ impl AsyncComponent for PlayerPage {
                     self.sleep_inhibitor.release(&self.window);
                 }
 
-                // Send immediate timeline update on play, pause, or stop state changes
-                if matches!(
-                    &state,
-                    PlayerState::Playing | PlayerState::Paused | PlayerState::Stopped
-                ) {
-                    if let (Some(media_id), Some(player)) = (&self.media_item_id, &self.player) {
-                        let player_handle = player.clone();
-                        let media_id_clone = media_id.clone();
-                        let state_clone = state.clone();
-                        let db_clone = (*self.db).clone();
-                        let context_clone = self.playlist_context.clone();
-
-                        glib::spawn_future_local(async move {
-                            // Get current position and duration
-                            if let Ok(Some(position)) = player_handle.get_position().await
-                                && let Ok(Some(duration)) = player_handle.get_duration().await
-                            {
-                                use crate::db::repository::source_repository::SourceRepositoryImpl;
-                                use crate::db::repository::{MediaRepositoryImpl, Repository};
-                                use crate::services::core::BackendService;
-                                use crate::services::core::playqueue::PlayQueueService;
-
-                                // Map player state to Plex state
-                                let plex_state = match state_clone {
-                                    PlayerState::Playing => "playing",
-                                    PlayerState::Paused => "paused",
-                                    PlayerState::Stopped => "stopped",
-                                    _ => return,
-                                };
-
-                                // If we have a PlayQueue context, sync with server
-                                if let Some(ref context) = context_clone
-                                    && let Some(_queue_info) = context.get_play_queue_info()
-                                {
-                                    let media_repo = MediaRepositoryImpl::new(db_clone.clone());
-                                    if let Ok(Some(media)) =
-                                        media_repo.find_by_id(media_id_clone.as_ref()).await
-                                    {
-                                        let source_repo =
-                                            SourceRepositoryImpl::new(db_clone.clone());
-                                        if let Ok(Some(source)) =
-                                            source_repo.find_by_id(&media.source_id).await
-                                        {
-                                            if let Ok(backend) =
-                                                BackendService::create_backend_for_source(
-                                                    &db_clone, &source,
-                                                )
-                                                .await
-                                            {
-                                                let _ =
-                                                    PlayQueueService::update_progress_with_queue(
-                                                        backend.as_any(),
-                                                        &context,
-                                                        &media_id_clone,
-                                                        position,
-                                                        duration,
-                                                        plex_state,
-                                                    )
-                                                    .await;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        });
-                    }
+                if matches!(&state, PlayerState::Paused) {
+                    self.pause_info
+                        .update_time(self.position, self.duration, self.playback_speed);
+                    self.pause_info
+                        .set_chapters(self.session.chapter_summary().as_deref());
+                    self.pause_info.paused();
+                } else {
+                    self.pause_info.resumed();
                 }
-            }
-            PlayerCommandOutput::LoadError(error_msg) => {
-                // Send toast notification for immediate feedback
-                sender
-                    .output(PlayerOutput::ShowToast(error_msg.clone()))
-                    .unwrap();
 
-                // Show error in overlay
-                sender.input(PlayerInput::ShowError(error_msg.clone()));
+                // Audiobooks and podcasts jump back a little after a long pause
+                if matches!(&state, PlayerState::Playing)
+                    && let Some(target) = self.session.snap_back()
+                {
+                    sender.input(PlayerInput::Seek(target));
+                }
 
-                // Navigate back after a small delay to ensure navigation stack is ready
-                let sender_clone = sender.clone();
-                glib::timeout_add_local_once(std::time::Duration::from_millis(500), move || {
-                    sender_clone.output(PlayerOutput::NavigateBack).unwrap();
-                });
+                let paused = matches!(&state, PlayerState::Paused)
+                    .then(|| self.session.media_id().cloned())
+                    .flatten();
+                sender.output(PlayerOutput::Paused(paused)).ok();
+                sender
+                    .output(PlayerOutput::Playing(matches!(
+                        &state,
+                        PlayerState::Playing | PlayerState::Loading
+                    )))
+                    .ok();
+
+                // Progress is written on pause and stop, and the server
+                // told about play, pause and stop
+                if let Some(report) = self.session.state_changed(&state).await {
+                    glib::spawn_future_local(report.send());
+                }
+            }
+            PlayerCommandOutput::LoadError(error) => {
+                self.buffering_overlay
+                    .emit(BufferingOverlayInput::SetStatus(None));
+                // The overlay offers the way out, the page stays until it is taken
+                sender.input(PlayerInput::ShowError(error));
             }
             PlayerCommandOutput::PositionUpdate {
                 position,
                 duration,
                 state,
             } => {
+                // Transcodes report positions in their playlist
+                let position = position.map(|pos| self.session.item_position(pos));
+                let duration = duration.map(|dur| self.session.item_duration(dur));
                 if let Some(pos) = position {
                     self.position = pos;
                     // Update position label and seek bar (only if not being dragged)
@@ -2237,44 +2944,15 @@ impl AsyncComponent for PlayerPage {
                     // Check skip button visibility based on position
                     sender.input(PlayerInput::UpdateSkipButtonsVisibility);
 
-                    // Save playback progress to database at configured interval
-                    if let (Some(media_id), Some(dur)) = (&self.media_item_id, duration) {
-                        // Check for auto-play when episode is nearly complete (>95%)
-                        self.auto_play_manager.check_auto_play(
-                            pos,
-                            dur,
-                            &self.playlist_context,
-                            &sender,
-                        );
-
-                        // Check if progress should be saved
-                        if self.progress_tracker.should_save_progress(pos, dur) {
-                            self.progress_tracker.reset_save_timer();
-
-                            let db = (*self.db).clone();
-                            let media_id = media_id.clone();
-                            let position_ms = pos.as_millis() as i64;
-                            let duration_ms = dur.as_millis() as i64;
-                            let watched = pos.as_secs_f64() / dur.as_secs_f64() > 0.9;
-
-                            relm4::spawn(async move {
-                                use crate::services::commands::{
-                                    Command, UpdatePlaybackProgressCommand,
-                                };
-
-                                let command = UpdatePlaybackProgressCommand {
-                                    db,
-                                    media_id,
-                                    position_ms,
-                                    duration_ms,
-                                    watched,
-                                };
-
-                                if let Err(e) = command.execute().await {
-                                    debug!("Failed to save playback progress: {}", e);
-                                }
-                            });
-                        }
+                    // Progress is written at the configured interval, and
+                    // the end of the item may start auto-play
+                    if let Some(dur) = duration
+                        && let Some(ending) = self.session.position_changed(pos, dur).await
+                    {
+                        self.auto_play_manager.schedule(ending, &sender);
+                    }
+                    if let Some(report) = self.session.session_update() {
+                        glib::spawn_future_local(report.send());
                     }
                 }
                 if let Some(dur) = duration {
@@ -2282,17 +2960,23 @@ impl AsyncComponent for PlayerPage {
                     // Update duration label and seek bar range
                     self.seek_bar_manager.update_duration(dur);
                 }
+                self.pause_info
+                    .update_time(self.position, self.duration, self.playback_speed);
+                // Players that only tell of a failing stream through their
+                // state fail it here, a load in progress reports its own
+                if matches!(state, PlayerState::Error)
+                    && !matches!(self.player_state, PlayerState::Error | PlayerState::Loading)
+                {
+                    sender.input(PlayerInput::ShowError(PlaybackError::Other(
+                        "Playback of the stream failed".to_string(),
+                    )));
+                }
                 self.player_state = state;
             }
         }
     }
 
     fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
-        // Unsubscribe from MessageBroker
-        relm4::spawn(async move {
-            BROKER.unsubscribe("PlayerPage").await;
-        });
-
         // Restore cursor visibility when player is destroyed
         if let Some(surface) = self.window.surface()
             && let Some(cursor) = gtk::gdk::Cursor::from_name("default", None)
@@ -2316,6 +3000,7 @@ impl AsyncComponent for PlayerPage {
         if let Some(timer) = self.window_event_debounce.take() {
             let _ = timer.remove();
         }
+        self.stop_keep_alive();
 
         // Retry timer cleanup is handled by ErrorRetryManager's Drop implementation
         // Auto-play timeout cleanup is handled by AutoPlayManager's Drop implementation
@@ -2326,3 +3011,115 @@ impl AsyncComponent for PlayerPage {
         tracing::debug!("PlayerPage shutdown: restored cursor and cleaned up timers");
     }
 }
+
+/// Record the item playing as the window closes. The main loop is shutting
+/// down, so the configuration is written directly.
+fn remember_interrupted_playback(media_id: &MediaItemId) {
+    match Config::load() {
+        Ok(mut config) => {
+            config.ui.interrupted_playback = Some(media_id.to_string());
+            if let Err(e) = config.save() {
+                error!("Failed to remember interrupted playback: {}", e);
+            }
+        }
+        Err(e) => error!("Failed to load config to remember playback: {}", e),
+    }
+}
+
+/// Remember a track picked for the playing item, matched by name on later
+/// plays of the item and of the other episodes of its show
+async fn remember_track_choice(
+    db: &crate::db::connection::DatabaseConnection,
+    media_id: &MediaItemId,
+    kind: TrackKind,
+    tracks: &[(i32, String)],
+    track_id: i32,
+) {
+    let Some((_, name)) = tracks.iter().find(|(id, _)| *id == track_id) else {
+        return;
+    };
+    if let Err(e) = TrackPreferencesService::remember(db, media_id, kind, name).await {
+        warn!("Failed to remember track choice: {}", e);
+    }
+}
+
+/// Language of the desktop session, such as "en_US.UTF-8"
+fn desktop_language() -> Option<String> {
+    glib::language_names()
+        .into_iter()
+        .map(|name| name.to_string())
+        .find(|name| name != "C" && name != "POSIX")
+}
+
+/// Select the tracks remembered for an item, or else for its show. Without a
+/// remembered subtitle choice, a forced subtitle track is shown when the
+/// audio is in `forced_language`. Returns whether a remembered subtitle file
+/// was loaded.
+async fn restore_track_choices(
+    player: &PlayerHandle,
+    db: &crate::db::connection::DatabaseConnection,
+    media_id: &MediaItemId,
+    forced_language: Option<String>,
+) -> bool {
+    let preferred = match TrackPreferencesService::preferred_tracks(db, media_id).await {
+        Ok(preferred) => preferred,
+        Err(e) => {
+            warn!("Failed to load remembered tracks: {}", e);
+            return false;
+        }
+    };
+
+    // The file's track has to exist before a remembered name can match it
+    let mut subtitle_file_added = false;
+    if let Some(path) = preferred
+        .subtitle_file
+        .filter(|path| std::path::Path::new(path).exists())
+    {
+        debug!("Loading remembered subtitle file {}", path);
+        match player.add_subtitle_file(&path).await {
+            Ok(()) => subtitle_file_added = true,
+            Err(e) => warn!("Failed to load remembered subtitle file: {}", e),
+        }
+    }
+
+    if let Some(name) = preferred.audio {
+        let tracks = player.get_audio_tracks().await.unwrap_or_default();
+        if let Some(track_id) = TrackPreferencesService::find_track(&tracks, &name) {
+            debug!("Restoring audio track {}", name);
+            let _ = player.set_audio_track(track_id).await;
+        }
+    }
+    if let Some(name) = preferred.subtitle {
+        let tracks = player.get_subtitle_tracks().await.unwrap_or_default();
+        if let Some(track_id) = TrackPreferencesService::find_track(&tracks, &name) {
+            debug!("Restoring subtitle track {}", name);
+            let _ = player.set_subtitle_track(track_id).await;
+        }
+    } else if let Some(language) = forced_language
+        && let Ok(languages) = player.get_track_languages().await
+        && let Some(track_id) =
+            TrackPreferencesService::forced_subtitle_track(&languages, &language)
+    {
+        debug!("Showing forced subtitle track {}", track_id);
+        let _ = player.set_subtitle_track(track_id).await;
+    }
+    subtitle_file_added
+}
+
+/// The source an item is played from
+async fn source_of(
+    db: &crate::db::connection::DatabaseConnection,
+    media_id: &MediaItemId,
+) -> anyhow::Result<Option<crate::db::entities::SourceModel>> {
+    use crate::db::repository::{MediaRepositoryImpl, Repository, SourceRepositoryImpl};
+
+    let Some(media) = MediaRepositoryImpl::new(db.clone())
+        .find_by_id(media_id.as_ref())
+        .await?
+    else {
+        return Ok(None);
+    };
+    SourceRepositoryImpl::new(db.clone())
+        .find_by_id(&media.source_id)
+        .await
+}
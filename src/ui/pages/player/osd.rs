@@ -0,0 +1,148 @@
+use gtk::glib::{self, SourceId};
+use gtk::prelude::*;
+use relm4::gtk;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use super::format_duration;
+
+/// How long a flash stays on screen after the last action
+const FLASH_DURATION: Duration = Duration::from_millis(1200);
+
+/// Short on-screen feedback for volume, mute, speed and seek actions, shown
+/// in the corner of the video even while the controls are hidden
+pub struct OsdFlash {
+    container: gtk::Box,
+    icon: gtk::Image,
+    label: gtk::Label,
+    hide_timer: Rc<RefCell<Option<SourceId>>>,
+}
+
+impl OsdFlash {
+    pub fn new() -> Self {
+        let container = gtk::Box::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .spacing(8)
+            .halign(gtk::Align::Start)
+            .valign(gtk::Align::Start)
+            .margin_top(64)
+            .margin_start(20)
+            .visible(false)
+            .can_target(false)
+            .build();
+        container.add_css_class("osd");
+        container.add_css_class("osd-flash");
+
+        let icon = gtk::Image::new();
+        let label = gtk::Label::new(None);
+        label.add_css_class("numeric");
+        container.append(&icon);
+        container.append(&label);
+
+        Self {
+            container,
+            icon,
+            label,
+            hide_timer: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    pub fn widget(&self) -> &gtk::Box {
+        &self.container
+    }
+
+    pub fn show_volume(&self, volume: f64) {
+        let icon = if volume <= 0.0 {
+            "audio-volume-muted-symbolic"
+        } else if volume < 0.34 {
+            "audio-volume-low-symbolic"
+        } else if volume < 0.67 {
+            "audio-volume-medium-symbolic"
+        } else {
+            "audio-volume-high-symbolic"
+        };
+        self.show(icon, &format!("Volume {:.0}%", volume * 100.0));
+    }
+
+    pub fn show_mute(&self, muted: bool) {
+        if muted {
+            self.show("audio-volume-muted-symbolic", "Muted");
+        } else {
+            self.show("audio-volume-high-symbolic", "Unmuted");
+        }
+    }
+
+    pub fn show_speed(&self, speed: f64) {
+        self.show("media-playback-start-symbolic", &speed_text(speed));
+    }
+
+    /// The position seeked to and how far it moved
+    pub fn show_seek(&self, position: Duration, duration: Duration, delta_secs: i64) {
+        let icon = if delta_secs < 0 {
+            "media-seek-backward-symbolic"
+        } else {
+            "media-seek-forward-symbolic"
+        };
+        self.show(icon, &seek_text(position, duration, delta_secs));
+    }
+
+    fn show(&self, icon: &str, text: &str) {
+        self.icon.set_icon_name(Some(icon));
+        self.label.set_label(text);
+        self.container.set_visible(true);
+
+        // Each new action keeps the flash up for the full duration again
+        if let Some(timer) = self.hide_timer.borrow_mut().take() {
+            timer.remove();
+        }
+        let container = self.container.clone();
+        let hide_timer = self.hide_timer.clone();
+        let timer = glib::timeout_add_local_once(FLASH_DURATION, move || {
+            hide_timer.borrow_mut().take();
+            container.set_visible(false);
+        });
+        self.hide_timer.replace(Some(timer));
+    }
+}
+
+impl Drop for OsdFlash {
+    fn drop(&mut self) {
+        if let Some(timer) = self.hide_timer.borrow_mut().take() {
+            timer.remove();
+        }
+    }
+}
+
+fn speed_text(speed: f64) -> String {
+    format!("Speed {:.2}×", speed)
+}
+
+fn seek_text(position: Duration, duration: Duration, delta_secs: i64) -> String {
+    let sign = if delta_secs < 0 { '-' } else { '+' };
+    format!(
+        "{} / {} ({}{}s)",
+        format_duration(position),
+        format_duration(duration),
+        sign,
+        delta_secs.unsigned_abs()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seek_text_shows_position_and_delta() {
+        assert_eq!(
+            seek_text(Duration::from_secs(75), Duration::from_secs(3600), 10),
+            "1:15 / 1:00:00 (+10s)"
+        );
+        assert_eq!(
+            seek_text(Duration::from_secs(0), Duration::from_secs(90), -5),
+            "0:00 / 1:30 (-5s)"
+        );
+        assert_eq!(speed_text(1.5), "Speed 1.50×");
+    }
+}
@@ -0,0 +1,263 @@
+use anyhow::Result;
+use gtk::glib::{self, SourceId};
+use gtk::prelude::*;
+use relm4::gtk;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use super::{ends_at, time_left};
+use crate::db::connection::DatabaseConnection;
+use crate::models::{MediaItem, MediaItemId, Person};
+use crate::services::core::MediaService;
+
+/// How long playback stays paused before the overlay fades in, so a quick
+/// pause doesn't cover the frame
+const SHOW_DELAY: Duration = Duration::from_secs(4);
+
+/// Cast members named on the overlay
+const CAST_SHOWN: usize = 4;
+
+/// What the overlay tells about the paused item
+#[derive(Debug, Clone, Default)]
+pub struct ItemInfo {
+    /// Show, season and episode of an episode
+    heading: Option<String>,
+    title: String,
+    overview: Option<String>,
+    cast: Vec<String>,
+}
+
+impl ItemInfo {
+    /// Information on `item`, with the cast of its show for an episode
+    fn new(item: &MediaItem, show: Option<&MediaItem>) -> Self {
+        let cast: &[Person] = match (item, show) {
+            (MediaItem::Movie(movie), _) => &movie.cast,
+            (MediaItem::Show(show), _) | (_, Some(MediaItem::Show(show))) => &show.cast,
+            _ => &[],
+        };
+        let cast = cast
+            .iter()
+            .take(CAST_SHOWN)
+            .map(|person| person.name.clone())
+            .collect();
+
+        match item {
+            MediaItem::Movie(movie) => Self {
+                heading: movie.year.map(|year| year.to_string()),
+                title: movie.title.clone(),
+                overview: movie.overview.clone(),
+                cast,
+            },
+            MediaItem::Episode(episode) => Self {
+                heading: Some(match &episode.show_title {
+                    Some(show) => format!(
+                        "{} · Season {}, Episode {}",
+                        show, episode.season_number, episode.episode_number
+                    ),
+                    None => format!(
+                        "Season {}, Episode {}",
+                        episode.season_number, episode.episode_number
+                    ),
+                }),
+                title: episode.title.clone(),
+                overview: episode.overview.clone(),
+                cast,
+            },
+            MediaItem::Show(show) => Self {
+                heading: None,
+                title: show.title.clone(),
+                overview: show.overview.clone(),
+                cast,
+            },
+            other => Self {
+                title: other.title().to_string(),
+                ..Self::default()
+            },
+        }
+    }
+}
+
+/// Load what the overlay shows for `media_id`
+pub async fn load_item_info(db: &DatabaseConnection, media_id: &MediaItemId) -> Result<ItemInfo> {
+    let item = MediaService::get_item_details(db, media_id).await?;
+    let show = match &item {
+        MediaItem::Episode(episode) => match &episode.show_id {
+            Some(show_id) => MediaService::get_media_item(db, &MediaItemId::new(show_id)).await?,
+            None => None,
+        },
+        _ => None,
+    };
+    Ok(ItemInfo::new(&item, show.as_ref()))
+}
+
+/// Title, synopsis, cast and time left of the item, faded in over a dimmed
+/// frame once playback has been paused for a few seconds
+pub struct PauseInfo {
+    container: gtk::Box,
+    heading: gtk::Label,
+    title: gtk::Label,
+    time: gtk::Label,
+    chapters: gtk::Label,
+    overview: gtk::Label,
+    cast: gtk::Label,
+    show_timer: Rc<RefCell<Option<SourceId>>>,
+}
+
+impl PauseInfo {
+    pub fn new() -> Self {
+        let container = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .valign(gtk::Align::Fill)
+            .halign(gtk::Align::Fill)
+            .visible(false)
+            .can_target(false)
+            .build();
+        container.add_css_class("pause-info");
+
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(8)
+            .halign(gtk::Align::Start)
+            .valign(gtk::Align::Center)
+            .vexpand(true)
+            .margin_start(64)
+            .margin_end(64)
+            .build();
+
+        let label = |css_class: &str| {
+            let label = gtk::Label::builder()
+                .halign(gtk::Align::Start)
+                .xalign(0.0)
+                .wrap(true)
+                .max_width_chars(60)
+                .build();
+            label.add_css_class(css_class);
+            content.append(&label);
+            label
+        };
+        let heading = label("pause-info-heading");
+        let title = label("pause-info-title");
+        let time = label("pause-info-time");
+        time.add_css_class("numeric");
+        let chapters = label("pause-info-chapters");
+        chapters.set_visible(false);
+        let overview = label("pause-info-overview");
+        overview.set_lines(5);
+        overview.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        let cast = label("pause-info-cast");
+        container.append(&content);
+
+        Self {
+            container,
+            heading,
+            title,
+            time,
+            chapters,
+            overview,
+            cast,
+            show_timer: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    pub fn widget(&self) -> &gtk::Box {
+        &self.container
+    }
+
+    /// Show `info` for the loaded item, None while it loads
+    pub fn set_info(&self, info: Option<&ItemInfo>) {
+        let info = info.cloned().unwrap_or_default();
+        set_text(&self.heading, info.heading.as_deref());
+        set_text(
+            &self.title,
+            Some(&info.title).filter(|title| !title.is_empty()),
+        );
+        set_text(&self.overview, info.overview.as_deref());
+        let cast = (!info.cast.is_empty()).then(|| format!("With {}", info.cast.join(", ")));
+        set_text(&self.cast, cast.as_deref());
+    }
+
+    /// Time left at `speed` and when the item ends, kept current while
+    /// paused since the end moves with the clock
+    pub fn update_time(&self, position: Duration, duration: Duration, speed: f64) {
+        if duration.is_zero() {
+            self.time.set_visible(false);
+            return;
+        }
+        let left = time_left(position, duration, speed);
+        self.time.set_label(&format!(
+            "{} · Ends at {}",
+            time_left_text(left),
+            ends_at(left)
+        ));
+        self.time.set_visible(true);
+    }
+
+    /// Where an audiobook or podcast is among its chapters, None for other
+    /// items
+    pub fn set_chapters(&self, summary: Option<&str>) {
+        set_text(&self.chapters, summary);
+    }
+
+    /// Fade the overlay in after playback stays paused for a moment
+    pub fn paused(&self) {
+        if self.container.is_visible() || self.show_timer.borrow().is_some() {
+            return;
+        }
+        let container = self.container.clone();
+        let show_timer = self.show_timer.clone();
+        let timer = glib::timeout_add_local_once(SHOW_DELAY, move || {
+            show_timer.borrow_mut().take();
+            container.set_visible(true);
+        });
+        self.show_timer.replace(Some(timer));
+    }
+
+    /// Hide the overlay as soon as playback resumes or stops
+    pub fn resumed(&self) {
+        if let Some(timer) = self.show_timer.borrow_mut().take() {
+            timer.remove();
+        }
+        self.container.set_visible(false);
+    }
+}
+
+impl Drop for PauseInfo {
+    fn drop(&mut self) {
+        if let Some(timer) = self.show_timer.borrow_mut().take() {
+            timer.remove();
+        }
+    }
+}
+
+fn set_text(label: &gtk::Label, text: Option<&str>) {
+    label.set_label(text.unwrap_or_default());
+    label.set_visible(text.is_some());
+}
+
+fn time_left_text(left: Duration) -> String {
+    let minutes = left.as_secs().div_ceil(60);
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "Ending".to_string(),
+        (0, minutes) => format!("{} min left", minutes),
+        (hours, 0) => format!("{} h left", hours),
+        (hours, minutes) => format!("{} h {} min left", hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_left_rounds_up_to_whole_minutes() {
+        assert_eq!(time_left_text(Duration::ZERO), "Ending");
+        assert_eq!(time_left_text(Duration::from_secs(20)), "1 min left");
+        assert_eq!(time_left_text(Duration::from_secs(42 * 60)), "42 min left");
+        assert_eq!(time_left_text(Duration::from_secs(2 * 3600)), "2 h left");
+        assert_eq!(
+            time_left_text(Duration::from_secs(3600 + 12 * 60 + 1)),
+            "1 h 13 min left"
+        );
+    }
+}
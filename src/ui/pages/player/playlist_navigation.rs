@@ -52,7 +52,7 @@ impl PlayerPage {
     pub(super) fn handle_previous_navigation(&self, sender: &AsyncComponentSender<Self>) {
         debug!("Previous track requested");
 
-        if let Some(ref context) = self.playlist_context {
+        if let Some(context) = self.session.context() {
             if let Some(prev_id) = context.get_previous_item() {
                 // Keep the context and just load the previous media
                 let mut new_context = context.clone();
@@ -74,7 +74,7 @@ impl PlayerPage {
     pub(super) fn handle_next_navigation(&self, sender: &AsyncComponentSender<Self>) {
         debug!("Next track requested");
 
-        if let Some(ref context) = self.playlist_context {
+        if let Some(context) = self.session.context() {
             if let Some(next_id) = context.get_next_item() {
                 // Keep the context and just load the next media
                 let mut new_context = context.clone();
@@ -1,9 +1,22 @@
 use gtk::prelude::*;
 use relm4::AsyncComponentSender;
 use relm4::gtk;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
 use std::time::Duration;
 
-use super::{PlayerInput, PlayerPage, format_duration};
+use super::{PlayerInput, PlayerPage, ends_at, format_duration, time_left};
+use crate::config::DurationDisplay;
+use crate::ui::shared::accessibility;
+use crate::workers::ImageLoader;
+use crate::workers::thumbnail_worker::SEEK_THUMBNAIL_SIZE;
+
+/// Cached image of the seek thumbnail last taken at or before `position`
+fn thumbnail_at(thumbnails: &[(Duration, PathBuf)], position: Duration) -> Option<&PathBuf> {
+    let index = thumbnails.partition_point(|(at, _)| *at <= position);
+    index.checked_sub(1).map(|index| &thumbnails[index].1)
+}
 
 /// Manages seek bar widget and position/duration display.
 /// Handles click and drag gestures for seeking, tooltip preview,
@@ -12,19 +25,33 @@ pub struct SeekBarManager {
     seek_bar: gtk::Scale,
     position_label: gtk::Label,
     duration_label: gtk::Label,
+    position: Duration,
+    duration: Duration,
+    speed: f64,
+    // Total, remaining or end time, cycled by clicking the duration label
+    duration_display: DurationDisplay,
     is_seeking: bool,
+    // Position and cached image of each seek thumbnail, local files only
+    thumbnails: Rc<RefCell<Vec<(Duration, PathBuf)>>>,
 }
 
 impl SeekBarManager {
     /// Create new SeekBarManager with initialized widgets and gesture handlers
-    pub fn new(sender: &AsyncComponentSender<PlayerPage>) -> Self {
+    pub fn new(
+        sender: &AsyncComponentSender<PlayerPage>,
+        duration_display: DurationDisplay,
+    ) -> Self {
         // Create seek bar widget
         let seek_bar = gtk::Scale::with_range(gtk::Orientation::Horizontal, 0.0, 100.0, 1.0);
         seek_bar.set_draw_value(false);
         seek_bar.set_has_tooltip(true);
+        seek_bar.update_property(&[gtk::accessible::Property::Label("Playback Position")]);
 
-        // Add tooltip to show time at cursor position
-        seek_bar.connect_query_tooltip(|scale, x, _y, _keyboard_mode, tooltip| {
+        // Add tooltip to show time at cursor position, with the frame there
+        // when there are seek thumbnails
+        let thumbnails: Rc<RefCell<Vec<(Duration, PathBuf)>>> = Rc::default();
+        let thumbnails_clone = thumbnails.clone();
+        seek_bar.connect_query_tooltip(move |scale, x, _y, _keyboard_mode, tooltip| {
             let adjustment = scale.adjustment();
             // x is already relative to the widget
             let width = scale.width() as f64;
@@ -33,7 +60,19 @@ impl SeekBarManager {
             let value = ratio * max;
             // Clamp value to ensure it's non-negative
             let duration = Duration::from_secs_f64(value.max(0.0));
-            tooltip.set_text(Some(&format_duration(duration)));
+            let thumbnails = thumbnails_clone.borrow();
+            match thumbnail_at(&thumbnails, duration) {
+                Some(path) => {
+                    let preview = gtk::Box::new(gtk::Orientation::Vertical, 6);
+                    let (width, height) = SEEK_THUMBNAIL_SIZE.dimensions();
+                    let picture = gtk::Picture::for_filename(path);
+                    picture.set_size_request(width as i32 / 2, height as i32 / 2);
+                    preview.append(&picture);
+                    preview.append(&gtk::Label::new(Some(&format_duration(duration))));
+                    tooltip.set_custom(Some(&preview));
+                }
+                None => tooltip.set_text(Some(&format_duration(duration))),
+            }
             true
         });
 
@@ -41,6 +80,18 @@ impl SeekBarManager {
         let position_label = gtk::Label::new(Some("0:00"));
         let duration_label = gtk::Label::new(Some("0:00"));
 
+        // Clicking the duration label switches what it shows
+        duration_label.set_cursor_from_name(Some("pointer"));
+        let display_click = gtk::GestureClick::new();
+        display_click.set_button(gtk::gdk::BUTTON_PRIMARY);
+        {
+            let sender = sender.clone();
+            display_click.connect_released(move |_gesture, _n_press, _x, _y| {
+                sender.input(PlayerInput::CycleDurationDisplay);
+            });
+        }
+        duration_label.add_controller(display_click);
+
         // Setup seek bar gesture handlers - handle clicks and drags for video seeking
         {
             let sender_start = sender.clone();
@@ -99,14 +150,31 @@ impl SeekBarManager {
             });
 
             seek_bar.add_controller(drag_gesture);
+
+            // Arrow keys and page keys on the focused seek bar; pointer drags
+            // are handled by the gestures above and arrive as jumps
+            let sender_keys = sender.clone();
+            seek_bar.connect_change_value(move |_scale, scroll, value| {
+                if scroll != gtk::ScrollType::Jump {
+                    sender_keys.input(PlayerInput::Seek(Duration::from_secs_f64(value.max(0.0))));
+                }
+                gtk::glib::Propagation::Proceed
+            });
         }
 
-        Self {
+        let manager = Self {
             seek_bar,
             position_label,
             duration_label,
+            position: Duration::ZERO,
+            duration: Duration::ZERO,
+            speed: 1.0,
+            duration_display,
             is_seeking: false,
-        }
+            thumbnails,
+        };
+        manager.update_duration_label();
+        manager
     }
 
     /// Get reference to seek bar widget
@@ -127,31 +195,131 @@ impl SeekBarManager {
     /// Update position display and seek bar value
     /// Only updates seek bar if not currently seeking (prevents flicker during drag)
     pub fn update_position(&mut self, position: Duration) {
+        self.position = position;
         self.position_label.set_text(&format_duration(position));
+        if self.duration_display != DurationDisplay::Total {
+            self.update_duration_label();
+        }
 
         // Only update seek bar position if we're not actively seeking
         // This prevents the bar from jumping around during drag operations
         if !self.is_seeking {
             self.seek_bar.set_value(position.as_secs_f64());
         }
+        self.seek_bar
+            .update_property(&[gtk::accessible::Property::ValueText(
+                &self.position_text(position),
+            )]);
+    }
+
+    /// Read the new position out after a keyboard seek
+    pub fn announce_position(&self, position: Duration) {
+        accessibility::announce(&self.seek_bar, &self.position_text(position));
+    }
+
+    fn position_text(&self, position: Duration) -> String {
+        format!(
+            "{} of {}",
+            format_duration(position),
+            format_duration(self.duration)
+        )
     }
 
     /// Update duration display and seek bar range
-    pub fn update_duration(&self, duration: Duration) {
-        self.duration_label.set_text(&format_duration(duration));
+    pub fn update_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+        self.update_duration_label();
         self.seek_bar.set_range(0.0, duration.as_secs_f64());
     }
 
+    /// Playback speed the remaining and end times are counted at
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = speed;
+        self.update_duration_label();
+    }
+
+    /// Switch the duration label to the next display, returning it so it
+    /// can be remembered
+    pub fn cycle_duration_display(&mut self) -> DurationDisplay {
+        self.duration_display = self.duration_display.next();
+        self.update_duration_label();
+        self.duration_display
+    }
+
+    fn update_duration_label(&self) {
+        let (text, tooltip) = if self.duration.is_zero() {
+            ("--:--".to_string(), "Duration")
+        } else {
+            let left = time_left(self.position, self.duration, self.speed);
+            match self.duration_display {
+                DurationDisplay::Total => (format_duration(self.duration), "Duration"),
+                DurationDisplay::Remaining => (format!("-{}", format_duration(left)), "Time Left"),
+                DurationDisplay::EndsAt => (format!("Ends {}", ends_at(left)), "Ends At"),
+            }
+        };
+        self.duration_label.set_text(&text);
+        self.duration_label.set_tooltip_text(Some(tooltip));
+    }
+
     /// Set seeking state (true when user is dragging seek bar)
     pub fn set_seeking(&mut self, seeking: bool) {
         self.is_seeking = seeking;
     }
 
+    /// Show frames from `thumbnails`, by position and image URL, when
+    /// hovering the seek bar
+    pub fn set_thumbnails(&self, thumbnails: &[(Duration, String)]) {
+        *self.thumbnails.borrow_mut() = thumbnails
+            .iter()
+            .map(|(position, url)| {
+                (
+                    *position,
+                    ImageLoader::get_cache_path(url, &SEEK_THUMBNAIL_SIZE),
+                )
+            })
+            .collect();
+    }
+
     /// Reset to initial state (0:00 position, unknown duration)
     pub fn reset(&mut self) {
         self.seek_bar.set_value(0.0);
         self.position_label.set_text("0:00");
-        self.duration_label.set_text("--:--");
+        self.position = Duration::ZERO;
+        self.duration = Duration::ZERO;
+        self.update_duration_label();
         self.is_seeking = false;
+        self.thumbnails.borrow_mut().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_at_takes_the_last_frame_before_the_position() {
+        let thumbnails: Vec<(Duration, PathBuf)> = [0, 10, 20]
+            .into_iter()
+            .map(|secs| {
+                (
+                    Duration::from_secs(secs),
+                    PathBuf::from(format!("{secs}.jpg")),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            thumbnail_at(&thumbnails, Duration::from_secs(15)),
+            Some(&PathBuf::from("10.jpg"))
+        );
+        assert_eq!(
+            thumbnail_at(&thumbnails, Duration::from_secs(20)),
+            Some(&PathBuf::from("20.jpg"))
+        );
+        assert_eq!(
+            thumbnail_at(&thumbnails, Duration::from_secs(90)),
+            Some(&PathBuf::from("20.jpg"))
+        );
+        assert_eq!(thumbnail_at(&[], Duration::from_secs(5)), None);
     }
 }
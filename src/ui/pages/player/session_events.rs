@@ -0,0 +1,163 @@
+use gtk4::prelude::*;
+use gtk4::{gio, glib};
+use std::cell::{Cell, RefCell};
+use std::os::fd::OwnedFd;
+use std::rc::Rc;
+use tracing::debug;
+
+const LOGIN1: &str = "org.freedesktop.login1";
+const LOGIN1_MANAGER_PATH: &str = "/org/freedesktop/login1";
+const LOGIN1_MANAGER: &str = "org.freedesktop.login1.Manager";
+const LOGIN1_SESSION_PATH: &str = "/org/freedesktop/login1/session/auto";
+const LOGIN1_SESSION: &str = "org.freedesktop.login1.Session";
+
+/// Suspend and screen lock transitions reported by logind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// The system is about to suspend
+    Suspending,
+    /// The system woke up from suspend
+    Resumed,
+    /// The session's screen was locked
+    Locked,
+    /// The session's screen was unlocked
+    Unlocked,
+}
+
+/// Delivers logind's suspend and screen lock transitions until dropped
+pub struct SessionMonitor {
+    // Signals only arrive while their proxy is alive
+    #[allow(dead_code)]
+    proxies: Rc<RefCell<Vec<gio::DBusProxy>>>,
+}
+
+impl SessionMonitor {
+    /// Start following the system's suspend and the session's lock state
+    pub fn start(on_event: impl Fn(SessionEvent) + Clone + 'static) -> Self {
+        let proxies = Rc::new(RefCell::new(Vec::new()));
+
+        let on_sleep = on_event.clone();
+        let manager_proxies = proxies.clone();
+        gio::DBusProxy::for_bus(
+            gio::BusType::System,
+            gio::DBusProxyFlags::DO_NOT_LOAD_PROPERTIES | gio::DBusProxyFlags::DO_NOT_AUTO_START,
+            None,
+            LOGIN1,
+            LOGIN1_MANAGER_PATH,
+            LOGIN1_MANAGER,
+            None::<&gio::Cancellable>,
+            move |result| {
+                let proxy = match result {
+                    Ok(proxy) => proxy,
+                    Err(e) => {
+                        debug!("logind unavailable, not following suspend: {}", e);
+                        return;
+                    }
+                };
+                proxy.connect_local("g-signal", false, move |values| {
+                    let signal = values[2].get::<String>().ok()?;
+                    let parameters = values[3].get::<glib::Variant>().ok()?;
+                    if signal == "PrepareForSleep"
+                        && let Some((sleeping,)) = parameters.get::<(bool,)>()
+                    {
+                        on_sleep(if sleeping {
+                            SessionEvent::Suspending
+                        } else {
+                            SessionEvent::Resumed
+                        });
+                    }
+                    None
+                });
+                manager_proxies.borrow_mut().push(proxy);
+            },
+        );
+
+        let session_proxies = proxies.clone();
+        gio::DBusProxy::for_bus(
+            gio::BusType::System,
+            gio::DBusProxyFlags::DO_NOT_AUTO_START,
+            None,
+            LOGIN1,
+            LOGIN1_SESSION_PATH,
+            LOGIN1_SESSION,
+            None::<&gio::Cancellable>,
+            move |result| {
+                let proxy = match result {
+                    Ok(proxy) => proxy,
+                    Err(e) => {
+                        debug!("logind unavailable, not following screen lock: {}", e);
+                        return;
+                    }
+                };
+                let locked_hint = |proxy: &gio::DBusProxy| {
+                    proxy
+                        .cached_property("LockedHint")
+                        .and_then(|value| value.get::<bool>())
+                        .unwrap_or(false)
+                };
+                // Screen lockers set the hint, property changes only tell
+                // whether it flipped
+                let locked = Cell::new(locked_hint(&proxy));
+                proxy.connect_local("g-properties-changed", false, move |values| {
+                    let proxy = values[0].get::<gio::DBusProxy>().ok()?;
+                    let now_locked = locked_hint(&proxy);
+                    if locked.replace(now_locked) != now_locked {
+                        on_event(if now_locked {
+                            SessionEvent::Locked
+                        } else {
+                            SessionEvent::Unlocked
+                        });
+                    }
+                    None
+                });
+                session_proxies.borrow_mut().push(proxy);
+            },
+        );
+
+        Self { proxies }
+    }
+}
+
+/// Ask logind to hold off suspending until the returned descriptor is closed,
+/// or at most logind's `InhibitDelayMaxSec`
+pub fn request_suspend_delay(on_granted: impl FnOnce(OwnedFd) + 'static) {
+    gio::bus_get(
+        gio::BusType::System,
+        None::<&gio::Cancellable>,
+        move |connection| {
+            let connection = match connection {
+                Ok(connection) => connection,
+                Err(e) => {
+                    debug!("System bus unavailable, not delaying suspend: {}", e);
+                    return;
+                }
+            };
+            connection.call_with_unix_fd_list(
+                Some(LOGIN1),
+                LOGIN1_MANAGER_PATH,
+                LOGIN1_MANAGER,
+                "Inhibit",
+                Some(&("sleep", "Reel", "Pausing playback", "delay").to_variant()),
+                Some(glib::VariantTy::new("(h)").unwrap()),
+                gio::DBusCallFlags::NONE,
+                -1,
+                None::<&gio::UnixFDList>,
+                None::<&gio::Cancellable>,
+                move |result| {
+                    let fd = result.map_err(|e| e.to_string()).and_then(|(reply, fds)| {
+                        let (index,) = reply
+                            .get::<(glib::variant::Handle,)>()
+                            .ok_or_else(|| "unexpected reply".to_string())?;
+                        fds.ok_or_else(|| "no descriptor in reply".to_string())?
+                            .get(index.0)
+                            .map_err(|e| e.to_string())
+                    });
+                    match fd {
+                        Ok(fd) => on_granted(fd),
+                        Err(e) => debug!("Could not delay suspend: {}", e),
+                    }
+                },
+            );
+        },
+    );
+}
@@ -1,4 +1,6 @@
-use crate::models::ChapterMarker;
+use crate::models::{ChapterMarker, ChapterType};
+use crate::player::Chapter;
+use crate::services::core::{ShowSkipModes, SkipPreferencesService};
 use relm4::AsyncComponentSender;
 use relm4::gtk::glib::{self, SourceId};
 use std::time::Duration;
@@ -6,12 +8,25 @@ use tracing::debug;
 
 use super::PlayerInput;
 
+/// Chapter names that mark an intro, matched case-insensitively
+const INTRO_CHAPTER_NAMES: &[&str] = &["intro", "opening"];
+
+/// Chapter names that mark end credits, matched case-insensitively
+const CREDITS_CHAPTER_NAMES: &[&str] = &["credits", "ending", "outro"];
+
 /// Manages skip intro and skip credits functionality including visibility,
 /// auto-skip behavior, and timer management.
+///
+/// Markers from the server come first. Without them, chapters named like an
+/// intro or credits in the file are used instead. The show of an episode
+/// can override whether its markers are skipped automatically.
 pub struct SkipMarkerManager {
     // Marker data
     intro_marker: Option<ChapterMarker>,
     credits_marker: Option<ChapterMarker>,
+    chapter_intro_marker: Option<ChapterMarker>,
+    chapter_credits_marker: Option<ChapterMarker>,
+    show_modes: ShowSkipModes,
 
     // Visibility state
     skip_intro_visible: bool,
@@ -40,6 +55,9 @@ impl SkipMarkerManager {
         Self {
             intro_marker: None,
             credits_marker: None,
+            chapter_intro_marker: None,
+            chapter_credits_marker: None,
+            show_modes: ShowSkipModes::default(),
             skip_intro_visible: false,
             skip_credits_visible: false,
             skip_intro_hide_timer: None,
@@ -68,10 +86,45 @@ impl SkipMarkerManager {
         self.credits_marker = credits;
     }
 
+    /// Derive markers from the chapters of the loaded media, used when the
+    /// server has none
+    pub fn load_chapters(&mut self, chapters: &[Chapter], duration: Duration) {
+        let (intro, credits) = markers_from_chapters(chapters, duration);
+        if intro.is_some() || credits.is_some() {
+            debug!(
+                "Chapter markers: intro {:?}, credits {:?}",
+                intro.as_ref().map(|m| (m.start_time, m.end_time)),
+                credits.as_ref().map(|m| (m.start_time, m.end_time))
+            );
+        }
+        self.chapter_intro_marker = intro;
+        self.chapter_credits_marker = credits;
+    }
+
+    /// Apply the skip overrides of the playing item's show
+    pub fn load_show_modes(&mut self, modes: ShowSkipModes) {
+        self.show_modes = modes;
+    }
+
+    fn intro(&self) -> Option<&ChapterMarker> {
+        self.intro_marker
+            .as_ref()
+            .or(self.chapter_intro_marker.as_ref())
+    }
+
+    fn credits(&self) -> Option<&ChapterMarker> {
+        self.credits_marker
+            .as_ref()
+            .or(self.chapter_credits_marker.as_ref())
+    }
+
     /// Clear all markers and hide buttons
     pub fn clear_markers(&mut self) {
         self.intro_marker = None;
         self.credits_marker = None;
+        self.chapter_intro_marker = None;
+        self.chapter_credits_marker = None;
+        self.show_modes = ShowSkipModes::default();
         self.skip_intro_visible = false;
         self.skip_credits_visible = false;
 
@@ -98,7 +151,7 @@ impl SkipMarkerManager {
         position: Duration,
         sender: &AsyncComponentSender<super::PlayerPage>,
     ) {
-        if let Some(ref intro) = self.intro_marker {
+        if let Some(intro) = self.intro().cloned() {
             // Check if marker meets minimum duration threshold
             let marker_duration = intro.end_time.as_secs() - intro.start_time.as_secs();
             let meets_threshold = marker_duration >= self.config_minimum_marker_duration_seconds;
@@ -106,9 +159,15 @@ impl SkipMarkerManager {
             // Check if we're in the intro time range
             let in_intro_range = position >= intro.start_time && position < intro.end_time;
 
+            let (auto_skip, show_button) = SkipPreferencesService::behavior(
+                self.show_modes.intro,
+                self.config_auto_skip_intro,
+                self.config_skip_intro_enabled,
+            );
+
             if in_intro_range && meets_threshold {
                 // Auto-skip if configured
-                if self.config_auto_skip_intro {
+                if auto_skip {
                     // Only skip once at the start of the intro
                     if !self.skip_intro_visible
                         && position < intro.start_time + Duration::from_secs(1)
@@ -116,7 +175,7 @@ impl SkipMarkerManager {
                         debug!("Auto-skipping intro");
                         sender.input(PlayerInput::Seek(intro.end_time));
                     }
-                } else if self.config_skip_intro_enabled {
+                } else if show_button {
                     // Show button if enabled
                     let was_visible = self.skip_intro_visible;
                     if !was_visible {
@@ -150,7 +209,7 @@ impl SkipMarkerManager {
         position: Duration,
         sender: &AsyncComponentSender<super::PlayerPage>,
     ) {
-        if let Some(ref credits) = self.credits_marker {
+        if let Some(credits) = self.credits().cloned() {
             // Check if marker meets minimum duration threshold
             let marker_duration = credits.end_time.as_secs() - credits.start_time.as_secs();
             let meets_threshold = marker_duration >= self.config_minimum_marker_duration_seconds;
@@ -158,9 +217,15 @@ impl SkipMarkerManager {
             // Check if we're in the credits time range
             let in_credits_range = position >= credits.start_time && position < credits.end_time;
 
+            let (auto_skip, show_button) = SkipPreferencesService::behavior(
+                self.show_modes.credits,
+                self.config_auto_skip_credits,
+                self.config_skip_credits_enabled,
+            );
+
             if in_credits_range && meets_threshold {
                 // Auto-skip if configured
-                if self.config_auto_skip_credits {
+                if auto_skip {
                     // Only skip once at the start of the credits
                     if !self.skip_credits_visible
                         && position < credits.start_time + Duration::from_secs(1)
@@ -168,7 +233,7 @@ impl SkipMarkerManager {
                         debug!("Auto-skipping credits");
                         sender.input(PlayerInput::Seek(credits.end_time));
                     }
-                } else if self.config_skip_credits_enabled {
+                } else if show_button {
                     // Show button if enabled
                     let was_visible = self.skip_credits_visible;
                     if !was_visible {
@@ -213,7 +278,7 @@ impl SkipMarkerManager {
 
     /// Handle manual skip intro button click
     pub fn skip_intro(&mut self, sender: &AsyncComponentSender<super::PlayerPage>) {
-        if let Some(ref intro) = self.intro_marker {
+        if let Some(intro) = self.intro().cloned() {
             // Seek to the end of the intro marker
             sender.input(PlayerInput::Seek(intro.end_time));
             // Hide the button immediately
@@ -226,7 +291,7 @@ impl SkipMarkerManager {
 
     /// Handle manual skip credits button click
     pub fn skip_credits(&mut self, sender: &AsyncComponentSender<super::PlayerPage>) {
-        if let Some(ref credits) = self.credits_marker {
+        if let Some(credits) = self.credits().cloned() {
             // Seek to the end of the credits marker
             sender.input(PlayerInput::Seek(credits.end_time));
             // Hide the button immediately
@@ -254,6 +319,43 @@ impl SkipMarkerManager {
     }
 }
 
+/// Intro and credits markers from chapter names. A chapter runs until the
+/// next one starts, the last one until the end. The first intro chapter and
+/// the last credits chapter are used.
+fn markers_from_chapters(
+    chapters: &[Chapter],
+    duration: Duration,
+) -> (Option<ChapterMarker>, Option<ChapterMarker>) {
+    let mut intro = None;
+    let mut credits = None;
+
+    for (i, chapter) in chapters.iter().enumerate() {
+        let end_time = chapters
+            .get(i + 1)
+            .map_or(duration, |next| next.start)
+            .max(chapter.start);
+        let title = chapter.title.to_lowercase();
+        let matches = |names: &[&str]| names.iter().any(|name| title.contains(name));
+
+        // "Opening credits" are an intro
+        if matches(INTRO_CHAPTER_NAMES) {
+            intro.get_or_insert(ChapterMarker {
+                start_time: chapter.start,
+                end_time,
+                marker_type: ChapterType::Intro,
+            });
+        } else if matches(CREDITS_CHAPTER_NAMES) {
+            credits = Some(ChapterMarker {
+                start_time: chapter.start,
+                end_time,
+                marker_type: ChapterType::Credits,
+            });
+        }
+    }
+
+    (intro, credits)
+}
+
 impl Drop for SkipMarkerManager {
     fn drop(&mut self) {
         // Clean up timers
@@ -265,3 +367,51 @@ impl Drop for SkipMarkerManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(title: &str, start: u64) -> Chapter {
+        Chapter {
+            title: title.to_string(),
+            start: Duration::from_secs(start),
+        }
+    }
+
+    #[test]
+    fn test_markers_from_chapter_names() {
+        let chapters = [
+            chapter("Recap", 0),
+            chapter("Opening Credits", 60),
+            chapter("Chapter 2", 150),
+            chapter("Ending", 1300),
+            chapter("Preview", 1390),
+        ];
+        let (intro, credits) = markers_from_chapters(&chapters, Duration::from_secs(1420));
+
+        let intro = intro.unwrap();
+        assert_eq!(intro.start_time, Duration::from_secs(60));
+        assert_eq!(intro.end_time, Duration::from_secs(150));
+        let credits = credits.unwrap();
+        assert_eq!(credits.start_time, Duration::from_secs(1300));
+        assert_eq!(credits.end_time, Duration::from_secs(1390));
+    }
+
+    #[test]
+    fn test_last_chapter_runs_to_the_end() {
+        let chapters = [chapter("Chapter 1", 0), chapter("End Credits", 5400)];
+        let (intro, credits) = markers_from_chapters(&chapters, Duration::from_secs(5700));
+
+        assert!(intro.is_none());
+        assert_eq!(credits.unwrap().end_time, Duration::from_secs(5700));
+    }
+
+    #[test]
+    fn test_unnamed_chapters_give_no_markers() {
+        let chapters = [chapter("", 0), chapter("Chapter 2", 600)];
+        let (intro, credits) = markers_from_chapters(&chapters, Duration::from_secs(1200));
+
+        assert!(intro.is_none() && credits.is_none());
+    }
+}
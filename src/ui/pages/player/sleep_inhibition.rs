@@ -1,10 +1,23 @@
 use gtk4::prelude::*;
 use libadwaita as adw;
+use std::cell::RefCell;
+use std::os::fd::{AsRawFd, OwnedFd};
+use std::rc::Rc;
 use tracing::debug;
 
+use super::session_events;
+
+/// logind's delay lock, which gives playback time to pause before suspend
+enum SuspendDelay {
+    Released,
+    Requested,
+    Held(OwnedFd),
+}
+
 /// Manages sleep and screensaver inhibition during video playback
 pub struct SleepInhibitor {
     inhibit_cookie: Option<u32>,
+    suspend_delay: Rc<RefCell<SuspendDelay>>,
 }
 
 impl SleepInhibitor {
@@ -12,6 +25,7 @@ impl SleepInhibitor {
     pub fn new() -> Self {
         Self {
             inhibit_cookie: None,
+            suspend_delay: Rc::new(RefCell::new(SuspendDelay::Released)),
         }
     }
 
@@ -33,10 +47,31 @@ impl SleepInhibitor {
                 debug!("Sleep inhibition enabled (cookie: {})", cookie);
             }
         }
+
+        // The session may still suspend, e.g. when the lid closes, so hold it
+        // off until playback has paused
+        *self.suspend_delay.borrow_mut() = SuspendDelay::Requested;
+        let suspend_delay = self.suspend_delay.clone();
+        session_events::request_suspend_delay(move |fd| {
+            let mut suspend_delay = suspend_delay.borrow_mut();
+            // Released while the request was in flight
+            if matches!(*suspend_delay, SuspendDelay::Requested) {
+                *suspend_delay = SuspendDelay::Held(fd);
+                debug!("Suspend delayed while playing");
+            }
+        });
     }
 
     /// Release sleep inhibition when playback stops/pauses
     pub fn release(&mut self, window: &adw::ApplicationWindow) {
+        // Closing the descriptor lets a pending suspend go ahead
+        if let SuspendDelay::Held(fd) = std::mem::replace(
+            &mut *self.suspend_delay.borrow_mut(),
+            SuspendDelay::Released,
+        ) {
+            debug!("Suspend no longer delayed (fd {})", fd.as_raw_fd());
+        }
+
         if let Some(cookie) = self.inhibit_cookie.take() {
             if let Some(app) = window.application() {
                 if let Ok(gtk_app) = app.downcast::<adw::Application>() {
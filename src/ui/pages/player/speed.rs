@@ -0,0 +1,133 @@
+use gtk::prelude::*;
+use relm4::AsyncComponentSender;
+use relm4::gtk;
+use std::cell::RefCell;
+use std::rc::Rc;
+use tracing::debug;
+
+use super::{PlayerInput, PlayerPage};
+
+/// Speeds offered as buttons in the speed popover
+const PRESETS: [f64; 6] = [0.75, 1.0, 1.25, 1.5, 1.75, 2.0];
+
+/// Slowest and fastest playback speed
+pub const SPEED_RANGE: (f64, f64) = (0.25, 4.0);
+
+/// Speed button of the control bar, with a popover of preset speeds and the
+/// pitch correction switch
+pub struct SpeedControl {
+    menu_button: gtk::MenuButton,
+    presets: Vec<(f64, gtk::ToggleButton)>,
+    pitch_correction: gtk::CheckButton,
+}
+
+impl SpeedControl {
+    pub fn new(
+        sender: &AsyncComponentSender<PlayerPage>,
+        popover_count: Rc<RefCell<usize>>,
+    ) -> Self {
+        let menu_button = gtk::MenuButton::new();
+        menu_button.set_label(&speed_label(1.0));
+        menu_button.add_css_class("flat");
+        menu_button.set_tooltip_text(Some("Playback Speed"));
+        menu_button.update_property(&[gtk::accessible::Property::Label("Playback Speed")]);
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 12);
+        content.set_margin_top(6);
+        content.set_margin_bottom(6);
+        content.set_margin_start(6);
+        content.set_margin_end(6);
+
+        let grid = gtk::Grid::builder()
+            .row_spacing(6)
+            .column_spacing(6)
+            .column_homogeneous(true)
+            .build();
+        let mut presets = Vec::new();
+        for (index, speed) in PRESETS.into_iter().enumerate() {
+            let button = gtk::ToggleButton::with_label(&speed_label(speed));
+            button.set_active(speed == 1.0);
+            let sender = sender.clone();
+            button.connect_clicked(move |_| {
+                sender.input(PlayerInput::SetPlaybackSpeed(speed));
+            });
+            grid.attach(&button, (index % 3) as i32, (index / 3) as i32, 1, 1);
+            presets.push((speed, button));
+        }
+        content.append(&grid);
+
+        let pitch_correction = gtk::CheckButton::with_label("Pitch Correction");
+        pitch_correction.set_active(true);
+        pitch_correction.set_tooltip_text(Some(
+            "Keep voices at their normal pitch when playing faster or slower",
+        ));
+        {
+            let sender = sender.clone();
+            pitch_correction.connect_toggled(move |check| {
+                sender.input(PlayerInput::SetPitchCorrection(check.is_active()));
+            });
+        }
+        content.append(&pitch_correction);
+
+        let popover = gtk::Popover::new();
+        popover.set_child(Some(&content));
+
+        // Track popover state to prevent control hiding
+        let popover_count_clone = popover_count.clone();
+        popover.connect_show(move |_| {
+            *popover_count_clone.borrow_mut() += 1;
+            debug!(
+                "Speed popover shown, count: {}",
+                *popover_count_clone.borrow()
+            );
+        });
+        popover.connect_hide(move |_| {
+            let mut count = popover_count.borrow_mut();
+            if *count > 0 {
+                *count -= 1;
+            }
+            debug!("Speed popover hidden, count: {}", *count);
+        });
+        menu_button.set_popover(Some(&popover));
+
+        Self {
+            menu_button,
+            presets,
+            pitch_correction,
+        }
+    }
+
+    pub fn widget(&self) -> &gtk::MenuButton {
+        &self.menu_button
+    }
+
+    /// Show the current speed on the button and mark its preset
+    pub fn set_speed(&self, speed: f64) {
+        self.menu_button.set_label(&speed_label(speed));
+        for (preset, button) in &self.presets {
+            button.set_active((preset - speed).abs() < 0.001);
+        }
+    }
+
+    pub fn set_pitch_correction(&self, enabled: bool) {
+        self.pitch_correction.set_active(enabled);
+    }
+}
+
+fn speed_label(speed: f64) -> String {
+    let text = format!("{:.2}", speed);
+    format!("{}×", text.trim_end_matches('0').trim_end_matches('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speed_label_drops_trailing_zeros() {
+        assert_eq!(speed_label(1.0), "1×");
+        assert_eq!(speed_label(1.5), "1.5×");
+        assert_eq!(speed_label(0.75), "0.75×");
+        assert_eq!(speed_label(1.1), "1.1×");
+    }
+}
@@ -0,0 +1,164 @@
+use crate::services::core::network_policy;
+use reqwest::StatusCode;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// A refreshed stream failing again within this long is shown as an error
+/// rather than refreshed once more, so a server refusing every new URL
+/// doesn't keep playback reloading
+const REFRESH_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// What to do about a failure of the playing stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamFailure {
+    /// Ask the server whether it still accepts the stream URL
+    Probe(String),
+    /// A probe is under way, its answer decides
+    Wait,
+    /// Show the error
+    Show,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RefreshState {
+    Ready,
+    Probing,
+    /// The server still accepts the URL, so the failure is something else
+    Accepted,
+    Refreshed(Instant),
+}
+
+/// Server URLs of streams carry a token, which may expire during a long
+/// session. A failing stream whose server refuses its URL is resolved again
+/// and resumes where it stopped, instead of showing an error.
+pub struct StreamRefresh {
+    /// URL of the server the stream comes from, None for local files
+    server_url: Option<String>,
+    state: RefreshState,
+}
+
+impl StreamRefresh {
+    pub fn new() -> Self {
+        Self {
+            server_url: None,
+            state: RefreshState::Ready,
+        }
+    }
+
+    /// Forget the stream, as another item loads
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Note the server URL of a stream once it starts
+    pub fn stream_started(&mut self, server_url: Option<String>) {
+        self.server_url = server_url;
+    }
+
+    /// What to do as the stream fails
+    pub fn failed(&mut self) -> StreamFailure {
+        match self.state {
+            RefreshState::Probing => return StreamFailure::Wait,
+            RefreshState::Accepted => return StreamFailure::Show,
+            RefreshState::Refreshed(at) if at.elapsed() < REFRESH_COOLDOWN => {
+                return StreamFailure::Show;
+            }
+            RefreshState::Ready | RefreshState::Refreshed(_) => {}
+        }
+        match &self.server_url {
+            Some(url) => {
+                self.state = RefreshState::Probing;
+                StreamFailure::Probe(url.clone())
+            }
+            None => StreamFailure::Show,
+        }
+    }
+
+    /// Note the answer of the probe, returning whether to refresh the stream
+    pub fn probed(&mut self, expired: bool) -> bool {
+        self.state = if expired {
+            RefreshState::Refreshed(Instant::now())
+        } else {
+            RefreshState::Accepted
+        };
+        expired
+    }
+}
+
+/// Whether a server answering with `status` refuses the stream URL
+fn refused(status: StatusCode) -> bool {
+    matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+}
+
+/// Whether the server no longer accepts a stream URL. Asks for the first
+/// byte only, the server may not answer HEAD requests.
+pub async fn expired(url: &str) -> bool {
+    let client = match network_policy().probe_client_builder().build() {
+        Ok(client) => client,
+        Err(e) => {
+            debug!("Failed to build a client to probe the stream: {}", e);
+            return false;
+        }
+    };
+    match client
+        .get(url)
+        .header(reqwest::header::RANGE, "bytes=0-0")
+        .send()
+        .await
+    {
+        Ok(response) => refused(response.status()),
+        Err(e) => {
+            debug!("Failed to probe the stream: {}", e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_refused_urls_count_as_expired() {
+        assert!(refused(StatusCode::UNAUTHORIZED));
+        assert!(refused(StatusCode::FORBIDDEN));
+        assert!(!refused(StatusCode::PARTIAL_CONTENT));
+        assert!(!refused(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_local_files_show_their_errors() {
+        let mut refresh = StreamRefresh::new();
+        refresh.stream_started(None);
+        assert_eq!(refresh.failed(), StreamFailure::Show);
+    }
+
+    #[test]
+    fn test_failures_wait_for_the_probe() {
+        let mut refresh = StreamRefresh::new();
+        refresh.stream_started(Some("https://plex/file".to_string()));
+        assert_eq!(
+            refresh.failed(),
+            StreamFailure::Probe("https://plex/file".to_string())
+        );
+        assert_eq!(refresh.failed(), StreamFailure::Wait);
+
+        assert!(!refresh.probed(false));
+        assert_eq!(refresh.failed(), StreamFailure::Show);
+    }
+
+    #[test]
+    fn test_refreshed_stream_failing_again_shows_the_error() {
+        let mut refresh = StreamRefresh::new();
+        refresh.stream_started(Some("https://plex/file".to_string()));
+        refresh.failed();
+        assert!(refresh.probed(true));
+
+        refresh.stream_started(Some("https://plex/file?new".to_string()));
+        assert_eq!(refresh.failed(), StreamFailure::Show);
+
+        refresh.clear();
+        refresh.stream_started(Some("https://plex/file".to_string()));
+        assert!(matches!(refresh.failed(), StreamFailure::Probe(_)));
+    }
+}
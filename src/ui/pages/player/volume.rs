@@ -3,6 +3,7 @@ use relm4::AsyncComponentSender;
 use relm4::gtk;
 
 use super::{PlayerInput, PlayerPage};
+use crate::ui::shared::accessibility;
 
 /// Manages volume control widget and volume adjustment logic.
 /// Handles volume slider widget, volume up/down operations,
@@ -10,6 +11,7 @@ use super::{PlayerInput, PlayerPage};
 pub struct VolumeManager {
     volume_slider: gtk::Scale,
     volume: f64,
+    muted: bool,
 }
 
 impl VolumeManager {
@@ -19,6 +21,7 @@ impl VolumeManager {
         let volume_slider = gtk::Scale::with_range(gtk::Orientation::Horizontal, 0.0, 1.0, 0.01);
         volume_slider.set_value(1.0);
         volume_slider.set_draw_value(false);
+        volume_slider.update_property(&[gtk::accessible::Property::Label("Volume")]);
 
         // Setup volume slider change handler
         {
@@ -32,6 +35,7 @@ impl VolumeManager {
         Self {
             volume_slider,
             volume: 1.0,
+            muted: false,
         }
     }
 
@@ -50,12 +54,18 @@ impl VolumeManager {
     pub fn set_volume(&mut self, volume: f64) {
         self.volume = volume.clamp(0.0, 1.0);
         self.volume_slider.set_value(self.volume);
+        self.volume_slider
+            .update_property(&[gtk::accessible::Property::ValueText(&self.volume_text())]);
     }
 
     /// Increase volume by 10%, capped at 100%
     pub fn volume_up(&mut self) -> f64 {
         self.volume = (self.volume + 0.1).min(1.0);
         self.volume_slider.set_value(self.volume);
+        accessibility::announce(
+            &self.volume_slider,
+            &format!("Volume {}", self.volume_text()),
+        );
         self.volume
     }
 
@@ -63,6 +73,21 @@ impl VolumeManager {
     pub fn volume_down(&mut self) -> f64 {
         self.volume = (self.volume - 0.1).max(0.0);
         self.volume_slider.set_value(self.volume);
+        accessibility::announce(
+            &self.volume_slider,
+            &format!("Volume {}", self.volume_text()),
+        );
         self.volume
     }
+
+    /// Flip the mute state the player was asked to toggle, returning whether
+    /// it is now muted
+    pub fn toggle_muted(&mut self) -> bool {
+        self.muted = !self.muted;
+        self.muted
+    }
+
+    fn volume_text(&self) -> String {
+        format!("{:.0}%", self.volume * 100.0)
+    }
 }
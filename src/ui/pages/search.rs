@@ -3,23 +3,158 @@ use libadwaita as adw;
 use relm4::factory::FactoryVecDeque;
 use relm4::gtk;
 use relm4::prelude::*;
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, error};
 
 use crate::db::connection::DatabaseConnection;
 use crate::db::entities::MediaItemModel;
-use crate::db::repository::{Repository, media_repository::MediaRepositoryImpl};
-use crate::models::MediaItemId;
+use crate::db::entities::media_items::UHD_MIN_HEIGHT;
+use crate::db::repository::{
+    LibraryRepositoryImpl, Repository, media_repository::MediaRepositoryImpl,
+    source_repository::SourceRepositoryImpl,
+};
+use crate::models::{MediaItemId, SourceId};
+use crate::services::core::KidsProfileService;
 use crate::ui::factories::media_card::{MediaCard, MediaCardInit, MediaCardInput, MediaCardOutput};
+use crate::ui::pages::library::{WatchState, WatchStatus};
+use crate::ui::shared::filter_widgets;
+use crate::ui::shared::undo_toast::UndoOffer;
 use crate::workers::{ImageLoader, ImageLoaderInput, ImageLoaderOutput, ImageRequest, ImageSize};
 use relm4::factory::DynamicIndex;
 
+/// Resolution classes offered by the resolution filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionClass {
+    Uhd,
+    FullHd,
+    Hd,
+    Sd,
+}
+
+impl ResolutionClass {
+    const ALL: [ResolutionClass; 4] = [Self::Uhd, Self::FullHd, Self::Hd, Self::Sd];
+
+    /// Classify a video by its height, allowing for cropped widescreen encodes
+    pub fn from_height(height: u32) -> Self {
+        match height {
+            UHD_MIN_HEIGHT.. => Self::Uhd,
+            900..UHD_MIN_HEIGHT => Self::FullHd,
+            600..900 => Self::Hd,
+            _ => Self::Sd,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Uhd => "4K",
+            Self::FullHd => "1080p",
+            Self::Hd => "720p",
+            Self::Sd => "SD",
+        }
+    }
+}
+
+/// Structured filters applied on top of the text query
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFilters {
+    pub media_type: Option<String>,
+    pub library_id: Option<String>,
+    pub source_id: Option<String>,
+    pub min_year: Option<i32>,
+    pub max_year: Option<i32>,
+    pub genres: Vec<String>,
+    pub resolution: Option<ResolutionClass>,
+    pub watch_status: WatchStatus,
+}
+
+impl SearchFilters {
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Check whether a search result passes every filter. `video_height` is
+    /// the item's, or for a show, that of its best episode.
+    pub fn matches(
+        &self,
+        item: &MediaItemModel,
+        watch_state: WatchState,
+        video_height: Option<u32>,
+    ) -> bool {
+        let media_type_match = self
+            .media_type
+            .as_ref()
+            .is_none_or(|media_type| &item.media_type == media_type);
+        let library_match = self
+            .library_id
+            .as_ref()
+            .is_none_or(|library_id| &item.library_id == library_id);
+        let source_match = self
+            .source_id
+            .as_ref()
+            .is_none_or(|source_id| &item.source_id == source_id);
+
+        let year_match = match item.year {
+            Some(year) => {
+                self.min_year.is_none_or(|min| year >= min)
+                    && self.max_year.is_none_or(|max| year <= max)
+            }
+            None => self.min_year.is_none() && self.max_year.is_none(),
+        };
+
+        let genre_match = self.genres.is_empty() || {
+            let item_genres = item.get_genres();
+            self.genres.iter().any(|genre| item_genres.contains(genre))
+        };
+
+        let resolution_match = self.resolution.is_none_or(|resolution| {
+            video_height.map(ResolutionClass::from_height) == Some(resolution)
+        });
+
+        let watch_status_match = self.watch_status.matches(watch_state);
+
+        media_type_match
+            && library_match
+            && source_match
+            && year_match
+            && genre_match
+            && resolution_match
+            && watch_status_match
+    }
+}
+
+/// Display name for a media type in the media type filter
+fn media_type_label(media_type: &str) -> String {
+    match media_type {
+        "movie" => "Movies".to_string(),
+        "show" => "Shows".to_string(),
+        "episode" => "Episodes".to_string(),
+        "album" => "Albums".to_string(),
+        "track" => "Tracks".to_string(),
+        "photo" => "Photos".to_string(),
+        other => other.to_string(),
+    }
+}
+
 pub struct SearchPage {
     db: DatabaseConnection,
     media_factory: FactoryVecDeque<MediaCard>,
     image_loader: relm4::WorkerController<ImageLoader>,
     image_requests: std::collections::HashMap<String, Vec<DynamicIndex>>,
     query: String,
+    /// Everything the query matched, before filtering
+    all_results: Vec<MediaItemModel>,
+    /// Results that pass the filters, as shown
     results: Vec<MediaItemModel>,
+    parent_shows: HashMap<String, MediaItemModel>,
+    watch_states: HashMap<String, WatchState>,
+    favorite_ids: HashSet<String>,
+    /// Highest episode video height of each show in the results
+    show_heights: HashMap<String, u32>,
+    filters: SearchFilters,
+    /// (id, name) of every library and source, for the filter choices
+    libraries: Vec<(String, String)>,
+    sources: Vec<(String, String)>,
+    filters_popover: gtk::Popover,
     is_loading: bool,
 }
 
@@ -28,6 +163,7 @@ impl std::fmt::Debug for SearchPage {
         f.debug_struct("SearchPage")
             .field("query", &self.query)
             .field("results_count", &self.results.len())
+            .field("filters", &self.filters)
             .field("is_loading", &self.is_loading)
             .finish()
     }
@@ -46,20 +182,72 @@ pub enum SearchPageInput {
     ParentShowsLoaded {
         items: Vec<MediaItemModel>,
         parent_shows: std::collections::HashMap<String, MediaItemModel>,
+        watch_states: HashMap<String, WatchState>,
+        favorite_ids: HashSet<String>,
+        show_heights: HashMap<String, u32>,
     },
+    /// Libraries and sources to offer in the filters
+    FilterOptionsLoaded {
+        libraries: Vec<(String, String)>,
+        sources: Vec<(String, String)>,
+    },
+    /// Show or hide the filters popover
+    ToggleFiltersPopover,
+    SetMediaTypeFilter(Option<String>),
+    SetLibraryFilter(Option<String>),
+    SetSourceFilter(Option<String>),
+    SetYearRange {
+        min: Option<i32>,
+        max: Option<i32>,
+    },
+    ToggleGenreFilter(String),
+    SetResolutionFilter(Option<ResolutionClass>),
+    SetWatchStatusFilter(WatchStatus),
+    ClearFilters,
     /// Media item selected
     MediaItemSelected(MediaItemId),
     /// Mark media item as watched
     MarkWatched(MediaItemId),
     /// Mark media item as unwatched
     MarkUnwatched(MediaItemId),
+    /// Hide a media item from the home page
+    Hide(MediaItemId),
+    /// Hide the movies and shows of a collection from the home page
+    HideCollection {
+        source_id: SourceId,
+        collection: String,
+    },
+    /// An action ran in the background, offer taking it back
+    OfferUndo(UndoOffer),
+    /// Add or remove a media item from favorites
+    SetFavorite {
+        id: MediaItemId,
+        favorite: bool,
+    },
+    /// Favorite state of a media item was saved
+    FavoriteUpdated {
+        id: MediaItemId,
+        favorite: bool,
+    },
+    /// A card was hovered long enough to start its preview
+    PreviewRequested {
+        id: MediaItemId,
+        index: DynamicIndex,
+    },
+    /// Preview stream resolved for the card at `index`
+    PreviewReady {
+        index: DynamicIndex,
+        url: String,
+    },
     /// Image loaded from worker
     ImageLoaded {
         id: String,
         texture: gtk::gdk::Texture,
     },
     /// Image load failed
-    ImageLoadFailed { id: String },
+    ImageLoadFailed {
+        id: String,
+    },
 }
 
 #[derive(Debug)]
@@ -89,28 +277,60 @@ impl AsyncComponent for SearchPage {
                     set_orientation: gtk::Orientation::Vertical,
                     set_spacing: 0,
 
-                    // Header with search query
+                    // Header with search query and filters
                     gtk::Box {
-                        set_orientation: gtk::Orientation::Vertical,
-                        set_spacing: 8,
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 12,
                         set_margin_top: 24,
                         set_margin_start: 16,
                         set_margin_end: 16,
                         #[watch]
-                        set_visible: !model.query.is_empty() && !model.results.is_empty(),
+                        set_visible: !model.query.is_empty() && !model.all_results.is_empty(),
 
-                        gtk::Label {
-                            set_halign: gtk::Align::Start,
-                            #[watch]
-                            set_text: &format!("Search results for: \"{}\"", model.query),
-                            add_css_class: "title-2",
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 8,
+                            set_hexpand: true,
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+                                #[watch]
+                                set_text: &format!("Search results for: \"{}\"", model.query),
+                                add_css_class: "title-2",
+                            },
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+                                #[watch]
+                                set_text: &if model.filters.is_active() {
+                                    format!("{} of {} results", model.results.len(), model.all_results.len())
+                                } else {
+                                    format!("{} results", model.results.len())
+                                },
+                                add_css_class: "dim-label",
+                            },
                         },
 
-                        gtk::Label {
-                            set_halign: gtk::Align::Start,
+                        gtk::Button {
+                            set_label: "Clear Filters",
+                            set_valign: gtk::Align::Center,
+                            add_css_class: "flat",
                             #[watch]
-                            set_text: &format!("{} results", model.results.len()),
-                            add_css_class: "dim-label",
+                            set_visible: model.filters.is_active(),
+                            connect_clicked[sender] => move |_| {
+                                sender.input(SearchPageInput::ClearFilters);
+                            }
+                        },
+
+                        #[name = "filters_button"]
+                        gtk::Button {
+                            set_icon_name: "funnel-symbolic",
+                            set_tooltip_text: Some("Filters"),
+                            set_valign: gtk::Align::Center,
+                            add_css_class: "flat",
+                            connect_clicked[sender] => move |_| {
+                                sender.input(SearchPageInput::ToggleFiltersPopover);
+                            }
                         },
                     },
 
@@ -168,7 +388,12 @@ impl AsyncComponent for SearchPage {
                         set_icon_name: Some("edit-find-symbolic"),
                         #[watch]
                         set_title: &format!("No results for \"{}\"", model.query),
-                        set_description: Some("Try a different search query"),
+                        #[watch]
+                        set_description: Some(if model.all_results.is_empty() {
+                            "Try a different search query"
+                        } else {
+                            "No results match the filters"
+                        }),
                         add_css_class: "compact",
                     },
                 },
@@ -204,6 +429,20 @@ impl AsyncComponent for SearchPage {
                 MediaCardOutput::GoToShow(id) => SearchPageInput::MediaItemSelected(id),
                 MediaCardOutput::MarkWatched(id) => SearchPageInput::MarkWatched(id),
                 MediaCardOutput::MarkUnwatched(id) => SearchPageInput::MarkUnwatched(id),
+                MediaCardOutput::Hide(id) => SearchPageInput::Hide(id),
+                MediaCardOutput::HideCollection {
+                    source_id,
+                    collection,
+                } => SearchPageInput::HideCollection {
+                    source_id,
+                    collection,
+                },
+                MediaCardOutput::PreviewRequested { id, index } => {
+                    SearchPageInput::PreviewRequested { id, index }
+                }
+                MediaCardOutput::SetFavorite { id, favorite } => {
+                    SearchPageInput::SetFavorite { id, favorite }
+                }
             });
 
         let model = SearchPage {
@@ -212,13 +451,53 @@ impl AsyncComponent for SearchPage {
             image_loader,
             image_requests: std::collections::HashMap::new(),
             query: String::new(),
+            all_results: Vec::new(),
             results: Vec::new(),
+            parent_shows: HashMap::new(),
+            watch_states: HashMap::new(),
+            show_heights: HashMap::new(),
+            favorite_ids: HashSet::new(),
+            filters: SearchFilters::default(),
+            libraries: Vec::new(),
+            sources: Vec::new(),
+            filters_popover: gtk::Popover::new(),
             is_loading: false,
         };
 
         let media_factory = model.media_factory.widget().clone();
         let widgets = view_output!();
 
+        model.filters_popover.set_parent(&widgets.filters_button);
+
+        // Load the libraries and sources the results can be narrowed to
+        let db = model.db.clone();
+        let input_sender = sender.input_sender().clone();
+        sender.oneshot_command(async move {
+            let libraries = match LibraryRepositoryImpl::new(db.clone()).find_all().await {
+                Ok(libraries) => libraries
+                    .into_iter()
+                    .map(|library| (library.id, library.title))
+                    .collect(),
+                Err(e) => {
+                    error!("Failed to load libraries for search filters: {}", e);
+                    Vec::new()
+                }
+            };
+            let sources = match SourceRepositoryImpl::new(db).find_all().await {
+                Ok(sources) => sources
+                    .into_iter()
+                    .map(|source| (source.id, source.name))
+                    .collect(),
+                Err(e) => {
+                    error!("Failed to load sources for search filters: {}", e);
+                    Vec::new()
+                }
+            };
+            input_sender
+                .send(SearchPageInput::FilterOptionsLoaded { libraries, sources })
+                .ok();
+        });
+
         AsyncComponentParts { model, widgets }
     }
 
@@ -226,7 +505,7 @@ impl AsyncComponent for SearchPage {
         &mut self,
         msg: Self::Input,
         sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         match msg {
             SearchPageInput::SetResults { query, results } => {
@@ -243,7 +522,7 @@ impl AsyncComponent for SearchPage {
                 let ids = results.clone();
                 let input_sender = sender.input_sender().clone();
                 sender.oneshot_command(async move {
-                    let repo = MediaRepositoryImpl::new(db);
+                    let repo = MediaRepositoryImpl::new(db.clone());
                     let mut items = Vec::new();
 
                     for id in ids {
@@ -254,6 +533,9 @@ impl AsyncComponent for SearchPage {
                         }
                     }
 
+                    // The kids profile leaves out what isn't rated for children
+                    KidsProfileService::retain_allowed(&db, &mut items).await;
+
                     input_sender
                         .send(SearchPageInput::ResultsLoaded(items))
                         .ok();
@@ -272,6 +554,7 @@ impl AsyncComponent for SearchPage {
 
                 // Batch fetch parent shows for episodes
                 let db = self.db.clone();
+                let db_for_progress = self.db.clone();
                 let input_sender = sender.input_sender().clone();
                 sender.oneshot_command(async move {
                     use crate::db::repository::Repository;
@@ -300,11 +583,70 @@ impl AsyncComponent for SearchPage {
                         }
                     }
 
+                    // Shows have no video of their own, the resolution filter
+                    // goes by their episodes
+                    let show_ids: Vec<String> = items
+                        .iter()
+                        .filter(|item| item.media_type == "show")
+                        .map(|item| item.id.clone())
+                        .collect();
+                    let show_heights = if show_ids.is_empty() {
+                        HashMap::new()
+                    } else {
+                        match media_repo.max_episode_video_heights(&show_ids).await {
+                            Ok(heights) => heights,
+                            Err(e) => {
+                                debug!("Failed to fetch episode resolutions: {}", e);
+                                HashMap::new()
+                            }
+                        }
+                    };
+
+                    // Watch state for the watch status filter
+                    let media_ids: Vec<String> = items.iter().map(|item| item.id.clone()).collect();
+                    let progress_map =
+                        match crate::services::core::MediaService::get_playback_progress_batch(
+                            &db_for_progress,
+                            &media_ids,
+                        )
+                        .await
+                        {
+                            Ok(map) => map,
+                            Err(e) => {
+                                debug!("Failed to fetch playback progress for filtering: {}", e);
+                                HashMap::new()
+                            }
+                        };
+                    let watch_states = items
+                        .iter()
+                        .map(|item| {
+                            (
+                                item.id.clone(),
+                                WatchState::of(item, progress_map.get(&item.id)),
+                            )
+                        })
+                        .collect();
+
+                    let favorite_ids = match crate::services::core::FavoritesService::favorite_ids(
+                        &db_for_progress,
+                    )
+                    .await
+                    {
+                        Ok(ids) => ids,
+                        Err(e) => {
+                            debug!("Failed to fetch favorites: {}", e);
+                            HashSet::new()
+                        }
+                    };
+
                     // Send message with parent shows data
                     input_sender
                         .send(SearchPageInput::ParentShowsLoaded {
                             items,
                             parent_shows: parent_shows_map,
+                            watch_states,
+                            favorite_ids,
+                            show_heights,
                         })
                         .ok();
                 });
@@ -313,79 +655,83 @@ impl AsyncComponent for SearchPage {
             SearchPageInput::ParentShowsLoaded {
                 items,
                 parent_shows,
+                watch_states,
+                favorite_ids,
+                show_heights,
             } => {
                 debug!("Rendering {} items with parent show data", items.len());
-                self.results = items.clone();
+                self.all_results = items;
+                self.parent_shows = parent_shows;
+                self.watch_states = watch_states;
+                self.favorite_ids = favorite_ids;
+                self.show_heights = show_heights;
                 self.is_loading = false;
+                self.apply_filters();
+                // The choices and year bounds come from the results
+                if self.filters_popover.is_visible() {
+                    self.update_filters_popover(sender.clone());
+                }
+            }
 
-                // Clear existing media cards
-                self.media_factory.guard().clear();
-
-                // Clear image requests
-                self.image_requests.clear();
-
-                // Add new media cards
-                for item in &items {
-                    // For episodes, use parent show poster
-                    let mut display_item = item.clone();
-                    if item.media_type == "episode" {
-                        if let Some(parent_id) = &item.parent_id {
-                            if let Some(parent_show) = parent_shows.get(parent_id) {
-                                // Use the show's poster instead of episode thumbnail
-                                display_item.poster_url = parent_show.poster_url.clone();
-                            } else {
-                                error!(
-                                    "Parent show not found for episode {} with parent_id {:?}",
-                                    item.id, parent_id
-                                );
-                                // Skip this episode if we can't find its parent show
-                                continue;
-                            }
-                        } else {
-                            error!("Episode {} has no parent_id set!", item.id);
-                            // Skip episodes without parent shows
-                            continue;
-                        }
-                    }
+            SearchPageInput::FilterOptionsLoaded { libraries, sources } => {
+                self.libraries = libraries;
+                self.sources = sources;
+            }
 
-                    let poster_url_to_load = display_item.poster_url.clone();
+            SearchPageInput::ToggleFiltersPopover => {
+                if self.filters_popover.is_visible() {
+                    self.filters_popover.popdown();
+                } else {
+                    // Build the popover content from the current results before showing
+                    self.update_filters_popover(sender.clone());
+                    self.filters_popover.popup();
+                }
+            }
 
-                    let card_init = MediaCardInit {
-                        item: display_item,
-                        show_progress: true,
-                        watched: false,
-                        progress_percent: 0.0,
-                        show_media_type_icon: true,
-                    };
+            SearchPageInput::SetMediaTypeFilter(media_type) => {
+                self.filters.media_type = media_type;
+                self.apply_filters();
+            }
 
-                    let index = self.media_factory.guard().push_back(card_init);
-
-                    // Request poster image
-                    if let Some(poster_url) = poster_url_to_load {
-                        // Track multiple cards that use the same poster URL
-                        self.image_requests
-                            .entry(poster_url.clone())
-                            .or_insert_with(Vec::new)
-                            .push(index);
-
-                        // Only request the image once per unique URL
-                        if self
-                            .image_requests
-                            .get(&poster_url)
-                            .map(|v| v.len())
-                            .unwrap_or(0)
-                            == 1
-                        {
-                            self.image_loader
-                                .emit(ImageLoaderInput::LoadImage(ImageRequest {
-                                    id: poster_url.clone(),
-                                    url: poster_url.clone(),
-                                    size: ImageSize::Thumbnail,
-                                    priority: 1,
-                                }));
-                        }
-                    }
+            SearchPageInput::SetLibraryFilter(library_id) => {
+                self.filters.library_id = library_id;
+                self.apply_filters();
+            }
+
+            SearchPageInput::SetSourceFilter(source_id) => {
+                self.filters.source_id = source_id;
+                self.apply_filters();
+            }
+
+            SearchPageInput::SetYearRange { min, max } => {
+                self.filters.min_year = min;
+                self.filters.max_year = max;
+                self.apply_filters();
+            }
+
+            SearchPageInput::ToggleGenreFilter(genre) => {
+                if let Some(pos) = self.filters.genres.iter().position(|g| g == &genre) {
+                    self.filters.genres.remove(pos);
+                } else {
+                    self.filters.genres.push(genre);
                 }
+                self.apply_filters();
+            }
+
+            SearchPageInput::SetResolutionFilter(resolution) => {
+                self.filters.resolution = resolution;
+                self.apply_filters();
+            }
+
+            SearchPageInput::SetWatchStatusFilter(status) => {
+                self.filters.watch_status = status;
+                self.apply_filters();
+            }
+
+            SearchPageInput::ClearFilters => {
+                self.filters = SearchFilters::default();
+                self.filters_popover.popdown();
+                self.apply_filters();
             }
 
             SearchPageInput::MediaItemSelected(id) => {
@@ -397,44 +743,160 @@ impl AsyncComponent for SearchPage {
 
             SearchPageInput::MarkWatched(media_id) => {
                 debug!("Marking item as watched: {}", media_id);
+                use crate::services::commands::media_commands::MarkWatchedCommand;
+
+                let cmd = MarkWatchedCommand {
+                    db: self.db.clone(),
+                    media_id,
+                };
+
+                let input_sender = sender.input_sender().clone();
+                sender.oneshot_command(async move {
+                    if let Some(offer) =
+                        UndoOffer::run(cmd, "Marked as watched", "Failed to mark item as watched")
+                            .await
+                    {
+                        input_sender.emit(SearchPageInput::OfferUndo(offer));
+                    }
+                });
+            }
+
+            SearchPageInput::MarkUnwatched(media_id) => {
+                debug!("Marking item as unwatched: {}", media_id);
                 let db = self.db.clone();
                 let media_id_clone = media_id.clone();
 
                 sender.oneshot_command(async move {
                     use crate::services::commands::Command;
-                    use crate::services::commands::media_commands::MarkWatchedCommand;
+                    use crate::services::commands::media_commands::MarkUnwatchedCommand;
 
-                    let cmd = MarkWatchedCommand {
+                    let cmd = MarkUnwatchedCommand {
                         db,
                         media_id: media_id_clone,
                     };
 
                     if let Err(e) = cmd.execute().await {
-                        tracing::error!("Failed to mark item as watched: {}", e);
+                        tracing::error!("Failed to mark item as unwatched: {}", e);
                     }
                 });
             }
 
-            SearchPageInput::MarkUnwatched(media_id) => {
-                debug!("Marking item as unwatched: {}", media_id);
+            SearchPageInput::Hide(media_id) => {
+                debug!("Hiding item from home: {}", media_id);
+                use crate::services::commands::media_commands::SetHiddenCommand;
+
+                let cmd = SetHiddenCommand {
+                    db: self.db.clone(),
+                    media_id,
+                    hidden: true,
+                };
+
+                let input_sender = sender.input_sender().clone();
+                sender.oneshot_command(async move {
+                    if let Some(offer) =
+                        UndoOffer::run(cmd, "Hidden from Home", "Failed to hide item").await
+                    {
+                        input_sender.emit(SearchPageInput::OfferUndo(offer));
+                    }
+                });
+            }
+
+            SearchPageInput::HideCollection {
+                source_id,
+                collection,
+            } => {
+                debug!("Hiding collection from home: {}", collection);
+                use crate::services::commands::media_commands::SetCollectionHiddenCommand;
+
+                let cmd = SetCollectionHiddenCommand {
+                    db: self.db.clone(),
+                    source_id,
+                    collection,
+                    hidden: true,
+                };
+
+                let input_sender = sender.input_sender().clone();
+                sender.oneshot_command(async move {
+                    if let Some(offer) = UndoOffer::run(
+                        cmd,
+                        "Collection hidden from Home",
+                        "Failed to hide collection",
+                    )
+                    .await
+                    {
+                        input_sender.emit(SearchPageInput::OfferUndo(offer));
+                    }
+                });
+            }
+
+            SearchPageInput::OfferUndo(offer) => offer.present(root),
+
+            SearchPageInput::SetFavorite { id, favorite } => {
+                debug!("Setting favorite={} for item: {}", favorite, id);
                 let db = self.db.clone();
-                let media_id_clone = media_id.clone();
+                let input_sender = sender.input_sender().clone();
 
                 sender.oneshot_command(async move {
                     use crate::services::commands::Command;
-                    use crate::services::commands::media_commands::MarkUnwatchedCommand;
+                    use crate::services::commands::media_commands::SetFavoriteCommand;
 
-                    let cmd = MarkUnwatchedCommand {
+                    let cmd = SetFavoriteCommand {
                         db,
-                        media_id: media_id_clone,
+                        media_id: id.clone(),
+                        favorite,
                     };
 
-                    if let Err(e) = cmd.execute().await {
-                        tracing::error!("Failed to mark item as unwatched: {}", e);
+                    match cmd.execute().await {
+                        Ok(()) => {
+                            input_sender
+                                .send(SearchPageInput::FavoriteUpdated { id, favorite })
+                                .ok();
+                        }
+                        Err(e) => tracing::error!("Failed to update favorite: {}", e),
                     }
                 });
             }
 
+            SearchPageInput::FavoriteUpdated { id, favorite } => {
+                if favorite {
+                    self.favorite_ids.insert(id.to_string());
+                } else {
+                    self.favorite_ids.remove(id.as_str());
+                }
+
+                for (index, item) in self.rendered_results().enumerate() {
+                    if item.id == id.as_str() {
+                        self.media_factory
+                            .send(index, MediaCardInput::SetFavorite(favorite));
+                    }
+                }
+            }
+
+            SearchPageInput::PreviewRequested { id, index } => {
+                let db = self.db.clone();
+                let input_sender = sender.input_sender().clone();
+                sender.oneshot_command(async move {
+                    match crate::services::core::previews::preview_url(&db, &id).await {
+                        Ok(Some(url)) => {
+                            input_sender
+                                .send(SearchPageInput::PreviewReady { index, url })
+                                .ok();
+                        }
+                        Ok(None) => {}
+                        Err(e) => debug!("Failed to get preview for {}: {}", id, e),
+                    }
+                });
+            }
+
+            SearchPageInput::PreviewReady { index, url } => {
+                // The results may have changed while the stream was resolved
+                let index = index.current_index();
+                if index < self.media_factory.len() {
+                    self.media_factory
+                        .send(index, MediaCardInput::PlayPreview(url));
+                }
+            }
+
             SearchPageInput::ImageLoaded { id, texture } => {
                 // Send the texture to all cards that share this poster URL
                 if let Some(indices) = self.image_requests.get(&id) {
@@ -454,3 +916,357 @@ impl AsyncComponent for SearchPage {
         }
     }
 }
+
+impl SearchPage {
+    /// Results that got a card, in card order. Episodes whose show isn't known are skipped
+    fn rendered_results(&self) -> impl Iterator<Item = &MediaItemModel> {
+        self.results.iter().filter(|item| {
+            item.media_type != "episode"
+                || item
+                    .parent_id
+                    .as_ref()
+                    .is_some_and(|parent_id| self.parent_shows.contains_key(parent_id))
+        })
+    }
+
+    /// Narrow the query results down to those passing the filters and render them
+    fn apply_filters(&mut self) {
+        self.results = self
+            .all_results
+            .iter()
+            .filter(|item| {
+                let video_height = item
+                    .get_video_height()
+                    .or_else(|| self.show_heights.get(&item.id).copied());
+                let watch_state = self
+                    .watch_states
+                    .get(&item.id)
+                    .copied()
+                    .unwrap_or(WatchState::Unwatched);
+                self.filters.matches(item, watch_state, video_height)
+            })
+            .cloned()
+            .collect();
+
+        // Clear existing media cards
+        self.media_factory.guard().clear();
+
+        // Clear image requests
+        self.image_requests.clear();
+
+        // Add new media cards
+        for item in &self.results {
+            // For episodes, use parent show poster
+            let mut display_item = item.clone();
+            if item.media_type == "episode" {
+                if let Some(parent_id) = &item.parent_id {
+                    if let Some(parent_show) = self.parent_shows.get(parent_id) {
+                        // Use the show's poster instead of episode thumbnail
+                        display_item.poster_url = parent_show.poster_url.clone();
+                    } else {
+                        error!(
+                            "Parent show not found for episode {} with parent_id {:?}",
+                            item.id, parent_id
+                        );
+                        // Skip this episode if we can't find its parent show
+                        continue;
+                    }
+                } else {
+                    error!("Episode {} has no parent_id set!", item.id);
+                    // Skip episodes without parent shows
+                    continue;
+                }
+            }
+
+            let poster_url_to_load = display_item.poster_url.clone();
+
+            let card_init = MediaCardInit {
+                item: display_item,
+                show_progress: true,
+                watched: false,
+                progress_percent: 0.0,
+                show_media_type_icon: true,
+                unwatched_count: 0,
+                favorite: self.favorite_ids.contains(&item.id),
+            };
+
+            let index = self.media_factory.guard().push_back(card_init);
+
+            // Request poster image
+            if let Some(poster_url) = poster_url_to_load {
+                // Track multiple cards that use the same poster URL
+                self.image_requests
+                    .entry(poster_url.clone())
+                    .or_insert_with(Vec::new)
+                    .push(index);
+
+                // Only request the image once per unique URL
+                if self
+                    .image_requests
+                    .get(&poster_url)
+                    .map(|v| v.len())
+                    .unwrap_or(0)
+                    == 1
+                {
+                    self.image_loader
+                        .emit(ImageLoaderInput::LoadImage(ImageRequest {
+                            id: poster_url.clone(),
+                            url: poster_url.clone(),
+                            size: ImageSize::Thumbnail,
+                            priority: 1,
+                        }));
+                }
+            }
+        }
+    }
+
+    /// Build the filters popover from what the current results contain
+    fn update_filters_popover(&self, sender: AsyncComponentSender<Self>) {
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 12);
+        content.set_margin_top(12);
+        content.set_margin_bottom(12);
+        content.set_margin_start(12);
+        content.set_margin_end(12);
+        content.set_width_request(320);
+
+        let mut media_types: Vec<String> = Vec::new();
+        let mut library_ids = HashSet::new();
+        let mut source_ids = HashSet::new();
+        let mut genres: Vec<String> = Vec::new();
+        for item in &self.all_results {
+            if !media_types.contains(&item.media_type) {
+                media_types.push(item.media_type.clone());
+            }
+            library_ids.insert(item.library_id.as_str());
+            source_ids.insert(item.source_id.as_str());
+            for genre in item.get_genres() {
+                if !genres.contains(&genre) {
+                    genres.push(genre);
+                }
+            }
+        }
+        genres.sort();
+        let years: Vec<i32> = self
+            .all_results
+            .iter()
+            .filter_map(|item| item.year)
+            .collect();
+
+        let media_type_options: Vec<(String, String)> = media_types
+            .into_iter()
+            .map(|media_type| {
+                let label = media_type_label(&media_type);
+                (media_type, label)
+            })
+            .collect();
+        let sender_clone = sender.clone();
+        content.append(&filter_widgets::build_choice_section(
+            "Media Type",
+            "All Types",
+            &media_type_options,
+            self.filters.media_type.as_ref(),
+            move |media_type| sender_clone.input(SearchPageInput::SetMediaTypeFilter(media_type)),
+        ));
+
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+        let library_options: Vec<(String, String)> = self
+            .libraries
+            .iter()
+            .filter(|(id, _)| library_ids.contains(id.as_str()))
+            .cloned()
+            .collect();
+        let sender_clone = sender.clone();
+        content.append(&filter_widgets::build_choice_section(
+            "Library",
+            "All Libraries",
+            &library_options,
+            self.filters.library_id.as_ref(),
+            move |library_id| sender_clone.input(SearchPageInput::SetLibraryFilter(library_id)),
+        ));
+
+        // Only worth offering when results come from more than one source
+        if self.sources.len() > 1 {
+            content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+            let source_options: Vec<(String, String)> = self
+                .sources
+                .iter()
+                .filter(|(id, _)| source_ids.contains(id.as_str()))
+                .cloned()
+                .collect();
+            let sender_clone = sender.clone();
+            content.append(&filter_widgets::build_choice_section(
+                "Source",
+                "All Sources",
+                &source_options,
+                self.filters.source_id.as_ref(),
+                move |source_id| sender_clone.input(SearchPageInput::SetSourceFilter(source_id)),
+            ));
+        }
+
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+        let sender_clone = sender.clone();
+        content.append(&filter_widgets::build_year_section(
+            (years.iter().min().copied(), years.iter().max().copied()),
+            (self.filters.min_year, self.filters.max_year),
+            move |min, max| sender_clone.input(SearchPageInput::SetYearRange { min, max }),
+        ));
+
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+        let sender_clone = sender.clone();
+        content.append(&filter_widgets::build_genre_section(
+            &genres,
+            &self.filters.genres,
+            move |genre| sender_clone.input(SearchPageInput::ToggleGenreFilter(genre)),
+        ));
+
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+        let resolution_options: Vec<(ResolutionClass, String)> = ResolutionClass::ALL
+            .iter()
+            .map(|resolution| (*resolution, resolution.label().to_string()))
+            .collect();
+        let sender_clone = sender.clone();
+        content.append(&filter_widgets::build_choice_section(
+            "Resolution",
+            "Any",
+            &resolution_options,
+            self.filters.resolution.as_ref(),
+            move |resolution| sender_clone.input(SearchPageInput::SetResolutionFilter(resolution)),
+        ));
+
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+        content.append(&filter_widgets::build_watch_status_section(
+            self.filters.watch_status,
+            move |status| sender.input(SearchPageInput::SetWatchStatusFilter(status)),
+        ));
+
+        let scrolled = gtk::ScrolledWindow::new();
+        scrolled.set_max_content_height(600);
+        scrolled.set_propagate_natural_height(true);
+        scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        scrolled.set_child(Some(&content));
+        self.filters_popover.set_child(Some(&scrolled));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn item(media_type: &str, year: Option<i32>, genres: &[&str]) -> MediaItemModel {
+        MediaItemModel {
+            id: "item".to_string(),
+            library_id: "library".to_string(),
+            source_id: "source".to_string(),
+            media_type: media_type.to_string(),
+            title: "Item".to_string(),
+            sort_title: None,
+            year,
+            duration_ms: None,
+            rating: None,
+            poster_url: None,
+            backdrop_url: None,
+            overview: None,
+            genres: Some(serde_json::json!(genres)),
+            added_at: None,
+            updated_at: Utc::now().naive_utc(),
+            metadata: None,
+            parent_id: None,
+            season_number: None,
+            episode_number: None,
+            intro_marker_start_ms: None,
+            intro_marker_end_ms: None,
+            credits_marker_start_ms: None,
+            credits_marker_end_ms: None,
+            fetched_at: None,
+        }
+    }
+
+    #[test]
+    fn test_resolution_class_from_height() {
+        assert_eq!(ResolutionClass::from_height(2160), ResolutionClass::Uhd);
+        assert_eq!(ResolutionClass::from_height(1600), ResolutionClass::Uhd);
+        assert_eq!(ResolutionClass::from_height(1080), ResolutionClass::FullHd);
+        assert_eq!(ResolutionClass::from_height(800), ResolutionClass::Hd);
+        assert_eq!(ResolutionClass::from_height(720), ResolutionClass::Hd);
+        assert_eq!(ResolutionClass::from_height(480), ResolutionClass::Sd);
+    }
+
+    #[test]
+    fn test_year_range_excludes_items_outside_or_without_a_year() {
+        let filters = SearchFilters {
+            min_year: Some(1990),
+            max_year: Some(1999),
+            ..Default::default()
+        };
+        assert!(filters.matches(&item("movie", Some(1995), &[]), WatchState::Unwatched, None));
+        assert!(filters.matches(&item("movie", Some(1999), &[]), WatchState::Unwatched, None));
+        assert!(!filters.matches(&item("movie", Some(2001), &[]), WatchState::Unwatched, None));
+        assert!(!filters.matches(&item("movie", None, &[]), WatchState::Unwatched, None));
+        assert!(SearchFilters::default().matches(
+            &item("movie", None, &[]),
+            WatchState::Unwatched,
+            None
+        ));
+    }
+
+    #[test]
+    fn test_genres_match_any_selected() {
+        let filters = SearchFilters {
+            genres: vec!["Comedy".to_string(), "Drama".to_string()],
+            ..Default::default()
+        };
+        assert!(filters.matches(
+            &item("movie", None, &["Drama"]),
+            WatchState::Unwatched,
+            None
+        ));
+        assert!(!filters.matches(
+            &item("movie", None, &["Horror"]),
+            WatchState::Unwatched,
+            None
+        ));
+    }
+
+    #[test]
+    fn test_resolution_uses_the_given_height() {
+        let filters = SearchFilters {
+            resolution: Some(ResolutionClass::Uhd),
+            ..Default::default()
+        };
+        let show = item("show", None, &[]);
+        assert!(filters.matches(&show, WatchState::Unwatched, Some(2160)));
+        assert!(!filters.matches(&show, WatchState::Unwatched, Some(1080)));
+        assert!(!filters.matches(&show, WatchState::Unwatched, None));
+    }
+
+    #[test]
+    fn test_watch_status() {
+        let movie = item("movie", None, &[]);
+        let watched = SearchFilters {
+            watch_status: WatchStatus::Watched,
+            ..Default::default()
+        };
+        let unwatched = SearchFilters {
+            watch_status: WatchStatus::Unwatched,
+            ..Default::default()
+        };
+        assert!(watched.matches(&movie, WatchState::Watched, None));
+        assert!(!watched.matches(&movie, WatchState::Unwatched, None));
+        assert!(unwatched.matches(&movie, WatchState::Unwatched, None));
+        assert!(!unwatched.matches(&movie, WatchState::Watched, None));
+
+        let in_progress = SearchFilters {
+            watch_status: WatchStatus::InProgress,
+            ..Default::default()
+        };
+        assert!(in_progress.matches(&movie, WatchState::InProgress, None));
+        assert!(!in_progress.matches(&movie, WatchState::Unwatched, None));
+    }
+}
@@ -1,14 +1,34 @@
-use crate::models::{Episode, MediaItem, MediaItemId, PlaylistContext, Show, ShowId};
-use crate::services::commands::Command;
+use crate::db::entities::QualityPreset;
+use crate::db::repository::{MediaRepositoryImpl, Repository, SkipKind};
+use crate::models::{
+    Episode, LibraryId, MediaItem, MediaItemId, PlaylistContext, SeasonProgress, Show, ShowId,
+};
 use crate::services::commands::media_commands::{
-    GetEpisodesCommand, GetItemDetailsCommand, MarkSeasonUnwatchedCommand,
-    MarkSeasonWatchedCommand, MarkShowUnwatchedCommand, MarkShowWatchedCommand,
-    MarkUnwatchedCommand, MarkWatchedCommand,
+    DeleteFromServerCommand, GetEpisodesCommand, GetItemDetailsCommand,
+    MarkPreviousEpisodesWatchedCommand, MarkSeasonUnwatchedCommand, MarkSeasonWatchedCommand,
+    MarkShowUnwatchedCommand, MarkShowWatchedCommand, MarkUnwatchedCommand, MarkWatchedCommand,
+    SetFavoriteCommand, SetLabelCommand,
+};
+use crate::services::commands::{Command, UndoableCommand};
+use crate::services::core::dominant_color::{self, DominantColor};
+use crate::services::core::downloads::DownloadEstimate;
+use crate::services::core::theme_music::theme_music_url;
+use crate::services::core::{
+    DeletionService, DownloadService, FavoritesService, LabelsService, MediaService,
+    PlaylistService, ShowSkipModes, SkipMode, SkipPreferencesService, connectivity,
 };
-use crate::services::core::PlaylistService;
-use crate::ui::shared::broker::{BROKER, BrokerMessage};
-use crate::ui::shared::image_helpers::load_image_from_url;
+use crate::ui::pages::library::FilterState;
+use crate::ui::shared::accessibility;
+use crate::ui::shared::broker::{BROKER, BrokerMessage, ConnectivityMessage, Subscription, Topic};
+use crate::ui::shared::delete_confirmation;
+use crate::ui::shared::image_helpers::{load_image_from_url, load_image_with_color};
+use crate::ui::shared::keyboard;
+use crate::ui::shared::label_chips::{self, ChipAction, LabelChips};
 use crate::ui::shared::person_card::create_person_card;
+use crate::ui::shared::theme_music;
+use crate::ui::shared::tint;
+use crate::ui::shared::type_ahead::{self, TypeAhead};
+use crate::ui::shared::undo_toast::UndoOffer;
 use crate::workers::image_loader::{
     ImageLoader, ImageLoaderInput, ImageLoaderOutput, ImageRequest, ImageSize,
 };
@@ -22,6 +42,15 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::error;
 
+/// Episode counts offered for auto-download, with 0 meaning off
+const AUTO_DOWNLOAD_COUNTS: [u32; 5] = [0, 1, 3, 5, 10];
+
+/// Image loader ID for the next episode's thumbnail, episode cards use their index
+const NEXT_UP_IMAGE_ID: &str = "next_up";
+
+/// Size of the watched progress rings in the season selector
+const SEASON_RING_SIZE: i32 = 18;
+
 pub struct ShowDetailsPage {
     show: Option<Show>,
     episodes: Vec<Episode>,
@@ -32,18 +61,43 @@ pub struct ShowDetailsPage {
     loading: bool,
     episode_grid: gtk::FlowBox,
     season_dropdown: gtk::DropDown,
+    season_progress: HashMap<u32, SeasonProgress>, // Keyed by season number
+    season_progress_ring: gtk::DrawingArea,
+    season_progress_label: gtk::Label,
+    next_up: Option<Episode>,
+    next_up_texture: Option<gtk::gdk::Texture>,
+    auto_download_dropdown: gtk::DropDown,
+    auto_download_count: u32,
+    skip_intro_dropdown: gtk::DropDown,
+    skip_credits_dropdown: gtk::DropDown,
+    skip_modes: ShowSkipModes,
     cast_box: gtk::Box,
+    studio_box: gtk::Box,
+    label_chips: LabelChips,
     poster_texture: Option<gtk::gdk::Texture>,
     backdrop_texture: Option<gtk::gdk::Texture>,
+    hero_tint: gtk::Box,
     image_loader: WorkerController<ImageLoader>,
     episode_pictures: HashMap<usize, gtk::Picture>,
     episode_popovers: HashMap<usize, gtk::PopoverMenu>,
+    episode_type_ahead: TypeAhead,
+    /// Whether watched episodes are hidden from the episode grid
+    unplayed_only: bool,
     person_textures: HashMap<String, gtk::gdk::Texture>,
     full_metadata_loaded: bool,
+    favorite: bool,
+    /// Whether the server lets the user delete the show
+    can_delete: bool,
+    /// Whether labels of the show can be edited on its server
+    can_edit_labels: bool,
+    /// Whether every server is unreachable, ruling out changes made on them
+    offline: bool,
+    theme_music: Option<gtk::MediaFile>,
     // Sync status tracking
     sync_status: crate::ui::shared::sync_status::SyncStatus,
     failed_syncs: Vec<(String, String)>, // (media_item_id, error)
     sync_indicator: gtk::Box,
+    _broker_subscription: Subscription,
 }
 
 impl std::fmt::Debug for ShowDetailsPage {
@@ -64,9 +118,40 @@ pub enum ShowDetailsInput {
     LoadShow(MediaItemId),
     SelectSeason(u32), // Season dropdown index (not season number)
     PlayEpisode(MediaItemId),
+    /// Type-to-find in the episode list, by title or episode number
+    FindEpisode(char),
     ToggleEpisodeWatched(usize),
+    /// Catch up to an episode, marking every one before it watched
+    MarkPreviousWatched(usize),
+    SetUnplayedOnly(bool),
     ToggleShowWatched,
     ToggleSeasonWatched,
+    ToggleFavorite,
+    SetAutoDownload(u32), // Auto-download dropdown index
+    AutoDownloadLoaded(u32),
+    /// Override how the show skips intros or credits, None to follow the
+    /// playback settings
+    SetSkipMode {
+        kind: SkipKind,
+        mode: Option<SkipMode>,
+    },
+    SkipModesLoaded(ShowSkipModes),
+    RequestDownload {
+        scope: DownloadScope,
+        quality: QualityPreset,
+    },
+    ConfirmDownload {
+        what: String,
+        quality: QualityPreset,
+        estimate: DownloadEstimate,
+    },
+    QueueDownloads {
+        media_ids: Vec<MediaItemId>,
+        quality: QualityPreset,
+    },
+    SeasonProgressLoaded(HashMap<u32, SeasonProgress>),
+    NextUpLoaded(Option<Episode>),
+    PlayNextUp,
     LoadEpisodes,
     ImageLoaded {
         id: String,
@@ -75,9 +160,27 @@ pub enum ShowDetailsInput {
     ImageLoadFailed {
         id: String,
     },
+    /// Open the item's library filtered to what it shares a studio,
+    /// collection or tag with
+    BrowseLibrary(FilterState),
+    AddLabel,
+    SetLabel {
+        label: String,
+        present: bool,
+    },
+    ConfirmDelete,
+    DeleteFromServer,
     BrokerMsg(crate::ui::shared::broker::BrokerMessage),
 }
 
+/// Which episodes a download request covers
+#[derive(Debug, Clone, Copy)]
+pub enum DownloadScope {
+    Show,
+    CurrentSeason,
+    Episode(usize),
+}
+
 #[derive(Debug)]
 pub enum ShowDetailsOutput {
     PlayMedia(MediaItemId),
@@ -85,6 +188,17 @@ pub enum ShowDetailsOutput {
         media_id: MediaItemId,
         context: PlaylistContext,
     },
+    /// Open the show's library filtered to a network or studio
+    NavigateToLibraryView {
+        library_id: LibraryId,
+        filter: FilterState,
+    },
+    /// The show was deleted from its server
+    NavigateBack,
+    /// Tell the user something went wrong
+    ShowToast(String),
+    /// The season shown changed, by its number
+    SeasonSelected(u32),
 }
 
 #[derive(Debug)]
@@ -93,15 +207,18 @@ pub enum ShowDetailsCommand {
     LoadEpisodes(String, u32),
     LoadPosterImage {
         url: String,
+        find_color: bool,
     },
     LoadBackdropImage {
         url: String,
     },
     PosterImageLoaded {
         texture: gtk::gdk::Texture,
+        color: Option<DominantColor>,
     },
     BackdropImageLoaded {
         texture: gtk::gdk::Texture,
+        color: Option<DominantColor>,
     },
     LoadPersonImage {
         person_id: String,
@@ -118,6 +235,20 @@ pub enum ShowDetailsCommand {
     PlayWithoutContext(MediaItemId),
     LoadFullMetadata,
     FullMetadataLoaded,
+    ThemeMusicLoaded {
+        item_id: MediaItemId,
+        url: Option<String>,
+    },
+    DeletePermissionLoaded {
+        item_id: MediaItemId,
+        can_delete: bool,
+    },
+    LabelsEditableLoaded {
+        item_id: MediaItemId,
+        editable: bool,
+    },
+    /// An action ran, offer taking it back unless it failed
+    OfferUndo(Option<UndoOffer>),
 }
 
 #[allow(unused_assignments)]
@@ -151,6 +282,17 @@ impl AsyncComponent for ShowDetailsPage {
                         set_paintable: model.backdrop_texture.as_ref(),
                     },
 
+                    // Scrim keeping the poster and title readable over bright backdrops
+                    add_overlay = &gtk::Box {
+                        add_css_class: "hero-scrim",
+                        set_can_target: false,
+                        #[watch]
+                        set_visible: model.backdrop_texture.is_some(),
+                    },
+
+                    // Wash of the artwork's dominant color
+                    add_overlay: &model.hero_tint,
+
                     // Enhanced gradient overlay with glass morphism
                     add_overlay = &gtk::Box {
                         add_css_class: "hero-gradient-modern",
@@ -376,6 +518,62 @@ impl AsyncComponent for ShowDetailsPage {
                                         connect_clicked => ShowDetailsInput::ToggleSeasonWatched,
                                     },
 
+                                    gtk::Button {
+                                        add_css_class: "pill",
+                                        add_css_class: "favorite-toggle",
+                                        #[watch]
+                                        set_class_active: ("favorited", model.favorite),
+                                        #[watch]
+                                        update_state: &[accessibility::pressed(model.favorite)],
+                                        #[watch]
+                                        set_tooltip_text: Some(if model.favorite {
+                                            "Remove from Favorites"
+                                        } else {
+                                            "Add to Favorites"
+                                        }),
+                                        #[watch]
+                                        set_sensitive: !model.offline,
+
+                                        adw::ButtonContent {
+                                            set_icon_name: "emblem-favorite-symbolic",
+                                            #[watch]
+                                            set_label: if model.favorite {
+                                                "Favorite"
+                                            } else {
+                                                "Add to Favorites"
+                                            },
+                                        },
+
+                                        connect_clicked => ShowDetailsInput::ToggleFavorite,
+                                    },
+
+                                    #[name = "download_show_button"]
+                                    gtk::MenuButton {
+                                        add_css_class: "pill",
+                                        set_tooltip_text: Some("Download every episode for offline playback"),
+                                        #[wrap(Some)]
+                                        set_child = &adw::ButtonContent {
+                                            set_icon_name: "folder-download-symbolic",
+                                            set_label: "Download Show",
+                                        },
+                                    },
+
+                                    gtk::Button {
+                                        add_css_class: "pill",
+                                        set_tooltip_text: Some("Delete the show and its episodes from the server"),
+                                        #[watch]
+                                        set_visible: model.can_delete,
+                                        #[watch]
+                                        set_sensitive: !model.offline,
+
+                                        adw::ButtonContent {
+                                            set_icon_name: "user-trash-symbolic",
+                                            set_label: "Delete from Server",
+                                        },
+
+                                        connect_clicked => ShowDetailsInput::ConfirmDelete,
+                                    },
+
                                     // Sync status indicator
                                     append: &model.sync_indicator,
                                 },
@@ -392,6 +590,61 @@ impl AsyncComponent for ShowDetailsPage {
                                     },
 
                                     append: &model.season_dropdown,
+                                    append: &model.season_progress_ring,
+                                    append: &model.season_progress_label,
+
+                                    gtk::ToggleButton {
+                                        add_css_class: "flat",
+                                        set_icon_name: "view-conceal-symbolic",
+                                        set_tooltip_text: Some("Only show episodes not watched yet"),
+                                        update_property: &[gtk::accessible::Property::Label("Unplayed Only")],
+                                        connect_toggled[sender] => move |button| {
+                                            sender.input(ShowDetailsInput::SetUnplayedOnly(button.is_active()));
+                                        },
+                                    },
+
+                                    #[name = "download_season_button"]
+                                    gtk::MenuButton {
+                                        add_css_class: "flat",
+                                        set_icon_name: "folder-download-symbolic",
+                                        set_tooltip_text: Some("Download this season for offline playback"),
+                                        update_property: &[gtk::accessible::Property::Label("Download Season")],
+                                    },
+                                },
+
+                                // Keep the next unwatched episodes downloaded
+                                gtk::Box {
+                                    set_orientation: gtk::Orientation::Horizontal,
+                                    set_spacing: 12,
+                                    set_margin_top: 12,
+
+                                    gtk::Label {
+                                        set_label: "Auto-download:",
+                                        add_css_class: "body",
+                                    },
+
+                                    append: &model.auto_download_dropdown,
+                                },
+
+                                // Override the auto-skip settings for this show
+                                gtk::Box {
+                                    set_orientation: gtk::Orientation::Horizontal,
+                                    set_spacing: 12,
+                                    set_margin_top: 12,
+
+                                    gtk::Label {
+                                        set_label: "Skip intros:",
+                                        add_css_class: "body",
+                                    },
+
+                                    append: &model.skip_intro_dropdown,
+
+                                    gtk::Label {
+                                        set_label: "Skip credits:",
+                                        add_css_class: "body",
+                                    },
+
+                                    append: &model.skip_credits_dropdown,
                                 },
                             },
                         },
@@ -405,6 +658,116 @@ impl AsyncComponent for ShowDetailsPage {
                     set_margin_top: 12,  // Reduce top margin to bring content up
                     set_spacing: 20,
 
+                    // Next episode to watch
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 20,
+                        add_css_class: "card",
+                        add_css_class: "next-up-spotlight",
+                        #[watch]
+                        set_visible: model.next_up.is_some(),
+
+                        gtk::Picture {
+                            set_width_request: 320,
+                            set_height_request: 180,
+                            set_content_fit: gtk::ContentFit::Cover,
+                            add_css_class: "next-up-thumbnail",
+                            #[watch]
+                            set_paintable: model.next_up_texture.as_ref(),
+                        },
+
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_valign: gtk::Align::Center,
+                            set_spacing: 8,
+                            set_hexpand: true,
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+                                add_css_class: "caption-heading",
+                                add_css_class: "accent",
+                                #[watch]
+                                set_label: if model.next_up_resumes() { "CONTINUE WATCHING" } else { "UP NEXT" },
+                            },
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+                                set_wrap: true,
+                                add_css_class: "title-2",
+                                #[watch]
+                                set_label: &model.next_up.as_ref()
+                                    .map(|ep| ep.title.clone())
+                                    .unwrap_or_default(),
+                            },
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+                                add_css_class: "dim-label",
+                                #[watch]
+                                set_label: &model.next_up.as_ref()
+                                    .map(|ep| format!(
+                                        "Season {} · Episode {} · {}m",
+                                        ep.season_number,
+                                        ep.episode_number,
+                                        ep.duration.as_secs() / 60
+                                    ))
+                                    .unwrap_or_default(),
+                            },
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+                                set_wrap: true,
+                                set_max_width_chars: 80,
+                                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                                set_lines: 2,
+                                #[watch]
+                                set_label: &model.next_up.as_ref()
+                                    .and_then(|ep| ep.overview.clone())
+                                    .unwrap_or_default(),
+                                #[watch]
+                                set_visible: model.next_up.as_ref()
+                                    .and_then(|ep| ep.overview.as_ref())
+                                    .is_some(),
+                            },
+
+                            gtk::ProgressBar {
+                                set_width_request: 320,
+                                set_halign: gtk::Align::Start,
+                                #[watch]
+                                set_fraction: model.next_up.as_ref()
+                                    .and_then(|ep| ep.playback_position.map(|p| {
+                                        p.as_secs_f64() / ep.duration.as_secs_f64().max(1.0)
+                                    }))
+                                    .unwrap_or(0.0),
+                                #[watch]
+                                set_visible: model.next_up_resumes(),
+                            },
+
+                            gtk::Button {
+                                set_halign: gtk::Align::Start,
+                                set_margin_top: 8,
+                                add_css_class: "pill",
+                                add_css_class: "suggested-action",
+                                add_css_class: "next-up-play",
+
+                                adw::ButtonContent {
+                                    set_icon_name: "media-playback-start-symbolic",
+                                    #[watch]
+                                    set_label: &model.next_up.as_ref()
+                                        .map(|ep| format!(
+                                            "{} S{}E{}",
+                                            if model.next_up_resumes() { "Resume" } else { "Play" },
+                                            ep.season_number,
+                                            ep.episode_number
+                                        ))
+                                        .unwrap_or_default(),
+                                },
+
+                                connect_clicked => ShowDetailsInput::PlayNextUp,
+                            },
+                        },
+                    },
+
                     // Episodes - moved to top priority
                     gtk::Box {
                         set_orientation: gtk::Orientation::Vertical,
@@ -426,6 +789,25 @@ impl AsyncComponent for ShowDetailsPage {
                         },
                     },
 
+                    // More from the show's networks and collections
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 6,
+                        #[watch]
+                        set_visible: model.show.as_ref().map(|s| !s.studios.is_empty() || !s.collections.is_empty()).unwrap_or(false),
+
+                        append: &model.studio_box,
+                    },
+
+                    // Tags and labels, each browsing the library for the others with it
+                    gtk::Box {
+                        #[watch]
+                        set_visible: model.can_edit_labels
+                            || model.show.as_ref().map(|s| !s.tags.is_empty()).unwrap_or(false),
+
+                        append: model.label_chips.widget(),
+                    },
+
                     // Cast
                     gtk::Box {
                         set_orientation: gtk::Orientation::Vertical,
@@ -483,6 +865,83 @@ impl AsyncComponent for ShowDetailsPage {
             });
         }
 
+        // Typing while an episode has focus finds another in the season
+        {
+            let sender = sender.clone();
+            let keys = gtk::EventControllerKey::new();
+            keys.connect_key_pressed(move |_, key, _, state| {
+                let modified = state.intersects(
+                    gtk::gdk::ModifierType::CONTROL_MASK | gtk::gdk::ModifierType::ALT_MASK,
+                );
+                match key.to_unicode() {
+                    Some(c) if !modified && c.is_alphanumeric() => {
+                        sender.input(ShowDetailsInput::FindEpisode(c));
+                        gtk::glib::Propagation::Stop
+                    }
+                    _ => gtk::glib::Propagation::Proceed,
+                }
+            });
+            episode_grid.add_controller(keys);
+        }
+
+        let season_progress_ring = gtk::DrawingArea::builder()
+            .content_width(SEASON_RING_SIZE)
+            .content_height(SEASON_RING_SIZE)
+            .valign(gtk::Align::Center)
+            .css_classes(["season-progress-ring"])
+            .visible(false)
+            .build();
+        let season_progress_label = gtk::Label::builder()
+            .css_classes(["dim-label", "caption", "numeric"])
+            .visible(false)
+            .build();
+
+        let auto_download_labels: Vec<String> = AUTO_DOWNLOAD_COUNTS
+            .iter()
+            .map(|count| match count {
+                0 => "Off".to_string(),
+                1 => "Next episode".to_string(),
+                n => format!("Next {} episodes", n),
+            })
+            .collect();
+        let auto_download_labels: Vec<&str> =
+            auto_download_labels.iter().map(String::as_str).collect();
+        let auto_download_dropdown = gtk::DropDown::from_strings(&auto_download_labels);
+        auto_download_dropdown.set_tooltip_text(Some(
+            "Keep the next unwatched episodes downloaded for offline playback",
+        ));
+
+        {
+            let sender = sender.clone();
+            auto_download_dropdown.connect_selected_notify(move |dropdown| {
+                sender.input(ShowDetailsInput::SetAutoDownload(dropdown.selected()));
+            });
+        }
+
+        let skip_mode_labels: Vec<&str> = std::iter::once("Default")
+            .chain(SkipMode::ALL.iter().map(|mode| mode.label()))
+            .collect();
+        let skip_dropdown = |kind: SkipKind, tooltip: &str| {
+            let dropdown = gtk::DropDown::from_strings(&skip_mode_labels);
+            dropdown.set_tooltip_text(Some(tooltip));
+            let sender = sender.clone();
+            dropdown.connect_selected_notify(move |dropdown| {
+                sender.input(ShowDetailsInput::SetSkipMode {
+                    kind,
+                    mode: skip_mode_at(dropdown.selected()),
+                });
+            });
+            dropdown
+        };
+        let skip_intro_dropdown = skip_dropdown(
+            SkipKind::Intro,
+            "Whether intros of this show are skipped, Default follows the playback settings",
+        );
+        let skip_credits_dropdown = skip_dropdown(
+            SkipKind::Credits,
+            "Whether credits of this show are skipped, Default follows the playback settings",
+        );
+
         // Create the image loader worker
         let image_loader =
             ImageLoader::builder()
@@ -505,8 +964,32 @@ impl AsyncComponent for ShowDetailsPage {
             .spacing(16)
             .css_classes(["stagger-animation"])
             .build();
+        let studio_box = gtk::Box::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .spacing(8)
+            .build();
+        let input_sender = sender.input_sender().clone();
+        let label_chips = LabelChips::new(move |action| {
+            input_sender.emit(match action {
+                ChipAction::Browse(tag) => {
+                    ShowDetailsInput::BrowseLibrary(FilterState::for_tag(&tag))
+                }
+                ChipAction::Remove(label) => ShowDetailsInput::SetLabel {
+                    label,
+                    present: false,
+                },
+                ChipAction::Add => ShowDetailsInput::AddLabel,
+            });
+        });
 
         // Create sync status indicator
+        // Tinted once the artwork loads, right away if its color is known
+        let hero_tint = gtk::Box::builder()
+            .css_classes(["hero-tint"])
+            .can_target(false)
+            .build();
+        tint::apply(&hero_tint, dominant_color::cached(init.0.as_str()));
+
         let sync_indicator = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         sync_indicator.set_visible(false);
 
@@ -520,37 +1003,65 @@ impl AsyncComponent for ShowDetailsPage {
             loading: true,
             episode_grid,
             season_dropdown,
+            season_progress: HashMap::new(),
+            season_progress_ring,
+            season_progress_label,
+            next_up: None,
+            next_up_texture: None,
+            auto_download_dropdown,
+            auto_download_count: 0,
+            skip_intro_dropdown,
+            skip_credits_dropdown,
+            skip_modes: ShowSkipModes::default(),
             cast_box: cast_box.clone(),
+            studio_box: studio_box.clone(),
+            label_chips,
             poster_texture: None,
             backdrop_texture: None,
+            hero_tint,
             image_loader,
             episode_pictures: HashMap::new(),
             episode_popovers: HashMap::new(),
+            unplayed_only: false,
+            episode_type_ahead: TypeAhead::default(),
             person_textures: HashMap::new(),
             full_metadata_loaded: false,
+            favorite: false,
+            can_delete: false,
+            can_edit_labels: false,
+            offline: connectivity::is_offline(),
+            theme_music: None,
             sync_status: crate::ui::shared::sync_status::SyncStatus::Idle,
             failed_syncs: Vec::new(),
             sync_indicator,
+            // Subscribe to MessageBroker for playback progress updates
+            _broker_subscription: BROKER.subscribe(
+                &[Topic::Data, Topic::PlaybackSync, Topic::Connectivity],
+                sender.input_sender(),
+                ShowDetailsInput::BrokerMsg,
+            ),
         };
 
         let widgets = view_output!();
 
-        // Subscribe to MessageBroker for playback progress updates
-        {
-            let broker_sender = sender.input_sender().clone();
-            relm4::spawn(async move {
-                let (tx, rx) = relm4::channel::<BrokerMessage>();
-                BROKER.subscribe("ShowDetailsPage".to_string(), tx).await;
-
-                while let Some(msg) = rx.recv().await {
-                    broker_sender
-                        .send(ShowDetailsInput::BrokerMsg(msg))
-                        .unwrap();
-                }
-            });
-        }
+        widgets
+            .download_show_button
+            .set_popover(Some(&download_quality_popover(
+                &sender,
+                DownloadScope::Show,
+            )));
+        widgets
+            .download_season_button
+            .set_popover(Some(&download_quality_popover(
+                &sender,
+                DownloadScope::CurrentSeason,
+            )));
 
         sender.oneshot_command(async { ShowDetailsCommand::LoadDetails });
+        model.load_auto_download(&sender);
+        model.load_theme_music(&sender);
+        model.load_delete_permission(&sender);
+        model.load_labels_editable(&sender);
 
         AsyncComponentParts { model, widgets }
     }
@@ -559,7 +1070,7 @@ impl AsyncComponent for ShowDetailsPage {
         &mut self,
         msg: Self::Input,
         sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         match msg {
             ShowDetailsInput::LoadShow(item_id) => {
@@ -567,15 +1078,35 @@ impl AsyncComponent for ShowDetailsPage {
                 self.show = None;
                 self.episodes.clear();
                 self.season_numbers.clear();
+                self.season_progress.clear();
+                self.update_season_progress_indicator();
+                self.next_up = None;
+                self.next_up_texture = None;
                 self.loading = true;
                 self.poster_texture = None;
                 self.backdrop_texture = None;
+                self.theme_music = None;
+                tint::apply(
+                    &self.hero_tint,
+                    dominant_color::cached(self.item_id.as_str()),
+                );
                 sender.oneshot_command(async { ShowDetailsCommand::LoadDetails });
+                self.load_auto_download(&sender);
+                self.load_skip_modes(&sender);
+                self.load_theme_music(&sender);
+                self.can_delete = false;
+                self.load_delete_permission(&sender);
+                self.can_edit_labels = false;
+                self.load_labels_editable(&sender);
             }
             ShowDetailsInput::SelectSeason(season_index) => {
                 // Look up the actual season number from the stored mapping
                 if let Some(&season_num) = self.season_numbers.get(season_index as usize) {
                     self.current_season = season_num;
+                    self.update_season_progress_indicator();
+                    sender
+                        .output(ShowDetailsOutput::SeasonSelected(season_num))
+                        .unwrap();
                     if let Some(show) = &self.show {
                         let show_id = show.id.clone();
                         sender.oneshot_command(async move {
@@ -614,29 +1145,88 @@ impl AsyncComponent for ShowDetailsPage {
                     }
                 });
             }
+            ShowDetailsInput::FindEpisode(c) => {
+                let text = self.episode_type_ahead.push(c).to_owned();
+                let found = match text.parse::<u32>() {
+                    Ok(number) => self
+                        .episodes
+                        .iter()
+                        .position(|episode| episode.episode_number == number),
+                    Err(_) => type_ahead::find(
+                        self.episodes.iter().map(|episode| episode.title.as_str()),
+                        &text,
+                    ),
+                };
+                self.highlight_episode(found);
+            }
             ShowDetailsInput::ToggleEpisodeWatched(index) => {
                 if let Some(episode) = self.episodes.get(index) {
                     let db = (*self.db).clone();
                     let media_id = MediaItemId::new(&episode.id);
                     let watched = episode.watched;
 
-                    relm4::spawn(async move {
-                        let result = if watched {
-                            // Mark as unwatched
+                    if watched {
+                        relm4::spawn(async move {
                             let cmd = MarkUnwatchedCommand { db, media_id };
-                            Command::execute(&cmd).await
-                        } else {
-                            // Mark as watched
-                            let cmd = MarkWatchedCommand { db, media_id };
-                            Command::execute(&cmd).await
-                        };
-
-                        if let Err(e) = result {
-                            error!("Failed to toggle episode watch status: {}", e);
-                        }
+                            if let Err(e) = Command::execute(&cmd).await {
+                                error!("Failed to toggle episode watch status: {}", e);
+                            }
+                        });
+                    } else {
+                        let cmd = MarkWatchedCommand { db, media_id };
+                        sender.oneshot_command(async move {
+                            ShowDetailsCommand::OfferUndo(
+                                UndoOffer::run(
+                                    cmd,
+                                    "Episode marked as watched",
+                                    "Failed to toggle episode watch status",
+                                )
+                                .await,
+                            )
+                        });
+                    }
+                }
+            }
+            ShowDetailsInput::MarkPreviousWatched(index) => {
+                if let (Some(show), Some(episode)) = (&self.show, self.episodes.get(index)) {
+                    let cmd = MarkPreviousEpisodesWatchedCommand {
+                        db: (*self.db).clone(),
+                        show_id: ShowId::new(show.id.clone()),
+                        season_number: episode.season_number,
+                        episode_number: episode.episode_number,
+                    };
+                    sender.oneshot_command(async move {
+                        ShowDetailsCommand::OfferUndo(
+                            UndoOffer::run(
+                                cmd,
+                                "Previous episodes marked as watched",
+                                "Failed to mark previous episodes as watched",
+                            )
+                            .await,
+                        )
                     });
                 }
             }
+            ShowDetailsInput::SetUnplayedOnly(unplayed_only) => {
+                self.unplayed_only = unplayed_only;
+                self.apply_unplayed_only();
+            }
+            ShowDetailsInput::ToggleFavorite => {
+                let db = (*self.db).clone();
+                let media_id = self.item_id.clone();
+                let favorite = !self.favorite;
+
+                relm4::spawn(async move {
+                    let cmd = SetFavoriteCommand {
+                        db,
+                        media_id,
+                        favorite,
+                    };
+                    if let Err(e) = Command::execute(&cmd).await {
+                        error!("Failed to update favorite: {}", e);
+                    }
+                });
+            }
             ShowDetailsInput::ToggleShowWatched => {
                 if let Some(show) = &self.show {
                     let db = (*self.db).clone();
@@ -726,6 +1316,166 @@ impl AsyncComponent for ShowDetailsPage {
                     });
                 }
             }
+            ShowDetailsInput::SetAutoDownload(index) => {
+                let Some(&count) = AUTO_DOWNLOAD_COUNTS.get(index as usize) else {
+                    return;
+                };
+                // Selecting the loaded rule programmatically must not rewrite it
+                if count == self.auto_download_count {
+                    return;
+                }
+                self.auto_download_count = count;
+
+                let db = (*self.db).clone();
+                let show_id = self.item_id.clone();
+                relm4::spawn(async move {
+                    if let Err(e) = DownloadService::set_auto_download(&db, &show_id, count).await {
+                        error!("Failed to update auto-download: {}", e);
+                    }
+                });
+            }
+            ShowDetailsInput::RequestDownload { scope, quality } => {
+                let (what, season, episode) = match scope {
+                    DownloadScope::Show => (
+                        self.show
+                            .as_ref()
+                            .map(|show| show.title.clone())
+                            .unwrap_or_else(|| "Show".to_string()),
+                        None,
+                        None,
+                    ),
+                    DownloadScope::CurrentSeason => (
+                        format!("Season {}", self.current_season),
+                        Some(self.current_season),
+                        None,
+                    ),
+                    DownloadScope::Episode(index) => {
+                        let Some(episode) = self.episodes.get(index) else {
+                            return;
+                        };
+                        (
+                            format!("Episode {}", episode.episode_number),
+                            None,
+                            Some(MediaItemId::new(&episode.id)),
+                        )
+                    }
+                };
+
+                let db = (*self.db).clone();
+                let show_id = self.item_id.clone();
+                let input_sender = sender.input_sender().clone();
+                relm4::spawn(async move {
+                    let media_ids = match episode {
+                        Some(episode_id) => Ok(vec![episode_id]),
+                        None => DownloadService::show_episode_ids(&db, &show_id, season).await,
+                    };
+                    let estimate = match media_ids {
+                        Ok(media_ids) => {
+                            DownloadService::estimate_downloads(&db, &media_ids, &quality).await
+                        }
+                        Err(e) => Err(e),
+                    };
+                    match estimate {
+                        Ok(estimate) => input_sender.emit(ShowDetailsInput::ConfirmDownload {
+                            what,
+                            quality,
+                            estimate,
+                        }),
+                        Err(e) => error!("Failed to estimate download size: {}", e),
+                    }
+                });
+            }
+            ShowDetailsInput::ConfirmDownload {
+                what,
+                quality,
+                estimate,
+            } => {
+                present_download_confirmation(root, &sender, &what, quality, estimate);
+            }
+            ShowDetailsInput::QueueDownloads { media_ids, quality } => {
+                let db = (*self.db).clone();
+                relm4::spawn(async move {
+                    if let Err(e) = DownloadService::queue_downloads(&db, &media_ids, quality).await
+                    {
+                        error!("Failed to queue downloads: {}", e);
+                    }
+                });
+            }
+            ShowDetailsInput::SetSkipMode { kind, mode } => {
+                let current = match kind {
+                    SkipKind::Intro => &mut self.skip_modes.intro,
+                    SkipKind::Credits => &mut self.skip_modes.credits,
+                };
+                // Selecting the loaded override programmatically must not rewrite it
+                if *current == mode {
+                    return;
+                }
+                *current = mode;
+
+                let db = (*self.db).clone();
+                let show_id = self.item_id.clone();
+                relm4::spawn(async move {
+                    if let Err(e) =
+                        SkipPreferencesService::set_mode(&db, &show_id, kind, mode).await
+                    {
+                        error!("Failed to update skip override: {}", e);
+                    }
+                });
+            }
+            ShowDetailsInput::SkipModesLoaded(modes) => {
+                self.skip_modes = modes;
+                self.skip_intro_dropdown
+                    .set_selected(skip_mode_index(modes.intro));
+                self.skip_credits_dropdown
+                    .set_selected(skip_mode_index(modes.credits));
+            }
+            ShowDetailsInput::AutoDownloadLoaded(count) => {
+                self.auto_download_count = count;
+                let index = AUTO_DOWNLOAD_COUNTS
+                    .iter()
+                    .position(|&c| c == count)
+                    .unwrap_or(0);
+                self.auto_download_dropdown.set_selected(index as u32);
+            }
+            ShowDetailsInput::SeasonProgressLoaded(progress) => {
+                if progress == self.season_progress {
+                    return;
+                }
+                self.season_progress = progress;
+
+                // A fresh factory rebinds the popup rows with the new counts
+                let rows = self
+                    .season_numbers
+                    .iter()
+                    .map(|season| self.season_progress.get(season).copied())
+                    .collect();
+                self.season_dropdown
+                    .set_list_factory(Some(&season_list_factory(rows)));
+                self.update_season_progress_indicator();
+            }
+            ShowDetailsInput::NextUpLoaded(episode) => {
+                let same_episode =
+                    self.next_up.as_ref().map(|ep| &ep.id) == episode.as_ref().map(|ep| &ep.id);
+                if !same_episode {
+                    self.next_up_texture = None;
+                    if let Some(url) = episode.as_ref().and_then(|ep| ep.thumbnail_url.clone()) {
+                        let _ = self.image_loader.sender().send(ImageLoaderInput::LoadImage(
+                            ImageRequest {
+                                id: NEXT_UP_IMAGE_ID.to_string(),
+                                url,
+                                size: ImageSize::Custom(320, 180),
+                                priority: 0,
+                            },
+                        ));
+                    }
+                }
+                self.next_up = episode;
+            }
+            ShowDetailsInput::PlayNextUp => {
+                if let Some(episode) = &self.next_up {
+                    sender.input(ShowDetailsInput::PlayEpisode(MediaItemId::new(&episode.id)));
+                }
+            }
             ShowDetailsInput::LoadEpisodes => {
                 if let Some(show) = &self.show {
                     let show_id = show.id.clone();
@@ -735,6 +1485,9 @@ impl AsyncComponent for ShowDetailsPage {
                     });
                 }
             }
+            ShowDetailsInput::ImageLoaded { id, texture } if id == NEXT_UP_IMAGE_ID => {
+                self.next_up_texture = Some(texture);
+            }
             ShowDetailsInput::ImageLoaded { id, texture } => {
                 // Find the picture widget for this episode
                 if let Ok(index) = id.parse::<usize>()
@@ -752,6 +1505,82 @@ impl AsyncComponent for ShowDetailsPage {
                     picture.remove_css_class("loading");
                 }
             }
+            ShowDetailsInput::AddLabel => {
+                let input_sender = sender.input_sender().clone();
+                label_chips::ask_for_label(root, move |label| {
+                    input_sender.emit(ShowDetailsInput::SetLabel {
+                        label,
+                        present: true,
+                    });
+                });
+            }
+            ShowDetailsInput::SetLabel { label, present } => {
+                let cmd = SetLabelCommand {
+                    db: (*self.db).clone(),
+                    media_id: self.item_id.clone(),
+                    label,
+                    present,
+                };
+                let output = sender.output_sender().clone();
+                sender.oneshot_command(async move {
+                    match cmd.execute_undoable().await {
+                        // Only taking a label off is offered back
+                        Ok(undo) => ShowDetailsCommand::OfferUndo(
+                            (!present).then(|| UndoOffer::new("Label removed", undo)),
+                        ),
+                        Err(e) => {
+                            error!("Failed to update labels: {}", e);
+                            output.emit(ShowDetailsOutput::ShowToast(format!(
+                                "Couldn't update labels: {}",
+                                e
+                            )));
+                            ShowDetailsCommand::OfferUndo(None)
+                        }
+                    }
+                });
+            }
+            ShowDetailsInput::ConfirmDelete => {
+                let Some(show) = &self.show else {
+                    return;
+                };
+                let what = format!("“{}” and all of its episodes", show.title);
+                let input_sender = sender.input_sender().clone();
+                delete_confirmation::present(root, &what, move || {
+                    input_sender.emit(ShowDetailsInput::DeleteFromServer);
+                });
+            }
+            ShowDetailsInput::DeleteFromServer => {
+                let cmd = DeleteFromServerCommand {
+                    db: (*self.db).clone(),
+                    media_id: self.item_id.clone(),
+                    version_id: None,
+                };
+                let output = sender.output_sender().clone();
+                relm4::spawn(async move {
+                    if let Err(e) = Command::execute(&cmd).await {
+                        error!("Failed to delete from the server: {}", e);
+                        output.emit(ShowDetailsOutput::ShowToast(format!(
+                            "Couldn't delete from the server: {}",
+                            e
+                        )));
+                    }
+                });
+            }
+            ShowDetailsInput::BrowseLibrary(filter) => {
+                let media_repo = MediaRepositoryImpl::new((*self.db).clone());
+                match media_repo.find_by_id(self.item_id.as_str()).await {
+                    Ok(Some(item)) => {
+                        sender
+                            .output(ShowDetailsOutput::NavigateToLibraryView {
+                                library_id: LibraryId::new(item.library_id),
+                                filter,
+                            })
+                            .unwrap();
+                    }
+                    Ok(None) => error!("Show {} not found", self.item_id),
+                    Err(e) => error!("Failed to find the show's library: {}", e),
+                }
+            }
             ShowDetailsInput::BrokerMsg(msg) => match msg {
                 BrokerMessage::Data(data_msg) => match data_msg {
                     crate::ui::shared::broker::DataMessage::PlaybackProgressUpdated {
@@ -767,6 +1596,10 @@ impl AsyncComponent for ShowDetailsPage {
                             );
                             // Reload episodes to update watch status
                             sender.input(ShowDetailsInput::LoadEpisodes);
+                        } else if watched && self.show.is_some() {
+                            // May be an episode of another season
+                            self.load_season_progress(&sender);
+                            self.load_next_up(&sender);
                         }
                     }
                     crate::ui::shared::broker::DataMessage::MediaUpdated { media_id } => {
@@ -785,6 +1618,19 @@ impl AsyncComponent for ShowDetailsPage {
                             sender.oneshot_command(async { ShowDetailsCommand::LoadDetails });
                         }
                     }
+                    crate::ui::shared::broker::DataMessage::FavoriteChanged {
+                        media_id,
+                        favorite,
+                    } => {
+                        if self.item_id.to_string() == media_id {
+                            self.favorite = favorite;
+                        }
+                    }
+                    crate::ui::shared::broker::DataMessage::MediaDeleted { media_id } => {
+                        if self.item_id.to_string() == media_id {
+                            sender.output(ShowDetailsOutput::NavigateBack).unwrap();
+                        }
+                    }
                     _ => {}
                 },
                 BrokerMessage::PlaybackSync(sync_msg) => {
@@ -830,6 +1676,13 @@ impl AsyncComponent for ShowDetailsPage {
                         _ => {}
                     }
                 }
+                BrokerMessage::Connectivity(change) => {
+                    self.offline = matches!(change, ConnectivityMessage::WentOffline);
+                    if let Some(show) = &self.show {
+                        self.label_chips
+                            .set_labels(&show.tags, self.labels_editable());
+                    }
+                }
                 _ => {}
             },
         }
@@ -839,9 +1692,14 @@ impl AsyncComponent for ShowDetailsPage {
         &mut self,
         msg: Self::CommandOutput,
         sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         match msg {
+            ShowDetailsCommand::OfferUndo(offer) => {
+                if let Some(offer) = offer {
+                    offer.present(root);
+                }
+            }
             ShowDetailsCommand::LoadDetails => {
                 let cmd = GetItemDetailsCommand {
                     db: (*self.db).clone(),
@@ -871,6 +1729,11 @@ impl AsyncComponent for ShowDetailsPage {
                             self.show = Some(show.clone());
                             self.loading = false;
 
+                            match FavoritesService::is_favorite(&self.db, &self.item_id).await {
+                                Ok(favorite) => self.favorite = favorite,
+                                Err(e) => error!("Failed to load favorite state: {}", e),
+                            }
+
                             tracing::info!(
                                 "Show loaded: watched_count={}, total_count={}",
                                 show.watched_episode_count,
@@ -890,9 +1753,14 @@ impl AsyncComponent for ShowDetailsPage {
                             }
 
                             // Load poster and backdrop images
+                            // The backdrop gives the page its color, or the poster without one
                             if let Some(poster_url) = show.poster_url.clone() {
+                                let find_color = show.backdrop_url.is_none();
                                 sender.oneshot_command(async move {
-                                    ShowDetailsCommand::LoadPosterImage { url: poster_url }
+                                    ShowDetailsCommand::LoadPosterImage {
+                                        url: poster_url,
+                                        find_color,
+                                    }
                                 });
                             }
 
@@ -902,6 +1770,45 @@ impl AsyncComponent for ShowDetailsPage {
                                 });
                             }
 
+                            // Update network links
+                            while let Some(child) = self.studio_box.first_child() {
+                                self.studio_box.remove(&child);
+                            }
+
+                            for studio in &show.studios {
+                                let button = gtk::Button::builder()
+                                    .label(format!("More from {}", studio))
+                                    .css_classes(["pill"])
+                                    .build();
+                                let input_sender = sender.input_sender().clone();
+                                let studio = studio.clone();
+                                button.connect_clicked(move |_| {
+                                    input_sender.emit(ShowDetailsInput::BrowseLibrary(
+                                        FilterState::for_studio(&studio),
+                                    ));
+                                });
+                                self.studio_box.append(&button);
+                            }
+
+                            for collection in &show.collections {
+                                let button = gtk::Button::builder()
+                                    .label(format!("{} Collection", collection))
+                                    .css_classes(["pill"])
+                                    .build();
+                                let input_sender = sender.input_sender().clone();
+                                let collection = collection.clone();
+                                button.connect_clicked(move |_| {
+                                    input_sender.emit(ShowDetailsInput::BrowseLibrary(
+                                        FilterState::for_collection(&collection),
+                                    ));
+                                });
+                                self.studio_box.append(&button);
+                            }
+
+                            // Update tag chips
+                            self.label_chips
+                                .set_labels(&show.tags, self.labels_editable());
+
                             // Update cast cards
                             while let Some(child) = self.cast_box.first_child() {
                                 self.cast_box.remove(&child);
@@ -1010,6 +1917,8 @@ impl AsyncComponent for ShowDetailsPage {
                             self.episodes.len()
                         );
                         self.update_episode_grid(&sender);
+                        self.load_season_progress(&sender);
+                        self.load_next_up(&sender);
                     }
                     Err(e) => {
                         tracing::error!("Failed to load episodes: {}", e);
@@ -1034,14 +1943,22 @@ impl AsyncComponent for ShowDetailsPage {
                     .output(ShowDetailsOutput::PlayMedia(episode_id))
                     .unwrap();
             }
-            ShowDetailsCommand::LoadPosterImage { url } => {
+            ShowDetailsCommand::LoadPosterImage { url, find_color } => {
                 // Spawn async task to download and create texture
                 let sender_clone = sender.clone();
+                let item_id = self.item_id.to_string();
                 relm4::spawn(async move {
-                    match load_image_from_url(&url, 300, 450).await {
-                        Ok(texture) => {
+                    let result = if find_color {
+                        load_image_with_color(&url, 300, 450, &item_id).await
+                    } else {
+                        load_image_from_url(&url, 300, 450)
+                            .await
+                            .map(|texture| (texture, None))
+                    };
+                    match result {
+                        Ok((texture, color)) => {
                             sender_clone.oneshot_command(async move {
-                                ShowDetailsCommand::PosterImageLoaded { texture }
+                                ShowDetailsCommand::PosterImageLoaded { texture, color }
                             });
                         }
                         Err(e) => {
@@ -1053,11 +1970,12 @@ impl AsyncComponent for ShowDetailsPage {
             ShowDetailsCommand::LoadBackdropImage { url } => {
                 // Spawn async task to download and create texture
                 let sender_clone = sender.clone();
+                let item_id = self.item_id.to_string();
                 relm4::spawn(async move {
-                    match load_image_from_url(&url, -1, 550).await {
-                        Ok(texture) => {
+                    match load_image_with_color(&url, -1, 550, &item_id).await {
+                        Ok((texture, color)) => {
                             sender_clone.oneshot_command(async move {
-                                ShowDetailsCommand::BackdropImageLoaded { texture }
+                                ShowDetailsCommand::BackdropImageLoaded { texture, color }
                             });
                         }
                         Err(e) => {
@@ -1066,11 +1984,36 @@ impl AsyncComponent for ShowDetailsPage {
                     }
                 });
             }
-            ShowDetailsCommand::PosterImageLoaded { texture } => {
+            ShowDetailsCommand::PosterImageLoaded { texture, color } => {
                 self.poster_texture = Some(texture);
+                if color.is_some() {
+                    tint::apply(&self.hero_tint, color);
+                }
             }
-            ShowDetailsCommand::BackdropImageLoaded { texture } => {
+            ShowDetailsCommand::BackdropImageLoaded { texture, color } => {
                 self.backdrop_texture = Some(texture);
+                if color.is_some() {
+                    tint::apply(&self.hero_tint, color);
+                }
+            }
+            ShowDetailsCommand::ThemeMusicLoaded { item_id, url } => {
+                // The page may have moved on to another show meanwhile
+                if let Some(url) = url.filter(|_| item_id == self.item_id) {
+                    self.theme_music = Some(theme_music::play_while_shown(&url, root));
+                }
+            }
+            ShowDetailsCommand::DeletePermissionLoaded {
+                item_id,
+                can_delete,
+            } => {
+                self.can_delete = can_delete && item_id == self.item_id;
+            }
+            ShowDetailsCommand::LabelsEditableLoaded { item_id, editable } => {
+                self.can_edit_labels = editable && item_id == self.item_id;
+                if let Some(show) = &self.show {
+                    self.label_chips
+                        .set_labels(&show.tags, self.labels_editable());
+                }
             }
             ShowDetailsCommand::LoadPersonImage { person_id, url } => {
                 // Spawn async task to download and create texture
@@ -1193,16 +2136,157 @@ impl AsyncComponent for ShowDetailsPage {
             }
         }
     }
+}
 
-    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
-        // Unsubscribe from MessageBroker
+impl ShowDetailsPage {
+    /// Labels are edited on the server, so not while it can't be reached
+    fn labels_editable(&self) -> bool {
+        self.can_edit_labels && !self.offline
+    }
+
+    /// Recount watched episodes per season for the season selector
+    fn load_season_progress(&self, sender: &AsyncComponentSender<Self>) {
+        let db = (*self.db).clone();
+        let show_id = ShowId::new(self.item_id.to_string());
+        let input_sender = sender.input_sender().clone();
         relm4::spawn(async move {
-            BROKER.unsubscribe("ShowDetailsPage").await;
+            match MediaService::get_season_progress(&db, &show_id).await {
+                Ok(progress) => input_sender.emit(ShowDetailsInput::SeasonProgressLoaded(progress)),
+                Err(e) => error!("Failed to load season progress: {}", e),
+            }
         });
     }
-}
 
-impl ShowDetailsPage {
+    /// Find the episode to spotlight as the next one to watch
+    fn load_next_up(&self, sender: &AsyncComponentSender<Self>) {
+        let db = (*self.db).clone();
+        let show_id = ShowId::new(self.item_id.to_string());
+        let input_sender = sender.input_sender().clone();
+        relm4::spawn(async move {
+            match MediaService::get_next_up_episode(&db, &show_id).await {
+                Ok(episode) => input_sender.emit(ShowDetailsInput::NextUpLoaded(episode)),
+                Err(e) => error!("Failed to find next episode: {}", e),
+            }
+        });
+    }
+
+    /// Whether the spotlighted episode was started and will resume
+    fn next_up_resumes(&self) -> bool {
+        self.next_up
+            .as_ref()
+            .and_then(|ep| ep.playback_position)
+            .is_some_and(|position| !position.is_zero())
+    }
+
+    /// Show the selected season's progress next to the season selector
+    fn update_season_progress_indicator(&self) {
+        match self.season_progress.get(&self.current_season) {
+            Some(progress) if progress.total > 0 => {
+                set_progress_ring_fraction(&self.season_progress_ring, progress.fraction());
+                self.season_progress_label
+                    .set_label(&format!("{}/{} watched", progress.watched, progress.total));
+                self.season_progress_ring.set_visible(true);
+                self.season_progress_label.set_visible(true);
+            }
+            _ => {
+                self.season_progress_ring.set_visible(false);
+                self.season_progress_label.set_visible(false);
+            }
+        }
+    }
+
+    /// Fetch the show's auto-download rule to reflect it in the dropdown
+    fn load_auto_download(&self, sender: &AsyncComponentSender<Self>) {
+        let db = (*self.db).clone();
+        let show_id = self.item_id.clone();
+        let input_sender = sender.input_sender().clone();
+        relm4::spawn(async move {
+            match DownloadService::auto_download_rule(&db, &show_id).await {
+                Ok(rule) => {
+                    let count = rule.map(|r| r.episode_count.max(0) as u32).unwrap_or(0);
+                    input_sender.emit(ShowDetailsInput::AutoDownloadLoaded(count));
+                }
+                Err(e) => error!("Failed to load auto-download rule: {}", e),
+            }
+        });
+    }
+
+    /// Fetch the show's skip overrides to reflect them in the dropdowns
+    fn load_skip_modes(&self, sender: &AsyncComponentSender<Self>) {
+        let db = (*self.db).clone();
+        let show_id = self.item_id.clone();
+        let input_sender = sender.input_sender().clone();
+        relm4::spawn(async move {
+            match SkipPreferencesService::show_modes(&db, &show_id).await {
+                Ok(modes) => input_sender.emit(ShowDetailsInput::SkipModesLoaded(modes)),
+                Err(e) => error!("Failed to load skip overrides: {}", e),
+            }
+        });
+    }
+
+    /// Fetch the show's theme music to play while the page is shown
+    fn load_theme_music(&self, sender: &AsyncComponentSender<Self>) {
+        let db = (*self.db).clone();
+        let item_id = self.item_id.clone();
+        sender.oneshot_command(async move {
+            let url = theme_music_url(&db, &item_id).await.unwrap_or_else(|e| {
+                tracing::debug!("No theme music for {}: {}", item_id, e);
+                None
+            });
+            ShowDetailsCommand::ThemeMusicLoaded { item_id, url }
+        });
+    }
+
+    /// Ask the server whether the user may delete the show
+    fn load_delete_permission(&self, sender: &AsyncComponentSender<Self>) {
+        let db = (*self.db).clone();
+        let item_id = self.item_id.clone();
+        sender.oneshot_command(async move {
+            let can_delete = DeletionService::can_delete(&db, &item_id)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::debug!("Can't tell if {} may be deleted: {}", item_id, e);
+                    false
+                });
+            ShowDetailsCommand::DeletePermissionLoaded {
+                item_id,
+                can_delete,
+            }
+        });
+    }
+
+    /// Look up whether labels of the show can be edited on its server
+    fn load_labels_editable(&self, sender: &AsyncComponentSender<Self>) {
+        let db = (*self.db).clone();
+        let item_id = self.item_id.clone();
+        sender.oneshot_command(async move {
+            let editable = LabelsService::can_edit_labels(&db, &item_id)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::debug!("Can't tell if labels of {} may be edited: {}", item_id, e);
+                    false
+                });
+            ShowDetailsCommand::LabelsEditableLoaded { item_id, editable }
+        });
+    }
+
+    /// Focus and mark the episode at `index`, scrolling it into view
+    fn highlight_episode(&self, index: Option<usize>) {
+        let mut child = self.episode_grid.first_child();
+        while let Some(widget) = child {
+            widget.remove_css_class("type-ahead-match");
+            child = widget.next_sibling();
+        }
+
+        match index.and_then(|index| self.episode_grid.child_at_index(index as i32)) {
+            Some(child) => {
+                child.add_css_class("type-ahead-match");
+                child.grab_focus();
+            }
+            None => self.episode_grid.error_bell(),
+        }
+    }
+
     fn update_episode_grid(&mut self, sender: &AsyncComponentSender<Self>) {
         tracing::debug!(
             "update_episode_grid called with {} episodes",
@@ -1240,6 +2324,16 @@ impl ShowDetailsPage {
         for (index, episode) in self.episodes.iter().enumerate() {
             let (card, picture, popover) = create_episode_card(episode, index, sender.clone());
             self.episode_grid.append(&card);
+
+            // Enter on a focused card plays it, the menu key opens its menu
+            if let Some(child) = card.parent().and_downcast::<gtk::FlowBoxChild>() {
+                let sender = sender.clone();
+                let episode_id = MediaItemId::new(&episode.id);
+                child.connect_activate(move |_| {
+                    sender.input(ShowDetailsInput::PlayEpisode(episode_id.clone()));
+                });
+                keyboard::connect_context_menu_key(&child, &popover);
+            }
             tracing::debug!(
                 "Added episode card {} to grid: '{}' (S{}E{})",
                 index,
@@ -1266,6 +2360,8 @@ impl ShowDetailsPage {
             }
         }
 
+        self.apply_unplayed_only();
+
         // Scroll to the first unwatched episode
         if let Some(first_unwatched_index) = self.episodes.iter().position(|e| !e.watched)
             && let Some(child) = self
@@ -1294,6 +2390,16 @@ impl ShowDetailsPage {
         );
     }
 
+    /// Hide the watched episodes while only unplayed ones are wanted. The
+    /// cards stay in the grid so their indices keep matching `episodes`.
+    fn apply_unplayed_only(&self) {
+        for (index, episode) in self.episodes.iter().enumerate() {
+            if let Some(child) = self.episode_grid.child_at_index(index as i32) {
+                child.set_visible(!(self.unplayed_only && episode.watched));
+            }
+        }
+    }
+
     fn update_sync_indicator(&mut self) {
         use crate::ui::shared::sync_status::create_sync_status_indicator;
 
@@ -1365,6 +2471,231 @@ async fn find_season_with_next_unwatched(
     show.seasons.first().map(|s| s.season_number).unwrap_or(1)
 }
 
+/// Draw a circular progress ring filled clockwise from the top
+/// Skip override picked in a skip dropdown, whose first entry follows the
+/// playback settings
+fn skip_mode_at(index: u32) -> Option<SkipMode> {
+    index
+        .checked_sub(1)
+        .and_then(|index| SkipMode::ALL.get(index as usize).copied())
+}
+
+/// Skip dropdown entry of an override
+fn skip_mode_index(mode: Option<SkipMode>) -> u32 {
+    mode.and_then(|mode| SkipMode::ALL.iter().position(|&m| m == mode))
+        .map_or(0, |index| index as u32 + 1)
+}
+
+fn set_progress_ring_fraction(ring: &gtk::DrawingArea, fraction: f64) {
+    let fraction = fraction.clamp(0.0, 1.0);
+    ring.set_draw_func(move |area, cr, width, height| {
+        let color = area.color();
+        let line_width = 3.0;
+        let (cx, cy) = (width as f64 / 2.0, height as f64 / 2.0);
+        let radius = cx.min(cy) - line_width / 2.0;
+        let start = -std::f64::consts::FRAC_PI_2;
+
+        cr.set_line_width(line_width);
+        cr.set_source_rgba(
+            color.red() as f64,
+            color.green() as f64,
+            color.blue() as f64,
+            color.alpha() as f64 * 0.25,
+        );
+        cr.arc(cx, cy, radius, 0.0, std::f64::consts::TAU);
+        let _ = cr.stroke();
+
+        if fraction > 0.0 {
+            cr.set_source_rgba(
+                color.red() as f64,
+                color.green() as f64,
+                color.blue() as f64,
+                color.alpha() as f64,
+            );
+            cr.arc(
+                cx,
+                cy,
+                radius,
+                start,
+                start + fraction * std::f64::consts::TAU,
+            );
+            let _ = cr.stroke();
+        }
+    });
+    ring.queue_draw();
+}
+
+/// Popup rows for the season selector showing each season's watched count
+fn season_list_factory(progress: Vec<Option<SeasonProgress>>) -> gtk::SignalListItemFactory {
+    let factory = gtk::SignalListItemFactory::new();
+
+    factory.connect_setup(|_, item| {
+        let Some(item) = item.downcast_ref::<gtk::ListItem>() else {
+            return;
+        };
+        let row = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        let title = gtk::Label::builder().xalign(0.0).hexpand(true).build();
+        let count = gtk::Label::builder()
+            .css_classes(["dim-label", "caption", "numeric"])
+            .build();
+        let ring = gtk::DrawingArea::builder()
+            .content_width(SEASON_RING_SIZE)
+            .content_height(SEASON_RING_SIZE)
+            .valign(gtk::Align::Center)
+            .css_classes(["season-progress-ring"])
+            .build();
+        row.append(&title);
+        row.append(&count);
+        row.append(&ring);
+        item.set_child(Some(&row));
+    });
+
+    factory.connect_bind(move |_, item| {
+        let Some(item) = item.downcast_ref::<gtk::ListItem>() else {
+            return;
+        };
+        let Some(row) = item.child() else {
+            return;
+        };
+        let (Some(title), Some(count), Some(ring)) = (
+            row.first_child().and_downcast::<gtk::Label>(),
+            row.first_child()
+                .and_then(|w| w.next_sibling())
+                .and_downcast::<gtk::Label>(),
+            row.last_child().and_downcast::<gtk::DrawingArea>(),
+        ) else {
+            return;
+        };
+
+        let label = item
+            .item()
+            .and_downcast::<gtk::StringObject>()
+            .map(|s| s.string().to_string())
+            .unwrap_or_default();
+        title.set_label(&label);
+
+        match progress.get(item.position() as usize).copied().flatten() {
+            Some(season) if season.total > 0 => {
+                count.set_label(&format!("{}/{}", season.watched, season.total));
+                set_progress_ring_fraction(&ring, season.fraction());
+                count.set_visible(true);
+                ring.set_visible(true);
+            }
+            _ => {
+                count.set_visible(false);
+                ring.set_visible(false);
+            }
+        }
+    });
+
+    factory
+}
+
+/// Quality choices for downloading the episodes in `scope`
+fn download_quality_popover(
+    sender: &AsyncComponentSender<ShowDetailsPage>,
+    scope: DownloadScope,
+) -> gtk::Popover {
+    let quality_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(2)
+        .build();
+    let popover = gtk::Popover::builder().child(&quality_box).build();
+    for preset in QualityPreset::all() {
+        let button = gtk::Button::builder()
+            .label(preset.label())
+            .css_classes(["flat"])
+            .build();
+        let input_sender = sender.input_sender().clone();
+        let popover = popover.clone();
+        button.connect_clicked(move |_| {
+            popover.popdown();
+            input_sender.emit(ShowDetailsInput::RequestDownload {
+                scope,
+                quality: preset.clone(),
+            });
+        });
+        quality_box.append(&button);
+    }
+    popover
+}
+
+/// Ask before queueing a batch of downloads, with the space they take
+fn present_download_confirmation(
+    parent: &gtk::ScrolledWindow,
+    sender: &AsyncComponentSender<ShowDetailsPage>,
+    what: &str,
+    quality: QualityPreset,
+    estimate: DownloadEstimate,
+) {
+    let count = estimate.media_ids.len();
+    let free = estimate
+        .available_bytes
+        .map(|bytes| format!(" {} free.", gtk::glib::format_size(bytes)))
+        .unwrap_or_default();
+
+    if count == 0 {
+        let dialog = adw::AlertDialog::builder()
+            .heading("Already Downloaded")
+            .body(format!(
+                "{} is already downloaded or queued at {}.",
+                what,
+                quality.label()
+            ))
+            .build();
+        dialog.add_response("close", "_Close");
+        dialog.present(Some(parent));
+        return;
+    }
+
+    let episodes = if count == 1 {
+        "1 episode".to_string()
+    } else {
+        format!("{} episodes", count)
+    };
+    let size = match estimate.max_bytes {
+        Some(bytes) => format!("up to {}", gtk::glib::format_size(bytes)),
+        None => "size depends on the original files".to_string(),
+    };
+    let short_on_space = estimate
+        .max_bytes
+        .zip(estimate.available_bytes)
+        .is_some_and(|(needed, available)| needed > available);
+    let warning = if short_on_space {
+        " There may not be enough free space."
+    } else {
+        ""
+    };
+
+    let dialog = adw::AlertDialog::builder()
+        .heading(format!("Download {}?", what))
+        .body(format!(
+            "{} at {}, {}.{}{}",
+            episodes,
+            quality.label(),
+            size,
+            free,
+            warning
+        ))
+        .close_response("cancel")
+        .default_response("download")
+        .build();
+    dialog.add_responses(&[("cancel", "_Cancel"), ("download", "_Download")]);
+    dialog.set_response_appearance("download", adw::ResponseAppearance::Suggested);
+
+    let input_sender = sender.input_sender().clone();
+    let media_ids = estimate.media_ids;
+    dialog.connect_response(None, move |_, response| {
+        if response == "download" {
+            input_sender.emit(ShowDetailsInput::QueueDownloads {
+                media_ids: media_ids.clone(),
+                quality: quality.clone(),
+            });
+        }
+    });
+    dialog.present(Some(parent));
+}
+
 fn create_episode_card(
     episode: &Episode,
     index: usize,
@@ -1398,6 +2729,24 @@ fn create_episode_card(
     } else {
         menu.append(Some("Mark as Watched"), Some("episode.mark_watched"));
     }
+    if episode.episode_number > 1 || episode.season_number > 1 {
+        menu.append(
+            Some("Mark Previous as Watched"),
+            Some("episode.mark_previous_watched"),
+        );
+    }
+
+    // Download at a chosen quality
+    let download_menu = gtk::gio::Menu::new();
+    for preset in QualityPreset::all() {
+        let item = gtk::gio::MenuItem::new(Some(preset.label()), None);
+        item.set_action_and_target_value(
+            Some("episode.download"),
+            Some(&preset.as_str().to_variant()),
+        );
+        download_menu.append_item(&item);
+    }
+    menu.append_submenu(Some("Download"), &download_menu);
 
     // Create popover menu
     let popover = gtk::PopoverMenu::from_model(Some(&menu));
@@ -1432,6 +2781,31 @@ fn create_episode_card(
     });
     action_group.add_action(&mark_unwatched_action);
 
+    // Mark Previous Watched action
+    let mark_previous_action = gtk::gio::SimpleAction::new("mark_previous_watched", None);
+    let sender_clone = sender.clone();
+    mark_previous_action.connect_activate(move |_, _| {
+        sender_clone.input(ShowDetailsInput::MarkPreviousWatched(index));
+    });
+    action_group.add_action(&mark_previous_action);
+
+    // Download action, with the quality as its target
+    let download_action =
+        gtk::gio::SimpleAction::new("download", Some(gtk::glib::VariantTy::STRING));
+    let sender_clone = sender.clone();
+    download_action.connect_activate(move |_, target| {
+        if let Some(quality) = target
+            .and_then(|target| target.str())
+            .and_then(QualityPreset::from_str)
+        {
+            sender_clone.input(ShowDetailsInput::RequestDownload {
+                scope: DownloadScope::Episode(index),
+                quality,
+            });
+        }
+    });
+    action_group.add_action(&download_action);
+
     // Insert action group into the card
     card.insert_action_group("episode", Some(&action_group));
 
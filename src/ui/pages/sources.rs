@@ -8,14 +8,20 @@ use tracing::{error, info};
 use crate::db::connection::DatabaseConnection;
 use crate::db::entities::sync_status::SyncStatusType;
 use crate::models::{
-    AuthStatus, SourceId,
+    AuthStatus, ClientIdentity, ConnectionPreferences, SourceId,
     auth_provider::{Source, SourceType},
 };
 use crate::services::commands::{
     Command,
     auth_commands::{LoadSourcesCommand, RemoveSourceCommand},
 };
-use crate::ui::shared::broker::{BROKER, BrokerMessage, SourceMessage};
+use crate::services::config_service::CONFIG_SERVICE;
+use crate::services::core::connectivity;
+use crate::services::core::sync::{LibrarySyncChoice, SyncService};
+use crate::services::core::troubleshoot::{self, CheckResult, CheckStatus};
+use crate::ui::shared::broker::{
+    BROKER, BrokerMessage, ConfigMessage, ConnectivityMessage, SourceMessage, Subscription, Topic,
+};
 
 #[derive(Debug)]
 pub struct SourcesPage {
@@ -23,6 +29,11 @@ pub struct SourcesPage {
     sources: Vec<Source>,
     sources_factory: FactoryVecDeque<SourceListItem>,
     is_loading: bool,
+    /// Source being tested and the dialog group its results go into
+    troubleshooting: Option<(SourceId, adw::PreferencesGroup)>,
+    /// Whether the kids profile is on, which locks the sources
+    kids_locked: bool,
+    _broker_subscription: Subscription,
 }
 
 #[derive(Debug)]
@@ -39,6 +50,10 @@ pub enum SourcesPageInput {
     SourceRemoved(SourceId),
     /// Sync a source
     SyncSource(SourceId),
+    /// Cancel the running sync of a source
+    CancelSync(SourceId),
+    /// Pause or resume the running sync of a source
+    SetSyncPaused { source_id: SourceId, paused: bool },
     /// Sync completed
     SyncCompleted(SourceId, Result<(), String>),
     /// Re-authenticate a source
@@ -56,6 +71,55 @@ pub enum SourcesPageInput {
         source_id: SourceId,
         is_connected: bool,
     },
+    /// Send a Wake-on-LAN packet to a source's server
+    WakeSource(SourceId),
+    /// Store the Wake-on-LAN MAC address for a source
+    SetMacAddress { source_id: SourceId, mac: String },
+    /// MAC address saved for a source
+    MacAddressUpdated {
+        source_id: SourceId,
+        mac_address: Option<String>,
+    },
+    /// Store the device name and identifier reported to a source's server
+    SetClientIdentity {
+        source_id: SourceId,
+        identity: ClientIdentity,
+    },
+    /// Client identity saved for a source
+    ClientIdentityUpdated {
+        source_id: SourceId,
+        identity: ClientIdentity,
+    },
+    /// Store which connections a source may use
+    SetConnectionPreferences {
+        source_id: SourceId,
+        preferences: ConnectionPreferences,
+    },
+    /// Connection preferences saved for a source
+    ConnectionPreferencesUpdated {
+        source_id: SourceId,
+        preferences: ConnectionPreferences,
+    },
+    /// Choose which libraries of a source are synced
+    ChooseLibraries(SourceId),
+    /// Libraries of a source loaded for the sync inclusion settings
+    LibraryChoicesLoaded {
+        source_id: SourceId,
+        choices: Vec<LibrarySyncChoice>,
+    },
+    /// Include a library in sync or exclude it
+    SetLibrarySynced {
+        source_id: SourceId,
+        library_id: String,
+        synced: bool,
+    },
+    /// Run the connection checks for a source and show the results
+    TroubleshootSource(SourceId),
+    /// Connection checks finished
+    TroubleshootCompleted {
+        source_id: SourceId,
+        results: Result<Vec<CheckResult>, String>,
+    },
     /// Error occurred
     Error(String),
 }
@@ -72,13 +136,20 @@ pub enum SourcesPageOutput {
     },
     /// Request sync for a source
     SyncSource(crate::models::SourceId),
+    /// Cancel the running sync of a source
+    CancelSync(SourceId),
+    /// Pause or resume the running sync of a source
+    SetSyncPaused { source_id: SourceId, paused: bool },
 }
 
 #[derive(Debug)]
 pub struct SourceListItem {
     source: Source,
     is_syncing: bool,
+    sync_paused: bool,
     is_connected: bool,
+    /// Whether every server is unreachable, which leaves nothing to sync or wake
+    offline: bool,
     is_reauthenticating: bool,
     sync_progress: Option<(usize, usize)>,
     sync_error: Option<String>,
@@ -87,10 +158,21 @@ pub struct SourceListItem {
 
 #[derive(Debug)]
 pub enum SourceListItemInput {
+    /// Sync the source, or cancel the sync while one runs
     Sync,
+    TogglePause,
     Remove,
     Reauth,
     UpdateConnectionStatus(bool),
+    Wake,
+    SetMacAddress(String),
+    MacAddressUpdated(Option<String>),
+    SetClientIdentity(ClientIdentity),
+    ClientIdentityUpdated(ClientIdentity),
+    SetConnectionPreferences(ConnectionPreferences),
+    ConnectionPreferencesUpdated(ConnectionPreferences),
+    ChooseLibraries,
+    Troubleshoot,
     ReauthStarted,
     ReauthCompleted(bool), // success or failure
 }
@@ -118,7 +200,7 @@ impl FactoryComponent for SourceListItem {
                 gtk::Image {
                     set_icon_name: Some(match self.source.source_type {
                         SourceType::PlexServer { .. } => "tv-symbolic",
-                        SourceType::JellyfinServer => "folder-videos-symbolic",
+                        SourceType::JellyfinServer | SourceType::EmbyServer => "folder-videos-symbolic",
                         _ => "folder-symbolic",
                     }),
                     set_pixel_size: 32,
@@ -141,6 +223,7 @@ impl FactoryComponent for SourceListItem {
                             match self.source.source_type {
                                 SourceType::PlexServer { .. } => "Plex",
                                 SourceType::JellyfinServer => "Jellyfin",
+                                SourceType::EmbyServer => "Emby",
                                 _ => "Local",
                             },
                             self.source.connection_info.primary_url.as_ref()
@@ -198,7 +281,9 @@ impl FactoryComponent for SourceListItem {
                         gtk::Label {
                             #[watch]
                             set_text: &if self.is_syncing {
-                                if let Some((current, total)) = self.sync_progress {
+                                if self.sync_paused {
+                                    "Paused".to_string()
+                                } else if let Some((current, total)) = self.sync_progress {
                                     format!("Syncing... {}/{}", current, total)
                                 } else {
                                     "Syncing...".to_string()
@@ -277,24 +362,246 @@ impl FactoryComponent for SourceListItem {
                             "Re-authenticate"
                         }),
                         #[watch]
-                        set_sensitive: !self.is_reauthenticating,
+                        set_sensitive: !self.is_reauthenticating && !self.offline,
                         add_css_class: "suggested-action",
                         connect_clicked => SourceListItemInput::Reauth,
                     },
 
-                    // Sync button
+                    // Connection test
                     gtk::Button {
-                        set_icon_name: "view-refresh-symbolic",
-                        set_tooltip_text: Some("Sync Library"),
+                        set_icon_name: "network-transmit-receive-symbolic",
+                        set_tooltip_text: Some("Test Connection"),
                         add_css_class: "flat",
+                        set_visible: !matches!(self.source.source_type, SourceType::LocalFolder { .. }),
+                        connect_clicked => SourceListItemInput::Troubleshoot,
+                    },
+
+                    // Wake-on-LAN button (only shown when a MAC address is configured)
+                    gtk::Button {
                         #[watch]
-                        set_sensitive: !self.is_syncing,
+                        set_visible: self.source.mac_address.is_some(),
+                        set_icon_name: "system-shutdown-symbolic",
+                        set_tooltip_text: Some("Wake Server"),
+                        add_css_class: "flat",
+                        #[watch]
+                        set_sensitive: !self.offline,
+                        connect_clicked => SourceListItemInput::Wake,
+                    },
+
+                    // Wake-on-LAN settings
+                    gtk::MenuButton {
+                        set_icon_name: "network-wired-symbolic",
+                        set_tooltip_text: Some("Wake-on-LAN Settings"),
+                        add_css_class: "flat",
+
+                        #[wrap(Some)]
+                        set_popover = &gtk::Popover {
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_spacing: 6,
+                                set_margin_all: 6,
+
+                                gtk::Label {
+                                    set_text: "Server MAC Address",
+                                    set_halign: gtk::Align::Start,
+                                    add_css_class: "heading",
+                                },
+
+                                gtk::Entry {
+                                    set_placeholder_text: Some("AA:BB:CC:DD:EE:FF"),
+                                    set_text: self.source.mac_address.as_deref().unwrap_or_default(),
+                                    set_width_chars: 20,
+                                    connect_activate[sender] => move |entry| {
+                                        sender.input(SourceListItemInput::SetMacAddress(entry.text().to_string()));
+                                    },
+                                },
+
+                                gtk::Label {
+                                    set_text: "Press Enter to save, leave empty to disable",
+                                    set_halign: gtk::Align::Start,
+                                    add_css_class: "dim-label",
+                                    add_css_class: "caption",
+                                },
+                            },
+                        },
+                    },
+
+                    // Device name and identifier reported to the server
+                    gtk::MenuButton {
+                        set_visible: !matches!(self.source.source_type, SourceType::LocalFolder { .. }),
+                        set_icon_name: "computer-symbolic",
+                        set_tooltip_text: Some("Client Identity"),
+                        add_css_class: "flat",
+
+                        #[wrap(Some)]
+                        set_popover = &gtk::Popover {
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_spacing: 6,
+                                set_margin_all: 6,
+
+                                gtk::Label {
+                                    set_text: "Client Identity",
+                                    set_halign: gtk::Align::Start,
+                                    add_css_class: "heading",
+                                },
+
+                                #[name = "device_name_entry"]
+                                gtk::Entry {
+                                    set_placeholder_text: Some("Device name"),
+                                    set_text: self.source.client_identity.device_name.as_deref().unwrap_or_default(),
+                                    set_width_chars: 24,
+                                },
+
+                                #[name = "client_identifier_entry"]
+                                gtk::Entry {
+                                    set_placeholder_text: Some("Client identifier"),
+                                    set_text: self.source.client_identity.client_identifier.as_deref().unwrap_or_default(),
+                                    set_width_chars: 24,
+                                },
+
+                                #[name = "platform_entry"]
+                                gtk::Entry {
+                                    set_visible: matches!(self.source.source_type, SourceType::PlexServer { .. }),
+                                    set_placeholder_text: Some("Platform"),
+                                    set_text: self.source.client_identity.platform.as_deref().unwrap_or_default(),
+                                    set_width_chars: 24,
+                                },
+
+                                gtk::Label {
+                                    set_text: "Shown in the server's dashboard, leave empty for the default",
+                                    set_halign: gtk::Align::Start,
+                                    set_wrap: true,
+                                    set_max_width_chars: 30,
+                                    add_css_class: "dim-label",
+                                    add_css_class: "caption",
+                                },
+
+                                gtk::Button {
+                                    set_label: "Save",
+                                    add_css_class: "suggested-action",
+                                    connect_clicked[sender, device_name_entry, client_identifier_entry, platform_entry] => move |_| {
+                                        sender.input(SourceListItemInput::SetClientIdentity(ClientIdentity::from_input(
+                                            &device_name_entry.text(),
+                                            &client_identifier_entry.text(),
+                                            &platform_entry.text(),
+                                        )));
+                                    },
+                                },
+                            },
+                        },
+                    },
+
+                    // Which connections to the server may be used
+                    gtk::MenuButton {
+                        set_visible: !matches!(self.source.source_type, SourceType::LocalFolder { .. }),
+                        set_icon_name: "network-wired-symbolic",
+                        set_tooltip_text: Some("Connection Preferences"),
+                        add_css_class: "flat",
+
+                        #[wrap(Some)]
+                        set_popover = &gtk::Popover {
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_spacing: 6,
+                                set_margin_all: 6,
+
+                                gtk::Label {
+                                    set_text: "Connection Preferences",
+                                    set_halign: gtk::Align::Start,
+                                    add_css_class: "heading",
+                                },
+
+                                #[name = "prefer_local_check"]
+                                gtk::CheckButton {
+                                    set_label: Some("Prefer local connections"),
+                                    set_active: self.source.connection_preferences.prefer_local,
+                                },
+
+                                #[name = "never_relay_check"]
+                                gtk::CheckButton {
+                                    set_visible: matches!(self.source.source_type, SourceType::PlexServer { .. }),
+                                    set_label: Some("Never use relay"),
+                                    set_active: self.source.connection_preferences.never_relay,
+                                },
+
+                                #[name = "preferred_url_entry"]
+                                gtk::Entry {
+                                    set_placeholder_text: Some("Preferred URL"),
+                                    set_text: self.source.connection_preferences.preferred_url.as_deref().unwrap_or_default(),
+                                    set_width_chars: 24,
+                                },
+
+                                gtk::Label {
+                                    set_text: "Relay connections cap streaming quality. The preferred URL is tried before all others.",
+                                    set_halign: gtk::Align::Start,
+                                    set_wrap: true,
+                                    set_max_width_chars: 30,
+                                    add_css_class: "dim-label",
+                                    add_css_class: "caption",
+                                },
+
+                                gtk::Button {
+                                    set_label: "Save",
+                                    add_css_class: "suggested-action",
+                                    connect_clicked[sender, prefer_local_check, never_relay_check, preferred_url_entry] => move |_| {
+                                        let preferred_url = preferred_url_entry.text().trim().to_string();
+                                        sender.input(SourceListItemInput::SetConnectionPreferences(ConnectionPreferences {
+                                            prefer_local: prefer_local_check.is_active(),
+                                            never_relay: never_relay_check.is_active(),
+                                            preferred_url: (!preferred_url.is_empty()).then_some(preferred_url),
+                                        }));
+                                    },
+                                },
+                            },
+                        },
+                    },
+
+                    // Library sync inclusion settings
+                    gtk::Button {
+                        set_icon_name: "view-list-symbolic",
+                        set_tooltip_text: Some("Choose Libraries to Sync"),
+                        add_css_class: "flat",
+                        connect_clicked => SourceListItemInput::ChooseLibraries,
+                    },
+
+                    // Pause button (shown only when syncing)
+                    gtk::Button {
+                        add_css_class: "flat",
+                        #[watch]
+                        set_visible: self.is_syncing,
+                        #[watch]
+                        set_icon_name: if self.sync_paused {
+                            "media-playback-start-symbolic"
+                        } else {
+                            "media-playback-pause-symbolic"
+                        },
+                        #[watch]
+                        set_tooltip_text: Some(if self.sync_paused {
+                            "Resume Sync"
+                        } else {
+                            "Pause Sync"
+                        }),
+                        connect_clicked => SourceListItemInput::TogglePause,
+                    },
+
+                    // Sync button, cancels the sync while one runs
+                    gtk::Button {
+                        add_css_class: "flat",
+                        #[watch]
+                        set_sensitive: self.is_syncing || (self.is_connected && !self.offline),
                         #[watch]
                         set_icon_name: if self.is_syncing {
-                            "content-loading-symbolic"
+                            "process-stop-symbolic"
                         } else {
                             "view-refresh-symbolic"
                         },
+                        #[watch]
+                        set_tooltip_text: Some(if self.is_syncing {
+                            "Cancel Sync"
+                        } else {
+                            "Sync Library"
+                        }),
                         connect_clicked => SourceListItemInput::Sync,
                     },
 
@@ -317,7 +624,9 @@ impl FactoryComponent for SourceListItem {
         Self {
             source,
             is_syncing: false,
+            sync_paused: false,
             is_connected,
+            offline: connectivity::is_offline(),
             is_reauthenticating: false,
             sync_progress: None,
             sync_error: None,
@@ -328,10 +637,20 @@ impl FactoryComponent for SourceListItem {
     fn update(&mut self, msg: Self::Input, sender: FactorySender<Self>) {
         match msg {
             SourceListItemInput::Sync => {
+                let source_id = SourceId::from(self.source.id.clone());
+                let action = if self.is_syncing {
+                    SourceItemAction::CancelSync(source_id)
+                } else {
+                    SourceItemAction::Sync(source_id)
+                };
+                sender.output(action).unwrap();
+            }
+            SourceListItemInput::TogglePause => {
                 sender
-                    .output(SourceItemAction::Sync(SourceId::from(
-                        self.source.id.clone(),
-                    )))
+                    .output(SourceItemAction::SetSyncPaused(
+                        SourceId::from(self.source.id.clone()),
+                        !self.sync_paused,
+                    ))
                     .unwrap();
             }
             SourceListItemInput::Remove => {
@@ -345,6 +664,7 @@ impl FactoryComponent for SourceListItem {
                 let source_type = match &self.source.source_type {
                     SourceType::PlexServer { .. } => "plex",
                     SourceType::JellyfinServer => "jellyfin",
+                    SourceType::EmbyServer => "emby",
                     _ => "unknown",
                 };
                 sender
@@ -358,6 +678,60 @@ impl FactoryComponent for SourceListItem {
             SourceListItemInput::UpdateConnectionStatus(is_connected) => {
                 self.is_connected = is_connected;
             }
+            SourceListItemInput::Wake => {
+                sender
+                    .output(SourceItemAction::Wake(SourceId::from(
+                        self.source.id.clone(),
+                    )))
+                    .unwrap();
+            }
+            SourceListItemInput::SetMacAddress(mac) => {
+                sender
+                    .output(SourceItemAction::SetMacAddress(
+                        SourceId::from(self.source.id.clone()),
+                        mac,
+                    ))
+                    .unwrap();
+            }
+            SourceListItemInput::MacAddressUpdated(mac_address) => {
+                self.source.mac_address = mac_address;
+            }
+            SourceListItemInput::SetClientIdentity(identity) => {
+                sender
+                    .output(SourceItemAction::SetClientIdentity(
+                        SourceId::from(self.source.id.clone()),
+                        identity,
+                    ))
+                    .unwrap();
+            }
+            SourceListItemInput::ClientIdentityUpdated(identity) => {
+                self.source.client_identity = identity;
+            }
+            SourceListItemInput::SetConnectionPreferences(preferences) => {
+                sender
+                    .output(SourceItemAction::SetConnectionPreferences(
+                        SourceId::from(self.source.id.clone()),
+                        preferences,
+                    ))
+                    .unwrap();
+            }
+            SourceListItemInput::ConnectionPreferencesUpdated(preferences) => {
+                self.source.connection_preferences = preferences;
+            }
+            SourceListItemInput::ChooseLibraries => {
+                sender
+                    .output(SourceItemAction::ChooseLibraries(SourceId::from(
+                        self.source.id.clone(),
+                    )))
+                    .unwrap();
+            }
+            SourceListItemInput::Troubleshoot => {
+                sender
+                    .output(SourceItemAction::Troubleshoot(SourceId::from(
+                        self.source.id.clone(),
+                    )))
+                    .unwrap();
+            }
             SourceListItemInput::ReauthStarted => {
                 self.is_reauthenticating = true;
             }
@@ -375,8 +749,16 @@ impl FactoryComponent for SourceListItem {
 #[derive(Debug)]
 pub enum SourceItemAction {
     Sync(SourceId),
+    CancelSync(SourceId),
+    SetSyncPaused(SourceId, bool),
     Remove(SourceId),
     Reauth(SourceId, String, String), // source_id, source_name, source_type
+    Wake(SourceId),
+    SetMacAddress(SourceId, String),
+    SetClientIdentity(SourceId, ClientIdentity),
+    SetConnectionPreferences(SourceId, ConnectionPreferences),
+    ChooseLibraries(SourceId),
+    Troubleshoot(SourceId),
 }
 
 #[allow(unused_assignments)]
@@ -393,6 +775,12 @@ impl AsyncComponent for SourcesPage {
             set_spacing: 0,
             add_css_class: "background",
 
+            adw::Banner {
+                set_title: "Sources can't be changed while the kids profile is on",
+                #[watch]
+                set_revealed: model.kids_locked,
+            },
+
             // Section header
             gtk::Box {
                 set_orientation: gtk::Orientation::Horizontal,
@@ -423,6 +811,8 @@ impl AsyncComponent for SourcesPage {
                     set_child = &gtk::Box {
                         set_orientation: gtk::Orientation::Vertical,
                         set_spacing: 24,
+                        #[watch]
+                        set_sensitive: !model.kids_locked,
 
                         // Sources list
                         #[local_ref]
@@ -461,6 +851,8 @@ impl AsyncComponent for SourcesPage {
                     set_halign: gtk::Align::Center,
                     add_css_class: "pill",
                     add_css_class: "suggested-action",
+                    #[watch]
+                    set_sensitive: !model.kids_locked,
                     connect_clicked => SourcesPageInput::AddSource,
                 },
             },
@@ -480,6 +872,10 @@ impl AsyncComponent for SourcesPage {
             .launch(sources_list.clone())
             .forward(sender.input_sender(), |output| match output {
                 SourceItemAction::Sync(id) => SourcesPageInput::SyncSource(id),
+                SourceItemAction::CancelSync(id) => SourcesPageInput::CancelSync(id),
+                SourceItemAction::SetSyncPaused(source_id, paused) => {
+                    SourcesPageInput::SetSyncPaused { source_id, paused }
+                }
                 SourceItemAction::Remove(id) => SourcesPageInput::RemoveSource(id),
                 SourceItemAction::Reauth(source_id, source_name, source_type) => {
                     SourcesPageInput::ReauthSource {
@@ -488,6 +884,24 @@ impl AsyncComponent for SourcesPage {
                         source_type,
                     }
                 }
+                SourceItemAction::Wake(id) => SourcesPageInput::WakeSource(id),
+                SourceItemAction::SetMacAddress(source_id, mac) => {
+                    SourcesPageInput::SetMacAddress { source_id, mac }
+                }
+                SourceItemAction::SetClientIdentity(source_id, identity) => {
+                    SourcesPageInput::SetClientIdentity {
+                        source_id,
+                        identity,
+                    }
+                }
+                SourceItemAction::SetConnectionPreferences(source_id, preferences) => {
+                    SourcesPageInput::SetConnectionPreferences {
+                        source_id,
+                        preferences,
+                    }
+                }
+                SourceItemAction::ChooseLibraries(id) => SourcesPageInput::ChooseLibraries(id),
+                SourceItemAction::Troubleshoot(id) => SourcesPageInput::TroubleshootSource(id),
             });
 
         let model = Self {
@@ -495,6 +909,13 @@ impl AsyncComponent for SourcesPage {
             sources: Vec::new(),
             sources_factory,
             is_loading: true,
+            troubleshooting: None,
+            kids_locked: CONFIG_SERVICE.get_config().await.kids.enabled,
+            _broker_subscription: BROKER.subscribe(
+                &[Topic::Source, Topic::Config, Topic::Connectivity],
+                sender.input_sender(),
+                SourcesPageInput::BrokerMsg,
+            ),
         };
 
         let widgets = view_output!();
@@ -502,23 +923,6 @@ impl AsyncComponent for SourcesPage {
         // Load sources on init
         sender.input(SourcesPageInput::LoadData);
 
-        // Subscribe to MessageBroker
-        let broker_sender = sender.input_sender().clone();
-        relm4::spawn(async move {
-            // Create a channel to forward broker messages
-            let (tx, rx) = relm4::channel::<BrokerMessage>();
-
-            // Subscribe to the broker with our channel
-            BROKER.subscribe("SourcesPage".to_string(), tx).await;
-
-            // Forward messages to the component
-            while let Some(msg) = rx.recv().await {
-                broker_sender
-                    .send(SourcesPageInput::BrokerMsg(msg))
-                    .unwrap();
-            }
-        });
-
         AsyncComponentParts { model, widgets }
     }
 
@@ -526,7 +930,7 @@ impl AsyncComponent for SourcesPage {
         &mut self,
         msg: Self::Input,
         sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         match msg {
             SourcesPageInput::LoadData => {
@@ -560,6 +964,9 @@ impl AsyncComponent for SourcesPage {
             }
 
             SourcesPageInput::AddSource => {
+                if self.kids_locked {
+                    return;
+                }
                 info!("Opening auth dialog to add source");
                 sender.output(SourcesPageOutput::OpenAuthDialog).unwrap();
             }
@@ -628,6 +1035,9 @@ impl AsyncComponent for SourcesPage {
             }
 
             SourcesPageInput::RemoveSource(source_id) => {
+                if self.kids_locked {
+                    return;
+                }
                 info!("Removing source: {}", source_id);
 
                 let db = self.db.clone();
@@ -709,6 +1119,7 @@ impl AsyncComponent for SourcesPage {
                         for item in factory_guard.iter_mut() {
                             if item.source.id == source_id {
                                 item.is_syncing = false;
+                                item.sync_paused = false;
                                 item.sync_progress = None;
                                 item.sync_error = None;
                                 item.last_sync_status = Some(SyncStatusType::Completed);
@@ -726,6 +1137,7 @@ impl AsyncComponent for SourcesPage {
                         for item in factory_guard.iter_mut() {
                             if item.source.id == source_id {
                                 item.is_syncing = false;
+                                item.sync_paused = false;
                                 item.sync_progress = None;
                                 item.sync_error = Some(error.clone());
                                 item.last_sync_status = Some(SyncStatusType::Failed);
@@ -733,6 +1145,35 @@ impl AsyncComponent for SourcesPage {
                         }
                         // Don't show global error, it's now displayed per-source
                     }
+                    BrokerMessage::Source(SourceMessage::SyncCancelled { source_id }) => {
+                        info!("Sync cancelled for source: {}", source_id);
+                        let mut factory_guard = self.sources_factory.guard();
+                        for item in factory_guard.iter_mut() {
+                            if item.source.id == source_id {
+                                item.is_syncing = false;
+                                item.sync_paused = false;
+                                item.sync_progress = None;
+                            }
+                        }
+                    }
+                    BrokerMessage::Source(SourceMessage::SyncPaused { source_id, paused }) => {
+                        let mut factory_guard = self.sources_factory.guard();
+                        for item in factory_guard.iter_mut() {
+                            if item.source.id == source_id {
+                                item.sync_paused = paused;
+                            }
+                        }
+                    }
+                    BrokerMessage::Config(ConfigMessage::Updated { config }) => {
+                        self.kids_locked = config.kids.enabled;
+                    }
+                    BrokerMessage::Connectivity(change) => {
+                        let offline = matches!(change, ConnectivityMessage::WentOffline);
+                        let mut factory_guard = self.sources_factory.guard();
+                        for item in factory_guard.iter_mut() {
+                            item.offline = offline;
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -756,6 +1197,19 @@ impl AsyncComponent for SourcesPage {
                     .unwrap();
             }
 
+            SourcesPageInput::CancelSync(source_id) => {
+                info!("Requesting cancellation of sync for source: {}", source_id);
+                sender
+                    .output(SourcesPageOutput::CancelSync(source_id))
+                    .unwrap();
+            }
+
+            SourcesPageInput::SetSyncPaused { source_id, paused } => {
+                sender
+                    .output(SourcesPageOutput::SetSyncPaused { source_id, paused })
+                    .unwrap();
+            }
+
             SourcesPageInput::SyncCompleted(source_id, result) => {
                 // Don't manually track syncing here - let the broker messages handle it
                 match result {
@@ -788,6 +1242,310 @@ impl AsyncComponent for SourcesPage {
                 }
             }
 
+            SourcesPageInput::WakeSource(source_id) => {
+                info!("Sending Wake-on-LAN packet for source: {}", source_id);
+
+                let db = self.db.clone();
+                sender.oneshot_command(async move {
+                    use crate::services::core::WakeOnLanService;
+
+                    match WakeOnLanService::wake_source(&db, &source_id).await {
+                        // Re-test connections once the server has had time to boot
+                        Ok(_) => {
+                            tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+                            SourcesPageInput::LoadData
+                        }
+                        Err(e) => {
+                            error!("Failed to wake source {}: {}", source_id, e);
+                            SourcesPageInput::Error(e.to_string())
+                        }
+                    }
+                });
+            }
+
+            SourcesPageInput::SetMacAddress { source_id, mac } => {
+                let db = self.db.clone();
+                sender.oneshot_command(async move {
+                    use crate::services::core::WakeOnLanService;
+
+                    match WakeOnLanService::set_mac_address(&db, &source_id, &mac).await {
+                        Ok(mac_address) => SourcesPageInput::MacAddressUpdated {
+                            source_id,
+                            mac_address,
+                        },
+                        Err(e) => {
+                            error!("Failed to set MAC address for {}: {}", source_id, e);
+                            SourcesPageInput::Error(e.to_string())
+                        }
+                    }
+                });
+            }
+
+            SourcesPageInput::MacAddressUpdated {
+                source_id,
+                mac_address,
+            } => {
+                info!(
+                    "Wake-on-LAN MAC address for {} set to {:?}",
+                    source_id, mac_address
+                );
+
+                if let Some(source) = self
+                    .sources
+                    .iter_mut()
+                    .find(|s| s.id == source_id.to_string())
+                {
+                    source.mac_address = mac_address.clone();
+                }
+
+                let idx_to_update = {
+                    let factory_guard = self.sources_factory.guard();
+                    factory_guard
+                        .iter()
+                        .position(|item| item.source.id == source_id.to_string())
+                };
+
+                if let Some(idx) = idx_to_update {
+                    self.sources_factory
+                        .send(idx, SourceListItemInput::MacAddressUpdated(mac_address));
+                }
+            }
+
+            SourcesPageInput::SetClientIdentity {
+                source_id,
+                identity,
+            } => {
+                let db = self.db.clone();
+                sender.oneshot_command(async move {
+                    use crate::services::core::ClientIdentityService;
+
+                    match ClientIdentityService::set_for_source(&db, &source_id, identity).await {
+                        Ok(identity) => SourcesPageInput::ClientIdentityUpdated {
+                            source_id,
+                            identity,
+                        },
+                        Err(e) => {
+                            error!("Failed to set client identity for {}: {}", source_id, e);
+                            SourcesPageInput::Error(e.to_string())
+                        }
+                    }
+                });
+            }
+
+            SourcesPageInput::ClientIdentityUpdated {
+                source_id,
+                identity,
+            } => {
+                info!("Client identity for {} set to {:?}", source_id, identity);
+
+                if let Some(source) = self
+                    .sources
+                    .iter_mut()
+                    .find(|s| s.id == source_id.to_string())
+                {
+                    source.client_identity = identity.clone();
+                }
+
+                let idx_to_update = {
+                    let factory_guard = self.sources_factory.guard();
+                    factory_guard
+                        .iter()
+                        .position(|item| item.source.id == source_id.to_string())
+                };
+
+                if let Some(idx) = idx_to_update {
+                    self.sources_factory
+                        .send(idx, SourceListItemInput::ClientIdentityUpdated(identity));
+                }
+            }
+
+            SourcesPageInput::SetConnectionPreferences {
+                source_id,
+                preferences,
+            } => {
+                let db = self.db.clone();
+                sender.oneshot_command(async move {
+                    use crate::services::core::ConnectionService;
+
+                    match ConnectionService::set_preferences(&db, &source_id, preferences).await {
+                        Ok(preferences) => SourcesPageInput::ConnectionPreferencesUpdated {
+                            source_id,
+                            preferences,
+                        },
+                        Err(e) => {
+                            error!(
+                                "Failed to set connection preferences for {}: {}",
+                                source_id, e
+                            );
+                            SourcesPageInput::Error(e.to_string())
+                        }
+                    }
+                });
+            }
+
+            SourcesPageInput::ConnectionPreferencesUpdated {
+                source_id,
+                preferences,
+            } => {
+                info!(
+                    "Connection preferences for {} set to {:?}",
+                    source_id, preferences
+                );
+
+                if let Some(source) = self
+                    .sources
+                    .iter_mut()
+                    .find(|s| s.id == source_id.to_string())
+                {
+                    source.connection_preferences = preferences.clone();
+                }
+
+                let idx_to_update = {
+                    let factory_guard = self.sources_factory.guard();
+                    factory_guard
+                        .iter()
+                        .position(|item| item.source.id == source_id.to_string())
+                };
+
+                if let Some(idx) = idx_to_update {
+                    self.sources_factory.send(
+                        idx,
+                        SourceListItemInput::ConnectionPreferencesUpdated(preferences),
+                    );
+                }
+            }
+
+            SourcesPageInput::ChooseLibraries(source_id) => {
+                let db = self.db.clone();
+                sender.oneshot_command(async move {
+                    match SyncService::library_sync_choices(&db, &source_id).await {
+                        Ok(choices) => {
+                            SourcesPageInput::LibraryChoicesLoaded { source_id, choices }
+                        }
+                        Err(e) => {
+                            error!("Failed to load libraries for {}: {}", source_id, e);
+                            SourcesPageInput::Error(e.to_string())
+                        }
+                    }
+                });
+            }
+
+            SourcesPageInput::LibraryChoicesLoaded { source_id, choices } => {
+                let group = adw::PreferencesGroup::builder()
+                    .title("Synced Libraries")
+                    .description(if choices.is_empty() {
+                        "Sync this source to choose from its libraries"
+                    } else {
+                        "Libraries that are turned off are not synced and don't appear in the sidebar"
+                    })
+                    .build();
+
+                for choice in choices {
+                    let row = adw::SwitchRow::builder()
+                        .title(&choice.title)
+                        .active(choice.synced)
+                        .build();
+                    let sender = sender.clone();
+                    let source_id = source_id.clone();
+                    row.connect_active_notify(move |row| {
+                        sender.input(SourcesPageInput::SetLibrarySynced {
+                            source_id: source_id.clone(),
+                            library_id: choice.library_id.clone(),
+                            synced: row.is_active(),
+                        });
+                    });
+                    group.add(&row);
+                }
+
+                let page = adw::PreferencesPage::new();
+                page.add(&group);
+                let dialog = adw::PreferencesDialog::builder().title("Libraries").build();
+                dialog.add(&page);
+                dialog.present(Some(root));
+            }
+
+            SourcesPageInput::SetLibrarySynced {
+                source_id,
+                library_id,
+                synced,
+            } => {
+                let db = self.db.clone();
+                sender.oneshot_command(async move {
+                    match SyncService::set_library_synced(&db, &source_id, &library_id, synced)
+                        .await
+                    {
+                        // Backfill the re-included library right away
+                        Ok(()) if synced => SourcesPageInput::SyncSource(source_id),
+                        Ok(()) => SourcesPageInput::LoadData,
+                        Err(e) => {
+                            error!("Failed to update sync of library {}: {}", library_id, e);
+                            SourcesPageInput::Error(e.to_string())
+                        }
+                    }
+                });
+            }
+
+            SourcesPageInput::TroubleshootSource(source_id) => {
+                info!("Testing connection to source: {}", source_id);
+
+                let spinner = gtk::Spinner::builder().spinning(true).build();
+                let group = adw::PreferencesGroup::builder()
+                    .title("Connection Test")
+                    .description(
+                        "Checking name resolution, server addresses, sign-in and playback…",
+                    )
+                    .header_suffix(&spinner)
+                    .build();
+
+                let page = adw::PreferencesPage::new();
+                page.add(&group);
+                let dialog = adw::PreferencesDialog::builder()
+                    .title("Test Connection")
+                    .build();
+                dialog.add(&page);
+                dialog.present(Some(root));
+                self.troubleshooting = Some((source_id.clone(), group));
+
+                let db = self.db.clone();
+                sender.oneshot_command(async move {
+                    let results = troubleshoot::troubleshoot_source(&db, &source_id)
+                        .await
+                        .map_err(|e| e.to_string());
+                    SourcesPageInput::TroubleshootCompleted { source_id, results }
+                });
+            }
+
+            SourcesPageInput::TroubleshootCompleted { source_id, results } => {
+                let Some((_, group)) = self.troubleshooting.take_if(|(id, _)| *id == source_id)
+                else {
+                    return;
+                };
+                group.set_header_suffix(None::<&gtk::Widget>);
+
+                let results = match results {
+                    Ok(results) => results,
+                    Err(e) => {
+                        error!("Connection test for {} failed: {}", source_id, e);
+                        group.set_description(Some(&format!("The test could not run: {}", e)));
+                        return;
+                    }
+                };
+
+                let failed = results
+                    .iter()
+                    .filter(|result| result.status == CheckStatus::Failed)
+                    .count();
+                group.set_description(Some(&match failed {
+                    0 => "All checks passed".to_string(),
+                    1 => "1 check failed".to_string(),
+                    n => format!("{} checks failed", n),
+                }));
+
+                for result in results {
+                    group.add(&check_result_row(&result));
+                }
+            }
+
             SourcesPageInput::Error(msg) => {
                 error!("Error: {}", msg);
                 // For now, just log the error. Toast implementation would require
@@ -801,9 +1559,34 @@ impl AsyncComponent for SourcesPage {
         &mut self,
         msg: Self::CommandOutput,
         sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         // Command outputs are SourcesPageInput messages, so forward to update
-        self.update(msg, sender, _root).await;
+        self.update(msg, sender, root).await;
     }
 }
+
+/// Row showing one connection check with its outcome and, if any, what to try
+fn check_result_row(result: &CheckResult) -> adw::ActionRow {
+    let subtitle = match &result.hint {
+        Some(hint) => format!("{}\n{}", result.detail, hint),
+        None => result.detail.clone(),
+    };
+    let row = adw::ActionRow::builder()
+        .title(&result.title)
+        .subtitle(&subtitle)
+        .use_markup(false)
+        .build();
+
+    let (icon_name, css_class) = match result.status {
+        CheckStatus::Passed => ("emblem-ok-symbolic", "success"),
+        CheckStatus::Warning => ("dialog-warning-symbolic", "warning"),
+        CheckStatus::Failed => ("dialog-error-symbolic", "error"),
+        CheckStatus::Skipped => ("action-unavailable-symbolic", "dim-label"),
+    };
+    let icon = gtk::Image::from_icon_name(icon_name);
+    icon.add_css_class(css_class);
+    row.add_prefix(&icon);
+
+    row
+}
@@ -0,0 +1,63 @@
+//! Helpers for exposing widget state to screen readers and following the
+//! system accessibility settings
+
+use gtk::prelude::*;
+use libadwaita as adw;
+use relm4::gtk;
+
+/// Accessible pressed state for a button that acts as a toggle
+pub fn pressed(active: bool) -> gtk::accessible::State {
+    gtk::accessible::State::Pressed(if active {
+        gtk::AccessibleTristate::True
+    } else {
+        gtk::AccessibleTristate::False
+    })
+}
+
+/// Read out a change that happened without moving focus, like a keyboard seek
+pub fn announce(widget: &impl IsA<gtk::Accessible>, message: &str) {
+    widget.announce(message, gtk::AccessibleAnnouncementPriority::Medium);
+}
+
+/// Whether the system asks for animations to be kept to a minimum
+pub fn reduced_motion() -> bool {
+    gtk::Settings::default().is_some_and(|settings| !settings.is_gtk_enable_animations())
+}
+
+/// Mirror the system high-contrast and reduce-animations settings as the
+/// `high-contrast` and `reduced-motion` window classes the stylesheets key off
+pub fn follow_system_settings(window: &impl IsA<gtk::Widget>) {
+    let window = window.as_ref();
+    apply_system_classes(window);
+
+    let weak = window.downgrade();
+    adw::StyleManager::default().connect_high_contrast_notify(move |_| {
+        if let Some(window) = weak.upgrade() {
+            apply_system_classes(&window);
+        }
+    });
+
+    if let Some(settings) = gtk::Settings::default() {
+        let weak = window.downgrade();
+        settings.connect_gtk_enable_animations_notify(move |_| {
+            if let Some(window) = weak.upgrade() {
+                apply_system_classes(&window);
+            }
+        });
+    }
+}
+
+fn apply_system_classes(window: &gtk::Widget) {
+    let high_contrast = adw::StyleManager::default().is_high_contrast();
+    if high_contrast {
+        window.add_css_class("high-contrast");
+    } else {
+        window.remove_css_class("high-contrast");
+    }
+
+    if reduced_motion() {
+        window.add_css_class("reduced-motion");
+    } else {
+        window.remove_css_class("reduced-motion");
+    }
+}
@@ -1,16 +1,23 @@
 //! MessageBroker System
 //!
-//! This module provides a centralized message passing system for the Relm4 UI.
+//! This module provides a centralized, typed message bus for the Relm4 UI.
+//! Messages are grouped into topics, and components subscribe to the topics
+//! they handle. A subscription lasts until the handle returned by
+//! `subscribe` is dropped, which components do by keeping it in their model,
+//! so nothing is delivered to a component after it shuts down. Each
+//! subscriber has a bounded queue; a subscriber that falls behind makes
+//! `broadcast` wait for room instead of letting messages pile up.
 //!
 //! ## Usage Patterns
 //!
 //! ### Subscribing to Messages
 //! ```ignore
-//! // In component's AsyncComponentParts::init()
-//! let broker_sender = sender.clone();
-//! relm4::spawn(async move {
-//!     BROKER.subscribe("ComponentName".to_string(), broker_sender).await;
-//! });
+//! // In component's init(), keeping the subscription in the model
+//! let broker_subscription = BROKER.subscribe(
+//!     &[Topic::Config, Topic::Data],
+//!     sender.input_sender(),
+//!     Input::BrokerMsg,
+//! );
 //! ```
 //!
 //! ### Broadcasting Messages
@@ -33,10 +40,11 @@
 //! }
 //! ```
 
+use crate::services::core::ConnectionType;
 use relm4::Sender;
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use tokio::sync::mpsc;
 
 #[derive(Debug, Clone)]
 pub enum BrokerMessage {
@@ -46,6 +54,8 @@ pub enum BrokerMessage {
     Cache(CacheMessage),
     PlaybackSync(PlaybackSyncMessage),
     MetadataRefresh(MetadataRefreshMessage),
+    Connectivity(ConnectivityMessage),
+    Power(PowerMessage),
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +73,10 @@ pub enum DataMessage {
     MediaUpdated {
         media_id: String,
     },
+    /// An item was deleted from its server and the database
+    MediaDeleted {
+        media_id: String,
+    },
     MediaBatchSaved {
         items: Vec<crate::db::entities::MediaItemModel>,
     },
@@ -78,6 +92,19 @@ pub enum DataMessage {
         media_id: String,
         watched: bool,
     },
+    FavoriteChanged {
+        media_id: String,
+        favorite: bool,
+    },
+    HiddenItemsChanged {
+        media_id: String,
+        hidden: bool,
+    },
+    HiddenCollectionChanged {
+        source_id: String,
+        collection: String,
+        hidden: bool,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -100,6 +127,15 @@ pub enum SourceMessage {
         source_id: String,
         error: String,
     },
+    /// A sync was cancelled before it finished
+    SyncCancelled {
+        source_id: String,
+    },
+    /// A running sync was paused or resumed
+    SyncPaused {
+        source_id: String,
+        paused: bool,
+    },
     LibrarySyncStarted {
         source_id: String,
         library_id: String,
@@ -111,6 +147,21 @@ pub enum SourceMessage {
         library_name: String,
         items_synced: usize,
     },
+    /// Libraries were added to or removed from a source outside of a sync
+    LibrariesChanged {
+        source_id: String,
+    },
+    /// The connection monitor found a source reachable or not
+    ConnectionChanged {
+        source_id: String,
+        connected: bool,
+        connection_type: Option<ConnectionType>,
+    },
+    /// The server of a source asks to sign in again, or no longer does
+    AuthChanged {
+        source_id: String,
+        needs_auth: bool,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -196,58 +247,148 @@ pub enum MetadataRefreshMessage {
     HomeSectionsRefreshed { source_id: String },
 }
 
+/// Application-wide connectivity transitions
+#[derive(Debug, Clone)]
+pub enum ConnectivityMessage {
+    /// Every source is unreachable - serve content from the database and caches
+    WentOffline,
+    /// A source is reachable again - queued changes are being reconciled
+    BackOnline,
+}
+
+/// Power state changes that affect playback
+#[derive(Debug, Clone)]
+pub enum PowerMessage {
+    /// Power saving playback was turned on or off by the power profile or
+    /// the battery level
+    SavingChanged { active: bool },
+}
+
+/// Group of messages a component can subscribe to, one per kind of
+/// `BrokerMessage`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topic {
+    Data,
+    Source,
+    Config,
+    Cache,
+    PlaybackSync,
+    MetadataRefresh,
+    Connectivity,
+    Power,
+}
+
+impl BrokerMessage {
+    /// Topic the message is delivered on
+    pub fn topic(&self) -> Topic {
+        match self {
+            Self::Data(_) => Topic::Data,
+            Self::Source(_) => Topic::Source,
+            Self::Config(_) => Topic::Config,
+            Self::Cache(_) => Topic::Cache,
+            Self::PlaybackSync(_) => Topic::PlaybackSync,
+            Self::MetadataRefresh(_) => Topic::MetadataRefresh,
+            Self::Connectivity(_) => Topic::Connectivity,
+            Self::Power(_) => Topic::Power,
+        }
+    }
+}
+
+/// Messages a subscriber may have waiting before broadcasts wait for it
+const SUBSCRIBER_QUEUE_SIZE: usize = 256;
+
+struct Subscriber {
+    id: u64,
+    topics: Vec<Topic>,
+    queue: mpsc::Sender<BrokerMessage>,
+}
+
+type Subscribers = Arc<RwLock<Vec<Subscriber>>>;
+
 pub struct MessageBroker {
-    subscribers: Arc<RwLock<HashMap<String, Vec<Sender<BrokerMessage>>>>>,
+    subscribers: Subscribers,
+    next_id: AtomicU64,
 }
 
 impl MessageBroker {
     pub fn new() -> Self {
         tracing::info!("Initializing MessageBroker");
         Self {
-            subscribers: Arc::new(RwLock::new(HashMap::new())),
+            subscribers: Arc::new(RwLock::new(Vec::new())),
+            next_id: AtomicU64::new(0),
         }
     }
 
-    pub async fn subscribe(&self, component_id: String, sender: Sender<BrokerMessage>) {
-        tracing::debug!("Component '{}' subscribing to MessageBroker", component_id);
-        let mut subs = self.subscribers.write().await;
-        subs.entry(component_id.clone())
-            .or_insert_with(Vec::new)
-            .push(sender);
-        tracing::info!(
-            "Component '{}' subscribed. Total subscribers: {}",
-            component_id,
-            subs.len()
-        );
+    /// Deliver the messages of `topics` to `sender`, wrapped by `wrap`, until
+    /// the returned subscription is dropped. Messages broadcast once this
+    /// returns are delivered in order.
+    pub fn subscribe<Input: Send + 'static>(
+        &self,
+        topics: &[Topic],
+        sender: &Sender<Input>,
+        wrap: fn(BrokerMessage) -> Input,
+    ) -> Subscription {
+        let (subscription, mut receiver) = self.subscribe_queue(topics);
+
+        let sender = sender.clone();
+        relm4::spawn(async move {
+            while let Some(message) = receiver.recv().await {
+                if sender.send(wrap(message)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        subscription
     }
 
-    pub async fn unsubscribe(&self, component_id: &str) {
+    /// Queue the messages of `topics` until the returned subscription is
+    /// dropped
+    fn subscribe_queue(&self, topics: &[Topic]) -> (Subscription, mpsc::Receiver<BrokerMessage>) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (queue, receiver) = mpsc::channel(SUBSCRIBER_QUEUE_SIZE);
+
+        let mut subscribers = self
+            .subscribers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        subscribers.push(Subscriber {
+            id,
+            topics: topics.to_vec(),
+            queue,
+        });
         tracing::debug!(
-            "Component '{}' unsubscribing from MessageBroker",
-            component_id
+            "Subscriber {} subscribed to {:?}. Total subscribers: {}",
+            id,
+            topics,
+            subscribers.len()
         );
-        let mut subs = self.subscribers.write().await;
-        if subs.remove(component_id).is_some() {
-            tracing::info!(
-                "Component '{}' unsubscribed. Remaining subscribers: {}",
-                component_id,
-                subs.len()
-            );
-        } else {
-            tracing::warn!("Component '{}' was not subscribed", component_id);
-        }
+
+        (
+            Subscription {
+                id,
+                subscribers: self.subscribers.clone(),
+            },
+            receiver,
+        )
     }
 
+    /// Deliver a message to the subscribers of its topic, waiting for room
+    /// in the queue of any that fell behind
     pub async fn broadcast(&self, message: BrokerMessage) {
-        let subs = self.subscribers.read().await;
-        let _subscriber_count = subs.len();
-        let mut _send_count = 0;
-        for senders in subs.values() {
-            for sender in senders {
-                if sender.send(message.clone()).is_ok() {
-                    _send_count += 1;
-                }
-            }
+        let topic = message.topic();
+        let queues: Vec<mpsc::Sender<BrokerMessage>> = self
+            .subscribers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|subscriber| subscriber.topics.contains(&topic))
+            .map(|subscriber| subscriber.queue.clone())
+            .collect();
+
+        for queue in queues {
+            // Fails only when the subscription was dropped meanwhile
+            let _ = queue.send(message.clone()).await;
         }
     }
 }
@@ -258,9 +399,115 @@ impl Default for MessageBroker {
     }
 }
 
+/// Keeps a subscriber subscribed. Dropping it unsubscribes right away.
+#[must_use = "the subscription ends when dropped"]
+pub struct Subscription {
+    id: u64,
+    subscribers: Subscribers,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut subscribers = self
+            .subscribers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        subscribers.retain(|subscriber| subscriber.id != self.id);
+        tracing::debug!(
+            "Subscriber {} unsubscribed. Remaining subscribers: {}",
+            self.id,
+            subscribers.len()
+        );
+    }
+}
+
+impl std::fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
 use once_cell::sync::Lazy;
 
 pub static BROKER: Lazy<MessageBroker> = Lazy::new(|| {
     tracing::info!("Initializing global MessageBroker instance");
     MessageBroker::new()
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn offline() -> BrokerMessage {
+        BrokerMessage::Connectivity(ConnectivityMessage::WentOffline)
+    }
+
+    fn power_saving() -> BrokerMessage {
+        BrokerMessage::Power(PowerMessage::SavingChanged { active: true })
+    }
+
+    #[tokio::test]
+    async fn test_only_subscribed_topics_are_delivered() {
+        let broker = MessageBroker::new();
+        let (sender, receiver) = relm4::channel();
+        let _subscription = broker.subscribe(&[Topic::Power], &sender, |message| message);
+
+        broker.broadcast(offline()).await;
+        broker.broadcast(power_saving()).await;
+
+        let delivered = tokio::time::timeout(Duration::from_secs(1), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(delivered.topic(), Topic::Power);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), receiver.recv())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dropping_the_subscription_unsubscribes() {
+        let broker = MessageBroker::new();
+        let (subscription, mut queue) = broker.subscribe_queue(&[Topic::Connectivity]);
+        let (_other, _other_queue) = broker.subscribe_queue(&[Topic::Connectivity]);
+        assert_eq!(broker.subscribers.read().unwrap().len(), 2);
+
+        drop(subscription);
+        assert_eq!(broker.subscribers.read().unwrap().len(), 1);
+        broker.broadcast(offline()).await;
+        // The queue closes with the subscription, nothing is delivered to it
+        assert!(queue.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_waits_for_a_subscriber_that_fell_behind() {
+        let broker = MessageBroker::new();
+        let (_subscription, mut queue) = broker.subscribe_queue(&[Topic::Connectivity]);
+
+        for _ in 0..SUBSCRIBER_QUEUE_SIZE {
+            broker.broadcast(offline()).await;
+        }
+        // The queue is full, so the next broadcast waits for room
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), broker.broadcast(offline()))
+                .await
+                .is_err()
+        );
+
+        queue.recv().await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), broker.broadcast(offline()))
+            .await
+            .unwrap();
+        let mut queued = 0;
+        while queue.try_recv().is_ok() {
+            queued += 1;
+        }
+        // Nothing was dropped: the timed out broadcast never queued its message
+        assert_eq!(queued, SUBSCRIBER_QUEUE_SIZE);
+    }
+}
@@ -1,35 +1,89 @@
 use crate::db::connection::DatabaseConnection;
-use crate::models::{MediaItemId, SourceId};
-use crate::services::core::backend::BackendService;
+use crate::models::{MediaItemId, PlaybackError, SourceId};
+use crate::services::core::playback_capabilities::PlaybackCapabilities;
+use crate::services::core::playback_decision::{self, PlaybackDecision, StreamChoice};
+use crate::services::core::{DownloadService, ResolveStatus, StreamResolutionService, metered};
 use anyhow::{Context, Result};
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum AppCommand {
-    StartPlayback { media_id: String },
+    StartPlayback {
+        media_id: String,
+        stream: StreamChoice,
+    },
+}
+
+/// The stream playback was started with
+#[derive(Debug, Clone)]
+pub struct StartedStream {
+    pub url: String,
+    /// How the server delivers the item, and why
+    pub decision: PlaybackDecision,
+    /// Codecs of the original, like "hevc, truehd"
+    pub codecs: Option<String>,
+    /// Length of the item, which the playlist of a transcode doesn't tell
+    pub duration: Option<Duration>,
+    /// URL the server streams from, behind the local cache if any. None for
+    /// offline downloads.
+    pub server_url: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub enum CommandResult {
-    PlaybackStarted { media_id: String, url: String },
-    Error(String),
+    PlaybackStarted {
+        media_id: String,
+        stream: StartedStream,
+    },
+    Error(PlaybackError),
 }
 
-pub async fn execute_command(command: AppCommand, db: &DatabaseConnection) -> CommandResult {
+/// Run a command, `on_status` hearing where resolving a stream is at
+pub async fn execute_command(
+    command: AppCommand,
+    db: &DatabaseConnection,
+    on_status: &(dyn Fn(ResolveStatus) + Send + Sync),
+) -> CommandResult {
     match command {
-        AppCommand::StartPlayback { media_id } => match start_playback(db, &media_id).await {
-            Ok(url) => CommandResult::PlaybackStarted { media_id, url },
-            Err(e) => CommandResult::Error(e.to_string()),
-        },
+        AppCommand::StartPlayback { media_id, stream } => {
+            match start_playback(db, &media_id, stream, on_status).await {
+                Ok(stream) => CommandResult::PlaybackStarted { media_id, stream },
+                Err(e) => CommandResult::Error(PlaybackError::from_error(&e)),
+            }
+        }
     }
 }
 
-async fn start_playback(db: &DatabaseConnection, media_id: &str) -> Result<String> {
+async fn start_playback(
+    db: &DatabaseConnection,
+    media_id: &str,
+    choice: StreamChoice,
+    on_status: &(dyn Fn(ResolveStatus) + Send + Sync),
+) -> Result<StartedStream> {
     use crate::db::repository::{MediaRepositoryImpl, Repository};
     use crate::services::cache_service::cache_service;
 
     // Get actual stream URL from backend using stateless BackendService
     let media_item_id = MediaItemId::new(media_id.to_string());
 
+    // Play finished offline downloads straight from disk
+    if let Some(download) = DownloadService::playable_download(db, &media_item_id).await? {
+        tracing::info!(
+            "Playing offline download of {} from {}",
+            media_id,
+            download.file_path
+        );
+        let url = url::Url::from_file_path(&download.file_path)
+            .map_err(|_| anyhow::anyhow!("Not an absolute path: {}", download.file_path))?;
+        return Ok(StartedStream {
+            url: url.into(),
+            decision: PlaybackDecision::downloaded(),
+            codecs: None,
+            duration: None,
+            server_url: None,
+        });
+    }
+
     // Get source_id from the media item
     let media_repo = MediaRepositoryImpl::new(db.clone());
     let media_entity = media_repo
@@ -37,9 +91,52 @@ async fn start_playback(db: &DatabaseConnection, media_id: &str) -> Result<Strin
         .await?
         .ok_or_else(|| anyhow::anyhow!("Media item not found: {}", media_item_id))?;
     let source_id = SourceId::new(media_entity.source_id);
+    let duration = media_entity
+        .duration_ms
+        .map(|ms| Duration::from_millis(ms.max(0) as u64));
+
+    // Resolve the stream within a time budget, trying other connections if need be
+    let stream_info = StreamResolutionService::resolve(db, &media_item_id, on_status).await?;
+    let codecs = Some(format!(
+        "{}, {}",
+        stream_info.video_codec, stream_info.audio_codec
+    ));
+    let capabilities = PlaybackCapabilities::current().await;
+    let decision = playback_decision::decide(
+        &stream_info,
+        &capabilities,
+        choice,
+        metered::active_restrictions().lower_stream_quality,
+    )?;
+    tracing::info!(
+        "{}: {} ({})",
+        media_id,
+        decision.method.label(),
+        decision.reason
+    );
 
-    // BackendService::get_stream_url handles all the backend creation and URL fetching
-    let stream_info = BackendService::get_stream_url(db, &media_item_id).await?;
+    // Transcoded streams are segmented and can't go through the byte-range cache
+    if let Some(option) = &decision.transcode {
+        tracing::info!("Streaming {} at {}", media_id, option.name);
+        return Ok(StartedStream {
+            url: option.url.clone(),
+            decision,
+            codecs,
+            duration,
+            server_url: Some(option.url.clone()),
+        });
+    }
+
+    // Files of local folders play straight from disk
+    if stream_info.url.starts_with("file://") {
+        return Ok(StartedStream {
+            url: stream_info.url,
+            decision,
+            codecs,
+            duration,
+            server_url: None,
+        });
+    }
 
     // Get cached stream - no fallback
     let cache_handle = cache_service()
@@ -47,6 +144,7 @@ async fn start_playback(db: &DatabaseConnection, media_id: &str) -> Result<Strin
         .await
         .context("Cache service is not available")?;
 
+    let server_url = stream_info.url.clone();
     let cached_stream = cache_handle
         .get_cached_stream(source_id, media_item_id, stream_info)
         .await
@@ -61,5 +159,11 @@ async fn start_playback(db: &DatabaseConnection, media_id: &str) -> Result<Strin
         playback_url
     );
 
-    Ok(playback_url.to_string())
+    Ok(StartedStream {
+        url: playback_url.to_string(),
+        decision,
+        codecs,
+        duration,
+        server_url: Some(server_url),
+    })
 }
@@ -0,0 +1,28 @@
+//! Confirmation before deleting files from a server, which can't be undone
+
+use adw::prelude::*;
+use libadwaita as adw;
+use relm4::gtk;
+
+/// Ask whether to delete `what` from its server, calling `on_confirm` if
+/// the user agrees
+pub fn present(parent: &impl IsA<gtk::Widget>, what: &str, on_confirm: impl Fn() + 'static) {
+    let dialog = adw::AlertDialog::builder()
+        .heading("Delete from Server?")
+        .body(format!(
+            "{} will be deleted from the server, for everyone using it. This can't be undone.",
+            what
+        ))
+        .close_response("cancel")
+        .default_response("cancel")
+        .build();
+    dialog.add_responses(&[("cancel", "_Cancel"), ("delete", "_Delete")]);
+    dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+
+    dialog.connect_response(None, move |_, response| {
+        if response == "delete" {
+            on_confirm();
+        }
+    });
+    dialog.present(Some(parent));
+}
@@ -0,0 +1,365 @@
+//! Filter controls shared by the library and search pages
+//!
+//! Each builder returns a self-contained section with a heading. Changes are
+//! reported through the given callback, so the owning page decides which
+//! message to send.
+
+use gtk::prelude::*;
+use relm4::gtk;
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::ui::pages::library::WatchStatus;
+
+fn section_with_header(title: &str) -> gtk::Box {
+    let section = gtk::Box::new(gtk::Orientation::Vertical, 6);
+
+    let header = gtk::Label::new(Some(title));
+    header.set_halign(gtk::Align::Start);
+    header.add_css_class("heading");
+    section.append(&header);
+
+    section
+}
+
+/// Genre checkboxes, reporting the genre that was toggled
+pub fn build_genre_section(
+    genres: &[String],
+    selected: &[String],
+    on_toggle: impl Fn(String) + Clone + 'static,
+) -> gtk::Box {
+    build_check_section("Genre", "No genres available", genres, selected, on_toggle)
+}
+
+/// Studio and network checkboxes, reporting the studio that was toggled
+pub fn build_studio_section(
+    studios: &[String],
+    selected: &[String],
+    on_toggle: impl Fn(String) + Clone + 'static,
+) -> gtk::Box {
+    build_check_section(
+        "Studio or Network",
+        "No studios available",
+        studios,
+        selected,
+        on_toggle,
+    )
+}
+
+/// Collection chips, reporting the collection that was toggled
+pub fn build_collection_section(
+    collections: &[String],
+    selected: &[String],
+    on_toggle: impl Fn(String) + Clone + 'static,
+) -> gtk::Box {
+    build_chip_section("Collection", collections, selected, on_toggle)
+}
+
+/// Tag chips, reporting the tag that was toggled
+pub fn build_tag_section(
+    tags: &[String],
+    selected: &[String],
+    on_toggle: impl Fn(String) + Clone + 'static,
+) -> gtk::Box {
+    build_chip_section("Tag", tags, selected, on_toggle)
+}
+
+/// A toggle chip for each of `options` under `title`, for short names that
+/// fit several to a line
+fn build_chip_section(
+    title: &str,
+    options: &[String],
+    selected: &[String],
+    on_toggle: impl Fn(String) + Clone + 'static,
+) -> gtk::Box {
+    let section = section_with_header(title);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_max_content_height(200);
+    scrolled.set_propagate_natural_height(true);
+    scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+
+    let chips = gtk::FlowBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .column_spacing(6)
+        .row_spacing(6)
+        .max_children_per_line(4)
+        .build();
+    for option in options {
+        let chip = gtk::ToggleButton::builder()
+            .label(option)
+            .active(selected.contains(option))
+            .css_classes(["pill"])
+            .build();
+        let option_clone = option.clone();
+        let on_toggle = on_toggle.clone();
+        chip.connect_toggled(move |_| on_toggle(option_clone.clone()));
+        chips.insert(&chip, -1);
+    }
+
+    scrolled.set_child(Some(&chips));
+    section.append(&scrolled);
+
+    section
+}
+
+/// Checkboxes for each of `options` under `title`
+fn build_check_section(
+    title: &str,
+    empty_text: &str,
+    options: &[String],
+    selected: &[String],
+    on_toggle: impl Fn(String) + Clone + 'static,
+) -> gtk::Box {
+    let section = section_with_header(title);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_max_content_height(200);
+    scrolled.set_propagate_natural_height(true);
+    scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+
+    let option_box = gtk::Box::new(gtk::Orientation::Vertical, 2);
+
+    if options.is_empty() {
+        let no_options = gtk::Label::new(Some(empty_text));
+        no_options.add_css_class("dim-label");
+        option_box.append(&no_options);
+    } else {
+        for option in options {
+            let check_button = gtk::CheckButton::with_label(option);
+            check_button.set_active(selected.contains(option));
+            check_button.add_css_class("flat");
+
+            let option_clone = option.clone();
+            let on_toggle = on_toggle.clone();
+            check_button.connect_toggled(move |_| on_toggle(option_clone.clone()));
+
+            option_box.append(&check_button);
+        }
+    }
+
+    scrolled.set_child(Some(&option_box));
+    section.append(&scrolled);
+
+    section
+}
+
+/// From/to year spin buttons bounded by `bounds` with a chip for each decade
+/// in them, reporting the new range
+pub fn build_year_section(
+    bounds: (Option<i32>, Option<i32>),
+    selected: (Option<i32>, Option<i32>),
+    on_change: impl Fn(Option<i32>, Option<i32>) + Clone + 'static,
+) -> gtk::Box {
+    let section = section_with_header("Year Range");
+    let (min_bound, max_bound) = bounds;
+    let (selected_min, selected_max) = selected;
+    // Each control changes one end of the range, keeping what the others set
+    let current = Rc::new(Cell::new(selected));
+
+    let year_controls = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+
+    let year_spin = |label: &str, value: Option<i32>, fallback: Option<i32>| {
+        let spin_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
+        let spin_label = gtk::Label::new(Some(label));
+        spin_label.set_halign(gtk::Align::Start);
+        spin_label.add_css_class("dim-label");
+        spin_box.append(&spin_label);
+
+        let spin = gtk::SpinButton::with_range(
+            min_bound.unwrap_or(1900) as f64,
+            max_bound.unwrap_or(2100) as f64,
+            1.0,
+        );
+        if let Some(year) = value.or(fallback) {
+            spin.set_value(year as f64);
+        }
+        spin_box.append(&spin);
+        year_controls.append(&spin_box);
+        spin
+    };
+
+    let min_spin = year_spin("From:", selected_min, min_bound);
+    let on_min_change = on_change.clone();
+    let min_current = current.clone();
+    min_spin.connect_value_changed(move |spin| {
+        let (_, max) = min_current.get();
+        min_current.set((Some(spin.value_as_int()), max));
+        on_min_change(Some(spin.value_as_int()), max);
+    });
+
+    let max_spin = year_spin("To:", selected_max, max_bound);
+    let on_max_change = on_change.clone();
+    let max_current = current.clone();
+    max_spin.connect_value_changed(move |spin| {
+        let (min, _) = max_current.get();
+        max_current.set((min, Some(spin.value_as_int())));
+        on_max_change(min, Some(spin.value_as_int()));
+    });
+
+    section.append(&year_controls);
+
+    // One click picks a whole decade, another clears it again
+    if let (Some(min), Some(max)) = (min_bound, max_bound) {
+        let chips = gtk::FlowBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .column_spacing(6)
+            .row_spacing(6)
+            .max_children_per_line(5)
+            .build();
+        for decade in decades(min, max) {
+            let range = (Some(decade), Some(decade + 9));
+            let chip = gtk::ToggleButton::builder()
+                .label(decade_label(decade))
+                .active(selected == range)
+                .css_classes(["pill"])
+                .build();
+            let on_change = on_change.clone();
+            let current = current.clone();
+            chip.connect_clicked(move |chip| {
+                if !chip.is_active() {
+                    current.set((None, None));
+                    on_change(None, None);
+                    return;
+                }
+                // Only one decade can be picked at a time
+                if let Some(chips) = chip.parent().and_then(|child| child.parent()) {
+                    let mut child = chips.first_child();
+                    while let Some(flow_child) = child {
+                        if let Some(other) = flow_child
+                            .first_child()
+                            .and_downcast::<gtk::ToggleButton>()
+                            .filter(|other| other != chip)
+                        {
+                            other.set_active(false);
+                        }
+                        child = flow_child.next_sibling();
+                    }
+                }
+                current.set(range);
+                on_change(range.0, range.1);
+            });
+            chips.insert(&chip, -1);
+        }
+        section.append(&chips);
+    }
+
+    section
+}
+
+/// First years of the decades from `min` to `max`
+fn decades(min: i32, max: i32) -> impl Iterator<Item = i32> {
+    (min.div_euclid(10)..=max.div_euclid(10)).map(|decade| decade * 10)
+}
+
+/// Short name of the decade starting in `year`, like "80s" or "2010s"
+fn decade_label(year: i32) -> String {
+    if (1910..2000).contains(&year) {
+        format!("{:02}s", year % 100)
+    } else {
+        format!("{}s", year)
+    }
+}
+
+/// Minimum rating scale, reporting None when set back to zero
+pub fn build_rating_section(
+    min_rating: Option<f32>,
+    on_change: impl Fn(Option<f32>) + 'static,
+) -> gtk::Box {
+    let section = section_with_header("Minimum Rating");
+
+    let scale = gtk::Scale::with_range(gtk::Orientation::Horizontal, 0.0, 10.0, 0.5);
+    scale.set_draw_value(true);
+    scale.set_value_pos(gtk::PositionType::Right);
+    scale.set_value(min_rating.unwrap_or(0.0) as f64);
+
+    scale.connect_value_changed(move |scale| {
+        let value = scale.value() as f32;
+        on_change(if value > 0.0 { Some(value) } else { None });
+    });
+
+    section.append(&scale);
+
+    section
+}
+
+/// "4K only" and "HDR only" checkboxes
+pub fn build_quality_section(
+    uhd_only: bool,
+    hdr_only: bool,
+    on_uhd_toggle: impl Fn(bool) + 'static,
+    on_hdr_toggle: impl Fn(bool) + 'static,
+) -> gtk::Box {
+    let section = section_with_header("Video Quality");
+
+    let uhd_check = gtk::CheckButton::with_label("4K only");
+    uhd_check.set_active(uhd_only);
+    uhd_check.connect_toggled(move |check| on_uhd_toggle(check.is_active()));
+    section.append(&uhd_check);
+
+    let hdr_check = gtk::CheckButton::with_label("HDR only");
+    hdr_check.set_active(hdr_only);
+    hdr_check.connect_toggled(move |check| on_hdr_toggle(check.is_active()));
+    section.append(&hdr_check);
+
+    section
+}
+
+/// All/Watched/Unwatched/In Progress radio buttons
+pub fn build_watch_status_section(
+    status: WatchStatus,
+    on_change: impl Fn(WatchStatus) + Clone + 'static,
+) -> gtk::Box {
+    let options = [
+        (WatchStatus::Watched, "Watched".to_string()),
+        (WatchStatus::Unwatched, "Unwatched".to_string()),
+        (WatchStatus::InProgress, "In Progress".to_string()),
+    ];
+    let selected = (status != WatchStatus::All).then_some(status);
+
+    build_choice_section(
+        "Watch Status",
+        "All",
+        &options,
+        selected.as_ref(),
+        move |choice| on_change(choice.unwrap_or(WatchStatus::All)),
+    )
+}
+
+/// Radio buttons for picking one of `options`, or none of them with the `all_label` entry
+pub fn build_choice_section<T: Clone + PartialEq + 'static>(
+    title: &str,
+    all_label: &str,
+    options: &[(T, String)],
+    selected: Option<&T>,
+    on_select: impl Fn(Option<T>) + Clone + 'static,
+) -> gtk::Box {
+    let section = section_with_header(title);
+
+    let all_radio = gtk::CheckButton::with_label(all_label);
+    all_radio.set_active(selected.is_none());
+    let on_all = on_select.clone();
+    all_radio.connect_toggled(move |btn| {
+        if btn.is_active() {
+            on_all(None);
+        }
+    });
+    section.append(&all_radio);
+
+    for (value, label) in options {
+        let radio = gtk::CheckButton::with_label(label);
+        radio.set_group(Some(&all_radio));
+        radio.set_active(selected == Some(value));
+
+        let value = value.clone();
+        let on_select = on_select.clone();
+        radio.connect_toggled(move |btn| {
+            if btn.is_active() {
+                on_select(Some(value.clone()));
+            }
+        });
+        section.append(&radio);
+    }
+
+    section
+}
@@ -1,27 +1,63 @@
 use relm4::gtk;
 
-/// Load an image from a URL and create a GDK texture
+use crate::services::core::dominant_color::{self, DominantColor};
+use crate::services::core::image_quality;
+
+/// Load an image shown at `width` x `height`, -1 for an unconstrained side,
+/// from a URL and create a GDK texture
 pub async fn load_image_from_url(
     url: &str,
-    _width: i32,
-    _height: i32,
+    width: i32,
+    height: i32,
 ) -> Result<gtk::gdk::Texture, String> {
+    let bytes = download_image(url, width, height).await?;
+    texture_from_bytes(&bytes)
+}
+
+/// Load an image like [`load_image_from_url`], also finding the dominant
+/// color of the artwork of `item_id` unless it is known already. The color
+/// is None if the image can't be decoded to count its colors.
+pub async fn load_image_with_color(
+    url: &str,
+    width: i32,
+    height: i32,
+    item_id: &str,
+) -> Result<(gtk::gdk::Texture, Option<DominantColor>), String> {
+    let bytes = download_image(url, width, height).await?;
+    let texture = texture_from_bytes(&bytes)?;
+
+    if let Some(color) = dominant_color::cached(item_id) {
+        return Ok((texture, Some(color)));
+    }
+
+    let color = tokio::task::spawn_blocking(move || dominant_color::from_image_bytes(&bytes))
+        .await
+        .map_err(|e| format!("Failed to find dominant color: {}", e))?;
+    if let Some(color) = color {
+        dominant_color::remember(item_id, color);
+    }
+
+    Ok((texture, color))
+}
+
+async fn download_image(url: &str, width: i32, height: i32) -> Result<bytes::Bytes, String> {
+    // Ask the server for the size shown rather than the full image
+    let (width, height) = image_quality::pixel_size(width.max(0) as u32, height.max(0) as u32);
+    let url = image_quality::sized_url(url, width, height);
+
     // Download the image
-    let response = reqwest::get(url)
+    let response = reqwest::get(&url)
         .await
         .map_err(|e| format!("Failed to download image: {}", e))?;
 
-    let bytes = response
+    response
         .bytes()
         .await
-        .map_err(|e| format!("Failed to read bytes: {}", e))?;
-
-    // Create texture from bytes
-    let glib_bytes = gtk::glib::Bytes::from(&bytes[..]);
-    let texture = gtk::gdk::Texture::from_bytes(&glib_bytes)
-        .map_err(|e| format!("Failed to create texture: {}", e))?;
+        .map_err(|e| format!("Failed to read bytes: {}", e))
+}
 
-    // If width and height are specified (not -1), we could resize here
-    // For now, just return the texture as is
-    Ok(texture)
+fn texture_from_bytes(bytes: &[u8]) -> Result<gtk::gdk::Texture, String> {
+    let glib_bytes = gtk::glib::Bytes::from(bytes);
+    gtk::gdk::Texture::from_bytes(&glib_bytes)
+        .map_err(|e| format!("Failed to create texture: {}", e))
 }
@@ -0,0 +1,109 @@
+//! Using card grids without a mouse
+//!
+//! Cards sit in flow boxes, which already move focus between their children
+//! with the arrow keys. What they don't do is open a card's context menu
+//! from the keyboard, or carry on into the next grid when the arrow keys run
+//! off the first or last row of one, as in a library grouped by date added.
+
+use gtk::prelude::*;
+use relm4::gtk;
+
+/// Whether `key` asks for a context menu: the Menu key or Shift+F10
+pub fn is_context_menu_key(key: gtk::gdk::Key, state: gtk::gdk::ModifierType) -> bool {
+    key == gtk::gdk::Key::Menu
+        || (key == gtk::gdk::Key::F10 && state.contains(gtk::gdk::ModifierType::SHIFT_MASK))
+}
+
+/// Open `popover` when the context menu key is pressed while focus is in
+/// `widget`, pointing at the middle of the popover's parent
+pub fn connect_context_menu_key(widget: &impl IsA<gtk::Widget>, popover: &gtk::PopoverMenu) {
+    let keys = gtk::EventControllerKey::new();
+    let popover = popover.downgrade();
+    keys.connect_key_pressed(move |_, key, _, state| {
+        if !is_context_menu_key(key, state) {
+            return gtk::glib::Propagation::Proceed;
+        }
+        let Some(popover) = popover.upgrade() else {
+            return gtk::glib::Propagation::Proceed;
+        };
+
+        if let Some(parent) = popover.parent() {
+            let rect = gtk::gdk::Rectangle::new(parent.width() / 2, parent.height() / 2, 1, 1);
+            popover.set_pointing_to(Some(&rect));
+        }
+        popover.popup();
+        gtk::glib::Propagation::Stop
+    });
+    widget.add_controller(keys);
+}
+
+/// Move focus on to the flow boxes next to `flow_box` in its parent when
+/// Up leaves its first row or Down its last, keeping to the same column
+pub fn connect_grid_edges(flow_box: &gtk::FlowBox) {
+    flow_box.connect_keynav_failed(|flow_box, direction| {
+        let down = match direction {
+            gtk::DirectionType::Down => true,
+            gtk::DirectionType::Up => false,
+            _ => return gtk::glib::Propagation::Proceed,
+        };
+
+        let mut sibling = if down {
+            flow_box.next_sibling()
+        } else {
+            flow_box.prev_sibling()
+        };
+        let target = loop {
+            match sibling {
+                Some(widget) => match widget.downcast::<gtk::FlowBox>() {
+                    Ok(target) if target.first_child().is_some() => break target,
+                    Ok(target) => sibling = next(target.upcast_ref(), down),
+                    Err(widget) => sibling = next(&widget, down),
+                },
+                None => return gtk::glib::Propagation::Proceed,
+            }
+        };
+
+        let x = flow_box
+            .focus_child()
+            .and_then(|child| child.compute_bounds(flow_box))
+            .map_or(0.0, |bounds| bounds.x());
+        match nearest_on_edge_row(&target, x, down) {
+            Some(child) => {
+                child.grab_focus();
+                gtk::glib::Propagation::Stop
+            }
+            None => gtk::glib::Propagation::Proceed,
+        }
+    });
+}
+
+fn next(widget: &gtk::Widget, down: bool) -> Option<gtk::Widget> {
+    if down {
+        widget.next_sibling()
+    } else {
+        widget.prev_sibling()
+    }
+}
+
+/// The child of the first row, or the last when coming from below, closest
+/// to `x`
+fn nearest_on_edge_row(flow_box: &gtk::FlowBox, x: f32, first_row: bool) -> Option<gtk::Widget> {
+    let mut children = Vec::new();
+    let mut child = flow_box.first_child();
+    while let Some(widget) = child {
+        child = widget.next_sibling();
+        if let Some(bounds) = widget.compute_bounds(flow_box) {
+            children.push((widget, bounds.x(), bounds.y()));
+        }
+    }
+
+    let edge = children
+        .iter()
+        .map(|(_, _, y)| *y)
+        .reduce(|a, b| if first_row { a.min(b) } else { a.max(b) })?;
+    children
+        .into_iter()
+        .filter(|(_, _, y)| *y == edge)
+        .min_by(|(_, a, _), (_, b, _)| (a - x).abs().total_cmp(&(b - x).abs()))
+        .map(|(widget, _, _)| widget)
+}
@@ -0,0 +1,196 @@
+//! Tag and label chips on detail pages
+//!
+//! Clicking a chip browses the library for everything else carrying it. Where
+//! the server lets labels be edited (Plex), the context menu of a chip takes
+//! its label off and a last chip puts a new one on.
+
+use adw::prelude::*;
+use libadwaita as adw;
+use relm4::gtk;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::keyboard;
+
+#[derive(Debug, Clone)]
+pub enum ChipAction {
+    Browse(String),
+    Remove(String),
+    Add,
+}
+
+pub struct LabelChips {
+    root: gtk::Box,
+    chips: gtk::FlowBox,
+    menu: gtk::PopoverMenu,
+    /// Label of the chip whose context menu is open
+    target: Rc<RefCell<Option<String>>>,
+    on_action: Rc<dyn Fn(ChipAction)>,
+}
+
+impl std::fmt::Debug for LabelChips {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LabelChips")
+            .field("target", &self.target)
+            .finish()
+    }
+}
+
+impl LabelChips {
+    pub fn new(on_action: impl Fn(ChipAction) + 'static) -> Self {
+        let root = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        let chips = gtk::FlowBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .column_spacing(6)
+            .row_spacing(6)
+            .max_children_per_line(8)
+            .hexpand(true)
+            .build();
+        root.append(&chips);
+
+        // One menu for all chips, pointed at the one it was opened on
+        let model = gtk::gio::Menu::new();
+        model.append(Some("Remove Label"), Some("labels.remove"));
+        let menu = gtk::PopoverMenu::from_model(Some(&model));
+        menu.set_parent(&root);
+        menu.set_has_arrow(false);
+
+        let target = Rc::new(RefCell::new(None));
+        let on_action: Rc<dyn Fn(ChipAction)> = Rc::new(on_action);
+
+        let actions = gtk::gio::SimpleActionGroup::new();
+        let remove = gtk::gio::SimpleAction::new("remove", None);
+        let target_clone = target.clone();
+        let on_action_clone = on_action.clone();
+        remove.connect_activate(move |_, _| {
+            if let Some(label) = target_clone.borrow_mut().take() {
+                on_action_clone(ChipAction::Remove(label));
+            }
+        });
+        actions.add_action(&remove);
+        root.insert_action_group("labels", Some(&actions));
+
+        Self {
+            root,
+            chips,
+            menu,
+            target,
+            on_action,
+        }
+    }
+
+    pub fn widget(&self) -> &gtk::Box {
+        &self.root
+    }
+
+    /// Show a chip for each of `labels`, and the means to edit them if
+    /// `editable`
+    pub fn set_labels(&self, labels: &[String], editable: bool) {
+        while let Some(child) = self.chips.first_child() {
+            self.chips.remove(&child);
+        }
+
+        for label in labels {
+            let chip = gtk::Button::builder()
+                .label(label)
+                .css_classes(["pill", "small"])
+                .build();
+            let on_action = self.on_action.clone();
+            let label_clone = label.clone();
+            chip.connect_clicked(move |_| on_action(ChipAction::Browse(label_clone.clone())));
+
+            if editable {
+                self.connect_menu(&chip, label);
+            }
+            self.chips.insert(&chip, -1);
+        }
+
+        if editable {
+            let add = gtk::Button::builder()
+                .icon_name("list-add-symbolic")
+                .tooltip_text("Add Label")
+                .css_classes(["pill", "small", "flat"])
+                .build();
+            let on_action = self.on_action.clone();
+            add.connect_clicked(move |_| on_action(ChipAction::Add));
+            self.chips.insert(&add, -1);
+        }
+    }
+
+    /// Open the menu on right click or the context menu key
+    fn connect_menu(&self, chip: &gtk::Button, label: &str) {
+        let open = {
+            let root = self.root.downgrade();
+            let menu = self.menu.clone();
+            let target = self.target.clone();
+            let label = label.to_string();
+            move |chip: &gtk::Widget, x: f32, y: f32| {
+                let Some(root) = root.upgrade() else {
+                    return;
+                };
+                let Some(point) = chip.compute_point(&root, &gtk::graphene::Point::new(x, y))
+                else {
+                    return;
+                };
+                *target.borrow_mut() = Some(label.clone());
+                let rect = gtk::gdk::Rectangle::new(point.x() as i32, point.y() as i32, 1, 1);
+                menu.set_pointing_to(Some(&rect));
+                menu.popup();
+            }
+        };
+
+        let right_click = gtk::GestureClick::new();
+        right_click.set_button(3);
+        let open_clone = open.clone();
+        right_click.connect_released(move |gesture, _, x, y| {
+            if let Some(chip) = gesture.widget() {
+                open_clone(&chip, x as f32, y as f32);
+            }
+        });
+        chip.add_controller(right_click);
+
+        let keys = gtk::EventControllerKey::new();
+        keys.connect_key_pressed(move |controller, key, _, state| {
+            if !keyboard::is_context_menu_key(key, state) {
+                return gtk::glib::Propagation::Proceed;
+            }
+            if let Some(chip) = controller.widget() {
+                open(&chip, chip.width() as f32 / 2.0, chip.height() as f32 / 2.0);
+            }
+            gtk::glib::Propagation::Stop
+        });
+        chip.add_controller(keys);
+    }
+}
+
+/// Ask for a label to add, calling `on_label` with it unless cancelled
+pub fn ask_for_label(parent: &impl IsA<gtk::Widget>, on_label: impl Fn(String) + 'static) {
+    let entry = gtk::Entry::builder()
+        .placeholder_text("Label")
+        .activates_default(true)
+        .build();
+    let dialog = adw::AlertDialog::builder()
+        .heading("Add Label")
+        .extra_child(&entry)
+        .close_response("cancel")
+        .default_response("add")
+        .build();
+    dialog.add_responses(&[("cancel", "_Cancel"), ("add", "_Add")]);
+    dialog.set_response_appearance("add", adw::ResponseAppearance::Suggested);
+    dialog.set_response_enabled("add", false);
+
+    let dialog_weak = dialog.downgrade();
+    entry.connect_changed(move |entry| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.set_response_enabled("add", !entry.text().trim().is_empty());
+        }
+    });
+
+    dialog.connect_response(None, move |_, response| {
+        let label = entry.text().trim().to_string();
+        if response == "add" && !label.is_empty() {
+            on_label(label);
+        }
+    });
+    dialog.present(Some(parent));
+}
@@ -1,6 +1,15 @@
+pub mod accessibility;
 pub mod broker;
 pub mod commands;
+pub mod delete_confirmation;
+pub mod filter_widgets;
 pub mod image_helpers;
+pub mod keyboard;
+pub mod label_chips;
 pub mod messages;
 pub mod person_card;
 pub mod sync_status;
+pub mod theme_music;
+pub mod tint;
+pub mod type_ahead;
+pub mod undo_toast;
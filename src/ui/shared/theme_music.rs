@@ -0,0 +1,35 @@
+//! Background theme music of detail pages
+
+use gtk::prelude::*;
+use relm4::gtk;
+
+/// Volume of theme music, quiet enough to read the page over
+const THEME_MUSIC_VOLUME: f64 = 0.2;
+
+/// Loop the theme music at `url` while `page` is on screen, pausing it when
+/// another page or the player covers it. Playback stops when the returned
+/// media file is dropped.
+pub fn play_while_shown(url: &str, page: &impl IsA<gtk::Widget>) -> gtk::MediaFile {
+    let media = gtk::MediaFile::for_file(&gtk::gio::File::for_uri(url));
+    media.set_volume(THEME_MUSIC_VOLUME);
+    media.set_loop(true);
+
+    let page = page.as_ref();
+    let weak = media.downgrade();
+    page.connect_map(move |_| {
+        if let Some(media) = weak.upgrade() {
+            media.play();
+        }
+    });
+    let weak = media.downgrade();
+    page.connect_unmap(move |_| {
+        if let Some(media) = weak.upgrade() {
+            media.pause();
+        }
+    });
+
+    if page.is_mapped() {
+        media.play();
+    }
+    media
+}
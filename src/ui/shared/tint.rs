@@ -0,0 +1,79 @@
+//! Tinting widgets with the dominant color of an item's artwork
+//!
+//! Each color gets a `tint-rrggbb` style class. Its rules are added to a
+//! display-wide stylesheet the first time the color is used, for each of
+//! the tinted surfaces: `hero-tint` on detail pages and
+//! `player-loading-tint` in the player.
+
+use gtk::prelude::*;
+use relm4::gtk;
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use crate::services::core::dominant_color::DominantColor;
+
+const CLASS_PREFIX: &str = "tint-";
+
+#[derive(Default)]
+struct Stylesheet {
+    provider: Option<gtk::CssProvider>,
+    css: String,
+    classes: HashSet<String>,
+}
+
+thread_local! {
+    static STYLESHEET: RefCell<Stylesheet> = RefCell::new(Stylesheet::default());
+}
+
+/// Tint `widget` with `color`, or remove its tint when there is none
+pub fn apply(widget: &impl IsA<gtk::Widget>, color: Option<DominantColor>) {
+    let widget = widget.as_ref();
+    for class in widget.css_classes() {
+        if class.starts_with(CLASS_PREFIX) {
+            widget.remove_css_class(&class);
+        }
+    }
+
+    if let Some(color) = color {
+        widget.add_css_class(&class_for(color));
+    }
+}
+
+/// The style class of a color, adding its rules on first use
+fn class_for(color: DominantColor) -> String {
+    let class = format!(
+        "{}{:02x}{:02x}{:02x}",
+        CLASS_PREFIX, color.red, color.green, color.blue
+    );
+
+    STYLESHEET.with_borrow_mut(|stylesheet| {
+        if !stylesheet.classes.insert(class.clone()) {
+            return;
+        }
+        stylesheet.css.push_str(&rules(&class, color));
+
+        let Some(display) = gtk::gdk::Display::default() else {
+            return;
+        };
+        let provider = stylesheet.provider.get_or_insert_with(|| {
+            let provider = gtk::CssProvider::new();
+            gtk::style_context_add_provider_for_display(
+                &display,
+                &provider,
+                gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+            );
+            provider
+        });
+        provider.load_from_string(&stylesheet.css);
+    });
+
+    class
+}
+
+fn rules(class: &str, color: DominantColor) -> String {
+    let rgb = format!("{}, {}, {}", color.red, color.green, color.blue);
+    format!(
+        ".hero-tint.{class} {{ background-image: linear-gradient(to top, rgba({rgb}, 0.55), rgba({rgb}, 0.15) 60%, rgba({rgb}, 0) 100%); }}\n\
+         .player-loading-tint.{class} {{ background-image: radial-gradient(circle, rgba({rgb}, 0.5), rgba({rgb}, 0) 70%); }}\n"
+    )
+}
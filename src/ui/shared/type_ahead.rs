@@ -0,0 +1,82 @@
+//! Type-to-find in lists and grids
+//!
+//! Keys typed in quick succession build up the text to find, a pause
+//! starts over. Finding only moves focus to the first match, unlike the
+//! library filter it never hides anything.
+
+use std::time::{Duration, Instant};
+
+/// Pause after which typing starts new text
+const TIMEOUT: Duration = Duration::from_millis(1000);
+
+#[derive(Debug, Default)]
+pub struct TypeAhead {
+    text: String,
+    typed_at: Option<Instant>,
+}
+
+impl TypeAhead {
+    /// Add a typed character, returning the text to find
+    pub fn push(&mut self, c: char) -> &str {
+        self.push_at(c, Instant::now())
+    }
+
+    fn push_at(&mut self, c: char, now: Instant) -> &str {
+        if self
+            .typed_at
+            .is_none_or(|at| now.duration_since(at) > TIMEOUT)
+        {
+            self.text.clear();
+        }
+        self.typed_at = Some(now);
+        self.text.extend(c.to_lowercase());
+        &self.text
+    }
+}
+
+/// Index of the first title starting with `text`, ignoring case, or of the
+/// first containing it when none starts with it
+pub fn find<'a>(titles: impl Iterator<Item = &'a str> + Clone, text: &str) -> Option<usize> {
+    let text = text.to_lowercase();
+    titles
+        .clone()
+        .position(|title| title.to_lowercase().starts_with(&text))
+        .or_else(|| titles.position(|title| title.to_lowercase().contains(&text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quick_keys_build_up_the_text() {
+        let start = Instant::now();
+        let mut type_ahead = TypeAhead::default();
+        type_ahead.push_at('T', start);
+        assert_eq!(
+            type_ahead.push_at('h', start + Duration::from_millis(300)),
+            "th"
+        );
+    }
+
+    #[test]
+    fn test_pause_starts_over() {
+        let start = Instant::now();
+        let mut type_ahead = TypeAhead::default();
+        type_ahead.push_at('t', start);
+        assert_eq!(type_ahead.push_at('p', start + Duration::from_secs(2)), "p");
+    }
+
+    #[test]
+    fn test_find_prefers_titles_starting_with_the_text() {
+        let titles = ["The Pilot", "Pilot Light", "Piloting"];
+        assert_eq!(find(titles.iter().copied(), "pilot"), Some(1));
+    }
+
+    #[test]
+    fn test_find_falls_back_to_titles_containing_the_text() {
+        let titles = ["Winter Is Coming", "The Kingsroad"];
+        assert_eq!(find(titles.iter().copied(), "kings"), Some(1));
+        assert_eq!(find(titles.iter().copied(), "dragons"), None);
+    }
+}
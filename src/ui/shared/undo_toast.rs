@@ -0,0 +1,101 @@
+//! Toasts offering to take back an action that just ran
+
+use adw::prelude::*;
+use libadwaita as adw;
+use relm4::gtk;
+use std::sync::Arc;
+
+use crate::services::commands::{Command, Undo, UndoableCommand};
+
+/// How long the toast stays, in seconds
+const TIMEOUT: u32 = 5;
+
+/// Toast overlay of the window holding `widget`
+fn overlay_of(widget: &impl IsA<gtk::Widget>) -> Option<adw::ToastOverlay> {
+    widget
+        .ancestor(adw::ToastOverlay::static_type())
+        .and_downcast::<adw::ToastOverlay>()
+}
+
+/// Show `title` in the window holding `widget`, with an Undo button calling
+/// `on_undo`
+pub fn present(widget: &impl IsA<gtk::Widget>, title: &str, on_undo: impl Fn() + 'static) {
+    let Some(overlay) = overlay_of(widget) else {
+        tracing::warn!("No toast overlay to offer undoing '{}' in", title);
+        return;
+    };
+
+    let toast = adw::Toast::builder()
+        .title(title)
+        .button_label("Undo")
+        .timeout(TIMEOUT)
+        .build();
+    toast.connect_button_clicked(move |_| on_undo());
+    overlay.add_toast(toast);
+}
+
+/// Show `title` with an Undo button running `undo`, telling the user when
+/// taking the action back failed
+pub fn present_command(widget: &impl IsA<gtk::Widget>, title: &str, undo: Undo) {
+    let undo: Arc<dyn Command<()>> = undo.into();
+    let overlay = overlay_of(widget);
+    present(widget, title, move || {
+        let undo = undo.clone();
+        let overlay = overlay.clone();
+        relm4::spawn_local(async move {
+            // Undoing talks to the database and servers, so not on the UI loop
+            let result = match relm4::spawn(async move { undo.execute().await }).await {
+                Ok(result) => result,
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = result {
+                tracing::error!("Failed to undo: {}", e);
+                if let Some(overlay) = overlay {
+                    overlay.add_toast(adw::Toast::new(&format!("Couldn't undo: {}", e)));
+                }
+            }
+        });
+    });
+}
+
+/// An action that ran in the background, for its page to offer taking back
+pub struct UndoOffer {
+    title: &'static str,
+    undo: Undo,
+}
+
+impl UndoOffer {
+    /// Offer running `undo`, titled `title`
+    pub fn new(title: &'static str, undo: Undo) -> Self {
+        Self { title, undo }
+    }
+
+    /// Run `command`, returning the offer to take it back titled `title`, or
+    /// None after logging `failure` when it failed
+    pub async fn run(
+        command: impl UndoableCommand,
+        title: &'static str,
+        failure: &str,
+    ) -> Option<Self> {
+        match command.execute_undoable().await {
+            Ok(undo) => Some(Self::new(title, undo)),
+            Err(e) => {
+                tracing::error!("{}: {}", failure, e);
+                None
+            }
+        }
+    }
+
+    /// Show the offer in the window holding `widget`
+    pub fn present(self, widget: &impl IsA<gtk::Widget>) {
+        present_command(widget, self.title, self.undo);
+    }
+}
+
+impl std::fmt::Debug for UndoOffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UndoOffer")
+            .field("title", &self.title)
+            .finish_non_exhaustive()
+    }
+}
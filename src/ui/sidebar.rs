@@ -1,4 +1,6 @@
-use gtk::prelude::*;
+use adw::prelude::*;
+use gtk::gio;
+use libadwaita as adw;
 use relm4::factory::{DynamicIndex, FactoryComponent, FactorySender, FactoryVecDeque};
 use relm4::prelude::*;
 use relm4::{Component, ComponentParts, ComponentSender, gtk};
@@ -7,11 +9,16 @@ use tracing::{debug, error, info, warn};
 
 use crate::db::connection::DatabaseConnection;
 use crate::models::auth_provider::{Source, SourceType};
-use crate::models::{Library, LibraryId, LibraryType, SourceId};
+use crate::models::{Library, LibraryId, LibraryType, MediaItemId, SourceId};
 use crate::services::commands::{Command, auth_commands::LoadSourcesCommand};
+use crate::services::config_service::config_service;
 use crate::services::core::ConnectionType as ConnType;
+use crate::services::core::connectivity;
 use crate::services::core::media::MediaService;
-use crate::ui::shared::broker::{BROKER, BrokerMessage, SourceMessage};
+use crate::ui::shared::broker::{
+    BROKER, BrokerMessage, ConnectivityMessage, DataMessage, SourceMessage, Subscription, Topic,
+};
+use crate::ui::shared::undo_toast;
 
 /// Connection state for sources
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,6 +31,13 @@ pub enum ConnectionState {
     Disconnected,
 }
 
+/// An item waiting in the queue
+#[derive(Debug, Clone)]
+pub struct QueuedItem {
+    pub id: MediaItemId,
+    pub title: String,
+}
+
 // Messages for the sidebar component
 #[derive(Debug)]
 pub enum SidebarInput {
@@ -41,17 +55,29 @@ pub enum SidebarInput {
     ManageSources,
     /// Update connection status
     UpdateConnectionStatus(String),
-    /// Update specific source connection status
-    UpdateSourceConnectionStatus {
+    /// Unwatched count of a library changed
+    UnwatchedCountChanged { library_id: LibraryId, count: u64 },
+    /// A media card was dropped on the queue
+    QueueDropped(String),
+    /// A dropped item was found to be playable and joins the queue
+    ItemQueued(QueuedItem),
+    /// Play everything in the queue
+    PlayQueue,
+    /// Take one item out of the queue
+    RemoveFromQueue(MediaItemId),
+    /// Put back an item taken out of the queue where it was
+    RestoreQueueItem { index: usize, item: QueuedItem },
+    /// Empty the queue
+    ClearQueue,
+    /// Put back the items of a cleared queue
+    RestoreQueue(Vec<QueuedItem>),
+    /// Reconnect chosen from a source's menu
+    ReconnectSource(SourceId),
+    /// Re-authenticate chosen from a source's menu
+    ReauthenticateSource {
         source_id: SourceId,
-        state: ConnectionState,
-        error: Option<String>,
-        connection_type: Option<ConnType>,
-    },
-    /// Connection check from monitor (doesn't override SyncFailed)
-    ConnectionCheckResult {
-        source_id: SourceId,
-        is_connected: bool,
+        source_name: String,
+        source_type: String,
     },
     /// Broker message received
     BrokerMsg(BrokerMessage),
@@ -65,6 +91,16 @@ pub enum SidebarOutput {
     NavigateToLibrary(LibraryId),
     /// Navigate to source management
     NavigateToSources,
+    /// Play the queued items in order
+    PlayQueue(Vec<MediaItemId>),
+    /// Check a source's connection again and retry its sync
+    ReconnectSource(SourceId),
+    /// Sign in to a source again
+    ReauthenticateSource {
+        source_id: SourceId,
+        source_name: String,
+        source_type: String,
+    },
 }
 
 // Source group factory component
@@ -80,20 +116,260 @@ pub struct SourceGroup {
     connection_type: Option<ConnType>, // Track connection type (local/remote/relay)
     sync_error_message: Option<String>,
     is_syncing: bool,
+    /// Library IDs in the order the user arranged them
+    library_order: Vec<String>,
+    hidden_libraries: HashSet<String>,
+    /// Whether hidden libraries are shown so they can be rearranged or unhidden
+    is_editing: bool,
+    /// Library ID -> number of unwatched movies and episodes
+    unwatched_counts: HashMap<String, u64>,
+    /// The server rejected the stored credentials
+    needs_auth: bool,
+    /// Context menu of the header, unparented in shutdown
+    popover: Option<gtk::PopoverMenu>,
+    /// Enabled while there is an error to show
+    view_error_action: Option<gio::SimpleAction>,
+    /// Disabled while every server is unreachable
+    reconnect_action: Option<gio::SimpleAction>,
 }
 
 impl SourceGroup {
-    fn update_library_list(&self, library_list: &gtk::ListBox) {
+    /// Count the unwatched items of each library and hand them to the group
+    async fn load_unwatched_counts(
+        db: &DatabaseConnection,
+        libraries: &[Library],
+        sender: &FactorySender<Self>,
+    ) {
+        let mut counts = HashMap::new();
+        for library in libraries {
+            let library_id = LibraryId::new(library.id.clone());
+            match MediaService::get_unwatched_count(db, &library_id).await {
+                Ok(count) => {
+                    counts.insert(library.id.clone(), count);
+                }
+                Err(e) => warn!("Failed to count unwatched items in {}: {}", library.id, e),
+            }
+        }
+        sender.input(SourceGroupInput::UnwatchedCountsLoaded(counts));
+    }
+
+    /// Libraries in the user's order; ones that were never arranged go last
+    fn ordered_libraries(&self) -> Vec<&Library> {
+        let mut libraries: Vec<&Library> = self.libraries.iter().collect();
+        libraries.sort_by_key(|library| {
+            self.library_order
+                .iter()
+                .position(|id| *id == library.id)
+                .unwrap_or(usize::MAX)
+        });
+        libraries
+    }
+
+    /// Move `library_id` next to `target_id` and persist the new order
+    fn move_library(&mut self, library_id: &str, target_id: &str, after: bool) {
+        let mut order: Vec<String> = self
+            .ordered_libraries()
+            .into_iter()
+            .map(|library| library.id.clone())
+            .collect();
+
+        let Some(from) = order.iter().position(|id| id == library_id) else {
+            // Dropped from another source's group
+            return;
+        };
+        let moved = order.remove(from);
+        let Some(target) = order.iter().position(|id| id == target_id) else {
+            return;
+        };
+        order.insert(if after { target + 1 } else { target }, moved);
+
+        self.library_order = order.clone();
+        let source_id = self.source.id.clone();
+        relm4::spawn(async move {
+            if let Err(e) = config_service()
+                .set_sidebar_library_order(source_id, order)
+                .await
+            {
+                error!("Failed to save sidebar library order: {}", e);
+            }
+        });
+    }
+
+    /// What is wrong with the source, None while it is healthy
+    fn health_problem(&self) -> Option<String> {
+        if self.needs_auth {
+            return Some("Signed out - re-authenticate to keep syncing".to_string());
+        }
+        match self.connection_state {
+            ConnectionState::Connected => None,
+            ConnectionState::SyncFailed => Some(match &self.sync_error_message {
+                Some(error) => format!("Sync failed: {}", error),
+                None => "Connected but sync failed".to_string(),
+            }),
+            ConnectionState::Disconnected => Some("Disconnected".to_string()),
+        }
+    }
+
+    /// Show the warning badge for an unhealthy source
+    /// #[watch] doesn't work in factory components, so this runs on every change
+    fn update_health_badge(&self, widgets: &SourceGroupWidgets) {
+        let problem = self.health_problem();
+        // The sync spinner takes precedence over the badge
+        widgets
+            .connection_icon
+            .set_visible(problem.is_some() && !self.is_syncing);
+
+        if let Some(problem) = &problem {
+            let (icon_name, css_class) = if self.needs_auth {
+                ("dialog-password-symbolic", "warning")
+            } else if self.connection_state == ConnectionState::Disconnected {
+                ("network-offline-symbolic", "error")
+            } else {
+                ("dialog-warning-symbolic", "warning")
+            };
+            widgets.connection_icon.set_icon_name(Some(icon_name));
+            widgets.connection_icon.set_css_classes(&[css_class]);
+            widgets.connection_icon.set_tooltip_text(Some(problem));
+        }
+
+        if let Some(action) = &self.view_error_action {
+            action.set_enabled(problem.is_some());
+        }
+    }
+
+    /// Build the header's context menu with the health actions
+    fn setup_context_menu(&mut self, header: &gtk::Button, sender: &FactorySender<Self>) {
+        let can_reauth = matches!(
+            self.source.source_type,
+            SourceType::PlexServer { .. } | SourceType::JellyfinServer | SourceType::EmbyServer
+        );
+
+        let menu = gio::Menu::new();
+        menu.append(Some("Reconnect"), Some("source.reconnect"));
+        if can_reauth {
+            menu.append(Some("Re-authenticate"), Some("source.reauthenticate"));
+        }
+        menu.append(Some("View Error"), Some("source.view_error"));
+
+        let popover = gtk::PopoverMenu::from_model(Some(&menu));
+        popover.set_parent(header);
+        popover.set_has_arrow(false);
+        self.popover = Some(popover.clone());
+
+        let action_group = gio::SimpleActionGroup::new();
+
+        let reconnect_action = gio::SimpleAction::new("reconnect", None);
+        reconnect_action.set_enabled(!connectivity::is_offline());
+        let sender_clone = sender.clone();
+        let source_id = SourceId::new(self.source.id.clone());
+        reconnect_action.connect_activate(move |_, _| {
+            sender_clone
+                .output(SourceGroupOutput::Reconnect(source_id.clone()))
+                .unwrap_or_else(|_| error!("Failed to send reconnect"));
+        });
+        action_group.add_action(&reconnect_action);
+        self.reconnect_action = Some(reconnect_action);
+
+        if can_reauth {
+            let reauth_action = gio::SimpleAction::new("reauthenticate", None);
+            let sender_clone = sender.clone();
+            let source = self.source.clone();
+            reauth_action.connect_activate(move |_, _| {
+                let source_type = match source.source_type {
+                    SourceType::PlexServer { .. } => "plex",
+                    SourceType::EmbyServer => "emby",
+                    _ => "jellyfin",
+                };
+                sender_clone
+                    .output(SourceGroupOutput::Reauthenticate {
+                        source_id: SourceId::new(source.id.clone()),
+                        source_name: source.name.clone(),
+                        source_type: source_type.to_string(),
+                    })
+                    .unwrap_or_else(|_| error!("Failed to send re-authenticate"));
+            });
+            action_group.add_action(&reauth_action);
+        }
+
+        let view_error_action = gio::SimpleAction::new("view_error", None);
+        view_error_action.set_enabled(false);
+        let sender_clone = sender.clone();
+        view_error_action.connect_activate(move |_, _| {
+            sender_clone.input(SourceGroupInput::ViewError);
+        });
+        action_group.add_action(&view_error_action);
+        self.view_error_action = Some(view_error_action);
+
+        header.insert_action_group("source", Some(&action_group));
+
+        let gesture = gtk::GestureClick::new();
+        gesture.set_button(3); // Right mouse button
+        gesture.connect_released(move |_, _, x, y| {
+            let rect = gtk::gdk::Rectangle::new(x as i32, y as i32, 1, 1);
+            popover.set_pointing_to(Some(&rect));
+            popover.popup();
+        });
+        header.add_controller(gesture);
+    }
+
+    fn apply_expanded(&self, widgets: &SourceGroupWidgets) {
+        widgets.library_list.set_visible(self.is_expanded);
+        if self.is_expanded {
+            widgets.root.add_css_class("source-expanded");
+        } else {
+            widgets.root.remove_css_class("source-expanded");
+        }
+    }
+
+    fn update_library_list(&self, library_list: &gtk::ListBox, sender: &FactorySender<Self>) {
         // Clear existing children
         while let Some(child) = library_list.first_child() {
             library_list.remove(&child);
         }
 
         // Add libraries from the actual data
-        for library in &self.libraries {
+        for library in self.ordered_libraries() {
+            let is_hidden = self.hidden_libraries.contains(&library.id);
+            if is_hidden && !self.is_editing {
+                continue;
+            }
+
             let row = gtk::ListBoxRow::new();
             row.set_activatable(true);
 
+            // Rows are dragged onto each other to reorder them
+            let drag_source = gtk::DragSource::new();
+            drag_source.set_actions(gtk::gdk::DragAction::MOVE);
+            let drag_id = library.id.clone();
+            drag_source.connect_prepare(move |_, _, _| {
+                Some(gtk::gdk::ContentProvider::for_value(&drag_id.to_value()))
+            });
+            row.add_controller(drag_source);
+
+            let drop_target =
+                gtk::DropTarget::new(String::static_type(), gtk::gdk::DragAction::MOVE);
+            let target_id = library.id.clone();
+            let drop_sender = sender.clone();
+            drop_target.connect_drop(move |target, value, _, y| {
+                let Ok(library_id) = value.get::<String>() else {
+                    return false;
+                };
+                if library_id == target_id {
+                    return false;
+                }
+                // Dropping on the lower half of a row places the library after it
+                let after = target
+                    .widget()
+                    .is_some_and(|widget| y > widget.height() as f64 / 2.0);
+                drop_sender.input(SourceGroupInput::MoveLibrary {
+                    library_id,
+                    target_id: target_id.clone(),
+                    after,
+                });
+                true
+            });
+            row.add_controller(drop_target);
+
             // Store the library ID as data on the row
             unsafe {
                 row.set_data("library_id", library.id.clone());
@@ -120,6 +396,9 @@ impl SourceGroup {
 
             let name_label = gtk::Label::new(Some(&library.title));
             name_label.set_halign(gtk::Align::Start);
+            if is_hidden {
+                name_label.add_css_class("dim-label");
+            }
             vbox.append(&name_label);
 
             // Display the actual item count from the library model
@@ -132,6 +411,16 @@ impl SourceGroup {
 
             hbox.append(&vbox);
 
+            if let Some(&count) = self.unwatched_counts.get(&library.id)
+                && count > 0
+            {
+                let badge = gtk::Label::new(Some(&count.to_string()));
+                badge.set_valign(gtk::Align::Center);
+                badge.add_css_class("unwatched-badge");
+                badge.set_tooltip_text(Some(&format!("{} unwatched", count)));
+                hbox.append(&badge);
+            }
+
             // Add spinner if this library is syncing
             if self.syncing_libraries.contains(&library.id) {
                 let spinner = gtk::Spinner::new();
@@ -139,10 +428,63 @@ impl SourceGroup {
                 hbox.append(&spinner);
             }
 
+            if self.is_editing {
+                let visibility_button = gtk::Button::from_icon_name(if is_hidden {
+                    "view-conceal-symbolic"
+                } else {
+                    "view-reveal-symbolic"
+                });
+                visibility_button.add_css_class("flat");
+                visibility_button.set_valign(gtk::Align::Center);
+                visibility_button.set_tooltip_text(Some(if is_hidden {
+                    "Show in Sidebar"
+                } else {
+                    "Hide from Sidebar"
+                }));
+                let library_id = library.id.clone();
+                let visibility_sender = sender.clone();
+                visibility_button.connect_clicked(move |_| {
+                    visibility_sender.input(SourceGroupInput::SetLibraryHidden(
+                        library_id.clone(),
+                        !is_hidden,
+                    ));
+                });
+                hbox.append(&visibility_button);
+            }
+
             row.set_child(Some(&hbox));
             library_list.append(&row);
         }
 
+        if !self.libraries.is_empty() {
+            let hidden_count = self
+                .libraries
+                .iter()
+                .filter(|library| self.hidden_libraries.contains(&library.id))
+                .count();
+            let label = if self.is_editing {
+                "Done".to_string()
+            } else if hidden_count > 0 {
+                format!("Edit ({} hidden)", hidden_count)
+            } else {
+                "Edit".to_string()
+            };
+
+            let edit_button = gtk::Button::with_label(&label);
+            edit_button.add_css_class("flat");
+            edit_button.add_css_class("caption");
+            edit_button.set_halign(gtk::Align::End);
+            let edit_sender = sender.clone();
+            edit_button.connect_clicked(move |_| {
+                edit_sender.input(SourceGroupInput::ToggleEditing);
+            });
+
+            let row = gtk::ListBoxRow::new();
+            row.set_activatable(false);
+            row.set_child(Some(&edit_button));
+            library_list.append(&row);
+        }
+
         // If no libraries, show a placeholder
         if self.libraries.is_empty() {
             let row = gtk::ListBoxRow::new();
@@ -168,6 +510,26 @@ pub enum SourceGroupInput {
     Refresh,
     /// Toggle expanded state
     ToggleExpanded,
+    /// Saved sidebar layout loaded: library order, hidden libraries and expanded state
+    LayoutLoaded {
+        library_order: Vec<String>,
+        hidden_libraries: HashSet<String>,
+        is_expanded: bool,
+    },
+    /// Move a library next to another one
+    MoveLibrary {
+        library_id: String,
+        target_id: String,
+        after: bool,
+    },
+    /// Hide a library from the sidebar or show it again
+    SetLibraryHidden(String, bool),
+    /// Toggle showing hidden libraries for editing
+    ToggleEditing,
+    /// Unwatched counts loaded for all libraries
+    UnwatchedCountsLoaded(HashMap<String, u64>),
+    /// Unwatched count of one library changed
+    SetUnwatchedCount(String, u64),
     /// Reload libraries from database (e.g., after sync)
     ReloadLibraries,
     /// Update connection status with optional error message and connection type
@@ -180,12 +542,26 @@ pub enum SourceGroupInput {
     SourceSyncStarted,
     /// Source sync completed
     SourceSyncCompleted,
+    /// The source's credentials were rejected or accepted again
+    SetNeedsAuth(bool),
+    /// Show the details of what is wrong with the source
+    ViewError,
+    /// Every server became unreachable, or one is reachable again
+    SetOffline(bool),
 }
 
 #[derive(Debug)]
 pub enum SourceGroupOutput {
     /// Navigate to library
     NavigateToLibrary(LibraryId),
+    /// Check the connection again and retry the sync
+    Reconnect(SourceId),
+    /// Sign in to the source again
+    Reauthenticate {
+        source_id: SourceId,
+        source_name: String,
+        source_type: String,
+    },
 }
 
 #[allow(unused_assignments)]
@@ -203,6 +579,7 @@ impl FactoryComponent for SourceGroup {
             set_spacing: 0,
             add_css_class: "source-group",
 
+            #[name = "header"]
             gtk::Button {
                 add_css_class: "flat",
                 add_css_class: "source-header",
@@ -220,7 +597,7 @@ impl FactoryComponent for SourceGroup {
                         #[watch]
                         set_icon_name: Some(match &self.source.source_type {
                             SourceType::PlexServer { .. } => "network-server-symbolic",
-                            SourceType::JellyfinServer => "network-workgroup-symbolic",
+                            SourceType::JellyfinServer | SourceType::EmbyServer => "network-workgroup-symbolic",
                             SourceType::LocalFolder { .. } => "folder-symbolic",
                             SourceType::NetworkShare { .. } => "folder-remote-symbolic",
                         }),
@@ -255,11 +632,10 @@ impl FactoryComponent for SourceGroup {
                         set_halign: gtk::Align::End,
                     },
 
-                    // Connection status indicator - only shown for problems
+                    // Health badge - only shown for problems, right click for actions
                     #[name = "connection_icon"]
                     gtk::Image {
-                        #[watch]
-                        set_visible: self.connection_state != ConnectionState::Connected && !self.is_syncing,
+                        set_visible: false, // Updated by update_health_badge
                         set_icon_name: Some("dialog-warning-symbolic"),  // Default icon
                         set_pixel_size: 16,
                     },
@@ -267,8 +643,7 @@ impl FactoryComponent for SourceGroup {
                     gtk::Image {
                         set_icon_name: Some("go-next-symbolic"),
                         set_pixel_size: 12,
-                        #[watch]
-                        add_css_class: if self.is_expanded { "source-expand-icon source-expanded" } else { "source-expand-icon" },
+                        add_css_class: "source-expand-icon",
                     },
                 }
             },
@@ -277,8 +652,6 @@ impl FactoryComponent for SourceGroup {
             library_list -> gtk::ListBox {
                 set_selection_mode: gtk::SelectionMode::None,
                 add_css_class: "library-list",
-                #[watch]
-                set_visible: self.is_expanded,
             }
         }
     }
@@ -289,8 +662,19 @@ impl FactoryComponent for SourceGroup {
         let db_clone = db.clone();
         let sender_clone = sender.clone();
 
-        // Load libraries for this source asynchronously
+        // Load the saved layout and libraries for this source asynchronously
         relm4::spawn(async move {
+            let ui = config_service().get_config().await.ui;
+            sender_clone.input(SourceGroupInput::LayoutLoaded {
+                library_order: ui
+                    .sidebar_library_order
+                    .get(&source_clone.id)
+                    .cloned()
+                    .unwrap_or_default(),
+                hidden_libraries: ui.hidden_sidebar_libraries.into_iter().collect(),
+                is_expanded: !ui.collapsed_sidebar_sources.contains(&source_clone.id),
+            });
+
             let source_id = SourceId::new(source_clone.id.clone());
             match MediaService::get_libraries_for_source(&db_clone, &source_id).await {
                 Ok(libraries) => {
@@ -302,7 +686,8 @@ impl FactoryComponent for SourceGroup {
                     for lib in &libraries {
                         debug!("Library '{}': item_count = {}", lib.title, lib.item_count);
                     }
-                    sender_clone.input(SourceGroupInput::LibrariesLoaded(libraries));
+                    sender_clone.input(SourceGroupInput::LibrariesLoaded(libraries.clone()));
+                    Self::load_unwatched_counts(&db_clone, &libraries, &sender_clone).await;
                 }
                 Err(e) => {
                     error!(
@@ -325,6 +710,14 @@ impl FactoryComponent for SourceGroup {
             connection_type: None, // Will be set when connection status is updated
             sync_error_message: None,
             is_syncing: false,
+            library_order: Vec::new(),
+            hidden_libraries: HashSet::new(),
+            is_editing: false,
+            unwatched_counts: HashMap::new(),
+            needs_auth: false,
+            popover: None,
+            view_error_action: None,
+            reconnect_action: None,
         }
     }
 
@@ -356,11 +749,20 @@ impl FactoryComponent for SourceGroup {
         let widgets = view_output!();
 
         // Initially populate with any libraries we already have
-        self.update_library_list(&widgets.library_list);
+        self.update_library_list(&widgets.library_list, &sender);
+        self.apply_expanded(&widgets);
+        self.setup_context_menu(&widgets.header, &sender);
 
         widgets
     }
 
+    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
+        // Unparent the popover before the header is finalized
+        if let Some(popover) = self.popover.take() {
+            popover.unparent();
+        }
+    }
+
     fn update_with_view(
         &mut self,
         widgets: &mut Self::Widgets,
@@ -375,7 +777,7 @@ impl FactoryComponent for SourceGroup {
                 self.libraries = libraries;
                 self.is_loading = false;
                 // Update the library list widget
-                self.update_library_list(&widgets.library_list);
+                self.update_library_list(&widgets.library_list, &sender);
             }
             SourceGroupInput::Refresh => {
                 debug!("Refreshing source: {}", self.source.name);
@@ -384,6 +786,68 @@ impl FactoryComponent for SourceGroup {
             }
             SourceGroupInput::ToggleExpanded => {
                 self.is_expanded = !self.is_expanded;
+                self.apply_expanded(widgets);
+
+                let source_id = self.source.id.clone();
+                let expanded = self.is_expanded;
+                relm4::spawn(async move {
+                    if let Err(e) = config_service()
+                        .set_sidebar_source_expanded(source_id, expanded)
+                        .await
+                    {
+                        error!("Failed to save sidebar expanded state: {}", e);
+                    }
+                });
+            }
+            SourceGroupInput::LayoutLoaded {
+                library_order,
+                hidden_libraries,
+                is_expanded,
+            } => {
+                self.library_order = library_order;
+                self.hidden_libraries = hidden_libraries;
+                self.is_expanded = is_expanded;
+                self.apply_expanded(widgets);
+                self.update_library_list(&widgets.library_list, &sender);
+            }
+            SourceGroupInput::MoveLibrary {
+                library_id,
+                target_id,
+                after,
+            } => {
+                self.move_library(&library_id, &target_id, after);
+                self.update_library_list(&widgets.library_list, &sender);
+            }
+            SourceGroupInput::SetLibraryHidden(library_id, hidden) => {
+                if hidden {
+                    self.hidden_libraries.insert(library_id.clone());
+                } else {
+                    self.hidden_libraries.remove(&library_id);
+                }
+                self.update_library_list(&widgets.library_list, &sender);
+
+                relm4::spawn(async move {
+                    if let Err(e) = config_service()
+                        .set_sidebar_library_hidden(library_id, hidden)
+                        .await
+                    {
+                        error!("Failed to save hidden sidebar library: {}", e);
+                    }
+                });
+            }
+            SourceGroupInput::ToggleEditing => {
+                self.is_editing = !self.is_editing;
+                self.update_library_list(&widgets.library_list, &sender);
+            }
+            SourceGroupInput::UnwatchedCountsLoaded(counts) => {
+                self.unwatched_counts = counts;
+                self.update_library_list(&widgets.library_list, &sender);
+            }
+            SourceGroupInput::SetUnwatchedCount(library_id, count) => {
+                if self.unwatched_counts.get(&library_id) != Some(&count) {
+                    self.unwatched_counts.insert(library_id, count);
+                    self.update_library_list(&widgets.library_list, &sender);
+                }
             }
             SourceGroupInput::ReloadLibraries => {
                 debug!("Reloading libraries for source: {}", self.source.name);
@@ -409,7 +873,9 @@ impl FactoryComponent for SourceGroup {
                                     lib.title, lib.item_count
                                 );
                             }
-                            sender_clone.input(SourceGroupInput::LibrariesLoaded(libraries));
+                            sender_clone
+                                .input(SourceGroupInput::LibrariesLoaded(libraries.clone()));
+                            Self::load_unwatched_counts(&db_clone, &libraries, &sender_clone).await;
                         }
                         Err(e) => {
                             error!(
@@ -428,7 +894,7 @@ impl FactoryComponent for SourceGroup {
                 );
                 self.syncing_libraries.insert(library_id);
                 // Update the library list to show spinners
-                self.update_library_list(&widgets.library_list);
+                self.update_library_list(&widgets.library_list, &sender);
             }
             SourceGroupInput::LibrarySyncCompleted(library_id) => {
                 debug!(
@@ -437,7 +903,7 @@ impl FactoryComponent for SourceGroup {
                 );
                 self.syncing_libraries.remove(&library_id);
                 // Update the library list to hide spinners
-                self.update_library_list(&widgets.library_list);
+                self.update_library_list(&widgets.library_list, &sender);
             }
             SourceGroupInput::UpdateConnectionStatus(state, error_msg, conn_type) => {
                 debug!(
@@ -458,7 +924,7 @@ impl FactoryComponent for SourceGroup {
                 }
 
                 self.connection_state = state;
-                self.sync_error_message = error_msg.clone();
+                self.sync_error_message = error_msg;
 
                 // Only update connection_type if a value is provided
                 // This preserves the existing type when None is passed
@@ -515,48 +981,7 @@ impl FactoryComponent for SourceGroup {
                     );
                 }
 
-                // Manually update the connection icon widget
-                // Hide icon when Connected or when syncing (spinner takes precedence)
-                let should_show = state != ConnectionState::Connected && !self.is_syncing;
-                debug!(
-                    "Setting icon visibility for {} to {} (state: {:?}, syncing: {})",
-                    self.source.name, should_show, state, self.is_syncing
-                );
-                widgets.connection_icon.set_visible(should_show);
-
-                if should_show {
-                    let icon_name = match state {
-                        ConnectionState::SyncFailed => "dialog-warning-symbolic",
-                        ConnectionState::Disconnected => "network-offline-symbolic",
-                        _ => "dialog-warning-symbolic", // Fallback
-                    };
-                    debug!("Setting icon name to: {}", icon_name);
-                    widgets.connection_icon.set_icon_name(Some(icon_name));
-                }
-
-                if should_show {
-                    widgets.connection_icon.set_css_classes(&[match state {
-                        ConnectionState::SyncFailed => "warning",
-                        ConnectionState::Disconnected => "error",
-                        _ => "warning", // Fallback
-                    }]);
-                }
-
-                // Set tooltip with error details if available
-                if state != ConnectionState::Connected {
-                    let tooltip = match state {
-                        ConnectionState::SyncFailed => {
-                            if let Some(ref error) = error_msg {
-                                format!("Sync failed: {}", error)
-                            } else {
-                                "Connected but sync failed".to_string()
-                            }
-                        }
-                        ConnectionState::Disconnected => "Disconnected".to_string(),
-                        _ => String::new(),
-                    };
-                    widgets.connection_icon.set_tooltip_text(Some(&tooltip));
-                }
+                self.update_health_badge(widgets);
             }
             SourceGroupInput::SourceSyncStarted => {
                 info!(
@@ -567,7 +992,7 @@ impl FactoryComponent for SourceGroup {
                 // Manually update widgets since #[watch] doesn't work in factory components
                 widgets.sync_spinner.set_visible(true);
                 widgets.sync_spinner.set_spinning(true);
-                widgets.connection_icon.set_visible(false);
+                self.update_health_badge(widgets);
                 info!(
                     "🔄 Spinner manually set to visible for source {}",
                     self.source.name
@@ -582,11 +1007,31 @@ impl FactoryComponent for SourceGroup {
                 // Manually update widgets
                 widgets.sync_spinner.set_visible(false);
                 widgets.sync_spinner.set_spinning(false);
-                // Re-evaluate connection icon visibility
-                let should_show = self.connection_state != ConnectionState::Connected;
-                widgets.connection_icon.set_visible(should_show);
+                self.update_health_badge(widgets);
                 info!("✅ Spinner manually hidden for source {}", self.source.name);
             }
+            SourceGroupInput::SetNeedsAuth(needs_auth) => {
+                self.needs_auth = needs_auth;
+                self.update_health_badge(widgets);
+            }
+            SourceGroupInput::ViewError => {
+                let Some(problem) = self.health_problem() else {
+                    return;
+                };
+                let dialog = adw::AlertDialog::builder()
+                    .heading(&self.source.name)
+                    .body(&problem)
+                    .build();
+                dialog.add_response("close", "Close");
+                dialog.set_default_response(Some("close"));
+                dialog.set_close_response("close");
+                dialog.present(Some(&widgets.root));
+            }
+            SourceGroupInput::SetOffline(offline) => {
+                if let Some(action) = &self.reconnect_action {
+                    action.set_enabled(!offline);
+                }
+            }
         }
     }
 }
@@ -602,9 +1047,76 @@ pub struct Sidebar {
     selected_library_id: Option<LibraryId>,
     syncing_sources: HashMap<String, String>,
     syncing_libraries: HashMap<String, (String, String)>,
+    /// Items dropped on the queue, in the order they will play
+    queue: Vec<QueuedItem>,
+    /// Last watched flag seen for each item, to tell progress from watched changes
+    watched_states: HashMap<String, bool>,
+    _broker_subscription: Subscription,
 }
 
 impl Sidebar {
+    /// Recount the unwatched items of the library holding a media item
+    fn recount_library_of(&self, media_id: String, sender: &ComponentSender<Self>) {
+        let db = self.db.clone();
+        let sender = sender.clone();
+        relm4::spawn(async move {
+            let media_id = MediaItemId::new(media_id);
+            match MediaService::get_unwatched_count_for_item(&db, &media_id).await {
+                Ok(Some((library_id, count))) => {
+                    sender.input(SidebarInput::UnwatchedCountChanged { library_id, count });
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("Failed to recount unwatched items for {}: {}", media_id, e)
+                }
+            }
+        });
+    }
+
+    fn update_queue_widgets(&self, widgets: &SidebarWidgets, sender: &ComponentSender<Self>) {
+        widgets.queue_count.set_label(&self.queue.len().to_string());
+        widgets.queue_count.set_visible(!self.queue.is_empty());
+        widgets
+            .queue_items_button
+            .set_visible(!self.queue.is_empty());
+        widgets
+            .queue_clear_button
+            .set_visible(!self.queue.is_empty());
+
+        while let Some(child) = widgets.queue_list.first_child() {
+            widgets.queue_list.remove(&child);
+        }
+        for item in &self.queue {
+            let row = gtk::Box::builder()
+                .orientation(gtk::Orientation::Horizontal)
+                .spacing(6)
+                .build();
+            row.append(
+                &gtk::Label::builder()
+                    .label(&item.title)
+                    .halign(gtk::Align::Start)
+                    .hexpand(true)
+                    .max_width_chars(30)
+                    .ellipsize(gtk::pango::EllipsizeMode::End)
+                    .build(),
+            );
+
+            let remove = gtk::Button::builder()
+                .icon_name("list-remove-symbolic")
+                .tooltip_text("Remove from Up Next")
+                .css_classes(["flat", "circular"])
+                .build();
+            let input_sender = sender.input_sender().clone();
+            let media_id = item.id.clone();
+            remove.connect_clicked(move |_| {
+                input_sender.emit(SidebarInput::RemoveFromQueue(media_id.clone()));
+            });
+            row.append(&remove);
+
+            widgets.queue_list.append(&row);
+        }
+    }
+
     fn update_status_text(&mut self) {
         if !self.has_sources {
             self.connection_status = String::new();
@@ -729,6 +1241,79 @@ impl Component for Sidebar {
                         },
                     },
 
+                    // Queue - media cards can be dragged onto it
+                    #[name = "queue_row"]
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_visible: model.has_sources,
+                        add_css_class: "queue-drop-target",
+                        set_tooltip_text: Some("Drag movies and episodes here to play them next"),
+
+                        gtk::Button {
+                            set_hexpand: true,
+                            add_css_class: "flat",
+                            add_css_class: "home-button",
+                            connect_clicked => SidebarInput::PlayQueue,
+
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Horizontal,
+                                set_spacing: 12,
+                                set_margin_top: 8,
+                                set_margin_bottom: 8,
+                                set_margin_start: 8,
+                                set_margin_end: 8,
+
+                                gtk::Image {
+                                    set_icon_name: Some("view-list-symbolic"),
+                                    set_pixel_size: 16,
+                                },
+
+                                gtk::Label {
+                                    set_text: "Up Next",
+                                    set_halign: gtk::Align::Start,
+                                    set_hexpand: true,
+                                },
+
+                                #[name = "queue_count"]
+                                gtk::Label {
+                                    set_visible: false,
+                                    set_valign: gtk::Align::Center,
+                                    add_css_class: "queue-count-badge",
+                                },
+                            },
+                        },
+
+                        #[name = "queue_items_button"]
+                        gtk::MenuButton {
+                            set_visible: false,
+                            set_valign: gtk::Align::Center,
+                            set_icon_name: "view-more-symbolic",
+                            set_tooltip_text: Some("Show Queue"),
+                            add_css_class: "flat",
+                            add_css_class: "circular",
+
+                            #[wrap(Some)]
+                            set_popover = &gtk::Popover {
+                                #[name = "queue_list"]
+                                gtk::ListBox {
+                                    set_selection_mode: gtk::SelectionMode::None,
+                                },
+                            },
+                        },
+
+                        #[name = "queue_clear_button"]
+                        gtk::Button {
+                            set_visible: false,
+                            set_valign: gtk::Align::Center,
+                            set_margin_end: 4,
+                            set_icon_name: "edit-clear-all-symbolic",
+                            set_tooltip_text: Some("Clear Queue"),
+                            add_css_class: "flat",
+                            add_css_class: "circular",
+                            connect_clicked => SidebarInput::ClearQueue,
+                        },
+                    },
+
                     // Sources container
                     #[local_ref]
                     sources_container -> gtk::Box {
@@ -782,6 +1367,16 @@ impl Component for Sidebar {
                 SourceGroupOutput::NavigateToLibrary(library_id) => {
                     SidebarInput::NavigateToLibrary(library_id)
                 }
+                SourceGroupOutput::Reconnect(source_id) => SidebarInput::ReconnectSource(source_id),
+                SourceGroupOutput::Reauthenticate {
+                    source_id,
+                    source_name,
+                    source_type,
+                } => SidebarInput::ReauthenticateSource {
+                    source_id,
+                    source_name,
+                    source_type,
+                },
             });
 
         let model = Self {
@@ -793,24 +1388,33 @@ impl Component for Sidebar {
             selected_library_id: None,
             syncing_sources: HashMap::new(),
             syncing_libraries: HashMap::new(),
+            queue: Vec::new(),
+            watched_states: HashMap::new(),
+            // Subscribe to broker messages for sync updates
+            _broker_subscription: BROKER.subscribe(
+                &[Topic::Data, Topic::Source, Topic::Connectivity],
+                sender.input_sender(),
+                SidebarInput::BrokerMsg,
+            ),
         };
 
         let sources_container = model.source_groups.widget();
         let widgets = view_output!();
 
-        // Load initial sources
-        sender.input(SidebarInput::RefreshSources);
-
-        // Subscribe to broker messages for sync updates
-        let broker_sender = sender.clone();
-        relm4::spawn(async move {
-            let (tx, rx) = relm4::channel::<BrokerMessage>();
-            BROKER.subscribe("sidebar".to_string(), tx).await;
-
-            while let Some(msg) = rx.recv().await {
-                broker_sender.input(SidebarInput::BrokerMsg(msg));
+        // Accept media cards dragged onto the queue
+        let drop_target = gtk::DropTarget::new(String::static_type(), gtk::gdk::DragAction::COPY);
+        let drop_sender = sender.clone();
+        drop_target.connect_drop(move |_, value, _, _| match value.get::<String>() {
+            Ok(media_id) => {
+                drop_sender.input(SidebarInput::QueueDropped(media_id));
+                true
             }
+            Err(_) => false,
         });
+        widgets.queue_row.add_controller(drop_target);
+
+        // Load initial sources
+        sender.input(SidebarInput::RefreshSources);
 
         ComponentParts { model, widgets }
     }
@@ -820,7 +1424,7 @@ impl Component for Sidebar {
         widgets: &mut Self::Widgets,
         msg: Self::Input,
         sender: ComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         match msg {
             SidebarInput::RefreshSources => {
@@ -861,6 +1465,7 @@ impl Component for Sidebar {
                 // Update visibility based on has_sources
                 widgets.welcome_box.set_visible(!self.has_sources);
                 widgets.home_button.set_visible(self.has_sources);
+                widgets.queue_row.set_visible(self.has_sources);
                 widgets.sources_container.set_visible(self.has_sources);
                 widgets.status_container.set_visible(self.has_sources);
 
@@ -931,88 +1536,124 @@ impl Component for Sidebar {
                 self.connection_status = status;
             }
 
-            SidebarInput::UpdateSourceConnectionStatus {
-                source_id,
-                state,
-                error,
-                connection_type,
-            } => {
-                // Find the source group and update its connection status
-                let idx_to_update = {
-                    let guard = self.source_groups.guard();
-                    guard.iter().enumerate().find_map(|(idx, sg)| {
-                        if sg.source.id == source_id.to_string() {
-                            Some(idx)
-                        } else {
-                            None
+            SidebarInput::QueueDropped(media_id) => {
+                let db = self.db.clone();
+                let sender = sender.clone();
+                relm4::spawn(async move {
+                    use crate::db::repository::{MediaRepositoryImpl, Repository};
+
+                    let media_id = MediaItemId::new(media_id);
+                    let repo = MediaRepositoryImpl::new(db);
+                    match repo.find_by_id(media_id.as_ref()).await {
+                        Ok(Some(item))
+                            if matches!(item.media_type.as_str(), "movie" | "episode") =>
+                        {
+                            sender.input(SidebarInput::ItemQueued(QueuedItem {
+                                id: media_id,
+                                title: item.title,
+                            }));
                         }
-                    })
-                };
+                        Ok(_) => debug!("Ignoring drop of unplayable item {}", media_id),
+                        Err(e) => error!("Failed to look up dropped item {}: {}", media_id, e),
+                    }
+                });
+            }
 
-                if let Some(idx) = idx_to_update {
-                    self.source_groups.send(
-                        idx,
-                        SourceGroupInput::UpdateConnectionStatus(state, error, connection_type),
-                    );
+            SidebarInput::ItemQueued(item) => {
+                if !self.queue.iter().any(|queued| queued.id == item.id) {
+                    info!("Queued {}", item.id);
+                    self.queue.push(item);
+                    self.update_queue_widgets(widgets, &sender);
+                }
+            }
+
+            SidebarInput::PlayQueue => {
+                if !self.queue.is_empty() {
+                    let items = std::mem::take(&mut self.queue)
+                        .into_iter()
+                        .map(|item| item.id)
+                        .collect();
+                    self.update_queue_widgets(widgets, &sender);
+                    let _ = sender.output(SidebarOutput::PlayQueue(items));
+                }
+            }
+
+            SidebarInput::RemoveFromQueue(media_id) => {
+                if let Some(index) = self.queue.iter().position(|item| item.id == media_id) {
+                    let item = self.queue.remove(index);
+                    self.update_queue_widgets(widgets, &sender);
+                    let input_sender = sender.input_sender().clone();
+                    undo_toast::present(root, "Removed from Up Next", move || {
+                        input_sender.emit(SidebarInput::RestoreQueueItem {
+                            index,
+                            item: item.clone(),
+                        });
+                    });
                 }
             }
 
-            SidebarInput::ConnectionCheckResult {
+            SidebarInput::RestoreQueueItem { index, item } => {
+                if !self.queue.iter().any(|queued| queued.id == item.id) {
+                    // Items removed before it may be gone
+                    let index = index.min(self.queue.len());
+                    self.queue.insert(index, item);
+                    self.update_queue_widgets(widgets, &sender);
+                }
+            }
+
+            SidebarInput::ClearQueue => {
+                let items = std::mem::take(&mut self.queue);
+                self.update_queue_widgets(widgets, &sender);
+                if !items.is_empty() {
+                    let input_sender = sender.input_sender().clone();
+                    undo_toast::present(root, "Queue cleared", move || {
+                        input_sender.emit(SidebarInput::RestoreQueue(items.clone()));
+                    });
+                }
+            }
+
+            SidebarInput::RestoreQueue(items) => {
+                // Items queued since clearing stay behind the restored ones
+                let queued_since = std::mem::replace(&mut self.queue, items);
+                for item in queued_since {
+                    if !self.queue.iter().any(|queued| queued.id == item.id) {
+                        self.queue.push(item);
+                    }
+                }
+                self.update_queue_widgets(widgets, &sender);
+            }
+
+            SidebarInput::ReconnectSource(source_id) => {
+                let _ = sender.output(SidebarOutput::ReconnectSource(source_id));
+            }
+
+            SidebarInput::ReauthenticateSource {
                 source_id,
-                is_connected,
+                source_name,
+                source_type,
             } => {
-                // Handle connection check from monitor
-                // Only update state if we're currently Disconnected
-                // Don't override SyncFailed state
-                let idx_to_check = {
+                let _ = sender.output(SidebarOutput::ReauthenticateSource {
+                    source_id,
+                    source_name,
+                    source_type,
+                });
+            }
+
+            SidebarInput::UnwatchedCountChanged { library_id, count } => {
+                let idx = {
                     let guard = self.source_groups.guard();
-                    guard.iter().enumerate().find_map(|(idx, sg)| {
-                        if sg.source.id == source_id.to_string() {
-                            // Check current state - only update if Disconnected
-                            if sg.connection_state == ConnectionState::Disconnected && is_connected
-                            {
-                                Some((idx, true))
-                            } else if !is_connected
-                                && sg.connection_state != ConnectionState::Disconnected
-                            {
-                                Some((idx, false))
-                            } else {
-                                None // Don't change if SyncFailed or already in correct state
-                            }
-                        } else {
-                            None
-                        }
+                    guard.iter().position(|sg| {
+                        sg.libraries
+                            .iter()
+                            .any(|library| library.id == library_id.as_ref())
                     })
                 };
 
-                if let Some((idx, should_connect)) = idx_to_check {
-                    if should_connect {
-                        debug!(
-                            "Connection restored for source {}, setting to Connected",
-                            source_id
-                        );
-                        self.source_groups.send(
-                            idx,
-                            SourceGroupInput::UpdateConnectionStatus(
-                                ConnectionState::Connected,
-                                None,
-                                None,
-                            ),
-                        );
-                    } else {
-                        debug!(
-                            "Connection lost for source {}, setting to Disconnected",
-                            source_id
-                        );
-                        self.source_groups.send(
-                            idx,
-                            SourceGroupInput::UpdateConnectionStatus(
-                                ConnectionState::Disconnected,
-                                None,
-                                None,
-                            ),
-                        );
-                    }
+                if let Some(idx) = idx {
+                    self.source_groups.send(
+                        idx,
+                        SourceGroupInput::SetUnwatchedCount(library_id.to_string(), count),
+                    );
                 }
             }
 
@@ -1156,6 +1797,41 @@ impl Component for Sidebar {
                         // Keep the error status visible - don't reset it
                         // The status will be updated on next successful sync
                     }
+                    BrokerMessage::Source(SourceMessage::SyncCancelled { source_id }) => {
+                        self.syncing_sources.remove(&source_id);
+                        let cancelled_libraries: Vec<String> = self
+                            .syncing_libraries
+                            .iter()
+                            .filter(|(_, (library_source, _))| *library_source == source_id)
+                            .map(|(library_id, _)| library_id.clone())
+                            .collect();
+                        for library_id in &cancelled_libraries {
+                            self.syncing_libraries.remove(library_id);
+                        }
+                        self.is_syncing =
+                            !self.syncing_sources.is_empty() || !self.syncing_libraries.is_empty();
+                        self.update_status_text();
+
+                        let idx_to_update = {
+                            let guard = self.source_groups.guard();
+                            guard.iter().enumerate().find_map(|(idx, sg)| {
+                                if sg.source.id == source_id {
+                                    Some(idx)
+                                } else {
+                                    None
+                                }
+                            })
+                        };
+
+                        if let Some(idx) = idx_to_update {
+                            for library_id in cancelled_libraries {
+                                self.source_groups
+                                    .send(idx, SourceGroupInput::LibrarySyncCompleted(library_id));
+                            }
+                            self.source_groups
+                                .send(idx, SourceGroupInput::SourceSyncCompleted);
+                        }
+                    }
                     BrokerMessage::Source(SourceMessage::LibrarySyncStarted {
                         source_id,
                         library_id,
@@ -1227,6 +1903,99 @@ impl Component for Sidebar {
                                 .input(SidebarInput::UpdateConnectionStatus("Ready".to_string()));
                         });
                     }
+                    BrokerMessage::Data(DataMessage::PlaybackProgressUpdated {
+                        media_id,
+                        watched,
+                    }) => {
+                        // Progress is saved every few seconds during playback,
+                        // but only a change of the watched flag changes a count
+                        if self.watched_states.insert(media_id.clone(), watched) != Some(watched) {
+                            self.recount_library_of(media_id, &sender);
+                        }
+                    }
+                    BrokerMessage::Data(DataMessage::MediaUpdated { media_id }) => {
+                        // Whole shows and seasons change without an event per episode
+                        self.watched_states.clear();
+                        self.recount_library_of(media_id, &sender);
+                    }
+                    BrokerMessage::Source(SourceMessage::ConnectionChanged {
+                        source_id,
+                        connected,
+                        connection_type,
+                    }) => {
+                        // A reachable server keeps showing its failed sync until
+                        // the next sync succeeds
+                        let update = {
+                            let guard = self.source_groups.guard();
+                            guard.iter().enumerate().find_map(|(idx, sg)| {
+                                if sg.source.id != source_id {
+                                    return None;
+                                }
+                                Some(match (connected, sg.connection_state) {
+                                    (false, _) => (idx, ConnectionState::Disconnected, None),
+                                    (true, ConnectionState::SyncFailed) => (
+                                        idx,
+                                        ConnectionState::SyncFailed,
+                                        sg.sync_error_message.clone(),
+                                    ),
+                                    (true, _) => (idx, ConnectionState::Connected, None),
+                                })
+                            })
+                        };
+
+                        if let Some((idx, state, error)) = update {
+                            debug!(
+                                "Connection check for source {}: connected={}, setting {:?}",
+                                source_id, connected, state
+                            );
+                            self.source_groups.send(
+                                idx,
+                                SourceGroupInput::UpdateConnectionStatus(
+                                    state,
+                                    error,
+                                    connection_type,
+                                ),
+                            );
+                        }
+                    }
+                    BrokerMessage::Source(SourceMessage::AuthChanged {
+                        source_id,
+                        needs_auth,
+                    }) => {
+                        let idx = {
+                            let guard = self.source_groups.guard();
+                            guard.iter().position(|sg| sg.source.id == source_id)
+                        };
+
+                        if let Some(idx) = idx {
+                            self.source_groups
+                                .send(idx, SourceGroupInput::SetNeedsAuth(needs_auth));
+                        }
+                    }
+                    BrokerMessage::Source(SourceMessage::LibrariesChanged { source_id }) => {
+                        let idx = {
+                            let guard = self.source_groups.guard();
+                            guard.iter().enumerate().find_map(|(idx, sg)| {
+                                if sg.source.id == source_id {
+                                    Some(idx)
+                                } else {
+                                    None
+                                }
+                            })
+                        };
+
+                        if let Some(idx) = idx {
+                            self.source_groups
+                                .send(idx, SourceGroupInput::ReloadLibraries);
+                        }
+                    }
+                    BrokerMessage::Connectivity(change) => {
+                        let offline = matches!(change, ConnectivityMessage::WentOffline);
+                        for idx in 0..self.source_groups.len() {
+                            self.source_groups
+                                .send(idx, SourceGroupInput::SetOffline(offline));
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -9,6 +9,9 @@ use crate::models::{AuthStatus, SourceId};
 use crate::services::core::connection::ConnectionService;
 use crate::services::core::connection_cache::ConnectionType;
 
+/// Delay before reacting to network changes, letting routes and DNS settle
+const NETWORK_CHANGE_DEBOUNCE: Duration = Duration::from_secs(2);
+
 #[derive(Debug)]
 pub struct ConnectionMonitor {
     pub(crate) db: DatabaseConnection,
@@ -20,7 +23,11 @@ pub struct ConnectionMonitor {
 #[derive(Debug, Clone)]
 pub enum ConnectionMonitorInput {
     CheckSource(SourceId),
+    /// Forget the cached connection of a source and look for one again
+    Reconnect(SourceId),
     CheckAllSources,
+    /// The network changed - cached connection results are stale
+    NetworkChanged,
     UpdateCheckTimes(HashMap<SourceId, Instant>),
     UpdateAuthStatus(HashMap<SourceId, AuthStatus>),
 }
@@ -62,6 +69,17 @@ impl Worker for ConnectionMonitor {
 
     fn update(&mut self, msg: Self::Input, sender: relm4::ComponentSender<Self>) {
         match msg {
+            ConnectionMonitorInput::Reconnect(source_id) => {
+                info!("Reconnecting source: {}", source_id);
+                self.next_check_times.remove(&source_id);
+
+                let sender = sender.clone();
+                self.runtime.spawn(async move {
+                    ConnectionService::cache().invalidate(&source_id).await;
+                    sender.input(ConnectionMonitorInput::CheckSource(source_id));
+                });
+            }
+
             ConnectionMonitorInput::CheckSource(source_id) => {
                 let db = self.db.clone();
                 let sender = sender.clone();
@@ -195,8 +213,12 @@ impl Worker for ConnectionMonitor {
                                         let quality_changed = previous_quality.as_deref()
                                             != Some(&connection_type.to_string());
 
+                                        // A source that was failing is reachable again
+                                        let recovered = source.connection_failure_count > 0;
+
                                         if previous_url.as_ref() != Some(&new_url)
                                             || quality_changed
+                                            || recovered
                                         {
                                             info!(
                                                 "Connection changed for {}: {:?} -> {} ({:?})",
@@ -212,7 +234,8 @@ impl Worker for ConnectionMonitor {
                                         }
                                     }
                                     Ok(None) => {
-                                        if previous_url.is_some() {
+                                        // Only report the transition, not every failed check
+                                        if source.connection_failure_count == 0 {
                                             warn!("Lost all connections for source: {}", source_id);
                                             let _ = sender.output(
                                                 ConnectionMonitorOutput::ConnectionLost {
@@ -268,6 +291,19 @@ impl Worker for ConnectionMonitor {
                 });
             }
 
+            ConnectionMonitorInput::NetworkChanged => {
+                info!("Network changed, re-selecting connections for all sources");
+
+                // Every source is due immediately - results describe the old network
+                self.next_check_times.clear();
+
+                let sender = sender.clone();
+                self.runtime.spawn(async move {
+                    ConnectionService::cache().clear().await;
+                    sender.input(ConnectionMonitorInput::CheckAllSources);
+                });
+            }
+
             ConnectionMonitorInput::UpdateCheckTimes(times) => {
                 self.next_check_times = times;
             }
@@ -315,6 +351,34 @@ impl ConnectionMonitor {
             }
         });
     }
+
+    /// Re-select connections as soon as the network changes (e.g. leaving the home
+    /// network) instead of waiting for requests against the old URL to time out.
+    ///
+    /// `network-changed` fires several times per transition, so checks are debounced.
+    pub fn watch_network_changes(sender: relm4::Sender<ConnectionMonitorInput>) {
+        use relm4::gtk::{gio, glib};
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let pending: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+
+        gio::NetworkMonitor::default().connect_network_changed(move |_, available| {
+            debug!("Network changed (available: {})", available);
+
+            if let Some(source) = pending.borrow_mut().take() {
+                source.remove();
+            }
+
+            let sender = sender.clone();
+            let pending_inner = pending.clone();
+            let source = glib::timeout_add_local_once(NETWORK_CHANGE_DEBOUNCE, move || {
+                pending_inner.borrow_mut().take();
+                let _ = sender.send(ConnectionMonitorInput::NetworkChanged);
+            });
+            *pending.borrow_mut() = Some(source);
+        });
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,230 @@
+use crate::db::DatabaseConnection;
+use crate::db::entities::OfflineContentModel;
+use crate::db::repository::{OfflineContentRepository, OfflineContentRepositoryImpl, Repository};
+use crate::services::core::{DownloadService, connectivity, metered};
+use relm4::{ComponentSender, Worker};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, error, info, warn};
+
+/// How often the download queue is polled
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often the cleanup policy runs over finished downloads
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone)]
+pub enum DownloadWorkerInput {
+    /// Start the next queued download if nothing is running
+    ProcessQueue,
+    /// A download finished, successfully or not
+    DownloadFinished,
+    /// Apply the per-show auto-download rules, e.g. after a sync
+    ScheduleAutoDownloads,
+    /// Delete watched downloads according to the cleanup policy
+    ApplyCleanupPolicy,
+}
+
+#[derive(Debug, Clone)]
+pub enum DownloadWorkerOutput {
+    DownloadStarted {
+        media_id: String,
+    },
+    DownloadProgress {
+        media_id: String,
+        downloaded_bytes: u64,
+        total_bytes: Option<u64>,
+    },
+    DownloadCompleted {
+        media_id: String,
+    },
+    DownloadFailed {
+        media_id: String,
+        error: String,
+    },
+}
+
+/// Background worker that downloads queued media for offline playback, one item at a time
+#[derive(Debug)]
+pub struct DownloadWorker {
+    db: Arc<DatabaseConnection>,
+    is_downloading: bool,
+    _poll_handle: relm4::JoinHandle<()>,
+    _cleanup_handle: relm4::JoinHandle<()>,
+}
+
+impl DownloadWorker {
+    async fn run_next(db: Arc<DatabaseConnection>, sender: ComponentSender<DownloadWorker>) {
+        let repo = OfflineContentRepositoryImpl::new(db.as_ref().clone());
+
+        let item = match repo.get_queued().await {
+            Ok(items) => items.into_iter().next(),
+            Err(e) => {
+                error!("Failed to fetch queued downloads: {}", e);
+                None
+            }
+        };
+
+        let Some(item) = item else {
+            sender.input(DownloadWorkerInput::DownloadFinished);
+            return;
+        };
+
+        let media_id = item.media_id.clone();
+        info!("Starting download of {}", media_id);
+        sender
+            .output(DownloadWorkerOutput::DownloadStarted {
+                media_id: media_id.clone(),
+            })
+            .ok();
+
+        let progress_sender = sender.clone();
+        let progress_media_id = media_id.clone();
+        let result = DownloadService::download_item(&db, &item, move |downloaded, total| {
+            progress_sender
+                .output(DownloadWorkerOutput::DownloadProgress {
+                    media_id: progress_media_id.clone(),
+                    downloaded_bytes: downloaded,
+                    total_bytes: total,
+                })
+                .ok();
+        })
+        .await;
+
+        match result {
+            Ok(_) => {
+                sender
+                    .output(DownloadWorkerOutput::DownloadCompleted { media_id })
+                    .ok();
+            }
+            Err(e) if Self::made_progress(&repo, &item).await => {
+                // A dropped connection doesn't lose what was written, the
+                // next poll continues from there
+                warn!("Download of {} interrupted, will resume: {}", media_id, e);
+                if let Err(e) = repo.requeue(item.id).await {
+                    error!("Failed to re-queue download {}: {}", item.id, e);
+                }
+            }
+            Err(e) => {
+                error!("Download of {} failed: {}", media_id, e);
+                let error_msg = e.to_string();
+                if let Err(e) = repo.mark_failed(item.id, &error_msg).await {
+                    error!("Failed to mark download {} as failed: {}", item.id, e);
+                }
+                // Nothing of a failed attempt is continued, so its file goes
+                if let Err(e) = DownloadService::discard_partial(&item).await {
+                    warn!("Failed to delete partial download {}: {}", item.id, e);
+                }
+                sender
+                    .output(DownloadWorkerOutput::DownloadFailed {
+                        media_id,
+                        error: error_msg,
+                    })
+                    .ok();
+            }
+        }
+
+        sender.input(DownloadWorkerInput::DownloadFinished);
+    }
+
+    /// Whether a failed attempt recorded more bytes than it started from.
+    /// Attempts that get nowhere fail the download instead of retrying it
+    /// forever.
+    async fn made_progress(
+        repo: &OfflineContentRepositoryImpl,
+        item: &OfflineContentModel,
+    ) -> bool {
+        match repo.find_by_id(&item.id.to_string()).await {
+            Ok(Some(current)) => current.downloaded_bytes > item.downloaded_bytes,
+            _ => false,
+        }
+    }
+}
+
+impl Worker for DownloadWorker {
+    type Init = Arc<DatabaseConnection>;
+    type Input = DownloadWorkerInput;
+    type Output = DownloadWorkerOutput;
+
+    fn init(db: Self::Init, sender: ComponentSender<Self>) -> Self {
+        // Downloads interrupted by the last exit continue where they stopped
+        let requeue_db = db.clone();
+        relm4::spawn(async move {
+            let repo = OfflineContentRepositoryImpl::new(requeue_db.as_ref().clone());
+            match repo.requeue_interrupted().await {
+                Ok(0) => {}
+                Ok(count) => info!("Re-queued {} interrupted downloads", count),
+                Err(e) => error!("Failed to re-queue interrupted downloads: {}", e),
+            }
+        });
+
+        let cleanup_sender = sender.clone();
+        let cleanup_handle = relm4::spawn(async move {
+            loop {
+                cleanup_sender.input(DownloadWorkerInput::ApplyCleanupPolicy);
+                sleep(CLEANUP_INTERVAL).await;
+            }
+        });
+
+        let poll_handle = relm4::spawn(async move {
+            loop {
+                sender.input(DownloadWorkerInput::ProcessQueue);
+                sleep(POLL_INTERVAL).await;
+            }
+        });
+
+        Self {
+            db,
+            is_downloading: false,
+            _poll_handle: poll_handle,
+            _cleanup_handle: cleanup_handle,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            DownloadWorkerInput::ProcessQueue => {
+                if self.is_downloading {
+                    return;
+                }
+                if connectivity::is_offline() {
+                    debug!("Offline, not starting downloads");
+                    return;
+                }
+                if metered::active_restrictions().pause_downloads {
+                    debug!("Metered connection, downloads paused");
+                    return;
+                }
+
+                self.is_downloading = true;
+                let db = self.db.clone();
+                relm4::spawn(async move {
+                    Self::run_next(db, sender).await;
+                });
+            }
+            DownloadWorkerInput::DownloadFinished => {
+                self.is_downloading = false;
+            }
+            DownloadWorkerInput::ScheduleAutoDownloads => {
+                let db = self.db.clone();
+                relm4::spawn(async move {
+                    match DownloadService::schedule_auto_downloads(&db).await {
+                        Ok(()) => sender.input(DownloadWorkerInput::ProcessQueue),
+                        Err(e) => error!("Failed to schedule auto-downloads: {}", e),
+                    }
+                });
+            }
+            DownloadWorkerInput::ApplyCleanupPolicy => {
+                let db = self.db.clone();
+                relm4::spawn(async move {
+                    match DownloadService::apply_cleanup_policy(&db).await {
+                        Ok(0) => {}
+                        Ok(count) => info!("Cleanup policy deleted {} downloads", count),
+                        Err(e) => error!("Failed to apply download cleanup policy: {}", e),
+                    }
+                });
+            }
+        }
+    }
+}
@@ -3,9 +3,12 @@ use relm4::prelude::*;
 use relm4::{ComponentSender, Worker};
 use std::collections::{BinaryHeap, HashMap};
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{debug, error, trace};
 
+use super::thumbnail_worker;
+use crate::services::core::{image_quality, storage_locations};
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ImageSize {
     Thumbnail, // 180x270
@@ -57,6 +60,7 @@ impl Ord for ImageRequest {
 
 #[derive(Debug, Clone)]
 pub enum ImageLoaderInput {
+    /// Load an image, or change the priority of an earlier request with the same id
     LoadImage(ImageRequest),
     CancelLoad {
         id: String,
@@ -64,12 +68,9 @@ pub enum ImageLoaderInput {
     ClearCache,
     SetCacheSize(usize),
     LoadCompleted {
-        id: String,
-    }, // Internal signal that a load completed
-    StoreInCache {
         key: String,
-        texture: gtk::gdk::Texture,
-    }, // Store loaded texture in cache
+        result: Result<gtk::gdk::Texture, String>,
+    }, // Internal signal that a download finished
 }
 
 #[derive(Debug, Clone)]
@@ -86,35 +87,81 @@ pub enum ImageLoaderOutput {
     CacheCleared,
 }
 
+/// One download, shared by every request for the same URL and size
+struct PendingLoad {
+    url: String,
+    size: ImageSize,
+    /// Ids waiting for this image and the priority each asked for
+    waiters: HashMap<String, u8>,
+    /// Set once the download has started
+    handle: Option<relm4::JoinHandle<()>>,
+}
+
+impl PendingLoad {
+    /// A load is as urgent as its most urgent waiter
+    fn priority(&self) -> u8 {
+        self.waiters.values().copied().min().unwrap_or(u8::MAX)
+    }
+}
+
+/// Queue entry for a load, stale once the load's priority changes or it starts
+#[derive(Debug, PartialEq, Eq)]
+struct QueuedLoad {
+    priority: u8,
+    /// Order of queueing, so equal priorities load first come first served
+    seq: u64,
+    key: String,
+}
+
+impl PartialOrd for QueuedLoad {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedLoad {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Reversed for the max-heap: lower priority values and older entries first
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
 pub struct ImageLoader {
-    cache_dir: PathBuf,
     memory_cache: LruCache<String, gtk::gdk::Texture>,
-    pending_loads: HashMap<String, ImageRequest>,
-    active_loads: HashMap<String, relm4::JoinHandle<()>>,
-    priority_queue: BinaryHeap<ImageRequest>,
+    /// Queued and running downloads by cache key
+    loads: HashMap<String, PendingLoad>,
+    /// Cache key each waiting id is attached to
+    load_for_id: HashMap<String, String>,
+    priority_queue: BinaryHeap<QueuedLoad>,
+    next_seq: u64,
+    active_loads: usize,
     max_concurrent_loads: usize,
 }
 
 impl ImageLoader {
     fn new() -> Self {
-        let cache_dir = dirs::cache_dir()
-            .unwrap_or_else(|| PathBuf::from("/tmp"))
-            .join("reel")
-            .join("images");
-
-        std::fs::create_dir_all(&cache_dir).ok();
+        std::fs::create_dir_all(Self::cache_dir()).ok();
 
         Self {
-            cache_dir,
             memory_cache: LruCache::new(NonZeroUsize::new(200).unwrap()), // Increased cache size
-            pending_loads: HashMap::new(),
-            active_loads: HashMap::new(),
+            loads: HashMap::new(),
+            load_for_id: HashMap::new(),
             priority_queue: BinaryHeap::new(),
+            next_seq: 0,
+            active_loads: 0,
             max_concurrent_loads: 6, // Limit concurrent network requests
         }
     }
 
-    fn get_cache_path(&self, url: &str, size: &ImageSize) -> PathBuf {
+    /// Disk cache directory, which can be moved in preferences while running
+    fn cache_dir() -> PathBuf {
+        storage_locations().image_cache
+    }
+
+    pub(crate) fn get_cache_path(url: &str, size: &ImageSize) -> PathBuf {
         let url_hash = format!("{:x}", md5::compute(url));
         let size_suffix = match size {
             ImageSize::Thumbnail => "thumb",
@@ -123,8 +170,12 @@ impl ImageLoader {
             ImageSize::Custom(w, h) => &format!("{}x{}", w, h),
         };
 
-        self.cache_dir
-            .join(format!("{}_{}.jpg", url_hash, size_suffix))
+        Self::cache_dir().join(format!(
+            "{}_{}_{}.jpg",
+            url_hash,
+            size_suffix,
+            image_quality::cache_tag()
+        ))
     }
 
     fn get_cache_key(url: &str, size: &ImageSize) -> String {
@@ -132,7 +183,8 @@ impl ImageLoader {
     }
 
     async fn load_image_async(
-        request: ImageRequest,
+        url: String,
+        size: ImageSize,
         cache_path: PathBuf,
     ) -> Result<gtk::gdk::Texture, String> {
         // Check if file exists in cache
@@ -141,34 +193,68 @@ impl ImageLoader {
                 .map_err(|e| format!("Failed to load cached image: {}", e));
         }
 
-        // Download the image
-        debug!("Downloading image: {} from {}", request.id, request.url);
-        let response = reqwest::get(&request.url)
-            .await
-            .map_err(|e| format!("Failed to download: {}", e))?;
-
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|e| format!("Failed to read bytes: {}", e))?;
-
-        // Process image based on size
-        let processed_bytes = if request.size != ImageSize::Full {
-            let (width, height) = request.size.dimensions();
-            resize_image(&bytes, width, height).map_err(|e| format!("Failed to resize: {}", e))?
-        } else {
-            bytes.to_vec()
-        };
+        let bytes = fetch_into_cache(&url, &size, &cache_path).await?;
 
-        // Save to cache
-        if let Err(e) = std::fs::write(&cache_path, &processed_bytes) {
+        // Create texture from bytes
+        create_texture_from_bytes(&bytes).map_err(|e| format!("Failed to create texture: {}", e))
+    }
+}
+
+/// Put an image into the disk cache without loading it, so it shows right
+/// away once a page asks for it. Returns whether it had to be fetched.
+pub(crate) async fn prefetch_image(url: &str, size: ImageSize) -> Result<bool, String> {
+    let cache_path = ImageLoader::get_cache_path(url, &size);
+    if cache_path.exists() {
+        return Ok(false);
+    }
+    fetch_into_cache(url, &size, &cache_path).await?;
+    Ok(true)
+}
+
+/// Fetch an image at `size` and write it to `cache_path`, returning its bytes
+async fn fetch_into_cache(
+    url: &str,
+    size: &ImageSize,
+    cache_path: &Path,
+) -> Result<Vec<u8>, String> {
+    // Frames of local files are decoded on first use
+    if let Some((path, position)) = thumbnail_worker::parse_frame_url(url) {
+        let frame = thumbnail_worker::extract_frame(path, position, size.clone()).await?;
+        if let Err(e) = std::fs::write(cache_path, &frame) {
             error!("Failed to cache image: {}", e);
         }
+        return Ok(frame);
+    }
 
-        // Create texture from bytes
-        create_texture_from_bytes(&processed_bytes)
-            .map_err(|e| format!("Failed to create texture: {}", e))
+    // Ask the server for the size shown rather than the full image
+    let (width, height) = size.dimensions();
+    let (width, height) = image_quality::pixel_size(width, height);
+    let url = image_quality::sized_url(url, width, height);
+
+    // Download the image
+    debug!("Downloading image from {}", url);
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to download: {}", e))?;
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read bytes: {}", e))?;
+
+    // Process image based on size
+    let processed_bytes = if *size != ImageSize::Full {
+        resize_image(&bytes, width, height).map_err(|e| format!("Failed to resize: {}", e))?
+    } else {
+        bytes.to_vec()
+    };
+
+    // Save to cache
+    if let Err(e) = std::fs::write(cache_path, &processed_bytes) {
+        error!("Failed to cache image: {}", e);
     }
+
+    Ok(processed_bytes)
 }
 
 fn load_texture_from_file(path: &PathBuf) -> Result<gtk::gdk::Texture, String> {
@@ -181,7 +267,7 @@ fn create_texture_from_bytes(bytes: &[u8]) -> Result<gtk::gdk::Texture, String>
 }
 
 fn resize_image(bytes: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
-    use image::ImageFormat;
+    use image::codecs::jpeg::JpegEncoder;
 
     let img =
         image::load_from_memory(bytes).map_err(|e| format!("Failed to decode image: {}", e))?;
@@ -193,8 +279,13 @@ fn resize_image(bytes: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String
     };
 
     let mut output = Vec::new();
+    let encoder = JpegEncoder::new_with_quality(
+        &mut std::io::Cursor::new(&mut output),
+        image_quality::quality().jpeg_quality(),
+    );
     resized
-        .write_to(&mut std::io::Cursor::new(&mut output), ImageFormat::Jpeg)
+        .to_rgb8()
+        .write_with_encoder(encoder)
         .map_err(|e| format!("Failed to encode image: {}", e))?;
 
     Ok(output)
@@ -218,83 +309,75 @@ impl Worker for ImageLoader {
                 let cache_key = Self::get_cache_key(&request.url, &request.size);
 
                 // Check memory cache first
-                if let Some(texture) = self.memory_cache.get(&cache_key) {
+                if let Some(texture) = self.memory_cache.get(&cache_key).cloned() {
                     trace!("Image {} found in memory cache", request.id);
+                    self.detach(&request.id, &sender);
                     // Ignore send errors during shutdown
                     let _ = sender.output(ImageLoaderOutput::ImageLoaded {
                         id: request.id,
-                        texture: texture.clone(),
+                        texture,
                         size: request.size,
                     });
                     return;
                 }
 
-                // Check if already pending or active
-                if self.pending_loads.contains_key(&request.id)
-                    || self.active_loads.contains_key(&request.id)
+                // A card reused for another image no longer wants the old one
+                if self
+                    .load_for_id
+                    .get(&request.id)
+                    .is_some_and(|key| *key != cache_key)
                 {
-                    trace!(
-                        "Image {} already being loaded, updating priority if needed",
-                        request.id
-                    );
-                    // Update priority if this request has higher priority
-                    if let Some(existing) = self.pending_loads.get_mut(&request.id)
-                        && request.priority < existing.priority
-                    {
-                        existing.priority = request.priority;
-                        // Re-sort the queue
-                        self.rebuild_priority_queue();
-                    }
-                    return;
+                    self.detach(&request.id, &sender);
                 }
 
-                // Add to priority queue
                 trace!(
-                    "Adding image {} to priority queue with priority {}",
+                    "Queueing image {} with priority {}",
                     request.id, request.priority
                 );
-                self.pending_loads
-                    .insert(request.id.clone(), request.clone());
-                self.priority_queue.push(request);
+                self.load_for_id
+                    .insert(request.id.clone(), cache_key.clone());
+                let load = self
+                    .loads
+                    .entry(cache_key.clone())
+                    .or_insert_with(|| PendingLoad {
+                        url: request.url.clone(),
+                        size: request.size.clone(),
+                        waiters: HashMap::new(),
+                        handle: None,
+                    });
+                let previous_priority =
+                    (load.handle.is_none() && !load.waiters.is_empty()).then(|| load.priority());
+                load.waiters.insert(request.id, request.priority);
+                self.requeue(&cache_key, previous_priority);
 
                 // Process queue if we have capacity
-                self.process_priority_queue(sender.clone());
+                self.process_priority_queue(sender);
             }
 
             ImageLoaderInput::CancelLoad { id } => {
                 trace!("Cancelling load for image {}", id);
-
-                // Cancel active load if exists
-                if let Some(handle) = self.active_loads.remove(&id) {
-                    handle.abort();
-                    debug!("Cancelled active load for {}", id);
-
-                    // Process next item in queue since we freed up a slot
-                    self.process_priority_queue(sender.clone());
-                }
-
-                // Remove from pending
-                if self.pending_loads.remove(&id).is_some() {
-                    // Rebuild queue to remove the cancelled item
-                    self.rebuild_priority_queue();
-                }
+                self.detach(&id, &sender);
             }
 
             ImageLoaderInput::ClearCache => {
                 self.memory_cache.clear();
-                self.pending_loads.clear();
                 self.priority_queue.clear();
+                self.load_for_id.clear();
+                self.active_loads = 0;
 
                 // Cancel all active loads
-                for (_, handle) in self.active_loads.drain() {
-                    handle.abort();
+                for (_, load) in self.loads.drain() {
+                    if let Some(handle) = load.handle {
+                        handle.abort();
+                    }
                 }
 
                 // Clear disk cache
-                if let Err(e) = std::fs::remove_dir_all(&self.cache_dir) {
+                let cache_dir = Self::cache_dir();
+                if let Err(e) = std::fs::remove_dir_all(&cache_dir) {
                     error!("Failed to clear cache directory: {}", e);
                 }
-                std::fs::create_dir_all(&self.cache_dir).ok();
+                std::fs::create_dir_all(&cache_dir).ok();
 
                 // Only send output if channel is still open
                 let _ = sender.output(ImageLoaderOutput::CacheCleared);
@@ -306,96 +389,178 @@ impl Worker for ImageLoader {
                 }
             }
 
-            ImageLoaderInput::LoadCompleted { id } => {
-                // Remove from active loads and pending loads
-                self.active_loads.remove(&id);
-                self.pending_loads.remove(&id);
+            ImageLoaderInput::LoadCompleted { key, result } => {
+                // Ignore loads that were cancelled or cleared meanwhile
+                if !self
+                    .loads
+                    .get(&key)
+                    .is_some_and(|load| load.handle.is_some())
+                {
+                    return;
+                }
+                let Some(load) = self.loads.remove(&key) else {
+                    return;
+                };
+                self.active_loads -= 1;
 
-                // Process next item in queue since we freed up a slot
-                self.process_priority_queue(sender.clone());
-            }
+                if let Ok(texture) = &result {
+                    self.memory_cache.put(key, texture.clone());
+                }
 
-            ImageLoaderInput::StoreInCache { key, texture } => {
-                // Store the loaded texture in memory cache
-                self.memory_cache.put(key, texture);
+                // Every id waiting for this URL gets the same result
+                for id in load.waiters.into_keys() {
+                    self.load_for_id.remove(&id);
+                    let output = match &result {
+                        Ok(texture) => ImageLoaderOutput::ImageLoaded {
+                            id,
+                            texture: texture.clone(),
+                            size: load.size.clone(),
+                        },
+                        Err(error) => ImageLoaderOutput::LoadFailed {
+                            id,
+                            error: error.clone(),
+                        },
+                    };
+                    let _ = sender.output(output);
+                }
+
+                // Process next item in queue since we freed up a slot
+                self.process_priority_queue(sender);
             }
         }
     }
 }
 
 impl ImageLoader {
+    /// Stop waiting for an image on behalf of `id`, dropping the load once
+    /// nobody waits for it anymore
+    fn detach(&mut self, id: &str, sender: &ComponentSender<Self>) {
+        let Some(key) = self.load_for_id.remove(id) else {
+            return;
+        };
+        let Some(load) = self.loads.get_mut(&key) else {
+            return;
+        };
+
+        let previous_priority = load.priority();
+        load.waiters.remove(id);
+
+        if !load.waiters.is_empty() {
+            if load.handle.is_none() {
+                self.requeue(&key, Some(previous_priority));
+            }
+            return;
+        }
+
+        if let Some(load) = self.loads.remove(&key)
+            && let Some(handle) = load.handle
+        {
+            handle.abort();
+            self.active_loads -= 1;
+            debug!("Cancelled active load for {}", id);
+
+            // Process next item in queue since we freed up a slot
+            self.process_priority_queue(sender.clone());
+        }
+    }
+
+    /// Queue a waiting load again when its priority changed, leaving the old
+    /// entry to be skipped as stale
+    fn requeue(&mut self, key: &str, previous_priority: Option<u8>) {
+        let Some(load) = self.loads.get(key) else {
+            return;
+        };
+        if load.handle.is_some() || previous_priority == Some(load.priority()) {
+            return;
+        }
+
+        self.priority_queue.push(QueuedLoad {
+            priority: load.priority(),
+            seq: self.next_seq,
+            key: key.to_string(),
+        });
+        self.next_seq += 1;
+
+        // Drop stale entries once they outnumber the live ones, e.g. after long scrolling
+        if self.priority_queue.len() > 4 * self.loads.len() + 64 {
+            let loads = &self.loads;
+            self.priority_queue.retain(|entry| {
+                loads
+                    .get(&entry.key)
+                    .is_some_and(|load| load.handle.is_none() && load.priority() == entry.priority)
+            });
+        }
+    }
+
     fn process_priority_queue(&mut self, sender: ComponentSender<Self>) {
         // Process items from priority queue while we have capacity
-        while self.active_loads.len() < self.max_concurrent_loads {
-            // Get next highest priority item
-            let request = loop {
-                if let Some(req) = self.priority_queue.pop() {
-                    // Check if this request is still pending (might have been cancelled)
-                    if self.pending_loads.contains_key(&req.id) {
-                        break Some(req);
-                    }
-                    // Skip cancelled items
-                } else {
-                    break None;
-                }
+        while self.active_loads < self.max_concurrent_loads {
+            let Some(entry) = self.priority_queue.pop() else {
+                break; // No more items in queue
             };
 
-            if let Some(request) = request {
-                self.start_image_load(request, sender.clone());
-            } else {
-                break; // No more items in queue
+            // Skip entries for cancelled, running or re-prioritized loads
+            let is_current = self
+                .loads
+                .get(&entry.key)
+                .is_some_and(|load| load.handle.is_none() && load.priority() == entry.priority);
+            if is_current {
+                self.start_image_load(entry.key, sender.clone());
             }
         }
     }
 
-    fn start_image_load(&mut self, request: ImageRequest, sender: ComponentSender<Self>) {
-        let cache_key = Self::get_cache_key(&request.url, &request.size);
-        let cache_path = self.get_cache_path(&request.url, &request.size);
-        let req_clone = request.clone();
-        let sender_clone = sender.clone();
-        let cache_key_clone = cache_key.clone();
-        let id = request.id.clone();
-
-        let handle = relm4::spawn(async move {
-            match Self::load_image_async(req_clone.clone(), cache_path).await {
-                Ok(texture) => {
-                    // Store in cache - ignore errors if channel is closed
-                    let _ = sender_clone
-                        .input_sender()
-                        .send(ImageLoaderInput::StoreInCache {
-                            key: cache_key_clone,
-                            texture: texture.clone(),
-                        });
+    fn start_image_load(&mut self, key: String, sender: ComponentSender<Self>) {
+        let Some(load) = self.loads.get_mut(&key) else {
+            return;
+        };
+        let url = load.url.clone();
+        let size = load.size.clone();
+        let cache_path = Self::get_cache_path(&url, &size);
+        let key_clone = key.clone();
+
+        load.handle = Some(relm4::spawn(async move {
+            let result = Self::load_image_async(url, size, cache_path).await;
+
+            // Hand the result back so it reaches every waiter - ignore if channel closed
+            let _ = sender.input_sender().send(ImageLoaderInput::LoadCompleted {
+                key: key_clone,
+                result,
+            });
+        }));
+        self.active_loads += 1;
+    }
+}
 
-                    let _ = sender_clone.output(ImageLoaderOutput::ImageLoaded {
-                        id: req_clone.id.clone(),
-                        texture,
-                        size: req_clone.size,
-                    });
-                }
-                Err(error) => {
-                    let _ = sender_clone.output(ImageLoaderOutput::LoadFailed {
-                        id: req_clone.id.clone(),
-                        error,
-                    });
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            // Notify that this load is complete so we can process more - ignore if channel closed
-            let _ = sender_clone
-                .input_sender()
-                .send(ImageLoaderInput::LoadCompleted { id: req_clone.id });
-        });
+    fn queued(priority: u8, seq: u64) -> QueuedLoad {
+        QueuedLoad {
+            priority,
+            seq,
+            key: format!("{}-{}", priority, seq),
+        }
+    }
 
-        self.active_loads.insert(id, handle);
-        // Note: We keep it in pending_loads until it completes or is cancelled
+    #[test]
+    fn test_queue_prefers_priority_then_age() {
+        let mut queue = BinaryHeap::from([queued(3, 0), queued(0, 2), queued(0, 1), queued(1, 3)]);
+        let order: Vec<_> = std::iter::from_fn(|| queue.pop())
+            .map(|entry| (entry.priority, entry.seq))
+            .collect();
+        assert_eq!(order, vec![(0, 1), (0, 2), (1, 3), (3, 0)]);
     }
 
-    fn rebuild_priority_queue(&mut self) {
-        // Rebuild the priority queue from pending loads
-        self.priority_queue.clear();
-        for request in self.pending_loads.values() {
-            self.priority_queue.push(request.clone());
-        }
+    #[test]
+    fn test_shared_load_takes_most_urgent_waiter() {
+        let load = PendingLoad {
+            url: "http://example.com/poster.jpg".to_string(),
+            size: ImageSize::Thumbnail,
+            waiters: HashMap::from([("a".to_string(), 7), ("b".to_string(), 2)]),
+            handle: None,
+        };
+        assert_eq!(load.priority(), 2);
     }
 }
@@ -0,0 +1,162 @@
+//! Fills the image cache with the artwork of synced sources while the app
+//! is idle, one source at a time. Using the app again interrupts it and the
+//! source is picked up again at the next idle spell, skipping what is cached.
+
+use crate::db::DatabaseConnection;
+use crate::models::SourceId;
+use crate::services::config_service::CONFIG_SERVICE;
+use crate::services::core::image_prefetch::ImagePrefetchService;
+use crate::services::core::{metered, power_saver};
+use crate::workers::image_loader::{ImageSize, prefetch_image};
+use futures::StreamExt;
+use relm4::{ComponentSender, Worker};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// Downloads at once, few enough to leave bandwidth for what the user starts
+const CONCURRENT_PREFETCHES: usize = 2;
+
+#[derive(Debug, Clone)]
+pub enum ImagePrefetchInput {
+    /// A source finished syncing, so its artwork may be new
+    SourceSynced(SourceId),
+    /// Whether the app is idle, prefetching only runs while it is
+    SetIdle(bool),
+    /// The prefetch of a source ended, `done` unless it was interrupted
+    Finished { source_id: SourceId, done: bool },
+}
+
+#[derive(Debug)]
+pub struct ImagePrefetchWorker {
+    db: Arc<DatabaseConnection>,
+    /// Sources waiting for their artwork, in the order they synced
+    pending: VecDeque<SourceId>,
+    idle: bool,
+    /// Interrupts the running prefetch once the app is in use again
+    running: Option<CancellationToken>,
+}
+
+impl ImagePrefetchWorker {
+    fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db,
+            pending: VecDeque::new(),
+            idle: false,
+            running: None,
+        }
+    }
+
+    /// Start on the next waiting source if the app is idle and nothing
+    /// restricts loading images ahead of time
+    fn start_next(&mut self, sender: &ComponentSender<Self>) {
+        if !self.idle || self.running.is_some() {
+            return;
+        }
+        if metered::active_restrictions().skip_image_prefetch || power_saver::is_active() {
+            return;
+        }
+        let Some(source_id) = self.pending.pop_front() else {
+            return;
+        };
+
+        let cancel = CancellationToken::new();
+        self.running = Some(cancel.clone());
+        let db = self.db.clone();
+        let sender = sender.clone();
+        relm4::spawn(async move {
+            let done = Self::prefetch_source(&db, &source_id, &cancel).await;
+            sender.input(ImagePrefetchInput::Finished { source_id, done });
+        });
+    }
+
+    /// Cache the posters shown first for a source, returning false if it was
+    /// interrupted before all of them were fetched
+    async fn prefetch_source(
+        db: &DatabaseConnection,
+        source_id: &SourceId,
+        cancel: &CancellationToken,
+    ) -> bool {
+        let home_row_items = CONFIG_SERVICE.get_config().await.ui.home_row_items();
+        let urls = match ImagePrefetchService::poster_urls(db, source_id, home_row_items).await {
+            Ok(urls) => urls,
+            Err(e) => {
+                warn!(
+                    "Failed to find artwork to prefetch for {}: {}",
+                    source_id, e
+                );
+                return true;
+            }
+        };
+
+        info!("Prefetching {} posters for {}", urls.len(), source_id);
+        let prefetch = futures::stream::iter(urls).for_each_concurrent(
+            CONCURRENT_PREFETCHES,
+            |url| async move {
+                if let Err(e) = prefetch_image(&url, ImageSize::Thumbnail).await {
+                    debug!("Failed to prefetch {}: {}", url, e);
+                }
+            },
+        );
+        cancel.run_until_cancelled(prefetch).await.is_some()
+    }
+
+    fn queue(&mut self, source_id: SourceId) {
+        if !self.pending.contains(&source_id) {
+            self.pending.push_back(source_id);
+        }
+    }
+}
+
+impl Worker for ImagePrefetchWorker {
+    type Init = Arc<DatabaseConnection>;
+    type Input = ImagePrefetchInput;
+    type Output = ();
+
+    fn init(db: Self::Init, _sender: ComponentSender<Self>) -> Self {
+        Self::new(db)
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            ImagePrefetchInput::SourceSynced(source_id) => {
+                self.queue(source_id);
+            }
+            ImagePrefetchInput::SetIdle(idle) => {
+                self.idle = idle;
+                if !idle && let Some(cancel) = &self.running {
+                    cancel.cancel();
+                }
+            }
+            ImagePrefetchInput::Finished { source_id, done } => {
+                self.running = None;
+                if done {
+                    debug!("Prefetched artwork for {}", source_id);
+                } else {
+                    // Go on with it first at the next idle spell
+                    self.pending.retain(|pending| *pending != source_id);
+                    self.pending.push_front(source_id);
+                }
+            }
+        }
+        self.start_next(&sender);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources_queue_once_in_sync_order() {
+        let mut worker = ImagePrefetchWorker::new(Arc::new(DatabaseConnection::default()));
+        worker.queue(SourceId::from("a"));
+        worker.queue(SourceId::from("b"));
+        worker.queue(SourceId::from("a"));
+        assert_eq!(
+            worker.pending,
+            VecDeque::from([SourceId::from("a"), SourceId::from("b")])
+        );
+    }
+}
@@ -2,10 +2,13 @@
 pub mod cache_cleanup_worker;
 pub mod config_manager;
 pub mod connection_monitor;
+pub mod download_worker;
 pub mod image_loader;
+pub mod image_prefetch_worker;
 pub mod playback_sync_worker;
 pub mod search_worker;
 pub mod sync_worker;
+pub mod thumbnail_worker;
 
 // Test modules
 #[cfg(test)]
@@ -19,9 +22,12 @@ pub use cache_cleanup_worker::{
     CleanupType,
 };
 pub use connection_monitor::{ConnectionMonitor, ConnectionMonitorInput, ConnectionMonitorOutput};
+pub use download_worker::{DownloadWorker, DownloadWorkerInput, DownloadWorkerOutput};
 pub use image_loader::{ImageLoader, ImageLoaderInput, ImageLoaderOutput, ImageRequest, ImageSize};
+pub use image_prefetch_worker::{ImagePrefetchInput, ImagePrefetchWorker};
 pub use playback_sync_worker::{
     PlaybackSyncWorker, PlaybackSyncWorkerInput, PlaybackSyncWorkerOutput, SyncConfig,
 };
 pub use search_worker::{SearchWorker, SearchWorkerInput, SearchWorkerOutput};
 pub use sync_worker::{SyncWorker, SyncWorkerInput, SyncWorkerOutput};
+pub use thumbnail_worker::{ThumbnailWorker, ThumbnailWorkerInput, ThumbnailWorkerOutput};
@@ -1,5 +1,5 @@
 use crate::models::{MediaItem, MediaItemId};
-use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage};
+use crate::ui::shared::broker::{BROKER, BrokerMessage, DataMessage, Subscription, Topic};
 use relm4::{ComponentSender, Worker};
 use sea_orm::DatabaseConnection;
 use std::collections::HashMap;
@@ -101,10 +101,12 @@ pub struct SearchWorker {
     overview_field: Field,
     year_field: Field,
     genres_field: Field,
+    _broker_subscription: Subscription,
 }
 
 impl SearchWorker {
-    fn new() -> Result<Self, String> {
+    /// Open the index in the data directory, creating it if need be
+    fn open_index(schema: Schema) -> Result<(Index, IndexReader, IndexWriter), String> {
         let index_dir = dirs::data_dir()
             .unwrap_or_else(|| PathBuf::from("/tmp"))
             .join("reel")
@@ -113,15 +115,6 @@ impl SearchWorker {
         std::fs::create_dir_all(&index_dir)
             .map_err(|e| format!("Failed to create index directory: {}", e))?;
 
-        // Build schema
-        let mut schema_builder = Schema::builder();
-        let id_field = schema_builder.add_text_field("id", STORED);
-        let title_field = schema_builder.add_text_field("title", TEXT | STORED);
-        let overview_field = schema_builder.add_text_field("overview", TEXT);
-        let year_field = schema_builder.add_text_field("year", TEXT | STORED);
-        let genres_field = schema_builder.add_text_field("genres", TEXT);
-        let schema = schema_builder.build();
-
         // Create or open index
         let index = if index_dir.join("meta.json").exists() {
             let mmap_dir = MmapDirectory::open(&index_dir)
@@ -130,7 +123,7 @@ impl SearchWorker {
         } else {
             let _mmap_dir = MmapDirectory::open(&index_dir)
                 .map_err(|e| format!("Failed to open index directory: {}", e))?;
-            Index::create_in_dir(&index_dir, schema.clone())
+            Index::create_in_dir(&index_dir, schema)
                 .map_err(|e| format!("Failed to create index: {}", e))?
         };
 
@@ -142,16 +135,7 @@ impl SearchWorker {
             .writer(50_000_000) // 50MB writer buffer
             .map_err(|e| format!("Failed to create index writer: {}", e))?;
 
-        Ok(Self {
-            index: Some(index),
-            reader: Some(reader),
-            writer: Some(writer),
-            id_field,
-            title_field,
-            overview_field,
-            year_field,
-            genres_field,
-        })
+        Ok((index, reader, writer))
     }
 
     fn index_documents(&mut self, documents: Vec<SearchDocument>) -> Result<usize, String> {
@@ -331,26 +315,25 @@ impl Worker for SearchWorker {
 
     fn init(db: Self::Init, sender: ComponentSender<Self>) -> Self {
         // Subscribe to broker for media updates
-        // Create a wrapper sender that maps BrokerMessage to SearchWorkerInput
-        let input_sender = sender.input_sender().clone();
-        relm4::spawn(async move {
-            // Create a channel to receive broker messages
-            let (broker_sender, broker_receiver) = relm4::channel::<BrokerMessage>();
-            BROKER
-                .subscribe("SearchWorker".to_string(), broker_sender)
-                .await;
-
-            // Forward broker messages as SearchWorkerInput
-            while let Some(msg) = broker_receiver.recv().await {
-                input_sender.send(SearchWorkerInput::BrokerMsg(msg)).ok();
-            }
-        });
+        let broker_subscription = BROKER.subscribe(
+            &[Topic::Data],
+            sender.input_sender(),
+            SearchWorkerInput::BrokerMsg,
+        );
 
         // Load initial index from database
         sender.input(SearchWorkerInput::LoadInitialIndex { db: db.clone() });
 
-        match Self::new() {
-            Ok(worker) => worker,
+        // Build schema
+        let mut schema_builder = Schema::builder();
+        let id_field = schema_builder.add_text_field("id", STORED);
+        let title_field = schema_builder.add_text_field("title", TEXT | STORED);
+        let overview_field = schema_builder.add_text_field("overview", TEXT);
+        let year_field = schema_builder.add_text_field("year", TEXT | STORED);
+        let genres_field = schema_builder.add_text_field("genres", TEXT);
+
+        let (index, reader, writer) = match Self::open_index(schema_builder.build()) {
+            Ok((index, reader, writer)) => (Some(index), Some(reader), Some(writer)),
             Err(e) => {
                 error!(
                     "Failed to initialize search worker: {}. Creating fallback worker.",
@@ -364,26 +347,21 @@ impl Worker for SearchWorker {
                     )))
                     .ok();
 
-                // Return a worker with None values - it will handle searches by returning empty results
-                // We still need to create the field definitions even without an index
-                let mut schema_builder = Schema::builder();
-                let id_field = schema_builder.add_text_field("id", STORED);
-                let title_field = schema_builder.add_text_field("title", TEXT | STORED);
-                let overview_field = schema_builder.add_text_field("overview", TEXT);
-                let year_field = schema_builder.add_text_field("year", TEXT | STORED);
-                let genres_field = schema_builder.add_text_field("genres", TEXT);
-
-                SearchWorker {
-                    index: None,
-                    writer: None,
-                    reader: None,
-                    id_field,
-                    title_field,
-                    overview_field,
-                    year_field,
-                    genres_field,
-                }
+                // Without an index, searches return empty results
+                (None, None, None)
             }
+        };
+
+        SearchWorker {
+            index,
+            reader,
+            writer,
+            id_field,
+            title_field,
+            overview_field,
+            year_field,
+            genres_field,
+            _broker_subscription: broker_subscription,
         }
     }
 
@@ -396,6 +374,11 @@ impl Worker for SearchWorker {
                         // Single item update - would need to fetch from DB to index
                         // For now, we'll rely on batch updates during sync
                     }
+                    BrokerMessage::Data(DataMessage::MediaDeleted { media_id }) => {
+                        sender.input(SearchWorkerInput::RemoveDocument(MediaItemId::new(
+                            media_id,
+                        )));
+                    }
                     BrokerMessage::Data(DataMessage::MediaBatchSaved { items }) => {
                         info!("Received MediaBatchSaved event with {} items", items.len());
                         // Convert MediaItemModel to MediaItem to SearchDocument
@@ -1,9 +1,14 @@
 use crate::db::DatabaseConnection;
 use crate::models::{LibraryId, SourceId};
 use crate::services::core::backend::BackendService;
-use crate::services::core::sync::SyncService;
+use crate::services::core::sync::{SyncService, SyncStatus};
+use crate::services::core::sync_control::{SyncCancelled, SyncControl};
+use crate::ui::shared::broker::{BROKER, BrokerMessage, SourceMessage};
+use anyhow::{Context, Result, anyhow};
+use futures::FutureExt;
 use relm4::{ComponentSender, Worker};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tracing::info;
@@ -25,15 +30,28 @@ pub enum SyncWorkerInput {
         library_id: Option<LibraryId>,
         force: bool,
     },
+    /// Cancel the running sync of a source at its next checkpoint
     StopSync {
         source_id: SourceId,
     },
+    /// Pause or resume the sync of a source
+    PauseSync {
+        source_id: SourceId,
+        paused: bool,
+    },
+    /// Playback started or stopped, syncs hold off while something plays
+    PlaybackActive(bool),
     StopAllSyncs,
     SetSyncInterval(Duration),
     EnableAutoSync(bool),
     RecordSuccessfulSync {
         source_id: SourceId,
     },
+    /// The sync task of a source ended, however it went
+    SyncFinished {
+        source_id: SourceId,
+        run: u64,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -60,10 +78,23 @@ pub enum SyncWorkerOutput {
     },
 }
 
+/// The sync task running for one source
+#[derive(Debug)]
+struct SourceSync {
+    /// Tells this sync from one started for the source after it
+    run: u64,
+    control: SyncControl,
+    handle: relm4::JoinHandle<()>,
+}
+
 #[derive(Debug)]
 pub struct SyncWorker {
     db: Arc<DatabaseConnection>,
-    active_syncs: HashMap<SourceId, relm4::JoinHandle<()>>,
+    active_syncs: HashMap<SourceId, SourceSync>,
+    next_run: u64,
+    /// Sources the user paused the running sync of
+    paused_sources: HashSet<SourceId>,
+    playback_active: bool,
     sync_interval: Duration,
     auto_sync_enabled: bool,
     last_sync_times: HashMap<SourceId, Instant>,
@@ -74,17 +105,24 @@ impl SyncWorker {
         Self {
             db,
             active_syncs: HashMap::new(),
+            next_run: 0,
+            paused_sources: HashSet::new(),
+            playback_active: false,
             sync_interval: Duration::from_secs(3600), // Default 1 hour
             auto_sync_enabled: true,
             last_sync_times: HashMap::new(),
         }
     }
 
+    /// Sync one source, reporting how it went. Runs as its own task next to
+    /// the syncs of other sources, so a source that fails, hangs on an
+    /// unreachable server or even panics only affects itself.
     async fn perform_sync(
         db: Arc<DatabaseConnection>,
         source_id: SourceId,
         library_id: Option<LibraryId>,
-        _force: bool,
+        run: u64,
+        control: SyncControl,
         sender: ComponentSender<SyncWorker>,
     ) {
         info!("perform_sync called for source: {:?}", source_id);
@@ -97,73 +135,27 @@ impl SyncWorker {
                 library_id: library_id.clone(),
             })
             .ok();
+        if control.is_paused() {
+            BROKER
+                .broadcast(BrokerMessage::Source(SourceMessage::SyncPaused {
+                    source_id: source_id.to_string(),
+                    paused: true,
+                }))
+                .await;
+        }
 
-        // Load source configuration to create backend
-        use crate::db::repository::Repository;
-        use crate::db::repository::source_repository::SourceRepositoryImpl;
-
-        let source_repo = SourceRepositoryImpl::new(db.as_ref().clone());
-        let source_entity = match source_repo.find_by_id(source_id.as_str()).await {
-            Ok(Some(entity)) => entity,
-            Ok(None) => {
-                tracing::error!("Source not found: {:?}", source_id);
-                sender
-                    .output(SyncWorkerOutput::SyncFailed {
-                        source_id: source_id.clone(),
-                        library_id,
-                        error: "Source not found".to_string(),
-                    })
-                    .ok();
-                return;
-            }
-            Err(e) => {
-                tracing::error!("Failed to load source: {}", e);
-                sender
-                    .output(SyncWorkerOutput::SyncFailed {
-                        source_id: source_id.clone(),
-                        library_id,
-                        error: e.to_string(),
-                    })
-                    .ok();
-                return;
-            }
-        };
-
-        // Create backend for this source
-        let backend = match BackendService::create_backend_for_source(&db, &source_entity).await {
-            Ok(backend) => backend,
-            Err(e) => {
-                tracing::error!("Failed to create backend: {}", e);
-                sender
-                    .output(SyncWorkerOutput::SyncFailed {
-                        source_id: source_id.clone(),
-                        library_id,
-                        error: e.to_string(),
-                    })
-                    .ok();
-                return;
-            }
-        };
-
-        info!("Calling SyncService::sync_source for {:?}", source_id);
-        // Call sync service directly with the backend
-        match SyncService::sync_source(&db, backend.as_ref(), &source_id).await {
-            Ok(sync_result) => {
-                info!(
-                    "Sync succeeded for {:?}: {} items",
-                    source_id, sync_result.items_synced
-                );
-
-                // After successful library/media sync, fetch and save home sections
-                info!("Fetching home sections for source: {:?}", source_id);
-                let sections_synced = Self::sync_home_sections(&db, &source_id, &sender).await;
+        let result = AssertUnwindSafe(Self::sync_source(&db, &source_id, &control, &sender))
+            .catch_unwind()
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("Sync stopped unexpectedly")));
 
-                // Send the sync completed output with sections count
+        match result {
+            Ok((items_synced, sections_synced)) => {
                 sender
                     .output(SyncWorkerOutput::SyncCompleted {
                         source_id: source_id.clone(),
                         library_id,
-                        items_synced: sync_result.items_synced,
+                        items_synced,
                         sections_synced,
                         duration: start_time.elapsed(),
                     })
@@ -174,26 +166,91 @@ impl SyncWorker {
                     source_id: source_id.clone(),
                 });
             }
+            Err(e) if e.is::<SyncCancelled>() => {
+                info!("Sync cancelled for {:?}", source_id);
+                if let Err(status_error) =
+                    SyncService::update_sync_status(&db, &source_id, SyncStatus::Idle, None).await
+                {
+                    tracing::warn!("Failed to record cancelled sync: {}", status_error);
+                }
+
+                BROKER
+                    .broadcast(BrokerMessage::Source(SourceMessage::SyncCancelled {
+                        source_id: source_id.to_string(),
+                    }))
+                    .await;
+
+                sender
+                    .output(SyncWorkerOutput::SyncCancelled {
+                        source_id: source_id.clone(),
+                    })
+                    .ok();
+            }
             Err(e) => {
-                tracing::error!("Sync failed for {:?}: {}", source_id, e);
-                // Log the error chain for debugging
-                let mut error_chain = vec![e.to_string()];
-                let mut source = e.source();
-                while let Some(err) = source {
-                    error_chain.push(err.to_string());
-                    source = err.source();
+                tracing::error!("Sync failed for {:?}: {:#}", source_id, e);
+                if let Err(status_error) =
+                    SyncService::update_sync_status(&db, &source_id, SyncStatus::Failed, None).await
+                {
+                    tracing::warn!("Failed to record failed sync: {}", status_error);
                 }
-                tracing::error!("Error chain for {:?}: {:?}", source_id, error_chain);
+
+                // Let views tracking this source stop showing it as syncing
+                BROKER
+                    .broadcast(BrokerMessage::Source(SourceMessage::SyncError {
+                        source_id: source_id.to_string(),
+                        error: e.to_string(),
+                    }))
+                    .await;
 
                 sender
                     .output(SyncWorkerOutput::SyncFailed {
-                        source_id,
+                        source_id: source_id.clone(),
                         library_id,
                         error: e.to_string(),
                     })
                     .ok();
             }
         }
+
+        sender.input(SyncWorkerInput::SyncFinished { source_id, run });
+    }
+
+    /// Sync the libraries and then the home sections of a source, returning
+    /// the number of items and sections synced
+    async fn sync_source(
+        db: &Arc<DatabaseConnection>,
+        source_id: &SourceId,
+        control: &SyncControl,
+        sender: &ComponentSender<SyncWorker>,
+    ) -> Result<(usize, usize)> {
+        // Load source configuration to create backend
+        use crate::db::repository::Repository;
+        use crate::db::repository::source_repository::SourceRepositoryImpl;
+
+        let source_repo = SourceRepositoryImpl::new(db.as_ref().clone());
+        let source_entity = source_repo
+            .find_by_id(source_id.as_str())
+            .await
+            .context("Failed to load source")?
+            .ok_or_else(|| anyhow!("Source not found"))?;
+
+        // Create backend for this source
+        let backend = BackendService::create_backend_for_source(db, &source_entity).await?;
+
+        info!("Calling SyncService::sync_source for {:?}", source_id);
+        let sync_result =
+            SyncService::sync_source(db, backend.as_ref(), source_id, control).await?;
+        info!(
+            "Sync succeeded for {:?}: {} items",
+            source_id, sync_result.items_synced
+        );
+
+        // After successful library/media sync, fetch and save home sections
+        control.checkpoint().await?;
+        info!("Fetching home sections for source: {:?}", source_id);
+        let sections_synced = Self::sync_home_sections(db, source_id, sender).await;
+
+        Ok((sync_result.items_synced, sections_synced))
     }
 
     /// Sync home sections for a source
@@ -288,6 +345,7 @@ impl SyncWorker {
                         crate::models::HomeSectionType::RecentPlaylists => {
                             "recent_playlists".to_string()
                         }
+                        crate::models::HomeSectionType::Favorites => "favorites".to_string(),
                         crate::models::HomeSectionType::Custom(ref name) => name.clone(),
                     },
                     position: index as i32,
@@ -441,12 +499,20 @@ impl SyncWorker {
         force: bool,
         sender: ComponentSender<Self>,
     ) {
-        // Cancel any existing sync for this source
-        if let Some(handle) = self.active_syncs.remove(&source_id) {
-            handle.abort();
+        if self.active_syncs.contains_key(&source_id) {
+            // A forced sync starts over, otherwise the running one carries on
+            if !force {
+                info!("Sync already running for {:?}", source_id);
+                return;
+            }
+            if let Some(sync) = self.active_syncs.remove(&source_id) {
+                sync.control.cancel();
+                sync.handle.abort();
+            }
         }
 
-        // Check if we should sync (unless forced)
+        // Check if we should sync (unless forced). Only successful syncs
+        // count, so a source that failed is retried on the next request.
         if !force
             && let Some(last_sync) = self.last_sync_times.get(&source_id)
             && last_sync.elapsed() < self.sync_interval
@@ -460,37 +526,85 @@ impl SyncWorker {
 
         // Start new sync
         info!("Starting async sync task for source: {:?}", source_id);
-        let db = self.db.clone();
-        let source_id_clone = source_id.clone();
-        let source_id_clone2 = source_id.clone();
-        let handle = relm4::spawn(async move {
-            info!(
-                "Async sync task starting for source: {:?}",
-                source_id_clone2
-            );
-            Self::perform_sync(db, source_id_clone, library_id, force, sender).await;
-            info!(
-                "Async sync task completed for source: {:?}",
-                source_id_clone2
-            );
-        });
-
-        self.active_syncs.insert(source_id.clone(), handle);
-        // For now, still track when sync starts to prevent rapid retries
-        // TODO: Only track successful syncs to allow retry after failures
-        self.last_sync_times.insert(source_id, Instant::now());
+        self.next_run += 1;
+        let run = self.next_run;
+        let control = SyncControl::default();
+        control.set_paused(self.is_paused(&source_id));
+        let handle = relm4::spawn(Self::perform_sync(
+            self.db.clone(),
+            source_id.clone(),
+            library_id,
+            run,
+            control.clone(),
+            sender,
+        ));
+        self.active_syncs.insert(
+            source_id,
+            SourceSync {
+                run,
+                control,
+                handle,
+            },
+        );
     }
 
+    /// Cancel the sync of a source. It stops at its next checkpoint and
+    /// reports the cancellation itself, so it stays tracked until then.
     fn stop_sync(&mut self, source_id: &SourceId) {
-        if let Some(handle) = self.active_syncs.remove(source_id) {
-            handle.abort();
-            info!("Stopped sync for {:?}", source_id);
+        if let Some(sync) = self.active_syncs.get(source_id) {
+            sync.control.cancel();
+            info!("Cancelling sync for {:?}", source_id);
+        }
+    }
+
+    /// Whether the sync of a source should hold off, because the user
+    /// paused it or something is playing
+    fn is_paused(&self, source_id: &SourceId) -> bool {
+        self.playback_active || self.paused_sources.contains(source_id)
+    }
+
+    /// Pause or resume the running syncs to match the pause state, telling
+    /// views about those that changed
+    fn apply_pause(&self) {
+        for (source_id, sync) in &self.active_syncs {
+            let paused = self.is_paused(source_id);
+            if sync.control.set_paused(paused) {
+                info!(
+                    "Sync {} for {:?}",
+                    if paused { "paused" } else { "resumed" },
+                    source_id
+                );
+                let source_id = source_id.to_string();
+                relm4::spawn(async move {
+                    BROKER
+                        .broadcast(BrokerMessage::Source(SourceMessage::SyncPaused {
+                            source_id,
+                            paused,
+                        }))
+                        .await;
+                });
+            }
+        }
+    }
+
+    /// Forget the sync task of a source once it ended, along with a pause
+    /// the user left on it. A sync started for the source since then stays
+    /// tracked.
+    fn finish_sync(&mut self, source_id: &SourceId, run: u64) {
+        if self
+            .active_syncs
+            .get(source_id)
+            .is_some_and(|sync| sync.run == run)
+        {
+            self.active_syncs.remove(source_id);
+            self.paused_sources.remove(source_id);
         }
     }
 
     fn stop_all_syncs(&mut self) {
-        for (_, handle) in self.active_syncs.drain() {
-            handle.abort();
+        for (_, sync) in self.active_syncs.drain() {
+            sync.control.cancel();
+            sync.handle.abort();
         }
         info!("Stopped all active syncs");
     }
@@ -521,9 +635,23 @@ impl Worker for SyncWorker {
 
             SyncWorkerInput::StopSync { source_id } => {
                 self.stop_sync(&source_id);
-                sender
-                    .output(SyncWorkerOutput::SyncCancelled { source_id })
-                    .ok();
+            }
+
+            SyncWorkerInput::PauseSync { source_id, paused } => {
+                if !self.active_syncs.contains_key(&source_id) {
+                    return;
+                }
+                if paused {
+                    self.paused_sources.insert(source_id);
+                } else {
+                    self.paused_sources.remove(&source_id);
+                }
+                self.apply_pause();
+            }
+
+            SyncWorkerInput::PlaybackActive(active) => {
+                self.playback_active = active;
+                self.apply_pause();
             }
 
             SyncWorkerInput::StopAllSyncs => {
@@ -550,6 +678,10 @@ impl Worker for SyncWorker {
                     .insert(source_id.clone(), Instant::now());
                 info!("Recorded successful sync time for {:?}", source_id);
             }
+
+            SyncWorkerInput::SyncFinished { source_id, run } => {
+                self.finish_sync(&source_id, run);
+            }
         }
     }
 }
@@ -559,6 +691,14 @@ mod tests {
     use super::*;
     use std::time::Duration;
 
+    fn tracked(handle: relm4::JoinHandle<()>) -> SourceSync {
+        SourceSync {
+            run: 1,
+            control: SyncControl::default(),
+            handle,
+        }
+    }
+
     #[test]
     fn test_sync_worker_initialization() {
         // Create a mock database connection for testing
@@ -647,9 +787,15 @@ mod tests {
         let handle2 = relm4::spawn(async {});
         let handle3 = relm4::spawn(async {});
 
-        worker.active_syncs.insert(source_id1.clone(), handle1);
-        worker.active_syncs.insert(source_id2.clone(), handle2);
-        worker.active_syncs.insert(source_id3.clone(), handle3);
+        worker
+            .active_syncs
+            .insert(source_id1.clone(), tracked(handle1));
+        worker
+            .active_syncs
+            .insert(source_id2.clone(), tracked(handle2));
+        worker
+            .active_syncs
+            .insert(source_id3.clone(), tracked(handle3));
 
         // Verify all syncs are tracked
         assert_eq!(worker.active_syncs.len(), 3);
@@ -658,8 +804,8 @@ mod tests {
         assert!(worker.active_syncs.contains_key(&source_id3));
 
         // Remove one sync
-        if let Some(handle) = worker.active_syncs.remove(&source_id2) {
-            handle.abort();
+        if let Some(sync) = worker.active_syncs.remove(&source_id2) {
+            sync.handle.abort();
         }
 
         assert_eq!(worker.active_syncs.len(), 2);
@@ -702,16 +848,20 @@ mod tests {
             tokio::time::sleep(Duration::from_secs(10)).await;
         });
 
-        worker.active_syncs.insert(source_id.clone(), handle);
+        worker
+            .active_syncs
+            .insert(source_id.clone(), tracked(handle));
         assert_eq!(worker.active_syncs.len(), 1);
 
         // Simulate cancelling and replacing with new sync
-        if let Some(old_handle) = worker.active_syncs.remove(&source_id) {
-            old_handle.abort();
+        if let Some(old_sync) = worker.active_syncs.remove(&source_id) {
+            old_sync.handle.abort();
         }
 
         let new_handle = relm4::spawn(async {});
-        worker.active_syncs.insert(source_id.clone(), new_handle);
+        worker
+            .active_syncs
+            .insert(source_id.clone(), tracked(new_handle));
 
         // Should still have exactly one sync for this source
         assert_eq!(worker.active_syncs.len(), 1);
@@ -727,14 +877,44 @@ mod tests {
 
         // Add a sync
         let handle = relm4::spawn(async {});
-        worker.active_syncs.insert(source_id.clone(), handle);
+        worker
+            .active_syncs
+            .insert(source_id.clone(), tracked(handle));
 
         assert!(worker.active_syncs.contains_key(&source_id));
 
-        // Stop the sync
+        // Stopping cancels the sync, which stays tracked until it winds down
         worker.stop_sync(&source_id);
 
-        assert!(!worker.active_syncs.contains_key(&source_id));
+        let sync = &worker.active_syncs[&source_id];
+        assert!(sync.control.is_cancelled());
+    }
+
+    #[test]
+    fn test_playback_pauses_every_sync() {
+        let db = Arc::new(DatabaseConnection::default());
+        let mut worker = SyncWorker::new(db);
+
+        let source_id1 = SourceId::from("test-source-1");
+        let source_id2 = SourceId::from("test-source-2");
+        worker
+            .active_syncs
+            .insert(source_id1.clone(), tracked(relm4::spawn(async {})));
+        worker
+            .active_syncs
+            .insert(source_id2.clone(), tracked(relm4::spawn(async {})));
+        worker.paused_sources.insert(source_id1.clone());
+
+        worker.playback_active = true;
+        worker.apply_pause();
+        assert!(worker.active_syncs[&source_id1].control.is_paused());
+        assert!(worker.active_syncs[&source_id2].control.is_paused());
+
+        // Once playback stops, only the sync the user paused holds off
+        worker.playback_active = false;
+        worker.apply_pause();
+        assert!(worker.active_syncs[&source_id1].control.is_paused());
+        assert!(!worker.active_syncs[&source_id2].control.is_paused());
     }
 
     #[test]
@@ -749,8 +929,8 @@ mod tests {
         let handle1 = relm4::spawn(async {});
         let handle2 = relm4::spawn(async {});
 
-        worker.active_syncs.insert(source_id1, handle1);
-        worker.active_syncs.insert(source_id2, handle2);
+        worker.active_syncs.insert(source_id1, tracked(handle1));
+        worker.active_syncs.insert(source_id2, tracked(handle2));
 
         assert_eq!(worker.active_syncs.len(), 2);
         assert!(worker.auto_sync_enabled);
@@ -762,4 +942,27 @@ mod tests {
         assert!(!worker.auto_sync_enabled);
         assert!(worker.active_syncs.is_empty());
     }
+
+    #[test]
+    fn test_finished_sync_only_forgets_its_own_run() {
+        let db = Arc::new(DatabaseConnection::default());
+        let mut worker = SyncWorker::new(db);
+
+        let source_id = SourceId::from("test-source");
+        let handle = relm4::spawn(async {});
+        worker.active_syncs.insert(
+            source_id.clone(),
+            SourceSync {
+                run: 2,
+                ..tracked(handle)
+            },
+        );
+
+        // The end of an earlier, restarted sync leaves the new one tracked
+        worker.finish_sync(&source_id, 1);
+        assert!(worker.active_syncs.contains_key(&source_id));
+
+        worker.finish_sync(&source_id, 2);
+        assert!(worker.active_syncs.is_empty());
+    }
 }
@@ -0,0 +1,443 @@
+//! Poster frames and seek thumbnails extracted from local video files
+//!
+//! Local files have no server to render artwork, so frames are decoded with
+//! GStreamer and written to the image cache, where the image loader finds them
+//! like any downloaded image. A frame is addressed by the file URL with a media
+//! fragment naming its position, e.g. `file:///movies/film.mkv#t=120.000`.
+
+use relm4::{ComponentSender, Worker};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, error, trace};
+
+use super::image_loader::{ImageLoader, ImageSize};
+
+/// Size seek thumbnails are stored at
+pub const SEEK_THUMBNAIL_SIZE: ImageSize = ImageSize::Custom(320, 180);
+
+/// Minimum spacing between seek thumbnails
+const SEEK_THUMBNAIL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Upper bound on seek thumbnails per file, long files space them further apart
+const MAX_SEEK_THUMBNAILS: u32 = 200;
+
+/// Share of the runtime at which the poster frame is taken, past most intros
+const POSTER_FRAME_FRACTION: f64 = 0.1;
+
+/// Poster frame position when the runtime is unknown
+const DEFAULT_POSTER_POSITION: Duration = Duration::from_secs(60);
+
+/// URL of the frame at `position` in a local video file
+pub fn frame_url(path: &Path, position: Duration) -> Option<String> {
+    let mut url = url::Url::from_file_path(path).ok()?;
+    url.set_fragment(Some(&format!("t={:.3}", position.as_secs_f64())));
+    Some(url.into())
+}
+
+/// URL of the frame used as a local video's poster
+pub fn poster_frame_url(path: &Path, duration: Option<Duration>) -> Option<String> {
+    let position = duration.map_or(DEFAULT_POSTER_POSITION, |duration| {
+        duration.mul_f64(POSTER_FRAME_FRACTION)
+    });
+    frame_url(path, position)
+}
+
+/// File and position a frame URL points at, None for any other URL
+pub fn parse_frame_url(url: &str) -> Option<(PathBuf, Duration)> {
+    let url = url::Url::parse(url).ok()?;
+    if url.scheme() != "file" {
+        return None;
+    }
+    let seconds: f64 = url.fragment()?.strip_prefix("t=")?.parse().ok()?;
+    if !seconds.is_finite() || seconds < 0.0 {
+        return None;
+    }
+    Some((url.to_file_path().ok()?, Duration::from_secs_f64(seconds)))
+}
+
+/// Positions of the seek thumbnails for a file of the given runtime
+fn seek_positions(duration: Duration) -> Vec<Duration> {
+    let interval = SEEK_THUMBNAIL_INTERVAL.max(duration / MAX_SEEK_THUMBNAILS);
+    std::iter::successors(Some(Duration::ZERO), |position| Some(*position + interval))
+        .take_while(|position| *position < duration)
+        .collect()
+}
+
+/// Decode the frame at `position` as a JPEG no larger than `size`
+pub async fn extract_frame(
+    path: PathBuf,
+    position: Duration,
+    size: ImageSize,
+) -> Result<Vec<u8>, String> {
+    tokio::task::spawn_blocking(move || FrameDecoder::open(&path)?.frame(position, &size))
+        .await
+        .map_err(|e| format!("Frame extraction task failed: {}", e))?
+}
+
+/// Decode the frame at `position` with `decoder`, opening `path` if there is
+/// none yet. The decoder is handed back so the next frame reuses it.
+async fn next_frame(
+    decoder: Option<FrameDecoder>,
+    path: PathBuf,
+    position: Duration,
+    size: ImageSize,
+) -> (Option<FrameDecoder>, Result<Vec<u8>, String>) {
+    tokio::task::spawn_blocking(move || {
+        let decoder = match decoder {
+            Some(decoder) => decoder,
+            None => match FrameDecoder::open(&path) {
+                Ok(decoder) => decoder,
+                Err(e) => return (None, Err(e)),
+            },
+        };
+        let frame = decoder.frame(position, &size);
+        (Some(decoder), frame)
+    })
+    .await
+    .unwrap_or_else(|e| (None, Err(format!("Frame extraction task failed: {}", e))))
+}
+
+/// A video file opened for decoding, kept paused so any number of frames can
+/// be taken from one pipeline
+#[cfg(feature = "gstreamer")]
+struct FrameDecoder {
+    playbin: gstreamer::Element,
+}
+
+#[cfg(feature = "gstreamer")]
+impl FrameDecoder {
+    /// How long to wait for the file to open and for a seek to land
+    const DECODE_TIMEOUT: gstreamer::ClockTime = gstreamer::ClockTime::from_seconds(15);
+
+    fn open(path: &Path) -> Result<Self, String> {
+        use gstreamer as gst;
+        use gstreamer::prelude::*;
+
+        gst::init().map_err(|e| e.to_string())?;
+
+        let uri = url::Url::from_file_path(path)
+            .map_err(|_| format!("Not an absolute path: {:?}", path))?;
+        let fakesink = |name: &str| {
+            gst::ElementFactory::make("fakesink")
+                .name(name)
+                .build()
+                .map_err(|e| e.to_string())
+        };
+        let playbin = gst::ElementFactory::make("playbin")
+            .property("uri", uri.as_str())
+            .property("video-sink", fakesink("thumbnail-video-sink")?)
+            .property("audio-sink", fakesink("thumbnail-audio-sink")?)
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        // Dropping the decoder shuts the pipeline down, failed opens included
+        let decoder = Self { playbin };
+        decoder
+            .playbin
+            .set_state(gst::State::Paused)
+            .map_err(|e| e.to_string())?;
+        decoder.wait_for_preroll()?;
+        Ok(decoder)
+    }
+
+    fn wait_for_preroll(&self) -> Result<(), String> {
+        use gstreamer as gst;
+        use gstreamer::prelude::*;
+
+        let bus = self.playbin.bus().ok_or("Pipeline has no bus")?;
+        match bus.timed_pop_filtered(
+            Self::DECODE_TIMEOUT,
+            &[gst::MessageType::AsyncDone, gst::MessageType::Error],
+        ) {
+            Some(message) => match message.view() {
+                gst::MessageView::Error(err) => Err(err.error().to_string()),
+                _ => Ok(()),
+            },
+            None => Err("Timed out decoding the file".to_string()),
+        }
+    }
+
+    /// The frame at `position` as a JPEG no larger than `size`
+    fn frame(&self, position: Duration, size: &ImageSize) -> Result<Vec<u8>, String> {
+        use gstreamer as gst;
+        use gstreamer::prelude::*;
+
+        self.playbin
+            .seek_simple(
+                gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT,
+                gst::ClockTime::from_mseconds(position.as_millis() as u64),
+            )
+            .map_err(|e| e.to_string())?;
+        self.wait_for_preroll()?;
+
+        let caps = gst::Caps::builder("video/x-raw")
+            .field("format", "RGB")
+            .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
+            .build();
+        let sample = self
+            .playbin
+            .emit_by_name::<Option<gst::Sample>>("convert-sample", &[&caps])
+            .ok_or("The file has no video frame at this position")?;
+        let info = sample
+            .caps()
+            .and_then(|caps| gstreamer_video::VideoInfo::from_caps(caps).ok())
+            .ok_or("Decoded frame has no video format")?;
+        let buffer = sample.buffer().ok_or("Decoded frame is empty")?;
+        let map = buffer.map_readable().map_err(|e| e.to_string())?;
+
+        // Rows are padded to the stride, copy them into a tightly packed image
+        let (width, height) = (info.width(), info.height());
+        let stride = info.stride()[0] as usize;
+        let row_bytes = width as usize * 3;
+        let mut pixels = Vec::with_capacity(row_bytes * height as usize);
+        for row in map.chunks(stride).take(height as usize) {
+            pixels.extend_from_slice(&row[..row_bytes]);
+        }
+        let frame = image::RgbImage::from_raw(width, height, pixels)
+            .ok_or("Decoded frame has an unexpected size")?;
+
+        encode_jpeg(image::DynamicImage::ImageRgb8(frame), size)
+    }
+}
+
+#[cfg(feature = "gstreamer")]
+impl Drop for FrameDecoder {
+    fn drop(&mut self) {
+        use gstreamer::prelude::*;
+
+        let _ = self.playbin.set_state(gstreamer::State::Null);
+    }
+}
+
+#[cfg(not(feature = "gstreamer"))]
+struct FrameDecoder;
+
+#[cfg(not(feature = "gstreamer"))]
+impl FrameDecoder {
+    fn open(_path: &Path) -> Result<Self, String> {
+        Err("Extracting frames from local files requires GStreamer support".to_string())
+    }
+
+    fn frame(&self, _position: Duration, _size: &ImageSize) -> Result<Vec<u8>, String> {
+        unreachable!("FrameDecoder cannot be opened without GStreamer")
+    }
+}
+
+#[cfg(feature = "gstreamer")]
+fn encode_jpeg(frame: image::DynamicImage, size: &ImageSize) -> Result<Vec<u8>, String> {
+    let (width, height) = size.dimensions();
+    let frame = if width > 0 && height > 0 {
+        frame.thumbnail(width, height)
+    } else {
+        frame
+    };
+
+    let mut output = Vec::new();
+    frame
+        .write_to(
+            &mut std::io::Cursor::new(&mut output),
+            image::ImageFormat::Jpeg,
+        )
+        .map_err(|e| format!("Failed to encode frame: {}", e))?;
+    Ok(output)
+}
+
+#[derive(Debug, Clone)]
+pub enum ThumbnailWorkerInput {
+    /// Extract thumbnails along the whole runtime for seek previews
+    GenerateSeekThumbnails {
+        media_id: String,
+        path: PathBuf,
+        duration: Duration,
+    },
+    /// Drop queued and running work for a media item
+    Cancel {
+        media_id: String,
+    },
+    JobFinished, // Internal signal that the running job is done
+}
+
+/// Queued extraction work
+struct ThumbnailJob {
+    media_id: String,
+    path: PathBuf,
+    duration: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub enum ThumbnailWorkerOutput {
+    SeekThumbnailsReady {
+        media_id: String,
+        /// Position and image URL of each thumbnail, in order
+        thumbnails: Vec<(Duration, String)>,
+    },
+    Failed {
+        media_id: String,
+        error: String,
+    },
+}
+
+/// Extracts frames one file at a time, so decoding stays in the background.
+/// Poster frames need no worker, the image loader decodes them on demand.
+pub struct ThumbnailWorker {
+    queue: VecDeque<ThumbnailJob>,
+    /// Media item of the running job, and the job
+    running: Option<(String, relm4::JoinHandle<()>)>,
+}
+
+impl Worker for ThumbnailWorker {
+    type Init = ();
+    type Input = ThumbnailWorkerInput;
+    type Output = ThumbnailWorkerOutput;
+
+    fn init(_init: Self::Init, _sender: ComponentSender<Self>) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            running: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            ThumbnailWorkerInput::GenerateSeekThumbnails {
+                media_id,
+                path,
+                duration,
+            } => {
+                self.queue.push_back(ThumbnailJob {
+                    media_id,
+                    path,
+                    duration,
+                });
+                self.start_next(sender);
+            }
+            ThumbnailWorkerInput::Cancel { media_id } => {
+                self.queue.retain(|job| job.media_id != media_id);
+                if let Some((running_id, handle)) = &self.running
+                    && *running_id == media_id
+                {
+                    // Aborting drops the decoder once its current frame is done
+                    handle.abort();
+                    self.running = None;
+                    self.start_next(sender);
+                }
+            }
+            ThumbnailWorkerInput::JobFinished => {
+                self.running = None;
+                self.start_next(sender);
+            }
+        }
+    }
+}
+
+impl ThumbnailWorker {
+    fn start_next(&mut self, sender: ComponentSender<Self>) {
+        if self.running.is_some() {
+            return;
+        }
+        let Some(job) = self.queue.pop_front() else {
+            return;
+        };
+
+        let media_id = job.media_id.clone();
+        let handle = relm4::spawn(async move {
+            let output =
+                Self::generate_seek_thumbnails(job.media_id, &job.path, job.duration).await;
+
+            let _ = sender.output(output);
+            // Ignore if channel closed
+            let _ = sender
+                .input_sender()
+                .send(ThumbnailWorkerInput::JobFinished);
+        });
+        self.running = Some((media_id, handle));
+    }
+
+    async fn generate_seek_thumbnails(
+        media_id: String,
+        path: &Path,
+        duration: Duration,
+    ) -> ThumbnailWorkerOutput {
+        debug!("Generating seek thumbnails for {}", media_id);
+
+        // Opened on the first frame not already cached, then reused
+        let mut decoder = None;
+        let mut thumbnails = Vec::new();
+        for position in seek_positions(duration) {
+            let Some(url) = frame_url(path, position) else {
+                break;
+            };
+            let cache_path = ImageLoader::get_cache_path(&url, &SEEK_THUMBNAIL_SIZE);
+            if cache_path.exists() {
+                trace!("Seek thumbnail {} already cached", url);
+            } else {
+                let (opened, frame) =
+                    next_frame(decoder, path.to_path_buf(), position, SEEK_THUMBNAIL_SIZE).await;
+                decoder = opened;
+                let written = match frame {
+                    Ok(jpeg) => tokio::fs::write(&cache_path, jpeg)
+                        .await
+                        .map_err(|e| format!("Failed to cache frame: {}", e)),
+                    Err(e) => Err(e),
+                };
+                if let Err(error) = written {
+                    error!(
+                        "Failed to generate seek thumbnail for {} at {:?}: {}",
+                        media_id, position, error
+                    );
+                    return ThumbnailWorkerOutput::Failed { media_id, error };
+                }
+            }
+            thumbnails.push((position, url));
+        }
+
+        ThumbnailWorkerOutput::SeekThumbnailsReady {
+            media_id,
+            thumbnails,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_url_round_trip() {
+        let path = Path::new("/media/movies/Some Film (2020).mkv");
+        let url = frame_url(path, Duration::from_millis(90_500)).unwrap();
+        assert_eq!(
+            url,
+            "file:///media/movies/Some%20Film%20(2020).mkv#t=90.500"
+        );
+        assert_eq!(
+            parse_frame_url(&url),
+            Some((path.to_path_buf(), Duration::from_millis(90_500)))
+        );
+    }
+
+    #[test]
+    fn test_parse_frame_url_ignores_other_urls() {
+        assert_eq!(parse_frame_url("https://server/poster.jpg#t=10"), None);
+        assert_eq!(parse_frame_url("file:///media/movie.mkv"), None);
+        assert_eq!(parse_frame_url("file:///media/movie.mkv#t=-5"), None);
+    }
+
+    #[test]
+    fn test_seek_positions_spread_over_long_files() {
+        let short = seek_positions(Duration::from_secs(35));
+        assert_eq!(
+            short,
+            vec![
+                Duration::ZERO,
+                Duration::from_secs(10),
+                Duration::from_secs(20),
+                Duration::from_secs(30)
+            ]
+        );
+
+        let long = seek_positions(Duration::from_secs(4 * 3600));
+        assert_eq!(long.len(), MAX_SEEK_THUMBNAILS as usize);
+    }
+}
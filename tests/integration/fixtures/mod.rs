@@ -207,6 +207,7 @@ pub fn sample_stream_info() -> StreamInfo {
     StreamInfo {
         url: "http://localhost:32400/video/:/transcode/universal/start.mp4".to_string(),
         direct_play: true,
+        mode: StreamMode::DirectPlay,
         video_codec: "h264".to_string(),
         audio_codec: "aac".to_string(),
         container: "mp4".to_string(),